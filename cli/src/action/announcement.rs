@@ -0,0 +1,142 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling announcement subcommands.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{NewAnnouncement, SplinterRestClient, SplinterRestClientBuilder},
+    output::{OutputFormat, Table},
+    time::Time,
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// The action responsible for listing the announcements of a Splinter node.
+///
+/// The specific args for this action:
+///
+/// * include_expired: whether announcements that have expired are listed
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+pub struct ListAction;
+
+impl Action for ListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+        let include_expired = arg_matches
+            .map(|args| args.is_present("include_expired"))
+            .unwrap_or(false);
+
+        let announcements = new_client(arg_matches)?.list_announcements(include_expired)?;
+
+        let mut table = Table::new(&["ID", "KIND", "CREATED", "EXPIRES", "MESSAGE"]);
+        for announcement in announcements {
+            table.add_row(vec![
+                announcement.id,
+                announcement.kind,
+                announcement.created_at.to_string(),
+                announcement
+                    .expires_at
+                    .map(|expires_at| expires_at.to_string())
+                    .unwrap_or_default(),
+                announcement.message,
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// The action responsible for creating an announcement on a Splinter node.
+///
+/// The specific args for this action:
+///
+/// * message: the text of the announcement
+/// * kind: one of "info", "maintenance" or "deprecation"; defaults to "info"
+/// * expires_in: a duration, such as "2d", after which the announcement expires
+pub struct CreateAction;
+
+impl Action for CreateAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let message = args
+            .value_of("message")
+            .ok_or_else(|| CliError::ActionError("A message is required".into()))?
+            .to_string();
+        let kind = args.value_of("kind").unwrap_or("info").to_string();
+        let expires_at = args
+            .value_of("expires_in")
+            .map(|expires_in| -> Result<u64, CliError> {
+                let duration = Duration::from(Time::make_duration_type_time(expires_in)?);
+                let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|_| {
+                    CliError::ActionError("System time is before the Unix epoch".into())
+                })?;
+                Ok((now + duration).as_secs())
+            })
+            .transpose()?;
+
+        let announcement = new_client(arg_matches)?.create_announcement(&NewAnnouncement {
+            kind,
+            message,
+            expires_at,
+        })?;
+
+        println!("Created announcement {}", announcement.id);
+
+        Ok(())
+    }
+}
+
+/// The action responsible for deleting an announcement from a Splinter node.
+///
+/// The specific args for this action:
+///
+/// * id: the ID of the announcement to delete
+pub struct DeleteAction;
+
+impl Action for DeleteAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let id = arg_matches
+            .and_then(|args| args.value_of("id"))
+            .ok_or_else(|| CliError::ActionError("An announcement ID is required".into()))?;
+
+        new_client(arg_matches)?.delete_announcement(id)?;
+
+        println!("Deleted announcement {}", id);
+
+        Ok(())
+    }
+}
+
+/// Builds a client from the `url` and `private_key_file` arguments.
+fn new_client(arg_matches: Option<&ArgMatches>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
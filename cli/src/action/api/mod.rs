@@ -17,8 +17,20 @@
 #[cfg(feature = "authorization-handler-rbac")]
 mod rbac;
 
+#[cfg(feature = "transfer")]
+use std::fs::File;
+#[cfg(feature = "transfer")]
+use std::io::Write;
+
 use reqwest::blocking::Client;
 use serde::Deserialize;
+#[cfg(any(
+    feature = "announcements",
+    feature = "audit",
+    feature = "peer-blocklist",
+    feature = "transfer"
+))]
+use serde::Serialize;
 
 use super::CliError;
 
@@ -27,6 +39,7 @@ pub use rbac::{
     assignments::{
         Assignment, AssignmentBuilder, AssignmentUpdate, AssignmentUpdateBuilder, Identity,
     },
+    import_export::{ImportResult, RbacExport},
     roles::{Role, RoleBuilder, RoleUpdate, RoleUpdateBuilder},
 };
 
@@ -188,6 +201,404 @@ impl SplinterRestClient {
             })
     }
 
+    /// Lists the announcements set by the operator of the Splinter node.
+    #[cfg(feature = "announcements")]
+    pub fn list_announcements(&self, include_expired: bool) -> Result<Vec<Announcement>, CliError> {
+        Client::new()
+            .get(&format!("{}/announcements", self.url))
+            .query(&[("include_expired", include_expired)])
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get announcements: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<AnnouncementsResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Announcements list request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get announcements list: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Creates an announcement on the Splinter node.
+    #[cfg(feature = "announcements")]
+    pub fn create_announcement(
+        &self,
+        announcement: &NewAnnouncement,
+    ) -> Result<Announcement, CliError> {
+        Client::new()
+            .post(&format!("{}/announcements", self.url))
+            .json(announcement)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to create announcement: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<Announcement>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Announcement create request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to create announcement: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Deletes an announcement from the Splinter node.
+    #[cfg(feature = "announcements")]
+    pub fn delete_announcement(&self, id: &str) -> Result<(), CliError> {
+        Client::new()
+            .delete(&format!("{}/announcements/{}", self.url, id))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to delete announcement: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Announcement delete request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to delete announcement: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Gets the configuration the Splinter node is running with, and the source of each value.
+    #[cfg(feature = "node-config")]
+    pub fn get_config(&self) -> Result<Vec<ConfigValue>, CliError> {
+        Client::new()
+            .get(&format!("{}/config", self.url))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get config: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<ConfigResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Config request failed with status code '{}', but error response \
+                                 was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get config: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the public keys and IP addresses blocked by the Splinter node.
+    #[cfg(feature = "peer-blocklist")]
+    pub fn list_peer_blocks(&self, include_expired: bool) -> Result<Vec<BlockedPeer>, CliError> {
+        Client::new()
+            .get(&format!("{}/peers/blocklist", self.url))
+            .query(&[("include_expired", include_expired)])
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get peer blocklist: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<BlockedPeersResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer blocklist request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get peer blocklist: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Blocks a public key or IP address on the Splinter node.
+    #[cfg(feature = "peer-blocklist")]
+    pub fn add_peer_block(&self, block: &NewBlockedPeer) -> Result<BlockedPeer, CliError> {
+        Client::new()
+            .post(&format!("{}/peers/blocklist", self.url))
+            .json(block)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to add peer block: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<BlockedPeer>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer block request failed with status code '{}', but error \
+                                 response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to add peer block: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Removes the block of a public key or IP address from the Splinter node.
+    #[cfg(feature = "peer-blocklist")]
+    pub fn remove_peer_block(&self, kind: &str, value: &str) -> Result<(), CliError> {
+        Client::new()
+            .delete(&format!("{}/peers/blocklist/{}/{}", self.url, kind, value))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to remove peer block: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    Ok(())
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Peer block removal request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to remove peer block: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the events in the Splinter node's audit log, most recent first.
+    #[cfg(feature = "audit")]
+    pub fn list_audit_events(&self, query: &AuditEventsQuery) -> Result<Vec<AuditEvent>, CliError> {
+        Client::new()
+            .get(&format!("{}/audit/events", self.url))
+            .query(query)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get audit events: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<AuditEventsResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Audit event list request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get audit events: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the membership and service changes of a circuit, oldest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_id` - The ID of the circuit
+    /// * `since` - Only list changes at or after this time, in seconds since the Unix epoch
+    #[cfg(feature = "audit")]
+    pub fn get_circuit_history(
+        &self,
+        circuit_id: &str,
+        since: Option<u64>,
+    ) -> Result<Vec<AuditEvent>, CliError> {
+        let mut request = Client::new()
+            .get(&format!(
+                "{}/audit/circuits/{}/history",
+                self.url, circuit_id
+            ))
+            .header("Authorization", &self.auth);
+        if let Some(since) = since {
+            request = request.query(&[("since", since)]);
+        }
+
+        request
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get circuit history: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<AuditEventsResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Circuit history request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get circuit history: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
+    /// Lists the consensus events of a scabbard service, in position order.
+    #[cfg(feature = "scabbard-consensus-log")]
+    pub fn list_scabbard_consensus_events(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Vec<ScabbardConsensusEvent>, CliError> {
+        Client::new()
+            .get(&format!(
+                "{}/scabbard/consensus/{}/{}/events",
+                self.url, circuit_id, service_id
+            ))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to get consensus events: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<ScabbardConsensusEventsResponse>()
+                        .map(|response| response.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Consensus event list request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to get consensus events: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
     /// Lists all REST API permissions for a Splinter node.
     pub fn list_permissions(&self) -> Result<Vec<Permission>, CliError> {
         Client::new()
@@ -288,6 +699,206 @@ impl SplinterRestClient {
     pub fn delete_assignment(&self, identity: &Identity) -> Result<(), CliError> {
         rbac::assignments::delete_assignment(&self.url, &self.auth, identity)
     }
+
+    #[cfg(feature = "authorization-handler-rbac")]
+    pub fn export_rbac(&self) -> Result<RbacExport, CliError> {
+        rbac::import_export::export_rbac(&self.url, &self.auth)
+    }
+
+    #[cfg(feature = "authorization-handler-rbac")]
+    pub fn import_rbac(&self, rbac: &RbacExport, dry_run: bool) -> Result<ImportResult, CliError> {
+        rbac::import_export::import_rbac(&self.url, &self.auth, rbac, dry_run)
+    }
+
+    /// Sends a file through a transfer service, which offers it to the service's peers.
+    #[cfg(feature = "transfer")]
+    pub fn send_file(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        name: &str,
+        file: File,
+    ) -> Result<TransferFile, CliError> {
+        Client::builder()
+            // Large files may take longer than the default timeout to upload
+            .timeout(None)
+            .build()
+            .map_err(|err| CliError::ActionError(format!("Failed to build client: {}", err)))?
+            .post(&format!(
+                "{}/transfer/{}/{}/send",
+                self.url, circuit_id, service_id
+            ))
+            .query(&[("name", name)])
+            .header("Authorization", &self.auth)
+            .body(file)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to send file: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<TransferFile>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    Err(CliError::ActionError(format!(
+                        "Failed to send file: {}",
+                        error_message(res, "File send")
+                    )))
+                }
+            })
+    }
+
+    /// Lists the files sent and received by a transfer service.
+    #[cfg(feature = "transfer")]
+    pub fn list_files(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Result<Vec<TransferFile>, CliError> {
+        Client::new()
+            .get(&format!(
+                "{}/transfer/{}/{}/files",
+                self.url, circuit_id, service_id
+            ))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to list files: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<Vec<TransferFile>>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    Err(CliError::ActionError(format!(
+                        "Failed to list files: {}",
+                        error_message(res, "File list")
+                    )))
+                }
+            })
+    }
+
+    /// Gets a file of a transfer service, including the progress of an incoming transfer.
+    #[cfg(feature = "transfer")]
+    pub fn get_file(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        file_id: &str,
+    ) -> Result<TransferFile, CliError> {
+        Client::new()
+            .get(&format!(
+                "{}/transfer/{}/{}/files/{}",
+                self.url, circuit_id, service_id, file_id
+            ))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get file: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<TransferFile>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    Err(CliError::ActionError(format!(
+                        "Failed to get file: {}",
+                        error_message(res, "File fetch")
+                    )))
+                }
+            })
+    }
+
+    /// Downloads the content of a file that a transfer service has completely sent or received.
+    #[cfg(feature = "transfer")]
+    pub fn download_file(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        file_id: &str,
+        writer: &mut dyn Write,
+    ) -> Result<u64, CliError> {
+        Client::builder()
+            .timeout(None)
+            .build()
+            .map_err(|err| CliError::ActionError(format!("Failed to build client: {}", err)))?
+            .get(&format!(
+                "{}/transfer/{}/{}/files/{}/content",
+                self.url, circuit_id, service_id, file_id
+            ))
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to download file: {}", err)))
+            .and_then(|mut res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.copy_to(writer).map_err(|err| {
+                        CliError::ActionError(format!("Failed to download file: {}", err))
+                    })
+                } else {
+                    Err(CliError::ActionError(format!(
+                        "Failed to download file: {}",
+                        error_message(res, "File download")
+                    )))
+                }
+            })
+    }
+
+    /// Gets the JSON response of an arbitrary endpoint of the Splinter node, requesting the given
+    /// protocol version if the endpoint requires one.
+    #[cfg(feature = "support-bundle")]
+    pub fn get_json(
+        &self,
+        path: &str,
+        protocol_version: Option<&str>,
+    ) -> Result<serde_json::Value, CliError> {
+        let mut request = Client::new()
+            .get(&format!("{}{}", self.url, path))
+            .header("Authorization", &self.auth);
+        if let Some(protocol_version) = protocol_version {
+            request = request.header("SplinterProtocolVersion", protocol_version);
+        }
+
+        request
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to get {}: {}", path, err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<serde_json::Value>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    Err(CliError::ActionError(format!(
+                        "Failed to get {}: {}",
+                        path,
+                        error_message(res, "Fetch")
+                    )))
+                }
+            })
+    }
+}
+
+/// Gets the message of an error response.
+#[cfg(any(feature = "support-bundle", feature = "transfer"))]
+fn error_message(res: reqwest::blocking::Response, request: &str) -> String {
+    let status = res.status();
+    res.json::<ServerError>()
+        .map(|err| err.message)
+        .unwrap_or_else(|_| {
+            format!(
+                "{} request failed with status code '{}', but error response was not valid",
+                request, status
+            )
+        })
 }
 
 #[derive(Deserialize)]
@@ -315,3 +926,142 @@ pub struct Permission {
     pub permission_display_name: String,
     pub permission_description: String,
 }
+
+#[cfg(feature = "announcements")]
+#[derive(Deserialize)]
+struct AnnouncementsResponse {
+    pub data: Vec<Announcement>,
+}
+
+#[cfg(feature = "announcements")]
+#[derive(Deserialize)]
+pub struct Announcement {
+    pub id: String,
+    pub kind: String,
+    pub message: String,
+    pub created_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+#[cfg(feature = "announcements")]
+#[derive(Serialize)]
+pub struct NewAnnouncement {
+    pub kind: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+#[cfg(feature = "node-config")]
+#[derive(Deserialize)]
+struct ConfigResponse {
+    pub data: Vec<ConfigValue>,
+}
+
+#[cfg(feature = "node-config")]
+#[derive(Deserialize)]
+pub struct ConfigValue {
+    pub name: String,
+    pub value: String,
+    pub source: String,
+    pub file: Option<String>,
+}
+
+#[cfg(feature = "peer-blocklist")]
+#[derive(Deserialize)]
+struct BlockedPeersResponse {
+    pub data: Vec<BlockedPeer>,
+}
+
+#[cfg(feature = "peer-blocklist")]
+#[derive(Deserialize)]
+pub struct BlockedPeer {
+    pub kind: String,
+    pub value: String,
+    pub reason: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[cfg(feature = "peer-blocklist")]
+#[derive(Serialize)]
+pub struct NewBlockedPeer {
+    pub kind: String,
+    pub value: String,
+    pub reason: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+}
+
+#[cfg(feature = "audit")]
+#[derive(Deserialize)]
+struct AuditEventsResponse {
+    pub data: Vec<AuditEvent>,
+}
+
+#[cfg(feature = "audit")]
+#[derive(Deserialize)]
+pub struct AuditEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub actor: String,
+    pub target: String,
+    pub detail: String,
+    pub timestamp: u64,
+}
+
+/// Filters for listing audit events; times are in seconds since the Unix epoch.
+#[cfg(feature = "audit")]
+#[derive(Default, Serialize)]
+pub struct AuditEventsQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub since: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+#[cfg(feature = "scabbard-consensus-log")]
+#[derive(Deserialize)]
+struct ScabbardConsensusEventsResponse {
+    pub data: Vec<ScabbardConsensusEvent>,
+}
+
+#[cfg(feature = "scabbard-consensus-log")]
+#[derive(Deserialize)]
+pub struct ScabbardConsensusEvent {
+    pub position: i32,
+    pub event_type: String,
+    pub deliver: Option<ScabbardDeliveredMessage>,
+    pub value: Option<String>,
+    pub vote: Option<bool>,
+    pub created_at: u64,
+    pub executed_at: Option<u64>,
+    pub executed_epoch: Option<u64>,
+}
+
+#[cfg(feature = "scabbard-consensus-log")]
+#[derive(Deserialize)]
+pub struct ScabbardDeliveredMessage {
+    pub from: String,
+    pub message_type: String,
+    pub epoch: u64,
+    pub vote: Option<bool>,
+    pub value: Option<String>,
+}
+
+#[cfg(feature = "transfer")]
+#[derive(Deserialize, Serialize)]
+pub struct TransferFile {
+    pub file_id: String,
+    pub name: String,
+    pub size: u64,
+    pub sha256: String,
+    pub direction: String,
+    pub peer: Option<String>,
+    pub received: u64,
+    pub status: String,
+}
@@ -44,6 +44,9 @@ pub struct Assignment {
     #[serde(flatten)]
     pub identity: Identity,
     pub roles: Vec<String>,
+    /// The time the assignment expires, in seconds since the Unix epoch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
 }
 
 impl Pageable for Assignment {
@@ -62,6 +65,7 @@ struct AssignmentGet {
 pub struct AssignmentBuilder {
     identity: Option<Identity>,
     roles: Vec<String>,
+    expires_at: Option<u64>,
 }
 
 impl AssignmentBuilder {
@@ -75,8 +79,17 @@ impl AssignmentBuilder {
         self
     }
 
+    pub fn with_expires_at(mut self, expires_at: Option<u64>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
     pub fn build(self) -> Result<Assignment, CliError> {
-        let AssignmentBuilder { identity, roles } = self;
+        let AssignmentBuilder {
+            identity,
+            roles,
+            expires_at,
+        } = self;
 
         if roles.is_empty() {
             return Err(CliError::ActionError(
@@ -100,7 +113,11 @@ impl AssignmentBuilder {
             }
         }
 
-        Ok(Assignment { identity, roles })
+        Ok(Assignment {
+            identity,
+            roles,
+            expires_at,
+        })
     }
 }
 
@@ -110,12 +127,16 @@ pub struct AssignmentUpdate {
     identity: Identity,
     #[serde(skip_serializing_if = "Option::is_none")]
     roles: Option<Vec<String>>,
+    /// `Some(None)` removes the expiration time, which is serialized as `null`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<Option<u64>>,
 }
 
 #[derive(Default)]
 pub struct AssignmentUpdateBuilder {
     identity: Option<Identity>,
     roles: Option<Vec<String>>,
+    expires_at: Option<Option<u64>>,
 }
 
 impl AssignmentUpdateBuilder {
@@ -129,8 +150,18 @@ impl AssignmentUpdateBuilder {
         self
     }
 
+    /// Sets the new expiration time; `Some(None)` removes it and `None` leaves it unchanged.
+    pub fn with_expires_at(mut self, expires_at: Option<Option<u64>>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
     pub fn build(self) -> Result<AssignmentUpdate, CliError> {
-        let AssignmentUpdateBuilder { identity, roles } = self;
+        let AssignmentUpdateBuilder {
+            identity,
+            roles,
+            expires_at,
+        } = self;
 
         let identity = identity.ok_or_else(|| {
             CliError::ActionError("An assignment must have an associated identity".into())
@@ -144,7 +175,11 @@ impl AssignmentUpdateBuilder {
             }
         }
 
-        Ok(AssignmentUpdate { identity, roles })
+        Ok(AssignmentUpdate {
+            identity,
+            roles,
+            expires_at,
+        })
     }
 }
 
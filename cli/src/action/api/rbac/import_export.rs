@@ -0,0 +1,137 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use reqwest::blocking::Client;
+use serde::{Deserialize, Serialize};
+
+use crate::action::api::ServerError;
+use crate::error::CliError;
+
+use super::assignments::{Assignment, Identity};
+use super::roles::Role;
+
+/// The import and export endpoints were added in version 2 of the protocol.
+const RBAC_IMPORT_EXPORT_PROTOCOL_VERSION: u32 = 2;
+
+/// All of the roles and assignments of a Splinter node.
+#[derive(Deserialize, Serialize)]
+pub struct RbacExport {
+    #[serde(default)]
+    pub roles: Vec<Role>,
+    #[serde(default)]
+    pub assignments: Vec<Assignment>,
+}
+
+/// The changes made by an import, or that would be made in the case of a dry run.
+#[derive(Deserialize)]
+pub struct ImportResult {
+    pub dry_run: bool,
+    pub roles_added: Vec<String>,
+    pub roles_updated: Vec<String>,
+    pub roles_unchanged: Vec<String>,
+    pub assignments_added: Vec<Identity>,
+    pub assignments_updated: Vec<Identity>,
+    pub assignments_unchanged: Vec<Identity>,
+}
+
+pub fn export_rbac(base_url: &str, auth: &str) -> Result<RbacExport, CliError> {
+    Client::new()
+        .get(&format!("{}/authorization/rbac/export", base_url))
+        .header(
+            "SplinterProtocolVersion",
+            RBAC_IMPORT_EXPORT_PROTOCOL_VERSION,
+        )
+        .header("Authorization", auth)
+        .send()
+        .map_err(|err| {
+            CliError::ActionError(format!("Failed to export roles and assignments: {}", err))
+        })
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<RbacExport>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else if status.as_u16() == 401 {
+                Err(CliError::ActionError("Not Authorized".into()))
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Export request failed with status code '{}', but error response was \
+                            not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to export roles and assignments: {}",
+                    message
+                )))
+            }
+        })
+}
+
+pub fn import_rbac(
+    base_url: &str,
+    auth: &str,
+    rbac: &RbacExport,
+    dry_run: bool,
+) -> Result<ImportResult, CliError> {
+    Client::new()
+        .post(&format!("{}/authorization/rbac/import", base_url))
+        .query(&[("dry_run", dry_run)])
+        .header(
+            "SplinterProtocolVersion",
+            RBAC_IMPORT_EXPORT_PROTOCOL_VERSION,
+        )
+        .header("Authorization", auth)
+        .json(rbac)
+        .send()
+        .map_err(|err| {
+            CliError::ActionError(format!("Failed to import roles and assignments: {}", err))
+        })
+        .and_then(|res| {
+            let status = res.status();
+            if status.is_success() {
+                res.json::<ImportResult>().map_err(|_| {
+                    CliError::ActionError(
+                        "Request was successful, but received an invalid response".into(),
+                    )
+                })
+            } else if status.as_u16() == 401 {
+                Err(CliError::ActionError("Not Authorized".into()))
+            } else {
+                let message = res
+                    .json::<ServerError>()
+                    .map_err(|_| {
+                        CliError::ActionError(format!(
+                            "Import request failed with status code '{}', but error response was \
+                            not valid",
+                            status
+                        ))
+                    })?
+                    .message;
+
+                Err(CliError::ActionError(format!(
+                    "Failed to import roles and assignments: {}",
+                    message
+                )))
+            }
+        })
+}
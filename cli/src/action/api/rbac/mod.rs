@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod assignments;
+pub mod import_export;
 pub mod roles;
 
 use std::collections::VecDeque;
@@ -27,6 +27,8 @@ pub struct Role {
     pub role_id: String,
     pub display_name: String,
     pub permissions: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parents: Vec<String>,
 }
 
 impl fmt::Display for Role {
@@ -39,6 +41,14 @@ impl fmt::Display for Role {
             write!(f, "\n        {}", perm)?;
         }
 
+        if !self.parents.is_empty() {
+            f.write_str("\n    Parents:")?;
+
+            for parent in self.parents.iter() {
+                write!(f, "\n        {}", parent)?;
+            }
+        }
+
         Ok(())
     }
 }
@@ -55,6 +65,7 @@ pub struct RoleBuilder {
     role_id: Option<String>,
     display_name: Option<String>,
     permissions: Vec<String>,
+    parents: Vec<String>,
 }
 
 impl RoleBuilder {
@@ -74,23 +85,30 @@ impl RoleBuilder {
 
     /// Sets the permissions included in the resulting Role.
     ///
-    /// Must not be empty.
+    /// Must not be empty, unless the Role has parents.
     pub fn with_permissions(mut self, permissions: Vec<String>) -> Self {
         self.permissions = permissions;
         self
     }
 
+    /// Sets the IDs of the roles the resulting Role inherits permissions from.
+    pub fn with_parents(mut self, parents: Vec<String>) -> Self {
+        self.parents = parents;
+        self
+    }
+
     /// Constructs the Role.
     pub fn build(self) -> Result<Role, CliError> {
         let RoleBuilder {
             role_id,
             display_name,
             permissions,
+            parents,
         } = self;
 
-        if permissions.is_empty() {
+        if permissions.is_empty() && parents.is_empty() {
             return Err(CliError::ActionError(
-                "A role must have at least one permission".into(),
+                "A role must have at least one permission or parent role".into(),
             ));
         }
 
@@ -107,6 +125,7 @@ impl RoleBuilder {
             role_id,
             display_name,
             permissions,
+            parents,
         })
     }
 }
@@ -120,6 +139,8 @@ pub struct RoleUpdate {
     display_name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     permissions: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    parents: Option<Vec<String>>,
 }
 
 #[derive(Default)]
@@ -127,6 +148,7 @@ pub struct RoleUpdateBuilder {
     role_id: Option<String>,
     display_name: Option<String>,
     permissions: Option<Vec<String>>,
+    parents: Option<Vec<String>>,
 }
 
 impl RoleUpdateBuilder {
@@ -146,18 +168,25 @@ impl RoleUpdateBuilder {
 
     /// Sets the permissions included in the resulting Role.
     ///
-    /// Must not be empty.
+    /// Must not be empty, unless the Role's parents are also set and are not empty.
     pub fn with_permissions(mut self, permissions: Option<Vec<String>>) -> Self {
         self.permissions = permissions;
         self
     }
 
+    /// Sets the IDs of the roles the resulting Role inherits permissions from.
+    pub fn with_parents(mut self, parents: Option<Vec<String>>) -> Self {
+        self.parents = parents;
+        self
+    }
+
     /// Constructs the Role.
     pub fn build(self) -> Result<RoleUpdate, CliError> {
         let RoleUpdateBuilder {
             role_id,
             display_name,
             permissions,
+            parents,
         } = self;
 
         let role_id =
@@ -167,9 +196,13 @@ impl RoleUpdateBuilder {
         }
 
         if let Some(permissions) = permissions.as_ref() {
-            if permissions.is_empty() {
+            let has_parents = parents
+                .as_ref()
+                .map(|parents| !parents.is_empty())
+                .unwrap_or(false);
+            if permissions.is_empty() && !has_parents {
                 return Err(CliError::ActionError(
-                    "A role must have at least one permission".into(),
+                    "A role must have at least one permission or parent role".into(),
                 ));
             }
         }
@@ -178,6 +211,7 @@ impl RoleUpdateBuilder {
             role_id,
             display_name,
             permissions,
+            parents,
         })
     }
 }
@@ -466,5 +500,17 @@ mod tests {
             .with_permissions(Some(vec![]))
             .build();
         assert!(res.is_err());
+
+        // Empty permissions, with parents
+        let role_update = RoleUpdateBuilder::default()
+            .with_role_id("parents_update".into())
+            .with_permissions(Some(vec![]))
+            .with_parents(Some(vec!["circuit_reader".to_string()]))
+            .build()
+            .expect("could not build a valid role");
+        assert_eq!(
+            Some(vec!["circuit_reader".to_string()]),
+            role_update.parents
+        );
     }
 }
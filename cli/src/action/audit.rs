@@ -0,0 +1,151 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling audit subcommands.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{AuditEventsQuery, SplinterRestClient, SplinterRestClientBuilder},
+    output::{OutputFormat, Table},
+    time::Time,
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// The action responsible for listing the events in a Splinter node's audit log.
+///
+/// The specific args for this action:
+///
+/// * event_type: only list events of this type, such as "proposal_vote"
+/// * actor: only list events caused by this public key, identity or client address
+/// * since: a duration, such as "2d", limiting the events to those that occurred within it
+/// * limit: the maximum number of events to list
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+pub struct ListAction;
+
+impl Action for ListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+
+        let since = parse_since(arg_matches)?;
+        let limit = arg_matches
+            .and_then(|args| args.value_of("limit"))
+            .map(|limit| {
+                limit
+                    .parse::<usize>()
+                    .map_err(|_| CliError::ActionError(format!("Invalid limit \"{}\"", limit)))
+            })
+            .transpose()?;
+
+        let query = AuditEventsQuery {
+            event_type: arg_matches
+                .and_then(|args| args.value_of("event_type"))
+                .map(ToOwned::to_owned),
+            actor: arg_matches
+                .and_then(|args| args.value_of("actor"))
+                .map(ToOwned::to_owned),
+            since,
+            limit,
+        };
+
+        let events = new_client(arg_matches)?.list_audit_events(&query)?;
+
+        let mut table = Table::new(&["ID", "TIME", "TYPE", "ACTOR", "TARGET", "DETAIL"]);
+        for event in events {
+            table.add_row(vec![
+                event.id.to_string(),
+                event.timestamp.to_string(),
+                event.event_type,
+                event.actor,
+                event.target,
+                event.detail,
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// The action responsible for listing the membership and service changes of a circuit, as
+/// recorded in a Splinter node's audit log. Exporting the history with `--format csv` gives a
+/// record of who proposed, voted on and committed each change to the circuit.
+///
+/// The specific args for this action:
+///
+/// * circuit_id: the circuit whose history is listed
+/// * since: a duration, such as "365d", limiting the changes to those that occurred within it
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+pub struct HistoryAction;
+
+impl Action for HistoryAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+
+        let circuit_id = arg_matches
+            .and_then(|args| args.value_of("circuit_id"))
+            .ok_or_else(|| CliError::ActionError("A circuit ID is required".into()))?;
+        let since = parse_since(arg_matches)?;
+
+        let events = new_client(arg_matches)?.get_circuit_history(circuit_id, since)?;
+
+        let mut table = Table::new(&["ID", "TIME", "TYPE", "ACTOR", "DETAIL"]);
+        for event in events {
+            table.add_row(vec![
+                event.id.to_string(),
+                event.timestamp.to_string(),
+                event.event_type,
+                event.actor,
+                event.detail,
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// Converts the `since` argument, a duration, into the time that long ago in seconds since the
+/// Unix epoch.
+fn parse_since(arg_matches: Option<&ArgMatches>) -> Result<Option<u64>, CliError> {
+    arg_matches
+        .and_then(|args| args.value_of("since"))
+        .map(|since| -> Result<u64, CliError> {
+            let duration = Duration::from(Time::make_duration_type_time(since)?);
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).map_err(|_| {
+                CliError::ActionError("System time is before the Unix epoch".into())
+            })?;
+            Ok(now.saturating_sub(duration).as_secs())
+        })
+        .transpose()
+}
+
+/// Builds a client from the `url` and `private_key_file` arguments.
+fn new_client(arg_matches: Option<&ArgMatches>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
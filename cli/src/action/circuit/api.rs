@@ -65,6 +65,48 @@ impl SplinterRestClient {
             })
     }
 
+    /// Validates a circuit create payload on this client's Splinter node, without proposing the
+    /// circuit.
+    #[cfg(feature = "circuit-propose-validate")]
+    pub fn validate_admin_payload(&self, payload: Vec<u8>) -> Result<ProposalValidation, CliError> {
+        Client::new()
+            .post(&format!("{}/admin/circuits/validate", self.url))
+            .header(header::CONTENT_TYPE, "octet-stream")
+            .header("SplinterProtocolVersion", CLI_ADMIN_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .body(payload)
+            .send()
+            .map_err(|err| {
+                CliError::ActionError(format!("Failed to validate admin payload: {}", err))
+            })
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<ProposalValidation>().map_err(|_| {
+                        CliError::ActionError(
+                            "Request was successful, but received an invalid response".into(),
+                        )
+                    })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Admin payload validate request failed with status code '{}', \
+                                 but error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to validate admin payload: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
     pub fn list_circuits(
         &self,
         member_filter: Option<&str>,
@@ -473,6 +515,22 @@ pub struct VoteRecord {
     pub voter_node_id: String,
 }
 
+/// The reasons a node would reject a circuit proposal.
+#[cfg(feature = "circuit-propose-validate")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ProposalValidation {
+    pub valid: bool,
+    pub errors: Vec<ProposalValidationError>,
+}
+
+#[cfg(feature = "circuit-propose-validate")]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ProposalValidationError {
+    /// The group of checks that found the problem, such as "requester" or "circuit"
+    pub check: String,
+    pub message: String,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct Paging {
     pub current: String,
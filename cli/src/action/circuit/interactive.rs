@@ -0,0 +1,465 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An interactive wizard for `splinter circuit propose --interactive`.
+//!
+//! The wizard walks the user through selecting the members of the circuit from the registry, the
+//! service type and arguments, and the management type, re-asking each question until the answer
+//! is valid.
+
+use std::collections::HashSet;
+use std::io::{self, BufRead, Write};
+
+use clap::ArgMatches;
+use splinter::admin::messages::is_valid_service_id;
+
+use crate::action::api::SplinterRestClientBuilder;
+use crate::action::registry::RegistryNode;
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::api::CircuitSlice;
+use super::builder::{parse_hex, CreateCircuitMessageBuilder};
+use super::{DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+
+const DEFAULT_SERVICE_TYPE: &str = "scabbard";
+
+/// Fills in the given builder from the user's answers to the wizard's questions.
+///
+/// The members of the circuit are selected from the registry of the node at the `url` argument.
+pub(super) fn run_wizard(
+    args: &ArgMatches,
+    builder: &mut CreateCircuitMessageBuilder,
+) -> Result<(), CliError> {
+    let url = args
+        .value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(args.value_of("key"))?;
+    let public_key = signer
+        .public_key()
+        .map_err(|err| CliError::ActionError(format!("Failed to get public key: {}", err)))?
+        .as_hex();
+
+    let registry_nodes = SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()?
+        .list_nodes()?;
+
+    let stdin = io::stdin();
+    let mut prompter = Prompter::new(stdin.lock(), io::stdout());
+    build_proposal(&mut prompter, &registry_nodes, &public_key, builder)
+}
+
+/// Shows the proposal that was built and asks whether it should be submitted.
+pub(super) fn confirm_submit(circuit: &CircuitSlice) -> Result<bool, CliError> {
+    let stdin = io::stdin();
+    let mut prompter = Prompter::new(stdin.lock(), io::stdout());
+    prompter.say(&format!("\n{}\n", circuit))?;
+    prompter.confirm("Submit this circuit proposal?", true)
+}
+
+fn build_proposal<R: BufRead, W: Write>(
+    prompter: &mut Prompter<R, W>,
+    registry_nodes: &[RegistryNode],
+    public_key: &str,
+    builder: &mut CreateCircuitMessageBuilder,
+) -> Result<(), CliError> {
+    if registry_nodes.is_empty() {
+        return Err(CliError::ActionError(
+            "The registry has no nodes to select as circuit members; add nodes with \
+             'splinter registry add' or propose the circuit with '--node'"
+                .into(),
+        ));
+    }
+
+    prompter.say("Nodes in the registry:")?;
+    for (i, node) in registry_nodes.iter().enumerate() {
+        prompter.say(&format!(
+            "  {}) {} ({}) {}",
+            i + 1,
+            node.identity,
+            node.display_name,
+            node.endpoints.join(",")
+        ))?;
+    }
+
+    let members = prompter.ask_valid(
+        "Members of the circuit, as numbers or node IDs separated by commas",
+        None,
+        |answer| parse_members(answer, registry_nodes),
+    )?;
+
+    let authorization_type = prompter.ask_valid(
+        "Authorization type (trust or challenge)",
+        Some("trust"),
+        |answer| match answer {
+            "trust" => Ok(answer.to_string()),
+            "challenge" => match members.iter().find(|node| node.keys.is_empty()) {
+                Some(node) => Err(format!(
+                    "node {} has no keys in the registry, which challenge authorization requires",
+                    node.identity
+                )),
+                None => Ok(answer.to_string()),
+            },
+            _ => Err("expected trust or challenge".into()),
+        },
+    )?;
+    builder.set_authorization_type(&authorization_type)?;
+
+    for node in &members {
+        let public_key = if authorization_type == "challenge" {
+            node.keys.first()
+        } else {
+            None
+        };
+        builder.add_node(&node.identity, &node.endpoints, public_key)?;
+    }
+
+    let management_type = prompter.ask_valid("Circuit management type", None, |answer| {
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            Err("the management type must be a non-empty string without spaces".into())
+        } else {
+            Ok(answer.to_string())
+        }
+    })?;
+    builder.set_management_type(&management_type);
+
+    let service_type =
+        prompter.ask_valid("Service type", Some(DEFAULT_SERVICE_TYPE), |answer| {
+            if answer.is_empty() || answer.contains(char::is_whitespace) {
+                Err("the service type must be a non-empty string without spaces".into())
+            } else {
+                Ok(answer.to_string())
+            }
+        })?;
+
+    let mut service_ids = Vec::with_capacity(members.len());
+    for (i, node) in members.iter().enumerate() {
+        let default_id = format!("a{:03}", i);
+        let service_id = prompter.ask_valid(
+            &format!("Service ID for node {}", node.identity),
+            Some(&default_id),
+            |answer| {
+                if !is_valid_service_id(answer) {
+                    Err("a service ID must be 4 alphanumeric characters".into())
+                } else if service_ids.iter().any(|id| id == answer) {
+                    Err(format!("service ID {} is already in use", answer))
+                } else {
+                    Ok(answer.to_string())
+                }
+            },
+        )?;
+        builder.add_service(&service_id, &[node.identity.clone()])?;
+        service_ids.push(service_id);
+    }
+    builder.apply_service_type("*", &service_type);
+
+    if service_ids.len() > 1
+        && prompter.confirm("Should the services be peers of each other?", true)?
+    {
+        let peers = service_ids.iter().map(String::as_str).collect::<Vec<_>>();
+        builder.apply_peer_services(&peers)?;
+    }
+
+    if service_type == DEFAULT_SERVICE_TYPE {
+        let admin_keys = prompter.ask_valid(
+            "Scabbard admin keys, separated by commas",
+            Some(public_key),
+            parse_admin_keys,
+        )?;
+        builder.apply_service_arguments("*", &("admin_keys".to_string(), admin_keys))?;
+    }
+
+    let mut argument_keys = HashSet::new();
+    loop {
+        let argument = prompter.ask_valid(
+            "Additional service argument as <key>=<value> (leave empty to finish)",
+            Some(""),
+            |answer| {
+                if answer.is_empty() {
+                    return Ok(None);
+                }
+                match answer.split_once('=') {
+                    Some((key, value)) if !key.is_empty() && !value.is_empty() => {
+                        if key == "peer_services"
+                            || key == "admin_keys"
+                            || argument_keys.contains(key)
+                        {
+                            Err(format!("service argument {} is already set", key))
+                        } else {
+                            Ok(Some((key.to_string(), value.to_string())))
+                        }
+                    }
+                    _ => Err("expected a service argument in the form <key>=<value>".into()),
+                }
+            },
+        )?;
+        match argument {
+            Some(argument) => {
+                builder.apply_service_arguments("*", &argument)?;
+                argument_keys.insert(argument.0);
+            }
+            None => break,
+        }
+    }
+
+    let display_name = prompter.ask("Display name (optional)", Some(""))?;
+    if !display_name.is_empty() {
+        builder.set_display_name(&display_name);
+    }
+
+    let comments = prompter.ask("Comments (optional)", Some(""))?;
+    if !comments.is_empty() {
+        builder.set_comments(&comments);
+    }
+
+    Ok(())
+}
+
+/// Returns the registry nodes selected by a comma-separated list of numbers from the list shown
+/// to the user, or of node IDs.
+fn parse_members<'a>(
+    answer: &str,
+    registry_nodes: &'a [RegistryNode],
+) -> Result<Vec<&'a RegistryNode>, String> {
+    let mut members: Vec<&RegistryNode> = vec![];
+    for selection in answer.split(',').map(str::trim) {
+        if selection.is_empty() {
+            continue;
+        }
+        let node = match selection.parse::<usize>() {
+            Ok(number) => number
+                .checked_sub(1)
+                .and_then(|index| registry_nodes.get(index))
+                .ok_or_else(|| format!("{} is not the number of a node in the list", number))?,
+            Err(_) => registry_nodes
+                .iter()
+                .find(|node| node.identity == selection)
+                .ok_or_else(|| format!("node {} is not in the registry", selection))?,
+        };
+        if members
+            .iter()
+            .any(|member| member.identity == node.identity)
+        {
+            return Err(format!(
+                "node {} was selected more than once",
+                node.identity
+            ));
+        }
+        members.push(node);
+    }
+
+    if members.is_empty() {
+        Err("at least one member must be selected".into())
+    } else {
+        Ok(members)
+    }
+}
+
+fn parse_admin_keys(answer: &str) -> Result<String, String> {
+    let keys = answer
+        .split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .collect::<Vec<_>>();
+    if keys.is_empty() {
+        return Err("at least one admin key is required".into());
+    }
+    for key in &keys {
+        match parse_hex(key) {
+            Ok(bytes) if bytes.len() == 33 => (),
+            _ => return Err(format!("{} is not a valid public key", key)),
+        }
+    }
+    Ok(keys.join(","))
+}
+
+/// Asks questions on an output and reads the answers from an input.
+struct Prompter<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    fn new(input: R, output: W) -> Self {
+        Prompter { input, output }
+    }
+
+    fn say(&mut self, message: &str) -> Result<(), CliError> {
+        writeln!(self.output, "{}", message).map_err(prompt_error)
+    }
+
+    /// Asks a question and returns the trimmed answer, or the default if the answer is empty.
+    fn ask(&mut self, question: &str, default: Option<&str>) -> Result<String, CliError> {
+        match default {
+            Some(default) if !default.is_empty() => {
+                write!(self.output, "{} [{}]: ", question, default)
+            }
+            _ => write!(self.output, "{}: ", question),
+        }
+        .and_then(|_| self.output.flush())
+        .map_err(prompt_error)?;
+
+        let mut answer = String::new();
+        if self.input.read_line(&mut answer).map_err(prompt_error)? == 0 {
+            return Err(CliError::ActionError(
+                "Input ended before the circuit proposal was complete".into(),
+            ));
+        }
+
+        let answer = answer.trim();
+        match default {
+            Some(default) if answer.is_empty() => Ok(default.to_string()),
+            _ => Ok(answer.to_string()),
+        }
+    }
+
+    /// Asks a question until the answer passes the given validation.
+    fn ask_valid<T, F>(
+        &mut self,
+        question: &str,
+        default: Option<&str>,
+        validate: F,
+    ) -> Result<T, CliError>
+    where
+        F: Fn(&str) -> Result<T, String>,
+    {
+        loop {
+            let answer = self.ask(question, default)?;
+            match validate(&answer) {
+                Ok(value) => return Ok(value),
+                Err(msg) => self.say(&format!("Invalid answer: {}", msg))?,
+            }
+        }
+    }
+
+    fn confirm(&mut self, question: &str, default: bool) -> Result<bool, CliError> {
+        let default = if default { "y" } else { "n" };
+        self.ask_valid(
+            &format!("{} (y/n)", question),
+            Some(default),
+            |answer| match answer.to_lowercase().as_str() {
+                "y" | "yes" => Ok(true),
+                "n" | "no" => Ok(false),
+                _ => Err("expected y or n".into()),
+            },
+        )
+    }
+}
+
+fn prompt_error(err: io::Error) -> CliError {
+    CliError::EnvironmentError(format!("Failed to prompt for input: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+    use std::io::Cursor;
+
+    const KEY_A: &str = "0384781d9f2ef1e7c0d6d5a1c2e4bd0b9b4bb3e2a9b7f0e1c6f9a4c1f3e2b1a0c9";
+    const KEY_B: &str = "02a5c9cbb2d4e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f0a1";
+
+    fn registry_node(identity: &str, endpoint: &str, keys: &[&str]) -> RegistryNode {
+        RegistryNode {
+            identity: identity.into(),
+            endpoints: vec![endpoint.into()],
+            display_name: identity.to_uppercase(),
+            keys: keys.iter().map(|key| key.to_string()).collect(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    /// Verify that the wizard builds a proposal from valid answers, re-asking the questions whose
+    /// answers are invalid.
+    #[test]
+    fn test_build_proposal() {
+        let registry_nodes = vec![
+            registry_node("alpha", "tcps://alpha:8044", &[KEY_A]),
+            registry_node("beta", "tcps://beta:8044", &[]),
+        ];
+
+        let answers = [
+            "3",         // not in the list
+            "1,alpha",   // selected twice
+            "1, beta",   // members
+            "challenge", // beta has no keys
+            "",          // trust
+            "",          // management type is required
+            "gameroom",  // management type
+            "",          // scabbard
+            "",          // a000
+            "a000",      // already in use
+            "b001",      // service ID for beta
+            "",          // peers
+            "",          // the requester's key
+            "version",   // not a key/value pair
+            "version=2", // service argument
+            "",          // no more arguments
+            "Test circuit",
+            "",
+        ];
+        let mut prompter = Prompter::new(Cursor::new(answers.join("\n")), vec![]);
+        let mut builder = CreateCircuitMessageBuilder::new();
+
+        build_proposal(&mut prompter, &registry_nodes, KEY_B, &mut builder)
+            .expect("Failed to build proposal");
+
+        let output = String::from_utf8(prompter.output).expect("Output is not UTF-8");
+        assert_eq!(output.matches("Invalid answer").count(), 6);
+
+        let circuit = builder.build().expect("Failed to build circuit");
+        assert_eq!(
+            circuit
+                .members
+                .iter()
+                .map(|node| node.node_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["alpha", "beta"]
+        );
+        assert_eq!(circuit.circuit_management_type, "gameroom");
+        assert_eq!(circuit.display_name.as_deref(), Some("Test circuit"));
+
+        assert_eq!(circuit.roster.len(), 2);
+        let service = &circuit.roster[1];
+        assert_eq!(service.service_id, "b001");
+        assert_eq!(service.service_type, "scabbard");
+        assert_eq!(service.allowed_nodes, vec!["beta".to_string()]);
+        assert!(service
+            .arguments
+            .contains(&("peer_services".to_string(), "[\"a000\"]".to_string())));
+        assert!(service
+            .arguments
+            .contains(&("admin_keys".to_string(), KEY_B.to_string())));
+        assert!(service
+            .arguments
+            .contains(&("version".to_string(), "2".to_string())));
+    }
+
+    /// Verify that the wizard stops with an error when the input ends early.
+    #[test]
+    fn test_build_proposal_input_ends() {
+        let registry_nodes = vec![registry_node("alpha", "tcps://alpha:8044", &[KEY_A])];
+
+        let mut prompter = Prompter::new(Cursor::new("1\n"), vec![]);
+        let mut builder = CreateCircuitMessageBuilder::new();
+
+        assert!(build_proposal(&mut prompter, &registry_nodes, KEY_B, &mut builder).is_err());
+    }
+}
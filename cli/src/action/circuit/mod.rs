@@ -14,14 +14,24 @@
 
 mod api;
 mod builder;
+#[cfg(feature = "circuit-propose-interactive")]
+mod interactive;
 mod payload;
 #[cfg(feature = "circuit-template")]
 pub mod template;
+#[cfg(feature = "circuit-template-file")]
+mod template_file;
 
+#[cfg(feature = "circuit-update-service")]
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::fmt::Write;
 use std::fs::File;
+#[cfg(feature = "circuit-disband-wait")]
+use std::thread;
+#[cfg(feature = "circuit-disband-wait")]
+use std::time::{Duration, Instant};
 
 use clap::ArgMatches;
 use cylinder::Signer;
@@ -37,16 +47,28 @@ use crate::signing::{create_cylinder_jwt_auth, load_signer};
 #[cfg(feature = "circuit-template")]
 use crate::template::CircuitTemplate;
 
+#[cfg(feature = "circuit-disband-wait")]
+use super::api::SplinterRestClient;
 use super::api::SplinterRestClientBuilder;
-use super::{
-    msg_from_io_error, print_table, Action, DEFAULT_SPLINTER_REST_API_URL,
-    SPLINTER_REST_API_URL_ENV,
-};
+#[cfg(feature = "database-archive")]
+use super::database::CircuitArchiver;
+use super::output::{print_value, OutputFormat, Table};
+use super::{msg_from_io_error, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
 
+#[cfg(feature = "circuit-disband-wait")]
+use api::ProposalSlice;
 use api::{CircuitMembers, CircuitServiceSlice, CircuitSlice};
 pub(crate) use builder::CreateCircuitMessageBuilder;
 use payload::make_signed_payload;
 
+#[cfg(feature = "circuit-disband-wait")]
+const DISBAND_POLL_INTERVAL: Duration = Duration::from_secs(2);
+#[cfg(feature = "circuit-version")]
+const CIRCUIT_VERSION_ENV: &str = "SPLINTER_CIRCUIT_VERSION";
+/// The service argument that names the encoding of the circuit's service message payloads
+#[cfg(feature = "circuit-payload-encoding")]
+const PAYLOAD_ENCODING_ARGUMENT: &str = "payload_encoding";
+
 pub struct CircuitProposeAction;
 
 impl Action for CircuitProposeAction {
@@ -55,6 +77,39 @@ impl Action for CircuitProposeAction {
 
         let mut builder = CreateCircuitMessageBuilder::new();
 
+        let compat_v0_4 = args.value_of("compat_version") == Some("0.4");
+        #[cfg(feature = "circuit-version")]
+        let circuit_version = get_circuit_version(args)?;
+        #[cfg(not(feature = "circuit-version"))]
+        let circuit_version = CIRCUIT_PROTOCOL_VERSION;
+        // Challenge authorization, display names and circuit statuses were added in circuit
+        // version 2, and cannot be parsed by nodes that only support older versions
+        let legacy_circuit = compat_v0_4 || circuit_version < CIRCUIT_PROTOCOL_VERSION;
+
+        // The wizard's answers replace the arguments that describe the circuit, which clap does
+        // not allow together with --interactive
+        #[cfg(feature = "circuit-propose-interactive")]
+        {
+            if args.is_present("interactive") {
+                interactive::run_wizard(args, &mut builder)?;
+            }
+        }
+
+        // The template file describes the members and services, which clap does not allow together
+        // with the arguments that would also define them
+        #[cfg(feature = "circuit-template-file")]
+        {
+            if let Some(template_file) = args.value_of("template_file") {
+                let mut template = template_file::CircuitTemplateFile::load(template_file)?;
+                if let Some(template_vars) = args.values_of("template_var") {
+                    template.set_variables(template_file::parse_template_vars(
+                        &template_vars.collect::<Vec<&str>>(),
+                    )?);
+                }
+                template.apply_to_builder(&mut builder)?;
+            }
+        }
+
         let mut public_keys = HashMap::new();
         if let Some(nodes_public_keys) = args.values_of("node_public_key") {
             for node_argument in nodes_public_keys {
@@ -114,6 +169,17 @@ impl Action for CircuitProposeAction {
             }
         }
 
+        #[cfg(feature = "circuit-payload-encoding")]
+        if let Some(payload_encoding) = args.value_of("payload_encoding") {
+            builder.apply_service_arguments(
+                "*",
+                &(
+                    PAYLOAD_ENCODING_ARGUMENT.to_string(),
+                    payload_encoding.to_string(),
+                ),
+            )?;
+        }
+
         if let Some(service_peer_group) = args.values_of("service_peer_group") {
             for peer_group in service_peer_group {
                 let group = parse_service_peer_group(peer_group)?;
@@ -123,17 +189,17 @@ impl Action for CircuitProposeAction {
 
         match args.value_of("authorization_type") {
             Some(auth_type) => {
-                if args.value_of("compat_version") == Some("0.4") && auth_type == "challenge" {
-                    return Err(CliError::ActionError(
-                        "Challenge authorization is not compatible with \
-                        Splinter v0.4"
-                            .to_string(),
-                    ));
+                if legacy_circuit && auth_type == "challenge" {
+                    return Err(CliError::ActionError(incompatible_feature(
+                        "Challenge authorization",
+                        compat_v0_4,
+                        circuit_version,
+                    )));
                 }
                 builder.set_authorization_type(auth_type)?;
             }
             None => {
-                if args.value_of("compat_version") == Some("0.4") {
+                if legacy_circuit {
                     builder.set_authorization_type("trust")?;
                 }
             }
@@ -189,24 +255,45 @@ impl Action for CircuitProposeAction {
         }
 
         if let Some(display_name) = args.value_of("display_name") {
-            if args.value_of("compat_version") == Some("0.4") {
-                return Err(CliError::ActionError(
-                    "Display name is not compatible with Splinter v0.4".to_string(),
-                ));
+            if legacy_circuit {
+                return Err(CliError::ActionError(incompatible_feature(
+                    "Display name",
+                    compat_v0_4,
+                    circuit_version,
+                )));
             }
             builder.set_display_name(display_name);
         }
 
-        if args.value_of("compat_version") != Some("0.4") {
-            builder.set_circuit_version(CIRCUIT_PROTOCOL_VERSION);
-            builder.set_circuit_status(CircuitStatus::Active);
+        if !compat_v0_4 {
+            builder.set_circuit_version(circuit_version);
+            if !legacy_circuit {
+                builder.set_circuit_status(CircuitStatus::Active);
+            }
         }
 
         let create_circuit = builder.build()?;
 
         let circuit_slice = CircuitSlice::try_from(&create_circuit)?;
 
-        if !args.is_present("dry_run") {
+        #[cfg(feature = "circuit-propose-interactive")]
+        {
+            if args.is_present("interactive")
+                && !args.is_present("dry_run")
+                && !interactive::confirm_submit(&circuit_slice)?
+            {
+                info!("The circuit proposal was not submitted");
+                return Ok(());
+            }
+        }
+
+        // A dry run validates the proposal on the node, if validation is enabled
+        #[cfg(feature = "circuit-propose-validate")]
+        let contact_node = true;
+        #[cfg(not(feature = "circuit-propose-validate"))]
+        let contact_node = !args.is_present("dry_run");
+
+        if contact_node {
             let url = args
                 .value_of("url")
                 .map(ToOwned::to_owned)
@@ -223,6 +310,26 @@ impl Action for CircuitProposeAction {
             let requester_node = client.get_node_status()?.node_id;
 
             let signed_payload = make_signed_payload(&requester_node, signer, create_circuit)?;
+
+            #[cfg(feature = "circuit-propose-validate")]
+            if args.is_present("dry_run") {
+                info!("{}", circuit_slice);
+                let validation = client.validate_admin_payload(signed_payload)?;
+                if validation.valid {
+                    info!("The circuit proposal is valid");
+                    return Ok(());
+                }
+                return Err(CliError::ActionError(format!(
+                    "The circuit proposal is invalid:\n{}",
+                    validation
+                        .errors
+                        .iter()
+                        .map(|error| format!("  {}: {}", error.check, error.message))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                )));
+            }
+
             client.submit_admin_payload(signed_payload)?;
 
             info!("The circuit proposal was submitted successfully");
@@ -293,6 +400,43 @@ fn load_nodes_from_local(node_file: &str) -> Result<Vec<Node>, CliError> {
     })
 }
 
+/// Returns the circuit version to propose, which is set by the `--circuit-version` argument or
+/// the `SPLINTER_CIRCUIT_VERSION` environment variable. Defaults to the newest circuit version.
+#[cfg(feature = "circuit-version")]
+fn get_circuit_version(args: &ArgMatches) -> Result<i32, CliError> {
+    let version = match args
+        .value_of("circuit_version")
+        .map(String::from)
+        .or_else(|| std::env::var(CIRCUIT_VERSION_ENV).ok())
+    {
+        Some(version) => version,
+        None => return Ok(CIRCUIT_PROTOCOL_VERSION),
+    };
+
+    version
+        .parse::<i32>()
+        .ok()
+        .filter(|version| (1..=CIRCUIT_PROTOCOL_VERSION).contains(version))
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Invalid circuit version {}, must be between 1 and {}",
+                version, CIRCUIT_PROTOCOL_VERSION
+            ))
+        })
+}
+
+/// Returns the error message for a feature that cannot be used by the circuit being proposed.
+fn incompatible_feature(feature: &str, compat_v0_4: bool, circuit_version: i32) -> String {
+    if compat_v0_4 {
+        format!("{} is not compatible with Splinter v0.4", feature)
+    } else {
+        format!(
+            "{} requires circuit version {}, but circuit version {} was requested",
+            feature, CIRCUIT_PROTOCOL_VERSION, circuit_version
+        )
+    }
+}
+
 fn parse_node_argument(node_argument: &str) -> Result<(String, Vec<String>), CliError> {
     let mut iter = node_argument.split("::");
 
@@ -740,7 +884,28 @@ impl Action for CircuitDisbandAction {
             .value_of("circuit_id")
             .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
 
-        propose_circuit_disband(&url, signer, circuit_id)
+        #[cfg(feature = "database-archive")]
+        let archiver = CircuitArchiver::from_args(args)?;
+
+        #[cfg(feature = "circuit-disband-wait")]
+        let wait = args
+            .value_of("wait")
+            .map(|wait| {
+                wait.parse::<u64>().map(Duration::from_secs).map_err(|_| {
+                    CliError::ActionError(format!("'{}' is not a valid number of seconds", wait))
+                })
+            })
+            .transpose()?;
+
+        propose_circuit_disband(
+            &url,
+            signer,
+            circuit_id,
+            #[cfg(feature = "database-archive")]
+            archiver,
+            #[cfg(feature = "circuit-disband-wait")]
+            wait,
+        )
     }
 }
 
@@ -748,6 +913,8 @@ fn propose_circuit_disband(
     url: &str,
     signer: Box<dyn Signer>,
     circuit_id: &str,
+    #[cfg(feature = "database-archive")] archiver: Option<CircuitArchiver>,
+    #[cfg(feature = "circuit-disband-wait")] wait: Option<Duration>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
         .with_url(url.to_string())
@@ -758,11 +925,23 @@ fn propose_circuit_disband(
     let circuit = client.fetch_circuit(circuit_id)?;
 
     if circuit.is_some() {
+        #[cfg(feature = "database-archive")]
+        if let Some(archiver) = archiver {
+            archiver.archive(circuit_id)?;
+        }
+
         let circuit_disband_request = CircuitDisband {
             circuit_id: circuit_id.into(),
         };
         let signed_payload = make_signed_payload(&requester_node, signer, circuit_disband_request)?;
-        client.submit_admin_payload(signed_payload)
+        client.submit_admin_payload(signed_payload)?;
+
+        #[cfg(feature = "circuit-disband-wait")]
+        if let Some(wait) = wait {
+            wait_for_disband(&client, circuit_id, wait, DISBAND_POLL_INTERVAL)?;
+        }
+
+        Ok(())
     } else {
         Err(CliError::ActionError(format!(
             "Circuit '{}' does not exist",
@@ -771,6 +950,200 @@ fn propose_circuit_disband(
     }
 }
 
+/// The requests made to the node while waiting for a circuit to be disbanded.
+#[cfg(feature = "circuit-disband-wait")]
+trait DisbandStatusClient {
+    fn fetch_proposal(&self, circuit_id: &str) -> Result<Option<ProposalSlice>, CliError>;
+
+    fn fetch_circuit(&self, circuit_id: &str) -> Result<Option<CircuitSlice>, CliError>;
+}
+
+#[cfg(feature = "circuit-disband-wait")]
+impl DisbandStatusClient for SplinterRestClient {
+    fn fetch_proposal(&self, circuit_id: &str) -> Result<Option<ProposalSlice>, CliError> {
+        SplinterRestClient::fetch_proposal(self, circuit_id)
+    }
+
+    fn fetch_circuit(&self, circuit_id: &str) -> Result<Option<CircuitSlice>, CliError> {
+        SplinterRestClient::fetch_circuit(self, circuit_id)
+    }
+}
+
+/// Waits until the circuit is disbanded, checking every `poll_interval` and logging the members'
+/// votes on the disband proposal as they are received.
+///
+/// Fails if the proposal is rejected, or if the circuit is not disbanded before the wait is over.
+#[cfg(feature = "circuit-disband-wait")]
+fn wait_for_disband(
+    client: &dyn DisbandStatusClient,
+    circuit_id: &str,
+    wait: Duration,
+    poll_interval: Duration,
+) -> Result<(), CliError> {
+    let deadline = Instant::now() + wait;
+    let mut reported_votes = 0;
+    let mut proposal_seen = false;
+
+    loop {
+        if let Some(proposal) = client
+            .fetch_proposal(circuit_id)?
+            .filter(|proposal| proposal.proposal_type == "Disband")
+        {
+            proposal_seen = true;
+            for vote in proposal.votes.iter().skip(reported_votes) {
+                info!("Node {} voted {}", vote.voter_node_id, vote.vote);
+            }
+            reported_votes = reported_votes.max(proposal.votes.len());
+        } else {
+            match client.fetch_circuit(circuit_id)? {
+                Some(circuit) if circuit.circuit_status == Some(CircuitStatus::Disbanded) => {
+                    info!("Circuit {} has been disbanded", circuit_id);
+                    return Ok(());
+                }
+                // The proposal is removed once it is rejected, leaving the circuit active
+                Some(_) if proposal_seen => {
+                    return Err(CliError::ActionError(format!(
+                        "The proposal to disband circuit {} was rejected",
+                        circuit_id
+                    )));
+                }
+                None => {
+                    return Err(CliError::ActionError(format!(
+                        "Circuit '{}' no longer exists",
+                        circuit_id
+                    )));
+                }
+                Some(_) => (),
+            }
+        }
+
+        if Instant::now() >= deadline {
+            return Err(CliError::ActionError(format!(
+                "Timed out waiting for circuit {} to be disbanded; {} vote(s) received",
+                circuit_id, reported_votes
+            )));
+        }
+        thread::sleep(poll_interval);
+    }
+}
+
+#[cfg(feature = "circuit-update-service")]
+struct UpdateServiceArguments {
+    circuit_id: String,
+    service_id: String,
+    arguments: BTreeMap<String, String>,
+}
+
+#[cfg(feature = "circuit-update-service")]
+pub struct CircuitUpdateServiceAction;
+
+#[cfg(feature = "circuit-update-service")]
+impl Action for CircuitUpdateServiceAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let url = args
+            .value_of("url")
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+            .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+        let signer = load_signer(args.value_of("private_key_file"))?;
+
+        let circuit_id = args
+            .value_of("circuit_id")
+            .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
+        let service_id = args
+            .value_of("service_id")
+            .ok_or_else(|| CliError::ActionError("'service-id' argument is required".into()))?;
+
+        let set_arguments = args
+            .values_of("argument")
+            .into_iter()
+            .flatten()
+            .map(|argument| match argument.split_once('=') {
+                Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+                _ => Err(CliError::ActionError(format!(
+                    "Invalid argument '{}': must be formatted as <key>=<value>",
+                    argument
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let removed_arguments = args
+            .values_of("remove_argument")
+            .into_iter()
+            .flatten()
+            .collect();
+
+        propose_service_update(
+            &url,
+            signer,
+            circuit_id,
+            service_id,
+            set_arguments,
+            removed_arguments,
+        )
+    }
+}
+
+/// Proposes to change the arguments of a service. The service's current arguments are fetched
+/// from the circuit, and the given arguments are set or removed; the arguments that are not named
+/// are kept.
+#[cfg(feature = "circuit-update-service")]
+fn propose_service_update(
+    url: &str,
+    signer: Box<dyn Signer>,
+    circuit_id: &str,
+    service_id: &str,
+    set_arguments: Vec<(String, String)>,
+    removed_arguments: Vec<&str>,
+) -> Result<(), CliError> {
+    let client = SplinterRestClientBuilder::new()
+        .with_url(url.to_string())
+        .with_auth(create_cylinder_jwt_auth(signer.clone())?)
+        .build()?;
+
+    let requester_node = client.get_node_status()?.node_id;
+    let circuit = client
+        .fetch_circuit(circuit_id)?
+        .ok_or_else(|| CliError::ActionError(format!("Circuit '{}' does not exist", circuit_id)))?;
+
+    if circuit.circuit_status != Some(CircuitStatus::Active) {
+        return Err(CliError::ActionError(format!(
+            "Circuit '{}' is not active",
+            circuit_id
+        )));
+    }
+
+    let mut arguments = circuit
+        .roster
+        .into_iter()
+        .find(|service| service.service_id == service_id)
+        .ok_or_else(|| {
+            CliError::ActionError(format!(
+                "Circuit '{}' does not have service '{}'",
+                circuit_id, service_id
+            ))
+        })?
+        .arguments;
+    for key in removed_arguments {
+        if arguments.remove(key).is_none() {
+            return Err(CliError::ActionError(format!(
+                "Service '{}' does not have argument '{}'",
+                service_id, key
+            )));
+        }
+    }
+    arguments.extend(set_arguments);
+
+    let update_service_arguments = UpdateServiceArguments {
+        circuit_id: circuit_id.into(),
+        service_id: service_id.into(),
+        arguments,
+    };
+    let signed_payload = make_signed_payload(&requester_node, signer, update_service_arguments)?;
+    client.submit_admin_payload(signed_payload)
+}
+
 struct CircuitPurge {
     circuit_id: String,
 }
@@ -792,7 +1165,16 @@ impl Action for CircuitPurgeAction {
             .value_of("circuit_id")
             .ok_or_else(|| CliError::ActionError("'circuit-id' argument is required".into()))?;
 
-        request_purge_circuit(&url, signer, circuit_id)
+        #[cfg(feature = "database-archive")]
+        let archiver = CircuitArchiver::from_args(args)?;
+
+        request_purge_circuit(
+            &url,
+            signer,
+            circuit_id,
+            #[cfg(feature = "database-archive")]
+            archiver,
+        )
     }
 }
 
@@ -800,6 +1182,7 @@ fn request_purge_circuit(
     url: &str,
     signer: Box<dyn Signer>,
     circuit_id: &str,
+    #[cfg(feature = "database-archive")] archiver: Option<CircuitArchiver>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
         .with_url(url.to_string())
@@ -819,6 +1202,11 @@ fn request_purge_circuit(
                 circuit_id
             )));
         }
+        #[cfg(feature = "database-archive")]
+        if let Some(archiver) = archiver {
+            archiver.archive(circuit_id)?;
+        }
+
         let circuit_purge_request = CircuitPurge {
             circuit_id: circuit_id.into(),
         };
@@ -958,15 +1346,7 @@ impl Action for CircuitListAction {
         let member_filter = arg_matches.and_then(|args| args.value_of("member"));
         let status_filter = arg_matches.and_then(|args| args.value_of("circuit_status"));
 
-        let format = arg_matches
-            .and_then(|args| {
-                if let Some(val) = args.value_of("hidden_format") {
-                    Some(val)
-                } else {
-                    args.value_of("format")
-                }
-            })
-            .unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
 
         let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
 
@@ -978,7 +1358,7 @@ fn list_circuits(
     url: &str,
     member_filter: Option<&str>,
     status_filter: Option<&str>,
-    format: &str,
+    format: OutputFormat,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
@@ -987,48 +1367,23 @@ fn list_circuits(
         .build()?;
 
     let circuits = client.list_circuits(member_filter, status_filter)?;
-    let mut data = vec![
-        // Header
-        vec![
-            "ID".to_string(),
-            "NAME".to_string(),
-            "MANAGEMENT".to_string(),
-            "MEMBERS".to_string(),
-        ],
-    ];
-    circuits.data.iter().for_each(|circuit| {
+    let mut table = Table::new(&["ID", "NAME", "MANAGEMENT", "MEMBERS"]);
+    circuits.data.into_iter().for_each(|circuit| {
         let members = circuit
             .members
             .iter()
             .map(|node| node.node_id.to_string())
             .collect::<Vec<String>>()
             .join(";");
-        let display_name = {
-            if format == "csv" {
-                circuit.display_name.clone().unwrap_or_default()
-            } else {
-                circuit
-                    .display_name
-                    .clone()
-                    .unwrap_or_else(|| "-".to_string())
-            }
-        };
-        data.push(vec![
-            circuit.id.to_string(),
-            display_name,
-            circuit.management_type.to_string(),
+        table.add_row(vec![
+            circuit.id,
+            circuit.display_name.unwrap_or_default(),
+            circuit.management_type,
             members,
         ]);
     });
 
-    if format == "csv" {
-        for row in data {
-            println!("{}", row.join(","))
-        }
-    } else {
-        print_table(data);
-    }
-    Ok(())
+    table.print(format)
 }
 
 pub struct CircuitShowAction;
@@ -1046,11 +1401,7 @@ impl Action for CircuitShowAction {
             .value_of("circuit")
             .ok_or_else(|| CliError::ActionError("'circuit' argument is required".to_string()))?;
 
-        let format = if let Some(val) = args.value_of("hidden_format") {
-            val
-        } else {
-            args.value_of("format").unwrap_or("human")
-        };
+        let format = OutputFormat::from_args(arg_matches)?;
 
         let signer = load_signer(args.value_of("private_key_file"))?;
 
@@ -1061,7 +1412,7 @@ impl Action for CircuitShowAction {
 fn show_circuit(
     url: &str,
     circuit_id: &str,
-    format: &str,
+    format: OutputFormat,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
@@ -1074,46 +1425,18 @@ fn show_circuit(
     let mut print_proposal = false;
     if let Some(circuit) = circuit {
         print_circuit = true;
-        match format {
-            "json" => println!(
-                "\n {}",
-                serde_json::to_string(&circuit).map_err(|err| CliError::ActionError(format!(
-                    "Cannot format circuit into json: {}",
-                    err
-                )))?
-            ),
-            "yaml" => println!(
-                "{}",
-                serde_yaml::to_string(&circuit).map_err(|err| CliError::ActionError(format!(
-                    "Cannot format circuit into yaml: {}",
-                    err
-                )))?
-            ),
-            _ => println!("{}", circuit),
-        }
+        print_value(format, "circuit", &circuit, |circuit| {
+            println!("{}", circuit)
+        })?;
     }
 
     let proposal = client.fetch_proposal(circuit_id)?;
 
     if let Some(proposal) = proposal {
         print_proposal = true;
-        match format {
-            "json" => println!(
-                "\n {}",
-                serde_json::to_string(&proposal).map_err(|err| CliError::ActionError(format!(
-                    "Cannot format proposal into json: {}",
-                    err
-                )))?
-            ),
-            "yaml" => println!(
-                "{}",
-                serde_yaml::to_string(&proposal).map_err(|err| CliError::ActionError(format!(
-                    "Cannot format proposal into yaml: {}",
-                    err
-                )))?
-            ),
-            _ => println!("{}", proposal),
-        }
+        print_value(format, "proposal", &proposal, |proposal| {
+            println!("{}", proposal)
+        })?;
     }
 
     if !print_circuit && !print_proposal {
@@ -1140,15 +1463,7 @@ impl Action for CircuitProposalsAction {
 
         let member_filter = arg_matches.and_then(|args| args.value_of("member"));
 
-        let format = arg_matches
-            .and_then(|args| {
-                if let Some(val) = args.value_of("hidden_format") {
-                    Some(val)
-                } else {
-                    args.value_of("format")
-                }
-            })
-            .unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
 
         let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
 
@@ -1160,7 +1475,7 @@ fn list_proposals(
     url: &str,
     management_type_filter: Option<&str>,
     member_filter: Option<&str>,
-    format: &str,
+    format: OutputFormat,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
@@ -1169,42 +1484,15 @@ fn list_proposals(
         .build()?;
 
     let proposals = client.list_proposals(management_type_filter, member_filter)?;
-    let mut data = vec![
-        // header
-        vec![
-            "ID".to_string(),
-            "NAME".to_string(),
-            "MANAGEMENT".to_string(),
-            "MEMBERS".to_string(),
-            "COMMENTS".to_string(),
-            "PROPOSAL_TYPE".to_string(),
-        ],
-    ];
-    proposals.data.iter().for_each(|proposal| {
-        let display_name = {
-            if format == "csv" {
-                proposal.circuit.display_name.clone().unwrap_or_default()
-            } else {
-                proposal
-                    .circuit
-                    .display_name
-                    .clone()
-                    .unwrap_or_else(|| "-".to_string())
-            }
-        };
-
-        let comments = {
-            if format == "csv" {
-                proposal.circuit.comments.clone().unwrap_or_default()
-            } else {
-                proposal
-                    .circuit
-                    .comments
-                    .clone()
-                    .unwrap_or_else(|| "-".to_string())
-            }
-        };
-
+    let mut table = Table::new(&[
+        "ID",
+        "NAME",
+        "MANAGEMENT",
+        "MEMBERS",
+        "COMMENTS",
+        "PROPOSAL_TYPE",
+    ]);
+    proposals.data.into_iter().for_each(|proposal| {
         let members = proposal
             .circuit
             .members
@@ -1212,23 +1500,162 @@ fn list_proposals(
             .map(|member| member.node_id.to_string())
             .collect::<Vec<String>>()
             .join(";");
-        data.push(vec![
-            proposal.circuit_id.to_string(),
-            display_name,
-            proposal.circuit.management_type.to_string(),
+        table.add_row(vec![
+            proposal.circuit_id,
+            proposal.circuit.display_name.unwrap_or_default(),
+            proposal.circuit.management_type,
             members,
-            comments,
-            proposal.proposal_type.to_string(),
+            proposal.circuit.comments.unwrap_or_default(),
+            proposal.proposal_type,
         ]);
     });
 
-    if format == "csv" {
-        for row in data {
-            println!("{}", row.join(","))
+    table.print(format)
+}
+
+#[cfg(all(test, feature = "circuit-disband-wait"))]
+mod tests {
+    use super::*;
+
+    use std::cell::{Cell, RefCell};
+    use std::collections::VecDeque;
+
+    use api::{ProposalCircuitSlice, VoteRecord};
+
+    const CIRCUIT_ID: &str = "0z2C4-hheAY";
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    /// Answers the disband wait's requests: each proposal request takes the next queued
+    /// proposal, or finds no proposal once the queue is empty, and circuit requests always
+    /// find the circuit with the given status.
+    struct MockDisbandStatusClient {
+        proposals: RefCell<VecDeque<ProposalSlice>>,
+        circuit_status: Option<CircuitStatus>,
+        proposal_requests: Cell<usize>,
+    }
+
+    impl MockDisbandStatusClient {
+        fn new(proposals: Vec<ProposalSlice>, circuit_status: Option<CircuitStatus>) -> Self {
+            Self {
+                proposals: RefCell::new(proposals.into()),
+                circuit_status,
+                proposal_requests: Cell::new(0),
+            }
         }
-    } else {
-        print_table(data);
     }
 
-    Ok(())
+    impl DisbandStatusClient for MockDisbandStatusClient {
+        fn fetch_proposal(&self, _circuit_id: &str) -> Result<Option<ProposalSlice>, CliError> {
+            self.proposal_requests.set(self.proposal_requests.get() + 1);
+            Ok(self.proposals.borrow_mut().pop_front())
+        }
+
+        fn fetch_circuit(&self, circuit_id: &str) -> Result<Option<CircuitSlice>, CliError> {
+            Ok(self.circuit_status.clone().map(|status| CircuitSlice {
+                id: circuit_id.to_string(),
+                members: vec![],
+                roster: vec![],
+                management_type: "test".to_string(),
+                display_name: None,
+                circuit_version: 2,
+                circuit_status: Some(status),
+            }))
+        }
+    }
+
+    /// Verify that the wait returns immediately if the circuit has already been disbanded.
+    #[test]
+    fn test_wait_for_disband_already_disbanded() {
+        let client = MockDisbandStatusClient::new(vec![], Some(CircuitStatus::Disbanded));
+
+        wait_for_disband(&client, CIRCUIT_ID, Duration::from_secs(5), POLL_INTERVAL)
+            .expect("failed to wait for disband");
+        assert_eq!(client.proposal_requests.get(), 1);
+    }
+
+    /// Verify that the wait polls the disband proposal until it is accepted:
+    ///
+    /// 1. Queue the proposal with one vote, then with two votes
+    /// 2. Wait for the circuit, which is disbanded once the proposal is gone
+    /// 3. Validate that the wait succeeded after polling past both proposals
+    #[test]
+    fn test_wait_for_disband_after_votes() {
+        let client = MockDisbandStatusClient::new(
+            vec![disband_proposal(1), disband_proposal(2)],
+            Some(CircuitStatus::Disbanded),
+        );
+
+        wait_for_disband(&client, CIRCUIT_ID, Duration::from_secs(5), POLL_INTERVAL)
+            .expect("failed to wait for disband");
+        assert_eq!(client.proposal_requests.get(), 3);
+    }
+
+    /// Verify that the wait fails if the disband proposal is removed while the circuit is still
+    /// active, which means the proposal was rejected.
+    #[test]
+    fn test_wait_for_disband_rejected() {
+        let client =
+            MockDisbandStatusClient::new(vec![disband_proposal(1)], Some(CircuitStatus::Active));
+
+        match wait_for_disband(&client, CIRCUIT_ID, Duration::from_secs(5), POLL_INTERVAL) {
+            Err(CliError::ActionError(msg)) => assert!(msg.contains("rejected")),
+            res => panic!("expected the proposal to be rejected, got {:?}", res),
+        }
+    }
+
+    /// Verify that the wait fails once the wait is over if the circuit is still active, after
+    /// polling more than once.
+    #[test]
+    fn test_wait_for_disband_timeout() {
+        let client = MockDisbandStatusClient::new(vec![], Some(CircuitStatus::Active));
+
+        match wait_for_disband(
+            &client,
+            CIRCUIT_ID,
+            Duration::from_millis(50),
+            POLL_INTERVAL,
+        ) {
+            Err(CliError::ActionError(msg)) => assert!(msg.contains("Timed out")),
+            res => panic!("expected the wait to time out, got {:?}", res),
+        }
+        assert!(client.proposal_requests.get() > 1);
+    }
+
+    /// Verify that the wait fails if the circuit no longer exists.
+    #[test]
+    fn test_wait_for_disband_circuit_removed() {
+        let client = MockDisbandStatusClient::new(vec![], None);
+
+        match wait_for_disband(&client, CIRCUIT_ID, Duration::from_secs(5), POLL_INTERVAL) {
+            Err(CliError::ActionError(msg)) => assert!(msg.contains("no longer exists")),
+            res => panic!("expected the circuit to be missing, got {:?}", res),
+        }
+    }
+
+    fn disband_proposal(votes: usize) -> ProposalSlice {
+        ProposalSlice {
+            proposal_type: "Disband".to_string(),
+            circuit_id: CIRCUIT_ID.to_string(),
+            circuit_hash: "abcd".to_string(),
+            circuit: ProposalCircuitSlice {
+                circuit_id: CIRCUIT_ID.to_string(),
+                members: vec![],
+                roster: vec![],
+                management_type: "test".to_string(),
+                comments: None,
+                display_name: None,
+                circuit_version: 2,
+                circuit_status: Some(CircuitStatus::Disbanded),
+            },
+            votes: (0..votes)
+                .map(|i| VoteRecord {
+                    public_key: format!("key{}", i),
+                    vote: "Accept".to_string(),
+                    voter_node_id: format!("node{}", i),
+                })
+                .collect(),
+            requester: "key0".to_string(),
+            requester_node_id: "node0".to_string(),
+        }
+    }
 }
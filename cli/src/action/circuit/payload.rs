@@ -23,10 +23,14 @@ use splinter::protos::admin::{
     CircuitManagementPayload_Action as Action, CircuitManagementPayload_Header as Header,
     CircuitProposalVote, CircuitProposalVote_Vote, CircuitPurgeRequest,
 };
+#[cfg(feature = "circuit-update-service")]
+use splinter::protos::admin::{CircuitUpdateServiceArgumentsRequest, SplinterService_Argument};
 
 use crate::error::CliError;
 
 use super::RemoveProposal;
+#[cfg(feature = "circuit-update-service")]
+use super::UpdateServiceArguments;
 use super::{AbandonedCircuit, CircuitDisband, CircuitPurge};
 use super::{CircuitVote, Vote};
 
@@ -211,3 +215,35 @@ impl ApplyToEnvelope for ProposalRemoveRequest {
         circuit_management_payload.set_proposal_remove_request(self);
     }
 }
+
+#[cfg(feature = "circuit-update-service")]
+impl CircuitAction<CircuitUpdateServiceArgumentsRequest> for UpdateServiceArguments {
+    fn action_type(&self) -> Action {
+        Action::CIRCUIT_UPDATE_SERVICE_ARGUMENTS_REQUEST
+    }
+
+    fn into_proto(self) -> Result<CircuitUpdateServiceArgumentsRequest, CliError> {
+        let mut update_request = CircuitUpdateServiceArgumentsRequest::new();
+        update_request.set_circuit_id(self.circuit_id);
+        update_request.set_service_id(self.service_id);
+        update_request.set_arguments(
+            self.arguments
+                .into_iter()
+                .map(|(key, value)| {
+                    let mut argument = SplinterService_Argument::new();
+                    argument.set_key(key);
+                    argument.set_value(value);
+                    argument
+                })
+                .collect(),
+        );
+        Ok(update_request)
+    }
+}
+
+#[cfg(feature = "circuit-update-service")]
+impl ApplyToEnvelope for CircuitUpdateServiceArgumentsRequest {
+    fn apply(self, circuit_management_payload: &mut CircuitManagementPayload) {
+        circuit_management_payload.set_circuit_update_service_arguments_request(self);
+    }
+}
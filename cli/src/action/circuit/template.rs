@@ -14,6 +14,7 @@
 
 use clap::ArgMatches;
 
+use crate::action::output::{OutputFormat, Table};
 use crate::error::CliError;
 use crate::template::CircuitTemplate;
 
@@ -26,49 +27,14 @@ impl Action for ListCircuitTemplates {
         // Collect list of template file stems and full paths to the associated file stem
         let templates = CircuitTemplate::list_available_templates()?;
 
-        let format = arg_matches
-            .and_then(|args| {
-                if let Some(val) = args.value_of("hidden_format") {
-                    Some(val)
-                } else {
-                    args.value_of("format")
-                }
-            })
-            .unwrap_or("human");
-
-        if format == "csv" {
-            print!("TEMPLATE,PATH,");
-            for (stem, path) in templates.iter() {
-                print!("{},{},", stem, path.display());
-            }
-            println!();
-        } else {
-            // Initialize the maximum column length for the first column in the template table,
-            // currently set to 8 as this is the length of the `TEMPLATE` header.
-            let mut max_length = 8;
-
-            // Find the max lengths of the initial column
-            for (stem, _) in templates.iter() {
-                if stem.len() > max_length {
-                    max_length = stem.len()
-                }
-            }
-            // Print headers for the template table to be displayed
-            let header_string = format!("{}{} {} ", "TEMPLATE", " ".repeat(max_length - 8), "PATH");
-            println!("{}", header_string);
-            // Iterate through the list of template file stems and file paths to be displayed
-            for (stem, path) in templates.iter() {
-                let row = format!(
-                    "{}{} {} ",
-                    &stem,
-                    " ".repeat(max_length - stem.len()),
-                    path.display()
-                );
-                println!("{}", row);
-            }
+        let format = OutputFormat::from_args(arg_matches)?;
+
+        let mut table = Table::new(&["TEMPLATE", "PATH"]);
+        for (stem, path) in templates {
+            table.add_row(vec![stem, path.display().to_string()]);
         }
 
-        Ok(())
+        table.print(format)
     }
 }
 
@@ -0,0 +1,538 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Circuit definitions read from a YAML file, for `splinter circuit propose --template-file`.
+//!
+//! A template file describes the members and services of a circuit, so that the same definition
+//! can be reused with different nodes. Any string in the `circuit` section may refer to a variable
+//! as `${name}`; variables are defined in the `variables` section and may be overridden with
+//! `--template-var name=value`.
+//!
+//! ```yaml
+//! version: v1
+//! variables:
+//!   acme_node: acme-node-000
+//!   acme_endpoint: tcps://splinterd-node-acme:8044
+//!   bubba_node: bubba-node-000
+//!   bubba_endpoint: tcps://splinterd-node-bubba:8044
+//! circuit:
+//!   management_type: gameroom
+//!   members:
+//!     - node_id: ${acme_node}
+//!       endpoints: [ "${acme_endpoint}" ]
+//!     - node_id: ${bubba_node}
+//!       endpoints: [ "${bubba_endpoint}" ]
+//!   services:
+//!     - service_id: a000
+//!       service_type: scabbard
+//!       allowed_nodes: [ "${acme_node}" ]
+//!       arguments:
+//!         admin_keys: ${admin_key}
+//!     - service_id: b000
+//!       service_type: scabbard
+//!       allowed_nodes: [ "${bubba_node}" ]
+//!       arguments:
+//!         admin_keys: ${admin_key}
+//!   peer_groups:
+//!     - [ a000, b000 ]
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+
+use serde::Deserialize;
+use splinter::admin::messages::is_valid_service_id;
+
+use crate::error::CliError;
+
+use super::builder::{parse_hex, CreateCircuitMessageBuilder};
+
+const SUPPORTED_VERSION: &str = "v1";
+
+/// A circuit definition loaded from a template file.
+pub(super) struct CircuitTemplateFile {
+    variables: HashMap<String, String>,
+    circuit: CircuitDefinition,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TemplateFileYaml {
+    version: String,
+    #[serde(default)]
+    variables: HashMap<String, String>,
+    circuit: CircuitDefinition,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CircuitDefinition {
+    management_type: Option<String>,
+    authorization_type: Option<String>,
+    display_name: Option<String>,
+    comments: Option<String>,
+    application_metadata: Option<String>,
+    members: Vec<MemberDefinition>,
+    services: Vec<ServiceDefinition>,
+    #[serde(default)]
+    peer_groups: Vec<Vec<String>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MemberDefinition {
+    node_id: String,
+    endpoints: Vec<String>,
+    public_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ServiceDefinition {
+    service_id: String,
+    service_type: Option<String>,
+    allowed_nodes: Vec<String>,
+    #[serde(default)]
+    arguments: BTreeMap<String, String>,
+}
+
+impl CircuitTemplateFile {
+    /// Loads a template file.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The file system path of the template file
+    pub fn load(path: &str) -> Result<Self, CliError> {
+        let yaml = fs::read_to_string(path).map_err(|err| {
+            CliError::EnvironmentError(format!("Unable to read template file '{}': {}", path, err))
+        })?;
+
+        Self::from_yaml(&yaml).map_err(|msg| {
+            CliError::ActionError(format!("Invalid template file '{}': {}", path, msg))
+        })
+    }
+
+    fn from_yaml(yaml: &str) -> Result<Self, String> {
+        let file: TemplateFileYaml = serde_yaml::from_str(yaml).map_err(|err| err.to_string())?;
+
+        if file.version != SUPPORTED_VERSION {
+            return Err(format!(
+                "unsupported version {}; expected {}",
+                file.version, SUPPORTED_VERSION
+            ));
+        }
+
+        Ok(CircuitTemplateFile {
+            variables: file.variables,
+            circuit: file.circuit,
+        })
+    }
+
+    /// Sets the values of variables, replacing any values given in the file.
+    pub fn set_variables(&mut self, variables: HashMap<String, String>) {
+        self.variables.extend(variables);
+    }
+
+    /// Substitutes the variables in the circuit definition, checks that the result is a valid
+    /// circuit and adds it to the builder.
+    pub fn apply_to_builder(
+        &self,
+        builder: &mut CreateCircuitMessageBuilder,
+    ) -> Result<(), CliError> {
+        let circuit = self
+            .resolve()
+            .and_then(|circuit| validate(&circuit).map(|_| circuit))
+            .map_err(|msg| CliError::ActionError(format!("Invalid template file: {}", msg)))?;
+
+        if let Some(authorization_type) = &circuit.authorization_type {
+            builder.set_authorization_type(authorization_type)?;
+        }
+
+        for member in &circuit.members {
+            builder.add_node(
+                &member.node_id,
+                &member.endpoints,
+                member.public_key.as_ref(),
+            )?;
+        }
+
+        for service in &circuit.services {
+            builder.add_service(&service.service_id, &service.allowed_nodes)?;
+            if let Some(service_type) = &service.service_type {
+                builder.apply_service_type(&service.service_id, service_type);
+            }
+            for (key, value) in &service.arguments {
+                builder
+                    .apply_service_arguments(&service.service_id, &(key.clone(), value.clone()))?;
+            }
+        }
+
+        for group in &circuit.peer_groups {
+            builder.apply_peer_services(&group.iter().map(String::as_str).collect::<Vec<_>>())?;
+        }
+
+        if let Some(management_type) = &circuit.management_type {
+            builder.set_management_type(management_type);
+        }
+        if let Some(display_name) = &circuit.display_name {
+            builder.set_display_name(display_name);
+        }
+        if let Some(comments) = &circuit.comments {
+            builder.set_comments(comments);
+        }
+        if let Some(application_metadata) = &circuit.application_metadata {
+            builder.set_application_metadata(application_metadata.as_bytes());
+        }
+
+        Ok(())
+    }
+
+    /// Returns the circuit definition with every variable replaced by its value.
+    fn resolve(&self) -> Result<CircuitDefinition, String> {
+        let sub = |value: &String| substitute(value, &self.variables);
+        let sub_opt = |value: &Option<String>| value.as_ref().map(sub).transpose();
+        let sub_all = |values: &[String]| values.iter().map(sub).collect::<Result<Vec<_>, _>>();
+
+        Ok(CircuitDefinition {
+            management_type: sub_opt(&self.circuit.management_type)?,
+            authorization_type: sub_opt(&self.circuit.authorization_type)?,
+            display_name: sub_opt(&self.circuit.display_name)?,
+            comments: sub_opt(&self.circuit.comments)?,
+            application_metadata: sub_opt(&self.circuit.application_metadata)?,
+            members: self
+                .circuit
+                .members
+                .iter()
+                .map(|member| -> Result<_, String> {
+                    Ok(MemberDefinition {
+                        node_id: sub(&member.node_id)?,
+                        endpoints: sub_all(&member.endpoints)?,
+                        public_key: sub_opt(&member.public_key)?,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            services: self
+                .circuit
+                .services
+                .iter()
+                .map(|service| -> Result<_, String> {
+                    Ok(ServiceDefinition {
+                        service_id: sub(&service.service_id)?,
+                        service_type: sub_opt(&service.service_type)?,
+                        allowed_nodes: sub_all(&service.allowed_nodes)?,
+                        arguments: service
+                            .arguments
+                            .iter()
+                            .map(|(key, value)| Ok((key.clone(), sub(value)?)))
+                            .collect::<Result<_, String>>()?,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            peer_groups: self
+                .circuit
+                .peer_groups
+                .iter()
+                .map(|group| sub_all(group))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// Replaces each `${name}` in the value with the value of the variable.
+fn substitute(value: &str, variables: &HashMap<String, String>) -> Result<String, String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unterminated variable in \"{}\"", value))?;
+        let name = after[..end].trim();
+        let replacement = variables
+            .get(name)
+            .ok_or_else(|| format!("variable {} is not defined", name))?;
+        result.push_str(replacement);
+        rest = &after[end + 1..];
+    }
+    result.push_str(rest);
+
+    Ok(result)
+}
+
+/// Checks the parts of a circuit definition that the admin service validates when the proposal is
+/// submitted, so that mistakes in the file are reported before anything is sent.
+fn validate(circuit: &CircuitDefinition) -> Result<(), String> {
+    if circuit.members.is_empty() {
+        return Err("the circuit must have members".into());
+    }
+
+    let challenge = circuit.authorization_type.as_deref() == Some("challenge");
+    let mut node_ids: Vec<&str> = vec![];
+    let mut endpoints: Vec<&str> = vec![];
+    for member in &circuit.members {
+        if member.node_id.is_empty() {
+            return Err("a member's node ID cannot be empty".into());
+        }
+        if node_ids.contains(&member.node_id.as_str()) {
+            return Err(format!(
+                "node {} is a member more than once",
+                member.node_id
+            ));
+        }
+        node_ids.push(&member.node_id);
+
+        if member.endpoints.is_empty() {
+            return Err(format!("node {} has no endpoints", member.node_id));
+        }
+        for endpoint in &member.endpoints {
+            if endpoint.is_empty() {
+                return Err(format!("node {} has an empty endpoint", member.node_id));
+            }
+            if endpoints.contains(&endpoint.as_str()) {
+                return Err(format!("endpoint {} is used more than once", endpoint));
+            }
+            endpoints.push(endpoint);
+        }
+
+        match &member.public_key {
+            Some(public_key) => {
+                parse_hex(public_key)
+                    .map_err(|_| format!("node {} has an invalid public key", member.node_id))?;
+            }
+            None if challenge => {
+                return Err(format!(
+                    "node {} needs a public key for challenge authorization",
+                    member.node_id
+                ))
+            }
+            None => (),
+        }
+    }
+
+    if circuit.services.is_empty() {
+        return Err("the circuit must have services".into());
+    }
+
+    let mut service_ids: Vec<&str> = vec![];
+    for service in &circuit.services {
+        if !is_valid_service_id(&service.service_id) {
+            return Err(format!(
+                "'{}' is not a valid service ID: must be a 4 character base62 string",
+                service.service_id
+            ));
+        }
+        if service_ids.contains(&service.service_id.as_str()) {
+            return Err(format!(
+                "service ID {} is used more than once",
+                service.service_id
+            ));
+        }
+        service_ids.push(&service.service_id);
+
+        match service.allowed_nodes.as_slice() {
+            [node_id] if node_ids.contains(&node_id.as_str()) => (),
+            [node_id] => {
+                return Err(format!(
+                    "service {} is allowed on node {}, which is not a member",
+                    service.service_id, node_id
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "service {} must have exactly one allowed node",
+                    service.service_id
+                ))
+            }
+        }
+
+        if service.service_type.as_deref() == Some("") {
+            return Err(format!(
+                "service {} has an empty service type",
+                service.service_id
+            ));
+        }
+    }
+
+    for group in &circuit.peer_groups {
+        if let Some(service_id) = group
+            .iter()
+            .find(|service_id| !service_ids.contains(&service_id.as_str()))
+        {
+            return Err(format!(
+                "peer group refers to service {}, which is not defined",
+                service_id
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses `--template-var` values of the form `<name>=<value>`.
+pub(super) fn parse_template_vars(vars: &[&str]) -> Result<HashMap<String, String>, CliError> {
+    vars.iter()
+        .map(|var| match var.split_once('=') {
+            Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+            _ => Err(CliError::ActionError(format!(
+                "Invalid template variable. Expected value in form <name>=<value> found {}",
+                var
+            ))),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "0384781d9f2ef1e7c0d6d5a1c2e4bd0b9b4bb3e2a9b7f0e1c6f9a4c1f3e2b1a0c9";
+
+    const TEMPLATE: &str = r#"
+version: v1
+variables:
+  acme_node: acme-node-000
+  acme_endpoint: tcps://acme:8044
+  bubba_node: bubba-node-000
+  bubba_endpoint: tcps://bubba:8044
+circuit:
+  management_type: gameroom
+  display_name: ${acme_node} and ${bubba_node}
+  members:
+    - node_id: ${acme_node}
+      endpoints: [ "${acme_endpoint}" ]
+    - node_id: ${bubba_node}
+      endpoints: [ "${bubba_endpoint}" ]
+  services:
+    - service_id: a000
+      service_type: scabbard
+      allowed_nodes: [ "${acme_node}" ]
+      arguments:
+        admin_keys: ${admin_key}
+    - service_id: b000
+      service_type: scabbard
+      allowed_nodes: [ "${bubba_node}" ]
+      arguments:
+        admin_keys: ${admin_key}
+  peer_groups:
+    - [ a000, b000 ]
+"#;
+
+    /// Verify that variables are substituted, and that unknown or unterminated variables are
+    /// rejected.
+    #[test]
+    fn test_substitute() {
+        let variables = vec![("node".to_string(), "acme-node-000".to_string())]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            substitute("${node}", &variables).expect("Failed to substitute"),
+            "acme-node-000"
+        );
+        assert_eq!(
+            substitute("tcps://${ node }:8044", &variables).expect("Failed to substitute"),
+            "tcps://acme-node-000:8044"
+        );
+        assert_eq!(
+            substitute("no variables", &variables).expect("Failed to substitute"),
+            "no variables"
+        );
+        assert!(substitute("${endpoint}", &variables).is_err());
+        assert!(substitute("${node", &variables).is_err());
+    }
+
+    /// Verify that a template file is applied to the builder with its variables substituted, and
+    /// that variables given on the command line replace those in the file.
+    #[test]
+    fn test_apply_template_file() {
+        let mut template = CircuitTemplateFile::from_yaml(TEMPLATE).expect("Failed to parse");
+
+        let mut builder = CreateCircuitMessageBuilder::new();
+        assert!(template.apply_to_builder(&mut builder).is_err());
+
+        template.set_variables(
+            parse_template_vars(&[
+                &format!("admin_key={}", KEY),
+                "bubba_endpoint=tcps://bubba-2:8044",
+            ])
+            .expect("Failed to parse variables"),
+        );
+
+        let mut builder = CreateCircuitMessageBuilder::new();
+        template
+            .apply_to_builder(&mut builder)
+            .expect("Failed to apply template");
+        let circuit = builder.build().expect("Failed to build circuit");
+
+        assert_eq!(circuit.circuit_management_type, "gameroom");
+        assert_eq!(
+            circuit.display_name.as_deref(),
+            Some("acme-node-000 and bubba-node-000")
+        );
+        assert_eq!(circuit.members[1].node_id, "bubba-node-000");
+        assert_eq!(circuit.members[1].endpoints, vec!["tcps://bubba-2:8044"]);
+
+        let service = &circuit.roster[1];
+        assert_eq!(service.service_id, "b000");
+        assert_eq!(service.service_type, "scabbard");
+        assert_eq!(service.allowed_nodes, vec!["bubba-node-000".to_string()]);
+        assert!(service
+            .arguments
+            .contains(&("admin_keys".to_string(), KEY.to_string())));
+        assert!(service
+            .arguments
+            .contains(&("peer_services".to_string(), "[\"a000\"]".to_string())));
+    }
+
+    /// Verify that template files that the admin service would reject are reported before they
+    /// are applied.
+    #[test]
+    fn test_invalid_template_files() {
+        let variables = format!("admin_key={}", KEY);
+        let invalid = |from: &str, to: &str| {
+            let mut template = CircuitTemplateFile::from_yaml(&TEMPLATE.replacen(from, to, 1))
+                .expect("Failed to parse");
+            template.set_variables(parse_template_vars(&[&variables]).expect("Invalid variable"));
+            template
+                .apply_to_builder(&mut CreateCircuitMessageBuilder::new())
+                .is_err()
+        };
+
+        // An allowed node that is not a member
+        assert!(invalid(
+            "allowed_nodes: [ \"${bubba_node}\" ]",
+            "allowed_nodes: [ cargill ]"
+        ));
+        // An invalid service ID
+        assert!(invalid("service_id: b000", "service_id: b0000"));
+        // A duplicate service ID
+        assert!(invalid("service_id: b000", "service_id: a000"));
+        // A duplicate endpoint
+        assert!(invalid("${bubba_endpoint}", "${acme_endpoint}"));
+        // A peer group with an undefined service
+        assert!(invalid("[ a000, b000 ]", "[ a000, c000 ]"));
+        // Challenge authorization without public keys
+        assert!(invalid(
+            "management_type: gameroom",
+            "management_type: gameroom\n  authorization_type: challenge"
+        ));
+
+        assert!(CircuitTemplateFile::from_yaml(&TEMPLATE.replace("v1", "v2")).is_err());
+        assert!(CircuitTemplateFile::from_yaml(
+            &TEMPLATE.replace("management_type", "managment_type")
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,890 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the export and import actions, which copy the contents of a splinter database to and
+//! from a portable archive.
+//!
+//! An archive is a directory containing:
+//!
+//! * `manifest.json` - the archive version, the node ID and the exported scabbard services
+//! * `circuits.yaml` and `circuit_proposals.yaml` - the admin service state, in the format of the
+//!   YAML admin service store
+//! * `registry.yaml` - the registry nodes, in the format of a local YAML registry
+//! * `scabbard/<circuit ID>/<service ID>.json` - the state entries at the current commit hash,
+//!   the commit hash and the transaction receipts of each of the node's scabbard services
+//!
+//! Biome and role-based access control data, such as roles and their assignments, are not
+//! exported; they must be recreated on the node the archive is imported into.
+//!
+//! The [`CircuitArchiver`] writes the scabbard services of a single circuit to one JSON file, so
+//! that their state and receipts are kept after the circuit is disbanded and purged.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+use scabbard::store::transact::factory::LmdbDatabaseFactory;
+use serde::{Deserialize, Serialize};
+use splinter::admin::store::yaml::YamlAdminServiceStore;
+use splinter::admin::store::{
+    AdminServiceStore, CircuitNodeBuilder, CircuitPredicate, CircuitStatus,
+};
+use splinter::registry::{LocalYamlRegistry, RegistryReader, RegistryWriter};
+use transact::protocol::receipt::TransactionReceipt;
+use transact::protos::{FromBytes, IntoBytes};
+use transact::state::{Reader, StateChange};
+
+use crate::action::database::{
+    state::{get_state_dir, write_and_prune_with_cleanup, StateTreeStore},
+    stores::{new_upgrade_stores, UpgradeStores, UpgradeStoresWithLmdb},
+    ConnectionUri,
+};
+use crate::error::CliError;
+
+#[cfg(feature = "postgres")]
+use super::postgres;
+#[cfg(feature = "sqlite")]
+use super::sqlite;
+use super::{get_default_database, Action};
+
+const ARCHIVE_VERSION: u32 = 1;
+const MANIFEST_FILE: &str = "manifest.json";
+const CIRCUITS_FILE: &str = "circuits.yaml";
+const PROPOSALS_FILE: &str = "circuit_proposals.yaml";
+const REGISTRY_FILE: &str = "registry.yaml";
+const SCABBARD_DIR: &str = "scabbard";
+const STATE_CHANGES_PER_COMMIT: usize = 1000;
+/// Appended to the name of the database to name the copy an archive is imported into
+const STAGING_DATABASE_SUFFIX: &str = "_import";
+
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    node_id: Option<String>,
+    scabbard_services: Vec<ScabbardServiceEntry>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ScabbardServiceEntry {
+    circuit_id: String,
+    service_id: String,
+}
+
+/// The scabbard services of one circuit, written by the [`CircuitArchiver`]
+#[derive(Serialize, Deserialize)]
+struct CircuitArchive {
+    version: u32,
+    circuit_id: String,
+    node_id: String,
+    /// Seconds since the UNIX epoch
+    archived_at: u64,
+    scabbard_services: Vec<CircuitServiceArchive>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CircuitServiceArchive {
+    service_id: String,
+    #[serde(flatten)]
+    archive: ScabbardServiceArchive,
+}
+
+/// The contents of a scabbard service's stores; values and receipts are hex-encoded.
+#[derive(Serialize, Deserialize)]
+struct ScabbardServiceArchive {
+    commit_hash: String,
+    state: Vec<(String, String)>,
+    receipts: Vec<String>,
+}
+
+pub struct ExportAction;
+
+impl Action for ExportAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let database_uri = get_database_uri(args)?;
+        let archive_dir = PathBuf::from(
+            args.value_of("archive")
+                .ok_or_else(|| CliError::ActionError("'archive' argument is required".into()))?,
+        );
+
+        if archive_dir.exists()
+            && fs::read_dir(&archive_dir)
+                .map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Unable to read archive directory {}: {}",
+                        archive_dir.display(),
+                        err
+                    ))
+                })?
+                .next()
+                .is_some()
+        {
+            return Err(CliError::ActionError(format!(
+                "Archive directory {} is not empty",
+                archive_dir.display()
+            )));
+        }
+        fs::create_dir_all(archive_dir.join(SCABBARD_DIR))?;
+
+        info!(
+            "Exporting the contents of {} to {}",
+            database_uri,
+            archive_dir.display()
+        );
+
+        let upgrade_stores = new_upgrade_stores(&database_uri)
+            .map_err(|err| CliError::ActionError(format!("Unable to get stores: {}", err)))?;
+        let state_dir = get_state_dir(arg_matches)?;
+        let lmdb_stores = UpgradeStoresWithLmdb::new(
+            new_upgrade_stores(&database_uri)
+                .map_err(|err| CliError::ActionError(format!("Unable to get stores: {}", err)))?,
+            LmdbDatabaseFactory::new_state_db_factory(&state_dir, None),
+        );
+
+        let node_id = upgrade_stores
+            .new_node_id_store()
+            .get_node_id()
+            .map_err(|err| CliError::ActionError(format!("Unable to get node ID: {}", err)))?;
+
+        let admin_store = upgrade_stores.new_admin_service_store();
+        let yaml_store = new_yaml_admin_service_store(&archive_dir)?;
+        let (circuits, proposals) = copy_admin_service_state(&*admin_store, &yaml_store)?;
+        info!(
+            "Exported {} circuit(s) and {} proposal(s)",
+            circuits, proposals
+        );
+
+        let nodes = export_registry(upgrade_stores.as_upgrade_stores(), &archive_dir)?;
+        info!("Exported {} registry node(s)", nodes);
+
+        let mut scabbard_services = vec![];
+        if let Some(node_id) = node_id.as_deref() {
+            for (circuit_id, service_id) in list_local_scabbard_services(&*admin_store, node_id)? {
+                if export_scabbard_service(
+                    upgrade_stores.as_upgrade_stores(),
+                    &lmdb_stores,
+                    &circuit_id,
+                    &service_id,
+                    &archive_dir,
+                )? {
+                    scabbard_services.push(ScabbardServiceEntry {
+                        circuit_id,
+                        service_id,
+                    });
+                }
+            }
+        } else {
+            info!("Skipping scabbard state export, no local node ID found");
+        }
+        info!(
+            "Exported the state of {} scabbard service(s)",
+            scabbard_services.len()
+        );
+
+        let manifest = Manifest {
+            version: ARCHIVE_VERSION,
+            node_id,
+            scabbard_services,
+        };
+        write_json(&archive_dir.join(MANIFEST_FILE), &manifest)?;
+
+        info!("Export to {} complete", archive_dir.display());
+
+        Ok(())
+    }
+}
+
+pub struct ImportAction;
+
+impl Action for ImportAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let database_uri = get_database_uri(args)?;
+        let archive_dir = PathBuf::from(
+            args.value_of("archive")
+                .ok_or_else(|| CliError::ActionError("'archive' argument is required".into()))?,
+        );
+
+        let manifest: Manifest = read_json(&archive_dir.join(MANIFEST_FILE))?;
+        if manifest.version != ARCHIVE_VERSION {
+            return Err(CliError::ActionError(format!(
+                "Unsupported archive version {}, expected {}",
+                manifest.version, ARCHIVE_VERSION
+            )));
+        }
+
+        info!(
+            "Importing the contents of {} to {}",
+            archive_dir.display(),
+            database_uri
+        );
+
+        let force = args.is_present("force");
+        if !force {
+            let upgrade_stores = new_upgrade_stores(&database_uri)
+                .map_err(|err| CliError::ActionError(format!("Unable to get stores: {}", err)))?;
+            let existing_node_id = upgrade_stores
+                .new_node_id_store()
+                .get_node_id()
+                .map_err(|err| CliError::ActionError(format!("Unable to get node ID: {}", err)))?;
+            let existing_circuits = upgrade_stores
+                .new_admin_service_store()
+                .count_circuits(&all_circuit_statuses())
+                .map_err(|err| {
+                    CliError::ActionError(format!("Unable to count circuits: {}", err))
+                })?;
+            if existing_node_id.is_some() || existing_circuits > 0 {
+                return Err(CliError::ActionError(format!(
+                    "Database {} already contains splinter data; use --force to import anyway",
+                    database_uri
+                )));
+            }
+        }
+
+        // The stores do not share a transaction, so the archive is imported into a copy of the
+        // database that only replaces it once the whole archive has been imported
+        let staging_uri = copy_database(&database_uri)?;
+        if let Err(err) = import_archive(&staging_uri, &archive_dir, &manifest, force) {
+            if let Err(drop_err) = drop_database(&staging_uri) {
+                warn!(
+                    "Unable to remove the partially imported database {}: {}",
+                    staging_uri, drop_err
+                );
+            }
+            return Err(err);
+        }
+        replace_database(&database_uri, &staging_uri)?;
+
+        info!("Import from {} complete", archive_dir.display());
+
+        Ok(())
+    }
+}
+
+/// Imports the archive's node ID, admin service state, registry and scabbard state into the
+/// database.
+fn import_archive(
+    database_uri: &ConnectionUri,
+    archive_dir: &Path,
+    manifest: &Manifest,
+    force: bool,
+) -> Result<(), CliError> {
+    let upgrade_stores = new_upgrade_stores(database_uri)
+        .map_err(|err| CliError::ActionError(format!("Unable to get stores: {}", err)))?;
+
+    if let Some(node_id) = &manifest.node_id {
+        upgrade_stores
+            .new_node_id_store()
+            .set_node_id(node_id.clone())
+            .map_err(|err| CliError::ActionError(format!("Unable to set node ID: {}", err)))?;
+    }
+
+    let yaml_store = new_yaml_admin_service_store(archive_dir)?;
+    let (circuits, proposals) =
+        copy_admin_service_state(&yaml_store, &*upgrade_stores.new_admin_service_store())?;
+    info!(
+        "Imported {} circuit(s) and {} proposal(s)",
+        circuits, proposals
+    );
+
+    let nodes = import_registry(upgrade_stores.as_upgrade_stores(), archive_dir)?;
+    info!("Imported {} registry node(s)", nodes);
+
+    for entry in &manifest.scabbard_services {
+        import_scabbard_service(
+            upgrade_stores.as_upgrade_stores(),
+            &entry.circuit_id,
+            &entry.service_id,
+            archive_dir,
+            force,
+        )?;
+    }
+    info!(
+        "Imported the state of {} scabbard service(s)",
+        manifest.scabbard_services.len()
+    );
+
+    Ok(())
+}
+
+/// Writes the state and receipts of a circuit's local scabbard services to a file.
+///
+/// The archiver reads the node's database directly, so it must be run on the node itself.
+pub struct CircuitArchiver {
+    database_uri: ConnectionUri,
+    state_dir: PathBuf,
+    archive_file: PathBuf,
+}
+
+impl CircuitArchiver {
+    /// Returns an archiver if the `archive` argument is present, using the `connect` and
+    /// `state_dir` arguments to find the node's database and state directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the archive file already exists or the database URI is invalid.
+    pub fn from_args(args: &ArgMatches) -> Result<Option<Self>, CliError> {
+        let archive_file = match args.value_of("archive") {
+            Some(archive_file) => PathBuf::from(archive_file),
+            None => return Ok(None),
+        };
+        if archive_file.exists() {
+            return Err(CliError::ActionError(format!(
+                "Archive file {} already exists",
+                archive_file.display()
+            )));
+        }
+
+        Ok(Some(Self {
+            database_uri: get_database_uri(args)?,
+            state_dir: get_state_dir(Some(args))?,
+            archive_file,
+        }))
+    }
+
+    /// Writes the state, commit hash and receipts of each of the circuit's scabbard services
+    /// that run on the local node to the archive file.
+    pub fn archive(&self, circuit_id: &str) -> Result<(), CliError> {
+        let upgrade_stores = new_upgrade_stores(&self.database_uri)
+            .map_err(|err| CliError::ActionError(format!("Unable to get stores: {}", err)))?;
+        let lmdb_stores = UpgradeStoresWithLmdb::new(
+            new_upgrade_stores(&self.database_uri)
+                .map_err(|err| CliError::ActionError(format!("Unable to get stores: {}", err)))?,
+            LmdbDatabaseFactory::new_state_db_factory(&self.state_dir, None),
+        );
+
+        let node_id = upgrade_stores
+            .new_node_id_store()
+            .get_node_id()
+            .map_err(|err| CliError::ActionError(format!("Unable to get node ID: {}", err)))?
+            .ok_or_else(|| {
+                CliError::ActionError(format!(
+                    "Unable to archive circuit {}: no local node ID found in {}",
+                    circuit_id, self.database_uri
+                ))
+            })?;
+
+        let circuit = upgrade_stores
+            .new_admin_service_store()
+            .get_circuit(circuit_id)
+            .map_err(|err| CliError::ActionError(format!("Unable to get circuit: {}", err)))?
+            .ok_or_else(|| {
+                CliError::ActionError(format!(
+                    "Unable to archive circuit {}: circuit not found in {}",
+                    circuit_id, self.database_uri
+                ))
+            })?;
+
+        let mut scabbard_services = vec![];
+        for service in circuit
+            .roster()
+            .iter()
+            .filter(|svc| svc.node_id() == node_id && svc.service_type() == "scabbard")
+        {
+            if let Some(archive) = read_scabbard_service(
+                upgrade_stores.as_upgrade_stores(),
+                &lmdb_stores,
+                circuit_id,
+                service.service_id(),
+            )? {
+                scabbard_services.push(CircuitServiceArchive {
+                    service_id: service.service_id().to_string(),
+                    archive,
+                });
+            }
+        }
+
+        let service_count = scabbard_services.len();
+        write_json(
+            &self.archive_file,
+            &CircuitArchive {
+                version: ARCHIVE_VERSION,
+                circuit_id: circuit_id.to_string(),
+                node_id,
+                archived_at: SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0),
+                scabbard_services,
+            },
+        )?;
+
+        info!(
+            "Archived the state of {} scabbard service(s) of circuit {} to {}",
+            service_count,
+            circuit_id,
+            self.archive_file.display()
+        );
+
+        Ok(())
+    }
+}
+
+fn get_database_uri(args: &ArgMatches) -> Result<ConnectionUri, CliError> {
+    let url = match args.value_of("connect") {
+        Some(url) => url.to_owned(),
+        None => get_default_database()?,
+    };
+    ConnectionUri::from_str(&url)
+}
+
+/// Copies the database, returning the URI of the copy.
+fn copy_database(database_uri: &ConnectionUri) -> Result<ConnectionUri, CliError> {
+    match database_uri {
+        #[cfg(feature = "postgres")]
+        ConnectionUri::Postgres(url) => {
+            postgres::copy_database(url, STAGING_DATABASE_SUFFIX).map(ConnectionUri::Postgres)
+        }
+        #[cfg(feature = "sqlite")]
+        ConnectionUri::Sqlite(connection_string) => {
+            sqlite::copy_database(connection_string, STAGING_DATABASE_SUFFIX)
+                .map(ConnectionUri::Sqlite)
+        }
+    }
+}
+
+/// Replaces the database with its copy.
+fn replace_database(
+    database_uri: &ConnectionUri,
+    copy_uri: &ConnectionUri,
+) -> Result<(), CliError> {
+    match (database_uri, copy_uri) {
+        #[cfg(feature = "postgres")]
+        (ConnectionUri::Postgres(url), ConnectionUri::Postgres(copy_url)) => {
+            postgres::replace_database(url, copy_url)
+        }
+        #[cfg(feature = "sqlite")]
+        (ConnectionUri::Sqlite(connection_string), ConnectionUri::Sqlite(copy)) => {
+            sqlite::replace_database(connection_string, copy)
+        }
+        #[cfg(all(feature = "postgres", feature = "sqlite"))]
+        _ => Err(CliError::ActionError(format!(
+            "Unable to replace database {} with {} of a different type",
+            database_uri, copy_uri
+        ))),
+    }
+}
+
+fn drop_database(database_uri: &ConnectionUri) -> Result<(), CliError> {
+    match database_uri {
+        #[cfg(feature = "postgres")]
+        ConnectionUri::Postgres(url) => postgres::drop_database(url),
+        #[cfg(feature = "sqlite")]
+        ConnectionUri::Sqlite(connection_string) => sqlite::drop_database(connection_string),
+    }
+}
+
+fn all_circuit_statuses() -> Vec<CircuitPredicate> {
+    vec![
+        CircuitPredicate::CircuitStatus(CircuitStatus::Active),
+        CircuitPredicate::CircuitStatus(CircuitStatus::Disbanded),
+        CircuitPredicate::CircuitStatus(CircuitStatus::Abandoned),
+    ]
+}
+
+fn new_yaml_admin_service_store(archive_dir: &Path) -> Result<YamlAdminServiceStore, CliError> {
+    YamlAdminServiceStore::new(
+        path_to_string(&archive_dir.join(CIRCUITS_FILE))?,
+        path_to_string(&archive_dir.join(PROPOSALS_FILE))?,
+    )
+    .map_err(|err| {
+        CliError::ActionError(format!(
+            "Unable to open the archive's admin service state: {}",
+            err
+        ))
+    })
+}
+
+/// Copies every circuit and proposal from one admin service store to another, returning the
+/// number of each that were copied.
+///
+/// The members of the circuits are rebuilt from the source store's nodes, because the YAML store
+/// does not keep the endpoints of circuit members.
+fn copy_admin_service_state(
+    from: &dyn AdminServiceStore,
+    to: &dyn AdminServiceStore,
+) -> Result<(usize, usize), CliError> {
+    let nodes = from
+        .list_nodes()
+        .map_err(|err| CliError::ActionError(format!("Unable to list nodes: {}", err)))?
+        .map(|node| (node.node_id().to_string(), node))
+        .collect::<HashMap<_, _>>();
+
+    let circuits = from
+        .list_circuits(&all_circuit_statuses())
+        .map_err(|err| CliError::ActionError(format!("Unable to list circuits: {}", err)))?;
+
+    let mut circuit_count = 0;
+    for circuit in circuits {
+        let members = circuit
+            .members()
+            .iter()
+            .map(|member| match nodes.get(member.node_id()) {
+                Some(node) => Ok(node.clone()),
+                None => CircuitNodeBuilder::new()
+                    .with_node_id(member.node_id())
+                    .with_endpoints(member.endpoints())
+                    .build()
+                    .map_err(|err| CliError::ActionError(err.to_string())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let circuit_id = circuit.circuit_id().to_string();
+        to.add_circuit(circuit, members).map_err(|err| {
+            CliError::ActionError(format!("Unable to add circuit {}: {}", circuit_id, err))
+        })?;
+        circuit_count += 1;
+    }
+
+    let proposals = from
+        .list_proposals(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to list proposals: {}", err)))?;
+
+    let mut proposal_count = 0;
+    for proposal in proposals {
+        let circuit_id = proposal.circuit_id().to_string();
+        to.add_proposal(proposal).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to add proposal for circuit {}: {}",
+                circuit_id, err
+            ))
+        })?;
+        proposal_count += 1;
+    }
+
+    Ok((circuit_count, proposal_count))
+}
+
+fn export_registry(stores: &dyn UpgradeStores, archive_dir: &Path) -> Result<usize, CliError> {
+    let yaml_registry = LocalYamlRegistry::new(&path_to_string(&archive_dir.join(REGISTRY_FILE))?)
+        .map_err(|err| CliError::ActionError(format!("Unable to create registry file: {}", err)))?;
+
+    let registry = stores.new_registry();
+    let nodes = registry
+        .list_nodes(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to list registry nodes: {}", err)))?;
+
+    let mut count = 0;
+    for node in nodes {
+        yaml_registry.add_node(node).map_err(|err| {
+            CliError::ActionError(format!("Unable to write registry node: {}", err))
+        })?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+fn import_registry(stores: &dyn UpgradeStores, archive_dir: &Path) -> Result<usize, CliError> {
+    let registry_file = archive_dir.join(REGISTRY_FILE);
+    if !registry_file.exists() {
+        warn!("Skipping registry import, no registry file found");
+        return Ok(0);
+    }
+
+    let yaml_registry = LocalYamlRegistry::new(&path_to_string(&registry_file)?)
+        .map_err(|err| CliError::ActionError(format!("Unable to read registry file: {}", err)))?;
+    let nodes = yaml_registry
+        .list_nodes(&[])
+        .map_err(|err| CliError::ActionError(format!("Unable to list registry nodes: {}", err)))?;
+
+    let registry = stores.new_registry();
+    let mut count = 0;
+    for node in nodes {
+        registry.add_node(node).map_err(|err| {
+            CliError::ActionError(format!("Unable to add registry node: {}", err))
+        })?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Lists the circuit and service IDs of the scabbard services that run on the given node.
+fn list_local_scabbard_services(
+    admin_store: &dyn AdminServiceStore,
+    node_id: &str,
+) -> Result<Vec<(String, String)>, CliError> {
+    Ok(admin_store
+        .list_circuits(&all_circuit_statuses())
+        .map_err(|err| CliError::ActionError(format!("Unable to list circuits: {}", err)))?
+        .flat_map(|circuit| {
+            circuit
+                .roster()
+                .iter()
+                .filter(|svc| svc.node_id() == node_id && svc.service_type() == "scabbard")
+                .map(|svc| {
+                    (
+                        circuit.circuit_id().to_string(),
+                        svc.service_id().to_string(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect())
+}
+
+/// Writes a scabbard service's state, commit hash and receipts to the archive. Returns `false`
+/// if the service has no state or commit hash, in which case nothing is written.
+fn export_scabbard_service(
+    stores: &dyn UpgradeStores,
+    lmdb_stores: &UpgradeStoresWithLmdb,
+    circuit_id: &str,
+    service_id: &str,
+    archive_dir: &Path,
+) -> Result<bool, CliError> {
+    let archive = match read_scabbard_service(stores, lmdb_stores, circuit_id, service_id)? {
+        Some(archive) => archive,
+        None => return Ok(false),
+    };
+
+    let service_dir = archive_dir.join(SCABBARD_DIR).join(circuit_id);
+    fs::create_dir_all(&service_dir)?;
+    write_json(&service_dir.join(format!("{}.json", service_id)), &archive)?;
+
+    Ok(true)
+}
+
+/// Reads a scabbard service's state, commit hash and receipts. The state is read from the
+/// database if the service's merkle tree is stored there, otherwise from its LMDB file. Returns
+/// `None` if the service has no state or commit hash.
+fn read_scabbard_service(
+    stores: &dyn UpgradeStores,
+    lmdb_stores: &UpgradeStoresWithLmdb,
+    circuit_id: &str,
+    service_id: &str,
+) -> Result<Option<ScabbardServiceArchive>, CliError> {
+    let state_stores: &dyn UpgradeStores = if stores
+        .new_state_tree_store()
+        .has_tree(circuit_id, service_id)
+        .map_err(|err| CliError::ActionError(err.to_string()))?
+    {
+        stores
+    } else if lmdb_stores
+        .new_state_tree_store()
+        .has_tree(circuit_id, service_id)
+        .map_err(|err| CliError::ActionError(err.to_string()))?
+    {
+        lmdb_stores
+    } else {
+        warn!(
+            "Skipping scabbard service {}::{}, no state found",
+            circuit_id, service_id
+        );
+        return Ok(None);
+    };
+
+    let commit_hash = match stores
+        .new_commit_hash_store(circuit_id, service_id)
+        .get_current_commit_hash()
+        .map_err(|err| CliError::ActionError(err.to_string()))?
+    {
+        Some(commit_hash) => commit_hash,
+        None => {
+            warn!(
+                "Skipping scabbard service {}::{}, no commit hash found",
+                circuit_id, service_id
+            );
+            return Ok(None);
+        }
+    };
+
+    info!("Reading state for {}::{}", circuit_id, service_id);
+
+    let merkle_state = state_stores
+        .get_merkle_state(circuit_id, service_id, false)
+        .map_err(|err| CliError::ActionError(err.to_string()))?;
+    let state = merkle_state
+        .filter_iter(&commit_hash, None)
+        .map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to read state of {}::{}: {}",
+                circuit_id, service_id, err
+            ))
+        })?
+        .map(|entry| {
+            entry
+                .map(|(address, value)| (address, to_hex(&value)))
+                .map_err(|err| CliError::ActionError(err.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let receipts = stores
+        .new_receipt_store(circuit_id, service_id)
+        .list_receipts_since(None)
+        .map_err(|err| CliError::ActionError(err.to_string()))?
+        .map(|receipt| {
+            receipt
+                .map_err(|err| CliError::ActionError(err.to_string()))?
+                .into_bytes()
+                .map(|bytes| to_hex(&bytes))
+                .map_err(|err| CliError::ActionError(err.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Some(ScabbardServiceArchive {
+        commit_hash,
+        state,
+        receipts,
+    }))
+}
+
+/// Writes a scabbard service's state, commit hash and receipts from the archive to the database.
+/// The resulting state root must match the archived commit hash.
+fn import_scabbard_service(
+    stores: &dyn UpgradeStores,
+    circuit_id: &str,
+    service_id: &str,
+    archive_dir: &Path,
+    force: bool,
+) -> Result<(), CliError> {
+    info!("Importing state for {}::{}", circuit_id, service_id);
+
+    let archive: ScabbardServiceArchive = read_json(
+        &archive_dir
+            .join(SCABBARD_DIR)
+            .join(circuit_id)
+            .join(format!("{}.json", service_id)),
+    )?;
+
+    if !force
+        && stores
+            .new_state_tree_store()
+            .has_tree(circuit_id, service_id)
+            .map_err(|err| CliError::ActionError(err.to_string()))?
+    {
+        return Err(CliError::ActionError(format!(
+            "Merkle tree for {}::{} already exists",
+            circuit_id, service_id
+        )));
+    }
+
+    let merkle_state = stores
+        .get_merkle_state(circuit_id, service_id, true)
+        .map_err(|err| CliError::ActionError(err.to_string()))?;
+    let mut state_root = merkle_state
+        .get_state_root()
+        .map_err(|err| CliError::ActionError(err.to_string()))?;
+
+    for chunk in archive.state.chunks(STATE_CHANGES_PER_COMMIT) {
+        let state_changes = chunk
+            .iter()
+            .map(|(address, value)| {
+                Ok(StateChange::Set {
+                    key: address.clone(),
+                    value: parse_hex(value)?,
+                })
+            })
+            .collect::<Result<Vec<_>, CliError>>()?;
+        state_root = write_and_prune_with_cleanup(&merkle_state, &state_root, &state_changes)
+            .map_err(|err| CliError::ActionError(err.to_string()))?;
+    }
+
+    if state_root != archive.commit_hash {
+        return Err(CliError::ActionError(format!(
+            "Imported state root of {}::{} did not match the archived commit hash {} != {}",
+            circuit_id, service_id, state_root, archive.commit_hash
+        )));
+    }
+
+    stores
+        .new_commit_hash_store(circuit_id, service_id)
+        .set_current_commit_hash(&archive.commit_hash)
+        .map_err(|err| CliError::ActionError(err.to_string()))?;
+
+    let receipts = archive
+        .receipts
+        .iter()
+        .map(|receipt| {
+            TransactionReceipt::from_bytes(&parse_hex(receipt)?)
+                .map_err(|err| CliError::ActionError(err.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if !receipts.is_empty() {
+        stores
+            .new_receipt_store(circuit_id, service_id)
+            .add_txn_receipts(receipts)
+            .map_err(|err| CliError::ActionError(err.to_string()))?;
+    }
+
+    Ok(())
+}
+
+fn path_to_string(path: &Path) -> Result<String, CliError> {
+    path.to_str().map(String::from).ok_or_else(|| {
+        CliError::ActionError(format!("'{}' is not a valid UTF-8 path", path.display()))
+    })
+}
+
+fn write_json<T: Serialize>(path: &Path, value: &T) -> Result<(), CliError> {
+    let file = fs::File::create(path)?;
+    serde_json::to_writer(file, value).map_err(|err| {
+        CliError::ActionError(format!("Unable to write {}: {}", path.display(), err))
+    })
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, CliError> {
+    let file = fs::File::open(path).map_err(|err| {
+        CliError::ActionError(format!("Unable to open {}: {}", path.display(), err))
+    })?;
+    serde_json::from_reader(file)
+        .map_err(|err| CliError::ActionError(format!("Unable to read {}: {}", path.display(), err)))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let mut buf = String::new();
+    for b in bytes {
+        write!(&mut buf, "{:02x}", b).expect("Unable to write to string");
+    }
+
+    buf
+}
+
+fn parse_hex(hex: &str) -> Result<Vec<u8>, CliError> {
+    if hex.len() % 2 != 0 {
+        return Err(CliError::ActionError(format!(
+            "Invalid hex string in archive: {}",
+            hex
+        )));
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| {
+                CliError::ActionError(format!("Invalid hex string in archive: {}", hex))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that bytes survive a round trip through the archive's hex encoding, and that
+    /// malformed hex is rejected.
+    #[test]
+    fn test_hex_round_trip() {
+        let bytes = vec![0x00, 0x0a, 0xff, 0x42];
+        assert_eq!(to_hex(&bytes), "000aff42");
+        assert_eq!(parse_hex("000aff42").expect("failed to parse hex"), bytes);
+        assert!(parse_hex("abc").is_err());
+        assert!(parse_hex("zz").is_err());
+    }
+}
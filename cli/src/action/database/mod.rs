@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "database-archive")]
+mod archive;
 #[cfg(feature = "postgres")]
 mod postgres;
 #[cfg(feature = "sqlite")]
@@ -28,6 +30,8 @@ use std::{env, fs};
 
 use clap::ArgMatches;
 
+#[cfg(feature = "database-archive")]
+pub use self::archive::{CircuitArchiver, ExportAction, ImportAction};
 #[cfg(not(feature = "sqlite"))]
 use self::postgres::get_default_database;
 #[cfg(feature = "sqlite")]
@@ -67,6 +71,27 @@ impl Action for MigrateAction {
     }
 }
 
+/// Returns the versions of the migrations that have been applied to the given database, oldest
+/// first. If no database is given, the default database is used; it is not created if it does not
+/// exist.
+#[cfg(feature = "support-bundle")]
+pub fn schema_versions(url: Option<&str>) -> Result<Vec<String>, CliError> {
+    let url = match url {
+        Some(url) => url.to_owned(),
+        #[cfg(feature = "sqlite")]
+        None => sqlite::get_database_at_state_path(SplinterEnvironment::load().get_state_path())?,
+        #[cfg(not(feature = "sqlite"))]
+        None => get_default_database()?,
+    };
+
+    match ConnectionUri::from_str(&url)? {
+        #[cfg(feature = "postgres")]
+        ConnectionUri::Postgres(url) => postgres::schema_versions(&url),
+        #[cfg(feature = "sqlite")]
+        ConnectionUri::Sqlite(connection_string) => sqlite::schema_versions(&connection_string),
+    }
+}
+
 /// The possible connection types and identifiers passed to the migrate command
 pub enum ConnectionUri {
     #[cfg(feature = "postgres")]
@@ -19,6 +19,8 @@ use std::fs::{self, OpenOptions};
 use std::os::unix::prelude::OpenOptionsExt;
 use std::path::{Path, PathBuf};
 
+#[cfg(feature = "support-bundle")]
+use diesel::{dsl::sql, sql_types::Text, Connection, RunQueryDsl};
 use diesel::{
     r2d2::{ConnectionManager, Pool},
     sqlite::SqliteConnection,
@@ -110,6 +112,81 @@ pub fn sqlite_migrations(connection_string: String) -> Result<(), CliError> {
     Ok(())
 }
 
+/// Returns the versions of the migrations that have been applied to the SQLite database
+///
+/// Unlike running migrations, this does not create the database if it does not exist.
+#[cfg(feature = "support-bundle")]
+pub fn schema_versions(connection_string: &str) -> Result<Vec<String>, CliError> {
+    if connection_string == MEMORY || !Path::new(connection_string).exists() {
+        return Err(CliError::ActionError(format!(
+            "SQLite database does not exist: {}",
+            connection_string
+        )));
+    }
+
+    let connection = SqliteConnection::establish(connection_string).map_err(|err| {
+        CliError::ActionError(format!(
+            "Failed to establish database connection to '{}': {}",
+            connection_string, err
+        ))
+    })?;
+
+    diesel::select(sql::<Text>(
+        "version FROM __diesel_schema_migrations ORDER BY version",
+    ))
+    .load(&connection)
+    .map_err(|err| CliError::ActionError(format!("Unable to read schema versions: {}", err)))
+}
+
+/// Copies the SQLite database file to a new file, named by appending the suffix to the
+/// database's path, and returns the path of the copy.
+#[cfg(feature = "database-archive")]
+pub fn copy_database(connection_string: &str, suffix: &str) -> Result<String, CliError> {
+    if connection_string == MEMORY {
+        return Err(CliError::ActionError(
+            "Unable to copy an in-memory SQLite database".to_string(),
+        ));
+    }
+
+    let copy = format!("{}{}", connection_string, suffix);
+    if Path::new(&copy).exists() {
+        return Err(CliError::ActionError(format!(
+            "Unable to copy SQLite database {}: {} already exists",
+            connection_string, copy
+        )));
+    }
+    fs::copy(connection_string, &copy).map_err(|err| {
+        CliError::ActionError(format!(
+            "Unable to copy SQLite database {} to {}: {}",
+            connection_string, copy, err
+        ))
+    })?;
+
+    Ok(copy)
+}
+
+/// Replaces the SQLite database file with the copy.
+#[cfg(feature = "database-archive")]
+pub fn replace_database(connection_string: &str, copy: &str) -> Result<(), CliError> {
+    fs::rename(copy, connection_string).map_err(|err| {
+        CliError::ActionError(format!(
+            "Unable to replace SQLite database {} with {}: {}",
+            connection_string, copy, err
+        ))
+    })
+}
+
+/// Removes the SQLite database file.
+#[cfg(feature = "database-archive")]
+pub fn drop_database(connection_string: &str) -> Result<(), CliError> {
+    fs::remove_file(connection_string).map_err(|err| {
+        CliError::ActionError(format!(
+            "Unable to remove SQLite database {}: {}",
+            connection_string, err
+        ))
+    })
+}
+
 /// Creates and returns the path to the default sqlite database
 ///
 /// Gets the splinter default state path, creating it if it does not exist. Creates a db file with
@@ -297,7 +297,7 @@ impl Action for StateMigrateAction {
 ///
 /// * PathBuf to state_dir if present in arg_matches, otherwise just the default from
 /// SplinterEnvironment
-fn get_state_dir(arg_matches: Option<&ArgMatches>) -> Result<PathBuf, CliError> {
+pub(super) fn get_state_dir(arg_matches: Option<&ArgMatches>) -> Result<PathBuf, CliError> {
     if let Some(arg_matches) = arg_matches {
         match arg_matches.value_of("state_dir") {
             Some(state_dir) => {
@@ -378,7 +378,7 @@ fn copy_state(
     Ok(())
 }
 
-fn write_and_prune_with_cleanup(
+pub(super) fn write_and_prune_with_cleanup(
     merkle_state: &MerkleState,
     state_id: &str,
     state_changes: &[StateChange],
@@ -24,6 +24,7 @@ use splinter::{
     admin::store::{diesel::DieselAdminServiceStore, AdminServiceStore},
     error::InternalError,
     node_id::store::{diesel::DieselNodeIdStore, NodeIdStore},
+    registry::{DieselRegistry, RwRegistry},
 };
 use transact::state::merkle::sql::{backend, SqlMerkleStateBuilder};
 
@@ -37,6 +38,8 @@ pub trait UpgradeStores {
 
     fn new_node_id_store<'a>(&'a self) -> Box<dyn NodeIdStore + 'a>;
 
+    fn new_registry<'a>(&'a self) -> Box<dyn RwRegistry + 'a>;
+
     fn new_commit_hash_store<'a>(
         &'a self,
         circuit_id: &str,
@@ -132,6 +135,10 @@ impl UpgradeStores for PostgresUpgradeStores {
         Box::new(DieselNodeIdStore::new(self.0.clone()))
     }
 
+    fn new_registry(&self) -> Box<dyn RwRegistry> {
+        Box::new(DieselRegistry::new(self.0.clone()))
+    }
+
     fn new_commit_hash_store(
         &self,
         circuit_id: &str,
@@ -209,6 +216,10 @@ impl<'a> UpgradeStores for InTransactionPostgresUpgradeStores<'a> {
         unimplemented!("NodeIdStore does not yet in-transaction behaviour")
     }
 
+    fn new_registry(&self) -> Box<dyn RwRegistry> {
+        unimplemented!("RwRegistry does not yet in-transaction behaviour")
+    }
+
     fn new_commit_hash_store<'b>(
         &'b self,
         circuit_id: &str,
@@ -262,6 +273,10 @@ impl UpgradeStores for SqliteUpgradeStores {
         Box::new(DieselNodeIdStore::new(self.0.clone()))
     }
 
+    fn new_registry(&self) -> Box<dyn RwRegistry> {
+        Box::new(DieselRegistry::new(self.0.clone()))
+    }
+
     fn new_commit_hash_store(
         &self,
         circuit_id: &str,
@@ -338,6 +353,10 @@ impl<'a> UpgradeStores for InTransactionSqliteUpgradeStores<'a> {
         unimplemented!("NodeIdStore does not yet in-transaction behaviour")
     }
 
+    fn new_registry(&self) -> Box<dyn RwRegistry> {
+        unimplemented!("RwRegistry does not yet in-transaction behaviour")
+    }
+
     fn new_commit_hash_store<'b>(
         &'b self,
         circuit_id: &str,
@@ -404,6 +423,10 @@ impl UpgradeStores for UpgradeStoresWithLmdb {
         self.upgrade_stores.new_node_id_store()
     }
 
+    fn new_registry<'a>(&'a self) -> Box<dyn RwRegistry + 'a> {
+        self.upgrade_stores.new_registry()
+    }
+
     fn new_commit_hash_store<'a>(
         &'a self,
         circuit_id: &str,
@@ -470,6 +493,10 @@ impl<'u> UpgradeStores for InTransactionUpgradeStoresWithLmdb<'u> {
         self.upgrade_stores.new_node_id_store()
     }
 
+    fn new_registry<'a>(&'a self) -> Box<dyn RwRegistry + 'a> {
+        self.upgrade_stores.new_registry()
+    }
+
     fn new_commit_hash_store<'a>(
         &'a self,
         circuit_id: &str,
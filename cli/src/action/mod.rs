@@ -13,7 +13,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "announcements")]
+pub mod announcement;
 mod api;
+#[cfg(feature = "audit")]
+pub mod audit;
 pub mod certs;
 pub mod circuit;
 #[cfg(feature = "command")]
@@ -23,6 +27,13 @@ pub mod database;
 pub mod keygen;
 #[cfg(feature = "authorization-handler-maintenance")]
 pub mod maintenance;
+#[cfg(feature = "support-bundle")]
+pub mod node;
+#[cfg(feature = "node-config")]
+pub mod node_config;
+pub mod output;
+#[cfg(feature = "peer-blocklist")]
+pub mod peer;
 pub mod permissions;
 #[cfg(feature = "playlist-smallbank")]
 pub mod playlist;
@@ -31,7 +42,11 @@ pub mod rbac;
 pub mod registry;
 #[cfg(any(feature = "workload", feature = "playlist-smallbank"))]
 mod request_logger;
+#[cfg(any(feature = "scabbard-consensus-log", feature = "scabbard-contracts"))]
+pub mod scabbard;
 pub mod time;
+#[cfg(feature = "transfer")]
+pub mod transfer;
 #[cfg(feature = "user")]
 pub mod user;
 #[cfg(feature = "workload")]
@@ -0,0 +1,729 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the support bundle action, which collects what maintainers need to diagnose an issue
+//! with a Splinter node into a single gzipped tar archive.
+//!
+//! A bundle contains, under a `splinter-support-bundle-<timestamp>` directory:
+//!
+//! * `manifest.json` - when and by which CLI version the bundle was created, and each file that
+//!   was collected or could not be collected, with the reason
+//! * `config/splinterd.toml` - the node's configuration file
+//! * `logs/<file>` - the last lines of each file in the node's log directory
+//! * `diagnostics/<endpoint>.json` - the responses of the node's status, config, capabilities,
+//!   circuit, proposal, peer and usage REST API endpoints
+//! * `database/schema_versions.json` - the migrations that have been applied to the database
+//! * `summary.json` - the node's ID and version, and a summary of its circuits, proposals and
+//!   peers
+//!
+//! Secrets are redacted from every file: the values of configuration keys that name a secret,
+//! passwords in URLs and bearer tokens. The bundle is assembled in memory and written directly to
+//! the archive, so nothing else is left behind. Sections can be left out with `--exclude`; a
+//! section that cannot be collected, for example because the node is not running, is recorded in
+//! the manifest instead of failing the command.
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::env;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use clap::ArgMatches;
+use flate2::{write::GzEncoder, Compression};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{SplinterRestClient, SplinterRestClientBuilder},
+    msg_from_io_error, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+const CONFIG_DIR_ENV: &str = "SPLINTER_CONFIG_DIR";
+const SPLINTER_HOME_ENV: &str = "SPLINTER_HOME";
+const DEFAULT_CONFIG_DIR: &str = "/etc/splinter";
+const CONFIG_FILE: &str = "splinterd.toml";
+const DEFAULT_LOG_DIR: &str = "/var/log/splinter";
+const DEFAULT_LOG_LINES: usize = 1000;
+
+const MANIFEST_FILE: &str = "manifest.json";
+const SUMMARY_FILE: &str = "summary.json";
+const REDACTED: &str = "<redacted>";
+// Configuration keys containing any of these are considered secrets
+const SECRET_KEYWORDS: &[&str] = &[
+    "credential",
+    "passwd",
+    "password",
+    "private_key",
+    "secret",
+    "token",
+];
+
+// The admin protocol version supported by the current CLI
+const ADMIN_PROTOCOL_VERSION: &str = "2";
+
+/// The REST API endpoints collected into the diagnostics section, with the file each response is
+/// written to and the protocol version to request
+const DIAGNOSTIC_ENDPOINTS: &[(&str, &str, Option<&str>)] = &[
+    ("/status", "status.json", None),
+    ("/status/startup", "startup.json", None),
+    ("/config", "config.json", None),
+    ("/capabilities", "capabilities.json", None),
+    (
+        "/admin/circuits?limit=1000",
+        "circuits.json",
+        Some(ADMIN_PROTOCOL_VERSION),
+    ),
+    (
+        "/admin/proposals?limit=1000",
+        "proposals.json",
+        Some(ADMIN_PROTOCOL_VERSION),
+    ),
+    ("/peers/capabilities", "peer_capabilities.json", None),
+    ("/peers/metadata", "peer_metadata.json", None),
+    ("/peers/ref-counts", "peer_ref_counts.json", None),
+    ("/services/restarts", "service_restarts.json", None),
+    ("/usage/circuits", "circuit_usage.json", None),
+];
+
+/// The sections of a support bundle, which may be excluded
+const SECTION_CONFIG: &str = "config";
+const SECTION_LOGS: &str = "logs";
+const SECTION_DIAGNOSTICS: &str = "diagnostics";
+const SECTION_DATABASE: &str = "database";
+
+pub struct SupportBundleAction;
+
+impl Action for SupportBundleAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let excluded: BTreeSet<&str> = args
+            .values_of("exclude")
+            .map(|values| values.collect())
+            .unwrap_or_default();
+
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+        let bundle_name = format!("splinter-support-bundle-{}", created_at);
+        let output = args
+            .value_of("output")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(format!("{}.tar.gz", bundle_name)));
+        if output.exists() {
+            return Err(CliError::EnvironmentError(format!(
+                "Output file already exists: {}",
+                output.display()
+            )));
+        }
+
+        let mut bundle = SupportBundle::new(&bundle_name, created_at);
+
+        if !excluded.contains(SECTION_CONFIG) {
+            let config_file = args
+                .value_of("config")
+                .map(PathBuf::from)
+                .unwrap_or_else(default_config_file);
+            bundle.add_result(
+                "config/splinterd.toml",
+                fs::read_to_string(&config_file)
+                    .map(|config| redact_config(&config))
+                    .map_err(|err| {
+                        format!(
+                            "Unable to read {}: {}",
+                            config_file.display(),
+                            msg_from_io_error(err)
+                        )
+                    }),
+            );
+        }
+
+        if !excluded.contains(SECTION_LOGS) {
+            let log_dir = PathBuf::from(args.value_of("log_dir").unwrap_or(DEFAULT_LOG_DIR));
+            let log_lines = args
+                .value_of("log_lines")
+                .map(|lines| {
+                    lines.parse::<usize>().map_err(|_| {
+                        CliError::ActionError(format!("Invalid number of log lines: {}", lines))
+                    })
+                })
+                .transpose()?
+                .unwrap_or(DEFAULT_LOG_LINES);
+            collect_logs(&mut bundle, &log_dir, log_lines);
+        }
+
+        let mut diagnostics = BTreeMap::new();
+        if !excluded.contains(SECTION_DIAGNOSTICS) {
+            match new_client(args) {
+                Ok(client) => {
+                    for (endpoint, file, protocol_version) in DIAGNOSTIC_ENDPOINTS {
+                        let path = format!("diagnostics/{}", file);
+                        match client.get_json(endpoint, *protocol_version) {
+                            Ok(value) => {
+                                bundle.add(&path, redact_text(&pretty_json(&value)));
+                                diagnostics.insert(*file, value);
+                            }
+                            Err(err) => bundle.add_error(&path, err.to_string()),
+                        }
+                    }
+                }
+                Err(err) => bundle.add_error("diagnostics", err.to_string()),
+            }
+        }
+
+        #[cfg(feature = "database")]
+        if !excluded.contains(SECTION_DATABASE) {
+            let schema_versions = super::database::schema_versions(args.value_of("connect"));
+            bundle.add_result(
+                "database/schema_versions.json",
+                schema_versions
+                    .map(|versions| pretty_json(&json!(versions)))
+                    .map_err(|err| err.to_string()),
+            );
+        }
+        #[cfg(not(feature = "database"))]
+        if !excluded.contains(SECTION_DATABASE) {
+            bundle.add_error(
+                "database",
+                "The CLI was built without database support".to_string(),
+            );
+        }
+
+        if !diagnostics.is_empty() {
+            bundle.add(
+                SUMMARY_FILE,
+                redact_text(&pretty_json(&summarize(&diagnostics))),
+            );
+        }
+
+        bundle.write(&output)?;
+
+        info!("Wrote support bundle to {}", output.display());
+        for entry in bundle.manifest.entries.iter() {
+            if let Some(error) = &entry.error {
+                warn!("Unable to collect {}: {}", entry.path, error);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The contents of a support bundle, collected in memory
+struct SupportBundle {
+    name: String,
+    files: Vec<(String, String)>,
+    manifest: Manifest,
+}
+
+#[derive(Serialize)]
+struct Manifest {
+    created_at: u64,
+    cli_version: &'static str,
+    entries: Vec<ManifestEntry>,
+}
+
+#[derive(Serialize)]
+struct ManifestEntry {
+    path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl SupportBundle {
+    fn new(name: &str, created_at: u64) -> Self {
+        Self {
+            name: name.to_string(),
+            files: vec![],
+            manifest: Manifest {
+                created_at,
+                cli_version: env!("CARGO_PKG_VERSION"),
+                entries: vec![],
+            },
+        }
+    }
+
+    /// Adds a file to the bundle; the contents must already be redacted.
+    fn add(&mut self, path: &str, contents: String) {
+        self.manifest.entries.push(ManifestEntry {
+            path: path.to_string(),
+            error: None,
+        });
+        self.files.push((path.to_string(), contents));
+    }
+
+    /// Records that a file or section could not be collected.
+    fn add_error(&mut self, path: &str, error: String) {
+        self.manifest.entries.push(ManifestEntry {
+            path: path.to_string(),
+            error: Some(redact_text(&error)),
+        });
+    }
+
+    fn add_result(&mut self, path: &str, result: Result<String, String>) {
+        match result {
+            Ok(contents) => self.add(path, contents),
+            Err(error) => self.add_error(path, error),
+        }
+    }
+
+    /// Writes the bundle and its manifest to a gzipped tar archive at the given path.
+    fn write(&self, output: &Path) -> Result<(), CliError> {
+        let archive = File::create(output).map_err(|err| {
+            CliError::ActionError(format!(
+                "Unable to create {}: {}",
+                output.display(),
+                msg_from_io_error(err)
+            ))
+        })?;
+        let mut builder = tar::Builder::new(GzEncoder::new(archive, Compression::default()));
+
+        let manifest = pretty_json(&json!(self.manifest));
+        for (path, contents) in std::iter::once((MANIFEST_FILE, &manifest)).chain(
+            self.files
+                .iter()
+                .map(|(path, contents)| (path.as_str(), contents)),
+        ) {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(contents.len() as u64);
+            header.set_mode(0o640);
+            header.set_mtime(self.manifest.created_at);
+            header.set_cksum();
+            builder
+                .append_data(
+                    &mut header,
+                    Path::new(&self.name).join(path),
+                    contents.as_bytes(),
+                )
+                .map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Unable to add {} to support bundle: {}",
+                        path,
+                        msg_from_io_error(err)
+                    ))
+                })?;
+        }
+
+        builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .map(|_| ())
+            .map_err(|err| {
+                CliError::ActionError(format!(
+                    "Unable to write support bundle: {}",
+                    msg_from_io_error(err)
+                ))
+            })
+    }
+}
+
+/// Adds the last lines of each file in the log directory to the bundle.
+fn collect_logs(bundle: &mut SupportBundle, log_dir: &Path, log_lines: usize) {
+    let mut log_files = match fs::read_dir(log_dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>(),
+        Err(err) => {
+            bundle.add_error(
+                "logs",
+                format!(
+                    "Unable to read log directory {}: {}",
+                    log_dir.display(),
+                    msg_from_io_error(err)
+                ),
+            );
+            return;
+        }
+    };
+    log_files.sort();
+
+    for log_file in log_files {
+        let name = match log_file.file_name().and_then(|name| name.to_str()) {
+            Some(name) => name,
+            None => continue,
+        };
+        bundle.add_result(
+            &format!("logs/{}", name),
+            tail(&log_file, log_lines)
+                .map(|lines| redact_text(&lines))
+                .map_err(|err| {
+                    format!(
+                        "Unable to read {}: {}",
+                        log_file.display(),
+                        msg_from_io_error(err)
+                    )
+                }),
+        );
+    }
+}
+
+/// Returns the last lines of the given file.
+fn tail(path: &Path, lines: usize) -> Result<String, std::io::Error> {
+    let mut last_lines = VecDeque::with_capacity(lines.min(DEFAULT_LOG_LINES));
+    for line in BufReader::new(File::open(path)?).split(b'\n') {
+        if last_lines.len() == lines {
+            last_lines.pop_front();
+        }
+        if lines > 0 {
+            last_lines.push_back(String::from_utf8_lossy(&line?).into_owned());
+        }
+    }
+
+    Ok(last_lines
+        .into_iter()
+        .map(|line| line + "\n")
+        .collect::<String>())
+}
+
+/// Summarizes the node, its circuits, proposals and peers from the collected diagnostics.
+fn summarize(diagnostics: &BTreeMap<&str, Value>) -> Value {
+    let status = diagnostics.get("status.json");
+    let node_id = status
+        .and_then(|status| status.get("node_id"))
+        .and_then(Value::as_str);
+
+    let circuits = diagnostics
+        .get("circuits.json")
+        .and_then(|circuits| circuits.get("data"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let mut circuits_by_status = BTreeMap::new();
+    let mut peers = BTreeSet::new();
+    let circuit_summaries = circuits
+        .iter()
+        .map(|circuit| {
+            let circuit_status = circuit
+                .get("circuit_status")
+                .and_then(Value::as_str)
+                .unwrap_or("Active");
+            *circuits_by_status.entry(circuit_status).or_insert(0) += 1;
+
+            let members = circuit
+                .get("members")
+                .and_then(Value::as_array)
+                .map(|members| {
+                    members
+                        .iter()
+                        .filter_map(|member| member.get("node_id").and_then(Value::as_str))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            peers.extend(
+                members
+                    .iter()
+                    .filter(|member| Some(**member) != node_id)
+                    .copied(),
+            );
+
+            json!({
+                "id": circuit.get("id"),
+                "status": circuit_status,
+                "members": members,
+                "services": circuit
+                    .get("roster")
+                    .and_then(Value::as_array)
+                    .map(|roster| roster.len())
+                    .unwrap_or(0),
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let proposals = diagnostics
+        .get("proposals.json")
+        .and_then(|proposals| proposals.get("data"))
+        .and_then(Value::as_array)
+        .map(|proposals| {
+            proposals
+                .iter()
+                .map(|proposal| {
+                    json!({
+                        "circuit_id": proposal.get("circuit_id"),
+                        "proposal_type": proposal.get("proposal_type"),
+                        "votes": proposal
+                            .get("votes")
+                            .and_then(Value::as_array)
+                            .map(|votes| votes.len())
+                            .unwrap_or(0),
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    json!({
+        "node_id": node_id,
+        "version": status.and_then(|status| status.get("version")),
+        "circuits": {
+            "count": circuit_summaries.len(),
+            "by_status": circuits_by_status,
+            "circuits": circuit_summaries,
+        },
+        "proposals": {
+            "count": proposals.len(),
+            "proposals": proposals,
+        },
+        "peers": peers,
+    })
+}
+
+/// Redacts the values of secret keys and the secrets in URLs and tokens from a TOML
+/// configuration file, including commented-out settings.
+fn redact_config(config: &str) -> String {
+    config
+        .lines()
+        .map(|line| match line.split_once('=') {
+            Some((key, _)) if is_secret_key(key) => format!("{}= \"{}\"\n", key, REDACTED),
+            _ => redact_text(line) + "\n",
+        })
+        .collect()
+}
+
+fn is_secret_key(key: &str) -> bool {
+    let key = key.trim().trim_start_matches('#').trim().to_lowercase();
+    SECRET_KEYWORDS.iter().any(|keyword| key.contains(keyword))
+}
+
+/// Redacts the passwords in URLs, such as database connection URLs, and bearer tokens from the
+/// given text.
+fn redact_text(text: &str) -> String {
+    redact_bearer_tokens(&redact_url_passwords(text))
+}
+
+fn redact_url_passwords(text: &str) -> String {
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find("://") {
+        let (before, after) = rest.split_at(index + "://".len());
+        redacted.push_str(before);
+
+        let authority_len = after
+            .find(|c: char| c == '/' || c == '"' || c == '\'' || c.is_whitespace())
+            .unwrap_or(after.len());
+        let authority = &after[..authority_len];
+        match authority.rfind('@') {
+            Some(at) if authority[..at].contains(':') => {
+                let user = authority[..at].split(':').next().unwrap_or_default();
+                redacted.push_str(&format!("{}:{}{}", user, REDACTED, &authority[at..]));
+            }
+            _ => redacted.push_str(authority),
+        }
+
+        rest = &after[authority_len..];
+    }
+    redacted.push_str(rest);
+
+    redacted
+}
+
+fn redact_bearer_tokens(text: &str) -> String {
+    const BEARER: &str = "Bearer ";
+
+    let mut redacted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(index) = rest.find(BEARER) {
+        let (before, after) = rest.split_at(index + BEARER.len());
+        redacted.push_str(before);
+        redacted.push_str(REDACTED);
+
+        let token_len = after
+            .find(|c: char| c == '"' || c == '\'' || c.is_whitespace())
+            .unwrap_or(after.len());
+        rest = &after[token_len..];
+    }
+    redacted.push_str(rest);
+
+    redacted
+}
+
+fn pretty_json(value: &Value) -> String {
+    // Serializing a JSON value cannot fail
+    serde_json::to_string_pretty(value).unwrap_or_default() + "\n"
+}
+
+/// Returns the path of the node's configuration file, as determined by `splinterd`.
+fn default_config_file() -> PathBuf {
+    env::var(CONFIG_DIR_ENV)
+        .map(PathBuf::from)
+        .or_else(|_| env::var(SPLINTER_HOME_ENV).map(|home| Path::new(&home).join("etc")))
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_CONFIG_DIR))
+        .join(CONFIG_FILE)
+}
+
+fn new_client(args: &ArgMatches<'_>) -> Result<SplinterRestClient, CliError> {
+    let url = args
+        .value_of("url")
+        .map(ToOwned::to_owned)
+        .or_else(|| env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(args.value_of("private_key_file"))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use tempfile::TempDir;
+
+    /// Verify that the values of secret configuration keys, commented out or not, passwords in
+    /// URLs and bearer tokens are redacted, and that everything else is kept.
+    #[test]
+    fn redact_secrets() {
+        let config = "\
+node_id = \"alpha-node\"
+database = \"postgres://admin:hunter2@db:5432/splinter\"
+admin_webhook_secret = \"shh\"
+#oauth_client_secret = \"also-shh\"
+tls_server_key = \"/etc/splinter/certs/server.key\"
+rest_api_endpoint = \"http://127.0.0.1:8080\"
+";
+
+        assert_eq!(
+            redact_config(config),
+            "\
+node_id = \"alpha-node\"
+database = \"postgres://admin:<redacted>@db:5432/splinter\"
+admin_webhook_secret = \"<redacted>\"
+#oauth_client_secret = \"<redacted>\"
+tls_server_key = \"/etc/splinter/certs/server.key\"
+rest_api_endpoint = \"http://127.0.0.1:8080\"
+"
+        );
+
+        assert_eq!(
+            redact_text("Sending request with Authorization: Bearer Cylinder:abc.def.ghi to node"),
+            "Sending request with Authorization: Bearer <redacted> to node"
+        );
+        assert_eq!(
+            redact_text("Connecting to postgres://admin@db/splinter and https://user:pw@host"),
+            "Connecting to postgres://admin@db/splinter and https://user:<redacted>@host"
+        );
+    }
+
+    /// Verify that only the last lines of a log file are kept.
+    #[test]
+    fn tail_log_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let log_file = temp_dir.path().join("splinterd.log");
+        fs::write(&log_file, "one\ntwo\nthree\nfour\n").expect("Failed to write log file");
+
+        assert_eq!(tail(&log_file, 2).expect("Failed to tail"), "three\nfour\n");
+        assert_eq!(
+            tail(&log_file, 10).expect("Failed to tail"),
+            "one\ntwo\nthree\nfour\n"
+        );
+        assert_eq!(tail(&log_file, 0).expect("Failed to tail"), "");
+    }
+
+    /// Verify that the circuits, proposals and peers of the node are summarized from its
+    /// diagnostics.
+    #[test]
+    fn summarize_diagnostics() {
+        let mut diagnostics = BTreeMap::new();
+        diagnostics.insert(
+            "status.json",
+            json!({"node_id": "alpha", "version": "0.7.1"}),
+        );
+        diagnostics.insert(
+            "circuits.json",
+            json!({"data": [
+                {
+                    "id": "abcde-01234",
+                    "circuit_status": "Active",
+                    "members": [{"node_id": "alpha"}, {"node_id": "beta"}],
+                    "roster": [{"service_id": "a000"}, {"service_id": "b000"}],
+                },
+                {
+                    "id": "fghij-56789",
+                    "circuit_status": "Disbanded",
+                    "members": [{"node_id": "alpha"}, {"node_id": "gamma"}],
+                    "roster": [],
+                },
+            ]}),
+        );
+
+        let summary = summarize(&diagnostics);
+        assert_eq!(summary["node_id"], "alpha");
+        assert_eq!(summary["version"], "0.7.1");
+        assert_eq!(summary["circuits"]["count"], 2);
+        assert_eq!(summary["circuits"]["by_status"]["Active"], 1);
+        assert_eq!(summary["circuits"]["circuits"][0]["services"], 2);
+        assert_eq!(summary["proposals"]["count"], 0);
+        assert_eq!(summary["peers"], json!(["beta", "gamma"]));
+    }
+
+    /// Verify that a bundle is written as a gzipped tar archive containing its manifest, which
+    /// records the files that could not be collected.
+    #[test]
+    fn write_bundle() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let output = temp_dir.path().join("bundle.tar.gz");
+
+        let mut bundle = SupportBundle::new("splinter-support-bundle-0", 0);
+        bundle.add("config/splinterd.toml", "node_id = \"alpha\"\n".to_string());
+        bundle.add_error("logs", "Unable to read log directory".to_string());
+        bundle.write(&output).expect("Failed to write bundle");
+
+        let mut archive = tar::Archive::new(GzDecoder::new(
+            File::open(&output).expect("Failed to open bundle"),
+        ));
+        let mut files = BTreeMap::new();
+        for entry in archive.entries().expect("Failed to read bundle") {
+            let mut entry = entry.expect("Failed to read entry");
+            let path = entry
+                .path()
+                .expect("Failed to get path")
+                .display()
+                .to_string();
+            let mut contents = String::new();
+            entry
+                .read_to_string(&mut contents)
+                .expect("Failed to read entry");
+            files.insert(path, contents);
+        }
+
+        assert_eq!(
+            files.get("splinter-support-bundle-0/config/splinterd.toml"),
+            Some(&"node_id = \"alpha\"\n".to_string())
+        );
+        let manifest: Value = serde_json::from_str(
+            files
+                .get("splinter-support-bundle-0/manifest.json")
+                .expect("Missing manifest"),
+        )
+        .expect("Invalid manifest");
+        assert_eq!(manifest["entries"][0]["path"], "config/splinterd.toml");
+        assert!(manifest["entries"][0].get("error").is_none());
+        assert_eq!(manifest["entries"][1]["path"], "logs");
+        assert_eq!(
+            manifest["entries"][1]["error"],
+            "Unable to read log directory"
+        );
+    }
+}
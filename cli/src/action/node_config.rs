@@ -0,0 +1,70 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling node config subcommands.
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{SplinterRestClient, SplinterRestClientBuilder},
+    output::{OutputFormat, Table},
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// The action responsible for showing the configuration a Splinter node is running with, and the
+/// source of each value. The values of secrets are redacted by the node.
+///
+/// The specific args for this action:
+///
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+pub struct ShowConfigAction;
+
+impl Action for ShowConfigAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+
+        let values = new_client(arg_matches)?.get_config()?;
+
+        let mut table = Table::new(&["NAME", "VALUE", "SOURCE", "FILE"]);
+        for value in values {
+            table.add_row(vec![
+                value.name,
+                value.value,
+                value.source,
+                value.file.unwrap_or_default(),
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// Builds a client from the `url` and `private_key_file` arguments.
+fn new_client(arg_matches: Option<&ArgMatches>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
@@ -0,0 +1,218 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structured output for the list and show actions, selected with the global `--format` argument.
+
+use std::str::FromStr;
+
+use clap::ArgMatches;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::CliError;
+
+use super::print_table;
+
+/// The values accepted by the `--format` argument.
+pub const OUTPUT_FORMATS: &[&str] = &["human", "csv", "json", "yaml"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Human,
+    Csv,
+    Json,
+    Yaml,
+}
+
+impl OutputFormat {
+    /// Returns the format given by the `format` argument, or by the hidden `-f` argument that some
+    /// commands still accept; defaults to `Human`.
+    pub fn from_args(arg_matches: Option<&ArgMatches>) -> Result<Self, CliError> {
+        arg_matches
+            .and_then(|args| {
+                args.value_of("hidden_format")
+                    .or_else(|| args.value_of("format"))
+            })
+            .map(OutputFormat::from_str)
+            .unwrap_or(Ok(OutputFormat::Human))
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = CliError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(OutputFormat::Human),
+            "csv" => Ok(OutputFormat::Csv),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" => Ok(OutputFormat::Yaml),
+            _ => Err(CliError::ActionError(format!(
+                "Unsupported output format: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// Rows of values under named columns.
+///
+/// A table is printed as aligned columns for human output, as CSV with a header row, or as a list
+/// of objects keyed by the column names for JSON and YAML. Empty values are shown as `-` in human
+/// output.
+pub struct Table {
+    columns: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(columns: &[&str]) -> Self {
+        Table {
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            rows: vec![],
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn print(self, format: OutputFormat) -> Result<(), CliError> {
+        match format {
+            OutputFormat::Human => {
+                let rows = self.rows.into_iter().map(|row| {
+                    row.into_iter()
+                        .map(|value| {
+                            if value.is_empty() {
+                                "-".to_string()
+                            } else {
+                                value
+                            }
+                        })
+                        .collect()
+                });
+                print_table(std::iter::once(self.columns).chain(rows).collect());
+            }
+            OutputFormat::Csv => {
+                for row in std::iter::once(&self.columns).chain(self.rows.iter()) {
+                    println!(
+                        "{}",
+                        row.iter()
+                            .map(|value| csv_escape(value))
+                            .collect::<Vec<_>>()
+                            .join(",")
+                    );
+                }
+            }
+            OutputFormat::Json | OutputFormat::Yaml => {
+                let keys = self
+                    .columns
+                    .iter()
+                    .map(|column| column.to_lowercase().replace(' ', "_"))
+                    .collect::<Vec<_>>();
+                let objects = self
+                    .rows
+                    .into_iter()
+                    .map(|row| {
+                        keys.iter()
+                            .cloned()
+                            .zip(row.into_iter().map(Value::String))
+                            .collect::<Map<_, _>>()
+                    })
+                    .collect::<Vec<_>>();
+                print_serialized(format, "table", &objects)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints a single value, such as a circuit or a role.
+///
+/// The value is serialized for JSON and YAML output; `human` is called to print it for human
+/// output. CSV output is not supported for single values.
+pub fn print_value<T, F>(
+    format: OutputFormat,
+    name: &str,
+    value: &T,
+    human: F,
+) -> Result<(), CliError>
+where
+    T: Serialize,
+    F: FnOnce(&T),
+{
+    match format {
+        OutputFormat::Human => {
+            human(value);
+            Ok(())
+        }
+        OutputFormat::Csv => Err(CliError::ActionError(format!(
+            "The {} cannot be displayed in csv format",
+            name
+        ))),
+        OutputFormat::Json | OutputFormat::Yaml => print_serialized(format, name, value),
+    }
+}
+
+fn print_serialized<T: Serialize + ?Sized>(
+    format: OutputFormat,
+    name: &str,
+    value: &T,
+) -> Result<(), CliError> {
+    let output = match format {
+        OutputFormat::Yaml => serde_yaml::to_string(value).map_err(|err| {
+            CliError::ActionError(format!("Cannot format {} into yaml: {}", name, err))
+        })?,
+        _ => serde_json::to_string_pretty(value).map_err(|err| {
+            CliError::ActionError(format!("Cannot format {} into json: {}", name, err))
+        })?,
+    };
+    println!("{}", output);
+
+    Ok(())
+}
+
+/// Quotes a CSV value if it contains a separator, a quote or a line break.
+fn csv_escape(value: &str) -> String {
+    if value.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that values are only quoted when they contain a separator, quote or line break.
+    #[test]
+    fn test_csv_escape() {
+        assert_eq!(csv_escape("circuit-1"), "circuit-1");
+        assert_eq!(csv_escape("alpha;beta"), "alpha;beta");
+        assert_eq!(csv_escape("alpha,beta"), "\"alpha,beta\"");
+        assert_eq!(csv_escape("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_escape("two\nlines"), "\"two\nlines\"");
+    }
+
+    /// Verify that every value accepted by the `--format` argument can be parsed.
+    #[test]
+    fn test_output_format_from_str() {
+        for format in OUTPUT_FORMATS {
+            assert!(format.parse::<OutputFormat>().is_ok());
+        }
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+}
@@ -0,0 +1,151 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling peer subcommands.
+
+use std::time::Duration;
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{NewBlockedPeer, SplinterRestClient, SplinterRestClientBuilder},
+    output::{OutputFormat, Table},
+    time::Time,
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// The action responsible for listing the public keys and IP addresses blocked by a Splinter node.
+///
+/// The specific args for this action:
+///
+/// * include_expired: whether blocks that have expired are listed
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+pub struct ListBlocksAction;
+
+impl Action for ListBlocksAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+        let include_expired = arg_matches
+            .map(|args| args.is_present("include_expired"))
+            .unwrap_or(false);
+
+        let blocks = new_client(arg_matches)?.list_peer_blocks(include_expired)?;
+
+        let mut table = Table::new(&["KIND", "VALUE", "CREATED", "EXPIRES", "REASON"]);
+        for block in blocks {
+            table.add_row(vec![
+                block.kind,
+                block.value,
+                block.created_at.to_string(),
+                block.expires_at.to_string(),
+                block.reason,
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// The action responsible for blocking a public key or IP address on a Splinter node.
+///
+/// The specific args for this action:
+///
+/// * kind: one of "public_key" or "ip"
+/// * value: the hex-encoded public key or the IP address
+/// * reason: why the peer is blocked
+/// * duration: a duration, such as "2d", after which the block expires; defaults to the node's
+///   default of one day
+pub struct AddBlockAction;
+
+impl Action for AddBlockAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let (kind, value) = target_args(args)?;
+        let reason = args.value_of("reason").unwrap_or_default().to_string();
+        let duration = args
+            .value_of("duration")
+            .map(|duration| -> Result<u64, CliError> {
+                Ok(Duration::from(Time::make_duration_type_time(duration)?).as_secs())
+            })
+            .transpose()?;
+
+        let block = new_client(arg_matches)?.add_peer_block(&NewBlockedPeer {
+            kind,
+            value,
+            reason,
+            duration,
+        })?;
+
+        println!(
+            "Blocked {} {} until {}",
+            block.kind, block.value, block.expires_at
+        );
+
+        Ok(())
+    }
+}
+
+/// The action responsible for removing the block of a public key or IP address from a Splinter
+/// node.
+///
+/// The specific args for this action:
+///
+/// * kind: one of "public_key" or "ip"
+/// * value: the hex-encoded public key or the IP address
+pub struct RemoveBlockAction;
+
+impl Action for RemoveBlockAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+
+        let (kind, value) = target_args(args)?;
+
+        new_client(arg_matches)?.remove_peer_block(&kind, &value)?;
+
+        println!("Removed the block of {} {}", kind, value);
+
+        Ok(())
+    }
+}
+
+/// Returns the `kind` and `value` arguments.
+fn target_args(args: &ArgMatches) -> Result<(String, String), CliError> {
+    let kind = args
+        .value_of("kind")
+        .ok_or_else(|| CliError::ActionError("A kind is required".into()))?;
+    let value = args
+        .value_of("value")
+        .ok_or_else(|| CliError::ActionError("A value is required".into()))?;
+    Ok((kind.to_string(), value.to_string()))
+}
+
+/// Builds a client from the `url` and `private_key_file` arguments.
+fn new_client(arg_matches: Option<&ArgMatches>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
@@ -21,8 +21,9 @@ use crate::error::CliError;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 
 use super::{
-    api::SplinterRestClientBuilder, print_table, Action, DEFAULT_SPLINTER_REST_API_URL,
-    SPLINTER_REST_API_URL_ENV,
+    api::SplinterRestClientBuilder,
+    output::{OutputFormat, Table},
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
 };
 
 /// The action responsible for listing permissions.
@@ -31,14 +32,12 @@ use super::{
 ///
 /// * url: specifies the URL of the splinter node to be queried; falls back to the environment
 ///   variable SPLINTER_REST_API_URL
-/// * format: specifies the output format; one of "human", "json" or "csv"
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
 pub struct ListAction;
 
 impl Action for ListAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        let format = arg_matches
-            .and_then(|args| args.value_of("format"))
-            .unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
         let url = arg_matches
             .and_then(|args| args.value_of("url"))
             .map(ToOwned::to_owned)
@@ -53,34 +52,15 @@ impl Action for ListAction {
             .build()?
             .list_permissions()?;
 
-        let data = std::iter::once(vec![
-            "ID".to_string(),
-            "NAME".to_string(),
-            "DESCRIPTION".to_string(),
-        ])
-        .chain(permissions.into_iter().map(|perm| {
-            vec![
+        let mut table = Table::new(&["ID", "NAME", "DESCRIPTION"]);
+        for perm in permissions {
+            table.add_row(vec![
                 perm.permission_id,
                 perm.permission_display_name,
                 perm.permission_description,
-            ]
-        }));
-
-        match format {
-            "csv" => {
-                for row in data {
-                    println!("{}", row.join(","))
-                }
-            }
-            "json" => println!(
-                "\n {}",
-                serde_json::to_string_pretty(&data.collect::<Vec<_>>()).map_err(|err| {
-                    CliError::ActionError(format!("Cannot format permissions into json: {}", err))
-                })?
-            ),
-            _ => print_table(data.collect()),
+            ]);
         }
 
-        Ok(())
+        table.print(format)
     }
 }
@@ -15,12 +15,14 @@
 //! Actions to support the RBAC subcommands related to authorizing identities.
 
 use std::collections::BTreeSet;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use clap::ArgMatches;
 
 use crate::action::{
     api::{Assignment, AssignmentBuilder, AssignmentUpdateBuilder, Identity, SplinterRestClient},
-    print_table, Action,
+    output::{print_value, OutputFormat, Table},
+    Action,
 };
 use crate::error::CliError;
 
@@ -30,48 +32,27 @@ use super::new_client;
 ///
 /// The specific args for this action:
 ///
-/// * format: specifies the output format; one of "human" or "csv"
+/// * format: specifies the output format; one of "human", "csv", "json", or "yaml"
 pub struct ListAssignmentsAction;
 
 impl Action for ListAssignmentsAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        let format = arg_matches
-            .and_then(|args| args.value_of("format"))
-            .unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
 
         let client = new_client(&arg_matches)?;
 
-        let mut assignments = client
-            .list_assignments()?
-            .map(|res| {
-                res.map(|assignment| {
-                    let (id, id_type) = assignment.identity.parts();
-                    vec![
-                        id.to_string(),
-                        id_type.to_string(),
-                        assignment.roles.len().to_string(),
-                    ]
-                })
-            })
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let header = vec![
-            "IDENTITY".to_string(),
-            "TYPE".to_string(),
-            "ROLES".to_string(),
-        ];
-        if format == "csv" {
-            println!("{}", header.join(","));
-            for row in assignments {
-                println!("{}", row.join(","));
-            }
-        } else {
-            let mut rows = vec![header];
-            rows.append(&mut assignments);
-            print_table(rows);
+        let mut table = Table::new(&["IDENTITY", "TYPE", "ROLES"]);
+        for assignment_res in client.list_assignments()? {
+            let assignment = assignment_res?;
+            let (id, id_type) = assignment.identity.parts();
+            table.add_row(vec![
+                id.to_string(),
+                id_type.to_string(),
+                assignment.roles.len().to_string(),
+            ]);
         }
 
-        Ok(())
+        table.print(format)
     }
 }
 
@@ -82,6 +63,8 @@ impl Action for ListAssignmentsAction {
 /// * id_key: an identifier of type key; a public key
 /// * id_user: an identifier of type user; a user ID
 /// * role: a role to add to the assignment; repeated
+/// * expires_at: the time the assignment expires, in seconds since the Unix epoch
+/// * expires_in: the time until the assignment expires, such as `30d`
 pub struct CreateAssignmentAction;
 
 impl Action for CreateAssignmentAction {
@@ -97,6 +80,7 @@ impl Action for CreateAssignmentAction {
         let assignment = AssignmentBuilder::default()
             .with_identity(identity.clone())
             .with_roles(roles)
+            .with_expires_at(get_expiration_arg(&arg_matches)?)
             .build()?;
         let client = new_client(&arg_matches)?;
         if !is_dry_run(&arg_matches) {
@@ -124,9 +108,7 @@ pub struct ShowAssignmentAction;
 
 impl Action for ShowAssignmentAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        let format = arg_matches
-            .and_then(|args| args.value_of("format"))
-            .unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
 
         let identity = get_identity_arg(&arg_matches)?;
 
@@ -140,21 +122,7 @@ impl Action for ShowAssignmentAction {
                 ))
             })?;
 
-        match format {
-            "json" => println!(
-                "\n {}",
-                serde_json::to_string(&assignment).map_err(|err| CliError::ActionError(
-                    format!("Cannot format assignment into json: {}", err)
-                ))?
-            ),
-            "yaml" => println!(
-                "{}",
-                serde_yaml::to_string(&assignment).map_err(|err| CliError::ActionError(
-                    format!("Cannot format assignment into yaml: {}", err)
-                ))?
-            ),
-            _ => display_human_readable(&assignment),
-        }
+        print_value(format, "assignment", &assignment, display_human_readable)?;
 
         Ok(())
     }
@@ -169,6 +137,9 @@ impl Action for ShowAssignmentAction {
 /// * add_role: a role to add to the assignment; repeated
 /// * rm_role: a role to remove from the assignment; repeated
 /// * rm_all: remove all the currently assigned roles
+/// * expires_at: the time the assignment expires, in seconds since the Unix epoch
+/// * expires_in: the time until the assignment expires, such as `30d`
+/// * no_expiration: remove the assignment's expiration time
 /// * dry_run: validate the inputs but do not submit the changes
 /// * force: applies the changes, even if a role is added and removed
 pub struct UpdateAssignmentAction;
@@ -202,11 +173,21 @@ impl Action for UpdateAssignmentAction {
             )
         };
 
+        let expiration = if arg_matches
+            .map(|args| args.is_present("no_expiration"))
+            .unwrap_or(false)
+        {
+            Some(None)
+        } else {
+            get_expiration_arg(&arg_matches)?.map(Some)
+        };
+
         update_assignment(
             new_client(&arg_matches)?,
             identity,
             roles_to_add,
             role_removal,
+            expiration,
             force,
             dry_run,
         )
@@ -223,6 +204,7 @@ fn update_assignment(
     identity: Identity,
     roles_to_add: Vec<String>,
     role_removal: RoleRemoval,
+    expiration: Option<Option<u64>>,
     force: bool,
     is_dry_run: bool,
 ) -> Result<(), CliError> {
@@ -282,6 +264,7 @@ fn update_assignment(
     let updated_assignment = AssignmentUpdateBuilder::default()
         .with_identity(identity)
         .with_roles(Some(roles))
+        .with_expires_at(expiration)
         .build()?;
 
     if !is_dry_run {
@@ -319,6 +302,66 @@ fn display_human_readable(assignment: &Assignment) {
     for role in &assignment.roles {
         println!("        {}", role);
     }
+    if let Some(expires_at) = assignment.expires_at {
+        let expired = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs() >= expires_at)
+            .unwrap_or(false);
+        println!(
+            "    Expires at: {}{}",
+            expires_at,
+            if expired { " (expired)" } else { "" }
+        );
+    }
+}
+
+/// Returns the expiration time given by either the `expires_at` or `expires_in` argument, in
+/// seconds since the Unix epoch.
+fn get_expiration_arg<'a>(arg_matches: &Option<&ArgMatches<'a>>) -> Result<Option<u64>, CliError> {
+    if let Some(expires_at) = arg_matches.and_then(|args| args.value_of("expires_at")) {
+        return expires_at.parse::<u64>().map(Some).map_err(|_| {
+            CliError::ActionError(format!(
+                "'{}' is not a valid expiration time; expected seconds since the Unix epoch",
+                expires_at
+            ))
+        });
+    }
+
+    if let Some(expires_in) = arg_matches.and_then(|args| args.value_of("expires_in")) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| CliError::ActionError("System time is before the Unix epoch".into()))?;
+        return Ok(Some(now.as_secs() + parse_duration_secs(expires_in)?));
+    }
+
+    Ok(None)
+}
+
+/// Parses a duration such as `90m`, `12h` or `30d` into seconds. The unit may be `s`, `m`, `h`,
+/// `d` or `w`.
+fn parse_duration_secs(duration: &str) -> Result<u64, CliError> {
+    let invalid = || {
+        CliError::ActionError(format!(
+            "'{}' is not a valid duration; expected a number followed by s, m, h, d or w",
+            duration
+        ))
+    };
+
+    let unit_secs = match duration.chars().last() {
+        Some('s') => 1,
+        Some('m') => 60,
+        Some('h') => 60 * 60,
+        Some('d') => 24 * 60 * 60,
+        Some('w') => 7 * 24 * 60 * 60,
+        _ => return Err(invalid()),
+    };
+
+    duration[..duration.len() - 1]
+        .parse::<u64>()
+        .ok()
+        .filter(|value| *value > 0)
+        .and_then(|value| value.checked_mul(unit_secs))
+        .ok_or_else(invalid)
 }
 
 fn get_identity_arg<'a>(arg_matches: &Option<&ArgMatches<'a>>) -> Result<Identity, CliError> {
@@ -346,3 +389,27 @@ fn is_dry_run<'a>(arg_matches: &Option<&ArgMatches<'a>>) -> bool {
         .map(|args| args.is_present("dry_run"))
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that durations are parsed into seconds, and that durations without a valid number
+    /// or unit are rejected.
+    #[test]
+    fn test_parse_duration_secs() {
+        assert_eq!(parse_duration_secs("45s").ok(), Some(45));
+        assert_eq!(parse_duration_secs("90m").ok(), Some(90 * 60));
+        assert_eq!(parse_duration_secs("12h").ok(), Some(12 * 60 * 60));
+        assert_eq!(parse_duration_secs("30d").ok(), Some(30 * 24 * 60 * 60));
+        assert_eq!(parse_duration_secs("2w").ok(), Some(14 * 24 * 60 * 60));
+
+        for invalid in &["", "d", "0d", "-1d", "30", "30y", "1.5h"] {
+            assert!(
+                parse_duration_secs(invalid).is_err(),
+                "{} was accepted",
+                invalid
+            );
+        }
+    }
+}
@@ -0,0 +1,139 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions to support exporting and importing all roles and assignments as YAML.
+
+use std::fs::{self, File};
+use std::io::Write;
+
+use clap::ArgMatches;
+
+use crate::action::{
+    api::{Identity, RbacExport},
+    msg_from_io_error, Action,
+};
+use crate::error::CliError;
+
+use super::new_client;
+
+/// The action responsible for exporting all roles and assignments.
+///
+/// The specific args for this action:
+///
+/// * output: the file to write the YAML export to; defaults to stdout
+pub struct ExportRbacAction;
+
+impl Action for ExportRbacAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let rbac = new_client(&arg_matches)?.export_rbac()?;
+
+        let yaml = serde_yaml::to_string(&rbac).map_err(|err| {
+            CliError::ActionError(format!(
+                "Cannot format roles and assignments into yaml: {}",
+                err
+            ))
+        })?;
+
+        match arg_matches.and_then(|args| args.value_of("output")) {
+            Some(output_file) => {
+                let mut file = File::create(output_file).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to create or overwrite '{}': {}",
+                        output_file,
+                        msg_from_io_error(err)
+                    ))
+                })?;
+                file.write_all(yaml.as_bytes()).map_err(|err| {
+                    CliError::ActionError(format!(
+                        "Failed to write to file '{}': {}",
+                        output_file,
+                        msg_from_io_error(err)
+                    ))
+                })?;
+                info!(
+                    "Exported {} roles and {} assignments to {}",
+                    rbac.roles.len(),
+                    rbac.assignments.len(),
+                    output_file
+                );
+            }
+            None => print!("{}", yaml),
+        }
+
+        Ok(())
+    }
+}
+
+/// The action responsible for importing roles and assignments.
+///
+/// Roles and assignments in the file replace the existing ones with the same ID or identity, and
+/// are added otherwise. The import is validated as a whole and either fully applied or not at all.
+///
+/// The specific args for this action:
+///
+/// * file: the YAML file to import, in the format produced by the export action
+/// * dry_run: validate the import and report the changes, but do not apply them
+pub struct ImportRbacAction;
+
+impl Action for ImportRbacAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let input_file = arg_matches
+            .and_then(|args| args.value_of("file"))
+            .ok_or_else(|| CliError::ActionError("An import file must be specified".into()))?;
+
+        let dry_run = arg_matches
+            .map(|args| args.is_present("dry_run"))
+            .unwrap_or(false);
+
+        let contents = fs::read_to_string(input_file).map_err(|err| {
+            CliError::EnvironmentError(format!(
+                "Unable to read import file '{}': {}",
+                input_file,
+                msg_from_io_error(err)
+            ))
+        })?;
+        let rbac: RbacExport = serde_yaml::from_str(&contents).map_err(|err| {
+            CliError::ActionError(format!("Invalid import file '{}': {}", input_file, err))
+        })?;
+
+        let result = new_client(&arg_matches)?.import_rbac(&rbac, dry_run)?;
+
+        if result.dry_run {
+            println!("Dry run; no changes were made");
+        }
+        print_ids("Roles added", &result.roles_added);
+        print_ids("Roles updated", &result.roles_updated);
+        print_ids("Roles unchanged", &result.roles_unchanged);
+        print_identities("Assignments added", &result.assignments_added);
+        print_identities("Assignments updated", &result.assignments_updated);
+        print_identities("Assignments unchanged", &result.assignments_unchanged);
+
+        Ok(())
+    }
+}
+
+fn print_ids(label: &str, ids: &[String]) {
+    println!("{}: {}", label, ids.len());
+    for id in ids {
+        println!("    {}", id);
+    }
+}
+
+fn print_identities(label: &str, identities: &[Identity]) {
+    println!("{}: {}", label, identities.len());
+    for identity in identities {
+        let (id, id_type) = identity.parts();
+        println!("    {} {}", id_type, id);
+    }
+}
@@ -15,6 +15,7 @@
 //! Actions for handling role-based access control subcommands.
 
 mod assignments;
+mod import_export;
 mod roles;
 
 use clap::ArgMatches;
@@ -28,8 +29,10 @@ pub use assignments::{
     CreateAssignmentAction, DeleteAssignmentAction, ListAssignmentsAction, ShowAssignmentAction,
     UpdateAssignmentAction,
 };
+pub use import_export::{ExportRbacAction, ImportRbacAction};
 pub use roles::{
-    CreateRoleAction, DeleteRoleAction, ListRolesAction, ShowRoleAction, UpdateRoleAction,
+    AddRoleParentsAction, CreateRoleAction, DeleteRoleAction, ListRolesAction,
+    RemoveRoleParentsAction, ShowRoleAction, UpdateRoleAction,
 };
 
 /// Constructs a new Splinter REST client from the CLI arguments.
@@ -20,7 +20,8 @@ use clap::ArgMatches;
 
 use crate::action::{
     api::{RoleBuilder, RoleUpdateBuilder, SplinterRestClient},
-    print_table, Action,
+    output::{print_value, OutputFormat, Table},
+    Action,
 };
 use crate::error::CliError;
 
@@ -30,36 +31,23 @@ use super::new_client;
 ///
 /// The specific args for this action:
 ///
-/// * format: specifies the output format; one of "human" or "csv"
+/// * format: specifies the output format; one of "human", "csv", "json", or "yaml"
 pub struct ListRolesAction;
 
 impl Action for ListRolesAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        let format = arg_matches
-            .and_then(|args| args.value_of("format"))
-            .unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
 
         let client = new_client(&arg_matches)?;
         let roles = client.list_roles()?;
 
-        let header = vec!["ID".to_string(), "NAME".to_string()];
-
-        if format == "csv" {
-            println!("{}", header.join(","));
-            for role_res in roles {
-                let role = role_res?;
-                println!("{},{}", role.role_id, role.display_name);
-            }
-        } else {
-            let mut rows = vec![header];
-            for role_res in roles {
-                let role = role_res?;
-                rows.push(vec![role.role_id, role.display_name]);
-            }
-            print_table(rows);
+        let mut table = Table::new(&["ID", "NAME"]);
+        for role_res in roles {
+            let role = role_res?;
+            table.add_row(vec![role.role_id, role.display_name]);
         }
 
-        Ok(())
+        table.print(format)
     }
 }
 
@@ -73,9 +61,7 @@ pub struct ShowRoleAction;
 
 impl Action for ShowRoleAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
-        let format = arg_matches
-            .and_then(|args| args.value_of("format"))
-            .unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
 
         let role_id = arg_matches
             .and_then(|args| args.value_of("role_id"))
@@ -85,23 +71,7 @@ impl Action for ShowRoleAction {
             .get_role(role_id)?
             .ok_or_else(|| CliError::ActionError(format!("Role {} does not exist", role_id)))?;
 
-        match format {
-            "json" => println!(
-                "\n {}",
-                serde_json::to_string(&role).map_err(|err| CliError::ActionError(format!(
-                    "Cannot format role into json: {}",
-                    err
-                )))?
-            ),
-            "yaml" => println!(
-                "{}",
-                serde_yaml::to_string(&role).map_err(|err| CliError::ActionError(format!(
-                    "Cannot format role into yaml: {}",
-                    err
-                )))?
-            ),
-            _ => println!("{}", role),
-        }
+        print_value(format, "role", &role, |role| println!("{}", role))?;
 
         Ok(())
     }
@@ -114,6 +84,7 @@ impl Action for ShowRoleAction {
 /// * role_id: the specified role ID
 /// * display_name: the role's display name
 /// * permission: a permission granted by the resulting role; repeated
+/// * parent: a role the resulting role inherits permissions from; repeated
 /// * dry_run: validate the inputs but do not submit the role
 pub struct CreateRoleAction;
 
@@ -129,16 +100,19 @@ impl Action for CreateRoleAction {
 
         let permissions = arg_matches
             .and_then(|args| args.values_of("permission"))
-            .ok_or_else(|| {
-                CliError::ActionError("A role must have at least one permission".into())
-            })?
-            .map(|s| s.to_owned())
-            .collect();
+            .map(|vals| vals.map(|s| s.to_owned()).collect())
+            .unwrap_or_else(Vec::new);
+
+        let parents = arg_matches
+            .and_then(|args| args.values_of("parent"))
+            .map(|vals| vals.map(|s| s.to_owned()).collect())
+            .unwrap_or_else(Vec::new);
 
         let role = RoleBuilder::default()
             .with_role_id(role_id.into())
             .with_display_name(display_name.into())
             .with_permissions(permissions)
+            .with_parents(parents)
             .build()?;
 
         let client = new_client(&arg_matches)?;
@@ -276,10 +250,131 @@ fn update_role(
         }
     };
 
+    // A role without permissions is only valid if it has parents, so they are included in the
+    // update to allow it to be validated
+    let parents = if permissions.is_empty() {
+        Some(role.parents)
+    } else {
+        None
+    };
+
     let updated_role = RoleUpdateBuilder::default()
         .with_role_id(role_id.into())
         .with_display_name(display_name)
         .with_permissions(Some(permissions))
+        .with_parents(parents)
+        .build()?;
+
+    if !is_dry_run {
+        client.update_role(updated_role)
+    } else {
+        Ok(())
+    }
+}
+
+/// The action responsible for adding parents to a specific role, so that it inherits their
+/// permissions.
+///
+/// The specific args for this action:
+///
+/// * role_id: the specified role ID
+/// * parent_id: a role to add to the role's parents; repeated
+/// * dry_run: validate the inputs but do not submit the changes
+pub struct AddRoleParentsAction;
+
+impl Action for AddRoleParentsAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let (role_id, parent_ids) = role_parent_args(&arg_matches)?;
+
+        update_role_parents(
+            new_client(&arg_matches)?,
+            role_id,
+            |parents| {
+                for parent_id in parent_ids {
+                    if parents.contains(&parent_id) {
+                        return Err(CliError::ActionError(format!(
+                            "Role {} already inherits from {}",
+                            role_id, parent_id
+                        )));
+                    }
+                    parents.push(parent_id);
+                }
+                Ok(())
+            },
+            is_dry_run(&arg_matches),
+        )
+    }
+}
+
+/// The action responsible for removing parents from a specific role.
+///
+/// The specific args for this action:
+///
+/// * role_id: the specified role ID
+/// * parent_id: a role to remove from the role's parents; repeated
+/// * dry_run: validate the inputs but do not submit the changes
+pub struct RemoveRoleParentsAction;
+
+impl Action for RemoveRoleParentsAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let (role_id, parent_ids) = role_parent_args(&arg_matches)?;
+
+        update_role_parents(
+            new_client(&arg_matches)?,
+            role_id,
+            |parents| {
+                for parent_id in parent_ids {
+                    let len = parents.len();
+                    parents.retain(|parent| parent != &parent_id);
+                    if parents.len() == len {
+                        return Err(CliError::ActionError(format!(
+                            "Role {} does not inherit from {}",
+                            role_id, parent_id
+                        )));
+                    }
+                }
+                Ok(())
+            },
+            is_dry_run(&arg_matches),
+        )
+    }
+}
+
+fn role_parent_args<'a>(
+    arg_matches: &Option<&'a ArgMatches<'_>>,
+) -> Result<(&'a str, Vec<String>), CliError> {
+    let role_id = arg_matches
+        .and_then(|args| args.value_of("role_id"))
+        .ok_or_else(|| CliError::ActionError("A role ID must be provided.".into()))?;
+
+    let parent_ids = arg_matches
+        .and_then(|args| args.values_of("parent_id"))
+        .map(|vals| vals.map(|s| s.to_owned()).collect::<Vec<_>>())
+        .filter(|parent_ids| !parent_ids.is_empty())
+        .ok_or_else(|| CliError::ActionError("A parent role ID must be provided.".into()))?;
+
+    Ok((role_id, parent_ids))
+}
+
+fn update_role_parents<F>(
+    client: SplinterRestClient,
+    role_id: &str,
+    apply: F,
+    is_dry_run: bool,
+) -> Result<(), CliError>
+where
+    F: FnOnce(&mut Vec<String>) -> Result<(), CliError>,
+{
+    let role = client
+        .get_role(role_id)?
+        .ok_or_else(|| CliError::ActionError(format!("Role {} does not exist", role_id)))?;
+
+    let mut parents = role.parents;
+    apply(&mut parents)?;
+
+    let updated_role = RoleUpdateBuilder::default()
+        .with_role_id(role_id.into())
+        .with_parents(Some(parents))
         .build()?;
 
     if !is_dry_run {
@@ -22,6 +22,8 @@ use std::collections::HashMap;
 use crate::action::api::{ServerError, SplinterRestClient};
 use crate::error::CliError;
 
+const PAGING_LIMIT: &str = "1000";
+
 impl SplinterRestClient {
     /// Adds a new node to the registry.
     pub fn add_node(&self, node: &RegistryNode) -> Result<(), CliError> {
@@ -56,6 +58,48 @@ impl SplinterRestClient {
             })
     }
 
+    /// Lists the nodes in the registry.
+    pub fn list_nodes(&self) -> Result<Vec<RegistryNode>, CliError> {
+        let request = Client::new()
+            .get(&format!(
+                "{}/registry/nodes?limit={}",
+                self.url, PAGING_LIMIT
+            ))
+            .header("Authorization", &self.auth);
+
+        request
+            .send()
+            .map_err(|err| CliError::ActionError(format!("Failed to list nodes: {}", err)))
+            .and_then(|res| {
+                let status = res.status();
+                if status.is_success() {
+                    res.json::<RegistryNodeList>()
+                        .map(|list| list.data)
+                        .map_err(|_| {
+                            CliError::ActionError(
+                                "Request was successful, but received an invalid response".into(),
+                            )
+                        })
+                } else {
+                    let message = res
+                        .json::<ServerError>()
+                        .map_err(|_| {
+                            CliError::ActionError(format!(
+                                "Registry list nodes request failed with status code '{}', but \
+                                 error response was not valid",
+                                status
+                            ))
+                        })?
+                        .message;
+
+                    Err(CliError::ActionError(format!(
+                        "Failed to list nodes: {}",
+                        message
+                    )))
+                }
+            })
+    }
+
     /// Retrieves the node with the given identity from the registry.
     pub fn get_node(&self, identity: &str) -> Result<Option<RegistryNode>, CliError> {
         let request = Client::new()
@@ -102,6 +146,11 @@ pub struct RegistryNode {
     pub metadata: HashMap<String, String>,
 }
 
+#[derive(Deserialize)]
+struct RegistryNodeList {
+    data: Vec<RegistryNode>,
+}
+
 #[cfg(feature = "registry")]
 impl fmt::Display for RegistryNode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
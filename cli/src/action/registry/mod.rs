@@ -25,7 +25,7 @@ use std::path::Path;
 
 use crate::error::CliError;
 #[cfg(feature = "registry")]
-use crate::registry::api::RegistryNode;
+pub(crate) use crate::registry::api::RegistryNode;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 
 use super::api::SplinterRestClientBuilder;
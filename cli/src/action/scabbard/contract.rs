@@ -0,0 +1,451 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for managing the Sabre smart contracts of a scabbard service.
+//!
+//! These actions submit Sabre transactions and read Sabre state through the scabbard REST API of
+//! the Splinter daemon, authenticating with the same key as the other `splinter` commands.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::ArgMatches;
+use cylinder::Signer;
+use sabre_sdk::protocol::{
+    compute_contract_address,
+    payload::{
+        CreateContractActionBuilder, CreateContractRegistryActionBuilder,
+        CreateNamespaceRegistryActionBuilder, CreateNamespaceRegistryPermissionActionBuilder,
+        DeleteContractActionBuilder, DeleteNamespaceRegistryPermissionActionBuilder,
+    },
+    state::{ContractList, ContractRegistryList, NamespaceRegistryList},
+    CONTRACT_REGISTRY_ADDRESS_PREFIX, NAMESPACE_REGISTRY_ADDRESS_PREFIX,
+};
+use sabre_sdk::protos::FromBytes;
+use scabbard::client::{
+    ReqwestScabbardClient, ReqwestScabbardClientBuilder, ScabbardClient, ServiceId,
+};
+use serde::Serialize;
+use splinter::hex::to_hex;
+use transact::contract::archive::{default_scar_path, SmartContractArchive};
+use transact::protocol::batch::Batch;
+
+use crate::action::{
+    output::{print_value, OutputFormat, Table},
+    Action,
+};
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::rest_api_url;
+
+const DEFAULT_WAIT_SECS: u64 = 300;
+
+/// The action responsible for uploading a smart contract archive (scar) to a scabbard service.
+///
+/// The contract's registry is created, owned by the signing key, if it does not exist yet. Each
+/// namespace given with `--grant-namespace` is given read and write permissions for the contract;
+/// the namespace's registry is also created if it does not exist yet.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully qualified ID of the service, such as "abcde-01234::a000"
+/// * scar: the name and version requirement of the scar, in the form "name:version"
+/// * path: the directories to search for the scar; defaults to the system's scar paths
+/// * grant_namespace: the namespaces the contract may read from and write to
+/// * wait: the time, in seconds, to wait for the batches to be committed
+pub struct ContractUploadAction;
+
+impl Action for ContractUploadAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let service_id = parse_service_id(args)?;
+        let signer = load_signer(args.value_of("private_key_file"))?;
+        let client = new_client(args, signer.clone())?;
+
+        let scar = args
+            .value_of("scar")
+            .ok_or_else(|| CliError::ActionError("A scar is required".into()))?;
+        let (name, version) = parse_name_version(scar)?;
+        let paths = match args.values_of("path") {
+            Some(paths) => paths.map(PathBuf::from).collect(),
+            None => default_scar_path(),
+        };
+        let archive = SmartContractArchive::from_scar_file(name, version, &paths)
+            .map_err(|err| CliError::ActionError(format!("Failed to load scar: {}", err)))?;
+        let name = archive.metadata.name.clone();
+
+        let owner = signer
+            .public_key()
+            .map_err(|err| CliError::ActionError(format!("Failed to get public key: {}", err)))?
+            .as_hex();
+
+        let mut batches = vec![];
+
+        if find_contract_registries(&client, &service_id)?
+            .iter()
+            .all(|registry| registry.name != name)
+        {
+            batches.push(build_batch(
+                CreateContractRegistryActionBuilder::new()
+                    .with_name(name.clone())
+                    .with_owners(vec![owner.clone()])
+                    .into_payload_builder(),
+                &*signer,
+            )?);
+        }
+
+        batches.push(build_batch(
+            CreateContractActionBuilder::new()
+                .with_name(name.clone())
+                .with_version(archive.metadata.version)
+                .with_inputs(archive.metadata.inputs)
+                .with_outputs(archive.metadata.outputs)
+                .with_contract(archive.contract)
+                .into_payload_builder(),
+            &*signer,
+        )?);
+
+        if let Some(namespaces) = args.values_of("grant_namespace") {
+            let existing_namespaces = find_namespaces(&client, &service_id)?;
+            for namespace in namespaces {
+                if !existing_namespaces
+                    .iter()
+                    .any(|existing| existing == namespace)
+                {
+                    batches.push(build_batch(
+                        CreateNamespaceRegistryActionBuilder::new()
+                            .with_namespace(namespace.into())
+                            .with_owners(vec![owner.clone()])
+                            .into_payload_builder(),
+                        &*signer,
+                    )?);
+                }
+
+                batches.push(build_batch(
+                    CreateNamespaceRegistryPermissionActionBuilder::new()
+                        .with_namespace(namespace.into())
+                        .with_contract_name(name.clone())
+                        .with_read(true)
+                        .with_write(true)
+                        .into_payload_builder(),
+                    &*signer,
+                )?);
+            }
+        }
+
+        submit(&client, &service_id, batches, args)?;
+
+        info!("Uploaded contract {}:{}", name, version);
+        Ok(())
+    }
+}
+
+/// The action responsible for listing the smart contracts registered on a scabbard service.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully qualified ID of the service, such as "abcde-01234::a000"
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+pub struct ContractListAction;
+
+impl Action for ContractListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let service_id = parse_service_id(args)?;
+        let client = new_client(args, load_signer(args.value_of("private_key_file"))?)?;
+
+        let mut table = Table::new(&["NAME", "VERSIONS", "OWNERS"]);
+        for registry in find_contract_registries(&client, &service_id)? {
+            table.add_row(vec![
+                registry.name,
+                registry.versions.join(", "),
+                registry.owners.join(", "),
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// The details of a smart contract version
+#[derive(Serialize)]
+struct ContractDetails {
+    name: String,
+    version: String,
+    inputs: Vec<String>,
+    outputs: Vec<String>,
+    creator: String,
+}
+
+/// The action responsible for showing the details of a smart contract version registered on a
+/// scabbard service.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully qualified ID of the service, such as "abcde-01234::a000"
+/// * contract: the name and version of the contract, in the form "name:version"
+/// * format: specifies the output format; one of "human", "json" or "yaml"
+pub struct ContractShowAction;
+
+impl Action for ContractShowAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let service_id = parse_service_id(args)?;
+        let client = new_client(args, load_signer(args.value_of("private_key_file"))?)?;
+
+        let contract = args
+            .value_of("contract")
+            .ok_or_else(|| CliError::ActionError("A contract is required".into()))?;
+        let (name, version) = parse_name_version(contract)?;
+
+        let address = compute_contract_address(name, version).map_err(|err| {
+            CliError::ActionError(format!("Failed to compute contract address: {}", err))
+        })?;
+        let bytes = client
+            .get_state_at_address(&service_id, &to_hex(&address))
+            .map_err(|err| CliError::ActionError(format!("Failed to get contract: {}", err)))?
+            .ok_or_else(|| CliError::ActionError(format!("Contract {} not found", contract)))?;
+        let contract_list = ContractList::from_bytes(&bytes)
+            .map_err(|err| CliError::ActionError(format!("Invalid contract state: {}", err)))?;
+        let contract = contract_list
+            .contracts()
+            .iter()
+            .find(|entry| entry.name() == name && entry.version() == version)
+            .ok_or_else(|| CliError::ActionError(format!("Contract {} not found", contract)))?;
+
+        let details = ContractDetails {
+            name: contract.name().to_string(),
+            version: contract.version().to_string(),
+            inputs: contract.inputs().to_vec(),
+            outputs: contract.outputs().to_vec(),
+            creator: contract.creator().to_string(),
+        };
+
+        print_value(format, "contract", &details, |details| {
+            println!("{} {}", details.name, details.version);
+            println!("  inputs:");
+            for input in &details.inputs {
+                println!("  - {}", input);
+            }
+            println!("  outputs:");
+            for output in &details.outputs {
+                println!("  - {}", output);
+            }
+            println!("  creator: {}", details.creator);
+        })
+    }
+}
+
+/// The action responsible for deleting a smart contract version from a scabbard service. The
+/// signing key must be an owner of the contract's registry.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully qualified ID of the service, such as "abcde-01234::a000"
+/// * contract: the name and version of the contract, in the form "name:version"
+/// * revoke_namespace: the namespaces whose permissions are removed; this removes the permissions
+///   of every contract in the namespace
+/// * wait: the time, in seconds, to wait for the batches to be committed
+pub struct ContractDeleteAction;
+
+impl Action for ContractDeleteAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let service_id = parse_service_id(args)?;
+        let signer = load_signer(args.value_of("private_key_file"))?;
+        let client = new_client(args, signer.clone())?;
+
+        let contract = args
+            .value_of("contract")
+            .ok_or_else(|| CliError::ActionError("A contract is required".into()))?;
+        let (name, version) = parse_name_version(contract)?;
+
+        let mut batches = vec![build_batch(
+            DeleteContractActionBuilder::new()
+                .with_name(name.into())
+                .with_version(version.into())
+                .into_payload_builder(),
+            &*signer,
+        )?];
+
+        if let Some(namespaces) = args.values_of("revoke_namespace") {
+            for namespace in namespaces {
+                batches.push(build_batch(
+                    DeleteNamespaceRegistryPermissionActionBuilder::new()
+                        .with_namespace(namespace.into())
+                        .into_payload_builder(),
+                    &*signer,
+                )?);
+            }
+        }
+
+        submit(&client, &service_id, batches, args)?;
+
+        info!("Deleted contract {}", contract);
+        Ok(())
+    }
+}
+
+/// A contract registry, with the versions of the contract that are registered
+struct ContractRegistry {
+    name: String,
+    versions: Vec<String>,
+    owners: Vec<String>,
+}
+
+fn find_contract_registries(
+    client: &ReqwestScabbardClient,
+    service_id: &ServiceId,
+) -> Result<Vec<ContractRegistry>, CliError> {
+    let entries = client
+        .get_state_with_prefix(service_id, Some(CONTRACT_REGISTRY_ADDRESS_PREFIX))
+        .map_err(|err| {
+            CliError::ActionError(format!("Failed to get contract registries: {}", err))
+        })?;
+
+    let mut registries = vec![];
+    for entry in entries {
+        let registry_list = ContractRegistryList::from_bytes(entry.value()).map_err(|err| {
+            CliError::ActionError(format!("Invalid contract registry state: {}", err))
+        })?;
+        registries.extend(registry_list.registries().iter().map(|registry| {
+            ContractRegistry {
+                name: registry.name().to_string(),
+                versions: registry
+                    .versions()
+                    .iter()
+                    .map(|version| version.version().to_string())
+                    .collect(),
+                owners: registry.owners().to_vec(),
+            }
+        }));
+    }
+
+    Ok(registries)
+}
+
+fn find_namespaces(
+    client: &ReqwestScabbardClient,
+    service_id: &ServiceId,
+) -> Result<Vec<String>, CliError> {
+    let entries = client
+        .get_state_with_prefix(service_id, Some(NAMESPACE_REGISTRY_ADDRESS_PREFIX))
+        .map_err(|err| {
+            CliError::ActionError(format!("Failed to get namespace registries: {}", err))
+        })?;
+
+    let mut namespaces = vec![];
+    for entry in entries {
+        let registry_list = NamespaceRegistryList::from_bytes(entry.value()).map_err(|err| {
+            CliError::ActionError(format!("Invalid namespace registry state: {}", err))
+        })?;
+        namespaces.extend(
+            registry_list
+                .registries()
+                .iter()
+                .map(|registry| registry.namespace().to_string()),
+        );
+    }
+
+    Ok(namespaces)
+}
+
+/// Builds a batch with a single Sabre transaction from the result of an action builder's
+/// `into_payload_builder`.
+fn build_batch<E: std::fmt::Display>(
+    payload_builder: Result<sabre_sdk::protocol::payload::SabrePayloadBuilder, E>,
+    signer: &dyn Signer,
+) -> Result<Batch, CliError> {
+    payload_builder
+        .map_err(|err| CliError::ActionError(format!("Failed to build Sabre action: {}", err)))?
+        .into_transaction_builder()
+        .map_err(|err| CliError::ActionError(format!("Failed to build transaction: {}", err)))?
+        .into_batch_builder(signer)
+        .map_err(|err| CliError::ActionError(format!("Failed to build batch: {}", err)))?
+        .build(signer)
+        .map_err(|err| CliError::ActionError(format!("Failed to build batch: {}", err)))
+}
+
+fn submit(
+    client: &ReqwestScabbardClient,
+    service_id: &ServiceId,
+    batches: Vec<Batch>,
+    args: &ArgMatches,
+) -> Result<(), CliError> {
+    let wait = match args.value_of("wait") {
+        Some(wait) => wait.parse::<u64>().map_err(|_| {
+            CliError::ActionError(format!("Invalid wait time \"{}\", expected seconds", wait))
+        })?,
+        None => DEFAULT_WAIT_SECS,
+    };
+
+    client
+        .submit(service_id, batches, Some(Duration::from_secs(wait)))
+        .map_err(|err| CliError::ActionError(format!("Failed to submit batches: {}", err)))
+}
+
+fn parse_service_id(args: &ArgMatches) -> Result<ServiceId, CliError> {
+    let service_id = args
+        .value_of("service_id")
+        .ok_or_else(|| CliError::ActionError("A service ID is required".into()))?;
+    ServiceId::from_string(service_id).map_err(|_| {
+        CliError::ActionError(format!(
+            "Invalid service ID \"{}\", expected <circuit-id>::<service-id>",
+            service_id
+        ))
+    })
+}
+
+fn parse_name_version(name_version: &str) -> Result<(&str, &str), CliError> {
+    match name_version.split_once(':') {
+        Some((name, version)) if !name.is_empty() && !version.is_empty() => Ok((name, version)),
+        _ => Err(CliError::ActionError(format!(
+            "Invalid contract \"{}\", expected <name>:<version>",
+            name_version
+        ))),
+    }
+}
+
+/// Builds a scabbard client from the `url` argument, authenticating with the given signer.
+fn new_client(
+    args: &ArgMatches,
+    signer: Box<dyn Signer>,
+) -> Result<ReqwestScabbardClient, CliError> {
+    ReqwestScabbardClientBuilder::new()
+        .with_url(&rest_api_url(args.value_of("url")))
+        .with_auth(&create_cylinder_jwt_auth(signer)?)
+        .build()
+        .map_err(|err| CliError::ActionError(format!("Failed to create client: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that contracts are parsed from their name and version, and that contracts
+    /// without both are rejected.
+    #[test]
+    fn test_parse_name_version() {
+        assert_eq!(
+            parse_name_version("intkey:1.0").ok(),
+            Some(("intkey", "1.0"))
+        );
+        assert!(parse_name_version("intkey").is_err());
+        assert!(parse_name_version("intkey:").is_err());
+        assert!(parse_name_version(":1.0").is_err());
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling scabbard subcommands.
+
+#[cfg(feature = "scabbard-contracts")]
+pub mod contract;
+
+#[cfg(feature = "scabbard-consensus-log")]
+use clap::ArgMatches;
+
+#[cfg(feature = "scabbard-consensus-log")]
+use crate::error::CliError;
+#[cfg(feature = "scabbard-consensus-log")]
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+#[cfg(feature = "scabbard-consensus-log")]
+use super::{
+    api::{ScabbardConsensusEvent, SplinterRestClient, SplinterRestClientBuilder},
+    output::{OutputFormat, Table},
+    Action,
+};
+use super::{DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+
+/// The action responsible for listing the consensus events of a scabbard service, which shows
+/// where consensus on a stuck commit stopped making progress.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully qualified ID of the service, such as "abcde-01234::a000"
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+#[cfg(feature = "scabbard-consensus-log")]
+pub struct ConsensusLogAction;
+
+#[cfg(feature = "scabbard-consensus-log")]
+impl Action for ConsensusLogAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let format = OutputFormat::from_args(arg_matches)?;
+
+        let service_id = arg_matches
+            .and_then(|args| args.value_of("service_id"))
+            .ok_or_else(|| CliError::ActionError("A service ID is required".into()))?;
+        let (circuit_id, service_id) = match service_id.split_once("::") {
+            Some((circuit_id, service_id)) if !circuit_id.is_empty() && !service_id.is_empty() => {
+                (circuit_id, service_id)
+            }
+            _ => {
+                return Err(CliError::ActionError(format!(
+                    "Invalid service ID \"{}\", expected <circuit-id>::<service-id>",
+                    service_id
+                )))
+            }
+        };
+
+        let events =
+            new_client(arg_matches)?.list_scabbard_consensus_events(circuit_id, service_id)?;
+
+        let mut table = Table::new(&["POSITION", "CREATED", "EXECUTED", "EPOCH", "TYPE", "DETAIL"]);
+        for event in events {
+            let detail = describe(&event);
+            table.add_row(vec![
+                event.position.to_string(),
+                event.created_at.to_string(),
+                event
+                    .executed_at
+                    .map(|executed_at| executed_at.to_string())
+                    .unwrap_or_else(|| "pending".into()),
+                event
+                    .executed_epoch
+                    .map(|epoch| epoch.to_string())
+                    .unwrap_or_else(|| "-".into()),
+                event.event_type,
+                detail,
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// Describes the payload of an event: the message of a deliver event, the value of a start event
+/// or the vote of a vote event.
+#[cfg(feature = "scabbard-consensus-log")]
+fn describe(event: &ScabbardConsensusEvent) -> String {
+    if let Some(deliver) = &event.deliver {
+        let mut detail = format!(
+            "{} from {} (epoch {})",
+            deliver.message_type, deliver.from, deliver.epoch
+        );
+        if let Some(vote) = deliver.vote {
+            detail.push_str(&format!(", vote: {}", vote));
+        }
+        if let Some(value) = &deliver.value {
+            detail.push_str(&format!(", value: {}", value));
+        }
+        detail
+    } else if let Some(value) = &event.value {
+        format!("value: {}", value)
+    } else if let Some(vote) = event.vote {
+        format!("vote: {}", vote)
+    } else {
+        String::new()
+    }
+}
+
+/// Builds a client from the `url` and `private_key_file` arguments.
+#[cfg(feature = "scabbard-consensus-log")]
+fn new_client(arg_matches: Option<&ArgMatches>) -> Result<SplinterRestClient, CliError> {
+    let url = rest_api_url(arg_matches.and_then(|args| args.value_of("url")));
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
+
+/// Returns the given URL of the Splinter daemon REST API, or else the URL in the environment, or
+/// else the default URL.
+fn rest_api_url(url: Option<&str>) -> String {
+    url.map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string())
+}
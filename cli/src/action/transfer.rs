@@ -0,0 +1,213 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Actions for handling transfer subcommands, which send and receive files through the transfer
+//! services of circuits.
+
+use std::fs::File;
+use std::path::Path;
+
+use clap::ArgMatches;
+
+use crate::error::CliError;
+use crate::signing::{create_cylinder_jwt_auth, load_signer};
+
+use super::{
+    api::{SplinterRestClient, SplinterRestClientBuilder},
+    output::{print_value, OutputFormat, Table},
+    Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV,
+};
+
+/// The action responsible for sending a file through a transfer service.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully-qualified ID of the transfer service, `<circuit>::<service>`
+/// * file: the path of the file to send
+/// * name: the name the file is sent as; defaults to the file's name
+pub struct SendAction;
+
+impl Action for SendAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let (circuit_id, service_id) = parse_service_id(args)?;
+
+        let path = args
+            .value_of("file")
+            .ok_or_else(|| CliError::ActionError("A file is required".into()))?;
+        let name = match args.value_of("name") {
+            Some(name) => name.to_string(),
+            None => Path::new(path)
+                .file_name()
+                .and_then(|name| name.to_str())
+                .map(String::from)
+                .ok_or_else(|| {
+                    CliError::ActionError(format!("Unable to get the name of file {}", path))
+                })?,
+        };
+        let file = File::open(path)
+            .map_err(|err| CliError::ActionError(format!("Unable to open {}: {}", path, err)))?;
+
+        let sent = new_client(arg_matches)?.send_file(circuit_id, service_id, &name, file)?;
+
+        println!("Sent {} as file {}", sent.name, sent.file_id);
+
+        Ok(())
+    }
+}
+
+/// The action responsible for listing the files sent and received by a transfer service.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully-qualified ID of the transfer service, `<circuit>::<service>`
+/// * format: specifies the output format; one of "human", "csv", "json" or "yaml"
+pub struct ListAction;
+
+impl Action for ListAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let format = OutputFormat::from_args(arg_matches)?;
+        let (circuit_id, service_id) = parse_service_id(args)?;
+
+        let files = new_client(arg_matches)?.list_files(circuit_id, service_id)?;
+
+        let mut table = Table::new(&["ID", "NAME", "DIRECTION", "SIZE", "RECEIVED", "STATUS"]);
+        for file in files {
+            table.add_row(vec![
+                file.file_id,
+                file.name,
+                file.direction,
+                file.size.to_string(),
+                file.received.to_string(),
+                file.status,
+            ]);
+        }
+
+        table.print(format)
+    }
+}
+
+/// The action responsible for showing a file of a transfer service, including the progress of an
+/// incoming transfer.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully-qualified ID of the transfer service, `<circuit>::<service>`
+/// * file_id: the ID of the file
+/// * format: specifies the output format; one of "human", "json" or "yaml"
+pub struct ShowAction;
+
+impl Action for ShowAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let format = OutputFormat::from_args(arg_matches)?;
+        let (circuit_id, service_id) = parse_service_id(args)?;
+        let file_id = args
+            .value_of("file_id")
+            .ok_or_else(|| CliError::ActionError("A file ID is required".into()))?;
+
+        let file = new_client(arg_matches)?.get_file(circuit_id, service_id, file_id)?;
+
+        print_value(format, "file", &file, |file| {
+            println!("ID: {}", file.file_id);
+            println!("Name: {}", file.name);
+            println!("Direction: {}", file.direction);
+            if let Some(peer) = &file.peer {
+                println!("From: {}", peer);
+            }
+            println!("Size: {}", file.size);
+            println!("Received: {}", file.received);
+            println!("SHA-256: {}", file.sha256);
+            println!("Status: {}", file.status);
+        })
+    }
+}
+
+/// The action responsible for downloading a file that a transfer service has completely sent or
+/// received.
+///
+/// The specific args for this action:
+///
+/// * service_id: the fully-qualified ID of the transfer service, `<circuit>::<service>`
+/// * file_id: the ID of the file
+/// * output: the path the file is written to; defaults to the file's name
+pub struct DownloadAction;
+
+impl Action for DownloadAction {
+    fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
+        let args = arg_matches.ok_or(CliError::RequiresArgs)?;
+        let (circuit_id, service_id) = parse_service_id(args)?;
+        let file_id = args
+            .value_of("file_id")
+            .ok_or_else(|| CliError::ActionError("A file ID is required".into()))?;
+
+        let client = new_client(arg_matches)?;
+        let output = match args.value_of("output") {
+            Some(output) => output.to_string(),
+            None => {
+                let name = client.get_file(circuit_id, service_id, file_id)?.name;
+                // Only the final component of the sender's name is used, so that the file is
+                // written to the current directory
+                Path::new(&name)
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| file_id.to_string())
+            }
+        };
+
+        let mut file = File::create(&output).map_err(|err| {
+            CliError::ActionError(format!("Unable to create {}: {}", output, err))
+        })?;
+        let size = client.download_file(circuit_id, service_id, file_id, &mut file)?;
+
+        println!("Downloaded {} bytes to {}", size, output);
+
+        Ok(())
+    }
+}
+
+/// Parses the `service_id` argument, `<circuit>::<service>`, into its circuit and service IDs.
+fn parse_service_id<'a>(args: &'a ArgMatches) -> Result<(&'a str, &'a str), CliError> {
+    let service_id = args
+        .value_of("service_id")
+        .ok_or_else(|| CliError::ActionError("A service ID is required".into()))?;
+
+    match service_id.split_once("::") {
+        Some((circuit_id, service_id)) if !circuit_id.is_empty() && !service_id.is_empty() => {
+            Ok((circuit_id, service_id))
+        }
+        _ => Err(CliError::ActionError(format!(
+            "Invalid service ID '{}': expected <circuit>::<service>",
+            service_id
+        ))),
+    }
+}
+
+/// Builds a client from the `url` and `private_key_file` arguments.
+fn new_client(arg_matches: Option<&ArgMatches>) -> Result<SplinterRestClient, CliError> {
+    let url = arg_matches
+        .and_then(|args| args.value_of("url"))
+        .map(ToOwned::to_owned)
+        .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+    let signer = load_signer(arg_matches.and_then(|args| args.value_of("private_key_file")))?;
+
+    SplinterRestClientBuilder::new()
+        .with_url(url)
+        .with_auth(create_cylinder_jwt_auth(signer)?)
+        .build()
+}
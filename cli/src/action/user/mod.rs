@@ -21,7 +21,8 @@ use crate::error::CliError;
 use crate::signing::{create_cylinder_jwt_auth, load_signer};
 
 use super::api::SplinterRestClientBuilder;
-use super::{print_table, Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
+use super::output::{OutputFormat, Table};
+use super::{Action, DEFAULT_SPLINTER_REST_API_URL, SPLINTER_REST_API_URL_ENV};
 use api::{ClientBiomeUser, ClientOAuthUser};
 
 pub struct ListSplinterUsersAction;
@@ -30,7 +31,7 @@ impl Action for ListSplinterUsersAction {
     fn run<'a>(&mut self, arg_matches: Option<&ArgMatches<'a>>) -> Result<(), CliError> {
         let args = arg_matches.ok_or(CliError::RequiresArgs)?;
 
-        let format = args.value_of("format").unwrap_or("human");
+        let format = OutputFormat::from_args(arg_matches)?;
         let signer = load_signer(args.value_of("private_key_file"))?;
         let url = args
             .value_of("url")
@@ -44,7 +45,7 @@ impl Action for ListSplinterUsersAction {
 
 fn display_splinter_users(
     url: &str,
-    format: &str,
+    format: OutputFormat,
     signer: Box<dyn Signer>,
 ) -> Result<(), CliError> {
     let client = SplinterRestClientBuilder::new()
@@ -68,10 +69,7 @@ fn display_splinter_users(
         }
     };
 
-    let mut data = vec![
-        // headers
-        vec!["ID".to_string(), "USERNAME".to_string(), "TYPE".to_string()],
-    ];
+    let mut table = Table::new(&["ID", "USERNAME", "TYPE"]);
 
     let users = match (biome_users, biome_oauth_users) {
         (Some(biome_users), Some(biome_oauth_users)) => biome_users
@@ -88,22 +86,14 @@ fn display_splinter_users(
     };
     users.into_iter().for_each(|user| match user {
         ClientSplinterUser::Biome(user) => {
-            data.push(vec![user.user_id, user.username, "Biome".to_string()])
+            table.add_row(vec![user.user_id, user.username, "Biome".to_string()])
         }
         ClientSplinterUser::OAuth(user) => {
-            data.push(vec![user.user_id, user.subject, "OAuth".to_string()])
+            table.add_row(vec![user.user_id, user.subject, "OAuth".to_string()])
         }
     });
 
-    if format == "csv" {
-        for row in data {
-            println!("{}", row.join(","));
-        }
-    } else {
-        print_table(data);
-    }
-
-    Ok(())
+    table.print(format)
 }
 
 /// Representation of the users that may be returned by Splinter.
@@ -28,7 +28,7 @@ mod template;
 
 use std::ffi::OsString;
 
-use clap::{clap_app, AppSettings, Arg, SubCommand};
+use clap::{clap_app, AppSettings, Arg, ArgGroup, SubCommand};
 #[cfg(test)]
 use flexi_logger::FlexiLoggerError;
 use flexi_logger::{DeferredNow, LogSpecBuilder, Logger};
@@ -36,6 +36,7 @@ use log::Record;
 
 #[cfg(feature = "command")]
 use action::command;
+use action::output::OUTPUT_FORMATS;
 #[cfg(feature = "playlist-smallbank")]
 use action::playlist;
 #[cfg(feature = "workload")]
@@ -95,6 +96,31 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         (@setting SubcommandRequiredElseHelp)
     );
 
+    app = app.arg(
+        Arg::with_name("format")
+            .short("F")
+            .long("format")
+            .global(true)
+            .takes_value(true)
+            .possible_values(OUTPUT_FORMATS)
+            .help("Output format for list and show commands; defaults to human"),
+    );
+
+    #[cfg(feature = "pkcs11-signer")]
+    {
+        app = app.arg(
+            Arg::with_name("signing_key_uri")
+                .long("signing-key-uri")
+                .global(true)
+                .takes_value(true)
+                .value_name("URI")
+                .help(
+                    "PKCS#11 URI of a key held by an HSM or YubiKey to sign with, instead of \
+                     the --key file",
+                ),
+        );
+    }
+
     app = app
         .subcommand(
         SubCommand::with_name("keygen")
@@ -158,14 +184,14 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             Arg::with_name("node_file")
                 .long("node-file")
                 .takes_value(true)
-                .required_unless("node")
+                .required_unless_one(&["node", "interactive", "template_file"])
                 .help("File system path or HTTP(S) URL to nodes file"),
         )
         .arg(
             Arg::with_name("node")
                 .long("node")
                 .takes_value(true)
-                .required_unless("node_file")
+                .required_unless_one(&["node_file", "interactive", "template_file"])
                 .multiple(true)
                 .help(
                     "Node that is part of a circuit \
@@ -178,7 +204,7 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 .takes_value(true)
                 .multiple(true)
                 .min_values(2)
-                .required_unless("template")
+                .required_unless_one(&["template", "interactive", "template_file"])
                 .help(
                     "Service ID and allowed nodes \
                      (<service-id>::<allowed_nodes>)",
@@ -271,6 +297,31 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             .help("Authorization type for the circuit"),
     );
 
+    #[cfg(feature = "circuit-version")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("circuit_version")
+            .long("circuit-version")
+            .takes_value(true)
+            .value_name("VERSION")
+            .conflicts_with("compat_version")
+            .help(
+                "Circuit version to propose; defaults to the SPLINTER_CIRCUIT_VERSION environment \
+                 variable or the newest version",
+            ),
+    );
+
+    #[cfg(feature = "circuit-payload-encoding")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("payload_encoding")
+            .long("payload-encoding")
+            .takes_value(true)
+            .value_name("ENCODING")
+            .help(
+                "Encoding of the service message payloads of the circuit, such as protobuf, cbor \
+                 or messagepack; sets the payload_encoding argument of every service",
+            ),
+    );
+
     let propose_circuit = propose_circuit.arg(
         Arg::with_name("node_public_key")
             .long("node-public-key")
@@ -282,13 +333,61 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    #[cfg(feature = "circuit-propose-interactive")]
+    let propose_circuit = propose_circuit.arg(
+        Arg::with_name("interactive")
+            .long("interactive")
+            .short("i")
+            .conflicts_with_all(&[
+                "authorization_type",
+                "comments",
+                "display_name",
+                "management_type",
+                "node",
+                "node_file",
+                "node_public_key",
+                "service",
+                "service_argument",
+                "service_peer_group",
+                "service_type",
+                "template",
+                "template_file",
+            ])
+            .help("Build the proposal by answering questions, selecting members from the registry"),
+    );
+
+    #[cfg(feature = "circuit-template-file")]
+    let propose_circuit = propose_circuit
+        .arg(
+            Arg::with_name("template_file")
+                .long("template-file")
+                .value_name("file")
+                .takes_value(true)
+                .conflicts_with_all(&[
+                    "node",
+                    "node_file",
+                    "node_public_key",
+                    "service",
+                    "template",
+                ])
+                .help("YAML file that describes the members and services of the circuit"),
+        )
+        .arg(
+            Arg::with_name("template_var")
+                .long("template-var")
+                .multiple(true)
+                .takes_value(true)
+                .requires("template_file")
+                .help("Value of a variable in the template file (<name>=<value>)"),
+        );
+
     #[cfg(feature = "circuit-template")]
     let propose_circuit = propose_circuit
         .arg(
             Arg::with_name("template")
                 .long("template")
                 .takes_value(true)
-                .required_unless("service")
+                .required_unless_one(&["service", "interactive", "template_file"])
                 .help("Template name to be applied to circuit"),
         )
         .arg(
@@ -371,21 +470,12 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         .possible_values(&["active", "disbanded", "abandoned"])
                         .takes_value(true),
                 )
-                .arg(
-                    Arg::with_name("format")
-                        .short("F")
-                        .long("format")
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .default_value("human")
-                        .takes_value(true),
-                )
                 .arg(
                     Arg::with_name("hidden_format")
                         .short("f")
                         .hidden(true)
                         .help("Output format")
-                        .possible_values(&["human", "csv"])
+                        .possible_values(OUTPUT_FORMATS)
                         .takes_value(true),
                 )
                 .arg(
@@ -413,21 +503,12 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         .required(true)
                         .takes_value(true),
                 )
-                .arg(
-                    Arg::with_name("format")
-                        .short("F")
-                        .long("format")
-                        .help("Output format")
-                        .possible_values(&["human", "yaml", "json"])
-                        .default_value("human")
-                        .takes_value(true),
-                )
                 .arg(
                     Arg::with_name("hidden_format")
                         .short("f")
                         .hidden(true)
                         .help("Output format")
-                        .possible_values(&["human", "yaml", "json"])
+                        .possible_values(OUTPUT_FORMATS)
                         .takes_value(true),
                 )
                 .arg(
@@ -467,21 +548,12 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         )
                         .takes_value(true),
                 )
-                .arg(
-                    Arg::with_name("format")
-                        .short("F")
-                        .long("format")
-                        .help("Output format")
-                        .possible_values(&["human", "csv"])
-                        .default_value("human")
-                        .takes_value(true),
-                )
                 .arg(
                     Arg::with_name("hidden_format")
                         .short("f")
                         .hidden(true)
                         .help("Output format")
-                        .possible_values(&["human", "csv"])
+                        .possible_values(OUTPUT_FORMATS)
                         .takes_value(true),
                 )
                 .arg(
@@ -494,9 +566,88 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 ),
         );
 
+    let circuit_disband_subcommand = SubCommand::with_name("disband")
+        .about("Propose to disband an existing circuit")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .takes_value(true)
+                .help("URL of Splinter Daemon"),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Path to private key file"),
+        )
+        .arg(
+            Arg::with_name("circuit_id")
+                .value_name("circuit-id")
+                .takes_value(true)
+                .required(true)
+                .help("ID of the circuit to be disbanded"),
+        );
+
+    #[cfg(feature = "database-archive")]
+    let circuit_disband_subcommand = circuit_disband_subcommand
+        .arg(
+            Arg::with_name("archive")
+                .long("archive")
+                .value_name("file")
+                .takes_value(true)
+                .long_help(
+                    "Write the state and receipts of the circuit's scabbard services on this \
+                     node to the given file before proposing to disband it; the file must not exist",
+                ),
+        )
+        .arg(
+            Arg::with_name("connect")
+                .short("C")
+                .long("connect")
+                .takes_value(true)
+                .requires("archive")
+                .help("Database connection URI of the local node, used with --archive"),
+        )
+        .arg(
+            Arg::with_name("state_dir")
+                .long("state-dir")
+                .takes_value(true)
+                .requires("archive")
+                .long_help(
+                    "The location of the state directory, used with --archive to read scabbard \
+                     state that is stored in LMDB files. Defaults to /var/lib/splinter. This \
+                     location can also be changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                     environment variables",
+                ),
+        );
+
+    #[cfg(feature = "circuit-disband-wait")]
+    let circuit_disband_subcommand = circuit_disband_subcommand.arg(
+        Arg::with_name("wait")
+            .long("wait")
+            .value_name("seconds")
+            .takes_value(true)
+            .long_help(
+                "Wait up to the given number of seconds for the members to vote on the disband \
+                 proposal and for the circuit to be disbanded, reporting each vote as it is \
+                 received",
+            ),
+    );
+
+    let circuit_command = circuit_command.subcommand(circuit_disband_subcommand);
+
+    #[cfg(feature = "circuit-update-service")]
     let circuit_command = circuit_command.subcommand(
-        SubCommand::with_name("disband")
-            .about("Propose to disband an existing circuit")
+        SubCommand::with_name("update-service")
+            .about("Propose to change the arguments of a service on an existing circuit")
+            .long_about(
+                "Propose to change the arguments of a service on an existing circuit. The \
+                 arguments that are not set or removed are kept. Once all members accept the \
+                 proposal, the service is restarted with its new arguments.",
+            )
             .arg(
                 Arg::with_name("url")
                     .short("U")
@@ -513,41 +664,105 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                     .help("Path to private key file"),
             )
             .arg(
-                Arg::with_name("circuit_id")
-                    .value_name("circuit-id")
-                    .takes_value(true)
-                    .required(true)
-                    .help("ID of the circuit to be disbanded"),
-            ),
-    );
-
-    let circuit_command = circuit_command.subcommand(
-        SubCommand::with_name("purge")
-            .about("Purge an existing inactive circuit")
-            .arg(
-                Arg::with_name("url")
-                    .short("U")
-                    .long("url")
+                Arg::with_name("argument")
+                    .long("arg")
+                    .value_name("key=value")
                     .takes_value(true)
-                    .help("URL of Splinter Daemon"),
+                    .multiple(true)
+                    .number_of_values(1)
+                    .help("Set an argument of the service"),
             )
             .arg(
-                Arg::with_name("private_key_file")
-                    .value_name("private-key-file")
-                    .short("k")
-                    .long("key")
+                Arg::with_name("remove_argument")
+                    .long("remove-arg")
+                    .value_name("key")
                     .takes_value(true)
-                    .help("Path to private key file"),
+                    .multiple(true)
+                    .number_of_values(1)
+                    .help("Remove an argument of the service"),
+            )
+            .group(
+                ArgGroup::with_name("changes")
+                    .args(&["argument", "remove_argument"])
+                    .multiple(true)
+                    .required(true),
             )
             .arg(
                 Arg::with_name("circuit_id")
                     .value_name("circuit-id")
                     .takes_value(true)
                     .required(true)
-                    .help("ID of the circuit to be purged"),
+                    .help("ID of the circuit"),
+            )
+            .arg(
+                Arg::with_name("service_id")
+                    .value_name("service-id")
+                    .takes_value(true)
+                    .required(true)
+                    .help("ID of the service to be changed"),
             ),
     );
 
+    let circuit_purge_subcommand = SubCommand::with_name("purge")
+        .about("Purge an existing inactive circuit")
+        .arg(
+            Arg::with_name("url")
+                .short("U")
+                .long("url")
+                .takes_value(true)
+                .help("URL of Splinter Daemon"),
+        )
+        .arg(
+            Arg::with_name("private_key_file")
+                .value_name("private-key-file")
+                .short("k")
+                .long("key")
+                .takes_value(true)
+                .help("Path to private key file"),
+        )
+        .arg(
+            Arg::with_name("circuit_id")
+                .value_name("circuit-id")
+                .takes_value(true)
+                .required(true)
+                .help("ID of the circuit to be purged"),
+        );
+
+    #[cfg(feature = "database-archive")]
+    let circuit_purge_subcommand = circuit_purge_subcommand
+        .arg(
+            Arg::with_name("archive")
+                .long("archive")
+                .value_name("file")
+                .takes_value(true)
+                .long_help(
+                    "Write the state and receipts of the circuit's scabbard services on this \
+                     node to the given file before purging it; the file must not exist",
+                ),
+        )
+        .arg(
+            Arg::with_name("connect")
+                .short("C")
+                .long("connect")
+                .takes_value(true)
+                .requires("archive")
+                .help("Database connection URI of the local node, used with --archive"),
+        )
+        .arg(
+            Arg::with_name("state_dir")
+                .long("state-dir")
+                .takes_value(true)
+                .requires("archive")
+                .long_help(
+                    "The location of the state directory, used with --archive to read scabbard \
+                     state that is stored in LMDB files. Defaults to /var/lib/splinter. This \
+                     location can also be changed with the SPLINTER_STATE_DIR or SPLINTER_HOME \
+                     environment variables",
+                ),
+        );
+
+    let circuit_command = circuit_command.subcommand(circuit_purge_subcommand);
+
     let circuit_command = circuit_command.subcommand(
         SubCommand::with_name("abandon")
             .about("Abandon an existing circuit")
@@ -757,19 +972,7 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         SubCommand::with_name("template")
             .about("Manage circuit templates")
             .setting(AppSettings::SubcommandRequiredElseHelp)
-            .subcommand(
-                SubCommand::with_name("list")
-                    .about("List available templates")
-                    .arg(
-                        Arg::with_name("format")
-                            .short("F")
-                            .long("format")
-                            .help("Output format")
-                            .possible_values(&["human", "csv"])
-                            .default_value("human")
-                            .takes_value(true),
-                    ),
-            )
+            .subcommand(SubCommand::with_name("list").about("List available templates"))
             .subcommand(
                 SubCommand::with_name("show").about("Show a template").arg(
                     Arg::with_name("name")
@@ -909,21 +1112,89 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     #[cfg(feature = "database")]
     {
-        app = app.subcommand(
-            SubCommand::with_name("database")
-                .about("Database commands")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("migrate")
-                        .about("Runs database migrations Splinter")
-                        .arg(
-                            Arg::with_name("connect")
-                                .short("C")
-                                .takes_value(true)
-                                .help("Database connection URI"),
-                        ),
-                ),
-        );
+        let database_command = SubCommand::with_name("database")
+            .about("Database commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("migrate")
+                    .about("Runs database migrations Splinter")
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    ),
+            );
+
+        #[cfg(feature = "database-archive")]
+        let database_command = database_command
+            .subcommand(
+                SubCommand::with_name("export")
+                    .about("Export the contents of a Splinter database to an archive directory")
+                    .long_about(
+                        "Export the node ID, circuits, circuit proposals, registry nodes and \
+                        scabbard state of a Splinter database to an archive directory. Biome \
+                        data and role-based access control roles and assignments are not \
+                        exported",
+                    )
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .long("connect")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    )
+                    .arg(
+                        Arg::with_name("archive")
+                            .long("archive")
+                            .required(true)
+                            .takes_value(true)
+                            .help("Directory to write the archive to; must be empty or not exist"),
+                    )
+                    .arg(
+                        Arg::with_name("state_dir")
+                            .long("state-dir")
+                            .long_help(
+                                "The location of the state directory, used to read scabbard \
+                                state that is stored in LMDB files. Defaults to \
+                                /var/lib/splinter. This location can also be changed with the \
+                                SPLINTER_STATE_DIR or SPLINTER_HOME environment variables",
+                            )
+                            .takes_value(true),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("import")
+                    .about("Import the contents of an archive directory into a Splinter database")
+                    .long_about(
+                        "Import the contents of an archive directory, created by \
+                        `splinter database export`, into a Splinter database. The database \
+                        must already be migrated with `splinter database migrate`. The archive \
+                        is imported into a copy of the database, which replaces the database \
+                        only if the whole archive is imported. Role-based access control roles \
+                        and assignments are not part of the archive and must be recreated",
+                    )
+                    .arg(
+                        Arg::with_name("connect")
+                            .short("C")
+                            .long("connect")
+                            .takes_value(true)
+                            .help("Database connection URI"),
+                    )
+                    .arg(
+                        Arg::with_name("archive")
+                            .long("archive")
+                            .required(true)
+                            .takes_value(true)
+                            .help("Directory containing the archive"),
+                    )
+                    .arg(Arg::with_name("force").short("f").long("force").help(
+                        "Import even if the database already contains a node ID, circuits or \
+                        scabbard state",
+                    )),
+            );
+
+        app = app.subcommand(database_command);
 
         app = app.subcommand(
             SubCommand::with_name("state")
@@ -1007,15 +1278,20 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         );
     }
 
-    #[cfg(feature = "authorization-handler-maintenance")]
+    #[cfg(feature = "announcements")]
     {
         app = app.subcommand(
-            SubCommand::with_name("maintenance")
-                .about("Maintenance mode commands")
+            SubCommand::with_name("announcement")
+                .about("Announcement commands")
                 .setting(AppSettings::SubcommandRequiredElseHelp)
                 .subcommand(
-                    SubCommand::with_name("status")
-                        .about("Checks if maintenance mode is enabled for a Splinter node")
+                    SubCommand::with_name("list")
+                        .about("Lists the announcements of a Splinter node")
+                        .arg(
+                            Arg::with_name("include_expired")
+                                .long("include-expired")
+                                .help("Include announcements that have expired"),
+                        )
                         .arg(
                             Arg::with_name("url")
                                 .short("U")
@@ -1033,8 +1309,31 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         ),
                 )
                 .subcommand(
-                    SubCommand::with_name("enable")
-                        .about("Enables maintenance mode for a Splinter node")
+                    SubCommand::with_name("create")
+                        .about("Creates an announcement on a Splinter node")
+                        .arg(
+                            Arg::with_name("message")
+                                .required(true)
+                                .takes_value(true)
+                                .help("Text of the announcement"),
+                        )
+                        .arg(
+                            Arg::with_name("kind")
+                                .long("kind")
+                                .possible_values(&["info", "maintenance", "deprecation"])
+                                .default_value("info")
+                                .takes_value(true)
+                                .help("Kind of announcement"),
+                        )
+                        .arg(
+                            Arg::with_name("expires_in")
+                                .value_name("duration")
+                                .long("expires-in")
+                                .takes_value(true)
+                                .help(
+                                    "Time after which the announcement expires, such as 2d or 6h",
+                                ),
+                        )
                         .arg(
                             Arg::with_name("url")
                                 .short("U")
@@ -1052,8 +1351,14 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         ),
                 )
                 .subcommand(
-                    SubCommand::with_name("disable")
-                        .about("Disables maintenance mode for a Splinter node")
+                    SubCommand::with_name("delete")
+                        .about("Deletes an announcement from a Splinter node")
+                        .arg(
+                            Arg::with_name("id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("ID of the announcement"),
+                        )
                         .arg(
                             Arg::with_name("url")
                                 .short("U")
@@ -1073,43 +1378,250 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         )
     }
 
-    #[cfg(feature = "authorization-handler-rbac")]
+    #[cfg(feature = "peer-blocklist")]
     {
         app = app.subcommand(
-            SubCommand::with_name("role")
-                .about("Role-based authorization role-related commands")
+            SubCommand::with_name("peer")
+                .about("Peer commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("blocklist")
+                        .about("Manage the public keys and IP addresses a Splinter node refuses")
+                        .setting(AppSettings::SubcommandRequiredElseHelp)
+                        .subcommand(
+                            SubCommand::with_name("list")
+                                .about("Lists the blocked public keys and IP addresses")
+                                .arg(
+                                    Arg::with_name("include_expired")
+                                        .long("include-expired")
+                                        .help("Include blocks that have expired"),
+                                )
+                                .arg(
+                                    Arg::with_name("url")
+                                        .short("U")
+                                        .long("url")
+                                        .help("URL of the Splinter daemon REST API")
+                                        .takes_value(true),
+                                )
+                                .arg(
+                                    Arg::with_name("private_key_file")
+                                        .value_name("private-key-file")
+                                        .short("k")
+                                        .long("key")
+                                        .takes_value(true)
+                                        .help("Name or path of private key"),
+                                ),
+                        )
+                        .subcommand(
+                            SubCommand::with_name("add")
+                                .about("Blocks a public key or IP address")
+                                .arg(
+                                    Arg::with_name("kind")
+                                        .required(true)
+                                        .possible_values(&["public_key", "ip"])
+                                        .takes_value(true)
+                                        .help("Kind of target to block"),
+                                )
+                                .arg(
+                                    Arg::with_name("value")
+                                        .required(true)
+                                        .takes_value(true)
+                                        .help("Hex-encoded public key or IP address to block"),
+                                )
+                                .arg(
+                                    Arg::with_name("reason")
+                                        .long("reason")
+                                        .takes_value(true)
+                                        .help("Why the peer is blocked"),
+                                )
+                                .arg(
+                                    Arg::with_name("duration")
+                                        .long("duration")
+                                        .takes_value(true)
+                                        .help("How long the block lasts, such as 2d or 6h"),
+                                )
+                                .arg(
+                                    Arg::with_name("url")
+                                        .short("U")
+                                        .long("url")
+                                        .help("URL of the Splinter daemon REST API")
+                                        .takes_value(true),
+                                )
+                                .arg(
+                                    Arg::with_name("private_key_file")
+                                        .value_name("private-key-file")
+                                        .short("k")
+                                        .long("key")
+                                        .takes_value(true)
+                                        .help("Name or path of private key"),
+                                ),
+                        )
+                        .subcommand(
+                            SubCommand::with_name("remove")
+                                .about("Removes the block of a public key or IP address")
+                                .arg(
+                                    Arg::with_name("kind")
+                                        .required(true)
+                                        .possible_values(&["public_key", "ip"])
+                                        .takes_value(true)
+                                        .help("Kind of blocked target"),
+                                )
+                                .arg(
+                                    Arg::with_name("value")
+                                        .required(true)
+                                        .takes_value(true)
+                                        .help("Hex-encoded public key or IP address"),
+                                )
+                                .arg(
+                                    Arg::with_name("url")
+                                        .short("U")
+                                        .long("url")
+                                        .help("URL of the Splinter daemon REST API")
+                                        .takes_value(true),
+                                )
+                                .arg(
+                                    Arg::with_name("private_key_file")
+                                        .value_name("private-key-file")
+                                        .short("k")
+                                        .long("key")
+                                        .takes_value(true)
+                                        .help("Name or path of private key"),
+                                ),
+                        ),
+                ),
+        )
+    }
+
+    #[cfg(feature = "audit")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("audit")
+                .about("Audit log commands")
                 .setting(AppSettings::SubcommandRequiredElseHelp)
                 .subcommand(
                     SubCommand::with_name("list")
-                        .about("Lists the available roles for a Splinter node")
+                        .about("Lists the events in the audit log of a Splinter node")
+                        .arg(
+                            Arg::with_name("event_type")
+                                .value_name("type")
+                                .long("type")
+                                .possible_values(&[
+                                    "proposal_submitted",
+                                    "proposal_vote",
+                                    "proposal_accepted",
+                                    "proposal_rejected",
+                                    "circuit_ready",
+                                    "circuit_disbanded",
+                                    "circuit_abandoned",
+                                    "circuit_purged",
+                                    "rbac_modified",
+                                    "auth_failed",
+                                ])
+                                .takes_value(true)
+                                .help("Only list events of this type"),
+                        )
                         .arg(
-                            Arg::with_name("url")
-                                .short("U")
-                                .long("url")
-                                .help("URL of the Splinter daemon REST API")
-                                .takes_value(true),
+                            Arg::with_name("actor")
+                                .long("actor")
+                                .takes_value(true)
+                                .help("Only list events caused by this actor"),
                         )
                         .arg(
-                            Arg::with_name("private_key_file")
+                            Arg::with_name("since")
+                                .value_name("duration")
+                                .long("since")
+                                .takes_value(true)
+                                .help("Only list events from the last duration, such as 2d or 6h"),
+                        )
+                        .arg(
+                            Arg::with_name("limit")
+                                .long("limit")
+                                .takes_value(true)
+                                .help("Maximum number of events to list"),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
                                 .value_name("private-key-file")
                                 .short("k")
                                 .long("key")
                                 .takes_value(true)
                                 .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("history")
+                        .about(
+                            "Lists the membership and service changes of a circuit, oldest \
+                             first",
+                        )
+                        .arg(
+                            Arg::with_name("circuit_id")
+                                .value_name("circuit-id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("ID of the circuit"),
+                        )
+                        .arg(
+                            Arg::with_name("since")
+                                .value_name("duration")
+                                .long("since")
+                                .takes_value(true)
+                                .help("Only list changes from the last duration, such as 365d"),
                         )
                         .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "csv"])
-                                .default_value("human")
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
                                 .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                ),
+        )
+    }
+
+    #[cfg(feature = "authorization-handler-maintenance")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("maintenance")
+                .about("Maintenance mode commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("status")
+                        .about("Checks if maintenance mode is enabled for a Splinter node")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
                         ),
                 )
                 .subcommand(
-                    SubCommand::with_name("show")
-                        .about("Show a specific role for a Splinter node")
+                    SubCommand::with_name("enable")
+                        .about("Enables maintenance mode for a Splinter node")
                         .arg(
                             Arg::with_name("url")
                                 .short("U")
@@ -1124,16 +1636,416 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .long("key")
                                 .takes_value(true)
                                 .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("disable")
+                        .about("Disables maintenance mode for a Splinter node")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
                         )
                         .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "json", "yaml"])
-                                .default_value("human")
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                ),
+        )
+    }
+
+    #[cfg(any(feature = "node-config", feature = "support-bundle"))]
+    let node_command = SubCommand::with_name("node")
+        .about("Node diagnostics commands")
+        .setting(AppSettings::SubcommandRequiredElseHelp);
+
+    #[cfg(feature = "node-config")]
+    let node_command = node_command.subcommand(
+        SubCommand::with_name("config")
+            .about("Node configuration commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("show")
+                    .about(
+                        "Shows the configuration a Splinter node is running with, and the \
+                         source of each value",
+                    )
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ),
+            ),
+    );
+
+    #[cfg(feature = "support-bundle")]
+    let node_command = node_command.subcommand(
+        SubCommand::with_name("support-bundle")
+            .about(
+                "Collects the configuration, logs and diagnostics of a Splinter node \
+                 into an archive, with secrets redacted",
+            )
+            .arg(
+                Arg::with_name("url")
+                    .short("U")
+                    .long("url")
+                    .help("URL of the Splinter daemon REST API")
+                    .takes_value(true),
+            )
+            .arg(
+                Arg::with_name("private_key_file")
+                    .value_name("private-key-file")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key"),
+            )
+            .arg(
+                Arg::with_name("config")
+                    .short("c")
+                    .long("config")
+                    .takes_value(true)
+                    .help("Path of the node's configuration file"),
+            )
+            .arg(
+                Arg::with_name("log_dir")
+                    .long("log-dir")
+                    .takes_value(true)
+                    .help("Directory of the node's log files"),
+            )
+            .arg(
+                Arg::with_name("log_lines")
+                    .long("log-lines")
+                    .takes_value(true)
+                    .help("Number of lines to collect from the end of each log file"),
+            )
+            .arg(
+                Arg::with_name("connect")
+                    .short("C")
+                    .long("connect")
+                    .takes_value(true)
+                    .help("Database connection URI of the node"),
+            )
+            .arg(
+                Arg::with_name("output")
+                    .short("o")
+                    .long("output")
+                    .takes_value(true)
+                    .help("Path of the archive to write; the file must not exist"),
+            )
+            .arg(
+                Arg::with_name("exclude")
+                    .long("exclude")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .possible_values(&["config", "logs", "diagnostics", "database"])
+                    .help("Section to leave out of the archive"),
+            ),
+    );
+
+    #[cfg(any(feature = "node-config", feature = "support-bundle"))]
+    {
+        app = app.subcommand(node_command);
+    }
+
+    #[cfg(any(feature = "scabbard-consensus-log", feature = "scabbard-contracts"))]
+    let scabbard_command = SubCommand::with_name("scabbard")
+        .about("Scabbard service commands")
+        .setting(AppSettings::SubcommandRequiredElseHelp);
+
+    #[cfg(feature = "scabbard-consensus-log")]
+    let scabbard_command = scabbard_command.subcommand(
+        SubCommand::with_name("consensus")
+            .about("Scabbard consensus commands")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("log")
+                    .about(
+                        "Lists the consensus events of a scabbard service in the \
+                         order they were recorded, including executed events",
+                    )
+                    .arg(
+                        Arg::with_name("service_id")
+                            .value_name("service-id")
+                            .required(true)
+                            .takes_value(true)
+                            .help(
+                                "Fully qualified ID of the service, in the form \
+                                 <circuit-id>::<service-id>",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ),
+            ),
+    );
+
+    #[cfg(feature = "scabbard-contracts")]
+    let scabbard_command = scabbard_command.subcommand(
+        SubCommand::with_name("contract")
+            .about("Manage the smart contracts of a scabbard service")
+            .setting(AppSettings::SubcommandRequiredElseHelp)
+            .subcommand(
+                SubCommand::with_name("upload")
+                    .about(
+                        "Uploads a smart contract archive (scar), creating its contract registry \
+                         if it does not exist",
+                    )
+                    .arg(
+                        Arg::with_name("service_id")
+                            .value_name("service-id")
+                            .required(true)
+                            .takes_value(true)
+                            .help(
+                                "Fully qualified ID of the service, in the form \
+                                 <circuit-id>::<service-id>",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("scar")
+                            .value_name("scar")
+                            .required(true)
+                            .takes_value(true)
+                            .help("Name and version requirement of the scar (name:version)"),
+                    )
+                    .arg(
+                        Arg::with_name("path")
+                            .short("p")
+                            .long("path")
+                            .value_name("path")
+                            .takes_value(true)
+                            .multiple(true)
+                            .help("Directory to search for the scar; defaults to the scar paths"),
+                    )
+                    .arg(
+                        Arg::with_name("grant_namespace")
+                            .long("grant-namespace")
+                            .value_name("namespace")
+                            .takes_value(true)
+                            .multiple(true)
+                            .help(
+                                "Namespace the contract may read from and write to; the \
+                                 namespace registry is created if it does not exist",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("wait")
+                            .long("wait")
+                            .value_name("seconds")
+                            .takes_value(true)
+                            .help("Seconds to wait for the batches to commit; defaults to 300"),
+                    )
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("list")
+                    .about("Lists the contract registries of a scabbard service")
+                    .arg(
+                        Arg::with_name("service_id")
+                            .value_name("service-id")
+                            .required(true)
+                            .takes_value(true)
+                            .help(
+                                "Fully qualified ID of the service, in the form \
+                                 <circuit-id>::<service-id>",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("show")
+                    .about("Shows the details of a smart contract version")
+                    .arg(
+                        Arg::with_name("service_id")
+                            .value_name("service-id")
+                            .required(true)
+                            .takes_value(true)
+                            .help(
+                                "Fully qualified ID of the service, in the form \
+                                 <circuit-id>::<service-id>",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("contract")
+                            .value_name("contract")
+                            .required(true)
+                            .takes_value(true)
+                            .help("Name and version of the contract, in the form name:version"),
+                    )
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ),
+            )
+            .subcommand(
+                SubCommand::with_name("delete")
+                    .about("Deletes a smart contract version")
+                    .arg(
+                        Arg::with_name("service_id")
+                            .value_name("service-id")
+                            .required(true)
+                            .takes_value(true)
+                            .help(
+                                "Fully qualified ID of the service, in the form \
+                                 <circuit-id>::<service-id>",
+                            ),
+                    )
+                    .arg(
+                        Arg::with_name("contract")
+                            .value_name("contract")
+                            .required(true)
+                            .takes_value(true)
+                            .help("Name and version of the contract, in the form name:version"),
+                    )
+                    .arg(
+                        Arg::with_name("revoke_namespace")
+                            .long("revoke-namespace")
+                            .value_name("namespace")
+                            .takes_value(true)
+                            .multiple(true)
+                            .help("Namespace whose contract permissions are removed"),
+                    )
+                    .arg(
+                        Arg::with_name("wait")
+                            .long("wait")
+                            .value_name("seconds")
+                            .takes_value(true)
+                            .help("Seconds to wait for the batches to commit; defaults to 300"),
+                    )
+                    .arg(
+                        Arg::with_name("url")
+                            .short("U")
+                            .long("url")
+                            .help("URL of the Splinter daemon REST API")
+                            .takes_value(true),
+                    )
+                    .arg(
+                        Arg::with_name("private_key_file")
+                            .value_name("private-key-file")
+                            .short("k")
+                            .long("key")
+                            .takes_value(true)
+                            .help("Name or path of private key"),
+                    ),
+            ),
+    );
+
+    #[cfg(any(feature = "scabbard-consensus-log", feature = "scabbard-contracts"))]
+    {
+        app = app.subcommand(scabbard_command);
+    }
+
+    #[cfg(feature = "authorization-handler-rbac")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("role")
+                .about("Role-based authorization role-related commands")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("Lists the available roles for a Splinter node")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("Show a specific role for a Splinter node")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
                                 .takes_value(true),
                         )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
                         .arg(
                             Arg::with_name("role_id")
                                 .required(true)
@@ -1177,9 +2089,18 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .takes_value(true)
                                 .multiple(true)
                                 .number_of_values(1)
-                                .required(true)
+                                .required_unless("parent")
                                 .help("A permission allowed by the role"),
                         )
+                        .arg(
+                            Arg::with_name("parent")
+                                .value_name("role-id")
+                                .long("parent")
+                                .takes_value(true)
+                                .multiple(true)
+                                .number_of_values(1)
+                                .help("A role whose permissions are inherited by the role"),
+                        )
                         .arg(
                             Arg::with_name("role_id")
                                 .required(true)
@@ -1268,6 +2189,86 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .help("Validate the command without performing the role update"),
                         ),
                 )
+                .subcommand(
+                    SubCommand::with_name("add-parent")
+                        .about("Add parent roles, whose permissions are inherited, to a role")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("role_id")
+                                .required(true)
+                                .takes_value(true)
+                                .value_name("ROLE ID")
+                                .help("ID of role to be updated"),
+                        )
+                        .arg(
+                            Arg::with_name("parent_id")
+                                .required(true)
+                                .takes_value(true)
+                                .multiple(true)
+                                .value_name("PARENT ROLE ID")
+                                .help("ID of a role to be inherited from"),
+                        )
+                        .arg(
+                            Arg::with_name("dry_run")
+                                .long("dry-run")
+                                .short("n")
+                                .help("Validate the command without performing the role update"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("rm-parent")
+                        .about("Remove parent roles from a role")
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("role_id")
+                                .required(true)
+                                .takes_value(true)
+                                .value_name("ROLE ID")
+                                .help("ID of role to be updated"),
+                        )
+                        .arg(
+                            Arg::with_name("parent_id")
+                                .required(true)
+                                .takes_value(true)
+                                .multiple(true)
+                                .value_name("PARENT ROLE ID")
+                                .help("ID of a role to no longer be inherited from"),
+                        )
+                        .arg(
+                            Arg::with_name("dry_run")
+                                .long("dry-run")
+                                .short("n")
+                                .help("Validate the command without performing the role update"),
+                        ),
+                )
                 .subcommand(
                     SubCommand::with_name("delete")
                         .about("Delete a specific role from a Splinter node")
@@ -1321,15 +2322,6 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .long("key")
                                 .takes_value(true)
                                 .help("Name or path of private key"),
-                        )
-                        .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "csv"])
-                                .default_value("human")
-                                .takes_value(true),
                         ),
                 )
                 .subcommand(
@@ -1350,15 +2342,6 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .takes_value(true)
                                 .help("Name or path of private key"),
                         )
-                        .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "json", "yaml"])
-                                .default_value("human")
-                                .takes_value(true),
-                        )
                         .arg(
                             Arg::with_name("id_key")
                                 .value_name("public-key")
@@ -1424,6 +2407,27 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                 .required(true)
                                 .help("A role to be assigned to the provided identity"),
                         )
+                        .arg(
+                            Arg::with_name("expires_at")
+                                .value_name("timestamp")
+                                .long("expires-at")
+                                .takes_value(true)
+                                .conflicts_with("expires_in")
+                                .help(
+                                    "The time the assignment expires, in seconds since the Unix \
+                                    epoch",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("expires_in")
+                                .value_name("duration")
+                                .long("expires-in")
+                                .takes_value(true)
+                                .conflicts_with("expires_at")
+                                .help(
+                                    "The time until the assignment expires, such as 12h or 30d",
+                                ),
+                        )
                         .arg(
                             Arg::with_name("dry_run")
                                 .long("dry-run")
@@ -1503,6 +2507,33 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                                     identity",
                                 ),
                         )
+                        .arg(
+                            Arg::with_name("expires_at")
+                                .value_name("timestamp")
+                                .long("expires-at")
+                                .takes_value(true)
+                                .conflicts_with("expires_in")
+                                .help(
+                                    "The time the assignment expires, in seconds since the Unix \
+                                    epoch",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("expires_in")
+                                .value_name("duration")
+                                .long("expires-in")
+                                .takes_value(true)
+                                .conflicts_with("expires_at")
+                                .help(
+                                    "The time until the assignment expires, such as 12h or 30d",
+                                ),
+                        )
+                        .arg(
+                            Arg::with_name("no_expiration")
+                                .long("no-expiration")
+                                .conflicts_with_all(&["expires_at", "expires_in"])
+                                .help("Remove the assignment's expiration time"),
+                        )
                         .arg(
                             Arg::with_name("dry_run")
                                 .long("dry-run")
@@ -1556,20 +2587,82 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                         ),
                 )
         );
+
+        app = app.subcommand(
+            SubCommand::with_name("rbac")
+                .about("Export and import all roles and authorized identities")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("export")
+                        .about(
+                            "Exports all roles and authorized identities of a Splinter node as \
+                            YAML",
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .value_name("file")
+                                .short("o")
+                                .long("output")
+                                .takes_value(true)
+                                .help("File to write the export to; defaults to stdout"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("import")
+                        .about(
+                            "Adds or replaces roles and authorized identities on a Splinter node \
+                            from a YAML export",
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        )
+                        .arg(
+                            Arg::with_name("file")
+                                .value_name("file")
+                                .takes_value(true)
+                                .required(true)
+                                .help("YAML file containing the roles and authorized identities"),
+                        )
+                        .arg(
+                            Arg::with_name("dry_run")
+                                .long("dry-run")
+                                .short("n")
+                                .help("Report the changes without applying them"),
+                        ),
+                ),
+        );
     }
 
     app = app.subcommand(
         SubCommand::with_name("permissions")
             .about("Lists REST API permissions for a Splinter node")
-            .arg(
-                Arg::with_name("format")
-                    .short("F")
-                    .long("format")
-                    .help("Output format")
-                    .possible_values(&["human", "csv", "json"])
-                    .default_value("human")
-                    .takes_value(true),
-            )
             .arg(
                 Arg::with_name("url")
                     .short("U")
@@ -1587,6 +2680,154 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
             ),
     );
 
+    #[cfg(feature = "transfer")]
+    {
+        app = app.subcommand(
+            SubCommand::with_name("transfer")
+                .about("Commands to send and receive files through transfer services")
+                .setting(AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(
+                    SubCommand::with_name("send")
+                        .about("Sends a file to the members of a circuit")
+                        .arg(
+                            Arg::with_name("service_id")
+                                .value_name("circuit::service")
+                                .long("service-id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("Fully-qualified ID of the transfer service"),
+                        )
+                        .arg(
+                            Arg::with_name("file")
+                                .required(true)
+                                .takes_value(true)
+                                .help("Path of the file to send"),
+                        )
+                        .arg(
+                            Arg::with_name("name")
+                                .long("name")
+                                .takes_value(true)
+                                .help("Name to send the file as; defaults to the file's name"),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("list")
+                        .about("Lists the files sent and received by a transfer service")
+                        .arg(
+                            Arg::with_name("service_id")
+                                .value_name("circuit::service")
+                                .long("service-id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("Fully-qualified ID of the transfer service"),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("show")
+                        .about("Shows a file, including the progress of an incoming transfer")
+                        .arg(
+                            Arg::with_name("service_id")
+                                .value_name("circuit::service")
+                                .long("service-id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("Fully-qualified ID of the transfer service"),
+                        )
+                        .arg(
+                            Arg::with_name("file_id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("ID of the file"),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                )
+                .subcommand(
+                    SubCommand::with_name("download")
+                        .about("Downloads a file that has been completely sent or received")
+                        .arg(
+                            Arg::with_name("service_id")
+                                .value_name("circuit::service")
+                                .long("service-id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("Fully-qualified ID of the transfer service"),
+                        )
+                        .arg(
+                            Arg::with_name("file_id")
+                                .required(true)
+                                .takes_value(true)
+                                .help("ID of the file"),
+                        )
+                        .arg(
+                            Arg::with_name("output")
+                                .short("o")
+                                .long("output")
+                                .takes_value(true)
+                                .help("Path to write the file to; defaults to the file's name"),
+                        )
+                        .arg(
+                            Arg::with_name("url")
+                                .short("U")
+                                .long("url")
+                                .help("URL of the Splinter daemon REST API")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::with_name("private_key_file")
+                                .value_name("private-key-file")
+                                .short("k")
+                                .long("key")
+                                .takes_value(true)
+                                .help("Name or path of private key"),
+                        ),
+                ),
+        );
+    }
+
     #[cfg(feature = "user")]
     {
         app = app.subcommand(
@@ -1595,15 +2836,6 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 .subcommand(
                     SubCommand::with_name("list")
                         .about("List Splinter users, including Biome and OAuth users")
-                        .arg(
-                            Arg::with_name("format")
-                                .short("F")
-                                .long("format")
-                                .help("Output format")
-                                .possible_values(&["human", "csv"])
-                                .default_value("human")
-                                .takes_value(true),
-                        )
                         .arg(
                             Arg::with_name("url")
                                 .short("U")
@@ -1995,6 +3227,12 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
 
     let matches = app.get_matches_from_safe(args)?;
 
+    // The signing key URI is read by `load_signer`, like the environment variable it overrides
+    #[cfg(feature = "pkcs11-signer")]
+    if let Some(signing_key_uri) = matches.value_of("signing_key_uri") {
+        std::env::set_var(signing::SIGNING_KEY_URI_ENV, signing_key_uri);
+    }
+
     // set default to info
     let log_level = if matches.is_present("quiet") {
         log::LevelFilter::Error
@@ -2043,6 +3281,10 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         .with_command("abandon", circuit::CircuitAbandonAction)
         .with_command("purge", circuit::CircuitPurgeAction);
 
+    #[cfg(feature = "circuit-update-service")]
+    let circuit_command =
+        circuit_command.with_command("update-service", circuit::CircuitUpdateServiceAction);
+
     let circuit_command =
         circuit_command.with_command("remove-proposal", circuit::RemoveProposalAction);
 
@@ -2068,10 +3310,15 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
     #[cfg(feature = "database")]
     {
         use action::database;
-        subcommands = subcommands.with_command(
-            "database",
-            SubcommandActions::new().with_command("migrate", database::MigrateAction),
-        );
+        let database_command =
+            SubcommandActions::new().with_command("migrate", database::MigrateAction);
+
+        #[cfg(feature = "database-archive")]
+        let database_command = database_command
+            .with_command("export", database::ExportAction)
+            .with_command("import", database::ImportAction);
+
+        subcommands = subcommands.with_command("database", database_command);
 
         subcommands = subcommands.with_command(
             "state",
@@ -2085,6 +3332,44 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
         subcommands = subcommands.with_command("upgrade", database::UpgradeAction);
     }
 
+    #[cfg(feature = "announcements")]
+    {
+        use action::announcement;
+        subcommands = subcommands.with_command(
+            "announcement",
+            SubcommandActions::new()
+                .with_command("list", announcement::ListAction)
+                .with_command("create", announcement::CreateAction)
+                .with_command("delete", announcement::DeleteAction),
+        )
+    }
+
+    #[cfg(feature = "peer-blocklist")]
+    {
+        use action::peer;
+        subcommands = subcommands.with_command(
+            "peer",
+            SubcommandActions::new().with_command(
+                "blocklist",
+                SubcommandActions::new()
+                    .with_command("list", peer::ListBlocksAction)
+                    .with_command("add", peer::AddBlockAction)
+                    .with_command("remove", peer::RemoveBlockAction),
+            ),
+        )
+    }
+
+    #[cfg(feature = "audit")]
+    {
+        use action::audit;
+        subcommands = subcommands.with_command(
+            "audit",
+            SubcommandActions::new()
+                .with_command("list", audit::ListAction)
+                .with_command("history", audit::HistoryAction),
+        )
+    }
+
     #[cfg(feature = "authorization-handler-maintenance")]
     {
         use action::maintenance;
@@ -2096,6 +3381,47 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 .with_command("disable", maintenance::DisableAction),
         )
     }
+    #[cfg(any(feature = "node-config", feature = "support-bundle"))]
+    {
+        let node_command = SubcommandActions::new();
+
+        #[cfg(feature = "node-config")]
+        let node_command = node_command.with_command(
+            "config",
+            SubcommandActions::new().with_command("show", action::node_config::ShowConfigAction),
+        );
+
+        #[cfg(feature = "support-bundle")]
+        let node_command =
+            node_command.with_command("support-bundle", action::node::SupportBundleAction);
+
+        subcommands = subcommands.with_command("node", node_command);
+    }
+
+    #[cfg(any(feature = "scabbard-consensus-log", feature = "scabbard-contracts"))]
+    {
+        use action::scabbard;
+
+        let scabbard_command = SubcommandActions::new();
+
+        #[cfg(feature = "scabbard-consensus-log")]
+        let scabbard_command = scabbard_command.with_command(
+            "consensus",
+            SubcommandActions::new().with_command("log", scabbard::ConsensusLogAction),
+        );
+
+        #[cfg(feature = "scabbard-contracts")]
+        let scabbard_command = scabbard_command.with_command(
+            "contract",
+            SubcommandActions::new()
+                .with_command("upload", scabbard::contract::ContractUploadAction)
+                .with_command("list", scabbard::contract::ContractListAction)
+                .with_command("show", scabbard::contract::ContractShowAction)
+                .with_command("delete", scabbard::contract::ContractDeleteAction),
+        );
+
+        subcommands = subcommands.with_command("scabbard", scabbard_command);
+    }
     #[cfg(feature = "authorization-handler-rbac")]
     {
         use action::rbac;
@@ -2105,6 +3431,8 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                 SubcommandActions::new()
                     .with_command("create", rbac::CreateRoleAction)
                     .with_command("update", rbac::UpdateRoleAction)
+                    .with_command("add-parent", rbac::AddRoleParentsAction)
+                    .with_command("rm-parent", rbac::RemoveRoleParentsAction)
                     .with_command("delete", rbac::DeleteRoleAction)
                     .with_command("list", rbac::ListRolesAction)
                     .with_command("show", rbac::ShowRoleAction),
@@ -2118,10 +3446,29 @@ fn run<I: IntoIterator<Item = T>, T: Into<OsString> + Clone>(args: I) -> Result<
                     .with_command("update", rbac::UpdateAssignmentAction)
                     .with_command("delete", rbac::DeleteAssignmentAction),
             )
+            .with_command(
+                "rbac",
+                SubcommandActions::new()
+                    .with_command("export", rbac::ExportRbacAction)
+                    .with_command("import", rbac::ImportRbacAction),
+            )
     }
 
     subcommands = subcommands.with_command("permissions", permissions::ListAction);
 
+    #[cfg(feature = "transfer")]
+    {
+        use action::transfer;
+        subcommands = subcommands.with_command(
+            "transfer",
+            SubcommandActions::new()
+                .with_command("send", transfer::SendAction)
+                .with_command("list", transfer::ListAction)
+                .with_command("show", transfer::ShowAction)
+                .with_command("download", transfer::DownloadAction),
+        )
+    }
+
     #[cfg(feature = "user")]
     {
         use action::user;
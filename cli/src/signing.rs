@@ -18,9 +18,23 @@ use cylinder::{
     current_user_key_name, current_user_search_path, jwt::JsonWebTokenBuilder, load_key,
     load_key_from_path, secp256k1::Secp256k1Context, Context, PrivateKey, Signer,
 };
+#[cfg(feature = "pkcs11-signer")]
+use splinter::signing::pkcs11::{Pkcs11Signer, Pkcs11Uri};
+#[cfg(feature = "vault-key-provider")]
+use splinter::signing::{vault::VaultKeyProvider, KeyProvider};
 
 use crate::error::CliError;
 
+/// The environment variable that holds the PKCS#11 URI of the key to sign with, which is set by
+/// the `--signing-key-uri` option
+#[cfg(feature = "pkcs11-signer")]
+pub const SIGNING_KEY_URI_ENV: &str = "SPLINTER_SIGNING_KEY_URI";
+#[cfg(feature = "pkcs11-signer")]
+const PKCS11_URI_PREFIX: &str = "pkcs11:";
+/// The environment variable that holds the URL of the Vault secret to read keys from
+#[cfg(feature = "vault-key-provider")]
+pub const VAULT_KEYS_URL_ENV: &str = "SPLINTER_VAULT_KEYS_URL";
+
 // If the `CYLINDER_PATH` environment variable is not set, add `$HOME/.splinter/keys`
 // to the vector of paths to search. This is for backwards compatibility.
 fn splinter_user_search_path() -> Vec<PathBuf> {
@@ -40,7 +54,36 @@ fn splinter_user_search_path() -> Vec<PathBuf> {
     }
 }
 
+/// Loads the key with the given name, or the current user's key, from the Vault secret
+/// identified by `url`.
+#[cfg(feature = "vault-key-provider")]
+fn load_vault_private_key(url: &str, key_name: Option<&str>) -> Result<PrivateKey, CliError> {
+    let key_name = key_name
+        .map(String::from)
+        .unwrap_or_else(current_user_key_name);
+    VaultKeyProvider::from_env(url)
+        .and_then(|provider| provider.get_key(&key_name))
+        .map_err(|err| {
+            CliError::ActionError(format!("Unable to load signing key from Vault: {}", err))
+        })?
+        .ok_or_else(|| {
+            CliError::ActionError(format!("No signing key named {} found in Vault", key_name))
+        })
+}
+
 fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
+    #[cfg(feature = "vault-key-provider")]
+    {
+        if let Ok(url) = env::var(VAULT_KEYS_URL_ENV) {
+            if !key_name
+                .map(|key_name| key_name.contains('/'))
+                .unwrap_or(false)
+            {
+                return load_vault_private_key(&url, key_name);
+            }
+        }
+    }
+
     let private_key = if let Some(key_name) = key_name {
         if key_name.contains('/') {
             load_key_from_path(Path::new(key_name))
@@ -83,10 +126,42 @@ fn load_private_key(key_name: Option<&str>) -> Result<PrivateKey, CliError> {
     Ok(private_key)
 }
 
+/// Loads the signer for the given key name or path, or for the default key if none is given.
+///
+/// With the `pkcs11-signer` feature, the key held by a PKCS#11 token is used instead if the
+/// `SPLINTER_SIGNING_KEY_URI` environment variable is set, or if the key name is a PKCS#11 URI.
+///
+/// With the `vault-key-provider` feature, a key given by name, or the default key, is read from
+/// the Vault secret identified by the `SPLINTER_VAULT_KEYS_URL` environment variable, if it is
+/// set.
 pub fn load_signer(key_name: Option<&str>) -> Result<Box<dyn Signer>, CliError> {
+    #[cfg(feature = "pkcs11-signer")]
+    {
+        let signing_key_uri = match env::var(SIGNING_KEY_URI_ENV) {
+            Ok(uri) => Some(uri),
+            Err(_) => key_name
+                .filter(|key_name| key_name.starts_with(PKCS11_URI_PREFIX))
+                .map(String::from),
+        };
+        if let Some(uri) = signing_key_uri {
+            return load_pkcs11_signer(&uri);
+        }
+    }
+
     Ok(Secp256k1Context::new().new_signer(load_private_key(key_name)?))
 }
 
+#[cfg(feature = "pkcs11-signer")]
+fn load_pkcs11_signer(uri: &str) -> Result<Box<dyn Signer>, CliError> {
+    let uri = uri
+        .parse::<Pkcs11Uri>()
+        .map_err(|err| CliError::ActionError(format!("Invalid signing key URI: {}", err)))?;
+    let signer = Pkcs11Signer::new(&uri).map_err(|err| {
+        CliError::ActionError(format!("Unable to load signing key from token: {}", err))
+    })?;
+    Ok(Box::new(signer))
+}
+
 pub fn create_cylinder_jwt_auth(signer: Box<dyn Signer>) -> Result<String, CliError> {
     let encoded_token = JsonWebTokenBuilder::new()
         .build(&*signer)
@@ -22,5 +22,7 @@ pub mod messages;
 pub mod service;
 pub mod store;
 mod token;
+#[cfg(feature = "admin-webhooks")]
+pub mod webhook;
 
 pub const CIRCUIT_PROTOCOL_VERSION: i32 = 2;
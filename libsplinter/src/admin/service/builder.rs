@@ -22,6 +22,12 @@ use cylinder::Verifier as SignatureVerifier;
 
 use crate::admin::lifecycle::LifecycleDispatch;
 use crate::admin::store::AdminServiceStore;
+#[cfg(feature = "admin-service-minimum-authorization")]
+use crate::admin::store::AuthorizationType;
+#[cfg(feature = "admin-service-circuit-version")]
+use crate::admin::CIRCUIT_PROTOCOL_VERSION;
+#[cfg(feature = "audit")]
+use crate::audit::store::AuditStore;
 use crate::circuit::routing::RoutingTableWriter;
 use crate::error::InvalidStateError;
 use crate::keys::KeyPermissionManager;
@@ -29,6 +35,8 @@ use crate::peer::PeerManagerConnector;
 use crate::public_key::PublicKey;
 use crate::service::instance::ServiceArgValidator;
 
+#[cfg(feature = "admin-service-proposal-limits")]
+use super::limits::ProposalLimits;
 use super::shared::AdminServiceShared;
 use super::{admin_service_id, AdminKeyVerifier, AdminService};
 
@@ -53,6 +61,16 @@ pub struct AdminServiceBuilder {
     routing_table_writer: Option<Box<dyn RoutingTableWriter>>,
     event_store: Option<Box<dyn AdminServiceStore>>,
     public_keys: Option<Vec<PublicKey>>,
+    #[cfg(feature = "audit")]
+    audit_store: Option<Box<dyn AuditStore>>,
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    member_wait_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-minimum-authorization")]
+    minimum_authorization_type: Option<AuthorizationType>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    proposal_limits: Option<ProposalLimits>,
+    #[cfg(feature = "admin-service-circuit-version")]
+    circuit_version: Option<i32>,
 }
 
 impl AdminServiceBuilder {
@@ -158,6 +176,64 @@ impl AdminServiceBuilder {
         self
     }
 
+    /// Sets the store that proposal, vote and circuit lifecycle events are recorded in.
+    #[cfg(feature = "audit")]
+    pub fn with_audit_store(mut self, audit_store: Box<dyn AuditStore>) -> Self {
+        self.audit_store = Some(audit_store);
+
+        self
+    }
+
+    /// Sets how long a submitted circuit management payload waits for its members to connect
+    /// and agree on a protocol before it is dropped.
+    ///
+    /// Payloads whose members are unreachable are held and delivered once the members connect.
+    /// By default, they are held indefinitely.
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    pub fn with_member_wait_timeout(mut self, member_wait_timeout: Duration) -> Self {
+        self.member_wait_timeout = Some(member_wait_timeout);
+
+        self
+    }
+
+    /// Sets the weakest authorization type that circuits on this node may use.
+    ///
+    /// With `AuthorizationType::Challenge`, proposals for trust-authorized circuits are rejected
+    /// and the members of existing trust-authorized circuits are not connected to. By default,
+    /// circuits may use any authorization type.
+    #[cfg(feature = "admin-service-minimum-authorization")]
+    pub fn with_minimum_authorization_type(
+        mut self,
+        minimum_authorization_type: AuthorizationType,
+    ) -> Self {
+        self.minimum_authorization_type = Some(minimum_authorization_type);
+
+        self
+    }
+
+    /// Sets the limits on the size and complexity of the circuits that may be proposed to this
+    /// node.
+    ///
+    /// By default, proposals are not limited.
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn with_proposal_limits(mut self, proposal_limits: ProposalLimits) -> Self {
+        self.proposal_limits = Some(proposal_limits);
+
+        self
+    }
+
+    /// Sets the newest circuit version that this node proposes and accepts.
+    ///
+    /// Proposals for newer circuits are rejected, as are the features of newer circuit versions,
+    /// such as display names and circuit disband, so that a node does not agree to a circuit its
+    /// peers cannot parse. By default, the newest supported circuit version is used.
+    #[cfg(feature = "admin-service-circuit-version")]
+    pub fn with_circuit_version(mut self, circuit_version: i32) -> Self {
+        self.circuit_version = Some(circuit_version);
+
+        self
+    }
+
     /// Constructs the AdminService.
     ///
     /// # Errors
@@ -190,6 +266,16 @@ impl AdminServiceBuilder {
             )
         })?;
 
+        #[cfg(feature = "admin-service-circuit-version")]
+        if let Some(circuit_version) = self.circuit_version {
+            if !(1..=CIRCUIT_PROTOCOL_VERSION).contains(&circuit_version) {
+                return Err(InvalidStateError::with_message(format!(
+                    "An admin service's circuit version must be between 1 and {}, not {}",
+                    CIRCUIT_PROTOCOL_VERSION, circuit_version
+                )));
+            }
+        }
+
         let signature_verifier = self.signature_verifier.ok_or_else(|| {
             InvalidStateError::with_message("An admin service requires a signature_verifier".into())
         })?;
@@ -219,7 +305,8 @@ impl AdminServiceBuilder {
 
         let public_keys = self.public_keys.unwrap_or_default();
 
-        let admin_service_shared = Arc::new(Mutex::new(AdminServiceShared::new(
+        #[allow(unused_mut)]
+        let mut admin_service_shared = AdminServiceShared::new(
             node_id.clone(),
             lifecycle_dispatch,
             service_arg_validators,
@@ -231,7 +318,18 @@ impl AdminServiceBuilder {
             routing_table_writer,
             admin_event_store,
             public_keys,
-        )));
+        );
+        #[cfg(feature = "audit")]
+        admin_service_shared.set_audit_store(self.audit_store);
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        admin_service_shared.set_member_wait_timeout(self.member_wait_timeout);
+        #[cfg(feature = "admin-service-minimum-authorization")]
+        admin_service_shared.set_minimum_authorization_type(self.minimum_authorization_type);
+        #[cfg(feature = "admin-service-proposal-limits")]
+        admin_service_shared.set_proposal_limits(self.proposal_limits);
+        #[cfg(feature = "admin-service-circuit-version")]
+        admin_service_shared.set_circuit_version(self.circuit_version);
+        let admin_service_shared = Arc::new(Mutex::new(admin_service_shared));
 
         Ok(AdminService {
             service_id,
@@ -29,6 +29,8 @@ use crate::consensus::{
     ProposalUpdate,
 };
 use crate::consensus::{ConsensusEngine, StartupState};
+#[cfg(feature = "admin-service-consensus-status")]
+use crate::error::InternalError;
 use crate::error::InvalidStateError;
 use crate::hex::to_hex;
 use crate::peer::PeerTokenPair;
@@ -59,6 +61,17 @@ impl AdminConsensusManager {
         let (consensus_msg_tx, consensus_msg_rx) = channel();
         let (proposal_update_tx, proposal_update_rx) = channel();
 
+        let mut two_phase_engine = TwoPhaseEngine::new(coordinator_timeout);
+        #[cfg(feature = "admin-service-consensus-status")]
+        shared
+            .lock()
+            .map_err(|_| {
+                AdminConsensusManagerError(Box::new(InternalError::with_message(
+                    "the admin shared lock was poisoned".into(),
+                )))
+            })?
+            .set_consensus_status_reader(Some(two_phase_engine.status_reader()));
+
         let proposal_manager =
             AdminProposalManager::new(proposal_update_tx.clone(), shared.clone());
         let consensus_network_sender = AdminConsensusNetworkSender::new(service_id.clone(), shared);
@@ -71,7 +84,6 @@ impl AdminConsensusManager {
         let thread_handle = Builder::new()
             .name(format!("consensus-{}", service_id))
             .spawn(move || {
-                let mut two_phase_engine = TwoPhaseEngine::new(coordinator_timeout);
                 if let Err(err) = two_phase_engine.run(
                     consensus_msg_rx,
                     proposal_update_rx,
@@ -164,6 +176,10 @@ impl ProposalManager for AdminProposalManager {
             .shared
             .lock()
             .map_err(|_| ServiceError::PoisonedLock("the admin state lock was poisoned".into()))?;
+        // The engine asks for a proposal whenever it is idle, so this is also where payloads that
+        // have waited too long for their members are dropped
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        shared.expire_waiting_payloads();
         if let Some(circuit_payload) = shared.pop_pending_circuit_payload() {
             let (expected_hash, circuit_proposal) = shared
                 .propose_change(circuit_payload.clone())
@@ -0,0 +1,210 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The state of the circuit management payloads that the admin service has not yet committed or
+//! rejected.
+
+use std::fmt;
+use std::time::Duration;
+
+use crate::consensus::PeerId;
+use crate::protos::admin::{CircuitManagementPayload, CircuitManagementPayload_Action};
+
+const ADMIN_SERVICE_ID_PREFIX: &str = "admin::";
+
+/// How far a circuit management payload has progressed through the admin service.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConsensusStage {
+    /// The local node is waiting to peer with some of the circuit's members
+    AwaitingPeers,
+    /// The local admin service is waiting to agree on a protocol version with some of the
+    /// members' admin services
+    AwaitingProtocol,
+    /// The payload is waiting for consensus to evaluate it
+    Queued,
+    /// Consensus is evaluating the payload and waiting for the members to agree on it
+    AwaitingAgreement,
+}
+
+impl ConsensusStage {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConsensusStage::AwaitingPeers => "awaiting_peers",
+            ConsensusStage::AwaitingProtocol => "awaiting_protocol",
+            ConsensusStage::Queued => "queued",
+            ConsensusStage::AwaitingAgreement => "awaiting_agreement",
+        }
+    }
+}
+
+impl fmt::Display for ConsensusStage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// The state of a circuit management payload that has not yet been committed or rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PayloadConsensusStatus {
+    pub(super) circuit_id: String,
+    pub(super) action: String,
+    pub(super) payload_hash: String,
+    pub(super) stage: ConsensusStage,
+    pub(super) coordinator: Option<String>,
+    pub(super) is_coordinator: bool,
+    pub(super) verified: Vec<String>,
+    pub(super) awaiting: Vec<String>,
+    pub(super) coordinator_timeout_remaining: Option<Duration>,
+}
+
+impl PayloadConsensusStatus {
+    /// Creates the status of a payload that is not being evaluated by consensus.
+    pub(super) fn new(
+        payload: &CircuitManagementPayload,
+        payload_hash: String,
+        stage: ConsensusStage,
+    ) -> Self {
+        PayloadConsensusStatus {
+            circuit_id: payload_circuit_id(payload).to_string(),
+            action: format!("{:?}", payload.get_action()),
+            payload_hash,
+            stage,
+            coordinator: None,
+            is_coordinator: false,
+            verified: vec![],
+            awaiting: vec![],
+            coordinator_timeout_remaining: None,
+        }
+    }
+
+    /// Returns the ID of the circuit the payload changes
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the payload's action, such as `CIRCUIT_CREATE_REQUEST`
+    pub fn action(&self) -> &str {
+        &self.action
+    }
+
+    /// Returns the hex-encoded SHA-256 hash of the payload
+    pub fn payload_hash(&self) -> &str {
+        &self.payload_hash
+    }
+
+    pub fn stage(&self) -> ConsensusStage {
+        self.stage
+    }
+
+    /// Returns the node coordinating consensus on the payload, if it is being evaluated
+    pub fn coordinator(&self) -> Option<&str> {
+        self.coordinator.as_deref()
+    }
+
+    /// Returns whether the local node is coordinating consensus on the payload
+    pub fn is_coordinator(&self) -> bool {
+        self.is_coordinator
+    }
+
+    /// Returns the nodes that have agreed to the payload; only known by the coordinator
+    pub fn verified(&self) -> &[String] {
+        &self.verified
+    }
+
+    /// Returns the nodes the payload is waiting on: the members that have not agreed to it, or the
+    /// members that are not peered or have not agreed on a protocol version
+    pub fn awaiting(&self) -> &[String] {
+        &self.awaiting
+    }
+
+    /// Returns the time left before the coordinator rejects the payload; only known by the
+    /// coordinator
+    pub fn coordinator_timeout_remaining(&self) -> Option<Duration> {
+        self.coordinator_timeout_remaining
+    }
+}
+
+/// Returns the node ID (or `public_key::<hex key>`) of the admin service with the given peer ID.
+pub(super) fn verifier_node_id(peer_id: &PeerId) -> String {
+    let service_id = String::from_utf8_lossy(peer_id.as_ref());
+    service_id
+        .strip_prefix(ADMIN_SERVICE_ID_PREFIX)
+        .unwrap_or(&service_id)
+        .to_string()
+}
+
+fn payload_circuit_id(payload: &CircuitManagementPayload) -> &str {
+    match payload.get_action() {
+        CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => payload
+            .get_circuit_create_request()
+            .get_circuit()
+            .get_circuit_id(),
+        CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE => {
+            payload.get_circuit_proposal_vote().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_DISBAND_REQUEST => {
+            payload.get_circuit_disband_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_PURGE_REQUEST => {
+            payload.get_circuit_purge_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_ABANDON => {
+            payload.get_circuit_abandon().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::PROPOSAL_REMOVE_REQUEST => {
+            payload.get_proposal_remove_request().get_circuit_id()
+        }
+        CircuitManagementPayload_Action::CIRCUIT_UPDATE_SERVICE_ARGUMENTS_REQUEST => payload
+            .get_circuit_update_service_arguments_request()
+            .get_circuit_id(),
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::protos::admin::CircuitProposalVote;
+
+    /// Verify that the status of a payload names the circuit and action of the payload.
+    #[test]
+    fn test_payload_consensus_status() {
+        let mut vote = CircuitProposalVote::new();
+        vote.set_circuit_id("WBKLF-BBBBB".into());
+        let mut payload = CircuitManagementPayload::new();
+        payload.set_action(CircuitManagementPayload_Action::CIRCUIT_PROPOSAL_VOTE);
+        payload.set_circuit_proposal_vote(vote);
+
+        let status =
+            PayloadConsensusStatus::new(&payload, "abcd".to_string(), ConsensusStage::Queued);
+        assert_eq!(status.circuit_id(), "WBKLF-BBBBB");
+        assert_eq!(status.action(), "CIRCUIT_PROPOSAL_VOTE");
+        assert_eq!(status.stage().as_str(), "queued");
+        assert_eq!(status.coordinator(), None);
+    }
+
+    /// Verify that the admin service prefix is removed from a verifier's peer ID.
+    #[test]
+    fn test_verifier_node_id() {
+        assert_eq!(
+            verifier_node_id(&PeerId::from(b"admin::acme-node-000".to_vec())),
+            "acme-node-000"
+        );
+        assert_eq!(
+            verifier_node_id(&PeerId::from(b"acme-node-000".to_vec())),
+            "acme-node-000"
+        );
+    }
+}
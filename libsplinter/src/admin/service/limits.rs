@@ -0,0 +1,142 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Limits on the size and complexity of circuit proposals.
+
+use protobuf::Message;
+
+use crate::protos::admin::Circuit;
+
+/// Limits on the circuits that may be proposed to this node.
+///
+/// A proposal is agreed on by the admin services of all of its members, so a very large proposal
+/// may time out partway through consensus. Proposals that exceed a limit are rejected when they
+/// are submitted, and are voted against when they are received from another node. By default,
+/// no limits are set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProposalLimits {
+    max_members: Option<usize>,
+    max_services: Option<usize>,
+    max_metadata_size: Option<usize>,
+    max_circuit_size: Option<usize>,
+}
+
+impl ProposalLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of members of a proposed circuit.
+    pub fn with_max_members(mut self, max_members: usize) -> Self {
+        self.max_members = Some(max_members);
+        self
+    }
+
+    /// Sets the maximum number of services of a proposed circuit.
+    pub fn with_max_services(mut self, max_services: usize) -> Self {
+        self.max_services = Some(max_services);
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a proposed circuit's application metadata.
+    pub fn with_max_metadata_size(mut self, max_metadata_size: usize) -> Self {
+        self.max_metadata_size = Some(max_metadata_size);
+        self
+    }
+
+    /// Sets the maximum size, in bytes, of a proposed circuit as a whole, which includes the
+    /// arguments of its services.
+    pub fn with_max_circuit_size(mut self, max_circuit_size: usize) -> Self {
+        self.max_circuit_size = Some(max_circuit_size);
+        self
+    }
+
+    /// Checks a proposed circuit against the limits, returning a description of the first limit
+    /// that is exceeded.
+    pub(super) fn check(&self, circuit: &Circuit) -> Result<(), String> {
+        check_limit("members", circuit.get_members().len(), self.max_members)?;
+        check_limit("services", circuit.get_roster().len(), self.max_services)?;
+        check_limit(
+            "bytes of application metadata",
+            circuit.get_application_metadata().len(),
+            self.max_metadata_size,
+        )?;
+        check_limit(
+            "bytes",
+            circuit.compute_size() as usize,
+            self.max_circuit_size,
+        )
+    }
+}
+
+fn check_limit(name: &str, value: usize, limit: Option<usize>) -> Result<(), String> {
+    match limit {
+        Some(limit) if value > limit => Err(format!(
+            "Proposed circuit has {} {}, which exceeds this node's limit of {}",
+            value, name, limit
+        )),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::protos::admin::{SplinterNode, SplinterService};
+
+    /// Verify that a circuit is only rejected by the limits that it exceeds.
+    #[test]
+    fn check_limits() {
+        let mut circuit = Circuit::new();
+        circuit.set_members(
+            vec!["node_a", "node_b", "node_c"]
+                .into_iter()
+                .map(|node_id| {
+                    let mut node = SplinterNode::new();
+                    node.set_node_id(node_id.into());
+                    node
+                })
+                .collect(),
+        );
+        circuit.set_roster(vec![SplinterService::new(), SplinterService::new()].into());
+        circuit.set_application_metadata(vec![0; 1024]);
+
+        assert!(ProposalLimits::new().check(&circuit).is_ok());
+        assert!(ProposalLimits::new()
+            .with_max_members(3)
+            .with_max_services(2)
+            .with_max_metadata_size(1024)
+            .with_max_circuit_size(2048)
+            .check(&circuit)
+            .is_ok());
+
+        assert!(ProposalLimits::new()
+            .with_max_members(2)
+            .check(&circuit)
+            .is_err());
+        assert!(ProposalLimits::new()
+            .with_max_services(1)
+            .check(&circuit)
+            .is_err());
+        assert!(ProposalLimits::new()
+            .with_max_metadata_size(1023)
+            .check(&circuit)
+            .is_err());
+        assert!(ProposalLimits::new()
+            .with_max_circuit_size(1024)
+            .check(&circuit)
+            .is_err());
+    }
+}
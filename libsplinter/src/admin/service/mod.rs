@@ -14,9 +14,15 @@
 
 mod builder;
 mod consensus;
+#[cfg(feature = "admin-service-consensus-status")]
+mod consensus_status;
 pub(crate) mod error;
+#[cfg(feature = "admin-service-proposal-limits")]
+mod limits;
 pub(crate) mod messages;
 pub mod proposal_store;
+#[cfg(feature = "admin-service-proposal-validation")]
+mod proposal_validation;
 mod shared;
 mod subscriber;
 
@@ -27,8 +33,8 @@ use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
 use cylinder::Verifier as SignatureVerifier;
-use openssl::hash::{hash, MessageDigest};
 use protobuf::{self, Message};
+use sha2::{Digest, Sha256};
 
 use crate::admin::lifecycle::LifecycleDispatch;
 use crate::admin::store::{self, AdminServiceStore};
@@ -54,9 +60,15 @@ use self::proposal_store::{AdminServiceProposalsFactory, ProposalStoreFactory};
 use self::shared::{get_peer_token_from_service_id, AdminServiceShared, PeerNodePair};
 
 pub use self::builder::AdminServiceBuilder;
+#[cfg(feature = "admin-service-consensus-status")]
+pub use self::consensus_status::{ConsensusStage, PayloadConsensusStatus};
 pub use self::error::AdminKeyVerifierError;
 pub use self::error::AdminServiceError;
 pub use self::error::AdminSubscriberError;
+#[cfg(feature = "admin-service-proposal-limits")]
+pub use self::limits::ProposalLimits;
+#[cfg(feature = "admin-service-proposal-validation")]
+pub use self::proposal_validation::{ProposalCheck, ProposalValidationError};
 pub use self::shared::AdminServiceStatus;
 pub use self::subscriber::AdminServiceEventSubscriber;
 
@@ -83,6 +95,19 @@ pub trait AdminCommands: Send + Sync {
 
     fn admin_service_status(&self) -> Result<AdminServiceStatus, AdminServiceError>;
 
+    /// Validates a circuit create request as the admin service would before proposing the
+    /// circuit, without proposing it, and returns every reason it would be rejected.
+    #[cfg(feature = "admin-service-proposal-validation")]
+    fn validate_circuit_change(
+        &self,
+        circuit_change: &CircuitManagementPayload,
+    ) -> Result<Vec<ProposalValidationError>, AdminServiceError>;
+
+    /// Returns the state of each circuit management payload that has been accepted by the admin
+    /// service, but has not yet been committed or rejected.
+    #[cfg(feature = "admin-service-consensus-status")]
+    fn consensus_statuses(&self) -> Result<Vec<PayloadConsensusStatus>, AdminServiceError>;
+
     fn clone_boxed(&self) -> Box<dyn AdminCommands>;
 }
 
@@ -238,6 +263,41 @@ impl AdminService {
             }
         }
 
+        let proposals: Vec<_> = self
+            .admin_service_shared
+            .lock()
+            .map_err(|_| {
+                ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
+            })?
+            .get_proposals(&[])
+            .map_err(|err| {
+                ServiceStartError::Internal(format!("Unable to get circuit proposals: {}", err))
+            })?
+            .collect();
+
+        // the members of pending proposals are reconnected to before the members of idle circuits
+        #[cfg(feature = "peer-connect-priority")]
+        {
+            let mut priority_peers = vec![];
+            for proposal in proposals.iter() {
+                let members = proposal.circuit().list_nodes().map_err(|err| {
+                    ServiceStartError::Internal(format!(
+                        "Unable to get peer tokens for members: {}",
+                        err
+                    ))
+                })?;
+                priority_peers.extend(
+                    members
+                        .into_iter()
+                        .filter(|member| member.node_id != self.node_id)
+                        .map(|member| member.token),
+                );
+            }
+            if let Err(err) = self.peer_connector.set_priority_peers(priority_peers) {
+                warn!("Unable to prioritize peers of pending proposals: {}", err);
+            }
+        }
+
         let mut peer_refs = vec![];
         // start all services of the supported types
         let mut writer = self
@@ -283,6 +343,27 @@ impl AdminService {
                 ))
             })?;
 
+            // circuits that no longer meet the minimum authorization type are not reconnected
+            #[cfg(feature = "admin-service-minimum-authorization")]
+            let members = match self
+                .admin_service_shared
+                .lock()
+                .map_err(|_| {
+                    ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?
+                .check_minimum_authorization(circuit.authorization_type())
+            {
+                Ok(()) => members,
+                Err(err) => {
+                    warn!(
+                        "Not connecting to the members of circuit {}: {}",
+                        circuit.circuit_id(),
+                        err
+                    );
+                    vec![]
+                }
+            };
+
             // restart all peer in the circuit
             for member in members {
                 if member.node_id != self.node_id {
@@ -424,17 +505,6 @@ impl AdminService {
             }
         }
 
-        let proposals = self
-            .admin_service_shared
-            .lock()
-            .map_err(|_| {
-                ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
-            })?
-            .get_proposals(&[])
-            .map_err(|err| {
-                ServiceStartError::Internal(format!("Unable to get circuit proposals: {}", err))
-            })?;
-
         for proposal in proposals {
             let local_required_auth = proposal
                 .circuit()
@@ -470,6 +540,26 @@ impl AdminService {
                 ))
             })?;
 
+            #[cfg(feature = "admin-service-minimum-authorization")]
+            let members = match self
+                .admin_service_shared
+                .lock()
+                .map_err(|_| {
+                    ServiceStartError::PoisonedLock("the admin shared lock was poisoned".into())
+                })?
+                .check_minimum_authorization(proposal.circuit().authorization_type())
+            {
+                Ok(()) => members,
+                Err(err) => {
+                    warn!(
+                        "Not connecting to the members of proposal {}: {}",
+                        proposal.circuit_id(),
+                        err
+                    );
+                    vec![]
+                }
+            };
+
             // connect to all peers in the circuit proposal
             for member in members.iter() {
                 if member.node_id != self.node_id {
@@ -929,6 +1019,37 @@ impl AdminCommands for AdminServiceCommands {
             .admin_service_status())
     }
 
+    #[cfg(feature = "admin-service-proposal-validation")]
+    fn validate_circuit_change(
+        &self,
+        circuit_change: &CircuitManagementPayload,
+    ) -> Result<Vec<ProposalValidationError>, AdminServiceError> {
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .validate_proposal(circuit_change)
+            .map_err(|err| {
+                AdminServiceError::general_error_with_source(
+                    "Unable to validate circuit change",
+                    Box::new(err),
+                )
+            })
+    }
+
+    #[cfg(feature = "admin-service-consensus-status")]
+    fn consensus_statuses(&self) -> Result<Vec<PayloadConsensusStatus>, AdminServiceError> {
+        self.shared
+            .lock()
+            .map_err(|_| AdminServiceError::general_error("Admin shared lock was lock poisoned"))?
+            .consensus_statuses()
+            .map_err(|err| {
+                AdminServiceError::general_error_with_source(
+                    "Unable to get consensus statuses",
+                    Box::new(err),
+                )
+            })
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminCommands> {
         Box::new(self.clone())
     }
@@ -945,9 +1066,7 @@ where
     let bytes = message
         .write_to_bytes()
         .map_err(|err| Sha256Error(Box::new(err)))?;
-    hash(MessageDigest::sha256(), &bytes)
-        .map(|digest| to_hex(&*digest))
-        .map_err(|err| Sha256Error(Box::new(err)))
+    Ok(to_hex(&Sha256::digest(&bytes)))
 }
 
 fn supported_protocol_version(min: u32, max: u32) -> u32 {
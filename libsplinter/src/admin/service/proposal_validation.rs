@@ -0,0 +1,78 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The result of validating a circuit proposal without submitting it.
+
+use std::fmt;
+
+/// A group of checks the admin service makes before it accepts a circuit proposal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalCheck {
+    /// The payload is well-formed, and is a circuit create request
+    Payload,
+    /// The payload is signed by its requester
+    Signature,
+    /// The requester's key is registered for the requester node, and may propose circuits
+    Requester,
+    /// No circuit or proposal already has the circuit ID
+    DuplicateCircuit,
+    /// The circuit's definition, including its members and services, is valid
+    Circuit,
+}
+
+impl ProposalCheck {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProposalCheck::Payload => "payload",
+            ProposalCheck::Signature => "signature",
+            ProposalCheck::Requester => "requester",
+            ProposalCheck::DuplicateCircuit => "duplicate_circuit",
+            ProposalCheck::Circuit => "circuit",
+        }
+    }
+}
+
+impl fmt::Display for ProposalCheck {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A reason the admin service would reject a circuit proposal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProposalValidationError {
+    check: ProposalCheck,
+    message: String,
+}
+
+impl ProposalValidationError {
+    pub(super) fn new(check: ProposalCheck, message: String) -> Self {
+        Self { check, message }
+    }
+
+    /// Returns the check that failed
+    pub fn check(&self) -> ProposalCheck {
+        self.check
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ProposalValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.check, self.message)
+    }
+}
@@ -16,12 +16,16 @@ use std::collections::{HashMap, HashSet, VecDeque};
 use std::convert::{TryFrom, TryInto};
 use std::iter::ExactSizeIterator;
 use std::sync::mpsc::Sender;
+#[cfg(feature = "admin-service-deferred-proposals")]
+use std::time::Duration;
 use std::time::Instant;
 
 use cylinder::{PublicKey, Signature, Verifier as SignatureVerifier};
 use protobuf::{Message, RepeatedField};
 
 use crate::admin::lifecycle::LifecycleDispatch;
+#[cfg(feature = "admin-service-minimum-authorization")]
+use crate::admin::store::AuthorizationType;
 use crate::admin::store::{
     AdminServiceStore, Circuit as StoreCircuit, CircuitBuilder as StoreCircuitBuilder,
     CircuitPredicate, CircuitProposal as StoreProposal, CircuitStatus as StoreCircuitStatus,
@@ -29,13 +33,19 @@ use crate::admin::store::{
 };
 use crate::admin::token::{PeerAuthorizationTokenReader, PeerNode};
 use crate::admin::CIRCUIT_PROTOCOL_VERSION;
+#[cfg(feature = "audit")]
+use crate::audit::{store::AuditStore, AuditEvent, AuditEventType};
 use crate::circuit::routing::{self, RoutingTableWriter};
+#[cfg(feature = "admin-service-consensus-status")]
+use crate::consensus::two_phase::v1::TwoPhaseStatusReader;
 use crate::consensus::{Proposal, ProposalId, ProposalUpdate};
 use crate::error::InternalError;
 use crate::hex::parse_hex;
 use crate::hex::to_hex;
 use crate::keys::KeyPermissionManager;
 use crate::peer::{PeerAuthorizationToken, PeerManagerConnector, PeerRef, PeerTokenPair};
+#[cfg(feature = "admin-service-update-service-arguments")]
+use crate::protos::admin::CircuitUpdateServiceArgumentsRequest;
 use crate::protos::admin::{
     AbandonedCircuit, AdminMessage, AdminMessage_Type, Circuit, CircuitManagementPayload,
     CircuitManagementPayload_Action, CircuitManagementPayload_Header, CircuitProposal,
@@ -46,9 +56,17 @@ use crate::protos::admin::{
 };
 use crate::public_key;
 use crate::service::instance::{ServiceArgValidator, ServiceError, ServiceNetworkSender};
+#[cfg(feature = "service-payload-codec")]
+use crate::service::payload_codec::{negotiate_payload_encoding, PAYLOAD_ENCODING_ARGUMENT};
 
+#[cfg(feature = "admin-service-consensus-status")]
+use super::consensus_status::{verifier_node_id, ConsensusStage, PayloadConsensusStatus};
 use super::error::{AdminSharedError, MarshallingError};
+#[cfg(feature = "admin-service-proposal-limits")]
+use super::limits::ProposalLimits;
 use super::messages;
+#[cfg(feature = "admin-service-proposal-validation")]
+use super::proposal_validation::{ProposalCheck, ProposalValidationError};
 use super::subscriber::SubscriberMap;
 use super::{admin_service_id, sha256, AdminKeyVerifier, AdminServiceEventSubscriber, Events};
 use super::{ADMIN_SERVICE_PROTOCOL_MIN, ADMIN_SERVICE_PROTOCOL_VERSION};
@@ -77,6 +95,19 @@ pub struct PendingPayload {
     pub payload_type: PayloadType,
     pub message_sender: String,
     pub members: Vec<PeerTokenPair>,
+    // when the payload started waiting for its members
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    pub queued_at: Instant,
+}
+
+#[cfg(feature = "admin-service-consensus-status")]
+impl PendingPayload {
+    fn circuit_management_payload(&self) -> &CircuitManagementPayload {
+        match &self.payload_type {
+            PayloadType::Circuit(payload) => payload,
+            PayloadType::Consensus(_, (_, payload)) => payload,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -134,6 +165,9 @@ pub struct AdminServiceShared {
     pending_changes: Option<CircuitProposalContext>,
     // the verifiers that should be broadcasted for the pending change
     current_consensus_verifiers: Vec<PeerTokenPair>,
+    // reads the state of the consensus engine, once it has been started
+    #[cfg(feature = "admin-service-consensus-status")]
+    consensus_status_reader: Option<TwoPhaseStatusReader>,
     // Admin Service Event Subscribers
     event_subscribers: SubscriberMap,
     // AdminServiceStore
@@ -153,6 +187,27 @@ pub struct AdminServiceShared {
     // Temporarily hold on to peers that should be removed. This helps avoid dropping messages
     // when removing a proposal.
     peers_to_be_removed: Vec<(Instant, Vec<PeerTokenPair>)>,
+    // Records proposal, vote and circuit lifecycle events, if an audit log is configured
+    #[cfg(feature = "audit")]
+    audit_store: Option<Box<dyn AuditStore>>,
+    // How long a circuit management payload may wait for its members to connect and agree on a
+    // protocol before it is dropped; if unset, payloads wait indefinitely
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    member_wait_timeout: Option<Duration>,
+    // The weakest authorization type circuits on this node may use; if unset, any type is allowed
+    #[cfg(feature = "admin-service-minimum-authorization")]
+    minimum_authorization_type: Option<AuthorizationType>,
+    // Limits on the size and complexity of proposed circuits; if unset, proposals are not limited
+    #[cfg(feature = "admin-service-proposal-limits")]
+    proposal_limits: Option<ProposalLimits>,
+    // The newest circuit version this node proposes and accepts; if unset, the newest supported
+    // version
+    #[cfg(feature = "admin-service-circuit-version")]
+    circuit_version: Option<i32>,
+    // The services whose arguments were changed by an accepted proposal, by circuit, which are
+    // restarted once all members are ready
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    updated_services: HashMap<String, Vec<String>>,
 }
 
 impl AdminServiceShared {
@@ -185,6 +240,8 @@ impl AdminServiceShared {
             pending_consensus_proposals: HashMap::new(),
             pending_changes: None,
             current_consensus_verifiers: Vec::new(),
+            #[cfg(feature = "admin-service-consensus-status")]
+            consensus_status_reader: None,
             event_subscribers: SubscriberMap::new(),
             admin_store,
             signature_verifier,
@@ -197,6 +254,18 @@ impl AdminServiceShared {
             public_keys,
             token_to_peer: HashMap::new(),
             peers_to_be_removed: Vec::new(),
+            #[cfg(feature = "audit")]
+            audit_store: None,
+            #[cfg(feature = "admin-service-deferred-proposals")]
+            member_wait_timeout: None,
+            #[cfg(feature = "admin-service-minimum-authorization")]
+            minimum_authorization_type: None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            proposal_limits: None,
+            #[cfg(feature = "admin-service-circuit-version")]
+            circuit_version: None,
+            #[cfg(feature = "admin-service-update-service-arguments")]
+            updated_services: HashMap::new(),
         }
     }
 
@@ -247,6 +316,250 @@ impl AdminServiceShared {
         self.proposal_sender = proposal_sender;
     }
 
+    #[cfg(feature = "audit")]
+    pub fn set_audit_store(&mut self, audit_store: Option<Box<dyn AuditStore>>) {
+        self.audit_store = audit_store;
+    }
+
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    pub fn set_member_wait_timeout(&mut self, member_wait_timeout: Option<Duration>) {
+        self.member_wait_timeout = member_wait_timeout;
+    }
+
+    #[cfg(feature = "admin-service-minimum-authorization")]
+    pub fn set_minimum_authorization_type(
+        &mut self,
+        minimum_authorization_type: Option<AuthorizationType>,
+    ) {
+        self.minimum_authorization_type = minimum_authorization_type;
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn set_proposal_limits(&mut self, proposal_limits: Option<ProposalLimits>) {
+        self.proposal_limits = proposal_limits;
+    }
+
+    #[cfg(feature = "admin-service-circuit-version")]
+    pub fn set_circuit_version(&mut self, circuit_version: Option<i32>) {
+        self.circuit_version = circuit_version;
+    }
+
+    /// Checks that a circuit, or a feature of a circuit such as its display name, is not newer
+    /// than the circuit version of this node.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_version` - The circuit version that the circuit or feature requires
+    /// * `description` - Describes the circuit or feature in the error
+    #[cfg(feature = "admin-service-circuit-version")]
+    fn check_circuit_version(
+        &self,
+        circuit_version: i32,
+        description: &str,
+    ) -> Result<(), AdminSharedError> {
+        match self.circuit_version {
+            Some(node_version) if circuit_version > node_version => {
+                Err(AdminSharedError::ValidationFailed(format!(
+                    "{} requires circuit version {}, but this node only allows circuit version {}",
+                    description, circuit_version, node_version
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that a circuit's authorization type is at least as strong as the minimum
+    /// authorization type of this node.
+    #[cfg(feature = "admin-service-minimum-authorization")]
+    pub fn check_minimum_authorization(
+        &self,
+        authorization_type: &AuthorizationType,
+    ) -> Result<(), AdminSharedError> {
+        match (&self.minimum_authorization_type, authorization_type) {
+            (Some(AuthorizationType::Challenge), AuthorizationType::Trust) => {
+                Err(AdminSharedError::ValidationFailed(
+                    "authorization_type TRUST is not permitted, this node requires CHALLENGE \
+                     authorization"
+                        .to_string(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Drops the circuit management payloads that have waited longer than the member wait
+    /// timeout for their members to connect and agree on a protocol, releasing the peer
+    /// references that were added for them.
+    ///
+    /// Proposals received from other nodes are not dropped, as their coordinator is responsible
+    /// for timing them out.
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    pub fn expire_waiting_payloads(&mut self) {
+        let member_wait_timeout = match self.member_wait_timeout {
+            Some(member_wait_timeout) => member_wait_timeout,
+            None => return,
+        };
+
+        let is_expired = |pending_payload: &PendingPayload| {
+            matches!(pending_payload.payload_type, PayloadType::Circuit(_))
+                && pending_payload.queued_at.elapsed() >= member_wait_timeout
+        };
+
+        let (expired, unpeered): (Vec<PendingPayload>, Vec<PendingPayload>) =
+            std::mem::take(&mut self.unpeered_payloads)
+                .into_iter()
+                .partition(is_expired);
+        self.unpeered_payloads = unpeered;
+
+        let (expired_protocol, pending_protocol): (Vec<PendingPayload>, Vec<PendingPayload>) =
+            std::mem::take(&mut self.pending_protocol_payloads)
+                .into_iter()
+                .partition(is_expired);
+        self.pending_protocol_payloads = pending_protocol;
+
+        for pending_payload in expired.into_iter().chain(expired_protocol) {
+            let mut waiting_for: Vec<String> = pending_payload
+                .unpeered_ids
+                .iter()
+                .map(|peer_token_pair| peer_token_pair.peer_id().id_as_string())
+                .collect();
+            waiting_for.extend(
+                pending_payload
+                    .missing_protocol_ids
+                    .iter()
+                    .map(|peer_node| peer_node.node_id.clone()),
+            );
+            warn!(
+                "Dropping circuit management payload from {} after waiting {:?} for members \
+                 {:?}",
+                pending_payload.message_sender, member_wait_timeout, waiting_for
+            );
+
+            let peers = pending_payload
+                .members
+                .into_iter()
+                .filter(|peer_token_pair| !self.is_local_node(peer_token_pair.peer_id()))
+                .collect();
+            self.remove_peer_refs(peers);
+        }
+    }
+
+    /// Records the given event in the audit log, if one is configured. Failures are logged, as
+    /// they must not interrupt circuit management.
+    #[cfg(feature = "audit")]
+    fn record_audit_event(&self, event: AuditEvent) {
+        if let Some(audit_store) = &self.audit_store {
+            if let Err(err) = audit_store.add_event(&event) {
+                error!("Unable to record audit event: {}", err);
+            }
+        }
+    }
+
+    #[cfg(feature = "admin-service-consensus-status")]
+    pub fn set_consensus_status_reader(
+        &mut self,
+        consensus_status_reader: Option<TwoPhaseStatusReader>,
+    ) {
+        self.consensus_status_reader = consensus_status_reader;
+    }
+
+    /// Returns the state of each circuit management payload that has been accepted by the admin
+    /// service, but has not yet been committed or rejected.
+    #[cfg(feature = "admin-service-consensus-status")]
+    pub fn consensus_statuses(&self) -> Result<Vec<PayloadConsensusStatus>, AdminSharedError> {
+        let engine_status = self
+            .consensus_status_reader
+            .as_ref()
+            .map(|reader| reader.status())
+            .transpose()
+            .map_err(|err| AdminSharedError::SplinterStateError(err.to_string()))?;
+
+        let mut statuses = vec![];
+
+        for pending_payload in &self.unpeered_payloads {
+            let payload = pending_payload.circuit_management_payload();
+            let mut status = PayloadConsensusStatus::new(
+                payload,
+                sha256(payload).map_err(AdminSharedError::HashError)?,
+                ConsensusStage::AwaitingPeers,
+            );
+            status.awaiting = pending_payload
+                .unpeered_ids
+                .iter()
+                .map(|peer_token_pair| peer_token_pair.peer_id().id_as_string())
+                .collect();
+            statuses.push(status);
+        }
+
+        for pending_payload in &self.pending_protocol_payloads {
+            let payload = pending_payload.circuit_management_payload();
+            let mut status = PayloadConsensusStatus::new(
+                payload,
+                sha256(payload).map_err(AdminSharedError::HashError)?,
+                ConsensusStage::AwaitingProtocol,
+            );
+            status.awaiting = pending_payload
+                .missing_protocol_ids
+                .iter()
+                .map(|peer_node| peer_node.node_id.clone())
+                .collect();
+            statuses.push(status);
+        }
+
+        for payload in &self.pending_circuit_payloads {
+            statuses.push(PayloadConsensusStatus::new(
+                payload,
+                sha256(payload).map_err(AdminSharedError::HashError)?,
+                ConsensusStage::Queued,
+            ));
+        }
+
+        let evaluating = engine_status
+            .as_ref()
+            .and_then(|engine_status| engine_status.evaluating.as_ref());
+        let mut consensus_statuses = vec![];
+        for (proposal_id, (_, payload)) in &self.pending_consensus_proposals {
+            let mut status = PayloadConsensusStatus::new(
+                payload,
+                sha256(payload).map_err(AdminSharedError::HashError)?,
+                ConsensusStage::Queued,
+            );
+
+            if let Some(evaluating) =
+                evaluating.filter(|evaluating| &evaluating.proposal_id == proposal_id)
+            {
+                status.stage = ConsensusStage::AwaitingAgreement;
+                status.coordinator = Some(verifier_node_id(&evaluating.coordinator_id));
+                status.is_coordinator = evaluating.is_coordinator;
+                // Only the coordinator collects the verifiers' responses
+                if evaluating.is_coordinator {
+                    status.verified = evaluating
+                        .peers_verified
+                        .iter()
+                        .map(verifier_node_id)
+                        .collect();
+                    status.awaiting = evaluating
+                        .required_verifiers
+                        .iter()
+                        .filter(|verifier| !evaluating.peers_verified.contains(verifier))
+                        .map(verifier_node_id)
+                        .collect();
+                }
+                status.coordinator_timeout_remaining = evaluating.coordinator_timeout_remaining;
+            }
+
+            consensus_statuses.push(status);
+        }
+        // The payload being evaluated comes first, followed by the others by circuit
+        consensus_statuses.sort_by(|a, b| {
+            (a.stage != ConsensusStage::AwaitingAgreement, &a.circuit_id)
+                .cmp(&(b.stage != ConsensusStage::AwaitingAgreement, &b.circuit_id))
+        });
+        statuses.append(&mut consensus_statuses);
+
+        Ok(statuses)
+    }
+
     pub fn pop_pending_circuit_payload(&mut self) -> Option<CircuitManagementPayload> {
         self.pending_circuit_payloads.pop_front()
     }
@@ -348,6 +661,8 @@ impl AdminServiceShared {
     }
 
     pub fn commit(&mut self) -> Result<(), AdminSharedError> {
+        #[cfg(feature = "otel-tracing")]
+        let _span = crate::otel::enter_span("admin commit", &[]);
         match self.pending_changes.take() {
             Some(circuit_proposal_context) => {
                 let circuit_proposal = circuit_proposal_context.circuit_proposal;
@@ -438,6 +753,17 @@ impl AdminServiceShared {
                                 }
                             }
                         } else {
+                            #[cfg(feature = "admin-service-update-service-arguments")]
+                            if circuit_proposal.get_proposal_type()
+                                == CircuitProposal_ProposalType::UPDATE_ROSTER
+                            {
+                                self.commit_service_arguments_update(
+                                    &circuit_proposal,
+                                    circuit_proposal_context.signer_public_key,
+                                )?;
+                                return self.add_uninitialized_circuit(circuit_proposal);
+                            }
+
                             // commit new circuit
                             self.admin_store.upgrade_proposal_to_circuit(circuit_id)?;
 
@@ -607,6 +933,25 @@ impl AdminServiceShared {
                                 );
                                 Ok(())
                             }
+                            #[cfg(feature = "admin-service-update-service-arguments")]
+                            CircuitManagementPayload_Action::CIRCUIT_UPDATE_SERVICE_ARGUMENTS_REQUEST => {
+                                self.add_proposal(circuit_proposal.clone())?;
+                                self.update_metrics()?;
+                                // notify registered application authorization handlers of the
+                                // committed circuit proposal
+                                let event = messages::AdminServiceEvent::ProposalSubmitted(
+                                    messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                                        .map_err(AdminSharedError::InvalidMessageFormat)?,
+                                );
+                                self.send_event(&mgmt_type, event);
+
+                                info!(
+                                    "committed changes for new circuit proposal to update service \
+                                     arguments on circuit {}",
+                                    circuit_id
+                                );
+                                Ok(())
+                            }
                             _ => Err(AdminSharedError::UnknownAction(format!(
                                 "Received unknown action: {:?}",
                                 action
@@ -673,6 +1018,11 @@ impl AdminServiceShared {
         self.verify_signature(&circuit_payload).map_err(|_| {
             AdminSharedError::ValidationFailed(String::from("Unable to verify signature"))
         })?;
+        #[cfg(feature = "otel-tracing")]
+        let _span = crate::otel::enter_span(
+            "admin propose change",
+            &[("action", format!("{:?}", header.get_action()).as_str())],
+        );
         match header.get_action() {
             CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
                 let mut create_request = circuit_payload.take_circuit_create_request();
@@ -961,6 +1311,79 @@ impl AdminServiceShared {
 
                 Ok((expected_hash, circuit_proposal))
             }
+            #[cfg(feature = "admin-service-update-service-arguments")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_SERVICE_ARGUMENTS_REQUEST => {
+                debug!("Circuit service arguments update request being processed");
+                let circuit_proposal = self.make_update_service_arguments_circuit_proposal(
+                    circuit_payload.get_circuit_update_service_arguments_request(),
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                )?;
+
+                let local_required_auth = circuit_proposal
+                    .get_circuit_proposal()
+                    .get_node_token(&self.node_id)
+                    .map_err(|err| {
+                        AdminSharedError::ValidationFailed(format!(
+                            "Unable to get local nodes token: {}",
+                            err
+                        ))
+                    })?
+                    .ok_or_else(|| {
+                        AdminSharedError::ValidationFailed(
+                            "Circuit does not have the local node".to_string(),
+                        )
+                    })?;
+
+                let mut protocol = ADMIN_SERVICE_PROTOCOL_VERSION;
+                for member in circuit_proposal
+                    .get_circuit_proposal()
+                    .list_nodes()
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?
+                {
+                    // Figure out what protocol version should be used for this proposal
+                    if let Some(protocol_version) = self.service_protocols.get(&PeerTokenPair::new(
+                        member.token.clone(),
+                        local_required_auth.clone(),
+                    )) {
+                        if protocol_version < &protocol {
+                            protocol = *protocol_version
+                        }
+                    }
+                }
+
+                self.validate_update_service_arguments(
+                    circuit_payload.get_circuit_update_service_arguments_request(),
+                    &circuit_proposal,
+                    header.get_requester(),
+                    header.get_requester_node_id(),
+                    protocol,
+                )?;
+
+                let expected_hash = sha256(&circuit_proposal)?;
+                self.pending_changes = Some(CircuitProposalContext {
+                    circuit_proposal: circuit_proposal.clone(),
+                    signer_public_key: header.get_requester().to_vec(),
+                    action:
+                        CircuitManagementPayload_Action::CIRCUIT_UPDATE_SERVICE_ARGUMENTS_REQUEST,
+                });
+                self.current_consensus_verifiers = circuit_proposal
+                    .get_circuit_proposal()
+                    .list_tokens(&self.node_id)
+                    .map_err(|_| {
+                        AdminSharedError::SplinterStateError(format!(
+                            "Unable to get tokens for proposal: {}",
+                            circuit_proposal.get_circuit_id()
+                        ))
+                    })?;
+
+                Ok((expected_hash, circuit_proposal))
+            }
             CircuitManagementPayload_Action::ACTION_UNSET => Err(
                 AdminSharedError::ValidationFailed("Action must be set".to_string()),
             ),
@@ -1126,7 +1549,55 @@ impl AdminServiceShared {
                 )))
             })?;
 
-        self.check_connected_peers_payload_disband(
+        self.check_connected_peers_payload_circuit_change(
+            &members,
+            local_required_auth,
+            payload,
+            message_sender,
+        )
+    }
+
+    /// Once a local `CircuitUpdateServiceArgumentsRequest` has been validated, the admin service
+    /// may now proceed to communicating with the remote circuit members to propose the change.
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    fn propose_update_service_arguments(
+        &mut self,
+        payload: CircuitManagementPayload,
+        circuit_proposal: &CircuitProposal,
+        message_sender: String,
+    ) -> Result<(), ServiceError> {
+        debug!(
+            "received service arguments update request for {}::{}",
+            circuit_proposal.get_circuit_id(),
+            payload
+                .get_circuit_update_service_arguments_request()
+                .get_service_id()
+        );
+
+        let local_required_auth = circuit_proposal
+            .get_circuit_proposal()
+            .get_node_token(&self.node_id)
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get local nodes token: {}", err),
+                )))
+            })?
+            .ok_or_else(|| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    "Circuit does not have the local node".to_string(),
+                )))
+            })?;
+
+        let members = circuit_proposal
+            .get_circuit_proposal()
+            .list_nodes()
+            .map_err(|err| {
+                ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
+                    format!("Unable to get peer tokens for members: {}", err),
+                )))
+            })?;
+
+        self.check_connected_peers_payload_circuit_change(
             &members,
             local_required_auth,
             payload,
@@ -1442,6 +1913,8 @@ impl AdminServiceShared {
                 payload_type: PayloadType::Circuit(payload),
                 members: pending_members,
                 message_sender,
+                #[cfg(feature = "admin-service-deferred-proposals")]
+                queued_at: Instant::now(),
             });
         }
 
@@ -1511,17 +1984,19 @@ impl AdminServiceShared {
                 payload_type: PayloadType::Circuit(payload),
                 members: pending_members,
                 message_sender,
+                #[cfg(feature = "admin-service-deferred-proposals")]
+                queued_at: Instant::now(),
             });
         }
 
         Ok(())
     }
 
-    /// Verify all members of the circuit to be disbanded are using a valid protocol version.
-    /// If all circuit members have agreed on a protocol version, the disband payload is moved into
+    /// Verify all members of the circuit to be disbanded or changed are using a valid protocol
+    /// version. If all circuit members have agreed on a protocol version, the payload is moved into
     /// the `pending_circuit_payloads` list for further processing. Otherwise, this payload is
     /// added to the `pending_protocol_payloads` list to await all nodes' protocol agreement.
-    fn check_connected_peers_payload_disband(
+    fn check_connected_peers_payload_circuit_change(
         &mut self,
         members: &[PeerNode],
         local_required_auth: PeerAuthorizationToken,
@@ -1555,12 +2030,89 @@ impl AdminServiceShared {
                 payload_type: PayloadType::Circuit(payload),
                 members: pending_members,
                 message_sender,
+                #[cfg(feature = "admin-service-deferred-proposals")]
+                queued_at: Instant::now(),
             });
         }
 
         Ok(())
     }
 
+    /// Makes the checks that `submit` makes before proposing a new circuit, without proposing it,
+    /// and returns every reason the proposal would be rejected.
+    ///
+    /// Failures that are not caused by the proposal, such as store errors, are returned as
+    /// errors.
+    #[cfg(feature = "admin-service-proposal-validation")]
+    pub fn validate_proposal(
+        &self,
+        payload: &CircuitManagementPayload,
+    ) -> Result<Vec<ProposalValidationError>, AdminSharedError> {
+        let header: CircuitManagementPayload_Header =
+            match Message::parse_from_bytes(payload.get_header()) {
+                Ok(header) => header,
+                Err(err) => {
+                    return Ok(vec![ProposalValidationError::new(
+                        ProposalCheck::Payload,
+                        format!("Unable to parse header: {}", err),
+                    )])
+                }
+            };
+
+        // The remaining checks depend on the header, so stop if it is invalid
+        let mut errors = vec![];
+        record_failure(
+            &mut errors,
+            ProposalCheck::Payload,
+            self.validate_circuit_management_payload(payload, &header),
+        )?;
+        if !errors.is_empty() {
+            return Ok(errors);
+        }
+
+        if header.get_action() != CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST {
+            return Ok(vec![ProposalValidationError::new(
+                ProposalCheck::Payload,
+                format!(
+                    "Only circuit create requests can be validated, not {:?}",
+                    header.get_action()
+                ),
+            )]);
+        }
+
+        match self.verify_signature(payload) {
+            Ok(true) => (),
+            Ok(false) => errors.push(ProposalValidationError::new(
+                ProposalCheck::Signature,
+                "Signature does not match the header and requester".to_string(),
+            )),
+            Err(err) => errors.push(ProposalValidationError::new(
+                ProposalCheck::Signature,
+                format!("Unable to verify signature: {}", err),
+            )),
+        }
+
+        let circuit = payload.get_circuit_create_request().get_circuit();
+        record_failure(
+            &mut errors,
+            ProposalCheck::Requester,
+            self.validate_requester(header.get_requester(), header.get_requester_node_id()),
+        )?;
+        record_failure(
+            &mut errors,
+            ProposalCheck::DuplicateCircuit,
+            self.validate_new_circuit_id(circuit.get_circuit_id()),
+        )?;
+        record_failure(
+            &mut errors,
+            ProposalCheck::Circuit,
+            self.validate_circuit_protocol(circuit, ADMIN_SERVICE_PROTOCOL_VERSION)
+                .and_then(|_| self.validate_circuit(circuit)),
+        )?;
+
+        Ok(errors)
+    }
+
     pub fn submit(&mut self, payload: CircuitManagementPayload) -> Result<(), ServiceError> {
         debug!("Payload submitted: {:?}", payload);
 
@@ -1568,6 +2120,11 @@ impl AdminServiceShared {
         self.validate_circuit_management_payload(&payload, &header)
             .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
         self.verify_signature(&payload)?;
+        #[cfg(feature = "otel-tracing")]
+        let _span = crate::otel::enter_span(
+            "admin submit",
+            &[("action", format!("{:?}", header.get_action()).as_str())],
+        );
 
         match header.get_action() {
             CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST => {
@@ -1645,6 +2202,34 @@ impl AdminServiceShared {
                     "local".to_string(),
                 )
             }
+            #[cfg(feature = "admin-service-update-service-arguments")]
+            CircuitManagementPayload_Action::CIRCUIT_UPDATE_SERVICE_ARGUMENTS_REQUEST => {
+                let signer_public_key = header.get_requester();
+                let requester_node_id = header.get_requester_node_id();
+                let request = payload.get_circuit_update_service_arguments_request();
+                let circuit_proposal = self
+                    .make_update_service_arguments_circuit_proposal(
+                        request,
+                        signer_public_key,
+                        requester_node_id,
+                    )
+                    .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.validate_update_service_arguments(
+                    request,
+                    &circuit_proposal,
+                    signer_public_key,
+                    requester_node_id,
+                    ADMIN_SERVICE_PROTOCOL_VERSION,
+                )
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+                self.propose_update_service_arguments(
+                    payload,
+                    &circuit_proposal,
+                    "local".to_string(),
+                )
+            }
             CircuitManagementPayload_Action::CIRCUIT_PURGE_REQUEST => {
                 let signer_public_key = header.get_requester();
                 let requester_node_id = header.get_requester_node_id();
@@ -1654,7 +2239,17 @@ impl AdminServiceShared {
                 self.validate_purge_request(circuit_id, signer_public_key, requester_node_id)
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
-                self.purge_circuit(circuit_id)
+                self.purge_circuit(circuit_id)?;
+                #[cfg(feature = "audit")]
+                self.record_audit_event(
+                    AuditEvent::new(
+                        AuditEventType::CircuitPurged,
+                        to_hex(signer_public_key),
+                        circuit_id.to_string(),
+                    )
+                    .with_detail(format!("purged by node {}", requester_node_id)),
+                );
+                Ok(())
             }
             CircuitManagementPayload_Action::CIRCUIT_ABANDON => {
                 let signer_public_key = header.get_requester();
@@ -1665,7 +2260,17 @@ impl AdminServiceShared {
                 self.validate_abandon_circuit(circuit_id, signer_public_key, requester_node_id)
                     .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
 
-                self.abandon_circuit(circuit_id)
+                self.abandon_circuit(circuit_id)?;
+                #[cfg(feature = "audit")]
+                self.record_audit_event(
+                    AuditEvent::new(
+                        AuditEventType::CircuitAbandoned,
+                        to_hex(signer_public_key),
+                        circuit_id.to_string(),
+                    )
+                    .with_detail(format!("abandoned by node {}", requester_node_id)),
+                );
+                Ok(())
             }
             CircuitManagementPayload_Action::PROPOSAL_REMOVE_REQUEST => {
                 let signer_public_key = header.get_requester();
@@ -1702,6 +2307,11 @@ impl AdminServiceShared {
         payload: CircuitManagementPayload,
         message_sender: String,
     ) -> Result<(), ServiceError> {
+        #[cfg(feature = "otel-tracing")]
+        let _span = crate::otel::enter_span(
+            "admin handle proposed circuit",
+            &[("requester", message_sender.as_str())],
+        );
         let mut missing_protocol_ids = vec![];
         let mut pending_peers = vec![];
         let mut added_peers: Vec<PeerTokenPair> = vec![];
@@ -1743,6 +2353,11 @@ impl AdminServiceShared {
                 )));
             }
 
+            // Refuse to connect to the members of a circuit this node would reject
+            #[cfg(feature = "admin-service-minimum-authorization")]
+            self.check_minimum_authorization(store_proposed_circuit.authorization_type())
+                .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
             let peer_members = store_proposed_circuit.list_nodes().map_err(|err| {
                 ServiceError::UnableToHandleMessage(Box::new(AdminSharedError::ValidationFailed(
                     format!("Unable to get peer tokens for members: {}", err),
@@ -1788,9 +2403,10 @@ impl AdminServiceShared {
                 );
             }
             members.extend(peer_members);
-        } else if payload.has_circuit_disband_request() {
-            // If a `CircuitDisbandRequest` is present in the payload, the members must be gathered
-            // from the admin store based on the provided circuit id.
+        } else if let Some(circuit_id) = changed_circuit_id(&payload) {
+            // If a `CircuitDisbandRequest` or another change to an existing circuit is present in
+            // the payload, the members must be gathered from the admin store based on the
+            // provided circuit id.
             // If the members list has already been updated, the payload was to create a
             // new circuit.
             if !members.is_empty() {
@@ -1800,7 +2416,6 @@ impl AdminServiceShared {
                     ),
                 )));
             }
-            let circuit_id = payload.get_circuit_disband_request().get_circuit_id();
             // If the proposed circuit is being disbanded, the circuit information must be
             // gathered from the admin store, as the `CircuitDisbandRequest` only contains
             // the `circuit_id`.
@@ -1851,7 +2466,7 @@ impl AdminServiceShared {
             for node in tokens {
                 let peer_token_pair =
                     PeerTokenPair::new(node.token.clone(), local_required_auth.clone());
-                // Verify each disband member has an agreed upon protocol version with this node
+                // Verify each member has an agreed upon protocol version with this node
                 // Otherwise, re-establish a peer connection
                 if !self.is_local_node(peer_token_pair.peer_id())
                     && self.service_protocols.get(&peer_token_pair).is_none()
@@ -1884,6 +2499,8 @@ impl AdminServiceShared {
                 payload_type: PayloadType::Consensus(proposal.id.clone(), (proposal, payload)),
                 members: pending_members,
                 message_sender,
+                #[cfg(feature = "admin-service-deferred-proposals")]
+                queued_at: Instant::now(),
             });
             Ok(())
         }
@@ -1946,6 +2563,9 @@ impl AdminServiceShared {
         circuit_management_type: &str,
         event: messages::AdminServiceEvent,
     ) {
+        #[cfg(feature = "audit")]
+        self.record_audit_event(audit_event_from_admin_event(&event));
+
         let admin_event = match self.event_store.add_event(event) {
             Ok(admin_event) => admin_event,
             Err(err) => {
@@ -2332,10 +2952,18 @@ impl AdminServiceShared {
         // If the proposal has type `CircuitProposal_ProposalType::DISBAND`, the proposal is
         // intended to disband a circuit and the associated services will need to be stopped. In
         // this case, the next step is to `cleanup_disbanded_circuit_if_members_ready`.
-        if circuit_proposal_type == CircuitProposal_ProposalType::DISBAND {
-            self.cleanup_disbanded_circuit_if_members_ready(&circuit_id)
-        } else {
-            self.initialize_services_if_members_ready(&circuit_id)
+        // If the proposal has type `CircuitProposal_ProposalType::UPDATE_ROSTER`, the proposal
+        // changed the arguments of services, which will need to be restarted. In this case, the
+        // next step is to `restart_updated_services_if_members_ready`.
+        match circuit_proposal_type {
+            CircuitProposal_ProposalType::DISBAND => {
+                self.cleanup_disbanded_circuit_if_members_ready(&circuit_id)
+            }
+            #[cfg(feature = "admin-service-update-service-arguments")]
+            CircuitProposal_ProposalType::UPDATE_ROSTER => {
+                self.restart_updated_services_if_members_ready(&circuit_id)
+            }
+            _ => self.initialize_services_if_members_ready(&circuit_id),
         }
     }
 
@@ -2388,6 +3016,10 @@ impl AdminServiceShared {
         // associated circuit proposal's type.
         match proposal_type {
             ProposalType::Disband => self.cleanup_disbanded_circuit_if_members_ready(circuit_id),
+            #[cfg(feature = "admin-service-update-service-arguments")]
+            ProposalType::UpdateRoster => {
+                self.restart_updated_services_if_members_ready(circuit_id)
+            }
             _ => self.initialize_services_if_members_ready(circuit_id),
         }
     }
@@ -2444,6 +3076,20 @@ impl AdminServiceShared {
         signer_public_key: &[u8],
         requester_node_id: &str,
         protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        self.validate_circuit_protocol(circuit, protocol)?;
+        self.validate_requester(signer_public_key, requester_node_id)?;
+        self.validate_new_circuit_id(circuit.get_circuit_id())?;
+        self.validate_circuit(circuit)?;
+        Ok(())
+    }
+
+    /// Checks that the proposed circuit is supported by the agreed upon protocol version, and
+    /// that it is within the proposal limits.
+    fn validate_circuit_protocol(
+        &self,
+        circuit: &Circuit,
+        protocol: u32,
     ) -> Result<(), AdminSharedError> {
         match protocol {
             ADMIN_SERVICE_PROTOCOL_VERSION => {
@@ -2454,6 +3100,14 @@ impl AdminServiceShared {
                         circuit.get_circuit_version()
                     )));
                 }
+
+                #[cfg(feature = "admin-service-circuit-version")]
+                {
+                    self.check_circuit_version(circuit.get_circuit_version(), "Proposed circuit")?;
+                    if !circuit.get_display_name().is_empty() {
+                        self.check_circuit_version(CIRCUIT_PROTOCOL_VERSION, "A display name")?;
+                    }
+                }
             }
 
             1 => {
@@ -2489,6 +3143,24 @@ impl AdminServiceShared {
             }
         }
 
+        // Reject oversized proposals before any further validation
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let Some(proposal_limits) = &self.proposal_limits {
+            proposal_limits
+                .check(circuit)
+                .map_err(AdminSharedError::ValidationFailed)?;
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the requester's key is registered for the requester node, and that it may
+    /// propose circuits.
+    fn validate_requester(
+        &self,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+    ) -> Result<(), AdminSharedError> {
         if requester_node_id.is_empty() {
             return Err(AdminSharedError::ValidationFailed(
                 "requester_node_id is empty".to_string(),
@@ -2518,25 +3190,25 @@ impl AdminServiceShared {
                 ))
             })?;
 
-        if self.has_proposal(circuit.get_circuit_id())? {
+        Ok(())
+    }
+
+    /// Checks that no circuit or proposal already has the given circuit ID.
+    fn validate_new_circuit_id(&self, circuit_id: &str) -> Result<(), AdminSharedError> {
+        if self.has_proposal(circuit_id)? {
             return Err(AdminSharedError::ValidationFailed(format!(
                 "Ignoring duplicate proposal for circuit {}",
-                circuit.get_circuit_id()
+                circuit_id
             )));
         }
 
-        if self
-            .admin_store
-            .get_circuit(circuit.get_circuit_id())?
-            .is_some()
-        {
+        if self.admin_store.get_circuit(circuit_id)?.is_some() {
             return Err(AdminSharedError::ValidationFailed(format!(
                 "Circuit with circuit id {} already exists",
-                circuit.get_circuit_id()
+                circuit_id
             )));
         }
 
-        self.validate_circuit(circuit)?;
         Ok(())
     }
 
@@ -2568,6 +3240,12 @@ impl AdminServiceShared {
             )));
         }
 
+        #[cfg(feature = "admin-service-minimum-authorization")]
+        self.check_minimum_authorization(
+            &AuthorizationType::try_from(&circuit.get_authorization_type())
+                .map_err(|err| AdminSharedError::ValidationFailed(err.to_string()))?,
+        )?;
+
         if circuit.get_persistence() == Circuit_PersistenceType::UNSET_PERSISTENCE_TYPE {
             return Err(AdminSharedError::ValidationFailed(
                 "persistence_type cannot be unset".to_string(),
@@ -2717,6 +3395,17 @@ impl AdminServiceShared {
             self.validate_service_args(service)?;
         }
 
+        // The services of a circuit must agree on the encoding of their message payloads
+        #[cfg(feature = "service-payload-codec")]
+        negotiate_payload_encoding(circuit.get_roster().iter().map(|service| {
+            service
+                .get_arguments()
+                .iter()
+                .find(|arg| arg.get_key() == PAYLOAD_ENCODING_ARGUMENT)
+                .map(|arg| arg.get_value())
+        }))
+        .map_err(|err| AdminSharedError::ValidationFailed(err.to_string()))?;
+
         if circuit.get_circuit_management_type().is_empty() {
             return Err(AdminSharedError::ValidationFailed(
                 "The circuit must have a management type".to_string(),
@@ -2761,8 +3450,22 @@ impl AdminServiceShared {
                     )));
                 }
             }
+
+            // Refuse to accept a circuit this node would not have proposed
+            #[cfg(feature = "admin-service-circuit-version")]
+            {
+                self.check_circuit_version(circuit.circuit_version(), "Proposed circuit")?;
+                if circuit.display_name().is_some() {
+                    self.check_circuit_version(CIRCUIT_PROTOCOL_VERSION, "A display name")?;
+                }
+            }
         };
 
+        #[cfg(feature = "admin-service-circuit-version")]
+        if circuit_proposal.proposal_type() == &ProposalType::Disband {
+            self.check_circuit_version(CIRCUIT_PROTOCOL_VERSION, "Circuit disband")?;
+        }
+
         let circuit_hash = proposal_vote.get_circuit_hash();
 
         self.validate_key(signer_public_key)?;
@@ -2840,6 +3543,9 @@ impl AdminServiceShared {
             )));
         }
 
+        #[cfg(feature = "admin-service-circuit-version")]
+        self.check_circuit_version(CIRCUIT_PROTOCOL_VERSION, "Circuit disband")?;
+
         if requester_node_id.is_empty() {
             return Err(AdminSharedError::ValidationFailed(
                 "requester_node_id is empty".to_string(),
@@ -2912,6 +3618,117 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Validates a `CircuitUpdateServiceArgumentsRequest` using the following:
+    ///
+    /// - Validate the protocol version used by the submitter node. Updating service arguments is
+    ///   only available to nodes using `ADMIN_SERVICE_PROTOCOL_VERSION` 2.
+    /// - Validate the requester is authorized to propose a change for the requesting node
+    /// - Validate the signer's public key is authorized for the requesting node
+    /// - Validate a `CircuitProposal` with the same ID is not present
+    /// - Validate the circuit is `Active` and the service's arguments are changed
+    /// - Validate the new arguments with the service type's argument validator
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    fn validate_update_service_arguments(
+        &self,
+        request: &CircuitUpdateServiceArgumentsRequest,
+        circuit_proposal: &CircuitProposal,
+        signer_public_key: &[u8],
+        requester_node_id: &str,
+        protocol: u32,
+    ) -> Result<(), AdminSharedError> {
+        if protocol != ADMIN_SERVICE_PROTOCOL_VERSION {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Updating service arguments is not available for protocol version {}",
+                protocol
+            )));
+        }
+
+        if requester_node_id.is_empty() {
+            return Err(AdminSharedError::ValidationFailed(
+                "requester_node_id is empty".to_string(),
+            ));
+        }
+
+        self.validate_key(signer_public_key)?;
+
+        if !self
+            .key_verifier
+            .is_permitted(requester_node_id, signer_public_key)?
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "{} is not registered for the requester node {}",
+                to_hex(signer_public_key),
+                requester_node_id,
+            )));
+        }
+
+        self.key_permission_manager
+            .is_permitted(signer_public_key, PROPOSER_ROLE)
+            .map_err(|_| {
+                AdminSharedError::ValidationFailed(format!(
+                    "{} is not permitted to update services for node {}",
+                    to_hex(signer_public_key),
+                    requester_node_id
+                ))
+            })?;
+
+        let circuit = circuit_proposal.get_circuit_proposal();
+        if self.has_proposal(circuit.get_circuit_id())? {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Ignoring duplicate proposal for circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
+        let stored_circuit = self
+            .admin_store
+            .get_circuit(circuit.get_circuit_id())?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received service arguments update for a circuit that does not exist: \
+                     circuit id {}",
+                    circuit.get_circuit_id()
+                ))
+            })?;
+
+        if stored_circuit.circuit_status() != &StoreCircuitStatus::Active {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Attempting to update services of an inactive circuit {}",
+                circuit.get_circuit_id()
+            )));
+        }
+
+        let arguments = request
+            .get_arguments()
+            .iter()
+            .map(|arg| (arg.get_key().to_string(), arg.get_value().to_string()))
+            .collect::<Vec<_>>();
+        if stored_circuit.roster().iter().any(|service| {
+            service.service_id() == request.get_service_id()
+                && service.arguments() == arguments.as_slice()
+        }) {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Service {} already has the proposed arguments",
+                request.get_service_id()
+            )));
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let Some(proposal_limits) = &self.proposal_limits {
+            proposal_limits
+                .check(circuit)
+                .map_err(AdminSharedError::ValidationFailed)?;
+        }
+
+        for service in circuit.get_roster() {
+            if service.get_service_id() == request.get_service_id() {
+                self.validate_service_args(service)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Validates a `CircuitPurgeRequest` using the following:
     ///
     /// - Validate the requester is authorized to propose a change on the requesting node
@@ -3213,56 +4030,95 @@ impl AdminServiceShared {
                     circuit_id
                 ))
             })?;
-        // Collecting the endpoints of the nodes apart of the circuit being disbanded
-        let circuit_members = store_circuit
-            .members()
-            .iter()
-            .map(|circuit_node| messages::SplinterNode {
-                node_id: circuit_node.node_id().to_string(),
-                endpoints: circuit_node.endpoints().to_vec(),
-                public_key: circuit_node
-                    .public_key()
-                    .clone()
-                    .map(|public_key| public_key.into_bytes()),
-            })
-            .collect::<Vec<messages::SplinterNode>>();
-        let mut create_circuit_builder = messages::CreateCircuitBuilder::new()
-            .with_circuit_id(circuit_id)
-            .with_roster(
-                store_circuit
-                    .roster()
-                    .iter()
-                    .map(|service| messages::SplinterService {
-                        service_id: service.service_id().into(),
-                        service_type: service.service_type().into(),
-                        allowed_nodes: vec![service.node_id().to_string()],
-                        arguments: service
-                            .arguments()
-                            .iter()
-                            .map(|(key, value)| (key.to_string(), value.to_string()))
-                            .collect(),
-                    })
-                    .collect::<Vec<messages::SplinterService>>()
-                    .as_ref(),
-            )
-            .with_members(circuit_members.as_ref())
-            .with_authorization_type(&messages::AuthorizationType::from(
-                store_circuit.authorization_type(),
-            ))
-            .with_persistence(&messages::PersistenceType::from(
-                store_circuit.persistence(),
-            ))
-            .with_durability(&messages::DurabilityType::from(store_circuit.durability()))
-            .with_routes(&messages::RouteType::from(store_circuit.routes()))
-            .with_circuit_management_type(store_circuit.circuit_management_type())
-            .with_circuit_version(store_circuit.circuit_version())
-            .with_circuit_status(&messages::CircuitStatus::Disbanded);
+        let proposed_circuit: Circuit = circuit_builder_from_store(&store_circuit)
+            .with_circuit_status(&messages::CircuitStatus::Disbanded)
+            .build()
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to build circuit {}",
+                    err
+                ))
+            })?
+            .try_into()
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to create proto circuit {}",
+                    err
+                ))
+            })?;
 
-        if let Some(display_name) = store_circuit.display_name() {
-            create_circuit_builder = create_circuit_builder.with_display_name(display_name);
+        let mut circuit_proposal = CircuitProposal::new();
+        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        circuit_proposal.set_circuit_id(circuit_id.to_string());
+        circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
+        circuit_proposal.set_circuit_proposal(proposed_circuit);
+        circuit_proposal.set_requester(requester.to_vec());
+        circuit_proposal.set_requester_node_id(requester_node_id.to_string());
+
+        Ok(circuit_proposal)
+    }
+
+    /// Makes the `CircuitProposal` associated with a `CircuitUpdateServiceArgumentsRequest` based
+    /// on the currently active circuit, with the arguments of the specified service replaced
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    fn make_update_service_arguments_circuit_proposal(
+        &self,
+        request: &CircuitUpdateServiceArgumentsRequest,
+        requester: &[u8],
+        requester_node_id: &str,
+    ) -> Result<CircuitProposal, AdminSharedError> {
+        let circuit_id = request.get_circuit_id();
+        let store_circuit = self
+            .admin_store
+            .get_circuit(circuit_id)
+            .map_err(|err| {
+                AdminSharedError::ValidationFailed(format!(
+                    "error occurred when trying to get circuit {}",
+                    err
+                ))
+            })?
+            .ok_or_else(|| {
+                AdminSharedError::ValidationFailed(format!(
+                    "Received service arguments update for a circuit that does not exist: \
+                     circuit id {}",
+                    circuit_id
+                ))
+            })?;
+
+        if !store_circuit
+            .roster()
+            .iter()
+            .any(|service| service.service_id() == request.get_service_id())
+        {
+            return Err(AdminSharedError::ValidationFailed(format!(
+                "Circuit {} does not have service {}",
+                circuit_id,
+                request.get_service_id()
+            )));
         }
 
-        let proposed_circuit: Circuit = create_circuit_builder
+        let roster = store_circuit
+            .roster()
+            .iter()
+            .map(|service| messages::SplinterService {
+                service_id: service.service_id().into(),
+                service_type: service.service_type().into(),
+                allowed_nodes: vec![service.node_id().to_string()],
+                arguments: if service.service_id() == request.get_service_id() {
+                    request
+                        .get_arguments()
+                        .iter()
+                        .map(|arg| (arg.get_key().to_string(), arg.get_value().to_string()))
+                        .collect()
+                } else {
+                    service.arguments().to_vec()
+                },
+            })
+            .collect::<Vec<messages::SplinterService>>();
+
+        let proposed_circuit: Circuit = circuit_builder_from_store(&store_circuit)
+            .with_roster(&roster)
+            .with_circuit_status(&messages::CircuitStatus::Active)
             .build()
             .map_err(|err| {
                 AdminSharedError::ValidationFailed(format!(
@@ -3279,7 +4135,7 @@ impl AdminServiceShared {
             })?;
 
         let mut circuit_proposal = CircuitProposal::new();
-        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::DISBAND);
+        circuit_proposal.set_proposal_type(CircuitProposal_ProposalType::UPDATE_ROSTER);
         circuit_proposal.set_circuit_id(circuit_id.to_string());
         circuit_proposal.set_circuit_hash(sha256(&proposed_circuit)?);
         circuit_proposal.set_circuit_proposal(proposed_circuit);
@@ -3289,6 +4145,92 @@ impl AdminServiceShared {
         Ok(circuit_proposal)
     }
 
+    /// Commits an accepted proposal to change the arguments of a circuit's services. The circuit
+    /// is updated in the admin store, and the changed services are recorded so that they can be
+    /// restarted once all members are ready. Routing does not depend on service arguments, so the
+    /// routing table is left as is.
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    fn commit_service_arguments_update(
+        &mut self,
+        circuit_proposal: &CircuitProposal,
+        signer_public_key: Vec<u8>,
+    ) -> Result<(), AdminSharedError> {
+        let circuit_id = circuit_proposal.get_circuit_id();
+        let store_circuit = StoreCircuit::try_from(circuit_proposal.get_circuit_proposal())
+            .map_err(|err| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to convert proto Circuit to store Circuit: {}",
+                    err
+                ))
+            })?;
+        let current_circuit = self.admin_store.get_circuit(circuit_id)?.ok_or_else(|| {
+            AdminSharedError::SplinterStateError(format!("Unable to get circuit: {}", circuit_id))
+        })?;
+        let updated_services = store_circuit
+            .roster()
+            .iter()
+            .filter(|service| !current_circuit.roster().contains(service))
+            .map(|service| service.service_id().to_string())
+            .collect();
+
+        self.admin_store
+            .update_circuit(store_circuit.clone())
+            .map_err(|_| {
+                AdminSharedError::SplinterStateError(format!(
+                    "Unable to update circuit {}",
+                    circuit_id
+                ))
+            })
+            .and_then(|_| self.remove_proposal(circuit_id))?;
+        self.update_metrics()?;
+        self.updated_services
+            .insert(circuit_id.to_string(), updated_services);
+
+        // send message about the proposal being accepted
+        let circuit_proposal_proto =
+            messages::CircuitProposal::from_proto(circuit_proposal.clone())
+                .map_err(AdminSharedError::InvalidMessageFormat)?;
+        let event = messages::AdminServiceEvent::ProposalAccepted((
+            circuit_proposal_proto,
+            signer_public_key,
+        ));
+        self.send_event(
+            &circuit_proposal
+                .get_circuit_proposal()
+                .circuit_management_type,
+            event,
+        );
+
+        // send MEMBER_READY message to all other members' admin services
+        if let Some(ref network_sender) = self.network_sender {
+            let mut member_ready = MemberReady::new();
+            member_ready.set_circuit_id(circuit_id.to_string());
+            member_ready.set_member_node_id(self.node_id.clone());
+            let mut msg = AdminMessage::new();
+            msg.set_message_type(AdminMessage_Type::MEMBER_READY);
+            msg.set_member_ready(member_ready);
+
+            let envelope_bytes = msg.write_to_bytes().map_err(MarshallingError::from)?;
+            for token in store_circuit
+                .list_tokens(&self.node_id)
+                .map_err(|_| {
+                    AdminSharedError::SplinterStateError(format!(
+                        "Unable to get member peer tokens from {}",
+                        circuit_id
+                    ))
+                })?
+                .iter()
+            {
+                if !self.is_local_node(token.peer_id()) {
+                    network_sender
+                        .send(&admin_service_id(&token.id_as_string()), &envelope_bytes)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Makes a `Circuit` and `StoreCircuit` with an `Abandoned` `circuit_status` to be used to
     /// update circuit state to reflect the abandoning change
     fn make_abandoned_circuit(
@@ -3558,6 +4500,100 @@ impl AdminServiceShared {
         Ok(())
     }
 
+    /// Verify all members have committed a change to service arguments before restarting the
+    /// changed services that this node runs, so that they start with their new arguments.
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    fn restart_updated_services_if_members_ready(
+        &mut self,
+        circuit_id: &str,
+    ) -> Result<(), AdminSharedError> {
+        let ready = {
+            if let Some(updated_circuit) = self.uninitialized_circuits.get(circuit_id) {
+                if let Some(ref circuit_proposal) = updated_circuit.circuit {
+                    let all_members = circuit_proposal
+                        .get_circuit_proposal()
+                        .members
+                        .iter()
+                        .map(|node| node.node_id.clone())
+                        .collect::<HashSet<String>>();
+                    all_members.is_subset(&updated_circuit.ready_members)
+                } else {
+                    false
+                }
+            } else {
+                false
+            }
+        };
+
+        if ready {
+            let circuit_proposal = self
+                .uninitialized_circuits
+                .remove(circuit_id)
+                .expect("Updated circuit not set")
+                .circuit
+                .expect("Updated circuit's circuit proposal not set");
+            let updated_services = self.updated_services.remove(circuit_id).unwrap_or_default();
+
+            let circuit = circuit_proposal.get_circuit_proposal();
+            for service in circuit.get_roster() {
+                if !service.allowed_nodes.contains(&self.node_id)
+                    || !updated_services.contains(&service.service_id)
+                {
+                    continue;
+                }
+
+                let service_arguments = service
+                    .arguments
+                    .iter()
+                    .map(|arg| (arg.key.clone(), arg.value.clone()))
+                    .collect::<Vec<_>>();
+
+                for dispatch in &self.lifecycle_dispatch {
+                    dispatch
+                        .retire_service(
+                            &circuit.circuit_id,
+                            &service.service_id,
+                            &service.service_type,
+                        )
+                        .map_err(|err| {
+                            error!("{}", err);
+                            AdminSharedError::ServiceShutdownFailed {
+                                context: format!(
+                                    "Unable to stop service {} on circuit {} for restart",
+                                    service.service_id, circuit.circuit_id
+                                ),
+                                source: None,
+                            }
+                        })?;
+                    dispatch
+                        .add_service(
+                            &circuit.circuit_id,
+                            &service.service_id,
+                            &service.service_type,
+                            service_arguments.clone(),
+                        )
+                        .map_err(|err| {
+                            error!("{}", err);
+                            AdminSharedError::ServiceInitializationFailed {
+                                context: format!(
+                                    "Unable to restart service {} on circuit {}",
+                                    service.service_id, circuit.circuit_id
+                                ),
+                                source: None,
+                            }
+                        })?;
+                }
+
+                info!(
+                    "Restarted service {}::{} with its new arguments",
+                    circuit.circuit_id, service.service_id
+                );
+            }
+        }
+
+        Ok(())
+    }
+
     /// Collect all circuits from the admin store, including `Disbanded` or `Abandoned` circuits
     pub fn get_circuits(
         &self,
@@ -3589,6 +4625,95 @@ impl AdminServiceShared {
     }
 }
 
+/// Records a validation failure as a failure of the given check; other errors are returned.
+#[cfg(feature = "admin-service-proposal-validation")]
+fn record_failure(
+    errors: &mut Vec<ProposalValidationError>,
+    check: ProposalCheck,
+    result: Result<(), AdminSharedError>,
+) -> Result<(), AdminSharedError> {
+    match result {
+        Ok(()) => Ok(()),
+        Err(AdminSharedError::ValidationFailed(message)) => {
+            errors.push(ProposalValidationError::new(check, message));
+            Ok(())
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Returns the ID of the existing circuit that a payload proposes to change, if any.
+fn changed_circuit_id(payload: &CircuitManagementPayload) -> Option<&str> {
+    if payload.has_circuit_disband_request() {
+        return Some(payload.get_circuit_disband_request().get_circuit_id());
+    }
+
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    if payload.has_circuit_update_service_arguments_request() {
+        return Some(
+            payload
+                .get_circuit_update_service_arguments_request()
+                .get_circuit_id(),
+        );
+    }
+
+    None
+}
+
+/// Makes a `CreateCircuitBuilder` with the definition of a stored circuit, to be used to propose
+/// a change to the circuit. The `circuit_status` is left for the caller to set.
+fn circuit_builder_from_store(store_circuit: &StoreCircuit) -> messages::CreateCircuitBuilder {
+    // Collecting the endpoints of the nodes apart of the circuit
+    let circuit_members = store_circuit
+        .members()
+        .iter()
+        .map(|circuit_node| messages::SplinterNode {
+            node_id: circuit_node.node_id().to_string(),
+            endpoints: circuit_node.endpoints().to_vec(),
+            public_key: circuit_node
+                .public_key()
+                .clone()
+                .map(|public_key| public_key.into_bytes()),
+        })
+        .collect::<Vec<messages::SplinterNode>>();
+    let mut create_circuit_builder = messages::CreateCircuitBuilder::new()
+        .with_circuit_id(store_circuit.circuit_id())
+        .with_roster(
+            store_circuit
+                .roster()
+                .iter()
+                .map(|service| messages::SplinterService {
+                    service_id: service.service_id().into(),
+                    service_type: service.service_type().into(),
+                    allowed_nodes: vec![service.node_id().to_string()],
+                    arguments: service
+                        .arguments()
+                        .iter()
+                        .map(|(key, value)| (key.to_string(), value.to_string()))
+                        .collect(),
+                })
+                .collect::<Vec<messages::SplinterService>>()
+                .as_ref(),
+        )
+        .with_members(circuit_members.as_ref())
+        .with_authorization_type(&messages::AuthorizationType::from(
+            store_circuit.authorization_type(),
+        ))
+        .with_persistence(&messages::PersistenceType::from(
+            store_circuit.persistence(),
+        ))
+        .with_durability(&messages::DurabilityType::from(store_circuit.durability()))
+        .with_routes(&messages::RouteType::from(store_circuit.routes()))
+        .with_circuit_management_type(store_circuit.circuit_management_type())
+        .with_circuit_version(store_circuit.circuit_version());
+
+    if let Some(display_name) = store_circuit.display_name() {
+        create_circuit_builder = create_circuit_builder.with_display_name(display_name);
+    }
+
+    create_circuit_builder
+}
+
 // This should never return an error since we received a message from this service id
 pub fn get_peer_token_from_service_id(
     service_id: &str,
@@ -3670,6 +4795,110 @@ pub fn get_peer_token_from_service_id(
     }
 }
 
+/// Converts an admin service event into the corresponding audit event. The actor is the
+/// requester of the proposal, or the voter for vote events. The details of submitted proposals
+/// and ready circuits list the circuit's members and services, so that the audit log records the
+/// circuit's membership over time.
+#[cfg(feature = "audit")]
+fn audit_event_from_admin_event(event: &messages::AdminServiceEvent) -> AuditEvent {
+    use messages::AdminServiceEvent::*;
+
+    let proposal = event.proposal();
+    let proposal_type = match proposal.proposal_type {
+        messages::ProposalType::Create => "create",
+        messages::ProposalType::UpdateRoster => "update roster",
+        messages::ProposalType::AddNode => "add node",
+        messages::ProposalType::RemoveNode => "remove node",
+        messages::ProposalType::Disband => "disband",
+    };
+    let (event_type, actor, detail) = match event {
+        ProposalSubmitted(_) => (
+            AuditEventType::ProposalSubmitted,
+            to_hex(&proposal.requester),
+            format!(
+                "{} proposal submitted by node {}; {}",
+                proposal_type,
+                proposal.requester_node_id,
+                describe_roster(&proposal.circuit)
+            ),
+        ),
+        ProposalVote((_, voter)) => {
+            let vote = proposal
+                .votes
+                .iter()
+                .find(|record| &record.public_key == voter);
+            (
+                AuditEventType::ProposalVote,
+                to_hex(voter),
+                match vote {
+                    Some(record) => format!(
+                        "voted to {} {} proposal as node {}",
+                        match record.vote {
+                            messages::Vote::Accept => "accept",
+                            messages::Vote::Reject => "reject",
+                        },
+                        proposal_type,
+                        record.voter_node_id
+                    ),
+                    None => format!("voted on {} proposal", proposal_type),
+                },
+            )
+        }
+        ProposalAccepted((_, voter)) => (
+            AuditEventType::ProposalAccepted,
+            to_hex(voter),
+            format!("{} proposal accepted", proposal_type),
+        ),
+        ProposalRejected((_, voter)) => (
+            AuditEventType::ProposalRejected,
+            to_hex(voter),
+            format!("{} proposal rejected", proposal_type),
+        ),
+        CircuitReady(_) => (
+            AuditEventType::CircuitReady,
+            to_hex(&proposal.requester),
+            format!(
+                "circuit ready after {} proposal; {}",
+                proposal_type,
+                describe_roster(&proposal.circuit)
+            ),
+        ),
+        CircuitDisbanded(_) => (
+            AuditEventType::CircuitDisbanded,
+            to_hex(&proposal.requester),
+            "circuit disbanded".to_string(),
+        ),
+    };
+
+    AuditEvent::new(event_type, actor, proposal.circuit_id.clone()).with_detail(detail)
+}
+
+/// Describes the members and services of a proposed circuit, such as
+/// "members: alpha, beta; services: a000 (scabbard on alpha), b000 (scabbard on beta)".
+#[cfg(feature = "audit")]
+fn describe_roster(circuit: &messages::CreateCircuit) -> String {
+    format!(
+        "members: {}; services: {}",
+        circuit
+            .members
+            .iter()
+            .map(|node| node.node_id.as_str())
+            .collect::<Vec<_>>()
+            .join(", "),
+        circuit
+            .roster
+            .iter()
+            .map(|service| format!(
+                "{} ({} on {})",
+                service.service_id,
+                service.service_type,
+                service.allowed_nodes.join(", ")
+            ))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
 #[cfg(all(test, feature = "sqlite"))]
 mod tests {
     use super::*;
@@ -3867,6 +5096,77 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    /// Test that circuit management payloads that have waited longer than the member wait
+    /// timeout for their members are dropped, and that proposals received from other nodes and
+    /// payloads still within the timeout are kept.
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    #[test]
+    fn test_expire_waiting_payloads() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        shared.set_member_wait_timeout(Some(Duration::from_secs(60)));
+
+        let expired = Instant::now()
+            .checked_sub(Duration::from_secs(120))
+            .expect("Unable to get an earlier instant");
+        let pending_payload = |payload_type, queued_at| PendingPayload {
+            unpeered_ids: vec![PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("node_b"),
+                PeerAuthorizationToken::from_peer_id("node_a"),
+            )],
+            missing_protocol_ids: vec![],
+            payload_type,
+            message_sender: "local".into(),
+            members: vec![],
+            queued_at,
+        };
+
+        shared.unpeered_payloads.push(pending_payload(
+            PayloadType::Circuit(CircuitManagementPayload::new()),
+            expired,
+        ));
+        shared.unpeered_payloads.push(pending_payload(
+            PayloadType::Circuit(CircuitManagementPayload::new()),
+            Instant::now(),
+        ));
+        shared.pending_protocol_payloads.push(pending_payload(
+            PayloadType::Consensus(
+                ProposalId::default(),
+                (Proposal::default(), CircuitManagementPayload::new()),
+            ),
+            expired,
+        ));
+
+        shared.expire_waiting_payloads();
+
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+
+        shutdown(mesh, cm, pm);
+    }
+
     /// Test that the CircuitManagementPayload message is dropped, if a node fails to match
     /// protocol versions
     #[test]
@@ -3949,48 +5249,145 @@ mod tests {
         payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
         payload.set_circuit_create_request(request);
 
-        shared
-            .propose_circuit(payload, "local".to_string())
-            .expect("Proposal not accepted");
+        shared
+            .propose_circuit(payload, "local".to_string())
+            .expect("Proposal not accepted");
+
+        // None of the proposed members are peered
+        assert_eq!(1, shared.unpeered_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+
+        // Set other-node to peered
+        shared
+            .on_peer_connected(&PeerTokenPair::new(
+                PeerAuthorizationToken::from_peer_id("other-node"),
+                PeerAuthorizationToken::from_peer_id("test-node"),
+            ))
+            .expect("Unable to set peer to peered");
+
+        assert_eq!(0, shared.unpeered_payloads.len());
+        assert_eq!(1, shared.pending_protocol_payloads.len());
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+        shared
+            .on_protocol_agreement("admin::other-node", 0)
+            .expect("received unexpected error");
+
+        // The message should be dropped
+        assert_eq!(0, shared.pending_circuit_payloads.len());
+        assert_eq!(0, shared.pending_protocol_payloads.len());
+
+        // sent the service protocol request but not the payload
+        assert_eq!(
+            1,
+            service_sender
+                .sent
+                .lock()
+                .expect("Network sender lock poisoned")
+                .len()
+        );
+        shutdown(mesh, cm, pm);
+    }
+
+    #[test]
+    // test that a valid circuit is validated correctly
+    fn test_validate_circuit_valid() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let circuit = setup_test_circuit();
+
+        if let Err(err) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        shutdown(mesh, cm, pm);
+    }
 
-        // None of the proposed members are peered
-        assert_eq!(1, shared.unpeered_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
+    #[test]
+    // test that a trust-authorized circuit is invalid if the node requires challenge
+    // authorization
+    #[cfg(feature = "admin-service-minimum-authorization")]
+    fn test_validate_circuit_below_minimum_authorization() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
 
-        // Set other-node to peered
-        shared
-            .on_peer_connected(&PeerTokenPair::new(
-                PeerAuthorizationToken::from_peer_id("other-node"),
-                PeerAuthorizationToken::from_peer_id("test-node"),
-            ))
-            .expect("Unable to set peer to peered");
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
 
-        assert_eq!(0, shared.unpeered_payloads.len());
-        assert_eq!(1, shared.pending_protocol_payloads.len());
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-        shared
-            .on_protocol_agreement("admin::other-node", 0)
-            .expect("received unexpected error");
+        let signature_verifier = Secp256k1Context::new().new_verifier();
 
-        // The message should be dropped
-        assert_eq!(0, shared.pending_circuit_payloads.len());
-        assert_eq!(0, shared.pending_protocol_payloads.len());
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        // sent the service protocol request but not the payload
-        assert_eq!(
-            1,
-            service_sender
-                .sent
-                .lock()
-                .expect("Network sender lock poisoned")
-                .len()
+        let mut admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
         );
+        admin_shared.set_minimum_authorization_type(Some(AuthorizationType::Challenge));
+        let circuit = setup_test_circuit();
+
+        if let Ok(()) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit uses trust authorization");
+        }
+
+        admin_shared.set_minimum_authorization_type(Some(AuthorizationType::Trust));
+        if let Err(err) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
         shutdown(mesh, cm, pm);
     }
 
     #[test]
-    // test that a valid circuit is validated correctly
-    fn test_validate_circuit_valid() {
+    // test that a version 2 circuit, or a version 1 circuit with a display name, is invalid if
+    // the node only allows version 1 circuits
+    #[cfg(feature = "admin-service-circuit-version")]
+    fn test_validate_circuit_above_circuit_version() {
         let store = setup_admin_service_store();
         let event_store = store.clone_boxed();
 
@@ -4002,7 +5399,7 @@ mod tests {
         let table = RoutingTable::default();
         let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
 
-        let admin_shared = AdminServiceShared::new(
+        let mut admin_shared = AdminServiceShared::new(
             "node_a".into(),
             vec![Box::new(orchestrator)],
             HashMap::new(),
@@ -4015,8 +5412,30 @@ mod tests {
             event_store,
             vec![],
         );
-        let circuit = setup_test_circuit();
+        admin_shared.set_circuit_version(Some(1));
+        let mut circuit = setup_test_circuit();
+
+        if let Ok(()) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit is version 2");
+        }
+
+        circuit.set_circuit_version(1);
+        circuit.set_circuit_status(admin::Circuit_CircuitStatus::UNSET_CIRCUIT_STATUS);
+        if let Ok(()) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the circuit has a display name");
+        }
 
+        circuit.clear_display_name();
         if let Err(err) = admin_shared.validate_create_circuit(
             &circuit,
             PUB_KEY,
@@ -4445,6 +5864,64 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that a circuit whose services name different payload encodings is invalid, and that a
+    // circuit whose services all name the same one is valid
+    #[cfg(feature = "service-payload-codec")]
+    fn test_validate_circuit_payload_encoding() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+        let mut circuit = setup_test_circuit();
+
+        let mut argument = admin::SplinterService_Argument::new();
+        argument.set_key(PAYLOAD_ENCODING_ARGUMENT.into());
+        argument.set_value("cbor".into());
+        circuit.mut_roster()[0].set_arguments(RepeatedField::from_vec(vec![argument.clone()]));
+
+        if let Ok(_) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid due to mismatched payload encodings");
+        }
+
+        circuit.mut_roster()[1].set_arguments(RepeatedField::from_vec(vec![argument]));
+
+        if let Err(err) = admin_shared.validate_create_circuit(
+            &circuit,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that if a circuit does not have any nodes in its members an error is returned
     fn test_validate_circuit_empty_members() {
@@ -5391,6 +6868,95 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    #[test]
+    // test that validating a proposal reports the failure of each group of checks, and nothing
+    // once the proposal is valid
+    #[cfg(feature = "admin-service-proposal-validation")]
+    fn test_validate_proposal() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let signature_verifier = context.new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let mut shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::new(false)),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        let mut circuit = setup_test_circuit();
+        let mut service_bad = SplinterService::new();
+        service_bad.set_service_id("0123".to_string());
+        service_bad.set_service_type("type_a".to_string());
+        service_bad.set_allowed_nodes(RepeatedField::from_vec(vec!["node_bad".to_string()]));
+        circuit.set_roster(RepeatedField::from_vec(vec![service_bad]));
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(circuit);
+
+        let mut header = admin::CircuitManagementPayload_Header::new();
+        header.set_action(admin::CircuitManagementPayload_Action::CIRCUIT_CREATE_REQUEST);
+        header.set_requester(signer.public_key().unwrap().into_bytes());
+        header.set_requester_node_id("node_a".to_string());
+        let mut payload = admin::CircuitManagementPayload::new();
+        payload.set_header(protobuf::Message::write_to_bytes(&header).unwrap());
+        payload.set_signature(vec![1; 64]);
+        payload.set_circuit_create_request(request);
+
+        let checks = shared
+            .validate_proposal(&payload)
+            .expect("Unable to validate proposal")
+            .iter()
+            .map(|error| error.check())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            checks,
+            vec![
+                ProposalCheck::Signature,
+                ProposalCheck::Requester,
+                ProposalCheck::Circuit
+            ]
+        );
+
+        // An empty signature stops validation at the payload check
+        payload.set_signature(Vec::new());
+        let checks = shared
+            .validate_proposal(&payload)
+            .expect("Unable to validate proposal")
+            .iter()
+            .map(|error| error.check())
+            .collect::<Vec<_>>();
+        assert_eq!(checks, vec![ProposalCheck::Payload]);
+
+        let mut request = admin::CircuitCreateRequest::new();
+        request.set_circuit(setup_test_circuit());
+        payload.set_circuit_create_request(request);
+        payload.set_signature(signer.sign(&payload.header).unwrap().take_bytes());
+        shared.key_verifier = Box::new(MockAdminKeyVerifier::default());
+        assert!(shared
+            .validate_proposal(&payload)
+            .expect("Unable to validate proposal")
+            .is_empty());
+
+        shutdown(mesh, cm, pm);
+    }
+
     #[test]
     // test that the validate_circuit_management_payload method returns an error in case the
     // signature is empty.
@@ -5974,6 +7540,110 @@ mod tests {
         shutdown(mesh, cm, pm);
     }
 
+    /// Tests that a proposal to change a service's arguments is made from the stored circuit and
+    /// validated correctly.
+    ///
+    /// 1. Set up `AdminServiceShared` and add an active circuit to the admin store
+    /// 2. Make the proposal to change the arguments of service "0123" and verify it is an
+    ///    `UPDATE_ROSTER` proposal of the active circuit with only that service's arguments
+    ///    changed, and that it is valid
+    /// 3. Verify a proposal cannot be made for a service that is not on the circuit
+    /// 4. Verify a proposal that does not change the service's arguments is invalid
+    #[cfg(feature = "admin-service-update-service-arguments")]
+    #[test]
+    fn test_update_service_arguments() {
+        let store = setup_admin_service_store();
+        let event_store = store.clone_boxed();
+
+        let (mesh, cm, pm, peer_connector) = setup_peer_connector(None);
+        let orchestrator = setup_orchestrator();
+
+        let signature_verifier = Secp256k1Context::new().new_verifier();
+
+        let table = RoutingTable::default();
+        let writer: Box<dyn RoutingTableWriter> = Box::new(table.clone());
+
+        let admin_shared = AdminServiceShared::new(
+            "node_a".into(),
+            vec![Box::new(orchestrator)],
+            HashMap::new(),
+            peer_connector,
+            store,
+            signature_verifier,
+            Box::new(MockAdminKeyVerifier::default()),
+            Box::new(AllowAllKeyPermissionManager),
+            writer,
+            event_store,
+            vec![],
+        );
+
+        admin_shared
+            .admin_store
+            .add_circuit(
+                store_circuit(CIRCUIT_PROTOCOL_VERSION, StoreCircuitStatus::Active),
+                store_circuit_nodes(),
+            )
+            .expect("unable to add circuit to store");
+
+        let mut argument = admin::SplinterService_Argument::new();
+        argument.set_key("admin_keys".into());
+        argument.set_value(r#"["02aaaa"]"#.into());
+        let mut request = CircuitUpdateServiceArgumentsRequest::new();
+        request.set_circuit_id("01234-ABCDE".into());
+        request.set_service_id("0123".into());
+        request.set_arguments(RepeatedField::from_vec(vec![argument]));
+
+        let circuit_proposal = admin_shared
+            .make_update_service_arguments_circuit_proposal(&request, PUB_KEY, "node_a")
+            .expect("unable to make proposal");
+        assert_eq!(
+            circuit_proposal.get_proposal_type(),
+            CircuitProposal_ProposalType::UPDATE_ROSTER
+        );
+        let circuit = circuit_proposal.get_circuit_proposal();
+        assert_eq!(circuit.get_circuit_status(), Circuit_CircuitStatus::ACTIVE);
+        for service in circuit.get_roster() {
+            if service.get_service_id() == "0123" {
+                assert_eq!(service.get_arguments(), request.get_arguments());
+            } else {
+                assert!(service.get_arguments().is_empty());
+            }
+        }
+
+        if let Err(err) = admin_shared.validate_update_service_arguments(
+            &request,
+            &circuit_proposal,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been valid: {}", err);
+        }
+
+        let mut unknown_service = request.clone();
+        unknown_service.set_service_id("EFGH".into());
+        assert!(admin_shared
+            .make_update_service_arguments_circuit_proposal(&unknown_service, PUB_KEY, "node_a")
+            .is_err());
+
+        let mut unchanged = request.clone();
+        unchanged.clear_arguments();
+        let circuit_proposal = admin_shared
+            .make_update_service_arguments_circuit_proposal(&unchanged, PUB_KEY, "node_a")
+            .expect("unable to make proposal");
+        if let Ok(()) = admin_shared.validate_update_service_arguments(
+            &unchanged,
+            &circuit_proposal,
+            PUB_KEY,
+            "node_a",
+            ADMIN_SERVICE_PROTOCOL_VERSION,
+        ) {
+            panic!("Should have been invalid because the arguments are unchanged");
+        }
+
+        shutdown(mesh, cm, pm);
+    }
+
     /// Tests that the payload submitted via `propose_disband` is moved to the admin service's
     /// payload lists as peers become fully peered, authorized and agree on a service protocol.
     ///
@@ -0,0 +1,187 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Structs for building the data schemas registered on a circuit
+use crate::error::InvalidStateError;
+
+/// A description of data that applications exchange on a circuit, registered so that other
+/// applications on the circuit can discover it
+///
+/// A schema is identified by its circuit, name and version; the name is usually a namespace,
+/// such as `com.example.orders`. The format describes how the definition should be interpreted,
+/// for example `json-schema`, `protobuf` or `avro`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CircuitSchema {
+    circuit_id: String,
+    name: String,
+    version: String,
+    format: String,
+    description: Option<String>,
+    definition: String,
+}
+
+impl CircuitSchema {
+    /// Returns the ID of the circuit the schema is registered on
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the name of the schema
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the version of the schema
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the format of the schema's definition
+    pub fn format(&self) -> &str {
+        &self.format
+    }
+
+    /// Returns the description of the schema
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Returns the definition of the schema
+    pub fn definition(&self) -> &str {
+        &self.definition
+    }
+}
+
+/// Builder for creating a `CircuitSchema`
+#[derive(Default, Clone)]
+pub struct CircuitSchemaBuilder {
+    circuit_id: Option<String>,
+    name: Option<String>,
+    version: Option<String>,
+    format: Option<String>,
+    description: Option<String>,
+    definition: Option<String>,
+}
+
+impl CircuitSchemaBuilder {
+    /// Creates a `CircuitSchemaBuilder`
+    pub fn new() -> Self {
+        CircuitSchemaBuilder::default()
+    }
+
+    /// Sets the circuit ID
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The ID of the circuit the schema is registered on
+    pub fn with_circuit_id(mut self, circuit_id: &str) -> CircuitSchemaBuilder {
+        self.circuit_id = Some(circuit_id.into());
+        self
+    }
+
+    /// Sets the name
+    ///
+    /// # Arguments
+    ///
+    ///  * `name` - The name of the schema, unique within the circuit
+    pub fn with_name(mut self, name: &str) -> CircuitSchemaBuilder {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the version
+    ///
+    /// # Arguments
+    ///
+    ///  * `version` - The version of the schema
+    pub fn with_version(mut self, version: &str) -> CircuitSchemaBuilder {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Sets the format
+    ///
+    /// # Arguments
+    ///
+    ///  * `format` - The format of the schema's definition, such as `json-schema`
+    pub fn with_format(mut self, format: &str) -> CircuitSchemaBuilder {
+        self.format = Some(format.into());
+        self
+    }
+
+    /// Sets the description
+    ///
+    /// # Arguments
+    ///
+    ///  * `description` - A human-readable description of the schema
+    pub fn with_description(mut self, description: &str) -> CircuitSchemaBuilder {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the definition
+    ///
+    /// # Arguments
+    ///
+    ///  * `definition` - The definition of the schema, in its format
+    pub fn with_definition(mut self, definition: &str) -> CircuitSchemaBuilder {
+        self.definition = Some(definition.into());
+        self
+    }
+
+    /// Builds the `CircuitSchema`
+    ///
+    /// Returns an error if the circuit ID, name, version, format or definition are not set, or
+    /// if the name, version or format are empty
+    pub fn build(self) -> Result<CircuitSchema, InvalidStateError> {
+        let circuit_id = self.circuit_id.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "unable to build, missing field: `circuit_id`".to_string(),
+            )
+        })?;
+
+        let name = non_empty(self.name, "name")?;
+        let version = non_empty(self.version, "version")?;
+        let format = non_empty(self.format, "format")?;
+
+        let definition = self.definition.ok_or_else(|| {
+            InvalidStateError::with_message(
+                "unable to build, missing field: `definition`".to_string(),
+            )
+        })?;
+
+        Ok(CircuitSchema {
+            circuit_id,
+            name,
+            version,
+            format,
+            description: self.description,
+            definition,
+        })
+    }
+}
+
+fn non_empty(value: Option<String>, field: &str) -> Result<String, InvalidStateError> {
+    match value {
+        Some(value) if !value.trim().is_empty() => Ok(value),
+        Some(_) => Err(InvalidStateError::with_message(format!(
+            "unable to build, field `{}` must not be empty",
+            field
+        ))),
+        None => Err(InvalidStateError::with_message(format!(
+            "unable to build, missing field: `{}`",
+            field
+        ))),
+    }
+}
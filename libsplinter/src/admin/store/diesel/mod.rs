@@ -29,58 +29,88 @@ use std::sync::{Arc, RwLock};
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use crate::admin::messages;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+use crate::admin::store::CircuitSchema;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use crate::admin::store::{
-    error::AdminServiceStoreError, AdminServiceStore, Circuit, CircuitNode, CircuitPredicate,
-    CircuitProposal, Service, ServiceId,
+    error::AdminServiceStoreError, AdminServiceStore, Circuit, CircuitNode, CircuitOrder,
+    CircuitPredicate, CircuitProposal, Service, ServiceId,
 };
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use crate::admin::store::{AdminServiceEvent, EventIter};
 use crate::store::pool::ConnectionPool;
 
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::add_circuit::AdminServiceStoreAddCircuitOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::add_event::AdminServiceStoreAddEventOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::add_proposal::AdminServiceStoreAddProposalOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::count_circuits::AdminServiceStoreCountCircuitsOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::count_proposals::AdminServiceStoreCountProposalsOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::get_circuit::AdminServiceStoreFetchCircuitOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+use operations::get_circuit_schema::AdminServiceStoreFetchCircuitSchemaOperation as _;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::get_node::AdminServiceStoreFetchNodeOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::get_proposal::AdminServiceStoreFetchProposalOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::get_service::AdminServiceStoreFetchServiceOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+use operations::list_circuit_schemas::AdminServiceStoreListCircuitSchemasOperation as _;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::list_circuits::AdminServiceStoreListCircuitsOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::list_events_by_management_type_since::AdminServiceStoreListEventsByManagementTypeSinceOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::list_events_since::AdminServiceStoreListEventsSinceOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::list_nodes::AdminServiceStoreListNodesOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::list_proposals::AdminServiceStoreListProposalsOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::list_services::AdminServiceStoreListServicesOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::remove_circuit::AdminServiceStoreRemoveCircuitOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+use operations::remove_circuit_schema::AdminServiceStoreRemoveCircuitSchemaOperation as _;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::remove_proposal::AdminServiceStoreRemoveProposalOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(all(
+    feature = "admin-service-circuit-search",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+use operations::search_circuits::AdminServiceStoreSearchCircuitsOperation as _;
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+use operations::set_circuit_schema::AdminServiceStoreSetCircuitSchemaOperation as _;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::update_circuit::AdminServiceStoreUpdateCircuitOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::update_proposal::AdminServiceStoreUpdateProposalOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::upgrade::AdminServiceStoreUpgradeProposalToCircuitOperation as _;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 use operations::AdminServiceStoreOperations;
 
 /// A database-backed AdminServiceStore, powered by [`Diesel`](https://crates.io/crates/diesel).
@@ -117,6 +147,15 @@ impl<C: diesel::Connection> DieselAdminServiceStore<C> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl Clone for DieselAdminServiceStore<diesel::mysql::MysqlConnection> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
 #[cfg(feature = "sqlite")]
 impl Clone for DieselAdminServiceStore<diesel::sqlite::SqliteConnection> {
     fn clone(&self) -> Self {
@@ -135,6 +174,229 @@ impl Clone for DieselAdminServiceStore<diesel::pg::PgConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl AdminServiceStore for DieselAdminServiceStore<diesel::mysql::MysqlConnection> {
+    fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_proposal(proposal))
+    }
+
+    fn update_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_proposal(proposal))
+    }
+
+    fn remove_proposal(&self, proposal_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).remove_proposal(proposal_id)
+        })
+    }
+
+    fn get_proposal(
+        &self,
+        proposal_id: &str,
+    ) -> Result<Option<CircuitProposal>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_proposal(proposal_id))
+    }
+
+    fn list_proposals(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitProposal>>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_proposals(predicates))
+    }
+
+    fn count_proposals(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_proposals(predicates))
+    }
+
+    fn add_circuit(
+        &self,
+        circuit: Circuit,
+        nodes: Vec<CircuitNode>,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).add_circuit(circuit, nodes)
+        })
+    }
+
+    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).update_circuit(circuit))
+    }
+
+    fn remove_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).remove_circuit(circuit_id))
+    }
+
+    fn get_circuit(&self, circuit_id: &str) -> Result<Option<Circuit>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_circuit(circuit_id))
+    }
+
+    fn list_circuits(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(predicates))
+    }
+
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        order: CircuitOrder,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .list_circuits_page(predicates, order, offset, limit)
+        })
+    }
+
+    fn count_circuits(
+        &self,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_circuits(predicates))
+    }
+
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn search_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).search_circuits(query, predicates, offset, limit)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn count_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).count_matching_circuits(query, predicates)
+        })
+    }
+
+    fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).upgrade_proposal_to_circuit(circuit_id)
+        })
+    }
+
+    fn get_node(&self, node_id: &str) -> Result<Option<CircuitNode>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_node(node_id))
+    }
+
+    fn list_nodes(
+        &self,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitNode>>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_nodes())
+    }
+
+    fn get_service(
+        &self,
+        service_id: &ServiceId,
+    ) -> Result<Option<Service>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).get_service(service_id))
+    }
+
+    fn list_services(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Service>>, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_services(circuit_id))
+    }
+
+    fn add_event(
+        &self,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).add_event(event))
+    }
+
+    fn list_events_since(&self, start: i64) -> Result<EventIter, AdminServiceStoreError> {
+        self.connection_pool
+            .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_events_since(start))
+    }
+
+    fn list_events_by_management_type_since(
+        &self,
+        management_type: String,
+        start: i64,
+    ) -> Result<EventIter, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .list_events_by_management_type_since(management_type, start)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).set_circuit_schema(schema))
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn get_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<CircuitSchema>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).get_circuit_schema(circuit_id, name, version)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn list_circuit_schemas(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitSchema>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuit_schemas(circuit_id)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn remove_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).remove_circuit_schema(circuit_id, name, version)
+        })
+    }
+
+    fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
+        Box::new(self.clone())
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
     fn add_proposal(&self, proposal: CircuitProposal) -> Result<(), AdminServiceStoreError> {
@@ -210,6 +472,19 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(predicates))
     }
 
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        order: CircuitOrder,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .list_circuits_page(predicates, order, offset, limit)
+        })
+    }
+
     fn count_circuits(
         &self,
         predicates: &[CircuitPredicate],
@@ -218,6 +493,30 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_circuits(predicates))
     }
 
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn search_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).search_circuits(query, predicates, offset, limit)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn count_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).count_matching_circuits(query, predicates)
+        })
+    }
+
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
         self.connection_pool.execute_write(|conn| {
             AdminServiceStoreOperations::new(conn).upgrade_proposal_to_circuit(circuit_id)
@@ -276,6 +575,46 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::pg::PgConnection> {
         })
     }
 
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).set_circuit_schema(schema))
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn get_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<CircuitSchema>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).get_circuit_schema(circuit_id, name, version)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn list_circuit_schemas(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitSchema>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuit_schemas(circuit_id)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn remove_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).remove_circuit_schema(circuit_id, name, version)
+        })
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -356,6 +695,19 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).list_circuits(predicates))
     }
 
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        order: CircuitOrder,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn)
+                .list_circuits_page(predicates, order, offset, limit)
+        })
+    }
+
     fn count_circuits(
         &self,
         predicates: &[CircuitPredicate],
@@ -364,6 +716,30 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
             .execute_read(|conn| AdminServiceStoreOperations::new(conn).count_circuits(predicates))
     }
 
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn search_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).search_circuits(query, predicates, offset, limit)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn count_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).count_matching_circuits(query, predicates)
+        })
+    }
+
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
         self.connection_pool.execute_write(|conn| {
             AdminServiceStoreOperations::new(conn).upgrade_proposal_to_circuit(circuit_id)
@@ -422,6 +798,46 @@ impl AdminServiceStore for DieselAdminServiceStore<diesel::sqlite::SqliteConnect
         })
     }
 
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool
+            .execute_write(|conn| AdminServiceStoreOperations::new(conn).set_circuit_schema(schema))
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn get_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<CircuitSchema>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).get_circuit_schema(circuit_id, name, version)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn list_circuit_schemas(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitSchema>>, AdminServiceStoreError> {
+        self.connection_pool.execute_read(|conn| {
+            AdminServiceStoreOperations::new(conn).list_circuit_schemas(circuit_id)
+        })
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn remove_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        self.connection_pool.execute_write(|conn| {
+            AdminServiceStoreOperations::new(conn).remove_circuit_schema(circuit_id, name, version)
+        })
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
@@ -443,7 +859,8 @@ pub mod tests {
     use crate::public_key::PublicKey;
 
     use diesel::{
-        r2d2::{ConnectionManager, Pool},
+        connection::SimpleConnection,
+        r2d2::{ConnectionManager, CustomizeConnection, Pool},
         sqlite::SqliteConnection,
     };
 
@@ -771,6 +1188,175 @@ pub mod tests {
         );
     }
 
+    /// Verify that circuits can be searched by display name and by the comments of the proposal
+    /// they were created from.
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add a proposal and upgrade it to a circuit
+    /// 4. Add an active and a disbanded circuit directly, without a proposal
+    /// 5. Search by the shared display name, validate both active circuits are returned
+    /// 6. Search by a prefix of the display name, validate both active circuits are returned
+    /// 7. Search by the proposal's comments, validate only the upgraded circuit is returned
+    /// 8. Search with a status predicate, validate only the disbanded circuit is returned
+    /// 9. Search for an unknown term, validate no circuits are returned
+    /// 10. Count the circuits matching the display name, validate the count is 2
+    /// 11. Search for one page at a time, validate each page holds a different circuit
+    /// 12. Rename the upgraded circuit, validate it is found by its new display name and is still
+    ///     found by its proposal's comments
+    /// 13. Remove the upgraded circuit, validate it is no longer returned by a search
+    #[cfg(feature = "admin-service-circuit-search")]
+    #[test]
+    fn test_search_circuits() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        store
+            .add_proposal(create_proposal())
+            .expect("Unable to add circuit proposal");
+        store
+            .upgrade_proposal_to_circuit("WBKLF-BBBBB")
+            .expect("Unable to upgrade proposal");
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-AAAAA", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+        store
+            .add_circuit(
+                create_circuit("WBKLF-CCCCC", CircuitStatus::Disbanded),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+
+        let circuit_ids = |circuits: Box<dyn ExactSizeIterator<Item = Circuit>>| {
+            let mut ids = circuits
+                .map(|circuit| circuit.circuit_id().to_string())
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            circuit_ids(
+                store
+                    .search_circuits("test_display", &[], 0, usize::MAX)
+                    .expect("Unable to search circuits")
+            ),
+            vec!["WBKLF-AAAAA", "WBKLF-BBBBB"]
+        );
+
+        assert_eq!(
+            circuit_ids(
+                store
+                    .search_circuits("tes", &[], 0, usize::MAX)
+                    .expect("Unable to search circuits")
+            ),
+            vec!["WBKLF-AAAAA", "WBKLF-BBBBB"]
+        );
+
+        assert_eq!(
+            circuit_ids(
+                store
+                    .search_circuits("this is a", &[], 0, usize::MAX)
+                    .expect("Unable to search circuits")
+            ),
+            vec!["WBKLF-BBBBB"]
+        );
+
+        assert_eq!(
+            circuit_ids(
+                store
+                    .search_circuits(
+                        "test_display",
+                        &[CircuitPredicate::CircuitStatus(CircuitStatus::Disbanded)],
+                        0,
+                        usize::MAX,
+                    )
+                    .expect("Unable to search circuits")
+            ),
+            vec!["WBKLF-CCCCC"]
+        );
+
+        assert!(store
+            .search_circuits("\"unknown OR", &[], 0, usize::MAX)
+            .expect("Unable to search circuits")
+            .next()
+            .is_none());
+
+        assert_eq!(
+            store
+                .count_matching_circuits("test_display", &[])
+                .expect("Unable to count circuits"),
+            2
+        );
+        let first_page = circuit_ids(
+            store
+                .search_circuits("test_display", &[], 0, 1)
+                .expect("Unable to search circuits"),
+        );
+        let second_page = circuit_ids(
+            store
+                .search_circuits("test_display", &[], 1, 1)
+                .expect("Unable to search circuits"),
+        );
+        assert_eq!(first_page.len(), 1);
+        assert_eq!(second_page.len(), 1);
+        assert_ne!(first_page, second_page);
+
+        let circuit = store
+            .get_circuit("WBKLF-BBBBB")
+            .expect("Unable to get circuit")
+            .expect("Got None when expecting circuit");
+        let renamed_circuit = CircuitBuilder::default()
+            .with_circuit_id(circuit.circuit_id())
+            .with_roster(circuit.roster())
+            .with_members(circuit.members())
+            .with_authorization_type(circuit.authorization_type())
+            .with_persistence(circuit.persistence())
+            .with_durability(circuit.durability())
+            .with_routes(circuit.routes())
+            .with_circuit_management_type(circuit.circuit_management_type())
+            .with_circuit_version(circuit.circuit_version())
+            .with_circuit_status(circuit.circuit_status())
+            .with_display_name("renamed")
+            .build()
+            .expect("Unable to build circuit");
+        store
+            .update_circuit(renamed_circuit)
+            .expect("Unable to update circuit");
+
+        assert_eq!(
+            circuit_ids(
+                store
+                    .search_circuits("renamed", &[], 0, usize::MAX)
+                    .expect("Unable to search circuits")
+            ),
+            vec!["WBKLF-BBBBB"]
+        );
+        assert_eq!(
+            circuit_ids(
+                store
+                    .search_circuits("this is a", &[], 0, usize::MAX)
+                    .expect("Unable to search circuits")
+            ),
+            vec!["WBKLF-BBBBB"]
+        );
+
+        store
+            .remove_circuit("WBKLF-BBBBB")
+            .expect("Unable to remove circuit");
+
+        assert!(store
+            .search_circuits("this is a", &[], 0, usize::MAX)
+            .expect("Unable to search circuits")
+            .next()
+            .is_none());
+    }
+
     /// Verify that a circuit can be added to the store correctly and then fetched from the store
     ///
     /// 1. Run sqlite migrations
@@ -939,6 +1525,87 @@ pub mod tests {
         assert_eq!(circuits.len(), 2);
     }
 
+    /// Verify that list_circuits_page works correctly
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Add three circuits to the store, in an order that differs from the order of their IDs
+    /// 4. List circuits in ascending and descending order of their IDs, validate the order
+    /// 5. List circuits in ascending and descending order of their creation, validate the order
+    /// 6. List pages of the circuits with an offset and limit, validate that only the circuits
+    ///    of the page are returned
+    /// 7. List a page of the circuits with a management type predicate, validate that the
+    ///    predicate is applied before the page is selected
+    #[test]
+    fn test_list_circuits_page() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let circuit_b = create_circuit("WBKLF-BBBBB", CircuitStatus::Active);
+        let circuit_c = create_extra_circuit("WBKLF-CCCCC");
+        let circuit_d = create_circuit("WBKLF-DDDDD", CircuitStatus::Active);
+
+        // Circuits are created a few milliseconds apart, so that their creation times differ
+        store
+            .add_circuit(circuit_c.clone(), create_extra_nodes())
+            .expect("Unable to add circuit");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        store
+            .add_circuit(circuit_d.clone(), create_nodes())
+            .expect("Unable to add circuit");
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        store
+            .add_circuit(circuit_b.clone(), create_nodes())
+            .expect("Unable to add circuit");
+
+        let list = |order, offset, limit| {
+            store
+                .list_circuits_page(&[], order, offset, limit)
+                .expect("Unable to list circuits")
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            list(CircuitOrder::CircuitIdAsc, 0, 10),
+            vec![circuit_b.clone(), circuit_c.clone(), circuit_d.clone()]
+        );
+        assert_eq!(
+            list(CircuitOrder::CircuitIdDesc, 0, 10),
+            vec![circuit_d.clone(), circuit_c.clone(), circuit_b.clone()]
+        );
+        assert_eq!(
+            list(CircuitOrder::CreatedAsc, 0, 10),
+            vec![circuit_c.clone(), circuit_d.clone(), circuit_b.clone()]
+        );
+        assert_eq!(
+            list(CircuitOrder::CreatedDesc, 0, 10),
+            vec![circuit_b.clone(), circuit_d.clone(), circuit_c.clone()]
+        );
+
+        assert_eq!(
+            list(CircuitOrder::CircuitIdAsc, 1, 1),
+            vec![circuit_c.clone()]
+        );
+        assert_eq!(
+            list(CircuitOrder::CreatedDesc, 2, 10),
+            vec![circuit_c.clone()]
+        );
+        assert!(list(CircuitOrder::CircuitIdAsc, 3, 10).is_empty());
+
+        let circuits = store
+            .list_circuits_page(
+                &[CircuitPredicate::ManagementTypeEq("gameroom".to_string())],
+                CircuitOrder::CircuitIdAsc,
+                1,
+                10,
+            )
+            .expect("Unable to list circuits with management type predicate")
+            .collect::<Vec<_>>();
+
+        assert_eq!(circuits, vec![circuit_d]);
+    }
+
     /// Verify that count_circuits works correctly
     ///
     /// 1. Run sqlite migrations
@@ -1095,6 +1762,77 @@ pub mod tests {
         assert_eq!(None, fetched_circuit);
     }
 
+    /// Verify that schemas can be registered on a circuit, listed, replaced and removed
+    ///
+    /// 1. Run sqlite migrations
+    /// 2. Create DieselAdminServiceStore
+    /// 3. Validate a schema cannot be registered on a circuit that does not exist
+    /// 4. Add a circuit and register two versions of a schema
+    /// 5. Validate the schemas are listed in version order
+    /// 6. Replace a schema and validate the new definition is returned
+    /// 7. Remove a schema and validate removing it again fails
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    #[test]
+    fn test_circuit_schemas() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let schema_v1 = create_circuit_schema("WBKLF-BBBBB", "1", "{}");
+        let schema_v2 = create_circuit_schema("WBKLF-BBBBB", "2", "{}");
+
+        assert!(matches!(
+            store.set_circuit_schema(schema_v1.clone()),
+            Err(AdminServiceStoreError::InvalidStateError(_))
+        ));
+
+        store
+            .add_circuit(
+                create_circuit("WBKLF-BBBBB", CircuitStatus::Active),
+                create_nodes(),
+            )
+            .expect("Unable to add circuit");
+
+        store
+            .set_circuit_schema(schema_v2.clone())
+            .expect("Unable to set schema");
+        store
+            .set_circuit_schema(schema_v1.clone())
+            .expect("Unable to set schema");
+
+        let schemas = store
+            .list_circuit_schemas("WBKLF-BBBBB")
+            .expect("Unable to list schemas")
+            .collect::<Vec<_>>();
+        assert_eq!(schemas, vec![schema_v1.clone(), schema_v2.clone()]);
+
+        let replaced = create_circuit_schema("WBKLF-BBBBB", "1", r#"{"type": "object"}"#);
+        store
+            .set_circuit_schema(replaced.clone())
+            .expect("Unable to replace schema");
+        assert_eq!(
+            store
+                .get_circuit_schema("WBKLF-BBBBB", "com.example.orders", "1")
+                .expect("Unable to get schema"),
+            Some(replaced)
+        );
+
+        store
+            .remove_circuit_schema("WBKLF-BBBBB", "com.example.orders", "1")
+            .expect("Unable to remove schema");
+        assert!(matches!(
+            store.remove_circuit_schema("WBKLF-BBBBB", "com.example.orders", "1"),
+            Err(AdminServiceStoreError::InvalidStateError(_))
+        ));
+        assert_eq!(
+            store
+                .list_circuit_schemas("WBKLF-BBBBB")
+                .expect("Unable to list schemas")
+                .collect::<Vec<_>>(),
+            vec![schema_v2]
+        );
+    }
+
     /// Verify that a service can be fetched from the store
     ///
     /// 1. Run sqlite migrations
@@ -1430,6 +2168,7 @@ pub mod tests {
         let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
         let pool = Pool::builder()
             .max_size(1)
+            .connection_customizer(Box::new(ForeignKeyCustomizer))
             .build(connection_manager)
             .expect("Failed to build connection pool");
 
@@ -1439,6 +2178,17 @@ pub mod tests {
         pool
     }
 
+    /// Enables foreign keys on every connection, as is done for the pools used in production.
+    #[derive(Debug)]
+    struct ForeignKeyCustomizer;
+
+    impl CustomizeConnection<SqliteConnection, diesel::r2d2::Error> for ForeignKeyCustomizer {
+        fn on_acquire(&self, conn: &mut SqliteConnection) -> Result<(), diesel::r2d2::Error> {
+            conn.batch_execute("PRAGMA foreign_keys = ON;")
+                .map_err(diesel::r2d2::Error::QueryError)
+        }
+    }
+
     fn create_proposal() -> CircuitProposal {
         CircuitProposalBuilder::default()
             .with_proposal_type(&ProposalType::Create)
@@ -1789,6 +2539,18 @@ pub mod tests {
             .build().expect("Unable to build proposals")
     }
 
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn create_circuit_schema(circuit_id: &str, version: &str, definition: &str) -> CircuitSchema {
+        crate::admin::store::CircuitSchemaBuilder::new()
+            .with_circuit_id(circuit_id)
+            .with_name("com.example.orders")
+            .with_version(version)
+            .with_format("json-schema")
+            .with_definition(definition)
+            .build()
+            .expect("Unable to build circuit schema")
+    }
+
     fn create_nodes() -> Vec<CircuitNode> {
         vec![
             CircuitNodeBuilder::default()
@@ -28,6 +28,8 @@ use diesel::{
 };
 
 use crate::admin::service::messages::{self, CreateCircuit};
+#[cfg(feature = "admin-service-circuit-schemas")]
+use crate::admin::store::diesel::schema::circuit_schema;
 use crate::admin::store::diesel::schema::{
     admin_event_circuit_proposal, admin_event_proposed_circuit, admin_event_proposed_node,
     admin_event_proposed_node_endpoint, admin_event_proposed_service,
@@ -45,6 +47,8 @@ use crate::admin::store::{
     Vote, VoteRecord, VoteRecordBuilder,
 };
 use crate::admin::store::{Circuit, CircuitProposal, ProposedCircuit};
+#[cfg(feature = "admin-service-circuit-schemas")]
+use crate::admin::store::{CircuitSchema, CircuitSchemaBuilder};
 use crate::error::{InternalError, InvalidStateError};
 use crate::public_key::PublicKey;
 
@@ -422,6 +426,54 @@ impl TryFrom<&Circuit> for Vec<ServiceArgumentModel> {
     }
 }
 
+/// Database model representation of a `CircuitSchema`
+#[cfg(feature = "admin-service-circuit-schemas")]
+#[derive(Debug, PartialEq, Eq, Identifiable, Insertable, Queryable)]
+#[table_name = "circuit_schema"]
+#[primary_key(circuit_id, name, version)]
+pub struct CircuitSchemaModel {
+    pub circuit_id: String,
+    pub name: String,
+    pub version: String,
+    pub format: String,
+    pub description: Option<String>,
+    pub definition: String,
+}
+
+#[cfg(feature = "admin-service-circuit-schemas")]
+impl From<&CircuitSchema> for CircuitSchemaModel {
+    fn from(schema: &CircuitSchema) -> Self {
+        CircuitSchemaModel {
+            circuit_id: schema.circuit_id().into(),
+            name: schema.name().into(),
+            version: schema.version().into(),
+            format: schema.format().into(),
+            description: schema.description().map(String::from),
+            definition: schema.definition().into(),
+        }
+    }
+}
+
+#[cfg(feature = "admin-service-circuit-schemas")]
+impl TryFrom<CircuitSchemaModel> for CircuitSchema {
+    type Error = AdminServiceStoreError;
+
+    fn try_from(model: CircuitSchemaModel) -> Result<Self, Self::Error> {
+        let mut builder = CircuitSchemaBuilder::new()
+            .with_circuit_id(&model.circuit_id)
+            .with_name(&model.name)
+            .with_version(&model.version)
+            .with_format(&model.format)
+            .with_definition(&model.definition);
+        if let Some(description) = &model.description {
+            builder = builder.with_description(description);
+        }
+        builder
+            .build()
+            .map_err(AdminServiceStoreError::InvalidStateError)
+    }
+}
+
 /// Database model representation of a `Circuit`
 #[derive(
     Debug, PartialEq, Eq, Associations, Identifiable, Insertable, Queryable, QueryableByName,
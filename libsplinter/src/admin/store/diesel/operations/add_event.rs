@@ -17,6 +17,8 @@
 use std::convert::TryFrom;
 
 use diesel::{dsl::insert_into, prelude::*};
+#[cfg(feature = "mysql")]
+use diesel::{dsl::sql, sql_types::BigInt};
 
 use super::AdminServiceStoreOperations;
 
@@ -49,6 +51,96 @@ pub(in crate::admin::store::diesel) trait AdminServiceStoreAddEventOperation {
     ) -> Result<AdminServiceEvent, AdminServiceStoreError>;
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> AdminServiceStoreAddEventOperation
+    for AdminServiceStoreOperations<'a, diesel::mysql::MysqlConnection>
+{
+    fn add_event(
+        &self,
+        event: messages::AdminServiceEvent,
+    ) -> Result<AdminServiceEvent, AdminServiceStoreError> {
+        self.conn.transaction::<AdminServiceEvent, _, _>(|| {
+            // Create a `NewAdminServiceEventModel` from the event
+            let new_event: NewAdminServiceEventModel = NewAdminServiceEventModel::from(&event);
+            // This creates the initial event entry; MySQL does not support `RETURNING`.
+            insert_into(admin_service_event::table)
+                .values(new_event)
+                .execute(self.conn)?;
+            // Retrieving the autoincremented ID generated by this connection's insert, used to
+            // associate the other database entries to this event.
+            let event_id: i64 =
+                diesel::select(sql::<BigInt>("LAST_INSERT_ID()")).get_result(self.conn)?;
+
+            // Saving the event's proposal to build the required models.
+            let proposal = event.proposal().clone();
+
+            // Check if an `CircuitProposal` already exists with the given `event_id`
+            if admin_event_circuit_proposal::table
+                .filter(admin_event_circuit_proposal::event_id.eq(event_id))
+                .first::<AdminEventCircuitProposalModel>(self.conn)
+                .optional()?
+                .is_some()
+            {
+                return Err(AdminServiceStoreError::ConstraintViolationError(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+                ));
+            }
+            // Insert the database model of the admin event's `CircuitProposal`
+            let proposal_model = AdminEventCircuitProposalModel::from((event_id, &proposal));
+            insert_into(admin_event_circuit_proposal::table)
+                .values(proposal_model)
+                .execute(self.conn)?;
+            // Insert `ProposedCircuitModel`, representing the `create_circuit` of an admin event's
+            // `CircuitProposal`
+            let proposed_circuit_model =
+                AdminEventProposedCircuitModel::from((event_id, &proposal.circuit));
+            insert_into(admin_event_proposed_circuit::table)
+                .values(proposed_circuit_model)
+                .execute(self.conn)?;
+            // Insert `members` of an admin event's `CreateCircuit`, represented by the
+            // `AdminEventProposedCircuitModel`
+            let proposed_members: Vec<AdminEventProposedNodeModel> =
+                AdminEventProposedNodeModel::list_from_proposal_with_id(event_id, &proposal)?;
+            insert_into(admin_event_proposed_node::table)
+                .values(proposed_members)
+                .execute(self.conn)?;
+            // Insert the node `endpoints` and the proposed `members` of an admin event's
+            // `CreateCircuit`, represented by the `AdminEventProposedCircuitModel`
+            let proposed_member_endpoints: Vec<AdminEventProposedNodeEndpointModel> =
+                AdminEventProposedNodeEndpointModel::list_from_proposal_with_id(
+                    event_id, &proposal,
+                )?;
+            insert_into(admin_event_proposed_node_endpoint::table)
+                .values(proposed_member_endpoints)
+                .execute(self.conn)?;
+            // Insert `roster`, list of `Services` of an admin event's `CreateCircuit`,
+            // represented by the `AdminEventProposedCircuitModel`
+            let proposed_services: Vec<AdminEventProposedServiceModel> =
+                AdminEventProposedServiceModel::list_from_proposal_with_id(event_id, &proposal)?;
+            insert_into(admin_event_proposed_service::table)
+                .values(proposed_services)
+                .execute(self.conn)?;
+            // Insert `service_arguments` from the `Services` inserted above
+            let proposed_service_arguments: Vec<AdminEventProposedServiceArgumentModel> =
+                AdminEventProposedServiceArgumentModel::list_from_proposal_with_id(
+                    event_id, &proposal,
+                )?;
+            insert_into(admin_event_proposed_service_argument::table)
+                .values(proposed_service_arguments)
+                .execute(self.conn)?;
+            // Insert `votes` from the `CircuitProposal`
+            let vote_records: Vec<AdminEventVoteRecordModel> =
+                AdminEventVoteRecordModel::list_from_proposal_with_id(event_id, &proposal)?;
+            insert_into(admin_event_vote_record::table)
+                .values(vote_records)
+                .execute(self.conn)?;
+
+            AdminServiceEvent::try_from((event_id, &event))
+                .map_err(AdminServiceStoreError::InvalidStateError)
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> AdminServiceStoreAddEventOperation
     for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
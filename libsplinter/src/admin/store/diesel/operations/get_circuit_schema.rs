@@ -0,0 +1,63 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "fetch circuit schema" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{Nullable, Text},
+};
+
+use crate::admin::store::{
+    diesel::{models::CircuitSchemaModel, schema::circuit_schema},
+    error::AdminServiceStoreError,
+    CircuitSchema,
+};
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreFetchCircuitSchemaOperation {
+    fn get_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<CircuitSchema>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreFetchCircuitSchemaOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    CircuitSchemaModel:
+        diesel::Queryable<(Text, Text, Text, Text, Nullable<Text>, Text), C::Backend>,
+{
+    fn get_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<CircuitSchema>, AdminServiceStoreError> {
+        circuit_schema::table
+            .filter(circuit_schema::circuit_id.eq(circuit_id))
+            .filter(circuit_schema::name.eq(name))
+            .filter(circuit_schema::version.eq(version))
+            .first::<CircuitSchemaModel>(self.conn)
+            .optional()?
+            .map(CircuitSchema::try_from)
+            .transpose()
+    }
+}
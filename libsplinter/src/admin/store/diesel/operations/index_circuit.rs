@@ -0,0 +1,160 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "index circuit" operations for the `DieselAdminServiceStore`.
+//!
+//! A circuit's entry in the `circuit_search` index holds its display name, along with the comments
+//! and application metadata of the proposal it was created from.
+
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text};
+
+use crate::admin::store::error::AdminServiceStoreError;
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreIndexCircuitOperation {
+    /// Writes the search index entry of a circuit, replacing any existing entry. The display name
+    /// is read from the stored circuit.
+    fn index_circuit(
+        &self,
+        circuit_id: &str,
+        comments: Option<&str>,
+        metadata: Option<&str>,
+    ) -> Result<(), AdminServiceStoreError>;
+
+    /// Rebuilds the search index entry of a circuit from the stored circuit, keeping the comments
+    /// and metadata already in the index.
+    fn reindex_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
+}
+
+#[cfg(feature = "mysql")]
+impl<'a> AdminServiceStoreIndexCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::mysql::MysqlConnection>
+{
+    fn index_circuit(
+        &self,
+        circuit_id: &str,
+        comments: Option<&str>,
+        metadata: Option<&str>,
+    ) -> Result<(), AdminServiceStoreError> {
+        diesel::sql_query(
+            "REPLACE INTO circuit_search (circuit_id, display_name, comments, metadata) \
+             SELECT circuit_id, display_name, ?, ? FROM circuit WHERE circuit_id = ?",
+        )
+        .bind::<Nullable<Text>, _>(comments)
+        .bind::<Nullable<Text>, _>(metadata)
+        .bind::<Text, _>(circuit_id)
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+
+    fn reindex_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        diesel::sql_query(
+            "UPDATE circuit_search JOIN circuit ON circuit.circuit_id = circuit_search.circuit_id \
+             SET circuit_search.display_name = circuit.display_name \
+             WHERE circuit.circuit_id = ?",
+        )
+        .bind::<Text, _>(circuit_id)
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreIndexCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn index_circuit(
+        &self,
+        circuit_id: &str,
+        comments: Option<&str>,
+        metadata: Option<&str>,
+    ) -> Result<(), AdminServiceStoreError> {
+        diesel::sql_query(
+            "INSERT INTO circuit_search (circuit_id, comments, metadata, document) \
+             SELECT circuit_id, $2, $3, \
+             setweight(to_tsvector('simple', coalesce(display_name, '')), 'A') || \
+             setweight(to_tsvector('simple', coalesce($2, '')), 'B') || \
+             setweight(to_tsvector('simple', coalesce($3, '')), 'C') \
+             FROM circuit WHERE circuit_id = $1 \
+             ON CONFLICT (circuit_id) DO UPDATE SET comments = EXCLUDED.comments, \
+             metadata = EXCLUDED.metadata, document = EXCLUDED.document",
+        )
+        .bind::<Text, _>(circuit_id)
+        .bind::<Nullable<Text>, _>(comments)
+        .bind::<Nullable<Text>, _>(metadata)
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+
+    fn reindex_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        diesel::sql_query(
+            "UPDATE circuit_search SET document = \
+             setweight(to_tsvector('simple', coalesce(circuit.display_name, '')), 'A') || \
+             setweight(to_tsvector('simple', coalesce(circuit_search.comments, '')), 'B') || \
+             setweight(to_tsvector('simple', coalesce(circuit_search.metadata, '')), 'C') \
+             FROM circuit WHERE circuit.circuit_id = circuit_search.circuit_id \
+             AND circuit.circuit_id = $1",
+        )
+        .bind::<Text, _>(circuit_id)
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreIndexCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn index_circuit(
+        &self,
+        circuit_id: &str,
+        comments: Option<&str>,
+        metadata: Option<&str>,
+    ) -> Result<(), AdminServiceStoreError> {
+        // The FTS5 table has no unique constraint on the circuit ID to upsert against
+        diesel::sql_query("DELETE FROM circuit_search WHERE circuit_id = ?")
+            .bind::<Text, _>(circuit_id)
+            .execute(self.conn)?;
+
+        diesel::sql_query(
+            "INSERT INTO circuit_search (circuit_id, display_name, comments, metadata) \
+             SELECT circuit_id, display_name, ?, ? FROM circuit WHERE circuit_id = ?",
+        )
+        .bind::<Nullable<Text>, _>(comments)
+        .bind::<Nullable<Text>, _>(metadata)
+        .bind::<Text, _>(circuit_id)
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+
+    fn reindex_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        diesel::sql_query(
+            "UPDATE circuit_search SET display_name = \
+             (SELECT display_name FROM circuit WHERE circuit_id = ?) \
+             WHERE circuit_id = ?",
+        )
+        .bind::<Text, _>(circuit_id)
+        .bind::<Text, _>(circuit_id)
+        .execute(self.conn)?;
+
+        Ok(())
+    }
+}
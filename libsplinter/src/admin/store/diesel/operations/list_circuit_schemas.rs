@@ -0,0 +1,60 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "list circuit schemas" operation for the `DieselAdminServiceStore`.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    prelude::*,
+    sql_types::{Nullable, Text},
+};
+
+use crate::admin::store::{
+    diesel::{models::CircuitSchemaModel, schema::circuit_schema},
+    error::AdminServiceStoreError,
+    CircuitSchema,
+};
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreListCircuitSchemasOperation {
+    fn list_circuit_schemas(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitSchema>>, AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreListCircuitSchemasOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    CircuitSchemaModel:
+        diesel::Queryable<(Text, Text, Text, Text, Nullable<Text>, Text), C::Backend>,
+{
+    fn list_circuit_schemas(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitSchema>>, AdminServiceStoreError> {
+        let schemas = circuit_schema::table
+            .filter(circuit_schema::circuit_id.eq(circuit_id))
+            .order((circuit_schema::name.asc(), circuit_schema::version.asc()))
+            .load::<CircuitSchemaModel>(self.conn)?
+            .into_iter()
+            .map(CircuitSchema::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Box::new(schemas.into_iter()))
+    }
+}
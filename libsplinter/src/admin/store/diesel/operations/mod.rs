@@ -14,47 +14,74 @@
 
 //! Provides database operations for the `DieselAdminServiceStore`.
 
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod add_circuit;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod add_event;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod add_proposal;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod count_circuits;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod count_proposals;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod get_circuit;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+pub(super) mod get_circuit_schema;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod get_node;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod get_proposal;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod get_service;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
+pub(super) mod index_circuit;
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+pub(super) mod list_circuit_schemas;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod list_circuits;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod list_events;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod list_events_by_management_type_since;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod list_events_since;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod list_nodes;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod list_proposals;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod list_services;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod remove_circuit;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+pub(super) mod remove_circuit_schema;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod remove_proposal;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(all(
+    feature = "admin-service-circuit-search",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+pub(super) mod search_circuits;
+#[cfg(all(
+    feature = "admin-service-circuit-schemas",
+    any(feature = "mysql", feature = "postgres", feature = "sqlite")
+))]
+pub(super) mod set_circuit_schema;
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod update_circuit;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod update_proposal;
-#[cfg(any(feature = "sqlite", feature = "postgres"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(super) mod upgrade;
 
 pub struct AdminServiceStoreOperations<'a, C> {
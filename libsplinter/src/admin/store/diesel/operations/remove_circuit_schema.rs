@@ -0,0 +1,62 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "remove circuit schema" operation for the `DieselAdminServiceStore`.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::admin::store::{diesel::schema::circuit_schema, error::AdminServiceStoreError};
+use crate::error::InvalidStateError;
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreRemoveCircuitSchemaOperation {
+    fn remove_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<(), AdminServiceStoreError>;
+}
+
+impl<'a, C> AdminServiceStoreRemoveCircuitSchemaOperation for AdminServiceStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    fn remove_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        let deleted = delete(
+            circuit_schema::table
+                .filter(circuit_schema::circuit_id.eq(circuit_id))
+                .filter(circuit_schema::name.eq(name))
+                .filter(circuit_schema::version.eq(version)),
+        )
+        .execute(self.conn)?;
+
+        if deleted == 0 {
+            return Err(AdminServiceStoreError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "Schema {} version {} is not registered on circuit {}",
+                    name, version, circuit_id
+                )),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,273 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "search circuits" operation for the `DieselAdminServiceStore`.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+use diesel::sql_types::{Binary, Integer, Nullable, Text};
+
+use crate::admin::store::{
+    diesel::models::{CircuitMemberModel, CircuitModel},
+    error::AdminServiceStoreError,
+    Circuit, CircuitOrder, CircuitPredicate,
+};
+use crate::error::InternalError;
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreSearchCircuitsOperation {
+    fn search_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
+
+    fn count_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError>;
+}
+
+/// Queries the backend's `circuit_search` index.
+pub(in crate::admin::store::diesel) trait CircuitSearchIndex {
+    /// Returns the IDs of the circuits in the index which match the query, in order of relevance.
+    fn search_index(&self, query: &str) -> Result<Vec<String>, AdminServiceStoreError>;
+}
+
+/// A row of the `circuit_search` index
+#[derive(QueryableByName)]
+struct CircuitSearchResult {
+    #[sql_type = "Text"]
+    circuit_id: String,
+}
+
+impl<'a, C> AdminServiceStoreSearchCircuitsOperation for AdminServiceStoreOperations<'a, C>
+where
+    Self: CircuitSearchIndex,
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<Integer, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+{
+    fn search_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = Circuit>>, _, _>(|| {
+                let circuits = self
+                    .select_matching_circuits(query, predicates)?
+                    .into_iter()
+                    .skip(offset)
+                    .take(limit)
+                    .collect();
+
+                // Only the requested page of circuits is fully loaded
+                Ok(Box::new(self.load_circuits(circuits)?.into_iter()))
+            })
+    }
+
+    fn count_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        self.conn.transaction::<u32, _, _>(|| {
+            let count = self.select_matching_circuits(query, predicates)?.len();
+            u32::try_from(count).map_err(|_| {
+                AdminServiceStoreError::InternalError(InternalError::with_message(
+                    "The number of circuits is larger than the max u32".to_string(),
+                ))
+            })
+        })
+    }
+}
+
+impl<'a, C> AdminServiceStoreOperations<'a, C>
+where
+    Self: CircuitSearchIndex,
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    i32: diesel::deserialize::FromSql<Integer, C::Backend>,
+    i16: diesel::deserialize::FromSql<diesel::sql_types::SmallInt, C::Backend>,
+    CircuitMemberModel: diesel::Queryable<(Text, Text, Integer, Nullable<Binary>), C::Backend>,
+{
+    /// Selects the circuits which match both the query and the predicates, in order of relevance.
+    /// As with listing circuits, only active circuits are selected unless a circuit status
+    /// predicate is provided.
+    fn select_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<Vec<CircuitModel>, AdminServiceStoreError> {
+        let circuit_ids = self.search_index(query)?;
+        if circuit_ids.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut circuits = self.select_circuits(
+            predicates,
+            Some(circuit_ids.as_slice()),
+            CircuitOrder::default(),
+            0,
+            usize::MAX,
+        )?;
+
+        let ranks = circuit_ids
+            .iter()
+            .enumerate()
+            .map(|(rank, circuit_id)| (circuit_id.as_str(), rank))
+            .collect::<HashMap<_, _>>();
+        circuits.sort_by_key(|circuit| ranks.get(circuit.circuit_id.as_str()).copied());
+
+        Ok(circuits)
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<'a> CircuitSearchIndex for AdminServiceStoreOperations<'a, diesel::mysql::MysqlConnection> {
+    fn search_index(&self, query: &str) -> Result<Vec<String>, AdminServiceStoreError> {
+        let match_expression = to_boolean_mode_expression(query);
+        if match_expression.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(diesel::sql_query(
+            "SELECT circuit_id FROM circuit_search \
+             WHERE MATCH (display_name, comments, metadata) AGAINST (? IN BOOLEAN MODE) \
+             ORDER BY MATCH (display_name, comments, metadata) \
+             AGAINST (? IN BOOLEAN MODE) DESC, circuit_id DESC",
+        )
+        .bind::<Text, _>(&match_expression)
+        .bind::<Text, _>(&match_expression)
+        .load::<CircuitSearchResult>(self.conn)?
+        .into_iter()
+        .map(|result| result.circuit_id)
+        .collect())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CircuitSearchIndex for AdminServiceStoreOperations<'a, diesel::pg::PgConnection> {
+    fn search_index(&self, query: &str) -> Result<Vec<String>, AdminServiceStoreError> {
+        Ok(diesel::sql_query(
+            "SELECT circuit_id FROM circuit_search \
+             WHERE document @@ plainto_tsquery('simple', $1) \
+             ORDER BY ts_rank(document, plainto_tsquery('simple', $1)) DESC, \
+             circuit_id DESC",
+        )
+        .bind::<Text, _>(query)
+        .load::<CircuitSearchResult>(self.conn)?
+        .into_iter()
+        .map(|result| result.circuit_id)
+        .collect())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CircuitSearchIndex for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn search_index(&self, query: &str) -> Result<Vec<String>, AdminServiceStoreError> {
+        let match_expression = to_fts5_match_expression(query);
+        if match_expression.is_empty() {
+            return Ok(vec![]);
+        }
+
+        Ok(diesel::sql_query(
+            "SELECT circuit_id FROM circuit_search \
+             WHERE circuit_search MATCH ? \
+             ORDER BY rank, circuit_id DESC",
+        )
+        .bind::<Text, _>(match_expression)
+        .load::<CircuitSearchResult>(self.conn)?
+        .into_iter()
+        .map(|result| result.circuit_id)
+        .collect())
+    }
+}
+
+/// Convert free-form search text into an FTS5 match expression.
+///
+/// Each term is quoted, so that characters with special meaning to FTS5 are treated as part of
+/// the term, and is matched as a prefix. All terms must match.
+#[cfg(feature = "sqlite")]
+fn to_fts5_match_expression(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Convert free-form search text into a MySQL boolean mode full-text expression.
+///
+/// The full-text parser splits words on punctuation, so each term is broken into its words, which
+/// also drops the characters that are operators in boolean mode. Every word is required and is
+/// matched as a prefix.
+#[cfg(feature = "mysql")]
+fn to_boolean_mode_expression(query: &str) -> String {
+    query
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+        .map(|word| format!("+{}*", word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(all(test, any(feature = "mysql", feature = "sqlite")))]
+mod tests {
+    use super::*;
+
+    /// Verify that search terms are quoted and matched as prefixes, and that embedded quotes are
+    /// escaped.
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_to_fts5_match_expression() {
+        assert_eq!(to_fts5_match_expression(""), "");
+        assert_eq!(to_fts5_match_expression("  "), "");
+        assert_eq!(
+            to_fts5_match_expression("acme  shipping"),
+            "\"acme\"* \"shipping\"*"
+        );
+        assert_eq!(to_fts5_match_expression("a\"b OR"), "\"a\"\"b\"* \"OR\"*");
+    }
+
+    /// Verify that search terms are split into required prefix words, and that boolean mode
+    /// operators are dropped.
+    #[cfg(feature = "mysql")]
+    #[test]
+    fn test_to_boolean_mode_expression() {
+        assert_eq!(to_boolean_mode_expression(""), "");
+        assert_eq!(to_boolean_mode_expression("  "), "");
+        assert_eq!(
+            to_boolean_mode_expression("acme  shipping"),
+            "+acme* +shipping*"
+        );
+        assert_eq!(
+            to_boolean_mode_expression("-a\"b (c)* acme-node"),
+            "+a* +b* +c* +acme* +node*"
+        );
+    }
+}
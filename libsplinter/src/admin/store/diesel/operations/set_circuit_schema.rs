@@ -0,0 +1,150 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides the "set circuit schema" operation for the `DieselAdminServiceStore`.
+
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+};
+
+use crate::admin::store::{
+    diesel::{
+        models::CircuitSchemaModel,
+        schema::{circuit, circuit_schema},
+    },
+    error::AdminServiceStoreError,
+    CircuitSchema,
+};
+use crate::error::InvalidStateError;
+
+use super::AdminServiceStoreOperations;
+
+pub(in crate::admin::store::diesel) trait AdminServiceStoreSetCircuitSchemaOperation {
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError>;
+}
+
+#[cfg(feature = "mysql")]
+impl<'a> AdminServiceStoreSetCircuitSchemaOperation
+    for AdminServiceStoreOperations<'a, diesel::mysql::MysqlConnection>
+{
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Verify the circuit the schema is registered on exists
+            let circuit_count: i64 = circuit::table
+                .filter(circuit::circuit_id.eq(schema.circuit_id()))
+                .count()
+                .get_result(self.conn)?;
+            if circuit_count == 0 {
+                return Err(AdminServiceStoreError::InvalidStateError(
+                    InvalidStateError::with_message(format!(
+                        "Circuit {} does not exist",
+                        schema.circuit_id()
+                    )),
+                ));
+            }
+
+            // Replace the schema with the same name and version, if there is one
+            delete(
+                circuit_schema::table
+                    .filter(circuit_schema::circuit_id.eq(schema.circuit_id()))
+                    .filter(circuit_schema::name.eq(schema.name()))
+                    .filter(circuit_schema::version.eq(schema.version())),
+            )
+            .execute(self.conn)?;
+
+            insert_into(circuit_schema::table)
+                .values(CircuitSchemaModel::from(&schema))
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AdminServiceStoreSetCircuitSchemaOperation
+    for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Verify the circuit the schema is registered on exists
+            let circuit_count: i64 = circuit::table
+                .filter(circuit::circuit_id.eq(schema.circuit_id()))
+                .count()
+                .get_result(self.conn)?;
+            if circuit_count == 0 {
+                return Err(AdminServiceStoreError::InvalidStateError(
+                    InvalidStateError::with_message(format!(
+                        "Circuit {} does not exist",
+                        schema.circuit_id()
+                    )),
+                ));
+            }
+
+            // Replace the schema with the same name and version, if there is one
+            delete(
+                circuit_schema::table
+                    .filter(circuit_schema::circuit_id.eq(schema.circuit_id()))
+                    .filter(circuit_schema::name.eq(schema.name()))
+                    .filter(circuit_schema::version.eq(schema.version())),
+            )
+            .execute(self.conn)?;
+
+            insert_into(circuit_schema::table)
+                .values(CircuitSchemaModel::from(&schema))
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AdminServiceStoreSetCircuitSchemaOperation
+    for AdminServiceStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Verify the circuit the schema is registered on exists
+            let circuit_count: i64 = circuit::table
+                .filter(circuit::circuit_id.eq(schema.circuit_id()))
+                .count()
+                .get_result(self.conn)?;
+            if circuit_count == 0 {
+                return Err(AdminServiceStoreError::InvalidStateError(
+                    InvalidStateError::with_message(format!(
+                        "Circuit {} does not exist",
+                        schema.circuit_id()
+                    )),
+                ));
+            }
+
+            // Replace the schema with the same name and version, if there is one
+            delete(
+                circuit_schema::table
+                    .filter(circuit_schema::circuit_id.eq(schema.circuit_id()))
+                    .filter(circuit_schema::name.eq(schema.name()))
+                    .filter(circuit_schema::version.eq(schema.version())),
+            )
+            .execute(self.conn)?;
+
+            insert_into(circuit_schema::table)
+                .values(CircuitSchemaModel::from(&schema))
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
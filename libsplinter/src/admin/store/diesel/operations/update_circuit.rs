@@ -20,7 +20,7 @@ use diesel::{
     prelude::*,
 };
 
-use super::AdminServiceStoreOperations;
+use super::{index_circuit::AdminServiceStoreIndexCircuitOperation, AdminServiceStoreOperations};
 use crate::admin::store::{
     diesel::{
         models::{CircuitMemberModel, CircuitModel, ServiceArgumentModel, ServiceModel},
@@ -35,6 +35,67 @@ pub(in crate::admin::store::diesel) trait AdminServiceStoreUpdateCircuitOperatio
     fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError>;
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> AdminServiceStoreUpdateCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::mysql::MysqlConnection>
+{
+    fn update_circuit(&self, circuit: Circuit) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Verify the `circuit` entry to be updated exists
+            circuit::table
+                .filter(circuit::circuit_id.eq(circuit.circuit_id()))
+                .first::<CircuitModel>(self.conn)
+                .optional()?
+                .ok_or_else(|| {
+                    AdminServiceStoreError::InvalidStateError(InvalidStateError::with_message(
+                        String::from("Circuit does not exist in AdminServiceStore"),
+                    ))
+                })?;
+
+            // Update existing `Circuit`
+            let circuit_model = CircuitModel::from(&circuit);
+            update(circuit::table.find(circuit.circuit_id()))
+                .set((
+                    circuit::authorization_type.eq(circuit_model.authorization_type),
+                    circuit::persistence.eq(circuit_model.persistence),
+                    circuit::durability.eq(circuit_model.durability),
+                    circuit::routes.eq(circuit_model.routes),
+                    circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
+                    circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::display_name.eq(circuit_model.display_name),
+                ))
+                .execute(self.conn)?;
+            // Delete existing data associated with the `Circuit`
+            delete(service::table.filter(service::circuit_id.eq(circuit.circuit_id())))
+                .execute(self.conn)?;
+            delete(
+                service_argument::table
+                    .filter(service_argument::circuit_id.eq(circuit.circuit_id())),
+            )
+            .execute(self.conn)?;
+            delete(
+                circuit_member::table.filter(circuit_member::circuit_id.eq(circuit.circuit_id())),
+            )
+            .execute(self.conn)?;
+            // Insert new data associate with the `Circuit`
+            let services: Vec<ServiceModel> = Vec::try_from(&circuit)?;
+            insert_into(service::table)
+                .values(&services)
+                .execute(self.conn)?;
+            let service_argument: Vec<ServiceArgumentModel> = Vec::try_from(&circuit)?;
+            insert_into(service_argument::table)
+                .values(&service_argument)
+                .execute(self.conn)?;
+            let circuit_member: Vec<CircuitMemberModel> = Vec::try_from(&circuit)?;
+            insert_into(circuit_member::table)
+                .values(circuit_member)
+                .execute(self.conn)?;
+            self.reindex_circuit(circuit.circuit_id())?;
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> AdminServiceStoreUpdateCircuitOperation
     for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
@@ -62,6 +123,7 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::display_name.eq(circuit_model.display_name),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -89,6 +151,7 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
+            self.reindex_circuit(circuit.circuit_id())?;
             Ok(())
         })
     }
@@ -121,6 +184,7 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
                     circuit::routes.eq(circuit_model.routes),
                     circuit::circuit_management_type.eq(circuit_model.circuit_management_type),
                     circuit::circuit_status.eq(circuit_model.circuit_status),
+                    circuit::display_name.eq(circuit_model.display_name),
                 ))
                 .execute(self.conn)?;
             // Delete existing data associated with the `Circuit`
@@ -148,6 +212,7 @@ impl<'a> AdminServiceStoreUpdateCircuitOperation
             insert_into(circuit_member::table)
                 .values(circuit_member)
                 .execute(self.conn)?;
+            self.reindex_circuit(circuit.circuit_id())?;
             Ok(())
         })
     }
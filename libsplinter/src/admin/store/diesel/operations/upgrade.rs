@@ -22,6 +22,7 @@ use crate::error::InvalidStateError;
 use super::{
     add_circuit::AdminServiceStoreAddCircuitOperation,
     get_proposal::AdminServiceStoreFetchProposalOperation,
+    index_circuit::AdminServiceStoreIndexCircuitOperation,
     remove_proposal::AdminServiceStoreRemoveProposalOperation, AdminServiceStoreOperations,
 };
 
@@ -29,6 +30,80 @@ pub(in crate::admin::store::diesel) trait AdminServiceStoreUpgradeProposalToCirc
     fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError>;
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
+    for AdminServiceStoreOperations<'a, diesel::mysql::MysqlConnection>
+{
+    fn upgrade_proposal_to_circuit(&self, circuit_id: &str) -> Result<(), AdminServiceStoreError> {
+        self.conn.transaction::<(), _, _>(|| {
+            // Attempting to fetch the proposal to be upgraded. If not found, an error is returned.
+            let proposal = match self.get_proposal(circuit_id)? {
+                Some(proposal) => Ok(proposal),
+                None => Err(AdminServiceStoreError::InvalidStateError(
+                    InvalidStateError::with_message(String::from(
+                        "CircuitProposal does not exist in AdminServiceStore",
+                    )),
+                )),
+            }?;
+            // Need to construct the `Circuit` from the `ProposedCircuit`
+            let proposed_circuit = proposal.circuit();
+            let mut builder = CircuitBuilder::new()
+                .with_circuit_id(proposed_circuit.circuit_id())
+                .with_roster(
+                    &proposed_circuit
+                        .roster()
+                        .iter()
+                        .map(Service::from)
+                        .collect::<Vec<Service>>(),
+                )
+                .with_members(
+                    &proposed_circuit
+                        .members()
+                        .iter()
+                        .map(CircuitNode::from)
+                        .collect::<Vec<CircuitNode>>(),
+                )
+                .with_authorization_type(proposed_circuit.authorization_type())
+                .with_persistence(proposed_circuit.persistence())
+                .with_durability(proposed_circuit.durability())
+                .with_routes(proposed_circuit.routes())
+                .with_circuit_management_type(proposed_circuit.circuit_management_type())
+                .with_circuit_version(proposed_circuit.circuit_version())
+                .with_circuit_status(proposed_circuit.circuit_status());
+
+            if let Some(display_name) = proposed_circuit.display_name() {
+                builder = builder.with_display_name(display_name);
+            }
+
+            let circuit = builder
+                .build()
+                .map_err(AdminServiceStoreError::InvalidStateError)?;
+
+            let circuit_nodes = proposed_circuit
+                .members()
+                .iter()
+                .map(CircuitNode::from)
+                .collect::<Vec<CircuitNode>>();
+
+            // The proposal's comments and metadata are read before it is removed, so they can be
+            // added to the circuit's search index entry
+            let metadata = proposed_circuit
+                .application_metadata()
+                .as_ref()
+                .map(|metadata| String::from_utf8_lossy(metadata).into_owned());
+
+            self.remove_proposal(proposal.circuit_id())
+                .and_then(|_| self.add_circuit(circuit, circuit_nodes))?;
+            self.index_circuit(
+                proposal.circuit_id(),
+                proposed_circuit.comments().as_deref(),
+                metadata.as_deref(),
+            )?;
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
     for AdminServiceStoreOperations<'a, diesel::pg::PgConnection>
@@ -84,8 +159,20 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 .map(CircuitNode::from)
                 .collect::<Vec<CircuitNode>>();
 
+            // The proposal's comments and metadata are read before it is removed, so they can be
+            // added to the circuit's search index entry
+            let metadata = proposed_circuit
+                .application_metadata()
+                .as_ref()
+                .map(|metadata| String::from_utf8_lossy(metadata).into_owned());
+
             self.remove_proposal(proposal.circuit_id())
                 .and_then(|_| self.add_circuit(circuit, circuit_nodes))?;
+            self.index_circuit(
+                proposal.circuit_id(),
+                proposed_circuit.comments().as_deref(),
+                metadata.as_deref(),
+            )?;
             Ok(())
         })
     }
@@ -146,8 +233,20 @@ impl<'a> AdminServiceStoreUpgradeProposalToCircuitOperation
                 .map(CircuitNode::from)
                 .collect::<Vec<CircuitNode>>();
 
+            // The proposal's comments and metadata are read before it is removed, so they can be
+            // added to the circuit's search index entry
+            let metadata = proposed_circuit
+                .application_metadata()
+                .as_ref()
+                .map(|metadata| String::from_utf8_lossy(metadata).into_owned());
+
             self.remove_proposal(proposal.circuit_id())
                 .and_then(|_| self.add_circuit(circuit, circuit_nodes))?;
+            self.index_circuit(
+                proposal.circuit_id(),
+                proposed_circuit.comments().as_deref(),
+                metadata.as_deref(),
+            )?;
             Ok(())
         })
     }
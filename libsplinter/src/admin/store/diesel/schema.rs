@@ -136,6 +136,17 @@ table! {
     }
 }
 
+table! {
+    circuit_schema (circuit_id, name, version) {
+        circuit_id -> Text,
+        name -> Text,
+        version -> Text,
+        format -> Text,
+        description -> Nullable<Text>,
+        definition -> Text,
+    }
+}
+
 allow_tables_to_appear_in_same_query!(
     proposed_circuit,
     proposed_node,
@@ -19,8 +19,8 @@
 //!
 //! * [`YamlAdminServiceStore`] - A YAML-backed store that is available by default
 //! * [`DieselAdminServiceStore`] - A database-backed store, powered by [`Diesel`], that currently
-//!   supports SQLite databases (with the `sqlite` feature) and PostgreSQL databases (with the
-//!   `postgres` feature).
+//!   supports SQLite databases (with the `sqlite` feature), PostgreSQL databases (with the
+//!   `postgres` feature), and MySQL or MariaDB databases (with the experimental `mysql` feature).
 //!
 //! [`AdminServiceStore`]: trait.AdminServiceStore.html
 //! [`YamlAdminServiceStore`]: yaml/struct.YamlAdminServiceStore.html
@@ -30,6 +30,8 @@
 mod circuit;
 mod circuit_node;
 mod circuit_proposal;
+#[cfg(feature = "admin-service-circuit-schemas")]
+mod circuit_schema;
 #[cfg(feature = "diesel")]
 pub mod diesel;
 pub mod error;
@@ -53,6 +55,8 @@ pub use self::circuit_node::{CircuitNode, CircuitNodeBuilder};
 pub use self::circuit_proposal::{
     CircuitProposal, CircuitProposalBuilder, ProposalType, Vote, VoteRecord, VoteRecordBuilder,
 };
+#[cfg(feature = "admin-service-circuit-schemas")]
+pub use self::circuit_schema::{CircuitSchema, CircuitSchemaBuilder};
 use self::error::AdminServiceStoreError;
 pub use self::event::{AdminServiceEvent, AdminServiceEventBuilder, EventType};
 pub use self::proposed_circuit::{ProposedCircuit, ProposedCircuitBuilder};
@@ -181,6 +185,26 @@ impl CircuitPredicate {
     }
 }
 
+/// The order in which circuits are listed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CircuitOrder {
+    /// By circuit ID, in ascending order
+    CircuitIdAsc,
+    /// By circuit ID, in descending order
+    CircuitIdDesc,
+    /// By the time the circuit was created, oldest first
+    CreatedAsc,
+    /// By the time the circuit was created, newest first
+    CreatedDesc,
+}
+
+impl Default for CircuitOrder {
+    /// Circuits are listed in descending order of their IDs by default
+    fn default() -> Self {
+        CircuitOrder::CircuitIdDesc
+    }
+}
+
 /// Return type of the admin store's `list_events_*` methods.
 pub type EventIter = Box<dyn ExactSizeIterator<Item = AdminServiceEvent> + Send>;
 
@@ -290,6 +314,46 @@ pub trait AdminServiceStore: Send + Sync {
         predicates: &[CircuitPredicate],
     ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
 
+    /// List a page of the circuits in the store, in the given order
+    ///
+    /// The default implementation lists all of the circuits and sorts them in memory; as it
+    /// cannot tell when a circuit was created, circuits are sorted by ID in place of their
+    /// creation time. Stores should implement this method to select the page where the circuits
+    /// are stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `predicates` - A list of predicates to be applied to the circuits
+    /// * `order` - The order in which the circuits are listed
+    /// * `offset` - The number of matching circuits to skip
+    /// * `limit` - The maximum number of circuits to return
+    fn list_circuits_page(
+        &self,
+        predicates: &[CircuitPredicate],
+        order: CircuitOrder,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let mut circuits = self.list_circuits(predicates)?.collect::<Vec<_>>();
+        match order {
+            CircuitOrder::CircuitIdAsc | CircuitOrder::CreatedAsc => {
+                circuits.sort_by(|a, b| a.circuit_id().cmp(b.circuit_id()))
+            }
+            CircuitOrder::CircuitIdDesc | CircuitOrder::CreatedDesc => {
+                circuits.sort_by(|a, b| b.circuit_id().cmp(a.circuit_id()))
+            }
+        }
+
+        Ok(Box::new(
+            circuits
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
     /// Returns the count of circuits in the store
     ///
     /// # Arguments
@@ -301,6 +365,40 @@ pub trait AdminServiceStore: Send + Sync {
         predicates: &[CircuitPredicate],
     ) -> Result<u32, AdminServiceStoreError>;
 
+    /// Search the circuits in the store using a full-text query
+    ///
+    /// The query is matched against the circuit's display name, along with the comments and
+    /// application metadata of the proposal the circuit was created from. Matching circuits are
+    /// returned in order of relevance.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The full-text search terms
+    /// * `predicates` - A list of predicates to be applied to the matching circuits
+    /// * `offset` - The number of matching circuits to skip
+    /// * `limit` - The maximum number of circuits to return
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn search_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError>;
+
+    /// Returns the count of circuits which match a full-text query
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The full-text search terms
+    /// * `predicates` - A list of predicates to be applied to the matching circuits
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn count_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError>;
+
     /// Adds a circuit, along with the associated services and nodes, to the store based on the
     /// proposal that is already in state. The associated circuit proposal for the circuit ID is
     /// also removed.
@@ -373,6 +471,57 @@ pub trait AdminServiceStore: Send + Sync {
         start: i64,
     ) -> Result<EventIter, AdminServiceStoreError>;
 
+    /// Adds a schema to the registry of a circuit, replacing the schema with the same name and
+    /// version if there is one
+    ///
+    /// # Arguments
+    ///
+    ///  * `schema` - The schema to be registered; its circuit must be in the store
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn set_circuit_schema(&self, schema: CircuitSchema) -> Result<(), AdminServiceStoreError>;
+
+    /// Fetches a schema from the registry of a circuit
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The ID of the circuit the schema is registered on
+    ///  * `name` - The name of the schema
+    ///  * `version` - The version of the schema
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn get_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<Option<CircuitSchema>, AdminServiceStoreError>;
+
+    /// Lists the schemas registered on a circuit, ordered by name and version
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The ID of the circuit the schemas are registered on
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn list_circuit_schemas(
+        &self,
+        circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitSchema>>, AdminServiceStoreError>;
+
+    /// Removes a schema from the registry of a circuit. The schemas of a circuit are also removed
+    /// when the circuit is removed.
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The ID of the circuit the schema is registered on
+    ///  * `name` - The name of the schema
+    ///  * `version` - The version of the schema
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn remove_circuit_schema(
+        &self,
+        circuit_id: &str,
+        name: &str,
+        version: &str,
+    ) -> Result<(), AdminServiceStoreError>;
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore>;
 }
 
@@ -26,6 +26,8 @@ use std::io::Write;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
+#[cfg(feature = "admin-service-circuit-schemas")]
+use super::CircuitSchema;
 use super::{AdminServiceEvent, EventIter};
 use super::{
     AdminServiceStore, AdminServiceStoreError, AuthorizationType, Circuit, CircuitBuilder,
@@ -922,6 +924,77 @@ impl AdminServiceStore for YamlAdminServiceStore {
         })
     }
 
+    /// Search the circuits in the store using a full-text query
+    ///
+    /// The YAML store does not keep a proposal's comments or application metadata once the
+    /// circuit has been created, so the query terms are matched, case-insensitively, against the
+    /// circuit's ID, display name, and management type. A circuit matches if every term matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The full-text search terms
+    /// * `predicates` - A list of predicates to be applied to the matching circuits
+    /// * `offset` - The number of matching circuits to skip
+    /// * `limit` - The maximum number of circuits to return
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn search_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+        offset: usize,
+        limit: usize,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Circuit>>, AdminServiceStoreError> {
+        let terms = query
+            .split_whitespace()
+            .map(|term| term.to_lowercase())
+            .collect::<Vec<_>>();
+
+        let mut circuits = self.list_circuits(predicates)?.collect::<Vec<_>>();
+
+        circuits.retain(|circuit| {
+            let document = format!(
+                "{} {} {}",
+                circuit.circuit_id(),
+                circuit.display_name().as_deref().unwrap_or(""),
+                circuit.circuit_management_type()
+            )
+            .to_lowercase();
+
+            !terms.is_empty() && terms.iter().all(|term| document.contains(term.as_str()))
+        });
+
+        Ok(Box::new(
+            circuits
+                .into_iter()
+                .skip(offset)
+                .take(limit)
+                .collect::<Vec<_>>()
+                .into_iter(),
+        ))
+    }
+
+    /// Returns the count of circuits which match a full-text query
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - The full-text search terms
+    /// * `predicates` - A list of predicates to be applied to the matching circuits
+    #[cfg(feature = "admin-service-circuit-search")]
+    fn count_matching_circuits(
+        &self,
+        query: &str,
+        predicates: &[CircuitPredicate],
+    ) -> Result<u32, AdminServiceStoreError> {
+        let count = self
+            .search_circuits(query, predicates, 0, usize::MAX)?
+            .len();
+        u32::try_from(count).map_err(|_| {
+            AdminServiceStoreError::InternalError(InternalError::with_message(
+                "The number of circuits is larger than the max u32".to_string(),
+            ))
+        })
+    }
+
     /// Adds a circuit to the underlying storage based on the proposal that is already in state..
     /// Also includes the associated Services and Nodes. The associated circuit proposal for
     /// the circuit ID is also removed
@@ -1115,11 +1188,51 @@ impl AdminServiceStore for YamlAdminServiceStore {
         unimplemented!()
     }
 
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn set_circuit_schema(&self, _schema: CircuitSchema) -> Result<(), AdminServiceStoreError> {
+        Err(schemas_unsupported())
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn get_circuit_schema(
+        &self,
+        _circuit_id: &str,
+        _name: &str,
+        _version: &str,
+    ) -> Result<Option<CircuitSchema>, AdminServiceStoreError> {
+        Err(schemas_unsupported())
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn list_circuit_schemas(
+        &self,
+        _circuit_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = CircuitSchema>>, AdminServiceStoreError> {
+        Err(schemas_unsupported())
+    }
+
+    #[cfg(feature = "admin-service-circuit-schemas")]
+    fn remove_circuit_schema(
+        &self,
+        _circuit_id: &str,
+        _name: &str,
+        _version: &str,
+    ) -> Result<(), AdminServiceStoreError> {
+        Err(schemas_unsupported())
+    }
+
     fn clone_boxed(&self) -> Box<dyn AdminServiceStore> {
         Box::new(self.clone())
     }
 }
 
+#[cfg(feature = "admin-service-circuit-schemas")]
+fn schemas_unsupported() -> AdminServiceStoreError {
+    AdminServiceStoreError::InternalError(InternalError::with_message(
+        "Circuit schemas are not supported by the YAML admin service store".to_string(),
+    ))
+}
+
 /// YAML file specific circuit definition. This circuit definition in the 0.4v YAML stores service
 /// arguments in a map format, which differs from the definition defined in the AdminServiceStore.
 /// To handle this, circuit needs to be converted to the correct format during read/write
@@ -0,0 +1,610 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Webhook notifications for admin service events.
+//!
+//! The [`WebhookDispatcher`] receives admin service events and POSTs a JSON payload to each
+//! configured [`Webhook`] when a circuit proposal is submitted, a vote is cast on a proposal, or a
+//! circuit becomes ready or is disbanded.
+//!
+//! Each request has the following headers:
+//!
+//! * `X-Splinter-Event` - The type of the event, such as `circuit_ready`
+//! * `X-Splinter-Delivery` - A unique ID for the delivery, which is the same for every attempt so
+//!   that receivers can ignore duplicates
+//! * `X-Splinter-Signature` - If the webhook has a secret, `sha256=` followed by the hex-encoded
+//!   HMAC-SHA256 of the request body, keyed with the secret
+//!
+//! A delivery that fails because the request could not be sent, or because the receiver responded
+//! with a server error or `429 Too Many Requests`, is retried with exponential backoff.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use reqwest::{blocking::Client, StatusCode};
+use sha2::Sha256;
+use url::Url;
+use uuid::Uuid;
+
+use crate::admin::service::{AdminServiceEventSubscriber, AdminSubscriberError};
+use crate::admin::store::{AdminServiceEvent, EventType, ProposalType, Vote};
+use crate::error::{InternalError, InvalidArgumentError};
+use crate::hex::to_hex;
+use crate::threading::lifecycle::ShutdownHandle;
+
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A URL that admin service event payloads are posted to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Webhook {
+    url: String,
+    secret: Option<String>,
+}
+
+impl Webhook {
+    /// Creates a webhook for the given URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgumentError` if the URL is not a valid `http` or `https` URL.
+    pub fn new(url: &str) -> Result<Self, InvalidArgumentError> {
+        match Url::parse(url) {
+            Ok(parsed) if parsed.scheme() == "http" || parsed.scheme() == "https" => Ok(Self {
+                url: url.to_string(),
+                secret: None,
+            }),
+            Ok(_) => Err(InvalidArgumentError::new(
+                "url".to_string(),
+                format!("webhook URL must use http or https: {}", url),
+            )),
+            Err(err) => Err(InvalidArgumentError::new(
+                "url".to_string(),
+                format!("invalid webhook URL {}: {}", url, err),
+            )),
+        }
+    }
+
+    /// Sets the secret used to sign the payloads posted to the webhook
+    pub fn with_secret(mut self, secret: String) -> Self {
+        self.secret = Some(secret);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+/// Builds and starts a [`WebhookDispatcher`].
+pub struct WebhookDispatcherBuilder {
+    webhooks: Vec<Webhook>,
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+}
+
+impl Default for WebhookDispatcherBuilder {
+    fn default() -> Self {
+        Self {
+            webhooks: vec![],
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+}
+
+impl WebhookDispatcherBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the webhooks that every payload is posted to
+    pub fn with_webhooks(mut self, webhooks: Vec<Webhook>) -> Self {
+        self.webhooks = webhooks;
+        self
+    }
+
+    /// Sets the number of times a payload is sent to a webhook before it is dropped; defaults to
+    /// 5.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets how long to wait before the first retry; the wait doubles for each further retry.
+    /// Defaults to 1 second.
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the longest wait between retries; defaults to 60 seconds.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Starts the dispatcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the local node, which is included in every payload
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the HTTP client could not be created or the dispatcher
+    /// thread could not be started.
+    pub fn start(self, node_id: &str) -> Result<WebhookDispatcher, InternalError> {
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let (sender, receiver) = channel();
+        let node_id = node_id.to_string();
+
+        let join_handle = thread::Builder::new()
+            .name("WebhookDispatcher".into())
+            .spawn(move || {
+                let mut pending: Vec<Delivery> = vec![];
+
+                loop {
+                    let message = match pending.iter().map(|delivery| delivery.next_attempt).min() {
+                        Some(next_attempt) => receiver
+                            .recv_timeout(next_attempt.saturating_duration_since(Instant::now())),
+                        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    };
+
+                    match message {
+                        Ok(DispatcherMessage::Event(event)) => {
+                            if let Some((event_type, body)) = payload_for(&node_id, &event) {
+                                let id = Uuid::new_v4().to_string();
+                                pending.extend((0..self.webhooks.len()).map(|webhook| Delivery {
+                                    webhook,
+                                    id: id.clone(),
+                                    event_type,
+                                    body: body.clone(),
+                                    attempts: 0,
+                                    next_attempt: Instant::now(),
+                                }));
+                            }
+                        }
+                        Ok(DispatcherMessage::Shutdown) | Err(RecvTimeoutError::Disconnected) => {
+                            break
+                        }
+                        Err(RecvTimeoutError::Timeout) => (),
+                    }
+
+                    let now = Instant::now();
+                    let (due, waiting): (Vec<_>, Vec<_>) = pending
+                        .into_iter()
+                        .partition(|delivery| delivery.next_attempt <= now);
+                    pending = waiting;
+
+                    for delivery in due {
+                        if let Some(retry) = self.attempt(&client, delivery) {
+                            pending.push(retry);
+                        }
+                    }
+                }
+
+                if !pending.is_empty() {
+                    warn!(
+                        "Dropping {} undelivered webhook payloads on shutdown",
+                        pending.len()
+                    );
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(WebhookDispatcher {
+            sender,
+            join_handle,
+        })
+    }
+
+    /// Sends a delivery, returning it with its next attempt scheduled if it should be retried.
+    fn attempt(&self, client: &Client, mut delivery: Delivery) -> Option<Delivery> {
+        let webhook = &self.webhooks[delivery.webhook];
+        delivery.attempts += 1;
+
+        let mut request = client
+            .post(webhook.url())
+            .header("Content-Type", "application/json")
+            .header("X-Splinter-Event", delivery.event_type)
+            .header("X-Splinter-Delivery", delivery.id.as_str());
+        if let Some(secret) = &webhook.secret {
+            request = request.header(
+                "X-Splinter-Signature",
+                format!("sha256={}", sign(secret, &delivery.body)),
+            );
+        }
+
+        let error = match request.body(delivery.body.clone()).send() {
+            Ok(response) if response.status().is_success() => {
+                debug!(
+                    "Delivered {} webhook {} to {}",
+                    delivery.event_type,
+                    delivery.id,
+                    webhook.url()
+                );
+                return None;
+            }
+            Ok(response)
+                if !response.status().is_server_error()
+                    && response.status() != StatusCode::TOO_MANY_REQUESTS =>
+            {
+                error!(
+                    "Webhook {} rejected {} payload {}: {}",
+                    webhook.url(),
+                    delivery.event_type,
+                    delivery.id,
+                    response.status()
+                );
+                return None;
+            }
+            Ok(response) => response.status().to_string(),
+            Err(err) => err.to_string(),
+        };
+
+        if delivery.attempts >= self.max_attempts {
+            error!(
+                "Unable to deliver {} payload {} to webhook {} after {} attempts: {}",
+                delivery.event_type,
+                delivery.id,
+                webhook.url(),
+                delivery.attempts,
+                error
+            );
+            return None;
+        }
+
+        let backoff = self
+            .initial_backoff
+            .checked_mul(1 << (delivery.attempts - 1).min(16))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff);
+        warn!(
+            "Unable to deliver {} payload {} to webhook {}, retrying in {:?}: {}",
+            delivery.event_type,
+            delivery.id,
+            webhook.url(),
+            backoff,
+            error
+        );
+        delivery.next_attempt = Instant::now() + backoff;
+        Some(delivery)
+    }
+}
+
+enum DispatcherMessage {
+    Event(Box<AdminServiceEvent>),
+    Shutdown,
+}
+
+/// A payload to be posted to a webhook
+struct Delivery {
+    /// The index of the webhook
+    webhook: usize,
+    id: String,
+    event_type: &'static str,
+    body: Vec<u8>,
+    attempts: u32,
+    next_attempt: Instant,
+}
+
+/// Posts admin service events to webhooks.
+///
+/// Events are received through the [`AdminServiceEventSubscriber`] returned by
+/// [`WebhookDispatcher::subscriber`], and the payloads are posted on a background thread so that
+/// slow or unavailable webhooks do not hold up the admin service. Payloads that have not been
+/// delivered when the dispatcher is shut down are dropped.
+pub struct WebhookDispatcher {
+    sender: Sender<DispatcherMessage>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl WebhookDispatcher {
+    /// Returns a subscriber to be added to the admin service, which passes events on to the
+    /// dispatcher.
+    pub fn subscriber(&self) -> Box<dyn AdminServiceEventSubscriber> {
+        Box::new(DispatcherSubscriber {
+            sender: self.sender.clone(),
+        })
+    }
+}
+
+impl ShutdownHandle for WebhookDispatcher {
+    fn signal_shutdown(&mut self) {
+        // An error means the thread has already stopped
+        let _ = self.sender.send(DispatcherMessage::Shutdown);
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle
+            .join()
+            .map_err(|_| InternalError::with_message("WebhookDispatcher thread panicked".into()))
+    }
+}
+
+struct DispatcherSubscriber {
+    sender: Sender<DispatcherMessage>,
+}
+
+impl AdminServiceEventSubscriber for DispatcherSubscriber {
+    fn handle_event(
+        &self,
+        admin_service_event: &AdminServiceEvent,
+    ) -> Result<(), AdminSubscriberError> {
+        self.sender
+            .send(DispatcherMessage::Event(Box::new(
+                admin_service_event.clone(),
+            )))
+            .map_err(|_| AdminSubscriberError::Unsubscribe)
+    }
+}
+
+/// Returns the event type and JSON body posted for the given event, or `None` if the event is not
+/// posted to webhooks.
+fn payload_for(node_id: &str, event: &AdminServiceEvent) -> Option<(&'static str, Vec<u8>)> {
+    let event_type = match event.event_type() {
+        EventType::ProposalSubmitted => "proposal_submitted",
+        EventType::ProposalVote { .. } => "proposal_vote",
+        EventType::CircuitReady => "circuit_ready",
+        EventType::CircuitDisbanded => "circuit_disbanded",
+        EventType::ProposalAccepted { .. } | EventType::ProposalRejected { .. } => return None,
+    };
+
+    let proposal = event.proposal();
+    let body = serde_json::json!({
+        "event_type": event_type,
+        "event_id": event.event_id(),
+        "node_id": node_id,
+        "timestamp": SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0),
+        "circuit_id": proposal.circuit_id(),
+        "display_name": proposal.circuit().display_name(),
+        "proposal_type": match proposal.proposal_type() {
+            ProposalType::Create => "create",
+            ProposalType::UpdateRoster => "update_roster",
+            ProposalType::AddNode => "add_node",
+            ProposalType::RemoveNode => "remove_node",
+            ProposalType::Disband => "disband",
+        },
+        "requester_node_id": proposal.requester_node_id(),
+        "members": proposal
+            .circuit()
+            .members()
+            .iter()
+            .map(|member| member.node_id())
+            .collect::<Vec<_>>(),
+        "votes": proposal
+            .votes()
+            .iter()
+            .map(|vote| serde_json::json!({
+                "voter_node_id": vote.voter_node_id(),
+                "vote": match vote.vote() {
+                    Vote::Accept => "accept",
+                    Vote::Reject => "reject",
+                },
+            }))
+            .collect::<Vec<_>>(),
+    });
+
+    Some((event_type, body.to_string().into_bytes()))
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of the body, keyed with the secret
+fn sign(secret: &str, body: &[u8]) -> String {
+    // HMAC accepts keys of any length, so this cannot fail
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take a key of any size");
+    mac.update(body);
+    to_hex(&mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    use crate::admin::store::{
+        AdminServiceEventBuilder, CircuitProposalBuilder, ProposedCircuitBuilder,
+        ProposedNodeBuilder,
+    };
+    use crate::public_key::PublicKey;
+
+    /// Verify the signature against the HMAC-SHA256 test vector from RFC 4231.
+    #[test]
+    fn test_sign() {
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    /// Verify that only http and https webhook URLs are accepted.
+    #[test]
+    fn test_webhook_url() {
+        assert!(Webhook::new("https://example.com/hooks/splinter").is_ok());
+        assert!(Webhook::new("http://127.0.0.1:8080").is_ok());
+        assert!(Webhook::new("ftp://example.com").is_err());
+        assert!(Webhook::new("not a url").is_err());
+    }
+
+    /// Verify that a payload is retried when the webhook responds with a server error, and that
+    /// the signature and delivery ID are sent with each attempt.
+    ///
+    /// 1. Start a server that responds to the first request with a 500 and to the second with a
+    ///    200.
+    /// 2. Start a dispatcher with a signed webhook for the server and pass it a circuit ready
+    ///    event.
+    /// 3. Verify that the payload was sent twice with the same delivery ID, a valid signature and
+    ///    the expected body.
+    #[test]
+    fn test_dispatcher_retry() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Unable to bind listener");
+        let url = format!("http://{}/hook", listener.local_addr().expect("No address"));
+        let requests = Arc::new(Mutex::new(vec![]));
+
+        let server_requests = requests.clone();
+        let server = thread::spawn(move || {
+            for status in &["500 Internal Server Error", "200 OK"] {
+                let (stream, _) = listener.accept().expect("Unable to accept connection");
+                let mut reader = BufReader::new(stream);
+                let mut headers = vec![];
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).expect("Unable to read line");
+                    let line = line.trim_end().to_string();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = line.split_once(": ") {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            content_length = value.parse().expect("Invalid content length");
+                        }
+                        headers.push((name.to_lowercase(), value.to_string()));
+                    }
+                }
+                let mut body = vec![0; content_length];
+                reader.read_exact(&mut body).expect("Unable to read body");
+                server_requests
+                    .lock()
+                    .expect("lock poisoned")
+                    .push((headers, body));
+
+                reader
+                    .get_mut()
+                    .write_all(
+                        format!("HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status).as_bytes(),
+                    )
+                    .expect("Unable to write response");
+            }
+        });
+
+        let mut dispatcher = WebhookDispatcherBuilder::new()
+            .with_webhooks(vec![Webhook::new(&url)
+                .expect("Invalid webhook")
+                .with_secret("secret".into())])
+            .with_initial_backoff(Duration::from_millis(10))
+            .start("acme-node-000")
+            .expect("Unable to start dispatcher");
+        dispatcher
+            .subscriber()
+            .handle_event(&create_event(EventType::CircuitReady))
+            .expect("Unable to handle event");
+
+        server.join().expect("Server thread panicked");
+        dispatcher.signal_shutdown();
+        dispatcher
+            .wait_for_shutdown()
+            .expect("Unable to shut down dispatcher");
+
+        let requests = requests.lock().expect("lock poisoned");
+        assert_eq!(requests.len(), 2);
+        let header = |index: usize, name: &str| {
+            requests[index]
+                .0
+                .iter()
+                .find(|(header, _)| header == name)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(
+            header(0, "x-splinter-delivery"),
+            header(1, "x-splinter-delivery")
+        );
+        assert_eq!(
+            header(1, "x-splinter-event").as_deref(),
+            Some("circuit_ready")
+        );
+        assert_eq!(
+            header(1, "x-splinter-signature"),
+            Some(format!("sha256={}", sign("secret", &requests[1].1)))
+        );
+
+        let body: serde_json::Value =
+            serde_json::from_slice(&requests[1].1).expect("Invalid payload");
+        assert_eq!(body["event_type"], "circuit_ready");
+        assert_eq!(body["node_id"], "acme-node-000");
+        assert_eq!(body["circuit_id"], "WBKLF-BBBBB");
+        assert_eq!(
+            body["members"],
+            serde_json::json!(["acme-node-000", "bubba-node-000"])
+        );
+    }
+
+    /// Verify that proposal accepted and rejected events are not posted.
+    #[test]
+    fn test_payload_for() {
+        assert!(
+            payload_for("acme-node-000", &create_event(EventType::ProposalSubmitted)).is_some()
+        );
+        assert!(payload_for(
+            "acme-node-000",
+            &create_event(EventType::ProposalAccepted { requester: vec![1] })
+        )
+        .is_none());
+    }
+
+    /// Creates an event for a proposal from acme-node-000 to create a circuit with bubba-node-000.
+    fn create_event(event_type: EventType) -> AdminServiceEvent {
+        let members = ["acme-node-000", "bubba-node-000"]
+            .iter()
+            .map(|node_id| {
+                ProposedNodeBuilder::default()
+                    .with_node_id(node_id)
+                    .with_endpoints(&[format!("tcps://{}:8044", node_id)])
+                    .build()
+                    .expect("Unable to build node")
+            })
+            .collect::<Vec<_>>();
+
+        let proposal = CircuitProposalBuilder::default()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_circuit_hash("7ddc4269")
+            .with_circuit(
+                &ProposedCircuitBuilder::default()
+                    .with_circuit_id("WBKLF-BBBBB")
+                    .with_roster(&[])
+                    .with_members(&members)
+                    .with_circuit_management_type("test")
+                    .build()
+                    .expect("Unable to build circuit"),
+            )
+            .with_requester(&PublicKey::from_bytes(b"acme".to_vec()))
+            .with_requester_node_id("acme-node-000")
+            .build()
+            .expect("Unable to build proposal");
+
+        AdminServiceEventBuilder::new()
+            .with_event_id(1)
+            .with_event_type(&event_type)
+            .with_proposal(&proposal)
+            .build()
+            .expect("Unable to build event")
+    }
+}
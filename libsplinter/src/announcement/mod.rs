@@ -0,0 +1,252 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operator-set announcements for a node, such as maintenance windows and deprecation notices.
+//!
+//! Announcements are stored in an [`AnnouncementStore`](store::AnnouncementStore) and are
+//! displayed by clients when they connect to the node, so that the operators of the other nodes
+//! in a consortium are aware of upcoming changes.
+
+pub mod store;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use uuid::Uuid;
+
+use crate::error::{InvalidArgumentError, InvalidStateError};
+
+/// The kind of an announcement, used by clients to decide how prominently it is displayed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnnouncementKind {
+    Info,
+    Maintenance,
+    Deprecation,
+}
+
+impl AnnouncementKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AnnouncementKind::Info => "info",
+            AnnouncementKind::Maintenance => "maintenance",
+            AnnouncementKind::Deprecation => "deprecation",
+        }
+    }
+}
+
+impl fmt::Display for AnnouncementKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AnnouncementKind {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(AnnouncementKind::Info),
+            "maintenance" => Ok(AnnouncementKind::Maintenance),
+            "deprecation" => Ok(AnnouncementKind::Deprecation),
+            _ => Err(InvalidArgumentError::new(
+                "kind",
+                format!(
+                    "unknown announcement kind \"{}\"; expected info, maintenance or deprecation",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+/// An announcement set by the node's operator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Announcement {
+    id: String,
+    kind: AnnouncementKind,
+    message: String,
+    created_at: SystemTime,
+    expires_at: Option<SystemTime>,
+}
+
+impl Announcement {
+    /// Returns the ID of the announcement
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the kind of the announcement
+    pub fn kind(&self) -> AnnouncementKind {
+        self.kind
+    }
+
+    /// Returns the text of the announcement
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Returns the time the announcement was created
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Returns the time after which the announcement is no longer displayed, if any
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Returns true if the announcement has expired at the given time.
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= time)
+            .unwrap_or(false)
+    }
+}
+
+/// Builds an [`Announcement`].
+#[derive(Default)]
+pub struct AnnouncementBuilder {
+    id: Option<String>,
+    kind: Option<AnnouncementKind>,
+    message: Option<String>,
+    created_at: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
+}
+
+impl AnnouncementBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the announcement; a random ID is generated if one is not set.
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Sets the kind of the announcement; defaults to [`AnnouncementKind::Info`].
+    pub fn with_kind(mut self, kind: AnnouncementKind) -> Self {
+        self.kind = Some(kind);
+        self
+    }
+
+    pub fn with_message(mut self, message: String) -> Self {
+        self.message = Some(message);
+        self
+    }
+
+    /// Sets the time the announcement was created; defaults to the current time.
+    pub fn with_created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Builds the announcement.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if the message is missing or empty, or if the announcement
+    /// expires before it was created.
+    pub fn build(self) -> Result<Announcement, InvalidStateError> {
+        let message = self
+            .message
+            .filter(|message| !message.trim().is_empty())
+            .ok_or_else(|| {
+                InvalidStateError::with_message(
+                    "an announcement requires a non-empty message".to_string(),
+                )
+            })?;
+
+        let created_at = self.created_at.unwrap_or_else(SystemTime::now);
+
+        if let Some(expires_at) = self.expires_at {
+            if expires_at <= created_at {
+                return Err(InvalidStateError::with_message(
+                    "an announcement must expire after it is created".to_string(),
+                ));
+            }
+        }
+
+        Ok(Announcement {
+            id: self.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            kind: self.kind.unwrap_or(AnnouncementKind::Info),
+            message,
+            created_at,
+            expires_at: self.expires_at,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    /// Verify that the builder fills in the defaults, and rejects an empty message or an
+    /// expiration that is not after the creation time.
+    #[test]
+    fn test_announcement_builder() {
+        let announcement = AnnouncementBuilder::new()
+            .with_message("Upgrading to 0.8 on Saturday".to_string())
+            .build()
+            .expect("Failed to build announcement");
+        assert_eq!(announcement.kind(), AnnouncementKind::Info);
+        assert!(!announcement.id().is_empty());
+        assert_eq!(announcement.expires_at(), None);
+        assert!(!announcement.is_expired_at(SystemTime::now() + Duration::from_secs(3600)));
+
+        assert!(AnnouncementBuilder::new()
+            .with_message(" ".to_string())
+            .build()
+            .is_err());
+
+        let created_at = SystemTime::now();
+        assert!(AnnouncementBuilder::new()
+            .with_message("Maintenance".to_string())
+            .with_created_at(created_at)
+            .with_expires_at(created_at)
+            .build()
+            .is_err());
+
+        let announcement = AnnouncementBuilder::new()
+            .with_kind(AnnouncementKind::Maintenance)
+            .with_message("Maintenance".to_string())
+            .with_created_at(created_at)
+            .with_expires_at(created_at + Duration::from_secs(60))
+            .build()
+            .expect("Failed to build announcement");
+        assert!(!announcement.is_expired_at(created_at));
+        assert!(announcement.is_expired_at(created_at + Duration::from_secs(60)));
+    }
+
+    /// Verify that announcement kinds round-trip through their string representation.
+    #[test]
+    fn test_announcement_kind_from_str() {
+        for kind in &[
+            AnnouncementKind::Info,
+            AnnouncementKind::Maintenance,
+            AnnouncementKind::Deprecation,
+        ] {
+            assert_eq!(kind.as_str().parse::<AnnouncementKind>().ok(), Some(*kind));
+        }
+        assert!("outage".parse::<AnnouncementKind>().is_err());
+    }
+}
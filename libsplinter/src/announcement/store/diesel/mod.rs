@@ -0,0 +1,240 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [AnnouncementStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::announcement::Announcement;
+use crate::store::pool::ConnectionPool;
+
+use super::{AnnouncementStore, AnnouncementStoreError};
+
+use operations::{
+    add_announcement::AnnouncementStoreAddAnnouncement as _,
+    get_announcement::AnnouncementStoreGetAnnouncement as _,
+    list_announcements::AnnouncementStoreListAnnouncements as _,
+    remove_announcement::AnnouncementStoreRemoveAnnouncement as _, AnnouncementStoreOperations,
+};
+
+/// Manages adding, fetching and removing a node's announcements in the database
+pub struct DieselAnnouncementStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselAnnouncementStore<C> {
+    /// Creates a new DieselAnnouncementStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselAnnouncementStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselAnnouncementStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl AnnouncementStore for DieselAnnouncementStore<diesel::pg::PgConnection> {
+    fn add_announcement(&self, announcement: &Announcement) -> Result<(), AnnouncementStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            AnnouncementStoreOperations::new(connection).add_announcement(announcement)
+        })
+    }
+
+    fn get_announcement(&self, id: &str) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            AnnouncementStoreOperations::new(connection).get_announcement(id)
+        })
+    }
+
+    fn list_announcements(&self) -> Result<Vec<Announcement>, AnnouncementStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            AnnouncementStoreOperations::new(connection).list_announcements()
+        })
+    }
+
+    fn remove_announcement(
+        &self,
+        id: &str,
+    ) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            AnnouncementStoreOperations::new(connection).remove_announcement(id)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn AnnouncementStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl AnnouncementStore for DieselAnnouncementStore<diesel::sqlite::SqliteConnection> {
+    fn add_announcement(&self, announcement: &Announcement) -> Result<(), AnnouncementStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            AnnouncementStoreOperations::new(connection).add_announcement(announcement)
+        })
+    }
+
+    fn get_announcement(&self, id: &str) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            AnnouncementStoreOperations::new(connection).get_announcement(id)
+        })
+    }
+
+    fn list_announcements(&self) -> Result<Vec<Announcement>, AnnouncementStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            AnnouncementStoreOperations::new(connection).list_announcements()
+        })
+    }
+
+    fn remove_announcement(
+        &self,
+        id: &str,
+    ) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            AnnouncementStoreOperations::new(connection).remove_announcement(id)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn AnnouncementStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::announcement::{AnnouncementBuilder, AnnouncementKind};
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a SQLite-backed `DieselAnnouncementStore` correctly supports adding,
+    /// fetching, listing and removing announcements.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Add two announcements, the second created before the first, and verify that adding an
+    ///    announcement with an existing ID fails.
+    /// 3. Verify that each announcement can be fetched and that they are listed in the order they
+    ///    were created.
+    /// 4. Remove an announcement and verify that it is returned, that it is no longer listed and
+    ///    that removing it again returns `None`.
+    #[test]
+    fn sqlite_add_list_and_remove_announcements() {
+        let store = DieselAnnouncementStore::new(create_connection_pool_and_migrate());
+
+        let maintenance = AnnouncementBuilder::new()
+            .with_id("maintenance".to_string())
+            .with_kind(AnnouncementKind::Maintenance)
+            .with_message("Down for maintenance on Saturday".to_string())
+            .with_created_at(UNIX_EPOCH + Duration::from_secs(2000))
+            .with_expires_at(UNIX_EPOCH + Duration::from_secs(3000))
+            .build()
+            .expect("Failed to build announcement");
+        let deprecation = AnnouncementBuilder::new()
+            .with_id("deprecation".to_string())
+            .with_kind(AnnouncementKind::Deprecation)
+            .with_message("Scabbard v1 circuits will no longer be supported".to_string())
+            .with_created_at(UNIX_EPOCH + Duration::from_secs(1000))
+            .build()
+            .expect("Failed to build announcement");
+
+        store
+            .add_announcement(&maintenance)
+            .expect("Failed to add announcement");
+        store
+            .add_announcement(&deprecation)
+            .expect("Failed to add announcement");
+        assert!(store.add_announcement(&deprecation).is_err());
+
+        assert_eq!(
+            store
+                .get_announcement("maintenance")
+                .expect("Failed to get announcement"),
+            Some(maintenance.clone())
+        );
+        assert_eq!(
+            store
+                .list_announcements()
+                .expect("Failed to list announcements"),
+            vec![deprecation.clone(), maintenance.clone()]
+        );
+
+        assert_eq!(
+            store
+                .remove_announcement("deprecation")
+                .expect("Failed to remove announcement"),
+            Some(deprecation)
+        );
+        assert_eq!(
+            store
+                .list_announcements()
+                .expect("Failed to list announcements"),
+            vec![maintenance]
+        );
+        assert_eq!(
+            store
+                .remove_announcement("deprecation")
+                .expect("Failed to remove announcement"),
+            None
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
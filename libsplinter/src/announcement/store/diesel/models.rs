@@ -0,0 +1,98 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::announcement::store::AnnouncementStoreError;
+use crate::announcement::{Announcement, AnnouncementBuilder, AnnouncementKind};
+use crate::error::{InternalError, InvalidArgumentError};
+
+use super::schema::announcement;
+
+#[derive(Insertable, Queryable, Identifiable, PartialEq, Eq, Debug)]
+#[table_name = "announcement"]
+#[primary_key(id)]
+pub struct AnnouncementModel {
+    pub id: String,
+    pub kind: String,
+    pub message: String,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+}
+
+impl TryFrom<&Announcement> for AnnouncementModel {
+    type Error = AnnouncementStoreError;
+
+    fn try_from(announcement: &Announcement) -> Result<Self, Self::Error> {
+        Ok(AnnouncementModel {
+            id: announcement.id().to_string(),
+            kind: announcement.kind().as_str().to_string(),
+            message: announcement.message().to_string(),
+            created_at: to_timestamp(announcement.created_at())?,
+            expires_at: announcement.expires_at().map(to_timestamp).transpose()?,
+        })
+    }
+}
+
+impl TryFrom<AnnouncementModel> for Announcement {
+    type Error = AnnouncementStoreError;
+
+    fn try_from(model: AnnouncementModel) -> Result<Self, Self::Error> {
+        let mut builder = AnnouncementBuilder::new()
+            .with_id(model.id)
+            .with_kind(
+                model
+                    .kind
+                    .parse::<AnnouncementKind>()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            )
+            .with_message(model.message)
+            .with_created_at(from_timestamp(model.created_at)?);
+
+        if let Some(expires_at) = model.expires_at {
+            builder = builder.with_expires_at(from_timestamp(expires_at)?);
+        }
+
+        builder.build().map_err(|err| {
+            AnnouncementStoreError::Internal(InternalError::from_source(Box::new(err)))
+        })
+    }
+}
+
+/// Converts the given time to the number of seconds since the Unix epoch, as stored in the
+/// timestamp columns.
+fn to_timestamp(time: SystemTime) -> Result<i64, AnnouncementStoreError> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            AnnouncementStoreError::InvalidArgument(InvalidArgumentError::new(
+                "announcement".to_string(),
+                "time cannot be stored as a timestamp".to_string(),
+            ))
+        })
+}
+
+fn from_timestamp(timestamp: i64) -> Result<SystemTime, AnnouncementStoreError> {
+    u64::try_from(timestamp)
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            AnnouncementStoreError::Internal(InternalError::with_message(format!(
+                "timestamp {} could not be represented as a SystemTime",
+                timestamp
+            )))
+        })
+}
@@ -0,0 +1,59 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::announcement::store::{
+    diesel::{models::AnnouncementModel, schema::announcement},
+    AnnouncementStoreError,
+};
+use crate::announcement::Announcement;
+
+use super::AnnouncementStoreOperations;
+
+pub trait AnnouncementStoreAddAnnouncement {
+    fn add_announcement(&self, announcement: &Announcement) -> Result<(), AnnouncementStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AnnouncementStoreAddAnnouncement
+    for AnnouncementStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_announcement(&self, announcement: &Announcement) -> Result<(), AnnouncementStoreError> {
+        let model = AnnouncementModel::try_from(announcement)?;
+
+        insert_into(announcement::table)
+            .values(&model)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AnnouncementStoreAddAnnouncement
+    for AnnouncementStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_announcement(&self, announcement: &Announcement) -> Result<(), AnnouncementStoreError> {
+        let model = AnnouncementModel::try_from(announcement)?;
+
+        insert_into(announcement::table)
+            .values(&model)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::announcement::store::{
+    diesel::{models::AnnouncementModel, schema::announcement},
+    AnnouncementStoreError,
+};
+use crate::announcement::Announcement;
+
+use super::AnnouncementStoreOperations;
+
+pub trait AnnouncementStoreListAnnouncements {
+    fn list_announcements(&self) -> Result<Vec<Announcement>, AnnouncementStoreError>;
+}
+
+impl<'a, C> AnnouncementStoreListAnnouncements for AnnouncementStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn list_announcements(&self) -> Result<Vec<Announcement>, AnnouncementStoreError> {
+        announcement::table
+            .order((announcement::created_at, announcement::id))
+            .load::<AnnouncementModel>(self.conn)?
+            .into_iter()
+            .map(Announcement::try_from)
+            .collect()
+    }
+}
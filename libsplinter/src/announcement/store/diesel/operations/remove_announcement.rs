@@ -0,0 +1,76 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::announcement::store::{
+    diesel::{models::AnnouncementModel, schema::announcement},
+    AnnouncementStoreError,
+};
+use crate::announcement::Announcement;
+
+use super::AnnouncementStoreOperations;
+
+pub trait AnnouncementStoreRemoveAnnouncement {
+    fn remove_announcement(&self, id: &str)
+        -> Result<Option<Announcement>, AnnouncementStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AnnouncementStoreRemoveAnnouncement
+    for AnnouncementStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_announcement(
+        &self,
+        id: &str,
+    ) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        self.conn.transaction::<_, AnnouncementStoreError, _>(|| {
+            let existing = announcement::table
+                .filter(announcement::id.eq(id))
+                .first::<AnnouncementModel>(self.conn)
+                .optional()?;
+
+            if existing.is_some() {
+                delete(announcement::table.filter(announcement::id.eq(id))).execute(self.conn)?;
+            }
+
+            existing.map(Announcement::try_from).transpose()
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AnnouncementStoreRemoveAnnouncement
+    for AnnouncementStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_announcement(
+        &self,
+        id: &str,
+    ) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        self.conn.transaction::<_, AnnouncementStoreError, _>(|| {
+            let existing = announcement::table
+                .filter(announcement::id.eq(id))
+                .first::<AnnouncementModel>(self.conn)
+                .optional()?;
+
+            if existing.is_some() {
+                delete(announcement::table.filter(announcement::id.eq(id))).execute(self.conn)?;
+            }
+
+            existing.map(Announcement::try_from).transpose()
+        })
+    }
+}
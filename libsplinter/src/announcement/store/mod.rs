@@ -0,0 +1,91 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the store that persists a node's announcements.
+
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod error;
+
+use super::Announcement;
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselAnnouncementStore;
+pub use error::AnnouncementStoreError;
+
+/// Defines methods for persisting a node's announcements
+pub trait AnnouncementStore: Send + Sync {
+    /// Adds an announcement to the store
+    ///
+    /// # Arguments
+    ///
+    ///  * `announcement` - The announcement to add; its ID must not already be in use
+    fn add_announcement(&self, announcement: &Announcement) -> Result<(), AnnouncementStoreError>;
+
+    /// Returns the announcement with the given ID, if it exists
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The ID of the announcement
+    fn get_announcement(&self, id: &str) -> Result<Option<Announcement>, AnnouncementStoreError>;
+
+    /// Returns every announcement, including expired announcements, sorted by the time they were
+    /// created
+    fn list_announcements(&self) -> Result<Vec<Announcement>, AnnouncementStoreError>;
+
+    /// Removes the announcement with the given ID, returning it if it existed
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The ID of the announcement
+    fn remove_announcement(&self, id: &str)
+        -> Result<Option<Announcement>, AnnouncementStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn AnnouncementStore>;
+}
+
+impl Clone for Box<dyn AnnouncementStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<AS> AnnouncementStore for Box<AS>
+where
+    AS: AnnouncementStore + ?Sized,
+{
+    fn add_announcement(&self, announcement: &Announcement) -> Result<(), AnnouncementStoreError> {
+        (**self).add_announcement(announcement)
+    }
+
+    fn get_announcement(&self, id: &str) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        (**self).get_announcement(id)
+    }
+
+    fn list_announcements(&self) -> Result<Vec<Announcement>, AnnouncementStoreError> {
+        (**self).list_announcements()
+    }
+
+    fn remove_announcement(
+        &self,
+        id: &str,
+    ) -> Result<Option<Announcement>, AnnouncementStoreError> {
+        (**self).remove_announcement(id)
+    }
+
+    fn clone_box(&self) -> Box<dyn AnnouncementStore> {
+        (**self).clone_box()
+    }
+}
@@ -0,0 +1,298 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! API keys used by machine clients to authenticate with the REST API.
+//!
+//! An API key is a random secret that is sent in an `Authorization: ApiKey <key>` header. Each
+//! key is issued for an identity (a public key or a user ID), so the permissions of a client using
+//! the key are those assigned to that identity. Only a SHA-256 hash of the secret is kept in the
+//! [`ApiKeyStore`](store::ApiKeyStore); the secret itself is only returned when the key is
+//! created.
+
+pub mod store;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::error::{InvalidArgumentError, InvalidStateError};
+use crate::hex::to_hex;
+
+/// The number of random alphanumeric characters in an API key's secret
+const API_KEY_SECRET_LENGTH: usize = 40;
+
+/// The identity a client authenticates as when using an API key
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ApiKeyIdentity {
+    /// A public key
+    Key(String),
+    /// A user ID
+    User(String),
+}
+
+impl ApiKeyIdentity {
+    /// Returns the type of the identity, either `key` or `user`
+    pub fn identity_type(&self) -> &'static str {
+        match self {
+            ApiKeyIdentity::Key(_) => "key",
+            ApiKeyIdentity::User(_) => "user",
+        }
+    }
+
+    /// Returns the public key or user ID
+    pub fn value(&self) -> &str {
+        match self {
+            ApiKeyIdentity::Key(value) | ApiKeyIdentity::User(value) => value,
+        }
+    }
+
+    /// Creates an identity from its type (`key` or `user`) and value
+    pub fn from_parts(identity_type: &str, value: String) -> Result<Self, InvalidArgumentError> {
+        match identity_type {
+            "key" => Ok(ApiKeyIdentity::Key(value)),
+            "user" => Ok(ApiKeyIdentity::User(value)),
+            _ => Err(InvalidArgumentError::new(
+                "identity_type",
+                format!(
+                    "unknown identity type \"{}\"; expected key or user",
+                    identity_type
+                ),
+            )),
+        }
+    }
+}
+
+impl fmt::Display for ApiKeyIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.identity_type(), self.value())
+    }
+}
+
+/// An API key, without its secret
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ApiKey {
+    id: String,
+    display_name: String,
+    identity: ApiKeyIdentity,
+    secret_hash: String,
+    created_at: SystemTime,
+}
+
+impl ApiKey {
+    /// Returns the ID of the API key, used to revoke it
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the human-readable name of the API key
+    pub fn display_name(&self) -> &str {
+        &self.display_name
+    }
+
+    /// Returns the identity a client authenticates as when using the API key
+    pub fn identity(&self) -> &ApiKeyIdentity {
+        &self.identity
+    }
+
+    /// Returns the hex-encoded SHA-256 hash of the API key's secret
+    pub fn secret_hash(&self) -> &str {
+        &self.secret_hash
+    }
+
+    /// Returns the time the API key was created
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+}
+
+/// Returns the hex-encoded SHA-256 hash of an API key secret, as stored in an
+/// [`ApiKeyStore`](store::ApiKeyStore)
+pub fn hash_api_key_secret(secret: &str) -> String {
+    to_hex(&Sha256::digest(secret.as_bytes()))
+}
+
+/// Builds an [`ApiKey`].
+#[derive(Default)]
+pub struct ApiKeyBuilder {
+    id: Option<String>,
+    display_name: Option<String>,
+    identity: Option<ApiKeyIdentity>,
+    secret_hash: Option<String>,
+    created_at: Option<SystemTime>,
+}
+
+impl ApiKeyBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the ID of the API key; a random ID is generated if one is not set.
+    pub fn with_id(mut self, id: String) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    pub fn with_display_name(mut self, display_name: String) -> Self {
+        self.display_name = Some(display_name);
+        self
+    }
+
+    pub fn with_identity(mut self, identity: ApiKeyIdentity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Sets the hash of the API key's secret, as returned by [`hash_api_key_secret`]
+    pub fn with_secret_hash(mut self, secret_hash: String) -> Self {
+        self.secret_hash = Some(secret_hash);
+        self
+    }
+
+    /// Sets the time the API key was created; defaults to the current time.
+    pub fn with_created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    /// Builds the API key.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if the display name, identity or secret hash is missing or
+    /// empty.
+    pub fn build(self) -> Result<ApiKey, InvalidStateError> {
+        let display_name = self
+            .display_name
+            .filter(|display_name| !display_name.trim().is_empty())
+            .ok_or_else(|| {
+                InvalidStateError::with_message(
+                    "an API key requires a non-empty display name".to_string(),
+                )
+            })?;
+        let identity = self
+            .identity
+            .filter(|identity| !identity.value().is_empty())
+            .ok_or_else(|| {
+                InvalidStateError::with_message(
+                    "an API key requires a non-empty identity".to_string(),
+                )
+            })?;
+        let secret_hash = self
+            .secret_hash
+            .filter(|secret_hash| !secret_hash.is_empty())
+            .ok_or_else(|| {
+                InvalidStateError::with_message("an API key requires a secret hash".to_string())
+            })?;
+
+        Ok(ApiKey {
+            id: self.id.unwrap_or_else(|| Uuid::new_v4().to_string()),
+            display_name,
+            identity,
+            secret_hash,
+            created_at: self.created_at.unwrap_or_else(SystemTime::now),
+        })
+    }
+
+    /// Generates a random secret and builds the API key with its hash.
+    ///
+    /// Returns the API key and its secret. The secret cannot be recovered from the API key, so it
+    /// must be given to the client now.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if the display name or identity is missing or empty.
+    pub fn generate(mut self) -> Result<(ApiKey, String), InvalidStateError> {
+        let mut rng = thread_rng();
+        let secret: String = std::iter::repeat(())
+            .map(|()| rng.sample(Alphanumeric))
+            .map(char::from)
+            .take(API_KEY_SECRET_LENGTH)
+            .collect();
+
+        self.secret_hash = Some(hash_api_key_secret(&secret));
+        Ok((self.build()?, secret))
+    }
+}
+
+impl FromStr for ApiKeyIdentity {
+    type Err = InvalidArgumentError;
+
+    /// Parses an identity formatted as `<identity type>:<value>`, such as `user:alice`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(identity_type), Some(value)) => {
+                ApiKeyIdentity::from_parts(identity_type, value.to_string())
+            }
+            _ => Err(InvalidArgumentError::new(
+                "identity",
+                format!(
+                    "\"{}\" is not formatted as <identity type>:<value>, such as user:alice",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that generating an API key stores the hash of the returned secret, and that the
+    /// builder rejects a missing display name or identity.
+    #[test]
+    fn test_api_key_builder() {
+        let (api_key, secret) = ApiKeyBuilder::new()
+            .with_display_name("CI pipeline".to_string())
+            .with_identity(ApiKeyIdentity::User("ci".to_string()))
+            .generate()
+            .expect("Failed to generate API key");
+        assert_eq!(secret.len(), API_KEY_SECRET_LENGTH);
+        assert_eq!(api_key.secret_hash(), hash_api_key_secret(&secret));
+        assert_ne!(api_key.secret_hash(), secret);
+        assert!(!api_key.id().is_empty());
+
+        assert!(ApiKeyBuilder::new()
+            .with_identity(ApiKeyIdentity::User("ci".to_string()))
+            .generate()
+            .is_err());
+        assert!(ApiKeyBuilder::new()
+            .with_display_name("CI pipeline".to_string())
+            .with_identity(ApiKeyIdentity::Key("".to_string()))
+            .generate()
+            .is_err());
+    }
+
+    /// Verify that identities round-trip through their string representation.
+    #[test]
+    fn test_api_key_identity_from_str() {
+        for identity in &[
+            ApiKeyIdentity::Key("02abcdef".to_string()),
+            ApiKeyIdentity::User("ci:pipeline".to_string()),
+        ] {
+            assert_eq!(
+                identity.to_string().parse::<ApiKeyIdentity>().ok().as_ref(),
+                Some(identity)
+            );
+        }
+        assert!("group:ops".parse::<ApiKeyIdentity>().is_err());
+        assert!("ops".parse::<ApiKeyIdentity>().is_err());
+    }
+}
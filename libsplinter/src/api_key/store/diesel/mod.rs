@@ -0,0 +1,259 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [ApiKeyStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::api_key::ApiKey;
+use crate::store::pool::ConnectionPool;
+
+use super::{ApiKeyStore, ApiKeyStoreError};
+
+use operations::{
+    add_api_key::ApiKeyStoreAddApiKey as _, get_api_key::ApiKeyStoreGetApiKey as _,
+    list_api_keys::ApiKeyStoreListApiKeys as _, remove_api_key::ApiKeyStoreRemoveApiKey as _,
+    ApiKeyStoreOperations,
+};
+
+/// Manages adding, fetching and removing API keys in the database
+pub struct DieselApiKeyStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselApiKeyStore<C> {
+    /// Creates a new DieselApiKeyStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselApiKeyStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselApiKeyStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl ApiKeyStore for DieselApiKeyStore<diesel::pg::PgConnection> {
+    fn add_api_key(&self, api_key: &ApiKey) -> Result<(), ApiKeyStoreError> {
+        self.connection_pool
+            .execute_write(|connection| ApiKeyStoreOperations::new(connection).add_api_key(api_key))
+    }
+
+    fn get_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool
+            .execute_read(|connection| ApiKeyStoreOperations::new(connection).get_api_key(id))
+    }
+
+    fn get_api_key_by_secret_hash(
+        &self,
+        secret_hash: &str,
+    ) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            ApiKeyStoreOperations::new(connection).get_api_key_by_secret_hash(secret_hash)
+        })
+    }
+
+    fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool
+            .execute_read(|connection| ApiKeyStoreOperations::new(connection).list_api_keys())
+    }
+
+    fn remove_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool
+            .execute_write(|connection| ApiKeyStoreOperations::new(connection).remove_api_key(id))
+    }
+
+    fn clone_box(&self) -> Box<dyn ApiKeyStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ApiKeyStore for DieselApiKeyStore<diesel::sqlite::SqliteConnection> {
+    fn add_api_key(&self, api_key: &ApiKey) -> Result<(), ApiKeyStoreError> {
+        self.connection_pool
+            .execute_write(|connection| ApiKeyStoreOperations::new(connection).add_api_key(api_key))
+    }
+
+    fn get_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool
+            .execute_read(|connection| ApiKeyStoreOperations::new(connection).get_api_key(id))
+    }
+
+    fn get_api_key_by_secret_hash(
+        &self,
+        secret_hash: &str,
+    ) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            ApiKeyStoreOperations::new(connection).get_api_key_by_secret_hash(secret_hash)
+        })
+    }
+
+    fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool
+            .execute_read(|connection| ApiKeyStoreOperations::new(connection).list_api_keys())
+    }
+
+    fn remove_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.connection_pool
+            .execute_write(|connection| ApiKeyStoreOperations::new(connection).remove_api_key(id))
+    }
+
+    fn clone_box(&self) -> Box<dyn ApiKeyStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::api_key::{hash_api_key_secret, ApiKeyBuilder, ApiKeyIdentity};
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a SQLite-backed `DieselApiKeyStore` correctly supports adding, fetching,
+    /// listing and removing API keys.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Add two API keys and verify that adding a key with an existing ID or secret hash fails.
+    /// 3. Verify that each key can be fetched by ID and by the hash of its secret, and that they
+    ///    are listed in the order they were created.
+    /// 4. Remove a key and verify that it is returned, that it can no longer be fetched by its
+    ///    secret hash and that removing it again returns `None`.
+    #[test]
+    fn sqlite_add_list_and_remove_api_keys() {
+        let store = DieselApiKeyStore::new(create_connection_pool_and_migrate());
+
+        let ci = ApiKeyBuilder::new()
+            .with_id("ci".to_string())
+            .with_display_name("CI pipeline".to_string())
+            .with_identity(ApiKeyIdentity::User("ci-user".to_string()))
+            .with_secret_hash(hash_api_key_secret("ci-secret"))
+            .with_created_at(UNIX_EPOCH + Duration::from_secs(2000))
+            .build()
+            .expect("Failed to build API key");
+        let monitor = ApiKeyBuilder::new()
+            .with_id("monitor".to_string())
+            .with_display_name("Monitoring".to_string())
+            .with_identity(ApiKeyIdentity::Key("02abcdef".to_string()))
+            .with_secret_hash(hash_api_key_secret("monitor-secret"))
+            .with_created_at(UNIX_EPOCH + Duration::from_secs(1000))
+            .build()
+            .expect("Failed to build API key");
+        let duplicate_secret = ApiKeyBuilder::new()
+            .with_id("duplicate".to_string())
+            .with_display_name("Duplicate".to_string())
+            .with_identity(ApiKeyIdentity::User("ci-user".to_string()))
+            .with_secret_hash(hash_api_key_secret("ci-secret"))
+            .build()
+            .expect("Failed to build API key");
+
+        store.add_api_key(&ci).expect("Failed to add API key");
+        store.add_api_key(&monitor).expect("Failed to add API key");
+        assert!(store.add_api_key(&monitor).is_err());
+        assert!(store.add_api_key(&duplicate_secret).is_err());
+
+        assert_eq!(
+            store.get_api_key("ci").expect("Failed to get API key"),
+            Some(ci.clone())
+        );
+        assert_eq!(
+            store
+                .get_api_key_by_secret_hash(&hash_api_key_secret("monitor-secret"))
+                .expect("Failed to get API key"),
+            Some(monitor.clone())
+        );
+        assert_eq!(
+            store
+                .get_api_key_by_secret_hash(&hash_api_key_secret("unknown"))
+                .expect("Failed to get API key"),
+            None
+        );
+        assert_eq!(
+            store.list_api_keys().expect("Failed to list API keys"),
+            vec![monitor.clone(), ci.clone()]
+        );
+
+        assert_eq!(
+            store
+                .remove_api_key("monitor")
+                .expect("Failed to remove API key"),
+            Some(monitor)
+        );
+        assert_eq!(
+            store
+                .get_api_key_by_secret_hash(&hash_api_key_secret("monitor-secret"))
+                .expect("Failed to get API key"),
+            None
+        );
+        assert_eq!(
+            store.list_api_keys().expect("Failed to list API keys"),
+            vec![ci]
+        );
+        assert_eq!(
+            store
+                .remove_api_key("monitor")
+                .expect("Failed to remove API key"),
+            None
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::api_key::store::ApiKeyStoreError;
+use crate::api_key::{ApiKey, ApiKeyBuilder, ApiKeyIdentity};
+use crate::error::{InternalError, InvalidArgumentError};
+
+use super::schema::api_key;
+
+#[derive(Insertable, Queryable, Identifiable, PartialEq, Eq, Debug)]
+#[table_name = "api_key"]
+#[primary_key(id)]
+pub struct ApiKeyModel {
+    pub id: String,
+    pub display_name: String,
+    pub identity_type: String,
+    pub identity: String,
+    pub secret_hash: String,
+    pub created_at: i64,
+}
+
+impl TryFrom<&ApiKey> for ApiKeyModel {
+    type Error = ApiKeyStoreError;
+
+    fn try_from(api_key: &ApiKey) -> Result<Self, Self::Error> {
+        Ok(ApiKeyModel {
+            id: api_key.id().to_string(),
+            display_name: api_key.display_name().to_string(),
+            identity_type: api_key.identity().identity_type().to_string(),
+            identity: api_key.identity().value().to_string(),
+            secret_hash: api_key.secret_hash().to_string(),
+            created_at: to_timestamp(api_key.created_at())?,
+        })
+    }
+}
+
+impl TryFrom<ApiKeyModel> for ApiKey {
+    type Error = ApiKeyStoreError;
+
+    fn try_from(model: ApiKeyModel) -> Result<Self, Self::Error> {
+        ApiKeyBuilder::new()
+            .with_id(model.id)
+            .with_display_name(model.display_name)
+            .with_identity(
+                ApiKeyIdentity::from_parts(&model.identity_type, model.identity)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            )
+            .with_secret_hash(model.secret_hash)
+            .with_created_at(from_timestamp(model.created_at)?)
+            .build()
+            .map_err(|err| ApiKeyStoreError::Internal(InternalError::from_source(Box::new(err))))
+    }
+}
+
+/// Converts the given time to the number of seconds since the Unix epoch, as stored in the
+/// `created_at` column.
+fn to_timestamp(time: SystemTime) -> Result<i64, ApiKeyStoreError> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            ApiKeyStoreError::InvalidArgument(InvalidArgumentError::new(
+                "api_key".to_string(),
+                "time cannot be stored as a timestamp".to_string(),
+            ))
+        })
+}
+
+fn from_timestamp(timestamp: i64) -> Result<SystemTime, ApiKeyStoreError> {
+    u64::try_from(timestamp)
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            ApiKeyStoreError::Internal(InternalError::with_message(format!(
+                "timestamp {} could not be represented as a SystemTime",
+                timestamp
+            )))
+        })
+}
@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::api_key::store::{
+    diesel::{models::ApiKeyModel, schema::api_key},
+    ApiKeyStoreError,
+};
+use crate::api_key::ApiKey;
+
+use super::ApiKeyStoreOperations;
+
+pub trait ApiKeyStoreAddApiKey {
+    fn add_api_key(&self, api_key: &ApiKey) -> Result<(), ApiKeyStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ApiKeyStoreAddApiKey for ApiKeyStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn add_api_key(&self, api_key: &ApiKey) -> Result<(), ApiKeyStoreError> {
+        let model = ApiKeyModel::try_from(api_key)?;
+
+        insert_into(api_key::table)
+            .values(&model)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ApiKeyStoreAddApiKey for ApiKeyStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_api_key(&self, api_key: &ApiKey) -> Result<(), ApiKeyStoreError> {
+        let model = ApiKeyModel::try_from(api_key)?;
+
+        insert_into(api_key::table)
+            .values(&model)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
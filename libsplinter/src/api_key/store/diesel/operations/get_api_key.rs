@@ -0,0 +1,62 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::api_key::store::{
+    diesel::{models::ApiKeyModel, schema::api_key},
+    ApiKeyStoreError,
+};
+use crate::api_key::ApiKey;
+
+use super::ApiKeyStoreOperations;
+
+pub trait ApiKeyStoreGetApiKey {
+    fn get_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError>;
+
+    fn get_api_key_by_secret_hash(
+        &self,
+        secret_hash: &str,
+    ) -> Result<Option<ApiKey>, ApiKeyStoreError>;
+}
+
+impl<'a, C> ApiKeyStoreGetApiKey for ApiKeyStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn get_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        api_key::table
+            .filter(api_key::id.eq(id))
+            .first::<ApiKeyModel>(self.conn)
+            .optional()?
+            .map(ApiKey::try_from)
+            .transpose()
+    }
+
+    fn get_api_key_by_secret_hash(
+        &self,
+        secret_hash: &str,
+    ) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        api_key::table
+            .filter(api_key::secret_hash.eq(secret_hash))
+            .first::<ApiKeyModel>(self.conn)
+            .optional()?
+            .map(ApiKey::try_from)
+            .transpose()
+    }
+}
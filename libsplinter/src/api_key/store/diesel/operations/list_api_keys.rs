@@ -0,0 +1,45 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::api_key::store::{
+    diesel::{models::ApiKeyModel, schema::api_key},
+    ApiKeyStoreError,
+};
+use crate::api_key::ApiKey;
+
+use super::ApiKeyStoreOperations;
+
+pub trait ApiKeyStoreListApiKeys {
+    fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStoreError>;
+}
+
+impl<'a, C> ApiKeyStoreListApiKeys for ApiKeyStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStoreError> {
+        api_key::table
+            .order((api_key::created_at, api_key::id))
+            .load::<ApiKeyModel>(self.conn)?
+            .into_iter()
+            .map(ApiKey::try_from)
+            .collect()
+    }
+}
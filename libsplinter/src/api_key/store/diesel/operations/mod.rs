@@ -0,0 +1,34 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides [`ApiKeyStore`](super::super::ApiKeyStore) operations implemented for a diesel
+//! backend
+
+pub(super) mod add_api_key;
+pub(super) mod get_api_key;
+pub(super) mod list_api_keys;
+pub(super) mod remove_api_key;
+
+pub(super) struct ApiKeyStoreOperations<'a, C> {
+    conn: &'a C,
+}
+
+impl<'a, C> ApiKeyStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    pub fn new(conn: &'a C) -> Self {
+        ApiKeyStoreOperations { conn }
+    }
+}
@@ -0,0 +1,65 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::api_key::store::{
+    diesel::{models::ApiKeyModel, schema::api_key},
+    ApiKeyStoreError,
+};
+use crate::api_key::ApiKey;
+
+use super::ApiKeyStoreOperations;
+
+pub trait ApiKeyStoreRemoveApiKey {
+    fn remove_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ApiKeyStoreRemoveApiKey for ApiKeyStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn remove_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.conn.transaction::<_, ApiKeyStoreError, _>(|| {
+            let existing = api_key::table
+                .filter(api_key::id.eq(id))
+                .first::<ApiKeyModel>(self.conn)
+                .optional()?;
+
+            if existing.is_some() {
+                delete(api_key::table.filter(api_key::id.eq(id))).execute(self.conn)?;
+            }
+
+            existing.map(ApiKey::try_from).transpose()
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ApiKeyStoreRemoveApiKey for ApiKeyStoreOperations<'a, diesel::pg::PgConnection> {
+    fn remove_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        self.conn.transaction::<_, ApiKeyStoreError, _>(|| {
+            let existing = api_key::table
+                .filter(api_key::id.eq(id))
+                .first::<ApiKeyModel>(self.conn)
+                .optional()?;
+
+            if existing.is_some() {
+                delete(api_key::table.filter(api_key::id.eq(id))).execute(self.conn)?;
+            }
+
+            existing.map(ApiKey::try_from).transpose()
+        })
+    }
+}
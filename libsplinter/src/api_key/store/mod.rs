@@ -0,0 +1,104 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the store that persists the REST API's API keys.
+
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod error;
+
+use super::ApiKey;
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselApiKeyStore;
+pub use error::ApiKeyStoreError;
+
+/// Defines methods for persisting API keys
+pub trait ApiKeyStore: Send + Sync {
+    /// Adds an API key to the store
+    ///
+    /// # Arguments
+    ///
+    ///  * `api_key` - The API key to add; its ID and secret hash must not already be in use
+    fn add_api_key(&self, api_key: &ApiKey) -> Result<(), ApiKeyStoreError>;
+
+    /// Returns the API key with the given ID, if it exists
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The ID of the API key
+    fn get_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError>;
+
+    /// Returns the API key whose secret has the given hash, if it exists
+    ///
+    /// # Arguments
+    ///
+    ///  * `secret_hash` - The hash of the secret, as returned by
+    ///    [`hash_api_key_secret`](super::hash_api_key_secret)
+    fn get_api_key_by_secret_hash(
+        &self,
+        secret_hash: &str,
+    ) -> Result<Option<ApiKey>, ApiKeyStoreError>;
+
+    /// Returns every API key, sorted by the time they were created
+    fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStoreError>;
+
+    /// Removes the API key with the given ID, returning it if it existed
+    ///
+    /// # Arguments
+    ///
+    ///  * `id` - The ID of the API key
+    fn remove_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn ApiKeyStore>;
+}
+
+impl Clone for Box<dyn ApiKeyStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<AS> ApiKeyStore for Box<AS>
+where
+    AS: ApiKeyStore + ?Sized,
+{
+    fn add_api_key(&self, api_key: &ApiKey) -> Result<(), ApiKeyStoreError> {
+        (**self).add_api_key(api_key)
+    }
+
+    fn get_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        (**self).get_api_key(id)
+    }
+
+    fn get_api_key_by_secret_hash(
+        &self,
+        secret_hash: &str,
+    ) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        (**self).get_api_key_by_secret_hash(secret_hash)
+    }
+
+    fn list_api_keys(&self) -> Result<Vec<ApiKey>, ApiKeyStoreError> {
+        (**self).list_api_keys()
+    }
+
+    fn remove_api_key(&self, id: &str) -> Result<Option<ApiKey>, ApiKeyStoreError> {
+        (**self).remove_api_key(id)
+    }
+
+    fn clone_box(&self) -> Box<dyn ApiKeyStore> {
+        (**self).clone_box()
+    }
+}
@@ -0,0 +1,291 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An append-only log of administrative and authorization events.
+//!
+//! The audit log records circuit proposal submissions and votes, circuit lifecycle changes, RBAC
+//! modifications and failed REST API authorization attempts, along with the actor responsible
+//! for each event and the time it occurred. Events are kept in an [`AuditStore`](store::AuditStore),
+//! which only supports adding and listing events.
+
+pub mod store;
+
+use std::fmt;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::error::InvalidArgumentError;
+
+/// The kinds of events recorded in the audit log
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuditEventType {
+    /// A circuit proposal was submitted
+    ProposalSubmitted,
+    /// A vote was cast on a circuit proposal
+    ProposalVote,
+    /// A circuit proposal was accepted by all members
+    ProposalAccepted,
+    /// A circuit proposal was rejected
+    ProposalRejected,
+    /// A circuit was created and is ready for use
+    CircuitReady,
+    /// A circuit was disbanded
+    CircuitDisbanded,
+    /// A circuit was abandoned by this node
+    CircuitAbandoned,
+    /// A circuit was purged from this node
+    CircuitPurged,
+    /// A role or role assignment was added, changed or removed
+    RbacModified,
+    /// A REST API request was rejected because the client could not be authorized
+    AuthFailed,
+}
+
+impl AuditEventType {
+    /// Returns the name of the event type, as stored and displayed
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEventType::ProposalSubmitted => "proposal_submitted",
+            AuditEventType::ProposalVote => "proposal_vote",
+            AuditEventType::ProposalAccepted => "proposal_accepted",
+            AuditEventType::ProposalRejected => "proposal_rejected",
+            AuditEventType::CircuitReady => "circuit_ready",
+            AuditEventType::CircuitDisbanded => "circuit_disbanded",
+            AuditEventType::CircuitAbandoned => "circuit_abandoned",
+            AuditEventType::CircuitPurged => "circuit_purged",
+            AuditEventType::RbacModified => "rbac_modified",
+            AuditEventType::AuthFailed => "auth_failed",
+        }
+    }
+}
+
+impl fmt::Display for AuditEventType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for AuditEventType {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "proposal_submitted" => Ok(AuditEventType::ProposalSubmitted),
+            "proposal_vote" => Ok(AuditEventType::ProposalVote),
+            "proposal_accepted" => Ok(AuditEventType::ProposalAccepted),
+            "proposal_rejected" => Ok(AuditEventType::ProposalRejected),
+            "circuit_ready" => Ok(AuditEventType::CircuitReady),
+            "circuit_disbanded" => Ok(AuditEventType::CircuitDisbanded),
+            "circuit_abandoned" => Ok(AuditEventType::CircuitAbandoned),
+            "circuit_purged" => Ok(AuditEventType::CircuitPurged),
+            "rbac_modified" => Ok(AuditEventType::RbacModified),
+            "auth_failed" => Ok(AuditEventType::AuthFailed),
+            _ => Err(InvalidArgumentError::new(
+                "event_type",
+                format!("unknown audit event type \"{}\"", s),
+            )),
+        }
+    }
+}
+
+/// An event to be recorded in the audit log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEvent {
+    event_type: AuditEventType,
+    actor: String,
+    target: String,
+    detail: String,
+    timestamp: SystemTime,
+}
+
+impl AuditEvent {
+    /// Creates an event that occurred at the current time.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_type` - The kind of event
+    /// * `actor` - The identity responsible for the event, such as a public key or user ID; for
+    ///   failed authorization attempts this is the address of the client
+    /// * `target` - What the event applies to, such as a circuit ID or role ID
+    pub fn new(event_type: AuditEventType, actor: String, target: String) -> Self {
+        Self {
+            event_type,
+            actor,
+            target,
+            detail: String::new(),
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    /// Sets a human-readable description of the event
+    pub fn with_detail(mut self, detail: String) -> Self {
+        self.detail = detail;
+        self
+    }
+
+    /// Sets the time the event occurred
+    pub fn with_timestamp(mut self, timestamp: SystemTime) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
+    pub fn event_type(&self) -> AuditEventType {
+        self.event_type
+    }
+
+    pub fn actor(&self) -> &str {
+        &self.actor
+    }
+
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    pub fn detail(&self) -> &str {
+        &self.detail
+    }
+
+    pub fn timestamp(&self) -> SystemTime {
+        self.timestamp
+    }
+}
+
+/// An event that has been recorded in the audit log
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuditEntry {
+    id: i64,
+    event: AuditEvent,
+}
+
+impl AuditEntry {
+    pub(crate) fn new(id: i64, event: AuditEvent) -> Self {
+        Self { id, event }
+    }
+
+    /// Returns the ID assigned to the event when it was recorded; IDs increase in the order that
+    /// events are recorded.
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    pub fn event(&self) -> &AuditEvent {
+        &self.event
+    }
+}
+
+/// Limits the events returned when listing the audit log
+#[derive(Clone, Debug, Default)]
+pub struct AuditFilter {
+    event_type: Option<AuditEventType>,
+    actor: Option<String>,
+    target: Option<String>,
+    since: Option<SystemTime>,
+    until: Option<SystemTime>,
+    limit: Option<usize>,
+}
+
+impl AuditFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only lists events of the given type
+    pub fn with_event_type(mut self, event_type: AuditEventType) -> Self {
+        self.event_type = Some(event_type);
+        self
+    }
+
+    /// Only lists events caused by the given actor
+    pub fn with_actor(mut self, actor: String) -> Self {
+        self.actor = Some(actor);
+        self
+    }
+
+    /// Only lists events that apply to the given target, such as a circuit ID
+    pub fn with_target(mut self, target: String) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    /// Only lists events that occurred at or after the given time
+    pub fn with_since(mut self, since: SystemTime) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    /// Only lists events that occurred before the given time
+    pub fn with_until(mut self, until: SystemTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Only lists the given number of the most recent matching events
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn event_type(&self) -> Option<AuditEventType> {
+        self.event_type
+    }
+
+    pub fn actor(&self) -> Option<&str> {
+        self.actor.as_deref()
+    }
+
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    pub fn since(&self) -> Option<SystemTime> {
+        self.since
+    }
+
+    pub fn until(&self) -> Option<SystemTime> {
+        self.until
+    }
+
+    pub fn limit(&self) -> Option<usize> {
+        self.limit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that every event type round-trips through its name, and that unknown names are
+    /// rejected.
+    #[test]
+    fn test_audit_event_type_from_str() {
+        for event_type in &[
+            AuditEventType::ProposalSubmitted,
+            AuditEventType::ProposalVote,
+            AuditEventType::ProposalAccepted,
+            AuditEventType::ProposalRejected,
+            AuditEventType::CircuitReady,
+            AuditEventType::CircuitDisbanded,
+            AuditEventType::CircuitAbandoned,
+            AuditEventType::CircuitPurged,
+            AuditEventType::RbacModified,
+            AuditEventType::AuthFailed,
+        ] {
+            assert_eq!(
+                event_type.as_str().parse::<AuditEventType>().ok(),
+                Some(*event_type)
+            );
+        }
+        assert!("circuit_created".parse::<AuditEventType>().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [AuditStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::audit::{AuditEntry, AuditEvent, AuditFilter};
+use crate::store::pool::ConnectionPool;
+
+use super::{AuditStore, AuditStoreError};
+
+use operations::{
+    add_event::AuditStoreAddEvent as _, list_events::AuditStoreListEvents as _,
+    AuditStoreOperations,
+};
+
+/// Manages recording and listing audit events in the database
+pub struct DieselAuditStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselAuditStore<C> {
+    /// Creates a new DieselAuditStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselAuditStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselAuditStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl AuditStore for DieselAuditStore<diesel::pg::PgConnection> {
+    fn add_event(&self, event: &AuditEvent) -> Result<(), AuditStoreError> {
+        self.connection_pool
+            .execute_write(|connection| AuditStoreOperations::new(connection).add_event(event))
+    }
+
+    fn list_events(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditStoreError> {
+        self.connection_pool
+            .execute_read(|connection| AuditStoreOperations::new(connection).list_events(filter))
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl AuditStore for DieselAuditStore<diesel::sqlite::SqliteConnection> {
+    fn add_event(&self, event: &AuditEvent) -> Result<(), AuditStoreError> {
+        self.connection_pool
+            .execute_write(|connection| AuditStoreOperations::new(connection).add_event(event))
+    }
+
+    fn list_events(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditStoreError> {
+        self.connection_pool
+            .execute_read(|connection| AuditStoreOperations::new(connection).list_events(filter))
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::audit::AuditEventType;
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a SQLite-backed `DieselAuditStore` correctly supports recording and listing
+    /// audit events.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Record three events from two actors at different times.
+    /// 3. Verify that all events are listed with increasing IDs, most recent first.
+    /// 4. Verify that events can be filtered by type, actor, target and time range, and that the
+    ///    limit returns the most recent events.
+    #[test]
+    fn sqlite_add_and_list_events() {
+        let store = DieselAuditStore::new(create_connection_pool_and_migrate());
+
+        let submitted = AuditEvent::new(
+            AuditEventType::ProposalSubmitted,
+            "02abcdef".to_string(),
+            "abcde-01234".to_string(),
+        )
+        .with_detail("create proposal submitted".to_string())
+        .with_timestamp(UNIX_EPOCH + Duration::from_secs(1000));
+        let vote = AuditEvent::new(
+            AuditEventType::ProposalVote,
+            "03fedcba".to_string(),
+            "abcde-01234".to_string(),
+        )
+        .with_timestamp(UNIX_EPOCH + Duration::from_secs(2000));
+        let auth_failed = AuditEvent::new(
+            AuditEventType::AuthFailed,
+            "127.0.0.1".to_string(),
+            "GET /admin/circuits".to_string(),
+        )
+        .with_timestamp(UNIX_EPOCH + Duration::from_secs(3000));
+
+        for event in &[&submitted, &vote, &auth_failed] {
+            store.add_event(event).expect("Failed to add event");
+        }
+
+        let entries = store
+            .list_events(&AuditFilter::new())
+            .expect("Failed to list events");
+        assert_eq!(
+            entries
+                .iter()
+                .map(|entry| entry.event().clone())
+                .collect::<Vec<_>>(),
+            vec![auth_failed.clone(), vote.clone(), submitted.clone()]
+        );
+        assert!(entries[0].id() > entries[1].id() && entries[1].id() > entries[2].id());
+
+        let list = |filter: AuditFilter| {
+            store
+                .list_events(&filter)
+                .expect("Failed to list events")
+                .into_iter()
+                .map(|entry| entry.event().clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            list(AuditFilter::new().with_event_type(AuditEventType::ProposalVote)),
+            vec![vote.clone()]
+        );
+        assert_eq!(
+            list(AuditFilter::new().with_actor("02abcdef".to_string())),
+            vec![submitted.clone()]
+        );
+        assert_eq!(
+            list(AuditFilter::new().with_target("abcde-01234".to_string())),
+            vec![vote.clone(), submitted.clone()]
+        );
+        assert_eq!(
+            list(
+                AuditFilter::new()
+                    .with_since(UNIX_EPOCH + Duration::from_secs(2000))
+                    .with_until(UNIX_EPOCH + Duration::from_secs(3000))
+            ),
+            vec![vote]
+        );
+        assert_eq!(list(AuditFilter::new().with_limit(1)), vec![auth_failed]);
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
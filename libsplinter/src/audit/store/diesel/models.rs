@@ -0,0 +1,102 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::audit::store::AuditStoreError;
+use crate::audit::{AuditEntry, AuditEvent, AuditEventType};
+use crate::error::{InternalError, InvalidArgumentError};
+
+use super::schema::audit_event;
+
+#[derive(Queryable, Identifiable, PartialEq, Eq, Debug)]
+#[table_name = "audit_event"]
+#[primary_key(id)]
+pub struct AuditEventModel {
+    pub id: i64,
+    pub event_type: String,
+    pub actor: String,
+    pub target: String,
+    pub detail: String,
+    pub timestamp: i64,
+}
+
+#[derive(Insertable, PartialEq, Eq, Debug)]
+#[table_name = "audit_event"]
+pub struct NewAuditEventModel<'a> {
+    pub event_type: &'a str,
+    pub actor: &'a str,
+    pub target: &'a str,
+    pub detail: &'a str,
+    pub timestamp: i64,
+}
+
+impl<'a> TryFrom<&'a AuditEvent> for NewAuditEventModel<'a> {
+    type Error = AuditStoreError;
+
+    fn try_from(event: &'a AuditEvent) -> Result<Self, Self::Error> {
+        Ok(NewAuditEventModel {
+            event_type: event.event_type().as_str(),
+            actor: event.actor(),
+            target: event.target(),
+            detail: event.detail(),
+            timestamp: to_timestamp(event.timestamp())?,
+        })
+    }
+}
+
+impl TryFrom<AuditEventModel> for AuditEntry {
+    type Error = AuditStoreError;
+
+    fn try_from(model: AuditEventModel) -> Result<Self, Self::Error> {
+        let event_type = model
+            .event_type
+            .parse::<AuditEventType>()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(AuditEntry::new(
+            model.id,
+            AuditEvent::new(event_type, model.actor, model.target)
+                .with_detail(model.detail)
+                .with_timestamp(from_timestamp(model.timestamp)?),
+        ))
+    }
+}
+
+/// Converts the given time to the number of seconds since the Unix epoch, as stored in the
+/// `timestamp` column.
+pub fn to_timestamp(time: SystemTime) -> Result<i64, AuditStoreError> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            AuditStoreError::InvalidArgument(InvalidArgumentError::new(
+                "timestamp".to_string(),
+                "time cannot be stored as a timestamp".to_string(),
+            ))
+        })
+}
+
+fn from_timestamp(timestamp: i64) -> Result<SystemTime, AuditStoreError> {
+    u64::try_from(timestamp)
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            AuditStoreError::Internal(InternalError::with_message(format!(
+                "timestamp {} could not be represented as a SystemTime",
+                timestamp
+            )))
+        })
+}
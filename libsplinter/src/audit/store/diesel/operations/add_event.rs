@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{dsl::insert_into, prelude::*};
+
+use crate::audit::store::{
+    diesel::{models::NewAuditEventModel, schema::audit_event},
+    AuditStoreError,
+};
+use crate::audit::AuditEvent;
+
+use super::AuditStoreOperations;
+
+pub trait AuditStoreAddEvent {
+    fn add_event(&self, event: &AuditEvent) -> Result<(), AuditStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AuditStoreAddEvent for AuditStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn add_event(&self, event: &AuditEvent) -> Result<(), AuditStoreError> {
+        let model = NewAuditEventModel::try_from(event)?;
+
+        insert_into(audit_event::table)
+            .values(&model)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AuditStoreAddEvent for AuditStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_event(&self, event: &AuditEvent) -> Result<(), AuditStoreError> {
+        let model = NewAuditEventModel::try_from(event)?;
+
+        insert_into(audit_event::table)
+            .values(&model)
+            .execute(self.conn)?;
+
+        Ok(())
+    }
+}
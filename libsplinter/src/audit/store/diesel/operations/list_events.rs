@@ -0,0 +1,98 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::audit::store::{
+    diesel::{
+        models::{to_timestamp, AuditEventModel},
+        schema::audit_event,
+    },
+    AuditStoreError,
+};
+use crate::audit::{AuditEntry, AuditFilter};
+
+use super::AuditStoreOperations;
+
+pub trait AuditStoreListEvents {
+    fn list_events(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> AuditStoreListEvents for AuditStoreOperations<'a, diesel::sqlite::SqliteConnection> {
+    fn list_events(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditStoreError> {
+        let mut query = audit_event::table.into_boxed();
+
+        if let Some(event_type) = filter.event_type() {
+            query = query.filter(audit_event::event_type.eq(event_type.as_str()));
+        }
+        if let Some(actor) = filter.actor() {
+            query = query.filter(audit_event::actor.eq(actor.to_string()));
+        }
+        if let Some(target) = filter.target() {
+            query = query.filter(audit_event::target.eq(target.to_string()));
+        }
+        if let Some(since) = filter.since() {
+            query = query.filter(audit_event::timestamp.ge(to_timestamp(since)?));
+        }
+        if let Some(until) = filter.until() {
+            query = query.filter(audit_event::timestamp.lt(to_timestamp(until)?));
+        }
+        if let Some(limit) = filter.limit() {
+            query = query.limit(i64::try_from(limit).unwrap_or(i64::MAX));
+        }
+
+        query
+            .order(audit_event::id.desc())
+            .load::<AuditEventModel>(self.conn)?
+            .into_iter()
+            .map(AuditEntry::try_from)
+            .collect()
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> AuditStoreListEvents for AuditStoreOperations<'a, diesel::pg::PgConnection> {
+    fn list_events(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditStoreError> {
+        let mut query = audit_event::table.into_boxed();
+
+        if let Some(event_type) = filter.event_type() {
+            query = query.filter(audit_event::event_type.eq(event_type.as_str()));
+        }
+        if let Some(actor) = filter.actor() {
+            query = query.filter(audit_event::actor.eq(actor.to_string()));
+        }
+        if let Some(target) = filter.target() {
+            query = query.filter(audit_event::target.eq(target.to_string()));
+        }
+        if let Some(since) = filter.since() {
+            query = query.filter(audit_event::timestamp.ge(to_timestamp(since)?));
+        }
+        if let Some(until) = filter.until() {
+            query = query.filter(audit_event::timestamp.lt(to_timestamp(until)?));
+        }
+        if let Some(limit) = filter.limit() {
+            query = query.limit(i64::try_from(limit).unwrap_or(i64::MAX));
+        }
+
+        query
+            .order(audit_event::id.desc())
+            .load::<AuditEventModel>(self.conn)?
+            .into_iter()
+            .map(AuditEntry::try_from)
+            .collect()
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the store that persists the audit log.
+
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod error;
+
+use super::{AuditEntry, AuditEvent, AuditFilter};
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselAuditStore;
+pub use error::AuditStoreError;
+
+/// Defines methods for persisting the audit log
+///
+/// The audit log is append-only: recorded events cannot be changed or removed.
+pub trait AuditStore: Send + Sync {
+    /// Records an event in the audit log
+    ///
+    /// # Arguments
+    ///
+    ///  * `event` - The event to record
+    fn add_event(&self, event: &AuditEvent) -> Result<(), AuditStoreError>;
+
+    /// Returns the recorded events that match the given filter, most recent first
+    ///
+    /// # Arguments
+    ///
+    ///  * `filter` - Limits the events that are returned
+    fn list_events(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn AuditStore>;
+}
+
+impl Clone for Box<dyn AuditStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<AS> AuditStore for Box<AS>
+where
+    AS: AuditStore + ?Sized,
+{
+    fn add_event(&self, event: &AuditEvent) -> Result<(), AuditStoreError> {
+        (**self).add_event(event)
+    }
+
+    fn list_events(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>, AuditStoreError> {
+        (**self).list_events(filter)
+    }
+
+    fn clone_box(&self) -> Box<dyn AuditStore> {
+        (**self).clone_box()
+    }
+}
@@ -34,6 +34,9 @@ pub mod credentials;
 #[cfg(feature = "biome-key-management")]
 pub mod key_management;
 
+#[cfg(feature = "biome-notifications")]
+pub mod notifications;
+
 #[cfg(feature = "oauth")]
 pub mod oauth;
 
@@ -43,6 +46,9 @@ pub mod profile;
 #[cfg(feature = "biome-credentials")]
 pub mod refresh_tokens;
 
+#[cfg(any(feature = "biome-credentials", feature = "oauth"))]
+pub mod retention;
+
 #[cfg(all(feature = "biome-credentials", feature = "diesel"))]
 pub use credentials::store::diesel::DieselCredentialsStore;
 #[cfg(feature = "biome-credentials")]
@@ -57,6 +63,13 @@ pub use key_management::store::memory::MemoryKeyStore;
 #[cfg(feature = "biome-key-management")]
 pub use key_management::store::KeyStore;
 
+#[cfg(all(feature = "biome-notifications", feature = "diesel"))]
+pub use notifications::store::diesel::DieselNotificationSubscriptionStore;
+#[cfg(feature = "biome-notifications")]
+pub use notifications::store::memory::MemoryNotificationSubscriptionStore;
+#[cfg(feature = "biome-notifications")]
+pub use notifications::store::NotificationSubscriptionStore;
+
 #[cfg(all(feature = "oauth", feature = "diesel"))]
 pub use oauth::store::diesel::DieselOAuthUserSessionStore;
 #[cfg(feature = "oauth")]
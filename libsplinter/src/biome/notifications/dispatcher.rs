@@ -0,0 +1,418 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Delivers notifications for admin service events to subscribed users.
+
+use std::sync::mpsc::{channel, Sender};
+use std::thread;
+
+use crate::admin::service::{AdminServiceEventSubscriber, AdminSubscriberError};
+use crate::admin::store::{AdminServiceEvent, CircuitProposal, EventType};
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::sender::NotificationSender;
+use super::store::NotificationSubscriptionStore;
+use super::{Notification, NotificationEvent};
+
+enum DispatcherMessage {
+    Event(Box<AdminServiceEvent>),
+    Shutdown,
+}
+
+/// Sends notifications to subscribed users as admin service events occur.
+///
+/// Events are received through the [`AdminServiceEventSubscriber`] returned by
+/// [`NotificationDispatcher::subscriber`], and the notifications are delivered on a background
+/// thread so that slow senders do not hold up the admin service.
+pub struct NotificationDispatcher {
+    sender: Sender<DispatcherMessage>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl NotificationDispatcher {
+    /// Starts the dispatcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the local node; proposals need a vote when this node is a member
+    ///     that has not yet voted
+    /// * `subscription_store` - The store that holds the users' subscriptions
+    /// * `senders` - The senders used to deliver notifications; subscriptions to channels without
+    ///     a sender are ignored
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the dispatcher thread could not be started.
+    pub fn start(
+        node_id: &str,
+        subscription_store: Box<dyn NotificationSubscriptionStore>,
+        senders: Vec<Box<dyn NotificationSender>>,
+    ) -> Result<Self, InternalError> {
+        let (sender, receiver) = channel();
+        let node_id = node_id.to_string();
+
+        let join_handle = thread::Builder::new()
+            .name("NotificationDispatcher".into())
+            .spawn(move || loop {
+                match receiver.recv() {
+                    Ok(DispatcherMessage::Event(event)) => {
+                        dispatch(&node_id, &*subscription_store, &senders, &event)
+                    }
+                    Ok(DispatcherMessage::Shutdown) | Err(_) => break,
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            sender,
+            join_handle,
+        })
+    }
+
+    /// Returns a subscriber to be added to the admin service, which passes events on to the
+    /// dispatcher.
+    pub fn subscriber(&self) -> Box<dyn AdminServiceEventSubscriber> {
+        Box::new(DispatcherSubscriber {
+            sender: self.sender.clone(),
+        })
+    }
+}
+
+impl ShutdownHandle for NotificationDispatcher {
+    fn signal_shutdown(&mut self) {
+        // An error means the thread has already stopped
+        let _ = self.sender.send(DispatcherMessage::Shutdown);
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|_| {
+            InternalError::with_message("NotificationDispatcher thread panicked".into())
+        })
+    }
+}
+
+struct DispatcherSubscriber {
+    sender: Sender<DispatcherMessage>,
+}
+
+impl AdminServiceEventSubscriber for DispatcherSubscriber {
+    fn handle_event(
+        &self,
+        admin_service_event: &AdminServiceEvent,
+    ) -> Result<(), AdminSubscriberError> {
+        self.sender
+            .send(DispatcherMessage::Event(Box::new(
+                admin_service_event.clone(),
+            )))
+            .map_err(|_| AdminSubscriberError::Unsubscribe)
+    }
+}
+
+/// Delivers the notification for the event, if any, to each subscribed user.
+fn dispatch(
+    node_id: &str,
+    subscription_store: &dyn NotificationSubscriptionStore,
+    senders: &[Box<dyn NotificationSender>],
+    event: &AdminServiceEvent,
+) {
+    let (notification_event, notification) = match notification_for(node_id, event) {
+        Some(notification) => notification,
+        None => return,
+    };
+
+    let subscriptions = match subscription_store.list_subscribers(notification_event) {
+        Ok(subscriptions) => subscriptions,
+        Err(err) => {
+            error!(
+                "Unable to list subscribers to {} notifications: {}",
+                notification_event, err
+            );
+            return;
+        }
+    };
+
+    for subscription in subscriptions {
+        match senders
+            .iter()
+            .find(|sender| sender.channel() == subscription.channel())
+        {
+            Some(sender) => {
+                if let Err(err) = sender.send(subscription.destination(), &notification) {
+                    warn!(
+                        "Unable to send {} notification to user {} by {}: {}",
+                        notification_event,
+                        subscription.user_id(),
+                        subscription.channel(),
+                        err
+                    );
+                }
+            }
+            None => debug!(
+                "Not sending {} notification to user {}: {} notifications are not enabled",
+                notification_event,
+                subscription.user_id(),
+                subscription.channel()
+            ),
+        }
+    }
+}
+
+/// Returns the notification for the given event, or `None` if users need not be notified.
+fn notification_for(
+    node_id: &str,
+    event: &AdminServiceEvent,
+) -> Option<(NotificationEvent, Notification)> {
+    let proposal = event.proposal();
+    if !is_member(node_id, proposal) {
+        return None;
+    }
+
+    match event.event_type() {
+        EventType::ProposalSubmitted
+            if proposal.requester_node_id() != node_id
+                && !proposal
+                    .votes()
+                    .iter()
+                    .any(|vote| vote.voter_node_id() == node_id) =>
+        {
+            Some((
+                NotificationEvent::ProposalNeedsVote,
+                Notification::new(
+                    format!(
+                        "Circuit proposal {} needs a vote",
+                        describe_circuit(proposal)
+                    ),
+                    format!(
+                        "Node {} has proposed circuit {} with members {}. A vote from node {} \
+                         is required before the circuit can be created.",
+                        proposal.requester_node_id(),
+                        describe_circuit(proposal),
+                        list_members(proposal),
+                        node_id,
+                    ),
+                ),
+            ))
+        }
+        EventType::CircuitReady => Some((
+            NotificationEvent::CircuitReady,
+            Notification::new(
+                format!("Circuit {} is ready", describe_circuit(proposal)),
+                format!(
+                    "Circuit {} has been accepted by all members ({}) and is ready for use.",
+                    describe_circuit(proposal),
+                    list_members(proposal),
+                ),
+            ),
+        )),
+        _ => None,
+    }
+}
+
+fn is_member(node_id: &str, proposal: &CircuitProposal) -> bool {
+    proposal
+        .circuit()
+        .members()
+        .iter()
+        .any(|member| member.node_id() == node_id)
+}
+
+fn describe_circuit(proposal: &CircuitProposal) -> String {
+    match proposal.circuit().display_name() {
+        Some(display_name) => format!("{} ({})", proposal.circuit_id(), display_name),
+        None => proposal.circuit_id().to_string(),
+    }
+}
+
+fn list_members(proposal: &CircuitProposal) -> String {
+    proposal
+        .circuit()
+        .members()
+        .iter()
+        .map(|member| member.node_id())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use crate::admin::store::{
+        AdminServiceEventBuilder, CircuitProposalBuilder, ProposalType, ProposedCircuitBuilder,
+        ProposedNodeBuilder, Vote, VoteRecordBuilder,
+    };
+    use crate::biome::notifications::store::memory::MemoryNotificationSubscriptionStore;
+    use crate::biome::notifications::store::NotificationSubscription;
+    use crate::biome::notifications::NotificationChannel;
+    use crate::public_key::PublicKey;
+
+    /// Verify that a proposal needs a vote only from members other than the requester that have
+    /// not yet voted, and that circuit ready notifications are only created for members.
+    #[test]
+    fn test_notification_for() {
+        let submitted = create_event(EventType::ProposalSubmitted, &[]);
+
+        let (event, notification) =
+            notification_for("bubba-node-000", &submitted).expect("No notification created");
+        assert_eq!(event, NotificationEvent::ProposalNeedsVote);
+        assert_eq!(
+            notification.subject(),
+            "Circuit proposal WBKLF-BBBBB (test_display) needs a vote"
+        );
+
+        assert!(notification_for("acme-node-000", &submitted).is_none());
+        assert!(notification_for("other-node-000", &submitted).is_none());
+        assert!(notification_for(
+            "bubba-node-000",
+            &create_event(EventType::ProposalSubmitted, &["bubba-node-000"])
+        )
+        .is_none());
+
+        let ready = create_event(EventType::CircuitReady, &["bubba-node-000"]);
+        let (event, _) =
+            notification_for("acme-node-000", &ready).expect("No notification created");
+        assert_eq!(event, NotificationEvent::CircuitReady);
+        assert!(notification_for("other-node-000", &ready).is_none());
+    }
+
+    /// Verify that the dispatcher delivers notifications to the subscribers of an event using the
+    /// sender for each subscription's channel, and skips channels without a sender.
+    ///
+    /// 1. Subscribe two users to proposal notifications by email and one by Slack.
+    /// 2. Start a dispatcher with only an email sender and pass it a proposal event.
+    /// 3. Shut down the dispatcher and verify that only the email subscribers were notified.
+    #[test]
+    fn test_dispatcher() {
+        let store = MemoryNotificationSubscriptionStore::new();
+        for (user_id, channel, destination) in &[
+            ("user1", NotificationChannel::Email, "user1@example.com"),
+            ("user2", NotificationChannel::Email, "user2@example.com"),
+            (
+                "user2",
+                NotificationChannel::Slack,
+                "https://hooks.slack.com/x",
+            ),
+        ] {
+            store
+                .add_subscription(NotificationSubscription::new(
+                    user_id,
+                    NotificationEvent::ProposalNeedsVote,
+                    *channel,
+                    destination,
+                ))
+                .expect("Unable to add subscription");
+        }
+
+        let sent = Arc::new(Mutex::new(vec![]));
+        let mut dispatcher = NotificationDispatcher::start(
+            "bubba-node-000",
+            Box::new(store),
+            vec![Box::new(MockSender { sent: sent.clone() })],
+        )
+        .expect("Unable to start dispatcher");
+
+        dispatcher
+            .subscriber()
+            .handle_event(&create_event(EventType::ProposalSubmitted, &[]))
+            .expect("Unable to handle event");
+
+        dispatcher.signal_shutdown();
+        dispatcher
+            .wait_for_shutdown()
+            .expect("Unable to shut down dispatcher");
+
+        assert_eq!(
+            *sent.lock().expect("lock poisoned"),
+            vec![
+                "user1@example.com".to_string(),
+                "user2@example.com".to_string()
+            ]
+        );
+    }
+
+    struct MockSender {
+        sent: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl NotificationSender for MockSender {
+        fn channel(&self) -> NotificationChannel {
+            NotificationChannel::Email
+        }
+
+        fn send(&self, destination: &str, _: &Notification) -> Result<(), InternalError> {
+            self.sent
+                .lock()
+                .expect("lock poisoned")
+                .push(destination.to_string());
+            Ok(())
+        }
+    }
+
+    /// Creates an event for a proposal from acme-node-000 to create a circuit with bubba-node-000,
+    /// with votes from the given nodes.
+    fn create_event(event_type: EventType, voters: &[&str]) -> AdminServiceEvent {
+        let members = ["acme-node-000", "bubba-node-000"]
+            .iter()
+            .map(|node_id| {
+                ProposedNodeBuilder::default()
+                    .with_node_id(node_id)
+                    .with_endpoints(&[format!("tcps://{}:8044", node_id)])
+                    .build()
+                    .expect("Unable to build node")
+            })
+            .collect::<Vec<_>>();
+
+        let votes = voters
+            .iter()
+            .map(|voter| {
+                VoteRecordBuilder::new()
+                    .with_public_key(&PublicKey::from_bytes(voter.as_bytes().to_vec()))
+                    .with_vote(&Vote::Accept)
+                    .with_voter_node_id(voter)
+                    .build()
+                    .expect("Unable to build vote record")
+            })
+            .collect::<Vec<_>>();
+
+        let proposal = CircuitProposalBuilder::default()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_circuit_hash("7ddc4269")
+            .with_circuit(
+                &ProposedCircuitBuilder::default()
+                    .with_circuit_id("WBKLF-BBBBB")
+                    .with_roster(&[])
+                    .with_members(&members)
+                    .with_circuit_management_type("test")
+                    .with_display_name("test_display")
+                    .build()
+                    .expect("Unable to build circuit"),
+            )
+            .with_requester(&PublicKey::from_bytes(b"acme".to_vec()))
+            .with_requester_node_id("acme-node-000")
+            .with_votes(&votes)
+            .build()
+            .expect("Unable to build proposal");
+
+        AdminServiceEventBuilder::new()
+            .with_event_id(1)
+            .with_event_type(&event_type)
+            .with_proposal(&proposal)
+            .build()
+            .expect("Unable to build event")
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Biome functionality to notify users of admin service events.
+//!
+//! Users subscribe to a [`NotificationEvent`] on a [`NotificationChannel`], such as email or a
+//! Slack webhook. The subscriptions are kept in a
+//! [`NotificationSubscriptionStore`](store::NotificationSubscriptionStore). The
+//! [`NotificationDispatcher`] receives admin service events and uses the configured
+//! [`NotificationSender`](sender::NotificationSender)s to deliver a notification to each
+//! subscribed user.
+
+mod dispatcher;
+#[cfg(feature = "rest-api-actix-web-1")]
+pub mod rest_api;
+pub mod sender;
+pub mod store;
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::InvalidArgumentError;
+
+pub use dispatcher::NotificationDispatcher;
+
+/// The events a user may be notified of.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NotificationEvent {
+    /// A circuit proposal has been submitted that requires a vote from this node.
+    ProposalNeedsVote,
+    /// A circuit that includes this node has been committed and is ready for use.
+    CircuitReady,
+}
+
+impl NotificationEvent {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationEvent::ProposalNeedsVote => "proposal_needs_vote",
+            NotificationEvent::CircuitReady => "circuit_ready",
+        }
+    }
+}
+
+impl fmt::Display for NotificationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NotificationEvent {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "proposal_needs_vote" => Ok(NotificationEvent::ProposalNeedsVote),
+            "circuit_ready" => Ok(NotificationEvent::CircuitReady),
+            _ => Err(InvalidArgumentError::new(
+                "event".to_string(),
+                format!("unknown notification event: {}", s),
+            )),
+        }
+    }
+}
+
+/// The channels a notification may be delivered on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NotificationChannel {
+    /// Delivered by email; the destination is an email address.
+    Email,
+    /// Delivered to a Slack incoming webhook; the destination is the webhook URL.
+    Slack,
+}
+
+impl NotificationChannel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::Email => "email",
+            NotificationChannel::Slack => "slack",
+        }
+    }
+}
+
+impl fmt::Display for NotificationChannel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NotificationChannel {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "email" => Ok(NotificationChannel::Email),
+            "slack" => Ok(NotificationChannel::Slack),
+            _ => Err(InvalidArgumentError::new(
+                "channel".to_string(),
+                format!("unknown notification channel: {}", s),
+            )),
+        }
+    }
+}
+
+/// A notification to be delivered to a user.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Notification {
+    subject: String,
+    body: String,
+}
+
+impl Notification {
+    pub fn new(subject: String, body: String) -> Self {
+        Self { subject, body }
+    }
+
+    /// Returns the single-line summary of the notification
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    /// Returns the full text of the notification
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
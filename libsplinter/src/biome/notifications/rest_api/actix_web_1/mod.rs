@@ -0,0 +1,46 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod subscriptions;
+
+use std::sync::Arc;
+
+use crate::biome::notifications::store::NotificationSubscriptionStore;
+use crate::rest_api::{Resource, RestResourceProvider};
+
+/// Provides the following REST API endpoints for Biome notification subscriptions:
+///
+/// * `GET /biome/notifications/subscriptions` - List the authenticated user's subscriptions
+/// * `POST /biome/notifications/subscriptions` - Add or replace a subscription for the
+///   authenticated user
+/// * `DELETE /biome/notifications/subscriptions/{event}/{channel}` - Remove a subscription of the
+///   authenticated user
+pub struct BiomeNotificationsRestResourceProvider {
+    subscription_store: Arc<dyn NotificationSubscriptionStore>,
+}
+
+impl BiomeNotificationsRestResourceProvider {
+    pub fn new(subscription_store: Arc<dyn NotificationSubscriptionStore>) -> Self {
+        Self { subscription_store }
+    }
+}
+
+impl RestResourceProvider for BiomeNotificationsRestResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        vec![
+            subscriptions::make_subscriptions_route(self.subscription_store.clone()),
+            subscriptions::make_subscription_route(self.subscription_store.clone()),
+        ]
+    }
+}
@@ -0,0 +1,366 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use actix_web::HttpResponse;
+use futures::{Future, IntoFuture};
+
+use crate::biome::notifications::sender::SLACK_WEBHOOK_URL_PREFIX;
+use crate::biome::notifications::store::{
+    NotificationSubscription, NotificationSubscriptionStore, NotificationSubscriptionStoreError,
+};
+use crate::biome::notifications::{NotificationChannel, NotificationEvent};
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::authorization::Permission;
+use crate::rest_api::{
+    actix_web_1::{into_bytes, HandlerFunction, Method, ProtocolVersionRangeGuard, Resource},
+    auth::identity::Identity,
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+const BIOME_NOTIFICATIONS_PROTOCOL_MIN: u32 = 2;
+
+#[derive(Deserialize)]
+struct SubscriptionPayload {
+    event: String,
+    channel: String,
+    destination: String,
+}
+
+#[derive(Serialize)]
+struct SubscriptionResponse<'a> {
+    event: &'a str,
+    channel: &'a str,
+    destination: &'a str,
+}
+
+impl<'a> From<&'a NotificationSubscription> for SubscriptionResponse<'a> {
+    fn from(subscription: &'a NotificationSubscription) -> Self {
+        Self {
+            event: subscription.event().as_str(),
+            channel: subscription.channel().as_str(),
+            destination: subscription.destination(),
+        }
+    }
+}
+
+/// Defines a REST endpoint for listing and adding the authenticated user's subscriptions
+pub fn make_subscriptions_route(
+    subscription_store: Arc<dyn NotificationSubscriptionStore>,
+) -> Resource {
+    let resource = Resource::build("/biome/notifications/subscriptions").add_request_guard(
+        ProtocolVersionRangeGuard::new(BIOME_NOTIFICATIONS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(
+                Method::Get,
+                Permission::AllowAuthenticated,
+                handle_get(subscription_store.clone()),
+            )
+            .add_method(
+                Method::Post,
+                Permission::AllowAuthenticated,
+                handle_post(subscription_store),
+            )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, handle_get(subscription_store.clone()))
+            .add_method(Method::Post, handle_post(subscription_store))
+    }
+}
+
+/// Defines a REST endpoint for removing one of the authenticated user's subscriptions
+pub fn make_subscription_route(
+    subscription_store: Arc<dyn NotificationSubscriptionStore>,
+) -> Resource {
+    let resource = Resource::build("/biome/notifications/subscriptions/{event}/{channel}")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            BIOME_NOTIFICATIONS_PROTOCOL_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Delete,
+            Permission::AllowAuthenticated,
+            handle_delete(subscription_store),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Delete, handle_delete(subscription_store))
+    }
+}
+
+/// Defines a REST endpoint method to list the authenticated user's subscriptions
+fn handle_get(subscription_store: Arc<dyn NotificationSubscriptionStore>) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user = match request.extensions().get::<Identity>() {
+            Some(Identity::User(user)) => user.clone(),
+            _ => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_future(),
+                )
+            }
+        };
+
+        match subscription_store.list_subscriptions(&user) {
+            Ok(subscriptions) => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({
+                        "data": subscriptions
+                            .iter()
+                            .map(SubscriptionResponse::from)
+                            .collect::<Vec<_>>()
+                    }))
+                    .into_future(),
+            ),
+            Err(err) => {
+                error!("Failed to list notification subscriptions: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Defines a REST endpoint method to add or replace a subscription of the authenticated user
+fn handle_post(subscription_store: Arc<dyn NotificationSubscriptionStore>) -> HandlerFunction {
+    Box::new(move |request, payload| {
+        let subscription_store = subscription_store.clone();
+
+        let user = match request.extensions().get::<Identity>() {
+            Some(Identity::User(user)) => user.clone(),
+            _ => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_future(),
+                )
+            }
+        };
+
+        Box::new(into_bytes(payload).and_then(move |bytes| {
+            let payload = match serde_json::from_slice::<SubscriptionPayload>(&bytes) {
+                Ok(val) => val,
+                Err(err) => {
+                    debug!("Error parsing payload {}", err);
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future();
+                }
+            };
+
+            let subscription = match to_subscription(&user, payload) {
+                Ok(subscription) => subscription,
+                Err(msg) => {
+                    return HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&msg))
+                        .into_future()
+                }
+            };
+
+            match subscription_store.add_subscription(subscription.clone()) {
+                Ok(()) => HttpResponse::Ok()
+                    .json(json!({
+                        "message": "Subscription added successfully",
+                        "data": SubscriptionResponse::from(&subscription)
+                    }))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to add notification subscription: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            }
+        }))
+    })
+}
+
+/// Defines a REST endpoint method to remove a subscription of the authenticated user
+fn handle_delete(subscription_store: Arc<dyn NotificationSubscriptionStore>) -> HandlerFunction {
+    Box::new(move |request, _| {
+        let user = match request.extensions().get::<Identity>() {
+            Some(Identity::User(user)) => user.clone(),
+            _ => {
+                return Box::new(
+                    HttpResponse::Unauthorized()
+                        .json(ErrorResponse::unauthorized())
+                        .into_future(),
+                )
+            }
+        };
+
+        let event = match request
+            .match_info()
+            .get("event")
+            .unwrap_or("")
+            .parse::<NotificationEvent>()
+        {
+            Ok(event) => event,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&err.to_string()))
+                        .into_future(),
+                )
+            }
+        };
+
+        let channel = match request
+            .match_info()
+            .get("channel")
+            .unwrap_or("")
+            .parse::<NotificationChannel>()
+        {
+            Ok(channel) => channel,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&err.to_string()))
+                        .into_future(),
+                )
+            }
+        };
+
+        match subscription_store.remove_subscription(&user, event, channel) {
+            Ok(()) => Box::new(
+                HttpResponse::Ok()
+                    .json(json!({ "message": "Subscription removed successfully" }))
+                    .into_future(),
+            ),
+            Err(NotificationSubscriptionStoreError::InvalidState(err)) => Box::new(
+                HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found(&err.to_string()))
+                    .into_future(),
+            ),
+            Err(err) => {
+                error!("Failed to remove notification subscription: {}", err);
+                Box::new(
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future(),
+                )
+            }
+        }
+    })
+}
+
+/// Converts the payload to a subscription for the given user, checking that the destination is
+/// valid for the channel.
+fn to_subscription(
+    user_id: &str,
+    payload: SubscriptionPayload,
+) -> Result<NotificationSubscription, String> {
+    let event = payload
+        .event
+        .parse::<NotificationEvent>()
+        .map_err(|err| err.to_string())?;
+    let channel = payload
+        .channel
+        .parse::<NotificationChannel>()
+        .map_err(|err| err.to_string())?;
+
+    let valid_destination = match channel {
+        NotificationChannel::Email => {
+            let mut parts = payload.destination.splitn(2, '@');
+            matches!(
+                (parts.next(), parts.next()),
+                (Some(local), Some(domain)) if !local.is_empty() && !domain.is_empty()
+            )
+        }
+        NotificationChannel::Slack => payload.destination.starts_with(SLACK_WEBHOOK_URL_PREFIX),
+    };
+    if !valid_destination {
+        return Err(match channel {
+            NotificationChannel::Email => {
+                format!("'{}' is not an email address", payload.destination)
+            }
+            NotificationChannel::Slack => format!(
+                "'{}' is not a Slack webhook URL; it must start with {}",
+                payload.destination, SLACK_WEBHOOK_URL_PREFIX
+            ),
+        });
+    }
+
+    Ok(NotificationSubscription::new(
+        user_id,
+        event,
+        channel,
+        &payload.destination,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(event: &str, channel: &str, destination: &str) -> SubscriptionPayload {
+        SubscriptionPayload {
+            event: event.into(),
+            channel: channel.into(),
+            destination: destination.into(),
+        }
+    }
+
+    /// Verify that payloads are converted to subscriptions only when the event and channel are
+    /// known and the destination is valid for the channel.
+    #[test]
+    fn test_to_subscription() {
+        assert_eq!(
+            to_subscription(
+                "user",
+                payload("proposal_needs_vote", "email", "user@example.com")
+            ),
+            Ok(NotificationSubscription::new(
+                "user",
+                NotificationEvent::ProposalNeedsVote,
+                NotificationChannel::Email,
+                "user@example.com"
+            ))
+        );
+        assert!(to_subscription(
+            "user",
+            payload(
+                "circuit_ready",
+                "slack",
+                "https://hooks.slack.com/services/x"
+            )
+        )
+        .is_ok());
+
+        assert!(to_subscription("user", payload("unknown", "email", "user@example.com")).is_err());
+        assert!(to_subscription("user", payload("circuit_ready", "sms", "555-0100")).is_err());
+        assert!(to_subscription("user", payload("circuit_ready", "email", "user")).is_err());
+        assert!(to_subscription(
+            "user",
+            payload("circuit_ready", "slack", "http://localhost:8080/")
+        )
+        .is_err());
+    }
+}
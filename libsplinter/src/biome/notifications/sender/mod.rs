@@ -0,0 +1,46 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Senders that deliver notifications on a [`NotificationChannel`].
+
+#[cfg(feature = "biome-notifications-slack")]
+mod slack;
+#[cfg(feature = "biome-notifications-smtp")]
+mod smtp;
+
+use crate::error::InternalError;
+
+use super::{Notification, NotificationChannel};
+
+#[cfg(feature = "biome-notifications-slack")]
+pub use slack::SlackWebhookSender;
+#[cfg(feature = "biome-notifications-smtp")]
+pub use smtp::SmtpSender;
+
+/// The URL prefix shared by all Slack incoming webhooks.
+pub const SLACK_WEBHOOK_URL_PREFIX: &str = "https://hooks.slack.com/";
+
+/// Delivers notifications on a single channel.
+pub trait NotificationSender: Send {
+    /// Returns the channel this sender delivers notifications on.
+    fn channel(&self) -> NotificationChannel;
+
+    /// Delivers the notification to the given destination, such as an email address or a webhook
+    /// URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the notification could not be delivered.
+    fn send(&self, destination: &str, notification: &Notification) -> Result<(), InternalError>;
+}
@@ -0,0 +1,62 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A [`NotificationSender`] that posts notifications to Slack incoming webhooks.
+
+use reqwest::blocking::Client;
+
+use crate::biome::notifications::{Notification, NotificationChannel};
+use crate::error::InternalError;
+
+use super::{NotificationSender, SLACK_WEBHOOK_URL_PREFIX};
+
+/// Posts notifications to the Slack incoming webhook given as the destination.
+///
+/// Only destinations that are Slack webhook URLs are accepted, so that a subscription cannot be
+/// used to make the node send requests to arbitrary URLs.
+#[derive(Default)]
+pub struct SlackWebhookSender {
+    client: Client,
+}
+
+impl SlackWebhookSender {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NotificationSender for SlackWebhookSender {
+    fn channel(&self) -> NotificationChannel {
+        NotificationChannel::Slack
+    }
+
+    fn send(&self, destination: &str, notification: &Notification) -> Result<(), InternalError> {
+        if !destination.starts_with(SLACK_WEBHOOK_URL_PREFIX) {
+            return Err(InternalError::with_message(format!(
+                "Slack notification destination is not a Slack webhook URL: {}",
+                destination
+            )));
+        }
+
+        self.client
+            .post(destination)
+            .json(&serde_json::json!({
+                "text": format!("*{}*\n{}", notification.subject(), notification.body())
+            }))
+            .send()
+            .and_then(|response| response.error_for_status())
+            .map(|_| ())
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
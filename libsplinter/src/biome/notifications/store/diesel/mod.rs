@@ -0,0 +1,310 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [NotificationSubscriptionStore], powered by [diesel].
+
+pub(in crate::biome) mod models;
+mod operations;
+pub(in crate::biome) mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::biome::notifications::{NotificationChannel, NotificationEvent};
+use crate::store::pool::ConnectionPool;
+
+use super::{
+    NotificationSubscription, NotificationSubscriptionStore, NotificationSubscriptionStoreError,
+};
+
+use operations::{
+    add_subscription::NotificationSubscriptionStoreAddSubscription as _,
+    list_subscriptions::NotificationSubscriptionStoreListSubscriptions as _,
+    remove_subscription::NotificationSubscriptionStoreRemoveSubscription as _,
+    NotificationSubscriptionStoreOperations,
+};
+
+/// Manages adding, removing, and listing notification subscriptions in the database
+pub struct DieselNotificationSubscriptionStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselNotificationSubscriptionStore<C> {
+    /// Creates a new DieselNotificationSubscriptionStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselNotificationSubscriptionStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselNotificationSubscriptionStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl NotificationSubscriptionStore
+    for DieselNotificationSubscriptionStore<diesel::pg::PgConnection>
+{
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection).add_subscription(subscription)
+        })
+    }
+
+    fn remove_subscription(
+        &self,
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection)
+                .remove_subscription(user_id, event, channel)
+        })
+    }
+
+    fn list_subscriptions(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection).list_subscriptions(user_id)
+        })
+    }
+
+    fn list_subscribers(
+        &self,
+        event: NotificationEvent,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection).list_subscribers(event)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn NotificationSubscriptionStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl NotificationSubscriptionStore
+    for DieselNotificationSubscriptionStore<diesel::sqlite::SqliteConnection>
+{
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection).add_subscription(subscription)
+        })
+    }
+
+    fn remove_subscription(
+        &self,
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection)
+                .remove_subscription(user_id, event, channel)
+        })
+    }
+
+    fn list_subscriptions(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection).list_subscriptions(user_id)
+        })
+    }
+
+    fn list_subscribers(
+        &self,
+        event: NotificationEvent,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            NotificationSubscriptionStoreOperations::new(connection).list_subscribers(event)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn NotificationSubscriptionStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a SQLite-backed `DieselNotificationSubscriptionStore` correctly supports
+    /// adding and listing subscriptions.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Add subscriptions for two users.
+    /// 3. Verify that each user's subscriptions are listed, and that subscribers are listed by
+    ///    event.
+    /// 4. Add a subscription for an existing user, event and channel and verify that it replaces
+    ///    the existing subscription.
+    #[test]
+    fn sqlite_add_and_list_subscriptions() {
+        let store = DieselNotificationSubscriptionStore::new(create_connection_pool_and_migrate());
+
+        let email = NotificationSubscription::new(
+            "user1",
+            NotificationEvent::ProposalNeedsVote,
+            NotificationChannel::Email,
+            "user1@example.com",
+        );
+        let slack = NotificationSubscription::new(
+            "user1",
+            NotificationEvent::CircuitReady,
+            NotificationChannel::Slack,
+            "https://hooks.slack.com/services/user1",
+        );
+        let other = NotificationSubscription::new(
+            "user2",
+            NotificationEvent::ProposalNeedsVote,
+            NotificationChannel::Email,
+            "user2@example.com",
+        );
+
+        store
+            .add_subscription(email.clone())
+            .expect("Failed to add email subscription");
+        store
+            .add_subscription(slack.clone())
+            .expect("Failed to add slack subscription");
+        store
+            .add_subscription(other.clone())
+            .expect("Failed to add other subscription");
+
+        assert_eq!(
+            store
+                .list_subscriptions("user1")
+                .expect("Failed to list subscriptions"),
+            vec![slack.clone(), email.clone()]
+        );
+        assert_eq!(
+            store
+                .list_subscribers(NotificationEvent::ProposalNeedsVote)
+                .expect("Failed to list subscribers"),
+            vec![email, other.clone()]
+        );
+
+        let updated = NotificationSubscription::new(
+            "user1",
+            NotificationEvent::ProposalNeedsVote,
+            NotificationChannel::Email,
+            "updated@example.com",
+        );
+        store
+            .add_subscription(updated.clone())
+            .expect("Failed to replace subscription");
+
+        assert_eq!(
+            store
+                .list_subscribers(NotificationEvent::ProposalNeedsVote)
+                .expect("Failed to list subscribers"),
+            vec![updated, other]
+        );
+    }
+
+    /// Verify that a SQLite-backed `DieselNotificationSubscriptionStore` correctly supports
+    /// removing subscriptions.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Add a subscription, remove it, and verify that it is no longer listed.
+    /// 3. Verify that removing the subscription again returns an `InvalidState` error.
+    #[test]
+    fn sqlite_remove_subscription() {
+        let store = DieselNotificationSubscriptionStore::new(create_connection_pool_and_migrate());
+
+        store
+            .add_subscription(NotificationSubscription::new(
+                "user1",
+                NotificationEvent::CircuitReady,
+                NotificationChannel::Email,
+                "user1@example.com",
+            ))
+            .expect("Failed to add subscription");
+
+        store
+            .remove_subscription(
+                "user1",
+                NotificationEvent::CircuitReady,
+                NotificationChannel::Email,
+            )
+            .expect("Failed to remove subscription");
+        assert!(store
+            .list_subscriptions("user1")
+            .expect("Failed to list subscriptions")
+            .is_empty());
+
+        match store.remove_subscription(
+            "user1",
+            NotificationEvent::CircuitReady,
+            NotificationChannel::Email,
+        ) {
+            Err(NotificationSubscriptionStoreError::InvalidState(_)) => (),
+            res => panic!("Expected Err(InvalidState), got {:?} instead", res),
+        }
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,62 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use crate::biome::notifications::store::{
+    NotificationSubscription, NotificationSubscriptionStoreError,
+};
+use crate::error::InternalError;
+
+use super::schema::biome_notification_subscriptions;
+
+#[derive(Insertable, Queryable, Identifiable, PartialEq, Eq, Debug)]
+#[table_name = "biome_notification_subscriptions"]
+#[primary_key(user_id, event_type, channel_type)]
+pub struct NotificationSubscriptionModel {
+    pub user_id: String,
+    pub event_type: String,
+    pub channel_type: String,
+    pub destination: String,
+}
+
+impl From<NotificationSubscription> for NotificationSubscriptionModel {
+    fn from(subscription: NotificationSubscription) -> Self {
+        NotificationSubscriptionModel {
+            user_id: subscription.user_id,
+            event_type: subscription.event.as_str().to_string(),
+            channel_type: subscription.channel.as_str().to_string(),
+            destination: subscription.destination,
+        }
+    }
+}
+
+impl TryFrom<NotificationSubscriptionModel> for NotificationSubscription {
+    type Error = NotificationSubscriptionStoreError;
+
+    fn try_from(model: NotificationSubscriptionModel) -> Result<Self, Self::Error> {
+        Ok(NotificationSubscription {
+            user_id: model.user_id,
+            event: model
+                .event_type
+                .parse()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            channel: model
+                .channel_type
+                .parse()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            destination: model.destination,
+        })
+    }
+}
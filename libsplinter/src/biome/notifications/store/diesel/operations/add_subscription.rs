@@ -0,0 +1,100 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+};
+
+use crate::biome::notifications::store::{
+    diesel::{models::NotificationSubscriptionModel, schema::biome_notification_subscriptions},
+    NotificationSubscription, NotificationSubscriptionStoreError,
+};
+
+use super::NotificationSubscriptionStoreOperations;
+
+pub trait NotificationSubscriptionStoreAddSubscription {
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> NotificationSubscriptionStoreAddSubscription
+    for NotificationSubscriptionStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        let model = NotificationSubscriptionModel::from(subscription);
+
+        self.conn
+            .transaction::<_, NotificationSubscriptionStoreError, _>(|| {
+                delete(
+                    biome_notification_subscriptions::table.filter(
+                        biome_notification_subscriptions::user_id
+                            .eq(&model.user_id)
+                            .and(biome_notification_subscriptions::event_type.eq(&model.event_type))
+                            .and(
+                                biome_notification_subscriptions::channel_type
+                                    .eq(&model.channel_type),
+                            ),
+                    ),
+                )
+                .execute(self.conn)?;
+
+                insert_into(biome_notification_subscriptions::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+
+                Ok(())
+            })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> NotificationSubscriptionStoreAddSubscription
+    for NotificationSubscriptionStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        let model = NotificationSubscriptionModel::from(subscription);
+
+        self.conn
+            .transaction::<_, NotificationSubscriptionStoreError, _>(|| {
+                delete(
+                    biome_notification_subscriptions::table.filter(
+                        biome_notification_subscriptions::user_id
+                            .eq(&model.user_id)
+                            .and(biome_notification_subscriptions::event_type.eq(&model.event_type))
+                            .and(
+                                biome_notification_subscriptions::channel_type
+                                    .eq(&model.channel_type),
+                            ),
+                    ),
+                )
+                .execute(self.conn)?;
+
+                insert_into(biome_notification_subscriptions::table)
+                    .values(&model)
+                    .execute(self.conn)?;
+
+                Ok(())
+            })
+    }
+}
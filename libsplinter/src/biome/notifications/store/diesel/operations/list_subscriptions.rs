@@ -0,0 +1,76 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::biome::notifications::store::{
+    diesel::{models::NotificationSubscriptionModel, schema::biome_notification_subscriptions},
+    NotificationSubscription, NotificationSubscriptionStoreError,
+};
+use crate::biome::notifications::NotificationEvent;
+
+use super::NotificationSubscriptionStoreOperations;
+
+pub trait NotificationSubscriptionStoreListSubscriptions {
+    fn list_subscriptions(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError>;
+
+    fn list_subscribers(
+        &self,
+        event: NotificationEvent,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError>;
+}
+
+impl<'a, C> NotificationSubscriptionStoreListSubscriptions
+    for NotificationSubscriptionStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn list_subscriptions(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        biome_notification_subscriptions::table
+            .filter(biome_notification_subscriptions::user_id.eq(user_id))
+            .order((
+                biome_notification_subscriptions::event_type,
+                biome_notification_subscriptions::channel_type,
+            ))
+            .load::<NotificationSubscriptionModel>(self.conn)?
+            .into_iter()
+            .map(NotificationSubscription::try_from)
+            .collect()
+    }
+
+    fn list_subscribers(
+        &self,
+        event: NotificationEvent,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        biome_notification_subscriptions::table
+            .filter(biome_notification_subscriptions::event_type.eq(event.as_str()))
+            .order((
+                biome_notification_subscriptions::user_id,
+                biome_notification_subscriptions::channel_type,
+            ))
+            .load::<NotificationSubscriptionModel>(self.conn)?
+            .into_iter()
+            .map(NotificationSubscription::try_from)
+            .collect()
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::biome::notifications::store::{
+    diesel::schema::biome_notification_subscriptions, NotificationSubscriptionStoreError,
+};
+use crate::biome::notifications::{NotificationChannel, NotificationEvent};
+use crate::error::InvalidStateError;
+
+use super::NotificationSubscriptionStoreOperations;
+
+pub trait NotificationSubscriptionStoreRemoveSubscription {
+    fn remove_subscription(
+        &self,
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+    ) -> Result<(), NotificationSubscriptionStoreError>;
+}
+
+impl<'a, C> NotificationSubscriptionStoreRemoveSubscription
+    for NotificationSubscriptionStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    fn remove_subscription(
+        &self,
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        let removed = delete(
+            biome_notification_subscriptions::table.filter(
+                biome_notification_subscriptions::user_id
+                    .eq(user_id)
+                    .and(biome_notification_subscriptions::event_type.eq(event.as_str()))
+                    .and(biome_notification_subscriptions::channel_type.eq(channel.as_str())),
+            ),
+        )
+        .execute(self.conn)?;
+
+        if removed == 0 {
+            return Err(NotificationSubscriptionStoreError::InvalidState(
+                InvalidStateError::with_message(format!(
+                    "User {} is not subscribed to {} notifications by {}",
+                    user_id, event, channel
+                )),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,135 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A memory-backed implementation of the [NotificationSubscriptionStore]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::biome::notifications::{NotificationChannel, NotificationEvent};
+use crate::error::{InternalError, InvalidStateError};
+
+use super::{
+    NotificationSubscription, NotificationSubscriptionStore, NotificationSubscriptionStoreError,
+};
+
+type SubscriptionKey = (String, NotificationEvent, NotificationChannel);
+
+#[derive(Default, Clone)]
+pub struct MemoryNotificationSubscriptionStore {
+    inner: Arc<Mutex<HashMap<SubscriptionKey, NotificationSubscription>>>,
+}
+
+impl MemoryNotificationSubscriptionStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn list_matching<F>(
+        &self,
+        predicate: F,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError>
+    where
+        F: Fn(&NotificationSubscription) -> bool,
+    {
+        let inner = self.inner.lock().map_err(|_| {
+            NotificationSubscriptionStoreError::Internal(InternalError::with_message(
+                "Cannot access notification subscription store: mutex lock poisoned".to_string(),
+            ))
+        })?;
+
+        let mut subscriptions = inner
+            .values()
+            .filter(|subscription| predicate(subscription))
+            .cloned()
+            .collect::<Vec<_>>();
+        subscriptions.sort_by(|a, b| {
+            (a.user_id(), a.event().as_str(), a.channel().as_str()).cmp(&(
+                b.user_id(),
+                b.event().as_str(),
+                b.channel().as_str(),
+            ))
+        });
+
+        Ok(subscriptions)
+    }
+}
+
+impl NotificationSubscriptionStore for MemoryNotificationSubscriptionStore {
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        let mut inner = self.inner.lock().map_err(|_| {
+            NotificationSubscriptionStoreError::Internal(InternalError::with_message(
+                "Cannot access notification subscription store: mutex lock poisoned".to_string(),
+            ))
+        })?;
+
+        inner.insert(
+            (
+                subscription.user_id().to_string(),
+                subscription.event(),
+                subscription.channel(),
+            ),
+            subscription,
+        );
+        Ok(())
+    }
+
+    fn remove_subscription(
+        &self,
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        let mut inner = self.inner.lock().map_err(|_| {
+            NotificationSubscriptionStoreError::Internal(InternalError::with_message(
+                "Cannot access notification subscription store: mutex lock poisoned".to_string(),
+            ))
+        })?;
+
+        if inner
+            .remove(&(user_id.to_string(), event, channel))
+            .is_some()
+        {
+            Ok(())
+        } else {
+            Err(NotificationSubscriptionStoreError::InvalidState(
+                InvalidStateError::with_message(format!(
+                    "User {} is not subscribed to {} notifications by {}",
+                    user_id, event, channel
+                )),
+            ))
+        }
+    }
+
+    fn list_subscriptions(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        self.list_matching(|subscription| subscription.user_id() == user_id)
+    }
+
+    fn list_subscribers(
+        &self,
+        event: NotificationEvent,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        self.list_matching(|subscription| subscription.event() == event)
+    }
+
+    fn clone_box(&self) -> Box<dyn NotificationSubscriptionStore> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,179 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the subscriptions users have to notifications, and the store that holds them.
+
+#[cfg(feature = "diesel")]
+pub(in crate::biome) mod diesel;
+pub mod error;
+pub(in crate::biome) mod memory;
+
+use super::{NotificationChannel, NotificationEvent};
+
+pub use error::NotificationSubscriptionStoreError;
+
+/// A user's subscription to an event on a notification channel
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NotificationSubscription {
+    user_id: String,
+    event: NotificationEvent,
+    channel: NotificationChannel,
+    destination: String,
+}
+
+impl NotificationSubscription {
+    /// Creates a new subscription
+    ///
+    /// # Arguments
+    ///
+    /// * `user_id`: The identity of the Biome user who is subscribing.
+    /// * `event`: The event the user will be notified of.
+    /// * `channel`: The channel the notification will be delivered on.
+    /// * `destination`: Where the notification is delivered on the channel, such as an email
+    ///     address or a webhook URL.
+    pub fn new(
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+        destination: &str,
+    ) -> Self {
+        Self {
+            user_id: user_id.to_string(),
+            event,
+            channel,
+            destination: destination.to_string(),
+        }
+    }
+
+    /// Returns the user ID of the subscriber
+    pub fn user_id(&self) -> &str {
+        &self.user_id
+    }
+
+    /// Returns the event subscribed to
+    pub fn event(&self) -> NotificationEvent {
+        self.event
+    }
+
+    /// Returns the channel notifications are delivered on
+    pub fn channel(&self) -> NotificationChannel {
+        self.channel
+    }
+
+    /// Returns where notifications are delivered on the channel
+    pub fn destination(&self) -> &str {
+        &self.destination
+    }
+}
+
+/// Defines methods for managing users' notification subscriptions
+pub trait NotificationSubscriptionStore: Send + Sync {
+    /// Adds a subscription to the underlying storage, replacing the user's existing subscription
+    /// to the same event on the same channel.
+    ///
+    /// # Arguments
+    ///
+    ///  * `subscription` - The subscription to be added
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError>;
+
+    /// Removes a subscription from the underlying storage.
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The user the subscription belongs to
+    ///  * `event` - The event subscribed to
+    ///  * `channel` - The channel notifications are delivered on
+    ///
+    /// # Errors
+    ///
+    /// Returns a `NotificationSubscriptionStoreError::InvalidState` if the subscription does
+    /// not exist.
+    fn remove_subscription(
+        &self,
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+    ) -> Result<(), NotificationSubscriptionStoreError>;
+
+    /// Lists the subscriptions of the given user.
+    ///
+    /// # Arguments
+    ///
+    ///  * `user_id` - The user whose subscriptions are listed
+    fn list_subscriptions(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError>;
+
+    /// Lists all subscriptions to the given event.
+    ///
+    /// # Arguments
+    ///
+    ///  * `event` - The event whose subscriptions are listed
+    fn list_subscribers(
+        &self,
+        event: NotificationEvent,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn NotificationSubscriptionStore>;
+}
+
+impl Clone for Box<dyn NotificationSubscriptionStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<NS> NotificationSubscriptionStore for Box<NS>
+where
+    NS: NotificationSubscriptionStore + ?Sized,
+{
+    fn add_subscription(
+        &self,
+        subscription: NotificationSubscription,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        (**self).add_subscription(subscription)
+    }
+
+    fn remove_subscription(
+        &self,
+        user_id: &str,
+        event: NotificationEvent,
+        channel: NotificationChannel,
+    ) -> Result<(), NotificationSubscriptionStoreError> {
+        (**self).remove_subscription(user_id, event, channel)
+    }
+
+    fn list_subscriptions(
+        &self,
+        user_id: &str,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        (**self).list_subscriptions(user_id)
+    }
+
+    fn list_subscribers(
+        &self,
+        event: NotificationEvent,
+    ) -> Result<Vec<NotificationSubscription>, NotificationSubscriptionStoreError> {
+        (**self).list_subscribers(event)
+    }
+
+    fn clone_box(&self) -> Box<dyn NotificationSubscriptionStore> {
+        (**self).clone_box()
+    }
+}
@@ -19,6 +19,7 @@ mod operations;
 pub(in crate::biome) mod schema;
 
 use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
@@ -34,6 +35,7 @@ use operations::{
     get_session::OAuthUserSessionStoreGetSession as _, get_user::OAuthUserSessionStoreGetUser as _,
     list_users::OAuthUserSessionStoreListUsers as _,
     remove_session::OAuthUserSessionStoreRemoveSession as _,
+    remove_sessions_authenticated_before::OAuthUserSessionStoreRemoveSessionsAuthenticatedBefore as _,
     update_session::OAuthUserSessionStoreUpdateSession as _, OAuthUserSessionStoreOperations,
 };
 
@@ -95,6 +97,16 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::sqlite::Sqlit
         })
     }
 
+    fn remove_sessions_authenticated_before(
+        &self,
+        before: SystemTime,
+    ) -> Result<usize, OAuthUserSessionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            OAuthUserSessionStoreOperations::new(connection)
+                .remove_sessions_authenticated_before(before)
+        })
+    }
+
     fn get_session(
         &self,
         splinter_access_token: &str,
@@ -152,6 +164,16 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::pg::PgConnect
         })
     }
 
+    fn remove_sessions_authenticated_before(
+        &self,
+        before: SystemTime,
+    ) -> Result<usize, OAuthUserSessionStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            OAuthUserSessionStoreOperations::new(connection)
+                .remove_sessions_authenticated_before(before)
+        })
+    }
+
     fn get_session(
         &self,
         splinter_access_token: &str,
@@ -184,6 +206,8 @@ impl OAuthUserSessionStore for DieselOAuthUserSessionStore<diesel::pg::PgConnect
 pub mod tests {
     use super::*;
 
+    use std::time::Duration;
+
     use crate::biome::oauth::store::InsertableOAuthUserSessionBuilder;
     use crate::migrations::run_sqlite_migrations;
 
@@ -376,6 +400,62 @@ pub mod tests {
         ));
     }
 
+    /// Verify that a SQLite-backed `DieselOAuthUserSessionStore` correctly supports removing
+    /// sessions that were last authenticated before a given time.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create a `DieselOAuthUserSessionStore`.
+    /// 3. Add an OAuth user session.
+    /// 4. Verify that the session is not removed when the given time is before the session was
+    ///    authenticated.
+    /// 5. Verify that the session is removed when the given time is after the session was
+    ///    authenticated, and that the session's user is kept.
+    #[test]
+    fn sqlite_remove_sessions_authenticated_before() {
+        let pool = create_connection_pool_and_migrate();
+
+        let oauth_user_session_store = DieselOAuthUserSessionStore::new(pool);
+
+        let splinter_access_token = "splinter_access_token";
+        let session = InsertableOAuthUserSessionBuilder::new()
+            .with_splinter_access_token(splinter_access_token.into())
+            .with_subject("subject".into())
+            .with_oauth_access_token("oauth_access_token".into())
+            .build()
+            .expect("Unable to build session");
+        oauth_user_session_store
+            .add_session(session)
+            .expect("Unable to add session");
+
+        let now = SystemTime::now();
+
+        assert_eq!(
+            oauth_user_session_store
+                .remove_sessions_authenticated_before(now - Duration::from_secs(60))
+                .expect("Unable to remove sessions"),
+            0
+        );
+        assert!(oauth_user_session_store
+            .get_session(splinter_access_token)
+            .expect("Unable to attempt to get session")
+            .is_some());
+
+        assert_eq!(
+            oauth_user_session_store
+                .remove_sessions_authenticated_before(now + Duration::from_secs(60))
+                .expect("Unable to remove sessions"),
+            1
+        );
+        assert!(oauth_user_session_store
+            .get_session(splinter_access_token)
+            .expect("Unable to attempt to get session")
+            .is_none());
+        assert!(oauth_user_session_store
+            .get_user("subject")
+            .expect("Unable to get user")
+            .is_some());
+    }
+
     /// Verify that a SQLite-backed `DieselOAuthUserSessionStore` correctly supports inserting and
     /// and getting OAuth users.
     ///
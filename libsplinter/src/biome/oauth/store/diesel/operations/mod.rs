@@ -20,6 +20,7 @@ pub(super) mod get_session;
 pub(super) mod get_user;
 pub(super) mod list_users;
 pub(super) mod remove_session;
+pub(super) mod remove_sessions_authenticated_before;
 pub(super) mod update_session;
 
 pub(super) struct OAuthUserSessionStoreOperations<'a, C> {
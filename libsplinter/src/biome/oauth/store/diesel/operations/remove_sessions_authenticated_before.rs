@@ -0,0 +1,58 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::biome::oauth::store::{diesel::schema::oauth_user_sessions, OAuthUserSessionStoreError};
+use crate::error::InternalError;
+
+use super::OAuthUserSessionStoreOperations;
+
+pub trait OAuthUserSessionStoreRemoveSessionsAuthenticatedBefore {
+    fn remove_sessions_authenticated_before(
+        &self,
+        before: SystemTime,
+    ) -> Result<usize, OAuthUserSessionStoreError>;
+}
+
+impl<'a, C> OAuthUserSessionStoreRemoveSessionsAuthenticatedBefore
+    for OAuthUserSessionStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn remove_sessions_authenticated_before(
+        &self,
+        before: SystemTime,
+    ) -> Result<usize, OAuthUserSessionStoreError> {
+        let before = before
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| {
+                OAuthUserSessionStoreError::Internal(InternalError::from_source(Box::new(err)))
+            })
+            .and_then(|duration| {
+                i64::try_from(duration.as_secs()).map_err(|err| {
+                    OAuthUserSessionStoreError::Internal(InternalError::from_source(Box::new(err)))
+                })
+            })?;
+
+        delete(oauth_user_sessions::table)
+            .filter(oauth_user_sessions::last_authenticated.lt(before))
+            .execute(self.conn)
+            .map_err(OAuthUserSessionStoreError::from)
+    }
+}
@@ -132,6 +132,23 @@ impl OAuthUserSessionStore for MemoryOAuthUserSessionStore {
             })
     }
 
+    fn remove_sessions_authenticated_before(
+        &self,
+        before: SystemTime,
+    ) -> Result<usize, OAuthUserSessionStoreError> {
+        let mut internal = self.internal.lock().map_err(|_| {
+            OAuthUserSessionStoreError::Internal(InternalError::with_message(
+                "Cannot access OAuth user session store: mutex lock poisoned".to_string(),
+            ))
+        })?;
+
+        let initial_len = internal.sessions.len();
+        internal
+            .sessions
+            .retain(|_, session| session.last_authenticated >= before);
+        Ok(initial_len - internal.sessions.len())
+    }
+
     fn get_session(
         &self,
         splinter_access_token: &str,
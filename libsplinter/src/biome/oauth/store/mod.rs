@@ -380,6 +380,16 @@ pub trait OAuthUserSessionStore: Send + Sync {
     fn remove_session(&self, splinter_access_token: &str)
         -> Result<(), OAuthUserSessionStoreError>;
 
+    /// Removes all OAuth sessions that were last authenticated before the given time, returning the
+    /// number of sessions removed.
+    ///
+    /// Removing a session also removes the OAuth access and refresh tokens cached for it. OAuth
+    /// users are not removed, so a user that logs in again keeps the same Biome user ID.
+    fn remove_sessions_authenticated_before(
+        &self,
+        before: SystemTime,
+    ) -> Result<usize, OAuthUserSessionStoreError>;
+
     /// Returns the OAuth session for the provided Splinter access token if it exists
     fn get_session(
         &self,
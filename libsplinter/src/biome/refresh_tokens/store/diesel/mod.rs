@@ -17,6 +17,7 @@ mod operations;
 mod schema;
 
 use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
 
 use diesel::r2d2::{ConnectionManager, Pool};
 
@@ -27,6 +28,7 @@ use operations::{
     add_token::RefreshTokenStoreAddTokenOperation,
     fetch_token::RefreshTokenStoreFetchTokenOperation,
     remove_token::RefreshTokenStoreRemoveTokenOperation,
+    remove_tokens_updated_before::RefreshTokenStoreRemoveTokensUpdatedBeforeOperation,
     update_token::RefreshTokenStoreUpdateTokenOperation, RefreshTokenStoreOperations,
 };
 
@@ -77,6 +79,11 @@ impl RefreshTokenStore for DieselRefreshTokenStore<diesel::pg::PgConnection> {
         self.connection_pool
             .execute_read(|conn| RefreshTokenStoreOperations::new(conn).fetch_token(user_id))
     }
+    fn remove_tokens_updated_before(&self, before: SystemTime) -> Result<usize, RefreshTokenError> {
+        self.connection_pool.execute_write(|conn| {
+            RefreshTokenStoreOperations::new(conn).remove_tokens_updated_before(before)
+        })
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -98,12 +105,19 @@ impl RefreshTokenStore for DieselRefreshTokenStore<diesel::sqlite::SqliteConnect
         self.connection_pool
             .execute_read(|conn| RefreshTokenStoreOperations::new(conn).fetch_token(user_id))
     }
+    fn remove_tokens_updated_before(&self, before: SystemTime) -> Result<usize, RefreshTokenError> {
+        self.connection_pool.execute_write(|conn| {
+            RefreshTokenStoreOperations::new(conn).remove_tokens_updated_before(before)
+        })
+    }
 }
 
 #[cfg(all(test, feature = "sqlite"))]
 pub mod tests {
     use super::*;
 
+    use std::time::Duration;
+
     use crate::migrations::run_sqlite_migrations;
 
     use diesel::{
@@ -221,6 +235,55 @@ pub mod tests {
         }
     }
 
+    /// Verify that a SQLite-backed `DieselRefreshTokenStore` correctly supports removing tokens
+    /// that were last updated before a given time.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Create the `DieselRefreshTokenStore`.
+    /// 3. Add some tokens.
+    /// 4. Verify that no tokens are removed when the given time is before the tokens were added.
+    /// 5. Verify that all tokens are removed when the given time is after the tokens were added.
+    #[test]
+    fn sqlite_remove_tokens_updated_before() {
+        let pool = create_connection_pool_and_migrate();
+
+        let store = DieselRefreshTokenStore::new(pool);
+
+        store
+            .add_token("user1", "token1")
+            .expect("Failed to add token1");
+        store
+            .add_token("user2", "token2")
+            .expect("Failed to add token2");
+
+        let now = SystemTime::now();
+
+        assert_eq!(
+            store
+                .remove_tokens_updated_before(now - Duration::from_secs(60))
+                .expect("Failed to remove tokens"),
+            0
+        );
+        assert_eq!(
+            store.fetch_token("user1").expect("Failed to fetch token1"),
+            "token1",
+        );
+
+        assert_eq!(
+            store
+                .remove_tokens_updated_before(now + Duration::from_secs(60))
+                .expect("Failed to remove tokens"),
+            2
+        );
+        match store.fetch_token("user1") {
+            Err(RefreshTokenError::NotFoundError(_)) => {}
+            res => panic!(
+                "Expected Err(RefreshTokenError::NotFoundError), got {:?} instead",
+                res
+            ),
+        }
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
@@ -21,6 +21,7 @@ pub struct RefreshToken {
     pub id: i64,
     pub user_id: String,
     pub token: String,
+    pub last_updated: i64,
 }
 
 #[derive(AsChangeset, Insertable, PartialEq, Eq, Debug)]
@@ -28,4 +29,5 @@ pub struct RefreshToken {
 pub struct NewRefreshToken<'a> {
     pub user_id: &'a str,
     pub token: &'a str,
+    pub last_updated: i64,
 }
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::RefreshTokenStoreOperations;
+use std::time::SystemTime;
+
+use super::{to_timestamp, RefreshTokenStoreOperations};
 use crate::biome::refresh_tokens::store::{
     diesel::{models::NewRefreshToken, schema::refresh_tokens},
     RefreshTokenError,
@@ -29,7 +31,11 @@ impl<'a> RefreshTokenStoreAddTokenOperation
 {
     fn add_token(&self, user_id: &str, token: &str) -> Result<(), RefreshTokenError> {
         insert_into(refresh_tokens::table)
-            .values(NewRefreshToken { user_id, token })
+            .values(NewRefreshToken {
+                user_id,
+                token,
+                last_updated: to_timestamp(SystemTime::now())?,
+            })
             .execute(self.conn)
             .map_err(|err| RefreshTokenError::OperationError {
                 context: "Failed to create token".to_string(),
@@ -45,7 +51,11 @@ impl<'a> RefreshTokenStoreAddTokenOperation
 {
     fn add_token(&self, user_id: &str, token: &str) -> Result<(), RefreshTokenError> {
         insert_into(refresh_tokens::table)
-            .values(NewRefreshToken { user_id, token })
+            .values(NewRefreshToken {
+                user_id,
+                token,
+                last_updated: to_timestamp(SystemTime::now())?,
+            })
             .execute(self.conn)
             .map_err(|err| RefreshTokenError::OperationError {
                 context: "Failed to create token".to_string(),
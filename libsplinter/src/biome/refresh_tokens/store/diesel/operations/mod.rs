@@ -15,8 +15,15 @@
 pub(super) mod add_token;
 pub(super) mod fetch_token;
 pub(super) mod remove_token;
+pub(super) mod remove_tokens_updated_before;
 pub(super) mod update_token;
 
+use std::convert::TryFrom;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::biome::refresh_tokens::store::RefreshTokenError;
+use crate::error::InternalError;
+
 pub(super) struct RefreshTokenStoreOperations<'a, C> {
     conn: &'a C,
 }
@@ -29,3 +36,15 @@ where
         RefreshTokenStoreOperations { conn }
     }
 }
+
+/// Converts the given time to the number of seconds since the Unix epoch, as stored in the
+/// `last_updated` column.
+fn to_timestamp(time: SystemTime) -> Result<i64, RefreshTokenError> {
+    time.duration_since(UNIX_EPOCH)
+        .map_err(|err| RefreshTokenError::InternalError(InternalError::from_source(Box::new(err))))
+        .and_then(|duration| {
+            i64::try_from(duration.as_secs()).map_err(|err| {
+                RefreshTokenError::InternalError(InternalError::from_source(Box::new(err)))
+            })
+        })
+}
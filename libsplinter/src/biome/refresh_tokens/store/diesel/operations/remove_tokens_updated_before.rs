@@ -0,0 +1,40 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::SystemTime;
+
+use super::{to_timestamp, RefreshTokenStoreOperations};
+use crate::biome::refresh_tokens::store::{diesel::schema::refresh_tokens, RefreshTokenError};
+use diesel::{dsl::delete, prelude::*};
+
+pub(in crate::biome) trait RefreshTokenStoreRemoveTokensUpdatedBeforeOperation {
+    fn remove_tokens_updated_before(&self, before: SystemTime) -> Result<usize, RefreshTokenError>;
+}
+
+impl<'a, C> RefreshTokenStoreRemoveTokensUpdatedBeforeOperation
+    for RefreshTokenStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::serialize::ToSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn remove_tokens_updated_before(&self, before: SystemTime) -> Result<usize, RefreshTokenError> {
+        delete(refresh_tokens::table)
+            .filter(refresh_tokens::last_updated.lt(to_timestamp(before)?))
+            .execute(self.conn)
+            .map_err(|err| RefreshTokenError::OperationError {
+                context: "Failed to remove expired refresh tokens".to_string(),
+                source: Box::new(err),
+            })
+    }
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::RefreshTokenStoreOperations;
+use std::time::SystemTime;
+
+use super::{to_timestamp, RefreshTokenStoreOperations};
 use crate::biome::refresh_tokens::store::{
     diesel::{models::NewRefreshToken, schema::refresh_tokens},
     RefreshTokenError,
@@ -33,7 +35,11 @@ where
     fn update_token(&self, user_id: &str, token: &str) -> Result<(), RefreshTokenError> {
         update(refresh_tokens::table)
             .filter(refresh_tokens::user_id.eq(&user_id))
-            .set(NewRefreshToken { user_id, token })
+            .set(NewRefreshToken {
+                user_id,
+                token,
+                last_updated: to_timestamp(SystemTime::now())?,
+            })
             .execute(self.conn)
             .map_err(|err| {
                 if err == NotFound {
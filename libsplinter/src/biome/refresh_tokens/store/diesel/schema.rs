@@ -17,5 +17,6 @@ table! {
         id -> Int8,
         user_id -> Text,
         token -> Text,
+        last_updated -> Int8,
     }
 }
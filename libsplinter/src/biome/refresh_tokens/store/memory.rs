@@ -14,12 +14,14 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
 use crate::biome::refresh_tokens::store::{error::RefreshTokenError, RefreshTokenStore};
 
 #[derive(Default, Clone)]
 pub struct MemoryRefreshTokenStore {
-    inner: Arc<Mutex<HashMap<String, String>>>,
+    // Maps user IDs to their token and the time the token was last added or updated
+    inner: Arc<Mutex<HashMap<String, (String, SystemTime)>>>,
 }
 
 impl MemoryRefreshTokenStore {
@@ -39,7 +41,7 @@ impl RefreshTokenStore for MemoryRefreshTokenStore {
                 context: "Cannot access refresh token store: mutex lock poisoned".to_string(),
                 source: None,
             })?;
-        inner.insert(user_id.to_string(), token.to_string());
+        inner.insert(user_id.to_string(), (token.to_string(), SystemTime::now()));
         Ok(())
     }
 
@@ -72,7 +74,7 @@ impl RefreshTokenStore for MemoryRefreshTokenStore {
             })?;
 
         if inner.contains_key(user_id) {
-            inner.insert(user_id.to_string(), token.to_string());
+            inner.insert(user_id.to_string(), (token.to_string(), SystemTime::now()));
             Ok(())
         } else {
             Err(RefreshTokenError::NotFoundError(format!(
@@ -91,7 +93,7 @@ impl RefreshTokenStore for MemoryRefreshTokenStore {
                 source: None,
             })?;
 
-        if let Some(token) = inner.get(user_id) {
+        if let Some((token, _)) = inner.get(user_id) {
             Ok(token.to_string())
         } else {
             Err(RefreshTokenError::NotFoundError(format!(
@@ -100,4 +102,18 @@ impl RefreshTokenStore for MemoryRefreshTokenStore {
             )))
         }
     }
+
+    fn remove_tokens_updated_before(&self, before: SystemTime) -> Result<usize, RefreshTokenError> {
+        let mut inner = self
+            .inner
+            .lock()
+            .map_err(|_| RefreshTokenError::StorageError {
+                context: "Cannot access refresh token store: mutex lock poisoned".to_string(),
+                source: None,
+            })?;
+
+        let initial_len = inner.len();
+        inner.retain(|_, (_, last_updated)| *last_updated >= before);
+        Ok(initial_len - inner.len())
+    }
 }
@@ -17,6 +17,8 @@ pub(crate) mod diesel;
 mod error;
 pub(in crate::biome) mod memory;
 
+use std::time::SystemTime;
+
 pub use error::RefreshTokenError;
 
 /// Defines methods for CRUD operations for handling refresh tokens
@@ -50,6 +52,14 @@ pub trait RefreshTokenStore: Send + Sync {
     ///
     ///   * `user_id` - The user whom which the token is for
     fn fetch_token(&self, user_id: &str) -> Result<String, RefreshTokenError>;
+
+    /// Removes all tokens that were last added or updated before the given time, returning the
+    /// number of tokens removed
+    ///
+    /// # Arguments
+    ///
+    ///   * `before` - Tokens last added or updated before this time are removed
+    fn remove_tokens_updated_before(&self, before: SystemTime) -> Result<usize, RefreshTokenError>;
 }
 
 impl<RTS> RefreshTokenStore for Box<RTS>
@@ -71,4 +81,8 @@ where
     fn fetch_token(&self, user_id: &str) -> Result<String, RefreshTokenError> {
         (**self).fetch_token(user_id)
     }
+
+    fn remove_tokens_updated_before(&self, before: SystemTime) -> Result<usize, RefreshTokenError> {
+        (**self).remove_tokens_updated_before(before)
+    }
 }
@@ -0,0 +1,225 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Periodic removal of Biome data that is no longer needed.
+//!
+//! Refresh tokens and OAuth sessions are added to their stores on every login, but are only
+//! removed when a user explicitly logs out. The [`BiomeRetentionPolicy`] removes those that have
+//! not been used within a configured retention period, and the [`BiomeRetentionScheduler`]
+//! applies the policy on an interval.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+#[cfg(feature = "oauth")]
+use super::OAuthUserSessionStore;
+#[cfg(feature = "biome-credentials")]
+use super::RefreshTokenStore;
+
+/// Defines how long Biome data is retained after it was last used.
+#[derive(Default)]
+pub struct BiomeRetentionPolicy {
+    #[cfg(feature = "biome-credentials")]
+    refresh_tokens: Option<(Box<dyn RefreshTokenStore>, Duration)>,
+    #[cfg(feature = "oauth")]
+    oauth_sessions: Option<(Box<dyn OAuthUserSessionStore>, Duration)>,
+}
+
+impl BiomeRetentionPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Removes refresh tokens that have not been added or updated within the given retention
+    /// period.
+    #[cfg(feature = "biome-credentials")]
+    pub fn with_refresh_token_retention(
+        mut self,
+        store: Box<dyn RefreshTokenStore>,
+        retention: Duration,
+    ) -> Self {
+        self.refresh_tokens = Some((store, retention));
+        self
+    }
+
+    /// Removes OAuth sessions, along with their cached OAuth tokens, that have not been
+    /// authenticated within the given retention period.
+    #[cfg(feature = "oauth")]
+    pub fn with_oauth_session_retention(
+        mut self,
+        store: Box<dyn OAuthUserSessionStore>,
+        retention: Duration,
+    ) -> Self {
+        self.oauth_sessions = Some((store, retention));
+        self
+    }
+
+    /// Removes all data that is older than its retention period.
+    pub fn apply(&self) -> Result<(), InternalError> {
+        #[cfg(feature = "biome-credentials")]
+        if let Some((store, retention)) = &self.refresh_tokens {
+            let removed = store
+                .remove_tokens_updated_before(retention_cutoff(*retention)?)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            if removed > 0 {
+                debug!("Removed {} expired refresh tokens", removed);
+            }
+        }
+
+        #[cfg(feature = "oauth")]
+        if let Some((store, retention)) = &self.oauth_sessions {
+            let removed = store
+                .remove_sessions_authenticated_before(retention_cutoff(*retention)?)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            if removed > 0 {
+                debug!("Removed {} inactive OAuth sessions", removed);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the time before which data is outside of the given retention period.
+fn retention_cutoff(retention: Duration) -> Result<SystemTime, InternalError> {
+    SystemTime::now().checked_sub(retention).ok_or_else(|| {
+        InternalError::with_message(format!(
+            "Retention period of {:?} cannot be represented as a time",
+            retention
+        ))
+    })
+}
+
+/// Applies a [`BiomeRetentionPolicy`] on a background thread.
+///
+/// The policy is applied when the scheduler is started, and then once per interval until the
+/// scheduler is shut down.
+pub struct BiomeRetentionScheduler {
+    shutdown_sender: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl BiomeRetentionScheduler {
+    /// Starts applying the given policy on the given interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the scheduler thread could not be started.
+    pub fn start(policy: BiomeRetentionPolicy, interval: Duration) -> Result<Self, InternalError> {
+        let (shutdown_sender, shutdown_receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("BiomeRetentionScheduler".into())
+            .spawn(move || loop {
+                if let Err(err) = policy.apply() {
+                    error!("Unable to remove expired Biome data: {}", err);
+                }
+
+                match shutdown_receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => continue,
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            shutdown_sender,
+            join_handle,
+        })
+    }
+}
+
+impl ShutdownHandle for BiomeRetentionScheduler {
+    fn signal_shutdown(&mut self) {
+        // An error means the thread has already stopped
+        let _ = self.shutdown_sender.send(());
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|_| {
+            InternalError::with_message("BiomeRetentionScheduler thread panicked".into())
+        })
+    }
+}
+
+#[cfg(all(test, feature = "biome-credentials", feature = "oauth"))]
+mod tests {
+    use super::*;
+
+    use crate::biome::oauth::store::InsertableOAuthUserSessionBuilder;
+    use crate::biome::{MemoryOAuthUserSessionStore, MemoryRefreshTokenStore};
+
+    /// Verify that applying a retention policy removes only the data that is older than its
+    /// retention period.
+    ///
+    /// 1. Add a refresh token and an OAuth session to memory-backed stores.
+    /// 2. Apply a policy with long retention periods and verify that nothing is removed.
+    /// 3. Apply a policy with no retention and verify that the token and session are removed.
+    #[test]
+    fn test_apply_retention_policy() {
+        let refresh_token_store = MemoryRefreshTokenStore::new();
+        refresh_token_store
+            .add_token("user", "token")
+            .expect("Failed to add token");
+
+        let oauth_user_session_store = MemoryOAuthUserSessionStore::new();
+        oauth_user_session_store
+            .add_session(
+                InsertableOAuthUserSessionBuilder::new()
+                    .with_splinter_access_token("splinter_access_token".into())
+                    .with_subject("subject".into())
+                    .with_oauth_access_token("oauth_access_token".into())
+                    .build()
+                    .expect("Unable to build session"),
+            )
+            .expect("Unable to add session");
+
+        let one_day = Duration::from_secs(86_400);
+        BiomeRetentionPolicy::new()
+            .with_refresh_token_retention(Box::new(refresh_token_store.clone()), one_day)
+            .with_oauth_session_retention(oauth_user_session_store.clone_box(), one_day)
+            .apply()
+            .expect("Failed to apply policy");
+
+        assert!(refresh_token_store.fetch_token("user").is_ok());
+        assert!(oauth_user_session_store
+            .get_session("splinter_access_token")
+            .expect("Unable to get session")
+            .is_some());
+
+        thread::sleep(Duration::from_millis(10));
+
+        BiomeRetentionPolicy::new()
+            .with_refresh_token_retention(
+                Box::new(refresh_token_store.clone()),
+                Duration::from_secs(0),
+            )
+            .with_oauth_session_retention(
+                oauth_user_session_store.clone_box(),
+                Duration::from_secs(0),
+            )
+            .apply()
+            .expect("Failed to apply policy");
+
+        assert!(refresh_token_store.fetch_token("user").is_err());
+        assert!(oauth_user_session_store
+            .get_session("splinter_access_token")
+            .expect("Unable to get session")
+            .is_none());
+    }
+}
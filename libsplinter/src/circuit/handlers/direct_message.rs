@@ -14,6 +14,8 @@
 
 use crate::circuit::handlers::create_message;
 use crate::circuit::routing::{RoutingTableReader, ServiceId as RoutingServiceId};
+#[cfg(feature = "circuit-usage")]
+use crate::circuit::usage::CircuitUsageTracker;
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::peer::PeerTokenPair;
 use crate::protos::circuit::{
@@ -36,6 +38,8 @@ pub struct CircuitDirectMessageHandler {
     routing_table: Box<dyn RoutingTableReader>,
     #[cfg(feature = "service-message-handler-dispatch")]
     service_dispatcher: ServiceDispatcher,
+    #[cfg(feature = "circuit-usage")]
+    usage_tracker: Option<CircuitUsageTracker>,
 }
 
 impl Handler for CircuitDirectMessageHandler {
@@ -93,6 +97,9 @@ impl Handler for CircuitDirectMessageHandler {
                         .map_err(|e| InternalError::from_source(Box::new(e)))?,
                 );
 
+                #[cfg(feature = "circuit-usage")]
+                self.record_usage(circuit_name, msg.get_payload().len());
+
                 let mut msg = msg;
                 self.service_dispatcher
                     .dispatch(to_service, from_service, msg.take_payload())?;
@@ -141,6 +148,9 @@ impl Handler for CircuitDirectMessageHandler {
                         .get_service(&recipient_id)
                         .map_err(|err| DispatchError::HandleError(err.to_string()))?
                     {
+                        #[cfg(feature = "circuit-usage")]
+                        self.record_usage(circuit_name, msg.get_payload().len());
+
                         let node_id = service.node_id().to_string();
                         let msg_bytes = context.message_bytes().to_vec();
                         let network_msg_bytes =
@@ -267,6 +277,22 @@ impl CircuitDirectMessageHandler {
             routing_table,
             #[cfg(feature = "service-message-handler-dispatch")]
             service_dispatcher,
+            #[cfg(feature = "circuit-usage")]
+            usage_tracker: None,
+        }
+    }
+
+    /// Records the messages forwarded or dispatched by this handler in the given tracker.
+    #[cfg(feature = "circuit-usage")]
+    pub fn with_usage_tracker(mut self, usage_tracker: CircuitUsageTracker) -> Self {
+        self.usage_tracker = Some(usage_tracker);
+        self
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    fn record_usage(&self, circuit_id: &str, payload_len: usize) {
+        if let Some(usage_tracker) = &self.usage_tracker {
+            usage_tracker.record_message(circuit_id, payload_len);
         }
     }
 }
@@ -42,5 +42,7 @@ fn create_message(
     let mut network_msg = NetworkMessage::new();
     network_msg.set_message_type(NetworkMessageType::CIRCUIT);
     network_msg.set_payload(circuit_bytes);
+    #[cfg(feature = "otel-tracing")]
+    crate::otel::inject_context(&mut network_msg);
     network_msg.write_to_bytes()
 }
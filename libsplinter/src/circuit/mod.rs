@@ -18,3 +18,5 @@ pub mod handlers;
 pub mod routing;
 #[cfg(feature = "circuit-template")]
 pub mod template;
+#[cfg(feature = "circuit-usage")]
+pub mod usage;
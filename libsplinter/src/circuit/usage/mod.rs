@@ -0,0 +1,331 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Accounting of the messages handled by this node on each circuit.
+//!
+//! The [`CircuitUsageTracker`] counts the direct messages, and the bytes of their payloads, that
+//! this node handles for each circuit. The [`CircuitUsagePersister`] periodically adds the counts
+//! recorded since it last ran to a [`CircuitUsageStore`](store::CircuitUsageStore), so that the
+//! totals survive a restart.
+//!
+//! A [`CircuitUsageQuota`] may be configured on the tracker. Quotas are soft: messages are never
+//! rejected, but a warning is logged the first time a circuit exceeds the quota, and the usage is
+//! reported as over quota.
+
+mod persister;
+pub mod store;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+pub use persister::CircuitUsagePersister;
+
+/// The number of messages, and the total size of their payloads, handled on a circuit.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CircuitUsage {
+    circuit_id: String,
+    message_count: u64,
+    byte_count: u64,
+}
+
+impl CircuitUsage {
+    pub fn new(circuit_id: &str, message_count: u64, byte_count: u64) -> Self {
+        Self {
+            circuit_id: circuit_id.to_string(),
+            message_count,
+            byte_count,
+        }
+    }
+
+    /// Returns the ID of the circuit the usage was recorded on
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    /// Returns the number of messages handled on the circuit
+    pub fn message_count(&self) -> u64 {
+        self.message_count
+    }
+
+    /// Returns the total number of payload bytes handled on the circuit
+    pub fn byte_count(&self) -> u64 {
+        self.byte_count
+    }
+
+    fn add(&mut self, message_count: u64, byte_count: u64) {
+        self.message_count = self.message_count.saturating_add(message_count);
+        self.byte_count = self.byte_count.saturating_add(byte_count);
+    }
+}
+
+/// Soft limits on the usage of each circuit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CircuitUsageQuota {
+    max_messages: Option<u64>,
+    max_bytes: Option<u64>,
+}
+
+impl CircuitUsageQuota {
+    /// Creates a quota with no limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the number of messages that may be handled on each circuit.
+    pub fn with_max_messages(mut self, max_messages: u64) -> Self {
+        self.max_messages = Some(max_messages);
+        self
+    }
+
+    /// Limits the total number of payload bytes that may be handled on each circuit.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    pub fn max_messages(&self) -> Option<u64> {
+        self.max_messages
+    }
+
+    pub fn max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+
+    /// Returns true if the given usage is over either limit of the quota.
+    pub fn is_exceeded_by(&self, usage: &CircuitUsage) -> bool {
+        self.max_messages
+            .map(|max| usage.message_count > max)
+            .unwrap_or(false)
+            || self
+                .max_bytes
+                .map(|max| usage.byte_count > max)
+                .unwrap_or(false)
+    }
+}
+
+#[derive(Default)]
+struct TrackerState {
+    // The total usage of each circuit, including usage that has already been persisted
+    totals: HashMap<String, CircuitUsage>,
+    // The usage recorded since it was last taken for persistence
+    unpersisted: HashMap<String, CircuitUsage>,
+    // The circuits that have been reported as over quota
+    over_quota: HashSet<String>,
+}
+
+/// Records the usage of each circuit in memory.
+///
+/// The tracker is cheap to clone; all clones share the same usage.
+#[derive(Clone, Default)]
+pub struct CircuitUsageTracker {
+    quota: CircuitUsageQuota,
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl CircuitUsageTracker {
+    /// Creates a tracker that reports usage over the given quota.
+    pub fn new(quota: CircuitUsageQuota) -> Self {
+        Self {
+            quota,
+            state: Arc::default(),
+        }
+    }
+
+    /// Returns the quota the tracker reports usage against
+    pub fn quota(&self) -> &CircuitUsageQuota {
+        &self.quota
+    }
+
+    /// Sets the totals of the given circuits, such as those previously persisted, without marking
+    /// them as needing to be persisted.
+    pub fn restore(&self, usage: Vec<CircuitUsage>) {
+        let mut state = mutex_lock_unwrap!(self.state);
+        for usage in usage {
+            if self.quota.is_exceeded_by(&usage) {
+                state.over_quota.insert(usage.circuit_id.clone());
+            }
+            state.totals.insert(usage.circuit_id.clone(), usage);
+        }
+    }
+
+    /// Records that a message with a payload of the given size was handled on a circuit.
+    ///
+    /// A warning is logged the first time the circuit's usage exceeds the quota.
+    pub fn record_message(&self, circuit_id: &str, payload_len: usize) {
+        let byte_count = payload_len as u64;
+        let mut guard = mutex_lock_unwrap!(self.state);
+        let state = &mut *guard;
+
+        state
+            .unpersisted
+            .entry(circuit_id.to_string())
+            .or_insert_with(|| CircuitUsage::new(circuit_id, 0, 0))
+            .add(1, byte_count);
+
+        let total = state
+            .totals
+            .entry(circuit_id.to_string())
+            .or_insert_with(|| CircuitUsage::new(circuit_id, 0, 0));
+        total.add(1, byte_count);
+
+        if self.quota.is_exceeded_by(total) && !state.over_quota.contains(circuit_id) {
+            warn!(
+                "Circuit {} has exceeded its usage quota: {} messages, {} bytes \
+                (quota: {} messages, {} bytes)",
+                circuit_id,
+                total.message_count,
+                total.byte_count,
+                display_limit(self.quota.max_messages),
+                display_limit(self.quota.max_bytes),
+            );
+            state.over_quota.insert(circuit_id.to_string());
+        }
+    }
+
+    /// Returns the total usage of a circuit, if any has been recorded.
+    pub fn get(&self, circuit_id: &str) -> Option<CircuitUsage> {
+        mutex_lock_unwrap!(self.state)
+            .totals
+            .get(circuit_id)
+            .cloned()
+    }
+
+    /// Returns the total usage of every circuit, sorted by circuit ID.
+    pub fn list(&self) -> Vec<CircuitUsage> {
+        let mut usage = mutex_lock_unwrap!(self.state)
+            .totals
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        usage.sort_by(|a, b| a.circuit_id.cmp(&b.circuit_id));
+        usage
+    }
+
+    /// Returns true if the circuit's usage has exceeded the quota.
+    pub fn is_over_quota(&self, circuit_id: &str) -> bool {
+        mutex_lock_unwrap!(self.state)
+            .over_quota
+            .contains(circuit_id)
+    }
+
+    /// Removes and returns the usage recorded since this was last called.
+    fn take_unpersisted(&self) -> Vec<CircuitUsage> {
+        mutex_lock_unwrap!(self.state)
+            .unpersisted
+            .drain()
+            .map(|(_, usage)| usage)
+            .collect()
+    }
+
+    /// Returns usage taken by `take_unpersisted` that could not be persisted, so that it is
+    /// included the next time usage is taken.
+    fn return_unpersisted(&self, usage: CircuitUsage) {
+        mutex_lock_unwrap!(self.state)
+            .unpersisted
+            .entry(usage.circuit_id.clone())
+            .or_insert_with(|| CircuitUsage::new(&usage.circuit_id, 0, 0))
+            .add(usage.message_count, usage.byte_count);
+    }
+}
+
+fn display_limit(limit: Option<u64>) -> String {
+    limit
+        .map(|limit| limit.to_string())
+        .unwrap_or_else(|| "unlimited".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the tracker records the messages and bytes of each circuit, and that clones
+    /// share the recorded usage.
+    #[test]
+    fn test_record_message() {
+        let tracker = CircuitUsageTracker::default();
+        let clone = tracker.clone();
+
+        tracker.record_message("bbbbb-00000", 10);
+        tracker.record_message("aaaaa-00000", 5);
+        tracker.record_message("bbbbb-00000", 20);
+
+        assert_eq!(
+            clone.get("bbbbb-00000"),
+            Some(CircuitUsage::new("bbbbb-00000", 2, 30))
+        );
+        assert_eq!(clone.get("ccccc-00000"), None);
+        assert_eq!(
+            clone.list(),
+            vec![
+                CircuitUsage::new("aaaaa-00000", 1, 5),
+                CircuitUsage::new("bbbbb-00000", 2, 30),
+            ]
+        );
+    }
+
+    /// Verify that restored usage is added to the totals but is not returned as unpersisted, and
+    /// that unpersisted usage which is returned is included the next time it is taken.
+    #[test]
+    fn test_restore_and_take_unpersisted() {
+        let tracker = CircuitUsageTracker::default();
+        tracker.restore(vec![CircuitUsage::new("aaaaa-00000", 100, 1000)]);
+        tracker.record_message("aaaaa-00000", 10);
+
+        assert_eq!(
+            tracker.get("aaaaa-00000"),
+            Some(CircuitUsage::new("aaaaa-00000", 101, 1010))
+        );
+
+        let unpersisted = tracker.take_unpersisted();
+        assert_eq!(unpersisted, vec![CircuitUsage::new("aaaaa-00000", 1, 10)]);
+        assert!(tracker.take_unpersisted().is_empty());
+
+        tracker.record_message("aaaaa-00000", 5);
+        for usage in unpersisted {
+            tracker.return_unpersisted(usage);
+        }
+        assert_eq!(
+            tracker.take_unpersisted(),
+            vec![CircuitUsage::new("aaaaa-00000", 2, 15)]
+        );
+    }
+
+    /// Verify that a circuit is reported as over quota once either limit is exceeded, and that
+    /// messages continue to be recorded.
+    #[test]
+    fn test_quota() {
+        let tracker = CircuitUsageTracker::new(
+            CircuitUsageQuota::new()
+                .with_max_messages(2)
+                .with_max_bytes(100),
+        );
+
+        tracker.record_message("aaaaa-00000", 10);
+        tracker.record_message("aaaaa-00000", 10);
+        assert!(!tracker.is_over_quota("aaaaa-00000"));
+
+        tracker.record_message("aaaaa-00000", 10);
+        assert!(tracker.is_over_quota("aaaaa-00000"));
+        assert_eq!(
+            tracker.get("aaaaa-00000"),
+            Some(CircuitUsage::new("aaaaa-00000", 3, 30))
+        );
+
+        tracker.record_message("bbbbb-00000", 101);
+        assert!(tracker.is_over_quota("bbbbb-00000"));
+
+        tracker.restore(vec![CircuitUsage::new("ccccc-00000", 5, 0)]);
+        assert!(tracker.is_over_quota("ccccc-00000"));
+    }
+}
@@ -0,0 +1,157 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::store::CircuitUsageStore;
+use super::CircuitUsageTracker;
+
+/// Persists the usage recorded by a [`CircuitUsageTracker`] on a background thread.
+///
+/// Usage is persisted once per interval, and a final time when the persister is shut down.
+pub struct CircuitUsagePersister {
+    shutdown_sender: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl CircuitUsagePersister {
+    /// Starts persisting the tracker's usage to the store on the given interval.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the persister thread could not be started.
+    pub fn start(
+        tracker: CircuitUsageTracker,
+        store: Box<dyn CircuitUsageStore>,
+        interval: Duration,
+    ) -> Result<Self, InternalError> {
+        let (shutdown_sender, shutdown_receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name("CircuitUsagePersister".into())
+            .spawn(move || loop {
+                let shutdown = match shutdown_receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => false,
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => true,
+                };
+
+                persist(&tracker, &*store);
+
+                if shutdown {
+                    break;
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            shutdown_sender,
+            join_handle,
+        })
+    }
+}
+
+/// Adds the usage recorded since the last call to the store. Usage that cannot be added is
+/// returned to the tracker, to be retried the next time.
+fn persist(tracker: &CircuitUsageTracker, store: &dyn CircuitUsageStore) {
+    for usage in tracker.take_unpersisted() {
+        if let Err(err) = store.add_usage(&usage) {
+            error!(
+                "Unable to persist usage of circuit {}: {}",
+                usage.circuit_id(),
+                err
+            );
+            tracker.return_unpersisted(usage);
+        }
+    }
+}
+
+impl ShutdownHandle for CircuitUsagePersister {
+    fn signal_shutdown(&mut self) {
+        // An error means the thread has already stopped
+        let _ = self.shutdown_sender.send(());
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle.join().map_err(|_| {
+            InternalError::with_message("CircuitUsagePersister thread panicked".into())
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::circuit::usage::store::DieselCircuitUsageStore;
+    use crate::circuit::usage::CircuitUsage;
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that the persister adds the recorded usage to the store when it is shut down.
+    ///
+    /// 1. Start a persister with a long interval.
+    /// 2. Record usage on two circuits and shut down the persister.
+    /// 3. Verify that the usage was added to the store, and that a tracker restored from the
+    ///    store reports the same totals.
+    #[test]
+    fn test_persist_on_shutdown() {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+        let store = DieselCircuitUsageStore::new(pool);
+
+        let tracker = CircuitUsageTracker::default();
+        let mut persister = CircuitUsagePersister::start(
+            tracker.clone(),
+            store.clone_box(),
+            Duration::from_secs(3600),
+        )
+        .expect("Failed to start persister");
+
+        tracker.record_message("aaaaa-00000", 10);
+        tracker.record_message("aaaaa-00000", 20);
+        tracker.record_message("bbbbb-00000", 5);
+
+        persister.signal_shutdown();
+        persister
+            .wait_for_shutdown()
+            .expect("Failed to shut down persister");
+
+        let persisted = store.list_usage().expect("Failed to list usage");
+        assert_eq!(
+            persisted,
+            vec![
+                CircuitUsage::new("aaaaa-00000", 2, 30),
+                CircuitUsage::new("bbbbb-00000", 1, 5),
+            ]
+        );
+
+        let restored = CircuitUsageTracker::default();
+        restored.restore(persisted);
+        assert_eq!(restored.list(), tracker.list());
+    }
+}
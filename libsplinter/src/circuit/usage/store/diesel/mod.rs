@@ -0,0 +1,184 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [CircuitUsageStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::circuit::usage::CircuitUsage;
+use crate::store::pool::ConnectionPool;
+
+use super::{CircuitUsageStore, CircuitUsageStoreError};
+
+use operations::{
+    add_usage::CircuitUsageStoreAddUsage as _, get_usage::CircuitUsageStoreGetUsage as _,
+    list_usage::CircuitUsageStoreListUsage as _, CircuitUsageStoreOperations,
+};
+
+/// Manages adding to and fetching the usage of each circuit in the database
+pub struct DieselCircuitUsageStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselCircuitUsageStore<C> {
+    /// Creates a new DieselCircuitUsageStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselCircuitUsageStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselCircuitUsageStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl CircuitUsageStore for DieselCircuitUsageStore<diesel::pg::PgConnection> {
+    fn add_usage(&self, usage: &CircuitUsage) -> Result<(), CircuitUsageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            CircuitUsageStoreOperations::new(connection).add_usage(usage)
+        })
+    }
+
+    fn get_usage(&self, circuit_id: &str) -> Result<Option<CircuitUsage>, CircuitUsageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            CircuitUsageStoreOperations::new(connection).get_usage(circuit_id)
+        })
+    }
+
+    fn list_usage(&self) -> Result<Vec<CircuitUsage>, CircuitUsageStoreError> {
+        self.connection_pool
+            .execute_read(|connection| CircuitUsageStoreOperations::new(connection).list_usage())
+    }
+
+    fn clone_box(&self) -> Box<dyn CircuitUsageStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl CircuitUsageStore for DieselCircuitUsageStore<diesel::sqlite::SqliteConnection> {
+    fn add_usage(&self, usage: &CircuitUsage) -> Result<(), CircuitUsageStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            CircuitUsageStoreOperations::new(connection).add_usage(usage)
+        })
+    }
+
+    fn get_usage(&self, circuit_id: &str) -> Result<Option<CircuitUsage>, CircuitUsageStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            CircuitUsageStoreOperations::new(connection).get_usage(circuit_id)
+        })
+    }
+
+    fn list_usage(&self) -> Result<Vec<CircuitUsage>, CircuitUsageStoreError> {
+        self.connection_pool
+            .execute_read(|connection| CircuitUsageStoreOperations::new(connection).list_usage())
+    }
+
+    fn clone_box(&self) -> Box<dyn CircuitUsageStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a SQLite-backed `DieselCircuitUsageStore` correctly supports adding to,
+    /// fetching and listing the usage of circuits.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Verify that a circuit with no usage returns `None`.
+    /// 3. Add usage for two circuits, then add more usage for one of them.
+    /// 4. Verify that the added usage is summed per circuit, and that the usage of both circuits
+    ///    is listed in order of circuit ID.
+    #[test]
+    fn sqlite_add_and_get_usage() {
+        let store = DieselCircuitUsageStore::new(create_connection_pool_and_migrate());
+
+        assert_eq!(
+            store.get_usage("aaaaa-00000").expect("Failed to get usage"),
+            None
+        );
+
+        store
+            .add_usage(&CircuitUsage::new("bbbbb-00000", 1, 10))
+            .expect("Failed to add usage");
+        store
+            .add_usage(&CircuitUsage::new("aaaaa-00000", 2, 20))
+            .expect("Failed to add usage");
+        store
+            .add_usage(&CircuitUsage::new("aaaaa-00000", 3, 30))
+            .expect("Failed to add usage");
+
+        assert_eq!(
+            store.get_usage("aaaaa-00000").expect("Failed to get usage"),
+            Some(CircuitUsage::new("aaaaa-00000", 5, 50))
+        );
+        assert_eq!(
+            store.list_usage().expect("Failed to list usage"),
+            vec![
+                CircuitUsage::new("aaaaa-00000", 5, 50),
+                CircuitUsage::new("bbbbb-00000", 1, 10),
+            ]
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,66 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use crate::circuit::usage::store::CircuitUsageStoreError;
+use crate::circuit::usage::CircuitUsage;
+use crate::error::{InternalError, InvalidArgumentError};
+
+use super::schema::circuit_usage;
+
+#[derive(Insertable, Queryable, Identifiable, PartialEq, Eq, Debug)]
+#[table_name = "circuit_usage"]
+#[primary_key(circuit_id)]
+pub struct CircuitUsageModel {
+    pub circuit_id: String,
+    pub message_count: i64,
+    pub byte_count: i64,
+}
+
+impl TryFrom<&CircuitUsage> for CircuitUsageModel {
+    type Error = CircuitUsageStoreError;
+
+    fn try_from(usage: &CircuitUsage) -> Result<Self, Self::Error> {
+        Ok(CircuitUsageModel {
+            circuit_id: usage.circuit_id().to_string(),
+            message_count: i64::try_from(usage.message_count()).map_err(|_| {
+                CircuitUsageStoreError::InvalidArgument(InvalidArgumentError::new(
+                    "usage".to_string(),
+                    "message count is too large to be stored".to_string(),
+                ))
+            })?,
+            byte_count: i64::try_from(usage.byte_count()).map_err(|_| {
+                CircuitUsageStoreError::InvalidArgument(InvalidArgumentError::new(
+                    "usage".to_string(),
+                    "byte count is too large to be stored".to_string(),
+                ))
+            })?,
+        })
+    }
+}
+
+impl TryFrom<CircuitUsageModel> for CircuitUsage {
+    type Error = CircuitUsageStoreError;
+
+    fn try_from(model: CircuitUsageModel) -> Result<Self, Self::Error> {
+        Ok(CircuitUsage::new(
+            &model.circuit_id,
+            u64::try_from(model.message_count)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            u64::try_from(model.byte_count)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+        ))
+    }
+}
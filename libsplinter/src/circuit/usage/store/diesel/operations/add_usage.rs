@@ -0,0 +1,108 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    dsl::{insert_into, update},
+    prelude::*,
+};
+
+use crate::circuit::usage::store::{
+    diesel::{models::CircuitUsageModel, schema::circuit_usage},
+    CircuitUsageStoreError,
+};
+use crate::circuit::usage::CircuitUsage;
+
+use super::CircuitUsageStoreOperations;
+
+pub trait CircuitUsageStoreAddUsage {
+    fn add_usage(&self, usage: &CircuitUsage) -> Result<(), CircuitUsageStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> CircuitUsageStoreAddUsage
+    for CircuitUsageStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_usage(&self, usage: &CircuitUsage) -> Result<(), CircuitUsageStoreError> {
+        let model = CircuitUsageModel::try_from(usage)?;
+
+        self.conn.transaction::<_, CircuitUsageStoreError, _>(|| {
+            let existing = circuit_usage::table
+                .filter(circuit_usage::circuit_id.eq(&model.circuit_id))
+                .first::<CircuitUsageModel>(self.conn)
+                .optional()?;
+
+            match existing {
+                Some(existing) => {
+                    update(
+                        circuit_usage::table
+                            .filter(circuit_usage::circuit_id.eq(&model.circuit_id)),
+                    )
+                    .set((
+                        circuit_usage::message_count
+                            .eq(existing.message_count.saturating_add(model.message_count)),
+                        circuit_usage::byte_count
+                            .eq(existing.byte_count.saturating_add(model.byte_count)),
+                    ))
+                    .execute(self.conn)?;
+                }
+                None => {
+                    insert_into(circuit_usage::table)
+                        .values(&model)
+                        .execute(self.conn)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> CircuitUsageStoreAddUsage for CircuitUsageStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_usage(&self, usage: &CircuitUsage) -> Result<(), CircuitUsageStoreError> {
+        let model = CircuitUsageModel::try_from(usage)?;
+
+        self.conn.transaction::<_, CircuitUsageStoreError, _>(|| {
+            let existing = circuit_usage::table
+                .filter(circuit_usage::circuit_id.eq(&model.circuit_id))
+                .first::<CircuitUsageModel>(self.conn)
+                .optional()?;
+
+            match existing {
+                Some(existing) => {
+                    update(
+                        circuit_usage::table
+                            .filter(circuit_usage::circuit_id.eq(&model.circuit_id)),
+                    )
+                    .set((
+                        circuit_usage::message_count
+                            .eq(existing.message_count.saturating_add(model.message_count)),
+                        circuit_usage::byte_count
+                            .eq(existing.byte_count.saturating_add(model.byte_count)),
+                    ))
+                    .execute(self.conn)?;
+                }
+                None => {
+                    insert_into(circuit_usage::table)
+                        .values(&model)
+                        .execute(self.conn)?;
+                }
+            }
+
+            Ok(())
+        })
+    }
+}
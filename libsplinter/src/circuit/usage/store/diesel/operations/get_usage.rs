@@ -0,0 +1,45 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::circuit::usage::store::{
+    diesel::{models::CircuitUsageModel, schema::circuit_usage},
+    CircuitUsageStoreError,
+};
+use crate::circuit::usage::CircuitUsage;
+
+use super::CircuitUsageStoreOperations;
+
+pub trait CircuitUsageStoreGetUsage {
+    fn get_usage(&self, circuit_id: &str) -> Result<Option<CircuitUsage>, CircuitUsageStoreError>;
+}
+
+impl<'a, C> CircuitUsageStoreGetUsage for CircuitUsageStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn get_usage(&self, circuit_id: &str) -> Result<Option<CircuitUsage>, CircuitUsageStoreError> {
+        circuit_usage::table
+            .filter(circuit_usage::circuit_id.eq(circuit_id))
+            .first::<CircuitUsageModel>(self.conn)
+            .optional()?
+            .map(CircuitUsage::try_from)
+            .transpose()
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the store that persists the usage of each circuit.
+
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod error;
+
+use super::CircuitUsage;
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselCircuitUsageStore;
+pub use error::CircuitUsageStoreError;
+
+/// Defines methods for persisting the usage of each circuit
+pub trait CircuitUsageStore: Send + Sync {
+    /// Adds the given message and byte counts to those already stored for the circuit.
+    ///
+    /// # Arguments
+    ///
+    ///  * `usage` - The usage to add, recorded since usage was last added for the circuit
+    fn add_usage(&self, usage: &CircuitUsage) -> Result<(), CircuitUsageStoreError>;
+
+    /// Returns the stored usage of a circuit, if any has been added
+    ///
+    /// # Arguments
+    ///
+    ///  * `circuit_id` - The ID of the circuit
+    fn get_usage(&self, circuit_id: &str) -> Result<Option<CircuitUsage>, CircuitUsageStoreError>;
+
+    /// Returns the stored usage of every circuit, sorted by circuit ID
+    fn list_usage(&self) -> Result<Vec<CircuitUsage>, CircuitUsageStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn CircuitUsageStore>;
+}
+
+impl Clone for Box<dyn CircuitUsageStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<CS> CircuitUsageStore for Box<CS>
+where
+    CS: CircuitUsageStore + ?Sized,
+{
+    fn add_usage(&self, usage: &CircuitUsage) -> Result<(), CircuitUsageStoreError> {
+        (**self).add_usage(usage)
+    }
+
+    fn get_usage(&self, circuit_id: &str) -> Result<Option<CircuitUsage>, CircuitUsageStoreError> {
+        (**self).get_usage(circuit_id)
+    }
+
+    fn list_usage(&self) -> Result<Vec<CircuitUsage>, CircuitUsageStoreError> {
+        (**self).list_usage()
+    }
+
+    fn clone_box(&self) -> Box<dyn CircuitUsageStore> {
+        (**self).clone_box()
+    }
+}
@@ -44,6 +44,8 @@ mod timing;
 
 use std::collections::{HashSet, VecDeque};
 use std::sync::mpsc::{Receiver, RecvTimeoutError};
+#[cfg(feature = "admin-service-consensus-status")]
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use protobuf::Message;
@@ -52,6 +54,8 @@ use crate::consensus::{
     ConsensusEngine, ConsensusEngineError, ConsensusMessage, ConsensusNetworkSender, PeerId,
     Proposal, ProposalId, ProposalManager, ProposalUpdate, StartupState,
 };
+#[cfg(feature = "admin-service-consensus-status")]
+use crate::error::InternalError;
 use crate::protos::two_phase::{
     RequiredVerifiers, TwoPhaseMessage, TwoPhaseMessage_ProposalResult,
     TwoPhaseMessage_ProposalVerificationResponse, TwoPhaseMessage_Type,
@@ -113,6 +117,53 @@ impl TwoPhaseProposal {
     }
 }
 
+/// The proposal that a `TwoPhaseEngine` is evaluating.
+#[cfg(feature = "admin-service-consensus-status")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EvaluatingProposal {
+    pub proposal_id: ProposalId,
+    pub coordinator_id: PeerId,
+    /// Whether the local node is the coordinator of the proposal
+    pub is_coordinator: bool,
+    pub required_verifiers: Vec<PeerId>,
+    /// The verifiers that have verified the proposal; only tracked by the coordinator
+    pub peers_verified: Vec<PeerId>,
+    /// The time left before the coordinator rejects the proposal; only set on the coordinator
+    pub coordinator_timeout_remaining: Option<Duration>,
+}
+
+/// A snapshot of the state of a running `TwoPhaseEngine`.
+#[cfg(feature = "admin-service-consensus-status")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TwoPhaseEngineStatus {
+    /// The proposal being evaluated, if any
+    pub evaluating: Option<EvaluatingProposal>,
+    /// The proposals waiting to be evaluated, in the order they will be evaluated
+    pub backlog: Vec<ProposalId>,
+}
+
+/// Reads the status of a `TwoPhaseEngine` while it runs on another thread.
+///
+/// The engine updates its status on each iteration of its main loop, so the status may be behind
+/// by a fraction of a second.
+#[cfg(feature = "admin-service-consensus-status")]
+#[derive(Clone, Default)]
+pub struct TwoPhaseStatusReader {
+    status: Arc<Mutex<TwoPhaseEngineStatus>>,
+}
+
+#[cfg(feature = "admin-service-consensus-status")]
+impl TwoPhaseStatusReader {
+    pub fn status(&self) -> Result<TwoPhaseEngineStatus, InternalError> {
+        self.status
+            .lock()
+            .map(|status| status.clone())
+            .map_err(|_| {
+                InternalError::with_message("Two-phase status lock was poisoned".to_string())
+            })
+    }
+}
+
 pub struct TwoPhaseEngine {
     id: PeerId,
     peers: HashSet<PeerId>,
@@ -120,6 +171,8 @@ pub struct TwoPhaseEngine {
     coordinator_timeout: Timeout,
     proposal_backlog: VecDeque<TwoPhaseProposal>,
     verification_request_backlog: VecDeque<ProposalId>,
+    #[cfg(feature = "admin-service-consensus-status")]
+    status_reader: TwoPhaseStatusReader,
 }
 
 impl TwoPhaseEngine {
@@ -131,6 +184,60 @@ impl TwoPhaseEngine {
             coordinator_timeout: Timeout::new(coordinator_timeout_duration),
             proposal_backlog: VecDeque::new(),
             verification_request_backlog: VecDeque::new(),
+            #[cfg(feature = "admin-service-consensus-status")]
+            status_reader: TwoPhaseStatusReader::default(),
+        }
+    }
+
+    /// Returns a reader for the status of the engine, which may be used after the engine is
+    /// moved to the thread that runs it.
+    #[cfg(feature = "admin-service-consensus-status")]
+    pub fn status_reader(&self) -> TwoPhaseStatusReader {
+        self.status_reader.clone()
+    }
+
+    /// Update the status available to the `TwoPhaseStatusReader`s of the engine.
+    #[cfg(feature = "admin-service-consensus-status")]
+    fn publish_status(&self) {
+        let evaluating = match self.state {
+            State::EvaluatingProposal(ref tpc_proposal) => {
+                let mut required_verifiers = tpc_proposal
+                    .required_verifiers()
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                required_verifiers.sort();
+                let mut peers_verified = tpc_proposal
+                    .peers_verified()
+                    .iter()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                peers_verified.sort();
+
+                Some(EvaluatingProposal {
+                    proposal_id: tpc_proposal.proposal_id().clone(),
+                    coordinator_id: tpc_proposal.coordinator_id().clone(),
+                    is_coordinator: tpc_proposal.coordinator_id() == &self.id,
+                    required_verifiers,
+                    peers_verified,
+                    coordinator_timeout_remaining: self.coordinator_timeout.remaining(),
+                })
+            }
+            State::Idle | State::AwaitingProposal => None,
+        };
+
+        let status = TwoPhaseEngineStatus {
+            evaluating,
+            backlog: self
+                .proposal_backlog
+                .iter()
+                .map(|tpc_proposal| tpc_proposal.proposal_id().clone())
+                .collect(),
+        };
+
+        match self.status_reader.status.lock() {
+            Ok(mut current_status) => *current_status = status,
+            Err(_) => error!("Two-phase status lock was poisoned"),
         }
     }
 
@@ -702,6 +809,9 @@ impl ConsensusEngine for TwoPhaseEngine {
                     break;
                 }
             }
+
+            #[cfg(feature = "admin-service-consensus-status")]
+            self.publish_status();
         }
 
         Ok(())
@@ -1326,4 +1436,71 @@ pub mod tests {
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
     }
+
+    /// Verify that the status reader reports the proposal the engine is coordinating, including
+    /// the verifiers that have not responded and the time left before the proposal times out.
+    #[cfg(feature = "admin-service-consensus-status")]
+    #[test]
+    fn test_status_reader() {
+        let (update_tx, update_rx) = channel();
+        let (_consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS));
+        let status_reader = engine.status_reader();
+        assert_eq!(
+            status_reader.status().expect("failed to get status"),
+            TwoPhaseEngineStatus::default()
+        );
+
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network),
+                    Box::new(manager),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Wait for the engine to verify the proposal itself, after which it waits for the other
+        // verifiers
+        let evaluating = loop {
+            match status_reader
+                .status()
+                .expect("failed to get status")
+                .evaluating
+            {
+                Some(evaluating) if !evaluating.peers_verified.is_empty() => break evaluating,
+                _ => (),
+            }
+        };
+
+        assert_eq!(evaluating.proposal_id, vec![1].into());
+        assert_eq!(evaluating.coordinator_id, vec![0].into());
+        assert!(evaluating.is_coordinator);
+        assert_eq!(
+            evaluating.required_verifiers,
+            vec![vec![0].into(), vec![1].into(), vec![2].into()]
+        );
+        assert_eq!(evaluating.peers_verified, vec![vec![0].into()]);
+        assert!(evaluating
+            .coordinator_timeout_remaining
+            .map(|remaining| remaining <= Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS))
+            .unwrap_or(false));
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
 }
@@ -59,4 +59,13 @@ impl Timeout {
     pub fn stop(&mut self) {
         self.state = TimeoutState::Inactive;
     }
+
+    /// Returns the time left before the timer expires, or `None` if the timer is not running.
+    #[cfg(feature = "admin-service-consensus-status")]
+    pub fn remaining(&self) -> Option<Duration> {
+        match self.state {
+            TimeoutState::Active => Some(self.duration.saturating_sub(self.start.elapsed())),
+            TimeoutState::Inactive | TimeoutState::Expired => None,
+        }
+    }
 }
@@ -26,6 +26,19 @@
 //! send the message when it restarts. This limitation will be solved by re-implementing 2PC as a
 //! stateless algorithm.
 //!
+//! # Timing and retries
+//!
+//! The engine checks its timers and polls for messages once per alarm interval (100 milliseconds
+//! by default). By default, the coordinator rejects a proposal as soon as the coordinator timeout
+//! expires, and participants wait indefinitely for the coordinator's result after voting. Both may
+//! be configured to retry a number of times before giving up, which helps on high-latency
+//! networks where messages are more likely to be delayed or lost:
+//!
+//! * When the coordinator timeout expires and retries remain, the coordinator broadcasts the
+//!   verification request again and restarts the timeout.
+//! * When the vote timeout expires and retries remain, a participant sends its vote to the
+//!   coordinator again and restarts the timeout.
+//!
 //! # Differences from previous version
 //!
 //! This version of the 2PC implementation differs from the previous version in the following ways:
@@ -57,8 +70,8 @@ use crate::protos::two_phase::{
 
 use self::timing::Timeout;
 
-const MESSAGE_RECV_TIMEOUT_MILLIS: u64 = 100;
-const PROPOSAL_RECV_TIMEOUT_MILLIS: u64 = 100;
+const DEFAULT_ALARM_INTERVAL_MILLIS: u64 = 100;
+const DEFAULT_VOTE_TIMEOUT_SECS: u64 = 30;
 
 #[derive(Debug)]
 enum State {
@@ -110,6 +123,10 @@ impl TwoPhaseProposal {
     fn add_verified_peer(&mut self, id: PeerId) {
         self.peers_verified.insert(id);
     }
+
+    fn is_verified_by(&self, id: &PeerId) -> bool {
+        self.peers_verified.contains(id)
+    }
 }
 
 pub struct TwoPhaseEngine {
@@ -117,6 +134,10 @@ pub struct TwoPhaseEngine {
     verifiers: HashSet<PeerId>,
     state: State,
     coordinator_timeout: Timeout,
+    vote_timeout: Timeout,
+    alarm_interval: Duration,
+    max_retries: u32,
+    retries_remaining: u32,
     proposals_received: HashSet<ProposalId>,
     verification_request_backlog: VecDeque<ProposalId>,
 }
@@ -128,11 +149,35 @@ impl TwoPhaseEngine {
             verifiers: HashSet::new(),
             state: State::Idle,
             coordinator_timeout: Timeout::new(coordinator_timeout_duration),
+            vote_timeout: Timeout::new(Duration::from_secs(DEFAULT_VOTE_TIMEOUT_SECS)),
+            alarm_interval: Duration::from_millis(DEFAULT_ALARM_INTERVAL_MILLIS),
+            max_retries: 0,
+            retries_remaining: 0,
             proposals_received: HashSet::new(),
             verification_request_backlog: VecDeque::new(),
         }
     }
 
+    /// Sets how often the engine checks its timers and polls for messages.
+    pub fn with_alarm_interval(mut self, alarm_interval: Duration) -> Self {
+        self.alarm_interval = alarm_interval;
+        self
+    }
+
+    /// Sets how long a participant waits for the coordinator's result after voting before it
+    /// sends its vote again, if any retries remain.
+    pub fn with_vote_timeout(mut self, vote_timeout: Duration) -> Self {
+        self.vote_timeout = Timeout::new(vote_timeout);
+        self
+    }
+
+    /// Sets the number of times the coordinator resends a verification request, or a participant
+    /// resends its vote, when its timeout expires before giving up.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
     /// Determines if this node is the coordinator.
     fn is_coordinator(&self) -> bool {
         &self.id == self.coordinator_id()
@@ -160,7 +205,18 @@ impl TwoPhaseEngine {
             TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST => {
                 debug!("Proposal verification request received: {}", proposal_id);
 
-                if self.state.is_evaluating_proposal_with_id(&proposal_id) {
+                if self.state.is_evaluating_proposal_with_id(&proposal_id) && self.has_voted() {
+                    // The coordinator is retrying; it may not have received this node's vote
+                    debug!(
+                        "Verification request received again, resending vote: {}",
+                        proposal_id
+                    );
+                    self.send_verification_response(
+                        proposal_id,
+                        TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+                        network_sender,
+                    )?;
+                } else if self.state.is_evaluating_proposal_with_id(&proposal_id) {
                     debug!(
                         "Proposal already in progress, backlogging verification request: {}",
                         proposal_id
@@ -241,6 +297,7 @@ impl TwoPhaseEngine {
                         debug!("Accepting proposal {}", proposal_id);
                         proposal_manager.accept_proposal(&proposal_id, None)?;
                         self.state = State::Idle;
+                        self.vote_timeout.stop();
                     } else {
                         warn!(
                             "Received unexpected apply result for proposal {}",
@@ -255,6 +312,7 @@ impl TwoPhaseEngine {
                     // Only update state if this was the currently evaluating proposal
                     if self.state.is_evaluating_proposal_with_id(&proposal_id) {
                         self.state = State::Idle;
+                        self.vote_timeout.stop();
                     }
                 }
                 TwoPhaseMessage_ProposalResult::UNSET_RESULT => warn!(
@@ -310,30 +368,22 @@ impl TwoPhaseEngine {
                 {
                     debug!("Proposal valid: {}", proposal_id);
 
-                    if is_coordinator {
-                        tpc_proposal.add_verified_peer(self.id.clone());
+                    // Record this node's own verification; for a participant, this records that it
+                    // has voted
+                    tpc_proposal.add_verified_peer(self.id.clone());
 
+                    if is_coordinator {
                         debug!("Requesting verification of proposal {}", proposal_id);
-
-                        let mut request = TwoPhaseMessage::new();
-                        request
-                            .set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
-                        request.set_proposal_id(proposal_id.into());
-
-                        network_sender.broadcast(request.write_to_bytes()?)?;
+                        Self::broadcast_verification_request(proposal_id, network_sender)?;
                     } else {
                         debug!("Sending verified response for proposal {}", proposal_id);
-
-                        let mut response = TwoPhaseMessage::new();
-                        response
-                            .set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-                        response.set_proposal_id(proposal_id.into());
-                        response.set_proposal_verification_response(
+                        self.send_verification_response(
+                            proposal_id,
                             TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
-                        );
-
-                        network_sender
-                            .send_to(self.coordinator_id(), response.write_to_bytes()?)?;
+                            network_sender,
+                        )?;
+                        self.retries_remaining = self.max_retries;
+                        self.vote_timeout.start();
                     }
                 }
                 _ => warn!("Got valid message for unknown proposal: {}", proposal_id),
@@ -352,17 +402,11 @@ impl TwoPhaseEngine {
                         )?;
                     } else {
                         debug!("Sending failed response for proposal {}", proposal_id);
-
-                        let mut response = TwoPhaseMessage::new();
-                        response
-                            .set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
-                        response.set_proposal_id(proposal_id.into());
-                        response.set_proposal_verification_response(
+                        self.send_verification_response(
+                            proposal_id,
                             TwoPhaseMessage_ProposalVerificationResponse::FAILED,
-                        );
-
-                        network_sender
-                            .send_to(self.coordinator_id(), response.write_to_bytes()?)?;
+                            network_sender,
+                        )?;
                     }
                 } else {
                     warn!("Got invalid message for unknown proposal: {}", proposal_id);
@@ -395,6 +439,7 @@ impl TwoPhaseEngine {
         match proposal_manager.check_proposal(&proposal_id) {
             Ok(_) => {
                 self.state = State::EvaluatingProposal(TwoPhaseProposal::new(proposal_id));
+                self.retries_remaining = self.max_retries;
                 self.coordinator_timeout.start();
             }
             Err(err) => {
@@ -449,14 +494,66 @@ impl TwoPhaseEngine {
         Ok(())
     }
 
-    /// If the coordinator timeout has expired, abort the current proposal.
+    /// Returns true if this node has verified the proposal it is currently evaluating. For a
+    /// participant, this means that it has voted.
+    fn has_voted(&self) -> bool {
+        match &self.state {
+            State::EvaluatingProposal(tpc_proposal) => tpc_proposal.is_verified_by(&self.id),
+            _ => false,
+        }
+    }
+
+    fn broadcast_verification_request(
+        proposal_id: ProposalId,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+        request.set_proposal_id(proposal_id.into());
+
+        network_sender.broadcast(request.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn send_verification_response(
+        &self,
+        proposal_id: ProposalId,
+        verification_response: TwoPhaseMessage_ProposalVerificationResponse,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        let mut response = TwoPhaseMessage::new();
+        response.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_RESPONSE);
+        response.set_proposal_id(proposal_id.into());
+        response.set_proposal_verification_response(verification_response);
+
+        network_sender.send_to(self.coordinator_id(), response.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    /// If the coordinator timeout has expired, retry the verification request if any retries
+    /// remain; otherwise, abort the current proposal.
     fn abort_proposal_if_timed_out(
         &mut self,
         network_sender: &dyn ConsensusNetworkSender,
         proposal_manager: &dyn ProposalManager,
     ) -> Result<(), ConsensusEngineError> {
         if let State::EvaluatingProposal(ref tpc_proposal) = self.state {
-            if self.coordinator_timeout.check_expired() {
+            if self.coordinator_timeout.check_expired() && self.retries_remaining > 0 {
+                self.retries_remaining -= 1;
+                warn!(
+                    "Proposal timed out; retrying ({} retries remaining): {}",
+                    self.retries_remaining,
+                    tpc_proposal.proposal_id()
+                );
+                // The verification request is only sent once this node has verified the proposal
+                if tpc_proposal.is_verified_by(&self.id) {
+                    let proposal_id = tpc_proposal.proposal_id().clone();
+                    Self::broadcast_verification_request(proposal_id, network_sender)?;
+                }
+                self.coordinator_timeout.start();
+            } else if self.coordinator_timeout.check_expired() {
                 warn!(
                     "Proposal timed out; rejecting: {}",
                     tpc_proposal.proposal_id()
@@ -474,6 +571,52 @@ impl TwoPhaseEngine {
         Ok(())
     }
 
+    /// If the vote timeout has expired, send the vote to the coordinator again if any retries
+    /// remain.
+    fn resend_vote_if_timed_out(
+        &mut self,
+        network_sender: &dyn ConsensusNetworkSender,
+    ) -> Result<(), ConsensusEngineError> {
+        if !self.vote_timeout.check_expired() {
+            return Ok(());
+        }
+
+        let proposal_id = match &self.state {
+            State::EvaluatingProposal(tpc_proposal) if self.has_voted() => {
+                tpc_proposal.proposal_id().clone()
+            }
+            _ => {
+                self.vote_timeout.stop();
+                return Ok(());
+            }
+        };
+
+        if self.retries_remaining > 0 {
+            self.retries_remaining -= 1;
+            warn!(
+                "No result received for proposal; resending vote ({} retries remaining): {}",
+                self.retries_remaining, proposal_id
+            );
+            self.send_verification_response(
+                proposal_id,
+                TwoPhaseMessage_ProposalVerificationResponse::VERIFIED,
+                network_sender,
+            )?;
+            self.vote_timeout.start();
+        } else {
+            if self.max_retries > 0 {
+                warn!(
+                    "No result received for proposal after {} retries; waiting for coordinator: \
+                     {}",
+                    self.max_retries, proposal_id
+                );
+            }
+            self.vote_timeout.stop();
+        }
+
+        Ok(())
+    }
+
     /// If not doing anything, see if there are any backlogged verification requests that this node
     /// has received a proposal for, and evaluate that proposal.
     fn handle_backlogged_verification_request(
@@ -530,8 +673,8 @@ impl ConsensusEngine for TwoPhaseEngine {
         proposal_manager: Box<dyn ProposalManager>,
         startup_state: StartupState,
     ) -> Result<(), ConsensusEngineError> {
-        let message_timeout = Duration::from_millis(MESSAGE_RECV_TIMEOUT_MILLIS);
-        let proposal_timeout = Duration::from_millis(PROPOSAL_RECV_TIMEOUT_MILLIS);
+        let message_timeout = self.alarm_interval;
+        let proposal_timeout = self.alarm_interval;
 
         self.id = startup_state.id;
         self.verifiers.insert(self.id.clone()); // This node is a verifier
@@ -546,6 +689,10 @@ impl ConsensusEngine for TwoPhaseEngine {
                 error!("Failed to abort timed-out proposal: {}", err);
             }
 
+            if let Err(err) = self.resend_vote_if_timed_out(&*network_sender) {
+                error!("Failed to resend vote: {}", err);
+            }
+
             if let Err(err) = self.handle_backlogged_verification_request(&*proposal_manager) {
                 error!("Failed to handle backlogged verification request: {}", err);
             }
@@ -651,6 +798,10 @@ pub mod tests {
             verifiers: peer_ids_hashset.clone(),
             state: State::Idle,
             coordinator_timeout: Timeout::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS)),
+            vote_timeout: Timeout::new(Duration::from_secs(DEFAULT_VOTE_TIMEOUT_SECS)),
+            alarm_interval: Duration::from_millis(DEFAULT_ALARM_INTERVAL_MILLIS),
+            max_retries: 0,
+            retries_remaining: 0,
             proposals_received: HashSet::new(),
             verification_request_backlog: VecDeque::new(),
         };
@@ -662,6 +813,10 @@ pub mod tests {
             verifiers: peer_ids_hashset,
             state: State::Idle,
             coordinator_timeout: Timeout::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS)),
+            vote_timeout: Timeout::new(Duration::from_secs(DEFAULT_VOTE_TIMEOUT_SECS)),
+            alarm_interval: Duration::from_millis(DEFAULT_ALARM_INTERVAL_MILLIS),
+            max_retries: 0,
+            retries_remaining: 0,
             proposals_received: HashSet::new(),
             verification_request_backlog: VecDeque::new(),
         };
@@ -1065,4 +1220,153 @@ pub mod tests {
             .expect("failed to send shutdown");
         thread.join().expect("failed to join engine thread");
     }
+
+    /// Test that the coordinator resends the verification request when the coordinator timeout
+    /// expires and retries remain, and rejects the proposal once the retries are exhausted.
+    #[test]
+    fn test_coordinator_timeout_retry() {
+        let (update_tx, update_rx) = channel();
+        let (_consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![0].into(),
+            peer_ids: vec![vec![1].into(), vec![2].into()],
+            last_proposal: None,
+        };
+
+        // Start engine with a short coordinator timeout and a single retry; the alarm interval is
+        // much shorter so the proposal is verified locally well before the timeout expires
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(50))
+            .with_alarm_interval(Duration::from_millis(1))
+            .with_max_retries(1);
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        // Verify the verification request is sent twice, then the Reject message is sent
+        loop {
+            let messages = network.broadcast_messages();
+            if messages.len() >= 3 {
+                for msg in &messages[0..2] {
+                    let msg: TwoPhaseMessage =
+                        Message::parse_from_bytes(msg).expect("failed to parse message");
+                    assert_eq!(
+                        msg.get_message_type(),
+                        TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST
+                    );
+                    assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                }
+
+                let msg: TwoPhaseMessage =
+                    Message::parse_from_bytes(&messages[2]).expect("failed to parse message");
+                assert_eq!(
+                    msg.get_message_type(),
+                    TwoPhaseMessage_Type::PROPOSAL_RESULT
+                );
+                assert_eq!(
+                    msg.get_proposal_result(),
+                    TwoPhaseMessage_ProposalResult::REJECT
+                );
+                assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+                break;
+            }
+        }
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
+
+    /// Test that a participant resends its vote when the vote timeout expires and retries remain,
+    /// and stops resending once the retries are exhausted.
+    #[test]
+    fn test_participant_vote_retry() {
+        let (update_tx, update_rx) = channel();
+        let (consensus_msg_tx, consensus_msg_rx) = channel();
+
+        let manager = MockProposalManager::new(update_tx.clone());
+        manager.set_return_proposal(false);
+        let network = MockConsensusNetworkSender::new();
+        let startup_state = StartupState {
+            id: vec![1].into(),
+            peer_ids: vec![vec![0].into()],
+            last_proposal: None,
+        };
+
+        let mut engine = TwoPhaseEngine::new(Duration::from_millis(COORDINATOR_TIMEOUT_MILLIS))
+            .with_alarm_interval(Duration::from_millis(10))
+            .with_vote_timeout(Duration::from_millis(10))
+            .with_max_retries(2);
+        let network_clone = network.clone();
+        let manager_clone = manager.clone();
+        let thread = std::thread::spawn(move || {
+            engine
+                .run(
+                    consensus_msg_rx,
+                    update_rx,
+                    Box::new(network_clone),
+                    Box::new(manager_clone),
+                    startup_state,
+                )
+                .expect("engine failed")
+        });
+
+        let mut proposal = Proposal::default();
+        proposal.id = vec![1].into();
+        update_tx
+            .send(ProposalUpdate::ProposalReceived(proposal, vec![0].into()))
+            .expect("failed to send proposal");
+
+        let mut request = TwoPhaseMessage::new();
+        request.set_message_type(TwoPhaseMessage_Type::PROPOSAL_VERIFICATION_REQUEST);
+        request.set_proposal_id(vec![1]);
+        let message_bytes = request
+            .write_to_bytes()
+            .expect("failed to write request to bytes");
+        consensus_msg_tx
+            .send(ConsensusMessage::new(message_bytes, vec![0].into()))
+            .expect("failed to send verification request");
+
+        // Check that the vote is sent once, then resent twice
+        loop {
+            if network.sent_messages().len() >= 3 {
+                break;
+            }
+        }
+
+        // Give the engine time to resend the vote again, which it should not do
+        std::thread::sleep(Duration::from_millis(100));
+
+        let sent_messages = network.sent_messages();
+        assert_eq!(sent_messages.len(), 3);
+        for (msg, peer_id) in sent_messages.iter() {
+            let msg: TwoPhaseMessage =
+                Message::parse_from_bytes(msg).expect("failed to parse message");
+            assert_eq!(peer_id, &vec![0].into());
+            assert_eq!(
+                msg.get_proposal_verification_response(),
+                TwoPhaseMessage_ProposalVerificationResponse::VERIFIED
+            );
+            assert_eq!(msg.get_proposal_id(), vec![1].as_slice());
+        }
+        drop(sent_messages);
+
+        update_tx
+            .send(ProposalUpdate::Shutdown)
+            .expect("failed to send shutdown");
+        thread.join().expect("failed to join engine thread");
+    }
 }
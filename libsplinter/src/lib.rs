@@ -31,7 +31,9 @@ extern crate serde_json;
         feature = "authorization-handler-rbac",
         feature = "biome-credentials",
         feature = "biome-key-management",
+        feature = "biome-notifications",
         feature = "biome-profile",
+        feature = "circuit-usage",
         feature = "node-id-store",
         feature = "oauth",
         feature = "registry",
@@ -46,6 +48,12 @@ extern crate diesel_migrations;
 #[macro_use]
 extern crate metrics;
 
+#[cfg(all(
+    feature = "https-bind",
+    not(any(feature = "tls-openssl", feature = "tls-rustls"))
+))]
+compile_error!("the https-bind feature requires either the tls-openssl or tls-rustls feature");
+
 // macros_use must come before any modules that make use of the macro
 #[macro_use]
 pub mod tap;
@@ -85,6 +93,12 @@ macro_rules! mutex_lock_unwrap {
 
 #[cfg(feature = "admin-service")]
 pub mod admin;
+#[cfg(feature = "announcements")]
+pub mod announcement;
+#[cfg(feature = "api-keys")]
+pub mod api_key;
+#[cfg(feature = "audit")]
+pub mod audit;
 mod base62;
 #[cfg(feature = "biome")]
 pub mod biome;
@@ -104,6 +118,8 @@ pub mod network;
 pub mod node_id;
 #[cfg(feature = "oauth")]
 pub mod oauth;
+#[cfg(feature = "otel-tracing")]
+pub mod otel;
 pub mod peer;
 pub mod protocol;
 pub mod protos;
@@ -121,6 +137,7 @@ pub mod rest_api;
 pub mod runtime;
 #[cfg(feature = "service")]
 pub mod service;
+pub mod signing;
 #[cfg(feature = "store")]
 pub mod store;
 pub mod threading;
@@ -0,0 +1,35 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+DROP TABLE IF EXISTS admin_event_proposed_service_argument;
+DROP TABLE IF EXISTS admin_event_proposed_service;
+DROP TABLE IF EXISTS admin_event_proposed_node_endpoint;
+DROP TABLE IF EXISTS admin_event_proposed_node;
+DROP TABLE IF EXISTS admin_event_proposed_circuit;
+DROP TABLE IF EXISTS admin_event_vote_record;
+DROP TABLE IF EXISTS admin_event_circuit_proposal;
+DROP TABLE IF EXISTS admin_service_event;
+DROP TABLE IF EXISTS node_endpoint;
+DROP TABLE IF EXISTS circuit_member;
+DROP TABLE IF EXISTS service_argument;
+DROP TABLE IF EXISTS service;
+DROP TABLE IF EXISTS circuit;
+DROP TABLE IF EXISTS proposed_service_argument;
+DROP TABLE IF EXISTS proposed_service;
+DROP TABLE IF EXISTS proposed_node_endpoint;
+DROP TABLE IF EXISTS proposed_node;
+DROP TABLE IF EXISTS proposed_circuit;
+DROP TABLE IF EXISTS vote_record;
+DROP TABLE IF EXISTS circuit_proposal;
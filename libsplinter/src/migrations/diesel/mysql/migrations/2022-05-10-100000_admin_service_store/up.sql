@@ -0,0 +1,214 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- MySQL cannot index TEXT columns without a prefix length, so the columns
+-- that are part of a primary key are VARCHAR. `key` is a reserved word.
+CREATE TABLE IF NOT EXISTS circuit_proposal (
+    proposal_type             TEXT NOT NULL,
+    circuit_id                VARCHAR(255) PRIMARY KEY,
+    circuit_hash              TEXT NOT NULL,
+    requester                 BLOB NOT NULL,
+    requester_node_id         TEXT NOT NULL
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS vote_record (
+    circuit_id                VARCHAR(255) NOT NULL,
+    public_key                BLOB NOT NULL,
+    vote                      TEXT NOT NULL,
+    voter_node_id             VARCHAR(255) NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (circuit_id, voter_node_id),
+    FOREIGN KEY (circuit_id) REFERENCES circuit_proposal(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS proposed_circuit (
+    circuit_id                VARCHAR(255) NOT NULL,
+    authorization_type        TEXT NOT NULL,
+    persistence               TEXT NOT NULL,
+    durability                TEXT NOT NULL,
+    routes                    TEXT NOT NULL,
+    circuit_management_type   TEXT NOT NULL,
+    application_metadata      BLOB,
+    comments                  TEXT,
+    display_name              TEXT,
+    circuit_version           INTEGER NOT NULL DEFAULT 1,
+    circuit_status            SMALLINT NOT NULL DEFAULT 1,
+    PRIMARY KEY (circuit_id),
+    FOREIGN KEY (circuit_id) REFERENCES circuit_proposal(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS proposed_node (
+    circuit_id                VARCHAR(255) NOT NULL,
+    node_id                   VARCHAR(255) NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    public_key                BLOB,
+    PRIMARY KEY (circuit_id, node_id),
+    FOREIGN KEY (circuit_id) REFERENCES proposed_circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS proposed_node_endpoint (
+    node_id                   VARCHAR(255) NOT NULL,
+    endpoint                  VARCHAR(255) NOT NULL,
+    circuit_id                VARCHAR(255) NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (circuit_id, node_id, endpoint),
+    FOREIGN KEY (circuit_id) REFERENCES proposed_circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS proposed_service (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    service_type              TEXT NOT NULL,
+    node_id                   TEXT NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (circuit_id, service_id),
+    FOREIGN KEY (circuit_id) REFERENCES proposed_circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS proposed_service_argument (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    `key`                     VARCHAR(255) NOT NULL,
+    value                     TEXT NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (circuit_id, service_id, `key`),
+    FOREIGN KEY (circuit_id) REFERENCES proposed_circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS circuit (
+    circuit_id                VARCHAR(255) PRIMARY KEY,
+    authorization_type        TEXT NOT NULL,
+    persistence               TEXT NOT NULL,
+    durability                TEXT NOT NULL,
+    routes                    TEXT NOT NULL,
+    circuit_management_type   TEXT NOT NULL,
+    display_name              TEXT,
+    circuit_version           INTEGER NOT NULL DEFAULT 1,
+    circuit_status            SMALLINT NOT NULL DEFAULT 1
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS service (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    service_type              TEXT NOT NULL,
+    node_id                   TEXT NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (circuit_id, service_id),
+    FOREIGN KEY (circuit_id) REFERENCES circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS service_argument (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    `key`                     VARCHAR(255) NOT NULL,
+    value                     TEXT NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (circuit_id, service_id, `key`),
+    FOREIGN KEY (circuit_id) REFERENCES circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS circuit_member (
+    circuit_id                VARCHAR(255) NOT NULL,
+    node_id                   VARCHAR(255) NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    public_key                BLOB,
+    PRIMARY KEY (circuit_id, node_id),
+    FOREIGN KEY (circuit_id) REFERENCES circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS node_endpoint (
+    node_id                   VARCHAR(255) NOT NULL,
+    endpoint                  VARCHAR(255) NOT NULL,
+    PRIMARY KEY (node_id, endpoint)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_service_event (
+    id                        BIGINT AUTO_INCREMENT PRIMARY KEY,
+    event_type                TEXT NOT NULL,
+    data                      BLOB
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_event_circuit_proposal (
+    event_id                  BIGINT PRIMARY KEY,
+    proposal_type             TEXT NOT NULL,
+    circuit_id                TEXT NOT NULL,
+    circuit_hash              TEXT NOT NULL,
+    requester                 BLOB NOT NULL,
+    requester_node_id         TEXT NOT NULL,
+    FOREIGN KEY (event_id) REFERENCES admin_service_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_event_vote_record (
+    event_id                  BIGINT NOT NULL,
+    public_key                BLOB NOT NULL,
+    vote                      TEXT NOT NULL,
+    voter_node_id             VARCHAR(255) NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_id, voter_node_id),
+    FOREIGN KEY (event_id) REFERENCES admin_service_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_event_proposed_circuit (
+    event_id                  BIGINT PRIMARY KEY,
+    circuit_id                TEXT NOT NULL,
+    authorization_type        TEXT NOT NULL,
+    persistence               TEXT NOT NULL,
+    durability                TEXT NOT NULL,
+    routes                    TEXT NOT NULL,
+    circuit_management_type   TEXT NOT NULL,
+    application_metadata      BLOB,
+    comments                  TEXT,
+    display_name              TEXT,
+    circuit_version           INTEGER NOT NULL DEFAULT 1,
+    circuit_status            SMALLINT NOT NULL DEFAULT 1,
+    FOREIGN KEY (event_id) REFERENCES admin_service_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_event_proposed_node (
+    event_id                  BIGINT NOT NULL,
+    node_id                   VARCHAR(255) NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_id, node_id),
+    FOREIGN KEY (event_id) REFERENCES admin_service_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_event_proposed_node_endpoint (
+    event_id                  BIGINT NOT NULL,
+    node_id                   VARCHAR(255) NOT NULL,
+    endpoint                  VARCHAR(255) NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_id, node_id, endpoint),
+    FOREIGN KEY (event_id) REFERENCES admin_service_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_event_proposed_service (
+    event_id                  BIGINT NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    service_type              TEXT NOT NULL,
+    node_id                   TEXT NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_id, service_id),
+    FOREIGN KEY (event_id) REFERENCES admin_service_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS admin_event_proposed_service_argument (
+    event_id                  BIGINT NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    `key`                     VARCHAR(255) NOT NULL,
+    value                     TEXT NOT NULL,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_id, service_id, `key`),
+    FOREIGN KEY (event_id) REFERENCES admin_service_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
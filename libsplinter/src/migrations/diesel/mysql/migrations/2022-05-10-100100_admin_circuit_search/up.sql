@@ -0,0 +1,27 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Full-text index over the searchable fields of a circuit. The comments and
+-- application metadata of a circuit are only stored on its proposal, so the
+-- entry of a circuit is written by the admin service store when the circuit is
+-- added, while they are still available.
+CREATE TABLE IF NOT EXISTS circuit_search (
+    circuit_id    VARCHAR(255) PRIMARY KEY,
+    display_name  TEXT,
+    comments      TEXT,
+    metadata      TEXT,
+    FULLTEXT INDEX circuit_search_document_idx (display_name, comments, metadata),
+    FOREIGN KEY (circuit_id) REFERENCES circuit(circuit_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
@@ -0,0 +1,25 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- The time each circuit was created, in milliseconds since the UNIX epoch.
+-- Circuits that existed before this migration have no known creation time,
+-- and are listed before all others when circuits are ordered by it.
+ALTER TABLE circuit
+ADD COLUMN created_at BIGINT NOT NULL DEFAULT 0;
+
+CREATE TRIGGER circuit_created_at
+  BEFORE INSERT ON circuit
+  FOR EACH ROW
+  SET NEW.created_at = ROUND(UNIX_TIMESTAMP(NOW(3)) * 1000);
@@ -0,0 +1,41 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tools to apply database migrations for MySQL.
+//!
+//! Only the tables of the admin service store are provided for MySQL.
+
+embed_migrations!("./src/migrations/diesel/mysql/migrations");
+
+use diesel::mysql::MysqlConnection;
+
+use crate::error::InternalError;
+
+/// Run all pending database migrations.
+///
+/// Unlike PostgreSQL and SQLite, MySQL commits schema changes implicitly, so there is no
+/// equivalent of `any_pending_migrations` that checks for pending migrations without applying
+/// them.
+///
+/// # Arguments
+///
+/// * `conn` - Connection to MySQL database
+///
+pub fn run_migrations(conn: &MysqlConnection) -> Result<(), InternalError> {
+    embedded_migrations::run(conn).map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    debug!("Successfully applied Splinter MySQL migrations");
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Full-text index over the searchable fields of a circuit. The comments and
+-- application metadata of a circuit are only stored on its proposal, so the
+-- entry of a circuit is written by the admin service store when the circuit is
+-- added, while they are still available. They are kept alongside the document
+-- so that it can be rebuilt when the circuit is updated.
+CREATE TABLE IF NOT EXISTS circuit_search (
+    circuit_id TEXT PRIMARY KEY,
+    document   TSVECTOR NOT NULL,
+    comments   TEXT,
+    metadata   TEXT,
+    FOREIGN KEY (circuit_id) REFERENCES circuit(circuit_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS circuit_search_document_idx
+  ON circuit_search USING GIN (document);
+
+INSERT INTO circuit_search (circuit_id, document)
+SELECT circuit_id, to_tsvector('simple', coalesce(display_name, ''))
+FROM circuit;
@@ -0,0 +1,26 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+
+CREATE TABLE IF NOT EXISTS audit_event (
+  id             BIGSERIAL   PRIMARY KEY,
+  event_type     TEXT        NOT NULL,
+  actor          TEXT        NOT NULL,
+  target         TEXT        NOT NULL,
+  detail         TEXT        NOT NULL,
+  timestamp      BIGINT      NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_event_timestamp ON audit_event(timestamp);
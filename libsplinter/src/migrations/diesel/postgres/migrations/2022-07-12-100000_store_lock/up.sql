@@ -0,0 +1,25 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Named locks shared by the processes that use the database. A released lock
+-- keeps its row, with no owner, so that the fencing token of its next lease
+-- continues from the last one. Expiry times are in milliseconds since the
+-- UNIX epoch.
+CREATE TABLE IF NOT EXISTS store_lock (
+  name            TEXT        PRIMARY KEY,
+  owner           TEXT,
+  fencing_token   BIGINT      NOT NULL,
+  expires_at      BIGINT      NOT NULL
+);
@@ -0,0 +1,34 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- Full-text index over the searchable fields of a circuit. The comments and
+-- application metadata of a circuit are only stored on its proposal, so the
+-- entry of a circuit is written by the admin service store when the circuit is
+-- added, while they are still available.
+CREATE VIRTUAL TABLE IF NOT EXISTS circuit_search USING fts5(
+    circuit_id UNINDEXED,
+    display_name,
+    comments,
+    metadata
+);
+
+INSERT INTO circuit_search (circuit_id, display_name)
+SELECT circuit_id, display_name FROM circuit;
+
+CREATE TRIGGER circuit_search_delete
+  AFTER DELETE ON circuit
+BEGIN
+  DELETE FROM circuit_search WHERE circuit_id = OLD.circuit_id;
+END;
@@ -0,0 +1,26 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+ALTER TABLE refresh_tokens ADD COLUMN last_updated INTEGER DEFAULT 0 NOT NULL;
+
+-- Existing tokens are considered to have been updated when this migration is run
+UPDATE refresh_tokens SET last_updated = strftime('%s','now');
+
+CREATE INDEX IF NOT EXISTS idx_refresh_tokens_last_updated ON refresh_tokens (
+    last_updated
+);
+CREATE INDEX IF NOT EXISTS idx_oauth_user_sessions_last_authenticated ON oauth_user_sessions (
+    last_authenticated
+);
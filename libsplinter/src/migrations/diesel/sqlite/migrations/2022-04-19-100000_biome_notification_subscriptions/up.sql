@@ -0,0 +1,25 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+CREATE TABLE IF NOT EXISTS biome_notification_subscriptions (
+  user_id       TEXT        NOT NULL,
+  event_type    TEXT        NOT NULL,
+  channel_type  TEXT        NOT NULL,
+  destination   TEXT        NOT NULL,
+  PRIMARY KEY (user_id, event_type, channel_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_biome_notification_subscriptions_event_type
+  ON biome_notification_subscriptions (event_type);
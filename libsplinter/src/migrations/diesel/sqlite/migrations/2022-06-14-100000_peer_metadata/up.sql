@@ -0,0 +1,29 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+
+CREATE TABLE IF NOT EXISTS peer_metadata (
+  peer_id        TEXT        PRIMARY KEY,
+  notes          TEXT        NOT NULL,
+  updated_at     BIGINT      NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS peer_metadata_label (
+  peer_id        TEXT        NOT NULL,
+  label_key      TEXT        NOT NULL,
+  label_value    TEXT        NOT NULL,
+  PRIMARY KEY (peer_id, label_key),
+  FOREIGN KEY (peer_id) REFERENCES peer_metadata(peer_id) ON DELETE CASCADE
+);
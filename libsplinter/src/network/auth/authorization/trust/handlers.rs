@@ -178,7 +178,10 @@ impl Handler for AuthTrustResponseHandler {
             }
         }
 
-        let auth_msg = AuthorizationMessage::AuthComplete(AuthComplete);
+        let capabilities = self.auth_manager.local_capabilities().map_err(|err| {
+            DispatchError::InternalError(InternalError::with_message(err.to_string()))
+        })?;
+        let auth_msg = AuthorizationMessage::AuthComplete(AuthComplete { capabilities });
         let msg_bytes =
             IntoBytes::<network::NetworkMessage>::into_bytes(NetworkMessage::from(auth_msg))?;
         sender
@@ -36,7 +36,8 @@ use crate::protocol::authorization::AuthChallengeNonceRequest;
 #[cfg(feature = "trust-authorization")]
 use crate::protocol::authorization::AuthTrustRequest;
 use crate::protocol::authorization::{
-    AuthProtocolRequest, AuthProtocolResponse, AuthorizationError, PeerAuthorizationType,
+    AuthComplete, AuthProtocolRequest, AuthProtocolResponse, AuthorizationError,
+    PeerAuthorizationType,
 };
 use crate::protocol::network::NetworkMessage;
 use crate::protocol::{PEER_AUTHORIZATION_PROTOCOL_MIN, PEER_AUTHORIZATION_PROTOCOL_VERSION};
@@ -87,6 +88,28 @@ impl Handler for AuthProtocolRequestHandler {
             }
 
             Ok(AuthorizationAcceptingState::ReceivedAuthProtocolRequest) => {
+                // Refuse the connection if the requester's clock is too far from the local clock
+                #[cfg(feature = "clock-skew-detection")]
+                if let Some(monitor) = self.auth_manager.clock_skew_monitor().map_err(|err| {
+                    DispatchError::InternalError(InternalError::with_message(err.to_string()))
+                })? {
+                    if let Err(err) = monitor.check(protocol_request.timestamp) {
+                        warn!(
+                            "Refusing connection {}: {}",
+                            context.source_connection_id(),
+                            err
+                        );
+                        send_authorization_error(
+                            &self.auth_manager,
+                            context.source_id(),
+                            context.source_connection_id(),
+                            sender,
+                            &format!("Clock skew too large: {}", err),
+                        )?;
+                        return Ok(());
+                    }
+                }
+
                 let version = supported_protocol_version(
                     protocol_request.auth_protocol_min,
                     protocol_request.auth_protocol_max,
@@ -437,7 +460,7 @@ impl Handler for AuthCompleteHandler {
 
     fn handle(
         &self,
-        _msg: Self::Message,
+        msg: Self::Message,
         context: &MessageContext<Self::Source, Self::MessageType>,
         sender: &dyn MessageSender<Self::Source>,
     ) -> Result<(), DispatchError> {
@@ -446,9 +469,11 @@ impl Handler for AuthCompleteHandler {
             context.source_connection_id()
         );
 
+        let auth_complete = AuthComplete::from_proto(msg)?;
+
         if let Err(err) = self
             .auth_manager
-            .received_complete(context.source_connection_id())
+            .received_complete(context.source_connection_id(), auth_complete.capabilities)
         {
             send_authorization_error(
                 &self.auth_manager,
@@ -502,6 +527,8 @@ mod tests {
 
     use std::collections::VecDeque;
     use std::sync::{Arc, Mutex};
+    #[cfg(feature = "clock-skew-detection")]
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     #[cfg(feature = "challenge-authorization")]
     use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
@@ -512,6 +539,8 @@ mod tests {
     #[cfg(feature = "challenge-authorization")]
     use crate::network::auth::Identity;
     use crate::network::auth::ManagedAuthorizationState;
+    #[cfg(feature = "clock-skew-detection")]
+    use crate::network::clock_skew::ClockSkewMonitor;
     use crate::protocol::authorization::AuthComplete;
     use crate::protos::network::NetworkMessageType;
     use crate::protos::{authorization, network};
@@ -544,6 +573,7 @@ mod tests {
             AuthorizationMessage::AuthProtocolRequest(AuthProtocolRequest {
                 auth_protocol_min: 1,
                 auth_protocol_max: 1,
+                timestamp: 0,
             }),
         )
         .expect("Unable to get message bytes for auth protocol request");
@@ -616,6 +646,7 @@ mod tests {
             AuthorizationMessage::AuthProtocolRequest(AuthProtocolRequest {
                 auth_protocol_min: 1,
                 auth_protocol_max: 1,
+                timestamp: 0,
             }),
         )
         .expect("Unable to get message bytes");
@@ -648,6 +679,72 @@ mod tests {
         );
     }
 
+    /// Test that an auth protocol request is rejected when the clock of the requesting node is
+    /// beyond the maximum skew
+    ///
+    /// This is verified by:
+    ///
+    /// 1) no error from the dispatcher
+    /// 2) the handler should send an AuthorizationError instead of an AuthProtocolResponse
+    #[test]
+    #[cfg(all(feature = "trust-authorization", feature = "clock-skew-detection"))]
+    fn protocol_request_dispatch_clock_skew() {
+        let auth_mgr = AuthorizationManagerStateMachine::default();
+        auth_mgr
+            .shared
+            .lock()
+            .expect("lock poisoned")
+            .clock_skew_monitor =
+            Some(ClockSkewMonitor::new().with_max_skew(Duration::from_secs(5)));
+        let mock_sender = MockSender::new();
+        let dispatch_sender = mock_sender.clone();
+
+        // mut is required if chalenge authorization is enabled
+        #[allow(unused_mut)]
+        let mut dispatcher_builder =
+            AuthorizationDispatchBuilder::new().with_identity("mock_identity");
+
+        let dispatcher = dispatcher_builder
+            .build(dispatch_sender, auth_mgr)
+            .expect("Unable to build authorization dispatcher");
+
+        // The requesting node's clock is an hour ahead
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("Unable to get time")
+            .as_millis() as u64
+            + 60 * 60 * 1000;
+        let msg_bytes = IntoBytes::<authorization::AuthorizationMessage>::into_bytes(
+            AuthorizationMessage::AuthProtocolRequest(AuthProtocolRequest {
+                auth_protocol_min: 1,
+                auth_protocol_max: 1,
+                timestamp,
+            }),
+        )
+        .expect("Unable to get message bytes for auth protocol request");
+
+        assert!(dispatcher
+            .dispatch(
+                "test_connection".into(),
+                &NetworkMessageType::AUTHORIZATION,
+                msg_bytes
+            )
+            .is_ok());
+
+        let (_, message_bytes) = mock_sender
+            .next_outbound()
+            .expect("Unable to receive message over the network");
+
+        let auth_error: authorization::AuthorizationError = expect_auth_message(
+            authorization::AuthorizationMessageType::AUTHORIZATION_ERROR,
+            &message_bytes,
+        );
+        assert_eq!(
+            authorization::AuthorizationError_AuthorizationErrorType::AUTHORIZATION_REJECTED,
+            auth_error.get_error_type()
+        );
+    }
+
     /// Test that an AuthComplete is properly handled. Also verify state is set to
     /// AuthorizedAndComplete
     ///
@@ -690,7 +787,7 @@ mod tests {
             .expect("Unable to build authorization dispatcher");
 
         let msg_bytes = IntoBytes::<authorization::AuthorizationMessage>::into_bytes(
-            AuthorizationMessage::AuthComplete(AuthComplete),
+            AuthorizationMessage::AuthComplete(AuthComplete::default()),
         )
         .expect("Unable to get message bytes");
 
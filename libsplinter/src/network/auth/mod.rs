@@ -25,6 +25,11 @@ use std::sync::{mpsc, Arc, Mutex};
 use cylinder::{Signer, VerifierFactory};
 use protobuf::Message;
 
+#[cfg(feature = "clock-skew-detection")]
+use crate::network::clock_skew::ClockSkewMonitor;
+#[cfg(feature = "peer-blocklist")]
+use crate::peer::blocklist::PeerBlocklist;
+use crate::peer::{NodeCapabilities, PeerAuthorizationToken, PeerCapabilitiesRegistry};
 #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
 use crate::protocol::authorization::AuthProtocolRequest;
 use crate::protocol::authorization::AuthorizationMessage;
@@ -32,7 +37,9 @@ use crate::protocol::authorization::AuthorizationMessage;
 use crate::protocol::authorization::ConnectRequest;
 use crate::protocol::network::NetworkMessage;
 #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
-use crate::protocol::{PEER_AUTHORIZATION_PROTOCOL_MIN, PEER_AUTHORIZATION_PROTOCOL_VERSION};
+use crate::protocol::{
+    current_timestamp, PEER_AUTHORIZATION_PROTOCOL_MIN, PEER_AUTHORIZATION_PROTOCOL_VERSION,
+};
 use crate::protos::network;
 use crate::protos::prelude::*;
 use crate::public_key::PublicKey;
@@ -94,6 +101,7 @@ pub struct AuthorizationManager {
     shared: Arc<Mutex<ManagedAuthorizations>>,
     #[cfg(feature = "challenge-authorization")]
     verifier_factory: Arc<Mutex<Box<dyn VerifierFactory>>>,
+    capabilities_registry: PeerCapabilitiesRegistry,
 }
 
 impl AuthorizationManager {
@@ -121,9 +129,58 @@ impl AuthorizationManager {
             shared,
             #[cfg(feature = "challenge-authorization")]
             verifier_factory,
+            capabilities_registry: PeerCapabilitiesRegistry::new(),
         })
     }
 
+    /// Sets the capabilities the local node advertises to its peers once authorization is
+    /// complete.
+    pub fn with_local_capabilities(
+        self,
+        capabilities: NodeCapabilities,
+    ) -> Result<Self, AuthorizationManagerError> {
+        self.shared
+            .lock()
+            .map_err(|_| AuthorizationManagerError("Authorization pool lock was poisoned".into()))?
+            .local_capabilities = Some(capabilities);
+
+        Ok(self)
+    }
+
+    /// Refuses connections from nodes whose clocks are beyond the maximum skew of the given
+    /// monitor, based on the timestamps of their authorization protocol requests.
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn with_clock_skew_monitor(
+        self,
+        monitor: ClockSkewMonitor,
+    ) -> Result<Self, AuthorizationManagerError> {
+        self.shared
+            .lock()
+            .map_err(|_| AuthorizationManagerError("Authorization pool lock was poisoned".into()))?
+            .clock_skew_monitor = Some(monitor);
+
+        Ok(self)
+    }
+
+    /// Refuses connections from the IP addresses and public keys blocked in the given blocklist.
+    #[cfg(feature = "peer-blocklist")]
+    pub fn with_peer_blocklist(
+        self,
+        blocklist: PeerBlocklist,
+    ) -> Result<Self, AuthorizationManagerError> {
+        self.shared
+            .lock()
+            .map_err(|_| AuthorizationManagerError("Authorization pool lock was poisoned".into()))?
+            .peer_blocklist = Some(blocklist);
+
+        Ok(self)
+    }
+
+    /// Returns the registry of the capabilities advertised by authorized peers.
+    pub fn peer_capabilities_registry(&self) -> PeerCapabilitiesRegistry {
+        self.capabilities_registry.clone()
+    }
+
     pub fn shutdown_signaler(&self) -> ShutdownSignaler {
         ShutdownSignaler {
             thread_pool_signaler: self.thread_pool.shutdown_signaler(),
@@ -143,6 +200,7 @@ impl AuthorizationManager {
             executor: self.thread_pool.executor(),
             #[cfg(feature = "challenge-authorization")]
             verifier_factory: self.verifier_factory.clone(),
+            capabilities_registry: self.capabilities_registry.clone(),
         }
     }
 }
@@ -168,6 +226,7 @@ pub struct AuthorizationConnector {
     executor: JobExecutor,
     #[cfg(feature = "challenge-authorization")]
     verifier_factory: Arc<Mutex<Box<dyn VerifierFactory>>>,
+    capabilities_registry: PeerCapabilitiesRegistry,
 }
 
 impl AuthorizationConnector {
@@ -187,6 +246,14 @@ impl AuthorizationConnector {
             shared: Arc::clone(&self.shared),
         };
         let msg_sender = AuthorizationMessageSender { sender: tx };
+        let capabilities_registry = self.capabilities_registry.clone();
+        #[cfg(feature = "peer-blocklist")]
+        let peer_blocklist = self
+            .shared
+            .lock()
+            .map_err(|_| AuthorizationManagerError("Authorization pool lock was poisoned".into()))?
+            .peer_blocklist
+            .clone();
 
         // mut is required if chalenge authorization is enabled
         #[allow(unused_mut, clippy::redundant_clone)]
@@ -243,6 +310,23 @@ impl AuthorizationConnector {
             })?;
 
         self.executor.execute(move || {
+            #[cfg(feature = "peer-blocklist")]
+            {
+                let endpoint = connection.remote_endpoint();
+                if let Some(blocklist) = &peer_blocklist {
+                    if is_blocked(blocklist.check_endpoint(&endpoint), &endpoint) {
+                        let auth_state = ConnectionAuthorizationState::Unauthorized {
+                            connection_id,
+                            connection,
+                        };
+                        if let Err(err) = on_complete_callback(auth_state) {
+                            error!("unable to pass auth result to callback: {}", err);
+                        }
+                        return;
+                    }
+                }
+            }
+
             #[cfg(not(any(feature = "trust-authorization", feature = "challenge-authorization")))]
             {
                 let connect_request_bytes = match connect_msg_bytes() {
@@ -298,6 +382,7 @@ impl AuthorizationConnector {
                 };
             }
 
+            let mut remote_capabilities = None;
             let authed_identities = 'main: loop {
                 match connection.recv() {
                     Ok(bytes) => {
@@ -359,10 +444,21 @@ impl AuthorizationConnector {
                 };
 
                 if let Some(true) = shared.is_complete(&connection_id) {
+                    remote_capabilities = shared.remote_capabilities.remove(&connection_id);
                     break 'main shared.take_connection_identity(&connection_id);
                 }
             };
 
+            // capabilities received on a connection that failed authorization are never used
+            if authed_identities.is_none() {
+                match connection_shared.lock() {
+                    Ok(mut shared) => {
+                        shared.remote_capabilities.remove(&connection_id);
+                    }
+                    Err(_) => error!("connection authorization lock poisoned"),
+                }
+            }
+
             // allow unused variable if challenge-authorization is not enabled
             #[allow(unused_variables)]
             let auth_state = if let Some((auth_identity, local_authorization)) = authed_identities {
@@ -398,6 +494,44 @@ impl AuthorizationConnector {
                 }
             };
 
+            #[cfg(all(feature = "peer-blocklist", feature = "challenge-authorization"))]
+            let auth_state = match (auth_state, &peer_blocklist) {
+                (
+                    ConnectionAuthorizationState::Authorized {
+                        connection_id,
+                        connection,
+                        identity: ConnectionAuthorizationType::Challenge { public_key },
+                        ..
+                    },
+                    Some(blocklist),
+                ) if is_blocked(
+                    blocklist.check_public_key(&public_key),
+                    &crate::hex::to_hex(public_key.as_slice()),
+                ) =>
+                {
+                    ConnectionAuthorizationState::Unauthorized {
+                        connection_id,
+                        connection,
+                    }
+                }
+                (auth_state, _) => auth_state,
+            };
+
+            if let ConnectionAuthorizationState::Authorized {
+                connection_id,
+                identity,
+                ..
+            } = &auth_state
+            {
+                let peer_id = PeerAuthorizationToken::from(identity.clone());
+                match remote_capabilities {
+                    Some(capabilities) => {
+                        capabilities_registry.insert(peer_id, connection_id, capabilities)
+                    }
+                    None => capabilities_registry.remove(&peer_id),
+                }
+            }
+
             if let Err(err) = on_complete_callback(auth_state) {
                 error!("unable to pass auth result to callback: {}", err);
             }
@@ -407,6 +541,35 @@ impl AuthorizationConnector {
     }
 }
 
+/// Returns whether the result of a blocklist check refuses the connection. A connection is not
+/// refused if the blocklist cannot be checked, so that an unavailable store does not stop the node
+/// from connecting to its peers.
+#[cfg(feature = "peer-blocklist")]
+fn is_blocked(
+    result: Result<
+        Option<crate::peer::blocklist::BlockedPeer>,
+        crate::peer::blocklist::store::PeerBlocklistStoreError,
+    >,
+    remote: &str,
+) -> bool {
+    match result {
+        Ok(Some(block)) => {
+            warn!(
+                "Refusing connection from blocked {} ({}): {}",
+                block.target(),
+                remote,
+                block.reason()
+            );
+            true
+        }
+        Ok(None) => false,
+        Err(err) => {
+            error!("Unable to check the peer blocklist for {}: {}", remote, err);
+            false
+        }
+    }
+}
+
 #[cfg(not(any(feature = "trust-authorization", feature = "challenge-authorization")))]
 fn connect_msg_bytes() -> Result<Vec<u8>, AuthorizationManagerError> {
     let connect_msg = AuthorizationMessage::ConnectRequest(ConnectRequest::Bidirectional);
@@ -421,6 +584,7 @@ fn protocol_msg_bytes() -> Result<Vec<u8>, AuthorizationManagerError> {
     let protocol_msg = AuthorizationMessage::AuthProtocolRequest(AuthProtocolRequest {
         auth_protocol_min: PEER_AUTHORIZATION_PROTOCOL_MIN,
         auth_protocol_max: PEER_AUTHORIZATION_PROTOCOL_VERSION,
+        timestamp: current_timestamp(),
     });
 
     IntoBytes::<network::NetworkMessage>::into_bytes(NetworkMessage::from(protocol_msg)).map_err(
@@ -442,12 +606,28 @@ impl AuthorizationMessageSender {
 #[derive(Default)]
 pub struct ManagedAuthorizations {
     states: HashMap<String, ManagedAuthorizationState>,
+    // The capabilities advertised to remote nodes
+    local_capabilities: Option<NodeCapabilities>,
+    // The capabilities advertised by remote nodes, by connection ID
+    remote_capabilities: HashMap<String, NodeCapabilities>,
+    // Checks the clocks of nodes requesting authorization
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_monitor: Option<ClockSkewMonitor>,
+    // Refuses connections from blocked addresses and public keys
+    #[cfg(feature = "peer-blocklist")]
+    peer_blocklist: Option<PeerBlocklist>,
 }
 
 impl ManagedAuthorizations {
     fn new() -> Self {
         Self {
             states: HashMap::new(),
+            local_capabilities: None,
+            remote_capabilities: HashMap::new(),
+            #[cfg(feature = "clock-skew-detection")]
+            clock_skew_monitor: None,
+            #[cfg(feature = "peer-blocklist")]
+            peer_blocklist: None,
         }
     }
 
@@ -657,6 +837,7 @@ pub(in crate::network) mod tests {
             AuthorizationMessage::AuthProtocolRequest(AuthProtocolRequest {
                 auth_protocol_min: PEER_AUTHORIZATION_PROTOCOL_MIN,
                 auth_protocol_max: PEER_AUTHORIZATION_PROTOCOL_VERSION,
+                timestamp: current_timestamp(),
             }),
         );
         mesh.send(env).expect("Unable to send protocol request");
@@ -725,7 +906,7 @@ pub(in crate::network) mod tests {
         // send auth complete
         let env = write_auth_message(
             connection_id,
-            AuthorizationMessage::AuthComplete(AuthComplete),
+            AuthorizationMessage::AuthComplete(AuthComplete::default()),
         );
         mesh.send(env).expect("unable to send authorized");
     }
@@ -20,6 +20,13 @@ pub mod trust_v1;
 use std::fmt;
 use std::sync::{Arc, Mutex};
 
+#[cfg(all(
+    feature = "clock-skew-detection",
+    any(feature = "trust-authorization", feature = "challenge-authorization")
+))]
+use crate::network::clock_skew::ClockSkewMonitor;
+#[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
+use crate::peer::NodeCapabilities;
 #[cfg(feature = "challenge-authorization")]
 use crate::public_key::PublicKey;
 
@@ -521,10 +528,13 @@ impl AuthorizationManagerStateMachine {
         }
     }
 
+    /// Records that the remote node has completed authorization, along with the capabilities it
+    /// advertised, if any.
     #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
     pub(crate) fn received_complete(
         &self,
         connection_id: &str,
+        capabilities: Option<NodeCapabilities>,
     ) -> Result<(), AuthorizationActionError> {
         let mut shared = self.shared.lock().map_err(|_| {
             AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
@@ -546,9 +556,42 @@ impl AuthorizationManagerStateMachine {
         if cur_state.initiating_state == AuthorizationInitiatingState::WaitForComplete {
             cur_state.initiating_state = AuthorizationInitiatingState::AuthorizedAndComplete;
         }
+
+        if let Some(capabilities) = capabilities {
+            shared
+                .remote_capabilities
+                .insert(connection_id.to_string(), capabilities);
+        }
         Ok(())
     }
 
+    /// Returns the capabilities the local node advertises to remote nodes, if any.
+    #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
+    pub(crate) fn local_capabilities(
+        &self,
+    ) -> Result<Option<NodeCapabilities>, AuthorizationActionError> {
+        let shared = self.shared.lock().map_err(|_| {
+            AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
+        })?;
+
+        Ok(shared.local_capabilities.clone())
+    }
+
+    /// Returns the monitor used to check the clocks of nodes requesting authorization, if any.
+    #[cfg(all(
+        feature = "clock-skew-detection",
+        any(feature = "trust-authorization", feature = "challenge-authorization")
+    ))]
+    pub(crate) fn clock_skew_monitor(
+        &self,
+    ) -> Result<Option<ClockSkewMonitor>, AuthorizationActionError> {
+        let shared = self.shared.lock().map_err(|_| {
+            AuthorizationActionError::InternalError("Authorization pool lock was poisoned".into())
+        })?;
+
+        Ok(shared.clock_skew_monitor.clone())
+    }
+
     pub(crate) fn set_local_authorization(
         &self,
         connection_id: &str,
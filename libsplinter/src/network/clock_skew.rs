@@ -0,0 +1,293 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Clock skew detection: comparing the clocks of peers with the local clock.
+//!
+//! Nodes include their time in the heartbeats they send and in their authorization protocol
+//! requests. A [`ClockSkewMonitor`] compares these timestamps with the local clock. Skew between
+//! nodes breaks the expiry checks of JWTs and the timeouts of consensus in ways that are hard to
+//! diagnose, so a warning is logged when the skew of a peer goes beyond the warning threshold,
+//! and, if a maximum is set, connections from nodes whose skew is beyond it are refused during
+//! authorization.
+//!
+//! The measured skew includes the time the message took to reach the local node, so thresholds
+//! should be well above the expected network latency. Timestamps of 0, which are sent by nodes
+//! that do not include their time, are ignored.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::protocol::current_timestamp;
+
+const DEFAULT_WARN_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Compares the timestamps received from peers with the local clock.
+///
+/// The monitor is cheap to clone; clones share the most recent skew of each peer.
+#[derive(Clone)]
+pub struct ClockSkewMonitor {
+    warn_threshold: Duration,
+    max_skew: Option<Duration>,
+    skews: Arc<Mutex<HashMap<String, i64>>>,
+}
+
+impl ClockSkewMonitor {
+    /// Creates a monitor that warns about peers whose skew is beyond five seconds, but does not
+    /// refuse connections.
+    pub fn new() -> Self {
+        Self {
+            warn_threshold: DEFAULT_WARN_THRESHOLD,
+            max_skew: None,
+            skews: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Sets the skew beyond which a warning is logged.
+    pub fn with_warn_threshold(mut self, warn_threshold: Duration) -> Self {
+        self.warn_threshold = warn_threshold;
+        self
+    }
+
+    /// Refuses connections from nodes whose skew is beyond the given maximum.
+    pub fn with_max_skew(mut self, max_skew: Duration) -> Self {
+        self.max_skew = Some(max_skew);
+        self
+    }
+
+    pub fn warn_threshold(&self) -> Duration {
+        self.warn_threshold
+    }
+
+    pub fn max_skew(&self) -> Option<Duration> {
+        self.max_skew
+    }
+
+    /// Checks the timestamp of a node that is requesting authorization.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`ClockSkewError`] if the skew of the node is beyond the maximum.
+    pub fn check(&self, timestamp: u64) -> Result<(), ClockSkewError> {
+        self.check_at(timestamp, current_timestamp())
+    }
+
+    /// Records the timestamp of a heartbeat received from the given peer.
+    ///
+    /// A warning is logged when the skew of the peer goes beyond the warning threshold or the
+    /// maximum, and a message is logged when it is back within the warning threshold.
+    pub fn record(&self, peer_id: &str, timestamp: u64) {
+        self.record_at(peer_id, timestamp, current_timestamp());
+    }
+
+    /// Returns the most recently recorded skew of the given peer, in milliseconds. The skew is
+    /// positive if the peer's clock is ahead of the local clock.
+    pub fn peer_skew(&self, peer_id: &str) -> Option<i64> {
+        match self.skews.lock() {
+            Ok(skews) => skews.get(peer_id).copied(),
+            Err(_) => {
+                error!("Clock skew lock was poisoned");
+                None
+            }
+        }
+    }
+
+    fn check_at(&self, timestamp: u64, now: u64) -> Result<(), ClockSkewError> {
+        if timestamp == 0 {
+            return Ok(());
+        }
+
+        let skew = skew(timestamp, now);
+        match self.level(skew) {
+            SkewLevel::Exceeded => Err(ClockSkewError {
+                skew,
+                max_skew: self.max_skew.unwrap_or_default(),
+            }),
+            SkewLevel::Warn => {
+                warn!(
+                    "Clock of connecting node is {}; JWT expiry and consensus timeouts may \
+                     not behave as expected",
+                    describe(skew)
+                );
+                Ok(())
+            }
+            SkewLevel::Within => Ok(()),
+        }
+    }
+
+    fn record_at(&self, peer_id: &str, timestamp: u64, now: u64) -> Option<SkewLevel> {
+        if timestamp == 0 {
+            return None;
+        }
+
+        let skew = skew(timestamp, now);
+        let level = self.level(skew);
+
+        let previous_level = match self.skews.lock() {
+            Ok(mut skews) => skews
+                .insert(peer_id.to_string(), skew)
+                .map(|previous| self.level(previous)),
+            Err(_) => {
+                error!("Clock skew lock was poisoned");
+                return None;
+            }
+        };
+
+        // Only log when the level changes, as heartbeats are frequent
+        if previous_level != Some(level) {
+            match level {
+                SkewLevel::Exceeded => warn!(
+                    "Clock of peer {} is {}, beyond the maximum of {}ms; the connection will be \
+                     refused when it is next authorized",
+                    peer_id,
+                    describe(skew),
+                    self.max_skew.unwrap_or_default().as_millis()
+                ),
+                SkewLevel::Warn => warn!(
+                    "Clock of peer {} is {}; JWT expiry and consensus timeouts may not behave \
+                     as expected",
+                    peer_id,
+                    describe(skew)
+                ),
+                SkewLevel::Within if previous_level.is_some() => info!(
+                    "Clock of peer {} is back within {}ms of the local clock",
+                    peer_id,
+                    self.warn_threshold.as_millis()
+                ),
+                SkewLevel::Within => (),
+            }
+        }
+
+        Some(level)
+    }
+
+    fn level(&self, skew: i64) -> SkewLevel {
+        let magnitude = u128::from(skew.unsigned_abs());
+        match self.max_skew {
+            Some(max_skew) if magnitude > max_skew.as_millis() => SkewLevel::Exceeded,
+            _ if magnitude > self.warn_threshold.as_millis() => SkewLevel::Warn,
+            _ => SkewLevel::Within,
+        }
+    }
+}
+
+impl Default for ClockSkewMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SkewLevel {
+    Within,
+    Warn,
+    Exceeded,
+}
+
+/// Returns the skew of a remote timestamp from the local time, in milliseconds.
+fn skew(timestamp: u64, now: u64) -> i64 {
+    (i128::from(timestamp) - i128::from(now)).clamp(i128::from(i64::MIN), i128::from(i64::MAX))
+        as i64
+}
+
+fn describe(skew: i64) -> String {
+    if skew < 0 {
+        format!("{}ms behind the local clock", skew.unsigned_abs())
+    } else {
+        format!("{}ms ahead of the local clock", skew)
+    }
+}
+
+/// Returned when the clock of a node is too far from the local clock
+#[derive(Debug)]
+pub struct ClockSkewError {
+    skew: i64,
+    max_skew: Duration,
+}
+
+impl ClockSkewError {
+    /// Returns the skew of the node, in milliseconds; positive if its clock is ahead.
+    pub fn skew(&self) -> i64 {
+        self.skew
+    }
+}
+
+impl Error for ClockSkewError {}
+
+impl fmt::Display for ClockSkewError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "clock is {}, beyond the maximum skew of {}ms",
+            describe(self.skew),
+            self.max_skew.as_millis()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 1_000_000;
+
+    /// Verify that connecting nodes are only refused when their skew, in either direction, is
+    /// beyond the maximum, and that timestamps of 0 are ignored.
+    #[test]
+    fn check_connecting_node() {
+        let monitor = ClockSkewMonitor::new();
+        assert!(monitor.check_at(NOW + 60_000, NOW).is_ok());
+
+        let monitor = monitor.with_max_skew(Duration::from_secs(5));
+        assert!(monitor.check_at(NOW + 3_000, NOW).is_ok());
+        assert!(monitor.check_at(0, NOW).is_ok());
+
+        let err = monitor
+            .check_at(NOW + 6_000, NOW)
+            .expect_err("Node ahead should be refused");
+        assert_eq!(err.skew(), 6_000);
+        let err = monitor
+            .check_at(NOW - 6_000, NOW)
+            .expect_err("Node behind should be refused");
+        assert_eq!(err.skew(), -6_000);
+    }
+
+    /// Verify that the skew of each peer is recorded at the expected level.
+    #[test]
+    fn record_peer_skew() {
+        let monitor = ClockSkewMonitor::new()
+            .with_warn_threshold(Duration::from_secs(1))
+            .with_max_skew(Duration::from_secs(5));
+
+        assert_eq!(
+            monitor.record_at("peer_1", NOW - 500, NOW),
+            Some(SkewLevel::Within)
+        );
+        assert_eq!(
+            monitor.record_at("peer_1", NOW - 2_000, NOW),
+            Some(SkewLevel::Warn)
+        );
+        assert_eq!(
+            monitor.record_at("peer_2", NOW + 10_000, NOW),
+            Some(SkewLevel::Exceeded)
+        );
+        assert_eq!(monitor.record_at("peer_3", 0, NOW), None);
+
+        assert_eq!(monitor.peer_skew("peer_1"), Some(-2_000));
+        assert_eq!(monitor.clone().peer_skew("peer_2"), Some(10_000));
+        assert_eq!(monitor.peer_skew("peer_3"), None);
+    }
+}
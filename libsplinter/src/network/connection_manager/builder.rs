@@ -411,10 +411,10 @@ fn send_heartbeats<T: ConnectionMatrixLifeCycle, U: ConnectionMatrixSender>(
     }
 }
 
-/// Creates NetworkHeartbeat message and serializes it into a byte array.
+/// Creates NetworkHeartbeat message with the current time and serializes it into a byte array.
 fn create_heartbeat() -> Result<Vec<u8>, ConnectionManagerError> {
     IntoBytes::<network::NetworkMessage>::into_bytes(NetworkMessage::NetworkHeartbeat(
-        NetworkHeartbeat,
+        NetworkHeartbeat::now(),
     ))
     .map_err(|_| {
         ConnectionManagerError::HeartbeatError("cannot create NetworkHeartbeat message".to_string())
@@ -188,6 +188,15 @@ impl ConnectionManager {
             sender: self.sender.clone(),
         }
     }
+
+    /// Change the interval, in seconds, at which heartbeats are sent to connections.
+    pub fn set_heartbeat_interval(&self, interval: u64) {
+        debug!(
+            "Changing connection manager heartbeat interval to {}s",
+            interval
+        );
+        self.pacemaker.set_interval(interval);
+    }
 }
 
 /// Connector is a client or handle to the connection manager and is used to
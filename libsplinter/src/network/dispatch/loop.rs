@@ -37,6 +37,10 @@ where
         message_bytes: Vec<u8>,
         source_id: Source,
         parent_context: Option<Box<dyn Any + Send>>,
+        /// The trace context of the thread that sent the message, so that the span of its
+        /// dispatch continues the sender's trace
+        #[cfg(feature = "otel-tracing")]
+        trace_context: opentelemetry::Context,
     },
     Shutdown,
 }
@@ -122,7 +126,11 @@ where
                         message_bytes,
                         source_id,
                         parent_context: Some(context),
+                        #[cfg(feature = "otel-tracing")]
+                        trace_context,
                     }) => {
+                        #[cfg(feature = "otel-tracing")]
+                        let _trace_context = trace_context.attach();
                         if let Err(err) = dispatcher.dispatch_with_parent_context(
                             source_id,
                             &message_type,
@@ -137,7 +145,11 @@ where
                         message_bytes,
                         source_id,
                         parent_context: None,
+                        #[cfg(feature = "otel-tracing")]
+                        trace_context,
                     }) => {
+                        #[cfg(feature = "otel-tracing")]
+                        let _trace_context = trace_context.attach();
                         if let Err(err) =
                             dispatcher.dispatch(source_id, &message_type, message_bytes)
                         {
@@ -266,6 +278,8 @@ where
                 message_bytes,
                 source_id,
                 parent_context: None,
+                #[cfg(feature = "otel-tracing")]
+                trace_context: opentelemetry::Context::current(),
             })
             .map_err(|err| match err.0 {
                 DispatchMessage::Message {
@@ -291,6 +305,8 @@ where
                 message_bytes,
                 source_id,
                 parent_context: Some(parent_context),
+                #[cfg(feature = "otel-tracing")]
+                trace_context: opentelemetry::Context::current(),
             })
             .map_err(|err| match err.0 {
                 DispatchMessage::Message {
@@ -298,6 +314,7 @@ where
                     message_bytes,
                     source_id,
                     parent_context: Some(pc),
+                    ..
                 } => (message_type, message_bytes, source_id, pc),
                 _ => unreachable!(), // we didn't anything else
             })
@@ -356,6 +356,9 @@ where
     }
 
     fn execute(&self, ctx: MessageContext<Source, MT>) -> Result<(), DispatchError> {
+        #[cfg(feature = "otel-tracing")]
+        let _span = crate::otel::enter_span(format!("dispatch {:?}", ctx.message_type()), &[]);
+
         self.handlers
             .get(ctx.message_type())
             .ok_or_else(|| {
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "clock-skew-detection")]
+use crate::network::clock_skew::ClockSkewMonitor;
 use crate::network::dispatch::{DispatchError, Handler, MessageContext, MessageSender, PeerId};
 use crate::peer::{PeerAuthorizationToken, PeerTokenPair};
 use crate::protocol::network::{NetworkEcho, NetworkMessage};
@@ -85,7 +87,10 @@ impl NetworkEchoHandler {
 
 // Implements a handler that handles NetworkHeartbeat Messages
 #[derive(Default)]
-pub struct NetworkHeartbeatHandler {}
+pub struct NetworkHeartbeatHandler {
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_monitor: Option<ClockSkewMonitor>,
+}
 
 impl Handler for NetworkHeartbeatHandler {
     type Source = PeerId;
@@ -103,13 +108,26 @@ impl Handler for NetworkHeartbeatHandler {
         _sender: &dyn MessageSender<Self::Source>,
     ) -> Result<(), DispatchError> {
         trace!("Received Heartbeat from {}", context.source_peer_id());
+
+        #[cfg(feature = "clock-skew-detection")]
+        if let Some(monitor) = &self.clock_skew_monitor {
+            monitor.record(&context.source_peer_id().to_string(), _msg.get_timestamp());
+        }
+
         Ok(())
     }
 }
 
 impl NetworkHeartbeatHandler {
     pub fn new() -> Self {
-        NetworkHeartbeatHandler {}
+        NetworkHeartbeatHandler::default()
+    }
+
+    /// Records the clock skew of peers from the timestamps of their heartbeats.
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn with_clock_skew_monitor(mut self, monitor: ClockSkewMonitor) -> Self {
+        self.clock_skew_monitor = Some(monitor);
+        self
     }
 }
 
@@ -15,6 +15,8 @@
 //! Network connection management and message dispatching framework.
 
 pub mod auth;
+#[cfg(feature = "clock-skew-detection")]
+pub mod clock_skew;
 pub mod connection_manager;
 pub mod dispatch;
 pub mod handlers;
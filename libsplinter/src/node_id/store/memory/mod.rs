@@ -0,0 +1,83 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A NodeIdStore backed by memory.
+
+use std::sync::{Arc, Mutex};
+
+use crate::error::InternalError;
+
+use super::NodeIdStore;
+use super::NodeIdStoreError;
+
+/// A [NodeIdStore] backed by memory.
+/// The node_id is shared by all clones of the store, and is lost when the last one is dropped.
+#[derive(Clone, Default)]
+pub struct MemoryNodeIdStore {
+    node_id: Arc<Mutex<Option<String>>>,
+}
+
+impl MemoryNodeIdStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NodeIdStore for MemoryNodeIdStore {
+    fn get_node_id(&self) -> Result<Option<String>, NodeIdStoreError> {
+        Ok(self
+            .node_id
+            .lock()
+            .map_err(|_| InternalError::with_message("node_id lock poisoned".into()))?
+            .clone())
+    }
+
+    fn set_node_id(&self, node_id: String) -> Result<(), NodeIdStoreError> {
+        *self
+            .node_id
+            .lock()
+            .map_err(|_| InternalError::with_message("node_id lock poisoned".into()))? =
+            Some(node_id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that a node_id that is set is returned by every clone of the store.
+    #[test]
+    fn set_and_get_node_id() {
+        let store = MemoryNodeIdStore::new();
+        let clone = store.clone();
+        assert_eq!(store.get_node_id().expect("Failed to get node_id"), None);
+
+        store
+            .set_node_id("node-1".into())
+            .expect("Failed to set node_id");
+        assert_eq!(
+            clone.get_node_id().expect("Failed to get node_id"),
+            Some("node-1".into())
+        );
+
+        clone
+            .set_node_id("node-2".into())
+            .expect("Failed to set node_id");
+        assert_eq!(
+            store.get_node_id().expect("Failed to get node_id"),
+            Some("node-2".into())
+        );
+    }
+}
@@ -16,6 +16,8 @@
 pub mod diesel;
 pub mod error;
 pub mod file;
+#[cfg(feature = "memory")]
+pub mod memory;
 
 use error::NodeIdStoreError;
 
@@ -0,0 +1,336 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Distributed tracing with OpenTelemetry.
+//!
+//! Spans are created for message dispatch, admin service proposals and, in scabbard, the
+//! lifecycle of each batch. The trace context of the current span is carried in the
+//! `trace_context` field of network messages, so that the spans created by a peer while handling a
+//! message join the sender's trace.
+//!
+//! Until `init` is called, spans are not recorded and no trace context is sent. Ended spans are
+//! queued and exported in batches by a separate thread, so that ending a span never waits for the
+//! collector; spans that end while the queue is full are dropped.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::mpsc::{channel, sync_channel, RecvTimeoutError, Sender, SyncSender, TrySendError};
+use std::sync::Mutex;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use opentelemetry::{
+    global,
+    sdk::{
+        export::trace::{SpanData, SpanExporter},
+        propagation::TraceContextPropagator,
+        trace::{self as sdktrace, SpanProcessor},
+        Resource,
+    },
+    trace::{
+        mark_span_as_active, Span as _, StatusCode, TraceContextExt, TraceError, TraceResult,
+        Tracer,
+    },
+    Context, ContextGuard, KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+
+use crate::error::InternalError;
+use crate::protos::network::NetworkMessage;
+
+/// The name of the tracer that creates all spans
+const TRACER_NAME: &str = "splinter";
+
+/// The most ended spans that may wait to be exported
+const MAX_QUEUED_SPANS: usize = 2048;
+
+/// The most spans exported in one request to the collector
+const MAX_EXPORT_BATCH_SIZE: usize = 512;
+
+/// How long ended spans may wait before they are exported, if there are too few to fill a batch
+const EXPORT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Exports spans to an OTLP collector and propagates trace context in the W3C format.
+///
+/// # Arguments
+///
+/// * `endpoint` - The URL of the collector's OTLP/HTTP traces endpoint, such as
+///   `http://localhost:4318/v1/traces`
+/// * `service_name` - The `service.name` of the exported spans, such as `splinterd`
+pub fn init(endpoint: &str, service_name: &str) -> Result<(), InternalError> {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let exporter = opentelemetry_otlp::new_exporter()
+        .http()
+        .with_endpoint(endpoint)
+        .build_span_exporter()
+        .map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to export traces to {}", endpoint),
+            )
+        })?;
+
+    let provider = sdktrace::TracerProvider::builder()
+        .with_span_processor(BatchExportProcessor::start(Box::new(exporter))?)
+        .with_config(
+            sdktrace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                service_name.to_string(),
+            )])),
+        )
+        .build();
+    global::set_tracer_provider(provider);
+
+    Ok(())
+}
+
+/// Exports any spans that have ended but not been exported.
+pub fn shutdown() {
+    global::shutdown_tracer_provider();
+}
+
+/// Starts a span as a child of the current span and makes it the current span until the returned
+/// guard is dropped, at which point the span ends.
+pub fn enter_span<N>(name: N, attributes: &[(&'static str, &str)]) -> SpanGuard
+where
+    N: Into<Cow<'static, str>>,
+{
+    SpanGuard {
+        _context: mark_span_as_active(Span::start(name, attributes).inner),
+    }
+}
+
+/// Keeps a span current until it is dropped; see `enter_span`.
+pub struct SpanGuard {
+    _context: ContextGuard,
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        // The context may be held elsewhere, such as by a message waiting to be dispatched, so
+        // the span is ended explicitly rather than when the context is dropped
+        Context::current().span().end();
+    }
+}
+
+/// A span that is not tied to the current thread, such as one that follows a batch from its
+/// submission until it is committed.
+pub struct Span {
+    inner: global::BoxedSpan,
+}
+
+impl Span {
+    /// Starts a span as a child of the current span.
+    pub fn start<N>(name: N, attributes: &[(&'static str, &str)]) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+    {
+        let tracer = global::tracer(TRACER_NAME);
+        let inner = tracer
+            .span_builder(name)
+            .with_attributes(
+                attributes
+                    .iter()
+                    .map(|(key, value)| KeyValue::new(*key, value.to_string()))
+                    .collect(),
+            )
+            .start(&tracer);
+        Self { inner }
+    }
+
+    /// Records that something happened during the span, such as a change of status.
+    pub fn add_event(&mut self, name: &'static str) {
+        self.inner.add_event(name, vec![]);
+    }
+
+    /// Marks the span as failed.
+    pub fn set_error(&mut self, message: String) {
+        self.inner.set_status(StatusCode::Error, message);
+    }
+
+    /// Ends the span.
+    pub fn end(mut self) {
+        self.inner.end();
+    }
+}
+
+enum ExportMessage {
+    Span(Box<SpanData>),
+    /// Export the queued spans, then reply
+    Flush(Sender<()>),
+    Shutdown,
+}
+
+/// Queues ended spans, which are exported in batches by a separate thread
+#[derive(Debug)]
+struct BatchExportProcessor {
+    sender: SyncSender<ExportMessage>,
+    join_handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+impl BatchExportProcessor {
+    fn start(mut exporter: Box<dyn SpanExporter>) -> Result<Self, InternalError> {
+        let (sender, receiver) = sync_channel(MAX_QUEUED_SPANS);
+
+        let join_handle = thread::Builder::new()
+            .name("SpanExporter".into())
+            .spawn(move || {
+                let mut batch = vec![];
+                let mut deadline = Instant::now() + EXPORT_INTERVAL;
+                loop {
+                    let timeout = deadline.saturating_duration_since(Instant::now());
+                    match receiver.recv_timeout(timeout) {
+                        Ok(ExportMessage::Span(span)) => {
+                            batch.push(*span);
+                            if batch.len() < MAX_EXPORT_BATCH_SIZE {
+                                continue;
+                            }
+                        }
+                        Ok(ExportMessage::Flush(reply)) => {
+                            export(&mut *exporter, &mut batch);
+                            let _ = reply.send(());
+                        }
+                        Err(RecvTimeoutError::Timeout) => (),
+                        Ok(ExportMessage::Shutdown) | Err(RecvTimeoutError::Disconnected) => {
+                            export(&mut *exporter, &mut batch);
+                            exporter.shutdown();
+                            break;
+                        }
+                    }
+                    export(&mut *exporter, &mut batch);
+                    deadline = Instant::now() + EXPORT_INTERVAL;
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            sender,
+            join_handle: Mutex::new(Some(join_handle)),
+        })
+    }
+}
+
+impl SpanProcessor for BatchExportProcessor {
+    fn on_start(&self, _span: &mut sdktrace::Span, _cx: &Context) {}
+
+    fn on_end(&self, span: SpanData) {
+        match self.sender.try_send(ExportMessage::Span(Box::new(span))) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) => debug!("Span export queue is full; dropping span"),
+            Err(TrySendError::Disconnected(_)) => (),
+        }
+    }
+
+    fn force_flush(&self) -> TraceResult<()> {
+        let (reply_sender, reply_receiver) = channel();
+        self.sender
+            .send(ExportMessage::Flush(reply_sender))
+            .map_err(|_| TraceError::from("span exporter has stopped"))?;
+        reply_receiver
+            .recv()
+            .map_err(|_| TraceError::from("span exporter has stopped"))
+    }
+
+    fn shutdown(&mut self) -> TraceResult<()> {
+        let _ = self.sender.send(ExportMessage::Shutdown);
+        let join_handle = self
+            .join_handle
+            .lock()
+            .map_err(|_| TraceError::from("span exporter lock poisoned"))?
+            .take();
+        match join_handle {
+            Some(join_handle) => join_handle
+                .join()
+                .map_err(|_| TraceError::from("span exporter thread panicked")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Exports and clears a batch of spans, if it is not empty.
+fn export(exporter: &mut dyn SpanExporter, batch: &mut Vec<SpanData>) {
+    if batch.is_empty() {
+        return;
+    }
+    let spans = std::mem::take(batch);
+    if let Err(err) = futures_0_3::executor::block_on(exporter.export(spans)) {
+        warn!("Unable to export spans: {}", err);
+    }
+}
+
+/// Adds the trace context of the current span to a network message.
+pub fn inject_context(message: &mut NetworkMessage) {
+    let mut trace_context: HashMap<String, String> = HashMap::new();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&Context::current(), &mut trace_context)
+    });
+    if !trace_context.is_empty() {
+        message.set_trace_context(trace_context);
+    }
+}
+
+/// Returns the trace context that a network message was sent with, which is the current context
+/// if the message has none.
+pub fn extract_context(message: &NetworkMessage) -> Context {
+    if message.get_trace_context().is_empty() {
+        return Context::current();
+    }
+    global::get_text_map_propagator(|propagator| propagator.extract(message.get_trace_context()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+
+    /// Verify that the trace context of the current span is carried by a network message and
+    /// restored from it.
+    #[test]
+    fn test_propagate_context() {
+        global::set_text_map_propagator(TraceContextPropagator::new());
+
+        let span_context = SpanContext::new(
+            TraceId::from_u128(0x4bf9_2f35_77b3_4da6_a3ce_929d_0e0e_4736),
+            SpanId::from_u64(0x00f0_67aa_0ba9_02b7),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let _guard = Context::current()
+            .with_remote_span_context(span_context.clone())
+            .attach();
+
+        let mut message = NetworkMessage::new();
+        inject_context(&mut message);
+        assert_eq!(
+            message
+                .get_trace_context()
+                .get("traceparent")
+                .map(String::as_str),
+            Some("00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+        );
+
+        let context = extract_context(&message);
+        assert_eq!(
+            context.span().span_context().trace_id(),
+            span_context.trace_id()
+        );
+        assert_eq!(
+            context.span().span_context().span_id(),
+            span_context.span_id()
+        );
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A blocklist of public keys and IP addresses whose connections are refused.
+//!
+//! Operators add a [`BlockedPeer`] for a misbehaving node, with the reason it was blocked and the
+//! time the block expires. The [`PeerBlocklist`] is checked when a connection is authorized:
+//! connections from a blocked IP address are refused before authorization starts, and
+//! connections that authorize with a blocked public key are refused once the key is known. Blocks
+//! are ignored once they expire, so the node is allowed to connect again without operator action.
+//!
+//! Connections that are already established when a block is added are not closed; the block is
+//! applied the next time the node connects.
+
+pub mod store;
+
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use crate::error::{InvalidArgumentError, InvalidStateError};
+use crate::hex::{parse_hex, to_hex};
+use crate::public_key::PublicKey;
+
+use self::store::{PeerBlocklistStore, PeerBlocklistStoreError};
+
+/// What a block applies to
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum BlockTarget {
+    /// Connections that authorize with the public key are refused
+    PublicKey(PublicKey),
+    /// Connections from the IP address are refused
+    Address(IpAddr),
+}
+
+impl BlockTarget {
+    /// Parses a target from its kind, `public_key` or `ip`, and its value, a hex-encoded public
+    /// key or an IP address.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgumentError` if the kind is unknown or the value cannot be parsed.
+    pub fn parse(kind: &str, value: &str) -> Result<Self, InvalidArgumentError> {
+        match kind {
+            "public_key" => parse_hex(value)
+                .map(|bytes| BlockTarget::PublicKey(PublicKey::from_bytes(bytes)))
+                .map_err(|err| InvalidArgumentError::new("value".to_string(), err.to_string())),
+            "ip" => IpAddr::from_str(value)
+                .map(BlockTarget::Address)
+                .map_err(|err| InvalidArgumentError::new("value".to_string(), err.to_string())),
+            _ => Err(InvalidArgumentError::new(
+                "kind".to_string(),
+                format!("unknown block target kind '{}'", kind),
+            )),
+        }
+    }
+
+    /// Returns the kind of the target, `public_key` or `ip`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            BlockTarget::PublicKey(_) => "public_key",
+            BlockTarget::Address(_) => "ip",
+        }
+    }
+
+    /// Returns the value of the target, a hex-encoded public key or an IP address
+    pub fn value(&self) -> String {
+        match self {
+            BlockTarget::PublicKey(public_key) => to_hex(public_key.as_slice()),
+            BlockTarget::Address(address) => address.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for BlockTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.kind(), self.value())
+    }
+}
+
+/// A public key or IP address whose connections are refused until the block expires
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlockedPeer {
+    target: BlockTarget,
+    reason: String,
+    created_at: SystemTime,
+    expires_at: SystemTime,
+}
+
+impl BlockedPeer {
+    /// Returns what the block applies to
+    pub fn target(&self) -> &BlockTarget {
+        &self.target
+    }
+
+    /// Returns the reason the peer was blocked
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Returns the time the block was added
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Returns the time after which the block no longer applies
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+
+    /// Returns whether the block has expired at the given time
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.expires_at <= time
+    }
+}
+
+/// Builds a [`BlockedPeer`].
+#[derive(Default)]
+pub struct BlockedPeerBuilder {
+    target: Option<BlockTarget>,
+    reason: Option<String>,
+    created_at: Option<SystemTime>,
+    expires_at: Option<SystemTime>,
+    duration: Option<Duration>,
+}
+
+impl BlockedPeerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_target(mut self, target: BlockTarget) -> Self {
+        self.target = Some(target);
+        self
+    }
+
+    pub fn with_reason(mut self, reason: String) -> Self {
+        self.reason = Some(reason);
+        self
+    }
+
+    /// Sets the time the block was added; defaults to the current time.
+    pub fn with_created_at(mut self, created_at: SystemTime) -> Self {
+        self.created_at = Some(created_at);
+        self
+    }
+
+    /// Sets the time the block expires.
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
+    /// Sets how long after it was added the block expires; ignored if the expiry time is set.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
+    /// Builds the blocked peer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if the target is missing, if neither the expiry time nor the
+    /// duration is set, or if the block expires before it was added.
+    pub fn build(self) -> Result<BlockedPeer, InvalidStateError> {
+        let target = self.target.ok_or_else(|| {
+            InvalidStateError::with_message("a blocked peer requires a target".to_string())
+        })?;
+        let created_at = self.created_at.unwrap_or_else(SystemTime::now);
+        let expires_at = match (self.expires_at, self.duration) {
+            (Some(expires_at), _) => expires_at,
+            (None, Some(duration)) => created_at.checked_add(duration).ok_or_else(|| {
+                InvalidStateError::with_message("the block duration is too long".to_string())
+            })?,
+            (None, None) => {
+                return Err(InvalidStateError::with_message(
+                    "a blocked peer requires an expiry time or a duration".to_string(),
+                ))
+            }
+        };
+        if expires_at <= created_at {
+            return Err(InvalidStateError::with_message(
+                "a block must expire after it was added".to_string(),
+            ));
+        }
+
+        Ok(BlockedPeer {
+            target,
+            reason: self.reason.unwrap_or_default(),
+            created_at,
+            expires_at,
+        })
+    }
+}
+
+/// Checks connections against the blocks in a [`PeerBlocklistStore`].
+#[derive(Clone)]
+pub struct PeerBlocklist {
+    store: Box<dyn PeerBlocklistStore>,
+}
+
+impl PeerBlocklist {
+    pub fn new(store: Box<dyn PeerBlocklistStore>) -> Self {
+        Self { store }
+    }
+
+    /// Returns the unexpired block of the given public key, if there is one.
+    pub fn check_public_key(
+        &self,
+        public_key: &PublicKey,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        self.check(&BlockTarget::PublicKey(public_key.clone()))
+    }
+
+    /// Returns the unexpired block of the IP address of the given endpoint, such as
+    /// `tcp://10.0.0.1:8044`, if there is one. Endpoints whose host is not an IP address are
+    /// never blocked.
+    pub fn check_endpoint(
+        &self,
+        endpoint: &str,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        match endpoint_address(endpoint) {
+            Some(address) => self.check(&BlockTarget::Address(address)),
+            None => Ok(None),
+        }
+    }
+
+    fn check(&self, target: &BlockTarget) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        Ok(self
+            .store
+            .get_block(target)?
+            .filter(|block| !block.is_expired_at(SystemTime::now())))
+    }
+}
+
+/// Returns the IP address of an endpoint of the form `<protocol>://<ip>:<port>`.
+fn endpoint_address(endpoint: &str) -> Option<IpAddr> {
+    let address = match endpoint.find("://") {
+        Some(index) => &endpoint[index + 3..],
+        None => endpoint,
+    };
+    SocketAddr::from_str(address)
+        .map(|socket_address| socket_address.ip())
+        .or_else(|_| IpAddr::from_str(address))
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that targets are parsed from their kind and value, and that unknown kinds and
+    /// invalid values are rejected.
+    #[test]
+    fn test_block_target_parse() {
+        let target = BlockTarget::parse("public_key", "0102ab").expect("Failed to parse key");
+        assert_eq!(
+            target,
+            BlockTarget::PublicKey(PublicKey::from_bytes(vec![1, 2, 171]))
+        );
+        assert_eq!(target.value(), "0102ab");
+
+        let target = BlockTarget::parse("ip", "::1").expect("Failed to parse address");
+        assert_eq!(target.kind(), "ip");
+        assert_eq!(target.value(), "::1");
+
+        assert!(BlockTarget::parse("ip", "not-an-address").is_err());
+        assert!(BlockTarget::parse("public_key", "xyz").is_err());
+        assert!(BlockTarget::parse("node_id", "node_a").is_err());
+    }
+
+    /// Verify that the builder computes the expiry time from the duration, and rejects a block
+    /// without an expiry or one that expires before it was added.
+    #[test]
+    fn test_blocked_peer_builder() {
+        let created_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1000);
+        let block = BlockedPeerBuilder::new()
+            .with_target(BlockTarget::Address(IpAddr::from([10, 0, 0, 1])))
+            .with_created_at(created_at)
+            .with_duration(Duration::from_secs(60))
+            .build()
+            .expect("Failed to build block");
+        assert_eq!(block.expires_at(), created_at + Duration::from_secs(60));
+        assert!(!block.is_expired_at(created_at + Duration::from_secs(59)));
+        assert!(block.is_expired_at(created_at + Duration::from_secs(60)));
+
+        assert!(BlockedPeerBuilder::new()
+            .with_target(BlockTarget::Address(IpAddr::from([10, 0, 0, 1])))
+            .build()
+            .is_err());
+        assert!(BlockedPeerBuilder::new()
+            .with_target(BlockTarget::Address(IpAddr::from([10, 0, 0, 1])))
+            .with_created_at(created_at)
+            .with_expires_at(created_at)
+            .build()
+            .is_err());
+    }
+
+    /// Verify that the IP address is taken from TCP and TLS endpoints, and that endpoints with a
+    /// host name have no address.
+    #[test]
+    fn test_endpoint_address() {
+        assert_eq!(
+            endpoint_address("tcp://10.0.0.1:8044"),
+            Some(IpAddr::from([10, 0, 0, 1]))
+        );
+        assert_eq!(
+            endpoint_address("tcps://[::1]:8044"),
+            Some(IpAddr::from_str("::1").unwrap())
+        );
+        assert_eq!(endpoint_address("tcps://splinter-node-a:8044"), None);
+    }
+}
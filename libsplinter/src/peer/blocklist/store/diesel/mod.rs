@@ -0,0 +1,274 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [PeerBlocklistStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::peer::blocklist::{BlockTarget, BlockedPeer};
+use crate::store::pool::ConnectionPool;
+
+use super::{PeerBlocklistStore, PeerBlocklistStoreError};
+
+use operations::{
+    add_block::PeerBlocklistStoreAddBlock as _, get_block::PeerBlocklistStoreGetBlock as _,
+    list_blocks::PeerBlocklistStoreListBlocks as _,
+    remove_block::PeerBlocklistStoreRemoveBlock as _,
+    remove_expired_blocks::PeerBlocklistStoreRemoveExpiredBlocks as _,
+    PeerBlocklistStoreOperations,
+};
+
+/// Manages the peer blocklist in the database
+pub struct DieselPeerBlocklistStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselPeerBlocklistStore<C> {
+    /// Creates a new DieselPeerBlocklistStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselPeerBlocklistStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselPeerBlocklistStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl PeerBlocklistStore for DieselPeerBlocklistStore<diesel::pg::PgConnection> {
+    fn add_block(&self, block: &BlockedPeer) -> Result<(), PeerBlocklistStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerBlocklistStoreOperations::new(connection).add_block(block)
+        })
+    }
+
+    fn get_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PeerBlocklistStoreOperations::new(connection).get_block(target)
+        })
+    }
+
+    fn list_blocks(&self) -> Result<Vec<BlockedPeer>, PeerBlocklistStoreError> {
+        self.connection_pool
+            .execute_read(|connection| PeerBlocklistStoreOperations::new(connection).list_blocks())
+    }
+
+    fn remove_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerBlocklistStoreOperations::new(connection).remove_block(target)
+        })
+    }
+
+    fn remove_expired_blocks(&self, time: SystemTime) -> Result<usize, PeerBlocklistStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerBlocklistStoreOperations::new(connection).remove_expired_blocks(time)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn PeerBlocklistStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl PeerBlocklistStore for DieselPeerBlocklistStore<diesel::sqlite::SqliteConnection> {
+    fn add_block(&self, block: &BlockedPeer) -> Result<(), PeerBlocklistStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerBlocklistStoreOperations::new(connection).add_block(block)
+        })
+    }
+
+    fn get_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PeerBlocklistStoreOperations::new(connection).get_block(target)
+        })
+    }
+
+    fn list_blocks(&self) -> Result<Vec<BlockedPeer>, PeerBlocklistStoreError> {
+        self.connection_pool
+            .execute_read(|connection| PeerBlocklistStoreOperations::new(connection).list_blocks())
+    }
+
+    fn remove_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerBlocklistStoreOperations::new(connection).remove_block(target)
+        })
+    }
+
+    fn remove_expired_blocks(&self, time: SystemTime) -> Result<usize, PeerBlocklistStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerBlocklistStoreOperations::new(connection).remove_expired_blocks(time)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn PeerBlocklistStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::net::IpAddr;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::peer::blocklist::BlockedPeerBuilder;
+    use crate::public_key::PublicKey;
+
+    /// Verify that a SQLite-backed `DieselPeerBlocklistStore` correctly supports adding, getting,
+    /// listing and removing blocks, and removing the blocks that have expired.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Block a public key and an IP address and verify that they are listed in the order they
+    ///    were added.
+    /// 3. Block the IP address again and verify that the block is replaced.
+    /// 4. Remove the expired blocks and verify that only the block of the public key is removed.
+    /// 5. Remove the block of the IP address and verify that it is returned, and that it can no
+    ///    longer be fetched.
+    #[test]
+    fn sqlite_peer_blocklist() {
+        let store = DieselPeerBlocklistStore::new(create_connection_pool_and_migrate());
+
+        let key_block = BlockedPeerBuilder::new()
+            .with_target(BlockTarget::PublicKey(PublicKey::from_bytes(vec![1, 2, 3])))
+            .with_reason("Sent invalid messages".to_string())
+            .with_created_at(UNIX_EPOCH + Duration::from_secs(1000))
+            .with_duration(Duration::from_secs(60))
+            .build()
+            .expect("Failed to build block");
+        let address = BlockTarget::Address(IpAddr::from([10, 0, 0, 1]));
+        let address_block = BlockedPeerBuilder::new()
+            .with_target(address.clone())
+            .with_created_at(UNIX_EPOCH + Duration::from_secs(2000))
+            .with_duration(Duration::from_secs(60))
+            .build()
+            .expect("Failed to build block");
+
+        store
+            .add_block(&address_block)
+            .expect("Failed to add block");
+        store.add_block(&key_block).expect("Failed to add block");
+
+        assert_eq!(
+            store.list_blocks().expect("Failed to list blocks"),
+            vec![key_block.clone(), address_block]
+        );
+
+        let address_block = BlockedPeerBuilder::new()
+            .with_target(address.clone())
+            .with_reason("Flooding connections".to_string())
+            .with_created_at(UNIX_EPOCH + Duration::from_secs(2000))
+            .with_duration(Duration::from_secs(3600))
+            .build()
+            .expect("Failed to build block");
+        store
+            .add_block(&address_block)
+            .expect("Failed to add block");
+        assert_eq!(
+            store.get_block(&address).expect("Failed to get block"),
+            Some(address_block.clone())
+        );
+
+        assert_eq!(
+            store
+                .remove_expired_blocks(UNIX_EPOCH + Duration::from_secs(2500))
+                .expect("Failed to remove expired blocks"),
+            1
+        );
+        assert_eq!(
+            store
+                .get_block(key_block.target())
+                .expect("Failed to get block"),
+            None
+        );
+
+        assert_eq!(
+            store
+                .remove_block(&address)
+                .expect("Failed to remove block"),
+            Some(address_block)
+        );
+        assert_eq!(
+            store.get_block(&address).expect("Failed to get block"),
+            None
+        );
+        assert_eq!(
+            store
+                .remove_block(&address)
+                .expect("Failed to remove block"),
+            None
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{InternalError, InvalidArgumentError};
+use crate::peer::blocklist::store::PeerBlocklistStoreError;
+use crate::peer::blocklist::{BlockTarget, BlockedPeer, BlockedPeerBuilder};
+
+use super::schema::peer_blocklist;
+
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "peer_blocklist"]
+pub struct PeerBlocklistModel {
+    pub target_kind: String,
+    pub target_value: String,
+    pub reason: String,
+    pub created_at: i64,
+    pub expires_at: i64,
+}
+
+impl TryFrom<&BlockedPeer> for PeerBlocklistModel {
+    type Error = PeerBlocklistStoreError;
+
+    fn try_from(block: &BlockedPeer) -> Result<Self, Self::Error> {
+        Ok(PeerBlocklistModel {
+            target_kind: block.target().kind().to_string(),
+            target_value: block.target().value(),
+            reason: block.reason().to_string(),
+            created_at: to_timestamp(block.created_at(), "created_at")?,
+            expires_at: to_timestamp(block.expires_at(), "expires_at")?,
+        })
+    }
+}
+
+impl TryFrom<PeerBlocklistModel> for BlockedPeer {
+    type Error = PeerBlocklistStoreError;
+
+    fn try_from(model: PeerBlocklistModel) -> Result<Self, Self::Error> {
+        let target =
+            BlockTarget::parse(&model.target_kind, &model.target_value).map_err(|err| {
+                PeerBlocklistStoreError::Internal(InternalError::from_source(Box::new(err)))
+            })?;
+
+        BlockedPeerBuilder::new()
+            .with_target(target)
+            .with_reason(model.reason)
+            .with_created_at(from_timestamp(model.created_at)?)
+            .with_expires_at(from_timestamp(model.expires_at)?)
+            .build()
+            .map_err(|err| {
+                PeerBlocklistStoreError::Internal(InternalError::from_source(Box::new(err)))
+            })
+    }
+}
+
+/// Converts a time to the number of seconds since the Unix epoch
+pub fn to_timestamp(time: SystemTime, field: &str) -> Result<i64, PeerBlocklistStoreError> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            PeerBlocklistStoreError::InvalidArgument(InvalidArgumentError::new(
+                field.to_string(),
+                "time cannot be stored as a timestamp".to_string(),
+            ))
+        })
+}
+
+fn from_timestamp(timestamp: i64) -> Result<SystemTime, PeerBlocklistStoreError> {
+    u64::try_from(timestamp)
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            PeerBlocklistStoreError::Internal(InternalError::with_message(format!(
+                "timestamp {} could not be represented as a SystemTime",
+                timestamp
+            )))
+        })
+}
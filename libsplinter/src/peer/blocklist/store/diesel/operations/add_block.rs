@@ -0,0 +1,78 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+};
+
+use crate::peer::blocklist::store::{
+    diesel::{models::PeerBlocklistModel, schema::peer_blocklist},
+    PeerBlocklistStoreError,
+};
+use crate::peer::blocklist::BlockedPeer;
+
+use super::PeerBlocklistStoreOperations;
+
+pub trait PeerBlocklistStoreAddBlock {
+    fn add_block(&self, block: &BlockedPeer) -> Result<(), PeerBlocklistStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PeerBlocklistStoreAddBlock
+    for PeerBlocklistStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_block(&self, block: &BlockedPeer) -> Result<(), PeerBlocklistStoreError> {
+        let model = PeerBlocklistModel::try_from(block)?;
+
+        self.conn.transaction::<_, PeerBlocklistStoreError, _>(|| {
+            delete(
+                peer_blocklist::table
+                    .filter(peer_blocklist::target_kind.eq(&model.target_kind))
+                    .filter(peer_blocklist::target_value.eq(&model.target_value)),
+            )
+            .execute(self.conn)?;
+
+            insert_into(peer_blocklist::table)
+                .values(&model)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PeerBlocklistStoreAddBlock for PeerBlocklistStoreOperations<'a, diesel::pg::PgConnection> {
+    fn add_block(&self, block: &BlockedPeer) -> Result<(), PeerBlocklistStoreError> {
+        let model = PeerBlocklistModel::try_from(block)?;
+
+        self.conn.transaction::<_, PeerBlocklistStoreError, _>(|| {
+            delete(
+                peer_blocklist::table
+                    .filter(peer_blocklist::target_kind.eq(&model.target_kind))
+                    .filter(peer_blocklist::target_value.eq(&model.target_value)),
+            )
+            .execute(self.conn)?;
+
+            insert_into(peer_blocklist::table)
+                .values(&model)
+                .execute(self.conn)?;
+
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::peer::blocklist::store::{
+    diesel::{models::PeerBlocklistModel, schema::peer_blocklist},
+    PeerBlocklistStoreError,
+};
+use crate::peer::blocklist::{BlockTarget, BlockedPeer};
+
+use super::PeerBlocklistStoreOperations;
+
+pub trait PeerBlocklistStoreGetBlock {
+    fn get_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError>;
+}
+
+impl<'a, C> PeerBlocklistStoreGetBlock for PeerBlocklistStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn get_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        peer_blocklist::table
+            .filter(peer_blocklist::target_kind.eq(target.kind()))
+            .filter(peer_blocklist::target_value.eq(target.value()))
+            .first::<PeerBlocklistModel>(self.conn)
+            .optional()?
+            .map(BlockedPeer::try_from)
+            .transpose()
+    }
+}
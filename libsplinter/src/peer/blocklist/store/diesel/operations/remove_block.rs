@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::peer::blocklist::store::{diesel::schema::peer_blocklist, PeerBlocklistStoreError};
+use crate::peer::blocklist::{BlockTarget, BlockedPeer};
+
+use super::{get_block::PeerBlocklistStoreGetBlock as _, PeerBlocklistStoreOperations};
+
+pub trait PeerBlocklistStoreRemoveBlock {
+    fn remove_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError>;
+}
+
+impl<'a, C> PeerBlocklistStoreRemoveBlock for PeerBlocklistStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn remove_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        self.conn.transaction::<_, PeerBlocklistStoreError, _>(|| {
+            let existing = self.get_block(target)?;
+
+            if existing.is_some() {
+                delete(
+                    peer_blocklist::table
+                        .filter(peer_blocklist::target_kind.eq(target.kind()))
+                        .filter(peer_blocklist::target_value.eq(target.value())),
+                )
+                .execute(self.conn)?;
+            }
+
+            Ok(existing)
+        })
+    }
+}
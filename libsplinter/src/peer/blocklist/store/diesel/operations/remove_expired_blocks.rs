@@ -0,0 +1,42 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::SystemTime;
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::peer::blocklist::store::{
+    diesel::{models::to_timestamp, schema::peer_blocklist},
+    PeerBlocklistStoreError,
+};
+
+use super::PeerBlocklistStoreOperations;
+
+pub trait PeerBlocklistStoreRemoveExpiredBlocks {
+    fn remove_expired_blocks(&self, time: SystemTime) -> Result<usize, PeerBlocklistStoreError>;
+}
+
+impl<'a, C> PeerBlocklistStoreRemoveExpiredBlocks for PeerBlocklistStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    fn remove_expired_blocks(&self, time: SystemTime) -> Result<usize, PeerBlocklistStoreError> {
+        let timestamp = to_timestamp(time, "time")?;
+
+        Ok(
+            delete(peer_blocklist::table.filter(peer_blocklist::expires_at.le(timestamp)))
+                .execute(self.conn)?,
+        )
+    }
+}
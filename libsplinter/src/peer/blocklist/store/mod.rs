@@ -0,0 +1,111 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the store that persists the peer blocklist.
+
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod error;
+
+use std::time::SystemTime;
+
+use super::{BlockTarget, BlockedPeer};
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselPeerBlocklistStore;
+pub use error::PeerBlocklistStoreError;
+
+/// Defines methods for persisting the peer blocklist
+pub trait PeerBlocklistStore: Send + Sync {
+    /// Adds a block, replacing any existing block of the same target
+    ///
+    /// # Arguments
+    ///
+    ///  * `block` - The block to add
+    fn add_block(&self, block: &BlockedPeer) -> Result<(), PeerBlocklistStoreError>;
+
+    /// Returns the block of the given target, including an expired block, if there is one
+    ///
+    /// # Arguments
+    ///
+    ///  * `target` - The public key or IP address
+    fn get_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError>;
+
+    /// Returns every block, including expired blocks, sorted by the time they were added
+    fn list_blocks(&self) -> Result<Vec<BlockedPeer>, PeerBlocklistStoreError>;
+
+    /// Removes the block of the given target, returning it if it existed
+    ///
+    /// # Arguments
+    ///
+    ///  * `target` - The public key or IP address
+    fn remove_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError>;
+
+    /// Removes the blocks that have expired at the given time, returning how many were removed
+    ///
+    /// # Arguments
+    ///
+    ///  * `time` - Blocks that expire at or before this time are removed
+    fn remove_expired_blocks(&self, time: SystemTime) -> Result<usize, PeerBlocklistStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn PeerBlocklistStore>;
+}
+
+impl Clone for Box<dyn PeerBlocklistStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<PS> PeerBlocklistStore for Box<PS>
+where
+    PS: PeerBlocklistStore + ?Sized,
+{
+    fn add_block(&self, block: &BlockedPeer) -> Result<(), PeerBlocklistStoreError> {
+        (**self).add_block(block)
+    }
+
+    fn get_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        (**self).get_block(target)
+    }
+
+    fn list_blocks(&self) -> Result<Vec<BlockedPeer>, PeerBlocklistStoreError> {
+        (**self).list_blocks()
+    }
+
+    fn remove_block(
+        &self,
+        target: &BlockTarget,
+    ) -> Result<Option<BlockedPeer>, PeerBlocklistStoreError> {
+        (**self).remove_block(target)
+    }
+
+    fn remove_expired_blocks(&self, time: SystemTime) -> Result<usize, PeerBlocklistStoreError> {
+        (**self).remove_expired_blocks(time)
+    }
+
+    fn clone_box(&self) -> Box<dyn PeerBlocklistStore> {
+        (**self).clone_box()
+    }
+}
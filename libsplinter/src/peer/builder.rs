@@ -18,8 +18,10 @@
 
 use crate::network::connection_manager::Connector;
 
+#[cfg(feature = "peer-endpoint-selection")]
+use super::endpoint_selection::EndpointSelector;
 use super::error::PeerManagerError;
-use super::PeerManager;
+use super::{PeerCapabilitiesRegistry, PeerManager};
 
 // Default value of how often the Pacemaker should send RetryPending message
 const DEFAULT_PACEMAKER_INTERVAL: u64 = 10;
@@ -43,6 +45,15 @@ pub struct PeerManagerBuilder {
     endpoint_retry_frequency: Option<u64>,
     identity: Option<String>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "peer-endpoint-selection")]
+    endpoint_selector: Option<EndpointSelector>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    retry_jitter: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    max_reconnect_attempts: Option<u64>,
+    #[cfg(feature = "peer-connect-priority")]
+    connect_ramp_rate: Option<u64>,
+    capabilities_registry: Option<PeerCapabilitiesRegistry>,
 }
 
 /// Constructs new `PeerManager` instances.
@@ -128,6 +139,61 @@ impl PeerManagerBuilder {
         self
     }
 
+    /// Set the endpoint_selector to use with the resulting `PeerManager`.
+    ///
+    /// Determines the order in which the `PeerManager` tries the endpoints of a peer. If not set,
+    /// the endpoints are tried in the order they were provided.
+    #[cfg(feature = "peer-endpoint-selection")]
+    pub fn with_endpoint_selector(mut self, endpoint_selector: EndpointSelector) -> Self {
+        self.endpoint_selector = Some(endpoint_selector);
+        self
+    }
+
+    /// Set the retry_jitter to use with the resulting `PeerManager`.
+    ///
+    /// The longest random time (in seconds) added to the wait before retrying a pending peer's
+    /// endpoints, so that nodes that lost their connections at the same time do not all retry
+    /// at once. Defaults to 0.
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn with_retry_jitter(mut self, retry_jitter: u64) -> Self {
+        self.retry_jitter = Some(retry_jitter);
+        self
+    }
+
+    /// Set the max_reconnect_attempts to use with the resulting `PeerManager`.
+    ///
+    /// The number of times the `PeerManager` retries a pending peer's endpoints before it stops
+    /// retrying them. The peer is retried again once it is connected to, or once it connects to
+    /// the local node. By default, pending peers are retried indefinitely.
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn with_max_reconnect_attempts(mut self, max_reconnect_attempts: u64) -> Self {
+        self.max_reconnect_attempts = Some(max_reconnect_attempts);
+        self
+    }
+
+    /// Set the connect_ramp_rate to use with the resulting `PeerManager`.
+    ///
+    /// The number of connections to peers that are not priority peers the `PeerManager` requests
+    /// per retry interval. Connections to priority peers are requested right away. By default,
+    /// connections are not limited.
+    #[cfg(feature = "peer-connect-priority")]
+    pub fn with_connect_ramp_rate(mut self, connect_ramp_rate: u64) -> Self {
+        self.connect_ramp_rate = Some(connect_ramp_rate);
+        self
+    }
+
+    /// Set the registry of peer capabilities to use with the resulting `PeerManager`.
+    ///
+    /// The `PeerManager` removes the capabilities a peer advertised on a connection once that
+    /// connection is disconnected.
+    pub fn with_capabilities_registry(
+        mut self,
+        capabilities_registry: PeerCapabilitiesRegistry,
+    ) -> Self {
+        self.capabilities_registry = Some(capabilities_registry);
+        self
+    }
+
     /// Starts the `PeerManager`
     ///
     /// Starts up a thread that will handle incoming requests to add, remove and get peers. Also
@@ -163,6 +229,15 @@ impl PeerManagerBuilder {
             retry_frequency,
             max_retry_frequency,
             endpoint_retry_frequency,
+            #[cfg(feature = "peer-endpoint-selection")]
+            self.endpoint_selector.take().unwrap_or_default(),
+            #[cfg(feature = "peer-reconnect-policy")]
+            self.retry_jitter.unwrap_or(0),
+            #[cfg(feature = "peer-reconnect-policy")]
+            self.max_reconnect_attempts,
+            #[cfg(feature = "peer-connect-priority")]
+            self.connect_ramp_rate,
+            self.capabilities_registry.take().unwrap_or_default(),
         )
     }
 }
@@ -0,0 +1,264 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Capabilities advertised by nodes during peer authorization.
+//!
+//! When authorization with a peer is complete, each node sends its [`NodeCapabilities`] to the
+//! other. The capabilities received from each peer are kept in a [`PeerCapabilitiesRegistry`], so
+//! that tooling can determine whether a peer is able to run a given service type before proposing
+//! a circuit that requires it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use super::PeerAuthorizationToken;
+
+/// The service types, protocol versions, and consensus engines supported by a node.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NodeCapabilities {
+    service_types: Vec<String>,
+    protocols: BTreeMap<String, u32>,
+    consensus_engines: Vec<String>,
+}
+
+impl NodeCapabilities {
+    pub(crate) fn new(
+        service_types: Vec<String>,
+        protocols: BTreeMap<String, u32>,
+        consensus_engines: Vec<String>,
+    ) -> Self {
+        Self {
+            service_types,
+            protocols,
+            consensus_engines,
+        }
+    }
+
+    /// Returns the service types the node is able to run.
+    pub fn service_types(&self) -> &[String] {
+        &self.service_types
+    }
+
+    /// Returns the protocols supported by the node, mapped to the supported version.
+    pub fn protocols(&self) -> &BTreeMap<String, u32> {
+        &self.protocols
+    }
+
+    /// Returns the consensus engines the node is able to run.
+    pub fn consensus_engines(&self) -> &[String] {
+        &self.consensus_engines
+    }
+
+    /// Returns true if the node is able to run the given service type.
+    pub fn supports_service_type(&self, service_type: &str) -> bool {
+        self.service_types.iter().any(|st| st == service_type)
+    }
+
+    /// Returns true if the node is able to run the given consensus engine.
+    pub fn supports_consensus_engine(&self, consensus_engine: &str) -> bool {
+        self.consensus_engines
+            .iter()
+            .any(|engine| engine == consensus_engine)
+    }
+}
+
+/// Builder for [`NodeCapabilities`].
+#[derive(Default)]
+pub struct NodeCapabilitiesBuilder {
+    service_types: Vec<String>,
+    protocols: BTreeMap<String, u32>,
+    consensus_engines: Vec<String>,
+}
+
+impl NodeCapabilitiesBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a service type the node is able to run.
+    pub fn with_service_type(mut self, service_type: &str) -> Self {
+        self.service_types.push(service_type.to_string());
+        self
+    }
+
+    /// Adds a protocol, and the version of it, that the node supports.
+    pub fn with_protocol(mut self, protocol: &str, version: u32) -> Self {
+        self.protocols.insert(protocol.to_string(), version);
+        self
+    }
+
+    /// Adds a consensus engine the node is able to run.
+    pub fn with_consensus_engine(mut self, consensus_engine: &str) -> Self {
+        self.consensus_engines.push(consensus_engine.to_string());
+        self
+    }
+
+    pub fn build(mut self) -> NodeCapabilities {
+        self.service_types.sort();
+        self.service_types.dedup();
+        self.consensus_engines.sort();
+        self.consensus_engines.dedup();
+
+        NodeCapabilities {
+            service_types: self.service_types,
+            protocols: self.protocols,
+            consensus_engines: self.consensus_engines,
+        }
+    }
+}
+
+/// The capabilities advertised by each authorized peer.
+///
+/// Each entry remembers the connection the capabilities were received on, so that a late
+/// disconnect of a previous connection does not remove the capabilities of a peer that has since
+/// reconnected.
+///
+/// The registry is cheap to clone; all clones share the same underlying entries.
+#[derive(Clone, Default)]
+pub struct PeerCapabilitiesRegistry {
+    capabilities: Arc<RwLock<HashMap<PeerAuthorizationToken, (String, NodeCapabilities)>>>,
+}
+
+impl PeerCapabilitiesRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the capabilities advertised by the given peer, if the peer advertised any.
+    pub fn get(&self, peer_id: &PeerAuthorizationToken) -> Option<NodeCapabilities> {
+        rwlock_read_unwrap!(self.capabilities)
+            .get(peer_id)
+            .map(|(_, capabilities)| capabilities.clone())
+    }
+
+    /// Returns the capabilities of all peers that advertised them, sorted by peer ID.
+    pub fn list(&self) -> Vec<(PeerAuthorizationToken, NodeCapabilities)> {
+        let mut capabilities = rwlock_read_unwrap!(self.capabilities)
+            .iter()
+            .map(|(peer_id, (_, capabilities))| (peer_id.clone(), capabilities.clone()))
+            .collect::<Vec<_>>();
+        capabilities.sort_by_key(|(peer_id, _)| peer_id.id_as_string());
+        capabilities
+    }
+
+    /// Records the capabilities advertised by a peer on the given connection, replacing any
+    /// previously advertised.
+    pub fn insert(
+        &self,
+        peer_id: PeerAuthorizationToken,
+        connection_id: &str,
+        capabilities: NodeCapabilities,
+    ) {
+        rwlock_write_unwrap!(self.capabilities)
+            .insert(peer_id, (connection_id.to_string(), capabilities));
+    }
+
+    /// Removes the capabilities recorded for a peer.
+    pub fn remove(&self, peer_id: &PeerAuthorizationToken) {
+        rwlock_write_unwrap!(self.capabilities).remove(peer_id);
+    }
+
+    /// Removes the capabilities that were received on the given connection, if the peer has not
+    /// since advertised capabilities on another connection.
+    pub fn remove_connection(&self, connection_id: &str) {
+        rwlock_write_unwrap!(self.capabilities).retain(|_, (capabilities_connection_id, _)| {
+            capabilities_connection_id != connection_id
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the builder produces sorted, deduplicated capabilities and that the support
+    /// checks reflect the built values.
+    #[test]
+    fn test_node_capabilities_builder() {
+        let capabilities = NodeCapabilitiesBuilder::new()
+            .with_service_type("scabbard")
+            .with_service_type("echo")
+            .with_service_type("scabbard")
+            .with_protocol("network", 1)
+            .with_consensus_engine("2pc")
+            .build();
+
+        assert_eq!(capabilities.service_types(), &["echo", "scabbard"]);
+        assert_eq!(capabilities.protocols().get("network"), Some(&1));
+        assert!(capabilities.supports_service_type("scabbard"));
+        assert!(!capabilities.supports_service_type("other"));
+        assert!(capabilities.supports_consensus_engine("2pc"));
+    }
+
+    /// Verify that capabilities can be added, fetched, listed and removed from the registry, and
+    /// that clones of the registry share entries.
+    #[test]
+    fn test_peer_capabilities_registry() {
+        let registry = PeerCapabilitiesRegistry::new();
+        let clone = registry.clone();
+
+        let peer_a = PeerAuthorizationToken::from_peer_id("node_a");
+        let peer_b = PeerAuthorizationToken::from_peer_id("node_b");
+        let capabilities = NodeCapabilitiesBuilder::new()
+            .with_service_type("scabbard")
+            .build();
+
+        registry.insert(peer_b.clone(), "connection_b", NodeCapabilities::default());
+        registry.insert(peer_a.clone(), "connection_a", capabilities.clone());
+
+        assert_eq!(clone.get(&peer_a), Some(capabilities.clone()));
+        assert_eq!(
+            clone.list(),
+            vec![
+                (peer_a.clone(), capabilities),
+                (peer_b, NodeCapabilities::default())
+            ]
+        );
+
+        clone.remove(&peer_a);
+        assert_eq!(registry.get(&peer_a), None);
+    }
+
+    /// Verify that the registry returns the capabilities a peer advertised on its latest
+    /// connection when it reconnects with different capabilities.
+    ///
+    /// 1. Add capabilities for a peer received on its first connection
+    /// 2. Add different capabilities for the peer received on a second connection, as if it
+    ///    reconnected before the first connection's disconnect was processed
+    /// 3. Remove the first connection and check the second connection's capabilities remain
+    /// 4. Remove the second connection and check the peer has no capabilities
+    #[test]
+    fn test_peer_capabilities_registry_reconnect() {
+        let registry = PeerCapabilitiesRegistry::new();
+        let peer = PeerAuthorizationToken::from_peer_id("node_a");
+
+        let old_capabilities = NodeCapabilitiesBuilder::new()
+            .with_service_type("scabbard")
+            .build();
+        let new_capabilities = NodeCapabilitiesBuilder::new()
+            .with_service_type("echo")
+            .build();
+
+        registry.insert(peer.clone(), "connection_1", old_capabilities);
+        registry.insert(peer.clone(), "connection_2", new_capabilities.clone());
+        assert_eq!(registry.get(&peer), Some(new_capabilities.clone()));
+
+        registry.remove_connection("connection_1");
+        assert_eq!(registry.get(&peer), Some(new_capabilities));
+
+        registry.remove_connection("connection_2");
+        assert_eq!(registry.get(&peer), None);
+        assert!(registry.list().is_empty());
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Prioritization of the connections the `PeerManager` requests.
+//!
+//! After a restart, a node may need to reconnect to hundreds of peers at once. The
+//! `ConnectionRamp` limits how many connections to ordinary peers are requested per retry
+//! interval, while connections to priority peers, such as the members of circuits with pending
+//! proposals, are always requested right away.
+
+use std::collections::HashSet;
+
+use super::PeerAuthorizationToken;
+
+/// Decides which peers may be connected to during the current retry interval.
+#[derive(Debug, Default)]
+pub(super) struct ConnectionRamp {
+    /// The number of connections to ordinary peers that may be requested per retry interval,
+    /// or `None` if they are not limited
+    rate: Option<u64>,
+    /// The number of connections to ordinary peers requested during the current interval
+    requested: u64,
+    priority_peers: HashSet<PeerAuthorizationToken>,
+}
+
+impl ConnectionRamp {
+    pub fn new(rate: Option<u64>) -> Self {
+        Self {
+            rate,
+            requested: 0,
+            priority_peers: HashSet::new(),
+        }
+    }
+
+    /// Replaces the set of priority peers.
+    pub fn set_priority_peers(&mut self, peer_ids: Vec<PeerAuthorizationToken>) {
+        self.priority_peers = peer_ids.into_iter().collect();
+    }
+
+    pub fn is_priority(&self, peer_id: &PeerAuthorizationToken) -> bool {
+        self.priority_peers.contains(peer_id)
+    }
+
+    /// Starts a new retry interval, allowing another `rate` connections to ordinary peers.
+    pub fn reset(&mut self) {
+        self.requested = 0;
+    }
+
+    /// Returns whether a connection to the peer may be requested now. Requests to ordinary peers
+    /// count against the rate of the current interval.
+    pub fn try_acquire(&mut self, peer_id: &PeerAuthorizationToken) -> bool {
+        if self.is_priority(peer_id) {
+            return true;
+        }
+
+        match self.rate {
+            Some(rate) if self.requested >= rate => false,
+            _ => {
+                self.requested += 1;
+                true
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that priority peers are always allowed, and that ordinary peers are limited to the
+    /// rate until the ramp is reset.
+    #[test]
+    fn test_connection_ramp() {
+        let priority = PeerAuthorizationToken::from_peer_id("priority");
+        let idle = PeerAuthorizationToken::from_peer_id("idle");
+
+        let mut ramp = ConnectionRamp::new(Some(2));
+        ramp.set_priority_peers(vec![priority.clone()]);
+
+        assert!(ramp.try_acquire(&idle));
+        assert!(ramp.try_acquire(&idle));
+        assert!(!ramp.try_acquire(&idle));
+        assert!(ramp.try_acquire(&priority));
+
+        ramp.reset();
+        assert!(ramp.try_acquire(&idle));
+
+        let mut unlimited = ConnectionRamp::new(None);
+        for _ in 0..100 {
+            assert!(unlimited.try_acquire(&idle));
+        }
+    }
+}
@@ -283,6 +283,32 @@ impl PeerManagerConnector {
             PeerManagerError::SendMessageError("The peer manager is no longer running".into())
         })?
     }
+
+    /// Sets the peers whose connections are requested before those of other peers, such as the
+    /// members of circuits with pending consensus activity. The given peers replace any that were
+    /// previously set.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PeerManagerError` if the `PeerManager` has stopped running.
+    #[cfg(feature = "peer-connect-priority")]
+    pub fn set_priority_peers(
+        &self,
+        peer_ids: Vec<PeerAuthorizationToken>,
+    ) -> Result<(), PeerManagerError> {
+        let (sender, recv) = channel();
+        self.sender
+            .send(PeerManagerMessage::Request(
+                PeerManagerRequest::SetPriorityPeers { peer_ids, sender },
+            ))
+            .map_err(|_| {
+                PeerManagerError::SendMessageError("The peer manager is no longer running".into())
+            })?;
+
+        recv.recv().map_err(|_| {
+            PeerManagerError::SendMessageError("The peer manager is no longer running".into())
+        })?
+    }
 }
 
 impl PeerLookup for PeerManagerConnector {
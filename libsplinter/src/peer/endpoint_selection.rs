@@ -0,0 +1,222 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Policies for choosing which of a peer's endpoints the `PeerManager` connects to first.
+//!
+//! By default, the `PeerManager` tries a peer's endpoints in the order they were provided. An
+//! [`EndpointSelector`] reorders them according to a list of [`EndpointPreference`]s, which may be
+//! set for all peers or for individual peers.
+
+use std::collections::HashMap;
+
+use super::PeerAuthorizationToken;
+
+/// A rule for ordering a peer's endpoints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndpointPreference {
+    /// Prefer endpoints that are labeled with the same region as the local node
+    SameRegion,
+    /// Prefer `tcps` endpoints over `tcp` endpoints
+    SecureTransport,
+    /// Prefer the endpoint of the last successful connection to the peer
+    LastSuccessful,
+}
+
+/// An ordered list of preferences; earlier preferences take precedence over later ones, and
+/// endpoints that no preference distinguishes keep the order in which they were provided.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EndpointSelectionPolicy {
+    preferences: Vec<EndpointPreference>,
+}
+
+impl EndpointSelectionPolicy {
+    pub fn new(preferences: Vec<EndpointPreference>) -> Self {
+        EndpointSelectionPolicy { preferences }
+    }
+
+    pub fn preferences(&self) -> &[EndpointPreference] {
+        &self.preferences
+    }
+}
+
+/// Orders the endpoints of peers according to their selection policies.
+///
+/// Peers are configured by the string form of their authorization token: the node ID for peers
+/// using trust authorization, or `public_key::<hex key>` for peers using challenge authorization.
+/// Peers without their own policy use the default policy, which is empty unless set, leaving the
+/// endpoints in the order they were provided.
+#[derive(Clone, Debug, Default)]
+pub struct EndpointSelector {
+    local_region: Option<String>,
+    endpoint_regions: HashMap<String, String>,
+    default_policy: EndpointSelectionPolicy,
+    peer_policies: HashMap<String, EndpointSelectionPolicy>,
+    last_successful: HashMap<PeerAuthorizationToken, String>,
+}
+
+impl EndpointSelector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the region label of the local node, used by [`EndpointPreference::SameRegion`].
+    pub fn with_local_region(mut self, region: &str) -> Self {
+        self.local_region = Some(region.to_string());
+        self
+    }
+
+    /// Labels an endpoint with the region it is in, used by [`EndpointPreference::SameRegion`].
+    pub fn with_endpoint_region(mut self, endpoint: &str, region: &str) -> Self {
+        self.endpoint_regions
+            .insert(endpoint.to_string(), region.to_string());
+        self
+    }
+
+    /// Sets the policy used for peers that do not have their own policy.
+    pub fn with_default_policy(mut self, policy: EndpointSelectionPolicy) -> Self {
+        self.default_policy = policy;
+        self
+    }
+
+    /// Sets the policy for a single peer, identified by its node ID or `public_key::<hex key>`.
+    pub fn with_peer_policy(mut self, peer_id: &str, policy: EndpointSelectionPolicy) -> Self {
+        self.peer_policies.insert(peer_id.to_string(), policy);
+        self
+    }
+
+    /// Returns the peer's endpoints, most preferred first.
+    pub(crate) fn order_endpoints(
+        &self,
+        peer_id: &PeerAuthorizationToken,
+        endpoints: Vec<String>,
+    ) -> Vec<String> {
+        let policy = self
+            .peer_policies
+            .get(&peer_id.id_as_string())
+            .unwrap_or(&self.default_policy);
+
+        if policy.preferences.is_empty() {
+            return endpoints;
+        }
+
+        let last_successful = self.last_successful.get(peer_id);
+
+        let mut endpoints = endpoints;
+        // the sort is stable, so endpoints that are ranked equally keep their relative order
+        endpoints.sort_by_cached_key(|endpoint| {
+            policy
+                .preferences
+                .iter()
+                .map(|preference| {
+                    let preferred = match preference {
+                        EndpointPreference::SameRegion => {
+                            self.local_region.is_some()
+                                && self.endpoint_regions.get(endpoint) == self.local_region.as_ref()
+                        }
+                        EndpointPreference::SecureTransport => endpoint.starts_with("tcps://"),
+                        EndpointPreference::LastSuccessful => last_successful == Some(endpoint),
+                    };
+                    !preferred
+                })
+                .collect::<Vec<_>>()
+        });
+        endpoints
+    }
+
+    /// Records that a connection to the peer was established on the given endpoint.
+    pub(crate) fn record_success(&mut self, peer_id: &PeerAuthorizationToken, endpoint: &str) {
+        self.last_successful
+            .insert(peer_id.clone(), endpoint.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endpoints() -> Vec<String> {
+        vec![
+            "tcp://us-east.acme.com:8044".to_string(),
+            "tcp://eu-west.acme.com:8044".to_string(),
+            "tcps://us-east.acme.com:8044".to_string(),
+            "tcps://eu-west.acme.com:8044".to_string(),
+        ]
+    }
+
+    /// Verify that endpoints keep their order when no policy applies to the peer.
+    #[test]
+    fn test_order_endpoints_without_policy() {
+        let peer_id = PeerAuthorizationToken::from_peer_id("acme-node-000");
+        let mut selector = EndpointSelector::new().with_local_region("eu-west");
+        selector.record_success(&peer_id, "tcps://eu-west.acme.com:8044");
+
+        assert_eq!(selector.order_endpoints(&peer_id, endpoints()), endpoints());
+    }
+
+    /// Verify that the preferences are applied in order, that ties keep the provided order, and
+    /// that a peer's own policy takes precedence over the default policy.
+    #[test]
+    fn test_order_endpoints() {
+        let peer_id = PeerAuthorizationToken::from_peer_id("acme-node-000");
+        let other_peer_id = PeerAuthorizationToken::from_peer_id("bubba-node-000");
+
+        let mut selector = EndpointSelector::new()
+            .with_local_region("eu-west")
+            .with_endpoint_region("tcp://eu-west.acme.com:8044", "eu-west")
+            .with_endpoint_region("tcps://eu-west.acme.com:8044", "eu-west")
+            .with_endpoint_region("tcps://us-east.acme.com:8044", "us-east")
+            .with_default_policy(EndpointSelectionPolicy::new(vec![
+                EndpointPreference::SecureTransport,
+            ]))
+            .with_peer_policy(
+                "acme-node-000",
+                EndpointSelectionPolicy::new(vec![
+                    EndpointPreference::LastSuccessful,
+                    EndpointPreference::SameRegion,
+                    EndpointPreference::SecureTransport,
+                ]),
+            );
+
+        assert_eq!(
+            selector.order_endpoints(&peer_id, endpoints()),
+            vec![
+                "tcps://eu-west.acme.com:8044".to_string(),
+                "tcp://eu-west.acme.com:8044".to_string(),
+                "tcps://us-east.acme.com:8044".to_string(),
+                "tcp://us-east.acme.com:8044".to_string(),
+            ]
+        );
+
+        selector.record_success(&peer_id, "tcp://us-east.acme.com:8044");
+        assert_eq!(
+            selector.order_endpoints(&peer_id, endpoints()),
+            vec![
+                "tcp://us-east.acme.com:8044".to_string(),
+                "tcps://eu-west.acme.com:8044".to_string(),
+                "tcp://eu-west.acme.com:8044".to_string(),
+                "tcps://us-east.acme.com:8044".to_string(),
+            ]
+        );
+
+        assert_eq!(
+            selector.order_endpoints(&other_peer_id, endpoints()),
+            vec![
+                "tcps://us-east.acme.com:8044".to_string(),
+                "tcps://eu-west.acme.com:8044".to_string(),
+                "tcp://us-east.acme.com:8044".to_string(),
+                "tcp://eu-west.acme.com:8044".to_string(),
+            ]
+        );
+    }
+}
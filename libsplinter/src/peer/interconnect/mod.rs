@@ -433,6 +433,9 @@ where
                 connection_id,
                 network_msg.get_message_type()
             );
+            // The message is dispatched in the context of the span that sent it
+            #[cfg(feature = "otel-tracing")]
+            let _trace_context = crate::otel::extract_context(&network_msg).attach();
             match dispatch_msg_sender.send(
                 network_msg.get_message_type(),
                 network_msg.take_payload(),
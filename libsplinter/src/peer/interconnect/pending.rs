@@ -141,6 +141,8 @@ where
                     connection_id,
                     network_msg.get_message_type()
                 );
+                #[cfg(feature = "otel-tracing")]
+                let _trace_context = crate::otel::extract_context(&network_msg).attach();
                 match dispatch_msg_sender.send(
                     network_msg.get_message_type(),
                     network_msg.take_payload(),
@@ -0,0 +1,162 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Operator-maintained metadata about peers.
+//!
+//! Operators can attach free-form notes and `key=value` labels to a peer, such as who to contact
+//! about the peer, related ticket references or its maintenance history. The metadata is only
+//! kept on the local node, in a [`PeerMetadataStore`](store::PeerMetadataStore), and is never
+//! sent to the peer. Metadata may be kept for a peer that is not currently connected.
+
+pub mod store;
+
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+use crate::error::InvalidStateError;
+
+/// The notes and labels attached to a peer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerMetadata {
+    peer_id: String,
+    notes: String,
+    labels: BTreeMap<String, String>,
+    updated_at: SystemTime,
+}
+
+impl PeerMetadata {
+    /// Returns the ID of the peer the metadata is attached to
+    pub fn peer_id(&self) -> &str {
+        &self.peer_id
+    }
+
+    /// Returns the free-form notes about the peer
+    pub fn notes(&self) -> &str {
+        &self.notes
+    }
+
+    /// Returns the labels attached to the peer, sorted by key
+    pub fn labels(&self) -> &BTreeMap<String, String> {
+        &self.labels
+    }
+
+    /// Returns the time the metadata was last changed
+    pub fn updated_at(&self) -> SystemTime {
+        self.updated_at
+    }
+}
+
+/// Builds [`PeerMetadata`].
+#[derive(Default)]
+pub struct PeerMetadataBuilder {
+    peer_id: Option<String>,
+    notes: Option<String>,
+    labels: BTreeMap<String, String>,
+    updated_at: Option<SystemTime>,
+}
+
+impl PeerMetadataBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_peer_id(mut self, peer_id: String) -> Self {
+        self.peer_id = Some(peer_id);
+        self
+    }
+
+    pub fn with_notes(mut self, notes: String) -> Self {
+        self.notes = Some(notes);
+        self
+    }
+
+    /// Adds a label, replacing any existing label with the same key
+    pub fn with_label(mut self, key: String, value: String) -> Self {
+        self.labels.insert(key, value);
+        self
+    }
+
+    /// Replaces all of the labels
+    pub fn with_labels(mut self, labels: BTreeMap<String, String>) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets the time the metadata was last changed; defaults to the current time.
+    pub fn with_updated_at(mut self, updated_at: SystemTime) -> Self {
+        self.updated_at = Some(updated_at);
+        self
+    }
+
+    /// Builds the peer metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidStateError` if the peer ID is missing or empty, or if a label has an
+    /// empty key.
+    pub fn build(self) -> Result<PeerMetadata, InvalidStateError> {
+        let peer_id = self
+            .peer_id
+            .filter(|peer_id| !peer_id.trim().is_empty())
+            .ok_or_else(|| {
+                InvalidStateError::with_message(
+                    "peer metadata requires a non-empty peer ID".to_string(),
+                )
+            })?;
+        if self.labels.keys().any(|key| key.trim().is_empty()) {
+            return Err(InvalidStateError::with_message(
+                "peer metadata labels must have non-empty keys".to_string(),
+            ));
+        }
+
+        Ok(PeerMetadata {
+            peer_id,
+            notes: self.notes.unwrap_or_default(),
+            labels: self.labels,
+            updated_at: self.updated_at.unwrap_or_else(SystemTime::now),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the builder defaults the notes and rejects a missing peer ID or an empty label
+    /// key.
+    #[test]
+    fn test_peer_metadata_builder() {
+        let metadata = PeerMetadataBuilder::new()
+            .with_peer_id("node_a".to_string())
+            .with_label("contact".to_string(), "ops@example.com".to_string())
+            .build()
+            .expect("Failed to build peer metadata");
+        assert_eq!(metadata.peer_id(), "node_a");
+        assert_eq!(metadata.notes(), "");
+        assert_eq!(
+            metadata.labels().get("contact").map(String::as_str),
+            Some("ops@example.com")
+        );
+
+        assert!(PeerMetadataBuilder::new()
+            .with_notes("no peer".to_string())
+            .build()
+            .is_err());
+        assert!(PeerMetadataBuilder::new()
+            .with_peer_id("node_a".to_string())
+            .with_label(" ".to_string(), "value".to_string())
+            .build()
+            .is_err());
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Database-backed implementation of the [PeerMetadataStore], powered by [diesel].
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::peer::metadata::PeerMetadata;
+use crate::store::pool::ConnectionPool;
+
+use super::{PeerMetadataStore, PeerMetadataStoreError};
+
+use operations::{
+    get_metadata::PeerMetadataStoreGetMetadata as _,
+    list_metadata::PeerMetadataStoreListMetadata as _,
+    remove_metadata::PeerMetadataStoreRemoveMetadata as _,
+    set_metadata::PeerMetadataStoreSetMetadata as _, PeerMetadataStoreOperations,
+};
+
+/// Manages the metadata attached to peers in the database
+pub struct DieselPeerMetadataStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselPeerMetadataStore<C> {
+    /// Creates a new DieselPeerMetadataStore
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool to the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselPeerMetadataStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselPeerMetadataStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl PeerMetadataStore for DieselPeerMetadataStore<diesel::pg::PgConnection> {
+    fn set_metadata(&self, metadata: &PeerMetadata) -> Result<(), PeerMetadataStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerMetadataStoreOperations::new(connection).set_metadata(metadata)
+        })
+    }
+
+    fn get_metadata(&self, peer_id: &str) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PeerMetadataStoreOperations::new(connection).get_metadata(peer_id)
+        })
+    }
+
+    fn list_metadata(&self) -> Result<Vec<PeerMetadata>, PeerMetadataStoreError> {
+        self.connection_pool
+            .execute_read(|connection| PeerMetadataStoreOperations::new(connection).list_metadata())
+    }
+
+    fn remove_metadata(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerMetadataStoreOperations::new(connection).remove_metadata(peer_id)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn PeerMetadataStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl PeerMetadataStore for DieselPeerMetadataStore<diesel::sqlite::SqliteConnection> {
+    fn set_metadata(&self, metadata: &PeerMetadata) -> Result<(), PeerMetadataStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerMetadataStoreOperations::new(connection).set_metadata(metadata)
+        })
+    }
+
+    fn get_metadata(&self, peer_id: &str) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            PeerMetadataStoreOperations::new(connection).get_metadata(peer_id)
+        })
+    }
+
+    fn list_metadata(&self) -> Result<Vec<PeerMetadata>, PeerMetadataStoreError> {
+        self.connection_pool
+            .execute_read(|connection| PeerMetadataStoreOperations::new(connection).list_metadata())
+    }
+
+    fn remove_metadata(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            PeerMetadataStoreOperations::new(connection).remove_metadata(peer_id)
+        })
+    }
+
+    fn clone_box(&self) -> Box<dyn PeerMetadataStore> {
+        Box::new(Self {
+            connection_pool: self.connection_pool.clone(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::peer::metadata::PeerMetadataBuilder;
+
+    /// Verify that a SQLite-backed `DieselPeerMetadataStore` correctly supports setting, getting,
+    /// listing and removing peer metadata.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Set the metadata of two peers and verify that they are listed sorted by peer ID.
+    /// 3. Replace the metadata of a peer and verify that its old labels are removed.
+    /// 4. Remove the metadata of a peer and verify that it is returned, and that it can no
+    ///    longer be fetched.
+    #[test]
+    fn sqlite_peer_metadata() {
+        let store = DieselPeerMetadataStore::new(create_connection_pool_and_migrate());
+
+        let node_b = PeerMetadataBuilder::new()
+            .with_peer_id("node_b".to_string())
+            .with_notes("Maintenance window on Sundays".to_string())
+            .with_label("contact".to_string(), "ops@example.com".to_string())
+            .with_label("ticket".to_string(), "OPS-123".to_string())
+            .with_updated_at(UNIX_EPOCH + Duration::from_secs(1000))
+            .build()
+            .expect("Failed to build metadata");
+        let node_a = PeerMetadataBuilder::new()
+            .with_peer_id("node_a".to_string())
+            .with_updated_at(UNIX_EPOCH + Duration::from_secs(2000))
+            .build()
+            .expect("Failed to build metadata");
+
+        store.set_metadata(&node_b).expect("Failed to set metadata");
+        store.set_metadata(&node_a).expect("Failed to set metadata");
+
+        assert_eq!(
+            store.list_metadata().expect("Failed to list metadata"),
+            vec![node_a.clone(), node_b.clone()]
+        );
+        assert_eq!(
+            store
+                .get_metadata("node_b")
+                .expect("Failed to get metadata"),
+            Some(node_b)
+        );
+
+        let updated_b = PeerMetadataBuilder::new()
+            .with_peer_id("node_b".to_string())
+            .with_notes("Decommissioned".to_string())
+            .with_label("ticket".to_string(), "OPS-456".to_string())
+            .with_updated_at(UNIX_EPOCH + Duration::from_secs(3000))
+            .build()
+            .expect("Failed to build metadata");
+        store
+            .set_metadata(&updated_b)
+            .expect("Failed to set metadata");
+        assert_eq!(
+            store
+                .get_metadata("node_b")
+                .expect("Failed to get metadata"),
+            Some(updated_b)
+        );
+
+        assert_eq!(
+            store
+                .remove_metadata("node_a")
+                .expect("Failed to remove metadata"),
+            Some(node_a)
+        );
+        assert_eq!(
+            store
+                .get_metadata("node_a")
+                .expect("Failed to get metadata"),
+            None
+        );
+        assert_eq!(
+            store
+                .remove_metadata("node_a")
+                .expect("Failed to remove metadata"),
+            None
+        );
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,109 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::error::{InternalError, InvalidArgumentError};
+use crate::peer::metadata::store::PeerMetadataStoreError;
+use crate::peer::metadata::{PeerMetadata, PeerMetadataBuilder};
+
+use super::schema::{peer_metadata, peer_metadata_label};
+
+#[derive(Queryable, Insertable, Identifiable, PartialEq, Eq, Debug)]
+#[table_name = "peer_metadata"]
+#[primary_key(peer_id)]
+pub struct PeerMetadataModel {
+    pub peer_id: String,
+    pub notes: String,
+    pub updated_at: i64,
+}
+
+#[derive(Queryable, Insertable, PartialEq, Eq, Debug)]
+#[table_name = "peer_metadata_label"]
+pub struct PeerMetadataLabelModel {
+    pub peer_id: String,
+    pub label_key: String,
+    pub label_value: String,
+}
+
+impl TryFrom<&PeerMetadata> for PeerMetadataModel {
+    type Error = PeerMetadataStoreError;
+
+    fn try_from(metadata: &PeerMetadata) -> Result<Self, Self::Error> {
+        let updated_at = metadata
+            .updated_at()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+            .ok_or_else(|| {
+                PeerMetadataStoreError::InvalidArgument(InvalidArgumentError::new(
+                    "updated_at".to_string(),
+                    "time cannot be stored as a timestamp".to_string(),
+                ))
+            })?;
+
+        Ok(PeerMetadataModel {
+            peer_id: metadata.peer_id().to_string(),
+            notes: metadata.notes().to_string(),
+            updated_at,
+        })
+    }
+}
+
+/// Returns the label models of the given metadata
+pub fn make_label_models(metadata: &PeerMetadata) -> Vec<PeerMetadataLabelModel> {
+    metadata
+        .labels()
+        .iter()
+        .map(|(key, value)| PeerMetadataLabelModel {
+            peer_id: metadata.peer_id().to_string(),
+            label_key: key.to_string(),
+            label_value: value.to_string(),
+        })
+        .collect()
+}
+
+impl TryFrom<(PeerMetadataModel, Vec<PeerMetadataLabelModel>)> for PeerMetadata {
+    type Error = PeerMetadataStoreError;
+
+    fn try_from(
+        (model, labels): (PeerMetadataModel, Vec<PeerMetadataLabelModel>),
+    ) -> Result<Self, Self::Error> {
+        let updated_at = u64::try_from(model.updated_at)
+            .ok()
+            .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+            .ok_or_else(|| {
+                PeerMetadataStoreError::Internal(InternalError::with_message(format!(
+                    "timestamp {} could not be represented as a SystemTime",
+                    model.updated_at
+                )))
+            })?;
+
+        PeerMetadataBuilder::new()
+            .with_peer_id(model.peer_id)
+            .with_notes(model.notes)
+            .with_labels(
+                labels
+                    .into_iter()
+                    .map(|label| (label.label_key, label.label_value))
+                    .collect(),
+            )
+            .with_updated_at(updated_at)
+            .build()
+            .map_err(|err| {
+                PeerMetadataStoreError::Internal(InternalError::from_source(Box::new(err)))
+            })
+    }
+}
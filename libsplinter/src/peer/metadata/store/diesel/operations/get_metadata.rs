@@ -0,0 +1,58 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::peer::metadata::store::{
+    diesel::{
+        models::{PeerMetadataLabelModel, PeerMetadataModel},
+        schema::{peer_metadata, peer_metadata_label},
+    },
+    PeerMetadataStoreError,
+};
+use crate::peer::metadata::PeerMetadata;
+
+use super::PeerMetadataStoreOperations;
+
+pub trait PeerMetadataStoreGetMetadata {
+    fn get_metadata(&self, peer_id: &str) -> Result<Option<PeerMetadata>, PeerMetadataStoreError>;
+}
+
+impl<'a, C> PeerMetadataStoreGetMetadata for PeerMetadataStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn get_metadata(&self, peer_id: &str) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        self.conn.transaction::<_, PeerMetadataStoreError, _>(|| {
+            let model = match peer_metadata::table
+                .filter(peer_metadata::peer_id.eq(peer_id))
+                .first::<PeerMetadataModel>(self.conn)
+                .optional()?
+            {
+                Some(model) => model,
+                None => return Ok(None),
+            };
+
+            let labels = peer_metadata_label::table
+                .filter(peer_metadata_label::peer_id.eq(peer_id))
+                .load::<PeerMetadataLabelModel>(self.conn)?;
+
+            PeerMetadata::try_from((model, labels)).map(Some)
+        })
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use diesel::prelude::*;
+
+use crate::peer::metadata::store::{
+    diesel::{
+        models::{PeerMetadataLabelModel, PeerMetadataModel},
+        schema::{peer_metadata, peer_metadata_label},
+    },
+    PeerMetadataStoreError,
+};
+use crate::peer::metadata::PeerMetadata;
+
+use super::PeerMetadataStoreOperations;
+
+pub trait PeerMetadataStoreListMetadata {
+    fn list_metadata(&self) -> Result<Vec<PeerMetadata>, PeerMetadataStoreError>;
+}
+
+impl<'a, C> PeerMetadataStoreListMetadata for PeerMetadataStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+{
+    fn list_metadata(&self) -> Result<Vec<PeerMetadata>, PeerMetadataStoreError> {
+        self.conn.transaction::<_, PeerMetadataStoreError, _>(|| {
+            let models = peer_metadata::table
+                .order(peer_metadata::peer_id)
+                .load::<PeerMetadataModel>(self.conn)?;
+
+            let mut labels: HashMap<String, Vec<PeerMetadataLabelModel>> = HashMap::new();
+            for label in peer_metadata_label::table.load::<PeerMetadataLabelModel>(self.conn)? {
+                labels.entry(label.peer_id.clone()).or_default().push(label);
+            }
+
+            models
+                .into_iter()
+                .map(|model| {
+                    let peer_labels = labels.remove(&model.peer_id).unwrap_or_default();
+                    PeerMetadata::try_from((model, peer_labels))
+                })
+                .collect()
+        })
+    }
+}
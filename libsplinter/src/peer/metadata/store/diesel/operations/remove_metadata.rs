@@ -0,0 +1,76 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+
+use crate::peer::metadata::store::{
+    diesel::schema::{peer_metadata, peer_metadata_label},
+    PeerMetadataStoreError,
+};
+use crate::peer::metadata::PeerMetadata;
+
+use super::{get_metadata::PeerMetadataStoreGetMetadata as _, PeerMetadataStoreOperations};
+
+pub trait PeerMetadataStoreRemoveMetadata {
+    fn remove_metadata(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerMetadata>, PeerMetadataStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PeerMetadataStoreRemoveMetadata
+    for PeerMetadataStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_metadata(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        self.conn.transaction::<_, PeerMetadataStoreError, _>(|| {
+            let existing = self.get_metadata(peer_id)?;
+
+            if existing.is_some() {
+                delete(peer_metadata_label::table.filter(peer_metadata_label::peer_id.eq(peer_id)))
+                    .execute(self.conn)?;
+                delete(peer_metadata::table.filter(peer_metadata::peer_id.eq(peer_id)))
+                    .execute(self.conn)?;
+            }
+
+            Ok(existing)
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PeerMetadataStoreRemoveMetadata
+    for PeerMetadataStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_metadata(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        self.conn.transaction::<_, PeerMetadataStoreError, _>(|| {
+            let existing = self.get_metadata(peer_id)?;
+
+            if existing.is_some() {
+                delete(peer_metadata_label::table.filter(peer_metadata_label::peer_id.eq(peer_id)))
+                    .execute(self.conn)?;
+                delete(peer_metadata::table.filter(peer_metadata::peer_id.eq(peer_id)))
+                    .execute(self.conn)?;
+            }
+
+            Ok(existing)
+        })
+    }
+}
@@ -0,0 +1,97 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+
+use diesel::{
+    dsl::{delete, insert_into},
+    prelude::*,
+};
+
+use crate::peer::metadata::store::{
+    diesel::{
+        models::{make_label_models, PeerMetadataModel},
+        schema::{peer_metadata, peer_metadata_label},
+    },
+    PeerMetadataStoreError,
+};
+use crate::peer::metadata::PeerMetadata;
+
+use super::PeerMetadataStoreOperations;
+
+pub trait PeerMetadataStoreSetMetadata {
+    fn set_metadata(&self, metadata: &PeerMetadata) -> Result<(), PeerMetadataStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PeerMetadataStoreSetMetadata
+    for PeerMetadataStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn set_metadata(&self, metadata: &PeerMetadata) -> Result<(), PeerMetadataStoreError> {
+        let model = PeerMetadataModel::try_from(metadata)?;
+        let labels = make_label_models(metadata);
+
+        self.conn.transaction::<_, PeerMetadataStoreError, _>(|| {
+            delete(
+                peer_metadata_label::table
+                    .filter(peer_metadata_label::peer_id.eq(metadata.peer_id())),
+            )
+            .execute(self.conn)?;
+            delete(peer_metadata::table.filter(peer_metadata::peer_id.eq(metadata.peer_id())))
+                .execute(self.conn)?;
+
+            insert_into(peer_metadata::table)
+                .values(&model)
+                .execute(self.conn)?;
+            if !labels.is_empty() {
+                insert_into(peer_metadata_label::table)
+                    .values(&labels)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> PeerMetadataStoreSetMetadata
+    for PeerMetadataStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn set_metadata(&self, metadata: &PeerMetadata) -> Result<(), PeerMetadataStoreError> {
+        let model = PeerMetadataModel::try_from(metadata)?;
+        let labels = make_label_models(metadata);
+
+        self.conn.transaction::<_, PeerMetadataStoreError, _>(|| {
+            delete(
+                peer_metadata_label::table
+                    .filter(peer_metadata_label::peer_id.eq(metadata.peer_id())),
+            )
+            .execute(self.conn)?;
+            delete(peer_metadata::table.filter(peer_metadata::peer_id.eq(metadata.peer_id())))
+                .execute(self.conn)?;
+
+            insert_into(peer_metadata::table)
+                .values(&model)
+                .execute(self.conn)?;
+            if !labels.is_empty() {
+                insert_into(peer_metadata_label::table)
+                    .values(&labels)
+                    .execute(self.conn)?;
+            }
+
+            Ok(())
+        })
+    }
+}
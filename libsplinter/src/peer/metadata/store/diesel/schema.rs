@@ -0,0 +1,29 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+table! {
+    peer_metadata (peer_id) {
+        peer_id -> Text,
+        notes -> Text,
+        updated_at -> BigInt,
+    }
+}
+
+table! {
+    peer_metadata_label (peer_id, label_key) {
+        peer_id -> Text,
+        label_key -> Text,
+        label_value -> Text,
+    }
+}
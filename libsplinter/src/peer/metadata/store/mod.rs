@@ -0,0 +1,92 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Defines the store that persists the metadata operators attach to peers.
+
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod error;
+
+use super::PeerMetadata;
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselPeerMetadataStore;
+pub use error::PeerMetadataStoreError;
+
+/// Defines methods for persisting the metadata attached to peers
+pub trait PeerMetadataStore: Send + Sync {
+    /// Sets the metadata of a peer, replacing any existing notes and labels
+    ///
+    /// # Arguments
+    ///
+    ///  * `metadata` - The metadata to set
+    fn set_metadata(&self, metadata: &PeerMetadata) -> Result<(), PeerMetadataStoreError>;
+
+    /// Returns the metadata of the given peer, if it has been set
+    ///
+    /// # Arguments
+    ///
+    ///  * `peer_id` - The ID of the peer
+    fn get_metadata(&self, peer_id: &str) -> Result<Option<PeerMetadata>, PeerMetadataStoreError>;
+
+    /// Returns the metadata of every peer, sorted by peer ID
+    fn list_metadata(&self) -> Result<Vec<PeerMetadata>, PeerMetadataStoreError>;
+
+    /// Removes the metadata of the given peer, returning it if it existed
+    ///
+    /// # Arguments
+    ///
+    ///  * `peer_id` - The ID of the peer
+    fn remove_metadata(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerMetadata>, PeerMetadataStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn PeerMetadataStore>;
+}
+
+impl Clone for Box<dyn PeerMetadataStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<PS> PeerMetadataStore for Box<PS>
+where
+    PS: PeerMetadataStore + ?Sized,
+{
+    fn set_metadata(&self, metadata: &PeerMetadata) -> Result<(), PeerMetadataStoreError> {
+        (**self).set_metadata(metadata)
+    }
+
+    fn get_metadata(&self, peer_id: &str) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        (**self).get_metadata(peer_id)
+    }
+
+    fn list_metadata(&self) -> Result<Vec<PeerMetadata>, PeerMetadataStoreError> {
+        (**self).list_metadata()
+    }
+
+    fn remove_metadata(
+        &self,
+        peer_id: &str,
+    ) -> Result<Option<PeerMetadata>, PeerMetadataStoreError> {
+        (**self).remove_metadata(peer_id)
+    }
+
+    fn clone_box(&self) -> Box<dyn PeerMetadataStore> {
+        (**self).clone_box()
+    }
+}
@@ -22,13 +22,23 @@
 //! [`PeerInterconnect`]: interconnect/struct.PeerInterconnect.html
 //! [`PeerManagerNotification`]: notification/enum.PeerManagerNotification.html
 
+#[cfg(feature = "peer-blocklist")]
+pub mod blocklist;
 mod builder;
+mod capabilities;
+#[cfg(feature = "peer-connect-priority")]
+mod connect_priority;
 mod connector;
+#[cfg(feature = "peer-endpoint-selection")]
+mod endpoint_selection;
 mod error;
 pub mod interconnect;
+#[cfg(feature = "peer-metadata")]
+pub mod metadata;
 mod notification;
 mod peer_map;
 mod peer_ref;
+mod ref_counts;
 mod token;
 mod unreferenced;
 
@@ -38,6 +48,8 @@ use std::sync::mpsc::{channel, Sender};
 use std::thread;
 use std::time::Instant;
 
+#[cfg(feature = "peer-reconnect-policy")]
+use rand::Rng;
 use uuid::Uuid;
 
 use crate::collections::{BiHashMap, RefMap};
@@ -48,8 +60,13 @@ use crate::threading::lifecycle::ShutdownHandle;
 use crate::threading::pacemaker;
 
 pub use self::builder::PeerManagerBuilder;
+pub use self::capabilities::{NodeCapabilities, NodeCapabilitiesBuilder, PeerCapabilitiesRegistry};
+#[cfg(feature = "peer-connect-priority")]
+use self::connect_priority::ConnectionRamp;
 use self::connector::PeerRemover;
 pub use self::connector::{PeerLookup, PeerManagerConnector};
+#[cfg(feature = "peer-endpoint-selection")]
+pub use self::endpoint_selection::{EndpointPreference, EndpointSelectionPolicy, EndpointSelector};
 use self::error::{
     PeerConnectionIdError, PeerListError, PeerLookupError, PeerManagerError, PeerRefAddError,
     PeerRefRemoveError, PeerUnknownAddError,
@@ -58,6 +75,10 @@ pub use self::notification::{PeerManagerNotification, PeerNotificationIter, Subs
 use self::notification::{Subscriber, SubscriberMap};
 use self::peer_map::{PeerMap, PeerStatus};
 pub use self::peer_ref::{EndpointPeerRef, PeerRef};
+#[cfg(feature = "peer-ref-counts")]
+pub use self::ref_counts::PeerRefCounts;
+#[cfg(not(feature = "peer-ref-counts"))]
+use self::ref_counts::PeerRefCounts;
 pub use self::token::{PeerAuthorizationToken, PeerTokenPair};
 use self::unreferenced::{RequestedEndpoint, UnreferencedPeer, UnreferencedPeerState};
 
@@ -129,6 +150,11 @@ pub(crate) enum PeerManagerRequest {
         subscriber_id: SubscriberId,
         sender: Sender<Result<(), PeerManagerError>>,
     },
+    #[cfg(feature = "peer-connect-priority")]
+    SetPriorityPeers {
+        peer_ids: Vec<PeerAuthorizationToken>,
+        sender: Sender<Result<(), PeerManagerError>>,
+    },
 }
 
 /// The `PeerManager` is in charge of keeping track of peers and their reference counts, as well as
@@ -138,6 +164,8 @@ pub struct PeerManager {
     join_handle: thread::JoinHandle<()>,
     sender: Sender<PeerManagerMessage>,
     pacemaker_shutdown_signaler: pacemaker::ShutdownSignaler,
+    #[cfg(feature = "peer-ref-counts")]
+    ref_counts: PeerRefCounts,
 }
 
 impl PeerManager {
@@ -205,6 +233,13 @@ impl PeerManager {
         PeerManagerConnector::new(self.sender.clone())
     }
 
+    /// Returns the reference counting settings of the `PeerManager`, which may be changed while
+    /// it is running.
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn ref_counts(&self) -> PeerRefCounts {
+        self.ref_counts.clone()
+    }
+
     /// Private constructor used by the builder to start the peer manager
     #[allow(clippy::too_many_arguments)]
     // Allow clippy errors for too_many_arguments. This method is private and is in support of the
@@ -219,6 +254,11 @@ impl PeerManager {
         retry_frequency: u64,
         max_retry_frequency: u64,
         endpoint_retry_frequency: u64,
+        #[cfg(feature = "peer-endpoint-selection")] endpoint_selector: EndpointSelector,
+        #[cfg(feature = "peer-reconnect-policy")] retry_jitter: u64,
+        #[cfg(feature = "peer-reconnect-policy")] max_reconnect_attempts: Option<u64>,
+        #[cfg(feature = "peer-connect-priority")] connect_ramp_rate: Option<u64>,
+        capabilities_registry: PeerCapabilitiesRegistry,
     ) -> Result<PeerManager, PeerManagerError> {
         debug!(
             "Starting peer manager with identity={}, retry_interval={}s, max_retry_attempts={} \
@@ -232,6 +272,16 @@ impl PeerManager {
             max_retry_frequency,
             endpoint_retry_frequency,
         );
+        #[cfg(feature = "peer-reconnect-policy")]
+        debug!(
+            "Peer manager reconnect policy: retry_jitter={}s, max_reconnect_attempts={:?}",
+            retry_jitter, max_reconnect_attempts,
+        );
+        #[cfg(feature = "peer-connect-priority")]
+        debug!(
+            "Peer manager connect ramp rate: {:?} connections per retry interval",
+            connect_ramp_rate
+        );
 
         let (sender, recv) = channel();
 
@@ -260,6 +310,10 @@ impl PeerManager {
 
         let pacemaker_shutdown_signaler = pacemaker.shutdown_signaler();
 
+        let ref_counts = PeerRefCounts::new(strict_ref_counts);
+        #[cfg(feature = "peer-ref-counts")]
+        let manager_ref_counts = ref_counts.clone();
+
         let join_handle = thread::Builder::new()
             .name("Peer Manager".into())
             .spawn(move || {
@@ -269,6 +323,10 @@ impl PeerManager {
                 let mut unreferenced_peers = UnreferencedPeerState::new(endpoint_retry_frequency);
                 let mut ref_map = RefMap::new();
                 let mut subscribers = SubscriberMap::new();
+                #[cfg(feature = "peer-endpoint-selection")]
+                let mut endpoint_selector = endpoint_selector;
+                #[cfg(feature = "peer-connect-priority")]
+                let mut ramp = ConnectionRamp::new(connect_ramp_rate);
                 loop {
                     match recv.recv() {
                         Ok(PeerManagerMessage::Shutdown) => break,
@@ -281,7 +339,11 @@ impl PeerManager {
                                 &peer_remover,
                                 &mut ref_map,
                                 &mut subscribers,
-                                strict_ref_counts,
+                                &ref_counts,
+                                #[cfg(feature = "peer-endpoint-selection")]
+                                &endpoint_selector,
+                                #[cfg(feature = "peer-connect-priority")]
+                                &mut ramp,
                             );
                         }
                         Ok(PeerManagerMessage::Subscribe(sender)) => {
@@ -300,6 +362,9 @@ impl PeerManager {
                                 max_retry_attempts,
                                 &mut ref_map,
                                 retry_frequency,
+                                &capabilities_registry,
+                                #[cfg(feature = "peer-endpoint-selection")]
+                                &mut endpoint_selector,
                             )
                         }
                         Ok(PeerManagerMessage::RetryPending) => retry_pending(
@@ -307,6 +372,12 @@ impl PeerManager {
                             connector.clone(),
                             &mut unreferenced_peers,
                             max_retry_frequency,
+                            #[cfg(feature = "peer-reconnect-policy")]
+                            retry_jitter,
+                            #[cfg(feature = "peer-reconnect-policy")]
+                            max_reconnect_attempts,
+                            #[cfg(feature = "peer-connect-priority")]
+                            &mut ramp,
                         ),
                         Err(_) => {
                             warn!("All senders have disconnected");
@@ -337,6 +408,8 @@ impl PeerManager {
             join_handle,
             sender,
             pacemaker_shutdown_signaler,
+            #[cfg(feature = "peer-ref-counts")]
+            ref_counts: manager_ref_counts,
         })
     }
 }
@@ -373,7 +446,9 @@ fn handle_request(
     peer_remover: &PeerRemover,
     ref_map: &mut RefMap<PeerTokenPair>,
     subscribers: &mut SubscriberMap,
-    strict_ref_counts: bool,
+    ref_counts: &PeerRefCounts,
+    #[cfg(feature = "peer-endpoint-selection")] endpoint_selector: &EndpointSelector,
+    #[cfg(feature = "peer-connect-priority")] ramp: &mut ConnectionRamp,
 ) {
     match request {
         PeerManagerRequest::AddPeer {
@@ -393,6 +468,10 @@ fn handle_request(
                     ref_map,
                     subscribers,
                     required_local_auth,
+                    #[cfg(feature = "peer-endpoint-selection")]
+                    endpoint_selector,
+                    #[cfg(feature = "peer-connect-priority")]
+                    ramp,
                 ))
                 .is_err()
             {
@@ -427,7 +506,7 @@ fn handle_request(
                     unreferenced_peers,
                     peers,
                     ref_map,
-                    strict_ref_counts,
+                    ref_counts,
                 ))
                 .is_err()
             {
@@ -446,7 +525,7 @@ fn handle_request(
                     connector,
                     peers,
                     ref_map,
-                    strict_ref_counts,
+                    ref_counts,
                 ))
                 .is_err()
             {
@@ -521,6 +600,14 @@ fn handle_request(
                 warn!("connector dropped before receiving result of remove connection");
             }
         }
+        #[cfg(feature = "peer-connect-priority")]
+        PeerManagerRequest::SetPriorityPeers { peer_ids, sender } => {
+            debug!("Prioritizing connections to {} peers", peer_ids.len());
+            ramp.set_priority_peers(peer_ids);
+            if sender.send(Ok(())).is_err() {
+                warn!("connector dropped before receiving result of setting priority peers");
+            }
+        }
     };
 }
 
@@ -537,7 +624,13 @@ fn add_peer(
     ref_map: &mut RefMap<PeerTokenPair>,
     subscribers: &mut SubscriberMap,
     required_local_auth: PeerAuthorizationToken,
+    #[cfg(feature = "peer-endpoint-selection")] endpoint_selector: &EndpointSelector,
+    #[cfg(feature = "peer-connect-priority")] ramp: &mut ConnectionRamp,
 ) -> Result<PeerRef, PeerRefAddError> {
+    // endpoints are stored in order of preference, so they are tried in that order
+    #[cfg(feature = "peer-endpoint-selection")]
+    let endpoints = endpoint_selector.order_endpoints(&peer_id, endpoints);
+
     let peer_token_pair = PeerTokenPair::new(peer_id.clone(), required_local_auth.clone());
 
     if check_for_duplicate_endpoint(&peer_id, &endpoints, peers) {
@@ -664,22 +757,32 @@ fn add_peer(
         }
     };
 
-    for endpoint in endpoints.iter() {
-        match connector.request_connection(
-            endpoint,
-            &connection_id,
-            Some(peer_id.clone().into()),
-            Some(required_local_auth.clone().into()),
-        ) {
-            Ok(()) => {
-                active_endpoint = endpoint.to_string();
-                break;
-            }
-            // If the request_connection errored we will retry in the future
-            Err(err) => {
-                log_connect_request_err(err, &peer_id, endpoint);
+    // peers over the ramp rate are left pending, and are connected to by retry_pending
+    #[cfg(feature = "peer-connect-priority")]
+    let request_connection = ramp.try_acquire(&peer_id);
+    #[cfg(not(feature = "peer-connect-priority"))]
+    let request_connection = true;
+
+    if request_connection {
+        for endpoint in endpoints.iter() {
+            match connector.request_connection(
+                endpoint,
+                &connection_id,
+                Some(peer_id.clone().into()),
+                Some(required_local_auth.clone().into()),
+            ) {
+                Ok(()) => {
+                    active_endpoint = endpoint.to_string();
+                    break;
+                }
+                // If the request_connection errored we will retry in the future
+                Err(err) => {
+                    log_connect_request_err(err, &peer_id, endpoint);
+                }
             }
         }
+    } else {
+        debug!("Deferring connection to peer {}", peer_id);
     }
 
     peers.insert(
@@ -753,7 +856,7 @@ fn remove_peer(
     unreferenced_peers: &mut UnreferencedPeerState,
     peers: &mut PeerMap,
     ref_map: &mut RefMap<PeerTokenPair>,
-    strict_ref_counts: bool,
+    ref_counts: &PeerRefCounts,
 ) -> Result<(), PeerRefRemoveError> {
     debug!("Removing peer: {}", peer_id);
 
@@ -764,7 +867,8 @@ fn remove_peer(
     let removed_peer = match ref_map.remove_ref(&peer_id) {
         Ok(removed_peer) => removed_peer,
         Err(err) => {
-            if strict_ref_counts {
+            ref_counts.record_dangling_ref_removal();
+            if ref_counts.strict_ref_counts() {
                 panic!(
                     "Trying to remove a reference that does not exist: {}",
                     peer_id
@@ -818,7 +922,7 @@ fn remove_peer_by_endpoint(
     connector: Connector,
     peers: &mut PeerMap,
     ref_map: &mut RefMap<PeerTokenPair>,
-    strict_ref_counts: bool,
+    ref_counts: &PeerRefCounts,
 ) -> Result<(), PeerRefRemoveError> {
     let peer_metadata = match peers.get_by_connection_id(&connection_id) {
         Some(peer_metadata) => peer_metadata,
@@ -843,7 +947,8 @@ fn remove_peer_by_endpoint(
     let removed_peer = match ref_map.remove_ref(&peer_token_pair) {
         Ok(removed_peer) => removed_peer,
         Err(err) => {
-            if strict_ref_counts {
+            ref_counts.record_dangling_ref_removal();
+            if ref_counts.strict_ref_counts() {
                 panic!(
                     "Trying to remove a reference that does not exist: {}",
                     peer_token_pair
@@ -903,6 +1008,8 @@ fn handle_notifications(
     max_retry_attempts: u64,
     ref_map: &mut RefMap<PeerTokenPair>,
     retry_frequency: u64,
+    capabilities_registry: &PeerCapabilitiesRegistry,
+    #[cfg(feature = "peer-endpoint-selection")] endpoint_selector: &mut EndpointSelector,
 ) {
     match notification {
         // If a connection has disconnected, forward notification to subscribers
@@ -910,15 +1017,18 @@ fn handle_notifications(
             endpoint,
             identity,
             connection_id,
-        } => handle_disconnection(
-            endpoint,
-            PeerAuthorizationToken::from(identity),
-            connection_id,
-            unreferenced_peers,
-            peers,
-            connector,
-            subscribers,
-        ),
+        } => {
+            capabilities_registry.remove_connection(&connection_id);
+            handle_disconnection(
+                endpoint,
+                PeerAuthorizationToken::from(identity),
+                connection_id,
+                unreferenced_peers,
+                peers,
+                connector,
+                subscribers,
+            )
+        }
         ConnectionManagerNotification::NonFatalConnectionError {
             endpoint,
             attempts,
@@ -1005,18 +1115,23 @@ fn handle_notifications(
             subscribers,
             ref_map,
             retry_frequency,
+            #[cfg(feature = "peer-endpoint-selection")]
+            endpoint_selector,
         ),
         ConnectionManagerNotification::FatalConnectionError {
             connection_id,
             error,
             ..
-        } => handle_fatal_connection(
-            connection_id,
-            error.to_string(),
-            peers,
-            subscribers,
-            max_retry_attempts,
-        ),
+        } => {
+            capabilities_registry.remove_connection(&connection_id);
+            handle_fatal_connection(
+                connection_id,
+                error.to_string(),
+                peers,
+                subscribers,
+                max_retry_attempts,
+            )
+        }
     }
 }
 
@@ -1166,6 +1281,10 @@ fn handle_inbound_connection(
         // reset retry settings
         peer_metadata.retry_frequency = retry_frequency;
         peer_metadata.last_connection_attempt = Instant::now();
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            peer_metadata.connection_attempts = 0;
+        }
 
         let notification = PeerManagerNotification::Connected {
             peer: peer_token_pair.clone(),
@@ -1254,6 +1373,7 @@ fn handle_connected(
     subscribers: &mut SubscriberMap,
     ref_map: &mut RefMap<PeerTokenPair>,
     retry_frequency: u64,
+    #[cfg(feature = "peer-endpoint-selection")] endpoint_selector: &mut EndpointSelector,
 ) {
     let peer_token_pair = PeerTokenPair::new(identity.clone(), local_authorization.clone());
     if let Some(mut peer_metadata) = peers.get_by_peer_id(&peer_token_pair).cloned() {
@@ -1303,12 +1423,22 @@ fn handle_connected(
         let starting_status = peer_metadata.status;
         let old_endpoint = peer_metadata.active_endpoint;
         let old_connection_id = peer_metadata.connection_id;
+        #[cfg(feature = "peer-endpoint-selection")]
+        {
+            endpoint_selector.record_success(&peer_metadata.id, &endpoint);
+            peer_metadata.endpoints =
+                endpoint_selector.order_endpoints(&peer_metadata.id, peer_metadata.endpoints);
+        }
         peer_metadata.active_endpoint = endpoint;
         peer_metadata.status = PeerStatus::Connected;
         peer_metadata.connection_id = connection_id.clone();
         // reset retry settings
         peer_metadata.retry_frequency = retry_frequency;
         peer_metadata.last_connection_attempt = Instant::now();
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            peer_metadata.connection_attempts = 0;
+        }
 
         if let Err(err) = peers.update_peer(peer_metadata) {
             error!("Unable to update peer: {}", err);
@@ -1481,6 +1611,16 @@ fn handle_fatal_connection(
     }
 }
 
+/// Returns a random time, in seconds, between 0 and `retry_jitter`.
+#[cfg(feature = "peer-reconnect-policy")]
+fn next_retry_jitter(retry_jitter: u64) -> u64 {
+    if retry_jitter == 0 {
+        0
+    } else {
+        rand::thread_rng().gen_range(0..=retry_jitter)
+    }
+}
+
 // If a pending peer's retry_frequency has elapsed, retry their endpoints. If successful,
 // their active endpoint will be updated. The retry_frequency will be increased and
 // and last_connection_attempt reset.
@@ -1489,14 +1629,43 @@ fn retry_pending(
     connector: Connector,
     unreferenced_peers: &mut UnreferencedPeerState,
     max_retry_frequency: u64,
+    #[cfg(feature = "peer-reconnect-policy")] retry_jitter: u64,
+    #[cfg(feature = "peer-reconnect-policy")] max_reconnect_attempts: Option<u64>,
+    #[cfg(feature = "peer-connect-priority")] ramp: &mut ConnectionRamp,
 ) {
+    #[cfg(feature = "peer-connect-priority")]
+    ramp.reset();
+
     let mut to_retry = Vec::new();
     for (_, peer) in peers.get_pending() {
-        if peer.last_connection_attempt.elapsed().as_secs() > peer.retry_frequency {
+        // peers that have used up their reconnect attempts are only retried once they connect
+        #[cfg(feature = "peer-reconnect-policy")]
+        if max_reconnect_attempts
+            .map(|max_attempts| peer.connection_attempts >= max_attempts)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+
+        #[cfg(feature = "peer-reconnect-policy")]
+        let retry_frequency = peer.retry_frequency + peer.retry_jitter;
+        #[cfg(not(feature = "peer-reconnect-policy"))]
+        let retry_frequency = peer.retry_frequency;
+        if peer.last_connection_attempt.elapsed().as_secs() > retry_frequency {
             to_retry.push(peer.clone());
         }
     }
 
+    // priority peers are retried first; peers over the ramp rate wait for the next interval
+    #[cfg(feature = "peer-connect-priority")]
+    let to_retry = {
+        to_retry.sort_by_key(|peer| !ramp.is_priority(&peer.id));
+        to_retry
+            .into_iter()
+            .filter(|peer| ramp.try_acquire(&peer.id))
+            .collect::<Vec<_>>()
+    };
+
     for mut peer_metadata in to_retry {
         debug!("Attempting to peer with pending peer {}", peer_metadata.id);
         for endpoint in peer_metadata.endpoints.iter() {
@@ -1516,6 +1685,17 @@ fn retry_pending(
 
         peer_metadata.retry_frequency = min(peer_metadata.retry_frequency * 2, max_retry_frequency);
         peer_metadata.last_connection_attempt = Instant::now();
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            peer_metadata.connection_attempts += 1;
+            peer_metadata.retry_jitter = next_retry_jitter(retry_jitter);
+            if Some(peer_metadata.connection_attempts) == max_reconnect_attempts {
+                warn!(
+                    "Stopped retrying peer {} after {} attempts",
+                    peer_metadata.id, peer_metadata.connection_attempts
+                );
+            }
+        }
         if let Err(err) = peers.update_peer(peer_metadata) {
             error!("Unable to update peer: {}", err);
         }
@@ -1663,6 +1843,17 @@ pub mod tests {
     use crate::transport::raw::RawTransport;
     use crate::transport::{Connection, Transport};
 
+    // Test that the retry jitter is never longer than the configured jitter, and is 0 if no
+    // jitter is configured
+    #[cfg(feature = "peer-reconnect-policy")]
+    #[test]
+    fn test_next_retry_jitter() {
+        assert_eq!(next_retry_jitter(0), 0);
+        for _ in 0..100 {
+            assert!(next_retry_jitter(5) <= 5);
+        }
+    }
+
     // Test that a call to add_peer_ref returns the correct PeerRef
     //
     // 1. add test_peer
@@ -2693,6 +2884,174 @@ pub mod tests {
         mesh1.wait_for_shutdown().expect("Unable to shutdown mesh");
     }
 
+    // Test that the PeerManager removes the capabilities a peer advertised on a connection once
+    // that connection is disconnected, so a reconnected peer is only known by the capabilities it
+    // advertised on its new connection.
+    //
+    // 1. add test_peer and wait for the Connected notification
+    // 2. record capabilities for the peer's connection, as authorization would
+    // 3. disconnect the connection made to test_peer
+    // 4. verify that subscribers receive a Disconnected notification and the peer's capabilities
+    //    have been removed
+    // 5. verify that subscribers receive a Connected notification when the peer reconnects, and
+    //    that different capabilities recorded for the new connection are returned
+    #[test]
+    fn test_peer_manager_capabilities_removed_on_disconnect() {
+        let mut transport = Box::new(RawTransport::default());
+        let mut listener = transport
+            .listen("tcp://localhost:0")
+            .expect("Cannot listen for connections");
+        let endpoint = listener.endpoint();
+        let mut mesh1 = Mesh::new(512, 128);
+        let mut mesh2 = Mesh::new(512, 128);
+
+        let (tx, rx) = mpsc::channel();
+        let (disconnect_tx, disconnect_rx) = mpsc::channel();
+        let jh = thread::spawn(move || {
+            // accept incoming connection and add it to mesh2
+            let conn = listener.accept().expect("Cannot accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+
+            // wait for the capabilities to be recorded, then remove the connection to cause a
+            // reconnection attempt
+            disconnect_rx.recv().unwrap();
+            let mut connection = mesh2
+                .remove("test_id")
+                .expect("Cannot remove connection from mesh");
+            connection
+                .disconnect()
+                .expect("Connection failed to disconnect");
+
+            // accept the reconnection
+            let conn = listener.accept().expect("Unable to accept connection");
+            mesh2
+                .add(conn, "test_id".to_string())
+                .expect("Cannot add connection to mesh");
+
+            rx.recv().unwrap();
+
+            mesh2.signal_shutdown();
+            mesh2.wait_for_shutdown().expect("Unable to shutdown mesh");
+        });
+
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new_multiple(&[
+                "test_peer",
+                "test_peer",
+            ])))
+            .with_matrix_life_cycle(mesh1.get_life_cycle())
+            .with_matrix_sender(mesh1.get_sender())
+            .with_transport(transport)
+            .with_heartbeat_interval(1)
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let capabilities_registry = PeerCapabilitiesRegistry::new();
+        let connector = cm.connector();
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .with_capabilities_registry(capabilities_registry.clone())
+            .start()
+            .expect("Cannot start peer_manager");
+        let peer_connector = peer_manager.connector();
+        let (notification_tx, notification_rx): (
+            Sender<PeerManagerNotification>,
+            mpsc::Receiver<PeerManagerNotification>,
+        ) = channel();
+        peer_connector
+            .subscribe_sender(notification_tx)
+            .expect("Unable to get subscriber");
+        let peer_id = PeerAuthorizationToken::from_peer_id("test_peer");
+        let peer_token_pair = PeerTokenPair::new(
+            peer_id.clone(),
+            PeerAuthorizationToken::from_peer_id("my_id"),
+        );
+        let _peer_ref = peer_connector
+            .add_peer_ref(
+                peer_id.clone(),
+                vec![endpoint],
+                PeerAuthorizationToken::from_peer_id("my_id"),
+            )
+            .expect("Unable to add peer");
+
+        // timeout after 60 seconds
+        let timeout = Duration::from_secs(60);
+        let notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Unable to get new notifications");
+        assert!(
+            notification
+                == PeerManagerNotification::Connected {
+                    peer: peer_token_pair.clone(),
+                }
+        );
+
+        let connection_id = peer_connector
+            .connection_ids()
+            .expect("Unable to get connection ids")
+            .get_by_key(&peer_token_pair)
+            .cloned()
+            .expect("Missing connection id for peer");
+        let old_capabilities = NodeCapabilitiesBuilder::new()
+            .with_service_type("scabbard")
+            .build();
+        capabilities_registry.insert(peer_id.clone(), &connection_id, old_capabilities.clone());
+        assert_eq!(capabilities_registry.get(&peer_id), Some(old_capabilities));
+
+        disconnect_tx.send(()).unwrap();
+
+        let disconnected_notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Cannot get message from subscriber");
+        assert!(
+            disconnected_notification
+                == PeerManagerNotification::Disconnected {
+                    peer: peer_token_pair.clone(),
+                }
+        );
+        assert_eq!(capabilities_registry.get(&peer_id), None);
+
+        let connected_notification = notification_rx
+            .recv_timeout(timeout)
+            .expect("Cannot get message from subscriber");
+        assert!(
+            connected_notification
+                == PeerManagerNotification::Connected {
+                    peer: peer_token_pair.clone(),
+                }
+        );
+
+        let connection_id = peer_connector
+            .connection_ids()
+            .expect("Unable to get connection ids")
+            .get_by_key(&peer_token_pair)
+            .cloned()
+            .expect("Missing connection id for peer");
+        let new_capabilities = NodeCapabilitiesBuilder::new()
+            .with_service_type("echo")
+            .build();
+        capabilities_registry.insert(peer_id.clone(), &connection_id, new_capabilities.clone());
+        assert_eq!(capabilities_registry.get(&peer_id), Some(new_capabilities));
+
+        tx.send(()).unwrap();
+
+        jh.join().unwrap();
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh1.signal_shutdown();
+        mesh1.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
     // Test that the PeerManager can be started and stopped
     #[test]
     fn test_peer_manager_shutdown() {
@@ -2727,6 +3086,63 @@ pub mod tests {
         mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
     }
 
+    // Test that removing a reference to a peer that is not referenced is counted as a dangling
+    // reference, and that strict reference counting can be turned off while the PeerManager is
+    // running.
+    //
+    // 1. Start a PeerManager with strict reference counting, then turn it off
+    // 2. Remove a reference to a peer that was never added
+    // 3. Verify that an error is returned instead of panicking, and that the dangling reference
+    //    was counted
+    #[cfg(feature = "peer-ref-counts")]
+    #[test]
+    fn test_peer_manager_dangling_ref() {
+        let transport = Box::new(InprocTransport::default());
+
+        let mut mesh = Mesh::new(512, 128);
+        let mut cm = ConnectionManager::builder()
+            .with_authorizer(Box::new(NoopAuthorizer::new("test_peer")))
+            .with_matrix_life_cycle(mesh.get_life_cycle())
+            .with_matrix_sender(mesh.get_sender())
+            .with_transport(transport.clone())
+            .start()
+            .expect("Unable to start Connection Manager");
+
+        let connector = cm.connector();
+        let mut peer_manager = PeerManager::builder()
+            .with_connector(connector)
+            .with_retry_interval(1)
+            .with_identity("my_id".to_string())
+            .with_strict_ref_counts(true)
+            .start()
+            .expect("Cannot start peer_manager");
+
+        let ref_counts = peer_manager.ref_counts();
+        assert!(ref_counts.strict_ref_counts());
+        ref_counts.set_strict_ref_counts(false);
+
+        let peer_remover = PeerRemover {
+            sender: peer_manager.sender.clone(),
+        };
+        let result = peer_remover.remove_peer_ref(&PeerTokenPair::new(
+            PeerAuthorizationToken::from_peer_id("unknown_peer"),
+            PeerAuthorizationToken::from_peer_id("my_id"),
+        ));
+
+        assert!(matches!(result, Err(PeerRefRemoveError::Remove(_))));
+        assert_eq!(ref_counts.dangling_ref_removals(), 1);
+
+        peer_manager.signal_shutdown();
+        cm.signal_shutdown();
+        peer_manager
+            .wait_for_shutdown()
+            .expect("Unable to shutdown peer manager");
+        cm.wait_for_shutdown()
+            .expect("Unable to shutdown connection manager");
+        mesh.signal_shutdown();
+        mesh.wait_for_shutdown().expect("Unable to shutdown mesh");
+    }
+
     // Test that the PeerManager can receive incoming peer requests and handle them appropriately.
     //
     // 1. Add a connection
@@ -53,6 +53,12 @@ pub struct PeerMetadata {
     pub retry_frequency: u64,
     /// The required way the local node must be identified, this is required on retry
     pub required_local_auth: PeerAuthorizationToken,
+    /// The number of times the peer has been retried since it was last connected
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub connection_attempts: u64,
+    /// The random time, in seconds, added to the retry frequency before the next retry
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub retry_jitter: u64,
 }
 
 /// A map of peer IDs to peer metadata, which also maintains a redirect table for updated peer IDs.
@@ -134,6 +140,10 @@ impl PeerMap {
             last_connection_attempt: Instant::now(),
             retry_frequency: self.initial_retry_frequency,
             required_local_auth: required_local_auth.clone(),
+            #[cfg(feature = "peer-reconnect-policy")]
+            connection_attempts: 0,
+            #[cfg(feature = "peer-reconnect-policy")]
+            retry_jitter: 0,
         };
 
         let peer_token_pair = PeerTokenPair::new(peer_id, required_local_auth);
@@ -583,6 +593,10 @@ pub mod tests {
             last_connection_attempt: Instant::now(),
             retry_frequency: 10,
             required_local_auth: PeerAuthorizationToken::from_peer_id("my_id"),
+            #[cfg(feature = "peer-reconnect-policy")]
+            connection_attempts: 0,
+            #[cfg(feature = "peer-reconnect-policy")]
+            retry_jitter: 0,
         };
 
         if let Ok(()) = peer_map.update_peer(no_peer_metadata) {
@@ -0,0 +1,98 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime control of the `PeerManager`'s reference counting.
+//!
+//! A reference is dangling when the `PeerManager` is asked to remove a reference to a peer that
+//! it has no references to, which indicates that a `PeerRef` was dropped more than once or for
+//! the wrong peer. When reference counting is strict, the `PeerManager` panics on a dangling
+//! reference; otherwise an error is returned to the caller. In either case, the dangling
+//! reference is counted.
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+#[derive(Default)]
+struct RefCountState {
+    strict: AtomicBool,
+    dangling_ref_removals: AtomicU64,
+}
+
+/// The reference counting settings of a `PeerManager`, and the dangling references it has
+/// encountered.
+///
+/// This is cheap to clone; all clones share the same settings and counts.
+#[derive(Clone, Default)]
+pub struct PeerRefCounts {
+    state: Arc<RefCountState>,
+}
+
+impl PeerRefCounts {
+    pub fn new(strict_ref_counts: bool) -> Self {
+        Self {
+            state: Arc::new(RefCountState {
+                strict: AtomicBool::new(strict_ref_counts),
+                dangling_ref_removals: AtomicU64::new(0),
+            }),
+        }
+    }
+
+    /// Returns whether the `PeerManager` panics when removing a dangling reference
+    pub fn strict_ref_counts(&self) -> bool {
+        self.state.strict.load(Ordering::SeqCst)
+    }
+
+    /// Sets whether the `PeerManager` panics when removing a dangling reference.
+    ///
+    /// The change applies to the next reference that is removed.
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn set_strict_ref_counts(&self, strict_ref_counts: bool) {
+        self.state.strict.store(strict_ref_counts, Ordering::SeqCst);
+    }
+
+    /// Returns the number of times the `PeerManager` was asked to remove a dangling reference
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn dangling_ref_removals(&self) -> u64 {
+        self.state.dangling_ref_removals.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn record_dangling_ref_removal(&self) {
+        self.state
+            .dangling_ref_removals
+            .fetch_add(1, Ordering::SeqCst);
+        counter!("splinter.peer_manager.dangling_ref_removals", 1);
+    }
+}
+
+#[cfg(all(test, feature = "peer-ref-counts"))]
+mod tests {
+    use super::*;
+
+    /// Verify that strictness can be changed and dangling references recorded, and that clones
+    /// share both.
+    #[test]
+    fn test_peer_ref_counts() {
+        let ref_counts = PeerRefCounts::new(true);
+        let clone = ref_counts.clone();
+
+        assert!(clone.strict_ref_counts());
+        ref_counts.set_strict_ref_counts(false);
+        assert!(!clone.strict_ref_counts());
+
+        assert_eq!(clone.dangling_ref_removals(), 0);
+        ref_counts.record_dangling_ref_removal();
+        ref_counts.record_dangling_ref_removal();
+        assert_eq!(clone.dangling_ref_removals(), 2);
+    }
+}
@@ -17,6 +17,9 @@
 //! These structs are used to operate on the messages that are sent and received during the
 //! authorization process for connections.
 
+use protobuf::RepeatedField;
+
+use crate::peer::NodeCapabilities;
 use crate::protos::authorization;
 use crate::protos::prelude::*;
 use crate::public_key::PublicKey;
@@ -220,18 +223,23 @@ impl FromNative<AuthorizationError> for authorization::AuthorizationError {
 
 /// A successful authorization message.
 ///
-/// This message is returned after Trust or Challenge authorization is completed
-#[derive(Debug)]
-pub struct AuthComplete;
+/// This message is returned after Trust or Challenge authorization is completed. It carries the
+/// capabilities of the sending node, if the node advertises them.
+#[derive(Debug, Default)]
+pub struct AuthComplete {
+    pub capabilities: Option<NodeCapabilities>,
+}
 
 /// A protocol request message.
 ///
 /// This message provides supported protocol versions and requests that an agreed upon version is
-/// returned.
+/// returned. The timestamp is the requester's time, in milliseconds since the UNIX epoch, or 0 if
+/// the requester did not include one.
 #[derive(Debug)]
 pub struct AuthProtocolRequest {
     pub auth_protocol_min: u32,
     pub auth_protocol_max: u32,
+    pub timestamp: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -305,14 +313,64 @@ pub struct AuthChallengeSubmitResponse {
 }
 
 impl FromProto<authorization::AuthComplete> for AuthComplete {
-    fn from_proto(_: authorization::AuthComplete) -> Result<Self, ProtoConversionError> {
-        Ok(AuthComplete)
+    fn from_proto(mut source: authorization::AuthComplete) -> Result<Self, ProtoConversionError> {
+        let capabilities = if source.has_capabilities() {
+            Some(NodeCapabilities::from_proto(source.take_capabilities())?)
+        } else {
+            None
+        };
+
+        Ok(AuthComplete { capabilities })
     }
 }
 
 impl FromNative<AuthComplete> for authorization::AuthComplete {
-    fn from_native(_: AuthComplete) -> Result<Self, ProtoConversionError> {
-        Ok(authorization::AuthComplete::new())
+    fn from_native(source: AuthComplete) -> Result<Self, ProtoConversionError> {
+        let mut auth_complete = authorization::AuthComplete::new();
+        if let Some(capabilities) = source.capabilities {
+            auth_complete.set_capabilities(capabilities.into_proto()?);
+        }
+
+        Ok(auth_complete)
+    }
+}
+
+impl FromProto<authorization::NodeCapabilities> for NodeCapabilities {
+    fn from_proto(
+        mut source: authorization::NodeCapabilities,
+    ) -> Result<Self, ProtoConversionError> {
+        Ok(NodeCapabilities::new(
+            source.take_service_types().into_vec(),
+            source
+                .take_protocols()
+                .into_iter()
+                .map(|mut protocol| (protocol.take_protocol(), protocol.get_version()))
+                .collect(),
+            source.take_consensus_engines().into_vec(),
+        ))
+    }
+}
+
+impl FromNative<NodeCapabilities> for authorization::NodeCapabilities {
+    fn from_native(source: NodeCapabilities) -> Result<Self, ProtoConversionError> {
+        let mut capabilities = authorization::NodeCapabilities::new();
+        capabilities.set_service_types(RepeatedField::from_vec(source.service_types().to_vec()));
+        capabilities.set_protocols(
+            source
+                .protocols()
+                .iter()
+                .map(|(protocol, version)| {
+                    let mut supported_protocol = authorization::SupportedProtocol::new();
+                    supported_protocol.set_protocol(protocol.to_string());
+                    supported_protocol.set_version(*version);
+                    supported_protocol
+                })
+                .collect(),
+        );
+        capabilities
+            .set_consensus_engines(RepeatedField::from_vec(source.consensus_engines().to_vec()));
+
+        Ok(capabilities)
     }
 }
 
@@ -323,6 +381,7 @@ impl FromProto<authorization::AuthProtocolRequest> for AuthProtocolRequest {
         Ok(AuthProtocolRequest {
             auth_protocol_min: source.get_auth_protocol_min(),
             auth_protocol_max: source.get_auth_protocol_max(),
+            timestamp: source.get_timestamp(),
         })
     }
 }
@@ -332,6 +391,7 @@ impl FromNative<AuthProtocolRequest> for authorization::AuthProtocolRequest {
         let mut proto_request = authorization::AuthProtocolRequest::new();
         proto_request.set_auth_protocol_min(req.auth_protocol_min);
         proto_request.set_auth_protocol_max(req.auth_protocol_max);
+        proto_request.set_timestamp(req.timestamp);
         Ok(proto_request)
     }
 }
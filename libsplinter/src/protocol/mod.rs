@@ -19,9 +19,20 @@ pub mod component;
 pub mod network;
 pub mod service;
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
 // Peer authorization protocol versions
 #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
 pub const PEER_AUTHORIZATION_PROTOCOL_VERSION: u32 = 1;
 
 #[cfg(any(feature = "trust-authorization", feature = "challenge-authorization"))]
 pub(crate) const PEER_AUTHORIZATION_PROTOCOL_MIN: u32 = 1;
+
+/// Returns the current time as it is carried in the timestamps of protocol messages: in
+/// milliseconds since the UNIX epoch, or 0 if the system clock is set before the epoch.
+pub(crate) fn current_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or(0)
+}
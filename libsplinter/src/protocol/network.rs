@@ -17,6 +17,7 @@ use crate::protos::network;
 use crate::protos::prelude::*;
 
 use super::authorization::AuthorizationMessage;
+use super::current_timestamp;
 
 /// The network message envelope
 #[derive(Debug)]
@@ -36,8 +37,22 @@ pub struct NetworkEcho {
 }
 
 /// This message is used to keep connections alive
+///
+/// The timestamp is the sender's time, in milliseconds since the UNIX epoch, or 0 if the sender
+/// did not include one.
 #[derive(Debug)]
-pub struct NetworkHeartbeat;
+pub struct NetworkHeartbeat {
+    pub timestamp: u64,
+}
+
+impl NetworkHeartbeat {
+    /// Creates a heartbeat with the current time.
+    pub fn now() -> Self {
+        Self {
+            timestamp: current_timestamp(),
+        }
+    }
+}
 
 impl FromProto<network::NetworkEcho> for NetworkEcho {
     fn from_proto(mut source: network::NetworkEcho) -> Result<Self, ProtoConversionError> {
@@ -61,14 +76,19 @@ impl FromNative<NetworkEcho> for network::NetworkEcho {
 }
 
 impl FromProto<network::NetworkHeartbeat> for NetworkHeartbeat {
-    fn from_proto(_: network::NetworkHeartbeat) -> Result<Self, ProtoConversionError> {
-        Ok(NetworkHeartbeat)
+    fn from_proto(source: network::NetworkHeartbeat) -> Result<Self, ProtoConversionError> {
+        Ok(NetworkHeartbeat {
+            timestamp: source.get_timestamp(),
+        })
     }
 }
 
 impl FromNative<NetworkHeartbeat> for network::NetworkHeartbeat {
-    fn from_native(_: NetworkHeartbeat) -> Result<Self, ProtoConversionError> {
-        Ok(network::NetworkHeartbeat::new())
+    fn from_native(source: NetworkHeartbeat) -> Result<Self, ProtoConversionError> {
+        let mut proto_heartbeat = network::NetworkHeartbeat::new();
+        proto_heartbeat.set_timestamp(source.timestamp);
+
+        Ok(proto_heartbeat)
     }
 }
 
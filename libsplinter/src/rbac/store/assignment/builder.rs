@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::SystemTime;
+
 use crate::error::InvalidStateError;
 use crate::rbac::store::Assignment;
 use crate::rbac::store::Identity;
@@ -21,6 +23,7 @@ use crate::rbac::store::Identity;
 pub struct AssignmentBuilder {
     identity: Option<Identity>,
     roles: Vec<String>,
+    expires_at: Option<SystemTime>,
 }
 
 impl AssignmentBuilder {
@@ -41,6 +44,12 @@ impl AssignmentBuilder {
         self
     }
 
+    /// Sets the time at which the assignment expires; by default, it does not expire.
+    pub fn with_expires_at(mut self, expires_at: SystemTime) -> Self {
+        self.expires_at = Some(expires_at);
+        self
+    }
+
     /// Builds a new assignment.
     ///
     /// # Errors
@@ -60,6 +69,7 @@ impl AssignmentBuilder {
                 InvalidStateError::with_message("An assignment requires an identity field".into())
             })?,
             roles: self.roles,
+            expires_at: self.expires_at,
         })
     }
 }
@@ -15,16 +15,22 @@
 mod builder;
 mod update_builder;
 
+use std::time::SystemTime;
+
 use crate::rbac::store::Identity;
 
 pub use builder::AssignmentBuilder;
 pub use update_builder::AssignmentUpdateBuilder;
 
 /// An assignment of roles to a particular identity.
+///
+/// An assignment may have an expiration time, after which its roles no longer grant any
+/// permissions to the identity.
 #[derive(Clone)]
 pub struct Assignment {
     identity: Identity,
     roles: Vec<String>,
+    expires_at: Option<SystemTime>,
 }
 
 impl Assignment {
@@ -38,19 +44,45 @@ impl Assignment {
         &self.roles
     }
 
+    /// Returns the time at which the assignment expires, if any.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    /// Returns whether the assignment has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .map(|expires_at| expires_at <= SystemTime::now())
+            .unwrap_or(false)
+    }
+
     /// Convert this assignment back into a builder, in order to update its values.
     pub fn into_update_builder(self) -> AssignmentUpdateBuilder {
-        let Assignment { identity, roles } = self;
-        AssignmentUpdateBuilder::new(identity).with_roles(roles)
+        let Assignment {
+            identity,
+            roles,
+            expires_at,
+        } = self;
+        AssignmentUpdateBuilder::new(identity)
+            .with_roles(roles)
+            .with_expires_at(expires_at)
     }
 
     /// Converts this assignment into it's constituent parts.  These parts are in the tuple:
-    /// `(identity, roles)`.
-    pub fn into_parts(self) -> (Identity, Vec<String>) {
-        (self.identity, self.roles)
+    /// `(identity, roles, expires_at)`.
+    pub fn into_parts(self) -> (Identity, Vec<String>, Option<SystemTime>) {
+        (self.identity, self.roles, self.expires_at)
     }
 
-    pub(super) fn new_unchecked(identity: Identity, roles: Vec<String>) -> Self {
-        Self { identity, roles }
+    pub(super) fn new_unchecked(
+        identity: Identity,
+        roles: Vec<String>,
+        expires_at: Option<SystemTime>,
+    ) -> Self {
+        Self {
+            identity,
+            roles,
+            expires_at,
+        }
     }
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::time::SystemTime;
+
 use crate::error::InvalidStateError;
 use crate::rbac::store::Assignment;
 use crate::rbac::store::Identity;
@@ -22,6 +24,7 @@ use crate::rbac::store::Identity;
 pub struct AssignmentUpdateBuilder {
     identity: Identity,
     roles: Vec<String>,
+    expires_at: Option<SystemTime>,
 }
 
 impl AssignmentUpdateBuilder {
@@ -29,6 +32,7 @@ impl AssignmentUpdateBuilder {
         Self {
             identity,
             roles: Vec::new(),
+            expires_at: None,
         }
     }
     /// Updates the assigned roles.
@@ -37,6 +41,12 @@ impl AssignmentUpdateBuilder {
         self
     }
 
+    /// Updates the time at which the assignment expires; `None` removes the expiration.
+    pub fn with_expires_at(mut self, expires_at: Option<SystemTime>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
     /// Builds the updated assignment.
     ///
     /// # Errors
@@ -53,6 +63,7 @@ impl AssignmentUpdateBuilder {
         Ok(Assignment {
             identity: self.identity,
             roles: self.roles,
+            expires_at: self.expires_at,
         })
     }
 }
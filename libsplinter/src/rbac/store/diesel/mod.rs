@@ -18,6 +18,7 @@ mod schema;
 
 use std::convert::TryFrom;
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crate::error::{
     ConstraintViolationError, ConstraintViolationType, InternalError, InvalidStateError,
@@ -35,7 +36,9 @@ use operations::add_assignment::RoleBasedAuthorizationStoreAddAssignment as _;
 use operations::add_role::RoleBasedAuthorizationStoreAddRole as _;
 use operations::get_assigned_roles::RoleBasedAuthorizationStoreGetAssignedRoles as _;
 use operations::get_assignment::RoleBasedAuthorizationStoreGetAssignment as _;
+use operations::get_inherited_roles::RoleBasedAuthorizationStoreGetInheritedRoles as _;
 use operations::get_role::RoleBasedAuthorizationStoreGetRole as _;
+use operations::import_roles_and_assignments::RoleBasedAuthorizationStoreImportRolesAndAssignments as _;
 use operations::list_assignments::RoleBasedAuthorizationStoreListAssignments as _;
 use operations::list_roles::RoleBasedAuthorizationStoreListRoles as _;
 use operations::remove_assignment::RoleBasedAuthorizationStoreRemoveAssignment as _;
@@ -84,6 +87,16 @@ impl RoleBasedAuthorizationStore
         })
     }
 
+    /// Returns the roles the given role inherits from, either directly or through its parents.
+    fn get_inherited_roles(
+        &self,
+        role_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).get_inherited_roles(role_id)
+        })
+    }
+
     /// Lists all roles.
     fn list_roles(
         &self,
@@ -213,6 +226,23 @@ impl RoleBasedAuthorizationStore
         })
     }
 
+    /// Adds or replaces the given roles and assignments as a single operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if the `admin` role is included, or if an assignment
+    /// refers to a role that does not exist.
+    fn import_roles_and_assignments(
+        &self,
+        roles: Vec<Role>,
+        assignments: Vec<Assignment>,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection)
+                .import_roles_and_assignments(roles, assignments)
+        })
+    }
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
         Box::new(DieselRoleBasedAuthorizationStore {
@@ -230,6 +260,16 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         })
     }
 
+    /// Returns the roles the given role inherits from, either directly or through its parents.
+    fn get_inherited_roles(
+        &self,
+        role_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_read(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection).get_inherited_roles(role_id)
+        })
+    }
+
     /// Lists all roles.
     fn list_roles(
         &self,
@@ -359,6 +399,23 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
         })
     }
 
+    /// Adds or replaces the given roles and assignments as a single operation.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if the `admin` role is included, or if an assignment
+    /// refers to a role that does not exist.
+    fn import_roles_and_assignments(
+        &self,
+        roles: Vec<Role>,
+        assignments: Vec<Assignment>,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            RoleBasedAuthorizationStoreOperations::new(connection)
+                .import_roles_and_assignments(roles, assignments)
+        })
+    }
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
         Box::new(DieselRoleBasedAuthorizationStore {
@@ -367,8 +424,15 @@ impl RoleBasedAuthorizationStore for DieselRoleBasedAuthorizationStore<diesel::p
     }
 }
 
-impl From<Role> for (models::RoleModel, Vec<models::RolePermissionModel>) {
+impl From<Role>
+    for (
+        models::RoleModel,
+        Vec<models::RolePermissionModel>,
+        Vec<models::RoleParentModel>,
+    )
+{
     fn from(role: Role) -> Self {
+        let parents = role.parents().to_vec();
         let (id, display_name, permissions) = role.into_parts();
 
         let perm_models = permissions
@@ -378,15 +442,36 @@ impl From<Role> for (models::RoleModel, Vec<models::RolePermissionModel>) {
                 permission,
             })
             .collect::<Vec<_>>();
-        (models::RoleModel { id, display_name }, perm_models)
+        let parent_models = parents
+            .into_iter()
+            .map(|parent_id| models::RoleParentModel {
+                role_id: id.clone(),
+                parent_id,
+            })
+            .collect::<Vec<_>>();
+        (
+            models::RoleModel { id, display_name },
+            perm_models,
+            parent_models,
+        )
     }
 }
 
-impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
+impl
+    TryFrom<(
+        models::RoleModel,
+        Vec<models::RolePermissionModel>,
+        Vec<models::RoleParentModel>,
+    )> for Role
+{
     type Error = InvalidStateError;
 
     fn try_from(
-        (role_model, perm_models): (models::RoleModel, Vec<models::RolePermissionModel>),
+        (role_model, perm_models, parent_models): (
+            models::RoleModel,
+            Vec<models::RolePermissionModel>,
+            Vec<models::RoleParentModel>,
+        ),
     ) -> Result<Self, Self::Error> {
         RoleBuilder::new()
             .with_id(role_model.id)
@@ -397,22 +482,33 @@ impl TryFrom<(models::RoleModel, Vec<models::RolePermissionModel>)> for Role {
                     .map(|perm| perm.permission)
                     .collect(),
             )
+            .with_parents(
+                parent_models
+                    .into_iter()
+                    .map(|parent| parent.parent_id)
+                    .collect(),
+            )
             .build()
     }
 }
 
-impl From<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>) {
-    fn from(assignment: Assignment) -> Self {
-        let (identity, roles) = assignment.into_parts();
+impl TryFrom<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>) {
+    type Error = InvalidStateError;
+
+    fn try_from(assignment: Assignment) -> Result<Self, Self::Error> {
+        let (identity, roles, expires_at) = assignment.into_parts();
+        let expires_at = expires_at.map(to_timestamp).transpose()?;
 
         let identity_model = match identity {
             Identity::Key(identity) => models::IdentityModel {
                 identity,
                 identity_type: models::IdentityModelType::Key,
+                expires_at,
             },
             Identity::User(identity) => models::IdentityModel {
                 identity,
                 identity_type: models::IdentityModelType::User,
+                expires_at,
             },
         };
 
@@ -424,7 +520,7 @@ impl From<Assignment> for (models::IdentityModel, Vec<models::AssignmentModel>)
             })
             .collect::<Vec<_>>();
 
-        (identity_model, role_models)
+        Ok((identity_model, role_models))
     }
 }
 
@@ -437,6 +533,7 @@ impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignme
         let models::IdentityModel {
             identity,
             identity_type,
+            expires_at,
         } = identity_model;
         let identity = match identity_type {
             models::IdentityModelType::Key => Identity::Key(identity),
@@ -453,10 +550,34 @@ impl TryFrom<(models::IdentityModel, Vec<models::AssignmentModel>)> for Assignme
                 .into_iter()
                 .map(|models::AssignmentModel { role_id, .. }| role_id)
                 .collect(),
+            expires_at.map(from_timestamp).transpose()?,
         ))
     }
 }
 
+/// Converts the given time to the number of seconds since the Unix epoch, as stored in the
+/// `expires_at` column.
+fn to_timestamp(time: SystemTime) -> Result<i64, InvalidStateError> {
+    time.duration_since(UNIX_EPOCH)
+        .ok()
+        .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+        .ok_or_else(|| {
+            InvalidStateError::with_message("time cannot be stored as a timestamp".into())
+        })
+}
+
+fn from_timestamp(timestamp: i64) -> Result<SystemTime, InvalidStateError> {
+    u64::try_from(timestamp)
+        .ok()
+        .and_then(|secs| UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            InvalidStateError::with_message(format!(
+                "timestamp {} could not be represented as a SystemTime",
+                timestamp
+            ))
+        })
+}
+
 impl From<diesel::result::Error> for RoleBasedAuthorizationStoreError {
     fn from(err: diesel::result::Error) -> Self {
         match err {
@@ -928,6 +1049,65 @@ mod tests {
         assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
     }
 
+    /// This test verifies the following:
+    /// 1. Adds a role and an assignment that expires
+    /// 2. Verifies the expiration time is returned via the get_assignment API
+    /// 3. Updates the assignment to remove the expiration and verifies it via the store API
+    #[test]
+    fn sqlite_assignment_expiration() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool.clone());
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let expires_at = UNIX_EPOCH + Duration::from_secs(1000);
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::User("contractor".into()))
+            .with_roles(vec!["test-role".to_string()])
+            .with_expires_at(expires_at)
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let stored_assignment = role_based_auth_store
+            .get_assignment(&Identity::User("contractor".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment not found");
+        assert_eq!(Some(expires_at), stored_assignment.expires_at());
+        assert!(stored_assignment.is_expired());
+
+        let updated_assignment = stored_assignment
+            .into_update_builder()
+            .with_expires_at(None)
+            .build()
+            .expect("Unable to build updated assignment");
+
+        role_based_auth_store
+            .update_assignment(updated_assignment)
+            .expect("Unable to update assignment");
+
+        let stored_assignment = role_based_auth_store
+            .get_assignment(&Identity::User("contractor".into()))
+            .expect("Unable to get assignment")
+            .expect("Assignment not found");
+        assert_eq!(None, stored_assignment.expires_at());
+        assert!(!stored_assignment.is_expired());
+        assert_eq!(&vec!["test-role".to_string()], stored_assignment.roles());
+    }
+
     /// This test verifies the following:
     /// 1. Add two roles
     /// 2. Add an assignment to one of the roles
@@ -1222,6 +1402,236 @@ mod tests {
         assert!(role_based_auth_store.remove_role(ADMIN_ROLE_ID).is_err());
     }
 
+    /// This tests verifies the following:
+    /// 1. Adds a role and an assignment via the store API
+    /// 2. Imports an updated version of the role, a new role, an updated version of the assignment
+    ///    and a new assignment
+    /// 3. Verifies that the existing role and assignment were replaced and the new ones added
+    #[test]
+    fn sqlite_import_roles_and_assignments() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        role_based_auth_store
+            .add_role(
+                RoleBuilder::new()
+                    .with_id("test-role-1".into())
+                    .with_display_name("Test Role 1".into())
+                    .with_permissions(vec!["a".to_string()])
+                    .build()
+                    .expect("Unable to build role"),
+            )
+            .expect("Unable to add role");
+        role_based_auth_store
+            .add_assignment(
+                AssignmentBuilder::new()
+                    .with_identity(Identity::Key("x".into()))
+                    .with_roles(vec!["test-role-1".to_string()])
+                    .build()
+                    .expect("Unable to build assignment"),
+            )
+            .expect("Unable to add assignment");
+
+        let roles = vec![
+            RoleBuilder::new()
+                .with_id("test-role-1".into())
+                .with_display_name("Updated Role 1".into())
+                .with_permissions(vec!["a".to_string(), "b".to_string()])
+                .build()
+                .expect("Unable to build role"),
+            RoleBuilder::new()
+                .with_id("test-role-2".into())
+                .with_display_name("Test Role 2".into())
+                .with_permissions(vec!["c".to_string()])
+                .build()
+                .expect("Unable to build role"),
+        ];
+        let assignments = vec![
+            AssignmentBuilder::new()
+                .with_identity(Identity::Key("x".into()))
+                .with_roles(vec!["test-role-2".to_string()])
+                .build()
+                .expect("Unable to build assignment"),
+            AssignmentBuilder::new()
+                .with_identity(Identity::User("y".into()))
+                .with_roles(vec!["test-role-1".to_string(), "test-role-2".to_string()])
+                .build()
+                .expect("Unable to build assignment"),
+        ];
+
+        role_based_auth_store
+            .import_roles_and_assignments(roles, assignments)
+            .expect("Unable to import roles and assignments");
+
+        let role = role_based_auth_store
+            .get_role("test-role-1")
+            .expect("Unable to lookup role by id")
+            .expect("Did not find the imported role");
+        assert_eq!("Updated Role 1", role.display_name());
+        assert_eq!(&["a".to_string(), "b".to_string()], role.permissions());
+        assert!(role_based_auth_store
+            .get_role("test-role-2")
+            .expect("Unable to lookup role by id")
+            .is_some());
+
+        let assignment = role_based_auth_store
+            .get_assignment(&Identity::Key("x".into()))
+            .expect("Unable to lookup assignment")
+            .expect("Did not find the imported assignment");
+        assert_eq!(&["test-role-2".to_string()], assignment.roles());
+        let assignment = role_based_auth_store
+            .get_assignment(&Identity::User("y".into()))
+            .expect("Unable to lookup assignment")
+            .expect("Did not find the imported assignment");
+        assert_eq!(
+            &["test-role-1".to_string(), "test-role-2".to_string()],
+            assignment.roles()
+        );
+    }
+
+    /// This tests verifies that an import is applied as a single operation:
+    /// 1. Imports a new role along with an assignment that refers to an unknown role
+    /// 2. Verifies that the import fails and that the new role was not added
+    /// 3. Verifies that importing the `admin` role fails
+    #[test]
+    fn sqlite_import_roles_and_assignments_rolls_back() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let roles = vec![RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role")];
+        let assignments = vec![AssignmentBuilder::new()
+            .with_identity(Identity::Key("x".into()))
+            .with_roles(vec!["unknown-role".to_string()])
+            .build()
+            .expect("Unable to build assignment")];
+
+        assert!(role_based_auth_store
+            .import_roles_and_assignments(roles, assignments)
+            .is_err());
+        assert!(role_based_auth_store
+            .get_role("test-role-1")
+            .expect("Unable to lookup role by id")
+            .is_none());
+
+        let admin_role = role_based_auth_store
+            .get_role(ADMIN_ROLE_ID)
+            .expect("Unable to lookup role by id")
+            .expect("Role not found");
+        assert!(role_based_auth_store
+            .import_roles_and_assignments(vec![admin_role], vec![])
+            .is_err());
+    }
+
+    /// This tests verifies the following:
+    /// 1. Adds a chain of roles that inherit from each other via the store API
+    /// 2. Verifies the inherited roles are returned via the get_inherited_roles API
+    /// 3. Verifies that unknown parents and cycles are rejected
+    /// 4. Verifies that a role cannot be removed while another role inherits from it
+    #[test]
+    fn sqlite_role_inheritance() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let build_role = |id: &str, parents: &[&str]| {
+            RoleBuilder::new()
+                .with_id(id.into())
+                .with_display_name(id.into())
+                .with_permissions(vec![format!("{}.perm", id)])
+                .with_parents(parents.iter().map(|parent| parent.to_string()).collect())
+                .build()
+                .expect("Unable to build role")
+        };
+
+        role_based_auth_store
+            .add_role(build_role("reader", &[]))
+            .expect("Unable to add role");
+        role_based_auth_store
+            .add_role(build_role("writer", &["reader"]))
+            .expect("Unable to add role");
+        role_based_auth_store
+            .add_role(build_role("owner", &["writer"]))
+            .expect("Unable to add role");
+
+        let role = role_based_auth_store
+            .get_role("owner")
+            .expect("Unable to lookup role by id")
+            .expect("Role not found");
+        assert_eq!(&["writer".to_string()], role.parents());
+
+        let mut inherited = role_based_auth_store
+            .get_inherited_roles("owner")
+            .expect("Unable to get inherited roles")
+            .map(|role| role.id().to_string())
+            .collect::<Vec<_>>();
+        inherited.sort();
+        assert_eq!(vec!["reader".to_string(), "writer".to_string()], inherited);
+
+        assert!(role_based_auth_store
+            .add_role(build_role("auditor", &["unknown"]))
+            .is_err());
+        assert!(role_based_auth_store
+            .update_role(build_role("reader", &["owner"]))
+            .is_err());
+        assert!(role_based_auth_store
+            .import_roles_and_assignments(
+                vec![build_role("a", &["b"]), build_role("b", &["a"])],
+                vec![]
+            )
+            .is_err());
+
+        assert!(role_based_auth_store.remove_role("writer").is_err());
+        role_based_auth_store
+            .remove_role("owner")
+            .expect("Unable to remove role");
+        role_based_auth_store
+            .remove_role("writer")
+            .expect("Unable to remove role");
+    }
+
+    /// This tests verifies that imported roles may inherit from roles that appear later in the
+    /// import.
+    #[test]
+    fn sqlite_import_roles_with_parents() {
+        let pool = create_connection_pool_and_migrate();
+
+        let role_based_auth_store = DieselRoleBasedAuthorizationStore::new(pool);
+
+        let roles = vec![
+            RoleBuilder::new()
+                .with_id("circuit-admin".into())
+                .with_display_name("Circuit Admin".into())
+                .with_permissions(vec!["circuit.write".to_string()])
+                .with_parents(vec!["circuit-reader".to_string()])
+                .build()
+                .expect("Unable to build role"),
+            RoleBuilder::new()
+                .with_id("circuit-reader".into())
+                .with_display_name("Circuit Reader".into())
+                .with_permissions(vec!["circuit.read".to_string()])
+                .build()
+                .expect("Unable to build role"),
+        ];
+
+        role_based_auth_store
+            .import_roles_and_assignments(roles, vec![])
+            .expect("Unable to import roles");
+
+        let inherited = role_based_auth_store
+            .get_inherited_roles("circuit-admin")
+            .expect("Unable to get inherited roles")
+            .map(|role| role.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["circuit-reader".to_string()], inherited);
+    }
+
     /// Creates a connection pool for an in-memory SQLite database with only a single connection
     /// available. Each connection is backed by a different in-memory SQLite database, so limiting
     /// the pool to a single connection insures that the same DB is used for all operations.
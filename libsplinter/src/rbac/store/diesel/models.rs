@@ -35,7 +35,9 @@ use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
 use diesel::sqlite::Sqlite;
 
-use super::schema::{rbac_assignments, rbac_identities, rbac_role_permissions, rbac_roles};
+use super::schema::{
+    rbac_assignments, rbac_identities, rbac_role_parents, rbac_role_permissions, rbac_roles,
+};
 
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
 #[table_name = "rbac_roles"]
@@ -54,6 +56,15 @@ pub(super) struct RolePermissionModel {
     pub permission: String,
 }
 
+#[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
+#[table_name = "rbac_role_parents"]
+#[belongs_to(RoleModel, foreign_key = "role_id")]
+#[primary_key(role_id, parent_id)]
+pub(super) struct RoleParentModel {
+    pub role_id: String,
+    pub parent_id: String,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub(super) enum IdentityModelType {
     Key,
@@ -212,6 +223,8 @@ impl HasSqlType<IdentityModelTypeMapping> for Sqlite {
 pub(super) struct IdentityModel {
     pub identity: String,
     pub identity_type: IdentityModelType,
+    /// The time the assignment expires, in seconds since the Unix epoch
+    pub expires_at: Option<i64>,
 }
 
 #[derive(Debug, PartialEq, Associations, Identifiable, Insertable, Queryable)]
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::TryInto;
+
 use diesel::{dsl::insert_into, prelude::*};
 
 use crate::rbac::store::{
@@ -39,7 +41,8 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) =
+            assignment.try_into()?;
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_identities::table)
                 .values(identity)
@@ -62,7 +65,8 @@ impl<'a> RoleBasedAuthorizationStoreAddAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, assignments): (IdentityModel, Vec<AssignmentModel>) =
+            assignment.try_into()?;
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_identities::table)
                 .values(identity)
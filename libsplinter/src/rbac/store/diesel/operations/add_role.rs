@@ -16,12 +16,13 @@ use diesel::{dsl::insert_into, prelude::*};
 
 use crate::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::{rbac_role_permissions, rbac_roles},
+        models::{RoleModel, RoleParentModel, RolePermissionModel},
+        schema::{rbac_role_parents, rbac_role_permissions, rbac_roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
 
+use super::get_inherited_roles::check_role_parents;
 use super::RoleBasedAuthorizationStoreOperations;
 
 pub trait RoleBasedAuthorizationStoreAddRole {
@@ -33,7 +34,11 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, parents): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleParentModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_roles::table)
@@ -44,6 +49,12 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            check_role_parents(self.conn, &parents)?;
+
+            insert_into(rbac_role_parents::table)
+                .values(parents)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
@@ -54,7 +65,11 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
 {
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, parents): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleParentModel>,
+        ) = role.into();
         self.conn.transaction::<_, _, _>(|| {
             insert_into(rbac_roles::table)
                 .values(role)
@@ -64,6 +79,12 @@ impl<'a> RoleBasedAuthorizationStoreAddRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            check_role_parents(self.conn, &parents)?;
+
+            insert_into(rbac_role_parents::table)
+                .values(parents)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
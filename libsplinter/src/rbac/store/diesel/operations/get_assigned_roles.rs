@@ -20,7 +20,7 @@ use crate::rbac::store::{
     diesel::{
         models::{
             AssignmentModel, IdentityModel, IdentityModelType, IdentityModelTypeMapping, RoleModel,
-            RolePermissionModel,
+            RoleParentModel, RolePermissionModel,
         },
         schema::{rbac_identities, rbac_roles},
     },
@@ -73,11 +73,16 @@ where
                     .load::<RolePermissionModel>(self.conn)?
                     .grouped_by(&roles);
 
+                let parents = RoleParentModel::belonging_to(&roles)
+                    .load::<RoleParentModel>(self.conn)?
+                    .grouped_by(&roles);
+
                 Ok(Box::new(
                     roles
                         .into_iter()
                         .zip(perms)
-                        .map(|models| models.try_into())
+                        .zip(parents)
+                        .map(|((role, perms), parents)| (role, perms, parents).try_into())
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
                         .into_iter(),
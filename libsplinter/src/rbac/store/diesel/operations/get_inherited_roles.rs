@@ -0,0 +1,149 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeSet;
+use std::convert::TryInto;
+
+use diesel::prelude::*;
+
+use crate::error::{ConstraintViolationError, ConstraintViolationType};
+use crate::rbac::store::{
+    diesel::{
+        models::{RoleModel, RoleParentModel, RolePermissionModel},
+        schema::{rbac_role_parents, rbac_roles},
+    },
+    Role, RoleBasedAuthorizationStoreError,
+};
+
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreGetInheritedRoles {
+    fn get_inherited_roles(
+        &self,
+        role_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreGetInheritedRoles
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn get_inherited_roles(
+        &self,
+        role_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError> {
+        self.conn
+            .transaction::<Box<dyn ExactSizeIterator<Item = Role>>, _, _>(|| {
+                let ancestor_ids = get_ancestor_ids(self.conn, role_id)?;
+
+                let roles = rbac_roles::table
+                    .filter(rbac_roles::id.eq_any(ancestor_ids))
+                    .load::<RoleModel>(self.conn)?;
+
+                let perms = RolePermissionModel::belonging_to(&roles)
+                    .load::<RolePermissionModel>(self.conn)?
+                    .grouped_by(&roles);
+
+                let parents = RoleParentModel::belonging_to(&roles)
+                    .load::<RoleParentModel>(self.conn)?
+                    .grouped_by(&roles);
+
+                Ok(Box::new(
+                    roles
+                        .into_iter()
+                        .zip(perms)
+                        .zip(parents)
+                        .map(|((role, perms), parents)| (role, perms, parents).try_into())
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(RoleBasedAuthorizationStoreError::from)?
+                        .into_iter(),
+                ))
+            })
+    }
+}
+
+/// Returns the IDs of the roles the given role inherits from, either directly or through its
+/// parents.
+///
+/// The role itself is only included if it is part of a cycle.
+pub(super) fn get_ancestor_ids<C>(
+    conn: &C,
+    role_id: &str,
+) -> Result<BTreeSet<String>, RoleBasedAuthorizationStoreError>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    let mut ancestor_ids = BTreeSet::new();
+    let mut unvisited = vec![role_id.to_string()];
+
+    while !unvisited.is_empty() {
+        let parent_ids = rbac_role_parents::table
+            .filter(rbac_role_parents::role_id.eq_any(unvisited))
+            .select(rbac_role_parents::parent_id)
+            .load::<String>(conn)?;
+
+        unvisited = parent_ids
+            .into_iter()
+            .filter(|parent_id| ancestor_ids.insert(parent_id.clone()))
+            .collect();
+    }
+
+    Ok(ancestor_ids)
+}
+
+/// Verifies that roles may inherit from the given parent roles.
+///
+/// # Errors
+///
+/// Returns a `ConstraintViolation` error if a parent role does not exist, or if a parent role
+/// already inherits from the role, which would create a cycle.
+pub(super) fn check_role_parents<C>(
+    conn: &C,
+    parents: &[RoleParentModel],
+) -> Result<(), RoleBasedAuthorizationStoreError>
+where
+    C: diesel::Connection,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    for RoleParentModel { role_id, parent_id } in parents {
+        let exists = !rbac_roles::table
+            .filter(rbac_roles::id.eq(parent_id))
+            .select(rbac_roles::id)
+            .load::<String>(conn)?
+            .is_empty();
+        if !exists {
+            return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                    format!("Parent role '{}' does not exist", parent_id),
+                )),
+            ));
+        }
+
+        if parent_id == role_id || get_ancestor_ids(conn, parent_id)?.contains(role_id) {
+            return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                    format!(
+                        "Role '{}' cannot inherit from '{}', as it would create a cycle",
+                        role_id, parent_id
+                    ),
+                )),
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -18,7 +18,7 @@ use diesel::prelude::*;
 
 use crate::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
+        models::{RoleModel, RoleParentModel, RolePermissionModel},
         schema::rbac_roles,
     },
     Role, RoleBasedAuthorizationStoreError,
@@ -45,9 +45,15 @@ where
                 .load::<RolePermissionModel>(self.conn)?
                 .grouped_by(&roles);
 
+            let parents = RoleParentModel::belonging_to(&roles)
+                .load::<RoleParentModel>(self.conn)?
+                .grouped_by(&roles);
+
             roles
                 .into_iter()
                 .zip(perms)
+                .zip(parents)
+                .map(|((role, perms), parents)| (role, perms, parents))
                 .next()
                 .map(|models| models.try_into())
                 .transpose()
@@ -0,0 +1,125 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashSet;
+
+use diesel::prelude::*;
+
+use crate::error::{ConstraintViolationError, ConstraintViolationType};
+use crate::rbac::store::{Assignment, Role, RoleBasedAuthorizationStoreError, ADMIN_ROLE_ID};
+
+use super::add_assignment::RoleBasedAuthorizationStoreAddAssignment;
+use super::add_role::RoleBasedAuthorizationStoreAddRole;
+use super::get_assignment::RoleBasedAuthorizationStoreGetAssignment;
+use super::get_role::RoleBasedAuthorizationStoreGetRole;
+use super::update_assignment::RoleBasedAuthorizationStoreUpdateAssignment;
+use super::update_role::RoleBasedAuthorizationStoreUpdateRole;
+use super::RoleBasedAuthorizationStoreOperations;
+
+pub trait RoleBasedAuthorizationStoreImportRolesAndAssignments {
+    fn import_roles_and_assignments(
+        &self,
+        roles: Vec<Role>,
+        assignments: Vec<Assignment>,
+    ) -> Result<(), RoleBasedAuthorizationStoreError>;
+}
+
+impl<'a, C> RoleBasedAuthorizationStoreImportRolesAndAssignments
+    for RoleBasedAuthorizationStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    Self: RoleBasedAuthorizationStoreGetRole
+        + RoleBasedAuthorizationStoreAddRole
+        + RoleBasedAuthorizationStoreUpdateRole
+        + RoleBasedAuthorizationStoreGetAssignment
+        + RoleBasedAuthorizationStoreAddAssignment
+        + RoleBasedAuthorizationStoreUpdateAssignment,
+{
+    fn import_roles_and_assignments(
+        &self,
+        roles: Vec<Role>,
+        assignments: Vec<Assignment>,
+    ) -> Result<(), RoleBasedAuthorizationStoreError> {
+        if roles.iter().any(|role| role.id() == ADMIN_ROLE_ID) {
+            return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                    format!("'{}' role cannot be imported", ADMIN_ROLE_ID),
+                )),
+            ));
+        }
+
+        let roles = order_by_inheritance(roles)?;
+
+        self.conn
+            .transaction::<_, RoleBasedAuthorizationStoreError, _>(|| {
+                for role in roles {
+                    if self.get_role(role.id())?.is_some() {
+                        self.update_role(role)?;
+                    } else {
+                        self.add_role(role)?;
+                    }
+                }
+
+                for assignment in assignments {
+                    if self.get_assignment(assignment.identity())?.is_some() {
+                        self.update_assignment(assignment)?;
+                    } else {
+                        self.add_assignment(assignment)?;
+                    }
+                }
+
+                Ok(())
+            })
+    }
+}
+
+/// Orders the given roles so that each role comes after the imported roles it inherits from.
+///
+/// # Errors
+///
+/// Returns a `ConstraintViolation` error if the roles inherit from each other in a cycle.
+fn order_by_inheritance(
+    mut roles: Vec<Role>,
+) -> Result<Vec<Role>, RoleBasedAuthorizationStoreError> {
+    let mut ordered = Vec::with_capacity(roles.len());
+
+    while !roles.is_empty() {
+        let pending = roles
+            .iter()
+            .map(|role| role.id().to_string())
+            .collect::<HashSet<_>>();
+        let (ready, blocked): (Vec<_>, Vec<_>) = roles.into_iter().partition(|role| {
+            role.parents()
+                .iter()
+                .all(|parent_id| !pending.contains(parent_id))
+        });
+
+        if ready.is_empty() {
+            let role_ids = blocked.iter().map(|role| role.id()).collect::<Vec<_>>();
+            return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                    format!(
+                        "Roles cannot inherit from each other in a cycle: {}",
+                        role_ids.join(", ")
+                    ),
+                )),
+            ));
+        }
+
+        ordered.extend(ready);
+        roles = blocked;
+    }
+
+    Ok(ordered)
+}
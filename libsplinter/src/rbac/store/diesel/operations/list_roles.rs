@@ -18,7 +18,7 @@ use diesel::prelude::*;
 
 use crate::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
+        models::{RoleModel, RoleParentModel, RolePermissionModel},
         schema::rbac_roles,
     },
     Role, RoleBasedAuthorizationStoreError,
@@ -48,11 +48,16 @@ where
                     .load::<RolePermissionModel>(self.conn)?
                     .grouped_by(&roles);
 
+                let parents = RoleParentModel::belonging_to(&roles)
+                    .load::<RoleParentModel>(self.conn)?
+                    .grouped_by(&roles);
+
                 Ok(Box::new(
                     roles
                         .into_iter()
                         .zip(perms)
-                        .map(|models| models.try_into())
+                        .zip(parents)
+                        .map(|((role, perms), parents)| (role, perms, parents).try_into())
                         .collect::<Result<Vec<_>, _>>()
                         .map_err(RoleBasedAuthorizationStoreError::from)?
                         .into_iter(),
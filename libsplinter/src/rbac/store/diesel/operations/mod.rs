@@ -16,7 +16,9 @@ pub(super) mod add_assignment;
 pub(super) mod add_role;
 pub(super) mod get_assigned_roles;
 pub(super) mod get_assignment;
+pub(super) mod get_inherited_roles;
 pub(super) mod get_role;
+pub(super) mod import_roles_and_assignments;
 pub(super) mod list_assignments;
 pub(super) mod list_roles;
 pub(super) mod remove_assignment;
@@ -14,8 +14,9 @@
 
 use diesel::{dsl::delete, prelude::*};
 
+use crate::error::{ConstraintViolationError, ConstraintViolationType};
 use crate::rbac::store::{
-    diesel::schema::{rbac_role_permissions, rbac_roles},
+    diesel::schema::{rbac_role_parents, rbac_role_permissions, rbac_roles},
     RoleBasedAuthorizationStoreError,
 };
 
@@ -32,6 +33,25 @@ where
 {
     fn remove_role(&self, role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
         self.conn.transaction::<_, _, _>(|| {
+            let child_ids = rbac_role_parents::table
+                .filter(rbac_role_parents::parent_id.eq(role_id))
+                .select(rbac_role_parents::role_id)
+                .load::<String>(self.conn)?;
+            if !child_ids.is_empty() {
+                return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Other(
+                        format!(
+                            "Role '{}' cannot be removed, as it is inherited by: {}",
+                            role_id,
+                            child_ids.join(", ")
+                        ),
+                    )),
+                ));
+            }
+
+            delete(rbac_role_parents::table.filter(rbac_role_parents::role_id.eq(role_id)))
+                .execute(self.conn)?;
+
             delete(rbac_role_permissions::table.filter(rbac_role_permissions::role_id.eq(role_id)))
                 .execute(self.conn)?;
 
@@ -12,8 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::TryInto;
+
 use diesel::{
-    dsl::{delete, insert_into},
+    dsl::{delete, insert_into, update},
     prelude::*,
 };
 
@@ -43,16 +45,17 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.try_into()?;
         self.conn.transaction::<_, _, _>(|| {
-            let count = rbac_identities::table
-                .filter(
+            let count = update(
+                rbac_identities::table.filter(
                     rbac_identities::identity
                         .eq(&identity.identity)
                         .and(rbac_identities::identity_type.eq(identity.identity_type)),
-                )
-                .count()
-                .get_result::<i64>(self.conn)?;
+                ),
+            )
+            .set(rbac_identities::expires_at.eq(identity.expires_at))
+            .execute(self.conn)?;
 
             if count == 0 {
                 return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
@@ -84,16 +87,17 @@ impl<'a> RoleBasedAuthorizationStoreUpdateAssignment
         &self,
         assignment: Assignment,
     ) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.into();
+        let (identity, roles): (IdentityModel, Vec<AssignmentModel>) = assignment.try_into()?;
         self.conn.transaction::<_, _, _>(|| {
-            let count = rbac_identities::table
-                .filter(
+            let count = update(
+                rbac_identities::table.filter(
                     rbac_identities::identity
                         .eq(&identity.identity)
                         .and(rbac_identities::identity_type.eq(identity.identity_type)),
-                )
-                .count()
-                .get_result::<i64>(self.conn)?;
+                ),
+            )
+            .set(rbac_identities::expires_at.eq(identity.expires_at))
+            .execute(self.conn)?;
 
             if count == 0 {
                 return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
@@ -20,12 +20,13 @@ use diesel::{
 use crate::error::{ConstraintViolationError, ConstraintViolationType};
 use crate::rbac::store::{
     diesel::{
-        models::{RoleModel, RolePermissionModel},
-        schema::{rbac_role_permissions, rbac_roles},
+        models::{RoleModel, RoleParentModel, RolePermissionModel},
+        schema::{rbac_role_parents, rbac_role_permissions, rbac_roles},
     },
     Role, RoleBasedAuthorizationStoreError,
 };
 
+use super::get_inherited_roles::check_role_parents;
 use super::RoleBasedAuthorizationStoreOperations;
 
 pub trait RoleBasedAuthorizationStoreUpdateRole {
@@ -37,7 +38,11 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::sqlite::SqliteConnection>
 {
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, parents): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleParentModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
             let updated = update(rbac_roles::table.find(&role.id))
@@ -61,6 +66,15 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            delete(rbac_role_parents::table.filter(rbac_role_parents::role_id.eq(&role.id)))
+                .execute(self.conn)?;
+
+            check_role_parents(self.conn, &parents)?;
+
+            insert_into(rbac_role_parents::table)
+                .values(parents)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
@@ -71,7 +85,11 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
     for RoleBasedAuthorizationStoreOperations<'a, diesel::pg::PgConnection>
 {
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
-        let (role, permissions): (RoleModel, Vec<RolePermissionModel>) = role.into();
+        let (role, permissions, parents): (
+            RoleModel,
+            Vec<RolePermissionModel>,
+            Vec<RoleParentModel>,
+        ) = role.into();
 
         self.conn.transaction::<_, _, _>(|| {
             let updated = update(rbac_roles::table.find(&role.id))
@@ -95,6 +113,15 @@ impl<'a> RoleBasedAuthorizationStoreUpdateRole
                 .values(permissions)
                 .execute(self.conn)?;
 
+            delete(rbac_role_parents::table.filter(rbac_role_parents::role_id.eq(&role.id)))
+                .execute(self.conn)?;
+
+            check_role_parents(self.conn, &parents)?;
+
+            insert_into(rbac_role_parents::table)
+                .values(parents)
+                .execute(self.conn)?;
+
             Ok(())
         })
     }
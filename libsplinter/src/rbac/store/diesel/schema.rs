@@ -26,8 +26,16 @@ table! {
     }
 }
 
+table! {
+    rbac_role_parents (role_id, parent_id) {
+        role_id -> Text,
+        parent_id -> Text,
+    }
+}
+
 joinable!(rbac_role_permissions -> rbac_roles (role_id));
-allow_tables_to_appear_in_same_query!(rbac_roles, rbac_role_permissions);
+joinable!(rbac_role_parents -> rbac_roles (role_id));
+allow_tables_to_appear_in_same_query!(rbac_roles, rbac_role_permissions, rbac_role_parents);
 
 table! {
     rbac_identities (identity) {
@@ -35,6 +43,7 @@ table! {
         identity_type ->
             // the macro output can't find this type if it isn't fully qualified.
             crate::rbac::store::diesel::models::IdentityModelTypeMapping,
+        expires_at -> Nullable<BigInt>,
     }
 }
 
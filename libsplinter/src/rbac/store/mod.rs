@@ -37,6 +37,12 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
     /// Returns the role for the given ID, if one exists.
     fn get_role(&self, id: &str) -> Result<Option<Role>, RoleBasedAuthorizationStoreError>;
 
+    /// Returns the roles the given role inherits from, either directly or through its parents.
+    fn get_inherited_roles(
+        &self,
+        role_id: &str,
+    ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>;
+
     /// Lists all roles.
     fn list_roles(
         &self,
@@ -46,21 +52,24 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
     ///
     /// # Errors
     ///
-    /// Returns a `ConstraintViolation` error if a duplicate role ID is added.
+    /// Returns a `ConstraintViolation` error if a duplicate role ID is added, if a parent role does
+    /// not exist, or if a parent role inherits from the role.
     fn add_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
 
     /// Updates a role.
     ///
     /// # Errors
     ///
-    /// Returns a `ConstraintViolation` error if the role does not exist.
+    /// Returns a `ConstraintViolation` error if the role does not exist, if a parent role does not
+    /// exist, or if a parent role inherits from the role.
     fn update_role(&self, role: Role) -> Result<(), RoleBasedAuthorizationStoreError>;
 
     /// Removes a role.
     ///
     /// # Errors
     ///
-    /// Returns a `InvalidState` error if the role does not exist.
+    /// Returns a `InvalidState` error if the role does not exist, or a `ConstraintViolation` error
+    /// if another role inherits from it.
     fn remove_role(&self, role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError>;
 
     /// Returns the role for the given Identity, if one exists.
@@ -111,6 +120,22 @@ pub trait RoleBasedAuthorizationStore: Send + Sync {
         identity: &Identity,
     ) -> Result<(), RoleBasedAuthorizationStoreError>;
 
+    /// Adds or replaces the given roles and assignments as a single operation.
+    ///
+    /// Roles and assignments that already exist are replaced, and all others are added. Either
+    /// all of the roles and assignments are stored, or none of them are.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConstraintViolation` error if the `admin` role is included, or if an assignment
+    /// refers to a role that is neither included nor already stored, or if the roles' parents form
+    /// a cycle.
+    fn import_roles_and_assignments(
+        &self,
+        roles: Vec<Role>,
+        assignments: Vec<Assignment>,
+    ) -> Result<(), RoleBasedAuthorizationStoreError>;
+
     /// Clone into a boxed, dynamically dispatched store
     fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore>;
 }
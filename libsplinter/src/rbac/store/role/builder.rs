@@ -21,6 +21,7 @@ pub struct RoleBuilder {
     id: Option<String>,
     display_name: Option<String>,
     permissions: Vec<String>,
+    parents: Vec<String>,
 }
 
 impl RoleBuilder {
@@ -47,6 +48,12 @@ impl RoleBuilder {
         self
     }
 
+    /// Sets the IDs of the roles the new role inherits permissions from.
+    pub fn with_parents(mut self, parents: Vec<String>) -> Self {
+        self.parents = parents;
+        self
+    }
+
     /// Builds the new Role.
     ///
     /// # Errors
@@ -54,11 +61,12 @@ impl RoleBuilder {
     /// Returns an [`InvalidStateError`] under the following conditions:
     /// * no ID or an empty ID was provided
     /// * no display name or an empty display name was provided
-    /// * empty permissions were provided
+    /// * neither permissions nor parent roles were provided
+    /// * the role is one of its own parents
     pub fn build(self) -> Result<Role, InvalidStateError> {
-        if self.permissions.is_empty() {
+        if self.permissions.is_empty() && self.parents.is_empty() {
             return Err(InvalidStateError::with_message(
-                "A role requires at least one permission".into(),
+                "A role requires at least one permission or parent role".into(),
             ));
         }
 
@@ -70,6 +78,11 @@ impl RoleBuilder {
                 "A role requires a non-empty id field".into(),
             ));
         }
+        if self.parents.contains(&id) {
+            return Err(InvalidStateError::with_message(
+                "A role cannot inherit from itself".into(),
+            ));
+        }
         let display_name = self.display_name.ok_or_else(|| {
             InvalidStateError::with_message("A role requires a display_name field".into())
         })?;
@@ -84,6 +97,7 @@ impl RoleBuilder {
             id,
             display_name,
             permissions: self.permissions,
+            parents: self.parents,
         })
     }
 }
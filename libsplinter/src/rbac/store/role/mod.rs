@@ -24,6 +24,7 @@ pub struct Role {
     id: String,
     display_name: String,
     permissions: Vec<String>,
+    parents: Vec<String>,
 }
 
 impl Role {
@@ -42,11 +43,17 @@ impl Role {
         &self.permissions
     }
 
+    /// Returns the IDs of the roles this role inherits permissions from.
+    pub fn parents(&self) -> &[String] {
+        &self.parents
+    }
+
     /// Convert this role back into a builder, in order to update its values.
     pub fn into_update_builder(self) -> RoleUpdateBuilder {
         RoleUpdateBuilder::new(self.id)
             .with_display_name(self.display_name)
             .with_permissions(self.permissions)
+            .with_parents(self.parents)
     }
 
     /// Converts this role into it's constituent parts.  These parts are in the tuple:
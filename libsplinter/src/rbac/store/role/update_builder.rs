@@ -22,6 +22,7 @@ pub struct RoleUpdateBuilder {
     id: String,
     display_name: Option<String>,
     permissions: Vec<String>,
+    parents: Vec<String>,
 }
 
 impl RoleUpdateBuilder {
@@ -30,6 +31,7 @@ impl RoleUpdateBuilder {
             id,
             display_name: None,
             permissions: Vec::new(),
+            parents: Vec::new(),
         }
     }
     /// Updates the display name for the updated role.
@@ -44,17 +46,30 @@ impl RoleUpdateBuilder {
         self
     }
 
+    /// Updates the IDs of the roles the updated role inherits permissions from.
+    pub fn with_parents(mut self, parents: Vec<String>) -> Self {
+        self.parents = parents;
+        self
+    }
+
     /// Builds the updated Role.
     ///
     /// # Errors
     ///
     /// Returns an [`InvalidStateError`] under the following conditions:
     /// * an empty display name was provided
-    /// * empty permissions were provided
+    /// * neither permissions nor parent roles were provided
+    /// * the role is one of its own parents
     pub fn build(self) -> Result<Role, InvalidStateError> {
-        if self.permissions.is_empty() {
+        if self.permissions.is_empty() && self.parents.is_empty() {
+            return Err(InvalidStateError::with_message(
+                "A role requires at least one permission or parent role".into(),
+            ));
+        }
+
+        if self.parents.contains(&self.id) {
             return Err(InvalidStateError::with_message(
-                "A role requires at least one permission".into(),
+                "A role cannot inherit from itself".into(),
             ));
         }
 
@@ -72,6 +87,7 @@ impl RoleUpdateBuilder {
             id: self.id,
             display_name,
             permissions: self.permissions,
+            parents: self.parents,
         })
     }
 }
@@ -0,0 +1,296 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Adds the members of committed circuits to the local registry.
+
+use crate::admin::service::{AdminServiceEventSubscriber, AdminSubscriberError};
+use crate::admin::store::{AdminServiceEvent, EventType, ProposedNode};
+use crate::error::InvalidStateError;
+use crate::hex::to_hex;
+
+use super::{Node, RegistryError, RwRegistry};
+
+/// Adds or updates a registry entry for each member of a circuit when the circuit is ready.
+///
+/// A member that is not in the registry is added with the endpoints and public key from the
+/// circuit. A member that is already in the registry keeps its display name and metadata, and
+/// gains any of the circuit's endpoints or its public key that the entry is missing; existing
+/// endpoints and keys are never removed. Members without a public key, and the local node, are
+/// skipped.
+///
+/// Entries are written to the writable registry, so members that are only defined in read-only
+/// registries are not updated.
+pub struct RegistryAutoPopulator {
+    node_id: String,
+    registry: Box<dyn RwRegistry>,
+}
+
+impl RegistryAutoPopulator {
+    /// Creates a new populator.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the local node, which is never added to the registry
+    /// * `registry` - The registry to which the circuit members are added
+    pub fn new(node_id: &str, registry: Box<dyn RwRegistry>) -> Self {
+        RegistryAutoPopulator {
+            node_id: node_id.to_string(),
+            registry,
+        }
+    }
+
+    fn register_member(
+        &self,
+        circuit_id: &str,
+        member: &ProposedNode,
+    ) -> Result<(), RegistryError> {
+        let key = match member.public_key() {
+            Some(public_key) => to_hex(public_key.as_slice()),
+            None => {
+                debug!(
+                    "Not adding node {} from circuit {} to the registry; it has no public key",
+                    member.node_id(),
+                    circuit_id
+                );
+                return Ok(());
+            }
+        };
+
+        match self.registry.get_node(member.node_id())? {
+            Some(node) => {
+                let mut endpoints = node.endpoints().to_vec();
+                for endpoint in member.endpoints() {
+                    if !endpoints.contains(endpoint) {
+                        endpoints.push(endpoint.clone());
+                    }
+                }
+
+                let mut keys = node.keys().to_vec();
+                if !node.has_key(&key) {
+                    keys.push(key);
+                }
+
+                if endpoints.len() == node.endpoints().len() && keys.len() == node.keys().len() {
+                    return Ok(());
+                }
+
+                let updated_node = node
+                    .metadata()
+                    .iter()
+                    .fold(
+                        Node::builder(node.identity())
+                            .with_display_name(node.display_name())
+                            .with_endpoints(endpoints)
+                            .with_keys(keys),
+                        |builder, (key, value)| builder.with_metadata(key, value),
+                    )
+                    .build()
+                    .map_err(invalid_node)?;
+
+                info!(
+                    "Updating node {} in the registry from circuit {}",
+                    member.node_id(),
+                    circuit_id
+                );
+                self.registry.update_node(updated_node)
+            }
+            None => {
+                let node = Node::builder(member.node_id())
+                    .with_endpoints(member.endpoints().to_vec())
+                    .with_key(key)
+                    .build()
+                    .map_err(invalid_node)?;
+
+                info!(
+                    "Adding node {} to the registry from circuit {}",
+                    member.node_id(),
+                    circuit_id
+                );
+                self.registry.add_node(node)
+            }
+        }
+    }
+}
+
+impl AdminServiceEventSubscriber for RegistryAutoPopulator {
+    fn handle_event(&self, event: &AdminServiceEvent) -> Result<(), AdminSubscriberError> {
+        if event.event_type() != &EventType::CircuitReady {
+            return Ok(());
+        }
+
+        let proposal = event.proposal();
+        for member in proposal.circuit().members() {
+            if member.node_id() == self.node_id {
+                continue;
+            }
+
+            // A member that cannot be registered should not prevent the others from being
+            // registered
+            if let Err(err) = self.register_member(proposal.circuit_id(), member) {
+                warn!(
+                    "Unable to add node {} from circuit {} to the registry: {}",
+                    member.node_id(),
+                    proposal.circuit_id(),
+                    err
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_node<E: std::fmt::Display>(err: E) -> RegistryError {
+    RegistryError::InvalidStateError(InvalidStateError::with_message(err.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    use crate::admin::store::{
+        AdminServiceEventBuilder, CircuitProposalBuilder, ProposalType, ProposedCircuitBuilder,
+        ProposedNodeBuilder,
+    };
+    use crate::public_key::PublicKey;
+    use crate::registry::{LocalYamlRegistry, RegistryReader, RegistryWriter};
+
+    /// Verify that the members of a ready circuit are added to the registry, that an existing
+    /// entry gains the circuit's endpoints and key without losing its own, and that the local
+    /// node, members without a key and other events are ignored.
+    #[test]
+    fn test_registry_auto_populator() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let registry = LocalYamlRegistry::new(
+            dir.path()
+                .join("registry.yaml")
+                .to_str()
+                .expect("Path is not valid UTF-8"),
+        )
+        .expect("Failed to create registry");
+
+        registry
+            .add_node(
+                Node::builder("bubba-node-000")
+                    .with_endpoint("tcps://bubba-old:8044")
+                    .with_key("aabb")
+                    .with_display_name("Bubba")
+                    .with_metadata("company", "Bubba Bakery")
+                    .build()
+                    .expect("Failed to build node"),
+            )
+            .expect("Failed to add node");
+
+        let populator = RegistryAutoPopulator::new("acme-node-000", registry.clone_box());
+
+        populator
+            .handle_event(&create_event(EventType::ProposalSubmitted))
+            .expect("Failed to handle event");
+        assert!(!registry
+            .has_node("cargill-node-000")
+            .expect("Failed to check node"));
+
+        populator
+            .handle_event(&create_event(EventType::CircuitReady))
+            .expect("Failed to handle event");
+
+        assert!(!registry
+            .has_node("acme-node-000")
+            .expect("Failed to check node"));
+        assert!(!registry
+            .has_node("dgc-node-000")
+            .expect("Failed to check node"));
+
+        let node = registry
+            .get_node("cargill-node-000")
+            .expect("Failed to get node")
+            .expect("Node was not added");
+        assert_eq!(
+            node.endpoints(),
+            &["tcps://cargill-node-000:8044".to_string()]
+        );
+        assert_eq!(node.keys(), &[to_hex(b"cargill-node-000")]);
+
+        let node = registry
+            .get_node("bubba-node-000")
+            .expect("Failed to get node")
+            .expect("Node was removed");
+        assert_eq!(
+            node.endpoints(),
+            &[
+                "tcps://bubba-old:8044".to_string(),
+                "tcps://bubba-node-000:8044".to_string()
+            ]
+        );
+        assert_eq!(
+            node.keys(),
+            &["aabb".to_string(), to_hex(b"bubba-node-000")]
+        );
+        assert_eq!(node.display_name(), "Bubba");
+        assert_eq!(
+            node.metadata().get("company").map(String::as_str),
+            Some("Bubba Bakery")
+        );
+    }
+
+    /// Creates an event for a circuit between acme-node-000, bubba-node-000, cargill-node-000 and
+    /// dgc-node-000, where every node except dgc-node-000 has a public key.
+    fn create_event(event_type: EventType) -> AdminServiceEvent {
+        let members = [
+            "acme-node-000",
+            "bubba-node-000",
+            "cargill-node-000",
+            "dgc-node-000",
+        ]
+        .iter()
+        .map(|node_id| {
+            let mut builder = ProposedNodeBuilder::default()
+                .with_node_id(node_id)
+                .with_endpoints(&[format!("tcps://{}:8044", node_id)]);
+            if *node_id != "dgc-node-000" {
+                builder =
+                    builder.with_public_key(&PublicKey::from_bytes(node_id.as_bytes().to_vec()));
+            }
+            builder.build().expect("Unable to build node")
+        })
+        .collect::<Vec<_>>();
+
+        let proposal = CircuitProposalBuilder::default()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_circuit_hash("7ddc4269")
+            .with_circuit(
+                &ProposedCircuitBuilder::default()
+                    .with_circuit_id("WBKLF-BBBBB")
+                    .with_roster(&[])
+                    .with_members(&members)
+                    .with_circuit_management_type("test")
+                    .build()
+                    .expect("Unable to build circuit"),
+            )
+            .with_requester(&PublicKey::from_bytes(b"acme".to_vec()))
+            .with_requester_node_id("acme-node-000")
+            .build()
+            .expect("Unable to build proposal");
+
+        AdminServiceEventBuilder::new()
+            .with_event_id(1)
+            .with_event_type(&event_type)
+            .with_proposal(&proposal)
+            .build()
+            .expect("Unable to build event")
+    }
+}
@@ -0,0 +1,386 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A read-only registry of the Splinter nodes discovered on the local network with mDNS.
+//!
+//! Each node advertises a `_splinter._tcp` DNS-SD service, whose TXT record holds the node's ID,
+//! display name, endpoints and public keys. [`MdnsRegistry`] browses for these services and keeps
+//! an entry for each node that is currently advertised; an entry is removed when its node stops
+//! advertising or its record expires. A node without an `endpoints` property is reachable at
+//! `tcps://<address>:<port>` of its service record.
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::Ipv4Addr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use flume::RecvTimeoutError;
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+
+use crate::error::InternalError;
+use crate::threading::lifecycle::ShutdownHandle;
+
+use super::{MetadataPredicate, Node, NodeBuilder, NodeIter, RegistryError, RegistryReader};
+
+/// The DNS-SD service type advertised by Splinter nodes
+pub const SPLINTER_SERVICE_TYPE: &str = "_splinter._tcp.local.";
+
+const NODE_ID_PROPERTY: &str = "node_id";
+const DISPLAY_NAME_PROPERTY: &str = "display_name";
+const ENDPOINTS_PROPERTY: &str = "endpoints";
+const KEYS_PROPERTY: &str = "keys";
+
+const EVENT_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The local node's entry, as advertised to the other nodes on the network
+pub struct MdnsAdvertisement {
+    node: Node,
+    address: Ipv4Addr,
+    port: u16,
+}
+
+impl MdnsAdvertisement {
+    /// Creates an advertisement for the given node, whose service record points to the given
+    /// address and port.
+    pub fn new(node: Node, address: Ipv4Addr, port: u16) -> Self {
+        Self {
+            node,
+            address,
+            port,
+        }
+    }
+
+    fn to_service_info(&self) -> Result<ServiceInfo, RegistryError> {
+        let mut properties = HashMap::new();
+        properties.insert(
+            NODE_ID_PROPERTY.to_string(),
+            self.node.identity().to_string(),
+        );
+        properties.insert(
+            DISPLAY_NAME_PROPERTY.to_string(),
+            self.node.display_name().to_string(),
+        );
+        properties.insert(
+            ENDPOINTS_PROPERTY.to_string(),
+            self.node.endpoints().join(","),
+        );
+        properties.insert(KEYS_PROPERTY.to_string(), self.node.keys().join(","));
+
+        ServiceInfo::new(
+            SPLINTER_SERVICE_TYPE,
+            self.node.identity(),
+            &format!("{}.local.", self.node.identity()),
+            self.address.to_string().as_str(),
+            self.port,
+            Some(properties),
+        )
+        .map_err(|err| {
+            RegistryError::InternalError(InternalError::with_message(format!(
+                "Unable to create mDNS service record for node {}: {}",
+                self.node.identity(),
+                err
+            )))
+        })
+    }
+}
+
+/// A read-only registry of the nodes advertised on the local network.
+///
+/// The registry's entries are kept up to date by a background thread, which is stopped with the
+/// registry's [`MdnsRegistryShutdownHandle`].
+pub struct MdnsRegistry {
+    /// The discovered nodes, keyed by the full name of their service record
+    nodes: Arc<Mutex<HashMap<String, Node>>>,
+    shutdown_handle: Option<MdnsRegistryShutdownHandle>,
+}
+
+impl MdnsRegistry {
+    /// Starts browsing for the nodes on the local network, and advertises the local node if an
+    /// advertisement is given. The local node is not added to the registry.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`RegistryError`] if the mDNS daemon could not be started or the local node
+    /// could not be advertised.
+    pub fn new(advertisement: Option<MdnsAdvertisement>) -> Result<Self, RegistryError> {
+        let daemon = ServiceDaemon::new().map_err(|err| {
+            RegistryError::InternalError(InternalError::with_message(format!(
+                "Unable to start mDNS daemon: {}",
+                err
+            )))
+        })?;
+
+        let advertised = match &advertisement {
+            Some(advertisement) => {
+                let service_info = advertisement.to_service_info()?;
+                let fullname = service_info.get_fullname().to_string();
+                daemon.register(service_info).map_err(|err| {
+                    RegistryError::InternalError(InternalError::with_message(format!(
+                        "Unable to advertise node over mDNS: {}",
+                        err
+                    )))
+                })?;
+                Some((advertisement.node.identity().to_string(), fullname))
+            }
+            None => None,
+        };
+
+        let receiver = daemon.browse(SPLINTER_SERVICE_TYPE).map_err(|err| {
+            RegistryError::InternalError(InternalError::with_message(format!(
+                "Unable to browse for mDNS services: {}",
+                err
+            )))
+        })?;
+
+        let nodes = Arc::new(Mutex::new(HashMap::new()));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_nodes = nodes.clone();
+        let thread_running = running.clone();
+        let join_handle = thread::Builder::new()
+            .name("mDNS Registry".into())
+            .spawn(move || {
+                let local_node_id = advertised.as_ref().map(|(node_id, _)| node_id.as_str());
+                while thread_running.load(Ordering::SeqCst) {
+                    match receiver.recv_timeout(EVENT_TIMEOUT) {
+                        Ok(event) => handle_event(&thread_nodes, event, local_node_id),
+                        Err(RecvTimeoutError::Timeout) => (),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            error!("mDNS daemon stopped unexpectedly");
+                            break;
+                        }
+                    }
+                }
+
+                if let Some((_, fullname)) = &advertised {
+                    let _ = daemon.unregister(fullname);
+                }
+                let _ = daemon.shutdown();
+            })
+            .map_err(|err| {
+                RegistryError::InternalError(InternalError::with_message(format!(
+                    "Unable to start mDNS registry thread: {}",
+                    err
+                )))
+            })?;
+
+        Ok(Self {
+            nodes,
+            shutdown_handle: Some(MdnsRegistryShutdownHandle {
+                running,
+                join_handle,
+            }),
+        })
+    }
+
+    /// Takes the handle that stops the registry's background thread.
+    pub fn take_shutdown_handle(&mut self) -> Option<MdnsRegistryShutdownHandle> {
+        self.shutdown_handle.take()
+    }
+
+    /// Returns the discovered nodes, sorted by identity; if more than one service advertises the
+    /// same node, only one of them is returned.
+    fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
+        Ok(self
+            .nodes
+            .lock()
+            .map_err(|_| {
+                RegistryError::InternalError(InternalError::with_message(
+                    "mDNS registry lock poisoned".into(),
+                ))
+            })?
+            .values()
+            .map(|node| (node.identity().to_string(), node.clone()))
+            .collect::<BTreeMap<_, _>>()
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect())
+    }
+}
+
+impl RegistryReader for MdnsRegistry {
+    fn get_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        Ok(self
+            .get_nodes()?
+            .into_iter()
+            .find(|node| node.identity() == identity))
+    }
+
+    fn list_nodes<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        let mut nodes = self.get_nodes()?;
+        nodes.retain(|node| predicates.iter().all(|predicate| predicate.apply(node)));
+        Ok(Box::new(nodes.into_iter()))
+    }
+
+    fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
+        Ok(self
+            .get_nodes()?
+            .iter()
+            .filter(|node| predicates.iter().all(|predicate| predicate.apply(node)))
+            .count() as u32)
+    }
+}
+
+/// Handle for stopping an `MdnsRegistry`; the local node's advertisement is withdrawn when the
+/// registry stops.
+pub struct MdnsRegistryShutdownHandle {
+    running: Arc<AtomicBool>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl ShutdownHandle for MdnsRegistryShutdownHandle {
+    fn signal_shutdown(&mut self) {
+        self.running.store(false, Ordering::SeqCst)
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        self.join_handle
+            .join()
+            .map_err(|_| InternalError::with_message("Unable to shutdown mDNS registry".into()))
+    }
+}
+
+fn handle_event(
+    nodes: &Mutex<HashMap<String, Node>>,
+    event: ServiceEvent,
+    local_node_id: Option<&str>,
+) {
+    let mut nodes = match nodes.lock() {
+        Ok(nodes) => nodes,
+        Err(_) => {
+            error!("mDNS registry lock poisoned");
+            return;
+        }
+    };
+
+    match event {
+        ServiceEvent::ServiceResolved(info) => {
+            let addresses = info.get_addresses().iter().copied().collect::<Vec<_>>();
+            match node_from_record(info.get_properties(), &addresses, info.get_port()) {
+                Ok(node) if Some(node.identity()) == local_node_id => (),
+                Ok(node) => {
+                    debug!(
+                        "Discovered node {} over mDNS at {}",
+                        node.identity(),
+                        node.endpoints().join(", ")
+                    );
+                    nodes.insert(info.get_fullname().to_string(), node);
+                }
+                Err(err) => warn!(
+                    "Ignoring invalid mDNS service record {}: {}",
+                    info.get_fullname(),
+                    err
+                ),
+            }
+        }
+        ServiceEvent::ServiceRemoved(_, fullname) => {
+            if let Some(node) = nodes.remove(&fullname) {
+                debug!("Node {} is no longer advertised over mDNS", node.identity());
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Builds a node from the TXT properties, addresses and port of its service record.
+fn node_from_record(
+    properties: &HashMap<String, String>,
+    addresses: &[Ipv4Addr],
+    port: u16,
+) -> Result<Node, String> {
+    let node_id = properties
+        .get(NODE_ID_PROPERTY)
+        .ok_or_else(|| format!("missing {} property", NODE_ID_PROPERTY))?;
+
+    let mut builder = NodeBuilder::new(node_id.as_str()).with_keys(split_list(
+        properties.get(KEYS_PROPERTY).map(String::as_str),
+    ));
+
+    let endpoints = split_list(properties.get(ENDPOINTS_PROPERTY).map(String::as_str));
+    builder = if endpoints.is_empty() {
+        builder.with_endpoints(
+            addresses
+                .iter()
+                .map(|address| format!("tcps://{}:{}", address, port))
+                .collect::<Vec<_>>(),
+        )
+    } else {
+        builder.with_endpoints(endpoints)
+    };
+
+    if let Some(display_name) = properties.get(DISPLAY_NAME_PROPERTY) {
+        builder = builder.with_display_name(display_name.as_str());
+    }
+
+    builder.build().map_err(|err| err.to_string())
+}
+
+fn split_list(list: Option<&str>) -> Vec<String> {
+    list.map(|list| {
+        list.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(String::from)
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a node is built from the properties of a service record, that the endpoints
+    /// default to the record's addresses, and that a record without a node ID or keys is
+    /// rejected.
+    #[test]
+    fn test_node_from_record() {
+        let mut properties = HashMap::new();
+        properties.insert(NODE_ID_PROPERTY.to_string(), "node-1".to_string());
+        properties.insert(DISPLAY_NAME_PROPERTY.to_string(), "Node 1".to_string());
+        properties.insert(KEYS_PROPERTY.to_string(), "0123,4567".to_string());
+        properties.insert(
+            ENDPOINTS_PROPERTY.to_string(),
+            "tcps://10.0.0.1:8044, tcps://10.0.0.2:8044".to_string(),
+        );
+
+        let node = node_from_record(&properties, &[], 8044).expect("Failed to build node");
+        assert_eq!(node.identity(), "node-1");
+        assert_eq!(node.display_name(), "Node 1");
+        assert_eq!(node.keys(), &["0123".to_string(), "4567".to_string()]);
+        assert_eq!(
+            node.endpoints(),
+            &[
+                "tcps://10.0.0.1:8044".to_string(),
+                "tcps://10.0.0.2:8044".to_string()
+            ]
+        );
+
+        properties.remove(ENDPOINTS_PROPERTY);
+        let node = node_from_record(&properties, &[Ipv4Addr::new(192, 168, 1, 7)], 8044)
+            .expect("Failed to build node");
+        assert_eq!(node.endpoints(), &["tcps://192.168.1.7:8044".to_string()]);
+
+        properties.remove(KEYS_PROPERTY);
+        assert!(node_from_record(&properties, &[Ipv4Addr::new(192, 168, 1, 7)], 8044).is_err());
+
+        properties.remove(NODE_ID_PROPERTY);
+        assert!(node_from_record(&properties, &[Ipv4Addr::new(192, 168, 1, 7)], 8044).is_err());
+    }
+}
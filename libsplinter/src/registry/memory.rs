@@ -0,0 +1,220 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An in-memory, read/write registry.
+
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::{InternalError, InvalidStateError};
+
+use super::{
+    validate_nodes, MetadataPredicate, Node, NodeIter, RegistryError, RegistryReader,
+    RegistryWriter, RwRegistry,
+};
+
+/// A read/write registry that keeps its nodes in memory.
+///
+/// The nodes are shared by all clones of the registry, and are lost when the last clone is
+/// dropped. Nodes are validated as they are written, just as they are by the other registries.
+#[derive(Clone, Default)]
+pub struct MemoryRegistry {
+    nodes: Arc<Mutex<Vec<Node>>>,
+}
+
+impl MemoryRegistry {
+    /// Constructs a new, empty `MemoryRegistry`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn lock_nodes(&self) -> Result<MutexGuard<Vec<Node>>, RegistryError> {
+        self.nodes.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "Memory registry's internal lock poisoned".into(),
+            ))
+        })
+    }
+
+    /// Validates the given nodes and replaces the registry's nodes with them.
+    fn write_nodes(nodes: Vec<Node>, registry: &mut Vec<Node>) -> Result<(), RegistryError> {
+        validate_nodes(&nodes).map_err(|err| {
+            RegistryError::InvalidStateError(InvalidStateError::with_message(err.to_string()))
+        })?;
+        *registry = nodes;
+        Ok(())
+    }
+}
+
+impl RegistryReader for MemoryRegistry {
+    fn list_nodes<'a, 'b: 'a>(
+        &'b self,
+        predicates: &'a [MetadataPredicate],
+    ) -> Result<NodeIter<'a>, RegistryError> {
+        let mut nodes = self.lock_nodes()?.clone();
+        nodes.retain(|node| predicates.iter().all(|predicate| predicate.apply(node)));
+        Ok(Box::new(nodes.into_iter()))
+    }
+
+    fn count_nodes(&self, predicates: &[MetadataPredicate]) -> Result<u32, RegistryError> {
+        Ok(self
+            .lock_nodes()?
+            .iter()
+            .filter(|node| predicates.iter().all(|predicate| predicate.apply(node)))
+            .count() as u32)
+    }
+
+    fn get_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        Ok(self
+            .lock_nodes()?
+            .iter()
+            .find(|node| node.identity == identity)
+            .cloned())
+    }
+}
+
+impl RegistryWriter for MemoryRegistry {
+    fn add_node(&self, node: Node) -> Result<(), RegistryError> {
+        let mut registry = self.lock_nodes()?;
+        let mut nodes = registry.clone();
+        nodes.push(node);
+        Self::write_nodes(nodes, &mut registry)
+    }
+
+    fn update_node(&self, node: Node) -> Result<(), RegistryError> {
+        let mut registry = self.lock_nodes()?;
+        if !registry.iter().any(|n| n.identity == node.identity) {
+            return Err(RegistryError::InvalidStateError(
+                InvalidStateError::with_message(format!(
+                    "Node does not exist in the registry: {}",
+                    node.identity
+                )),
+            ));
+        }
+        let mut nodes = registry.clone();
+        nodes.retain(|existing_node| existing_node.identity != node.identity);
+        nodes.push(node);
+        Self::write_nodes(nodes, &mut registry)
+    }
+
+    fn delete_node(&self, identity: &str) -> Result<Option<Node>, RegistryError> {
+        let mut registry = self.lock_nodes()?;
+        Ok(registry
+            .iter()
+            .position(|node| node.identity == identity)
+            .map(|index| registry.remove(index)))
+    }
+}
+
+impl RwRegistry for MemoryRegistry {
+    fn clone_box(&self) -> Box<dyn RwRegistry> {
+        Box::new(self.clone())
+    }
+
+    fn clone_box_as_reader(&self) -> Box<dyn RegistryReader> {
+        Box::new(self.clone())
+    }
+
+    fn clone_box_as_writer(&self) -> Box<dyn RegistryWriter> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_node(identity: &str, endpoint: &str) -> Node {
+        Node::builder(identity)
+            .with_endpoint(endpoint)
+            .with_display_name(identity)
+            .with_key("abcd")
+            .with_metadata("company", "Bitwise IO")
+            .build()
+            .expect("Failed to build node")
+    }
+
+    /// Verifies that nodes can be added, read, updated and deleted, and that the changes are
+    /// seen by every clone of the registry.
+    #[test]
+    fn add_update_delete() {
+        let registry = MemoryRegistry::new();
+        let clone = registry.clone();
+
+        registry
+            .add_node(create_node("Node-123", "tcps://12.0.0.123:8431"))
+            .expect("Failed to add node");
+        registry
+            .add_node(create_node("Node-456", "tcps://12.0.0.123:8434"))
+            .expect("Failed to add node");
+
+        assert_eq!(clone.count_nodes(&[]).expect("Failed to count"), 2);
+        assert!(clone.has_node("Node-123").expect("Failed to check node"));
+
+        let updated = Node::builder("Node-123")
+            .with_endpoint("tcps://12.0.0.123:8431")
+            .with_display_name("Node 123")
+            .with_key("abcd")
+            .with_metadata("company", "Cargill")
+            .build()
+            .expect("Failed to build node");
+        registry
+            .update_node(updated.clone())
+            .expect("Failed to update node");
+        assert_eq!(
+            clone.get_node("Node-123").expect("Failed to get node"),
+            Some(updated.clone())
+        );
+
+        let nodes = clone
+            .list_nodes(&[MetadataPredicate::eq("company", "Cargill")])
+            .expect("Failed to list nodes")
+            .collect::<Vec<_>>();
+        assert_eq!(nodes, vec![updated.clone()]);
+
+        assert_eq!(
+            registry.delete_node("Node-123").expect("Failed to delete"),
+            Some(updated)
+        );
+        assert_eq!(
+            registry.delete_node("Node-123").expect("Failed to delete"),
+            None
+        );
+        assert_eq!(clone.count_nodes(&[]).expect("Failed to count"), 1);
+    }
+
+    /// Verifies that nodes that would make the registry invalid are rejected, and leave the
+    /// registry unchanged.
+    #[test]
+    fn reject_invalid_nodes() {
+        let registry = MemoryRegistry::new();
+        registry
+            .add_node(create_node("Node-123", "tcps://12.0.0.123:8431"))
+            .expect("Failed to add node");
+
+        // Duplicate identity
+        assert!(registry
+            .add_node(create_node("Node-123", "tcps://12.0.0.123:8434"))
+            .is_err());
+        // Duplicate endpoint
+        assert!(registry
+            .add_node(create_node("Node-456", "tcps://12.0.0.123:8431"))
+            .is_err());
+        // Missing node
+        assert!(registry
+            .update_node(create_node("Node-456", "tcps://12.0.0.123:8434"))
+            .is_err());
+
+        assert_eq!(registry.count_nodes(&[]).expect("Failed to count"), 1);
+    }
+}
@@ -24,24 +24,42 @@
 //! [`RegistryWriter`]: trait.RegistryWriter.html
 //! [`RwRegistry`]: trait.RwRegistry.html
 
+#[cfg(feature = "registry-auto-populate")]
+mod auto_populate;
 #[cfg(feature = "registry-client")]
 pub mod client;
 #[cfg(feature = "diesel")]
 mod diesel;
 mod error;
+#[cfg(feature = "registry-mdns")]
+mod mdns;
+#[cfg(feature = "memory")]
+mod memory;
+#[cfg(feature = "registry-signed-entries")]
+mod signature;
 mod unified;
 mod yaml;
 
 use std::collections::HashMap;
 use std::iter::ExactSizeIterator;
 
+#[cfg(feature = "registry-auto-populate")]
+pub use self::auto_populate::RegistryAutoPopulator;
 #[cfg(feature = "diesel")]
 pub use self::diesel::DieselRegistry;
 pub use error::{InvalidNodeError, RegistryError};
+#[cfg(feature = "registry-mdns")]
+pub use mdns::{
+    MdnsAdvertisement, MdnsRegistry, MdnsRegistryShutdownHandle, SPLINTER_SERVICE_TYPE,
+};
+#[cfg(feature = "memory")]
+pub use memory::MemoryRegistry;
+#[cfg(feature = "registry-signed-entries")]
+pub use signature::{node_deletion_signing_bytes, node_signing_bytes, NodeSignatureVerifier};
 pub use unified::UnifiedRegistry;
 pub use yaml::{LocalYamlRegistry, YamlNode};
 #[cfg(feature = "registry-remote")]
-pub use yaml::{RemoteYamlRegistry, RemoteYamlShutdownHandle};
+pub use yaml::{RemoteYamlRefreshHandle, RemoteYamlRegistry, RemoteYamlShutdownHandle};
 
 /// Native representation of a node in a registry.
 #[derive(Debug, Clone, PartialEq, Eq)]
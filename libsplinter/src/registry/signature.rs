@@ -0,0 +1,267 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Signed registry entries.
+//!
+//! A node entry can be signed with the private key of one of the entry's public keys, which
+//! shows that the entry was written by someone who controls the node. The signature covers the
+//! bytes returned by [`node_signing_bytes`]: the entry's identity, endpoints, display name, keys
+//! and metadata, encoded as JSON with the metadata sorted by key.
+//!
+//! Deleting an entry is signed the same way, over the bytes returned by
+//! [`node_deletion_signing_bytes`], so that a deletion signature cannot be mistaken for the
+//! signature of the entry itself.
+
+use std::collections::BTreeMap;
+
+use cylinder::{PublicKey, Signature, Verifier};
+use serde::Serialize;
+
+use crate::error::InvalidArgumentError;
+use crate::hex::parse_hex;
+
+use super::Node;
+
+#[derive(Serialize)]
+struct SigningNode<'a> {
+    identity: &'a str,
+    endpoints: &'a [String],
+    display_name: &'a str,
+    keys: &'a [String],
+    metadata: BTreeMap<&'a str, &'a str>,
+}
+
+/// Returns the bytes that are signed for a node entry.
+pub fn node_signing_bytes(node: &Node) -> Vec<u8> {
+    let signing_node = SigningNode {
+        identity: node.identity(),
+        endpoints: node.endpoints(),
+        display_name: node.display_name(),
+        keys: node.keys(),
+        metadata: node
+            .metadata()
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+            .collect(),
+    };
+
+    // Serializing strings, string slices and a map with string keys cannot fail
+    serde_json::to_vec(&signing_node).unwrap_or_default()
+}
+
+/// Returns the bytes that are signed to delete a node entry: the entry's signing bytes, prefixed
+/// by `delete:`.
+pub fn node_deletion_signing_bytes(node: &Node) -> Vec<u8> {
+    let mut bytes = b"delete:".to_vec();
+    bytes.extend(node_signing_bytes(node));
+    bytes
+}
+
+/// Verifies the signatures of node entries
+pub struct NodeSignatureVerifier {
+    verifier: Box<dyn Verifier>,
+}
+
+impl NodeSignatureVerifier {
+    pub fn new(verifier: Box<dyn Verifier>) -> Self {
+        Self { verifier }
+    }
+
+    /// Verifies that a node entry was signed by one of its keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The signed node entry
+    /// * `public_key` - The hex-encoded public key that signed the entry; it must be one of the
+    ///   entry's keys
+    /// * `signature` - The hex-encoded signature of the entry's signing bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the public key is not one of the entry's keys, if
+    /// the public key or signature is not valid hex, or if the signature does not match.
+    pub fn verify(
+        &self,
+        node: &Node,
+        public_key: &str,
+        signature: &str,
+    ) -> Result<(), InvalidArgumentError> {
+        self.verify_bytes(node, &node_signing_bytes(node), public_key, signature)
+    }
+
+    /// Verifies that the deletion of a node entry was signed by one of the entry's keys.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The node entry being deleted
+    /// * `public_key` - The hex-encoded public key that signed the deletion; it must be one of
+    ///   the entry's keys
+    /// * `signature` - The hex-encoded signature of the entry's deletion signing bytes
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the public key is not one of the entry's keys, if
+    /// the public key or signature is not valid hex, or if the signature does not match.
+    pub fn verify_deletion(
+        &self,
+        node: &Node,
+        public_key: &str,
+        signature: &str,
+    ) -> Result<(), InvalidArgumentError> {
+        self.verify_bytes(
+            node,
+            &node_deletion_signing_bytes(node),
+            public_key,
+            signature,
+        )
+    }
+
+    fn verify_bytes(
+        &self,
+        node: &Node,
+        signed_bytes: &[u8],
+        public_key: &str,
+        signature: &str,
+    ) -> Result<(), InvalidArgumentError> {
+        if !node.has_key(public_key) {
+            return Err(InvalidArgumentError::new(
+                "public_key",
+                format!("node {} was not signed by one of its keys", node.identity()),
+            ));
+        }
+
+        let public_key = parse_hex(public_key)
+            .map_err(|err| InvalidArgumentError::new("public_key", err.to_string()))?;
+        let signature = parse_hex(signature)
+            .map_err(|err| InvalidArgumentError::new("signature", err.to_string()))?;
+
+        match self.verifier.verify(
+            signed_bytes,
+            &Signature::new(signature),
+            &PublicKey::new(public_key),
+        ) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(InvalidArgumentError::new(
+                "signature",
+                format!("signature does not match node {}", node.identity()),
+            )),
+            Err(err) => Err(InvalidArgumentError::new("signature", err.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+
+    use crate::hex::to_hex;
+
+    /// Verify that a node entry signed by one of its keys is accepted, and that a modified entry,
+    /// or an entry signed by a key that is not one of its keys, is rejected.
+    #[test]
+    fn test_verify_node_signature() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = to_hex(
+            signer
+                .public_key()
+                .expect("Failed to get public key")
+                .as_slice(),
+        );
+
+        let node = Node::builder("node-1")
+            .with_endpoint("tcps://12.0.0.123:8044")
+            .with_display_name("Node 1")
+            .with_key(&public_key)
+            .with_metadata("company", "Bitwise IO")
+            .build()
+            .expect("Failed to build node");
+        let signature = to_hex(
+            signer
+                .sign(&node_signing_bytes(&node))
+                .expect("Failed to sign node")
+                .as_slice(),
+        );
+
+        let verifier = NodeSignatureVerifier::new(context.new_verifier());
+        assert!(verifier.verify(&node, &public_key, &signature).is_ok());
+
+        let modified_node = Node::builder("node-1")
+            .with_endpoint("tcps://12.0.0.123:8044")
+            .with_display_name("Node 1")
+            .with_key(&public_key)
+            .with_metadata("company", "Cargill")
+            .build()
+            .expect("Failed to build node");
+        assert!(verifier
+            .verify(&modified_node, &public_key, &signature)
+            .is_err());
+
+        let other_node = Node::builder("node-1")
+            .with_endpoint("tcps://12.0.0.123:8044")
+            .with_display_name("Node 1")
+            .with_key("0123")
+            .build()
+            .expect("Failed to build node");
+        assert!(verifier
+            .verify(&other_node, &public_key, &signature)
+            .is_err());
+    }
+
+    /// Verify that the deletion of a node entry must be signed over the deletion signing bytes,
+    /// so that the entry's own signature is not accepted in place of a deletion signature.
+    #[test]
+    fn test_verify_node_deletion_signature() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = to_hex(
+            signer
+                .public_key()
+                .expect("Failed to get public key")
+                .as_slice(),
+        );
+
+        let node = Node::builder("node-1")
+            .with_endpoint("tcps://12.0.0.123:8044")
+            .with_display_name("Node 1")
+            .with_key(&public_key)
+            .build()
+            .expect("Failed to build node");
+        let entry_signature = to_hex(
+            signer
+                .sign(&node_signing_bytes(&node))
+                .expect("Failed to sign node")
+                .as_slice(),
+        );
+        let deletion_signature = to_hex(
+            signer
+                .sign(&node_deletion_signing_bytes(&node))
+                .expect("Failed to sign node deletion")
+                .as_slice(),
+        );
+
+        let verifier = NodeSignatureVerifier::new(context.new_verifier());
+        assert!(verifier
+            .verify_deletion(&node, &public_key, &deletion_signature)
+            .is_ok());
+        assert!(verifier
+            .verify_deletion(&node, &public_key, &entry_signature)
+            .is_err());
+        assert!(verifier
+            .verify(&node, &public_key, &deletion_signature)
+            .is_err());
+    }
+}
@@ -29,7 +29,7 @@ use std::sync::{
 use std::thread;
 use std::time::{Duration, Instant};
 
-use openssl::hash::{hash, MessageDigest};
+use sha2::{Digest, Sha256};
 
 use crate::error::{InternalError, InvalidStateError};
 use crate::hex::to_hex;
@@ -41,6 +41,9 @@ use crate::threading::lifecycle::ShutdownHandle;
 
 use super::{LocalYamlRegistry, YamlNode};
 
+/// The default amount of time allowed for fetching the remote YAML file.
+const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// A remote, read-only registry.
 ///
 /// The `RemoteYamlRegistry` provides access to a remote registry YAML file over HTTP(S). The remote
@@ -67,15 +70,25 @@ use super::{LocalYamlRegistry, YamlNode};
 /// attempt to cache the remote file on each read after the given time since the last successful
 /// cache attempt has elapsed.
 ///
+/// Each attempt to fetch the remote file is abandoned if it does not complete within the
+/// `fetch_timeout` (see [`new_with_fetch_timeout`]), so an unresponsive server cannot block the
+/// registry indefinitely. Automatic refreshes fetch the file without holding the registry's
+/// internal lock, so reads are not blocked while a background refresh is in progress.
+///
 /// If a forced or automatic cache refresh fails for any reason, an error message will be logged
 /// and the previously cached registry values will continue to be used. The next time the registry
 /// is read, it will try again to refresh the cache.
 ///
+/// Both refresh periods may be changed while the registry is running with a
+/// [`RemoteYamlRefreshHandle`].
+///
 /// [`Node`]: struct.Node.html
 /// [`RegistryReader`]: trait.RegistryReader.html
 /// [`constructor`]: struct.RemoteYamlRegistry.html#method.new
+/// [`new_with_fetch_timeout`]: struct.RemoteYamlRegistry.html#method.new_with_fetch_timeout
 pub struct RemoteYamlRegistry {
     internal: Arc<Mutex<Internal>>,
+    automatic_refresh_period: Arc<Mutex<Option<Duration>>>,
     shutdown_handle: Option<RemoteYamlShutdownHandle>,
 }
 
@@ -92,57 +105,83 @@ impl RemoteYamlRegistry {
     /// * `forced_refresh_period` - Amount of time since the last successful cache refresh before
     ///   attempting to refresh on every read operation. If `None`, forced refreshes will be
     ///   disabled.
+    ///
+    /// Fetches of the remote YAML file time out after 10 seconds.
     pub fn new(
         url: &str,
         cache_dir: &str,
         automatic_refresh_period: Option<Duration>,
         forced_refresh_period: Option<Duration>,
+    ) -> Result<Self, RegistryError> {
+        Self::new_with_fetch_timeout(
+            url,
+            cache_dir,
+            automatic_refresh_period,
+            forced_refresh_period,
+            DEFAULT_FETCH_TIMEOUT,
+        )
+    }
+
+    /// Construct a new `RemoteYamlRegistry` that abandons each fetch of the remote YAML file after
+    /// the given `fetch_timeout`.
+    ///
+    /// The initial fetch is attempted before this returns, so `fetch_timeout` also bounds how long
+    /// construction may block on an unresponsive server. See [`new`] for the other arguments.
+    ///
+    /// [`new`]: struct.RemoteYamlRegistry.html#method.new
+    pub fn new_with_fetch_timeout(
+        url: &str,
+        cache_dir: &str,
+        automatic_refresh_period: Option<Duration>,
+        forced_refresh_period: Option<Duration>,
+        fetch_timeout: Duration,
     ) -> Result<Self, RegistryError> {
         let internal = Arc::new(Mutex::new(Internal::new(
             url,
             cache_dir,
             forced_refresh_period,
+            fetch_timeout,
         )?));
 
-        let (running, join_handle) = {
-            if let Some(refresh_period) = automatic_refresh_period {
-                let running = Arc::new(AtomicBool::new(true));
-
-                let thread_internal = internal.clone();
-                let thread_url = url.to_string();
-                let thread_running = running.clone();
-                let join_handle = thread::Builder::new()
-                    .name(format!("Remote Registry Automatic Refresh: {}", url))
-                    .spawn(move || {
-                        automatic_refresh_loop(
-                            refresh_period,
-                            thread_internal,
-                            &thread_url,
-                            thread_running,
-                        )
-                    })
-                    .map_err(|err| {
-                        RegistryError::InternalError(InternalError::from_source_with_message(
-                            Box::new(err),
-                            format!(
-                                "Failed to spawn automatic refresh thread for remote registry '{}'",
-                                url
-                            ),
-                        ))
-                    })?;
-                (Some(running), Some(join_handle))
-            } else {
-                (None, None)
-            }
-        };
+        let automatic_refresh_period = Arc::new(Mutex::new(automatic_refresh_period));
+
+        // The automatic refresh thread is started even if automatic refreshes are disabled, so
+        // that they may be enabled later with a `RemoteYamlRefreshHandle`
+        let running = Arc::new(AtomicBool::new(true));
+
+        let thread_internal = internal.clone();
+        let thread_refresh_period = automatic_refresh_period.clone();
+        let thread_url = url.to_string();
+        let thread_running = running.clone();
+        let join_handle = thread::Builder::new()
+            .name(format!("Remote Registry Automatic Refresh: {}", url))
+            .spawn(move || {
+                automatic_refresh_loop(
+                    thread_refresh_period,
+                    thread_internal,
+                    &thread_url,
+                    fetch_timeout,
+                    thread_running,
+                )
+            })
+            .map_err(|err| {
+                RegistryError::InternalError(InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!(
+                        "Failed to spawn automatic refresh thread for remote registry '{}'",
+                        url
+                    ),
+                ))
+            })?;
 
         let shutdown_handle = RemoteYamlShutdownHandle {
-            running,
-            join_handle,
+            running: Some(running),
+            join_handle: Some(join_handle),
         };
 
         Ok(Self {
             internal,
+            automatic_refresh_period,
             shutdown_handle: Some(shutdown_handle),
         })
     }
@@ -151,6 +190,14 @@ impl RemoteYamlRegistry {
         self.shutdown_handle.take()
     }
 
+    /// Returns a handle for changing the registry's refresh periods while it is running.
+    pub fn refresh_handle(&self) -> RemoteYamlRefreshHandle {
+        RemoteYamlRefreshHandle {
+            internal: self.internal.clone(),
+            automatic_refresh_period: self.automatic_refresh_period.clone(),
+        }
+    }
+
     /// Acquire the lock for the internal cache and get the nodes from it.
     fn get_nodes(&self) -> Result<Vec<Node>, RegistryError> {
         self.internal
@@ -194,6 +241,7 @@ impl RegistryReader for RemoteYamlRegistry {
 struct Internal {
     url: String,
     cache: LocalYamlRegistry,
+    fetch_timeout: Duration,
     last_refresh_successful: bool,
     forced_refresh_period: Option<Duration>,
     next_forced_refresh: Option<Instant>,
@@ -205,6 +253,7 @@ impl Internal {
         url: &str,
         cache_dir: &str,
         forced_refresh_period: Option<Duration>,
+        fetch_timeout: Duration,
     ) -> Result<Self, RegistryError> {
         let url = url.to_string();
 
@@ -213,6 +262,7 @@ impl Internal {
         let mut internal = Self {
             url,
             cache,
+            fetch_timeout,
             last_refresh_successful: false,
             forced_refresh_period,
             next_forced_refresh: None,
@@ -232,7 +282,16 @@ impl Internal {
 
     /// Attempt to refresh the internal cache and update state accordingly.
     fn refresh_cache(&mut self) -> Result<(), RegistryError> {
-        fetch_nodes_from_remote(&self.url)
+        let fetch_result = fetch_nodes_from_remote(&self.url, self.fetch_timeout);
+        self.update_cache(fetch_result)
+    }
+
+    /// Update the internal cache and state with the result of a fetch of the remote file.
+    fn update_cache(
+        &mut self,
+        fetch_result: Result<Vec<Node>, RegistryError>,
+    ) -> Result<(), RegistryError> {
+        fetch_result
             .and_then(|nodes| self.cache.write_nodes(nodes))
             .map_err(|err| {
                 self.last_refresh_successful = false;
@@ -258,6 +317,31 @@ impl Internal {
             })
     }
 
+    /// Change the forced refresh period. The next forced refresh is scheduled from now, if the
+    /// last refresh was successful.
+    fn set_forced_refresh_period(
+        &mut self,
+        forced_refresh_period: Option<Duration>,
+    ) -> Result<(), RegistryError> {
+        self.forced_refresh_period = forced_refresh_period;
+        self.next_forced_refresh = if self.last_refresh_successful {
+            forced_refresh_period
+                .map(|duration| {
+                    Instant::now().checked_add(duration).ok_or_else(|| {
+                        RegistryError::InternalError(InternalError::with_message(
+                            "Forced refresh time could not be determined; \
+                             forced_refresh_period may be too large"
+                                .into(),
+                        ))
+                    })
+                })
+                .transpose()?
+        } else {
+            None
+        };
+        Ok(())
+    }
+
     /// Attempt to refresh the internal cache if necessary and return the cache's contents.
     fn get_nodes(&mut self) -> Result<Vec<Node>, RegistryError> {
         // If the last attempt to refresh the cache wasn't successful, try again
@@ -295,14 +379,7 @@ impl Internal {
 // Derive the filename for the cache from a hash of the URL; this makes the location deterministic,
 // which allows the local cache to be used across restarts.
 fn compute_cache_filename(url: &str, cache_dir: &str) -> Result<String, RegistryError> {
-    let hash = hash(MessageDigest::sha256(), url.as_bytes())
-        .map(|digest| to_hex(&*digest))
-        .map_err(|err| {
-            RegistryError::InternalError(InternalError::from_source_with_message(
-                Box::new(err),
-                "Failed to hash URL for cache file".into(),
-            ))
-        })?;
+    let hash = to_hex(&Sha256::digest(url.as_bytes()));
 
     let filename = format!("remote_registry_{}.yaml", hash);
     Ok(Path::new(cache_dir)
@@ -312,9 +389,13 @@ fn compute_cache_filename(url: &str, cache_dir: &str) -> Result<String, Registry
         .to_string())
 }
 
-/// Fetch, parse, and validate the YAML registry file at the given URL.
-fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
-    let bytes = reqwest::blocking::get(url)
+/// Fetch, parse, and validate the YAML registry file at the given URL, giving up if the request
+/// does not complete within the `timeout`.
+fn fetch_nodes_from_remote(url: &str, timeout: Duration) -> Result<Vec<Node>, RegistryError> {
+    let bytes = reqwest::blocking::Client::builder()
+        .timeout(timeout)
+        .build()
+        .and_then(|client| client.get(url).send())
         .and_then(|response| response.error_for_status())
         .map_err(|err| {
             RegistryError::InternalError(InternalError::from_source_with_message(
@@ -354,24 +435,52 @@ fn fetch_nodes_from_remote(url: &str) -> Result<Vec<Node>, RegistryError> {
 }
 
 /// Infinitely loop, attempting to refresh the `internal` cache every `refresh_period`, until no
-/// longer `running`.
+/// longer `running`. No refreshes are attempted while the `refresh_period` is `None`.
+///
+/// The remote file is fetched before the `internal` lock is acquired, so reads of the registry are
+/// only blocked while the cache is written.
 fn automatic_refresh_loop(
-    refresh_period: Duration,
+    refresh_period: Arc<Mutex<Option<Duration>>>,
     internal: Arc<Mutex<Internal>>,
     url: &str,
+    fetch_timeout: Duration,
     running: Arc<AtomicBool>,
 ) {
+    let mut last_refresh = Instant::now();
     loop {
-        // Wait the `refresh_period`, checking for shutdown every second
-        let refresh_time = Instant::now() + refresh_period;
-        while Instant::now() < refresh_time {
+        // Wait the `refresh_period`, checking for shutdown and for a change to the period every
+        // second
+        loop {
             if !running.load(Ordering::SeqCst) {
                 return;
             }
-            if let Some(time_left) = refresh_time.checked_duration_since(Instant::now()) {
-                thread::sleep(std::cmp::min(time_left, Duration::from_secs(1)));
+
+            let period = match refresh_period.lock() {
+                Ok(period) => *period,
+                Err(_) => {
+                    warn!("Refresh period lock poisoned for remote registry '{}'", url);
+                    return;
+                }
+            };
+
+            match period.and_then(|period| last_refresh.checked_add(period)) {
+                Some(refresh_time) => match refresh_time.checked_duration_since(Instant::now()) {
+                    Some(time_left) if time_left > Duration::from_secs(0) => {
+                        thread::sleep(std::cmp::min(time_left, Duration::from_secs(1)))
+                    }
+                    _ => break,
+                },
+                None => {
+                    // Automatic refreshes are disabled; if they are enabled later, wait a full
+                    // period before the first refresh
+                    last_refresh = Instant::now();
+                    thread::sleep(Duration::from_secs(1));
+                }
             }
         }
+        last_refresh = Instant::now();
+
+        let fetch_result = fetch_nodes_from_remote(url, fetch_timeout);
 
         let mut internal = match internal.lock() {
             Ok(internal) => internal,
@@ -383,7 +492,7 @@ fn automatic_refresh_loop(
 
         let previous_refresh_successful = internal.last_refresh_successful;
 
-        match internal.refresh_cache() {
+        match internal.update_cache(fetch_result) {
             Ok(_) => debug!("Automatic refresh of remote registry '{}' successful", url),
             Err(err) => {
                 // If the previous attempt was successful, log with WARN because
@@ -403,6 +512,46 @@ fn automatic_refresh_loop(
     }
 }
 
+/// Handle for changing the refresh periods of a running `RemoteYamlRegistry`.
+///
+/// The handle is cheap to clone; all clones change the same registry.
+#[derive(Clone)]
+pub struct RemoteYamlRefreshHandle {
+    internal: Arc<Mutex<Internal>>,
+    automatic_refresh_period: Arc<Mutex<Option<Duration>>>,
+}
+
+impl RemoteYamlRefreshHandle {
+    /// Change the amount of time between automatic refreshes; `None` disables them.
+    pub fn set_automatic_refresh_period(
+        &self,
+        automatic_refresh_period: Option<Duration>,
+    ) -> Result<(), RegistryError> {
+        *self.automatic_refresh_period.lock().map_err(|_| {
+            RegistryError::InternalError(InternalError::with_message(
+                "Refresh period lock poisoned".into(),
+            ))
+        })? = automatic_refresh_period;
+        Ok(())
+    }
+
+    /// Change the amount of time since the last successful refresh before a refresh is forced on
+    /// read; `None` disables forced refreshes.
+    pub fn set_forced_refresh_period(
+        &self,
+        forced_refresh_period: Option<Duration>,
+    ) -> Result<(), RegistryError> {
+        self.internal
+            .lock()
+            .map_err(|_| {
+                RegistryError::InternalError(InternalError::with_message(
+                    "Internal lock poisoned".into(),
+                ))
+            })?
+            .set_forced_refresh_period(forced_refresh_period)
+    }
+}
+
 /// Handle for signaling the `RemoteYamlRegistry` to shutdown.
 pub struct RemoteYamlShutdownHandle {
     running: Option<Arc<AtomicBool>>,
@@ -944,6 +1093,48 @@ mod tests {
         test_config.shutdown();
     }
 
+    /// Verifies that when the remote server accepts the connection but never responds, the initial
+    /// fetch is abandoned after the fetch timeout and the registry starts up with an empty cache.
+    #[test]
+    fn fetch_timeout() {
+        let temp_dir = Builder::new()
+            .prefix("fetch_timeout")
+            .tempdir()
+            .expect("Failed to create temp dir");
+        // Connections are queued by the OS but never accepted, so requests never get a response
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Failed to bind listener");
+        let url = format!(
+            "http://{}/registry.yaml",
+            listener.local_addr().expect("Failed to get address")
+        );
+
+        let start = Instant::now();
+        let mut remote_registry = RemoteYamlRegistry::new_with_fetch_timeout(
+            &url,
+            temp_dir.path().to_str().expect("Failed to get path"),
+            None,
+            None,
+            Duration::from_millis(500),
+        )
+        .expect("Failed to create registry");
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        assert_eq!(
+            remote_registry
+                .count_nodes(&[])
+                .expect("Failed to count nodes"),
+            0
+        );
+
+        let mut shutdown_handle = remote_registry
+            .take_shutdown_handle()
+            .expect("Unable to get shutdown handle");
+        shutdown_handle.signal_shutdown();
+        shutdown_handle
+            .wait_for_shutdown()
+            .expect("Unable to shutdown remote registry");
+    }
+
     /// Verifies that when auto refresh is turned off, the auto refresh thread is not running.
     #[test]
     fn auto_refresh_disabled() {
@@ -18,13 +18,19 @@ use std::thread;
 use actix_web::{middleware, App, HttpServer};
 use futures::Future;
 
+#[cfg(feature = "audit")]
+use crate::audit::store::AuditStore;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::{
     routes::AuthorizationResourceProvider, AuthorizationHandler, PermissionMap,
 };
+#[cfg(feature = "rest-api-client-cert-auth")]
+use crate::rest_api::auth::client_cert::{record_client_certificate, ClientCertAuth};
+#[cfg(feature = "rest-api-proxy-auth")]
+use crate::rest_api::auth::proxy::ProxyAuth;
 use crate::rest_api::auth::{actix::Authorization, identity::IdentityProvider};
 #[cfg(feature = "rest-api-cors")]
-use crate::rest_api::cors::Cors;
+use crate::rest_api::cors::{Cors, CorsAllowList};
 use crate::rest_api::{BindConfig, RestApiServerError};
 
 use super::Resource;
@@ -53,10 +59,16 @@ pub struct RestApi {
     pub(super) resources: Vec<Resource>,
     pub(super) bind: BindConfig,
     #[cfg(feature = "rest-api-cors")]
-    pub(super) allow_list: Option<Vec<String>>,
+    pub(super) allow_list: Option<CorsAllowList>,
     pub(super) identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     pub(super) authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub(super) proxy_auth: Option<ProxyAuth>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub(super) client_cert_auth: Option<ClientCertAuth>,
+    #[cfg(feature = "audit")]
+    pub(super) audit_store: Option<Box<dyn AuditStore>>,
 }
 
 impl RestApi {
@@ -86,10 +98,18 @@ impl RestApi {
             #[cfg(feature = "authorization")]
             self.authorization_handlers.to_owned(),
         );
+        #[cfg(feature = "rest-api-proxy-auth")]
+        let authorization = authorization.with_proxy_auth(self.proxy_auth);
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        let client_cert_auth_enabled = self.client_cert_auth.is_some();
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        let authorization = authorization.with_client_cert_auth(self.client_cert_auth);
+        #[cfg(feature = "audit")]
+        let authorization = authorization.with_audit_store(self.audit_store);
 
         #[cfg(feature = "rest-api-cors")]
         let cors = match &allow_list {
-            Some(list) => Cors::new(list.to_vec()),
+            Some(list) => Cors::with_allow_list(list.clone()),
             None => Cors::new_allow_any(),
         };
 
@@ -99,13 +119,14 @@ impl RestApi {
                 bind,
                 cert_path,
                 key_path,
+                #[cfg(feature = "rest-api-client-cert-auth")]
+                client_ca_path,
             } => {
-                let mut acceptor =
-                    openssl::ssl::SslAcceptor::mozilla_modern(openssl::ssl::SslMethod::tls())?;
-                acceptor.set_private_key_file(key_path, openssl::ssl::SslFiletype::PEM)?;
-                acceptor.set_certificate_chain_file(&cert_path)?;
-                acceptor.check_private_key()?;
-
+                #[cfg(feature = "rest-api-client-cert-auth")]
+                let acceptor =
+                    build_tls_acceptor(&cert_path, &key_path, client_ca_path.as_deref())?;
+                #[cfg(not(feature = "rest-api-client-cert-auth"))]
+                let acceptor = build_tls_acceptor(&cert_path, &key_path, None)?;
                 (bind, Some(acceptor))
             }
             BindConfig::Http(bind) => (bind, None),
@@ -164,18 +185,38 @@ impl RestApi {
                     app
                 });
 
+                // Client certificates are read when the connection is established, so that the
+                // authorization middleware can identify the client by its certificate
+                #[cfg(feature = "rest-api-client-cert-auth")]
+                let server = if client_cert_auth_enabled {
+                    server.on_connect(record_client_certificate)
+                } else {
+                    server
+                };
+
                 #[cfg(feature = "https-bind")]
                 let (bind_url, opt_acceptor) = bind_info;
                 #[cfg(not(feature = "https-bind"))]
                 let bind_url = bind_info;
 
-                #[cfg(feature = "https-bind")]
+                #[cfg(all(feature = "https-bind", feature = "tls-openssl"))]
                 let server = if let Some(acceptor) = opt_acceptor {
                     server.bind_ssl(&bind_url, acceptor)
                 } else {
                     server.bind(&bind_url)
                 };
 
+                #[cfg(all(
+                    feature = "https-bind",
+                    feature = "tls-rustls",
+                    not(feature = "tls-openssl")
+                ))]
+                let server = if let Some(config) = opt_acceptor {
+                    server.bind_rustls(&bind_url, config)
+                } else {
+                    server.bind(&bind_url)
+                };
+
                 #[cfg(not(feature = "https-bind"))]
                 let server = server.bind(&bind_url);
 
@@ -259,7 +300,7 @@ impl RestApi {
 
         #[cfg(feature = "rest-api-cors")]
         let cors = match &allow_list {
-            Some(list) => Cors::new(list.to_vec()),
+            Some(list) => Cors::with_allow_list(list.clone()),
             None => Cors::new_allow_any(),
         };
 
@@ -345,3 +386,92 @@ impl RestApi {
         ))
     }
 }
+
+/// Builds the OpenSSL acceptor used to serve HTTPS with the given PEM-encoded certificate chain
+/// and private key. If a client CA is provided, for client certificate authentication, clients
+/// must present a certificate signed by it.
+#[cfg(all(feature = "https-bind", feature = "tls-openssl"))]
+fn build_tls_acceptor(
+    cert_path: &str,
+    key_path: &str,
+    client_ca_path: Option<&str>,
+) -> Result<openssl::ssl::SslAcceptorBuilder, RestApiServerError> {
+    let mut acceptor = openssl::ssl::SslAcceptor::mozilla_modern(openssl::ssl::SslMethod::tls())?;
+    acceptor.set_private_key_file(key_path, openssl::ssl::SslFiletype::PEM)?;
+    acceptor.set_certificate_chain_file(cert_path)?;
+    acceptor.check_private_key()?;
+
+    if let Some(client_ca_path) = client_ca_path {
+        acceptor.set_ca_file(client_ca_path)?;
+        acceptor.set_verify(
+            openssl::ssl::SslVerifyMode::PEER | openssl::ssl::SslVerifyMode::FAIL_IF_NO_PEER_CERT,
+        );
+    }
+
+    Ok(acceptor)
+}
+
+/// Builds the rustls configuration used to serve HTTPS with the given PEM-encoded certificate
+/// chain and PKCS#8 or RSA private key. If a client CA is provided, clients must present a
+/// certificate signed by it.
+#[cfg(all(
+    feature = "https-bind",
+    feature = "tls-rustls",
+    not(feature = "tls-openssl")
+))]
+fn build_tls_acceptor(
+    cert_path: &str,
+    key_path: &str,
+    client_ca_path: Option<&str>,
+) -> Result<rustls_0_15::ServerConfig, RestApiServerError> {
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use rustls_0_15::internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
+
+    let cert_chain = certs(&mut BufReader::new(File::open(cert_path)?)).map_err(|_| {
+        RestApiServerError::StartUpError(format!("Unable to read certificates from {}", cert_path))
+    })?;
+
+    let read_key_error = |_| {
+        RestApiServerError::StartUpError(format!("Unable to read private key from {}", key_path))
+    };
+    let mut keys =
+        pkcs8_private_keys(&mut BufReader::new(File::open(key_path)?)).map_err(read_key_error)?;
+    if keys.is_empty() {
+        keys =
+            rsa_private_keys(&mut BufReader::new(File::open(key_path)?)).map_err(read_key_error)?;
+    }
+    let key = keys.into_iter().next().ok_or_else(|| {
+        RestApiServerError::StartUpError(format!("No private key found in {}", key_path))
+    })?;
+
+    let client_cert_verifier = match client_ca_path {
+        Some(client_ca_path) => {
+            let mut client_roots = rustls_0_15::RootCertStore::empty();
+            let (added, _) = client_roots
+                .add_pem_file(&mut BufReader::new(File::open(client_ca_path)?))
+                .map_err(|_| {
+                    RestApiServerError::StartUpError(format!(
+                        "Unable to read client CA certificates from {}",
+                        client_ca_path
+                    ))
+                })?;
+            if added == 0 {
+                return Err(RestApiServerError::StartUpError(format!(
+                    "No client CA certificates found in {}",
+                    client_ca_path
+                )));
+            }
+            rustls_0_15::AllowAnyAuthenticatedClient::new(client_roots)
+        }
+        None => rustls_0_15::NoClientAuth::new(),
+    };
+
+    let mut config = rustls_0_15::ServerConfig::new(client_cert_verifier);
+    config.set_single_cert(cert_chain, key).map_err(|err| {
+        RestApiServerError::StartUpError(format!("Invalid TLS certificate: {}", err))
+    })?;
+
+    Ok(config)
+}
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "rest-api-proxy-auth")]
+use std::net::IpAddr;
+
 use actix_web::HttpRequest;
 #[cfg(feature = "cylinder-jwt")]
 use cylinder::Verifier;
@@ -22,6 +25,8 @@ use crate::biome::credentials::rest_api::BiomeCredentialsRestResourceProvider;
 use crate::biome::OAuthUserSessionStore;
 #[cfg(all(feature = "oauth", feature = "biome-profile"))]
 use crate::biome::UserProfileStore;
+#[cfg(feature = "rest-api-client-cert-auth")]
+use crate::rest_api::auth::client_cert::CertificateMatcher;
 #[cfg(feature = "oauth")]
 use crate::rest_api::OAuthConfig;
 use crate::rest_api::{auth::identity::IdentityProvider, RequestError};
@@ -54,6 +59,22 @@ pub enum AuthConfig {
         #[cfg(feature = "biome-profile")]
         user_profile_store: Box<dyn UserProfileStore>,
     },
+    /// Authentication by an upstream reverse proxy, such as an SSO gateway, that passes the
+    /// authenticated user in a request header
+    #[cfg(feature = "rest-api-proxy-auth")]
+    Proxy {
+        /// The name of the header that contains the authenticated user
+        header: String,
+        /// The addresses of the proxies whose header is trusted; at least one must be given
+        trusted_addresses: Vec<IpAddr>,
+    },
+    /// Authentication by TLS client certificates; the REST API must require client certificates,
+    /// and no other credentials are accepted
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    ClientCertificate {
+        /// Pairs of certificate matchers and the user that a matching certificate identifies
+        identities: Vec<(CertificateMatcher, String)>,
+    },
     /// A custom authentication method
     Custom {
         /// REST API resources that would allow a client to receive some authentication credentials
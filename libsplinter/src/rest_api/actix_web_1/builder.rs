@@ -17,13 +17,21 @@ use std::sync::Arc;
 #[cfg(feature = "cylinder-jwt")]
 use std::sync::Mutex;
 
+#[cfg(feature = "audit")]
+use crate::audit::store::AuditStore;
 use crate::error::InvalidStateError;
 #[cfg(feature = "oauth")]
 use crate::oauth::{GithubOAuthClientBuilder, OpenIdOAuthClientBuilder};
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::AuthorizationHandler;
+#[cfg(feature = "rest-api-client-cert-auth")]
+use crate::rest_api::auth::client_cert::ClientCertAuth;
 #[cfg(feature = "cylinder-jwt")]
 use crate::rest_api::auth::identity::cylinder::CylinderKeyIdentityProvider;
+#[cfg(feature = "rest-api-proxy-auth")]
+use crate::rest_api::auth::proxy::ProxyAuth;
+#[cfg(feature = "rest-api-cors")]
+use crate::rest_api::cors::CorsAllowList;
 #[cfg(feature = "oauth")]
 use crate::rest_api::{
     auth::identity::oauth::OAuthUserIdentityProvider, OAuthConfig, OAuthResourceProvider,
@@ -31,6 +39,8 @@ use crate::rest_api::{
 use crate::rest_api::{auth::identity::IdentityProvider, BindConfig, RestApiServerError};
 
 use super::AuthConfig;
+#[cfg(feature = "rest-api-openapi")]
+use super::OpenApiConfig;
 #[cfg(any(feature = "biome-credentials", feature = "oauth"))]
 use super::RestResourceProvider;
 use super::{Resource, RestApi};
@@ -41,10 +51,14 @@ pub struct RestApiBuilder {
     resources: Vec<Resource>,
     bind: Option<BindConfig>,
     #[cfg(feature = "rest-api-cors")]
-    allow_list: Option<Vec<String>>,
+    allow_list: Option<CorsAllowList>,
     auth_configs: Vec<AuthConfig>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "audit")]
+    audit_store: Option<Box<dyn AuditStore>>,
+    #[cfg(feature = "rest-api-openapi")]
+    openapi: Option<OpenApiConfig>,
 }
 
 impl RestApiBuilder {
@@ -76,14 +90,22 @@ impl RestApiBuilder {
 
     #[cfg(feature = "rest-api-cors")]
     pub fn with_allow_list(mut self, values: Vec<String>) -> Self {
-        self.allow_list = Some(values);
+        self.allow_list = Some(CorsAllowList::new(values));
+        self
+    }
+
+    /// Use the given allow list for CORS, so that the allowed domains can be updated while the
+    /// REST API is running.
+    #[cfg(feature = "rest-api-cors")]
+    pub fn with_cors_allow_list(mut self, allow_list: CorsAllowList) -> Self {
+        self.allow_list = Some(allow_list);
         self
     }
 
     #[deprecated(since = "0.7.0", note = "please use `with_allow_list` instead")]
     #[cfg(feature = "rest-api-cors")]
     pub fn with_white_list(mut self, values: Vec<String>) -> Self {
-        self.allow_list = Some(values);
+        self.allow_list = Some(CorsAllowList::new(values));
         self
     }
 
@@ -110,6 +132,22 @@ impl RestApiBuilder {
         self
     }
 
+    /// Records requests that are rejected because the client could not be authorized in the given
+    /// audit log.
+    #[cfg(feature = "audit")]
+    pub fn with_audit_store(mut self, audit_store: Box<dyn AuditStore>) -> Self {
+        self.audit_store = Some(audit_store);
+        self
+    }
+
+    /// Serves an OpenAPI document that describes the REST API, generated from its resources when
+    /// it is built.
+    #[cfg(feature = "rest-api-openapi")]
+    pub fn with_openapi(mut self, openapi: OpenApiConfig) -> Self {
+        self.openapi = Some(openapi);
+        self
+    }
+
     // Allowing unused_mut because self must be mutable if feature `auth` is enabled
     #[allow(unused_mut)]
     pub fn build(mut self) -> Result<RestApi, RestApiServerError> {
@@ -117,6 +155,11 @@ impl RestApiBuilder {
             .bind
             .ok_or_else(|| RestApiServerError::MissingField("bind".to_string()))?;
 
+        #[cfg(feature = "rest-api-proxy-auth")]
+        let mut proxy_auth = None;
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        let mut client_cert_auth = None;
+
         let identity_providers = {
             if self.auth_configs.is_empty() {
                 return Err(RestApiServerError::InvalidStateError(
@@ -240,6 +283,64 @@ impl RestApiBuilder {
                         );
                         oauth_configured = true;
                     }
+                    #[cfg(feature = "rest-api-proxy-auth")]
+                    AuthConfig::Proxy {
+                        header,
+                        trusted_addresses,
+                    } => {
+                        if proxy_auth.is_some() {
+                            return Err(RestApiServerError::InvalidStateError(
+                                InvalidStateError::with_message(
+                                    "Only one reverse proxy can be configured".to_string(),
+                                ),
+                            ));
+                        }
+
+                        // Any client can set the header, so it must only be trusted from proxies
+                        // that were explicitly listed
+                        if trusted_addresses.is_empty() {
+                            return Err(RestApiServerError::InvalidStateError(
+                                InvalidStateError::with_message(
+                                    "Reverse proxy authentication requires at least one trusted \
+                                     proxy address"
+                                        .to_string(),
+                                ),
+                            ));
+                        }
+
+                        proxy_auth = Some(ProxyAuth::new(header, trusted_addresses));
+                    }
+                    #[cfg(feature = "rest-api-client-cert-auth")]
+                    AuthConfig::ClientCertificate { identities } => {
+                        if client_cert_auth.is_some() {
+                            return Err(RestApiServerError::InvalidStateError(
+                                InvalidStateError::with_message(
+                                    "Client certificate authentication can only be configured \
+                                     once"
+                                        .to_string(),
+                                ),
+                            ));
+                        }
+
+                        // The certificate is only trustworthy if the TLS layer verified it
+                        if !matches!(
+                            bind,
+                            BindConfig::Https {
+                                client_ca_path: Some(_),
+                                ..
+                            }
+                        ) {
+                            return Err(RestApiServerError::InvalidStateError(
+                                InvalidStateError::with_message(
+                                    "Client certificate authentication requires an HTTPS bind \
+                                     with a client CA"
+                                        .to_string(),
+                                ),
+                            ));
+                        }
+
+                        client_cert_auth = Some(ClientCertAuth::new(identities));
+                    }
                     AuthConfig::Custom {
                         mut resources,
                         identity_provider,
@@ -253,6 +354,30 @@ impl RestApiBuilder {
             identity_providers
         };
 
+        #[cfg(all(feature = "rest-api-client-cert-auth", feature = "rest-api-proxy-auth"))]
+        if client_cert_auth.is_some() && proxy_auth.is_some() {
+            return Err(RestApiServerError::InvalidStateError(
+                InvalidStateError::with_message(
+                    "Client certificate authentication cannot be combined with reverse proxy \
+                     authentication"
+                        .to_string(),
+                ),
+            ));
+        }
+
+        // The OpenAPI document is generated last, so that it describes all of the resources,
+        // including those added by the auth configs
+        #[cfg(feature = "rest-api-openapi")]
+        if let Some(openapi) = self.openapi.take() {
+            let mut openapi_resources = openapi.into_resources(&self.resources).map_err(|err| {
+                RestApiServerError::InvalidStateError(InvalidStateError::with_message(format!(
+                    "Unable to generate OpenAPI document: {}",
+                    err
+                )))
+            })?;
+            self.resources.append(&mut openapi_resources);
+        }
+
         Ok(RestApi {
             bind,
             resources: self.resources,
@@ -261,6 +386,12 @@ impl RestApiBuilder {
             identity_providers,
             #[cfg(feature = "authorization")]
             authorization_handlers: self.authorization_handlers,
+            #[cfg(feature = "rest-api-proxy-auth")]
+            proxy_auth,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            client_cert_auth,
+            #[cfg(feature = "audit")]
+            audit_store: self.audit_store,
         })
     }
 }
@@ -352,6 +483,12 @@ mod test {
                 identity_providers: vec![],
                 #[cfg(feature = "authorization")]
                 authorization_handlers: vec![],
+                #[cfg(feature = "rest-api-proxy-auth")]
+                proxy_auth: None,
+                #[cfg(feature = "rest-api-client-cert-auth")]
+                client_cert_auth: None,
+                #[cfg(feature = "audit")]
+                audit_store: None,
             })
         }
     }
@@ -0,0 +1,353 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Degraded mode: keeping read-only endpoints available while the database is down.
+//!
+//! Resources wrapped by [`DegradedMode`] remember their last successful `GET` responses. When a
+//! request fails with a server error and the [`DatabaseHealthCheck`] reports that the database
+//! is unavailable, a `GET` request is answered with the remembered response, marked as stale with
+//! the `Age` and `Warning` headers. Requests that cannot be answered from the cache, including
+//! all writes, are answered with `503 Service Unavailable` and a `Retry-After` header instead of
+//! `500 Internal Server Error`.
+//!
+//! The health check is only consulted after a request fails, so it does not slow down requests
+//! while the database is available.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use actix_web::{
+    dev::{Body, ResponseBody},
+    http::{header, HeaderValue, StatusCode},
+    web, Error as ActixError, HttpRequest, HttpResponse,
+};
+use futures::{future::IntoFuture, Future};
+
+use crate::rest_api::ErrorResponse;
+
+use super::{HandlerFunction, Method, Resource};
+
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_MAX_ENTRIES: usize = 1000;
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// Reports whether the database behind the REST API can be reached
+pub trait DatabaseHealthCheck: Send + Sync {
+    /// Returns `true` if the database is available.
+    ///
+    /// This may block, but should return within a few seconds.
+    fn is_available(&self) -> bool;
+}
+
+/// Serves cached responses for reads and rejects writes while the database is unavailable.
+///
+/// ```ignore
+/// let degraded_mode = DegradedMode::new(Arc::new(health_check))
+///     .with_retry_after(Duration::from_secs(10));
+///
+/// let resources = degraded_mode.wrap_resources(status_provider.resources());
+/// ```
+#[derive(Clone)]
+pub struct DegradedMode {
+    state: Arc<DegradedModeState>,
+}
+
+impl DegradedMode {
+    /// Creates a degraded mode that serves cached responses for up to an hour and asks clients to
+    /// retry rejected requests after 30 seconds.
+    pub fn new(health_check: Arc<dyn DatabaseHealthCheck>) -> Self {
+        Self {
+            state: Arc::new(DegradedModeState {
+                health_check,
+                cache: Mutex::new(HashMap::new()),
+                max_age: DEFAULT_MAX_AGE,
+                max_entries: DEFAULT_MAX_ENTRIES,
+                retry_after: DEFAULT_RETRY_AFTER,
+            }),
+        }
+    }
+
+    /// Sets how old a cached response may be and still be served.
+    ///
+    /// This must be called before any resources are wrapped.
+    pub fn with_max_age(self, max_age: Duration) -> Self {
+        self.with_state(|state| state.max_age = max_age)
+    }
+
+    /// Sets how many responses are cached; when the cache is full, the oldest response is
+    /// replaced.
+    ///
+    /// This must be called before any resources are wrapped.
+    pub fn with_max_entries(self, max_entries: usize) -> Self {
+        self.with_state(|state| state.max_entries = max_entries)
+    }
+
+    /// Sets the value of the `Retry-After` header of rejected requests.
+    ///
+    /// This must be called before any resources are wrapped.
+    pub fn with_retry_after(self, retry_after: Duration) -> Self {
+        self.with_state(|state| state.retry_after = retry_after)
+    }
+
+    fn with_state<F: FnOnce(&mut DegradedModeState)>(mut self, f: F) -> Self {
+        match Arc::get_mut(&mut self.state) {
+            Some(state) => f(state),
+            None => warn!("Ignoring degraded mode setting, resources have already been wrapped"),
+        }
+        self
+    }
+
+    /// Wraps the methods of the given resource.
+    pub fn wrap(&self, resource: Resource) -> Resource {
+        resource.map_handlers(|method, handler| self.wrap_handler(method, handler))
+    }
+
+    /// Wraps the methods of each of the given resources.
+    pub fn wrap_resources(&self, resources: Vec<Resource>) -> Vec<Resource> {
+        resources
+            .into_iter()
+            .map(|resource| self.wrap(resource))
+            .collect()
+    }
+
+    fn wrap_handler(&self, method: Method, handler: Arc<HandlerFunction>) -> HandlerFunction {
+        let state = self.state.clone();
+        Box::new(
+            move |request: HttpRequest,
+                  payload: web::Payload|
+                  -> Box<dyn Future<Item = HttpResponse, Error = ActixError>> {
+                let state = state.clone();
+                let key = cache_key(&request);
+                Box::new(
+                    (handler)(request, payload)
+                        .then(move |result| handle_result(state, method, key, result)),
+                )
+            },
+        )
+    }
+}
+
+/// Caches successful `GET` responses, and replaces a failed response with a degraded one if the
+/// database is unavailable.
+fn handle_result(
+    state: Arc<DegradedModeState>,
+    method: Method,
+    key: String,
+    result: Result<HttpResponse, ActixError>,
+) -> Box<dyn Future<Item = HttpResponse, Error = ActixError>> {
+    match result {
+        Ok(response) if method == Method::Get && response.status().is_success() => {
+            state.store(key, &response);
+            Box::new(Ok(response).into_future())
+        }
+        Ok(response) if !response.status().is_server_error() => {
+            Box::new(Ok(response).into_future())
+        }
+        result => Box::new(
+            web::block(move || {
+                let available = state.health_check.is_available();
+                Ok::<_, ()>((state, available))
+            })
+            .then(move |health| match health {
+                Ok((state, false)) => {
+                    warn!(
+                        "Database is unavailable, serving degraded response for {}",
+                        key
+                    );
+                    Ok(state.degraded_response(method, &key))
+                }
+                _ => result,
+            }),
+        ),
+    }
+}
+
+struct CachedResponse {
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: web::Bytes,
+    cached_at: Instant,
+}
+
+struct DegradedModeState {
+    health_check: Arc<dyn DatabaseHealthCheck>,
+    cache: Mutex<HashMap<String, CachedResponse>>,
+    max_age: Duration,
+    max_entries: usize,
+    retry_after: Duration,
+}
+
+impl DegradedModeState {
+    /// Caches a successful response; streamed responses are not cached.
+    fn store(&self, key: String, response: &HttpResponse) {
+        let body = match response.body() {
+            ResponseBody::Body(Body::Bytes(bytes)) | ResponseBody::Other(Body::Bytes(bytes)) => {
+                bytes.clone()
+            }
+            _ => return,
+        };
+
+        let mut cache = match self.cache.lock() {
+            Ok(cache) => cache,
+            Err(_) => {
+                error!("Degraded mode cache lock poisoned");
+                return;
+            }
+        };
+
+        if !cache.contains_key(&key) && cache.len() >= self.max_entries {
+            let oldest = cache
+                .iter()
+                .min_by_key(|(_, cached)| cached.cached_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                cache.remove(&oldest);
+            }
+        }
+
+        if self.max_entries > 0 {
+            cache.insert(
+                key,
+                CachedResponse {
+                    status: response.status(),
+                    content_type: response.headers().get(header::CONTENT_TYPE).cloned(),
+                    body,
+                    cached_at: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Returns the cached response for a `GET` request if there is one that is recent enough,
+    /// otherwise a `503 Service Unavailable` response.
+    fn degraded_response(&self, method: Method, key: &str) -> HttpResponse {
+        if method == Method::Get {
+            if let Some(response) = self.stale_response(key) {
+                return response;
+            }
+        }
+
+        HttpResponse::ServiceUnavailable()
+            .header(header::RETRY_AFTER, self.retry_after.as_secs().to_string())
+            .json(ErrorResponse::service_unavailable(
+                "The database is temporarily unavailable",
+            ))
+    }
+
+    fn stale_response(&self, key: &str) -> Option<HttpResponse> {
+        let cache = self.cache.lock().ok()?;
+        let cached = cache.get(key)?;
+        let age = cached.cached_at.elapsed();
+        if age > self.max_age {
+            return None;
+        }
+
+        let mut response = HttpResponse::build(cached.status);
+        if let Some(content_type) = &cached.content_type {
+            response.header(header::CONTENT_TYPE, content_type.clone());
+        }
+        Some(
+            response
+                .header(header::AGE, age.as_secs().to_string())
+                .header(header::WARNING, "110 - \"Response is Stale\"")
+                .body(cached.body.clone()),
+        )
+    }
+}
+
+/// Responses are cached per path, query and protocol version, since the protocol version
+/// changes the format of the response.
+fn cache_key(request: &HttpRequest) -> String {
+    let protocol_version = request
+        .headers()
+        .get("SplinterProtocolVersion")
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("");
+    format!(
+        "{}?{}#{}",
+        request.path(),
+        request.query_string(),
+        protocol_version
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Unavailable;
+
+    impl DatabaseHealthCheck for Unavailable {
+        fn is_available(&self) -> bool {
+            false
+        }
+    }
+
+    fn state(max_age: Duration, max_entries: usize) -> DegradedModeState {
+        DegradedModeState {
+            health_check: Arc::new(Unavailable),
+            cache: Mutex::new(HashMap::new()),
+            max_age,
+            max_entries,
+            retry_after: Duration::from_secs(5),
+        }
+    }
+
+    /// Verify that a cached response is served with the staleness headers, and that a request
+    /// without a cached response is rejected with a Retry-After header.
+    #[test]
+    fn test_degraded_response() {
+        let state = state(DEFAULT_MAX_AGE, DEFAULT_MAX_ENTRIES);
+        state.store(
+            "/admin/circuits?#".into(),
+            &HttpResponse::Ok().json(serde_json::json!({ "data": [] })),
+        );
+
+        let response = state.degraded_response(Method::Get, "/admin/circuits?#");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().contains_key(header::AGE));
+        assert!(response.headers().contains_key(header::WARNING));
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static("application/json"))
+        );
+
+        let response = state.degraded_response(Method::Post, "/admin/circuits?#");
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            response.headers().get(header::RETRY_AFTER),
+            Some(&HeaderValue::from_static("5"))
+        );
+
+        let response = state.degraded_response(Method::Get, "/registry/nodes?#");
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    /// Verify that responses older than the maximum age are not served, and that the oldest
+    /// response is replaced when the cache is full.
+    #[test]
+    fn test_cache_limits() {
+        let state = state(Duration::from_secs(0), DEFAULT_MAX_ENTRIES);
+        state.store("/status?#".into(), &HttpResponse::Ok().body("ok"));
+        std::thread::sleep(Duration::from_millis(10));
+        assert!(state.stale_response("/status?#").is_none());
+
+        let state = state(DEFAULT_MAX_AGE, 1);
+        state.store("/status?#".into(), &HttpResponse::Ok().body("ok"));
+        state.store("/admin/circuits?#".into(), &HttpResponse::Ok().body("[]"));
+        assert!(state.stale_response("/status?#").is_none());
+        assert!(state.stale_response("/admin/circuits?#").is_some());
+    }
+}
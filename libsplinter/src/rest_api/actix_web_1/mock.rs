@@ -0,0 +1,460 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A mock of the Splinter REST API, for developing applications without running a network.
+//!
+//! [`MockResourceProvider`] serves canned responses and scripted event streams described by
+//! [`MockFixtures`]. Fixtures are loaded from a YAML or JSON file:
+//!
+//! ```yaml
+//! routes:
+//!   - path: /admin/circuits/alpha-beta
+//!     body: { "id": "alpha-beta", "members": [] }
+//!   - path: /admin/submit
+//!     method: POST
+//!     status: 202
+//! event_streams:
+//!   - path: /admin/events/scabbard
+//!     interval_ms: 2000
+//!     repeat: true
+//!     events:
+//!       - { "eventType": "ProposalSubmitted", "message": {} }
+//! ```
+//!
+//! A route answers requests for its method and path with its status and JSON body. An event
+//! stream answers `GET` requests with a Server-Sent Events stream that sends its events in order,
+//! one every `interval_ms` milliseconds, starting over once the last event is sent if `repeat` is
+//! set. No authorization is required for any of the resources.
+
+use std::collections::BTreeMap;
+use std::thread;
+use std::time::Duration;
+
+use actix_web::{
+    error::ErrorInternalServerError, http::StatusCode, web::Bytes, HttpRequest, HttpResponse,
+};
+use futures::{sync::mpsc, IntoFuture, Stream};
+use serde_json::{json, Value};
+
+use crate::error::InvalidArgumentError;
+#[cfg(feature = "authorization")]
+use crate::rest_api::auth::authorization::Permission;
+
+use super::{Method, Resource, RestResourceProvider};
+
+fn default_status() -> u16 {
+    200
+}
+
+fn default_interval_ms() -> u64 {
+    1000
+}
+
+/// The canned responses and event streams served by a [`MockResourceProvider`]
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct MockFixtures {
+    #[serde(default)]
+    routes: Vec<MockRoute>,
+    #[serde(default)]
+    event_streams: Vec<MockEventStream>,
+}
+
+/// A canned response to the requests for a method and path
+#[derive(Clone, Debug, Deserialize)]
+struct MockRoute {
+    path: String,
+    #[serde(default)]
+    method: Option<String>,
+    #[serde(default = "default_status")]
+    status: u16,
+    #[serde(default)]
+    body: Value,
+}
+
+/// A scripted Server-Sent Events stream
+#[derive(Clone, Debug, Deserialize)]
+struct MockEventStream {
+    path: String,
+    #[serde(default = "default_interval_ms")]
+    interval_ms: u64,
+    #[serde(default)]
+    repeat: bool,
+    events: Vec<Value>,
+}
+
+impl MockFixtures {
+    /// Parses fixtures from YAML or JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the fixtures cannot be parsed, or if a route has an
+    /// unknown method or an invalid status.
+    pub fn from_yaml(yaml: &str) -> Result<Self, InvalidArgumentError> {
+        let fixtures: Self = serde_yaml::from_str(yaml)
+            .map_err(|err| InvalidArgumentError::new("fixtures", err.to_string()))?;
+
+        for route in &fixtures.routes {
+            route.method()?;
+            StatusCode::from_u16(route.status).map_err(|_| {
+                InvalidArgumentError::new(
+                    "fixtures",
+                    format!(
+                        "route {} has an invalid status {}",
+                        route.path, route.status
+                    ),
+                )
+            })?;
+        }
+
+        Ok(fixtures)
+    }
+
+    /// Returns a small two-node network: the node's status, one circuit between the two nodes, a
+    /// pending proposal, the registry entries and peers of the two nodes, and a stream of admin
+    /// events for the `scabbard` circuit management type.
+    pub fn sample() -> Self {
+        let alpha = json!({
+            "identity": "alpha-node-000",
+            "endpoints": ["tcps://alpha.example.com:8044"],
+            "display_name": "Alpha",
+            "keys": ["02d9a6d5e2f48a7c1e9f7a1e7c0a3d4f2e6b8a9c0d1e2f3a4b5c6d7e8f9a0b1c2d"],
+            "metadata": {"organization": "Alpha Inc."}
+        });
+        let beta = json!({
+            "identity": "beta-node-000",
+            "endpoints": ["tcps://beta.example.com:8044"],
+            "display_name": "Beta",
+            "keys": ["03f1e2d3c4b5a6978877665544332211ffeeddccbbaa99887766554433221100aa"],
+            "metadata": {"organization": "Beta LLC"}
+        });
+        let circuit = json!({
+            "id": "alpha-beta",
+            "members": [
+                {"node_id": "alpha-node-000", "endpoints": ["tcps://alpha.example.com:8044"]},
+                {"node_id": "beta-node-000", "endpoints": ["tcps://beta.example.com:8044"]}
+            ],
+            "roster": [{
+                "service_id": "a000",
+                "service_type": "scabbard",
+                "node_id": "alpha-node-000",
+                "arguments": [["admin_keys", "[]"], ["peer_services", "[\"b000\"]"]]
+            }, {
+                "service_id": "b000",
+                "service_type": "scabbard",
+                "node_id": "beta-node-000",
+                "arguments": [["admin_keys", "[]"], ["peer_services", "[\"a000\"]"]]
+            }],
+            "management_type": "scabbard",
+            "display_name": "Alpha-Beta",
+            "circuit_version": 2,
+            "circuit_status": "Active"
+        });
+        let proposal = json!({
+            "proposal_type": "Create",
+            "circuit_id": "alpha-gamma",
+            "circuit_hash": "7ddc426972710adc0b2ecd49e89a9dd805fb9206bf516079724c887bedbcdf1d",
+            "circuit": {
+                "circuit_id": "alpha-gamma",
+                "members": [
+                    {"node_id": "alpha-node-000", "endpoints": ["tcps://alpha.example.com:8044"]}
+                ],
+                "roster": [],
+                "management_type": "scabbard",
+                "comments": "A proposal waiting for votes",
+                "display_name": "Alpha-Gamma",
+                "circuit_version": 2,
+                "circuit_status": "Active"
+            },
+            "votes": [],
+            "requester": "02d9a6d5e2f48a7c1e9f7a1e7c0a3d4f2e6b8a9c0d1e2f3a4b5c6d7e8f9a0b1c2d",
+            "requester_node_id": "alpha-node-000"
+        });
+
+        Self {
+            routes: vec![
+                MockRoute::get(
+                    "/status",
+                    json!({
+                        "node_id": "alpha-node-000",
+                        "display_name": "Alpha",
+                        "network_endpoints": ["tcps://0.0.0.0:8044"],
+                        "advertised_endpoints": ["tcps://alpha.example.com:8044"],
+                        "version": env!("CARGO_PKG_VERSION")
+                    }),
+                ),
+                MockRoute::get("/admin/circuits", paged(vec![circuit.clone()])),
+                MockRoute::get("/admin/circuits/alpha-beta", circuit),
+                MockRoute::get("/admin/proposals", paged(vec![proposal.clone()])),
+                MockRoute::get("/admin/proposals/alpha-gamma", proposal),
+                MockRoute::get("/registry/nodes", paged(vec![alpha.clone(), beta.clone()])),
+                MockRoute::get("/registry/nodes/alpha-node-000", alpha),
+                MockRoute::get("/registry/nodes/beta-node-000", beta),
+                MockRoute::get("/admin/peers", paged(vec![json!("beta-node-000")])),
+                MockRoute {
+                    path: "/admin/submit".into(),
+                    method: Some("POST".into()),
+                    status: 202,
+                    body: Value::Null,
+                },
+            ],
+            event_streams: vec![MockEventStream {
+                path: "/admin/events/scabbard".into(),
+                interval_ms: 5000,
+                repeat: true,
+                events: vec![
+                    json!({
+                        "eventType": "ProposalSubmitted",
+                        "message": {"circuit_id": "alpha-gamma"}
+                    }),
+                    json!({
+                        "eventType": "ProposalVote",
+                        "message": {"circuit_id": "alpha-gamma", "vote": "Accept"}
+                    }),
+                    json!({
+                        "eventType": "CircuitReady",
+                        "message": {"circuit_id": "alpha-gamma"}
+                    }),
+                ],
+            }],
+        }
+    }
+}
+
+impl MockRoute {
+    fn get(path: &str, body: Value) -> Self {
+        Self {
+            path: path.into(),
+            method: None,
+            status: default_status(),
+            body,
+        }
+    }
+
+    fn method(&self) -> Result<Method, InvalidArgumentError> {
+        match self
+            .method
+            .as_deref()
+            .unwrap_or("GET")
+            .to_uppercase()
+            .as_str()
+        {
+            "GET" => Ok(Method::Get),
+            "POST" => Ok(Method::Post),
+            "PUT" => Ok(Method::Put),
+            "PATCH" => Ok(Method::Patch),
+            "DELETE" => Ok(Method::Delete),
+            "HEAD" => Ok(Method::Head),
+            method => Err(InvalidArgumentError::new(
+                "fixtures",
+                format!("route {} has an unknown method {}", self.path, method),
+            )),
+        }
+    }
+}
+
+/// Wraps a list in the paging envelope returned by the list endpoints.
+fn paged(data: Vec<Value>) -> Value {
+    let total = data.len();
+    json!({
+        "data": data,
+        "paging": {
+            "current": "?offset=0&limit=100",
+            "offset": 0,
+            "limit": 100,
+            "total": total,
+            "first": "?offset=0&limit=100",
+            "prev": "?offset=0&limit=100",
+            "next": "?offset=0&limit=100",
+            "last": "?offset=0&limit=100"
+        }
+    })
+}
+
+/// Provides the resources described by a set of [`MockFixtures`]
+pub struct MockResourceProvider {
+    fixtures: MockFixtures,
+}
+
+impl MockResourceProvider {
+    pub fn new(fixtures: MockFixtures) -> Self {
+        Self { fixtures }
+    }
+}
+
+impl RestResourceProvider for MockResourceProvider {
+    /// Returns one resource per path; the routes and event stream that share a path are served by
+    /// the same resource.
+    fn resources(&self) -> Vec<Resource> {
+        let mut resources = BTreeMap::new();
+
+        for route in &self.fixtures.routes {
+            // The fixtures were validated when they were parsed
+            let method = match route.method() {
+                Ok(method) => method,
+                Err(err) => {
+                    warn!("Ignoring mock route: {}", err);
+                    continue;
+                }
+            };
+            let status = StatusCode::from_u16(route.status).unwrap_or(StatusCode::OK);
+            let body = route.body.clone();
+            let resource = resources
+                .remove(&route.path)
+                .unwrap_or_else(|| Resource::build(&route.path));
+            resources.insert(
+                route.path.clone(),
+                add_method(resource, method, move |_| {
+                    let mut response = HttpResponse::build(status);
+                    if body.is_null() {
+                        response.finish()
+                    } else {
+                        response.json(body.clone())
+                    }
+                }),
+            );
+        }
+
+        for stream in &self.fixtures.event_streams {
+            let stream = stream.clone();
+            let resource = resources
+                .remove(&stream.path)
+                .unwrap_or_else(|| Resource::build(&stream.path));
+            resources.insert(
+                stream.path.clone(),
+                add_method(resource, Method::Get, move |_| stream_events(&stream)),
+            );
+        }
+
+        resources
+            .into_iter()
+            .map(|(_, resource)| resource)
+            .collect()
+    }
+}
+
+fn add_method<F>(resource: Resource, method: Method, respond: F) -> Resource
+where
+    F: Fn(&HttpRequest) -> HttpResponse + Send + Sync + 'static,
+{
+    let handler = move |request: HttpRequest, _| {
+        Box::new(respond(&request).into_future())
+            as Box<dyn futures::Future<Item = HttpResponse, Error = actix_web::Error>>
+    };
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(method, Permission::AllowUnauthenticated, handler)
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(method, handler)
+    }
+}
+
+/// Starts a thread that sends the stream's events to a new client until it disconnects, or until
+/// the last event is sent if the stream does not repeat.
+fn stream_events(stream: &MockEventStream) -> HttpResponse {
+    let (sender, receiver) = mpsc::unbounded();
+    let stream = stream.clone();
+
+    let spawned = thread::Builder::new()
+        .name(format!("Mock Event Stream {}", stream.path))
+        .spawn(move || {
+            let mut event_id = 0;
+            loop {
+                for event in &stream.events {
+                    event_id += 1;
+                    if sender.unbounded_send(to_sse(event_id, event)).is_err() {
+                        return;
+                    }
+                    thread::sleep(Duration::from_millis(stream.interval_ms));
+                }
+                if !stream.repeat || stream.events.is_empty() {
+                    return;
+                }
+            }
+        });
+    if let Err(err) = spawned {
+        error!("Unable to start mock event stream: {}", err);
+        return HttpResponse::InternalServerError().finish();
+    }
+
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .header("Cache-Control", "no-cache")
+        .streaming(receiver.map_err(|_| ErrorInternalServerError("mock event stream closed")))
+}
+
+fn to_sse(event_id: u64, event: &Value) -> Bytes {
+    Bytes::from(format!("id: {}\ndata: {}\n\n", event_id, event))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that fixtures are parsed with their defaults, and that routes with an unknown method
+    /// or an invalid status are rejected.
+    #[test]
+    fn test_fixtures_from_yaml() {
+        let fixtures = MockFixtures::from_yaml(
+            r#"
+routes:
+  - path: /status
+    body: { "node_id": "alpha-node-000" }
+  - path: /admin/submit
+    method: post
+    status: 202
+event_streams:
+  - path: /admin/events/scabbard
+    events:
+      - { "eventType": "CircuitReady" }
+"#,
+        )
+        .expect("Failed to parse fixtures");
+
+        assert_eq!(fixtures.routes.len(), 2);
+        assert_eq!(fixtures.routes[0].status, 200);
+        assert!(matches!(fixtures.routes[0].method(), Ok(Method::Get)));
+        assert!(matches!(fixtures.routes[1].method(), Ok(Method::Post)));
+        assert_eq!(fixtures.event_streams[0].interval_ms, 1000);
+        assert!(!fixtures.event_streams[0].repeat);
+
+        assert!(MockFixtures::from_yaml("routes: [{ path: /status, method: FETCH }]").is_err());
+        assert!(MockFixtures::from_yaml("routes: [{ path: /status, status: 1000 }]").is_err());
+    }
+
+    /// Verify that the routes and event streams that share a path are served by one resource.
+    #[test]
+    fn test_resources_per_path() {
+        let fixtures = MockFixtures::from_yaml(
+            r#"
+routes:
+  - path: /admin/submit
+    method: POST
+  - path: /admin/submit
+    method: GET
+event_streams:
+  - path: /admin/events/scabbard
+    events: []
+"#,
+        )
+        .expect("Failed to parse fixtures");
+
+        assert_eq!(MockResourceProvider::new(fixtures).resources().len(), 2);
+        assert!(!MockResourceProvider::new(MockFixtures::sample())
+            .resources()
+            .is_empty());
+    }
+}
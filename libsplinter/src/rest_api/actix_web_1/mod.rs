@@ -15,16 +15,28 @@
 mod api;
 mod auth;
 mod builder;
+#[cfg(feature = "rest-api-degraded-mode")]
+mod degraded;
 mod error;
 mod guard;
+#[cfg(feature = "rest-api-mock")]
+mod mock;
+#[cfg(feature = "rest-api-openapi")]
+mod openapi;
 mod resource;
 mod websocket;
 
 pub use api::{RestApi, RestApiShutdownHandle};
 pub use auth::{get_authorization_token, require_header, AuthConfig};
 pub use builder::RestApiBuilder;
+#[cfg(feature = "rest-api-degraded-mode")]
+pub use degraded::{DatabaseHealthCheck, DegradedMode};
 pub use error::ResponseError;
 pub use guard::{Continuation, ProtocolVersionRangeGuard, RequestGuard};
+#[cfg(feature = "rest-api-mock")]
+pub use mock::{MockFixtures, MockResourceProvider};
+#[cfg(feature = "rest-api-openapi")]
+pub use openapi::OpenApiConfig;
 pub use resource::{
     into_bytes, into_protobuf, HandlerFunction, Method, Resource, RestResourceProvider,
 };
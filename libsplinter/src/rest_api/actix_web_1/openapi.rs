@@ -0,0 +1,481 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Generation of an OpenAPI 3 document that describes the REST API.
+//!
+//! The document is generated from the resources the REST API is built with, so it lists exactly
+//! the routes the REST API serves, each with its path parameters, whether the client must be
+//! authenticated, and the permission it requires (in the `x-permission` extension). What cannot
+//! be read from a route, such as its summary, request body and responses, is taken from the
+//! route's operation in a hand-written base document, if it has one. Operations of the base
+//! document whose routes are not served, such as those of features that are not enabled, are left
+//! out.
+//!
+//! The document is served at `/openapi.json`, and may also be browsed with Swagger UI at
+//! `/swagger-ui`. Neither requires authorization, as the document describes the REST API and none
+//! of the node's data.
+
+use actix_web::HttpResponse;
+use futures::IntoFuture;
+use serde_json::{json, Map, Value};
+
+use crate::error::InvalidArgumentError;
+use crate::rest_api::auth::authorization::Permission;
+
+use super::{Method, Resource};
+
+const OPENAPI_VERSION: &str = "3.0.0";
+
+const SWAGGER_UI_PAGE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8" />
+    <title>Splinter REST API</title>
+    <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@4/swagger-ui.css" />
+  </head>
+  <body>
+    <div id="swagger-ui"></div>
+    <script src="https://unpkg.com/swagger-ui-dist@4/swagger-ui-bundle.js"></script>
+    <script>
+      window.onload = () => {
+        window.ui = SwaggerUIBundle({ url: "openapi.json", dom_id: "#swagger-ui" });
+      };
+    </script>
+  </body>
+</html>
+"##;
+
+/// The configuration of the REST API's OpenAPI document.
+#[derive(Clone, Debug)]
+pub struct OpenApiConfig {
+    base_document: Option<String>,
+    title: String,
+    version: String,
+    swagger_ui: bool,
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            base_document: None,
+            title: "Splinter REST API".into(),
+            version: env!("CARGO_PKG_VERSION").into(),
+            swagger_ui: false,
+        }
+    }
+}
+
+impl OpenApiConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the hand-written OpenAPI document, in YAML or JSON, that documents the operations of
+    /// the generated document.
+    pub fn with_base_document(mut self, base_document: &str) -> Self {
+        self.base_document = Some(base_document.to_string());
+        self
+    }
+
+    /// Sets the title of the document, if the base document does not have one.
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_string();
+        self
+    }
+
+    /// Sets the version of the document, if the base document does not have one.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = version.to_string();
+        self
+    }
+
+    /// Sets whether the document may be browsed with Swagger UI.
+    pub fn with_swagger_ui(mut self, swagger_ui: bool) -> Self {
+        self.swagger_ui = swagger_ui;
+        self
+    }
+
+    /// Generates the OpenAPI document for the given resources, and returns the resources that
+    /// serve it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [InvalidArgumentError] if the base document is not a valid YAML or JSON object.
+    pub(super) fn into_resources(
+        self,
+        resources: &[Resource],
+    ) -> Result<Vec<Resource>, InvalidArgumentError> {
+        let document = self.generate_document(resources)?.to_string();
+
+        let mut openapi_resources = vec![Resource::build("/openapi.json").add_method(
+            Method::Get,
+            Permission::AllowUnauthenticated,
+            move |_, _| {
+                Box::new(
+                    HttpResponse::Ok()
+                        .content_type("application/json")
+                        .body(document.clone())
+                        .into_future(),
+                )
+            },
+        )];
+
+        if self.swagger_ui {
+            openapi_resources.push(Resource::build("/swagger-ui").add_method(
+                Method::Get,
+                Permission::AllowUnauthenticated,
+                |_, _| {
+                    Box::new(
+                        HttpResponse::Ok()
+                            .content_type("text/html; charset=utf-8")
+                            .body(SWAGGER_UI_PAGE)
+                            .into_future(),
+                    )
+                },
+            ));
+        }
+
+        Ok(openapi_resources)
+    }
+
+    /// Generates the OpenAPI document for the given resources.
+    fn generate_document(&self, resources: &[Resource]) -> Result<Value, InvalidArgumentError> {
+        let mut document = match &self.base_document {
+            Some(base_document) => match serde_yaml::from_str(base_document) {
+                Ok(Value::Object(document)) => document,
+                Ok(_) => {
+                    return Err(InvalidArgumentError::new(
+                        "base_document",
+                        "must be an object",
+                    ))
+                }
+                Err(err) => {
+                    return Err(InvalidArgumentError::new("base_document", err.to_string()))
+                }
+            },
+            None => Map::new(),
+        };
+
+        let base_paths = document
+            .remove("paths")
+            .and_then(|paths| match paths {
+                Value::Object(paths) => Some(paths),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let has_auth_parameter = document
+            .get("components")
+            .and_then(|components| components.get("parameters"))
+            .and_then(|parameters| parameters.get("auth"))
+            .is_some();
+
+        let mut paths = Map::new();
+        for resource in resources {
+            let (path, path_parameters) = openapi_path(resource.route());
+            for (method, permission) in resource.method_permissions() {
+                let method = method.to_string().to_lowercase();
+                let base_operation = base_paths
+                    .get(&path)
+                    .and_then(|path_item| path_item.get(&method))
+                    .and_then(Value::as_object);
+
+                let operation = generate_operation(
+                    base_operation,
+                    &path_parameters,
+                    permission,
+                    has_auth_parameter,
+                );
+
+                if let Value::Object(path_item) = paths
+                    .entry(path.clone())
+                    .or_insert_with(|| Value::Object(Map::new()))
+                {
+                    path_item.insert(method, Value::Object(operation));
+                }
+            }
+        }
+
+        document
+            .entry("openapi")
+            .or_insert_with(|| Value::from(OPENAPI_VERSION));
+        document
+            .entry("info")
+            .or_insert_with(|| json!({ "title": self.title, "version": self.version }));
+        document.insert("paths".into(), Value::Object(paths));
+
+        Ok(Value::Object(document))
+    }
+}
+
+/// Converts an actix route to an OpenAPI path, removing the patterns of its path parameters, and
+/// returns the path with the names of its path parameters.
+fn openapi_path(route: &str) -> (String, Vec<String>) {
+    let mut path = String::new();
+    let mut parameters = vec![];
+
+    if !route.starts_with('/') {
+        path.push('/');
+    }
+
+    let mut rest = route;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = rest[start + 1..end]
+            .split(':')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        path.push_str(&rest[..start]);
+        path.push('{');
+        path.push_str(&name);
+        path.push('}');
+        parameters.push(name);
+        rest = &rest[end + 1..];
+    }
+    path.push_str(rest);
+
+    (path, parameters)
+}
+
+/// Generates an operation from its base operation, if there is one, adding the path parameters
+/// and authorization header the base operation does not list.
+fn generate_operation(
+    base_operation: Option<&Map<String, Value>>,
+    path_parameters: &[String],
+    permission: Permission,
+    has_auth_parameter: bool,
+) -> Map<String, Value> {
+    let mut operation = base_operation.cloned().unwrap_or_else(|| {
+        let mut operation = Map::new();
+        operation.insert(
+            "responses".into(),
+            json!({ "default": { "description": "The response of the endpoint" } }),
+        );
+        operation
+    });
+
+    let mut parameters = match operation.remove("parameters") {
+        Some(Value::Array(parameters)) => parameters,
+        _ => vec![],
+    };
+
+    for name in path_parameters {
+        let listed = parameters.iter().any(|parameter| {
+            parameter.get("in") == Some(&Value::from("path"))
+                && parameter.get("name") == Some(&Value::from(name.as_str()))
+        });
+        if !listed {
+            parameters.push(json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            }));
+        }
+    }
+
+    let requires_auth = !matches!(permission, Permission::AllowUnauthenticated);
+    // A base operation lists its authorization header, if it has one
+    if requires_auth && base_operation.is_none() {
+        if has_auth_parameter {
+            parameters.push(json!({ "$ref": "#/components/parameters/auth" }));
+        } else {
+            parameters.push(json!({
+                "name": "Authorization",
+                "in": "header",
+                "required": true,
+                "schema": { "type": "string" },
+            }));
+        }
+    }
+
+    if !parameters.is_empty() {
+        operation.insert("parameters".into(), Value::Array(parameters));
+    }
+
+    if let Permission::Check { permission_id, .. } = permission {
+        operation.insert("x-permission".into(), Value::from(permission_id));
+    }
+
+    operation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use actix_web::HttpRequest;
+
+    const BASE_DOCUMENT: &str = r#"
+openapi: '3.0.0'
+info:
+  version: 0.7.1
+  title: splinterd API
+paths:
+  /admin/circuits/{circuit_id}:
+    get:
+      summary: Fetch a circuit
+      parameters:
+        - name: circuit_id
+          in: path
+          description: The circuit ID
+          required: true
+          schema:
+            type: string
+      responses:
+        '200':
+          description: The circuit
+  /biome/login:
+    post:
+      summary: Log in
+components:
+  parameters:
+    auth:
+      name: Authorization
+      in: header
+"#;
+
+    fn resource(route: &str, method: Method, permission: Permission) -> Resource {
+        Resource::build(route).add_method(method, permission, |_: HttpRequest, _| {
+            Box::new(HttpResponse::Ok().finish().into_future())
+        })
+    }
+
+    /// Verify that actix routes are converted to OpenAPI paths, with their path parameters.
+    #[test]
+    fn test_openapi_path() {
+        assert_eq!(openapi_path("/status"), ("/status".into(), vec![]));
+        assert_eq!(
+            openapi_path("/admin/circuits/{circuit_id}"),
+            (
+                "/admin/circuits/{circuit_id}".into(),
+                vec!["circuit_id".into()]
+            )
+        );
+        assert_eq!(
+            openapi_path("/scabbard/{circuit}/{service_id:[a-z]+}/state"),
+            (
+                "/scabbard/{circuit}/{service_id}/state".into(),
+                vec!["circuit".into(), "service_id".into()]
+            )
+        );
+        assert_eq!(
+            openapi_path(".openapi.yaml"),
+            ("/.openapi.yaml".into(), vec![])
+        );
+    }
+
+    /// Verify that the generated document:
+    ///
+    /// 1. Keeps the documentation of the base document's operations that are served
+    /// 2. Leaves out the base document's operations that are not served
+    /// 3. Generates operations, with their path parameters and authorization header, for routes
+    ///    the base document does not document
+    /// 4. Lists the permission each operation requires
+    #[test]
+    fn test_generate_document() {
+        let permission = Permission::Check {
+            permission_id: "circuit.read",
+            permission_display_name: "Circuit read",
+            permission_description: "Allows the client to read circuits",
+        };
+        let resources = vec![
+            resource("/admin/circuits/{circuit_id}", Method::Get, permission),
+            resource(
+                "/registry/nodes/{identity}",
+                Method::Delete,
+                Permission::AllowAuthenticated,
+            ),
+            resource("/health", Method::Get, Permission::AllowUnauthenticated),
+        ];
+
+        let document = OpenApiConfig::new()
+            .with_base_document(BASE_DOCUMENT)
+            .generate_document(&resources)
+            .expect("Failed to generate document");
+
+        assert_eq!(document["openapi"], "3.0.0");
+        assert_eq!(document["info"]["title"], "splinterd API");
+        assert!(document["components"]["parameters"]["auth"].is_object());
+
+        let paths = document["paths"].as_object().expect("No paths");
+        assert_eq!(paths.len(), 3);
+        assert!(paths.get("/biome/login").is_none());
+
+        let get_circuit = &paths["/admin/circuits/{circuit_id}"]["get"];
+        assert_eq!(get_circuit["summary"], "Fetch a circuit");
+        assert_eq!(get_circuit["x-permission"], "circuit.read");
+        assert_eq!(
+            get_circuit["parameters"],
+            json!([{
+                "name": "circuit_id",
+                "in": "path",
+                "description": "The circuit ID",
+                "required": true,
+                "schema": { "type": "string" },
+            }])
+        );
+
+        let delete_node = &paths["/registry/nodes/{identity}"]["delete"];
+        assert!(delete_node.get("x-permission").is_none());
+        assert_eq!(
+            delete_node["parameters"],
+            json!([
+                {
+                    "name": "identity",
+                    "in": "path",
+                    "required": true,
+                    "schema": { "type": "string" },
+                },
+                { "$ref": "#/components/parameters/auth" },
+            ])
+        );
+
+        let health = &paths["/health"]["get"];
+        assert!(health.get("parameters").is_none());
+        assert!(health["responses"]["default"].is_object());
+    }
+
+    /// Verify that without a base document, the document's info is taken from the configuration,
+    /// and that an invalid base document is rejected.
+    #[test]
+    fn test_generate_document_without_base() {
+        let document = OpenApiConfig::new()
+            .with_title("Test API")
+            .with_version("1.2.3")
+            .generate_document(&[resource(
+                "/status",
+                Method::Get,
+                Permission::AllowAuthenticated,
+            )])
+            .expect("Failed to generate document");
+
+        assert_eq!(
+            document["info"],
+            json!({ "title": "Test API", "version": "1.2.3" })
+        );
+        assert_eq!(
+            document["paths"]["/status"]["get"]["parameters"][0]["name"],
+            "Authorization"
+        );
+
+        assert!(OpenApiConfig::new()
+            .with_base_document("- not\n- an object\n")
+            .generate_document(&[])
+            .is_err());
+    }
+}
@@ -233,6 +233,39 @@ impl Resource {
         self
     }
 
+    #[cfg(feature = "rest-api-openapi")]
+    pub(super) fn route(&self) -> &str {
+        &self.route
+    }
+
+    /// Returns the methods of the resource, with the permission each method requires.
+    #[cfg(feature = "rest-api-openapi")]
+    pub(super) fn method_permissions(&self) -> Vec<(Method, Permission)> {
+        self.methods
+            .iter()
+            .map(|resource_method| (resource_method.method, resource_method.permission))
+            .collect()
+    }
+
+    /// Replaces the handler of each method with the result of `f`, which is given the method and
+    /// its current handler.
+    #[cfg(feature = "rest-api-degraded-mode")]
+    pub(super) fn map_handlers<F>(mut self, f: F) -> Self
+    where
+        F: Fn(Method, Arc<HandlerFunction>) -> HandlerFunction,
+    {
+        #[cfg(feature = "authorization")]
+        for resource_method in self.methods.iter_mut() {
+            resource_method.handler =
+                Arc::new(f(resource_method.method, resource_method.handler.clone()));
+        }
+        #[cfg(not(feature = "authorization"))]
+        for (method, handler) in self.methods.iter_mut() {
+            *handler = Arc::new(f(*method, handler.clone()));
+        }
+        self
+    }
+
     #[cfg(feature = "authorization")]
     pub(super) fn into_route(self) -> (actix_web::Resource, PermissionMap<Method>) {
         let mut resource = web::resource(&self.route);
@@ -0,0 +1,255 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Records requests that were rejected as unauthorized in the audit log, without blocking the
+//! REST API's workers.
+//!
+//! Failures are queued for a writer thread, and dropped if the queue is full. The first failure
+//! of a client is recorded as it arrives; the client's further failures within the aggregation
+//! window are counted, and recorded as a single event when the window ends.
+
+use std::collections::HashMap;
+use std::sync::mpsc::{sync_channel, RecvTimeoutError, SyncSender, TrySendError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::audit::{store::AuditStore, AuditEvent, AuditEventType};
+use crate::error::InternalError;
+
+/// The most failures that may wait to be recorded
+const QUEUE_SIZE: usize = 1024;
+
+/// How long a client's failures are aggregated after its first recorded failure
+const AGGREGATION_WINDOW: Duration = Duration::from_secs(60);
+
+/// A request that was rejected as unauthorized
+pub(super) struct AuthFailure {
+    /// The client's address
+    pub client: String,
+    /// The request's method and path
+    pub request: String,
+    /// Why the request was rejected
+    pub detail: &'static str,
+}
+
+/// Sends failures to the thread that records them
+#[derive(Clone)]
+pub(super) struct AuthFailureRecorder {
+    sender: SyncSender<AuthFailure>,
+}
+
+impl AuthFailureRecorder {
+    /// Starts the thread that records failures in the given audit log. The thread stops once
+    /// every recorder has been dropped.
+    pub fn start(audit_store: Box<dyn AuditStore>) -> Result<Self, InternalError> {
+        let (sender, receiver) = sync_channel::<AuthFailure>(QUEUE_SIZE);
+
+        thread::Builder::new()
+            .name("AuthFailureRecorder".into())
+            .spawn(move || {
+                let mut aggregator = FailureAggregator::default();
+                loop {
+                    let timeout = aggregator
+                        .next_expiry()
+                        .map(|expiry| expiry.saturating_duration_since(Instant::now()))
+                        .unwrap_or(AGGREGATION_WINDOW);
+                    let events = match receiver.recv_timeout(timeout) {
+                        Ok(failure) => aggregator
+                            .add(failure, Instant::now())
+                            .into_iter()
+                            .collect(),
+                        Err(RecvTimeoutError::Timeout) => aggregator.expire(Instant::now()),
+                        Err(RecvTimeoutError::Disconnected) => {
+                            for event in aggregator.expire_all() {
+                                add_event(&*audit_store, &event);
+                            }
+                            break;
+                        }
+                    };
+                    for event in events {
+                        add_event(&*audit_store, &event);
+                    }
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self { sender })
+    }
+
+    /// Queues a failure to be recorded, dropping it if the queue is full.
+    pub fn record(&self, failure: AuthFailure) {
+        match self.sender.try_send(failure) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) => {
+                debug!("Authorization failure queue is full; dropping audit event")
+            }
+            Err(TrySendError::Disconnected(_)) => {
+                error!("Unable to record audit event: recorder has stopped")
+            }
+        }
+    }
+}
+
+fn add_event(audit_store: &dyn AuditStore, event: &AuditEvent) {
+    if let Err(err) = audit_store.add_event(event) {
+        error!("Unable to record audit event: {}", err);
+    }
+}
+
+/// The failures of a client within its aggregation window
+struct ClientFailures {
+    window_start: Instant,
+    /// The failures that have not been recorded
+    suppressed: u64,
+    last_request: String,
+    last_detail: &'static str,
+}
+
+/// Aggregates each client's failures, returning the events to record
+#[derive(Default)]
+struct FailureAggregator {
+    clients: HashMap<String, ClientFailures>,
+}
+
+impl FailureAggregator {
+    /// Adds a failure, returning its event if it is the first of the client's window.
+    fn add(&mut self, failure: AuthFailure, now: Instant) -> Option<AuditEvent> {
+        if let Some(client) = self.clients.get_mut(&failure.client) {
+            if now.duration_since(client.window_start) < AGGREGATION_WINDOW {
+                client.suppressed += 1;
+                client.last_request = failure.request;
+                client.last_detail = failure.detail;
+                return None;
+            }
+        }
+
+        self.clients.insert(
+            failure.client.clone(),
+            ClientFailures {
+                window_start: now,
+                suppressed: 0,
+                last_request: failure.request.clone(),
+                last_detail: failure.detail,
+            },
+        );
+        Some(
+            AuditEvent::new(AuditEventType::AuthFailed, failure.client, failure.request)
+                .with_detail(failure.detail.to_string()),
+        )
+    }
+
+    /// Returns when the earliest window ends, if any client has one.
+    fn next_expiry(&self) -> Option<Instant> {
+        self.clients
+            .values()
+            .map(|client| client.window_start + AGGREGATION_WINDOW)
+            .min()
+    }
+
+    /// Ends the windows that have passed, returning an event for each with suppressed failures.
+    fn expire(&mut self, now: Instant) -> Vec<AuditEvent> {
+        let expired = self
+            .clients
+            .iter()
+            .filter(|(_, client)| now.duration_since(client.window_start) >= AGGREGATION_WINDOW)
+            .map(|(address, _)| address.clone())
+            .collect::<Vec<_>>();
+
+        expired
+            .into_iter()
+            .filter_map(|address| {
+                let client = self.clients.remove(&address)?;
+                summary(address, client)
+            })
+            .collect()
+    }
+
+    /// Ends every window, returning an event for each with suppressed failures.
+    fn expire_all(&mut self) -> Vec<AuditEvent> {
+        self.clients
+            .drain()
+            .filter_map(|(address, client)| summary(address, client))
+            .collect()
+    }
+}
+
+/// Returns the event that records a client's suppressed failures, if it has any.
+fn summary(address: String, client: ClientFailures) -> Option<AuditEvent> {
+    if client.suppressed == 0 {
+        return None;
+    }
+    Some(
+        AuditEvent::new(AuditEventType::AuthFailed, address, client.last_request).with_detail(
+            format!(
+                "{} further requests were rejected within {} seconds; the last because {}",
+                client.suppressed,
+                AGGREGATION_WINDOW.as_secs(),
+                client.last_detail
+            ),
+        ),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn failure(client: &str, request: &str) -> AuthFailure {
+        AuthFailure {
+            client: client.into(),
+            request: request.into(),
+            detail: "no credentials were provided",
+        }
+    }
+
+    /// Verify that a client's first failure is recorded immediately, its further failures within
+    /// the window are recorded as one event when the window ends, and other clients' failures
+    /// are recorded separately.
+    #[test]
+    fn test_aggregate_failures() {
+        let mut aggregator = FailureAggregator::default();
+        let start = Instant::now();
+
+        assert!(aggregator
+            .add(failure("10.0.0.1", "GET /admin/circuits"), start)
+            .is_some());
+        assert!(aggregator
+            .add(failure("10.0.0.1", "GET /admin/circuits"), start)
+            .is_none());
+        assert!(aggregator
+            .add(failure("10.0.0.1", "GET /registry/nodes"), start)
+            .is_none());
+        assert!(aggregator
+            .add(failure("10.0.0.2", "GET /admin/circuits"), start)
+            .is_some());
+
+        assert_eq!(aggregator.next_expiry(), Some(start + AGGREGATION_WINDOW));
+        assert!(aggregator.expire(start).is_empty());
+
+        let events = aggregator.expire(start + AGGREGATION_WINDOW);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].actor(), "10.0.0.1");
+        assert_eq!(events[0].target(), "GET /registry/nodes");
+        assert!(events[0].detail().starts_with("2 further requests"));
+        assert_eq!(aggregator.next_expiry(), None);
+
+        // A new window starts with the client's next failure
+        assert!(aggregator
+            .add(
+                failure("10.0.0.1", "GET /admin/circuits"),
+                start + AGGREGATION_WINDOW
+            )
+            .is_some());
+    }
+}
@@ -22,9 +22,19 @@ use actix_web::{
 };
 use futures::{Future, IntoFuture, Poll};
 
+#[cfg(feature = "audit")]
+use crate::rest_api::auth::actix::auth_failures::{AuthFailure, AuthFailureRecorder};
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::{AuthorizationHandler, PermissionMap};
-use crate::rest_api::auth::{authorize, identity::IdentityProvider, AuthorizationResult};
+#[cfg(feature = "rest-api-client-cert-auth")]
+use crate::rest_api::auth::client_cert::{ClientCertAuth, ClientCertificate};
+#[cfg(feature = "rest-api-proxy-auth")]
+use crate::rest_api::auth::proxy::ProxyAuth;
+use crate::rest_api::auth::{
+    authorize, authorize_identity,
+    identity::{Identity, IdentityProvider},
+    AuthorizationResult,
+};
 use crate::rest_api::ErrorResponse;
 #[cfg(feature = "authorization")]
 use crate::rest_api::Method;
@@ -33,9 +43,39 @@ pub struct AuthorizationMiddleware<S> {
     pub(super) identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     pub(super) authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub(super) proxy_auth: Option<ProxyAuth>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub(super) client_cert_auth: Option<ClientCertAuth>,
+    #[cfg(feature = "audit")]
+    pub(super) auth_failure_recorder: Option<AuthFailureRecorder>,
     pub(super) service: S,
 }
 
+#[cfg(feature = "audit")]
+impl<S> AuthorizationMiddleware<S> {
+    /// Records a request that was rejected as unauthorized in the audit log, if one is configured.
+    /// The client's identity is unknown, so the actor is the client's address.
+    fn record_auth_failure(&self, req: &ServiceRequest) {
+        if let Some(recorder) = &self.auth_failure_recorder {
+            let client = req
+                .peer_addr()
+                .map(|addr| addr.ip().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+            let detail = if req.headers().contains_key(header::AUTHORIZATION) {
+                "credentials were invalid or lacked the required permission"
+            } else {
+                "no credentials were provided"
+            };
+            recorder.record(AuthFailure {
+                client,
+                request: format!("{} {}", req.method(), req.path()),
+                detail,
+            });
+        }
+    }
+}
+
 impl<S, B> Service for AuthorizationMiddleware<S>
 where
     S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = ActixError>,
@@ -122,17 +162,65 @@ where
             }
         };
 
-        match authorize(
-            #[cfg(feature = "authorization")]
-            &method,
-            req.path(),
-            auth_header,
-            #[cfg(feature = "authorization")]
-            permission_map.get_ref(),
-            &self.identity_providers,
-            #[cfg(feature = "authorization")]
-            &self.authorization_handlers,
-        ) {
+        // The identity set by a trusted reverse proxy takes precedence over the Authorization
+        // header
+        #[cfg(feature = "rest-api-proxy-auth")]
+        let proxy_identity: Option<Identity> = self.proxy_auth.as_ref().and_then(|proxy_auth| {
+            proxy_auth.get_identity(
+                req.peer_addr().map(|addr| addr.ip()),
+                req.headers()
+                    .get(proxy_auth.header())
+                    .and_then(|value| value.to_str().ok()),
+            )
+        });
+        #[cfg(not(feature = "rest-api-proxy-auth"))]
+        let proxy_identity: Option<Identity> = None;
+
+        // When clients are authenticated by their certificates, the certificate is the only
+        // credential that is accepted; the outer option is only set in that case
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        let client_cert_identity: Option<Option<Identity>> =
+            self.client_cert_auth.as_ref().map(|client_cert_auth| {
+                client_cert_auth.get_identity(req.conn_data::<ClientCertificate>())
+            });
+        #[cfg(not(feature = "rest-api-client-cert-auth"))]
+        let client_cert_identity: Option<Option<Identity>> = None;
+
+        let authorization_result = match (client_cert_identity, proxy_identity) {
+            (Some(identity), _) => authorize_identity(
+                #[cfg(feature = "authorization")]
+                &method,
+                req.path(),
+                move || identity,
+                #[cfg(feature = "authorization")]
+                permission_map.get_ref(),
+                #[cfg(feature = "authorization")]
+                &self.authorization_handlers,
+            ),
+            (None, Some(identity)) => authorize_identity(
+                #[cfg(feature = "authorization")]
+                &method,
+                req.path(),
+                move || Some(identity),
+                #[cfg(feature = "authorization")]
+                permission_map.get_ref(),
+                #[cfg(feature = "authorization")]
+                &self.authorization_handlers,
+            ),
+            (None, None) => authorize(
+                #[cfg(feature = "authorization")]
+                &method,
+                req.path(),
+                auth_header,
+                #[cfg(feature = "authorization")]
+                permission_map.get_ref(),
+                &self.identity_providers,
+                #[cfg(feature = "authorization")]
+                &self.authorization_handlers,
+            ),
+        };
+
+        match authorization_result {
             AuthorizationResult::Authorized(identity) => {
                 debug!("Authenticated user {:?}", identity);
                 req.extensions_mut().insert(identity);
@@ -144,6 +232,9 @@ where
             ))]
             AuthorizationResult::NoAuthorizationNecessary => {}
             AuthorizationResult::Unauthorized => {
+                #[cfg(feature = "audit")]
+                self.record_auth_failure(&req);
+
                 return Box::new(
                     req.into_response(
                         HttpResponse::Unauthorized()
@@ -151,7 +242,7 @@ where
                             .into_body(),
                     )
                     .into_future(),
-                )
+                );
             }
             #[cfg(feature = "authorization")]
             AuthorizationResult::UnknownEndpoint => {
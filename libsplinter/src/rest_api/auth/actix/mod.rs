@@ -14,6 +14,8 @@
 
 //! Authorization middleware for the Actix REST API
 
+#[cfg(feature = "audit")]
+mod auth_failures;
 mod middleware;
 mod transform;
 
@@ -170,6 +172,96 @@ mod tests {
         assert_eq!(resp.status(), StatusCode::OK);
     }
 
+    /// Verifies that the authorization middleware uses the identity header set by a reverse proxy
+    /// for requests from a trusted address, and ignores it for requests from other addresses.
+    #[cfg(feature = "rest-api-proxy-auth")]
+    #[test]
+    fn auth_middleware_proxy_identity() {
+        use crate::rest_api::auth::proxy::ProxyAuth;
+
+        let auth_middleware = Authorization::new(
+            vec![],
+            #[cfg(feature = "authorization")]
+            vec![],
+        )
+        .with_proxy_auth(Some(ProxyAuth::new(
+            "X-Auth-Request-User".into(),
+            vec!["10.0.0.5".parse().expect("Failed to parse address")],
+        )));
+
+        let app = App::new().wrap(auth_middleware).route(
+            "/",
+            web::get().to(|req: HttpRequest| {
+                if req.extensions().get() == Some(&Identity::User("alice".into())) {
+                    HttpResponse::Ok()
+                } else {
+                    HttpResponse::InternalServerError()
+                }
+            }),
+        );
+
+        #[cfg(feature = "authorization")]
+        let app = {
+            let mut permission_map = PermissionMap::<Method>::new();
+            permission_map.add_permission(Method::Get, "/", Permission::AllowAuthenticated);
+            app.data(permission_map)
+        };
+
+        let mut service = test::init_service(app);
+
+        let req = test::TestRequest::with_uri("/")
+            .header("X-Auth-Request-User", "alice")
+            .peer_addr("10.0.0.5:8000".parse().expect("Failed to parse address"))
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let req = test::TestRequest::with_uri("/")
+            .header("X-Auth-Request-User", "alice")
+            .peer_addr("10.0.0.6:8000".parse().expect("Failed to parse address"))
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    /// Verifies that the authorization middleware ignores the Authorization header when clients
+    /// are authenticated by their certificates, so a request without a mapped certificate is
+    /// unauthorized even if its bearer token is valid.
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    #[test]
+    fn auth_middleware_client_cert_refuses_bearer_tokens() {
+        use crate::rest_api::auth::client_cert::{CertificateMatcher, ClientCertAuth};
+
+        let auth_middleware = Authorization::new(
+            vec![Box::new(AlwaysAcceptIdentityProvider)],
+            #[cfg(feature = "authorization")]
+            vec![],
+        )
+        .with_client_cert_auth(Some(ClientCertAuth::new(vec![(
+            CertificateMatcher::SubjectAltName("alice.example.com".into()),
+            "alice".into(),
+        )])));
+
+        let app = App::new()
+            .wrap(auth_middleware)
+            .route("/", web::get().to(|| HttpResponse::Ok()));
+
+        #[cfg(feature = "authorization")]
+        let app = {
+            let mut permission_map = PermissionMap::<Method>::new();
+            permission_map.add_permission(Method::Get, "/", Permission::AllowAuthenticated);
+            app.data(permission_map)
+        };
+
+        let mut service = test::init_service(app);
+
+        let req = test::TestRequest::with_uri("/")
+            .header("Authorization", "Bearer token")
+            .to_request();
+        let resp = test::block_on(service.call(req)).unwrap();
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+    }
+
     /// An identity provider that always returns `Ok(Some(_))`
     #[derive(Clone)]
     struct AlwaysAcceptIdentityProvider;
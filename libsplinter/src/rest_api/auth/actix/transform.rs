@@ -16,9 +16,17 @@ use actix_web::dev::*;
 use actix_web::Error as ActixError;
 use futures::future::{ok, FutureResult};
 
+#[cfg(feature = "audit")]
+use crate::audit::store::AuditStore;
+#[cfg(feature = "audit")]
+use crate::rest_api::auth::actix::auth_failures::AuthFailureRecorder;
 use crate::rest_api::auth::actix::AuthorizationMiddleware;
 #[cfg(feature = "authorization")]
 use crate::rest_api::auth::authorization::AuthorizationHandler;
+#[cfg(feature = "rest-api-client-cert-auth")]
+use crate::rest_api::auth::client_cert::ClientCertAuth;
+#[cfg(feature = "rest-api-proxy-auth")]
+use crate::rest_api::auth::proxy::ProxyAuth;
 use crate::rest_api::auth::IdentityProvider;
 
 /// Wrapper for the authorization middleware
@@ -27,6 +35,12 @@ pub struct Authorization {
     identity_providers: Vec<Box<dyn IdentityProvider>>,
     #[cfg(feature = "authorization")]
     authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    proxy_auth: Option<ProxyAuth>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    client_cert_auth: Option<ClientCertAuth>,
+    #[cfg(feature = "audit")]
+    auth_failure_recorder: Option<AuthFailureRecorder>,
 }
 
 impl Authorization {
@@ -40,8 +54,46 @@ impl Authorization {
             identity_providers,
             #[cfg(feature = "authorization")]
             authorization_handlers,
+            #[cfg(feature = "rest-api-proxy-auth")]
+            proxy_auth: None,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            client_cert_auth: None,
+            #[cfg(feature = "audit")]
+            auth_failure_recorder: None,
         }
     }
+
+    /// Trusts the identity header set by an authenticating reverse proxy
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub fn with_proxy_auth(mut self, proxy_auth: Option<ProxyAuth>) -> Self {
+        self.proxy_auth = proxy_auth;
+        self
+    }
+
+    /// Authenticates clients by their TLS client certificates, ignoring any other credentials
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn with_client_cert_auth(mut self, client_cert_auth: Option<ClientCertAuth>) -> Self {
+        self.client_cert_auth = client_cert_auth;
+        self
+    }
+
+    /// Records requests that are rejected because the client could not be authorized in the given
+    /// audit log. Failures are recorded by a separate thread, and a client's repeated failures are
+    /// aggregated.
+    #[cfg(feature = "audit")]
+    pub fn with_audit_store(mut self, audit_store: Option<Box<dyn AuditStore>>) -> Self {
+        self.auth_failure_recorder =
+            audit_store.and_then(
+                |audit_store| match AuthFailureRecorder::start(audit_store) {
+                    Ok(recorder) => Some(recorder),
+                    Err(err) => {
+                        error!("Unable to record authorization failures: {}", err);
+                        None
+                    }
+                },
+            );
+        self
+    }
 }
 
 impl<S, B> Transform<S> for Authorization
@@ -62,6 +114,12 @@ where
             identity_providers: self.identity_providers.clone(),
             #[cfg(feature = "authorization")]
             authorization_handlers: self.authorization_handlers.clone(),
+            #[cfg(feature = "rest-api-proxy-auth")]
+            proxy_auth: self.proxy_auth.clone(),
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            client_cert_auth: self.client_cert_auth.clone(),
+            #[cfg(feature = "audit")]
+            auth_failure_recorder: self.auth_failure_recorder.clone(),
             service,
         })
     }
@@ -0,0 +1,53 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::error::{InternalError, InvalidArgumentError};
+
+/// Errors that may occur when the bootstrap token is exchanged.
+#[derive(Debug)]
+pub enum BootstrapError {
+    /// The token does not match, or has already been exchanged
+    InvalidToken,
+    InvalidArgument(InvalidArgumentError),
+    Internal(InternalError),
+}
+
+impl Error for BootstrapError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BootstrapError::InvalidToken => None,
+            BootstrapError::InvalidArgument(err) => Some(err),
+            BootstrapError::Internal(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for BootstrapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BootstrapError::InvalidToken => f.write_str("bootstrap token is not valid"),
+            BootstrapError::InvalidArgument(err) => f.write_str(&err.to_string()),
+            BootstrapError::Internal(err) => f.write_str(&err.to_string()),
+        }
+    }
+}
+
+impl From<InternalError> for BootstrapError {
+    fn from(err: InternalError) -> Self {
+        BootstrapError::Internal(err)
+    }
+}
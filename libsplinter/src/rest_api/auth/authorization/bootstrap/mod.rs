@@ -0,0 +1,349 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A one-time token that grants admin access to the first identity that presents it, so that a
+//! node started without any authorization can be set up through the REST API.
+
+mod error;
+mod routes;
+
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use rand::Rng;
+
+use crate::error::{InternalError, InvalidArgumentError};
+use crate::hex::to_hex;
+#[cfg(feature = "authorization-handler-rbac")]
+use crate::rbac::store::{
+    AssignmentBuilder, Identity as RBACIdentity, RoleBasedAuthorizationStore, ADMIN_ROLE_ID,
+};
+
+pub use error::BootstrapError;
+
+/// The number of random bytes in a token
+const TOKEN_BYTES: usize = 32;
+
+/// The identity that is granted admin access in exchange for the bootstrap token
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BootstrapIdentity {
+    /// A public key, which is added to the allow keys file, if there is one, or otherwise
+    /// assigned the `admin` role
+    Key(String),
+    /// A Biome user ID, which is assigned the `admin` role
+    User(String),
+}
+
+/// A one-time admin token for setting up the authorization of a new node.
+///
+/// A token is only generated if no identity has been granted access by the allow keys file or the
+/// RBAC store. It is logged, and written to the token file with permissions that only allow the
+/// node's user to read it. The token can be exchanged once, with `POST /authorization/bootstrap`,
+/// for admin access for a key or user; after that, or once access has been granted some other
+/// way, the token is discarded and its file removed.
+///
+/// The handle is cheap to clone; all clones share the same token.
+#[derive(Clone)]
+pub struct BootstrapToken {
+    token_file: PathBuf,
+    token: Arc<Mutex<Option<String>>>,
+    allow_keys_file: Option<PathBuf>,
+    #[cfg(feature = "authorization-handler-rbac")]
+    rbac_store: Option<Box<dyn RoleBasedAuthorizationStore>>,
+}
+
+impl BootstrapToken {
+    /// Constructs a `BootstrapToken`, which has no token until one is generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `token_file` - The file the token is written to
+    pub fn new<P: Into<PathBuf>>(token_file: P) -> Self {
+        Self {
+            token_file: token_file.into(),
+            token: Arc::new(Mutex::new(None)),
+            allow_keys_file: None,
+            #[cfg(feature = "authorization-handler-rbac")]
+            rbac_store: None,
+        }
+    }
+
+    /// Checks the given allow keys file for keys, and adds keys that are exchanged for the token
+    /// to it.
+    pub fn with_allow_keys_file<P: Into<PathBuf>>(mut self, allow_keys_file: P) -> Self {
+        self.allow_keys_file = Some(allow_keys_file.into());
+        self
+    }
+
+    /// Checks the given RBAC store for assignments, and assigns the `admin` role to identities
+    /// that are exchanged for the token.
+    #[cfg(feature = "authorization-handler-rbac")]
+    pub fn with_rbac_store(mut self, rbac_store: Box<dyn RoleBasedAuthorizationStore>) -> Self {
+        self.rbac_store = Some(rbac_store);
+        self
+    }
+
+    /// Generates a token if no identity has been granted access, returning whether one was
+    /// generated. The token replaces any token left by a previous run.
+    pub fn generate_if_unconfigured(&self) -> Result<bool, InternalError> {
+        if self.is_authorization_configured()? {
+            self.remove_token_file();
+            return Ok(false);
+        }
+
+        let token = to_hex(&rand::thread_rng().gen::<[u8; TOKEN_BYTES]>());
+        self.write_token_file(&token)?;
+        warn!(
+            "No authorization is configured; exchange the bootstrap admin token {} (also \
+            written to {}) with POST /authorization/bootstrap to grant admin access",
+            token,
+            self.token_file.display()
+        );
+        *self.lock()? = Some(token);
+        Ok(true)
+    }
+
+    /// Returns true if a token has been generated and not yet exchanged.
+    pub fn is_active(&self) -> Result<bool, InternalError> {
+        Ok(self.lock()?.is_some())
+    }
+
+    /// Grants admin access to the identity, if the token matches, and discards the token.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidToken` if the token does not match, has already been exchanged, or if
+    /// access has been granted since the token was generated. Returns `InvalidArgument` if a
+    /// user is given and there is no RBAC store, or a key is given and there is neither an allow
+    /// keys file nor an RBAC store.
+    pub fn exchange(&self, token: &str, identity: BootstrapIdentity) -> Result<(), BootstrapError> {
+        let mut current = self.lock()?;
+        match current.as_deref() {
+            Some(current) if constant_time_eq(current.as_bytes(), token.as_bytes()) => (),
+            _ => return Err(BootstrapError::InvalidToken),
+        }
+
+        if self.is_authorization_configured()? {
+            *current = None;
+            self.remove_token_file();
+            return Err(BootstrapError::InvalidToken);
+        }
+
+        self.grant(&identity)?;
+        info!("Exchanged the bootstrap admin token for {:?}", identity);
+        *current = None;
+        self.remove_token_file();
+        Ok(())
+    }
+
+    fn grant(&self, identity: &BootstrapIdentity) -> Result<(), BootstrapError> {
+        match (identity, &self.allow_keys_file) {
+            (BootstrapIdentity::Key(key), Some(allow_keys_file)) => {
+                if key.is_empty() || key.contains(char::is_whitespace) {
+                    return Err(BootstrapError::InvalidArgument(InvalidArgumentError::new(
+                        "key",
+                        "must be a public key",
+                    )));
+                }
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(allow_keys_file)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                writeln!(file, "{}", key)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                Ok(())
+            }
+            #[cfg(feature = "authorization-handler-rbac")]
+            (identity, _) if self.rbac_store.is_some() => {
+                let rbac_identity = match identity {
+                    BootstrapIdentity::Key(key) => RBACIdentity::Key(key.clone()),
+                    BootstrapIdentity::User(user_id) => RBACIdentity::User(user_id.clone()),
+                };
+                let assignment = AssignmentBuilder::new()
+                    .with_identity(rbac_identity)
+                    .with_roles(vec![ADMIN_ROLE_ID.to_string()])
+                    .build()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                self.rbac_store
+                    .as_ref()
+                    .expect("RBAC store was checked")
+                    .add_assignment(assignment)
+                    .map_err(|err| InternalError::from_source(Box::new(err)).into())
+            }
+            (BootstrapIdentity::Key(_), _) => Err(BootstrapError::InvalidArgument(
+                InvalidArgumentError::new("key", "keys cannot be granted access by this node"),
+            )),
+            (BootstrapIdentity::User(_), _) => Err(BootstrapError::InvalidArgument(
+                InvalidArgumentError::new("user_id", "users cannot be granted access by this node"),
+            )),
+        }
+    }
+
+    /// Returns true if the allow keys file has a key, or the RBAC store has an assignment.
+    fn is_authorization_configured(&self) -> Result<bool, InternalError> {
+        if let Some(allow_keys_file) = &self.allow_keys_file {
+            if has_keys(allow_keys_file)? {
+                return Ok(true);
+            }
+        }
+
+        #[cfg(feature = "authorization-handler-rbac")]
+        if let Some(rbac_store) = &self.rbac_store {
+            if rbac_store
+                .list_assignments()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?
+                .next()
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn write_token_file(&self, token: &str) -> Result<(), InternalError> {
+        // The file is created anew so that it never has wider permissions than the token needs
+        self.remove_token_file();
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&self.token_file).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!(
+                    "Unable to write bootstrap token file {}",
+                    self.token_file.display()
+                ),
+            )
+        })?;
+        writeln!(file, "{}", token).map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn remove_token_file(&self) {
+        if self.token_file.exists() {
+            if let Err(err) = fs::remove_file(&self.token_file) {
+                error!(
+                    "Unable to remove bootstrap token file {}: {}",
+                    self.token_file.display(),
+                    err
+                );
+            }
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<Option<String>>, InternalError> {
+        self.token
+            .lock()
+            .map_err(|_| InternalError::with_message("Bootstrap token lock was poisoned".into()))
+    }
+}
+
+/// Returns true if the allow keys file exists and has at least one key.
+fn has_keys(allow_keys_file: &Path) -> Result<bool, InternalError> {
+    if !allow_keys_file.is_file() {
+        return Ok(false);
+    }
+    let file =
+        fs::File::open(allow_keys_file).map_err(|err| InternalError::from_source(Box::new(err)))?;
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|err| InternalError::from_source(Box::new(err)))?;
+        if !line.trim().is_empty() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Compares two byte strings in time that depends only on their lengths, so that a token cannot be
+/// guessed one byte at a time.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a token is generated when the allow keys file has no keys, that it can be
+    /// exchanged once for a key, which is added to the file, and that the token file is removed.
+    #[test]
+    fn test_exchange_for_allow_key() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let token_file = dir.path().join("bootstrap_token");
+        let allow_keys_file = dir.path().join("allow_keys");
+
+        let bootstrap = BootstrapToken::new(&token_file).with_allow_keys_file(&allow_keys_file);
+        assert!(bootstrap
+            .generate_if_unconfigured()
+            .expect("Failed to generate token"));
+        assert!(bootstrap.is_active().expect("Failed to check token"));
+
+        let token = fs::read_to_string(&token_file).expect("Failed to read token file");
+        let token = token.trim();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&token_file)
+                .expect("Failed to read token file metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        assert!(matches!(
+            bootstrap.exchange("not the token", BootstrapIdentity::Key("abcd".into())),
+            Err(BootstrapError::InvalidToken)
+        ));
+
+        bootstrap
+            .exchange(token, BootstrapIdentity::Key("abcd".into()))
+            .expect("Failed to exchange token");
+        assert_eq!(
+            fs::read_to_string(&allow_keys_file).expect("Failed to read allow keys file"),
+            "abcd\n"
+        );
+        assert!(!token_file.exists());
+        assert!(!bootstrap.is_active().expect("Failed to check token"));
+
+        assert!(matches!(
+            bootstrap.exchange(token, BootstrapIdentity::Key("efgh".into())),
+            Err(BootstrapError::InvalidToken)
+        ));
+    }
+
+    /// Verify that no token is generated if the allow keys file already has a key, and that a
+    /// token left by a previous run is removed.
+    #[test]
+    fn test_configured() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let token_file = dir.path().join("bootstrap_token");
+        let allow_keys_file = dir.path().join("allow_keys");
+        fs::write(&allow_keys_file, "abcd\n").expect("Failed to write allow keys file");
+        fs::write(&token_file, "stale\n").expect("Failed to write token file");
+
+        let bootstrap = BootstrapToken::new(&token_file).with_allow_keys_file(&allow_keys_file);
+        assert!(!bootstrap
+            .generate_if_unconfigured()
+            .expect("Failed to check configuration"));
+        assert!(!bootstrap.is_active().expect("Failed to check token"));
+        assert!(!token_file.exists());
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `POST /authorization/bootstrap` endpoint for exchanging the bootstrap
+//! admin token for admin access.
+
+use actix_web::{web, Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use crate::rest_api::{
+    actix_web_1::{into_bytes, Method, ProtocolVersionRangeGuard, Resource},
+    auth::authorization::bootstrap::{BootstrapError, BootstrapIdentity, BootstrapToken},
+    auth::authorization::Permission,
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+use super::resources::PostBootstrapRequest;
+
+const AUTHORIZATION_BOOTSTRAP_MIN: u32 = 2;
+
+pub fn make_bootstrap_resource(bootstrap_token: BootstrapToken) -> Resource {
+    Resource::build("/authorization/bootstrap")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            AUTHORIZATION_BOOTSTRAP_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        // The token is the client's credential, so the request is not authorized otherwise
+        .add_method(
+            Method::Post,
+            Permission::AllowUnauthenticated,
+            move |_, payload| post_bootstrap(payload, bootstrap_token.clone()),
+        )
+}
+
+fn post_bootstrap(
+    payload: web::Payload,
+    bootstrap_token: BootstrapToken,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(into_bytes(payload).and_then(move |bytes| {
+        let request = match serde_json::from_slice::<PostBootstrapRequest>(&bytes) {
+            Ok(request) => request,
+            Err(err) => {
+                return HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Failed to parse payload: {}",
+                        err
+                    )))
+                    .into_future()
+            }
+        };
+
+        let identity = match (request.key, request.user_id) {
+            (Some(key), None) => BootstrapIdentity::Key(key),
+            (None, Some(user_id)) => BootstrapIdentity::User(user_id),
+            _ => {
+                return HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(
+                        "Exactly one of key and user_id must be given",
+                    ))
+                    .into_future()
+            }
+        };
+
+        match bootstrap_token.exchange(&request.token, identity) {
+            Ok(()) => HttpResponse::Ok().finish().into_future(),
+            Err(BootstrapError::InvalidToken) => HttpResponse::Unauthorized()
+                .json(ErrorResponse::unauthorized())
+                .into_future(),
+            Err(BootstrapError::InvalidArgument(err)) => HttpResponse::BadRequest()
+                .json(ErrorResponse::bad_request(&err.to_string()))
+                .into_future(),
+            Err(BootstrapError::Internal(err)) => {
+                error!("Unable to exchange bootstrap token: {}", err);
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future()
+            }
+        }
+    }))
+}
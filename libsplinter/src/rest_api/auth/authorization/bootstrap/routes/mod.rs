@@ -0,0 +1,47 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API endpoints for the bootstrap admin token
+
+#[cfg(feature = "rest-api-actix-web-1")]
+mod actix;
+#[cfg(feature = "rest-api-actix-web-1")]
+mod resources;
+
+use crate::rest_api::actix_web_1::{Resource, RestResourceProvider};
+
+use super::BootstrapToken;
+
+/// The `BootstrapToken` provides the following endpoint as a REST API resource:
+///
+/// * `POST /authorization/bootstrap` - Exchange the token for admin access for a key or user
+///
+/// This endpoint is only available if the following REST API backend feature is enabled:
+///
+/// * `rest-api-actix`
+impl RestResourceProvider for BootstrapToken {
+    fn resources(&self) -> Vec<Resource> {
+        // Allowing unused_mut because resources must be mutable if feature rest-api-actix is
+        // enabled
+        #[allow(unused_mut)]
+        let mut resources = Vec::new();
+
+        #[cfg(feature = "rest-api-actix-web-1")]
+        {
+            resources.push(actix::make_bootstrap_resource(self.clone()));
+        }
+
+        resources
+    }
+}
@@ -0,0 +1,24 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides resources for the bootstrap admin token's REST API endpoint
+
+/// The token and the identity it is exchanged for; exactly one of `key` and `user_id` must be
+/// given.
+#[derive(Deserialize)]
+pub struct PostBootstrapRequest {
+    pub token: String,
+    pub key: Option<String>,
+    pub user_id: Option<String>,
+}
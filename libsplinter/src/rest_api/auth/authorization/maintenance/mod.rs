@@ -236,6 +236,14 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_inherited_roles(
+            &self,
+            _role_id: &str,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn list_roles(
             &self,
         ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
@@ -318,6 +326,14 @@ mod tests {
             unimplemented!()
         }
 
+        fn import_roles_and_assignments(
+            &self,
+            _roles: Vec<Role>,
+            _assignments: Vec<Assignment>,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
             Box::new(self.clone())
         }
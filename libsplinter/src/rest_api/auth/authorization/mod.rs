@@ -17,6 +17,8 @@
 #[cfg(feature = "authorization-handler-allow-keys")]
 pub mod allow_keys;
 mod authorization_handler_result;
+#[cfg(feature = "authorization-bootstrap")]
+pub mod bootstrap;
 #[cfg(feature = "authorization-handler-maintenance")]
 pub mod maintenance;
 mod permission;
@@ -0,0 +1,193 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::error::InternalError;
+
+use crate::rest_api::auth::{
+    authorization::{AuthorizationHandler, AuthorizationHandlerResult},
+    identity::Identity,
+};
+
+use crate::rbac::store::{RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
+
+use super::handler::role_has_permission;
+
+/// An authorization handler that grants the roles configured for users identified by their
+/// client certificates.
+///
+/// This handler determines if a user has a requested permission by looking up the role IDs
+/// configured for the user. If one of these roles contains the permission, then the user is
+/// allowed access. If not, the handler defers to the next handler in the chain.
+///
+/// It currently does not deny any permissions.
+pub struct ClientCertRoleAuthorizationHandler {
+    user_roles: HashMap<String, Vec<String>>,
+    role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+}
+
+impl ClientCertRoleAuthorizationHandler {
+    /// Construct a new client certificate role authorization handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_roles` - Pairs of users, as mapped from their client certificates, and the role IDs
+    ///   granted to them
+    /// * `role_based_auth_store` - The store that contains the roles' permissions
+    pub fn new(
+        user_roles: Vec<(String, String)>,
+        role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+    ) -> Self {
+        let mut user_role_map: HashMap<String, Vec<String>> = HashMap::new();
+        for (user, role) in user_roles {
+            user_role_map.entry(user).or_default().push(role);
+        }
+
+        Self {
+            user_roles: user_role_map,
+            role_based_auth_store,
+        }
+    }
+}
+
+impl AuthorizationHandler for ClientCertRoleAuthorizationHandler {
+    fn has_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        let role_ids = match identity {
+            Identity::User(user) => match self.user_roles.get(user) {
+                Some(role_ids) => role_ids,
+                None => return Ok(AuthorizationHandlerResult::Continue),
+            },
+            _ => return Ok(AuthorizationHandlerResult::Continue),
+        };
+
+        for role_id in role_ids {
+            if role_id == ADMIN_ROLE_ID {
+                return Ok(AuthorizationHandlerResult::Allow);
+            }
+
+            let role = self
+                .role_based_auth_store
+                .get_role(role_id)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            let has_permission = match role {
+                Some(role) => {
+                    role_has_permission(&*self.role_based_auth_store, &role, permission_id)?
+                }
+                None => false,
+            };
+            if has_permission {
+                return Ok(AuthorizationHandlerResult::Allow);
+            }
+        }
+
+        Ok(AuthorizationHandlerResult::Continue)
+    }
+
+    fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+        Box::new(ClientCertRoleAuthorizationHandler {
+            user_roles: self.user_roles.clone(),
+            role_based_auth_store: self.role_based_auth_store.clone_box(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use crate::rbac::store::{DieselRoleBasedAuthorizationStore, RoleBuilder};
+
+    use crate::migrations::run_sqlite_migrations;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    /// This test checks that a user with a configured role that has the permission is allowed,
+    /// that a permission outside of the role returns Continue, and that other users return
+    /// Continue.
+    #[test]
+    fn allow_user_with_configured_role() {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let handler = ClientCertRoleAuthorizationHandler::new(
+            vec![("alice".into(), "test-role-1".into())],
+            role_based_auth_store,
+        );
+
+        let result = handler
+            .has_permission(&Identity::User("alice".into()), "a")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_permission(&Identity::User("alice".into()), "z")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission(&Identity::User("bob".into()), "a")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission(&Identity::Key("alice".into()), "a")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// This test checks that a user with the admin role is allowed any permission.
+    #[test]
+    fn allow_user_with_admin_role() {
+        let handler = ClientCertRoleAuthorizationHandler::new(
+            vec![("alice".into(), ADMIN_ROLE_ID.into())],
+            create_role_based_authorization_store(),
+        );
+
+        let result = handler
+            .has_permission(&Identity::User("alice".into()), "perm")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+    }
+
+    /// Creates a RoleBasedAuthorizationStore
+    fn create_role_based_authorization_store() -> Box<dyn RoleBasedAuthorizationStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselRoleBasedAuthorizationStore::new(pool))
+    }
+}
@@ -19,13 +19,15 @@ use crate::rest_api::auth::{
     identity::Identity,
 };
 
-use crate::rbac::store::{RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
+use crate::rbac::store::{Role, RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
 
 /// A Role-based authorization handler.
 ///
 /// This handler determines if an identity has a requested permission by examining the roles that
 /// it has been assigned.  If one of the identity's assigned roles contains the permission, then
 /// the identity is allowed access. If not, the handler defers to the next handler in the chain.
+/// A role also contains the permissions of the roles it inherits from. An expired assignment does
+/// not grant any permissions.
 ///
 /// It currently does not deny any permissions.
 pub struct RoleBasedAuthorizationHandler {
@@ -48,16 +50,28 @@ impl AuthorizationHandler for RoleBasedAuthorizationHandler {
         permission_id: &str,
     ) -> Result<AuthorizationHandlerResult, InternalError> {
         match identity.into() {
-            Some(identity) => Ok(self
-                .role_based_auth_store
-                .get_assigned_roles(&identity)
-                .map_err(|err| InternalError::from_source(Box::new(err)))?
-                .find(|role| {
-                    role.id() == ADMIN_ROLE_ID
-                        || role.permissions().iter().any(|perm| perm == permission_id)
-                })
-                .map(|_| AuthorizationHandlerResult::Allow)
-                .unwrap_or(AuthorizationHandlerResult::Continue)),
+            Some(identity) => {
+                let expired = self
+                    .role_based_auth_store
+                    .get_assignment(&identity)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?
+                    .map(|assignment| assignment.is_expired())
+                    .unwrap_or(false);
+                if expired {
+                    return Ok(AuthorizationHandlerResult::Continue);
+                }
+
+                let roles = self
+                    .role_based_auth_store
+                    .get_assigned_roles(&identity)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                for role in roles {
+                    if role_has_permission(&*self.role_based_auth_store, &role, permission_id)? {
+                        return Ok(AuthorizationHandlerResult::Allow);
+                    }
+                }
+                Ok(AuthorizationHandlerResult::Continue)
+            }
             None => Ok(AuthorizationHandlerResult::Continue),
         }
     }
@@ -69,10 +83,37 @@ impl AuthorizationHandler for RoleBasedAuthorizationHandler {
     }
 }
 
+/// Returns whether the given role, or one of the roles it inherits from, is the admin role or
+/// contains the permission.
+pub(super) fn role_has_permission(
+    role_based_auth_store: &dyn RoleBasedAuthorizationStore,
+    role: &Role,
+    permission_id: &str,
+) -> Result<bool, InternalError> {
+    let grants_permission = |role: &Role| {
+        role.id() == ADMIN_ROLE_ID || role.permissions().iter().any(|perm| perm == permission_id)
+    };
+
+    if grants_permission(role) {
+        return Ok(true);
+    }
+
+    if role.parents().is_empty() {
+        return Ok(false);
+    }
+
+    Ok(role_based_auth_store
+        .get_inherited_roles(role.id())
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        .any(|inherited_role| grants_permission(&inherited_role)))
+}
+
 #[cfg(all(test, feature = "sqlite",))]
 mod tests {
     use super::*;
 
+    use std::time::{Duration, SystemTime};
+
     use crate::rbac::store::{
         AssignmentBuilder, DieselRoleBasedAuthorizationStore, Identity as StoreIdentity,
         RoleBuilder,
@@ -143,6 +184,106 @@ mod tests {
         test_continue_identity_with_no_assignment(Identity::User("some-user-id".into()));
     }
 
+    /// This test checks that an identity is allowed the permissions of the roles its assigned role
+    /// inherits from, including indirectly, but not the permissions of unrelated roles.
+    #[test]
+    fn allow_identity_with_inherited_role() {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        for (id, permissions, parents) in [
+            ("circuit-reader", vec!["circuit.read"], vec![]),
+            (
+                "circuit-admin",
+                vec!["circuit.write"],
+                vec!["circuit-reader"],
+            ),
+            ("operator", vec![], vec!["circuit-admin"]),
+            ("registry-reader", vec!["registry.read"], vec![]),
+        ] {
+            let role = RoleBuilder::new()
+                .with_id(id.into())
+                .with_display_name(id.into())
+                .with_permissions(permissions.into_iter().map(String::from).collect())
+                .with_parents(parents.into_iter().map(String::from).collect())
+                .build()
+                .expect("Unable to build role");
+            role_based_auth_store
+                .add_role(role)
+                .expect("Unable to add role");
+        }
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(StoreIdentity::User("some-user-id".into()))
+            .with_roles(vec!["operator".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store);
+        let identity = Identity::User("some-user-id".into());
+
+        for permission in &["circuit.read", "circuit.write"] {
+            let result = handler
+                .has_permission(&identity, permission)
+                .expect("Should have returned an auth result");
+            assert!(matches!(result, AuthorizationHandlerResult::Allow));
+        }
+
+        let result = handler
+            .has_permission(&identity, "registry.read")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// This test checks that an identity whose assignment has expired is not allowed the
+    /// permissions of its roles, while an assignment that expires in the future still grants them.
+    #[test]
+    fn continue_identity_with_expired_assignment() {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        let role = RoleBuilder::new()
+            .with_id("test-role".into())
+            .with_display_name("Test Role".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        for (user, expires_at) in [
+            (
+                "former-contractor",
+                SystemTime::now() - Duration::from_secs(60),
+            ),
+            ("contractor", SystemTime::now() + Duration::from_secs(3600)),
+        ] {
+            let assignment = AssignmentBuilder::new()
+                .with_identity(StoreIdentity::User(user.into()))
+                .with_roles(vec!["test-role".to_string()])
+                .with_expires_at(expires_at)
+                .build()
+                .expect("Unable to build assignment");
+            role_based_auth_store
+                .add_assignment(assignment)
+                .expect("Unable to add assignment");
+        }
+
+        let handler = RoleBasedAuthorizationHandler::new(role_based_auth_store);
+
+        let result = handler
+            .has_permission(&Identity::User("former-contractor".into()), "a")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission(&Identity::User("contractor".into()), "a")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+    }
+
     #[test]
     fn continue_custom_identity() {
         let role_based_auth_store = create_role_based_authorization_store();
@@ -0,0 +1,231 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+
+use crate::error::InternalError;
+
+use crate::rest_api::auth::{
+    authorization::{AuthorizationHandler, AuthorizationHandlerResult},
+    identity::{ldap::LdapUserGroups, Identity},
+};
+
+use crate::rbac::store::{RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
+
+use super::handler::role_has_permission;
+
+/// An authorization handler that grants the roles mapped to a user's LDAP groups.
+///
+/// This handler determines if an LDAP user has a requested permission by mapping the groups the
+/// user belongs to (as recorded by an `LdapIdentityProvider`) to role IDs. If one of these roles
+/// contains the permission, then the user is allowed access. If not, the handler defers to the
+/// next handler in the chain.
+///
+/// It currently does not deny any permissions.
+pub struct LdapGroupAuthorizationHandler {
+    user_groups: LdapUserGroups,
+    group_roles: HashMap<String, Vec<String>>,
+    role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+}
+
+impl LdapGroupAuthorizationHandler {
+    /// Construct a new LDAP group authorization handler.
+    ///
+    /// # Arguments
+    ///
+    /// * `user_groups` - The groups of the users authenticated by the LDAP identity provider
+    /// * `group_roles` - Pairs of LDAP group names and the role IDs granted to their members
+    /// * `role_based_auth_store` - The store that contains the roles' permissions
+    pub fn new(
+        user_groups: LdapUserGroups,
+        group_roles: Vec<(String, String)>,
+        role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+    ) -> Self {
+        let mut group_role_map: HashMap<String, Vec<String>> = HashMap::new();
+        for (group, role) in group_roles {
+            group_role_map.entry(group).or_default().push(role);
+        }
+
+        Self {
+            user_groups,
+            group_roles: group_role_map,
+            role_based_auth_store,
+        }
+    }
+}
+
+impl AuthorizationHandler for LdapGroupAuthorizationHandler {
+    fn has_permission(
+        &self,
+        identity: &Identity,
+        permission_id: &str,
+    ) -> Result<AuthorizationHandlerResult, InternalError> {
+        let username = match identity {
+            Identity::User(username) => username,
+            _ => return Ok(AuthorizationHandlerResult::Continue),
+        };
+
+        let groups = match self.user_groups.get(username)? {
+            Some(groups) => groups,
+            None => return Ok(AuthorizationHandlerResult::Continue),
+        };
+
+        for role_id in groups
+            .iter()
+            .filter_map(|group| self.group_roles.get(group))
+            .flatten()
+        {
+            if role_id == ADMIN_ROLE_ID {
+                return Ok(AuthorizationHandlerResult::Allow);
+            }
+
+            let role = self
+                .role_based_auth_store
+                .get_role(role_id)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            let has_permission = match role {
+                Some(role) => {
+                    role_has_permission(&*self.role_based_auth_store, &role, permission_id)?
+                }
+                None => false,
+            };
+            if has_permission {
+                return Ok(AuthorizationHandlerResult::Allow);
+            }
+        }
+
+        Ok(AuthorizationHandlerResult::Continue)
+    }
+
+    fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+        Box::new(LdapGroupAuthorizationHandler {
+            user_groups: self.user_groups.clone(),
+            group_roles: self.group_roles.clone(),
+            role_based_auth_store: self.role_based_auth_store.clone_box(),
+        })
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use crate::rbac::store::{DieselRoleBasedAuthorizationStore, RoleBuilder};
+
+    use crate::migrations::run_sqlite_migrations;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    /// This test checks that a user whose LDAP group maps to a role with the permission is
+    /// allowed, and that a permission outside of the role returns Continue.
+    #[test]
+    fn allow_user_with_mapped_group() {
+        let role_based_auth_store = create_role_based_authorization_store();
+
+        let role = RoleBuilder::new()
+            .with_id("test-role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string(), "b".to_string()])
+            .build()
+            .expect("Unable to build role");
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let user_groups = LdapUserGroups::default();
+        user_groups
+            .set("alice".into(), vec!["operators".into()])
+            .expect("Unable to set groups");
+
+        let handler = LdapGroupAuthorizationHandler::new(
+            user_groups,
+            vec![("operators".into(), "test-role-1".into())],
+            role_based_auth_store,
+        );
+
+        let result = handler
+            .has_permission(&Identity::User("alice".into()), "a")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+
+        let result = handler
+            .has_permission(&Identity::User("alice".into()), "z")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// This test checks that a user whose LDAP group maps to the admin role is allowed any
+    /// permission.
+    #[test]
+    fn allow_user_with_admin_group() {
+        let user_groups = LdapUserGroups::default();
+        user_groups
+            .set("alice".into(), vec!["admins".into()])
+            .expect("Unable to set groups");
+
+        let handler = LdapGroupAuthorizationHandler::new(
+            user_groups,
+            vec![("admins".into(), ADMIN_ROLE_ID.into())],
+            create_role_based_authorization_store(),
+        );
+
+        let result = handler
+            .has_permission(&Identity::User("alice".into()), "perm")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Allow));
+    }
+
+    /// This test checks that users that were not authenticated through LDAP, or whose groups are
+    /// not mapped, return Continue.
+    #[test]
+    fn continue_unknown_user_or_group() {
+        let user_groups = LdapUserGroups::default();
+        user_groups
+            .set("bob".into(), vec!["guests".into()])
+            .expect("Unable to set groups");
+
+        let handler = LdapGroupAuthorizationHandler::new(
+            user_groups,
+            vec![("admins".into(), ADMIN_ROLE_ID.into())],
+            create_role_based_authorization_store(),
+        );
+
+        let result = handler
+            .has_permission(&Identity::User("alice".into()), "perm")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+
+        let result = handler
+            .has_permission(&Identity::User("bob".into()), "perm")
+            .expect("Should have returned an auth result");
+        assert!(matches!(result, AuthorizationHandlerResult::Continue));
+    }
+
+    /// Creates a RoleBasedAuthorizationStore
+    fn create_role_based_authorization_store() -> Box<dyn RoleBasedAuthorizationStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselRoleBasedAuthorizationStore::new(pool))
+    }
+}
@@ -12,7 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "rest-api-client-cert-auth")]
+mod client_cert;
 mod handler;
+#[cfg(feature = "ldap")]
+mod ldap;
 pub mod rest_api;
 
+#[cfg(feature = "rest-api-client-cert-auth")]
+pub use client_cert::ClientCertRoleAuthorizationHandler;
 pub use handler::RoleBasedAuthorizationHandler;
+#[cfg(feature = "ldap")]
+pub use ldap::LdapGroupAuthorizationHandler;
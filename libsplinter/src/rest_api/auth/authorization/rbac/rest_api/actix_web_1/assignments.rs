@@ -24,7 +24,7 @@ use crate::rest_api::{
     auth::authorization::rbac::rest_api::{
         resources::{
             assignments::{
-                AssignmentPayload, AssignmentResponse, AssignmentUpdatePayload,
+                from_secs, AssignmentPayload, AssignmentResponse, AssignmentUpdatePayload,
                 ListAssignmentsResponse,
             },
             PagingQuery,
@@ -36,11 +36,13 @@ use crate::rest_api::{
 };
 
 use super::error::SendableRoleBasedAuthorizationStoreError;
+use super::RbacAuditLog;
 
 const AUTHORIZATION_RBAC_ASSIGNMENTS_MIN: u32 = 1;
 
 pub fn make_assignments_resource(
     role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+    audit_log: RbacAuditLog,
 ) -> Resource {
     let list_store = role_based_auth_store.clone();
     let add_store = role_based_auth_store;
@@ -52,17 +54,24 @@ pub fn make_assignments_resource(
         .add_method(Method::Get, RBAC_READ_PERMISSION, move |r, _| {
             list_assignments(r, web::Data::new(list_store.clone()))
         })
-        .add_method(Method::Post, RBAC_WRITE_PERMISSION, move |_, p| {
-            add_assignment(p, web::Data::new(add_store.clone()))
+        .add_method(Method::Post, RBAC_WRITE_PERMISSION, move |r, p| {
+            add_assignment(
+                p,
+                web::Data::new(add_store.clone()),
+                audit_log.for_request(&r),
+            )
         })
 }
 
 pub fn make_assignment_resource(
     role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+    audit_log: RbacAuditLog,
 ) -> Resource {
     let get_store = role_based_auth_store.clone();
     let patch_store = role_based_auth_store.clone();
     let delete_store = role_based_auth_store;
+    let patch_audit_log = audit_log.clone();
+    let delete_audit_log = audit_log;
     Resource::build("/authorization/assignments/{identity_type}/{identity}")
         .add_request_guard(ProtocolVersionRangeGuard::new(
             AUTHORIZATION_RBAC_ASSIGNMENTS_MIN,
@@ -72,10 +81,12 @@ pub fn make_assignment_resource(
             get_assignment(r, web::Data::new(get_store.clone()))
         })
         .add_method(Method::Patch, RBAC_WRITE_PERMISSION, move |r, p| {
-            patch_assignment(r, p, web::Data::new(patch_store.clone()))
+            let audit_log = patch_audit_log.for_request(&r);
+            patch_assignment(r, p, web::Data::new(patch_store.clone()), audit_log)
         })
         .add_method(Method::Delete, RBAC_WRITE_PERMISSION, move |r, _| {
-            delete_assignment(r, web::Data::new(delete_store.clone()))
+            let audit_log = delete_audit_log.for_request(&r);
+            delete_assignment(r, web::Data::new(delete_store.clone()), audit_log)
         })
 }
 
@@ -134,6 +145,7 @@ fn list_assignments(
 fn add_assignment(
     payload: web::Payload,
     role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+    audit_log: RbacAuditLog,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     Box::new(
         payload
@@ -144,21 +156,25 @@ fn add_assignment(
             })
             .into_future()
             .and_then(move |body| {
-                let assignment_res: Result<Assignment, _> =
-                    serde_json::from_slice::<AssignmentPayload>(&body)
-                        .map_err(|err| err.to_string())
-                        .and_then(|assignment_payload| {
-                            assignment_payload
-                                .try_into()
-                                .map_err(|err: InvalidStateError| err.to_string())
-                        });
+                let assignment_res: Result<Assignment, _> = AssignmentPayload::from_json(&body)
+                    .and_then(|assignment_payload| {
+                        assignment_payload
+                            .try_into()
+                            .map_err(|err: InvalidStateError| err.to_string())
+                    });
 
                 match assignment_res {
                     Ok(assignment) => Box::new(
                         web::block(move || {
+                            let target = assignment_target(assignment.identity());
+                            let detail = format!(
+                                "assignment added with roles {}",
+                                assignment.roles().join(", ")
+                            );
                             role_based_auth_store
                                 .add_assignment(assignment)
                                 .map_err(SendableRoleBasedAuthorizationStoreError::from)
+                                .map(|_| audit_log.record(target, detail))
                         })
                         .then(|res| {
                             Ok(match res {
@@ -250,6 +266,7 @@ fn patch_assignment(
     req: HttpRequest,
     payload: web::Payload,
     role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+    audit_log: RbacAuditLog,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let identity = try_identity_from_req!(req);
     Box::new(
@@ -261,25 +278,30 @@ fn patch_assignment(
             })
             .into_future()
             .and_then(move |body| {
-                let assignment_update =
-                    match serde_json::from_slice::<AssignmentUpdatePayload>(&body) {
-                        Ok(assignment_update) => assignment_update,
-                        Err(err) => {
-                            return Box::new(
-                                HttpResponse::BadRequest()
-                                    .json(ErrorResponse::bad_request(&format!(
-                                        "Invalid assignment payload: {}",
-                                        err
-                                    )))
-                                    .into_future(),
-                            )
-                                as Box<dyn Future<Item = HttpResponse, Error = Error>>;
-                        }
-                    };
+                let assignment_update = match AssignmentUpdatePayload::from_json(&body) {
+                    Ok(assignment_update) => assignment_update,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid assignment payload: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>;
+                    }
+                };
 
                 Box::new(
                     web::block(move || {
                         update_assignment(&**role_based_auth_store, &identity, assignment_update)
+                            .map(|_| {
+                                audit_log.record(
+                                    assignment_target(&identity),
+                                    "assignment updated".into(),
+                                )
+                            })
                     })
                     .then(|res| {
                         use SendableRoleBasedAuthorizationStoreError::*;
@@ -310,16 +332,23 @@ fn patch_assignment(
 fn update_assignment(
     role_based_auth_store: &dyn RoleBasedAuthorizationStore,
     identity: &Identity,
-    AssignmentUpdatePayload { roles }: AssignmentUpdatePayload,
+    AssignmentUpdatePayload { roles, expires_at }: AssignmentUpdatePayload,
 ) -> Result<(), SendableRoleBasedAuthorizationStoreError> {
+    let expires_at = expires_at
+        .map(|expires_at| expires_at.map(from_secs).transpose())
+        .transpose()
+        .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?;
+
     role_based_auth_store
         .get_assignment(identity)
         .map_err(SendableRoleBasedAuthorizationStoreError::from)
         .and_then(|assignment_opt| {
             if let Some(assignment) = assignment_opt {
-                let updated_assignment = assignment
-                    .into_update_builder()
-                    .with_roles(roles)
+                let mut update_builder = assignment.into_update_builder().with_roles(roles);
+                if let Some(expires_at) = expires_at {
+                    update_builder = update_builder.with_expires_at(expires_at);
+                }
+                let updated_assignment = update_builder
                     .build()
                     .map_err(SendableRoleBasedAuthorizationStoreError::InvalidState)?;
 
@@ -341,6 +370,7 @@ fn update_assignment(
 fn delete_assignment(
     req: HttpRequest,
     role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+    audit_log: RbacAuditLog,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let identity = try_identity_from_req!(req);
     Box::new(
@@ -348,6 +378,9 @@ fn delete_assignment(
             role_based_auth_store
                 .remove_assignment(&identity)
                 .map_err(SendableRoleBasedAuthorizationStoreError::from)
+                .map(|_| {
+                    audit_log.record(assignment_target(&identity), "assignment removed".into())
+                })
         })
         .then(|res| {
             Ok(match res {
@@ -361,6 +394,14 @@ fn delete_assignment(
     )
 }
 
+/// Returns the audit log target of the assignment for the given identity
+fn assignment_target(identity: &Identity) -> String {
+    match identity {
+        Identity::Key(key) => format!("assignment:key:{}", key),
+        Identity::User(user) => format!("assignment:user:{}", user),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -445,9 +486,10 @@ mod tests {
             .expect("Unable to add assignment");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignments_resource(Box::new(
-                role_based_auth_store,
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignments_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/assignments", bind_url))
             .expect("Failed to parse URL");
@@ -551,9 +593,10 @@ mod tests {
         }
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignments_resource(Box::new(
-                role_based_auth_store,
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignments_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/assignments", bind_url))
             .expect("Failed to parse URL");
@@ -667,9 +710,10 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignments_resource(Box::new(
-                role_based_auth_store.clone(),
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignments_resource(
+                Box::new(role_based_auth_store.clone()),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/assignments", bind_url))
             .expect("Failed to parse URL");
@@ -755,9 +799,10 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignments_resource(Box::new(
-                role_based_auth_store.clone(),
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignments_resource(
+                Box::new(role_based_auth_store.clone()),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/assignments", bind_url))
             .expect("Failed to parse URL");
@@ -855,9 +900,10 @@ mod tests {
             .expect("Unable to add assignment");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignment_resource(Box::new(
-                role_based_auth_store,
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/assignments/key/x",
@@ -926,9 +972,10 @@ mod tests {
     fn test_get_assignment_bad_request() {
         let role_based_auth_store = MemRoleBasedAuthorizationStore::default();
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignment_resource(Box::new(
-                role_based_auth_store,
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/assignments/unknown/x",
@@ -959,9 +1006,10 @@ mod tests {
     fn test_get_assignment_not_found() {
         let role_based_auth_store = MemRoleBasedAuthorizationStore::default();
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignment_resource(Box::new(
-                role_based_auth_store,
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/assignments/key/x",
@@ -1039,9 +1087,10 @@ mod tests {
             .expect("Unable to add assignment");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignment_resource(Box::new(
-                role_based_auth_store,
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/assignments/key/x",
@@ -1123,6 +1172,102 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    /// Test that a PATCH /authorization/assignment/{type}/{identity} updates the expiration time.
+    /// 1. Adds a role and an assignment for a key identity to the store
+    /// 2. Perform a PATCH that sets the expiration time, and verify it via a GET
+    /// 3. Perform a PATCH that only alters the roles, and verify the expiration time is unchanged
+    /// 4. Perform a PATCH that sets the expiration time to null, and verify it was removed
+    #[test]
+    fn test_patch_assignment_expiration() {
+        let role_based_auth_store = MemRoleBasedAuthorizationStore::default();
+
+        let role = RoleBuilder::new()
+            .with_id("role-1".into())
+            .with_display_name("Test Role 1".into())
+            .with_permissions(vec!["a".to_string()])
+            .build()
+            .expect("Unable to build role");
+
+        role_based_auth_store
+            .add_role(role)
+            .expect("Unable to add role");
+
+        let assignment = AssignmentBuilder::new()
+            .with_identity(Identity::Key("x".into()))
+            .with_roles(vec!["role-1".to_string()])
+            .build()
+            .expect("Unable to build assignment");
+
+        role_based_auth_store
+            .add_assignment(assignment)
+            .expect("Unable to add assignment");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/assignments/key/x",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+
+        for (payload, expected) in [
+            (
+                json!({ "roles": ["role-1"], "expires_at": 2000000000 }),
+                json!({
+                    "identity": "x",
+                    "identity_type": "key",
+                    "roles": ["role-1"],
+                    "expires_at": 2000000000,
+                }),
+            ),
+            (
+                json!({ "roles": ["role-1"] }),
+                json!({
+                    "identity": "x",
+                    "identity_type": "key",
+                    "roles": ["role-1"],
+                    "expires_at": 2000000000,
+                }),
+            ),
+            (
+                json!({ "roles": ["role-1"], "expires_at": null }),
+                json!({
+                    "identity": "x",
+                    "identity_type": "key",
+                    "roles": ["role-1"],
+                }),
+            ),
+        ] {
+            let resp = Client::new()
+                .patch(url.clone())
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .json(&payload)
+                .send()
+                .expect("Failed to perform request");
+
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let resp = Client::new()
+                .get(url.clone())
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .send()
+                .expect("Failed to perform request");
+            assert_eq!(resp.status(), StatusCode::OK);
+
+            let body: JsonValue = resp.json().expect("Failed to deserialize body");
+            assert_eq!(json!({ "data": expected }), body);
+        }
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     /// Test a DELETE /authorization/assignments/{type}/{identity} returns OK on a valid identity
     /// with a valid payload.
     /// 1. Adds two roles to the store
@@ -1180,9 +1325,10 @@ mod tests {
             .expect("Unable to add assignment");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_assignment_resource(Box::new(
-                role_based_auth_store.clone(),
-            ))]);
+            run_rest_api_on_open_port(vec![make_assignment_resource(
+                Box::new(role_based_auth_store.clone()),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/assignments/key/x",
@@ -1313,6 +1459,14 @@ mod tests {
             unimplemented!()
         }
 
+        fn get_inherited_roles(
+            &self,
+            _role_id: &str,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn list_roles(
             &self,
         ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
@@ -1427,6 +1581,14 @@ mod tests {
             Ok(())
         }
 
+        fn import_roles_and_assignments(
+            &self,
+            _roles: Vec<Role>,
+            _assignments: Vec<Assignment>,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
             Box::new(self.clone())
         }
@@ -0,0 +1,722 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Resources for exporting and importing all roles and assignments at once.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::convert::TryInto;
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{Future, IntoFuture, Stream};
+
+use crate::error::InvalidStateError;
+use crate::rbac::store::{Assignment, Identity, Role, RoleBasedAuthorizationStore, ADMIN_ROLE_ID};
+use crate::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    auth::authorization::rbac::rest_api::{
+        resources::{
+            assignments::AssignmentResponse,
+            import_export::{
+                ExportResponse, ImportChanges, ImportPayload, ImportQuery, ImportResponse,
+            },
+            roles::RoleResponse,
+        },
+        RBAC_READ_PERMISSION, RBAC_WRITE_PERMISSION,
+    },
+    ErrorResponse, SPLINTER_PROTOCOL_VERSION,
+};
+
+use super::error::SendableRoleBasedAuthorizationStoreError;
+use super::RbacAuditLog;
+
+const AUTHORIZATION_RBAC_IMPORT_EXPORT_MIN: u32 = 2;
+
+pub fn make_export_resource(
+    role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+) -> Resource {
+    Resource::build("/authorization/rbac/export")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            AUTHORIZATION_RBAC_IMPORT_EXPORT_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Get, RBAC_READ_PERMISSION, move |_, _| {
+            export_roles_and_assignments(web::Data::new(role_based_auth_store.clone()))
+        })
+}
+
+pub fn make_import_resource(
+    role_based_auth_store: Box<dyn RoleBasedAuthorizationStore>,
+    audit_log: RbacAuditLog,
+) -> Resource {
+    Resource::build("/authorization/rbac/import")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            AUTHORIZATION_RBAC_IMPORT_EXPORT_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ))
+        .add_method(Method::Post, RBAC_WRITE_PERMISSION, move |r, p| {
+            let audit_log = audit_log.for_request(&r);
+            import_roles_and_assignments(
+                r,
+                p,
+                web::Data::new(role_based_auth_store.clone()),
+                audit_log,
+            )
+        })
+}
+
+fn export_roles_and_assignments(
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        web::block(move || {
+            let roles = role_based_auth_store
+                .list_roles()
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?
+                .collect::<Vec<_>>();
+            let assignments = role_based_auth_store
+                .list_assignments()
+                .map_err(SendableRoleBasedAuthorizationStoreError::from)?
+                .collect::<Vec<_>>();
+
+            Ok((roles, assignments))
+        })
+        .then(
+            |res: Result<_, BlockingError<SendableRoleBasedAuthorizationStoreError>>| match res {
+                Ok((roles, assignments)) => Ok(HttpResponse::Ok().json(ExportResponse {
+                    roles: roles.iter().map(RoleResponse::from).collect(),
+                    assignments: assignments.iter().map(AssignmentResponse::from).collect(),
+                })),
+                Err(err) => {
+                    error!("Unable to export roles and assignments: {}", err);
+                    Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+                }
+            },
+        ),
+    )
+}
+
+fn import_roles_and_assignments(
+    req: HttpRequest,
+    payload: web::Payload,
+    role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+    audit_log: RbacAuditLog,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let web::Query(ImportQuery { dry_run }) = match web::Query::from_query(req.query_string()) {
+        Ok(query) => query,
+        Err(_) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            )
+        }
+    };
+
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let import_res = serde_json::from_slice::<ImportPayload>(&body)
+                    .map_err(|err| err.to_string())
+                    .and_then(|ImportPayload { roles, assignments }| {
+                        let roles = roles
+                            .into_iter()
+                            .map(|role| role.try_into())
+                            .collect::<Result<Vec<Role>, InvalidStateError>>()
+                            .map_err(|err| err.to_string())?;
+                        let assignments = assignments
+                            .into_iter()
+                            .map(|assignment| assignment.try_into())
+                            .collect::<Result<Vec<Assignment>, InvalidStateError>>()
+                            .map_err(|err| err.to_string())?;
+                        Ok((roles, assignments))
+                    });
+
+                let (roles, assignments) = match import_res {
+                    Ok(import) => import,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid import payload: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>
+                    }
+                };
+
+                Box::new(
+                    web::block(move || {
+                        let (roles, assignments) =
+                            plan_import(&**role_based_auth_store, roles, assignments)?;
+
+                        if !dry_run {
+                            let changed_roles = roles.changed();
+                            let changed_assignments = assignments.changed();
+                            let detail = format!(
+                                "imported {} roles and {} assignments",
+                                changed_roles.len(),
+                                changed_assignments.len()
+                            );
+                            role_based_auth_store
+                                .import_roles_and_assignments(changed_roles, changed_assignments)
+                                .map_err(SendableRoleBasedAuthorizationStoreError::from)?;
+                            audit_log.record("rbac".into(), detail);
+                        }
+
+                        Ok((roles, assignments))
+                    })
+                    .then(move |res| {
+                        use SendableRoleBasedAuthorizationStoreError::*;
+                        Ok(match res {
+                            Ok((roles, assignments)) => HttpResponse::Ok()
+                                .json(ImportResponse::new(dry_run, &roles, &assignments)),
+                            Err(BlockingError::Error(InvalidState(err))) => {
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request(&err.to_string()))
+                            }
+                            Err(BlockingError::Error(ConstraintViolation(msg))) => {
+                                HttpResponse::Conflict().json(ErrorResponse::conflict(&msg))
+                            }
+                            Err(err) => {
+                                error!("Unable to import roles and assignments: {}", err);
+                                HttpResponse::InternalServerError()
+                                    .json(ErrorResponse::internal_error())
+                            }
+                        })
+                    }),
+                ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+            }),
+    )
+}
+
+/// Validates the roles and assignments to be imported against each other and against the current
+/// contents of the store, and groups them by the effect importing them would have.
+///
+/// All validation failures are reported together, so that an import file can be corrected in a
+/// single pass.
+fn plan_import(
+    role_based_auth_store: &dyn RoleBasedAuthorizationStore,
+    roles: Vec<Role>,
+    assignments: Vec<Assignment>,
+) -> Result<
+    (ImportChanges<Role>, ImportChanges<Assignment>),
+    SendableRoleBasedAuthorizationStoreError,
+> {
+    let existing_roles = role_based_auth_store
+        .list_roles()
+        .map_err(SendableRoleBasedAuthorizationStoreError::from)?
+        .map(|role| (role.id().to_string(), role))
+        .collect::<HashMap<_, _>>();
+
+    let mut errors = vec![];
+
+    let mut role_ids = HashSet::new();
+    for role in roles.iter() {
+        if !role_ids.insert(role.id()) {
+            errors.push(format!("role {} is included more than once", role.id()));
+        }
+    }
+
+    let mut identities = HashSet::new();
+    for assignment in assignments.iter() {
+        let identity = identity_to_string(assignment.identity());
+        if identities.contains(&identity) {
+            errors.push(format!(
+                "assignment for {} is included more than once",
+                identity
+            ));
+        }
+
+        for role_id in assignment.roles() {
+            if !role_ids.contains(role_id.as_str()) && !existing_roles.contains_key(role_id) {
+                errors.push(format!(
+                    "assignment for {} refers to unknown role {}",
+                    identity, role_id
+                ));
+            }
+        }
+
+        identities.insert(identity);
+    }
+
+    let mut role_changes = ImportChanges::default();
+    for role in roles {
+        match existing_roles.get(role.id()) {
+            Some(existing) if roles_match(existing, &role) => role_changes.unchanged.push(role),
+            Some(_) if role.id() == ADMIN_ROLE_ID => {
+                errors.push(format!("'{}' role cannot be altered", ADMIN_ROLE_ID))
+            }
+            Some(_) => role_changes.updated.push(role),
+            None => role_changes.added.push(role),
+        }
+    }
+
+    let mut assignment_changes = ImportChanges::default();
+    for assignment in assignments {
+        match role_based_auth_store
+            .get_assignment(assignment.identity())
+            .map_err(SendableRoleBasedAuthorizationStoreError::from)?
+        {
+            Some(existing) if as_set(existing.roles()) == as_set(assignment.roles()) => {
+                assignment_changes.unchanged.push(assignment)
+            }
+            Some(_) => assignment_changes.updated.push(assignment),
+            None => assignment_changes.added.push(assignment),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok((role_changes, assignment_changes))
+    } else {
+        Err(SendableRoleBasedAuthorizationStoreError::InvalidState(
+            InvalidStateError::with_message(errors.join("; ")),
+        ))
+    }
+}
+
+fn roles_match(a: &Role, b: &Role) -> bool {
+    a.display_name() == b.display_name() && as_set(a.permissions()) == as_set(b.permissions())
+}
+
+fn as_set(values: &[String]) -> BTreeSet<&str> {
+    values.iter().map(String::as_str).collect()
+}
+
+fn identity_to_string(identity: &Identity) -> String {
+    match identity {
+        Identity::Key(key) => format!("key {}", key),
+        Identity::User(user) => format!("user {}", user),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::BTreeMap;
+    use std::sync::{Arc, Mutex};
+
+    use reqwest::{blocking::Client, StatusCode, Url};
+    use serde_json::Value as JsonValue;
+
+    use crate::error::{ConstraintViolationError, ConstraintViolationType};
+    use crate::rbac::store::{AssignmentBuilder, RoleBasedAuthorizationStoreError, RoleBuilder};
+    use crate::rest_api::actix_web_1::{RestApiBuilder, RestApiShutdownHandle};
+
+    /// Tests a GET /authorization/rbac/export request which returns all roles and assignments.
+    #[test]
+    fn test_export_ok() {
+        let role_based_auth_store = create_populated_store();
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_export_resource(Box::new(role_based_auth_store))]);
+
+        let url = Url::parse(&format!("http://{}/authorization/rbac/export", bind_url))
+            .expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            body,
+            json!({
+                "roles": [{
+                    "role_id": "role-1",
+                    "display_name": "Role 1",
+                    "permissions": ["a", "b"],
+                }],
+                "assignments": [{
+                    "identity_type": "key",
+                    "identity": "x",
+                    "roles": ["role-1"],
+                }],
+            })
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Tests POST /authorization/rbac/import requests.
+    /// 1. Import with `dry_run=true` and verify that the changes are reported but not applied
+    /// 2. Import again without a dry run and verify that the changes are applied
+    #[test]
+    fn test_import_ok() {
+        let role_based_auth_store = create_populated_store();
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_import_resource(
+                Box::new(role_based_auth_store.clone()),
+                RbacAuditLog::default(),
+            )]);
+
+        let payload = json!({
+            "roles": [
+                {
+                    "role_id": "role-1",
+                    "display_name": "Role 1",
+                    "permissions": ["b", "a"],
+                },
+                {
+                    "role_id": "role-2",
+                    "display_name": "Role 2",
+                    "permissions": ["c"],
+                },
+            ],
+            "assignments": [
+                {
+                    "identity_type": "key",
+                    "identity": "x",
+                    "roles": ["role-1", "role-2"],
+                },
+                {
+                    "identity_type": "user",
+                    "identity": "y",
+                    "roles": ["role-2"],
+                },
+            ],
+        });
+        let expected_changes = json!({
+            "roles_added": ["role-2"],
+            "roles_updated": [],
+            "roles_unchanged": ["role-1"],
+            "assignments_added": [{"identity_type": "user", "identity": "y"}],
+            "assignments_updated": [{"identity_type": "key", "identity": "x"}],
+            "assignments_unchanged": [],
+        });
+
+        let url = Url::parse(&format!(
+            "http://{}/authorization/rbac/import?dry_run=true",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let resp = Client::new()
+            .post(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&payload)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let mut body: JsonValue = resp.json().expect("Failed to deserialize body");
+        assert_eq!(body["dry_run"], json!(true));
+        body.as_object_mut()
+            .expect("response is not an object")
+            .remove("dry_run");
+        assert_eq!(body, expected_changes);
+        assert!(role_based_auth_store
+            .get_role("role-2")
+            .expect("Unable to get role")
+            .is_none());
+
+        let url = Url::parse(&format!("http://{}/authorization/rbac/import", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .post(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&payload)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let mut body: JsonValue = resp.json().expect("Failed to deserialize body");
+        assert_eq!(body["dry_run"], json!(false));
+        body.as_object_mut()
+            .expect("response is not an object")
+            .remove("dry_run");
+        assert_eq!(body, expected_changes);
+        assert!(role_based_auth_store
+            .get_role("role-2")
+            .expect("Unable to get role")
+            .is_some());
+        assert_eq!(
+            role_based_auth_store
+                .get_assignment(&Identity::Key("x".into()))
+                .expect("Unable to get assignment")
+                .expect("Assignment not found")
+                .roles(),
+            &["role-1".to_string(), "role-2".to_string()]
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    /// Tests that a POST /authorization/rbac/import request with duplicate roles and an assignment
+    /// referring to an unknown role returns BAD_REQUEST, and that nothing is imported.
+    #[test]
+    fn test_import_invalid() {
+        let role_based_auth_store = create_populated_store();
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_import_resource(
+                Box::new(role_based_auth_store.clone()),
+                RbacAuditLog::default(),
+            )]);
+
+        let url = Url::parse(&format!("http://{}/authorization/rbac/import", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .post(url)
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&json!({
+                "roles": [
+                    {
+                        "role_id": "role-2",
+                        "display_name": "Role 2",
+                        "permissions": ["c"],
+                    },
+                    {
+                        "role_id": "role-2",
+                        "display_name": "Role 2",
+                        "permissions": ["d"],
+                    },
+                ],
+                "assignments": [{
+                    "identity_type": "user",
+                    "identity": "y",
+                    "roles": ["unknown"],
+                }],
+            }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+        let body: JsonValue = resp.json().expect("Failed to deserialize body");
+        let message = body["message"].as_str().expect("No message in response");
+        assert!(message.contains("role role-2 is included more than once"));
+        assert!(message.contains("assignment for user y refers to unknown role unknown"));
+
+        assert!(role_based_auth_store
+            .get_role("role-2")
+            .expect("Unable to get role")
+            .is_none());
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_populated_store() -> MemRoleBasedAuthorizationStore {
+        let role_based_auth_store = MemRoleBasedAuthorizationStore::default();
+        role_based_auth_store
+            .import_roles_and_assignments(
+                vec![RoleBuilder::new()
+                    .with_id("role-1".into())
+                    .with_display_name("Role 1".into())
+                    .with_permissions(vec!["a".to_string(), "b".to_string()])
+                    .build()
+                    .expect("Unable to build role")],
+                vec![AssignmentBuilder::new()
+                    .with_identity(Identity::Key("x".into()))
+                    .with_roles(vec!["role-1".to_string()])
+                    .build()
+                    .expect("Unable to build assignment")],
+            )
+            .expect("Unable to populate store");
+        role_based_auth_store
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = crate::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .build_insecure()
+            .expect("Failed to build REST API")
+            .run_insecure();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MemRoleBasedAuthorizationStore {
+        roles: Arc<Mutex<BTreeMap<String, Role>>>,
+        assignments: Arc<Mutex<BTreeMap<String, Assignment>>>,
+    }
+
+    impl RoleBasedAuthorizationStore for MemRoleBasedAuthorizationStore {
+        fn get_role(&self, id: &str) -> Result<Option<Role>, RoleBasedAuthorizationStoreError> {
+            Ok(self
+                .roles
+                .lock()
+                .expect("mem role based authorization store lock was poisoned")
+                .get(id)
+                .cloned())
+        }
+
+        fn get_inherited_roles(
+            &self,
+            _role_id: &str,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
+        fn list_roles(
+            &self,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            Ok(Box::new(
+                self.roles
+                    .lock()
+                    .expect("mem role based authorization store lock was poisoned")
+                    .values()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ))
+        }
+
+        fn add_role(&self, _role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn update_role(&self, _role: Role) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn remove_role(&self, _role_id: &str) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn get_assignment(
+            &self,
+            identity: &Identity,
+        ) -> Result<Option<Assignment>, RoleBasedAuthorizationStoreError> {
+            Ok(self
+                .assignments
+                .lock()
+                .expect("mem role based authorization store lock was poisoned")
+                .get(&identity_to_string(identity))
+                .cloned())
+        }
+
+        fn get_assigned_roles(
+            &self,
+            _identity: &Identity,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
+        fn list_assignments(
+            &self,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Assignment>>, RoleBasedAuthorizationStoreError>
+        {
+            Ok(Box::new(
+                self.assignments
+                    .lock()
+                    .expect("mem role based authorization store lock was poisoned")
+                    .values()
+                    .cloned()
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ))
+        }
+
+        fn add_assignment(
+            &self,
+            _assignment: Assignment,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn update_assignment(
+            &self,
+            _assignment: Assignment,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn remove_assignment(
+            &self,
+            _identity: &Identity,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
+        fn import_roles_and_assignments(
+            &self,
+            roles: Vec<Role>,
+            assignments: Vec<Assignment>,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            let mut stored_roles = self
+                .roles
+                .lock()
+                .expect("mem role based authorization store lock was poisoned");
+            let mut stored_assignments = self
+                .assignments
+                .lock()
+                .expect("mem role based authorization store lock was poisoned");
+
+            let all_roles_exist = assignments.iter().all(|assignment| {
+                assignment.roles().iter().all(|role_id| {
+                    stored_roles.contains_key(role_id)
+                        || roles.iter().any(|role| role.id() == role_id)
+                })
+            });
+            if !all_roles_exist {
+                return Err(RoleBasedAuthorizationStoreError::ConstraintViolation(
+                    ConstraintViolationError::with_violation_type(
+                        ConstraintViolationType::ForeignKey,
+                    ),
+                ));
+            }
+
+            for role in roles {
+                stored_roles.insert(role.id().to_string(), role);
+            }
+            for assignment in assignments {
+                stored_assignments.insert(identity_to_string(assignment.identity()), assignment);
+            }
+
+            Ok(())
+        }
+
+        fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -16,14 +16,22 @@
 
 mod assignments;
 mod error;
+mod import_export;
 mod roles;
 
+use actix_web::HttpRequest;
+
+#[cfg(feature = "audit")]
+use crate::audit::{store::AuditStore, AuditEvent, AuditEventType};
 use crate::rbac::store::RoleBasedAuthorizationStore;
+#[cfg(feature = "audit")]
+use crate::rest_api::auth::identity::Identity;
 use crate::rest_api::{Resource, RestResourceProvider};
 
 /// REST Resource Provider for Role-based Authorization REST resources.
 pub struct RoleBasedAuthorizationResourceProvider {
     role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+    audit_log: RbacAuditLog,
 }
 
 impl RoleBasedAuthorizationResourceProvider {
@@ -31,17 +39,89 @@ impl RoleBasedAuthorizationResourceProvider {
     pub fn new(role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>) -> Self {
         Self {
             role_based_authorization_store,
+            audit_log: RbacAuditLog::default(),
+        }
+    }
+
+    /// Records the roles and assignments modified through these resources in the given audit
+    /// store.
+    #[cfg(feature = "audit")]
+    pub fn with_audit_store(mut self, audit_store: Box<dyn AuditStore>) -> Self {
+        self.audit_log.audit_store = Some(audit_store);
+        self
+    }
+}
+
+/// Records the RBAC modifications made through the REST API, if an audit store has been set.
+#[derive(Clone, Default)]
+pub struct RbacAuditLog {
+    #[cfg(feature = "audit")]
+    audit_store: Option<Box<dyn AuditStore>>,
+    #[cfg(feature = "audit")]
+    actor: String,
+}
+
+impl RbacAuditLog {
+    /// Returns a copy of the log that attributes modifications to the client that sent the request.
+    #[cfg(feature = "audit")]
+    fn for_request(&self, req: &HttpRequest) -> Self {
+        let actor = req
+            .extensions()
+            .get::<Identity>()
+            .map(|identity| identity.to_string())
+            .unwrap_or_else(|| "anonymous".into());
+        Self {
+            audit_store: self.audit_store.clone(),
+            actor,
+        }
+    }
+
+    #[cfg(not(feature = "audit"))]
+    fn for_request(&self, _req: &HttpRequest) -> Self {
+        self.clone()
+    }
+
+    /// Records a modification of the given role or assignment; failures are only logged, since the
+    /// modification has already been made.
+    #[cfg(feature = "audit")]
+    fn record(&self, target: String, detail: String) {
+        if let Some(audit_store) = &self.audit_store {
+            let event = AuditEvent::new(AuditEventType::RbacModified, self.actor.clone(), target)
+                .with_detail(detail);
+            if let Err(err) = audit_store.add_event(&event) {
+                error!("Unable to record RBAC modification in audit log: {}", err);
+            }
         }
     }
+
+    #[cfg(not(feature = "audit"))]
+    fn record(&self, _target: String, _detail: String) {}
 }
 
 impl RestResourceProvider for RoleBasedAuthorizationResourceProvider {
     fn resources(&self) -> Vec<Resource> {
         vec![
-            roles::make_roles_resource(self.role_based_authorization_store.clone()),
-            roles::make_role_resource(self.role_based_authorization_store.clone()),
-            assignments::make_assignments_resource(self.role_based_authorization_store.clone()),
-            assignments::make_assignment_resource(self.role_based_authorization_store.clone()),
+            roles::make_roles_resource(
+                self.role_based_authorization_store.clone(),
+                self.audit_log.clone(),
+            ),
+            roles::make_role_resource(
+                self.role_based_authorization_store.clone(),
+                self.audit_log.clone(),
+            ),
+            assignments::make_assignments_resource(
+                self.role_based_authorization_store.clone(),
+                self.audit_log.clone(),
+            ),
+            assignments::make_assignment_resource(
+                self.role_based_authorization_store.clone(),
+                self.audit_log.clone(),
+            ),
+            import_export::make_export_resource(self.role_based_authorization_store.clone()),
+            import_export::make_import_resource(
+                self.role_based_authorization_store.clone(),
+                self.audit_log.clone(),
+            ),
         ]
     }
 }
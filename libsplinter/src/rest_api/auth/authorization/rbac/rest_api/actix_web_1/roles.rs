@@ -37,12 +37,14 @@ use crate::rest_api::{
 };
 
 use super::error::SendableRoleBasedAuthorizationStoreError;
+use super::RbacAuditLog;
 
 const AUTHORIZATION_RBAC_ROLE_MIN: u32 = 1;
 const AUTHORIZATION_RBAC_ROLES_MIN: u32 = 1;
 
 pub fn make_roles_resource(
     role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+    audit_log: RbacAuditLog,
 ) -> Resource {
     let list_store = role_based_authorization_store.clone();
     let post_store = role_based_authorization_store;
@@ -54,17 +56,24 @@ pub fn make_roles_resource(
         .add_method(Method::Get, RBAC_READ_PERMISSION, move |r, _| {
             list_roles(r, web::Data::new(list_store.clone()))
         })
-        .add_method(Method::Post, RBAC_WRITE_PERMISSION, move |_, p| {
-            add_role(p, web::Data::new(post_store.clone()))
+        .add_method(Method::Post, RBAC_WRITE_PERMISSION, move |r, p| {
+            add_role(
+                p,
+                web::Data::new(post_store.clone()),
+                audit_log.for_request(&r),
+            )
         })
 }
 
 pub fn make_role_resource(
     role_based_authorization_store: Box<dyn RoleBasedAuthorizationStore>,
+    audit_log: RbacAuditLog,
 ) -> Resource {
     let get_store = role_based_authorization_store.clone();
     let patch_store = role_based_authorization_store.clone();
     let delete_store = role_based_authorization_store;
+    let patch_audit_log = audit_log.clone();
+    let delete_audit_log = audit_log;
     Resource::build("/authorization/roles/{role_id}")
         .add_request_guard(ProtocolVersionRangeGuard::new(
             AUTHORIZATION_RBAC_ROLE_MIN,
@@ -74,10 +83,12 @@ pub fn make_role_resource(
             get_role(r, web::Data::new(get_store.clone()))
         })
         .add_method(Method::Patch, RBAC_WRITE_PERMISSION, move |r, p| {
-            patch_role(r, p, web::Data::new(patch_store.clone()))
+            let audit_log = patch_audit_log.for_request(&r);
+            patch_role(r, p, web::Data::new(patch_store.clone()), audit_log)
         })
         .add_method(Method::Delete, RBAC_WRITE_PERMISSION, move |r, _| {
-            delete_role(r, web::Data::new(delete_store.clone()))
+            let audit_log = delete_audit_log.for_request(&r);
+            delete_role(r, web::Data::new(delete_store.clone()), audit_log)
         })
 }
 
@@ -136,6 +147,7 @@ fn list_roles(
 fn add_role(
     payload: web::Payload,
     role_based_authorization_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+    audit_log: RbacAuditLog,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     Box::new(
         payload
@@ -146,9 +158,8 @@ fn add_role(
             })
             .into_future()
             .and_then(move |body| {
-                let role_res: Result<Role, _> = serde_json::from_slice::<RolePayload>(&body)
-                    .map_err(|err| err.to_string())
-                    .and_then(|role_payload| {
+                let role_res: Result<Role, _> =
+                    RolePayload::from_json(&body).and_then(|role_payload| {
                         role_payload
                             .try_into()
                             .map_err(|err: InvalidStateError| err.to_string())
@@ -157,9 +168,14 @@ fn add_role(
                 match role_res {
                     Ok(role) => Box::new(
                         web::block(move || {
+                            let role_id = role.id().to_string();
                             role_based_authorization_store
                                 .add_role(role)
                                 .map_err(SendableRoleBasedAuthorizationStoreError::from)
+                                .map(|_| {
+                                    audit_log
+                                        .record(format!("role:{}", role_id), "role added".into())
+                                })
                         })
                         .then(|res| {
                             Ok(match res {
@@ -223,6 +239,7 @@ fn patch_role(
     req: HttpRequest,
     payload: web::Payload,
     role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+    audit_log: RbacAuditLog,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let role_id = req.match_info().get("role_id").unwrap_or("").to_string();
     Box::new(
@@ -234,7 +251,7 @@ fn patch_role(
             })
             .into_future()
             .and_then(move |body| {
-                let role_update = match serde_json::from_slice::<RoleUpdatePayload>(&body) {
+                let role_update = match RoleUpdatePayload::from_json(&body) {
                     Ok(role_update) => role_update,
                     Err(err) => {
                         return Box::new(
@@ -251,7 +268,9 @@ fn patch_role(
 
                 Box::new(
                     web::block(move || {
-                        update_role(&**role_based_auth_store, &role_id, role_update)
+                        update_role(&**role_based_auth_store, &role_id, role_update).map(|_| {
+                            audit_log.record(format!("role:{}", role_id), "role updated".into())
+                        })
                     })
                     .then(|res| {
                         use SendableRoleBasedAuthorizationStoreError::*;
@@ -292,6 +311,7 @@ fn update_role(
                 let RoleUpdatePayload {
                     display_name,
                     permissions,
+                    parents,
                 } = role_update;
                 let mut update_builder = role.into_update_builder();
 
@@ -301,6 +321,9 @@ fn update_role(
                 if let Some(permissions) = permissions {
                     update_builder = update_builder.with_permissions(permissions);
                 }
+                if let Some(parents) = parents {
+                    update_builder = update_builder.with_parents(parents);
+                }
 
                 let updated_role = update_builder
                     .build()
@@ -321,6 +344,7 @@ fn update_role(
 fn delete_role(
     req: HttpRequest,
     role_based_auth_store: web::Data<Box<dyn RoleBasedAuthorizationStore>>,
+    audit_log: RbacAuditLog,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let role_id = req.match_info().get("role_id").unwrap_or("").to_string();
     Box::new(
@@ -328,10 +352,14 @@ fn delete_role(
             role_based_auth_store
                 .remove_role(&role_id)
                 .map_err(SendableRoleBasedAuthorizationStoreError::from)
+                .map(|_| audit_log.record(format!("role:{}", role_id), "role removed".into()))
         })
         .then(|role_res| {
             Ok(match role_res {
                 Ok(()) => HttpResponse::Ok().finish(),
+                Err(BlockingError::Error(
+                    SendableRoleBasedAuthorizationStoreError::ConstraintViolation(msg),
+                )) => HttpResponse::Conflict().json(ErrorResponse::conflict(&msg)),
                 Err(err) => {
                     error!("Unable to delete role: {}", err);
                     HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
@@ -399,7 +427,10 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_roles_resource(Box::new(role_based_auth_store))]);
+            run_rest_api_on_open_port(vec![make_roles_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/roles", bind_url))
             .expect("Failed to parse URL");
@@ -424,6 +455,7 @@ mod tests {
                 role_id: "test-role-1",
                 display_name: "Test Role 1",
                 permissions: &["a".to_string(), "b".to_string(), "c".to_string()],
+                parents: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -433,6 +465,7 @@ mod tests {
                 role_id: "test-role-2",
                 display_name: "Test Role 2",
                 permissions: &["x".to_string(), "y".to_string(), "z".to_string()],
+                parents: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(1).expect("no first item")
@@ -478,7 +511,10 @@ mod tests {
         }
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_roles_resource(Box::new(role_based_auth_store))]);
+            run_rest_api_on_open_port(vec![make_roles_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/roles", bind_url))
             .expect("Failed to parse URL");
@@ -503,6 +539,7 @@ mod tests {
                     role_id: &format!("test-role-{:0>3}", i),
                     display_name: &format!("Test Role {}", i),
                     permissions: &[format!("perm-{}", i)],
+                    parents: &[],
                 })
                 .expect("Failed to convert to value"),
                 json_roles.get(i).expect("no first item")
@@ -551,6 +588,7 @@ mod tests {
                 role_id: "test-role-100",
                 display_name: "Test Role 100",
                 permissions: &["perm-100".to_string()],
+                parents: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -568,7 +606,10 @@ mod tests {
     fn test_post_role_ok() {
         let role_based_auth_store = MemRoleBasedAuthorizationStore::default();
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_roles_resource(Box::new(role_based_auth_store))]);
+            run_rest_api_on_open_port(vec![make_roles_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/roles", bind_url))
             .expect("Failed to parse URL");
@@ -609,6 +650,7 @@ mod tests {
                 role_id: "new_test_role",
                 display_name: "New Test Display Name",
                 permissions: &["my-permission-1".to_string(), "my-permission-2".to_string()],
+                parents: &[],
             })
             .expect("Failed to convert to value"),
             json_roles.get(0).expect("no first item")
@@ -638,7 +680,10 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_roles_resource(Box::new(role_based_auth_store))]);
+            run_rest_api_on_open_port(vec![make_roles_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/authorization/roles", bind_url))
             .expect("Failed to parse URL");
@@ -689,8 +734,11 @@ mod tests {
             .add_role(role)
             .expect("Unable to add role");
         let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
-            make_roles_resource(Box::new(role_based_auth_store.clone())),
-            make_role_resource(Box::new(role_based_auth_store)),
+            make_roles_resource(
+                Box::new(role_based_auth_store.clone()),
+                RbacAuditLog::default(),
+            ),
+            make_role_resource(Box::new(role_based_auth_store), RbacAuditLog::default()),
         ]);
 
         let url = Url::parse(&format!(
@@ -752,8 +800,11 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
-            make_roles_resource(Box::new(role_based_auth_store.clone())),
-            make_role_resource(Box::new(role_based_auth_store)),
+            make_roles_resource(
+                Box::new(role_based_auth_store.clone()),
+                RbacAuditLog::default(),
+            ),
+            make_role_resource(Box::new(role_based_auth_store), RbacAuditLog::default()),
         ]);
 
         let url = Url::parse(&format!(
@@ -806,7 +857,10 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_role_resource(Box::new(role_based_auth_store))]);
+            run_rest_api_on_open_port(vec![make_role_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/roles/{}",
@@ -919,7 +973,10 @@ mod tests {
     fn test_patch_role_not_found() {
         let role_based_auth_store = MemRoleBasedAuthorizationStore::default();
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_role_resource(Box::new(role_based_auth_store))]);
+            run_rest_api_on_open_port(vec![make_role_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/roles/{}",
@@ -960,7 +1017,10 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_role_resource(Box::new(role_based_auth_store))]);
+            run_rest_api_on_open_port(vec![make_role_resource(
+                Box::new(role_based_auth_store),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/roles/{}",
@@ -1001,7 +1061,10 @@ mod tests {
             .expect("Unable to add role");
 
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_role_resource(role_based_auth_store.clone_box())]);
+            run_rest_api_on_open_port(vec![make_role_resource(
+                role_based_auth_store.clone_box(),
+                RbacAuditLog::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/authorization/roles/{}",
@@ -1101,6 +1164,14 @@ mod tests {
                 .cloned())
         }
 
+        fn get_inherited_roles(
+            &self,
+            _role_id: &str,
+        ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
+        {
+            unimplemented!()
+        }
+
         fn list_roles(
             &self,
         ) -> Result<Box<dyn ExactSizeIterator<Item = Role>>, RoleBasedAuthorizationStoreError>
@@ -1202,6 +1273,14 @@ mod tests {
             unimplemented!()
         }
 
+        fn import_roles_and_assignments(
+            &self,
+            _roles: Vec<Role>,
+            _assignments: Vec<Assignment>,
+        ) -> Result<(), RoleBasedAuthorizationStoreError> {
+            unimplemented!()
+        }
+
         fn clone_box(&self) -> Box<dyn RoleBasedAuthorizationStore> {
             Box::new(self.clone())
         }
@@ -13,11 +13,17 @@
 // limitations under the License.
 
 use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
 
 use crate::error::InvalidStateError;
 use crate::rbac::store::{Assignment, AssignmentBuilder, Identity};
 use crate::rest_api::paging::Paging;
 
+use super::parse_json;
+
 #[derive(Serialize)]
 pub struct ListAssignmentsResponse<'a> {
     pub data: Vec<AssignmentResponse<'a>>,
@@ -29,6 +35,9 @@ pub struct AssignmentResponse<'a> {
     #[serde(flatten)]
     identity: IdentityResponse<'a>,
     roles: &'a [String],
+    /// The time the assignment expires, in seconds since the Unix epoch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expires_at: Option<u64>,
 }
 
 #[derive(Serialize)]
@@ -44,6 +53,12 @@ impl<'a> From<&'a Assignment> for AssignmentResponse<'a> {
         Self {
             identity: assignment.identity().into(),
             roles: assignment.roles(),
+            expires_at: assignment.expires_at().map(|expires_at| {
+                expires_at
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or(0)
+            }),
         }
     }
 }
@@ -62,6 +77,8 @@ pub struct AssignmentPayload {
     #[serde(flatten)]
     identity: IdentityPayload,
     roles: Vec<String>,
+    #[serde(default)]
+    expires_at: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -76,19 +93,94 @@ impl TryFrom<AssignmentPayload> for Assignment {
     type Error = InvalidStateError;
 
     fn try_from(
-        AssignmentPayload { identity, roles }: AssignmentPayload,
+        AssignmentPayload {
+            identity,
+            roles,
+            expires_at,
+        }: AssignmentPayload,
     ) -> Result<Self, Self::Error> {
-        AssignmentBuilder::new()
+        let builder = AssignmentBuilder::new()
             .with_identity(match identity {
                 IdentityPayload::Key(key) => Identity::Key(key),
                 IdentityPayload::User(user) => Identity::User(user),
             })
-            .with_roles(roles)
-            .build()
+            .with_roles(roles);
+
+        match expires_at {
+            Some(expires_at) => builder.with_expires_at(from_secs(expires_at)?).build(),
+            None => builder.build(),
+        }
     }
 }
 
 #[derive(Deserialize)]
 pub struct AssignmentUpdatePayload {
     pub roles: Vec<String>,
+    /// The new expiration time, in seconds since the Unix epoch: `None` if the expiration is not
+    /// updated, or `Some(None)` if it is removed
+    #[serde(default, deserialize_with = "deserialize_nullable")]
+    pub expires_at: Option<Option<u64>>,
+}
+
+/// Converts a number of seconds since the Unix epoch into a time.
+pub fn from_secs(secs: u64) -> Result<SystemTime, InvalidStateError> {
+    UNIX_EPOCH
+        .checked_add(Duration::from_secs(secs))
+        .ok_or_else(|| {
+            InvalidStateError::with_message(format!("{} is not a valid expiration time", secs))
+        })
+}
+
+/// Distinguishes a field that is set to `null` from a field that is absent, which is `None` by
+/// default.
+fn deserialize_nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl AssignmentPayload {
+    /// Deserializes an assignment payload from a JSON request body
+    pub fn from_json(body: &[u8]) -> Result<Self, String> {
+        parse_json(body, assignment_payload_schema)
+    }
+}
+
+impl AssignmentUpdatePayload {
+    /// Deserializes an assignment update payload from a JSON request body
+    pub fn from_json(body: &[u8]) -> Result<Self, String> {
+        parse_json(body, assignment_update_payload_schema)
+    }
+}
+
+fn assignment_payload_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["identity_type", "identity", "roles"],
+        "properties": {
+            "identity_type": { "enum": ["key", "user"] },
+            "identity": { "type": "string", "minLength": 1 },
+            "roles": {
+                "type": "array",
+                "items": { "type": "string", "minLength": 1 }
+            },
+            "expires_at": { "type": "integer" }
+        }
+    })
+}
+
+fn assignment_update_payload_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["roles"],
+        "properties": {
+            "roles": {
+                "type": "array",
+                "items": { "type": "string", "minLength": 1 }
+            },
+            "expires_at": { "type": ["integer", "null"] }
+        }
+    })
 }
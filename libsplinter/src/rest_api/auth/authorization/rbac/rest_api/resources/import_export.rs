@@ -0,0 +1,109 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API request and response structs for importing and exporting roles and assignments.
+
+use crate::rbac::store::{Assignment, Role};
+
+use super::assignments::{AssignmentPayload, AssignmentResponse, IdentityResponse};
+use super::roles::{RolePayload, RoleResponse};
+
+#[derive(Serialize)]
+pub struct ExportResponse<'a> {
+    pub roles: Vec<RoleResponse<'a>>,
+    pub assignments: Vec<AssignmentResponse<'a>>,
+}
+
+#[derive(Deserialize)]
+pub struct ImportPayload {
+    #[serde(default)]
+    pub roles: Vec<RolePayload>,
+    #[serde(default)]
+    pub assignments: Vec<AssignmentPayload>,
+}
+
+#[derive(Deserialize)]
+pub struct ImportQuery {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Serialize)]
+pub struct ImportResponse<'a> {
+    pub dry_run: bool,
+    pub roles_added: Vec<&'a str>,
+    pub roles_updated: Vec<&'a str>,
+    pub roles_unchanged: Vec<&'a str>,
+    pub assignments_added: Vec<IdentityResponse<'a>>,
+    pub assignments_updated: Vec<IdentityResponse<'a>>,
+    pub assignments_unchanged: Vec<IdentityResponse<'a>>,
+}
+
+impl<'a> ImportResponse<'a> {
+    pub fn new(
+        dry_run: bool,
+        roles: &'a ImportChanges<Role>,
+        assignments: &'a ImportChanges<Assignment>,
+    ) -> Self {
+        Self {
+            dry_run,
+            roles_added: roles.added.iter().map(Role::id).collect(),
+            roles_updated: roles.updated.iter().map(Role::id).collect(),
+            roles_unchanged: roles.unchanged.iter().map(Role::id).collect(),
+            assignments_added: assignments
+                .added
+                .iter()
+                .map(|assignment| assignment.identity().into())
+                .collect(),
+            assignments_updated: assignments
+                .updated
+                .iter()
+                .map(|assignment| assignment.identity().into())
+                .collect(),
+            assignments_unchanged: assignments
+                .unchanged
+                .iter()
+                .map(|assignment| assignment.identity().into())
+                .collect(),
+        }
+    }
+}
+
+/// The items of an import, grouped by the effect importing them has on the store.
+pub struct ImportChanges<T> {
+    pub added: Vec<T>,
+    pub updated: Vec<T>,
+    pub unchanged: Vec<T>,
+}
+
+impl<T> Default for ImportChanges<T> {
+    fn default() -> Self {
+        Self {
+            added: vec![],
+            updated: vec![],
+            unchanged: vec![],
+        }
+    }
+}
+
+impl<T: Clone> ImportChanges<T> {
+    /// Returns the items that must be written to the store.
+    pub fn changed(&self) -> Vec<T> {
+        self.added
+            .iter()
+            .chain(self.updated.iter())
+            .cloned()
+            .collect()
+    }
+}
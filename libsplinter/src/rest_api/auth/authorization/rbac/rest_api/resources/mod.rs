@@ -15,8 +15,14 @@
 //! Web-framework-agnostic resources.
 
 pub mod assignments;
+pub mod import_export;
 pub mod roles;
 
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+#[cfg(feature = "rest-api-json-schema")]
+use crate::rest_api::json_schema::JsonSchema;
 use crate::rest_api::paging::{DEFAULT_LIMIT, DEFAULT_OFFSET};
 
 #[derive(Deserialize)]
@@ -34,3 +40,18 @@ fn default_limit() -> usize {
 fn default_offset() -> usize {
     DEFAULT_OFFSET
 }
+
+/// Deserializes a JSON request body. When the `rest-api-json-schema` feature is enabled, the body
+/// is first validated against the given schema so that every invalid field is reported.
+#[cfg(feature = "rest-api-json-schema")]
+fn parse_json<T: DeserializeOwned>(body: &[u8], schema: fn() -> Value) -> Result<T, String> {
+    JsonSchema::new(schema())
+        .parse(body)
+        .map_err(|err| err.to_string())
+}
+
+/// Deserializes a JSON request body.
+#[cfg(not(feature = "rest-api-json-schema"))]
+fn parse_json<T: DeserializeOwned>(body: &[u8], _schema: fn() -> Value) -> Result<T, String> {
+    serde_json::from_slice(body).map_err(|err| err.to_string())
+}
@@ -16,10 +16,14 @@
 
 use std::convert::TryFrom;
 
+use serde_json::{json, Value};
+
 use crate::error::InvalidStateError;
 use crate::rbac::store::{Role, RoleBuilder};
 use crate::rest_api::paging::Paging;
 
+use super::parse_json;
+
 #[derive(Serialize)]
 pub struct ListRoleResponse<'a> {
     pub data: Vec<RoleResponse<'a>>,
@@ -31,6 +35,8 @@ pub struct RoleResponse<'a> {
     pub role_id: &'a str,
     pub display_name: &'a str,
     pub permissions: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    pub parents: &'a [String],
 }
 
 #[derive(Deserialize)]
@@ -38,12 +44,65 @@ pub struct RolePayload {
     pub role_id: String,
     pub display_name: String,
     pub permissions: Vec<String>,
+    #[serde(default)]
+    pub parents: Vec<String>,
 }
 
 #[derive(Deserialize)]
 pub struct RoleUpdatePayload {
     pub display_name: Option<String>,
     pub permissions: Option<Vec<String>>,
+    pub parents: Option<Vec<String>>,
+}
+
+impl RolePayload {
+    /// Deserializes a role payload from a JSON request body
+    pub fn from_json(body: &[u8]) -> Result<Self, String> {
+        parse_json(body, role_payload_schema)
+    }
+}
+
+impl RoleUpdatePayload {
+    /// Deserializes a role update payload from a JSON request body
+    pub fn from_json(body: &[u8]) -> Result<Self, String> {
+        parse_json(body, role_update_payload_schema)
+    }
+}
+
+fn role_payload_schema() -> Value {
+    json!({
+        "type": "object",
+        "required": ["role_id", "display_name", "permissions"],
+        "properties": {
+            "role_id": { "type": "string", "minLength": 1 },
+            "display_name": { "type": "string", "minLength": 1 },
+            "permissions": {
+                "type": "array",
+                "items": { "type": "string", "minLength": 1 }
+            },
+            "parents": {
+                "type": "array",
+                "items": { "type": "string", "minLength": 1 }
+            }
+        }
+    })
+}
+
+fn role_update_payload_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "display_name": { "type": ["string", "null"], "minLength": 1 },
+            "permissions": {
+                "type": ["array", "null"],
+                "items": { "type": "string", "minLength": 1 }
+            },
+            "parents": {
+                "type": ["array", "null"],
+                "items": { "type": "string", "minLength": 1 }
+            }
+        }
+    })
 }
 
 impl<'a> From<&'a Role> for RoleResponse<'a> {
@@ -52,6 +111,7 @@ impl<'a> From<&'a Role> for RoleResponse<'a> {
             role_id: role.id(),
             display_name: role.display_name(),
             permissions: role.permissions(),
+            parents: role.parents(),
         }
     }
 }
@@ -64,6 +124,7 @@ impl TryFrom<RolePayload> for Role {
             .with_id(payload.role_id)
             .with_display_name(payload.display_name)
             .with_permissions(payload.permissions)
+            .with_parents(payload.parents)
             .build()
     }
 }
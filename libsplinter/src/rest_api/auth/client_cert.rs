@@ -0,0 +1,371 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Authentication of REST API clients by their TLS client certificates
+//!
+//! Some environments forbid bearer tokens entirely. When the REST API is served over HTTPS with a
+//! client CA, every client must present a certificate signed by that CA; with [ClientCertAuth],
+//! the certificate is also the client's only credential. Each configured [CertificateMatcher]
+//! maps a certificate, identified by its SHA-256 fingerprint or one of its subject alternative
+//! names, to an [Identity::User], which is then authorized like any other user. Roles can be
+//! granted to these users with RBAC assignments, or with the
+//! `ClientCertRoleAuthorizationHandler`.
+//!
+//! The `Authorization` header, and the identity header of an authenticating reverse proxy, are
+//! ignored: requests whose certificate is not mapped to a user are unauthorized.
+
+use std::any::Any;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+use actix_tls::accept::openssl::TlsStream;
+use actix_web::dev::Extensions;
+use actix_web::rt::net::TcpStream;
+use openssl::hash::MessageDigest;
+use openssl::x509::X509Ref;
+
+use crate::error::{InternalError, InvalidArgumentError};
+use crate::hex::to_hex;
+
+use super::identity::Identity;
+
+/// The details of a verified client certificate that identities are mapped from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientCertificate {
+    fingerprint: String,
+    subject_alt_names: Vec<String>,
+}
+
+impl ClientCertificate {
+    /// Reads the SHA-256 fingerprint and the DNS, email, URI and IP address subject alternative
+    /// names of the given certificate.
+    pub fn from_x509(certificate: &X509Ref) -> Result<Self, InternalError> {
+        let fingerprint = certificate
+            .digest(MessageDigest::sha256())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let subject_alt_names = certificate
+            .subject_alt_names()
+            .map(|names| {
+                names
+                    .iter()
+                    .filter_map(|name| {
+                        name.dnsname()
+                            .or_else(|| name.email())
+                            .or_else(|| name.uri())
+                            .map(String::from)
+                            .or_else(|| name.ipaddress().and_then(ip_address_to_string))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self {
+            fingerprint: to_hex(&fingerprint),
+            subject_alt_names,
+        })
+    }
+
+    /// Returns the lowercase, hex-encoded SHA-256 fingerprint of the certificate
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Returns the subject alternative names of the certificate
+    pub fn subject_alt_names(&self) -> &[String] {
+        &self.subject_alt_names
+    }
+}
+
+fn ip_address_to_string(bytes: &[u8]) -> Option<String> {
+    match bytes.len() {
+        4 => {
+            let mut octets = [0; 4];
+            octets.copy_from_slice(bytes);
+            Some(IpAddr::from(octets).to_string())
+        }
+        16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(bytes);
+            Some(IpAddr::from(octets).to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Identifies the client certificates that are mapped to a user
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CertificateMatcher {
+    /// Matches the certificate with the given lowercase, hex-encoded SHA-256 fingerprint
+    Fingerprint(String),
+    /// Matches any certificate with the given subject alternative name
+    SubjectAltName(String),
+}
+
+impl CertificateMatcher {
+    fn matches(&self, certificate: &ClientCertificate) -> bool {
+        match self {
+            CertificateMatcher::Fingerprint(fingerprint) => {
+                certificate.fingerprint() == fingerprint
+            }
+            CertificateMatcher::SubjectAltName(name) => certificate
+                .subject_alt_names()
+                .iter()
+                .any(|subject_alt_name| subject_alt_name == name),
+        }
+    }
+}
+
+impl FromStr for CertificateMatcher {
+    type Err = InvalidArgumentError;
+
+    /// Parses a matcher of the form `sha256:<fingerprint>`, where the fingerprint may be
+    /// separated by colons as printed by `openssl x509 -fingerprint`, or `san:<name>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some("sha256"), Some(fingerprint)) => {
+                let fingerprint = fingerprint.replace(':', "").to_lowercase();
+                if fingerprint.len() != 64 || !fingerprint.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(InvalidArgumentError::new(
+                        "certificate".to_string(),
+                        "a SHA-256 fingerprint must be 32 hex-encoded bytes".to_string(),
+                    ));
+                }
+                Ok(CertificateMatcher::Fingerprint(fingerprint))
+            }
+            (Some("san"), Some(name)) if !name.is_empty() => {
+                Ok(CertificateMatcher::SubjectAltName(name.to_string()))
+            }
+            _ => Err(InvalidArgumentError::new(
+                "certificate".to_string(),
+                format!(
+                    "'{}' must be in the format sha256:<fingerprint> or san:<name>",
+                    s
+                ),
+            )),
+        }
+    }
+}
+
+/// Configuration for authenticating REST API clients by their TLS client certificates
+#[derive(Clone, Debug)]
+pub struct ClientCertAuth {
+    identities: Vec<(CertificateMatcher, String)>,
+}
+
+impl ClientCertAuth {
+    /// Creates a new configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `identities` - Pairs of certificate matchers and the user that a matching certificate
+    ///   identifies
+    pub fn new(identities: Vec<(CertificateMatcher, String)>) -> Self {
+        Self { identities }
+    }
+
+    /// Returns the user identified by the client's certificate. Fingerprint matches take
+    /// precedence over subject alternative name matches.
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate` - The client's verified certificate, if it presented one
+    pub(crate) fn get_identity(&self, certificate: Option<&ClientCertificate>) -> Option<Identity> {
+        let certificate = certificate?;
+        let is_fingerprint =
+            |matcher: &CertificateMatcher| matches!(matcher, CertificateMatcher::Fingerprint(_));
+
+        self.identities
+            .iter()
+            .filter(|(matcher, _)| is_fingerprint(matcher))
+            .chain(
+                self.identities
+                    .iter()
+                    .filter(|(matcher, _)| !is_fingerprint(matcher)),
+            )
+            .find(|(matcher, _)| matcher.matches(certificate))
+            .map(|(_, user)| Identity::User(user.clone()))
+    }
+}
+
+/// Records the certificate of a client that connected over TLS in the connection's data, where
+/// the authorization middleware reads it from.
+pub(crate) fn record_client_certificate(connection: &dyn Any, data: &mut Extensions) {
+    let certificate = match connection
+        .downcast_ref::<TlsStream<TcpStream>>()
+        .and_then(|stream| stream.ssl().peer_certificate())
+    {
+        Some(certificate) => certificate,
+        None => return,
+    };
+
+    match ClientCertificate::from_x509(&certificate) {
+        Ok(certificate) => {
+            data.insert(certificate);
+        }
+        Err(err) => error!("Unable to read REST API client certificate: {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use openssl::asn1::Asn1Time;
+    use openssl::pkey::PKey;
+    use openssl::rsa::Rsa;
+    use openssl::x509::{extension::SubjectAlternativeName, X509Builder, X509NameBuilder, X509};
+
+    const FINGERPRINT: &str = "4f3c2a1b0e9d8c7b6a5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d4c3b";
+
+    /// Verifies that matchers are parsed from fingerprints, with or without colons, and subject
+    /// alternative names, and that other values are rejected.
+    #[test]
+    fn parse_certificate_matcher() {
+        assert_eq!(
+            format!("sha256:{}", FINGERPRINT)
+                .parse::<CertificateMatcher>()
+                .ok(),
+            Some(CertificateMatcher::Fingerprint(FINGERPRINT.into()))
+        );
+
+        let with_colons = FINGERPRINT
+            .to_uppercase()
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| String::from_utf8_lossy(pair).into_owned())
+            .collect::<Vec<_>>()
+            .join(":");
+        assert_eq!(
+            format!("sha256:{}", with_colons)
+                .parse::<CertificateMatcher>()
+                .ok(),
+            Some(CertificateMatcher::Fingerprint(FINGERPRINT.into()))
+        );
+
+        assert_eq!(
+            "san:alice@example.com".parse::<CertificateMatcher>().ok(),
+            Some(CertificateMatcher::SubjectAltName(
+                "alice@example.com".into()
+            ))
+        );
+
+        assert!("sha256:abcd".parse::<CertificateMatcher>().is_err());
+        assert!("san:".parse::<CertificateMatcher>().is_err());
+        assert!("cn:alice".parse::<CertificateMatcher>().is_err());
+        assert!("alice".parse::<CertificateMatcher>().is_err());
+    }
+
+    /// Verifies that the fingerprint and subject alternative names are read from a certificate.
+    #[test]
+    fn client_certificate_from_x509() {
+        let certificate = create_certificate(&["alice.example.com"]);
+
+        let client_certificate =
+            ClientCertificate::from_x509(&certificate).expect("Failed to read certificate");
+
+        let digest = certificate
+            .digest(MessageDigest::sha256())
+            .expect("Failed to get digest");
+        assert_eq!(client_certificate.fingerprint(), to_hex(&digest));
+        assert_eq!(
+            client_certificate.subject_alt_names(),
+            &["alice.example.com".to_string()]
+        );
+    }
+
+    /// Verifies that certificates are mapped to users by fingerprint before subject alternative
+    /// name, and that unmapped or missing certificates have no identity.
+    #[test]
+    fn get_identity_from_certificate() {
+        let client_cert_auth = ClientCertAuth::new(vec![
+            (
+                CertificateMatcher::SubjectAltName("alice.example.com".into()),
+                "alice".into(),
+            ),
+            (
+                CertificateMatcher::Fingerprint(FINGERPRINT.into()),
+                "bob".into(),
+            ),
+        ]);
+
+        let alice = ClientCertificate {
+            fingerprint: "00".repeat(32),
+            subject_alt_names: vec!["alice.example.com".into()],
+        };
+        assert_eq!(
+            client_cert_auth.get_identity(Some(&alice)),
+            Some(Identity::User("alice".into()))
+        );
+
+        let bob = ClientCertificate {
+            fingerprint: FINGERPRINT.into(),
+            subject_alt_names: vec!["alice.example.com".into()],
+        };
+        assert_eq!(
+            client_cert_auth.get_identity(Some(&bob)),
+            Some(Identity::User("bob".into()))
+        );
+
+        let unknown = ClientCertificate {
+            fingerprint: "00".repeat(32),
+            subject_alt_names: vec!["carol.example.com".into()],
+        };
+        assert_eq!(client_cert_auth.get_identity(Some(&unknown)), None);
+        assert_eq!(client_cert_auth.get_identity(None), None);
+    }
+
+    fn create_certificate(dns_names: &[&str]) -> X509 {
+        let key = PKey::from_rsa(Rsa::generate(2048).expect("Failed to generate key"))
+            .expect("Failed to create key");
+
+        let mut name = X509NameBuilder::new().expect("Failed to create name builder");
+        name.append_entry_by_text("CN", "client")
+            .expect("Failed to add common name");
+        let name = name.build();
+
+        let mut builder = X509Builder::new().expect("Failed to create certificate builder");
+        builder.set_version(2).expect("Failed to set version");
+        builder
+            .set_subject_name(&name)
+            .expect("Failed to set subject");
+        builder
+            .set_issuer_name(&name)
+            .expect("Failed to set issuer");
+        builder.set_pubkey(&key).expect("Failed to set public key");
+        builder
+            .set_not_before(&Asn1Time::days_from_now(0).expect("Failed to create time"))
+            .expect("Failed to set not before");
+        builder
+            .set_not_after(&Asn1Time::days_from_now(1).expect("Failed to create time"))
+            .expect("Failed to set not after");
+
+        let mut subject_alt_name = SubjectAlternativeName::new();
+        for dns_name in dns_names {
+            subject_alt_name.dns(dns_name);
+        }
+        let subject_alt_name = subject_alt_name
+            .build(&builder.x509v3_context(None, None))
+            .expect("Failed to build subject alternative name");
+        builder
+            .append_extension(subject_alt_name)
+            .expect("Failed to add subject alternative name");
+
+        builder
+            .sign(&key, MessageDigest::sha256())
+            .expect("Failed to sign certificate");
+        builder.build()
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An identity provider that resolves API keys to the identities they were issued for
+
+use crate::api_key::{hash_api_key_secret, store::ApiKeyStore, ApiKeyIdentity};
+use crate::error::InternalError;
+use crate::rest_api::auth::AuthorizationHeader;
+
+use super::{Identity, IdentityProvider};
+
+/// Resolves API keys to the identities they were issued for
+///
+/// This provider only accepts `AuthorizationHeader::Custom` authorizations that use the `ApiKey`
+/// scheme (`ApiKey <secret>`). The secret is hashed and looked up in the provider's
+/// [ApiKeyStore]; if a key is found, the key's public key or user ID is returned as the client's
+/// identity, so the client is granted the permissions assigned to that identity.
+#[derive(Clone)]
+pub struct ApiKeyIdentityProvider {
+    api_key_store: Box<dyn ApiKeyStore>,
+}
+
+impl ApiKeyIdentityProvider {
+    /// Creates a new API key identity provider backed by the given store
+    pub fn new(api_key_store: Box<dyn ApiKeyStore>) -> Self {
+        Self { api_key_store }
+    }
+}
+
+impl IdentityProvider for ApiKeyIdentityProvider {
+    fn get_identity(
+        &self,
+        authorization: &AuthorizationHeader,
+    ) -> Result<Option<Identity>, InternalError> {
+        let secret = match authorization {
+            AuthorizationHeader::Custom(auth_str) => match parse_api_key(auth_str) {
+                Some(secret) => secret,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        let api_key = self
+            .api_key_store
+            .get_api_key_by_secret_hash(&hash_api_key_secret(secret))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(api_key.map(|api_key| match api_key.identity() {
+            ApiKeyIdentity::Key(key) => Identity::Key(key.clone()),
+            ApiKeyIdentity::User(user_id) => Identity::User(user_id.clone()),
+        }))
+    }
+
+    fn clone_box(&self) -> Box<dyn IdentityProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Extracts the secret from an `ApiKey <secret>` authorization
+fn parse_api_key(auth_str: &str) -> Option<&str> {
+    let secret = auth_str.strip_prefix("ApiKey ")?.trim();
+    if secret.is_empty() {
+        None
+    } else {
+        Some(secret)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::api_key::{store::DieselApiKeyStore, ApiKeyBuilder};
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verifies that `parse_api_key` only accepts non-empty `ApiKey` authorizations.
+    #[test]
+    fn parse_api_key_authorization() {
+        assert_eq!(parse_api_key("ApiKey abc123"), Some("abc123"));
+        assert_eq!(parse_api_key("ApiKey  "), None);
+        assert_eq!(parse_api_key("Basic abc123"), None);
+        assert_eq!(parse_api_key("abc123"), None);
+    }
+
+    /// Verifies that the provider returns the identity an API key was issued for, and `None` for
+    /// unknown keys or other authorization types.
+    #[test]
+    fn get_identity() {
+        let store = create_api_key_store();
+        let (user_key, user_secret) = ApiKeyBuilder::new()
+            .with_display_name("CI pipeline".into())
+            .with_identity(ApiKeyIdentity::User("ci-user".into()))
+            .generate()
+            .expect("Failed to generate API key");
+        store.add_api_key(&user_key).expect("Failed to add API key");
+        let (pubkey_key, pubkey_secret) = ApiKeyBuilder::new()
+            .with_display_name("Monitoring".into())
+            .with_identity(ApiKeyIdentity::Key("02abcdef".into()))
+            .generate()
+            .expect("Failed to generate API key");
+        store
+            .add_api_key(&pubkey_key)
+            .expect("Failed to add API key");
+
+        let provider = ApiKeyIdentityProvider::new(store);
+
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Custom(format!(
+                    "ApiKey {}",
+                    user_secret
+                )))
+                .expect("Failed to get identity"),
+            Some(Identity::User("ci-user".into()))
+        );
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Custom(format!(
+                    "ApiKey {}",
+                    pubkey_secret
+                )))
+                .expect("Failed to get identity"),
+            Some(Identity::Key("02abcdef".into()))
+        );
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Custom("ApiKey unknown".into()))
+                .expect("Failed to get identity"),
+            None
+        );
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Custom(user_secret))
+                .expect("Failed to get identity"),
+            None
+        );
+    }
+
+    fn create_api_key_store() -> Box<dyn ApiKeyStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselApiKeyStore::new(pool))
+    }
+}
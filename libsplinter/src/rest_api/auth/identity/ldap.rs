@@ -0,0 +1,434 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An identity provider backed by an LDAP (or Active Directory) server
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ldap3::{ldap_escape, LdapConn, LdapConnSettings, Scope, SearchEntry};
+
+use crate::error::{InternalError, InvalidStateError};
+use crate::rest_api::auth::AuthorizationHeader;
+
+use super::{Identity, IdentityProvider};
+
+/// The default filter used to find a user's entry; `{username}` is replaced by the username
+const DEFAULT_USER_SEARCH_FILTER: &str = "(uid={username})";
+/// The default filter used to find a user's groups; `{user_dn}` is replaced by the user's DN
+const DEFAULT_GROUP_SEARCH_FILTER: &str = "(member={user_dn})";
+/// The default attribute that holds a group's name
+const DEFAULT_GROUP_NAME_ATTRIBUTE: &str = "cn";
+/// The default amount of time to wait when connecting to the LDAP server
+const DEFAULT_CONNECTION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The LDAP groups of the users that have been authenticated by an [LdapIdentityProvider]
+///
+/// The groups of a user are refreshed each time the user is authenticated, so authorization
+/// handlers that run after the identity provider may rely on them being current for the request.
+#[derive(Clone, Default)]
+pub struct LdapUserGroups {
+    groups: Arc<Mutex<HashMap<String, Vec<String>>>>,
+}
+
+impl LdapUserGroups {
+    /// Returns the groups of the given user, or `None` if the user has not been authenticated
+    /// through LDAP.
+    pub fn get(&self, username: &str) -> Result<Option<Vec<String>>, InternalError> {
+        Ok(self
+            .groups
+            .lock()
+            .map_err(|_| InternalError::with_message("LDAP user groups lock poisoned".into()))?
+            .get(username)
+            .cloned())
+    }
+
+    pub(crate) fn set(&self, username: String, groups: Vec<String>) -> Result<(), InternalError> {
+        self.groups
+            .lock()
+            .map_err(|_| InternalError::with_message("LDAP user groups lock poisoned".into()))?
+            .insert(username, groups);
+        Ok(())
+    }
+}
+
+/// An identity provider, backed by an LDAP server, that returns the username of a user
+///
+/// This provider only accepts `AuthorizationHeader::Custom` authorizations that use the `Basic`
+/// scheme (`Basic <base64 of username:password>`). The provider binds to the LDAP server with its
+/// configured service account, searches for the user's entry and groups, and then binds as the
+/// user to verify the password. On success, the user's groups are recorded in the provider's
+/// [LdapUserGroups].
+#[derive(Clone)]
+pub struct LdapIdentityProvider {
+    url: String,
+    bind_dn: Option<String>,
+    bind_password: Option<String>,
+    user_search_base: String,
+    user_search_filter: String,
+    group_search_base: String,
+    group_search_filter: String,
+    group_name_attribute: String,
+    connection_timeout: Duration,
+    user_groups: LdapUserGroups,
+}
+
+impl LdapIdentityProvider {
+    /// Returns the groups of the users authenticated by this provider
+    pub fn user_groups(&self) -> LdapUserGroups {
+        self.user_groups.clone()
+    }
+
+    fn connect(&self) -> Result<LdapConn, InternalError> {
+        let settings = LdapConnSettings::new().set_conn_timeout(self.connection_timeout);
+        let mut conn = LdapConn::with_settings(settings, &self.url).map_err(|err| {
+            InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to connect to LDAP server".into(),
+            )
+        })?;
+
+        if let Some(bind_dn) = &self.bind_dn {
+            conn.simple_bind(bind_dn, self.bind_password.as_deref().unwrap_or(""))
+                .and_then(|result| result.success())
+                .map_err(|err| {
+                    InternalError::from_source_with_prefix(
+                        Box::new(err),
+                        "Unable to bind to LDAP server with the configured bind DN".into(),
+                    )
+                })?;
+        }
+
+        Ok(conn)
+    }
+
+    fn authenticate(&self, username: &str, password: &str) -> Result<bool, InternalError> {
+        let mut conn = self.connect()?;
+
+        let user_filter = self
+            .user_search_filter
+            .replace("{username}", &ldap_escape(username));
+        let (entries, _) = conn
+            .search(
+                &self.user_search_base,
+                Scope::Subtree,
+                &user_filter,
+                vec!["1.1"],
+            )
+            .and_then(|result| result.success())
+            .map_err(|err| {
+                InternalError::from_source_with_prefix(
+                    Box::new(err),
+                    "Unable to search for LDAP user".into(),
+                )
+            })?;
+
+        let user_dn = match entries.as_slice() {
+            [entry] => SearchEntry::construct(entry.clone()).dn,
+            [] => return Ok(false),
+            _ => {
+                warn!(
+                    "LDAP user search for {} returned multiple entries",
+                    username
+                );
+                return Ok(false);
+            }
+        };
+
+        let group_filter = self
+            .group_search_filter
+            .replace("{user_dn}", &ldap_escape(&user_dn))
+            .replace("{username}", &ldap_escape(username));
+        let (entries, _) = conn
+            .search(
+                &self.group_search_base,
+                Scope::Subtree,
+                &group_filter,
+                vec![self.group_name_attribute.as_str()],
+            )
+            .and_then(|result| result.success())
+            .map_err(|err| {
+                InternalError::from_source_with_prefix(
+                    Box::new(err),
+                    "Unable to search for LDAP groups".into(),
+                )
+            })?;
+        let groups = entries
+            .into_iter()
+            .filter_map(|entry| {
+                SearchEntry::construct(entry)
+                    .attrs
+                    .remove(&self.group_name_attribute)
+                    .and_then(|mut values| values.pop())
+            })
+            .collect();
+
+        // Binding as the user verifies the password; any non-success result code (e.g. invalid
+        // credentials) means the user is not authenticated.
+        let bind_result = conn.simple_bind(&user_dn, password).map_err(|err| {
+            InternalError::from_source_with_prefix(
+                Box::new(err),
+                "Unable to bind to LDAP server as user".into(),
+            )
+        })?;
+        let _ = conn.unbind();
+        if bind_result.rc != 0 {
+            debug!(
+                "LDAP bind failed for user {}: {}",
+                username, bind_result.text
+            );
+            return Ok(false);
+        }
+
+        self.user_groups.set(username.to_string(), groups)?;
+
+        Ok(true)
+    }
+}
+
+impl IdentityProvider for LdapIdentityProvider {
+    fn get_identity(
+        &self,
+        authorization: &AuthorizationHeader,
+    ) -> Result<Option<Identity>, InternalError> {
+        let (username, password) = match authorization {
+            AuthorizationHeader::Custom(auth_str) => match parse_basic_credentials(auth_str) {
+                Some(credentials) => credentials,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        // An empty password would result in an unauthenticated bind, which LDAP servers report as
+        // successful
+        if username.is_empty() || password.is_empty() {
+            return Ok(None);
+        }
+
+        if self.authenticate(&username, &password)? {
+            Ok(Some(Identity::User(username)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn clone_box(&self) -> Box<dyn IdentityProvider> {
+        Box::new(self.clone())
+    }
+}
+
+/// Parses the username and password from a `Basic` authorization string
+fn parse_basic_credentials(auth_str: &str) -> Option<(String, String)> {
+    let encoded = auth_str.strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(base64::decode(encoded.trim()).ok()?).ok()?;
+    let mut parts = decoded.splitn(2, ':');
+    match (parts.next(), parts.next()) {
+        (Some(username), Some(password)) => Some((username.to_string(), password.to_string())),
+        _ => None,
+    }
+}
+
+/// Builds an [LdapIdentityProvider]
+#[derive(Default)]
+pub struct LdapIdentityProviderBuilder {
+    url: Option<String>,
+    bind_dn: Option<String>,
+    bind_password: Option<String>,
+    user_search_base: Option<String>,
+    user_search_filter: Option<String>,
+    group_search_base: Option<String>,
+    group_search_filter: Option<String>,
+    group_name_attribute: Option<String>,
+    connection_timeout: Option<Duration>,
+}
+
+impl LdapIdentityProviderBuilder {
+    /// Creates a new builder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the URL of the LDAP server (e.g. `ldaps://ldap.example.com:636`). This is required.
+    pub fn with_url(mut self, url: String) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    /// Sets the DN of the service account used to search the directory. If not set, searches are
+    /// performed anonymously.
+    pub fn with_bind_dn(mut self, bind_dn: String) -> Self {
+        self.bind_dn = Some(bind_dn);
+        self
+    }
+
+    /// Sets the password of the service account used to search the directory
+    pub fn with_bind_password(mut self, bind_password: String) -> Self {
+        self.bind_password = Some(bind_password);
+        self
+    }
+
+    /// Sets the base DN under which users are searched for. This is required.
+    pub fn with_user_search_base(mut self, user_search_base: String) -> Self {
+        self.user_search_base = Some(user_search_base);
+        self
+    }
+
+    /// Sets the filter used to find a user's entry. The filter must contain `{username}`, which
+    /// is replaced by the escaped username. Defaults to `(uid={username})`.
+    pub fn with_user_search_filter(mut self, user_search_filter: String) -> Self {
+        self.user_search_filter = Some(user_search_filter);
+        self
+    }
+
+    /// Sets the base DN under which groups are searched for. Defaults to the user search base.
+    pub fn with_group_search_base(mut self, group_search_base: String) -> Self {
+        self.group_search_base = Some(group_search_base);
+        self
+    }
+
+    /// Sets the filter used to find a user's groups. `{user_dn}` and `{username}` are replaced by
+    /// the escaped DN and username of the user. Defaults to `(member={user_dn})`.
+    pub fn with_group_search_filter(mut self, group_search_filter: String) -> Self {
+        self.group_search_filter = Some(group_search_filter);
+        self
+    }
+
+    /// Sets the attribute that holds a group's name. Defaults to `cn`.
+    pub fn with_group_name_attribute(mut self, group_name_attribute: String) -> Self {
+        self.group_name_attribute = Some(group_name_attribute);
+        self
+    }
+
+    /// Sets the amount of time to wait when connecting to the LDAP server. Defaults to 10 seconds.
+    pub fn with_connection_timeout(mut self, connection_timeout: Duration) -> Self {
+        self.connection_timeout = Some(connection_timeout);
+        self
+    }
+
+    /// Builds the identity provider
+    ///
+    /// # Errors
+    ///
+    /// Returns an [InvalidStateError] if the URL or user search base was not set, or if the user
+    /// search filter does not contain `{username}`.
+    pub fn build(self) -> Result<LdapIdentityProvider, InvalidStateError> {
+        let url = self.url.ok_or_else(|| {
+            InvalidStateError::with_message("An LDAP URL is required".to_string())
+        })?;
+        let user_search_base = self.user_search_base.ok_or_else(|| {
+            InvalidStateError::with_message("An LDAP user search base is required".to_string())
+        })?;
+        let user_search_filter = self
+            .user_search_filter
+            .unwrap_or_else(|| DEFAULT_USER_SEARCH_FILTER.to_string());
+        if !user_search_filter.contains("{username}") {
+            return Err(InvalidStateError::with_message(format!(
+                "LDAP user search filter must contain {{username}}: {}",
+                user_search_filter
+            )));
+        }
+
+        Ok(LdapIdentityProvider {
+            url,
+            bind_dn: self.bind_dn,
+            bind_password: self.bind_password,
+            group_search_base: self
+                .group_search_base
+                .unwrap_or_else(|| user_search_base.clone()),
+            user_search_base,
+            user_search_filter,
+            group_search_filter: self
+                .group_search_filter
+                .unwrap_or_else(|| DEFAULT_GROUP_SEARCH_FILTER.to_string()),
+            group_name_attribute: self
+                .group_name_attribute
+                .unwrap_or_else(|| DEFAULT_GROUP_NAME_ATTRIBUTE.to_string()),
+            connection_timeout: self
+                .connection_timeout
+                .unwrap_or(DEFAULT_CONNECTION_TIMEOUT),
+            user_groups: LdapUserGroups::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that `parse_basic_credentials` extracts the username and password from a `Basic`
+    /// authorization, and allows the password to contain a colon.
+    #[test]
+    fn parse_basic() {
+        let auth = format!("Basic {}", base64::encode("alice:pass:word"));
+        assert_eq!(
+            parse_basic_credentials(&auth),
+            Some(("alice".to_string(), "pass:word".to_string()))
+        );
+    }
+
+    /// Verifies that `parse_basic_credentials` rejects other schemes and malformed credentials.
+    #[test]
+    fn parse_basic_invalid() {
+        assert_eq!(parse_basic_credentials("Bearer abc"), None);
+        assert_eq!(parse_basic_credentials("Basic not-base64!"), None);
+        let auth = format!("Basic {}", base64::encode("no-colon"));
+        assert_eq!(parse_basic_credentials(&auth), None);
+    }
+
+    /// Verifies that the identity provider ignores authorizations that are not `Basic` or that
+    /// have an empty password, without contacting the LDAP server.
+    #[test]
+    fn ignore_unsupported_authorizations() {
+        let provider = LdapIdentityProviderBuilder::new()
+            .with_url("ldap://127.0.0.1:1".into())
+            .with_user_search_base("dc=example,dc=com".into())
+            .build()
+            .expect("Failed to build provider");
+
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Custom("test".into()))
+                .expect("Failed to get identity"),
+            None
+        );
+        let auth = format!("Basic {}", base64::encode("alice:"));
+        assert_eq!(
+            provider
+                .get_identity(&AuthorizationHeader::Custom(auth))
+                .expect("Failed to get identity"),
+            None
+        );
+    }
+
+    /// Verifies that the builder requires a URL, a user search base and a user search filter
+    /// containing `{username}`.
+    #[test]
+    fn builder_validation() {
+        assert!(LdapIdentityProviderBuilder::new()
+            .with_user_search_base("dc=example,dc=com".into())
+            .build()
+            .is_err());
+        assert!(LdapIdentityProviderBuilder::new()
+            .with_url("ldap://localhost".into())
+            .build()
+            .is_err());
+        assert!(LdapIdentityProviderBuilder::new()
+            .with_url("ldap://localhost".into())
+            .with_user_search_base("dc=example,dc=com".into())
+            .with_user_search_filter("(uid=alice)".into())
+            .build()
+            .is_err());
+    }
+}
@@ -14,13 +14,19 @@
 
 //! Tools for identifying clients and users
 
+#[cfg(feature = "api-keys")]
+pub mod api_key;
 #[cfg(feature = "biome-credentials")]
 pub mod biome;
 #[cfg(feature = "cylinder-jwt")]
 pub mod cylinder;
+#[cfg(feature = "ldap")]
+pub mod ldap;
 #[cfg(feature = "oauth")]
 pub mod oauth;
 
+use std::fmt;
+
 use crate::error::InternalError;
 
 use super::AuthorizationHeader;
@@ -36,6 +42,17 @@ pub enum Identity {
     User(String),
 }
 
+impl fmt::Display for Identity {
+    /// Formats the identity as `<type>:<value>`, such as `user:alice`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Identity::Custom(custom) => write!(f, "custom:{}", custom),
+            Identity::Key(key) => write!(f, "key:{}", key),
+            Identity::User(user) => write!(f, "user:{}", user),
+        }
+    }
+}
+
 /// A service that fetches identities from a backing provider
 pub trait IdentityProvider: Send + Sync {
     /// Attempts to get the identity that corresponds to the given authorization header. This method
@@ -23,7 +23,11 @@ mod authorization_header;
 #[cfg(feature = "rest-api-actix-web-1")]
 mod authorization_result;
 mod bearer_token;
+#[cfg(feature = "rest-api-client-cert-auth")]
+pub mod client_cert;
 pub mod identity;
+#[cfg(feature = "rest-api-proxy-auth")]
+pub mod proxy;
 
 #[cfg(feature = "rest-api-actix-web-1")]
 pub use authorization_header::AuthorizationHeader;
@@ -54,6 +58,37 @@ use identity::{Identity, IdentityProvider};
 ///   permissions
 #[cfg(feature = "rest-api-actix-web-1")]
 fn authorize(
+    #[cfg(feature = "authorization")] method: &Method,
+    endpoint: &str,
+    auth_header: Option<&str>,
+    #[cfg(feature = "authorization")] permission_map: &PermissionMap<Method>,
+    identity_providers: &[Box<dyn IdentityProvider>],
+    #[cfg(feature = "authorization")] authorization_handlers: &[Box<dyn AuthorizationHandler>],
+) -> AuthorizationResult {
+    authorize_identity(
+        #[cfg(feature = "authorization")]
+        method,
+        endpoint,
+        || get_identity(auth_header, identity_providers),
+        #[cfg(feature = "authorization")]
+        permission_map,
+        #[cfg(feature = "authorization")]
+        authorization_handlers,
+    )
+}
+
+/// Checks authorization for the request using the identity returned by `resolve_identity`, which
+/// is only called if the endpoint requires an authenticated client.
+///
+/// # Arguments
+///
+/// * `method` - The HTTP method used for the request
+/// * `endpoint` - The endpoint that is being requested. Example: "/endpoint/path"
+/// * `resolve_identity` - Returns the client's identity, if it could be determined
+/// * `authorization_handlers` - The authorization handlers that will be used to check the client's
+///   permissions
+#[cfg(feature = "rest-api-actix-web-1")]
+fn authorize_identity<F>(
     #[cfg(feature = "authorization")] method: &Method,
     #[cfg(any(
         feature = "authorization",
@@ -67,11 +102,13 @@ fn authorize(
         feature = "oauth"
     )))]
     _endpoint: &str,
-    auth_header: Option<&str>,
+    resolve_identity: F,
     #[cfg(feature = "authorization")] permission_map: &PermissionMap<Method>,
-    identity_providers: &[Box<dyn IdentityProvider>],
     #[cfg(feature = "authorization")] authorization_handlers: &[Box<dyn AuthorizationHandler>],
-) -> AuthorizationResult {
+) -> AuthorizationResult
+where
+    F: FnOnce() -> Option<Identity>,
+{
     #[cfg(feature = "authorization")]
     {
         // Get the permission that applies to this request
@@ -82,12 +119,12 @@ fn authorize(
 
         match *permission {
             Permission::AllowUnauthenticated => AuthorizationResult::NoAuthorizationNecessary,
-            Permission::AllowAuthenticated => match get_identity(auth_header, identity_providers) {
+            Permission::AllowAuthenticated => match resolve_identity() {
                 Some(identity) => AuthorizationResult::Authorized(identity),
                 None => AuthorizationResult::Unauthorized,
             },
             Permission::Check { permission_id, .. } => {
-                match get_identity(auth_header, identity_providers) {
+                match resolve_identity() {
                     Some(identity) => {
                         for handler in authorization_handlers {
                             match handler.has_permission(&identity, permission_id) {
@@ -131,7 +168,7 @@ fn authorize(
             }
         }
 
-        match get_identity(auth_header, identity_providers) {
+        match resolve_identity() {
             Some(identity) => AuthorizationResult::Authorized(identity),
             None => AuthorizationResult::Unauthorized,
         }
@@ -0,0 +1,111 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Trust in the identity header set by an authenticating reverse proxy
+//!
+//! SSO gateways such as oauth2-proxy authenticate users themselves and pass the authenticated
+//! user to the upstream service in a request header (for example, `X-Auth-Request-User`). When
+//! the REST API is configured with [ProxyAuth], the value of this header is used as the client's
+//! [Identity::User], which is then authorized like any other user.
+//!
+//! Because any client can set the header, it is only trusted on requests from one of the
+//! explicitly configured proxy addresses. The header is ignored on all other requests, which are
+//! authenticated with the `Authorization` header as usual.
+
+use std::net::IpAddr;
+
+use super::identity::Identity;
+
+/// Configuration for trusting the identity header set by an authenticating reverse proxy
+#[derive(Clone, Debug)]
+pub struct ProxyAuth {
+    header: String,
+    trusted_addresses: Vec<IpAddr>,
+}
+
+impl ProxyAuth {
+    /// Creates a new configuration
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The name of the header that contains the authenticated user, such as
+    ///   `X-Auth-Request-User`
+    /// * `trusted_addresses` - The addresses of the proxies whose header is trusted
+    pub fn new(header: String, trusted_addresses: Vec<IpAddr>) -> Self {
+        Self {
+            header,
+            trusted_addresses,
+        }
+    }
+
+    /// Returns the name of the header that contains the authenticated user
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// Returns the addresses of the proxies whose header is trusted
+    pub fn trusted_addresses(&self) -> &[IpAddr] {
+        &self.trusted_addresses
+    }
+
+    /// Returns the identity in the header value if the request came from a trusted peer
+    ///
+    /// # Arguments
+    ///
+    /// * `peer` - The address of the client that sent the request
+    /// * `header_value` - The value of the identity header, if it was set
+    pub(crate) fn get_identity(
+        &self,
+        peer: Option<IpAddr>,
+        header_value: Option<&str>,
+    ) -> Option<Identity> {
+        let user = header_value?.trim();
+        if user.is_empty() || !self.trusted_addresses.contains(&peer?) {
+            return None;
+        }
+
+        Some(Identity::User(user.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that the header is only trusted from the trusted addresses, and that empty or
+    /// missing headers are ignored.
+    #[test]
+    fn get_identity_from_trusted_peers() {
+        let proxy = "10.0.0.5".parse().expect("Failed to parse address");
+        let other = "10.0.0.6".parse().expect("Failed to parse address");
+        let proxy_auth = ProxyAuth::new("X-Auth-Request-User".into(), vec![proxy]);
+
+        assert_eq!(
+            proxy_auth.get_identity(Some(proxy), Some("alice")),
+            Some(Identity::User("alice".into()))
+        );
+        assert_eq!(
+            proxy_auth.get_identity(Some(proxy), Some(" alice ")),
+            Some(Identity::User("alice".into()))
+        );
+        assert_eq!(proxy_auth.get_identity(Some(other), Some("alice")), None);
+        assert_eq!(
+            proxy_auth.get_identity("127.0.0.1".parse().ok(), Some("alice")),
+            None
+        );
+        assert_eq!(proxy_auth.get_identity(None, Some("alice")), None);
+        assert_eq!(proxy_auth.get_identity(Some(proxy), Some(" ")), None);
+        assert_eq!(proxy_auth.get_identity(Some(proxy), None), None);
+    }
+}
@@ -21,6 +21,10 @@ pub enum BindConfig {
         bind: String,
         cert_path: String,
         key_path: String,
+        /// The CA that client certificates must be signed by; client certificates are not required
+        /// if not set
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        client_ca_path: Option<String>,
     },
     /// A insecure HTTP binding.
     Http(String),
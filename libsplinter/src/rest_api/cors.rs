@@ -13,6 +13,8 @@
 // limitations under the License.
 
 //! Provides CORS support for the REST API
+use std::sync::{Arc, RwLock};
+
 use actix_web::dev::*;
 use actix_web::{
     http::header, http::header::HeaderValue, http::Method, Error as ActixError, HttpResponse,
@@ -22,23 +24,74 @@ use futures::{
     Future, IntoFuture, Poll,
 };
 
+/// The domains allowed by CORS.
+///
+/// The allow list is cheap to clone; all clones share the same domains, so the domains may be
+/// replaced while the REST API is running.
+#[derive(Clone)]
+pub struct CorsAllowList {
+    domains: Arc<RwLock<Vec<String>>>,
+}
+
+impl CorsAllowList {
+    /// Create an allow list with the given domains.
+    pub fn new(domains: Vec<String>) -> Self {
+        Self {
+            domains: Arc::new(RwLock::new(domains)),
+        }
+    }
+
+    /// Create an allow list that allows any domain.
+    pub fn allow_any() -> Self {
+        Self::new(vec!["*".into()])
+    }
+
+    /// Returns the allowed domains.
+    pub fn domains(&self) -> Vec<String> {
+        rwlock_read_unwrap!(self.domains).clone()
+    }
+
+    /// Replaces the allowed domains.
+    pub fn set_domains(&self, domains: Vec<String>) {
+        debug!("Updating CORS allow list: {:?}", domains);
+        *rwlock_write_unwrap!(self.domains) = domains;
+    }
+
+    fn is_allowed(&self, origin: &str) -> bool {
+        rwlock_read_unwrap!(self.domains)
+            .iter()
+            .any(|domain| domain == "*" || origin.contains(domain.as_str()))
+    }
+}
+
 /// Configuration for CORS support
 #[derive(Clone)]
 pub struct Cors {
-    allow_list: Vec<String>,
+    allow_list: CorsAllowList,
 }
 
 impl Cors {
     /// Initialize the CORS preflight check with a set of allowed domains.
     pub fn new(allow_list: Vec<String>) -> Self {
         debug!("Creating CORS with with_allow_list: {:?}", allow_list);
-        Cors { allow_list }
+        Cors {
+            allow_list: CorsAllowList::new(allow_list),
+        }
     }
 
     /// Initialize the CORS preflight check with "*" domains.
     pub fn new_allow_any() -> Self {
         Cors::new(vec!["*".into()])
     }
+
+    /// Initialize the CORS preflight check with an allow list that may be updated later.
+    pub fn with_allow_list(allow_list: CorsAllowList) -> Self {
+        debug!(
+            "Creating CORS with with_allow_list: {:?}",
+            allow_list.domains()
+        );
+        Cors { allow_list }
+    }
 }
 
 impl<S, B> Transform<S> for Cors
@@ -65,7 +118,7 @@ where
 #[doc(hidden)]
 pub struct CorsMiddleware<S> {
     service: S,
-    allow_list: Vec<String>,
+    allow_list: CorsAllowList,
 }
 
 impl<S, B> Service for CorsMiddleware<S>
@@ -96,10 +149,7 @@ where
                     .headers()
                     .get(header::ACCESS_CONTROL_REQUEST_HEADERS)
                     .cloned();
-                let allowed_origin = self
-                    .allow_list
-                    .iter()
-                    .any(|domain| domain == "*" || origin.contains(domain));
+                let allowed_origin = self.allow_list.is_allowed(&origin);
                 // This verifies if a client is making a preflight check with the OPTIONS
                 // http request method and the origin is allowed, the preflight check responds
                 // with a 200 OK status.
@@ -147,3 +197,26 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that an allow list allows origins containing one of its domains, and that replacing
+    /// the domains is seen by clones of the allow list.
+    #[test]
+    fn test_cors_allow_list() {
+        let allow_list = CorsAllowList::new(vec!["example.com".into()]);
+        let clone = allow_list.clone();
+
+        assert!(clone.is_allowed("https://app.example.com"));
+        assert!(!clone.is_allowed("https://other.org"));
+
+        allow_list.set_domains(vec!["other.org".into()]);
+        assert!(!clone.is_allowed("https://app.example.com"));
+        assert!(clone.is_allowed("https://other.org"));
+
+        allow_list.set_domains(vec!["*".into()]);
+        assert!(clone.is_allowed("https://anything.net"));
+    }
+}
@@ -75,7 +75,7 @@ impl fmt::Display for RestApiServerError {
     }
 }
 
-#[cfg(feature = "https-bind")]
+#[cfg(all(feature = "https-bind", feature = "tls-openssl"))]
 impl From<openssl::error::ErrorStack> for RestApiServerError {
     fn from(err: openssl::error::ErrorStack) -> Self {
         RestApiServerError::InternalError(InternalError::from_source(Box::new(err)))
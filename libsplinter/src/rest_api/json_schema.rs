@@ -0,0 +1,391 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Validation of JSON request bodies against JSON schemas.
+//!
+//! Endpoints that accept JSON bodies may validate them with a [JsonSchema] before deserializing
+//! them, so that clients receive an error for every invalid field (identified by its JSON pointer)
+//! instead of only the first deserialization failure.
+//!
+//! Only the following subset of JSON schema keywords is supported: `type`, `properties`,
+//! `required`, `additionalProperties`, `items`, `enum`, `minLength` and `minItems`. Other keywords
+//! are ignored.
+
+use std::error::Error;
+use std::fmt;
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// A JSON schema used to validate request bodies
+#[derive(Clone, Debug)]
+pub struct JsonSchema {
+    schema: Value,
+}
+
+impl JsonSchema {
+    /// Creates a new schema from its JSON representation
+    pub fn new(schema: Value) -> Self {
+        Self { schema }
+    }
+
+    /// Validates the given JSON value against the schema
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SchemaValidationError] that lists every part of the value that does not conform
+    /// to the schema.
+    pub fn validate(&self, value: &Value) -> Result<(), SchemaValidationError> {
+        let mut violations = vec![];
+        validate_value(&self.schema, value, "", &mut violations);
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaValidationError { violations })
+        }
+    }
+
+    /// Parses the given request body as JSON, validates it against the schema and deserializes it
+    ///
+    /// # Errors
+    ///
+    /// Returns a [SchemaValidationError] if the body is not valid JSON, does not conform to the
+    /// schema, or cannot be deserialized into `T`.
+    pub fn parse<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, SchemaValidationError> {
+        let value = serde_json::from_slice::<Value>(body).map_err(|err| {
+            SchemaValidationError::from_violation("", format!("body is not valid JSON: {}", err))
+        })?;
+
+        self.validate(&value)?;
+
+        serde_json::from_value(value)
+            .map_err(|err| SchemaValidationError::from_violation("", err.to_string()))
+    }
+}
+
+/// A part of a JSON value that does not conform to a schema
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchemaViolation {
+    path: String,
+    message: String,
+}
+
+impl SchemaViolation {
+    /// Returns the JSON pointer of the invalid part of the value (`""` for the whole value)
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Returns a description of the violation
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "/: {}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+/// An error returned when a JSON value does not conform to a schema
+#[derive(Debug)]
+pub struct SchemaValidationError {
+    violations: Vec<SchemaViolation>,
+}
+
+impl SchemaValidationError {
+    fn from_violation(path: &str, message: String) -> Self {
+        Self {
+            violations: vec![SchemaViolation {
+                path: path.into(),
+                message,
+            }],
+        }
+    }
+
+    /// Returns the violations found in the value
+    pub fn violations(&self) -> &[SchemaViolation] {
+        &self.violations
+    }
+}
+
+impl Error for SchemaValidationError {}
+
+impl fmt::Display for SchemaValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let violations = self
+            .violations
+            .iter()
+            .map(|violation| violation.to_string())
+            .collect::<Vec<_>>();
+        f.write_str(&violations.join("; "))
+    }
+}
+
+fn validate_value(
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    let mut violation = |message: String| {
+        violations.push(SchemaViolation {
+            path: path.into(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let expected_types = match expected {
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            Value::String(expected_type) => vec![expected_type.as_str()],
+            _ => vec![],
+        };
+        if !expected_types.is_empty()
+            && !expected_types
+                .iter()
+                .any(|expected_type| is_type(value, expected_type))
+        {
+            violation(format!(
+                "expected {}, found {}",
+                expected_types.join(" or "),
+                type_name(value)
+            ));
+            // The remaining keywords do not apply to a value of the wrong type
+            return;
+        }
+    }
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            let allowed = allowed
+                .iter()
+                .map(|allowed| allowed.to_string())
+                .collect::<Vec<_>>();
+            violation(format!("must be one of: {}", allowed.join(", ")));
+        }
+    }
+
+    match value {
+        Value::String(string) => {
+            if let Some(min_length) = schema.get("minLength").and_then(Value::as_u64) {
+                if (string.chars().count() as u64) < min_length {
+                    violation(format!("must be at least {} characters long", min_length));
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(min_items) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min_items {
+                    violation(format!("must contain at least {} items", min_items));
+                }
+            }
+            if let Some(items_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_value(
+                        items_schema,
+                        item,
+                        &format!("{}/{}", path, index),
+                        violations,
+                    );
+                }
+            }
+        }
+        Value::Object(object) => validate_object(schema, object, path, violations),
+        _ => (),
+    }
+}
+
+fn validate_object(
+    schema: &Value,
+    object: &Map<String, Value>,
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for property in required.iter().filter_map(Value::as_str) {
+            if !object.contains_key(property) {
+                violations.push(SchemaViolation {
+                    path: format!("{}/{}", path, escape_pointer(property)),
+                    message: "missing required property".into(),
+                });
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    for (property, property_value) in object {
+        let property_path = format!("{}/{}", path, escape_pointer(property));
+        match properties.and_then(|properties| properties.get(property)) {
+            Some(property_schema) => {
+                validate_value(property_schema, property_value, &property_path, violations)
+            }
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => violations.push(SchemaViolation {
+                    path: property_path,
+                    message: "unknown property".into(),
+                }),
+                Some(additional_schema @ Value::Object(_)) => validate_value(
+                    additional_schema,
+                    property_value,
+                    &property_path,
+                    violations,
+                ),
+                _ => (),
+            },
+        }
+    }
+}
+
+fn is_type(value: &Value, expected_type: &str) -> bool {
+    match expected_type {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "string" => value.is_string(),
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => false,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_i64() || number.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Escapes a property name for use in a JSON pointer, as described in RFC 6901
+fn escape_pointer(property: &str) -> String {
+    property.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn node_schema() -> JsonSchema {
+        JsonSchema::new(json!({
+            "type": "object",
+            "required": ["identity", "endpoints"],
+            "properties": {
+                "identity": { "type": "string", "minLength": 1 },
+                "endpoints": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": { "type": "string" }
+                },
+                "kind": { "enum": ["a", "b"] },
+                "metadata": {
+                    "type": "object",
+                    "additionalProperties": { "type": "string" }
+                }
+            },
+            "additionalProperties": false
+        }))
+    }
+
+    /// Verifies that a value that conforms to the schema is valid.
+    #[test]
+    fn valid_value() {
+        assert!(node_schema()
+            .validate(&json!({
+                "identity": "node-a",
+                "endpoints": ["tcps://localhost:8044"],
+                "kind": "a",
+                "metadata": { "company": "Cargill" }
+            }))
+            .is_ok());
+    }
+
+    /// Verifies that every violation is reported with the JSON pointer of the invalid field.
+    #[test]
+    fn invalid_fields() {
+        let err = node_schema()
+            .validate(&json!({
+                "identity": "",
+                "endpoints": ["tcps://localhost:8044", 1],
+                "kind": "c",
+                "metadata": { "company": false },
+                "extra/field": true
+            }))
+            .expect_err("Value should be invalid");
+
+        let mut violations = err
+            .violations()
+            .iter()
+            .map(|violation| violation.path().to_string())
+            .collect::<Vec<_>>();
+        violations.sort();
+        assert_eq!(
+            violations,
+            vec![
+                "/endpoints/1",
+                "/extra~1field",
+                "/identity",
+                "/kind",
+                "/metadata/company"
+            ]
+        );
+    }
+
+    /// Verifies that missing required fields, empty arrays and a wrong top-level type are
+    /// reported.
+    #[test]
+    fn missing_and_wrong_type() {
+        let err = node_schema()
+            .validate(&json!({ "endpoints": [] }))
+            .expect_err("Value should be invalid");
+        assert_eq!(
+            err.to_string(),
+            "/identity: missing required property; /endpoints: must contain at least 1 items"
+        );
+
+        let err = node_schema()
+            .validate(&json!([]))
+            .expect_err("Value should be invalid");
+        assert_eq!(err.to_string(), "/: expected object, found array");
+    }
+
+    /// Verifies that `parse` rejects invalid JSON and deserializes valid bodies.
+    #[test]
+    fn parse_body() {
+        #[derive(Deserialize)]
+        struct Node {
+            identity: String,
+        }
+
+        assert!(node_schema().parse::<Node>(b"{").is_err());
+
+        let node = node_schema()
+            .parse::<Node>(br#"{"identity": "node-a", "endpoints": ["tcp://a:8044"]}"#)
+            .expect("Failed to parse body");
+        assert_eq!(node.identity, "node-a");
+    }
+}
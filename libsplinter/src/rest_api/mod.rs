@@ -74,6 +74,8 @@ mod bind_config;
 #[cfg(feature = "rest-api-cors")]
 pub mod cors;
 mod errors;
+#[cfg(feature = "rest-api-json-schema")]
+pub mod json_schema;
 #[cfg(feature = "oauth")]
 mod oauth_config;
 pub mod paging;
@@ -128,3 +130,9 @@ const QUERY_ENCODE_SET: &AsciiSet = &CONTROLS
 pub fn percent_encode_filter_query(input: &str) -> String {
     percent_encoding::utf8_percent_encode(input, QUERY_ENCODE_SET).to_string()
 }
+
+/// Percent-encodes a free-form query parameter value, including the characters that separate
+/// query parameters.
+pub fn percent_encode_query_value(input: &str) -> String {
+    percent_encoding::utf8_percent_encode(input, percent_encoding::NON_ALPHANUMERIC).to_string()
+}
@@ -15,11 +15,19 @@
 mod orchestrator;
 mod processor;
 
+#[cfg(feature = "subsystem-control")]
+pub use orchestrator::ServiceSuspendHandle;
 pub use orchestrator::{
     AddServiceError, InitializeServiceError, ListServicesError, ManagedService,
     NewOrchestratorError, OrchestratorError, ServiceDefinition, ServiceOrchestrator,
     ServiceOrchestratorBuilder, ShutdownServiceError,
 };
+#[cfg(feature = "service-restart-policy")]
+pub use orchestrator::{
+    RestartPolicy, ServiceCrash, ServiceRestartHistory, ServiceRestartTracker, ServiceRunState,
+};
+#[cfg(feature = "service-type-limits")]
+pub use orchestrator::{ServiceTypeLimits, SERVICE_MEMORY_BUDGET_ARG, SERVICE_THREAD_BUDGET_ARG};
 
 pub use processor::{
     registry::StandardServiceNetworkRegistry, JoinHandles, ServiceProcessor,
@@ -14,10 +14,17 @@
 
 //! Builder for constructing new service orchestrators.
 
+#[cfg(any(feature = "service-restart-policy", feature = "service-type-limits"))]
+use std::collections::HashMap;
+
 use crate::error::InvalidStateError;
 use crate::service::instance::OrchestratableServiceFactory;
 use crate::transport::Connection;
 
+#[cfg(feature = "service-type-limits")]
+use super::limits::ServiceTypeLimits;
+#[cfg(feature = "service-restart-policy")]
+use super::restart::RestartPolicy;
 use super::runnable::RunnableServiceOrchestrator;
 
 const DEFAULT_INCOMING_CAPACITY: usize = 512;
@@ -32,6 +39,12 @@ pub struct ServiceOrchestratorBuilder {
     outgoing_capacity: Option<usize>,
     channel_capacity: Option<usize>,
     service_factories: Vec<Box<dyn OrchestratableServiceFactory>>,
+    #[cfg(feature = "service-restart-policy")]
+    restart_policy: Option<RestartPolicy>,
+    #[cfg(feature = "service-restart-policy")]
+    service_type_restart_policies: HashMap<String, RestartPolicy>,
+    #[cfg(feature = "service-type-limits")]
+    service_type_limits: HashMap<String, ServiceTypeLimits>,
 }
 
 impl ServiceOrchestratorBuilder {
@@ -87,6 +100,40 @@ impl ServiceOrchestratorBuilder {
         self
     }
 
+    /// Sets the policy used to restart services that crash.
+    ///
+    /// If not set, [`RestartPolicy::default`] is used.
+    #[cfg(feature = "service-restart-policy")]
+    pub fn with_restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
+        self.restart_policy = Some(restart_policy);
+        self
+    }
+
+    /// Sets the policy used to restart services of the given type that crash, in place of the
+    /// policy set by [`with_restart_policy`](Self::with_restart_policy).
+    #[cfg(feature = "service-restart-policy")]
+    pub fn with_service_type_restart_policy<S: Into<String>>(
+        mut self,
+        service_type: S,
+        restart_policy: RestartPolicy,
+    ) -> Self {
+        self.service_type_restart_policies
+            .insert(service_type.into(), restart_policy);
+        self
+    }
+
+    /// Sets the limits on the services of the given type. The services of a type without limits
+    /// are unbounded.
+    #[cfg(feature = "service-type-limits")]
+    pub fn with_service_type_limits<S: Into<String>>(
+        mut self,
+        service_type: S,
+        limits: ServiceTypeLimits,
+    ) -> Self {
+        self.service_type_limits.insert(service_type.into(), limits);
+        self
+    }
+
     /// Construct the RunnableServiceOrchestrator.
     ///
     /// # Errors
@@ -119,6 +166,12 @@ impl ServiceOrchestratorBuilder {
             incoming_capacity,
             outgoing_capacity,
             channel_capacity,
+            #[cfg(feature = "service-restart-policy")]
+            restart_policy: self.restart_policy.unwrap_or_default(),
+            #[cfg(feature = "service-restart-policy")]
+            service_type_restart_policies: self.service_type_restart_policies,
+            #[cfg(feature = "service-type-limits")]
+            service_type_limits: self.service_type_limits,
         })
     }
 }
@@ -64,6 +64,12 @@ pub enum InitializeServiceError {
     InitializationFailed(Box<dyn Error + Send>),
     LockPoisoned,
     UnknownType,
+    /// The orchestrator already runs the maximum number of services of the type
+    #[cfg(feature = "service-type-limits")]
+    InstanceLimitReached {
+        service_type: String,
+        max_instances: usize,
+    },
 }
 
 impl Error for InitializeServiceError {
@@ -72,6 +78,8 @@ impl Error for InitializeServiceError {
             InitializeServiceError::InitializationFailed(err) => Some(&**err),
             InitializeServiceError::LockPoisoned => None,
             InitializeServiceError::UnknownType => None,
+            #[cfg(feature = "service-type-limits")]
+            InitializeServiceError::InstanceLimitReached { .. } => None,
         }
     }
 }
@@ -84,6 +92,15 @@ impl std::fmt::Display for InitializeServiceError {
             }
             InitializeServiceError::LockPoisoned => write!(f, "internal lock poisoned"),
             InitializeServiceError::UnknownType => write!(f, "service type unknown"),
+            #[cfg(feature = "service-type-limits")]
+            InitializeServiceError::InstanceLimitReached {
+                service_type,
+                max_instances,
+            } => write!(
+                f,
+                "the maximum of {} {} services are already running",
+                max_instances, service_type
+            ),
         }
     }
 }
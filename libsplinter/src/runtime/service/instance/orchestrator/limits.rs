@@ -0,0 +1,122 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Limits on the services of each type that the orchestrator runs.
+//!
+//! An orchestrator refuses to initialize a service if it already runs the maximum number of
+//! services of that type, so that a flood of circuit proposals cannot exhaust the node's
+//! resources. The orchestrator cannot measure the threads or memory that a service uses, so the
+//! thread and memory budgets of a service type are passed to each of its instances in the
+//! [`SERVICE_THREAD_BUDGET_ARG`] and [`SERVICE_MEMORY_BUDGET_ARG`] arguments, and it is up to the
+//! service's factory to keep the instance within them.
+
+use std::collections::HashMap;
+
+/// The argument that holds the number of threads a service instance may use
+pub const SERVICE_THREAD_BUDGET_ARG: &str = "service_thread_budget";
+
+/// The argument that holds the number of bytes of memory a service instance may use
+pub const SERVICE_MEMORY_BUDGET_ARG: &str = "service_memory_budget";
+
+/// The limits on the services of one type.
+///
+/// Any limit that is not given is unbounded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServiceTypeLimits {
+    max_instances: Option<usize>,
+    thread_budget: Option<usize>,
+    memory_budget: Option<u64>,
+}
+
+impl ServiceTypeLimits {
+    /// Creates limits that do not bound the services of the type.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of services of the type that may be running at the same time.
+    pub fn with_max_instances(mut self, max_instances: usize) -> Self {
+        self.max_instances = Some(max_instances);
+        self
+    }
+
+    /// Sets the number of threads each service of the type may use.
+    pub fn with_thread_budget(mut self, thread_budget: usize) -> Self {
+        self.thread_budget = Some(thread_budget);
+        self
+    }
+
+    /// Sets the number of bytes of memory each service of the type may use.
+    pub fn with_memory_budget(mut self, memory_budget: u64) -> Self {
+        self.memory_budget = Some(memory_budget);
+        self
+    }
+
+    pub fn max_instances(&self) -> Option<usize> {
+        self.max_instances
+    }
+
+    pub fn thread_budget(&self) -> Option<usize> {
+        self.thread_budget
+    }
+
+    pub fn memory_budget(&self) -> Option<u64> {
+        self.memory_budget
+    }
+
+    /// Sets the budget arguments of a service of the type. Budgets given in the service's own
+    /// arguments, such as by a circuit proposal, are replaced, so that only the node's operator
+    /// sets them.
+    pub(super) fn apply_budgets(&self, args: &mut HashMap<String, String>) {
+        args.remove(SERVICE_THREAD_BUDGET_ARG);
+        args.remove(SERVICE_MEMORY_BUDGET_ARG);
+
+        if let Some(thread_budget) = self.thread_budget {
+            args.insert(
+                SERVICE_THREAD_BUDGET_ARG.to_string(),
+                thread_budget.to_string(),
+            );
+        }
+        if let Some(memory_budget) = self.memory_budget {
+            args.insert(
+                SERVICE_MEMORY_BUDGET_ARG.to_string(),
+                memory_budget.to_string(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verifies that the budgets are set in a service's arguments, and replace any budgets the
+    /// arguments already hold.
+    #[test]
+    fn apply_budgets() {
+        let mut args = HashMap::new();
+        args.insert("admin_keys".to_string(), "[]".to_string());
+        args.insert(SERVICE_THREAD_BUDGET_ARG.to_string(), "64".to_string());
+        args.insert(SERVICE_MEMORY_BUDGET_ARG.to_string(), "1".to_string());
+
+        ServiceTypeLimits::new()
+            .with_max_instances(10)
+            .with_thread_budget(2)
+            .apply_budgets(&mut args);
+
+        assert_eq!(args.get("admin_keys"), Some(&"[]".to_string()));
+        assert_eq!(args.get(SERVICE_THREAD_BUDGET_ARG), Some(&"2".to_string()));
+        assert_eq!(args.get(SERVICE_MEMORY_BUDGET_ARG), None);
+    }
+}
@@ -18,9 +18,17 @@ mod builder;
 #[cfg(feature = "rest-api-actix-web-1")]
 mod endpoint_provider;
 mod error;
+#[cfg(feature = "service-type-limits")]
+mod limits;
+#[cfg(feature = "service-restart-policy")]
+mod restart;
 mod runnable;
+#[cfg(feature = "subsystem-control")]
+mod suspend;
 
 use std::collections::HashMap;
+#[cfg(feature = "service-type-limits")]
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread::{self, JoinHandle};
@@ -50,7 +58,15 @@ pub use self::error::{
     AddServiceError, InitializeServiceError, ListServicesError, NewOrchestratorError,
     OrchestratorError, ShutdownServiceError,
 };
+#[cfg(feature = "service-type-limits")]
+pub use self::limits::{ServiceTypeLimits, SERVICE_MEMORY_BUDGET_ARG, SERVICE_THREAD_BUDGET_ARG};
+#[cfg(feature = "service-restart-policy")]
+pub use self::restart::{
+    RestartPolicy, ServiceCrash, ServiceRestartHistory, ServiceRestartTracker, ServiceRunState,
+};
 pub use self::runnable::RunnableServiceOrchestrator;
+#[cfg(feature = "subsystem-control")]
+pub use self::suspend::ServiceSuspendHandle;
 
 // Recv timeout in secs
 const TIMEOUT_SEC: u64 = 2;
@@ -92,6 +108,16 @@ pub struct ServiceOrchestrator {
     /// A (ServiceDefinition, ManagedService) map of services that have been stopped, but yet to
     /// be completely destroyed
     stopped_services: Arc<Mutex<HashMap<ServiceDefinition, Box<dyn OrchestratableService>>>>,
+    /// A (ServiceDefinition, ManagedService) map of services that have been stopped for
+    /// maintenance, and may be started again
+    #[cfg(feature = "subsystem-control")]
+    suspended_services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+    /// Tracks the services that have been initialized, so that they are restarted if they crash
+    #[cfg(feature = "service-restart-policy")]
+    restart_tracker: ServiceRestartTracker,
+    /// The limits on the services of each type
+    #[cfg(feature = "service-type-limits")]
+    service_type_limits: HashMap<String, ServiceTypeLimits>,
 
     /// `running` and `join_handles` are used to shutdown the orchestrator's background threads
     running: Arc<AtomicBool>,
@@ -156,6 +182,34 @@ impl ServiceOrchestrator {
             })
             .ok_or(InitializeServiceError::UnknownType)?;
 
+        #[cfg(feature = "service-type-limits")]
+        let mut args = args;
+        #[cfg(feature = "service-type-limits")]
+        if let Some(limits) = self
+            .service_type_limits
+            .get(&service_definition.service_type)
+        {
+            self.check_instance_limit(&service_definition, limits)?;
+            limits.apply_budgets(&mut args);
+        }
+
+        // A service that is initialized again after it was stopped, such as to restart it with new
+        // arguments, replaces the stopped instance, which must release the service's resources
+        // first.
+        if let Some(stopped_service) = self
+            .stopped_services
+            .lock()
+            .map_err(|_| InitializeServiceError::LockPoisoned)?
+            .remove(&service_definition)
+        {
+            stopped_service
+                .destroy()
+                .map_err(|err| InitializeServiceError::InitializationFailed(Box::new(err)))?;
+        }
+
+        #[cfg(feature = "service-restart-policy")]
+        let restart_args = args.clone();
+
         // Create the service.
         let mut service = factory.create_orchestratable_service(
             service_definition.service_id.clone(),
@@ -175,6 +229,11 @@ impl ServiceOrchestrator {
             .start(&registry)
             .map_err(|err| InitializeServiceError::InitializationFailed(Box::new(err)))?;
 
+        #[cfg(feature = "service-restart-policy")]
+        self.restart_tracker
+            .started(service_definition.clone(), restart_args)
+            .map_err(|_| InitializeServiceError::LockPoisoned)?;
+
         // Save the service.
         self.services
             .lock()
@@ -184,20 +243,105 @@ impl ServiceOrchestrator {
         Ok(())
     }
 
+    /// Checks that the orchestrator does not already run the maximum number of services of the
+    /// given service's type. The service itself is not counted, so that a running service may be
+    /// initialized again.
+    #[cfg(feature = "service-type-limits")]
+    fn check_instance_limit(
+        &self,
+        service_definition: &ServiceDefinition,
+        limits: &ServiceTypeLimits,
+    ) -> Result<(), InitializeServiceError> {
+        let max_instances = match limits.max_instances() {
+            Some(max_instances) => max_instances,
+            None => return Ok(()),
+        };
+
+        let is_instance = |service: &ServiceDefinition| {
+            service.service_type == service_definition.service_type && service != service_definition
+        };
+
+        #[allow(unused_mut)]
+        let mut instances = self
+            .services
+            .lock()
+            .map_err(|_| InitializeServiceError::LockPoisoned)?
+            .keys()
+            .filter(|service| is_instance(service))
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        // A service that crashed still counts against the limit while it waits to be restarted
+        #[cfg(feature = "service-restart-policy")]
+        instances.extend(
+            self.restart_tracker
+                .list()
+                .map_err(|_| InitializeServiceError::LockPoisoned)?
+                .into_iter()
+                .filter(|history| {
+                    history.state() == ServiceRunState::Restarting && is_instance(history.service())
+                })
+                .map(|history| history.service().clone()),
+        );
+
+        if instances.len() >= max_instances {
+            return Err(InitializeServiceError::InstanceLimitReached {
+                service_type: service_definition.service_type.clone(),
+                max_instances,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Stop the specified service.
     pub fn stop_service(
         &self,
         service_definition: &ServiceDefinition,
     ) -> Result<(), ShutdownServiceError> {
-        let ManagedService {
-            mut service,
-            registry,
-        } = self
+        let managed_service = self
             .services
             .lock()
             .map_err(|_| ShutdownServiceError::LockPoisoned)?
+            .remove(service_definition);
+
+        // A suspended service has already been stopped
+        #[cfg(feature = "subsystem-control")]
+        if managed_service.is_none() {
+            if let Some(suspended) = self
+                .suspended_services
+                .lock()
+                .map_err(|_| ShutdownServiceError::LockPoisoned)?
+                .remove(service_definition)
+            {
+                #[cfg(feature = "service-restart-policy")]
+                self.restart_tracker
+                    .remove(service_definition)
+                    .map_err(|_| ShutdownServiceError::LockPoisoned)?;
+                self.stopped_services
+                    .lock()
+                    .map_err(|_| ShutdownServiceError::LockPoisoned)?
+                    .insert(service_definition.clone(), suspended.service);
+                return Ok(());
+            }
+        }
+
+        // A service that crashed is not running, but may still be stopped so that it is no longer
+        // restarted.
+        #[cfg(feature = "service-restart-policy")]
+        if self
+            .restart_tracker
             .remove(service_definition)
-            .ok_or(ShutdownServiceError::UnknownService)?;
+            .map_err(|_| ShutdownServiceError::LockPoisoned)?
+            && managed_service.is_none()
+        {
+            return Ok(());
+        }
+
+        let ManagedService {
+            mut service,
+            registry,
+        } = managed_service.ok_or(ShutdownServiceError::UnknownService)?;
 
         service.stop(&registry).map_err(|err| {
             ShutdownServiceError::ShutdownFailed((service_definition.clone(), Box::new(err)))
@@ -250,6 +394,19 @@ impl ServiceOrchestrator {
                 ShutdownServiceError::ShutdownFailed((service_definition, Box::new(err)))
             })?;
         }
+
+        // Suspended services have already been stopped
+        #[cfg(feature = "subsystem-control")]
+        for (service_definition, managed_service) in self
+            .suspended_services
+            .lock()
+            .map_err(|_| ShutdownServiceError::LockPoisoned)?
+            .drain()
+        {
+            managed_service.service.destroy().map_err(|err| {
+                ShutdownServiceError::ShutdownFailed((service_definition, Box::new(err)))
+            })?;
+        }
         self.running.store(false, Ordering::SeqCst);
 
         Ok(())
@@ -340,6 +497,18 @@ impl ServiceOrchestrator {
     pub fn services(&self) -> Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>> {
         self.services.clone()
     }
+
+    /// Returns a handle that suspends and resumes the orchestrator's services.
+    #[cfg(feature = "subsystem-control")]
+    pub fn suspend_handle(&self) -> ServiceSuspendHandle {
+        ServiceSuspendHandle::new(self.services.clone(), self.suspended_services.clone())
+    }
+
+    /// Returns the tracker of the state and restart history of the orchestrator's services.
+    #[cfg(feature = "service-restart-policy")]
+    pub fn restart_tracker(&self) -> ServiceRestartTracker {
+        self.restart_tracker.clone()
+    }
 }
 
 pub struct JoinHandles<T> {
@@ -529,6 +698,7 @@ fn run_inbound_loop(
     services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
     inbound_receiver: Receiver<Result<(CircuitMessageType, Vec<u8>), channel::RecvError>>,
     inbound_running: Arc<AtomicBool>,
+    #[cfg(feature = "service-restart-policy")] restart_tracker: ServiceRestartTracker,
 ) -> Result<(), OrchestratorError> {
     let timeout = Duration::from_secs(TIMEOUT_SEC);
     while inbound_running.load(Ordering::SeqCst) {
@@ -550,6 +720,8 @@ fn run_inbound_loop(
                 let services = services
                     .lock()
                     .map_err(|_| OrchestratorError::LockPoisoned)?;
+                #[cfg(feature = "service-restart-policy")]
+                let mut crashed = None;
 
                 match services.iter().find_map(|(service_def, managed_service)| {
                     if service_def.circuit == admin_direct_message.get_circuit()
@@ -567,6 +739,21 @@ fn run_inbound_loop(
                             correlation_id: admin_direct_message.take_correlation_id(),
                         };
 
+                        #[cfg(feature = "service-restart-policy")]
+                        match restart::handle_message(
+                            &**service,
+                            admin_direct_message.get_payload(),
+                            &msg_context,
+                        ) {
+                            Ok(()) => (),
+                            Err(restart::HandleMessageError::Failed(err)) => {
+                                error!("unable to handle admin direct message: {}", err)
+                            }
+                            Err(restart::HandleMessageError::Crashed(reason)) => {
+                                crashed = Some((msg_context.circuit.clone(), reason))
+                            }
+                        }
+                        #[cfg(not(feature = "service-restart-policy"))]
                         if let Err(err) =
                             service.handle_message(admin_direct_message.get_payload(), &msg_context)
                         {
@@ -579,6 +766,17 @@ fn run_inbound_loop(
                         admin_direct_message.get_circuit(),
                     ),
                 }
+
+                #[cfg(feature = "service-restart-policy")]
+                if let Some((circuit, reason)) = crashed {
+                    restart::isolate_crashed_service(
+                        services,
+                        &restart_tracker,
+                        &circuit,
+                        admin_direct_message.get_recipient(),
+                        reason,
+                    )?;
+                }
             }
             (CircuitMessageType::CIRCUIT_DIRECT_MESSAGE, msg) => {
                 let mut circuit_direct_message: CircuitDirectMessage =
@@ -588,6 +786,8 @@ fn run_inbound_loop(
                 let services = services
                     .lock()
                     .map_err(|_| OrchestratorError::LockPoisoned)?;
+                #[cfg(feature = "service-restart-policy")]
+                let mut crashed = None;
 
                 match services.iter().find_map(|(service_def, managed_service)| {
                     if service_def.circuit == circuit_direct_message.get_circuit()
@@ -605,6 +805,21 @@ fn run_inbound_loop(
                             correlation_id: circuit_direct_message.take_correlation_id(),
                         };
 
+                        #[cfg(feature = "service-restart-policy")]
+                        match restart::handle_message(
+                            &**service,
+                            circuit_direct_message.get_payload(),
+                            &msg_context,
+                        ) {
+                            Ok(()) => (),
+                            Err(restart::HandleMessageError::Failed(err)) => {
+                                error!("unable to handle direct message: {}", err)
+                            }
+                            Err(restart::HandleMessageError::Crashed(reason)) => {
+                                crashed = Some((msg_context.circuit.clone(), reason))
+                            }
+                        }
+                        #[cfg(not(feature = "service-restart-policy"))]
                         if let Err(err) = service
                             .handle_message(circuit_direct_message.get_payload(), &msg_context)
                         {
@@ -617,6 +832,17 @@ fn run_inbound_loop(
                         circuit_direct_message.get_circuit(),
                     ),
                 }
+
+                #[cfg(feature = "service-restart-policy")]
+                if let Some((circuit, reason)) = crashed {
+                    restart::isolate_crashed_service(
+                        services,
+                        &restart_tracker,
+                        &circuit,
+                        circuit_direct_message.get_recipient(),
+                        reason,
+                    )?;
+                }
             }
             (msg_type, _) => warn!(
                 "Received message ({:?}) that does not have a correlation id",
@@ -0,0 +1,701 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Restarting services that crash.
+//!
+//! A service crashes if it panics while handling a message, or if it reports that it cannot
+//! recover from a poisoned lock. The panic is caught so that the orchestrator keeps handling the
+//! messages of its other services, and the crashed service is stopped and replaced by a new
+//! instance according to its [`RestartPolicy`].
+
+use std::any::Any;
+use std::collections::{HashMap, VecDeque};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+use crossbeam_channel::Sender;
+
+use crate::error::InternalError;
+use crate::network::reply::InboundRouter;
+use crate::protos::circuit::CircuitMessageType;
+use crate::runtime::service::instance::StandardServiceNetworkRegistry;
+use crate::service::instance::{
+    FactoryCreateError, OrchestratableService, OrchestratableServiceFactory, ServiceError,
+    ServiceFactory, ServiceInstance, ServiceMessageContext,
+};
+
+use super::{ManagedService, OrchestratorError, ServiceDefinition};
+
+const DEFAULT_MAX_RESTARTS: u32 = 5;
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+const DEFAULT_RESET_AFTER: Duration = Duration::from_secs(600);
+
+/// The number of crashes kept in each service's history
+const MAX_CRASH_HISTORY: usize = 10;
+
+/// How often the restart thread checks for services that are due to be restarted
+const RESTART_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+/// How the orchestrator restarts a service that crashes.
+///
+/// A crashed service is restarted once its backoff has elapsed. The backoff starts at the initial
+/// backoff and doubles with each restart, up to the maximum backoff. If a service crashes again
+/// after it has been restarted the maximum number of times, without running for the reset period
+/// in between, it is marked as failed and is not restarted until it is initialized again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RestartPolicy {
+    max_restarts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    reset_after: Duration,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self {
+            max_restarts: DEFAULT_MAX_RESTARTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            reset_after: DEFAULT_RESET_AFTER,
+        }
+    }
+}
+
+impl RestartPolicy {
+    /// Creates a policy that restarts a service up to five times, backing off from one second to
+    /// one minute, and resets once the service has run for ten minutes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of times a service is restarted before it is marked as failed. A policy
+    /// with no restarts marks a service as failed as soon as it crashes.
+    pub fn with_max_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Sets the time to wait before the first restart of a crashed service.
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the longest time to wait before restarting a crashed service.
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets how long a restarted service must run before its restarts are no longer counted.
+    pub fn with_reset_after(mut self, reset_after: Duration) -> Self {
+        self.reset_after = reset_after;
+        self
+    }
+
+    pub fn max_restarts(&self) -> u32 {
+        self.max_restarts
+    }
+
+    pub fn initial_backoff(&self) -> Duration {
+        self.initial_backoff
+    }
+
+    pub fn max_backoff(&self) -> Duration {
+        self.max_backoff
+    }
+
+    pub fn reset_after(&self) -> Duration {
+        self.reset_after
+    }
+
+    /// Returns the time to wait before restarting a service that has been restarted the given
+    /// number of times.
+    fn backoff(&self, restarts: u32) -> Duration {
+        2u32.checked_pow(restarts)
+            .and_then(|factor| self.initial_backoff.checked_mul(factor))
+            .map(|backoff| backoff.min(self.max_backoff))
+            .unwrap_or(self.max_backoff)
+    }
+}
+
+/// Whether a service managed by the orchestrator is running
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ServiceRunState {
+    Running,
+    /// The service crashed and is waiting to be restarted
+    Restarting,
+    /// The service crashed more often than its restart policy allows, and is not restarted
+    Failed,
+}
+
+/// A crash of a service managed by the orchestrator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceCrash {
+    crashed_at: SystemTime,
+    reason: String,
+}
+
+impl ServiceCrash {
+    pub fn crashed_at(&self) -> SystemTime {
+        self.crashed_at
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+}
+
+/// The restart history of a service managed by the orchestrator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServiceRestartHistory {
+    service: ServiceDefinition,
+    state: ServiceRunState,
+    restarts: u32,
+    crashes: Vec<ServiceCrash>,
+}
+
+impl ServiceRestartHistory {
+    pub fn service(&self) -> &ServiceDefinition {
+        &self.service
+    }
+
+    pub fn state(&self) -> ServiceRunState {
+        self.state
+    }
+
+    /// Returns the number of times the service has been restarted since its restarts were last
+    /// reset.
+    pub fn restarts(&self) -> u32 {
+        self.restarts
+    }
+
+    /// Returns the service's most recent crashes, oldest first.
+    pub fn crashes(&self) -> &[ServiceCrash] {
+        &self.crashes
+    }
+}
+
+struct SupervisedService {
+    args: HashMap<String, String>,
+    policy: RestartPolicy,
+    state: ServiceRunState,
+    restarts: u32,
+    started_at: Instant,
+    restart_at: Option<Instant>,
+    crashes: VecDeque<ServiceCrash>,
+}
+
+/// Tracks the state and restart history of the services managed by an orchestrator.
+#[derive(Clone)]
+pub struct ServiceRestartTracker {
+    default_policy: RestartPolicy,
+    service_type_policies: Arc<HashMap<String, RestartPolicy>>,
+    services: Arc<Mutex<HashMap<ServiceDefinition, SupervisedService>>>,
+}
+
+impl ServiceRestartTracker {
+    pub(super) fn new(
+        default_policy: RestartPolicy,
+        service_type_policies: HashMap<String, RestartPolicy>,
+    ) -> Self {
+        Self {
+            default_policy,
+            service_type_policies: Arc::new(service_type_policies),
+            services: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Lists the restart history of every service, ordered by circuit and service ID.
+    pub fn list(&self) -> Result<Vec<ServiceRestartHistory>, InternalError> {
+        let mut histories = self
+            .lock()?
+            .iter()
+            .map(|(service, supervised)| ServiceRestartHistory {
+                service: service.clone(),
+                state: supervised.state,
+                restarts: supervised.restarts,
+                crashes: supervised.crashes.iter().cloned().collect(),
+            })
+            .collect::<Vec<_>>();
+        histories.sort_by(|a, b| {
+            (&a.service.circuit, &a.service.service_id)
+                .cmp(&(&b.service.circuit, &b.service.service_id))
+        });
+        Ok(histories)
+    }
+
+    /// Records that a service was initialized with the given arguments, which clears its history.
+    pub(super) fn started(
+        &self,
+        service: ServiceDefinition,
+        args: HashMap<String, String>,
+    ) -> Result<(), InternalError> {
+        let policy = self
+            .service_type_policies
+            .get(&service.service_type)
+            .unwrap_or(&self.default_policy)
+            .clone();
+        self.lock()?.insert(
+            service,
+            SupervisedService {
+                args,
+                policy,
+                state: ServiceRunState::Running,
+                restarts: 0,
+                started_at: Instant::now(),
+                restart_at: None,
+                crashes: VecDeque::new(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Stops tracking a service, returning whether it was tracked.
+    pub(super) fn remove(&self, service: &ServiceDefinition) -> Result<bool, InternalError> {
+        Ok(self.lock()?.remove(service).is_some())
+    }
+
+    /// Records that a service crashed, and schedules its restart if its policy allows.
+    pub(super) fn crashed(
+        &self,
+        service: &ServiceDefinition,
+        reason: String,
+    ) -> Result<(), InternalError> {
+        let mut services = self.lock()?;
+        let supervised = match services.get_mut(service) {
+            Some(supervised) => supervised,
+            None => return Ok(()),
+        };
+
+        if supervised.started_at.elapsed() >= supervised.policy.reset_after {
+            supervised.restarts = 0;
+        }
+        if supervised.crashes.len() == MAX_CRASH_HISTORY {
+            supervised.crashes.pop_front();
+        }
+        supervised.crashes.push_back(ServiceCrash {
+            crashed_at: SystemTime::now(),
+            reason: reason.clone(),
+        });
+
+        if supervised.restarts >= supervised.policy.max_restarts {
+            error!(
+                "Service {} crashed after {} restarts and will not be restarted again: {}",
+                service, supervised.restarts, reason
+            );
+            supervised.state = ServiceRunState::Failed;
+            supervised.restart_at = None;
+        } else {
+            let backoff = supervised.policy.backoff(supervised.restarts);
+            warn!(
+                "Service {} crashed, restarting in {:?}: {}",
+                service, backoff, reason
+            );
+            supervised.state = ServiceRunState::Restarting;
+            supervised.restart_at = Some(Instant::now() + backoff);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the services that are due to be restarted, with their arguments, and counts the
+    /// restart.
+    fn take_due(&self) -> Result<Vec<(ServiceDefinition, HashMap<String, String>)>, InternalError> {
+        let now = Instant::now();
+        Ok(self
+            .lock()?
+            .iter_mut()
+            .filter(|(_, supervised)| {
+                supervised.state == ServiceRunState::Restarting
+                    && supervised
+                        .restart_at
+                        .map(|restart_at| restart_at <= now)
+                        .unwrap_or(false)
+            })
+            .map(|(service, supervised)| {
+                supervised.restarts += 1;
+                supervised.started_at = now;
+                supervised.restart_at = None;
+                (service.clone(), supervised.args.clone())
+            })
+            .collect())
+    }
+
+    /// Records that a service was restarted, returning false if the service is no longer tracked
+    /// because it was stopped in the meantime.
+    fn restarted(&self, service: &ServiceDefinition) -> Result<bool, InternalError> {
+        Ok(match self.lock()?.get_mut(service) {
+            Some(supervised) => {
+                supervised.state = ServiceRunState::Running;
+                true
+            }
+            None => false,
+        })
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<MutexGuard<HashMap<ServiceDefinition, SupervisedService>>, InternalError> {
+        self.services.lock().map_err(|_| {
+            InternalError::with_message("Service restart tracker lock was poisoned".into())
+        })
+    }
+}
+
+/// A service factory that is shared between the orchestrator and its restart thread
+#[derive(Clone)]
+pub(super) struct SharedServiceFactory {
+    service_types: Vec<String>,
+    factory: Arc<Mutex<Box<dyn OrchestratableServiceFactory>>>,
+}
+
+impl SharedServiceFactory {
+    /// Wraps the given factories, returning them both as boxed factories for the orchestrator
+    /// and as shared factories for the restart thread.
+    pub fn share(
+        factories: Vec<Box<dyn OrchestratableServiceFactory>>,
+    ) -> (Vec<Box<dyn OrchestratableServiceFactory>>, Vec<Self>) {
+        let shared = factories
+            .into_iter()
+            .map(|factory| Self {
+                service_types: factory.available_service_types().to_vec(),
+                factory: Arc::new(Mutex::new(factory)),
+            })
+            .collect::<Vec<_>>();
+        let boxed = shared
+            .iter()
+            .cloned()
+            .map(|factory| Box::new(factory) as Box<dyn OrchestratableServiceFactory>)
+            .collect();
+        (boxed, shared)
+    }
+
+    fn lock(
+        &self,
+    ) -> Result<MutexGuard<Box<dyn OrchestratableServiceFactory>>, FactoryCreateError> {
+        self.factory
+            .lock()
+            .map_err(|_| FactoryCreateError::Internal("Service factory lock was poisoned".into()))
+    }
+}
+
+impl ServiceFactory for SharedServiceFactory {
+    fn available_service_types(&self) -> &[String] {
+        &self.service_types
+    }
+
+    fn create(
+        &self,
+        service_id: String,
+        service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn ServiceInstance>, FactoryCreateError> {
+        self.lock()?
+            .create(service_id, service_type, circuit_id, args)
+    }
+}
+
+impl OrchestratableServiceFactory for SharedServiceFactory {
+    fn create_orchestratable_service(
+        &self,
+        service_id: String,
+        service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn OrchestratableService>, FactoryCreateError> {
+        self.lock()?
+            .create_orchestratable_service(service_id, service_type, circuit_id, args)
+    }
+}
+
+/// The outcome of a service handling a message that it did not handle successfully
+pub(super) enum HandleMessageError {
+    /// The service returned an error
+    Failed(ServiceError),
+    /// The service crashed, for the given reason
+    Crashed(String),
+}
+
+/// Handles a message with a service, catching a panic so that it does not stop the orchestrator
+/// from handling the messages of its other services.
+pub(super) fn handle_message(
+    service: &dyn OrchestratableService,
+    message_bytes: &[u8],
+    message_context: &ServiceMessageContext,
+) -> Result<(), HandleMessageError> {
+    match panic::catch_unwind(AssertUnwindSafe(|| {
+        service.handle_message(message_bytes, message_context)
+    })) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(ServiceError::PoisonedLock(msg))) => Err(HandleMessageError::Crashed(format!(
+            "service lock was poisoned: {}",
+            msg
+        ))),
+        Ok(Err(err)) => Err(HandleMessageError::Failed(err)),
+        Err(payload) => Err(HandleMessageError::Crashed(format!(
+            "service panicked: {}",
+            panic_message(&*payload)
+        ))),
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause")
+}
+
+/// Removes a crashed service from the running services, stops and destroys it, and records the
+/// crash so that the service is restarted according to its policy.
+pub(super) fn isolate_crashed_service(
+    mut services: MutexGuard<HashMap<ServiceDefinition, ManagedService>>,
+    tracker: &ServiceRestartTracker,
+    circuit: &str,
+    service_id: &str,
+    reason: String,
+) -> Result<(), OrchestratorError> {
+    let service_definition = match services
+        .keys()
+        .find(|service| service.circuit == circuit && service.service_id == service_id)
+        .cloned()
+    {
+        Some(service_definition) => service_definition,
+        None => return Ok(()),
+    };
+    let managed_service = services.remove(&service_definition);
+    drop(services);
+
+    if let Some(ManagedService {
+        mut service,
+        registry,
+    }) = managed_service
+    {
+        if let Err(err) = service.stop(&registry) {
+            warn!(
+                "Unable to stop crashed service {}: {}",
+                service_definition, err
+            );
+        }
+        if let Err(err) = service.destroy() {
+            warn!(
+                "Unable to destroy crashed service {}: {}",
+                service_definition, err
+            );
+        }
+    }
+
+    tracker
+        .crashed(&service_definition, reason)
+        .map_err(|err| OrchestratorError::Internal(Box::new(err)))
+}
+
+/// Restarts crashed services when they are due until the orchestrator shuts down.
+pub(super) fn run_restart_loop(
+    services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+    tracker: ServiceRestartTracker,
+    factories: Vec<SharedServiceFactory>,
+    network_sender: Sender<Vec<u8>>,
+    inbound_router: InboundRouter<CircuitMessageType>,
+    running: Arc<AtomicBool>,
+) -> Result<(), OrchestratorError> {
+    while running.load(Ordering::SeqCst) {
+        thread::sleep(RESTART_CHECK_INTERVAL);
+
+        let due = tracker
+            .take_due()
+            .map_err(|err| OrchestratorError::Internal(Box::new(err)))?;
+        for (service_definition, args) in due {
+            info!("Restarting service {}", service_definition);
+            let registry = StandardServiceNetworkRegistry::new(
+                service_definition.circuit.clone(),
+                network_sender.clone(),
+                inbound_router.clone(),
+            );
+            match start_service(&factories, &service_definition, args, &registry) {
+                Ok(service) => {
+                    restarted(&services, &tracker, service_definition, service, registry)?
+                }
+                Err(reason) => tracker
+                    .crashed(
+                        &service_definition,
+                        format!("unable to restart service: {}", reason),
+                    )
+                    .map_err(|err| OrchestratorError::Internal(Box::new(err)))?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn start_service(
+    factories: &[SharedServiceFactory],
+    service_definition: &ServiceDefinition,
+    args: HashMap<String, String>,
+    registry: &StandardServiceNetworkRegistry,
+) -> Result<Box<dyn OrchestratableService>, String> {
+    let factory = factories
+        .iter()
+        .find(|factory| {
+            factory
+                .available_service_types()
+                .contains(&service_definition.service_type)
+        })
+        .ok_or_else(|| "no factory for the service's type".to_string())?;
+
+    let mut service = factory
+        .create_orchestratable_service(
+            service_definition.service_id.clone(),
+            &service_definition.service_type,
+            &service_definition.circuit,
+            args,
+        )
+        .map_err(|err| err.to_string())?;
+    service.start(registry).map_err(|err| err.to_string())?;
+
+    Ok(service)
+}
+
+/// Adds a restarted service to the running services, unless the service was stopped or
+/// initialized again while it was being restarted.
+fn restarted(
+    services: &Mutex<HashMap<ServiceDefinition, ManagedService>>,
+    tracker: &ServiceRestartTracker,
+    service_definition: ServiceDefinition,
+    mut service: Box<dyn OrchestratableService>,
+    registry: StandardServiceNetworkRegistry,
+) -> Result<(), OrchestratorError> {
+    let mut services = services
+        .lock()
+        .map_err(|_| OrchestratorError::LockPoisoned)?;
+    let tracked = tracker
+        .restarted(&service_definition)
+        .map_err(|err| OrchestratorError::Internal(Box::new(err)))?;
+
+    if tracked && !services.contains_key(&service_definition) {
+        info!("Restarted service {}", service_definition);
+        services.insert(service_definition, ManagedService { service, registry });
+    } else {
+        drop(services);
+        debug!(
+            "Discarding restarted service {}, which was replaced while restarting",
+            service_definition
+        );
+        if let Err(err) = service.stop(&registry) {
+            warn!(
+                "Unable to stop restarted service {}: {}",
+                service_definition, err
+            );
+        }
+        if let Err(err) = service.destroy() {
+            warn!(
+                "Unable to destroy restarted service {}: {}",
+                service_definition, err
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn service_definition() -> ServiceDefinition {
+        ServiceDefinition {
+            circuit: "abcde-01234".into(),
+            service_id: "a000".into(),
+            service_type: "test".into(),
+        }
+    }
+
+    /// Verify that the backoff doubles with each restart up to the maximum backoff.
+    #[test]
+    fn restart_policy_backoff() {
+        let policy = RestartPolicy::new()
+            .with_initial_backoff(Duration::from_secs(1))
+            .with_max_backoff(Duration::from_secs(10));
+
+        assert_eq!(policy.backoff(0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(1), Duration::from_secs(2));
+        assert_eq!(policy.backoff(3), Duration::from_secs(8));
+        assert_eq!(policy.backoff(4), Duration::from_secs(10));
+        assert_eq!(policy.backoff(64), Duration::from_secs(10));
+    }
+
+    /// Verify that a crashed service is restarted once its backoff has elapsed, and that it is
+    /// marked as failed once it crashes after its maximum number of restarts.
+    #[test]
+    fn crashed_service_fails_after_max_restarts() {
+        let tracker = ServiceRestartTracker::new(
+            RestartPolicy::new()
+                .with_max_restarts(1)
+                .with_initial_backoff(Duration::from_secs(0)),
+            HashMap::new(),
+        );
+        let service = service_definition();
+        let args = vec![("key".to_string(), "value".to_string())]
+            .into_iter()
+            .collect::<HashMap<_, _>>();
+
+        tracker.started(service.clone(), args.clone()).unwrap();
+        tracker.crashed(&service, "first".into()).unwrap();
+        assert_eq!(
+            tracker.list().unwrap()[0].state(),
+            ServiceRunState::Restarting
+        );
+
+        assert_eq!(tracker.take_due().unwrap(), vec![(service.clone(), args)]);
+        assert!(tracker.take_due().unwrap().is_empty());
+        assert!(tracker.restarted(&service).unwrap());
+        assert_eq!(tracker.list().unwrap()[0].state(), ServiceRunState::Running);
+
+        tracker.crashed(&service, "second".into()).unwrap();
+        let history = tracker.list().unwrap().remove(0);
+        assert_eq!(history.state(), ServiceRunState::Failed);
+        assert_eq!(history.restarts(), 1);
+        assert_eq!(
+            history
+                .crashes()
+                .iter()
+                .map(ServiceCrash::reason)
+                .collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+        assert!(tracker.take_due().unwrap().is_empty());
+
+        // A stopped service is no longer tracked, so it is not restarted
+        assert!(tracker.remove(&service).unwrap());
+        assert!(!tracker.restarted(&service).unwrap());
+    }
+
+    /// Verify that a panic while handling a message is reported as a crash.
+    #[test]
+    fn panic_message_is_reported() {
+        let payload = panic::catch_unwind(|| panic!("boom")).unwrap_err();
+        assert_eq!(panic_message(&*payload), "boom");
+
+        let payload = panic::catch_unwind(|| panic!("{} failed", "service")).unwrap_err();
+        assert_eq!(panic_message(&*payload), "service failed");
+    }
+}
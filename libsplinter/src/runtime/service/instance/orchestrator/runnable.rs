@@ -26,6 +26,10 @@ use crate::mesh::Mesh;
 use crate::network::reply::InboundRouter;
 use crate::transport::Connection;
 
+#[cfg(feature = "service-type-limits")]
+use super::limits::ServiceTypeLimits;
+#[cfg(feature = "service-restart-policy")]
+use super::restart::{RestartPolicy, ServiceRestartTracker, SharedServiceFactory};
 use super::{JoinHandles, OrchestratableServiceFactory, ServiceOrchestrator};
 
 /// A runnable service orchestrator is configured, but not started ServiceOrchestrator. It may only
@@ -37,6 +41,12 @@ pub struct RunnableServiceOrchestrator {
     pub(super) channel_capacity: usize,
     pub(super) service_factories: Vec<Box<dyn OrchestratableServiceFactory>>,
     pub(super) supported_service_types: Vec<String>,
+    #[cfg(feature = "service-restart-policy")]
+    pub(super) restart_policy: RestartPolicy,
+    #[cfg(feature = "service-restart-policy")]
+    pub(super) service_type_restart_policies: HashMap<String, RestartPolicy>,
+    #[cfg(feature = "service-type-limits")]
+    pub(super) service_type_limits: HashMap<String, ServiceTypeLimits>,
 }
 
 impl RunnableServiceOrchestrator {
@@ -57,8 +67,17 @@ impl RunnableServiceOrchestrator {
         let service_factories = self.service_factories;
         let supported_service_types = self.supported_service_types;
 
+        // The factories are shared with the restart thread, which recreates crashed services.
+        #[cfg(feature = "service-restart-policy")]
+        let (service_factories, restart_factories) = SharedServiceFactory::share(service_factories);
+        #[cfg(feature = "service-restart-policy")]
+        let restart_tracker =
+            ServiceRestartTracker::new(self.restart_policy, self.service_type_restart_policies);
+
         let services = Arc::new(Mutex::new(HashMap::new()));
         let stopped_services = Arc::new(Mutex::new(HashMap::new()));
+        #[cfg(feature = "subsystem-control")]
+        let suspended_services = Arc::new(Mutex::new(HashMap::new()));
 
         let mesh = Mesh::new(self.incoming_capacity, self.outgoing_capacity);
         let mesh_id = format!("{}", Uuid::new_v4());
@@ -96,12 +115,18 @@ impl RunnableServiceOrchestrator {
         // Start thread that handles messages that do not have a matching correlation id.
         let inbound_services = services.clone();
         let inbound_running = running.clone();
+        #[cfg(feature = "service-restart-policy")]
+        let inbound_restart_tracker = restart_tracker.clone();
         let inbound_join_handle = thread::Builder::new()
             .name("Orchestrator Inbound".into())
             .spawn(move || {
-                if let Err(err) =
-                    super::run_inbound_loop(inbound_services, inbound_receiver, inbound_running)
-                {
+                if let Err(err) = super::run_inbound_loop(
+                    inbound_services,
+                    inbound_receiver,
+                    inbound_running,
+                    #[cfg(feature = "service-restart-policy")]
+                    inbound_restart_tracker,
+                ) {
                     error!(
                         "Terminating orchestrator inbound thread due to error: {}",
                         err
@@ -132,20 +157,59 @@ impl RunnableServiceOrchestrator {
             })
             .map_err(|err| InternalError::from_source(Box::new(err)))?;
 
+        // Start thread that restarts services that crashed.
+        #[cfg(feature = "service-restart-policy")]
+        let restart_join_handle = {
+            let restart_services = services.clone();
+            let restart_tracker = restart_tracker.clone();
+            let restart_network_sender = network_sender.clone();
+            let restart_router = inbound_router.clone();
+            let restart_running = running.clone();
+            thread::Builder::new()
+                .name("Orchestrator Restart".into())
+                .spawn(move || {
+                    if let Err(err) = super::restart::run_restart_loop(
+                        restart_services,
+                        restart_tracker,
+                        restart_factories,
+                        restart_network_sender,
+                        restart_router,
+                        restart_running,
+                    ) {
+                        error!(
+                            "Terminating orchestrator restart thread due to error: {}",
+                            err
+                        );
+                        Err(err)
+                    } else {
+                        Ok(())
+                    }
+                })
+                .map_err(|err| InternalError::from_source(Box::new(err)))?
+        };
+
         let join_handles = JoinHandles::new(vec![
             incoming_join_handle,
             inbound_join_handle,
             outgoing_join_handle,
+            #[cfg(feature = "service-restart-policy")]
+            restart_join_handle,
         ]);
 
         info!("Service orchestrator started");
         Ok(ServiceOrchestrator {
             services,
             stopped_services,
+            #[cfg(feature = "subsystem-control")]
+            suspended_services,
             service_factories,
             supported_service_types,
             network_sender,
             inbound_router,
+            #[cfg(feature = "service-restart-policy")]
+            restart_tracker,
+            #[cfg(feature = "service-type-limits")]
+            service_type_limits: self.service_type_limits,
             running,
             join_handles: Some(join_handles),
         })
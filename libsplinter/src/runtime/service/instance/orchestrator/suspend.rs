@@ -0,0 +1,119 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Suspending the services of an orchestrator for maintenance.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::InternalError;
+
+use super::{ManagedService, ServiceDefinition};
+
+type ServiceMap = HashMap<ServiceDefinition, ManagedService>;
+
+/// Stops services of an orchestrator and starts them again, without destroying them, so that a
+/// single service can be paused for maintenance while the others keep running.
+///
+/// A suspended service receives no messages. It is destroyed if it is stopped by the admin
+/// service, such as when its circuit is disbanded, or when the orchestrator shuts down.
+///
+/// The handle is cheap to clone; all clones suspend the services of the same orchestrator.
+#[derive(Clone)]
+pub struct ServiceSuspendHandle {
+    services: Arc<Mutex<ServiceMap>>,
+    suspended_services: Arc<Mutex<ServiceMap>>,
+}
+
+impl ServiceSuspendHandle {
+    pub(super) fn new(
+        services: Arc<Mutex<ServiceMap>>,
+        suspended_services: Arc<Mutex<ServiceMap>>,
+    ) -> Self {
+        Self {
+            services,
+            suspended_services,
+        }
+    }
+
+    /// Stops a running service, returning false if the orchestrator does not run the service.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The ID of the service's circuit
+    /// * `service_id` - The ID of the service
+    pub fn suspend_service(&self, circuit: &str, service_id: &str) -> Result<bool, InternalError> {
+        let mut services = lock(&self.services)?;
+        let service_definition = match find(&services, circuit, service_id) {
+            Some(service_definition) => service_definition,
+            None => return Ok(false),
+        };
+        let mut managed_service = services
+            .remove(&service_definition)
+            .expect("Service was found in the map, but was not removed");
+
+        managed_service
+            .service
+            .stop(&managed_service.registry)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        info!("Suspended service {}", service_definition);
+        lock(&self.suspended_services)?.insert(service_definition, managed_service);
+        Ok(true)
+    }
+
+    /// Starts a suspended service again, returning false if the service is not suspended.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit` - The ID of the service's circuit
+    /// * `service_id` - The ID of the service
+    pub fn resume_service(&self, circuit: &str, service_id: &str) -> Result<bool, InternalError> {
+        let mut suspended_services = lock(&self.suspended_services)?;
+        let service_definition = match find(&suspended_services, circuit, service_id) {
+            Some(service_definition) => service_definition,
+            None => return Ok(false),
+        };
+        let mut managed_service = suspended_services
+            .remove(&service_definition)
+            .expect("Service was found in the map, but was not removed");
+
+        if let Err(err) = managed_service.service.start(&managed_service.registry) {
+            suspended_services.insert(service_definition, managed_service);
+            return Err(InternalError::from_source(Box::new(err)));
+        }
+
+        info!("Resumed service {}", service_definition);
+        lock(&self.services)?.insert(service_definition, managed_service);
+        Ok(true)
+    }
+
+    /// Returns the services that are suspended.
+    pub fn list_suspended_services(&self) -> Result<Vec<ServiceDefinition>, InternalError> {
+        Ok(lock(&self.suspended_services)?.keys().cloned().collect())
+    }
+}
+
+fn lock(services: &Mutex<ServiceMap>) -> Result<MutexGuard<ServiceMap>, InternalError> {
+    services
+        .lock()
+        .map_err(|_| InternalError::with_message("Orchestrator service lock was poisoned".into()))
+}
+
+fn find(services: &ServiceMap, circuit: &str, service_id: &str) -> Option<ServiceDefinition> {
+    services
+        .keys()
+        .find(|service| service.circuit == circuit && service.service_id == service_id)
+        .cloned()
+}
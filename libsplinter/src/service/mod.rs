@@ -49,6 +49,8 @@ mod message_handler_factory;
 mod message_sender;
 #[cfg(feature = "service-message-sender-factory")]
 mod message_sender_factory;
+#[cfg(feature = "service-payload-codec")]
+pub mod payload_codec;
 #[cfg(feature = "rest-api-actix-web-1")]
 pub mod rest_api;
 mod routable;
@@ -63,6 +65,8 @@ mod timer_filter;
 mod timer_handler;
 #[cfg(feature = "service-timer-handler-factory")]
 mod timer_handler_factory;
+#[cfg(feature = "service-transfer")]
+pub mod transfer;
 
 #[cfg(feature = "service-arguments-converter")]
 pub use arguments_converter::ArgumentsConverter;
@@ -0,0 +1,410 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Codecs for service message payloads, and the negotiation of the codec used by the services of
+//! a circuit.
+//!
+//! Each service message payload is encoded as bytes by a [`MessageConverter`]. A circuit's
+//! services agree on an encoding by naming it in their `payload_encoding` argument, which is part
+//! of the circuit proposal voted on by every member; a service that does not name one uses
+//! protobuf. Services that accept more than one encoding register a codec for each in a
+//! [`PayloadCodecRegistry`], and select the codec for their circuit from their arguments.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::{InternalError, InvalidArgumentError};
+
+use super::MessageConverter;
+
+/// The service argument that names the payload encoding of a circuit's services
+pub const PAYLOAD_ENCODING_ARGUMENT: &str = "payload_encoding";
+
+/// The name of the protobuf encoding, which is used if a circuit does not name one
+pub const PROTOBUF_ENCODING: &str = "protobuf";
+/// The name of the CBOR encoding
+pub const CBOR_ENCODING: &str = "cbor";
+/// The name of the MessagePack encoding
+pub const MESSAGE_PACK_ENCODING: &str = "messagepack";
+
+/// Encodes and decodes protobuf messages.
+pub struct ProtobufCodec<M> {
+    _message: PhantomData<fn() -> M>,
+}
+
+impl<M> ProtobufCodec<M> {
+    pub fn new() -> Self {
+        Self {
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<M> Default for ProtobufCodec<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> Clone for ProtobufCodec<M> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<M: protobuf::Message> MessageConverter<M, Vec<u8>> for ProtobufCodec<M> {
+    fn to_left(&self, right: Vec<u8>) -> Result<M, InternalError> {
+        M::parse_from_bytes(&right).map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn to_right(&self, left: M) -> Result<Vec<u8>, InternalError> {
+        left.write_to_bytes()
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+/// Encodes and decodes messages as CBOR, using their serde implementations.
+pub struct CborCodec<M> {
+    _message: PhantomData<fn() -> M>,
+}
+
+impl<M> CborCodec<M> {
+    pub fn new() -> Self {
+        Self {
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<M> Default for CborCodec<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> Clone for CborCodec<M> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Serialize + DeserializeOwned> MessageConverter<M, Vec<u8>> for CborCodec<M> {
+    fn to_left(&self, right: Vec<u8>) -> Result<M, InternalError> {
+        serde_cbor::from_slice(&right).map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn to_right(&self, left: M) -> Result<Vec<u8>, InternalError> {
+        serde_cbor::to_vec(&left).map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+/// Encodes and decodes messages as MessagePack, using their serde implementations.
+///
+/// Structs are encoded as maps, so that messages can be read by implementations in other
+/// languages without knowing the order of the fields.
+pub struct MessagePackCodec<M> {
+    _message: PhantomData<fn() -> M>,
+}
+
+impl<M> MessagePackCodec<M> {
+    pub fn new() -> Self {
+        Self {
+            _message: PhantomData,
+        }
+    }
+}
+
+impl<M> Default for MessagePackCodec<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<M> Clone for MessagePackCodec<M> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<M: Serialize + DeserializeOwned> MessageConverter<M, Vec<u8>> for MessagePackCodec<M> {
+    fn to_left(&self, right: Vec<u8>) -> Result<M, InternalError> {
+        rmp_serde::from_slice(&right).map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn to_right(&self, left: M) -> Result<Vec<u8>, InternalError> {
+        rmp_serde::to_vec_named(&left).map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+type BoxedCodec<M> = Arc<dyn MessageConverter<M, Vec<u8>> + Send + Sync>;
+
+/// The codec for a payload encoding, selected from a [`PayloadCodecRegistry`].
+///
+/// The codec can be used as the message converter of a service's message handler.
+pub struct PayloadCodec<M> {
+    encoding: String,
+    codec: BoxedCodec<M>,
+}
+
+impl<M> PayloadCodec<M> {
+    /// Returns the name of the codec's encoding
+    pub fn encoding(&self) -> &str {
+        &self.encoding
+    }
+}
+
+impl<M> Clone for PayloadCodec<M> {
+    fn clone(&self) -> Self {
+        Self {
+            encoding: self.encoding.clone(),
+            codec: self.codec.clone(),
+        }
+    }
+}
+
+impl<M> fmt::Debug for PayloadCodec<M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("PayloadCodec")
+            .field("encoding", &self.encoding)
+            .finish()
+    }
+}
+
+impl<M> MessageConverter<M, Vec<u8>> for PayloadCodec<M> {
+    fn to_left(&self, right: Vec<u8>) -> Result<M, InternalError> {
+        self.codec.to_left(right)
+    }
+
+    fn to_right(&self, left: M) -> Result<Vec<u8>, InternalError> {
+        self.codec.to_right(left)
+    }
+}
+
+/// The codecs a service accepts for its message payloads, by the name of their encoding.
+pub struct PayloadCodecRegistry<M> {
+    codecs: BTreeMap<String, BoxedCodec<M>>,
+}
+
+impl<M> PayloadCodecRegistry<M> {
+    /// Creates a registry with no codecs.
+    pub fn new() -> Self {
+        Self {
+            codecs: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the codec for an encoding, replacing any codec already registered for it.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - The name of the encoding, as given by the `payload_encoding` argument
+    /// * `codec` - Converts between messages and their encoded payloads
+    pub fn with_codec<C>(mut self, encoding: &str, codec: C) -> Self
+    where
+        C: MessageConverter<M, Vec<u8>> + Send + Sync + 'static,
+    {
+        self.codecs.insert(encoding.to_string(), Arc::new(codec));
+        self
+    }
+
+    /// Returns the names of the registered encodings, in sorted order
+    pub fn encodings(&self) -> Vec<&str> {
+        self.codecs.keys().map(String::as_str).collect()
+    }
+
+    /// Returns the codec registered for the encoding, if any.
+    pub fn get(&self, encoding: &str) -> Option<PayloadCodec<M>> {
+        self.codecs.get(encoding).map(|codec| PayloadCodec {
+            encoding: encoding.to_string(),
+            codec: codec.clone(),
+        })
+    }
+
+    /// Returns the codec for the encoding named by a service's `payload_encoding` argument, or
+    /// for protobuf if the service has no such argument.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgumentError` if no codec is registered for the encoding.
+    pub fn codec_for_arguments(
+        &self,
+        arguments: &[(String, String)],
+    ) -> Result<PayloadCodec<M>, InvalidArgumentError> {
+        let encoding = arguments
+            .iter()
+            .find(|(key, _)| key == PAYLOAD_ENCODING_ARGUMENT)
+            .map(|(_, value)| value.as_str())
+            .unwrap_or(PROTOBUF_ENCODING);
+
+        self.get(encoding).ok_or_else(|| {
+            InvalidArgumentError::new(
+                PAYLOAD_ENCODING_ARGUMENT,
+                format!(
+                    "unsupported payload encoding {}, expected one of: {}",
+                    encoding,
+                    self.encodings().join(", ")
+                ),
+            )
+        })
+    }
+}
+
+impl<M> Default for PayloadCodecRegistry<M> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the payload encoding of a circuit, given the `payload_encoding` argument of each of
+/// its services, if set.
+///
+/// # Errors
+///
+/// Returns an `InvalidArgumentError` if an encoding is empty, or if the services do not all use
+/// the same encoding. A service without an encoding uses protobuf.
+pub fn negotiate_payload_encoding<'a, I>(encodings: I) -> Result<String, InvalidArgumentError>
+where
+    I: IntoIterator<Item = Option<&'a str>>,
+{
+    let mut negotiated: Option<&str> = None;
+    for encoding in encodings {
+        let encoding = encoding.unwrap_or(PROTOBUF_ENCODING);
+        if encoding.is_empty() {
+            return Err(InvalidArgumentError::new(
+                PAYLOAD_ENCODING_ARGUMENT,
+                "payload encoding cannot be empty",
+            ));
+        }
+
+        match negotiated {
+            Some(negotiated) if negotiated != encoding => {
+                return Err(InvalidArgumentError::new(
+                    PAYLOAD_ENCODING_ARGUMENT,
+                    format!(
+                        "all services of a circuit must use the same payload encoding, found {} \
+                         and {}",
+                        negotiated, encoding
+                    ),
+                ))
+            }
+            _ => negotiated = Some(encoding),
+        }
+    }
+
+    Ok(negotiated.unwrap_or(PROTOBUF_ENCODING).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+
+    use crate::protos::admin::Circuit;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Transfer {
+        from: String,
+        to: String,
+        amount: u64,
+    }
+
+    fn transfer() -> Transfer {
+        Transfer {
+            from: "alice".into(),
+            to: "bob".into(),
+            amount: 10,
+        }
+    }
+
+    /// Verify that the CBOR and MessagePack codecs decode the messages they encode, and that the
+    /// MessagePack codec encodes structs as maps.
+    #[test]
+    fn test_serde_codecs() {
+        let cbor = CborCodec::<Transfer>::new();
+        let bytes = cbor.to_right(transfer()).expect("Failed to encode CBOR");
+        assert_eq!(
+            cbor.to_left(bytes).expect("Failed to decode CBOR"),
+            transfer()
+        );
+
+        let message_pack = MessagePackCodec::<Transfer>::new();
+        let bytes = message_pack
+            .to_right(transfer())
+            .expect("Failed to encode MessagePack");
+        // fixmap with 3 entries
+        assert_eq!(bytes[0], 0x83);
+        assert_eq!(
+            message_pack
+                .to_left(bytes)
+                .expect("Failed to decode MessagePack"),
+            transfer()
+        );
+
+        assert!(cbor.to_left(vec![0xff]).is_err());
+    }
+
+    /// Verify that the registry selects the codec named by the payload_encoding argument,
+    /// protobuf if there is none, and rejects encodings that are not registered.
+    #[test]
+    fn test_codec_for_arguments() {
+        let registry = PayloadCodecRegistry::<Circuit>::new()
+            .with_codec(PROTOBUF_ENCODING, ProtobufCodec::new());
+
+        let codec = registry
+            .codec_for_arguments(&[("admin_keys".into(), "[]".into())])
+            .expect("Failed to get codec");
+        assert_eq!(codec.encoding(), PROTOBUF_ENCODING);
+
+        let mut circuit = Circuit::new();
+        circuit.set_circuit_id("abcDE-F0123".into());
+        let bytes = codec.to_right(circuit.clone()).expect("Failed to encode");
+        assert_eq!(codec.to_left(bytes).expect("Failed to decode"), circuit);
+
+        assert!(registry
+            .codec_for_arguments(&[(PAYLOAD_ENCODING_ARGUMENT.into(), CBOR_ENCODING.into())])
+            .is_err());
+    }
+
+    /// Verify that a circuit's encoding is the one its services agree on, or protobuf if none
+    /// name one, and that services that disagree are rejected.
+    #[test]
+    fn test_negotiate_payload_encoding() {
+        assert_eq!(
+            negotiate_payload_encoding(vec![None, None]).expect("Failed to negotiate"),
+            PROTOBUF_ENCODING
+        );
+        assert_eq!(
+            negotiate_payload_encoding(vec![Some(CBOR_ENCODING), Some(CBOR_ENCODING)])
+                .expect("Failed to negotiate"),
+            CBOR_ENCODING
+        );
+        assert_eq!(
+            negotiate_payload_encoding(vec![Some(PROTOBUF_ENCODING), None])
+                .expect("Failed to negotiate"),
+            PROTOBUF_ENCODING
+        );
+
+        assert!(negotiate_payload_encoding(vec![Some(CBOR_ENCODING), None]).is_err());
+        assert!(
+            negotiate_payload_encoding(vec![Some(CBOR_ENCODING), Some(MESSAGE_PACK_ENCODING)])
+                .is_err()
+        );
+        assert!(negotiate_payload_encoding(vec![Some("")]).is_err());
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::error::InvalidArgumentError;
+use crate::service::instance::{
+    FactoryCreateError, OrchestratableService, OrchestratableServiceFactory, ServiceArgValidator,
+    ServiceFactory, ServiceInstance,
+};
+
+use super::{TransferService, DEFAULT_MAX_FILE_SIZE, SERVICE_TYPE};
+
+/// Creates transfer services, which store their files within the factory's storage directory.
+pub struct TransferServiceFactory {
+    service_types: Vec<String>,
+    storage_dir: PathBuf,
+    max_file_size: u64,
+}
+
+impl TransferServiceFactory {
+    pub fn new<P: Into<PathBuf>>(storage_dir: P) -> Self {
+        Self {
+            service_types: vec![SERVICE_TYPE.into()],
+            storage_dir: storage_dir.into(),
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+
+    /// Sets the largest file, in bytes, that the created services send or accept from their
+    /// peers.
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    fn create_transfer_service(
+        &self,
+        service_id: String,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<TransferService, FactoryCreateError> {
+        let peer_services = parse_peer_services(&args)
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        TransferService::new(service_id, circuit_id, peer_services, &self.storage_dir)
+            .map(|service| service.with_max_file_size(self.max_file_size))
+            .map_err(|err| FactoryCreateError::Internal(err.reduce_to_string()))
+    }
+}
+
+impl ServiceFactory for TransferServiceFactory {
+    fn available_service_types(&self) -> &[String] {
+        self.service_types.as_slice()
+    }
+
+    /// `args` must include the following:
+    /// - `peer_services`: list of other transfer services on the same circuit that files sent
+    ///   by this service are offered to, formatted as a JSON array or a comma-separated list
+    fn create(
+        &self,
+        service_id: String,
+        _service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn ServiceInstance>, FactoryCreateError> {
+        Ok(Box::new(
+            self.create_transfer_service(service_id, circuit_id, args)?,
+        ))
+    }
+}
+
+impl OrchestratableServiceFactory for TransferServiceFactory {
+    fn create_orchestratable_service(
+        &self,
+        service_id: String,
+        _service_type: &str,
+        circuit_id: &str,
+        args: HashMap<String, String>,
+    ) -> Result<Box<dyn OrchestratableService>, FactoryCreateError> {
+        Ok(Box::new(
+            self.create_transfer_service(service_id, circuit_id, args)?,
+        ))
+    }
+}
+
+/// Validates the arguments of transfer services in circuit proposals.
+pub struct TransferArgValidator;
+
+impl ServiceArgValidator for TransferArgValidator {
+    fn validate(&self, args: &HashMap<String, String>) -> Result<(), InvalidArgumentError> {
+        parse_peer_services(args).map(|_| ())
+    }
+}
+
+fn parse_peer_services(
+    args: &HashMap<String, String>,
+) -> Result<Vec<String>, InvalidArgumentError> {
+    let peer_services_str = args
+        .get("peer_services")
+        .ok_or_else(|| InvalidArgumentError::new("peer_services", "argument not provided"))?;
+
+    let peer_services: Vec<String> = if peer_services_str.starts_with('[') {
+        serde_json::from_str(peer_services_str).map_err(|err| {
+            InvalidArgumentError::new("peer_services", format!("failed to parse list: {}", err))
+        })?
+    } else {
+        peer_services_str.split(',').map(String::from).collect()
+    };
+
+    if peer_services.iter().any(|service| service.is_empty()) {
+        return Err(InvalidArgumentError::new(
+            "peer_services",
+            "must provide at least one service ID",
+        ));
+    }
+
+    Ok(peer_services)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the `peer_services` argument is required and may be formatted as a JSON array
+    /// or a comma-separated list.
+    #[test]
+    fn validate_peer_services() {
+        let validator = TransferArgValidator;
+
+        assert!(validator.validate(&HashMap::new()).is_err());
+
+        let mut args = HashMap::new();
+        args.insert(
+            "peer_services".to_string(),
+            r#"["a000","b000"]"#.to_string(),
+        );
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("peer_services".to_string(), "a000,b000".to_string());
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("peer_services".to_string(), "".to_string());
+        assert!(validator.validate(&args).is_err());
+    }
+}
@@ -0,0 +1,569 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A service for transferring files between the members of a circuit.
+//!
+//! A file sent through a transfer service is stored by the service and offered to its peer
+//! services. Each peer pulls the file in chunks, starting from the number of bytes it already
+//! holds, so an interrupted transfer resumes where it stopped when the services are restarted.
+//! A received file is only made available once its SHA-256 hash matches the offered hash.
+
+mod factory;
+mod store;
+
+use std::any::Any;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use protobuf::Message;
+use uuid::Uuid;
+
+use crate::error::InternalError;
+use crate::protos::transfer::{Chunk, ChunkRequest, Offer, TransferMessage, TransferMessage_Type};
+use crate::service::instance::{
+    OrchestratableService, ServiceDestroyError, ServiceError, ServiceInstance,
+    ServiceMessageContext, ServiceNetworkRegistry, ServiceNetworkSender, ServiceStartError,
+    ServiceStopError,
+};
+
+pub use factory::{TransferArgValidator, TransferServiceFactory};
+pub use store::{TransferDirection, TransferRecord, TransferStatus};
+
+use store::TransferStore;
+
+pub const SERVICE_TYPE: &str = "transfer";
+
+/// The size of the chunks that files are sent in
+const CHUNK_SIZE: u64 = 256 * 1024;
+/// The largest chunk size accepted in an offer
+const MAX_CHUNK_SIZE: u64 = 4 * 1024 * 1024;
+/// The largest file that may be sent or received, unless the service is configured otherwise
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// A service that transfers files between the members of a circuit.
+#[derive(Clone)]
+pub struct TransferService {
+    service_id: String,
+    circuit_id: String,
+    peer_services: Vec<String>,
+    max_file_size: u64,
+    store: Arc<Mutex<TransferStore>>,
+    network_sender: Arc<Mutex<Option<Box<dyn ServiceNetworkSender>>>>,
+}
+
+impl TransferService {
+    /// Creates a transfer service that stores its files in a directory of its own within
+    /// `storage_dir`.
+    pub fn new(
+        service_id: String,
+        circuit_id: &str,
+        peer_services: Vec<String>,
+        storage_dir: &Path,
+    ) -> Result<Self, InternalError> {
+        let store = TransferStore::new(storage_dir.join(format!("{}-{}", circuit_id, service_id)))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            service_id,
+            circuit_id: circuit_id.into(),
+            peer_services,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            store: Arc::new(Mutex::new(store)),
+            network_sender: Arc::new(Mutex::new(None)),
+        })
+    }
+
+    /// Sets the largest file, in bytes, that the service sends or accepts from its peers.
+    pub fn with_max_file_size(mut self, max_file_size: u64) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Returns the largest file, in bytes, that the service sends or accepts from its peers.
+    pub fn max_file_size(&self) -> u64 {
+        self.max_file_size
+    }
+
+    /// Stores a file and offers it to the service's peers, returning the record of the transfer.
+    /// A file larger than the service's maximum file size is rejected.
+    pub fn send_file<R: Read>(
+        &self,
+        name: &str,
+        reader: R,
+    ) -> Result<TransferRecord, InternalError> {
+        if self.lock_sender()?.is_none() {
+            return Err(InternalError::with_message(
+                "Transfer service has not been started".into(),
+            ));
+        }
+
+        let file_id = Uuid::new_v4().to_string();
+        let record = {
+            let store = self.lock_store()?;
+            let (size, sha256) = store
+                .write_file(&file_id, reader, self.max_file_size)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            let record = TransferRecord {
+                file_id,
+                name: name.into(),
+                size,
+                sha256,
+                chunk_size: CHUNK_SIZE,
+                direction: TransferDirection::Outgoing,
+                peer: None,
+                received: size,
+                status: TransferStatus::Complete,
+            };
+            store
+                .save_record(&record)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            record
+        };
+
+        self.offer(&record)?;
+
+        Ok(record)
+    }
+
+    /// Lists the files sent and received by the service.
+    pub fn list_transfers(&self) -> Result<Vec<TransferRecord>, InternalError> {
+        self.lock_store()?
+            .list_records()
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    pub fn get_transfer(&self, file_id: &str) -> Result<Option<TransferRecord>, InternalError> {
+        if !store::is_valid_file_id(file_id) {
+            return Ok(None);
+        }
+        self.lock_store()?
+            .get_record(file_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    /// Opens the content of a file, if the file has been completely sent or received.
+    pub fn open_file(&self, file_id: &str) -> Result<Option<File>, InternalError> {
+        match self.get_transfer(file_id)? {
+            Some(record) if record.status == TransferStatus::Complete => self
+                .lock_store()?
+                .open_file(file_id)
+                .map(Some)
+                .map_err(|err| InternalError::from_source(Box::new(err))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Offers a file to each of the service's peers.
+    fn offer(&self, record: &TransferRecord) -> Result<(), InternalError> {
+        let sha256 = crate::hex::parse_hex(&record.sha256)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let mut offer = Offer::new();
+        offer.set_file_id(record.file_id.clone());
+        offer.set_name(record.name.clone());
+        offer.set_size(record.size);
+        offer.set_sha256(sha256);
+        offer.set_chunk_size(record.chunk_size);
+
+        let mut msg = TransferMessage::new();
+        msg.set_message_type(TransferMessage_Type::OFFER);
+        msg.set_offer(offer);
+        let bytes = msg
+            .write_to_bytes()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let sender = self.lock_sender()?;
+        let sender = sender.as_ref().ok_or_else(|| {
+            InternalError::with_message("Transfer service has not been started".into())
+        })?;
+        for peer in &self.peer_services {
+            if let Err(err) = sender.send(peer, &bytes) {
+                warn!(
+                    "Unable to offer file {} to {}::{}: {}",
+                    record.file_id, self.circuit_id, peer, err
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Requests the chunk of an incoming file that starts at the given offset.
+    fn request_chunk(&self, peer: &str, file_id: &str, offset: u64) -> Result<(), ServiceError> {
+        let mut request = ChunkRequest::new();
+        request.set_file_id(file_id.into());
+        request.set_offset(offset);
+
+        let mut msg = TransferMessage::new();
+        msg.set_message_type(TransferMessage_Type::CHUNK_REQUEST);
+        msg.set_chunk_request(request);
+
+        self.lock_sender()
+            .map_err(|err| ServiceError::PoisonedLock(err.reduce_to_string()))?
+            .as_ref()
+            .ok_or(ServiceError::NotStarted)?
+            .send(peer, &msg.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn handle_offer(&self, offer: &Offer, sender: &str) -> Result<(), ServiceError> {
+        if !store::is_valid_file_id(offer.get_file_id())
+            || offer.get_chunk_size() == 0
+            || offer.get_chunk_size() > MAX_CHUNK_SIZE
+        {
+            warn!(
+                "Ignoring invalid offer of file {:?} from {}",
+                offer.get_file_id(),
+                sender
+            );
+            return Ok(());
+        }
+
+        if offer.get_size() > self.max_file_size {
+            warn!(
+                "Ignoring offer of file {} from {}: its size of {} bytes is larger than the \
+                 maximum of {} bytes",
+                offer.get_file_id(),
+                sender,
+                offer.get_size(),
+                self.max_file_size
+            );
+            return Ok(());
+        }
+
+        let offset = {
+            let store = self
+                .lock_store()
+                .map_err(|err| ServiceError::PoisonedLock(err.reduce_to_string()))?;
+            match store
+                .get_record(offer.get_file_id())
+                .map_err(to_service_error)?
+            {
+                Some(record) if record.status == TransferStatus::InProgress => store
+                    .partial_len(&record.file_id)
+                    .map_err(to_service_error)?,
+                // The file has already been received, or sent by this service
+                Some(record) if record.status != TransferStatus::Failed => return Ok(()),
+                // A new file, or a file whose last transfer failed
+                _ => {
+                    store
+                        .remove_partial(offer.get_file_id())
+                        .map_err(to_service_error)?;
+                    store
+                        .save_record(&TransferRecord {
+                            file_id: offer.get_file_id().into(),
+                            name: offer.get_name().into(),
+                            size: offer.get_size(),
+                            sha256: crate::hex::to_hex(offer.get_sha256()),
+                            chunk_size: offer.get_chunk_size(),
+                            direction: TransferDirection::Incoming,
+                            peer: Some(sender.into()),
+                            received: 0,
+                            status: TransferStatus::InProgress,
+                        })
+                        .map_err(to_service_error)?;
+                    0
+                }
+            }
+        };
+
+        if offset < offer.get_size() {
+            self.request_chunk(sender, offer.get_file_id(), offset)
+        } else {
+            // An empty file needs no chunks
+            self.finish(offer.get_file_id())
+        }
+    }
+
+    fn handle_chunk_request(
+        &self,
+        request: &ChunkRequest,
+        context: &ServiceMessageContext,
+    ) -> Result<(), ServiceError> {
+        let data = {
+            let store = self
+                .lock_store()
+                .map_err(|err| ServiceError::PoisonedLock(err.reduce_to_string()))?;
+            match store
+                .get_record(request.get_file_id())
+                .map_err(to_service_error)?
+            {
+                Some(record)
+                    if record.status == TransferStatus::Complete
+                        && request.get_offset() < record.size =>
+                {
+                    store
+                        .read_chunk(&record.file_id, request.get_offset(), record.chunk_size)
+                        .map_err(to_service_error)?
+                }
+                _ => {
+                    warn!(
+                        "Ignoring request for unavailable file {:?} from {}",
+                        request.get_file_id(),
+                        context.sender
+                    );
+                    return Ok(());
+                }
+            }
+        };
+
+        let mut chunk = Chunk::new();
+        chunk.set_file_id(request.get_file_id().into());
+        chunk.set_offset(request.get_offset());
+        chunk.set_data(data);
+
+        let mut msg = TransferMessage::new();
+        msg.set_message_type(TransferMessage_Type::CHUNK);
+        msg.set_chunk(chunk);
+
+        self.lock_sender()
+            .map_err(|err| ServiceError::PoisonedLock(err.reduce_to_string()))?
+            .as_ref()
+            .ok_or(ServiceError::NotStarted)?
+            .reply(context, &msg.write_to_bytes()?)?;
+
+        Ok(())
+    }
+
+    fn handle_chunk(&self, chunk: &Chunk, sender: &str) -> Result<(), ServiceError> {
+        let (received, size) = {
+            let store = self
+                .lock_store()
+                .map_err(|err| ServiceError::PoisonedLock(err.reduce_to_string()))?;
+            let mut record = match store
+                .get_record(chunk.get_file_id())
+                .map_err(to_service_error)?
+            {
+                Some(record)
+                    if record.status == TransferStatus::InProgress
+                        && record.peer.as_deref() == Some(sender) =>
+                {
+                    record
+                }
+                _ => {
+                    warn!(
+                        "Ignoring unexpected chunk of file {:?} from {}",
+                        chunk.get_file_id(),
+                        sender
+                    );
+                    return Ok(());
+                }
+            };
+
+            if chunk.get_data().is_empty()
+                || record.received + chunk.get_data().len() as u64 > record.size
+            {
+                warn!(
+                    "Ignoring invalid chunk of file {} from {}",
+                    record.file_id, sender
+                );
+                return Ok(());
+            }
+
+            let received = store
+                .append_chunk(&record.file_id, chunk.get_offset(), chunk.get_data())
+                .map_err(to_service_error)?;
+            if received == record.received {
+                // A duplicate chunk; the next chunk has already been requested
+                return Ok(());
+            }
+            record.received = received;
+            store.save_record(&record).map_err(to_service_error)?;
+
+            (received, record.size)
+        };
+
+        if received < size {
+            self.request_chunk(sender, chunk.get_file_id(), received)
+        } else {
+            self.finish(chunk.get_file_id())
+        }
+    }
+
+    /// Verifies the hash of a completely received file.
+    fn finish(&self, file_id: &str) -> Result<(), ServiceError> {
+        let store = self
+            .lock_store()
+            .map_err(|err| ServiceError::PoisonedLock(err.reduce_to_string()))?;
+        let mut record = match store.get_record(file_id).map_err(to_service_error)? {
+            Some(record) => record,
+            None => return Ok(()),
+        };
+
+        if record.size == 0 {
+            store
+                .append_chunk(file_id, 0, &[])
+                .map_err(to_service_error)?;
+        }
+
+        if store
+            .finish_file(file_id, &record.sha256)
+            .map_err(to_service_error)?
+        {
+            info!("Received file {} ({})", record.file_id, record.name);
+            record.status = TransferStatus::Complete;
+        } else {
+            error!(
+                "Received file {} ({}) does not match its hash",
+                record.file_id, record.name
+            );
+            record.status = TransferStatus::Failed;
+            record.received = 0;
+        }
+        store.save_record(&record).map_err(to_service_error)
+    }
+
+    /// Offers the service's outgoing files and resumes its incoming transfers, so that transfers
+    /// interrupted by a restart of either service are completed.
+    fn resume_transfers(&self) -> Result<(), InternalError> {
+        for record in self.list_transfers()? {
+            match (record.direction, record.status, record.peer.as_deref()) {
+                (TransferDirection::Outgoing, TransferStatus::Complete, _) => {
+                    self.offer(&record)?
+                }
+                (TransferDirection::Incoming, TransferStatus::InProgress, Some(peer)) => {
+                    if let Err(err) = self.request_chunk(peer, &record.file_id, record.received) {
+                        warn!("Unable to resume transfer of {}: {}", record.file_id, err);
+                    }
+                }
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn lock_store(&self) -> Result<std::sync::MutexGuard<TransferStore>, InternalError> {
+        self.store
+            .lock()
+            .map_err(|_| InternalError::with_message("Transfer store lock poisoned".into()))
+    }
+
+    fn lock_sender(
+        &self,
+    ) -> Result<std::sync::MutexGuard<Option<Box<dyn ServiceNetworkSender>>>, InternalError> {
+        self.network_sender
+            .lock()
+            .map_err(|_| InternalError::with_message("Network sender lock poisoned".into()))
+    }
+}
+
+fn to_service_error(err: io::Error) -> ServiceError {
+    ServiceError::UnableToHandleMessage(Box::new(err))
+}
+
+impl ServiceInstance for TransferService {
+    fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    fn service_type(&self) -> &str {
+        SERVICE_TYPE
+    }
+
+    fn start(
+        &mut self,
+        service_registry: &dyn ServiceNetworkRegistry,
+    ) -> Result<(), ServiceStartError> {
+        let mut network_sender = self
+            .network_sender
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("network sender lock poisoned".into()))?;
+        if network_sender.is_some() {
+            return Err(ServiceStartError::AlreadyStarted);
+        }
+        *network_sender = Some(
+            service_registry
+                .connect(&self.service_id)
+                .map_err(ServiceStartError::UnableToConnect)?,
+        );
+        drop(network_sender);
+
+        self.resume_transfers()
+            .map_err(|err| ServiceStartError::Internal(err.reduce_to_string()))
+    }
+
+    fn stop(
+        &mut self,
+        service_registry: &dyn ServiceNetworkRegistry,
+    ) -> Result<(), ServiceStopError> {
+        self.network_sender
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("network sender lock poisoned".into()))?
+            .take()
+            .ok_or(ServiceStopError::NotStarted)?;
+
+        service_registry
+            .disconnect(&self.service_id)
+            .map_err(ServiceStopError::UnableToDisconnect)
+    }
+
+    fn destroy(self: Box<Self>) -> Result<(), ServiceDestroyError> {
+        if self
+            .network_sender
+            .lock()
+            .map_err(|_| ServiceDestroyError::PoisonedLock("network sender lock poisoned".into()))?
+            .is_some()
+        {
+            Err(ServiceDestroyError::NotStopped)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn purge(&mut self) -> Result<(), InternalError> {
+        self.lock_store()?
+            .purge()
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+
+    fn handle_message(
+        &self,
+        message_bytes: &[u8],
+        message_context: &ServiceMessageContext,
+    ) -> Result<(), ServiceError> {
+        let msg: TransferMessage = Message::parse_from_bytes(message_bytes)?;
+
+        match msg.get_message_type() {
+            TransferMessage_Type::OFFER => {
+                self.handle_offer(msg.get_offer(), &message_context.sender)
+            }
+            TransferMessage_Type::CHUNK_REQUEST => {
+                self.handle_chunk_request(msg.get_chunk_request(), message_context)
+            }
+            TransferMessage_Type::CHUNK => {
+                self.handle_chunk(msg.get_chunk(), &message_context.sender)
+            }
+            TransferMessage_Type::UNSET_TYPE => Err(ServiceError::InvalidMessageFormat(Box::new(
+                io::Error::new(io::ErrorKind::InvalidData, "transfer message type is unset"),
+            ))),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl OrchestratableService for TransferService {
+    fn clone_box(&self) -> Box<dyn OrchestratableService> {
+        Box::new(self.clone())
+    }
+
+    fn as_service(&self) -> &dyn ServiceInstance {
+        self
+    }
+}
@@ -0,0 +1,287 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! On-disk storage for a transfer service's files.
+//!
+//! Each file is stored in the service's directory under its file ID, along with a `<id>.json`
+//! record of the transfer. The content of an incoming file is written to `<id>.part` until the
+//! file is complete and its hash has been verified.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const RECORD_EXTENSION: &str = "json";
+const PARTIAL_EXTENSION: &str = "part";
+
+/// Whether a file was sent or received by this service
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransferDirection {
+    Outgoing,
+    Incoming,
+}
+
+/// The status of a transfer
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransferStatus {
+    InProgress,
+    Complete,
+    /// The received content did not match the offered hash
+    Failed,
+}
+
+/// The record of a file sent or received by a transfer service
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransferRecord {
+    pub file_id: String,
+    pub name: String,
+    pub size: u64,
+    /// The hex-encoded SHA-256 hash of the file's content
+    pub sha256: String,
+    pub chunk_size: u64,
+    pub direction: TransferDirection,
+    /// The service that sent the file, for incoming transfers
+    pub peer: Option<String>,
+    /// The number of bytes of the file held by this service
+    pub received: u64,
+    pub status: TransferStatus,
+}
+
+pub(super) struct TransferStore {
+    dir: PathBuf,
+}
+
+impl TransferStore {
+    pub fn new(dir: PathBuf) -> io::Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn save_record(&self, record: &TransferRecord) -> io::Result<()> {
+        let path = self.path(&record.file_id, Some(RECORD_EXTENSION))?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec(record)?)?;
+        fs::rename(tmp_path, path)
+    }
+
+    pub fn get_record(&self, file_id: &str) -> io::Result<Option<TransferRecord>> {
+        match fs::read(self.path(file_id, Some(RECORD_EXTENSION))?) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    pub fn list_records(&self) -> io::Result<Vec<TransferRecord>> {
+        let mut records = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) == Some(RECORD_EXTENSION) {
+                records.push(serde_json::from_slice(&fs::read(path)?)?);
+            }
+        }
+        records.sort_by(|a: &TransferRecord, b| a.name.cmp(&b.name));
+        Ok(records)
+    }
+
+    /// Writes the content of a new file, returning its size and hex-encoded SHA-256 hash. The
+    /// file is removed if its content is larger than `max_size`.
+    pub fn write_file<R: Read>(
+        &self,
+        file_id: &str,
+        mut reader: R,
+        max_size: u64,
+    ) -> io::Result<(u64, String)> {
+        let path = self.path(file_id, None)?;
+        let mut file = File::create(&path)?;
+        let mut hasher = Sha256::new();
+        let mut size = 0;
+        let mut buf = vec![0; 64 * 1024];
+        loop {
+            let read = reader.read(&mut buf)?;
+            if read == 0 {
+                break;
+            }
+            size += read as u64;
+            if size > max_size {
+                drop(file);
+                fs::remove_file(&path)?;
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("file is larger than the maximum of {} bytes", max_size),
+                ));
+            }
+            hasher.update(&buf[..read]);
+            file.write_all(&buf[..read])?;
+        }
+        file.sync_all()?;
+
+        Ok((size, crate::hex::to_hex(&hasher.finalize())))
+    }
+
+    pub fn open_file(&self, file_id: &str) -> io::Result<File> {
+        File::open(self.path(file_id, None)?)
+    }
+
+    pub fn read_chunk(&self, file_id: &str, offset: u64, len: u64) -> io::Result<Vec<u8>> {
+        let mut file = self.open_file(file_id)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut data = vec![];
+        file.take(len).read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    /// Returns the number of bytes received of an incoming file.
+    pub fn partial_len(&self, file_id: &str) -> io::Result<u64> {
+        match fs::metadata(self.path(file_id, Some(PARTIAL_EXTENSION))?) {
+            Ok(metadata) => Ok(metadata.len()),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(0),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Appends a chunk to an incoming file, if it starts where the received content ends, and
+    /// returns the number of bytes received.
+    pub fn append_chunk(&self, file_id: &str, offset: u64, data: &[u8]) -> io::Result<u64> {
+        let received = self.partial_len(file_id)?;
+        if offset != received {
+            return Ok(received);
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.path(file_id, Some(PARTIAL_EXTENSION))?)?;
+        file.write_all(data)?;
+        file.sync_data()?;
+
+        Ok(received + data.len() as u64)
+    }
+
+    /// Discards the received content of an incoming file.
+    pub fn remove_partial(&self, file_id: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(file_id, Some(PARTIAL_EXTENSION))?) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    /// Completes an incoming file if its content matches the given hex-encoded SHA-256 hash;
+    /// otherwise the received content is discarded. Returns whether the hash matched.
+    pub fn finish_file(&self, file_id: &str, sha256: &str) -> io::Result<bool> {
+        let partial_path = self.path(file_id, Some(PARTIAL_EXTENSION))?;
+
+        let mut file = File::open(&partial_path)?;
+        let mut hasher = Sha256::new();
+        io::copy(&mut file, &mut hasher)?;
+
+        if crate::hex::to_hex(&hasher.finalize()) == sha256 {
+            fs::rename(partial_path, self.path(file_id, None)?)?;
+            Ok(true)
+        } else {
+            fs::remove_file(partial_path)?;
+            Ok(false)
+        }
+    }
+
+    /// Removes all of the service's files and records.
+    pub fn purge(&self) -> io::Result<()> {
+        match fs::remove_dir_all(&self.dir) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+
+    fn path(&self, file_id: &str, extension: Option<&str>) -> io::Result<PathBuf> {
+        if !is_valid_file_id(file_id) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Invalid file ID: {}", file_id),
+            ));
+        }
+
+        let path = Path::new(&self.dir).join(file_id);
+        Ok(match extension {
+            Some(extension) => path.with_extension(extension),
+            None => path,
+        })
+    }
+}
+
+/// File IDs are used as file names, so they are limited to alphanumeric characters and dashes.
+pub(super) fn is_valid_file_id(file_id: &str) -> bool {
+    !file_id.is_empty()
+        && file_id.len() <= 64
+        && file_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+
+    /// Verify that an incoming file is assembled from its chunks, that chunks which do not start
+    /// where the received content ends are ignored, and that the file is only completed if its
+    /// hash matches.
+    #[test]
+    fn assemble_incoming_file() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let store =
+            TransferStore::new(temp_dir.path().join("svc")).expect("Failed to create store");
+
+        let (size, sha256) = store
+            .write_file("outgoing", &b"hello, world"[..], 12)
+            .expect("Failed to write file");
+        assert_eq!(size, 12);
+
+        // A file larger than the maximum is not kept
+        assert!(store
+            .write_file("too_large", &b"hello, world"[..], 11)
+            .is_err());
+        assert!(store.open_file("too_large").is_err());
+
+        assert_eq!(store.append_chunk("incoming", 0, b"hello").unwrap(), 5);
+        // A duplicate chunk is ignored
+        assert_eq!(store.append_chunk("incoming", 0, b"hello").unwrap(), 5);
+        assert_eq!(
+            store
+                .append_chunk("incoming", 5, &store.read_chunk("outgoing", 5, 7).unwrap())
+                .unwrap(),
+            12
+        );
+        assert!(store.finish_file("incoming", &sha256).unwrap());
+
+        let mut content = String::new();
+        store
+            .open_file("incoming")
+            .unwrap()
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "hello, world");
+
+        store.append_chunk("corrupt", 0, b"hello, w0rld").unwrap();
+        assert!(!store.finish_file("corrupt", &sha256).unwrap());
+        assert_eq!(store.partial_len("corrupt").unwrap(), 0);
+
+        assert!(store.path("../escape", None).is_err());
+    }
+}
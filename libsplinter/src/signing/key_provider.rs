@@ -0,0 +1,154 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ffi::OsStr;
+use std::fs;
+use std::path::PathBuf;
+
+use cylinder::{load_key_from_path, PrivateKey};
+
+use crate::error::InternalError;
+
+const PRIVATE_KEY_EXTENSION: &str = "priv";
+
+/// A source of named private keys, such as a directory of key files or a secret store.
+pub trait KeyProvider {
+    /// Returns every key the provider holds, with its name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the keys cannot be read, or if a key is not valid.
+    fn keys(&self) -> Result<Vec<(String, PrivateKey)>, InternalError>;
+
+    /// Returns the key with the given name, or `None` if the provider does not hold it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the keys cannot be read, or if the key is not valid.
+    fn get_key(&self, name: &str) -> Result<Option<PrivateKey>, InternalError> {
+        Ok(self
+            .keys()?
+            .into_iter()
+            .find(|(key_name, _)| key_name == name)
+            .map(|(_, key)| key))
+    }
+}
+
+/// Provides the keys stored in the `<name>.priv` files of a directory.
+pub struct DirectoryKeyProvider {
+    dir: PathBuf,
+}
+
+impl DirectoryKeyProvider {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self { dir: dir.into() }
+    }
+}
+
+impl KeyProvider for DirectoryKeyProvider {
+    fn keys(&self) -> Result<Vec<(String, PrivateKey)>, InternalError> {
+        let entries = fs::read_dir(&self.dir).map_err(|err| {
+            InternalError::from_source_with_message(
+                Box::new(err),
+                format!("Unable to read keys in {}", self.dir.display()),
+            )
+        })?;
+
+        let mut keys = vec![];
+        for entry in entries {
+            let path = entry
+                .map_err(|err| {
+                    InternalError::from_source_with_message(
+                        Box::new(err),
+                        format!("Unable to read keys in {}", self.dir.display()),
+                    )
+                })?
+                .path();
+            if path.extension() != Some(OsStr::new(PRIVATE_KEY_EXTENSION)) {
+                continue;
+            }
+
+            let name = path
+                .file_stem()
+                .and_then(OsStr::to_str)
+                .ok_or_else(|| {
+                    InternalError::with_message(format!(
+                        "Invalid key file name: {}",
+                        path.display()
+                    ))
+                })?
+                .to_string();
+            let key = load_key_from_path(&path)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            keys.push((name, key));
+        }
+        keys.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        Ok(keys)
+    }
+
+    fn get_key(&self, name: &str) -> Result<Option<PrivateKey>, InternalError> {
+        let path = self.dir.join(name).with_extension(PRIVATE_KEY_EXTENSION);
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        load_key_from_path(&path)
+            .map(Some)
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY_A: &str = "2f1e7b7a130d7ba9da0068b3bb0ba1d79e7e77110302c9f746c3c2a63fe40088";
+    const KEY_B: &str = "0c4d2f5d79b4b0c34f4d3d56e45fa36ac3b3b5fdb6b6c2cf34e32f2b5e4d9f11";
+
+    /// Verify that only the `.priv` files of the directory are provided, by name, and that a
+    /// missing key is `None`.
+    #[test]
+    fn test_directory_key_provider() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        fs::write(dir.path().join("b.priv"), format!("{}\n", KEY_B)).expect("Failed to write key");
+        fs::write(dir.path().join("a.priv"), KEY_A).expect("Failed to write key");
+        fs::write(dir.path().join("a.pub"), "not a private key").expect("Failed to write key");
+
+        let provider = DirectoryKeyProvider::new(dir.path());
+
+        let keys = provider.keys().expect("Failed to get keys");
+        let names = keys
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["a", "b"]);
+        assert_eq!(keys[0].1.as_hex(), KEY_A);
+        assert_eq!(keys[1].1.as_hex(), KEY_B);
+
+        let key = provider.get_key("b").expect("Failed to get key");
+        assert_eq!(key.map(|key| key.as_hex()), Some(KEY_B.to_string()));
+        assert!(provider.get_key("c").expect("Failed to get key").is_none());
+    }
+
+    /// Verify that a missing directory is an error.
+    #[test]
+    fn test_directory_key_provider_missing_dir() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+
+        assert!(DirectoryKeyProvider::new(dir.path().join("keys"))
+            .keys()
+            .is_err());
+    }
+}
@@ -0,0 +1,331 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A signer whose secp256k1 private key is held by a PKCS#11 token, such as an HSM or a YubiKey.
+//!
+//! The private key never leaves the token. The signer hashes each message with SHA-256 and asks
+//! the token to sign the hash, so its signatures can be verified by the Cylinder secp256k1
+//! verifier like those of a key loaded from a file.
+
+mod uri;
+
+use std::sync::{Arc, Mutex};
+
+use cryptoki::context::{CInitializeArgs, Pkcs11};
+use cryptoki::mechanism::Mechanism;
+use cryptoki::object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle};
+use cryptoki::session::{Session, UserType};
+use cryptoki::slot::Slot;
+use cylinder::{PublicKey, Signature, Signer, SigningError};
+use sha2::{Digest, Sha256};
+
+use crate::error::InternalError;
+
+pub use uri::Pkcs11Uri;
+
+/// The DER encoding of the secp256k1 curve's OID, 1.3.132.0.10
+const SECP256K1_OID: [u8; 7] = [0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+/// The order of the secp256k1 curve
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Half the order of the secp256k1 curve; signatures with a larger `s` are not accepted by the
+/// secp256k1 verifier
+const SECP256K1_HALF_ORDER: [u8; 32] = [
+    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+    0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46, 0x68, 0x1b, 0x20, 0xa0,
+];
+
+/// Signs messages with a secp256k1 key held by a PKCS#11 token.
+///
+/// Clones of the signer share the token session, so only one of them signs at a time.
+#[derive(Clone)]
+pub struct Pkcs11Signer {
+    session: Arc<Mutex<Session>>,
+    private_key: ObjectHandle,
+    public_key: PublicKey,
+}
+
+impl Pkcs11Signer {
+    /// Opens a session with the token identified by the URI, logs in with the URI's PIN, if it
+    /// has one, and finds the key.
+    ///
+    /// The key must be a secp256k1 key, and the token must hold its public key as well, with the
+    /// same label or ID.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the module cannot be loaded, if no token or more than one
+    /// token matches the URI, if the login fails, or if the key cannot be found or is not a
+    /// secp256k1 key.
+    pub fn new(uri: &Pkcs11Uri) -> Result<Self, InternalError> {
+        let pkcs11 = Pkcs11::new(uri.module_path()).map_err(|err| {
+            InternalError::with_message(format!(
+                "Unable to load PKCS#11 module {}: {}",
+                uri.module_path(),
+                err
+            ))
+        })?;
+        pkcs11
+            .initialize(CInitializeArgs::OsThreads)
+            .map_err(|err| pkcs11_error("Unable to initialize PKCS#11 module", err))?;
+
+        let slot = find_slot(&pkcs11, uri)?;
+        let session = pkcs11
+            .open_ro_session(slot)
+            .map_err(|err| pkcs11_error("Unable to open PKCS#11 session", err))?;
+        if let Some(pin) = uri.pin()? {
+            session
+                .login(UserType::User, Some(&pin))
+                .map_err(|err| pkcs11_error("Unable to log in to PKCS#11 token", err))?;
+        }
+
+        let private_key = find_key(&session, uri, ObjectClass::PRIVATE_KEY)?;
+        let public_key_handle = find_key(&session, uri, ObjectClass::PUBLIC_KEY)?;
+
+        let mut ec_point = None;
+        for attribute in session
+            .get_attributes(
+                public_key_handle,
+                &[AttributeType::EcParams, AttributeType::EcPoint],
+            )
+            .map_err(|err| pkcs11_error("Unable to get PKCS#11 public key", err))?
+        {
+            match attribute {
+                Attribute::EcParams(params) if params != SECP256K1_OID => {
+                    return Err(InternalError::with_message(
+                        "PKCS#11 key is not a secp256k1 key".into(),
+                    ))
+                }
+                Attribute::EcPoint(point) => ec_point = Some(point),
+                _ => (),
+            }
+        }
+        let public_key = ec_point
+            .ok_or_else(|| InternalError::with_message("PKCS#11 public key has no EC point".into()))
+            .and_then(|point| compress_ec_point(&point))?;
+
+        Ok(Self {
+            session: Arc::new(Mutex::new(session)),
+            private_key,
+            public_key: PublicKey::new(public_key),
+        })
+    }
+}
+
+impl Signer for Pkcs11Signer {
+    fn algorithm_name(&self) -> &str {
+        "secp256k1"
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature, SigningError> {
+        let hash = Sha256::digest(message);
+        let session = self
+            .session
+            .lock()
+            .map_err(|_| SigningError::Internal("PKCS#11 session lock poisoned".into()))?;
+        let mut signature = session
+            .sign(&Mechanism::Ecdsa, self.private_key, &hash)
+            .map_err(|err| {
+                SigningError::Internal(format!("PKCS#11 token failed to sign: {}", err))
+            })?;
+
+        if signature.len() != 64 {
+            return Err(SigningError::Internal(format!(
+                "PKCS#11 token returned a {}-byte signature, expected 64 bytes",
+                signature.len()
+            )));
+        }
+        normalize_s(&mut signature[32..]);
+
+        Ok(Signature::new(signature))
+    }
+
+    fn public_key(&self) -> Result<PublicKey, SigningError> {
+        Ok(self.public_key.clone())
+    }
+
+    fn clone_box(&self) -> Box<dyn Signer> {
+        Box::new(self.clone())
+    }
+}
+
+fn pkcs11_error(message: &str, err: cryptoki::error::Error) -> InternalError {
+    InternalError::with_message(format!("{}: {}", message, err))
+}
+
+/// Returns the slot of the only token that matches the URI's token label and slot ID.
+fn find_slot(pkcs11: &Pkcs11, uri: &Pkcs11Uri) -> Result<Slot, InternalError> {
+    let mut matches = vec![];
+    for slot in pkcs11
+        .get_slots_with_token()
+        .map_err(|err| pkcs11_error("Unable to list PKCS#11 slots", err))?
+    {
+        if let Some(slot_id) = uri.slot_id() {
+            if slot.id() != slot_id {
+                continue;
+            }
+        }
+        if let Some(token) = uri.token() {
+            let info = pkcs11
+                .get_token_info(slot)
+                .map_err(|err| pkcs11_error("Unable to get PKCS#11 token info", err))?;
+            if info.label().trim_end() != token {
+                continue;
+            }
+        }
+        matches.push(slot);
+    }
+
+    match matches.len() {
+        1 => Ok(matches[0]),
+        0 => Err(InternalError::with_message(
+            "No PKCS#11 token matches the URI".into(),
+        )),
+        _ => Err(InternalError::with_message(
+            "More than one PKCS#11 token matches the URI; specify the token or slot-id".into(),
+        )),
+    }
+}
+
+/// Returns the only EC key of the given class that matches the URI's object label and ID.
+fn find_key(
+    session: &Session,
+    uri: &Pkcs11Uri,
+    class: ObjectClass,
+) -> Result<ObjectHandle, InternalError> {
+    let mut template = vec![Attribute::Class(class), Attribute::KeyType(KeyType::EC)];
+    if let Some(object) = uri.object() {
+        template.push(Attribute::Label(object.as_bytes().to_vec()));
+    }
+    if let Some(id) = uri.id() {
+        template.push(Attribute::Id(id.to_vec()));
+    }
+
+    let kind = if class == ObjectClass::PRIVATE_KEY {
+        "private"
+    } else {
+        "public"
+    };
+    let mut keys = session
+        .find_objects(&template)
+        .map_err(|err| pkcs11_error("Unable to find PKCS#11 key", err))?;
+    match keys.len() {
+        1 => Ok(keys.remove(0)),
+        0 => Err(InternalError::with_message(format!(
+            "No PKCS#11 {} key matches the URI",
+            kind
+        ))),
+        _ => Err(InternalError::with_message(format!(
+            "More than one PKCS#11 {} key matches the URI",
+            kind
+        ))),
+    }
+}
+
+/// Converts an uncompressed EC point, optionally wrapped in a DER octet string as PKCS#11
+/// requires, to the compressed public key used by the secp256k1 signer.
+fn compress_ec_point(point: &[u8]) -> Result<Vec<u8>, InternalError> {
+    let point = match point {
+        [0x04, 0x41, rest @ ..] if rest.len() == 65 => rest,
+        _ => point,
+    };
+    if point.len() != 65 || point[0] != 0x04 {
+        return Err(InternalError::with_message(
+            "PKCS#11 public key is not an uncompressed secp256k1 point".into(),
+        ));
+    }
+
+    let mut public_key = Vec::with_capacity(33);
+    public_key.push(if point[64] % 2 == 0 { 0x02 } else { 0x03 });
+    public_key.extend_from_slice(&point[1..33]);
+    Ok(public_key)
+}
+
+/// Replaces `s` with `n - s` if it is more than half the curve's order `n`. Both are valid
+/// signatures, but the secp256k1 verifier only accepts the lower one.
+fn normalize_s(s: &mut [u8]) {
+    if *s <= SECP256K1_HALF_ORDER[..] {
+        return;
+    }
+
+    let mut borrow = 0i16;
+    for i in (0..32).rev() {
+        let mut diff = i16::from(SECP256K1_ORDER[i]) - i16::from(s[i]) - borrow;
+        borrow = if diff < 0 {
+            diff += 256;
+            1
+        } else {
+            0
+        };
+        s[i] = diff as u8;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that uncompressed points, with or without the DER octet string header, are
+    /// compressed with the prefix for the parity of y.
+    #[test]
+    fn test_compress_ec_point() {
+        let mut point = vec![0x04];
+        point.extend_from_slice(&[0x11; 32]);
+        point.extend_from_slice(&[0x22; 32]);
+
+        let mut expected = vec![0x02];
+        expected.extend_from_slice(&[0x11; 32]);
+        assert_eq!(
+            compress_ec_point(&point).expect("Failed to compress"),
+            expected
+        );
+
+        point[64] = 0x23;
+        expected[0] = 0x03;
+        let mut wrapped = vec![0x04, 0x41];
+        wrapped.extend_from_slice(&point);
+        assert_eq!(
+            compress_ec_point(&wrapped).expect("Failed to compress"),
+            expected
+        );
+
+        assert!(compress_ec_point(&point[1..]).is_err());
+    }
+
+    /// Verify that a high `s` is replaced with `n - s`, and a low `s` is unchanged.
+    #[test]
+    fn test_normalize_s() {
+        let mut low = SECP256K1_HALF_ORDER;
+        normalize_s(&mut low);
+        assert_eq!(low, SECP256K1_HALF_ORDER);
+
+        // n - 1 becomes 1
+        let mut high = SECP256K1_ORDER;
+        high[31] -= 1;
+        normalize_s(&mut high);
+        let mut one = [0u8; 32];
+        one[31] = 1;
+        assert_eq!(high, one);
+
+        // (n + 1) / 2 becomes (n - 1) / 2
+        let mut just_high = SECP256K1_HALF_ORDER;
+        just_high[31] += 1;
+        normalize_s(&mut just_high);
+        assert_eq!(just_high, SECP256K1_HALF_ORDER);
+    }
+}
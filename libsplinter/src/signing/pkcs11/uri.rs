@@ -0,0 +1,263 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of the PKCS#11 URIs defined by RFC 7512, which identify a key held by a token.
+
+use std::fmt;
+use std::fs;
+use std::str::FromStr;
+
+use percent_encoding::percent_decode_str;
+
+use crate::error::{InternalError, InvalidArgumentError};
+
+const SCHEME: &str = "pkcs11:";
+
+/// Identifies a private key held by a PKCS#11 token, and the module used to access it.
+///
+/// For example, the key labeled `splinterd` on a YubiKey is identified by
+/// `pkcs11:token=YubiKey%20PIV;object=splinterd?module-path=/usr/lib/libykcs11.so`.
+///
+/// The path attributes `token`, `slot-id`, `object` and `id` select the key, and the query
+/// attributes `module-path`, `pin-value` and `pin-source` describe how to access it. Other
+/// attributes are ignored.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Pkcs11Uri {
+    token: Option<String>,
+    slot_id: Option<u64>,
+    object: Option<String>,
+    id: Option<Vec<u8>>,
+    module_path: String,
+    pin_value: Option<String>,
+    pin_source: Option<String>,
+}
+
+impl Pkcs11Uri {
+    /// Returns the label of the token that holds the key
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    /// Returns the ID of the slot that holds the token
+    pub fn slot_id(&self) -> Option<u64> {
+        self.slot_id
+    }
+
+    /// Returns the label of the key
+    pub fn object(&self) -> Option<&str> {
+        self.object.as_deref()
+    }
+
+    /// Returns the ID of the key
+    pub fn id(&self) -> Option<&[u8]> {
+        self.id.as_deref()
+    }
+
+    /// Returns the path of the PKCS#11 module that accesses the token
+    pub fn module_path(&self) -> &str {
+        &self.module_path
+    }
+
+    /// Returns the PIN used to log in to the token, if any.
+    ///
+    /// The PIN is either given by the `pin-value` attribute, or read from the file named by the
+    /// `pin-source` attribute.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the `pin-source` file cannot be read.
+    pub fn pin(&self) -> Result<Option<String>, InternalError> {
+        if let Some(pin) = &self.pin_value {
+            return Ok(Some(pin.clone()));
+        }
+
+        self.pin_source
+            .as_ref()
+            .map(|path| {
+                fs::read_to_string(path)
+                    .map(|pin| pin.trim_end().to_string())
+                    .map_err(|err| {
+                        InternalError::with_message(format!(
+                            "Unable to read PKCS#11 PIN from {}: {}",
+                            path, err
+                        ))
+                    })
+            })
+            .transpose()
+    }
+}
+
+// The PIN is omitted, so that the URI can be logged
+impl fmt::Debug for Pkcs11Uri {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pkcs11Uri")
+            .field("token", &self.token)
+            .field("slot_id", &self.slot_id)
+            .field("object", &self.object)
+            .field("id", &self.id)
+            .field("module_path", &self.module_path)
+            .finish()
+    }
+}
+
+impl FromStr for Pkcs11Uri {
+    type Err = InvalidArgumentError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rest = s.strip_prefix(SCHEME).ok_or_else(|| {
+            InvalidArgumentError::new("uri", format!("PKCS#11 URI must start with {}", SCHEME))
+        })?;
+
+        let (path, query) = match rest.find('?') {
+            Some(index) => (&rest[..index], &rest[index + 1..]),
+            None => (rest, ""),
+        };
+
+        let mut uri = Pkcs11Uri {
+            token: None,
+            slot_id: None,
+            object: None,
+            id: None,
+            module_path: String::new(),
+            pin_value: None,
+            pin_source: None,
+        };
+
+        for (name, value) in attributes(path, ';')? {
+            match name {
+                "token" => uri.token = Some(decode_str(name, value)?),
+                "slot-id" => {
+                    uri.slot_id = Some(decode_str(name, value)?.parse().map_err(|_| {
+                        InvalidArgumentError::new("uri", format!("Invalid slot-id: {}", value))
+                    })?)
+                }
+                "object" => uri.object = Some(decode_str(name, value)?),
+                "id" => uri.id = Some(percent_decode_str(value).collect()),
+                _ => (),
+            }
+        }
+
+        for (name, value) in attributes(query, '&')? {
+            match name {
+                "module-path" => uri.module_path = decode_str(name, value)?,
+                "pin-value" => uri.pin_value = Some(decode_str(name, value)?),
+                "pin-source" => uri.pin_source = Some(decode_str(name, value)?),
+                _ => (),
+            }
+        }
+
+        if uri.module_path.is_empty() {
+            return Err(InvalidArgumentError::new(
+                "uri",
+                "PKCS#11 URI must include a module-path",
+            ));
+        }
+        if uri.object.is_none() && uri.id.is_none() {
+            return Err(InvalidArgumentError::new(
+                "uri",
+                "PKCS#11 URI must include an object or an id",
+            ));
+        }
+
+        Ok(uri)
+    }
+}
+
+/// Splits the path or query of a URI into its attributes.
+fn attributes(s: &str, separator: char) -> Result<Vec<(&str, &str)>, InvalidArgumentError> {
+    s.split(separator)
+        .filter(|attribute| !attribute.is_empty())
+        .map(|attribute| {
+            let mut parts = attribute.splitn(2, '=');
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(value)) => Ok((name, value)),
+                _ => Err(InvalidArgumentError::new(
+                    "uri",
+                    format!("Invalid PKCS#11 URI attribute: {}", attribute),
+                )),
+            }
+        })
+        .collect()
+}
+
+fn decode_str(name: &str, value: &str) -> Result<String, InvalidArgumentError> {
+    percent_decode_str(value)
+        .decode_utf8()
+        .map(|value| value.into_owned())
+        .map_err(|_| InvalidArgumentError::new("uri", format!("Invalid {}: {}", name, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the path and query attributes of a URI are parsed and decoded, and that
+    /// unknown attributes are ignored.
+    #[test]
+    fn test_parse_uri() {
+        let uri = "pkcs11:token=YubiKey%20PIV;slot-id=2;object=splinterd;id=%01%a2;\
+                   manufacturer=Yubico?module-path=/usr/lib/libykcs11.so&pin-value=123456"
+            .parse::<Pkcs11Uri>()
+            .expect("Failed to parse URI");
+
+        assert_eq!(uri.token(), Some("YubiKey PIV"));
+        assert_eq!(uri.slot_id(), Some(2));
+        assert_eq!(uri.object(), Some("splinterd"));
+        assert_eq!(uri.id(), Some(&[0x01, 0xa2][..]));
+        assert_eq!(uri.module_path(), "/usr/lib/libykcs11.so");
+        assert_eq!(
+            uri.pin().expect("Failed to get PIN"),
+            Some("123456".to_string())
+        );
+        assert!(!format!("{:?}", uri).contains("123456"));
+    }
+
+    /// Verify that the PIN is read from the pin-source file.
+    #[test]
+    fn test_pin_source() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let pin_path = dir.path().join("pin");
+        fs::write(&pin_path, "654321\n").expect("Failed to write PIN");
+
+        let uri = format!(
+            "pkcs11:object=splinterd?module-path=/usr/lib/softhsm/libsofthsm2.so&pin-source={}",
+            pin_path.display()
+        )
+        .parse::<Pkcs11Uri>()
+        .expect("Failed to parse URI");
+
+        assert_eq!(
+            uri.pin().expect("Failed to get PIN"),
+            Some("654321".to_string())
+        );
+    }
+
+    /// Verify that URIs that do not identify a key or a module are rejected.
+    #[test]
+    fn test_invalid_uri() {
+        assert!("splinterd".parse::<Pkcs11Uri>().is_err());
+        assert!("pkcs11:object=splinterd".parse::<Pkcs11Uri>().is_err());
+        assert!("pkcs11:token=Token?module-path=/usr/lib/libykcs11.so"
+            .parse::<Pkcs11Uri>()
+            .is_err());
+        assert!("pkcs11:object?module-path=/usr/lib/libykcs11.so"
+            .parse::<Pkcs11Uri>()
+            .is_err());
+        assert!(
+            "pkcs11:object=key;slot-id=one?module-path=/usr/lib/libykcs11.so"
+                .parse::<Pkcs11Uri>()
+                .is_err()
+        );
+    }
+}
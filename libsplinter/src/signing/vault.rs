@@ -0,0 +1,423 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A key provider that reads private keys from a HashiCorp Vault secret, so that they are never
+//! written to disk.
+//!
+//! The keys are the fields of a secret in a KV version 2 secrets engine, each named after the key
+//! and holding the key in hex, as in a `.priv` file. If a transit key is given, each field
+//! instead holds the key encrypted with the transit key, and is decrypted by Vault's transit
+//! secrets engine.
+//!
+//! The secret is identified by a URL made of the Vault server's address, the mount path of the
+//! KV secrets engine and the path of the secret, such as
+//! `https://vault.example.com:8200/secret/splinterd/keys`. The query attributes `transit-key`,
+//! the mount path and name of the transit key such as `transit/splinterd`, and `approle-mount`,
+//! the mount path of the AppRole auth method, which defaults to `approle`, are optional.
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+
+use cylinder::PrivateKey;
+use reqwest::blocking::{Client, RequestBuilder};
+use reqwest::Certificate;
+use serde::de::DeserializeOwned;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::error::{InternalError, InvalidArgumentError};
+
+use super::KeyProvider;
+
+/// The environment variable that holds the Vault token
+pub const VAULT_TOKEN_ENV: &str = "VAULT_TOKEN";
+/// The environment variable that holds the role ID used to log in with AppRole
+pub const VAULT_ROLE_ID_ENV: &str = "VAULT_ROLE_ID";
+/// The environment variable that holds the secret ID used to log in with AppRole
+pub const VAULT_SECRET_ID_ENV: &str = "VAULT_SECRET_ID";
+/// The environment variable that holds the path of the CA certificate of the Vault server
+pub const VAULT_CACERT_ENV: &str = "VAULT_CACERT";
+
+const DEFAULT_APPROLE_MOUNT: &str = "approle";
+
+/// How the key provider authenticates with Vault.
+#[derive(Clone)]
+pub enum VaultAuth {
+    /// A Vault token
+    Token(String),
+    /// The role ID and secret ID of an AppRole, used to log in for a token
+    AppRole { role_id: String, secret_id: String },
+}
+
+impl VaultAuth {
+    /// Returns the token in `VAULT_TOKEN` if it is set, or the AppRole in `VAULT_ROLE_ID` and
+    /// `VAULT_SECRET_ID` otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgumentError` if neither a token nor both AppRole IDs are set.
+    pub fn from_env() -> Result<Self, InvalidArgumentError> {
+        if let Ok(token) = env::var(VAULT_TOKEN_ENV) {
+            return Ok(VaultAuth::Token(token));
+        }
+
+        match (env::var(VAULT_ROLE_ID_ENV), env::var(VAULT_SECRET_ID_ENV)) {
+            (Ok(role_id), Ok(secret_id)) => Ok(VaultAuth::AppRole { role_id, secret_id }),
+            _ => Err(InvalidArgumentError::new(
+                "auth",
+                format!(
+                    "Either {} or both {} and {} must be set to read keys from Vault",
+                    VAULT_TOKEN_ENV, VAULT_ROLE_ID_ENV, VAULT_SECRET_ID_ENV
+                ),
+            )),
+        }
+    }
+}
+
+// The token and secret ID are omitted, so that the auth can be logged
+impl fmt::Debug for VaultAuth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VaultAuth::Token(_) => f.write_str("Token"),
+            VaultAuth::AppRole { role_id, .. } => {
+                f.debug_struct("AppRole").field("role_id", role_id).finish()
+            }
+        }
+    }
+}
+
+/// Provides the keys held by the fields of a Vault secret.
+///
+/// The secret is read each time the keys are requested; nothing is cached.
+pub struct VaultKeyProvider {
+    address: String,
+    kv_mount: String,
+    secret_path: String,
+    transit_key: Option<(String, String)>,
+    approle_mount: String,
+    auth: VaultAuth,
+    client: Client,
+}
+
+impl VaultKeyProvider {
+    /// Creates a provider for the secret identified by the URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidArgumentError` if the URL is not an `http` or `https` URL with the
+    /// mount path and the path of a secret, or if its `transit-key` is not a mount path and a
+    /// key name.
+    pub fn new(url: &str, auth: VaultAuth) -> Result<Self, InvalidArgumentError> {
+        let url = Url::parse(url)
+            .map_err(|err| InvalidArgumentError::new("url", format!("Invalid URL: {}", err)))?;
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(InvalidArgumentError::new(
+                "url",
+                "Vault URL must be an http or https URL",
+            ));
+        }
+        let host = url
+            .host_str()
+            .ok_or_else(|| InvalidArgumentError::new("url", "Vault URL must include a host"))?;
+        let address = match url.port() {
+            Some(port) => format!("{}://{}:{}", url.scheme(), host, port),
+            None => format!("{}://{}", url.scheme(), host),
+        };
+
+        let mut segments = url
+            .path_segments()
+            .map(|segments| {
+                segments
+                    .filter(|segment| !segment.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if segments.len() < 2 {
+            return Err(InvalidArgumentError::new(
+                "url",
+                "Vault URL must include the mount path of the KV secrets engine and the path of \
+                 the secret",
+            ));
+        }
+        let kv_mount = segments.remove(0).to_string();
+        let secret_path = segments.join("/");
+
+        let mut transit_key = None;
+        let mut approle_mount = DEFAULT_APPROLE_MOUNT.to_string();
+        for (name, value) in url.query_pairs() {
+            match &*name {
+                "transit-key" => {
+                    let index = value.rfind('/').ok_or_else(|| {
+                        InvalidArgumentError::new(
+                            "url",
+                            "transit-key must be the mount path and name of the key, such as \
+                             transit/splinterd",
+                        )
+                    })?;
+                    transit_key = Some((value[..index].to_string(), value[index + 1..].to_string()))
+                }
+                "approle-mount" => approle_mount = value.into_owned(),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            address,
+            kv_mount,
+            secret_path,
+            transit_key,
+            approle_mount,
+            auth,
+            client: Client::new(),
+        })
+    }
+
+    /// Creates a provider for the secret identified by the URL, which authenticates with the
+    /// token or AppRole in the environment, and trusts the CA certificate in `VAULT_CACERT` if it
+    /// is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the URL is not valid, if the environment does not hold a
+    /// token or AppRole, or if the CA certificate cannot be read.
+    pub fn from_env(url: &str) -> Result<Self, InternalError> {
+        let auth =
+            VaultAuth::from_env().map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let mut provider =
+            Self::new(url, auth).map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        if let Ok(ca_cert_path) = env::var(VAULT_CACERT_ENV) {
+            let ca_cert = fs::read(&ca_cert_path)
+                .map_err(|err| Box::new(err) as Box<dyn std::error::Error>)
+                .and_then(|pem| {
+                    Certificate::from_pem(&pem)
+                        .map_err(|err| Box::new(err) as Box<dyn std::error::Error>)
+                })
+                .map_err(|err| {
+                    InternalError::from_source_with_message(
+                        err,
+                        format!("Unable to load Vault CA certificate {}", ca_cert_path),
+                    )
+                })?;
+            provider.client = Client::builder()
+                .add_root_certificate(ca_cert)
+                .build()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        }
+
+        Ok(provider)
+    }
+
+    /// Returns a token, logging in with the AppRole if the provider does not have one.
+    fn token(&self) -> Result<String, InternalError> {
+        match &self.auth {
+            VaultAuth::Token(token) => Ok(token.clone()),
+            VaultAuth::AppRole { role_id, secret_id } => {
+                let response: Value = send(
+                    self.client
+                        .post(&format!(
+                            "{}/v1/auth/{}/login",
+                            self.address, self.approle_mount
+                        ))
+                        .json(&json!({ "role_id": role_id, "secret_id": secret_id })),
+                    "log in to Vault",
+                )?;
+                response["auth"]["client_token"]
+                    .as_str()
+                    .map(String::from)
+                    .ok_or_else(|| {
+                        InternalError::with_message("Vault login response has no token".into())
+                    })
+            }
+        }
+    }
+
+    /// Returns the fields of the secret.
+    fn read_secret(&self, token: &str) -> Result<BTreeMap<String, String>, InternalError> {
+        let mut response: Value = send(
+            self.client
+                .get(&format!(
+                    "{}/v1/{}/data/{}",
+                    self.address, self.kv_mount, self.secret_path
+                ))
+                .header("X-Vault-Token", token),
+            "read keys from Vault",
+        )?;
+
+        let fields: BTreeMap<String, Value> =
+            serde_json::from_value(response["data"]["data"].take()).map_err(|_| {
+                InternalError::with_message(format!(
+                    "Vault secret {}/{} has no data",
+                    self.kv_mount, self.secret_path
+                ))
+            })?;
+        fields
+            .into_iter()
+            .map(|(name, value)| match value {
+                Value::String(value) => Ok((name, value)),
+                _ => Err(InternalError::with_message(format!(
+                    "Vault secret field {} is not a string",
+                    name
+                ))),
+            })
+            .collect()
+    }
+
+    /// Decrypts a ciphertext with the transit key.
+    fn decrypt(
+        &self,
+        token: &str,
+        (mount, key): &(String, String),
+        ciphertext: &str,
+    ) -> Result<String, InternalError> {
+        let response: Value = send(
+            self.client
+                .post(&format!("{}/v1/{}/decrypt/{}", self.address, mount, key))
+                .header("X-Vault-Token", token)
+                .json(&json!({ "ciphertext": ciphertext })),
+            "decrypt key with Vault",
+        )?;
+        let plaintext = response["data"]["plaintext"].as_str().ok_or_else(|| {
+            InternalError::with_message("Vault decrypt response has no plaintext".into())
+        })?;
+
+        decode_plaintext(plaintext)
+    }
+}
+
+impl KeyProvider for VaultKeyProvider {
+    fn keys(&self) -> Result<Vec<(String, PrivateKey)>, InternalError> {
+        let token = self.token()?;
+
+        self.read_secret(&token)?
+            .into_iter()
+            .map(|(name, value)| {
+                let hex = match &self.transit_key {
+                    Some(transit_key) => self.decrypt(&token, transit_key, &value)?,
+                    None => value,
+                };
+                let key = PrivateKey::new_from_hex(hex.trim()).map_err(|err| {
+                    InternalError::from_source_with_message(
+                        Box::new(err),
+                        format!("Vault secret field {} is not a valid key", name),
+                    )
+                })?;
+                Ok((name, key))
+            })
+            .collect()
+    }
+}
+
+/// Sends the request, and returns the body of a successful response.
+fn send<T: DeserializeOwned>(request: RequestBuilder, action: &str) -> Result<T, InternalError> {
+    let response = request.send().map_err(|err| {
+        InternalError::from_source_with_message(Box::new(err), format!("Unable to {}", action))
+    })?;
+
+    let status = response.status();
+    if !status.is_success() {
+        // Vault describes the failure with a list of errors
+        let errors = response
+            .json::<Value>()
+            .ok()
+            .and_then(|body| serde_json::from_value::<Vec<String>>(body["errors"].clone()).ok())
+            .map(|errors| errors.join("; "))
+            .unwrap_or_default();
+        return Err(InternalError::with_message(format!(
+            "Unable to {}: Vault responded with {}: {}",
+            action, status, errors
+        )));
+    }
+
+    response.json::<T>().map_err(|err| {
+        InternalError::from_source_with_message(
+            Box::new(err),
+            format!("Unable to {}: invalid response", action),
+        )
+    })
+}
+
+/// Decodes the base64 plaintext returned by the transit secrets engine.
+fn decode_plaintext(plaintext: &str) -> Result<String, InternalError> {
+    base64::decode(plaintext)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or_else(|| InternalError::with_message("Vault returned an invalid plaintext".into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the address, mount path and secret path are taken from the URL, with the
+    /// optional transit key and AppRole mount path.
+    #[test]
+    fn test_parse_url() {
+        let provider = VaultKeyProvider::new(
+            "https://vault.example.com:8200/secret/splinterd/keys",
+            VaultAuth::Token("s.token".into()),
+        )
+        .expect("Failed to parse URL");
+        assert_eq!(provider.address, "https://vault.example.com:8200");
+        assert_eq!(provider.kv_mount, "secret");
+        assert_eq!(provider.secret_path, "splinterd/keys");
+        assert_eq!(provider.transit_key, None);
+        assert_eq!(provider.approle_mount, "approle");
+
+        let provider = VaultKeyProvider::new(
+            "http://vault/kv/alpha?transit-key=ops/transit/splinterd&approle-mount=ci",
+            VaultAuth::Token("s.token".into()),
+        )
+        .expect("Failed to parse URL");
+        assert_eq!(provider.address, "http://vault");
+        assert_eq!(provider.kv_mount, "kv");
+        assert_eq!(provider.secret_path, "alpha");
+        assert_eq!(
+            provider.transit_key,
+            Some(("ops/transit".to_string(), "splinterd".to_string()))
+        );
+        assert_eq!(provider.approle_mount, "ci");
+    }
+
+    /// Verify that URLs that do not identify a secret, or have an invalid transit key, are
+    /// rejected.
+    #[test]
+    fn test_invalid_url() {
+        let auth = VaultAuth::Token("s.token".into());
+        assert!(VaultKeyProvider::new("vault.example.com/secret/keys", auth.clone()).is_err());
+        assert!(VaultKeyProvider::new("ftp://vault/secret/keys", auth.clone()).is_err());
+        assert!(VaultKeyProvider::new("https://vault/secret", auth.clone()).is_err());
+        assert!(VaultKeyProvider::new("https://vault/secret/keys?transit-key=key", auth).is_err());
+    }
+
+    /// Verify that the plaintext is decoded from base64, and that the auth's secrets are not
+    /// included in its debug output.
+    #[test]
+    fn test_decode_plaintext_and_debug() {
+        assert_eq!(
+            decode_plaintext(&base64::encode("2f1e7b7a")).expect("Failed to decode"),
+            "2f1e7b7a"
+        );
+        assert!(decode_plaintext("not base64!").is_err());
+
+        let auth = VaultAuth::AppRole {
+            role_id: "role".into(),
+            secret_id: "hunter2".into(),
+        };
+        assert!(!format!("{:?}", auth).contains("hunter2"));
+        assert!(!format!("{:?}", VaultAuth::Token("s.token".into())).contains("s.token"));
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{mysql::MysqlConnection, Connection};
+
+use crate::error::InternalError;
+use crate::store::command::{DieselStoreCommandExecutor, StoreCommand, StoreCommandExecutor};
+
+impl StoreCommandExecutor for DieselStoreCommandExecutor<MysqlConnection> {
+    type Context = MysqlConnection;
+
+    fn execute<C: StoreCommand<Context = Self::Context>>(
+        &self,
+        store_commands: Vec<C>,
+    ) -> Result<(), InternalError> {
+        self.conn.execute_write(|conn| {
+            conn.transaction::<(), InternalError, _>(|| {
+                for cmd in store_commands {
+                    cmd.execute(conn)?;
+                }
+                Ok(())
+            })
+        })
+    }
+}
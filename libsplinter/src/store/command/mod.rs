@@ -15,7 +15,7 @@
 //! StoreCommand trait
 mod executor;
 
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub use executor::DieselStoreCommandExecutor;
 pub use executor::StoreCommandExecutor;
 
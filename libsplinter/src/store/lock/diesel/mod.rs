@@ -0,0 +1,277 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A database-backed implementation of the [`LockStore`](super::LockStore) trait.
+
+mod models;
+mod operations;
+mod schema;
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use diesel::r2d2::{ConnectionManager, Pool};
+
+use crate::store::pool::ConnectionPool;
+
+use super::{Lease, LockStore, LockStoreError};
+
+use operations::acquire_lock::LockStoreAcquireLockOperation as _;
+use operations::get_lock::LockStoreGetLockOperation as _;
+use operations::release_lock::LockStoreReleaseLockOperation as _;
+use operations::renew_lock::LockStoreRenewLockOperation as _;
+use operations::LockStoreOperations;
+
+/// A `LockStore` that stores its locks in a database, using the database's clock to decide when
+/// leases expire.
+pub struct DieselLockStore<C: diesel::Connection + 'static> {
+    connection_pool: ConnectionPool<C>,
+}
+
+impl<C: diesel::Connection> DieselLockStore<C> {
+    /// Creates a new `DieselLockStore`.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: connection pool for the database
+    pub fn new(connection_pool: Pool<ConnectionManager<C>>) -> Self {
+        DieselLockStore {
+            connection_pool: connection_pool.into(),
+        }
+    }
+
+    /// Create a new `DieselLockStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    ///  * `connection_pool`: read-write lock-guarded connection pool for the database
+    pub fn new_with_write_exclusivity(
+        connection_pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+    ) -> Self {
+        Self {
+            connection_pool: connection_pool.into(),
+        }
+    }
+}
+
+impl<C: diesel::Connection> Clone for DieselLockStore<C> {
+    fn clone(&self) -> Self {
+        Self {
+            connection_pool: self.connection_pool.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl LockStore for DieselLockStore<diesel::pg::PgConnection> {
+    fn acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>, LockStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            LockStoreOperations::new(connection).acquire_lock(name, owner, ttl)
+        })
+    }
+
+    fn renew_lock(&self, lease: &Lease, ttl: Duration) -> Result<Option<Lease>, LockStoreError> {
+        self.connection_pool
+            .execute_write(|connection| LockStoreOperations::new(connection).renew_lock(lease, ttl))
+    }
+
+    fn release_lock(&self, lease: &Lease) -> Result<bool, LockStoreError> {
+        self.connection_pool
+            .execute_write(|connection| LockStoreOperations::new(connection).release_lock(lease))
+    }
+
+    fn get_lock(&self, name: &str) -> Result<Option<Lease>, LockStoreError> {
+        self.connection_pool
+            .execute_read(|connection| LockStoreOperations::new(connection).get_lock(name))
+    }
+
+    fn clone_box(&self) -> Box<dyn LockStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl LockStore for DieselLockStore<diesel::sqlite::SqliteConnection> {
+    fn acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>, LockStoreError> {
+        self.connection_pool.execute_write(|connection| {
+            LockStoreOperations::new(connection).acquire_lock(name, owner, ttl)
+        })
+    }
+
+    fn renew_lock(&self, lease: &Lease, ttl: Duration) -> Result<Option<Lease>, LockStoreError> {
+        self.connection_pool
+            .execute_write(|connection| LockStoreOperations::new(connection).renew_lock(lease, ttl))
+    }
+
+    fn release_lock(&self, lease: &Lease) -> Result<bool, LockStoreError> {
+        self.connection_pool
+            .execute_write(|connection| LockStoreOperations::new(connection).release_lock(lease))
+    }
+
+    fn get_lock(&self, name: &str) -> Result<Option<Lease>, LockStoreError> {
+        self.connection_pool
+            .execute_read(|connection| LockStoreOperations::new(connection).get_lock(name))
+    }
+
+    fn clone_box(&self) -> Box<dyn LockStore> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+pub mod tests {
+    use super::*;
+
+    use std::thread;
+
+    use diesel::sqlite::SqliteConnection;
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Verify that a SQLite-backed `DieselLockStore` grants a lock to one owner at a time.
+    ///
+    /// 1. Create a connection pool for an in-memory SQLite database and run migrations.
+    /// 2. Acquire a lock and verify that a second owner cannot acquire it, and that the lease is
+    ///    returned by `get_lock`.
+    /// 3. Renew the lease and verify that it expires later.
+    /// 4. Release the lease, verify that it is no longer returned and cannot be released or
+    ///    renewed again, and that the next lease has a higher fencing token.
+    #[test]
+    fn sqlite_lock_acquire_renew_release() {
+        let store = DieselLockStore::new(create_connection_pool_and_migrate());
+        let ttl = Duration::from_secs(60);
+
+        let lease = store
+            .acquire_lock("backup", "node_a", ttl)
+            .expect("Failed to acquire lock")
+            .expect("Lock was not acquired");
+        assert_eq!(lease.name(), "backup");
+        assert_eq!(lease.owner(), "node_a");
+        assert_eq!(lease.fencing_token(), 1);
+
+        assert_eq!(
+            store
+                .acquire_lock("backup", "node_b", ttl)
+                .expect("Failed to acquire lock"),
+            None
+        );
+        assert_eq!(
+            store.get_lock("backup").expect("Failed to get lock"),
+            Some(lease.clone())
+        );
+        assert_eq!(store.get_lock("vacuum").expect("Failed to get lock"), None);
+
+        let renewed = store
+            .renew_lock(&lease, Duration::from_secs(120))
+            .expect("Failed to renew lock")
+            .expect("Lock was not renewed");
+        assert_eq!(renewed.fencing_token(), lease.fencing_token());
+        assert!(renewed.expires_at() > lease.expires_at());
+
+        assert!(store
+            .release_lock(&renewed)
+            .expect("Failed to release lock"));
+        assert_eq!(store.get_lock("backup").expect("Failed to get lock"), None);
+        assert!(!store
+            .release_lock(&renewed)
+            .expect("Failed to release lock"));
+        assert_eq!(
+            store
+                .renew_lock(&renewed, ttl)
+                .expect("Failed to renew lock"),
+            None
+        );
+
+        let next = store
+            .acquire_lock("backup", "node_b", ttl)
+            .expect("Failed to acquire lock")
+            .expect("Lock was not acquired");
+        assert_eq!(next.owner(), "node_b");
+        assert_eq!(next.fencing_token(), 2);
+    }
+
+    /// Verify that a SQLite-backed `DieselLockStore` lets another owner take over a lock whose
+    /// lease has expired, and that the expired lease can no longer be renewed or released.
+    #[test]
+    fn sqlite_lock_expired_lease() {
+        let store = DieselLockStore::new(create_connection_pool_and_migrate());
+
+        let expired = store
+            .acquire_lock("backup", "node_a", Duration::from_millis(1))
+            .expect("Failed to acquire lock")
+            .expect("Lock was not acquired");
+        thread::sleep(Duration::from_millis(10));
+        assert_eq!(store.get_lock("backup").expect("Failed to get lock"), None);
+
+        let lease = store
+            .acquire_lock("backup", "node_b", Duration::from_secs(60))
+            .expect("Failed to acquire lock")
+            .expect("Lock was not acquired");
+        assert_eq!(lease.fencing_token(), expired.fencing_token() + 1);
+
+        assert_eq!(
+            store
+                .renew_lock(&expired, Duration::from_secs(60))
+                .expect("Failed to renew lock"),
+            None
+        );
+        assert!(!store
+            .release_lock(&expired)
+            .expect("Failed to release lock"));
+        assert_eq!(
+            store.get_lock("backup").expect("Failed to get lock"),
+            Some(lease)
+        );
+    }
+
+    /// Verify that a lease with a zero ttl is rejected.
+    #[test]
+    fn sqlite_lock_zero_ttl() {
+        let store = DieselLockStore::new(create_connection_pool_and_migrate());
+
+        assert!(matches!(
+            store.acquire_lock("backup", "node_a", Duration::from_secs(0)),
+            Err(LockStoreError::InvalidArgument(_))
+        ));
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+}
@@ -0,0 +1,89 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryFrom;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::{InternalError, InvalidArgumentError};
+use crate::store::lock::{Lease, LockStoreError};
+
+use super::schema::store_lock;
+
+#[derive(Insertable, Queryable, PartialEq, Eq, Debug)]
+#[table_name = "store_lock"]
+pub struct StoreLockModel {
+    pub name: String,
+    // The owner is cleared when the lock is released, and the row is kept so that the fencing
+    // tokens of later leases continue from the last one
+    pub owner: Option<String>,
+    pub fencing_token: i64,
+    pub expires_at: i64,
+}
+
+impl TryFrom<StoreLockModel> for Lease {
+    type Error = LockStoreError;
+
+    fn try_from(model: StoreLockModel) -> Result<Self, Self::Error> {
+        let owner = model.owner.ok_or_else(|| {
+            LockStoreError::Internal(InternalError::with_message(format!(
+                "lock {} is not held",
+                model.name
+            )))
+        })?;
+        let fencing_token = u64::try_from(model.fencing_token).map_err(|_| {
+            LockStoreError::Internal(InternalError::with_message(format!(
+                "lock {} has an invalid fencing token {}",
+                model.name, model.fencing_token
+            )))
+        })?;
+
+        Ok(Lease::new(
+            model.name,
+            owner,
+            fencing_token,
+            from_millis(model.expires_at)?,
+        ))
+    }
+}
+
+/// Converts the given ttl, which must not be zero, to milliseconds.
+pub(super) fn ttl_millis(ttl: Duration) -> Result<i64, LockStoreError> {
+    if ttl == Duration::from_secs(0) {
+        return Err(LockStoreError::InvalidArgument(InvalidArgumentError::new(
+            "ttl".to_string(),
+            "a lease must last longer than zero".to_string(),
+        )));
+    }
+
+    i64::try_from(ttl.as_millis()).map_err(|_| {
+        LockStoreError::InvalidArgument(InvalidArgumentError::new(
+            "ttl".to_string(),
+            "the lease would expire too far in the future".to_string(),
+        ))
+    })
+}
+
+/// Converts the number of milliseconds since the Unix epoch, as stored in the `expires_at`
+/// column, to a time.
+pub(super) fn from_millis(millis: i64) -> Result<SystemTime, LockStoreError> {
+    u64::try_from(millis)
+        .ok()
+        .and_then(|millis| UNIX_EPOCH.checked_add(Duration::from_millis(millis)))
+        .ok_or_else(|| {
+            LockStoreError::Internal(InternalError::with_message(format!(
+                "timestamp {} could not be represented as a SystemTime",
+                millis
+            )))
+        })
+}
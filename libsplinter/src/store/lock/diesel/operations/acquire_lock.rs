@@ -0,0 +1,140 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::time::Duration;
+
+use diesel::{dsl::update, prelude::*};
+
+use crate::store::lock::diesel::models::{ttl_millis, StoreLockModel};
+use crate::store::lock::diesel::schema::store_lock;
+use crate::store::lock::{Lease, LockStoreError};
+
+use super::{expires_at_millis, now_millis, LockStoreOperations};
+
+pub(in crate::store::lock::diesel) trait LockStoreAcquireLockOperation {
+    fn acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>, LockStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> LockStoreAcquireLockOperation
+    for LockStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>, LockStoreError> {
+        let now = now_millis::<diesel::sqlite::SqliteConnection>();
+        let expires_at = expires_at_millis::<diesel::sqlite::SqliteConnection>(ttl_millis(ttl)?);
+
+        self.conn.transaction::<_, LockStoreError, _>(|| {
+            let inserted = diesel::insert_or_ignore_into(store_lock::table)
+                .values((
+                    store_lock::name.eq(name),
+                    store_lock::owner.eq(Some(owner)),
+                    store_lock::fencing_token.eq(1i64),
+                    store_lock::expires_at.eq(expires_at.clone()),
+                ))
+                .execute(self.conn)?;
+
+            if inserted == 0 {
+                // The lock has been held before; take it over if it has been released or its
+                // lease has expired, with the next fencing token
+                let updated = update(
+                    store_lock::table.filter(store_lock::name.eq(name)).filter(
+                        store_lock::owner
+                            .is_null()
+                            .or(store_lock::expires_at.le(now)),
+                    ),
+                )
+                .set((
+                    store_lock::owner.eq(Some(owner)),
+                    store_lock::fencing_token.eq(store_lock::fencing_token + 1),
+                    store_lock::expires_at.eq(expires_at),
+                ))
+                .execute(self.conn)?;
+
+                if updated == 0 {
+                    return Ok(None);
+                }
+            }
+
+            store_lock::table
+                .find(name)
+                .first::<StoreLockModel>(self.conn)?
+                .try_into()
+                .map(Some)
+        })
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> LockStoreAcquireLockOperation for LockStoreOperations<'a, diesel::pg::PgConnection> {
+    fn acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>, LockStoreError> {
+        let now = now_millis::<diesel::pg::PgConnection>();
+        let expires_at = expires_at_millis::<diesel::pg::PgConnection>(ttl_millis(ttl)?);
+
+        self.conn.transaction::<_, LockStoreError, _>(|| {
+            let inserted = diesel::insert_into(store_lock::table)
+                .values((
+                    store_lock::name.eq(name),
+                    store_lock::owner.eq(Some(owner)),
+                    store_lock::fencing_token.eq(1i64),
+                    store_lock::expires_at.eq(expires_at.clone()),
+                ))
+                .on_conflict_do_nothing()
+                .execute(self.conn)?;
+
+            if inserted == 0 {
+                // The lock has been held before; take it over if it has been released or its
+                // lease has expired, with the next fencing token
+                let updated = update(
+                    store_lock::table.filter(store_lock::name.eq(name)).filter(
+                        store_lock::owner
+                            .is_null()
+                            .or(store_lock::expires_at.le(now)),
+                    ),
+                )
+                .set((
+                    store_lock::owner.eq(Some(owner)),
+                    store_lock::fencing_token.eq(store_lock::fencing_token + 1),
+                    store_lock::expires_at.eq(expires_at),
+                ))
+                .execute(self.conn)?;
+
+                if updated == 0 {
+                    return Ok(None);
+                }
+            }
+
+            store_lock::table
+                .find(name)
+                .first::<StoreLockModel>(self.conn)?
+                .try_into()
+                .map(Some)
+        })
+    }
+}
@@ -0,0 +1,45 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+
+use diesel::prelude::*;
+
+use crate::store::lock::diesel::models::StoreLockModel;
+use crate::store::lock::diesel::schema::store_lock;
+use crate::store::lock::{Lease, LockStoreError};
+
+use super::{now_millis, DatabaseClock, LockStoreOperations};
+
+pub(in crate::store::lock::diesel) trait LockStoreGetLockOperation {
+    fn get_lock(&self, name: &str) -> Result<Option<Lease>, LockStoreError>;
+}
+
+impl<'a, C> LockStoreGetLockOperation for LockStoreOperations<'a, C>
+where
+    C: diesel::Connection + DatabaseClock,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn get_lock(&self, name: &str) -> Result<Option<Lease>, LockStoreError> {
+        store_lock::table
+            .filter(store_lock::name.eq(name))
+            .filter(store_lock::owner.is_not_null())
+            .filter(store_lock::expires_at.gt(now_millis::<C>()))
+            .first::<StoreLockModel>(self.conn)
+            .optional()?
+            .map(TryInto::try_into)
+            .transpose()
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Provides [`LockStore`](super::super::LockStore) operations implemented for a diesel backend
+
+pub(super) mod acquire_lock;
+pub(super) mod get_lock;
+pub(super) mod release_lock;
+pub(super) mod renew_lock;
+
+use diesel::dsl::{sql, SqlLiteral};
+use diesel::sql_types::BigInt;
+
+pub(super) struct LockStoreOperations<'a, C> {
+    conn: &'a C,
+}
+
+impl<'a, C> LockStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+{
+    pub fn new(conn: &'a C) -> Self {
+        LockStoreOperations { conn }
+    }
+}
+
+/// Provides the database's clock, so that every process that shares a lock decides when its
+/// leases expire by the same clock
+pub(super) trait DatabaseClock {
+    /// An SQL expression for the current time, in milliseconds since the Unix epoch, as stored
+    /// in the `expires_at` column
+    const NOW_MILLIS: &'static str;
+}
+
+#[cfg(feature = "postgres")]
+impl DatabaseClock for diesel::pg::PgConnection {
+    const NOW_MILLIS: &'static str = "CAST(EXTRACT(EPOCH FROM CURRENT_TIMESTAMP) * 1000 AS BIGINT)";
+}
+
+#[cfg(feature = "sqlite")]
+impl DatabaseClock for diesel::sqlite::SqliteConnection {
+    // %f is the seconds with their milliseconds, as SS.SSS
+    const NOW_MILLIS: &'static str = "(CAST(strftime('%s', 'now') AS INTEGER) * 1000 \
+        + CAST(substr(strftime('%f', 'now'), 4) AS INTEGER))";
+}
+
+/// Returns the database's current time, in milliseconds since the Unix epoch.
+pub(super) fn now_millis<C: DatabaseClock>() -> SqlLiteral<BigInt> {
+    sql::<BigInt>(C::NOW_MILLIS)
+}
+
+/// Returns the time `ttl_millis` after the database's current time, in milliseconds since the
+/// Unix epoch.
+pub(super) fn expires_at_millis<C: DatabaseClock>(ttl_millis: i64) -> SqlLiteral<BigInt> {
+    sql::<BigInt>(&format!("({} + {})", C::NOW_MILLIS, ttl_millis))
+}
@@ -0,0 +1,45 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::update, prelude::*};
+
+use crate::store::lock::diesel::schema::store_lock;
+use crate::store::lock::{Lease, LockStoreError};
+
+use super::LockStoreOperations;
+
+pub(in crate::store::lock::diesel) trait LockStoreReleaseLockOperation {
+    fn release_lock(&self, lease: &Lease) -> Result<bool, LockStoreError>;
+}
+
+impl<'a, C> LockStoreReleaseLockOperation for LockStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn release_lock(&self, lease: &Lease) -> Result<bool, LockStoreError> {
+        // The row is kept, so that the next lease of the lock gets the next fencing token
+        let updated = update(
+            store_lock::table
+                .filter(store_lock::name.eq(lease.name()))
+                .filter(store_lock::owner.eq(lease.owner()))
+                .filter(store_lock::fencing_token.eq(lease.fencing_token() as i64)),
+        )
+        .set(store_lock::owner.eq(None::<String>))
+        .execute(self.conn)?;
+
+        Ok(updated == 1)
+    }
+}
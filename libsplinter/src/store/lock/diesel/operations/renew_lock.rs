@@ -0,0 +1,61 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::convert::TryInto;
+use std::time::Duration;
+
+use diesel::{dsl::update, prelude::*};
+
+use crate::store::lock::diesel::models::{ttl_millis, StoreLockModel};
+use crate::store::lock::diesel::schema::store_lock;
+use crate::store::lock::{Lease, LockStoreError};
+
+use super::{expires_at_millis, now_millis, DatabaseClock, LockStoreOperations};
+
+pub(in crate::store::lock::diesel) trait LockStoreRenewLockOperation {
+    fn renew_lock(&self, lease: &Lease, ttl: Duration) -> Result<Option<Lease>, LockStoreError>;
+}
+
+impl<'a, C> LockStoreRenewLockOperation for LockStoreOperations<'a, C>
+where
+    C: diesel::Connection + DatabaseClock,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
+{
+    fn renew_lock(&self, lease: &Lease, ttl: Duration) -> Result<Option<Lease>, LockStoreError> {
+        let ttl_millis = ttl_millis(ttl)?;
+
+        self.conn.transaction::<_, LockStoreError, _>(|| {
+            let updated = update(
+                store_lock::table
+                    .filter(store_lock::name.eq(lease.name()))
+                    .filter(store_lock::owner.eq(lease.owner()))
+                    .filter(store_lock::fencing_token.eq(lease.fencing_token() as i64))
+                    .filter(store_lock::expires_at.gt(now_millis::<C>())),
+            )
+            .set(store_lock::expires_at.eq(expires_at_millis::<C>(ttl_millis)))
+            .execute(self.conn)?;
+
+            if updated == 0 {
+                return Ok(None);
+            }
+
+            store_lock::table
+                .find(lease.name())
+                .first::<StoreLockModel>(self.conn)?
+                .try_into()
+                .map(Some)
+        })
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::error::{InternalError, InvalidArgumentError};
+
+/// Errors that may occur during [`LockStore`](super::LockStore) operations.
+#[derive(Debug)]
+pub enum LockStoreError {
+    Internal(InternalError),
+    InvalidArgument(InvalidArgumentError),
+}
+
+impl Error for LockStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LockStoreError::Internal(err) => err.source(),
+            LockStoreError::InvalidArgument(err) => err.source(),
+        }
+    }
+}
+
+impl fmt::Display for LockStoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LockStoreError::Internal(err) => f.write_str(&err.to_string()),
+            LockStoreError::InvalidArgument(err) => f.write_str(&err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for LockStoreError {
+    fn from(err: diesel::r2d2::PoolError) -> LockStoreError {
+        LockStoreError::Internal(InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for LockStoreError {
+    fn from(err: diesel::result::Error) -> Self {
+        LockStoreError::Internal(InternalError::from_source(Box::new(err)))
+    }
+}
+
+impl From<InternalError> for LockStoreError {
+    fn from(err: InternalError) -> Self {
+        Self::Internal(err)
+    }
+}
@@ -0,0 +1,167 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Leases on named locks, stored in the database so that they are shared by every process that
+//! uses it, such as the nodes of a highly available deployment and their backup and maintenance
+//! jobs.
+//!
+//! A lock is held by at most one owner at a time, for a limited time: a [`Lease`] expires unless
+//! it is renewed, so that a lock held by a process that has died is eventually released. Each
+//! lease of a lock has a fencing token greater than those of all of the lock's earlier leases.
+//! A resource guarded by a lock should reject writes made with a fencing token lower than one
+//! it has already seen, so that an owner whose lease expired while it was paused cannot
+//! overwrite the work of the next owner.
+//!
+//! Expiry is decided by the database's clock, so that the processes that share a lock agree on
+//! when its leases expire regardless of their own clocks. A process that compares a lease's
+//! expiry with its own clock should still renew the lease well before it expires.
+
+#[cfg(feature = "diesel")]
+mod diesel;
+pub mod error;
+
+use std::time::{Duration, SystemTime};
+
+#[cfg(feature = "diesel")]
+pub use self::diesel::DieselLockStore;
+pub use error::LockStoreError;
+
+/// A lease on a named lock, held by an owner until it expires.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lease {
+    name: String,
+    owner: String,
+    fencing_token: u64,
+    expires_at: SystemTime,
+}
+
+impl Lease {
+    pub fn new(name: String, owner: String, fencing_token: u64, expires_at: SystemTime) -> Self {
+        Self {
+            name,
+            owner,
+            fencing_token,
+            expires_at,
+        }
+    }
+
+    /// Returns the name of the lock
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the owner of the lease
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Returns the fencing token of the lease, which is greater than the fencing tokens of all
+    /// earlier leases of the same lock
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token
+    }
+
+    /// Returns the time the lease expires, unless it is renewed
+    pub fn expires_at(&self) -> SystemTime {
+        self.expires_at
+    }
+
+    /// Returns true if the lease has expired at the given time.
+    pub fn is_expired_at(&self, time: SystemTime) -> bool {
+        self.expires_at <= time
+    }
+}
+
+/// Defines methods for acquiring, renewing and releasing leases on named locks
+pub trait LockStore: Send + Sync {
+    /// Acquires the lock for the owner, if the lock is not held or its lease has expired,
+    /// returning the new lease, or `None` if the lock is held by an unexpired lease
+    ///
+    /// A lock is not reentrant: an owner that already holds the lock cannot acquire it again
+    /// until its lease is released or expires.
+    ///
+    /// # Arguments
+    ///
+    ///  * `name` - The name of the lock
+    ///  * `owner` - The owner of the new lease, such as a node ID or a process name
+    ///  * `ttl` - How long the lease lasts, unless it is renewed
+    fn acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>, LockStoreError>;
+
+    /// Extends the lease so that it expires `ttl` from now, returning the renewed lease, or
+    /// `None` if the lease has expired or has been released
+    ///
+    /// # Arguments
+    ///
+    ///  * `lease` - The lease to renew
+    ///  * `ttl` - How long the renewed lease lasts, from now
+    fn renew_lock(&self, lease: &Lease, ttl: Duration) -> Result<Option<Lease>, LockStoreError>;
+
+    /// Releases the lock held by the lease, returning false if the lease no longer held the lock
+    ///
+    /// # Arguments
+    ///
+    ///  * `lease` - The lease to release
+    fn release_lock(&self, lease: &Lease) -> Result<bool, LockStoreError>;
+
+    /// Returns the unexpired lease on the lock, if it is held
+    ///
+    /// # Arguments
+    ///
+    ///  * `name` - The name of the lock
+    fn get_lock(&self, name: &str) -> Result<Option<Lease>, LockStoreError>;
+
+    /// Clone into a boxed, dynamically dispatched store
+    fn clone_box(&self) -> Box<dyn LockStore>;
+}
+
+impl Clone for Box<dyn LockStore> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+impl<LS> LockStore for Box<LS>
+where
+    LS: LockStore + ?Sized,
+{
+    fn acquire_lock(
+        &self,
+        name: &str,
+        owner: &str,
+        ttl: Duration,
+    ) -> Result<Option<Lease>, LockStoreError> {
+        (**self).acquire_lock(name, owner, ttl)
+    }
+
+    fn renew_lock(&self, lease: &Lease, ttl: Duration) -> Result<Option<Lease>, LockStoreError> {
+        (**self).renew_lock(lease, ttl)
+    }
+
+    fn release_lock(&self, lease: &Lease) -> Result<bool, LockStoreError> {
+        (**self).release_lock(lease)
+    }
+
+    fn get_lock(&self, name: &str) -> Result<Option<Lease>, LockStoreError> {
+        (**self).get_lock(name)
+    }
+
+    fn clone_box(&self) -> Box<dyn LockStore> {
+        (**self).clone_box()
+    }
+}
@@ -13,6 +13,12 @@
 // limitations under the License.
 
 //! Implementation of a `StoreFactory` for in memory
+//!
+//! The registry, node ID, Biome and OAuth stores are kept in memory natively. The remaining stores
+//! use an in-memory SQLite database, which is created with its migrations applied, so no database
+//! file or `splinter database migrate` is needed.
+
+use std::sync::{Arc, RwLock};
 
 use diesel::{
     r2d2::{ConnectionManager, Pool},
@@ -27,13 +33,19 @@ use crate::biome::{
 };
 #[cfg(feature = "biome-key-management")]
 use crate::biome::{KeyStore, MemoryKeyStore};
+#[cfg(feature = "biome-notifications")]
+use crate::biome::{MemoryNotificationSubscriptionStore, NotificationSubscriptionStore};
 #[cfg(feature = "biome-profile")]
 use crate::biome::{MemoryUserProfileStore, UserProfileStore};
 use crate::error::InternalError;
+#[cfg(feature = "node-id-store")]
+use crate::node_id::store::memory::MemoryNodeIdStore;
 #[cfg(feature = "oauth")]
 use crate::oauth::store::MemoryInflightOAuthRequestStore;
+#[cfg(feature = "registry")]
+use crate::registry::MemoryRegistry;
 
-use super::sqlite::ConnectionCustomizer;
+use super::sqlite::create_sqlite_connection_pool_with_write_exclusivity;
 use super::StoreFactory;
 
 /// A `StoryFactory` backed by memory.
@@ -50,12 +62,30 @@ pub struct MemoryStoreFactory {
     inflight_request_store: MemoryInflightOAuthRequestStore,
     #[cfg(feature = "biome-profile")]
     biome_profile_store: MemoryUserProfileStore,
+    #[cfg(feature = "biome-notifications")]
+    biome_notification_subscription_store: MemoryNotificationSubscriptionStore,
+    #[cfg(feature = "registry")]
+    registry: MemoryRegistry,
+    #[cfg(feature = "node-id-store")]
+    node_id_store: MemoryNodeIdStore,
     // to be used for sqlite in memory implementations
-    pool: Pool<ConnectionManager<SqliteConnection>>,
+    pool: Arc<RwLock<Pool<ConnectionManager<SqliteConnection>>>>,
 }
 
 impl MemoryStoreFactory {
+    /// Create a new `MemoryStoreFactory` with its own in-memory SQLite database.
     pub fn new() -> Result<Self, InternalError> {
+        Ok(Self::new_with_write_exclusivity(
+            create_sqlite_connection_pool_with_write_exclusivity(":memory:")?,
+        ))
+    }
+
+    /// Create a new `MemoryStoreFactory` whose SQLite-backed stores share the given in-memory
+    /// database, such as with the other stores of a node. The database's migrations must already
+    /// be applied, as they are for a pool created for ":memory:".
+    pub fn new_with_write_exclusivity(
+        pool: Arc<RwLock<Pool<ConnectionManager<SqliteConnection>>>>,
+    ) -> Self {
         #[cfg(feature = "biome-credentials")]
         let biome_credentials_store = MemoryCredentialsStore::new();
 
@@ -73,21 +103,7 @@ impl MemoryStoreFactory {
         #[cfg(feature = "biome-profile")]
         let biome_profile_store = MemoryUserProfileStore::new();
 
-        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
-        let pool = Pool::builder()
-            .max_size(1)
-            .connection_customizer(Box::new(ConnectionCustomizer::default()))
-            .build(connection_manager)
-            .map_err(|err| InternalError::from_source(Box::new(err)))?;
-
-        crate::migrations::run_sqlite_migrations(
-            &*pool
-                .get()
-                .map_err(|err| InternalError::from_source(Box::new(err)))?,
-        )
-        .map_err(|err| InternalError::from_source(Box::new(err)))?;
-
-        Ok(Self {
+        Self {
             #[cfg(feature = "biome-credentials")]
             biome_credentials_store,
             #[cfg(feature = "biome-key-management")]
@@ -100,8 +116,14 @@ impl MemoryStoreFactory {
             inflight_request_store,
             #[cfg(feature = "biome-profile")]
             biome_profile_store,
+            #[cfg(feature = "biome-notifications")]
+            biome_notification_subscription_store: MemoryNotificationSubscriptionStore::new(),
+            #[cfg(feature = "registry")]
+            registry: MemoryRegistry::new(),
+            #[cfg(feature = "node-id-store")]
+            node_id_store: MemoryNodeIdStore::new(),
             pool,
-        })
+        }
     }
 }
 
@@ -128,9 +150,11 @@ impl StoreFactory for MemoryStoreFactory {
 
     #[cfg(feature = "admin-service")]
     fn get_admin_service_store(&self) -> Box<dyn crate::admin::store::AdminServiceStore> {
-        Box::new(crate::admin::store::diesel::DieselAdminServiceStore::new(
-            self.pool.clone(),
-        ))
+        Box::new(
+            crate::admin::store::diesel::DieselAdminServiceStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
     }
 
     #[cfg(feature = "oauth")]
@@ -142,16 +166,18 @@ impl StoreFactory for MemoryStoreFactory {
 
     #[cfg(feature = "registry")]
     fn get_registry_store(&self) -> Box<dyn crate::registry::RwRegistry> {
-        Box::new(crate::registry::DieselRegistry::new(self.pool.clone()))
+        Box::new(self.registry.clone())
     }
 
     #[cfg(feature = "authorization-handler-rbac")]
     fn get_role_based_authorization_store(
         &self,
     ) -> Box<dyn crate::rbac::store::RoleBasedAuthorizationStore> {
-        Box::new(crate::rbac::store::DieselRoleBasedAuthorizationStore::new(
-            self.pool.clone(),
-        ))
+        Box::new(
+            crate::rbac::store::DieselRoleBasedAuthorizationStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
     }
 
     #[cfg(feature = "biome-profile")]
@@ -159,17 +185,79 @@ impl StoreFactory for MemoryStoreFactory {
         Box::new(self.biome_profile_store.clone())
     }
 
+    #[cfg(feature = "biome-notifications")]
+    fn get_biome_notification_subscription_store(&self) -> Box<dyn NotificationSubscriptionStore> {
+        Box::new(self.biome_notification_subscription_store.clone())
+    }
+
+    #[cfg(feature = "announcements")]
+    fn get_announcement_store(&self) -> Box<dyn crate::announcement::store::AnnouncementStore> {
+        Box::new(
+            crate::announcement::store::DieselAnnouncementStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "api-keys")]
+    fn get_api_key_store(&self) -> Box<dyn crate::api_key::store::ApiKeyStore> {
+        Box::new(
+            crate::api_key::store::DieselApiKeyStore::new_with_write_exclusivity(self.pool.clone()),
+        )
+    }
+
+    #[cfg(feature = "audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::audit::store::AuditStore> {
+        Box::new(
+            crate::audit::store::DieselAuditStore::new_with_write_exclusivity(self.pool.clone()),
+        )
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    fn get_circuit_usage_store(&self) -> Box<dyn crate::circuit::usage::store::CircuitUsageStore> {
+        Box::new(
+            crate::circuit::usage::store::DieselCircuitUsageStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "peer-blocklist")]
+    fn get_peer_blocklist_store(
+        &self,
+    ) -> Box<dyn crate::peer::blocklist::store::PeerBlocklistStore> {
+        Box::new(
+            crate::peer::blocklist::store::DieselPeerBlocklistStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "peer-metadata")]
+    fn get_peer_metadata_store(&self) -> Box<dyn crate::peer::metadata::store::PeerMetadataStore> {
+        Box::new(
+            crate::peer::metadata::store::DieselPeerMetadataStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
     #[cfg(feature = "node-id-store")]
     fn get_node_id_store(&self) -> Box<dyn crate::node_id::store::NodeIdStore> {
-        Box::new(crate::node_id::store::diesel::DieselNodeIdStore::new(
-            self.pool.clone(),
-        ))
+        Box::new(self.node_id_store.clone())
     }
 
     #[cfg(feature = "service-lifecycle-store")]
     fn get_lifecycle_store(&self) -> Box<dyn crate::runtime::service::LifecycleStore + Send> {
-        Box::new(crate::runtime::service::DieselLifecycleStore::new(
-            self.pool.clone(),
-        ))
+        Box::new(
+            crate::runtime::service::DieselLifecycleStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "store-lock")]
+    fn get_lock_store(&self) -> Box<dyn crate::store::lock::LockStore> {
+        Box::new(crate::store::lock::DieselLockStore::new_with_write_exclusivity(self.pool.clone()))
     }
 }
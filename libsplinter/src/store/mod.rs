@@ -15,15 +15,22 @@
 //! Contains a `StoreFactory` trait, which is an abstract factory for building stores
 //! backed by a single storage mechanism (e.g. database)
 pub mod command;
+#[cfg(feature = "store-lock")]
+pub mod lock;
 #[cfg(all(feature = "store-factory", feature = "memory"))]
 pub mod memory;
 #[cfg(feature = "diesel")]
 pub(crate) mod pool;
+#[cfg(all(feature = "diesel", feature = "store-pool-settings"))]
+mod pool_settings;
 #[cfg(all(feature = "store-factory", feature = "postgres"))]
 pub mod postgres;
 #[cfg(all(feature = "store-factory", feature = "sqlite"))]
 pub mod sqlite;
 
+#[cfg(all(feature = "diesel", feature = "store-pool-settings"))]
+pub use pool_settings::ConnectionPoolSettings;
+
 /// An abstract factory for creating Splinter stores backed by the same storage
 #[cfg(feature = "store-factory")]
 pub trait StoreFactory {
@@ -62,9 +69,45 @@ pub trait StoreFactory {
     #[cfg(feature = "biome-profile")]
     fn get_biome_user_profile_store(&self) -> Box<dyn crate::biome::UserProfileStore>;
 
+    /// Get a new `NotificationSubscriptionStore`
+    #[cfg(feature = "biome-notifications")]
+    fn get_biome_notification_subscription_store(
+        &self,
+    ) -> Box<dyn crate::biome::NotificationSubscriptionStore>;
+
+    /// Get a new `AnnouncementStore`
+    #[cfg(feature = "announcements")]
+    fn get_announcement_store(&self) -> Box<dyn crate::announcement::store::AnnouncementStore>;
+
+    /// Get a new `ApiKeyStore`
+    #[cfg(feature = "api-keys")]
+    fn get_api_key_store(&self) -> Box<dyn crate::api_key::store::ApiKeyStore>;
+
+    /// Get a new `AuditStore`
+    #[cfg(feature = "audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::audit::store::AuditStore>;
+
+    /// Get a new `CircuitUsageStore`
+    #[cfg(feature = "circuit-usage")]
+    fn get_circuit_usage_store(&self) -> Box<dyn crate::circuit::usage::store::CircuitUsageStore>;
+
+    /// Get a new `PeerBlocklistStore`
+    #[cfg(feature = "peer-blocklist")]
+    fn get_peer_blocklist_store(
+        &self,
+    ) -> Box<dyn crate::peer::blocklist::store::PeerBlocklistStore>;
+
+    /// Get a new `PeerMetadataStore`
+    #[cfg(feature = "peer-metadata")]
+    fn get_peer_metadata_store(&self) -> Box<dyn crate::peer::metadata::store::PeerMetadataStore>;
+
     #[cfg(feature = "node-id-store")]
     fn get_node_id_store(&self) -> Box<dyn crate::node_id::store::NodeIdStore>;
 
     #[cfg(feature = "service-lifecycle-store")]
     fn get_lifecycle_store(&self) -> Box<dyn crate::runtime::service::LifecycleStore + Send>;
+
+    /// Get a new `LockStore`
+    #[cfg(feature = "store-lock")]
+    fn get_lock_store(&self) -> Box<dyn crate::store::lock::LockStore>;
 }
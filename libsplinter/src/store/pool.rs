@@ -17,7 +17,7 @@ use std::sync::{Arc, RwLock};
 use diesel::r2d2::{ConnectionManager, Pool};
 
 #[cfg(any(
-    any(feature = "postgres", feature = "sqlite"),
+    any(feature = "mysql", feature = "postgres", feature = "sqlite"),
     all(feature = "diesel", feature = "registry")
 ))]
 use crate::error::InternalError;
@@ -28,7 +28,7 @@ pub enum ConnectionPool<C: diesel::Connection + 'static> {
 }
 
 #[cfg(any(
-    any(feature = "postgres", feature = "sqlite"),
+    any(feature = "mysql", feature = "postgres", feature = "sqlite"),
     all(feature = "diesel", feature = "registry")
 ))]
 macro_rules! conn {
@@ -40,7 +40,7 @@ macro_rules! conn {
 }
 
 #[cfg(any(
-    any(feature = "postgres", feature = "sqlite"),
+    any(feature = "mysql", feature = "postgres", feature = "sqlite"),
     all(feature = "diesel", feature = "registry")
 ))]
 impl<C: diesel::Connection> ConnectionPool<C> {
@@ -0,0 +1,212 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Settings for the database connection pools that back Diesel stores.
+
+use std::time::Duration;
+
+use diesel::r2d2::{Builder, ManageConnection};
+
+use crate::error::InvalidArgumentError;
+
+/// The connection pool's default maximum number of connections
+const DEFAULT_MAX_SIZE: u32 = 10;
+
+/// The sizes and timeouts of a database connection pool.
+///
+/// Any setting that is not given keeps the connection pool's default: at most 10 connections,
+/// all kept open while idle, and 30 seconds to wait for a connection.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionPoolSettings {
+    max_size: Option<u32>,
+    connection_timeout: Option<Duration>,
+    min_idle: Option<u32>,
+    idle_timeout: Option<Duration>,
+}
+
+impl ConnectionPoolSettings {
+    /// Constructs settings that keep all of the connection pool's defaults.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of connections the pool will open.
+    pub fn with_max_size(mut self, max_size: u32) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Sets how long to wait for a connection before a request for one fails.
+    pub fn with_connection_timeout(mut self, connection_timeout: Duration) -> Self {
+        self.connection_timeout = Some(connection_timeout);
+        self
+    }
+
+    /// Sets the number of idle connections the pool will keep open.
+    pub fn with_min_idle(mut self, min_idle: u32) -> Self {
+        self.min_idle = Some(min_idle);
+        self
+    }
+
+    /// Sets how long a connection may be idle before it is closed, while the pool holds more
+    /// than its minimum number of idle connections.
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    pub fn max_size(&self) -> Option<u32> {
+        self.max_size
+    }
+
+    pub fn connection_timeout(&self) -> Option<Duration> {
+        self.connection_timeout
+    }
+
+    pub fn min_idle(&self) -> Option<u32> {
+        self.min_idle
+    }
+
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    /// Applies these settings to the given connection pool builder.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [InvalidArgumentError] if the maximum size or a timeout is zero, or if the
+    /// minimum number of idle connections is greater than the maximum size.
+    pub fn apply<M: ManageConnection>(
+        &self,
+        mut builder: Builder<M>,
+    ) -> Result<Builder<M>, InvalidArgumentError> {
+        if let Some(max_size) = self.max_size {
+            if max_size == 0 {
+                return Err(InvalidArgumentError::new(
+                    "max_size",
+                    "must be greater than 0",
+                ));
+            }
+            builder = builder.max_size(max_size);
+        }
+
+        if let Some(connection_timeout) = self.connection_timeout {
+            if connection_timeout == Duration::from_secs(0) {
+                return Err(InvalidArgumentError::new(
+                    "connection_timeout",
+                    "must be greater than 0",
+                ));
+            }
+            builder = builder.connection_timeout(connection_timeout);
+        }
+
+        if let Some(min_idle) = self.min_idle {
+            if min_idle > self.max_size.unwrap_or(DEFAULT_MAX_SIZE) {
+                return Err(InvalidArgumentError::new(
+                    "min_idle",
+                    "must not be greater than the maximum size",
+                ));
+            }
+            builder = builder.min_idle(Some(min_idle));
+        }
+
+        if let Some(idle_timeout) = self.idle_timeout {
+            if idle_timeout == Duration::from_secs(0) {
+                return Err(InvalidArgumentError::new(
+                    "idle_timeout",
+                    "must be greater than 0",
+                ));
+            }
+            builder = builder.idle_timeout(Some(idle_timeout));
+        }
+
+        Ok(builder)
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::r2d2::{ConnectionManager, Pool};
+    use diesel::sqlite::SqliteConnection;
+
+    type SqliteBuilder = Builder<ConnectionManager<SqliteConnection>>;
+
+    fn build_pool(builder: SqliteBuilder) -> Pool<ConnectionManager<SqliteConnection>> {
+        builder.build_unchecked(ConnectionManager::new(":memory:"))
+    }
+
+    /// Verifies that the given settings are applied to a pool builder, and that settings that are
+    /// not given keep the pool's defaults.
+    #[test]
+    fn apply_settings() {
+        let default_pool = build_pool(Pool::builder());
+        assert_eq!(default_pool.max_size(), DEFAULT_MAX_SIZE);
+
+        let pool = build_pool(
+            ConnectionPoolSettings::new()
+                .with_max_size(4)
+                .with_connection_timeout(Duration::from_secs(5))
+                .with_min_idle(2)
+                .apply(Pool::builder())
+                .expect("Failed to apply settings"),
+        );
+
+        assert_eq!(pool.max_size(), 4);
+        assert_eq!(pool.connection_timeout(), Duration::from_secs(5));
+        assert_eq!(pool.min_idle(), Some(2));
+        assert_eq!(pool.idle_timeout(), default_pool.idle_timeout());
+
+        let pool = build_pool(
+            ConnectionPoolSettings::new()
+                .apply(Pool::builder())
+                .expect("Failed to apply settings"),
+        );
+
+        assert_eq!(pool.max_size(), default_pool.max_size());
+        assert_eq!(pool.min_idle(), default_pool.min_idle());
+        assert_eq!(pool.connection_timeout(), default_pool.connection_timeout());
+    }
+
+    /// Verifies that settings the pool would reject are returned as errors.
+    #[test]
+    fn apply_invalid_settings() {
+        let builder = || -> SqliteBuilder { Pool::builder() };
+
+        assert!(ConnectionPoolSettings::new()
+            .with_max_size(0)
+            .apply(builder())
+            .is_err());
+        assert!(ConnectionPoolSettings::new()
+            .with_connection_timeout(Duration::from_secs(0))
+            .apply(builder())
+            .is_err());
+        assert!(ConnectionPoolSettings::new()
+            .with_idle_timeout(Duration::from_secs(0))
+            .apply(builder())
+            .is_err());
+        assert!(ConnectionPoolSettings::new()
+            .with_max_size(2)
+            .with_min_idle(3)
+            .apply(builder())
+            .is_err());
+        // The minimum is checked against the default maximum if no maximum is given
+        assert!(ConnectionPoolSettings::new()
+            .with_min_idle(DEFAULT_MAX_SIZE + 1)
+            .apply(builder())
+            .is_err());
+    }
+}
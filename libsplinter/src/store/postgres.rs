@@ -16,12 +16,14 @@
 
 use diesel::{
     pg::PgConnection,
-    r2d2::{ConnectionManager, Pool},
+    r2d2::{Builder, ConnectionManager, Pool},
 };
 
 use crate::error::InternalError;
 use crate::migrations::any_pending_postgres_migrations;
 
+#[cfg(feature = "store-pool-settings")]
+use super::ConnectionPoolSettings;
 use super::StoreFactory;
 
 /// Create a Postgres connection pool.
@@ -37,9 +39,40 @@ use super::StoreFactory;
 /// * The database requires any pending migrations
 pub fn create_postgres_connection_pool(
     url: &str,
+) -> Result<Pool<ConnectionManager<PgConnection>>, InternalError> {
+    build_postgres_connection_pool(url, Pool::builder())
+}
+
+/// Create a Postgres connection pool with the given sizes and timeouts.
+///
+/// # Arguments
+///
+/// * url - a valid postges connection url
+/// * settings - the settings of the pool
+///
+/// # Errors
+///
+/// An [InternalError] is returned if
+/// * The settings are invalid
+/// * The pool cannot be created
+/// * The database requires any pending migrations
+#[cfg(feature = "store-pool-settings")]
+pub fn create_postgres_connection_pool_with_settings(
+    url: &str,
+    settings: &ConnectionPoolSettings,
+) -> Result<Pool<ConnectionManager<PgConnection>>, InternalError> {
+    let pool_builder = settings
+        .apply(Pool::builder())
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    build_postgres_connection_pool(url, pool_builder)
+}
+
+fn build_postgres_connection_pool(
+    url: &str,
+    pool_builder: Builder<ConnectionManager<PgConnection>>,
 ) -> Result<Pool<ConnectionManager<PgConnection>>, InternalError> {
     let connection_manager = ConnectionManager::<diesel::pg::PgConnection>::new(url);
-    let pool = Pool::builder().build(connection_manager).map_err(|err| {
+    let pool = pool_builder.build(connection_manager).map_err(|err| {
         InternalError::from_source_with_prefix(
             Box::new(err),
             "Failed to build connection pool".to_string(),
@@ -129,6 +162,57 @@ impl StoreFactory for PgStoreFactory {
         Box::new(crate::biome::DieselUserProfileStore::new(self.pool.clone()))
     }
 
+    #[cfg(feature = "biome-notifications")]
+    fn get_biome_notification_subscription_store(
+        &self,
+    ) -> Box<dyn crate::biome::NotificationSubscriptionStore> {
+        Box::new(crate::biome::DieselNotificationSubscriptionStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "announcements")]
+    fn get_announcement_store(&self) -> Box<dyn crate::announcement::store::AnnouncementStore> {
+        Box::new(crate::announcement::store::DieselAnnouncementStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "api-keys")]
+    fn get_api_key_store(&self) -> Box<dyn crate::api_key::store::ApiKeyStore> {
+        Box::new(crate::api_key::store::DieselApiKeyStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::audit::store::AuditStore> {
+        Box::new(crate::audit::store::DieselAuditStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    fn get_circuit_usage_store(&self) -> Box<dyn crate::circuit::usage::store::CircuitUsageStore> {
+        Box::new(crate::circuit::usage::store::DieselCircuitUsageStore::new(
+            self.pool.clone(),
+        ))
+    }
+
+    #[cfg(feature = "peer-blocklist")]
+    fn get_peer_blocklist_store(
+        &self,
+    ) -> Box<dyn crate::peer::blocklist::store::PeerBlocklistStore> {
+        Box::new(crate::peer::blocklist::store::DieselPeerBlocklistStore::new(self.pool.clone()))
+    }
+
+    #[cfg(feature = "peer-metadata")]
+    fn get_peer_metadata_store(&self) -> Box<dyn crate::peer::metadata::store::PeerMetadataStore> {
+        Box::new(crate::peer::metadata::store::DieselPeerMetadataStore::new(
+            self.pool.clone(),
+        ))
+    }
+
     #[cfg(feature = "node-id-store")]
     fn get_node_id_store(&self) -> Box<dyn crate::node_id::store::NodeIdStore> {
         Box::new(crate::node_id::store::diesel::DieselNodeIdStore::new(
@@ -142,4 +226,9 @@ impl StoreFactory for PgStoreFactory {
             self.pool.clone(),
         ))
     }
+
+    #[cfg(feature = "store-lock")]
+    fn get_lock_store(&self) -> Box<dyn crate::store::lock::LockStore> {
+        Box::new(crate::store::lock::DieselLockStore::new(self.pool.clone()))
+    }
 }
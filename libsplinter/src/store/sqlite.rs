@@ -17,7 +17,7 @@ use std::sync::{Arc, RwLock};
 
 use diesel::{
     connection::SimpleConnection,
-    r2d2::{ConnectionManager, CustomizeConnection, Pool},
+    r2d2::{Builder, ConnectionManager, CustomizeConnection, Pool},
     sqlite::SqliteConnection,
 };
 
@@ -26,6 +26,8 @@ use crate::migrations::{any_pending_sqlite_migrations, run_sqlite_migrations};
 #[cfg(feature = "authorization-handler-rbac")]
 use crate::rbac::store::{DieselRoleBasedAuthorizationStore, RoleBasedAuthorizationStore};
 
+#[cfg(feature = "store-pool-settings")]
+use super::ConnectionPoolSettings;
 use super::StoreFactory;
 
 /// Create a SQLite connection pool.
@@ -42,6 +44,39 @@ use super::StoreFactory;
 /// * The database requires any pending migrations
 pub fn create_sqlite_connection_pool(
     conn_str: &str,
+) -> Result<Pool<ConnectionManager<SqliteConnection>>, InternalError> {
+    build_sqlite_connection_pool(conn_str, Pool::builder())
+}
+
+/// Create a SQLite connection pool with the given sizes and timeouts. An in-memory database is
+/// always limited to a single connection.
+///
+/// # Arguments
+///
+/// * conn_str - a filename or ":memory:"
+/// * settings - the settings of the pool
+///
+/// # Errors
+///
+/// An [InternalError] is returned if
+/// * The settings are invalid
+/// * The file does not exist
+/// * The pool cannot be created
+/// * The database requires any pending migrations
+#[cfg(feature = "store-pool-settings")]
+pub fn create_sqlite_connection_pool_with_settings(
+    conn_str: &str,
+    settings: &ConnectionPoolSettings,
+) -> Result<Pool<ConnectionManager<SqliteConnection>>, InternalError> {
+    let pool_builder = settings
+        .apply(Pool::builder())
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    build_sqlite_connection_pool(conn_str, pool_builder)
+}
+
+fn build_sqlite_connection_pool(
+    conn_str: &str,
+    pool_builder: Builder<ConnectionManager<SqliteConnection>>,
 ) -> Result<Pool<ConnectionManager<SqliteConnection>>, InternalError> {
     if (conn_str != ":memory:") && !std::path::Path::new(&conn_str).exists() {
         return Err(InternalError::with_message(format!(
@@ -50,14 +85,14 @@ pub fn create_sqlite_connection_pool(
         )));
     }
     let connection_manager = ConnectionManager::<SqliteConnection>::new(conn_str);
-    let mut pool_builder = Pool::builder()
+    let mut pool_builder = pool_builder
         .connection_customizer(Box::new(ConnectionCustomizer::default()))
         .error_handler(Box::new(HandlePoolError));
     // A new database is created for each connection to the in-memory SQLite
     // implementation; to ensure that the resulting stores will operate on the same
     // database, only one connection is allowed.
     if conn_str == ":memory:" {
-        pool_builder = pool_builder.max_size(1);
+        pool_builder = pool_builder.max_size(1).min_idle(None);
     }
     let pool = pool_builder.build(connection_manager).map_err(|err| {
         InternalError::from_source_with_prefix(
@@ -180,6 +215,69 @@ impl StoreFactory for SqliteStoreFactory {
         )
     }
 
+    #[cfg(feature = "biome-notifications")]
+    fn get_biome_notification_subscription_store(
+        &self,
+    ) -> Box<dyn crate::biome::NotificationSubscriptionStore> {
+        Box::new(
+            crate::biome::DieselNotificationSubscriptionStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "announcements")]
+    fn get_announcement_store(&self) -> Box<dyn crate::announcement::store::AnnouncementStore> {
+        Box::new(
+            crate::announcement::store::DieselAnnouncementStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "api-keys")]
+    fn get_api_key_store(&self) -> Box<dyn crate::api_key::store::ApiKeyStore> {
+        Box::new(
+            crate::api_key::store::DieselApiKeyStore::new_with_write_exclusivity(self.pool.clone()),
+        )
+    }
+
+    #[cfg(feature = "audit")]
+    fn get_audit_store(&self) -> Box<dyn crate::audit::store::AuditStore> {
+        Box::new(
+            crate::audit::store::DieselAuditStore::new_with_write_exclusivity(self.pool.clone()),
+        )
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    fn get_circuit_usage_store(&self) -> Box<dyn crate::circuit::usage::store::CircuitUsageStore> {
+        Box::new(
+            crate::circuit::usage::store::DieselCircuitUsageStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "peer-blocklist")]
+    fn get_peer_blocklist_store(
+        &self,
+    ) -> Box<dyn crate::peer::blocklist::store::PeerBlocklistStore> {
+        Box::new(
+            crate::peer::blocklist::store::DieselPeerBlocklistStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
+    #[cfg(feature = "peer-metadata")]
+    fn get_peer_metadata_store(&self) -> Box<dyn crate::peer::metadata::store::PeerMetadataStore> {
+        Box::new(
+            crate::peer::metadata::store::DieselPeerMetadataStore::new_with_write_exclusivity(
+                self.pool.clone(),
+            ),
+        )
+    }
+
     #[cfg(feature = "node-id-store")]
     fn get_node_id_store(&self) -> Box<dyn crate::node_id::store::NodeIdStore> {
         Box::new(
@@ -197,6 +295,11 @@ impl StoreFactory for SqliteStoreFactory {
             ),
         )
     }
+
+    #[cfg(feature = "store-lock")]
+    fn get_lock_store(&self) -> Box<dyn crate::store::lock::LockStore> {
+        Box::new(crate::store::lock::DieselLockStore::new_with_write_exclusivity(self.pool.clone()))
+    }
 }
 
 #[derive(Default, Debug)]
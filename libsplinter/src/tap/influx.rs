@@ -16,20 +16,42 @@
 //! trait. InfluxRecorder enables using the metrics macros and sending the metrics data to a
 //! running Influx database.
 //!
+//! Metric points are not written as they are recorded. They are buffered and written to the
+//! database in batches, either when a batch is full or on an interval, as configured by
+//! [`InfluxBatchSettings`]. The number of points waiting to be written is bounded; points that are
+//! recorded while the buffer is full, or that are in a batch that could not be written, are
+//! dropped. Dropped points are counted, logged, and reported to the database as the
+//! `splinter.tap.influx.dropped_points` counter.
+//!
 //! Available if the `metrics` feature is enabled
 
 use std::collections::HashMap;
+#[cfg(feature = "subsystem-control")]
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use influxdb::Client;
 use influxdb::InfluxDbWriteable;
+use influxdb::WriteQuery;
 use metrics::{GaugeValue, Key, Label, Recorder, SharedString, Unit};
 use tokio_1::runtime::Runtime;
-use tokio_1::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_1::sync::mpsc::{channel, error::TrySendError, Sender};
 use tokio_1::task::JoinHandle;
+use tokio_1::time::interval;
 
 use crate::error::InternalError;
 use crate::threading::lifecycle::ShutdownHandle;
+#[cfg(feature = "subsystem-control")]
+use crate::threading::subsystem::Subsystem;
+
+const DEFAULT_BATCH_SIZE: usize = 500;
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_PENDING_POINTS: usize = 10_000;
+
+const DROPPED_POINTS_KEY: &str = "splinter.tap.influx.dropped_points";
 
 #[derive(InfluxDbWriteable)]
 struct Counter<'a> {
@@ -84,11 +106,219 @@ enum MetricRequest {
     Shutdown,
 }
 
+/// How an [`InfluxRecorder`] batches the metric points it writes to InfluxDB
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InfluxBatchSettings {
+    batch_size: usize,
+    flush_interval: Duration,
+    max_pending_points: usize,
+}
+
+impl InfluxBatchSettings {
+    /// Creates settings that write batches of up to 500 points at least every second, and keep at
+    /// most 10,000 points waiting to be batched.
+    pub fn new() -> Self {
+        Self {
+            batch_size: DEFAULT_BATCH_SIZE,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
+            max_pending_points: DEFAULT_MAX_PENDING_POINTS,
+        }
+    }
+
+    /// Sets the number of points that are written in a single request.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Sets how often a batch is written, even if it is not full.
+    pub fn with_flush_interval(mut self, flush_interval: Duration) -> Self {
+        self.flush_interval = flush_interval;
+        self
+    }
+
+    /// Sets the number of recorded points that may wait to be batched; points recorded beyond it
+    /// are dropped.
+    pub fn with_max_pending_points(mut self, max_pending_points: usize) -> Self {
+        self.max_pending_points = max_pending_points;
+        self
+    }
+
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    pub fn flush_interval(&self) -> Duration {
+        self.flush_interval
+    }
+
+    pub fn max_pending_points(&self) -> usize {
+        self.max_pending_points
+    }
+}
+
+impl Default for InfluxBatchSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The points waiting to be written in the next batch, along with the current values of the
+/// counters and gauges they were computed from
+struct MetricBatch {
+    counters: HashMap<Box<str>, CounterEntry>,
+    gauges: HashMap<Box<str>, GaugeEntry>,
+    queries: Vec<WriteQuery>,
+}
+
+impl MetricBatch {
+    fn new() -> Self {
+        Self {
+            counters: HashMap::new(),
+            gauges: HashMap::new(),
+            queries: Vec::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.queries.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.queries.is_empty()
+    }
+
+    /// Removes the points of the batch, keeping the current values of counters and gauges.
+    fn take(&mut self) -> Vec<WriteQuery> {
+        std::mem::take(&mut self.queries)
+    }
+
+    fn add_counter(&mut self, key: &str, value: u64, labels: Vec<Label>, time: DateTime<Utc>) {
+        let counter = {
+            if let Some(counter_entry) = self.counters.get_mut(key) {
+                counter_entry.value += value;
+                counter_entry.time = time;
+                Counter {
+                    key,
+                    value: counter_entry.value,
+                    time: counter_entry.time,
+                }
+            } else {
+                // Convert the Cow<'_, str> to a Box<str> to only create a pointer to the
+                // immutable str
+                self.counters
+                    .insert(Box::from(key), CounterEntry { value, time });
+
+                Counter { time, key, value }
+            }
+        };
+
+        self.push(counter.into_query(key), labels);
+    }
+
+    fn add_gauge(&mut self, key: &str, value: GaugeValue, labels: Vec<Label>, time: DateTime<Utc>) {
+        let gauge = {
+            if let Some(gauge_entry) = self.gauges.get_mut(key) {
+                match value {
+                    GaugeValue::Absolute(total) => gauge_entry.value = total,
+                    GaugeValue::Increment(amount) => gauge_entry.value += amount,
+                    GaugeValue::Decrement(amount) => gauge_entry.value -= amount,
+                }
+                gauge_entry.time = time;
+                Gauge {
+                    time: gauge_entry.time,
+                    key,
+                    value: gauge_entry.value,
+                }
+            } else {
+                let mut gauge_value = 0.0;
+                match value {
+                    GaugeValue::Absolute(total) => gauge_value = total,
+                    GaugeValue::Increment(amount) => gauge_value += amount,
+                    GaugeValue::Decrement(amount) => gauge_value -= amount,
+                }
+                self.gauges.insert(
+                    Box::from(key),
+                    GaugeEntry {
+                        value: gauge_value,
+                        time,
+                    },
+                );
+
+                Gauge {
+                    time,
+                    key,
+                    value: gauge_value,
+                }
+            }
+        };
+
+        self.push(gauge.into_query(key), labels);
+    }
+
+    fn add_histogram(&mut self, key: &str, value: f64, labels: Vec<Label>, time: DateTime<Utc>) {
+        let histogram = Histogram { time, key, value };
+        self.push(histogram.into_query(key), labels);
+    }
+
+    /// Adds the total number of dropped points to the batch; it is not counted as a counter, as
+    /// the total is tracked by the recorder.
+    fn add_dropped_points(&mut self, total: u64) {
+        let dropped = Counter {
+            time: Utc::now(),
+            key: DROPPED_POINTS_KEY,
+            value: total,
+        };
+        self.queries.push(dropped.into_query(DROPPED_POINTS_KEY));
+    }
+
+    fn push(&mut self, mut query: WriteQuery, labels: Vec<Label>) {
+        for label in labels {
+            query = query.add_tag(label.key(), label.value());
+        }
+        self.queries.push(query);
+    }
+}
+
+/// Writes the points of the batch to the database in a single request.
+///
+/// The total number of dropped points is added to the batch if it has changed since it was last
+/// reported. The points of a batch that cannot be written are dropped.
+async fn flush(client: &Client, batch: &mut MetricBatch, dropped: &AtomicU64, reported: &mut u64) {
+    let total_dropped = dropped.load(Ordering::Relaxed);
+    if total_dropped > *reported {
+        warn!(
+            "Dropped {} metric point(s) because they could not be written to InfluxDB in time",
+            total_dropped - *reported
+        );
+        batch.add_dropped_points(total_dropped);
+        *reported = total_dropped;
+    }
+
+    if batch.is_empty() {
+        return;
+    }
+
+    let queries = batch.take();
+    if let Err(err) = client.query(&queries).await {
+        error!(
+            "Unable to submit batch of {} influx points: {}",
+            queries.len(),
+            err
+        );
+        dropped.fetch_add(queries.len() as u64, Ordering::Relaxed);
+    }
+}
+
 /// Enables using the metrics macros and sending the metrics data to a running Influx database
 pub struct InfluxRecorder {
-    sender: UnboundedSender<MetricRequest>,
+    sender: Sender<MetricRequest>,
+    dropped: Arc<AtomicU64>,
     join_handle: JoinHandle<()>,
     rt: Runtime,
+    // Points are only recorded while enabled
+    #[cfg(feature = "subsystem-control")]
+    enabled: Arc<AtomicBool>,
 }
 
 impl InfluxRecorder {
@@ -97,137 +327,82 @@ impl InfluxRecorder {
         db_name: &str,
         username: &str,
         password: &str,
+        settings: InfluxBatchSettings,
     ) -> Result<Self, InternalError> {
-        let (sender, mut recv) = unbounded_channel();
+        if settings.batch_size == 0 || settings.max_pending_points == 0 {
+            return Err(InternalError::with_message(
+                "Metrics batch size and maximum pending points must be greater than 0".to_string(),
+            ));
+        }
+        if settings.flush_interval == Duration::from_secs(0) {
+            return Err(InternalError::with_message(
+                "Metrics flush interval must be greater than 0".to_string(),
+            ));
+        }
+
+        let (sender, mut recv) = channel(settings.max_pending_points);
+        let dropped = Arc::new(AtomicU64::new(0));
         let rt = Runtime::new().map_err(|_| {
             InternalError::with_message("Unable to start metrics runtime".to_string())
         })?;
 
         let client = Client::new(db_url, db_name).with_auth(username, password);
 
+        let task_dropped = dropped.clone();
         let join_handle = rt.spawn(async move {
-            let mut counters: HashMap<Box<str>, CounterEntry> = HashMap::new();
-            let mut gauges: HashMap<Box<str>, GaugeEntry> = HashMap::new();
+            let mut batch = MetricBatch::new();
+            let mut reported_dropped = 0;
+            let mut flush_interval = interval(settings.flush_interval);
             loop {
-                match recv.recv().await {
-                    Some(MetricRequest::Counter {
-                        key,
-                        value,
-                        labels,
-                        time,
-                    }) => {
-                        let counter = {
-                            if let Some(mut counter_entry) = counters.get_mut(&*key) {
-                                counter_entry.value += value;
-                                counter_entry.time = time;
-                                Counter {
-                                    key: &*key,
-                                    value: counter_entry.value,
-                                    time: counter_entry.time,
-                                }
-                            } else {
-                                let counter = Counter {
-                                    time,
-                                    key: &*key,
-                                    value,
-                                };
-                                // Convert the Cow<'_, str> to a Box<str> to only create a pointer
-                                // to the immutable str
-                                counters.insert(Box::from(&*key), CounterEntry { value, time });
-
-                                counter
-                            }
-                        };
-
-                        let mut query = counter.into_query(&*key);
-                        for label in labels {
-                            query = query.add_tag(label.key(), label.value());
-                        }
-                        if let Err(err) = client.query(&query).await {
-                            error!("Unable to submit influx query: {}", err)
-                        };
-                    }
-                    Some(MetricRequest::Gauge {
-                        key,
-                        value,
-                        labels,
-                        time,
-                    }) => {
-                        let gauge = {
-                            if let Some(mut gauge_entry) = gauges.get_mut(&*key) {
-                                match value {
-                                    GaugeValue::Absolute(total) => gauge_entry.value = total,
-                                    GaugeValue::Increment(amount) => gauge_entry.value += amount,
-                                    GaugeValue::Decrement(amount) => gauge_entry.value -= amount,
-                                }
-                                gauge_entry.time = time;
-                                Gauge {
-                                    time: gauge_entry.time,
-                                    key: &*key,
-                                    value: gauge_entry.value,
-                                }
-                            } else {
-                                let mut gauge_value = 0.0;
-                                match value {
-                                    GaugeValue::Absolute(total) => gauge_value = total,
-                                    GaugeValue::Increment(amount) => gauge_value += amount,
-                                    GaugeValue::Decrement(amount) => gauge_value -= amount,
-                                }
-                                gauges.insert(
-                                    Box::from(&*key),
-                                    GaugeEntry {
-                                        value: gauge_value,
-                                        time,
-                                    },
-                                );
-
-                                Gauge {
-                                    time,
-                                    key: &*key,
-                                    value: gauge_value,
-                                }
+                tokio_1::select! {
+                    request = recv.recv() => {
+                        match request {
+                            Some(MetricRequest::Counter {
+                                key,
+                                value,
+                                labels,
+                                time,
+                            }) => batch.add_counter(&key, value, labels, time),
+                            Some(MetricRequest::Gauge {
+                                key,
+                                value,
+                                labels,
+                                time,
+                            }) => batch.add_gauge(&key, value, labels, time),
+                            Some(MetricRequest::Histogram {
+                                key,
+                                value,
+                                labels,
+                                time,
+                            }) => batch.add_histogram(&key, value, labels, time),
+                            Some(MetricRequest::Shutdown) | None => {
+                                info!("Received MetricRequest::Shutdown");
+                                break;
                             }
-                        };
-                        let mut query = gauge.into_query(&*key);
-                        for label in labels {
-                            query = query.add_tag(label.key(), label.value());
                         }
-                        if let Err(err) = client.query(&query).await {
-                            error!("Unable to submit influx query: {}", err)
-                        };
-                    }
-                    Some(MetricRequest::Histogram {
-                        key,
-                        value,
-                        labels,
-                        time,
-                    }) => {
-                        let histogram = Histogram {
-                            time,
-                            key: &key,
-                            value,
-                        };
-                        let mut query = histogram.into_query(&*key);
-                        for label in labels {
-                            query = query.add_tag(label.key(), label.value());
+
+                        if batch.len() >= settings.batch_size {
+                            flush(&client, &mut batch, &task_dropped, &mut reported_dropped)
+                                .await;
                         }
-                        if let Err(err) = client.query(&query).await {
-                            error!("Unable to submit influx query: {}", err)
-                        };
                     }
-                    Some(MetricRequest::Shutdown) => {
-                        info!("Received MetricRequest::Shutdown");
-                        break;
+                    _ = flush_interval.tick() => {
+                        flush(&client, &mut batch, &task_dropped, &mut reported_dropped).await;
                     }
-                    _ => unimplemented!(),
                 }
             }
+
+            // Write the points that were recorded before shutdown
+            flush(&client, &mut batch, &task_dropped, &mut reported_dropped).await;
         });
 
         Ok(Self {
             sender,
+            dropped,
             join_handle,
             rt,
+            #[cfg(feature = "subsystem-control")]
+            enabled: Arc::new(AtomicBool::new(true)),
         })
     }
 
@@ -235,6 +410,8 @@ impl InfluxRecorder {
     /// InfluxDB instance. The record is then added to the metrics library as the recorder which
     /// enables sending the metrics data to the database.
     ///
+    /// Metric points are written in batches using the default [`InfluxBatchSettings`].
+    ///
     /// # Arguments
     ///
     /// * `db_url` - The URL to connect the InfluxDB database for metrics collection
@@ -247,15 +424,113 @@ impl InfluxRecorder {
         username: &str,
         password: &str,
     ) -> Result<(), InternalError> {
-        let recorder = Self::new(db_url, db_name, username, password)?;
+        Self::init_with_batch_settings(
+            db_url,
+            db_name,
+            username,
+            password,
+            InfluxBatchSettings::default(),
+        )
+    }
+
+    /// Initialize metric collection as [`InfluxRecorder::init`] does, writing metric points in
+    /// batches using the given settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_url` - The URL to connect the InfluxDB database for metrics collection
+    /// * `db_name` - The name of the InfluxDB database for metrics Collection.
+    /// * `username` - The username used for authorization with the InfluxDB.
+    /// * `password` - The password used for authorization with the InfluxDB.
+    /// * `settings` - How metric points are batched.
+    pub fn init_with_batch_settings(
+        db_url: &str,
+        db_name: &str,
+        username: &str,
+        password: &str,
+        settings: InfluxBatchSettings,
+    ) -> Result<(), InternalError> {
+        let recorder = Self::new(db_url, db_name, username, password, settings)?;
         metrics::set_boxed_recorder(Box::new(recorder))
             .map_err(|err| InternalError::from_source(Box::new(err)))
     }
+
+    /// Initialize metric collection as [`InfluxRecorder::init_with_batch_settings`] does,
+    /// returning a switch that stops and starts recording points.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_url` - The URL to connect the InfluxDB database for metrics collection
+    /// * `db_name` - The name of the InfluxDB database for metrics Collection.
+    /// * `username` - The username used for authorization with the InfluxDB.
+    /// * `password` - The password used for authorization with the InfluxDB.
+    /// * `settings` - How metric points are batched.
+    #[cfg(feature = "subsystem-control")]
+    pub fn init_with_switch(
+        db_url: &str,
+        db_name: &str,
+        username: &str,
+        password: &str,
+        settings: InfluxBatchSettings,
+    ) -> Result<InfluxRecorderSwitch, InternalError> {
+        let recorder = Self::new(db_url, db_name, username, password, settings)?;
+        let switch = InfluxRecorderSwitch {
+            enabled: recorder.enabled.clone(),
+        };
+        metrics::set_boxed_recorder(Box::new(recorder))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        Ok(switch)
+    }
+
+    /// Queues a metric point to be batched, dropping it if too many points are already waiting.
+    fn send(&self, request: MetricRequest, action: &str) {
+        #[cfg(feature = "subsystem-control")]
+        if !self.enabled.load(Ordering::Relaxed) {
+            return;
+        }
+
+        match self.sender.try_send(request) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+            Err(TrySendError::Closed(_)) => {
+                error!("Unable to {} metric, recorder has shut down", action)
+            }
+        }
+    }
+}
+
+/// Stops and starts the recording of metric points by an [`InfluxRecorder`], such as while the
+/// database is maintained. Points recorded while stopped are discarded; points that were already
+/// recorded are still written.
+#[cfg(feature = "subsystem-control")]
+#[derive(Clone)]
+pub struct InfluxRecorderSwitch {
+    enabled: Arc<AtomicBool>,
+}
+
+#[cfg(feature = "subsystem-control")]
+impl Subsystem for InfluxRecorderSwitch {
+    fn is_running(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    fn start(&mut self) -> Result<(), InternalError> {
+        self.enabled.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), InternalError> {
+        self.enabled.store(false, Ordering::Relaxed);
+        Ok(())
+    }
 }
 
 impl ShutdownHandle for InfluxRecorder {
     fn signal_shutdown(&mut self) {
-        if self.sender.send(MetricRequest::Shutdown).is_err() {
+        // Wait for room in the buffer, so the shutdown request is not dropped
+        if self.sender.blocking_send(MetricRequest::Shutdown).is_err() {
             error!("Unable to send shutdown message to InfluxRecorder");
         }
     }
@@ -270,62 +545,67 @@ impl ShutdownHandle for InfluxRecorder {
 impl Recorder for InfluxRecorder {
     fn increment_counter(&self, key: &Key, value: u64) {
         let (name, labels) = key.clone().into_parts();
-        if let Err(err) = self.sender.send(MetricRequest::Counter {
-            key: name,
-            labels,
-            value,
-            time: Utc::now(),
-        }) {
-            error!("Unable to to increment counter metric, {}", err);
-        };
+        self.send(
+            MetricRequest::Counter {
+                key: name,
+                labels,
+                value,
+                time: Utc::now(),
+            },
+            "increment counter",
+        );
     }
 
     fn update_gauge(&self, key: &Key, value: GaugeValue) {
         let (name, labels) = key.clone().into_parts();
-        if let Err(err) = self.sender.send(MetricRequest::Gauge {
-            key: name,
-            labels,
-            value,
-            time: Utc::now(),
-        }) {
-            error!("Unable to update gauge metric, {}", err);
-        };
+        self.send(
+            MetricRequest::Gauge {
+                key: name,
+                labels,
+                value,
+                time: Utc::now(),
+            },
+            "update gauge",
+        );
     }
 
     fn record_histogram(&self, key: &Key, value: f64) {
         let (name, labels) = key.clone().into_parts();
-        if let Err(err) = self.sender.send(MetricRequest::Histogram {
-            key: name,
-            labels,
-            value,
-            time: Utc::now(),
-        }) {
-            error!("Unable to record histogram metric, {}", err);
-        };
+        self.send(
+            MetricRequest::Histogram {
+                key: name,
+                labels,
+                value,
+                time: Utc::now(),
+            },
+            "record histogram",
+        );
     }
 
     fn register_counter(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
         let (name, labels) = key.clone().into_parts();
-        if let Err(err) = self.sender.send(MetricRequest::Counter {
-            key: name,
-            labels,
-            value: 0,
-            time: Utc::now(),
-        }) {
-            error!("Unable to to register counter metric, {}", err);
-        };
+        self.send(
+            MetricRequest::Counter {
+                key: name,
+                labels,
+                value: 0,
+                time: Utc::now(),
+            },
+            "register counter",
+        );
     }
 
     fn register_gauge(&self, key: &Key, _unit: Option<Unit>, _description: Option<&'static str>) {
         let (name, labels) = key.clone().into_parts();
-        if let Err(err) = self.sender.send(MetricRequest::Gauge {
-            key: name,
-            labels,
-            value: GaugeValue::Absolute(0.0),
-            time: Utc::now(),
-        }) {
-            error!("Unable to register gauge metric, {}", err);
-        };
+        self.send(
+            MetricRequest::Gauge {
+                key: name,
+                labels,
+                value: GaugeValue::Absolute(0.0),
+                time: Utc::now(),
+            },
+            "register gauge",
+        );
     }
 
     fn register_histogram(
@@ -335,13 +615,71 @@ impl Recorder for InfluxRecorder {
         _description: Option<&'static str>,
     ) {
         let (name, labels) = key.clone().into_parts();
-        if let Err(err) = self.sender.send(MetricRequest::Histogram {
-            key: name,
-            labels,
-            value: 0.0,
-            time: Utc::now(),
-        }) {
-            error!("Unable to register histogram metric, {}", err);
-        };
+        self.send(
+            MetricRequest::Histogram {
+                key: name,
+                labels,
+                value: 0.0,
+                time: Utc::now(),
+            },
+            "register histogram",
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the points of a batch are removed when it is taken, while the values of
+    /// counters and gauges carry over to the next batch.
+    #[test]
+    fn batch_carries_over_values() {
+        let mut batch = MetricBatch::new();
+        let time = Utc::now();
+
+        batch.add_counter("test.counter", 2, vec![], time);
+        batch.add_counter("test.counter", 3, vec![Label::new("node", "alpha")], time);
+        batch.add_gauge("test.gauge", GaugeValue::Absolute(10.0), vec![], time);
+        batch.add_histogram("test.histogram", 1.5, vec![], time);
+        assert_eq!(batch.len(), 4);
+
+        assert_eq!(batch.take().len(), 4);
+        assert!(batch.is_empty());
+
+        batch.add_counter("test.counter", 1, vec![], time);
+        batch.add_gauge("test.gauge", GaugeValue::Decrement(4.0), vec![], time);
+        batch.add_dropped_points(7);
+        assert_eq!(batch.len(), 3);
+
+        assert_eq!(
+            batch.counters.get("test.counter").map(|entry| entry.value),
+            Some(6)
+        );
+        assert_eq!(
+            batch.gauges.get("test.gauge").map(|entry| entry.value),
+            Some(6.0)
+        );
+        // The dropped points are not tracked as a counter of the batch
+        assert!(batch.counters.get(DROPPED_POINTS_KEY).is_none());
+    }
+
+    /// Verify that a recorder is not created with settings that would never write a batch.
+    #[test]
+    fn invalid_batch_settings() {
+        for settings in [
+            InfluxBatchSettings::new().with_batch_size(0),
+            InfluxBatchSettings::new().with_max_pending_points(0),
+            InfluxBatchSettings::new().with_flush_interval(Duration::from_secs(0)),
+        ] {
+            assert!(InfluxRecorder::new(
+                "http://localhost:8086",
+                "splinter",
+                "user",
+                "password",
+                settings
+            )
+            .is_err());
+        }
     }
 }
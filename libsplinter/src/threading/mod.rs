@@ -18,3 +18,7 @@ pub(crate) mod error;
 pub mod lifecycle;
 pub(crate) mod pacemaker;
 pub(crate) mod pool;
+#[cfg(feature = "subsystem-control")]
+pub mod subsystem;
+#[cfg(feature = "signature-verification-pool")]
+pub mod verification;
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use std::sync::{
-    atomic::{AtomicBool, Ordering},
+    atomic::{AtomicBool, AtomicU64, Ordering},
     mpsc::Sender,
     Arc,
 };
@@ -92,14 +92,18 @@ where
             .take()
             .ok_or_else(|| PacemakerStartError("No message factory function provided".into()))?;
 
+        let interval = Arc::new(AtomicU64::new(interval));
+        let interval_clone = interval.clone();
+
         let join_handle = thread::Builder::new()
             .name("Pacemaker".into())
             .spawn(move || {
                 let mut start = Instant::now();
                 let loop_duration = Duration::from_secs(1);
-                let pace_duration = Duration::from_secs(interval);
 
                 while running_clone.load(Ordering::SeqCst) {
+                    // The interval is read on each loop so that changes take effect immediately
+                    let pace_duration = Duration::from_secs(interval_clone.load(Ordering::SeqCst));
                     if start.elapsed() >= pace_duration {
                         start = Instant::now();
                         if let Err(err) = sender.send(new_message()) {
@@ -117,6 +121,7 @@ where
             .map_err(|err| PacemakerStartError(err.to_string()))?;
         Ok(Pacemaker {
             join_handle,
+            interval,
             shutdown_signaler: ShutdownSignaler { running },
         })
     }
@@ -127,6 +132,7 @@ where
 /// a notification that some action should take place.
 pub struct Pacemaker {
     join_handle: thread::JoinHandle<()>,
+    interval: Arc<AtomicU64>,
     shutdown_signaler: ShutdownSignaler,
 }
 
@@ -140,6 +146,11 @@ impl Pacemaker {
         PacemakerBuilder::new()
     }
 
+    /// Change the firing interval, in seconds, of the running pacemaker.
+    pub fn set_interval(&self, interval: u64) {
+        self.interval.store(interval, Ordering::SeqCst);
+    }
+
     pub fn shutdown_signaler(&self) -> ShutdownSignaler {
         self.shutdown_signaler.clone()
     }
@@ -0,0 +1,341 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Subsystems of a running node that can be stopped and started again individually, such as for
+//! maintenance, while the rest of the node keeps running.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use crate::error::InternalError;
+
+use super::lifecycle::ShutdownHandle;
+
+/// A component that can be stopped and started again while the node runs.
+pub trait Subsystem: Send {
+    /// Returns true if the subsystem is running
+    fn is_running(&self) -> bool;
+
+    /// Starts the subsystem; does nothing if it is already running.
+    fn start(&mut self) -> Result<(), InternalError>;
+
+    /// Stops the subsystem, returning once it has stopped; does nothing if it is not running.
+    fn stop(&mut self) -> Result<(), InternalError>;
+}
+
+/// A subsystem made of a component that is shut down by its [`ShutdownHandle`] when the subsystem
+/// is stopped, and created again by a factory when it is started.
+pub struct ShutdownHandleSubsystem<H, F> {
+    factory: F,
+    handle: Option<H>,
+}
+
+impl<H, F> ShutdownHandleSubsystem<H, F>
+where
+    H: ShutdownHandle + Send,
+    F: FnMut() -> Result<H, InternalError> + Send,
+{
+    /// Creates a subsystem that is not running.
+    ///
+    /// # Arguments
+    ///
+    /// * `factory` - Creates and starts the component, returning its shutdown handle
+    pub fn new(factory: F) -> Self {
+        Self {
+            factory,
+            handle: None,
+        }
+    }
+
+    /// Creates a subsystem for a component that is already running.
+    ///
+    /// # Arguments
+    ///
+    /// * `handle` - The shutdown handle of the running component
+    /// * `factory` - Creates and starts the component again after it is stopped
+    pub fn running(handle: H, factory: F) -> Self {
+        Self {
+            factory,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl<H, F> Subsystem for ShutdownHandleSubsystem<H, F>
+where
+    H: ShutdownHandle + Send,
+    F: FnMut() -> Result<H, InternalError> + Send,
+{
+    fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    fn start(&mut self) -> Result<(), InternalError> {
+        if self.handle.is_none() {
+            self.handle = Some((self.factory)()?);
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), InternalError> {
+        match self.handle.take() {
+            Some(mut handle) => {
+                handle.signal_shutdown();
+                handle.wait_for_shutdown()
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+/// The name of a subsystem and whether it is running.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SubsystemStatus {
+    name: String,
+    running: bool,
+}
+
+impl SubsystemStatus {
+    /// Returns the name of the subsystem
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns true if the subsystem is running
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+}
+
+/// Errors that may occur when a subsystem is stopped or started.
+#[derive(Debug)]
+pub enum SubsystemError {
+    /// No subsystem has the given name
+    NotFound(String),
+    Internal(InternalError),
+}
+
+impl Error for SubsystemError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SubsystemError::NotFound(_) => None,
+            SubsystemError::Internal(err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for SubsystemError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubsystemError::NotFound(name) => write!(f, "subsystem {} does not exist", name),
+            SubsystemError::Internal(err) => f.write_str(&err.to_string()),
+        }
+    }
+}
+
+impl From<InternalError> for SubsystemError {
+    fn from(err: InternalError) -> Self {
+        SubsystemError::Internal(err)
+    }
+}
+
+/// The subsystems of a node, by name.
+///
+/// The manager is cheap to clone; all clones manage the same subsystems. When the node shuts
+/// down, the manager's [`ShutdownHandle`] stops the subsystems that are still running.
+#[derive(Clone, Default)]
+pub struct SubsystemManager {
+    subsystems: Arc<Mutex<BTreeMap<String, Box<dyn Subsystem>>>>,
+}
+
+impl SubsystemManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a subsystem, replacing any subsystem with the same name.
+    pub fn add_subsystem(
+        &self,
+        name: &str,
+        subsystem: Box<dyn Subsystem>,
+    ) -> Result<(), InternalError> {
+        self.lock()?.insert(name.to_string(), subsystem);
+        Ok(())
+    }
+
+    /// Returns the status of every subsystem, sorted by name.
+    pub fn list_subsystems(&self) -> Result<Vec<SubsystemStatus>, InternalError> {
+        Ok(self
+            .lock()?
+            .iter()
+            .map(|(name, subsystem)| SubsystemStatus {
+                name: name.clone(),
+                running: subsystem.is_running(),
+            })
+            .collect())
+    }
+
+    /// Starts the subsystem with the given name.
+    pub fn start_subsystem(&self, name: &str) -> Result<(), SubsystemError> {
+        info!("Starting subsystem {}", name);
+        self.lock()?
+            .get_mut(name)
+            .ok_or_else(|| SubsystemError::NotFound(name.to_string()))?
+            .start()
+            .map_err(SubsystemError::from)
+    }
+
+    /// Stops the subsystem with the given name, returning once it has stopped.
+    pub fn stop_subsystem(&self, name: &str) -> Result<(), SubsystemError> {
+        info!("Stopping subsystem {}", name);
+        self.lock()?
+            .get_mut(name)
+            .ok_or_else(|| SubsystemError::NotFound(name.to_string()))?
+            .stop()
+            .map_err(SubsystemError::from)
+    }
+
+    fn lock(&self) -> Result<MutexGuard<BTreeMap<String, Box<dyn Subsystem>>>, InternalError> {
+        self.subsystems
+            .lock()
+            .map_err(|_| InternalError::with_message("Subsystem manager lock was poisoned".into()))
+    }
+}
+
+impl ShutdownHandle for SubsystemManager {
+    fn signal_shutdown(&mut self) {
+        // Subsystems are stopped one at a time in wait_for_shutdown
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        let mut errors = vec![];
+        for (name, subsystem) in self.lock()?.iter_mut() {
+            if let Err(err) = subsystem.stop() {
+                errors.push(format!("{}: {}", name, err));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(InternalError::with_message(format!(
+                "Unable to stop subsystems: {}",
+                errors.join(", ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Counts how many times it has been shut down
+    struct CountingHandle {
+        shutdowns: Arc<AtomicUsize>,
+    }
+
+    impl ShutdownHandle for CountingHandle {
+        fn signal_shutdown(&mut self) {}
+
+        fn wait_for_shutdown(self) -> Result<(), InternalError> {
+            self.shutdowns.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    }
+
+    /// Verify that a subsystem's component is shut down when it is stopped and created again when
+    /// it is started, and that stopping or starting it twice does nothing.
+    #[test]
+    fn test_shutdown_handle_subsystem() {
+        let starts = Arc::new(AtomicUsize::new(0));
+        let shutdowns = Arc::new(AtomicUsize::new(0));
+
+        let factory_starts = starts.clone();
+        let factory_shutdowns = shutdowns.clone();
+        let mut subsystem = ShutdownHandleSubsystem::new(move || {
+            factory_starts.fetch_add(1, Ordering::SeqCst);
+            Ok(CountingHandle {
+                shutdowns: factory_shutdowns.clone(),
+            })
+        });
+        assert!(!subsystem.is_running());
+
+        subsystem.start().expect("Failed to start");
+        subsystem.start().expect("Failed to start");
+        assert!(subsystem.is_running());
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+
+        subsystem.stop().expect("Failed to stop");
+        subsystem.stop().expect("Failed to stop");
+        assert!(!subsystem.is_running());
+        assert_eq!(shutdowns.load(Ordering::SeqCst), 1);
+
+        subsystem.start().expect("Failed to start");
+        assert_eq!(starts.load(Ordering::SeqCst), 2);
+    }
+
+    /// Verify that the manager stops and starts subsystems by name, rejects unknown names, and
+    /// stops the running subsystems when it is shut down.
+    #[test]
+    fn test_subsystem_manager() {
+        let shutdowns = Arc::new(AtomicUsize::new(0));
+        let factory_shutdowns = shutdowns.clone();
+        let subsystem = ShutdownHandleSubsystem::running(
+            CountingHandle {
+                shutdowns: shutdowns.clone(),
+            },
+            move || {
+                Ok(CountingHandle {
+                    shutdowns: factory_shutdowns.clone(),
+                })
+            },
+        );
+
+        let mut manager = SubsystemManager::new();
+        manager
+            .add_subsystem("registry-refresher", Box::new(subsystem))
+            .expect("Failed to add subsystem");
+
+        manager
+            .stop_subsystem("registry-refresher")
+            .expect("Failed to stop subsystem");
+        assert!(!manager
+            .list_subsystems()
+            .expect("Failed to list subsystems")[0]
+            .is_running());
+        assert!(matches!(
+            manager.stop_subsystem("metrics-recorder"),
+            Err(SubsystemError::NotFound(_))
+        ));
+
+        manager
+            .start_subsystem("registry-refresher")
+            .expect("Failed to start subsystem");
+        assert!(manager
+            .list_subsystems()
+            .expect("Failed to list subsystems")[0]
+            .is_running());
+
+        manager.signal_shutdown();
+        manager
+            .wait_for_shutdown()
+            .expect("Failed to shut down subsystems");
+        assert_eq!(shutdowns.load(Ordering::SeqCst), 2);
+    }
+}
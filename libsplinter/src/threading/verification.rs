@@ -0,0 +1,382 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A pool of threads that verify signatures in parallel.
+//!
+//! Verifying a signature is CPU bound, so a component that verifies many signatures at once, such
+//! as a scabbard service verifying the transactions of a batch, is limited to one core when it
+//! verifies them in turn. A `PooledVerifier` sends the signatures to the pool's threads instead,
+//! each of which has its own `Verifier`, and waits for all of the results.
+//!
+//! The results are cached by the pool, so a signature that is checked more than once, such as
+//! that of a batch that is verified when it is submitted and again when it is proposed, is only
+//! verified the first time.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crossbeam_channel::{Receiver, Sender};
+use cylinder::{PublicKey, Signature, VerificationError, Verifier, VerifierFactory};
+use sha2::{Digest, Sha256};
+
+use crate::error::InternalError;
+
+use super::lifecycle::ShutdownHandle;
+
+/// A signature to verify, along with the message it signs and the key that signed it.
+#[derive(Clone, Debug)]
+pub struct VerificationRequest {
+    message: Vec<u8>,
+    signature: Signature,
+    public_key: PublicKey,
+}
+
+impl VerificationRequest {
+    pub fn new(message: Vec<u8>, signature: Signature, public_key: PublicKey) -> Self {
+        Self {
+            message,
+            signature,
+            public_key,
+        }
+    }
+
+    /// Returns the key the result of this request is cached under.
+    fn cache_key(&self) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(&self.message);
+        hasher.update(self.signature.as_slice());
+        hasher.update(self.public_key.as_slice());
+        hasher.finalize().to_vec()
+    }
+}
+
+struct Job {
+    index: usize,
+    request: VerificationRequest,
+    reply: Sender<(usize, Result<bool, String>)>,
+}
+
+/// The results of recent verifications. When the cache is full, the oldest result is evicted.
+struct VerificationCache {
+    capacity: usize,
+    results: HashMap<Vec<u8>, bool>,
+    order: VecDeque<Vec<u8>>,
+}
+
+impl VerificationCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            results: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&self, key: &[u8]) -> Option<bool> {
+        self.results.get(key).copied()
+    }
+
+    fn insert(&mut self, key: Vec<u8>, result: bool) {
+        if self.capacity == 0 || self.results.contains_key(&key) {
+            return;
+        }
+        while self.order.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.results.remove(&oldest);
+            }
+        }
+        self.order.push_back(key.clone());
+        self.results.insert(key, result);
+    }
+}
+
+/// Verifies signatures on a pool of threads.
+pub struct VerificationPool {
+    sender: Arc<Mutex<Option<Sender<Job>>>>,
+    cache: Arc<Mutex<VerificationCache>>,
+    algorithm_name: String,
+    workers: Vec<thread::JoinHandle<()>>,
+}
+
+impl VerificationPool {
+    /// Starts a verification pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - The number of threads that verify signatures
+    /// * `cache_size` - The number of results that are cached; results are not cached if 0
+    /// * `verifier_factory` - Creates the verifier of each thread
+    pub fn start(
+        size: usize,
+        cache_size: usize,
+        verifier_factory: &dyn VerifierFactory,
+    ) -> Result<Self, InternalError> {
+        if size == 0 {
+            return Err(InternalError::with_message(
+                "A verification pool requires at least one thread".into(),
+            ));
+        }
+
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        let algorithm_name = verifier_factory.new_verifier().algorithm_name().to_string();
+
+        let workers = (0..size)
+            .map(|i| {
+                let receiver = receiver.clone();
+                let verifier = verifier_factory.new_verifier();
+                thread::Builder::new()
+                    .name(format!("SignatureVerification-{}", i))
+                    .spawn(move || run_worker(receiver, verifier))
+                    .map_err(|err| {
+                        InternalError::with_message(format!(
+                            "Unable to start verification thread: {}",
+                            err
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            sender: Arc::new(Mutex::new(Some(sender))),
+            cache: Arc::new(Mutex::new(VerificationCache::new(cache_size))),
+            algorithm_name,
+            workers,
+        })
+    }
+
+    /// Returns a verifier that verifies signatures on this pool. It stops working once the pool
+    /// is shut down.
+    pub fn verifier(&self) -> PooledVerifier {
+        PooledVerifier {
+            sender: Arc::clone(&self.sender),
+            cache: Arc::clone(&self.cache),
+            algorithm_name: self.algorithm_name.clone(),
+        }
+    }
+}
+
+impl ShutdownHandle for VerificationPool {
+    fn signal_shutdown(&mut self) {
+        // The workers stop once the sender is dropped and they have finished the jobs that were
+        // already sent
+        match self.sender.lock() {
+            Ok(mut sender) => {
+                sender.take();
+            }
+            Err(_) => error!("Verification pool sender lock poisoned"),
+        }
+    }
+
+    fn wait_for_shutdown(self) -> Result<(), InternalError> {
+        for worker in self.workers {
+            worker.join().map_err(|_| {
+                InternalError::with_message("Signature verification thread panicked".into())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+fn run_worker(receiver: Receiver<Job>, verifier: Box<dyn Verifier>) {
+    while let Ok(job) = receiver.recv() {
+        let result = verifier
+            .verify(
+                &job.request.message,
+                &job.request.signature,
+                &job.request.public_key,
+            )
+            .map_err(|err| err.to_string());
+        // The requester has given up waiting if the reply receiver was dropped
+        let _ = job.reply.send((job.index, result));
+    }
+}
+
+/// Verifies signatures on a `VerificationPool`.
+#[derive(Clone)]
+pub struct PooledVerifier {
+    sender: Arc<Mutex<Option<Sender<Job>>>>,
+    cache: Arc<Mutex<VerificationCache>>,
+    algorithm_name: String,
+}
+
+impl PooledVerifier {
+    /// Verifies the given signatures in parallel, and returns whether each is valid, in the order
+    /// of the requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if a signature could not be verified, or if the pool has been
+    /// shut down.
+    pub fn verify_all(
+        &self,
+        requests: Vec<VerificationRequest>,
+    ) -> Result<Vec<bool>, InternalError> {
+        let keys = requests
+            .iter()
+            .map(VerificationRequest::cache_key)
+            .collect::<Vec<_>>();
+
+        let mut results = {
+            let cache = self.cache.lock().map_err(|_| {
+                InternalError::with_message("Verification cache lock poisoned".into())
+            })?;
+            keys.iter().map(|key| cache.get(key)).collect::<Vec<_>>()
+        };
+
+        let sender = self
+            .sender
+            .lock()
+            .map_err(|_| {
+                InternalError::with_message("Verification pool sender lock poisoned".into())
+            })?
+            .clone()
+            .ok_or_else(|| {
+                InternalError::with_message("Verification pool has been shut down".into())
+            })?;
+        let (reply_sender, reply_receiver) = crossbeam_channel::unbounded();
+        let mut pending = 0;
+        for (index, request) in requests.into_iter().enumerate() {
+            if results[index].is_some() {
+                continue;
+            }
+            sender
+                .send(Job {
+                    index,
+                    request,
+                    reply: reply_sender.clone(),
+                })
+                .map_err(|_| {
+                    InternalError::with_message("Verification pool has been shut down".into())
+                })?;
+            pending += 1;
+        }
+        // Only the jobs hold reply senders now, so the replies end if the jobs are dropped
+        drop(sender);
+        drop(reply_sender);
+
+        let mut verified = Vec::with_capacity(pending);
+        for _ in 0..pending {
+            let (index, result) = reply_receiver.recv().map_err(|_| {
+                InternalError::with_message("Verification pool has been shut down".into())
+            })?;
+            let valid = result.map_err(|err| {
+                InternalError::with_message(format!("Unable to verify signature: {}", err))
+            })?;
+            results[index] = Some(valid);
+            verified.push(index);
+        }
+
+        // Cache the new results in the order of the requests
+        verified.sort_unstable();
+        let mut cache = self
+            .cache
+            .lock()
+            .map_err(|_| InternalError::with_message("Verification cache lock poisoned".into()))?;
+        for index in verified {
+            if let Some(valid) = results[index] {
+                cache.insert(keys[index].clone(), valid);
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .map(|result| result.unwrap_or(false))
+            .collect())
+    }
+}
+
+impl Verifier for PooledVerifier {
+    fn algorithm_name(&self) -> &str {
+        &self.algorithm_name
+    }
+
+    fn verify(
+        &self,
+        message: &[u8],
+        signature: &Signature,
+        public_key: &PublicKey,
+    ) -> Result<bool, VerificationError> {
+        self.verify_all(vec![VerificationRequest::new(
+            message.to_vec(),
+            signature.clone(),
+            public_key.clone(),
+        )])
+        .map(|results| results.into_iter().all(|valid| valid))
+        .map_err(|err| VerificationError::Internal(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+
+    /// Verify that a pool verifies each signature of a set, returning the results in order, and
+    /// caches the results.
+    #[test]
+    fn test_verify_all() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = signer.public_key().expect("Failed to get public key");
+
+        let requests = (0..10u8)
+            .map(|i| {
+                let message = vec![i; 32];
+                let signature = if i % 3 == 0 {
+                    // Sign a different message, so that the signature is invalid
+                    signer.sign(&[i + 1; 32])
+                } else {
+                    signer.sign(&message)
+                }
+                .expect("Failed to sign message");
+                VerificationRequest::new(message, signature, public_key.clone())
+            })
+            .collect::<Vec<_>>();
+
+        let mut pool =
+            VerificationPool::start(3, 5, &context).expect("Failed to start verification pool");
+        let verifier = pool.verifier();
+
+        let expected = (0..10).map(|i| i % 3 != 0).collect::<Vec<_>>();
+        assert_eq!(
+            verifier
+                .verify_all(requests.clone())
+                .expect("Failed to verify signatures"),
+            expected
+        );
+
+        // Only the most recent results are cached
+        {
+            let cache = verifier.cache.lock().expect("cache lock poisoned");
+            assert_eq!(cache.results.len(), 5);
+            assert_eq!(cache.get(&requests[9].cache_key()), Some(true));
+            assert_eq!(cache.get(&requests[0].cache_key()), None);
+        }
+
+        // Cached and uncached results are combined
+        assert_eq!(
+            verifier
+                .verify_all(requests)
+                .expect("Failed to verify signatures"),
+            expected
+        );
+
+        pool.signal_shutdown();
+        drop(verifier);
+        pool.wait_for_shutdown()
+            .expect("Failed to shut down verification pool");
+    }
+}
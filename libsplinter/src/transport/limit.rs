@@ -0,0 +1,323 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Limits on the number of concurrent inbound connections.
+//!
+//! A [`ConnectionLimiter`] wraps listeners so that they reject connections once the total number
+//! of open inbound connections, or the number of open connections from a single source IP
+//! address, reaches its [`ConnectionLimits`]. The limits are shared by all of the listeners
+//! wrapped by the same limiter. A connection stops counting towards the limits when it is
+//! dropped.
+//!
+//! The number of open inbound connections is reported with the
+//! `splinter.transport.inbound_connections` gauge, and each rejected connection increments the
+//! `splinter.transport.rejected_connections` counter.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+
+use mio::Evented;
+
+use super::{AcceptError, Connection, DisconnectError, Listener, RecvError, SendError};
+
+/// The maximum numbers of concurrent inbound connections accepted by a [`ConnectionLimiter`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConnectionLimits {
+    max_connections: Option<usize>,
+    max_connections_per_ip: Option<usize>,
+}
+
+impl ConnectionLimits {
+    /// Creates limits that accept any number of connections.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of open inbound connections.
+    pub fn with_max_connections(mut self, max_connections: usize) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    /// Sets the maximum number of open inbound connections from a single source IP address.
+    pub fn with_max_connections_per_ip(mut self, max_connections_per_ip: usize) -> Self {
+        self.max_connections_per_ip = Some(max_connections_per_ip);
+        self
+    }
+
+    pub fn max_connections(&self) -> Option<usize> {
+        self.max_connections
+    }
+
+    pub fn max_connections_per_ip(&self) -> Option<usize> {
+        self.max_connections_per_ip
+    }
+}
+
+/// The limit that caused a connection to be rejected
+enum ExceededLimit {
+    Total(usize),
+    PerIp(usize),
+}
+
+impl ExceededLimit {
+    /// Returns the label used for the limit in metrics
+    fn label(&self) -> &'static str {
+        match self {
+            ExceededLimit::Total(_) => "total",
+            ExceededLimit::PerIp(_) => "per_ip",
+        }
+    }
+}
+
+/// The open inbound connections, shared by all of a limiter's listeners and connections
+#[derive(Default)]
+struct ConnectionCounts {
+    total: usize,
+    per_ip: HashMap<String, usize>,
+}
+
+/// Applies [`ConnectionLimits`] to the connections accepted by one or more listeners
+#[derive(Clone)]
+pub struct ConnectionLimiter {
+    limits: ConnectionLimits,
+    counts: Arc<Mutex<ConnectionCounts>>,
+}
+
+impl ConnectionLimiter {
+    pub fn new(limits: ConnectionLimits) -> Self {
+        gauge!("splinter.transport.inbound_connections", 0.0);
+
+        Self {
+            limits,
+            counts: Arc::new(Mutex::new(ConnectionCounts::default())),
+        }
+    }
+
+    /// Wraps a listener so that the connections it accepts count towards the limits.
+    ///
+    /// A connection that would exceed the limits is disconnected and returned from `accept` as an
+    /// `AcceptError::ProtocolError`, so that the caller can log it and continue accepting
+    /// connections.
+    pub fn limit_listener(&self, listener: Box<dyn Listener>) -> Box<dyn Listener> {
+        Box::new(LimitedListener {
+            listener,
+            limiter: self.clone(),
+        })
+    }
+
+    /// Counts a connection from the given IP address, or returns the limit it would exceed.
+    fn reserve(&self, ip: &str) -> Result<ConnectionSlot, ExceededLimit> {
+        let mut counts = mutex_lock_unwrap!(self.counts);
+
+        if let Some(max_connections) = self.limits.max_connections {
+            if counts.total >= max_connections {
+                return Err(ExceededLimit::Total(max_connections));
+            }
+        }
+        let ip_count = counts.per_ip.get(ip).copied().unwrap_or(0);
+        if let Some(max_connections_per_ip) = self.limits.max_connections_per_ip {
+            if ip_count >= max_connections_per_ip {
+                return Err(ExceededLimit::PerIp(max_connections_per_ip));
+            }
+        }
+
+        counts.total += 1;
+        counts.per_ip.insert(ip.to_string(), ip_count + 1);
+        gauge!(
+            "splinter.transport.inbound_connections",
+            counts.total as f64
+        );
+
+        Ok(ConnectionSlot {
+            counts: self.counts.clone(),
+            ip: ip.to_string(),
+        })
+    }
+}
+
+/// A listener whose connections are counted by a [`ConnectionLimiter`]
+struct LimitedListener {
+    listener: Box<dyn Listener>,
+    limiter: ConnectionLimiter,
+}
+
+impl Listener for LimitedListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        let mut connection = self.listener.accept()?;
+        let remote_endpoint = connection.remote_endpoint();
+        let ip = source_ip(&remote_endpoint);
+
+        match self.limiter.reserve(&ip) {
+            Ok(slot) => Ok(Box::new(LimitedConnection {
+                connection,
+                _slot: slot,
+            })),
+            Err(limit) => {
+                counter!(
+                    "splinter.transport.rejected_connections",
+                    1,
+                    "limit" => limit.label()
+                );
+                if let Err(err) = connection.disconnect() {
+                    debug!(
+                        "Unable to disconnect rejected connection from {}: {}",
+                        remote_endpoint, err
+                    );
+                }
+                Err(AcceptError::ProtocolError(match limit {
+                    ExceededLimit::Total(max) => format!(
+                        "rejected connection from {}: the maximum of {} inbound connections \
+                         are open",
+                        remote_endpoint, max
+                    ),
+                    ExceededLimit::PerIp(max) => format!(
+                        "rejected connection from {}: the maximum of {} inbound connections \
+                         from {} are open",
+                        remote_endpoint, max, ip
+                    ),
+                }))
+            }
+        }
+    }
+
+    fn endpoint(&self) -> String {
+        self.listener.endpoint()
+    }
+}
+
+/// Removes a connection from the counts when it is dropped
+struct ConnectionSlot {
+    counts: Arc<Mutex<ConnectionCounts>>,
+    ip: String,
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        let mut counts = match self.counts.lock() {
+            Ok(counts) => counts,
+            // Don't panic while the connection is being dropped
+            Err(_) => return,
+        };
+
+        counts.total = counts.total.saturating_sub(1);
+        if let Some(ip_count) = counts.per_ip.get_mut(&self.ip) {
+            *ip_count -= 1;
+            if *ip_count == 0 {
+                counts.per_ip.remove(&self.ip);
+            }
+        }
+        gauge!(
+            "splinter.transport.inbound_connections",
+            counts.total as f64
+        );
+    }
+}
+
+/// A connection accepted by a [`LimitedListener`], which counts towards its limits until dropped
+struct LimitedConnection {
+    connection: Box<dyn Connection>,
+    _slot: ConnectionSlot,
+}
+
+impl Connection for LimitedConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        self.connection.send(message)
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        self.connection.recv()
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.connection.remote_endpoint()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.connection.local_endpoint()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        self.connection.disconnect()
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        self.connection.evented()
+    }
+}
+
+/// Returns the IP address of an endpoint such as `tcps://127.0.0.1:8044`; endpoints without an
+/// IP address, such as unix sockets, are returned without their protocol.
+fn source_ip(endpoint: &str) -> String {
+    let address = endpoint
+        .split_once("://")
+        .map(|(_, address)| address)
+        .unwrap_or(endpoint);
+
+    match address.parse::<SocketAddr>() {
+        Ok(socket_addr) => socket_addr.ip().to_string(),
+        Err(_) => address.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::transport::socket::TcpTransport;
+    use crate::transport::Transport;
+
+    /// Verify that the IP address is extracted from IPv4, IPv6 and non-IP endpoints.
+    #[test]
+    fn test_source_ip() {
+        assert_eq!(source_ip("tcp://127.0.0.1:8044"), "127.0.0.1");
+        assert_eq!(source_ip("tcps://[::1]:8044"), "::1");
+        assert_eq!(source_ip("unix:///tmp/splinter.sock"), "/tmp/splinter.sock");
+    }
+
+    /// Verify that connections beyond the per-IP limit are rejected, and that dropping an
+    /// accepted connection allows a new connection to be accepted.
+    #[test]
+    fn test_per_ip_limit() {
+        let mut transport = TcpTransport::default();
+        let limiter = ConnectionLimiter::new(
+            ConnectionLimits::new()
+                .with_max_connections(5)
+                .with_max_connections_per_ip(1),
+        );
+        let mut listener = limiter.limit_listener(
+            transport
+                .listen("tcp://127.0.0.1:0")
+                .expect("Failed to listen"),
+        );
+        let endpoint = listener.endpoint();
+
+        let _client1 = transport.connect(&endpoint).expect("Failed to connect");
+        let accepted = listener.accept().expect("Failed to accept connection");
+
+        let _client2 = transport.connect(&endpoint).expect("Failed to connect");
+        match listener.accept() {
+            Err(AcceptError::ProtocolError(_)) => (),
+            res => panic!(
+                "Expected the connection to be rejected, got {:?}",
+                res.map(|connection| connection.remote_endpoint())
+            ),
+        }
+
+        drop(accepted);
+        let _client3 = transport.connect(&endpoint).expect("Failed to connect");
+        assert!(listener.accept().is_ok());
+    }
+}
@@ -37,6 +37,8 @@
 
 mod error;
 pub mod inproc;
+#[cfg(feature = "connection-limits")]
+pub mod limit;
 pub(crate) mod matrix;
 pub mod multi;
 #[deprecated(since = "0.3.14", note = "please use splinter::transport::socket")]
@@ -12,14 +12,61 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod error;
 mod frame;
 mod tcp;
+#[cfg(feature = "tls-openssl")]
 mod tls;
+#[cfg(feature = "tls-rustls")]
+mod tls_rustls;
+#[cfg(all(unix, feature = "unix-transport"))]
+mod unix;
 
+#[cfg(any(feature = "tls-openssl", feature = "tls-rustls"))]
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+#[cfg(any(feature = "tls-openssl", feature = "tls-rustls"))]
+use url::{ParseError, Url};
+
+#[cfg(any(feature = "tls-openssl", feature = "tls-rustls"))]
+use crate::transport::ConnectError;
+
+pub use error::TlsInitError;
 pub use tcp::TcpTransport;
-pub use tls::{TlsConnection, TlsInitError, TlsTransport};
+#[cfg(feature = "tls-openssl")]
+pub use tls::{TlsConnection, TlsTransport};
+#[cfg(feature = "tls-rustls")]
+pub use tls_rustls::{RustlsConnection, RustlsTransport};
+#[cfg(all(unix, feature = "unix-transport"))]
+pub use unix::{validate_unix_endpoint, UnixTransport};
+
+/// Returns the name used to verify the certificate of the server at the given endpoint; IP
+/// addresses are verified as `localhost`.
+#[cfg(any(feature = "tls-openssl", feature = "tls-rustls"))]
+fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
+    let mut address = String::from("tcp://");
+    address.push_str(endpoint);
+    let url = Url::parse(&address)?;
+    let dns_name = match url.domain() {
+        Some(d) if d.parse::<Ipv4Addr>().is_ok() => "localhost",
+        Some(d) if d.parse::<Ipv6Addr>().is_ok() => "localhost",
+        Some(d) => d,
+        None => "localhost",
+    };
+    Ok(String::from(dns_name))
+}
+
+#[cfg(any(feature = "tls-openssl", feature = "tls-rustls"))]
+impl From<ParseError> for ConnectError {
+    fn from(parse_error: ParseError) -> Self {
+        ConnectError::ParseError(format!("Parse Error: {:?}", parse_error.to_string()))
+    }
+}
 
 #[cfg(test)]
 pub mod tests {
+    #[cfg(feature = "tls-openssl")]
     pub use super::tls::tests::create_test_tls_transport;
+    #[cfg(all(feature = "tls-rustls", not(feature = "tls-openssl")))]
+    pub use super::tls_rustls::tests::create_test_rustls_transport as create_test_tls_transport;
 }
@@ -18,21 +18,21 @@ use openssl::ssl::{
     Error as OpensslError, HandshakeError, SslAcceptor, SslConnector, SslFiletype, SslMethod,
     SslStream, SslVerifyMode,
 };
-use url::{ParseError, Url};
 
-use std::error::Error;
-use std::fmt;
 use std::io;
-use std::net::{Ipv4Addr, Ipv6Addr, TcpListener, TcpStream};
+use std::net::{TcpListener, TcpStream};
 use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
 
+#[cfg(feature = "tls-peer-ca")]
+use crate::transport::tls::PeerTrustAnchor;
 use crate::transport::{
     AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
     SendError, Transport,
 };
 
 use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+use super::{endpoint_to_dns_name, TlsInitError};
 
 /// tls:// is deprecated, tcps:// should be used instead
 const DEPRECATED_PROTOCOL_PREFIX: &str = "tls://";
@@ -41,6 +41,8 @@ const PROTOCOL_PREFIX: &str = "tcps://";
 pub struct TlsTransport {
     connector: SslConnector,
     acceptor: SslAcceptor,
+    #[cfg(feature = "tls-peer-ca")]
+    peer_connectors: Vec<(PeerTrustAnchor, SslConnector)>,
 }
 
 impl TlsTransport {
@@ -51,57 +53,126 @@ impl TlsTransport {
         server_key: String,
         server_cert: String,
     ) -> Result<Self, TlsInitError> {
-        let client_cert_path = Path::new(&client_cert);
-        let client_key_path = Path::new(&client_key);
-        let server_cert_path = Path::new(&server_cert);
-        let server_key_path = Path::new(&server_key);
-
-        // Build TLS Connector
-        let mut connector = SslConnector::builder(SslMethod::tls())?;
-        connector.set_private_key_file(&client_key_path, SslFiletype::PEM)?;
-        connector.set_certificate_chain_file(client_cert_path)?;
-        connector.check_private_key()?;
-
-        // Build TLS Acceptor
-        let mut acceptor = SslAcceptor::mozilla_modern(SslMethod::tls())?;
-        acceptor.set_private_key_file(server_key_path, SslFiletype::PEM)?;
-        acceptor.set_certificate_chain_file(&server_cert_path)?;
-        acceptor.check_private_key()?;
-
-        // if ca_cert is provided set as accept cert, otherwise set verify to none
-        let (acceptor, connector) = {
-            if let Some(ca_cert) = ca_cert {
-                let ca_cert_path = Path::new(&ca_cert);
-                acceptor.set_ca_file(ca_cert_path)?;
-                connector.set_ca_file(ca_cert_path)?;
-                connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-                acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
-            } else {
-                connector.set_verify(SslVerifyMode::NONE);
-                acceptor.set_verify(SslVerifyMode::NONE);
-            }
+        let connector = build_connector(&client_key, &client_cert, ca_cert.as_deref())?;
+        let acceptor = build_acceptor(&server_key, &server_cert, ca_cert.as_deref(), &[])?;
 
-            (acceptor.build(), connector.build())
-        };
+        Ok(TlsTransport {
+            connector,
+            acceptor,
+            #[cfg(feature = "tls-peer-ca")]
+            peer_connectors: vec![],
+        })
+    }
+
+    /// Creates a TLS transport that verifies the peers whose endpoints match one of the given
+    /// trust anchors using that anchor's CA certificates, instead of `ca_cert`.
+    ///
+    /// Outgoing connections use the first trust anchor whose pattern matches the endpoint, or
+    /// `ca_cert` if none match. Incoming connections are accepted from a peer whose certificate
+    /// is signed by `ca_cert` or by any of the trust anchors' CA certificates, as the remote
+    /// endpoint is not known until after the connection has been authorized.
+    #[cfg(feature = "tls-peer-ca")]
+    pub fn new_with_peer_trust_anchors(
+        ca_cert: Option<String>,
+        client_key: String,
+        client_cert: String,
+        server_key: String,
+        server_cert: String,
+        peer_trust_anchors: Vec<PeerTrustAnchor>,
+    ) -> Result<Self, TlsInitError> {
+        let connector = build_connector(&client_key, &client_cert, ca_cert.as_deref())?;
+
+        let peer_ca_certs = peer_trust_anchors
+            .iter()
+            .map(PeerTrustAnchor::ca_certs_file)
+            .collect::<Vec<_>>();
+        let acceptor = build_acceptor(
+            &server_key,
+            &server_cert,
+            ca_cert.as_deref(),
+            &peer_ca_certs,
+        )?;
+
+        let peer_connectors = peer_trust_anchors
+            .into_iter()
+            .map(|anchor| {
+                let connector =
+                    build_connector(&client_key, &client_cert, Some(anchor.ca_certs_file()))?;
+                Ok((anchor, connector))
+            })
+            .collect::<Result<_, TlsInitError>>()?;
 
         Ok(TlsTransport {
             connector,
             acceptor,
+            peer_connectors,
         })
     }
+
+    /// Returns the connector whose CA certificates are used to verify the peer at the given
+    /// address.
+    #[cfg(feature = "tls-peer-ca")]
+    fn connector_for(&self, address: &str) -> &SslConnector {
+        self.peer_connectors
+            .iter()
+            .find(|(anchor, _)| anchor.matches(address))
+            .map(|(_, connector)| connector)
+            .unwrap_or(&self.connector)
+    }
+
+    #[cfg(not(feature = "tls-peer-ca"))]
+    fn connector_for(&self, _address: &str) -> &SslConnector {
+        &self.connector
+    }
+}
+
+/// Builds a TLS connector; if `ca_cert` is provided, the server's certificate must be signed by
+/// one of its CA certificates, otherwise the server is not verified.
+fn build_connector(
+    client_key: &str,
+    client_cert: &str,
+    ca_cert: Option<&str>,
+) -> Result<SslConnector, TlsInitError> {
+    let mut connector = SslConnector::builder(SslMethod::tls())?;
+    connector.set_private_key_file(Path::new(client_key), SslFiletype::PEM)?;
+    connector.set_certificate_chain_file(Path::new(client_cert))?;
+    connector.check_private_key()?;
+
+    if let Some(ca_cert) = ca_cert {
+        connector.set_ca_file(Path::new(ca_cert))?;
+        connector.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    } else {
+        connector.set_verify(SslVerifyMode::NONE);
+    }
+
+    Ok(connector.build())
 }
 
-fn endpoint_to_dns_name(endpoint: &str) -> Result<String, ParseError> {
-    let mut address = String::from("tcp://");
-    address.push_str(endpoint);
-    let url = Url::parse(&address)?;
-    let dns_name = match url.domain() {
-        Some(d) if d.parse::<Ipv4Addr>().is_ok() => "localhost",
-        Some(d) if d.parse::<Ipv6Addr>().is_ok() => "localhost",
-        Some(d) => d,
-        None => "localhost",
-    };
-    Ok(String::from(dns_name))
+/// Builds a TLS acceptor; if `ca_cert` is provided, the client's certificate must be signed by
+/// one of its CA certificates or one of the `extra_ca_certs`, otherwise the client is not
+/// verified.
+fn build_acceptor(
+    server_key: &str,
+    server_cert: &str,
+    ca_cert: Option<&str>,
+    extra_ca_certs: &[&str],
+) -> Result<SslAcceptor, TlsInitError> {
+    let mut acceptor = SslAcceptor::mozilla_modern(SslMethod::tls())?;
+    acceptor.set_private_key_file(Path::new(server_key), SslFiletype::PEM)?;
+    acceptor.set_certificate_chain_file(Path::new(server_cert))?;
+    acceptor.check_private_key()?;
+
+    if let Some(ca_cert) = ca_cert {
+        acceptor.set_ca_file(Path::new(ca_cert))?;
+        for extra_ca_cert in extra_ca_certs {
+            acceptor.set_ca_file(Path::new(extra_ca_cert))?;
+        }
+        acceptor.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+    } else {
+        acceptor.set_verify(SslVerifyMode::NONE);
+    }
+
+    Ok(acceptor.build())
 }
 
 impl Transport for TlsTransport {
@@ -130,7 +201,7 @@ impl Transport for TlsTransport {
         let dns_name = endpoint_to_dns_name(address)?;
 
         let stream = TcpStream::connect(address)?;
-        let mut tls_stream = self.connector.connect(&dns_name, stream)?;
+        let mut tls_stream = self.connector_for(address).connect(&dns_name, stream)?;
 
         let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
             .negotiate(&mut tls_stream)
@@ -297,21 +368,6 @@ impl Evented for TlsConnection {
     }
 }
 
-#[derive(Debug)]
-pub enum TlsInitError {
-    ProtocolError(String),
-}
-
-impl Error for TlsInitError {}
-
-impl fmt::Display for TlsInitError {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self {
-            TlsInitError::ProtocolError(msg) => write!(f, "unable to initialize TLS: {}", msg),
-        }
-    }
-}
-
 impl From<ErrorStack> for TlsInitError {
     fn from(error: ErrorStack) -> Self {
         TlsInitError::ProtocolError(format!("Openssl Error: {}", error))
@@ -330,12 +386,6 @@ impl From<HandshakeError<TcpStream>> for ConnectError {
     }
 }
 
-impl From<ParseError> for ConnectError {
-    fn from(parse_error: ParseError) -> Self {
-        ConnectError::ParseError(format!("Parse Error: {:?}", parse_error.to_string()))
-    }
-}
-
 impl From<OpensslError> for DisconnectError {
     fn from(openssl_error: OpensslError) -> Self {
         DisconnectError::ProtocolError(format!("Openssl Err: {}", openssl_error))
@@ -425,6 +475,82 @@ pub(crate) mod tests {
         .unwrap()
     }
 
+    /// Create a TLS transport whose own certificates are signed by one CA, while the default CA
+    /// file contains a different CA. If `endpoint_pattern` is provided, it is configured as a peer
+    /// trust anchor for the CA that signed the certificates.
+    #[cfg(feature = "tls-peer-ca")]
+    fn create_test_peer_ca_transport(endpoint_pattern: Option<&str>) -> TlsTransport {
+        let (peer_ca_key, peer_ca_cert) = make_ca_cert();
+        let (_, default_ca_cert) = make_ca_cert();
+
+        let temp_dir = Builder::new()
+            .prefix("tls-transport-peer-ca-test")
+            .tempdir()
+            .unwrap();
+        let temp_dir_path = temp_dir.path();
+
+        let default_ca_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "default_ca.cert",
+            &default_ca_cert.to_pem().unwrap(),
+        );
+        let peer_ca_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "peer_ca.cert",
+            &peer_ca_cert.to_pem().unwrap(),
+        );
+
+        let (key, cert) = make_ca_signed_cert(&peer_ca_cert, &peer_ca_key);
+        let cert_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "node.cert",
+            &cert.to_pem().unwrap(),
+        );
+        let key_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "node.key",
+            &key.private_key_to_pem_pkcs8().unwrap(),
+        );
+
+        let peer_trust_anchors = endpoint_pattern
+            .map(|pattern| vec![PeerTrustAnchor::new(pattern, peer_ca_file).unwrap()])
+            .unwrap_or_default();
+
+        TlsTransport::new_with_peer_trust_anchors(
+            Some(default_ca_file),
+            key_file.clone(),
+            cert_file.clone(),
+            key_file,
+            cert_file,
+            peer_trust_anchors,
+        )
+        .unwrap()
+    }
+
+    /// Verify that a connection to an endpoint matching a peer trust anchor is verified with the
+    /// anchor's CA certificate, rather than the default CA file.
+    #[cfg(feature = "tls-peer-ca")]
+    #[test]
+    fn test_transport_peer_trust_anchor() {
+        let transport = create_test_peer_ca_transport(Some("127.0.0.1:*"));
+        tests::test_transport(transport, "127.0.0.1:0");
+    }
+
+    /// Verify that a connection to an endpoint that does not match any peer trust anchor is
+    /// verified with the default CA file, and fails if the peer's certificate is not signed by it.
+    #[cfg(feature = "tls-peer-ca")]
+    #[test]
+    fn test_transport_peer_trust_anchor_not_matched() {
+        let mut transport = create_test_peer_ca_transport(Some("10.0.1.*:*"));
+        let mut listener = transport.listen("127.0.0.1:0").unwrap();
+        let endpoint = listener.endpoint();
+
+        let handle = std::thread::spawn(move || listener.accept().is_err());
+
+        assert!(transport.connect(&endpoint).is_err());
+        assert!(handle.join().unwrap());
+    }
+
     #[test]
     fn test_transport() {
         let transport = create_test_tls_transport(true);
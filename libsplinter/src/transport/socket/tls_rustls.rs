@@ -0,0 +1,563 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A TLS transport implemented with rustls, which does not require OpenSSL.
+
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use mio::{unix::EventedFd, Evented, Poll, PollOpt, Ready, Token};
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{
+    Certificate, ClientConfig, ClientConnection, PrivateKey, RootCertStore, ServerConfig,
+    ServerConnection, ServerName, StreamOwned,
+};
+
+use crate::transport::{
+    AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
+    SendError, Transport,
+};
+
+use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+use super::{endpoint_to_dns_name, TlsInitError};
+
+/// tls:// is deprecated, tcps:// should be used instead
+const DEPRECATED_PROTOCOL_PREFIX: &str = "tls://";
+const PROTOCOL_PREFIX: &str = "tcps://";
+
+/// A TLS transport that uses rustls instead of OpenSSL.
+///
+/// The transport accepts the same endpoints and certificate files as the OpenSSL-based
+/// `TlsTransport`, and the two may connect to each other.
+pub struct RustlsTransport {
+    client_config: Arc<ClientConfig>,
+    server_config: Arc<ServerConfig>,
+}
+
+impl RustlsTransport {
+    /// Creates a new transport from PEM-encoded keys and certificates.
+    ///
+    /// If `ca_cert` is provided, the certificates of both servers and clients must be signed by
+    /// one of its CA certificates; otherwise, peers are not verified.
+    pub fn new(
+        ca_cert: Option<String>,
+        client_key: String,
+        client_cert: String,
+        server_key: String,
+        server_cert: String,
+    ) -> Result<Self, TlsInitError> {
+        let root_store = ca_cert.as_deref().map(load_root_store).transpose()?;
+
+        let client_config = build_client_config(&client_key, &client_cert, root_store.clone())?;
+        let server_config = build_server_config(&server_key, &server_cert, root_store)?;
+
+        Ok(RustlsTransport {
+            client_config: Arc::new(client_config),
+            server_config: Arc::new(server_config),
+        })
+    }
+}
+
+fn build_client_config(
+    client_key: &str,
+    client_cert: &str,
+    root_store: Option<RootCertStore>,
+) -> Result<ClientConfig, TlsInitError> {
+    let builder = ClientConfig::builder().with_safe_defaults();
+    let builder = match root_store {
+        Some(root_store) => builder.with_root_certificates(root_store),
+        None => builder.with_custom_certificate_verifier(Arc::new(NoServerVerification)),
+    };
+
+    Ok(builder.with_single_cert(load_certs(client_cert)?, load_private_key(client_key)?)?)
+}
+
+fn build_server_config(
+    server_key: &str,
+    server_cert: &str,
+    root_store: Option<RootCertStore>,
+) -> Result<ServerConfig, TlsInitError> {
+    let builder = ServerConfig::builder().with_safe_defaults();
+    let builder = match root_store {
+        Some(root_store) => {
+            builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(root_store))
+        }
+        None => builder.with_no_client_auth(),
+    };
+
+    Ok(builder.with_single_cert(load_certs(server_cert)?, load_private_key(server_key)?)?)
+}
+
+fn open_pem_file(path: &str) -> Result<BufReader<File>, TlsInitError> {
+    File::open(path)
+        .map(BufReader::new)
+        .map_err(|err| TlsInitError::ProtocolError(format!("Unable to open {}: {}", path, err)))
+}
+
+fn load_certs(path: &str) -> Result<Vec<Certificate>, TlsInitError> {
+    let certs = rustls_pemfile::certs(&mut open_pem_file(path)?).map_err(|err| {
+        TlsInitError::ProtocolError(format!(
+            "Unable to read certificates from {}: {}",
+            path, err
+        ))
+    })?;
+
+    if certs.is_empty() {
+        return Err(TlsInitError::ProtocolError(format!(
+            "No certificates found in {}",
+            path
+        )));
+    }
+
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// Loads the first RSA, PKCS#8 or EC private key in the file.
+fn load_private_key(path: &str) -> Result<PrivateKey, TlsInitError> {
+    let mut reader = open_pem_file(path)?;
+    loop {
+        let item = rustls_pemfile::read_one(&mut reader).map_err(|err| {
+            TlsInitError::ProtocolError(format!(
+                "Unable to read private key from {}: {}",
+                path, err
+            ))
+        })?;
+        match item {
+            Some(rustls_pemfile::Item::RSAKey(key))
+            | Some(rustls_pemfile::Item::PKCS8Key(key))
+            | Some(rustls_pemfile::Item::ECKey(key)) => return Ok(PrivateKey(key)),
+            Some(_) => continue,
+            None => {
+                return Err(TlsInitError::ProtocolError(format!(
+                    "No private key found in {}",
+                    path
+                )))
+            }
+        }
+    }
+}
+
+fn load_root_store(path: &str) -> Result<RootCertStore, TlsInitError> {
+    let mut root_store = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        root_store.add(&cert).map_err(|err| {
+            TlsInitError::ProtocolError(format!("Invalid CA certificate in {}: {}", path, err))
+        })?;
+    }
+    Ok(root_store)
+}
+
+/// Accepts any server certificate, as the OpenSSL transport does when no CA certificates are
+/// provided.
+struct NoServerVerification;
+
+impl ServerCertVerifier for NoServerVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+impl Transport for RustlsTransport {
+    fn accepts(&self, address: &str) -> bool {
+        address.starts_with(PROTOCOL_PREFIX)
+            || address.starts_with(DEPRECATED_PROTOCOL_PREFIX)
+            || !address.contains("://")
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        if !self.accepts(endpoint) {
+            return Err(ConnectError::ProtocolError(format!(
+                "Invalid protocol \"{}\"",
+                endpoint
+            )));
+        }
+
+        let address = if let Some(address) = endpoint.strip_prefix(PROTOCOL_PREFIX) {
+            address
+        } else if let Some(address) = endpoint.strip_prefix(DEPRECATED_PROTOCOL_PREFIX) {
+            address
+        } else {
+            endpoint
+        };
+
+        let dns_name = endpoint_to_dns_name(address)?;
+        let server_name = ServerName::try_from(dns_name.as_str())
+            .map_err(|_| ConnectError::ParseError(format!("Invalid server name: {}", dns_name)))?;
+
+        let mut stream = TcpStream::connect(address)?;
+        let mut connection = ClientConnection::new(self.client_config.clone(), server_name)
+            .map_err(|err| ConnectError::ProtocolError(format!("TLS Err: {}", err)))?;
+        while connection.is_handshaking() {
+            connection.complete_io(&mut stream).map_err(|err| {
+                ConnectError::ProtocolError(format!("TLS Handshake Err: {}", err))
+            })?;
+        }
+        let mut tls_stream = RustlsStream::Client(StreamOwned::new(connection, stream));
+
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+            .negotiate(&mut tls_stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => ConnectError::ProtocolError(
+                    "Unable to connect; remote version is not with in range".into(),
+                ),
+                FrameError::IoError(err) => ConnectError::from(err),
+                e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
+            })?;
+
+        tls_stream.sock().set_nonblocking(true)?;
+        Ok(Box::new(RustlsConnection {
+            frame_version,
+            stream: tls_stream,
+        }))
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        if !self.accepts(bind) {
+            return Err(ListenError::ProtocolError(format!(
+                "Invalid protocol \"{}\"",
+                bind
+            )));
+        }
+
+        let address = if let Some(address) = bind.strip_prefix(PROTOCOL_PREFIX) {
+            address
+        } else if let Some(address) = bind.strip_prefix(DEPRECATED_PROTOCOL_PREFIX) {
+            address
+        } else {
+            bind
+        };
+
+        Ok(Box::new(RustlsListener {
+            listener: TcpListener::bind(address).map_err(|err| {
+                ListenError::IoError(format!("Failed to bind to {}", address), err)
+            })?,
+            server_config: self.server_config.clone(),
+        }))
+    }
+}
+
+pub struct RustlsListener {
+    listener: TcpListener,
+    server_config: Arc<ServerConfig>,
+}
+
+impl Listener for RustlsListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        let (mut stream, _) = self.listener.accept()?;
+        let mut connection = ServerConnection::new(self.server_config.clone())
+            .map_err(|err| AcceptError::ProtocolError(format!("TLS Err: {}", err)))?;
+        while connection.is_handshaking() {
+            connection
+                .complete_io(&mut stream)
+                .map_err(|err| AcceptError::ProtocolError(format!("TLS Handshake Err: {}", err)))?;
+        }
+        let mut tls_stream = RustlsStream::Server(StreamOwned::new(connection, stream));
+
+        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+            .negotiate(&mut tls_stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
+                    "Local {} protocol version {} not supported by remote",
+                    PROTOCOL_PREFIX,
+                    FrameVersion::V1
+                )),
+                FrameError::IoError(err) => AcceptError::from(err),
+                err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
+            })?;
+
+        tls_stream.sock().set_nonblocking(true)?;
+        Ok(Box::new(RustlsConnection {
+            frame_version,
+            stream: tls_stream,
+        }))
+    }
+
+    fn endpoint(&self) -> String {
+        format!("tcps://{}", self.listener.local_addr().unwrap())
+    }
+}
+
+/// The stream of either side of a TLS connection.
+enum RustlsStream {
+    Client(StreamOwned<ClientConnection, TcpStream>),
+    Server(StreamOwned<ServerConnection, TcpStream>),
+}
+
+impl RustlsStream {
+    fn sock(&self) -> &TcpStream {
+        match self {
+            RustlsStream::Client(stream) => &stream.sock,
+            RustlsStream::Server(stream) => &stream.sock,
+        }
+    }
+
+    fn send_close_notify(&mut self) -> io::Result<()> {
+        match self {
+            RustlsStream::Client(stream) => stream.conn.send_close_notify(),
+            RustlsStream::Server(stream) => stream.conn.send_close_notify(),
+        }
+        self.flush()
+    }
+}
+
+impl Read for RustlsStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            RustlsStream::Client(stream) => stream.read(buf),
+            RustlsStream::Server(stream) => stream.read(buf),
+        }
+    }
+}
+
+impl Write for RustlsStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            RustlsStream::Client(stream) => stream.write(buf),
+            RustlsStream::Server(stream) => stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            RustlsStream::Client(stream) => stream.flush(),
+            RustlsStream::Server(stream) => stream.flush(),
+        }
+    }
+}
+
+pub struct RustlsConnection {
+    frame_version: FrameVersion,
+    stream: RustlsStream,
+}
+
+impl Connection for RustlsConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        match FrameRef::new(self.frame_version, message).write(&mut self.stream) {
+            Err(FrameError::IoError(e)) => Err(SendError::from(e)),
+            Err(err) => Err(SendError::ProtocolError(err.to_string())),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        match Frame::read(&mut self.stream) {
+            Err(FrameError::IoError(e)) => Err(RecvError::from(e)),
+            Err(err) => Err(RecvError::ProtocolError(err.to_string())),
+            Ok(frame) => Ok(frame.into_inner()),
+        }
+    }
+
+    fn remote_endpoint(&self) -> String {
+        format!("tcps://{}", self.stream.sock().peer_addr().unwrap())
+    }
+
+    fn local_endpoint(&self) -> String {
+        format!("tcps://{}", self.stream.sock().local_addr().unwrap())
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        self.stream.send_close_notify()?;
+        Ok(())
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        self
+    }
+}
+
+impl AsRawFd for RustlsConnection {
+    fn as_raw_fd(&self) -> RawFd {
+        self.stream.sock().as_raw_fd()
+    }
+}
+
+impl Evented for RustlsConnection {
+    fn register(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.as_raw_fd()).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.as_raw_fd()).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &Poll) -> io::Result<()> {
+        EventedFd(&self.as_raw_fd()).deregister(poll)
+    }
+}
+
+impl From<rustls::Error> for TlsInitError {
+    fn from(error: rustls::Error) -> Self {
+        TlsInitError::ProtocolError(format!("Rustls Error: {}", error))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    use crate::transport::tests;
+    use crate::transport::tls::tests::{make_ca_cert, make_ca_signed_cert};
+
+    use std::path::PathBuf;
+    use tempfile::Builder;
+
+    fn write_file(mut temp_dir: PathBuf, file_name: &str, bytes: &[u8]) -> String {
+        temp_dir.push(file_name);
+        let path = temp_dir.to_str().unwrap().to_string();
+        let mut file = File::create(path.to_string()).unwrap();
+        file.write_all(bytes).unwrap();
+
+        path
+    }
+
+    pub fn create_test_rustls_transport(insecure: bool) -> RustlsTransport {
+        let (ca_key, ca_cert) = make_ca_cert();
+
+        let temp_dir = Builder::new()
+            .prefix("rustls-transport-test")
+            .tempdir()
+            .unwrap();
+        let temp_dir_path = temp_dir.path();
+        let ca_path_file = if insecure {
+            None
+        } else {
+            Some(write_file(
+                temp_dir_path.to_path_buf(),
+                "ca.cert",
+                &ca_cert.to_pem().unwrap(),
+            ))
+        };
+
+        let (client_key, client_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+        let (server_key, server_cert) = make_ca_signed_cert(&ca_cert, &ca_key);
+
+        let client_cert_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "client.cert",
+            &client_cert.to_pem().unwrap(),
+        );
+        let client_key_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "client.key",
+            &client_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+        let server_cert_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "server.cert",
+            &server_cert.to_pem().unwrap(),
+        );
+        let server_key_file = write_file(
+            temp_dir_path.to_path_buf(),
+            "server.key",
+            &server_key.private_key_to_pem_pkcs8().unwrap(),
+        );
+
+        RustlsTransport::new(
+            ca_path_file,
+            client_key_file,
+            client_cert_file,
+            server_key_file,
+            server_cert_file,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_transport() {
+        let transport = create_test_rustls_transport(true);
+        tests::test_transport(transport, "127.0.0.1:0");
+    }
+
+    #[test]
+    fn test_transport_explicit_protocol() {
+        let transport = create_test_rustls_transport(true);
+        tests::test_transport(transport, "tcps://127.0.0.1:0");
+    }
+
+    #[test]
+    fn test_transport_verify() {
+        let transport = create_test_rustls_transport(false);
+        tests::test_transport(transport, "127.0.0.1:0");
+    }
+
+    /// Verify that a transport which verifies its peers will not connect to a server whose
+    /// certificate is signed by a different CA.
+    #[test]
+    fn test_transport_verify_unknown_ca() {
+        let mut server = create_test_rustls_transport(true);
+        let mut client = create_test_rustls_transport(false);
+
+        let mut listener = server.listen("127.0.0.1:0").unwrap();
+        let endpoint = listener.endpoint();
+
+        let handle = std::thread::spawn(move || listener.accept().is_err());
+
+        assert!(client.connect(&endpoint).is_err());
+        assert!(handle.join().unwrap());
+    }
+
+    /// Verify that the rustls and OpenSSL transports can connect to each other.
+    #[cfg(feature = "tls-openssl")]
+    #[test]
+    fn test_openssl_interop() {
+        use crate::transport::socket::tests::create_test_tls_transport;
+
+        let mut server = create_test_rustls_transport(true);
+        let mut client = create_test_tls_transport(true);
+
+        let mut listener = server.listen("127.0.0.1:0").unwrap();
+        let endpoint = listener.endpoint();
+
+        let handle = std::thread::spawn(move || {
+            let mut connection = listener.accept().unwrap();
+            loop {
+                match connection.recv() {
+                    Ok(message) => break assert_eq!(message, b"hello".to_vec()),
+                    Err(RecvError::WouldBlock) => continue,
+                    Err(err) => panic!("Failed to receive message: {}", err),
+                }
+            }
+        });
+
+        let mut connection = client.connect(&endpoint).unwrap();
+        connection.send(b"hello").unwrap();
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,335 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A transport over Unix domain sockets, for connections between processes on the same host.
+//!
+//! Endpoints have the form `unix:///path/to/socket`.
+
+use std::fs;
+use std::io;
+use std::net::Shutdown;
+use std::os::unix::fs::FileTypeExt;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener as StdUnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+
+use mio::{unix::EventedFd, Evented, Poll, PollOpt, Ready, Token};
+
+use crate::transport::{
+    AcceptError, ConnectError, Connection, DisconnectError, ListenError, Listener, RecvError,
+    SendError, Transport,
+};
+
+use super::frame::{Frame, FrameError, FrameNegotiation, FrameRef, FrameVersion};
+
+const PROTOCOL_PREFIX: &str = "unix://";
+
+// The size of `sun_path` in `sockaddr_un`, less the terminating null byte, on Linux; other
+// platforms allow at most this length.
+const MAX_PATH_LEN: usize = 107;
+
+#[derive(Default)]
+pub struct UnixTransport {}
+
+impl Transport for UnixTransport {
+    fn accepts(&self, address: &str) -> bool {
+        address.starts_with(PROTOCOL_PREFIX)
+    }
+
+    fn connect(&mut self, endpoint: &str) -> Result<Box<dyn Connection>, ConnectError> {
+        let path = endpoint.strip_prefix(PROTOCOL_PREFIX).ok_or_else(|| {
+            ConnectError::ProtocolError(format!("Invalid protocol \"{}\"", endpoint))
+        })?;
+
+        let mut stream = UnixStream::connect(path)?;
+
+        let frame_version = FrameNegotiation::outbound(FrameVersion::V1, FrameVersion::V1)
+            .negotiate(&mut stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => ConnectError::ProtocolError(
+                    "Unable to connect; remote version is not with in range".into(),
+                ),
+                FrameError::IoError(err) => ConnectError::from(err),
+                e => ConnectError::ProtocolError(format!("Unexpected protocol error: {}", e)),
+            })?;
+
+        stream.set_nonblocking(true)?;
+
+        Ok(Box::new(UnixConnection {
+            frame_version,
+            stream,
+            endpoint: endpoint.to_string(),
+        }))
+    }
+
+    fn listen(&mut self, bind: &str) -> Result<Box<dyn Listener>, ListenError> {
+        validate_unix_endpoint(bind)?;
+        let path = PathBuf::from(&bind[PROTOCOL_PREFIX.len()..]);
+
+        // A socket file left behind by a previous process prevents binding to the path
+        remove_stale_socket(&path).map_err(|err| {
+            ListenError::IoError(
+                format!("Failed to remove stale socket {}", path.display()),
+                err,
+            )
+        })?;
+
+        let listener = StdUnixListener::bind(&path).map_err(|err| {
+            ListenError::IoError(format!("Failed to bind to {}", path.display()), err)
+        })?;
+
+        Ok(Box::new(UnixListener { listener, path }))
+    }
+}
+
+/// Checks that the given endpoint is a `unix://` endpoint with a path that a listener could be
+/// bound to.
+///
+/// The path must not be longer than the platform allows, its parent directory must exist, and it
+/// must not refer to an existing file that is not a socket.
+pub fn validate_unix_endpoint(endpoint: &str) -> Result<(), ListenError> {
+    let path = endpoint
+        .strip_prefix(PROTOCOL_PREFIX)
+        .ok_or_else(|| ListenError::ProtocolError(format!("Invalid protocol \"{}\"", endpoint)))?;
+
+    if path.is_empty() {
+        return Err(ListenError::ProtocolError(format!(
+            "No socket path provided in \"{}\"",
+            endpoint
+        )));
+    }
+
+    if path.len() > MAX_PATH_LEN {
+        return Err(ListenError::ProtocolError(format!(
+            "Socket path {} is longer than {} bytes",
+            path, MAX_PATH_LEN
+        )));
+    }
+
+    let path = Path::new(path);
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.is_dir() => {
+            return Err(ListenError::ProtocolError(format!(
+                "Socket directory {} does not exist",
+                parent.display()
+            )))
+        }
+        _ => (),
+    }
+
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if !metadata.file_type().is_socket() => Err(ListenError::ProtocolError(
+            format!("{} exists and is not a socket", path.display()),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn remove_stale_socket(path: &Path) -> Result<(), io::Error> {
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path),
+        _ => Ok(()),
+    }
+}
+
+struct UnixListener {
+    listener: StdUnixListener,
+    path: PathBuf,
+}
+
+impl Listener for UnixListener {
+    fn accept(&mut self) -> Result<Box<dyn Connection>, AcceptError> {
+        let (mut stream, _) = self.listener.accept()?;
+
+        let frame_version = FrameNegotiation::inbound(FrameVersion::V1)
+            .negotiate(&mut stream)
+            .map_err(|err| match err {
+                FrameError::UnsupportedVersion => AcceptError::ProtocolError(format!(
+                    "Local {} protocol version {} not supported by remote",
+                    PROTOCOL_PREFIX,
+                    FrameVersion::V1
+                )),
+                FrameError::IoError(err) => AcceptError::from(err),
+                err => AcceptError::ProtocolError(format!("Unexpected protocol error: {}", err)),
+            })?;
+
+        stream.set_nonblocking(true)?;
+
+        Ok(Box::new(UnixConnection {
+            frame_version,
+            stream,
+            endpoint: self.endpoint(),
+        }))
+    }
+
+    fn endpoint(&self) -> String {
+        format!("{}{}", PROTOCOL_PREFIX, self.path.display())
+    }
+}
+
+impl Drop for UnixListener {
+    fn drop(&mut self) {
+        if let Err(err) = fs::remove_file(&self.path) {
+            debug!("Unable to remove socket {}: {}", self.path.display(), err);
+        }
+    }
+}
+
+/// A connection over a Unix domain socket.
+///
+/// Client sockets are usually unnamed, so both sides of the connection report the endpoint of the
+/// listener as their remote and local endpoints.
+struct UnixConnection {
+    frame_version: FrameVersion,
+    stream: UnixStream,
+    endpoint: String,
+}
+
+impl Connection for UnixConnection {
+    fn send(&mut self, message: &[u8]) -> Result<(), SendError> {
+        match FrameRef::new(self.frame_version, message).write(&mut self.stream) {
+            Err(FrameError::IoError(e)) => Err(SendError::from(e)),
+            Err(err) => Err(SendError::ProtocolError(err.to_string())),
+            Ok(_) => Ok(()),
+        }
+    }
+
+    fn recv(&mut self) -> Result<Vec<u8>, RecvError> {
+        match Frame::read(&mut self.stream) {
+            Err(FrameError::IoError(e)) => Err(RecvError::from(e)),
+            Err(err) => Err(RecvError::ProtocolError(err.to_string())),
+            Ok(frame) => Ok(frame.into_inner()),
+        }
+    }
+
+    fn remote_endpoint(&self) -> String {
+        self.endpoint.clone()
+    }
+
+    fn local_endpoint(&self) -> String {
+        self.endpoint.clone()
+    }
+
+    fn disconnect(&mut self) -> Result<(), DisconnectError> {
+        self.stream
+            .shutdown(Shutdown::Both)
+            .map_err(DisconnectError::from)
+    }
+
+    fn evented(&self) -> &dyn Evented {
+        self
+    }
+}
+
+impl Evented for UnixConnection {
+    fn register(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.stream.as_raw_fd()).register(poll, token, interest, opts)
+    }
+
+    fn reregister(
+        &self,
+        poll: &Poll,
+        token: Token,
+        interest: Ready,
+        opts: PollOpt,
+    ) -> io::Result<()> {
+        EventedFd(&self.stream.as_raw_fd()).reregister(poll, token, interest, opts)
+    }
+
+    fn deregister(&self, poll: &Poll) -> io::Result<()> {
+        EventedFd(&self.stream.as_raw_fd()).deregister(poll)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::tests;
+
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_accepts() {
+        let transport = UnixTransport::default();
+        assert!(transport.accepts("unix:///var/run/splinter/service.sock"));
+
+        assert!(!transport.accepts("/var/run/splinter/service.sock"));
+        assert!(!transport.accepts("tcp://127.0.0.1:8043"));
+    }
+
+    #[test]
+    fn test_transport() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let transport = UnixTransport::default();
+
+        tests::test_transport(
+            transport,
+            &format!("unix://{}", dir.path().join("test.sock").display()),
+        );
+    }
+
+    #[test]
+    fn test_poll() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+        let transport = UnixTransport::default();
+
+        tests::test_poll(
+            transport,
+            &format!("unix://{}", dir.path().join("test.sock").display()),
+        );
+    }
+
+    /// Verify that endpoints without a path, with a missing parent directory or that refer to a
+    /// file which is not a socket are rejected, and that a stale socket is replaced when
+    /// listening.
+    #[test]
+    fn test_validate_unix_endpoint() {
+        let dir = TempDir::new().expect("Failed to create temp dir");
+
+        assert!(validate_unix_endpoint("unix://").is_err());
+        assert!(validate_unix_endpoint("tcp://127.0.0.1:8043").is_err());
+        assert!(validate_unix_endpoint(&format!(
+            "unix://{}",
+            dir.path().join("missing").join("test.sock").display()
+        ))
+        .is_err());
+
+        let file = dir.path().join("file");
+        fs::write(&file, b"not a socket").expect("Failed to write file");
+        assert!(validate_unix_endpoint(&format!("unix://{}", file.display())).is_err());
+
+        let socket = dir.path().join("test.sock");
+        let endpoint = format!("unix://{}", socket.display());
+        assert!(validate_unix_endpoint(&endpoint).is_ok());
+
+        // Leave a stale socket behind, as a process that exited without cleaning up would
+        let stale = StdUnixListener::bind(&socket).expect("Failed to bind socket");
+        drop(stale);
+        assert!(socket.exists());
+        assert!(validate_unix_endpoint(&endpoint).is_ok());
+
+        let mut transport = UnixTransport::default();
+        let listener = transport.listen(&endpoint).expect("Failed to listen");
+        assert_eq!(listener.endpoint(), endpoint);
+
+        drop(listener);
+        assert!(!socket.exists());
+    }
+}
@@ -20,12 +20,65 @@ use openssl::error::ErrorStack;
 #[cfg(feature = "ws-transport")]
 use openssl::ssl::{SslAcceptor, SslConnector, SslFiletype, SslMethod, SslVerifyMode};
 
+#[cfg(feature = "tls-peer-ca")]
+use crate::error::InvalidArgumentError;
+
+/// A CA certificate bundle that is used, instead of the default CA certificates, to verify the
+/// peers whose endpoints match a pattern.
+///
+/// The pattern is a glob (for example, `*.acme.example.com:*` or `10.0.1.*:8044`) that is matched
+/// against the endpoint's address, without its protocol prefix.
+#[cfg(feature = "tls-peer-ca")]
+#[derive(Clone, Debug)]
+pub struct PeerTrustAnchor {
+    endpoint_pattern: glob::Pattern,
+    ca_certs_file: String,
+}
+
+#[cfg(feature = "tls-peer-ca")]
+impl PeerTrustAnchor {
+    /// Creates a trust anchor for the endpoints that match the given pattern.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the endpoint pattern is not a valid glob pattern.
+    pub fn new(
+        endpoint_pattern: &str,
+        ca_certs_file: String,
+    ) -> Result<Self, InvalidArgumentError> {
+        let endpoint_pattern = glob::Pattern::new(endpoint_pattern).map_err(|err| {
+            InvalidArgumentError::new("endpoint_pattern", format!("invalid pattern: {}", err))
+        })?;
+
+        Ok(Self {
+            endpoint_pattern,
+            ca_certs_file,
+        })
+    }
+
+    pub fn endpoint_pattern(&self) -> &str {
+        self.endpoint_pattern.as_str()
+    }
+
+    pub fn ca_certs_file(&self) -> &str {
+        &self.ca_certs_file
+    }
+
+    /// Returns true if the given address, without its protocol prefix, matches the endpoint
+    /// pattern.
+    pub fn matches(&self, address: &str) -> bool {
+        self.endpoint_pattern.matches(address)
+    }
+}
+
 pub struct TlsConfig {
     ca_certs_file: Option<String>,
     server_cert_file: String,
     server_private_key_file: String,
     client_cert_file: String,
     client_private_key_file: String,
+    #[cfg(feature = "tls-peer-ca")]
+    peer_trust_anchors: Vec<PeerTrustAnchor>,
 }
 
 impl TlsConfig {
@@ -33,6 +86,13 @@ impl TlsConfig {
         &self.ca_certs_file
     }
 
+    /// Returns the CA certificate bundles used to verify specific peers, in the order in which
+    /// they are matched.
+    #[cfg(feature = "tls-peer-ca")]
+    pub fn peer_trust_anchors(&self) -> &[PeerTrustAnchor] {
+        &self.peer_trust_anchors
+    }
+
     pub fn server_cert_file(&self) -> &str {
         &self.server_cert_file
     }
@@ -57,6 +117,8 @@ pub struct TlsConfigBuilder {
     server_private_key_file: Option<String>,
     client_cert_file: Option<String>,
     client_private_key_file: Option<String>,
+    #[cfg(feature = "tls-peer-ca")]
+    peer_trust_anchors: Vec<PeerTrustAnchor>,
 }
 
 impl TlsConfigBuilder {
@@ -67,6 +129,8 @@ impl TlsConfigBuilder {
             server_private_key_file: None,
             client_cert_file: None,
             client_private_key_file: None,
+            #[cfg(feature = "tls-peer-ca")]
+            peer_trust_anchors: vec![],
         }
     }
 
@@ -95,6 +159,14 @@ impl TlsConfigBuilder {
         self
     }
 
+    /// Adds a CA certificate bundle that is used to verify the peers whose endpoints match its
+    /// pattern. If more than one pattern matches an endpoint, the first one added is used.
+    #[cfg(feature = "tls-peer-ca")]
+    pub fn with_peer_trust_anchor(mut self, peer_trust_anchor: PeerTrustAnchor) -> Self {
+        self.peer_trust_anchors.push(peer_trust_anchor);
+        self
+    }
+
     pub fn build(self) -> Result<TlsConfig, TlsConfigBuilderError> {
         let ca_certs_file = self.ca_certs_file;
         let server_cert_file = self
@@ -116,6 +188,8 @@ impl TlsConfigBuilder {
             server_private_key_file,
             client_cert_file,
             client_private_key_file,
+            #[cfg(feature = "tls-peer-ca")]
+            peer_trust_anchors: self.peer_trust_anchors,
         })
     }
 }
@@ -179,12 +253,17 @@ pub(super) fn build_acceptor(config: &TlsConfig) -> Result<SslAcceptor, ErrorSta
 
 #[cfg(test)]
 pub(super) mod tests {
+    #[cfg(feature = "tls-peer-ca")]
+    use super::PeerTrustAnchor;
+
     use openssl::asn1::Asn1Time;
     use openssl::bn::{BigNum, MsbOption};
     use openssl::hash::MessageDigest;
     use openssl::pkey::{PKey, PKeyRef, Private};
     use openssl::rsa::Rsa;
-    use openssl::x509::extension::{BasicConstraints, ExtendedKeyUsage, KeyUsage};
+    use openssl::x509::extension::{
+        BasicConstraints, ExtendedKeyUsage, KeyUsage, SubjectAlternativeName,
+    };
     use openssl::x509::{X509NameBuilder, X509Ref, X509};
 
     // Make a certificate and private key for the Certificate Authority
@@ -261,6 +340,12 @@ pub(super) mod tests {
                     .unwrap(),
             )
             .unwrap();
+        // webpki, used by the rustls transport, only checks the subject alternative name
+        let subject_alt_name = SubjectAlternativeName::new()
+            .dns("localhost")
+            .build(&cert_builder.x509v3_context(Some(ca_cert), None))
+            .unwrap();
+        cert_builder.append_extension(subject_alt_name).unwrap();
 
         cert_builder
             .sign(&ca_privkey, MessageDigest::sha256())
@@ -269,4 +354,25 @@ pub(super) mod tests {
 
         (privkey, cert)
     }
+
+    /// Verify that a peer trust anchor matches addresses against its glob pattern, and that an
+    /// invalid pattern is rejected.
+    #[cfg(feature = "tls-peer-ca")]
+    #[test]
+    fn test_peer_trust_anchor_matches() {
+        let anchor = PeerTrustAnchor::new("*.acme.example.com:*", "acme-ca.pem".into())
+            .expect("Failed to create trust anchor");
+
+        assert!(anchor.matches("node-1.acme.example.com:8044"));
+        assert!(!anchor.matches("node-1.other.example.com:8044"));
+        assert!(!anchor.matches("acme.example.com:8044"));
+
+        let anchor = PeerTrustAnchor::new("10.0.1.*:8044", "acme-ca.pem".into())
+            .expect("Failed to create trust anchor");
+
+        assert!(anchor.matches("10.0.1.12:8044"));
+        assert!(!anchor.matches("10.0.1.12:8045"));
+
+        assert!(PeerTrustAnchor::new("[node", "acme-ca.pem".into()).is_err());
+    }
 }
@@ -21,7 +21,7 @@ use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, Future};
 use std::collections::HashMap;
 
-use splinter::admin::store::{AdminServiceStore, CircuitPredicate, CircuitStatus};
+use splinter::admin::store::{AdminServiceStore, CircuitOrder, CircuitPredicate, CircuitStatus};
 use splinter::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
     paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
@@ -104,9 +104,25 @@ fn list_circuits(
     };
 
     let mut new_queries = vec![];
-    let member_filter = match query.get("filter") {
+    // `member` filters circuits by a member node; `filter` is its original name, and is kept
+    // for the clients that still use it
+    let member_filter = match query.get("member") {
         Some(value) => {
-            new_queries.push(format!("filter={}", value));
+            new_queries.push(format!("member={}", value));
+            Some(value.to_string())
+        }
+        None => match query.get("filter") {
+            Some(value) => {
+                new_queries.push(format!("filter={}", value));
+                Some(value.to_string())
+            }
+            None => None,
+        },
+    };
+
+    let management_type_filter = match query.get("management_type") {
+        Some(value) => {
+            new_queries.push(format!("management_type={}", value));
             Some(value.to_string())
         }
         None => None,
@@ -119,6 +135,28 @@ fn list_circuits(
         }
         None => None,
     };
+
+    let order = match query.get("sort") {
+        Some(value) => match parse_circuit_order(value) {
+            Some(order) => {
+                new_queries.push(format!("sort={}", value));
+                order
+            }
+            None => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid sort value passed: {}. Expected one of circuit_id, \
+                            -circuit_id, created or -created",
+                            value
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => CircuitOrder::default(),
+    };
+
     let mut link = req.uri().path().to_string();
     if !new_queries.is_empty() {
         if let Err(e) = write!(link, "?{}&", new_queries.join("&")) {
@@ -150,18 +188,35 @@ fn list_circuits(
         store,
         link,
         member_filter,
+        management_type_filter,
         status_filter,
+        order,
         Some(offset),
         Some(limit),
         protocol_version,
     ))
 }
 
+/// Parses the value of the `sort` query parameter: the name of the field to sort by, preceded by
+/// a `-` to sort in descending order.
+fn parse_circuit_order(value: &str) -> Option<CircuitOrder> {
+    match value {
+        "circuit_id" => Some(CircuitOrder::CircuitIdAsc),
+        "-circuit_id" => Some(CircuitOrder::CircuitIdDesc),
+        "created" => Some(CircuitOrder::CreatedAsc),
+        "-created" => Some(CircuitOrder::CreatedDesc),
+        _ => None,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 fn query_list_circuits(
     store: web::Data<Box<dyn AdminServiceStore>>,
     link: String,
     member_filter: Option<String>,
+    management_type_filter: Option<String>,
     status_filter: Option<String>,
+    order: CircuitOrder,
     offset: Option<usize>,
     limit: Option<usize>,
     protocol_version: String,
@@ -174,6 +229,9 @@ fn query_list_circuits(
                 vec![]
             }
         };
+        if let Some(management_type) = management_type_filter {
+            filters.push(CircuitPredicate::ManagementTypeEq(management_type));
+        }
         if let Some(status) = status_filter {
             filters.push(CircuitPredicate::CircuitStatus(
                 CircuitStatus::try_from(status)
@@ -181,17 +239,19 @@ fn query_list_circuits(
             ));
         }
 
-        let circuits = store
-            .list_circuits(&filters)
+        // Only the requested page of circuits is read from the store
+        let total = store
+            .count_circuits(&filters)
             .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?;
 
-        let offset_value = offset.unwrap_or(0);
-        let total = circuits.len();
-        let limit_value = limit.unwrap_or(total as usize);
-
-        let circuits = circuits
-            .skip(offset_value)
-            .take(limit_value)
+        let circuits = store
+            .list_circuits_page(
+                &filters,
+                order,
+                offset.unwrap_or(0),
+                limit.unwrap_or(usize::MAX),
+            )
+            .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?
             .collect::<Vec<_>>();
 
         Ok((
@@ -678,6 +738,126 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    #[test]
+    /// Tests a GET /admin/circuits?sort=circuit_id&limit=1 request returns the first circuit in
+    /// ascending order of the circuit IDs.
+    fn test_list_circuit_with_sort_and_limit() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/circuits?sort=circuit_id&limit=1",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let circuits: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            circuits.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::circuits::CircuitResponse::from(
+                &get_circuit_1().0
+            )])
+            .expect("failed to convert expected data"),
+        );
+
+        assert_eq!(
+            circuits.get("paging").expect("no paging field in response"),
+            &to_value(create_test_paging_response(
+                0,
+                1,
+                1,
+                0,
+                1,
+                2,
+                "/admin/circuits?sort=circuit_id&",
+            ))
+            .expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /admin/circuits request with the `management_type` and `member` filters
+    /// returns the expected circuit.
+    fn test_list_circuit_with_management_type_ok() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/circuits?management_type=circuit_2_type&member=node_3",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let circuits: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            circuits.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::circuits::CircuitResponse::from(
+                &get_circuit_2().0
+            )])
+            .expect("failed to convert expected data"),
+        );
+
+        assert_eq!(
+            circuits.get("paging").expect("no paging field in response"),
+            &to_value(create_test_paging_response(
+                0,
+                100,
+                0,
+                0,
+                0,
+                1,
+                "/admin/circuits?member=node_3&management_type=circuit_2_type&",
+            ))
+            .expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /admin/circuits request with an unknown `sort` value returns a bad request.
+    fn test_list_circuit_with_invalid_sort() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_list_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!("http://{}/admin/circuits?sort=members", bind_url))
+            .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     fn create_test_paging_response(
         offset: usize,
         limit: usize,
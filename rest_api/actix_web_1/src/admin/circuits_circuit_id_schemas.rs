@@ -0,0 +1,478 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints for the data schemas registered on a circuit:
+//!
+//! * `GET /admin/circuits/{circuit_id}/schemas` for listing the schemas registered on a circuit
+//! * `GET /admin/circuits/{circuit_id}/schemas/{name}/{version}` for fetching a schema
+//! * `PUT /admin/circuits/{circuit_id}/schemas/{name}/{version}` for registering or replacing a
+//!   schema
+//! * `DELETE /admin/circuits/{circuit_id}/schemas/{name}/{version}` for removing a schema
+//!
+//! Schemas are stored by the local node only; they are not shared with the other members of the
+//! circuit.
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::admin::store::{
+    error::AdminServiceStoreError, AdminServiceStore, CircuitSchemaBuilder,
+};
+use splinter::rest_api::{
+    actix_web_1::{into_bytes, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::v2::circuits_circuit_id_schemas::{
+    CircuitSchemaResponse, CircuitSchemaUpdate, ListCircuitSchemasResponse,
+};
+#[cfg(feature = "authorization")]
+use super::{CIRCUIT_READ_PERMISSION, CIRCUIT_WRITE_PERMISSION};
+
+const ADMIN_CIRCUIT_SCHEMAS_MIN: u32 = 2;
+
+pub fn make_list_circuit_schemas_resource(store: Box<dyn AdminServiceStore>) -> Resource {
+    let resource = Resource::build("/admin/circuits/{circuit_id}/schemas").add_request_guard(
+        ProtocolVersionRangeGuard::new(ADMIN_CIRCUIT_SCHEMAS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |r, _| {
+            list_schemas(r, store.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| list_schemas(r, store.clone()))
+    }
+}
+
+pub fn make_circuit_schema_resource(store: Box<dyn AdminServiceStore>) -> Resource {
+    let put_store = store.clone();
+    let delete_store = store.clone();
+    let resource =
+        Resource::build("/admin/circuits/{circuit_id}/schemas/{name}/{version}").add_request_guard(
+            ProtocolVersionRangeGuard::new(ADMIN_CIRCUIT_SCHEMAS_MIN, SPLINTER_PROTOCOL_VERSION),
+        );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |r, _| {
+                fetch_schema(r, store.clone())
+            })
+            .add_method(Method::Put, CIRCUIT_WRITE_PERMISSION, move |r, payload| {
+                set_schema(r, payload, put_store.clone())
+            })
+            .add_method(Method::Delete, CIRCUIT_WRITE_PERMISSION, move |r, _| {
+                remove_schema(r, delete_store.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |r, _| fetch_schema(r, store.clone()))
+            .add_method(Method::Put, move |r, payload| {
+                set_schema(r, payload, put_store.clone())
+            })
+            .add_method(Method::Delete, move |r, _| {
+                remove_schema(r, delete_store.clone())
+            })
+    }
+}
+
+/// The circuit ID, schema name and schema version taken from the request's path
+struct SchemaPath {
+    circuit_id: String,
+    name: String,
+    version: String,
+}
+
+impl SchemaPath {
+    fn from_request(request: &HttpRequest) -> Self {
+        let get = |key| request.match_info().get(key).unwrap_or("").to_string();
+        Self {
+            circuit_id: get("circuit_id"),
+            name: get("name"),
+            version: get("version"),
+        }
+    }
+}
+
+fn list_schemas(
+    request: HttpRequest,
+    store: Box<dyn AdminServiceStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = request
+        .match_info()
+        .get("circuit_id")
+        .unwrap_or("")
+        .to_string();
+    Box::new(
+        web::block(move || {
+            if store.get_circuit(&circuit_id)?.is_none() {
+                return Ok(None);
+            }
+            store
+                .list_circuit_schemas(&circuit_id)
+                .map(|schemas| Some(schemas.collect::<Vec<_>>()))
+        })
+        .then(|res| {
+            Ok(match res {
+                Ok(Some(schemas)) => HttpResponse::Ok().json(ListCircuitSchemasResponse {
+                    data: schemas.iter().map(CircuitSchemaResponse::from).collect(),
+                }),
+                Ok(None) => {
+                    HttpResponse::NotFound().json(ErrorResponse::not_found("Circuit not found"))
+                }
+                Err(err) => {
+                    error!("Unable to list circuit schemas: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn fetch_schema(
+    request: HttpRequest,
+    store: Box<dyn AdminServiceStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let path = SchemaPath::from_request(&request);
+    Box::new(
+        web::block(move || store.get_circuit_schema(&path.circuit_id, &path.name, &path.version))
+            .then(|res| {
+                Ok(match res {
+                    Ok(Some(schema)) => {
+                        HttpResponse::Ok().json(CircuitSchemaResponse::from(&schema))
+                    }
+                    Ok(None) => HttpResponse::NotFound()
+                        .json(ErrorResponse::not_found("Circuit schema not found")),
+                    Err(err) => {
+                        error!("Unable to fetch circuit schema: {}", err);
+                        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                    }
+                })
+            }),
+    )
+}
+
+fn set_schema(
+    request: HttpRequest,
+    payload: web::Payload,
+    store: Box<dyn AdminServiceStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let path = SchemaPath::from_request(&request);
+    Box::new(into_bytes(payload).and_then(move |bytes| {
+        let update = match serde_json::from_slice::<CircuitSchemaUpdate>(&bytes) {
+            Ok(update) => update,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future(),
+                ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+            }
+        };
+
+        let mut builder = CircuitSchemaBuilder::new()
+            .with_circuit_id(&path.circuit_id)
+            .with_name(&path.name)
+            .with_version(&path.version)
+            .with_format(&update.format)
+            .with_definition(&update.definition);
+        if let Some(description) = &update.description {
+            builder = builder.with_description(description);
+        }
+        let schema = match builder.build() {
+            Ok(schema) => schema,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&err.to_string()))
+                        .into_future(),
+                )
+            }
+        };
+
+        Box::new(
+            web::block(move || store.set_circuit_schema(schema.clone()).map(|_| schema)).then(
+                |res| {
+                    Ok(match res {
+                        Ok(schema) => HttpResponse::Ok().json(CircuitSchemaResponse::from(&schema)),
+                        Err(actix_web::error::BlockingError::Error(
+                            AdminServiceStoreError::InvalidStateError(_),
+                        )) => HttpResponse::NotFound()
+                            .json(ErrorResponse::not_found("Circuit not found")),
+                        Err(err) => {
+                            error!("Unable to set circuit schema: {}", err);
+                            HttpResponse::InternalServerError()
+                                .json(ErrorResponse::internal_error())
+                        }
+                    })
+                },
+            ),
+        )
+    }))
+}
+
+fn remove_schema(
+    request: HttpRequest,
+    store: Box<dyn AdminServiceStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let path = SchemaPath::from_request(&request);
+    Box::new(
+        web::block(move || {
+            store.remove_circuit_schema(&path.circuit_id, &path.name, &path.version)
+        })
+        .then(|res| {
+            Ok(match res {
+                Ok(()) => HttpResponse::Ok().finish(),
+                Err(actix_web::error::BlockingError::Error(
+                    AdminServiceStoreError::InvalidStateError(_),
+                )) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("Circuit schema not found")),
+                Err(err) => {
+                    error!("Unable to remove circuit schema: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::admin::store::diesel::DieselAdminServiceStore;
+    use splinter::admin::store::{
+        AuthorizationType, CircuitBuilder, CircuitNodeBuilder, DurabilityType, PersistenceType,
+        RouteType, ServiceBuilder,
+    };
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a PUT /admin/circuits/{circuit_id}/schemas/{name}/{version} request registers a
+    /// schema, that it is returned by the fetch and list endpoints, that a schema cannot be
+    /// registered on an unknown circuit, and that a DELETE request removes it.
+    fn test_set_fetch_list_and_remove_schema() {
+        let store = create_store();
+
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_list_circuit_schemas_resource(store.clone()),
+            make_circuit_schema_resource(store.clone()),
+        ]);
+
+        let send = |method: reqwest::Method, path: &str, body: Option<serde_json::Value>| {
+            let url =
+                Url::parse(&format!("http://{}{}", bind_url, path)).expect("Failed to parse URL");
+            let mut request = Client::new()
+                .request(method, url)
+                .header("Authorization", "custom")
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+            if let Some(body) = body {
+                request = request.json(&body);
+            }
+            request.send().expect("Failed to perform request")
+        };
+        let schema_path = "/admin/circuits/WBKLF-BBBBB/schemas/com.example.orders/1";
+
+        assert_eq!(
+            send(reqwest::Method::GET, schema_path, None).status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let body = json!({
+            "format": "json-schema",
+            "description": "Orders exchanged on the circuit",
+            "definition": "{\"type\": \"object\"}",
+        });
+        let resp = send(reqwest::Method::PUT, schema_path, Some(body.clone()));
+        assert_eq!(resp.status(), StatusCode::OK);
+        let schema = store
+            .get_circuit_schema("WBKLF-BBBBB", "com.example.orders", "1")
+            .expect("Failed to get schema")
+            .expect("Schema was not set");
+        assert_eq!(schema.format(), "json-schema");
+
+        let resp = send(reqwest::Method::GET, schema_path, None);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: CircuitSchemaResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(response, CircuitSchemaResponse::from(&schema));
+
+        let resp = send(
+            reqwest::Method::GET,
+            "/admin/circuits/WBKLF-BBBBB/schemas",
+            None,
+        );
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: ListCircuitSchemasResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(response.data, vec![CircuitSchemaResponse::from(&schema)]);
+
+        assert_eq!(
+            send(
+                reqwest::Method::PUT,
+                "/admin/circuits/ABCDE-00000/schemas/com.example.orders/1",
+                Some(body),
+            )
+            .status(),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            send(
+                reqwest::Method::PUT,
+                schema_path,
+                Some(json!({ "format": "", "definition": "{}" })),
+            )
+            .status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        assert_eq!(
+            send(reqwest::Method::DELETE, schema_path, None).status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            send(reqwest::Method::DELETE, schema_path, None).status(),
+            StatusCode::NOT_FOUND
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store() -> Box<dyn AdminServiceStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        let store = DieselAdminServiceStore::new(pool);
+
+        let service = ServiceBuilder::new()
+            .with_service_id("gsAA")
+            .with_service_type("type_a")
+            .with_node_id("node_1")
+            .build()
+            .expect("Unable to build service");
+        let nodes = vec![CircuitNodeBuilder::new()
+            .with_node_id("node_1")
+            .with_endpoints(&["tcp://localhost:8000".to_string()])
+            .build()
+            .expect("Unable to build node")];
+        let circuit = CircuitBuilder::new()
+            .with_circuit_id("WBKLF-BBBBB")
+            .with_authorization_type(&AuthorizationType::Trust)
+            .with_members(&nodes)
+            .with_roster(&[service])
+            .with_persistence(&PersistenceType::Any)
+            .with_durability(&DurabilityType::NoDurability)
+            .with_routes(&RouteType::Any)
+            .with_circuit_management_type("circuit_management_type")
+            .build()
+            .expect("Unable to build circuit");
+        store
+            .add_circuit(circuit, nodes)
+            .expect("Unable to add circuit");
+
+        Box::new(store)
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,647 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /admin/circuits/search` endpoint for searching the circuits in
+//! Splinter's state by their display names, comments, and metadata.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::admin::store::{AdminServiceStore, CircuitPredicate, CircuitStatus};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
+    percent_encode_query_value, ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::error::CircuitListError;
+use super::resources;
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_READ_PERMISSION;
+
+const ADMIN_SEARCH_CIRCUITS_MIN: u32 = 1;
+
+pub fn make_search_circuits_resource(store: Box<dyn AdminServiceStore>) -> Resource {
+    let resource = Resource::build("/admin/circuits/search").add_request_guard(
+        ProtocolVersionRangeGuard::new(ADMIN_SEARCH_CIRCUITS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |r, _| {
+            search_circuits(r, web::Data::new(store.clone()))
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| {
+            search_circuits(r, web::Data::new(store.clone()))
+        })
+    }
+}
+
+fn search_circuits(
+    req: HttpRequest,
+    store: web::Data<Box<dyn AdminServiceStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(req.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    let search_query = match query.get("q") {
+        Some(value) if !value.trim().is_empty() => value.to_string(),
+        _ => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(
+                        "A non-empty search query must be provided with the q parameter",
+                    ))
+                    .into_future(),
+            )
+        }
+    };
+
+    let offset = match query.get("offset") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(val) => val,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid offset value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => DEFAULT_OFFSET,
+    };
+
+    let limit = match query.get("limit") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(val) => val,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid limit value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => DEFAULT_LIMIT,
+    };
+
+    let mut new_queries = vec![format!("q={}", percent_encode_query_value(&search_query))];
+    let status_filter = match query.get("status") {
+        Some(value) => {
+            new_queries.push(format!("status={}", percent_encode_query_value(value)));
+            Some(value.to_string())
+        }
+        None => None,
+    };
+    let mut link = req.uri().path().to_string();
+    if let Err(e) = write!(link, "?{}&", new_queries.join("&")) {
+        return Box::new(
+            HttpResponse::InternalServerError()
+                .body(e.to_string())
+                .into_future(),
+        );
+    }
+
+    let protocol_version = match req.headers().get("SplinterProtocolVersion") {
+        Some(header_value) => match header_value.to_str() {
+            Ok(protocol_version) => protocol_version.to_string(),
+            Err(_) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(
+                            "Unable to get SplinterProtocolVersion",
+                        ))
+                        .into_future(),
+                )
+            }
+        },
+        None => format!("{}", SPLINTER_PROTOCOL_VERSION),
+    };
+
+    Box::new(query_search_circuits(
+        store,
+        link,
+        search_query,
+        status_filter,
+        offset,
+        limit,
+        protocol_version,
+    ))
+}
+
+fn query_search_circuits(
+    store: web::Data<Box<dyn AdminServiceStore>>,
+    link: String,
+    search_query: String,
+    status_filter: Option<String>,
+    offset: usize,
+    limit: usize,
+    protocol_version: String,
+) -> impl Future<Item = HttpResponse, Error = Error> {
+    web::block(move || {
+        let mut filters = vec![];
+        if let Some(status) = status_filter {
+            filters.push(CircuitPredicate::CircuitStatus(
+                CircuitStatus::try_from(status)
+                    .map_err(|e| CircuitListError::CircuitStatusError(e.to_string()))?,
+            ));
+        }
+
+        // Only the requested page of circuits is read from the store
+        let total = store
+            .count_matching_circuits(&search_query, &filters)
+            .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?;
+
+        let circuits = store
+            .search_circuits(&search_query, &filters, offset, limit)
+            .map_err(|err| CircuitListError::CircuitStoreError(err.to_string()))?
+            .collect::<Vec<_>>();
+
+        Ok((circuits, link, total as usize, protocol_version))
+    })
+    .then(move |res| match res {
+        Ok((circuits, link, total_count, protocol_version)) => {
+            let paging = PagingBuilder::new(link, total_count)
+                .with_limit(limit)
+                .with_offset(offset)
+                .build();
+            match protocol_version.as_str() {
+                "1" => Ok(
+                    HttpResponse::Ok().json(resources::v1::circuits::ListCircuitsResponse {
+                        data: circuits
+                            .iter()
+                            .map(resources::v1::circuits::CircuitResponse::from)
+                            .collect(),
+                        paging,
+                    }),
+                ),
+
+                // Handles 2
+                "2" => Ok(
+                    HttpResponse::Ok().json(resources::v2::circuits::ListCircuitsResponse {
+                        data: circuits
+                            .iter()
+                            .map(resources::v2::circuits::CircuitResponse::from)
+                            .collect(),
+                        paging,
+                    }),
+                ),
+                _ => Ok(
+                    HttpResponse::BadRequest().json(ErrorResponse::bad_request(&format!(
+                        "Unsupported SplinterProtocolVersion: {}",
+                        protocol_version
+                    ))),
+                ),
+            }
+        }
+        Err(err) => match err {
+            BlockingError::Error(CircuitListError::CircuitStatusError(msg)) => {
+                Ok(HttpResponse::BadRequest().json(ErrorResponse::bad_request(&msg)))
+            }
+            BlockingError::Error(CircuitListError::CircuitStoreError(err)) => {
+                error!("{}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+            _ => {
+                error!("{}", err);
+                Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+            }
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager as DieselConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+    use serde_json::{to_value, Value as JsonValue};
+
+    use splinter::admin::store::diesel::DieselAdminServiceStore;
+    use splinter::admin::store::{
+        AuthorizationType, Circuit, CircuitBuilder, CircuitNode, CircuitNodeBuilder,
+        DurabilityType, PersistenceType, RouteType, ServiceBuilder,
+    };
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::rest_api::actix_web_1::AuthConfig;
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+    use splinter::rest_api::{
+        actix_web_1::{RestApiBuilder, RestApiShutdownHandle},
+        paging::Paging,
+    };
+
+    #[test]
+    /// Tests a GET /admin/circuits/search request returns the active circuits matching the query.
+    fn test_search_circuits_ok() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_search_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!("http://{}/admin/circuits/search?q=test", bind_url))
+            .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let circuits: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            circuits.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::circuits::CircuitResponse::from(
+                &get_circuit_1().0
+            )])
+            .expect("failed to convert expected data"),
+        );
+        assert_eq!(
+            circuits.get("paging").expect("no paging field in response"),
+            &to_value(create_test_paging_response(
+                0,
+                100,
+                0,
+                0,
+                0,
+                1,
+                "/admin/circuits/search?q=test&",
+            ))
+            .expect("failed to convert expected paging")
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /admin/circuits/search request with the `status` filter returns the matching
+    /// circuits with that status.
+    fn test_search_circuits_with_status_ok() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_search_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!(
+            "http://{}/admin/circuits/search?q=test&status=disbanded",
+            bind_url
+        ))
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let circuits: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            circuits.get("data").expect("no data field in response"),
+            &to_value(vec![resources::v2::circuits::CircuitResponse::from(
+                &get_circuit_3().0
+            )])
+            .expect("failed to convert expected data"),
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests that the query is percent-encoded in the paging links of a GET
+    /// /admin/circuits/search response, so that it cannot add parameters to them.
+    fn test_search_circuits_encodes_paging_links() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_search_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse_with_params(
+            &format!("http://{}/admin/circuits/search", bind_url),
+            &[("q", "test&status=disbanded")],
+        )
+        .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let circuits: JsonValue = resp.json().expect("Failed to deserialize body");
+
+        assert_eq!(
+            circuits
+                .get("paging")
+                .and_then(|paging| paging.get("current"))
+                .expect("no current paging link in response"),
+            "/admin/circuits/search?q=test%26status%3Ddisbanded&limit=100&offset=0"
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /admin/circuits/search request without a query returns a bad request.
+    fn test_search_circuits_missing_query() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_search_circuits_resource(filled_splinter_state())]);
+
+        let url = Url::parse(&format!("http://{}/admin/circuits/search", bind_url))
+            .expect("Failed to parse URL");
+        let req = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+        let resp = req.send().expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_test_paging_response(
+        offset: usize,
+        limit: usize,
+        next_offset: usize,
+        previous_offset: usize,
+        last_offset: usize,
+        total: usize,
+        link: &str,
+    ) -> Paging {
+        let base_link = format!("{}limit={}&", link, limit);
+        let current_link = format!("{}offset={}", base_link, offset);
+        let first_link = format!("{}offset=0", base_link);
+        let next_link = format!("{}offset={}", base_link, next_offset);
+        let previous_link = format!("{}offset={}", base_link, previous_offset);
+        let last_link = format!("{}offset={}", base_link, last_offset);
+
+        Paging {
+            current: current_link,
+            offset,
+            limit,
+            total,
+            first: first_link,
+            prev: previous_link,
+            next: next_link,
+            last: last_link,
+        }
+    }
+
+    fn get_circuit_1() -> (Circuit, Vec<CircuitNode>) {
+        let service = ServiceBuilder::new()
+            .with_service_id("aaaa")
+            .with_service_type("type_a")
+            .with_node_id("node_1")
+            .build()
+            .expect("Unable to build service");
+
+        let nodes = vec![
+            CircuitNodeBuilder::new()
+                .with_node_id("node_1")
+                .with_endpoints(&["tcp://localhost:8000".to_string()])
+                .build()
+                .expect("Unable to build node"),
+            CircuitNodeBuilder::new()
+                .with_node_id("node_2")
+                .with_endpoints(&["tcp://localhost:8001".to_string()])
+                .build()
+                .expect("Unable to build node"),
+        ];
+
+        (
+            CircuitBuilder::new()
+                .with_circuit_id("abcde-12345".into())
+                .with_authorization_type(&AuthorizationType::Trust)
+                .with_members(&nodes)
+                .with_roster(&[service])
+                .with_persistence(&PersistenceType::Any)
+                .with_durability(&DurabilityType::NoDurability)
+                .with_routes(&RouteType::Any)
+                .with_circuit_management_type("circuit_1_type")
+                .with_display_name("test_display")
+                .build()
+                .expect("Should have built a correct circuit"),
+            nodes,
+        )
+    }
+
+    fn get_circuit_2() -> (Circuit, Vec<CircuitNode>) {
+        let service = ServiceBuilder::new()
+            .with_service_id("bbbb")
+            .with_service_type("other_type")
+            .with_node_id("node_3")
+            .build()
+            .expect("unable to build service");
+
+        let nodes = vec![
+            CircuitNodeBuilder::new()
+                .with_node_id("node_3")
+                .with_endpoints(&["tcp://localhost:8000".to_string()])
+                .build()
+                .expect("Unable to build node"),
+            CircuitNodeBuilder::new()
+                .with_node_id("node_4")
+                .with_endpoints(&["tcp://localhost:8001".to_string()])
+                .build()
+                .expect("Unable to build node"),
+        ];
+
+        (
+            CircuitBuilder::new()
+                .with_circuit_id("efghi-56789")
+                .with_authorization_type(&AuthorizationType::Trust)
+                .with_members(&nodes)
+                .with_roster(&[service])
+                .with_persistence(&PersistenceType::Any)
+                .with_durability(&DurabilityType::NoDurability)
+                .with_routes(&RouteType::Any)
+                .with_circuit_management_type("circuit_2_type")
+                .build()
+                .expect("Should have built a correct circuit"),
+            nodes,
+        )
+    }
+
+    fn get_circuit_3() -> (Circuit, Vec<CircuitNode>) {
+        let service = ServiceBuilder::new()
+            .with_service_id("cccc")
+            .with_service_type("other_type")
+            .with_node_id("node_5")
+            .build()
+            .expect("unable to build service");
+
+        let nodes = vec![
+            CircuitNodeBuilder::new()
+                .with_node_id("node_5")
+                .with_endpoints(&["tcp://localhost:8000".to_string()])
+                .build()
+                .expect("Unable to build node"),
+            CircuitNodeBuilder::new()
+                .with_node_id("node_6")
+                .with_endpoints(&["tcp://localhost:8001".to_string()])
+                .build()
+                .expect("Unable to build node"),
+        ];
+
+        (
+            CircuitBuilder::new()
+                .with_circuit_id("efghi-12345")
+                .with_authorization_type(&AuthorizationType::Trust)
+                .with_members(&nodes)
+                .with_roster(&[service])
+                .with_persistence(&PersistenceType::Any)
+                .with_durability(&DurabilityType::NoDurability)
+                .with_routes(&RouteType::Any)
+                .with_circuit_management_type("circuit_3_type")
+                .with_display_name("test_disbanded")
+                .with_circuit_status(&CircuitStatus::Disbanded)
+                .build()
+                .expect("Should have built a correct circuit"),
+            nodes,
+        )
+    }
+
+    fn setup_admin_service_store() -> Box<dyn AdminServiceStore> {
+        let connection_manager = DieselConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselAdminServiceStore::new(pool))
+    }
+
+    fn filled_splinter_state() -> Box<dyn AdminServiceStore> {
+        let admin_store = setup_admin_service_store();
+        let (circuit, nodes) = get_circuit_1();
+        admin_store
+            .add_circuit(circuit, nodes)
+            .expect("Unable to add circuit_1");
+
+        let (circuit, nodes) = get_circuit_2();
+        admin_store
+            .add_circuit(circuit, nodes)
+            .expect("Unable to add circuit_2");
+
+        let (circuit, nodes) = get_circuit_3();
+        admin_store
+            .add_circuit(circuit, nodes)
+            .expect("Unable to add circuit_3");
+
+        admin_store
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `POST /admin/circuits/validate` endpoint, which validates a circuit
+//! create request as the admin service would before proposing the circuit, without proposing it.
+//!
+//! The response lists every reason the proposal would be rejected, so that a proposal can be
+//! corrected before the other members are asked to vote on it.
+
+use actix_web::{Error, HttpResponse};
+use futures::{Future, IntoFuture};
+
+use splinter::admin::service::{AdminCommands, ProposalValidationError};
+use splinter::protos::admin::CircuitManagementPayload;
+use splinter::rest_api::{
+    actix_web_1::{into_protobuf, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_WRITE_PERMISSION;
+
+const ADMIN_CIRCUITS_VALIDATE_PROTOCOL_MIN: u32 = 2;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProposalValidationErrorResponse {
+    pub check: String,
+    pub message: String,
+}
+
+impl From<&ProposalValidationError> for ProposalValidationErrorResponse {
+    fn from(error: &ProposalValidationError) -> Self {
+        Self {
+            check: error.check().to_string(),
+            message: error.message().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProposalValidationResponse {
+    pub valid: bool,
+    pub errors: Vec<ProposalValidationErrorResponse>,
+}
+
+pub fn make_validate_circuit_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource = Resource::build("/admin/circuits/validate").add_request_guard(
+        ProtocolVersionRangeGuard::new(
+            ADMIN_CIRCUITS_VALIDATE_PROTOCOL_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ),
+    );
+
+    // Validation requires the same permission as submitting the proposal
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, CIRCUIT_WRITE_PERMISSION, move |_, payload| {
+            let admin_commands = admin_commands.clone();
+            Box::new(
+                into_protobuf::<CircuitManagementPayload>(payload)
+                    .and_then(move |payload| validate_circuit_change(&admin_commands, &payload)),
+            )
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |_, payload| {
+            let admin_commands = admin_commands.clone();
+            Box::new(
+                into_protobuf::<CircuitManagementPayload>(payload)
+                    .and_then(move |payload| validate_circuit_change(&admin_commands, &payload)),
+            )
+        })
+    }
+}
+
+fn validate_circuit_change<A: AdminCommands>(
+    admin_commands: &A,
+    payload: &CircuitManagementPayload,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match admin_commands.validate_circuit_change(payload) {
+        Ok(errors) => Box::new(
+            HttpResponse::Ok()
+                .json(ProposalValidationResponse {
+                    valid: errors.is_empty(),
+                    errors: errors
+                        .iter()
+                        .map(ProposalValidationErrorResponse::from)
+                        .collect(),
+                })
+                .into_future(),
+        ),
+        Err(err) => {
+            error!("Unable to validate circuit change: {}", err);
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            )
+        }
+    }
+}
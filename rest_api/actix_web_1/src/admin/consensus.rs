@@ -0,0 +1,116 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /admin/consensus` endpoint, which lists the circuit management
+//! payloads the admin service has not yet committed or rejected, along with how far each has
+//! progressed through consensus.
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::admin::service::{AdminCommands, PayloadConsensusStatus};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_READ_PERMISSION;
+
+const ADMIN_CONSENSUS_STATUS_PROTOCOL_MIN: u32 = 2;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PayloadConsensusStatusResponse {
+    pub circuit_id: String,
+    pub action: String,
+    pub payload_hash: String,
+    pub stage: String,
+    pub coordinator: Option<String>,
+    pub is_coordinator: bool,
+    pub verified: Vec<String>,
+    pub awaiting: Vec<String>,
+    pub coordinator_timeout_remaining_secs: Option<u64>,
+}
+
+impl From<&PayloadConsensusStatus> for PayloadConsensusStatusResponse {
+    fn from(status: &PayloadConsensusStatus) -> Self {
+        Self {
+            circuit_id: status.circuit_id().to_string(),
+            action: status.action().to_string(),
+            payload_hash: status.payload_hash().to_string(),
+            stage: status.stage().to_string(),
+            coordinator: status.coordinator().map(String::from),
+            is_coordinator: status.is_coordinator(),
+            verified: status.verified().to_vec(),
+            awaiting: status.awaiting().to_vec(),
+            coordinator_timeout_remaining_secs: status
+                .coordinator_timeout_remaining()
+                .map(|remaining| remaining.as_secs()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConsensusStatusListResponse {
+    pub data: Vec<PayloadConsensusStatusResponse>,
+}
+
+pub fn make_consensus_status_resource<A: AdminCommands + Clone + 'static>(
+    admin_commands: A,
+) -> Resource {
+    let resource =
+        Resource::build("/admin/consensus").add_request_guard(ProtocolVersionRangeGuard::new(
+            ADMIN_CONSENSUS_STATUS_PROTOCOL_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ));
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |_, _| {
+            list_consensus_statuses(&admin_commands)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| {
+            list_consensus_statuses(&admin_commands)
+        })
+    }
+}
+
+fn list_consensus_statuses<A: AdminCommands>(
+    admin_commands: &A,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match admin_commands.consensus_statuses() {
+        Ok(statuses) => Box::new(
+            HttpResponse::Ok()
+                .json(ConsensusStatusListResponse {
+                    data: statuses
+                        .iter()
+                        .map(PayloadConsensusStatusResponse::from)
+                        .collect(),
+                })
+                .into_future(),
+        ),
+        Err(err) => {
+            error!("Unable to get consensus statuses: {}", err);
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            )
+        }
+    }
+}
@@ -0,0 +1,378 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A Server-Sent Events stream of the admin events for a circuit management type.
+//!
+//! The stream carries the same events as `/ws/admin/register/{type}`, for clients that cannot
+//! use a WebSocket, such as browsers behind proxies that do not support them. Each event is sent
+//! with its event ID as the SSE `id`, so a client that reconnects with a `Last-Event-ID` header
+//! receives the events it missed.
+
+use std::convert::TryInto;
+use std::str::FromStr;
+
+use actix_web::{error::ErrorInternalServerError, web::Bytes, HttpRequest, HttpResponse};
+use futures::{sync::mpsc, Future, IntoFuture, Stream};
+use splinter::admin::service::{
+    AdminCommands, AdminServiceEventSubscriber, AdminServiceStatus, AdminSubscriberError,
+};
+use splinter::admin::store;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::events::{EventFormat, ADMIN_SERVICE_EVENT_TYPE};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use crate::event_format::negotiate_event_format;
+
+use super::ws_register_type::JsonAdminEvent;
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_READ_PERMISSION;
+
+const ADMIN_EVENTS_PROTOCOL_MIN: u32 = 1;
+
+pub fn make_admin_events_route<A: AdminCommands + Clone + 'static>(admin_commands: A) -> Resource {
+    let resource = Resource::build("/admin/events/{type}").add_request_guard(
+        ProtocolVersionRangeGuard::new(ADMIN_EVENTS_PROTOCOL_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_READ_PERMISSION, move |request, _| {
+            stream_admin_events(request, &admin_commands)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |request, _| {
+            stream_admin_events(request, &admin_commands)
+        })
+    }
+}
+
+fn stream_admin_events<A: AdminCommands>(
+    request: HttpRequest,
+    admin_commands: &A,
+) -> Box<dyn Future<Item = HttpResponse, Error = actix_web::Error>> {
+    match admin_commands.admin_service_status() {
+        Ok(AdminServiceStatus::Running) => (),
+        Ok(_) => {
+            warn!("Admin service is not running");
+            return Box::new(HttpResponse::ServiceUnavailable().finish().into_future());
+        }
+        Err(err) => {
+            error!("Unable to get admin service status: {}", err);
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+    }
+
+    let circuit_management_type = match request.match_info().get("type") {
+        Some(circuit_management_type) => circuit_management_type.to_string(),
+        None => return Box::new(HttpResponse::BadRequest().finish().into_future()),
+    };
+
+    let protocol_version = match header_value(&request, "SplinterProtocolVersion") {
+        Ok(protocol_version) => protocol_version.unwrap_or(SPLINTER_PROTOCOL_VERSION),
+        Err(msg) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&msg))
+                    .into_future(),
+            )
+        }
+    };
+
+    // Browsers send the ID of the last event they received when reconnecting; the `last` query
+    // parameter is accepted as well, as with the WebSocket route.
+    let last_event_id = match header_value::<i64>(&request, "Last-Event-ID") {
+        Ok(Some(last_event_id)) => Some(last_event_id),
+        Ok(None) => match query_last(request.query_string()) {
+            Ok(last) => last,
+            Err(msg) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&msg))
+                        .into_future(),
+                )
+            }
+        },
+        Err(msg) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&msg))
+                    .into_future(),
+            )
+        }
+    };
+
+    let format = match negotiate_event_format(request.query_string()) {
+        Ok(format) => format,
+        Err(msg) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&msg))
+                    .into_future(),
+            )
+        }
+    };
+
+    debug!(
+        "Streaming admin events for \"{}\" with protocol {} since {:?}",
+        circuit_management_type, protocol_version, last_event_id
+    );
+
+    let (sender, receiver) = mpsc::unbounded();
+
+    // Only the events after the last seen event are returned by the query
+    let initial_events = admin_commands
+        .get_events_since(&last_event_id.unwrap_or(0), &circuit_management_type)
+        .map_err(|err| err.to_string())
+        .and_then(|events| to_sse_events(events, format, protocol_version));
+    match initial_events {
+        Ok(initial_events) => {
+            for event in initial_events {
+                // The receiver is held below, so sending cannot fail
+                let _ = sender.unbounded_send(event);
+            }
+        }
+        Err(err) => {
+            error!(
+                "Unable to load initial set of admin events for {}: {}",
+                circuit_management_type, err
+            );
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+    }
+
+    if let Err(err) = admin_commands.add_event_subscriber(
+        &circuit_management_type,
+        Box::new(SseAdminServiceEventSubscriber {
+            sender,
+            protocol_version,
+            format,
+        }),
+    ) {
+        error!("Unable to add admin event subscriber: {}", err);
+        return Box::new(
+            HttpResponse::InternalServerError()
+                .json(ErrorResponse::internal_error())
+                .into_future(),
+        );
+    }
+
+    Box::new(
+        HttpResponse::Ok()
+            .content_type("text/event-stream")
+            .header("Cache-Control", "no-cache")
+            // Stops reverse proxies such as nginx from buffering the stream
+            .header("X-Accel-Buffering", "no")
+            .streaming(receiver.map_err(|_| ErrorInternalServerError("admin event stream closed")))
+            .into_future(),
+    )
+}
+
+/// Parses the value of an optional header.
+fn header_value<T: FromStr>(request: &HttpRequest, name: &str) -> Result<Option<T>, String> {
+    request
+        .headers()
+        .get(name)
+        .map(|value| {
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| T::from_str(value.trim()).ok())
+                .ok_or_else(|| format!("Unable to parse {}", name))
+        })
+        .transpose()
+}
+
+/// Parses the optional `last` query parameter, the ID of the last event the client received.
+fn query_last(query_string: &str) -> Result<Option<i64>, String> {
+    for (key, value) in query_string
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+    {
+        if key == "last" {
+            return value
+                .parse::<u64>()
+                .ok()
+                .and_then(|last| last.try_into().ok())
+                .map(Some)
+                .ok_or_else(|| "Unable to parse last".to_string());
+        }
+    }
+    Ok(None)
+}
+
+/// Formats an event as a Server-Sent Event in the given format, with the event's ID as the SSE
+/// `id`.
+fn to_sse(
+    event: JsonAdminEvent,
+    format: EventFormat,
+    protocol_version: u32,
+) -> Result<Bytes, String> {
+    let event_id = event.event_id();
+    let data =
+        serde_json::to_string(&format.format(ADMIN_SERVICE_EVENT_TYPE, protocol_version, event))
+            .map_err(|err| err.to_string())?;
+    let message = match event_id {
+        Some(event_id) => format!("id: {}\ndata: {}\n\n", event_id, data),
+        None => format!("data: {}\n\n", data),
+    };
+    Ok(Bytes::from(message))
+}
+
+/// Formats each of the events as a Server-Sent Event.
+fn to_sse_events<I: Iterator<Item = store::AdminServiceEvent>>(
+    events: I,
+    format: EventFormat,
+    protocol_version: u32,
+) -> Result<Vec<Bytes>, String> {
+    events
+        .map(|event| {
+            JsonAdminEvent::new(&event, protocol_version)
+                .map_err(|err| err.to_string())
+                .and_then(|event| to_sse(event, format, protocol_version))
+        })
+        .collect()
+}
+
+struct SseAdminServiceEventSubscriber {
+    sender: mpsc::UnboundedSender<Bytes>,
+    protocol_version: u32,
+    format: EventFormat,
+}
+
+impl AdminServiceEventSubscriber for SseAdminServiceEventSubscriber {
+    fn handle_event(&self, event: &store::AdminServiceEvent) -> Result<(), AdminSubscriberError> {
+        let message = JsonAdminEvent::new(event, self.protocol_version)
+            .map_err(|err| err.to_string())
+            .and_then(|event| to_sse(event, self.format, self.protocol_version))
+            .map_err(AdminSubscriberError::UnableToHandleEvent)?;
+        self.sender.unbounded_send(message).map_err(|_| {
+            debug!(
+                "Dropping admin service event and unsubscribing due to event stream being closed"
+            );
+            AdminSubscriberError::Unsubscribe
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager as DieselConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use splinter::admin::messages::{AdminServiceEvent, CircuitProposal};
+    use splinter::admin::store::diesel::DieselAdminServiceStore;
+    use splinter::admin::store::{
+        AdminServiceStore, CircuitProposalBuilder, ProposalType, ProposedCircuitBuilder,
+        ProposedNodeBuilder,
+    };
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::public_key::PublicKey;
+
+    /// Verify that the `last` query parameter is found among other parameters, and that invalid
+    /// values are rejected.
+    #[test]
+    fn test_query_last() {
+        assert_eq!(query_last(""), Ok(None));
+        assert_eq!(query_last("limit=10&last=42"), Ok(Some(42)));
+        assert!(query_last("last=yesterday").is_err());
+    }
+
+    /// Verify that a client reconnecting with the ID of the last event it received is sent every
+    /// event after that one, and not the event itself.
+    ///
+    /// 1. Add three events to an admin service store
+    /// 2. Query the events since the first, as is done when a client reconnects with the first
+    ///    event's ID
+    /// 3. Verify that the second and third events are sent, with their IDs
+    #[test]
+    fn test_reconnect_sends_missed_events() {
+        let store = setup_admin_service_store();
+        let event_ids = ["abcDE-00000", "abcDE-00001", "abcDE-00002"]
+            .iter()
+            .map(|circuit_id| {
+                let event = store
+                    .add_event(AdminServiceEvent::ProposalSubmitted(CircuitProposal::from(
+                        create_proposal(circuit_id),
+                    )))
+                    .expect("Unable to add event");
+                *event.event_id()
+            })
+            .collect::<Vec<_>>();
+
+        let events = store
+            .list_events_by_management_type_since("mgmt_type".into(), event_ids[0])
+            .expect("Unable to list events");
+        let messages = to_sse_events(events, EventFormat::Bare, SPLINTER_PROTOCOL_VERSION)
+            .expect("Unable to format events");
+
+        assert_eq!(messages.len(), 2);
+        for (message, event_id) in messages.iter().zip(&event_ids[1..]) {
+            assert!(message.starts_with(format!("id: {}\n", event_id).as_bytes()));
+        }
+    }
+
+    fn setup_admin_service_store() -> DieselAdminServiceStore<SqliteConnection> {
+        let connection_manager = DieselConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        DieselAdminServiceStore::new(pool)
+    }
+
+    fn create_proposal(circuit_id: &str) -> store::CircuitProposal {
+        CircuitProposalBuilder::new()
+            .with_proposal_type(&ProposalType::Create)
+            .with_circuit_id(circuit_id)
+            .with_circuit_hash("012345")
+            .with_circuit(
+                &ProposedCircuitBuilder::new()
+                    .with_circuit_id(circuit_id)
+                    .with_roster(&[])
+                    .with_members(&[ProposedNodeBuilder::new()
+                        .with_node_id("node_id")
+                        .with_endpoints(&["".into()])
+                        .build()
+                        .expect("Unable to build circuit node")])
+                    .with_circuit_management_type("mgmt_type")
+                    .build()
+                    .expect("Unable to create proposed circuit"),
+            )
+            .with_requester(&PublicKey::from_bytes(vec![]))
+            .with_requester_node_id("node_id")
+            .build()
+            .expect("Unable to build proposal")
+    }
+}
@@ -14,7 +14,17 @@
 
 mod circuits;
 mod circuits_circuit_id;
+#[cfg(feature = "admin-service-circuit-schemas")]
+mod circuits_circuit_id_schemas;
+#[cfg(feature = "admin-service-circuit-search")]
+mod circuits_search;
+#[cfg(feature = "admin-service-proposal-validation")]
+mod circuits_validate;
+#[cfg(feature = "admin-service-consensus-status")]
+mod consensus;
 mod error;
+#[cfg(feature = "admin-service-event-stream")]
+mod events_type;
 mod proposals;
 mod proposals_circuit_id;
 mod resources;
@@ -47,12 +57,23 @@ pub struct AdminServiceRestProvider {
 
 impl AdminServiceRestProvider {
     pub fn new(source: &AdminService) -> Self {
-        let resources = vec![
+        #[allow(unused_mut)]
+        let mut resources = vec![
             ws_register_type::make_application_handler_registration_route(source.commands()),
             submit::make_submit_route(source.commands()),
             proposals_circuit_id::make_fetch_proposal_resource(source.proposal_store_factory()),
             proposals::make_list_proposals_resource(source.proposal_store_factory()),
         ];
+        #[cfg(feature = "admin-service-consensus-status")]
+        resources.push(consensus::make_consensus_status_resource(source.commands()));
+        // Registered here, ahead of the circuit resources, so that `validate` is not treated as
+        // a circuit ID
+        #[cfg(feature = "admin-service-proposal-validation")]
+        resources.push(circuits_validate::make_validate_circuit_resource(
+            source.commands(),
+        ));
+        #[cfg(feature = "admin-service-event-stream")]
+        resources.push(events_type::make_admin_events_route(source.commands()));
         Self { resources }
     }
 }
@@ -69,8 +90,17 @@ impl RestResourceProvider for AdminServiceRestProvider {
 /// The following endpoints are provided:
 ///
 /// * `GET /admin/circuits` - List circuits in Splinter's state
+/// * `GET /admin/circuits/search` - Search circuits in Splinter's state by their display names,
+///   comments, and metadata
 /// * `GET /admin/circuits/{circuit_id}` - Fetch a specific circuit in Splinter's state by circuit
 ///   ID
+/// * `GET /admin/circuits/{circuit_id}/schemas` - List the data schemas registered on a circuit
+/// * `GET`, `PUT` and `DELETE /admin/circuits/{circuit_id}/schemas/{name}/{version}` - Fetch,
+///   register or remove a data schema registered on a circuit
+///
+/// The search endpoint is only available if the `admin-service-circuit-search` feature is
+/// enabled, and the schema endpoints are only available if the `admin-service-circuit-schemas`
+/// feature is enabled.
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
@@ -89,8 +119,17 @@ impl CircuitResourceProvider {
 /// The circuit store provides the following endpoints as REST API resources:
 ///
 /// * `GET /admin/circuits` - List circuits in Splinter's state
+/// * `GET /admin/circuits/search` - Search circuits in Splinter's state by their display names,
+///   comments, and metadata
 /// * `GET /admin/circuits/{circuit_id}` - Fetch a specific circuit in Splinter's state by circuit
 ///   ID
+/// * `GET /admin/circuits/{circuit_id}/schemas` - List the data schemas registered on a circuit
+/// * `GET`, `PUT` and `DELETE /admin/circuits/{circuit_id}/schemas/{name}/{version}` - Fetch,
+///   register or remove a data schema registered on a circuit
+///
+/// The search endpoint is only available if the `admin-service-circuit-search` feature is
+/// enabled, and the schema endpoints are only available if the `admin-service-circuit-schemas`
+/// feature is enabled.
 ///
 /// These endpoints are only available if the following REST API backend feature is enabled:
 ///
@@ -102,10 +141,21 @@ impl RestResourceProvider for CircuitResourceProvider {
         #[allow(unused_mut)]
         let mut resources = Vec::new();
 
+        // The search resource must be registered before the fetch resource, so that `search` is
+        // not treated as a circuit ID
+        #[cfg(feature = "admin-service-circuit-search")]
+        resources.push(circuits_search::make_search_circuits_resource(
+            self.store.clone(),
+        ));
         resources.append(&mut vec![
             circuits_circuit_id::make_fetch_circuit_resource(self.store.clone()),
             circuits::make_list_circuits_resource(self.store.clone()),
         ]);
+        #[cfg(feature = "admin-service-circuit-schemas")]
+        resources.append(&mut vec![
+            circuits_circuit_id_schemas::make_list_circuit_schemas_resource(self.store.clone()),
+            circuits_circuit_id_schemas::make_circuit_schema_resource(self.store.clone()),
+        ]);
         resources
     }
 }
@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use splinter::admin::store::CircuitSchema;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct CircuitSchemaResponse {
+    pub circuit_id: String,
+    pub name: String,
+    pub version: String,
+    pub format: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub definition: String,
+}
+
+impl From<&CircuitSchema> for CircuitSchemaResponse {
+    fn from(schema: &CircuitSchema) -> Self {
+        Self {
+            circuit_id: schema.circuit_id().to_string(),
+            name: schema.name().to_string(),
+            version: schema.version().to_string(),
+            format: schema.format().to_string(),
+            description: schema.description().map(String::from),
+            definition: schema.definition().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct ListCircuitSchemasResponse {
+    pub data: Vec<CircuitSchemaResponse>,
+}
+
+/// The body of a request to register a schema; the circuit, name and version are taken from the
+/// path
+#[derive(Debug, Deserialize)]
+pub(crate) struct CircuitSchemaUpdate {
+    pub format: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub definition: String,
+}
@@ -34,8 +34,11 @@ use splinter::rest_api::{
     },
     ErrorResponse,
 };
+use splinter_rest_api_common::events::{EventFormat, StreamEvent, ADMIN_SERVICE_EVENT_TYPE};
 use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
 
+use crate::event_format::negotiate_event_format;
+
 #[cfg(feature = "authorization")]
 use super::CIRCUIT_READ_PERMISSION;
 
@@ -106,6 +109,17 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
                     circuit_management_type, protocol_version
                 );
 
+                let format = match negotiate_event_format(request.query_string()) {
+                    Ok(format) => format,
+                    Err(msg) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&msg))
+                                .into_future(),
+                        )
+                    }
+                };
+
                 let mut query =
                     match web::Query::<HashMap<String, u64>>::from_query(request.query_string()) {
                         Ok(query) => query,
@@ -160,13 +174,17 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
                 };
 
                 let request = Request::from((request, payload));
-                match new_websocket_event_sender(request, Box::new(initial_events.into_iter())) {
+                let initial_events = initial_events.into_iter().map(move |event| {
+                    format.format(ADMIN_SERVICE_EVENT_TYPE, protocol_version, event)
+                });
+                match new_websocket_event_sender(request, Box::new(initial_events)) {
                     Ok((sender, res)) => {
                         if let Err(err) = admin_commands.add_event_subscriber(
                             &circuit_management_type,
                             Box::new(WsAdminServiceEventSubscriber {
                                 sender,
                                 protocol_version,
+                                format,
                             }),
                         ) {
                             error!("Unable to add admin event subscriber: {}", err);
@@ -237,6 +255,17 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
                 circuit_management_type, protocol_version
             );
 
+            let format = match negotiate_event_format(request.query_string()) {
+                Ok(format) => format,
+                Err(msg) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&msg))
+                            .into_future(),
+                    )
+                }
+            };
+
             let mut query =
                 match web::Query::<HashMap<String, u64>>::from_query(request.query_string()) {
                     Ok(query) => query,
@@ -288,13 +317,17 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
             };
 
             let request = Request::from((request, payload));
-            match new_websocket_event_sender(request, Box::new(initial_events.into_iter())) {
+            let initial_events = initial_events
+                .into_iter()
+                .map(move |event| format.format(ADMIN_SERVICE_EVENT_TYPE, protocol_version, event));
+            match new_websocket_event_sender(request, Box::new(initial_events)) {
                 Ok((sender, res)) => {
                     if let Err(err) = admin_commands.add_event_subscriber(
                         &circuit_management_type,
                         Box::new(WsAdminServiceEventSubscriber {
                             sender,
                             protocol_version,
+                            format,
                         }),
                     ) {
                         error!("Unable to add admin event subscriber: {}", err);
@@ -315,15 +348,19 @@ pub fn make_application_handler_registration_route<A: AdminCommands + Clone + 's
 }
 
 struct WsAdminServiceEventSubscriber {
-    sender: EventSender<JsonAdminEvent>,
+    sender: EventSender<StreamEvent<JsonAdminEvent>>,
     protocol_version: u32,
+    format: EventFormat,
 }
 
 impl AdminServiceEventSubscriber for WsAdminServiceEventSubscriber {
     fn handle_event(&self, event: &store::AdminServiceEvent) -> Result<(), AdminSubscriberError> {
         let json_event = JsonAdminEvent::new(event, self.protocol_version)
             .map_err(|err| AdminSubscriberError::UnableToHandleEvent(err.to_string()))?;
-        self.sender.send(json_event).map_err(|_| {
+        let stream_event =
+            self.format
+                .format(ADMIN_SERVICE_EVENT_TYPE, self.protocol_version, json_event);
+        self.sender.send(stream_event).map_err(|_| {
             debug!("Dropping admin service event and unsubscribing due to websocket being closed");
             AdminSubscriberError::Unsubscribe
         })
@@ -333,7 +370,7 @@ impl AdminServiceEventSubscriber for WsAdminServiceEventSubscriber {
 // `timestamp` is set to the current time to allow for backward-compatibility, as the
 // `timestamp` is not used by the `AdminServiceStore`.
 #[derive(Debug, Serialize, Clone)]
-struct JsonAdminEvent {
+pub(super) struct JsonAdminEvent {
     #[serde(serialize_with = "st_as_millis")]
     timestamp: time::SystemTime,
 
@@ -351,7 +388,7 @@ struct JsonAdminEvent {
 }
 
 impl JsonAdminEvent {
-    fn new(
+    pub(super) fn new(
         event: &store::AdminServiceEvent,
         protocol_version: u32,
     ) -> Result<Self, InvalidStateError> {
@@ -379,6 +416,11 @@ impl JsonAdminEvent {
             ))),
         }
     }
+
+    #[cfg(feature = "admin-service-event-stream")]
+    pub(super) fn event_id(&self) -> Option<i64> {
+        self.event_id
+    }
 }
 
 fn st_as_millis<S>(data: &time::SystemTime, serializer: S) -> Result<S::Ok, S::Error>
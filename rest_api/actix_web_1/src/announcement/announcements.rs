@@ -0,0 +1,360 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /announcements` for listing the node's announcements; expired announcements are only
+//!   listed if the `include_expired=true` query parameter is provided
+//! * `POST /announcements` for creating an announcement
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+
+use splinter::announcement::store::AnnouncementStore;
+use splinter::announcement::{Announcement, AnnouncementBuilder, AnnouncementKind};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{AnnouncementResponse, ListAnnouncementsResponse, NewAnnouncement};
+#[cfg(feature = "authorization")]
+use super::{ANNOUNCEMENT_READ_PERMISSION, ANNOUNCEMENT_WRITE_PERMISSION};
+
+const ANNOUNCEMENTS_MIN: u32 = 2;
+
+pub fn make_announcements_resource(store: Box<dyn AnnouncementStore>) -> Resource {
+    let store1 = store.clone();
+    let resource = Resource::build("/announcements").add_request_guard(
+        ProtocolVersionRangeGuard::new(ANNOUNCEMENTS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, ANNOUNCEMENT_READ_PERMISSION, move |r, _| {
+                list_announcements(r, store.clone())
+            })
+            .add_method(Method::Post, ANNOUNCEMENT_WRITE_PERMISSION, move |_, p| {
+                add_announcement(p, store1.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |r, _| {
+                list_announcements(r, store.clone())
+            })
+            .add_method(Method::Post, move |_, p| {
+                add_announcement(p, store1.clone())
+            })
+    }
+}
+
+fn list_announcements(
+    request: HttpRequest,
+    store: Box<dyn AnnouncementStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(request.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    let include_expired = match query.get("include_expired").map(|value| value.parse()) {
+        Some(Ok(include_expired)) => include_expired,
+        Some(Err(_)) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(
+                        "Invalid include_expired value, expected true or false",
+                    ))
+                    .into_future(),
+            )
+        }
+        None => false,
+    };
+
+    Box::new(
+        web::block(move || store.list_announcements()).then(move |res| {
+            Ok(match res {
+                Ok(announcements) => {
+                    let now = SystemTime::now();
+                    let data = announcements
+                        .iter()
+                        .filter(|announcement| include_expired || !announcement.is_expired_at(now))
+                        .map(AnnouncementResponse::from)
+                        .collect();
+                    HttpResponse::Ok().json(ListAnnouncementsResponse { data })
+                }
+                Err(err) => {
+                    error!("Unable to list announcements: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn add_announcement(
+    payload: web::Payload,
+    store: Box<dyn AnnouncementStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let announcement = match serde_json::from_slice::<NewAnnouncement>(&body)
+                    .map_err(|err| err.to_string())
+                    .and_then(build_announcement)
+                {
+                    Ok(announcement) => announcement,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid announcement: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>
+                    }
+                };
+
+                Box::new(
+                    web::block(move || store.add_announcement(&announcement).map(|_| announcement))
+                        .then(|res| {
+                            Ok(match res {
+                                Ok(announcement) => HttpResponse::Created()
+                                    .json(AnnouncementResponse::from(&announcement)),
+                                Err(err) => {
+                                    error!("Unable to add announcement: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                            })
+                        }),
+                )
+            }),
+    )
+}
+
+fn build_announcement(new_announcement: NewAnnouncement) -> Result<Announcement, String> {
+    let mut builder = AnnouncementBuilder::new().with_message(new_announcement.message);
+
+    if let Some(kind) = new_announcement.kind {
+        builder = builder.with_kind(
+            kind.parse::<AnnouncementKind>()
+                .map_err(|err| err.to_string())?,
+        );
+    }
+
+    if let Some(expires_at) = new_announcement.expires_at {
+        builder = builder.with_expires_at(UNIX_EPOCH + Duration::from_secs(expires_at));
+    }
+
+    builder.build().map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::announcement::store::DieselAnnouncementStore;
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that an announcement created by a POST /announcements request is returned by a
+    /// GET /announcements request, that expired announcements are only listed when requested,
+    /// and that invalid announcements are rejected.
+    fn test_add_and_list_announcements() {
+        let store = create_store();
+        store
+            .add_announcement(
+                &AnnouncementBuilder::new()
+                    .with_id("expired".to_string())
+                    .with_message("Upgraded to 0.6".to_string())
+                    .with_created_at(UNIX_EPOCH + Duration::from_secs(1000))
+                    .with_expires_at(UNIX_EPOCH + Duration::from_secs(2000))
+                    .build()
+                    .expect("Failed to build announcement"),
+            )
+            .expect("Failed to add announcement");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_announcements_resource(store)]);
+
+        let url =
+            Url::parse(&format!("http://{}/announcements", bind_url)).expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .post(url.clone())
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&NewAnnouncement {
+                kind: Some("maintenance".to_string()),
+                message: "Down for maintenance on Saturday".to_string(),
+                expires_at: None,
+            })
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let created: AnnouncementResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(created.kind, "maintenance");
+
+        let resp = Client::new()
+            .get(url.clone())
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let list: ListAnnouncementsResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(list.data, vec![created]);
+
+        let resp = Client::new()
+            .get(url.clone())
+            .query(&[("include_expired", "true")])
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let list: ListAnnouncementsResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(list.data.len(), 2);
+        assert_eq!(list.data[0].id, "expired");
+
+        let resp = Client::new()
+            .post(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&NewAnnouncement {
+                kind: Some("outage".to_string()),
+                message: "Unknown kind".to_string(),
+                expires_at: None,
+            })
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store() -> Box<dyn AnnouncementStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselAnnouncementStore::new(pool))
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
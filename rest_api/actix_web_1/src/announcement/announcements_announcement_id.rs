@@ -0,0 +1,75 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `DELETE /announcements/{announcement_id}` endpoint for removing an
+//! announcement.
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::Future;
+
+use splinter::announcement::store::AnnouncementStore;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::ANNOUNCEMENT_WRITE_PERMISSION;
+
+const ANNOUNCEMENT_REMOVE_MIN: u32 = 2;
+
+pub fn make_announcements_announcement_id_resource(store: Box<dyn AnnouncementStore>) -> Resource {
+    let resource = Resource::build("/announcements/{announcement_id}").add_request_guard(
+        ProtocolVersionRangeGuard::new(ANNOUNCEMENT_REMOVE_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Delete,
+            ANNOUNCEMENT_WRITE_PERMISSION,
+            move |r, _| remove_announcement(r, store.clone()),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Delete, move |r, _| {
+            remove_announcement(r, store.clone())
+        })
+    }
+}
+
+fn remove_announcement(
+    request: HttpRequest,
+    store: Box<dyn AnnouncementStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let announcement_id = request
+        .match_info()
+        .get("announcement_id")
+        .unwrap_or("")
+        .to_string();
+    Box::new(
+        web::block(move || store.remove_announcement(&announcement_id)).then(|res| {
+            Ok(match res {
+                Ok(Some(_)) => HttpResponse::Ok().finish(),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("Announcement not found")),
+                Err(err) => {
+                    error!("Unable to remove announcement: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
@@ -0,0 +1,67 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for the announcements an operator has set for this
+//! node, such as maintenance windows and deprecation notices.
+
+mod announcements;
+mod announcements_announcement_id;
+mod resources;
+
+use splinter::announcement::store::AnnouncementStore;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+pub use resources::{AnnouncementResponse, ListAnnouncementsResponse, NewAnnouncement};
+
+#[cfg(feature = "authorization")]
+const ANNOUNCEMENT_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "announcement.read",
+    permission_display_name: "Announcement read",
+    permission_description: "Allows the client to read the node's announcements",
+};
+
+#[cfg(feature = "authorization")]
+const ANNOUNCEMENT_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "announcement.write",
+    permission_display_name: "Announcement write",
+    permission_description: "Allows the client to create and remove the node's announcements",
+};
+
+pub struct AnnouncementResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl AnnouncementResourceProvider {
+    pub fn new(store: Box<dyn AnnouncementStore>) -> Self {
+        let resources = vec![
+            announcements::make_announcements_resource(store.clone()),
+            announcements_announcement_id::make_announcements_announcement_id_resource(store),
+        ];
+        Self { resources }
+    }
+}
+
+/// The `AnnouncementResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /announcements` - List the node's announcements
+/// * `POST /announcements` - Create an announcement
+/// * `DELETE /announcements/{announcement_id}` - Remove an announcement
+impl RestResourceProvider for AnnouncementResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use splinter::announcement::Announcement;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AnnouncementResponse {
+    pub id: String,
+    pub kind: String,
+    pub message: String,
+    /// The time the announcement was created, in seconds since the Unix epoch
+    pub created_at: u64,
+    /// The time the announcement expires, in seconds since the Unix epoch
+    pub expires_at: Option<u64>,
+}
+
+impl From<&Announcement> for AnnouncementResponse {
+    fn from(announcement: &Announcement) -> Self {
+        Self {
+            id: announcement.id().to_string(),
+            kind: announcement.kind().to_string(),
+            message: announcement.message().to_string(),
+            created_at: to_secs(announcement.created_at()),
+            expires_at: announcement.expires_at().map(to_secs),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListAnnouncementsResponse {
+    pub data: Vec<AnnouncementResponse>,
+}
+
+/// The body of a request to create an announcement
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewAnnouncement {
+    /// One of `info`, `maintenance` or `deprecation`; defaults to `info`
+    pub kind: Option<String>,
+    pub message: String,
+    /// The time the announcement expires, in seconds since the Unix epoch
+    pub expires_at: Option<u64>,
+}
+
+fn to_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
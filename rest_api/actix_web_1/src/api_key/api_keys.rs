@@ -0,0 +1,453 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /api_keys` for listing the node's API keys
+//! * `POST /api_keys` for creating an API key
+
+use actix_web::{web, Error, HttpMessage, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, stream::Stream, Future};
+
+use splinter::api_key::store::ApiKeyStore;
+use splinter::api_key::{ApiKey, ApiKeyBuilder, ApiKeyIdentity};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    auth::identity::Identity,
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{ApiKeyResponse, ListApiKeysResponse, NewApiKey, NewApiKeyResponse};
+#[cfg(feature = "authorization")]
+use super::{API_KEY_READ_PERMISSION, API_KEY_WRITE_PERMISSION};
+
+const API_KEYS_MIN: u32 = 2;
+const CUSTOM_IDENTITY_MESSAGE: &str =
+    "API keys can only be created by clients authenticated by a public key or as a user";
+
+pub fn make_api_keys_resource(store: Box<dyn ApiKeyStore>) -> Resource {
+    let store1 = store.clone();
+    let resource = Resource::build("/api_keys").add_request_guard(ProtocolVersionRangeGuard::new(
+        API_KEYS_MIN,
+        SPLINTER_PROTOCOL_VERSION,
+    ));
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, API_KEY_READ_PERMISSION, move |_, _| {
+                list_api_keys(store.clone())
+            })
+            .add_method(Method::Post, API_KEY_WRITE_PERMISSION, move |r, p| {
+                add_api_key(r, p, store1.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |_, _| list_api_keys(store.clone()))
+            .add_method(Method::Post, move |r, p| add_api_key(r, p, store1.clone()))
+    }
+}
+
+fn list_api_keys(
+    store: Box<dyn ApiKeyStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(web::block(move || store.list_api_keys()).then(move |res| {
+        Ok(match res {
+            Ok(api_keys) => HttpResponse::Ok().json(ListApiKeysResponse {
+                data: api_keys.iter().map(ApiKeyResponse::from).collect(),
+            }),
+            Err(err) => {
+                error!("Unable to list API keys: {}", err);
+                HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+            }
+        })
+    }))
+}
+
+fn add_api_key(
+    request: HttpRequest,
+    payload: web::Payload,
+    store: Box<dyn ApiKeyStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    // A key always authenticates as the client that creates it, so that the key cannot be used
+    // to gain the permissions of another identity
+    let identity = match request.extensions().get::<Identity>() {
+        Some(Identity::Key(key)) => ApiKeyIdentity::Key(key.clone()),
+        Some(Identity::User(user)) => ApiKeyIdentity::User(user.clone()),
+        Some(Identity::Custom(_)) => {
+            return Box::new(
+                HttpResponse::Forbidden()
+                    .json(ErrorResponse::forbidden(CUSTOM_IDENTITY_MESSAGE))
+                    .into_future(),
+            )
+        }
+        None => {
+            return Box::new(
+                HttpResponse::Unauthorized()
+                    .json(ErrorResponse::unauthorized())
+                    .into_future(),
+            )
+        }
+    };
+
+    Box::new(
+        payload
+            .from_err::<Error>()
+            .fold(web::BytesMut::new(), move |mut body, chunk| {
+                body.extend_from_slice(&chunk);
+                Ok::<_, Error>(body)
+            })
+            .into_future()
+            .and_then(move |body| {
+                let new_api_key = match serde_json::from_slice::<NewApiKey>(&body) {
+                    Ok(new_api_key) => new_api_key,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid API key: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>
+                    }
+                };
+
+                if !is_requested_identity(&new_api_key, &identity) {
+                    return Box::new(
+                        HttpResponse::Forbidden()
+                            .json(ErrorResponse::forbidden(
+                                "API keys can only be created for the client's own identity",
+                            ))
+                            .into_future(),
+                    );
+                }
+
+                let (api_key, secret) = match generate_api_key(new_api_key, identity) {
+                    Ok(generated) => generated,
+                    Err(err) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&format!(
+                                    "Invalid API key: {}",
+                                    err
+                                )))
+                                .into_future(),
+                        )
+                            as Box<dyn Future<Item = HttpResponse, Error = Error>>
+                    }
+                };
+
+                Box::new(
+                    web::block(move || store.add_api_key(&api_key).map(|_| api_key)).then(
+                        move |res| {
+                            Ok(match res {
+                                Ok(api_key) => HttpResponse::Created().json(NewApiKeyResponse {
+                                    api_key: ApiKeyResponse::from(&api_key),
+                                    key: secret,
+                                }),
+                                Err(err) => {
+                                    error!("Unable to add API key: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                            })
+                        },
+                    ),
+                )
+            }),
+    )
+}
+
+/// Returns whether the identity given in the request, if any, is the client's own identity.
+fn is_requested_identity(new_api_key: &NewApiKey, identity: &ApiKeyIdentity) -> bool {
+    new_api_key
+        .identity_type
+        .as_deref()
+        .map(|identity_type| identity_type == identity.identity_type())
+        .unwrap_or(true)
+        && new_api_key
+            .identity
+            .as_deref()
+            .map(|value| value == identity.value())
+            .unwrap_or(true)
+}
+
+fn generate_api_key(
+    new_api_key: NewApiKey,
+    identity: ApiKeyIdentity,
+) -> Result<(ApiKey, String), String> {
+    ApiKeyBuilder::new()
+        .with_display_name(new_api_key.display_name)
+        .with_identity(identity)
+        .generate()
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::api_key::hash_api_key_secret;
+    use splinter::api_key::store::DieselApiKeyStore;
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    use crate::api_key::api_keys_api_key_id::make_api_keys_api_key_id_resource;
+
+    #[test]
+    /// Tests that an API key created by a POST /api_keys request is returned with its secret, that
+    /// only the hash of the secret is stored, that a GET /api_keys request lists the key, that
+    /// other identity types are rejected, and that a DELETE /api_keys/{api_key_id} request
+    /// revokes the key.
+    fn test_add_list_and_remove_api_keys() {
+        let store = create_store();
+
+        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
+            make_api_keys_resource(store.clone()),
+            make_api_keys_api_key_id_resource(store.clone()),
+        ]);
+
+        let url =
+            Url::parse(&format!("http://{}/api_keys", bind_url)).expect("Failed to parse URL");
+
+        let resp = Client::new()
+            .post(url.clone())
+            .header("Authorization", "user:ci-user")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&NewApiKey {
+                display_name: "CI pipeline".to_string(),
+                identity_type: Some("user".to_string()),
+                identity: Some("ci-user".to_string()),
+            })
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let created: NewApiKeyResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(created.api_key.identity, "ci-user");
+        assert_eq!(
+            store
+                .get_api_key_by_secret_hash(&hash_api_key_secret(&created.key))
+                .expect("Failed to get API key")
+                .map(|api_key| api_key.id().to_string()),
+            Some(created.api_key.id.clone())
+        );
+
+        let resp = Client::new()
+            .get(url.clone())
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let list: ListApiKeysResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(list.data, vec![created.api_key]);
+
+        let resp = Client::new()
+            .post(url.clone())
+            .header("Authorization", "user:ci-user")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&NewApiKey {
+                display_name: "Ops".to_string(),
+                identity_type: Some("group".to_string()),
+                identity: Some("ci-user".to_string()),
+            })
+            .send()
+            .expect("Failed to perform request");
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let key_url = url
+            .join(&format!("api_keys/{}", list.data[0].id))
+            .expect("Failed to parse URL");
+        for expected_status in &[StatusCode::OK, StatusCode::NOT_FOUND] {
+            let resp = Client::new()
+                .delete(key_url.clone())
+                .header("Authorization", "custom")
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .send()
+                .expect("Failed to perform request");
+            assert_eq!(&resp.status(), expected_status);
+        }
+        assert!(store
+            .list_api_keys()
+            .expect("Failed to list API keys")
+            .is_empty());
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests that a client can only create API keys that authenticate as itself:
+    ///
+    /// 1. A user that does not give an identity gets a key for their own identity
+    /// 2. A user cannot create a key for another user, or for a public key
+    /// 3. A client with a custom identity cannot create a key
+    /// 4. Validate that only the user's own key was stored
+    fn test_add_api_key_for_other_identity() {
+        let store = create_store();
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_api_keys_resource(store.clone())]);
+
+        let url =
+            Url::parse(&format!("http://{}/api_keys", bind_url)).expect("Failed to parse URL");
+
+        let post = |authorization: &str, identity_type: Option<&str>, identity: Option<&str>| {
+            Client::new()
+                .post(url.clone())
+                .header("Authorization", authorization)
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .json(&NewApiKey {
+                    display_name: "key".to_string(),
+                    identity_type: identity_type.map(ToString::to_string),
+                    identity: identity.map(ToString::to_string),
+                })
+                .send()
+                .expect("Failed to perform request")
+        };
+
+        let resp = post("user:alice", None, None);
+        assert_eq!(resp.status(), StatusCode::CREATED);
+        let created: NewApiKeyResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(created.api_key.identity_type, "user");
+        assert_eq!(created.api_key.identity, "alice");
+
+        let resp = post("user:alice", Some("user"), Some("bob"));
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let resp = post("user:alice", Some("key"), Some("admin-public-key"));
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let resp = post("user:alice", None, Some("bob"));
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let resp = post("custom", Some("user"), Some("alice"));
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+
+        let stored = store.list_api_keys().expect("Failed to list API keys");
+        assert_eq!(stored.len(), 1);
+        assert_eq!(
+            stored[0].identity(),
+            &ApiKeyIdentity::User("alice".to_string())
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store() -> Box<dyn ApiKeyStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselApiKeyStore::new(pool))
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        /// Authenticates `user:<user ID>` and `key:<public key>` headers as the given user or
+        /// key, and any other header as a custom identity.
+        fn get_identity(
+            &self,
+            authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            let identity = match authorization {
+                AuthorizationHeader::Custom(value) => match value.split_once(':') {
+                    Some(("user", user)) => Identity::User(user.to_string()),
+                    Some(("key", key)) => Identity::Key(key.to_string()),
+                    _ => Identity::Custom(value.to_string()),
+                },
+                _ => Identity::Custom("custom".to_string()),
+            };
+            Ok(Some(identity))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `DELETE /api_keys/{api_key_id}` endpoint for revoking an API key.
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::Future;
+
+use splinter::api_key::store::ApiKeyStore;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::API_KEY_WRITE_PERMISSION;
+
+const API_KEY_REMOVE_MIN: u32 = 2;
+
+pub fn make_api_keys_api_key_id_resource(store: Box<dyn ApiKeyStore>) -> Resource {
+    let resource = Resource::build("/api_keys/{api_key_id}").add_request_guard(
+        ProtocolVersionRangeGuard::new(API_KEY_REMOVE_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Delete, API_KEY_WRITE_PERMISSION, move |r, _| {
+            remove_api_key(r, store.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Delete, move |r, _| remove_api_key(r, store.clone()))
+    }
+}
+
+fn remove_api_key(
+    request: HttpRequest,
+    store: Box<dyn ApiKeyStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let api_key_id = request
+        .match_info()
+        .get("api_key_id")
+        .unwrap_or("")
+        .to_string();
+    Box::new(
+        web::block(move || store.remove_api_key(&api_key_id)).then(|res| {
+            Ok(match res {
+                Ok(Some(_)) => HttpResponse::Ok().finish(),
+                Ok(None) => {
+                    HttpResponse::NotFound().json(ErrorResponse::not_found("API key not found"))
+                }
+                Err(err) => {
+                    error!("Unable to remove API key: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
@@ -0,0 +1,66 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for managing the API keys that machine clients use
+//! to authenticate with the node's REST API.
+
+mod api_keys;
+mod api_keys_api_key_id;
+mod resources;
+
+use splinter::api_key::store::ApiKeyStore;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+pub use resources::{ApiKeyResponse, ListApiKeysResponse, NewApiKey, NewApiKeyResponse};
+
+#[cfg(feature = "authorization")]
+const API_KEY_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "api_key.read",
+    permission_display_name: "API key read",
+    permission_description: "Allows the client to list the node's API keys",
+};
+
+#[cfg(feature = "authorization")]
+const API_KEY_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "api_key.write",
+    permission_display_name: "API key write",
+    permission_description: "Allows the client to create and revoke the node's API keys",
+};
+
+pub struct ApiKeyResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl ApiKeyResourceProvider {
+    pub fn new(store: Box<dyn ApiKeyStore>) -> Self {
+        let resources = vec![
+            api_keys::make_api_keys_resource(store.clone()),
+            api_keys_api_key_id::make_api_keys_api_key_id_resource(store),
+        ];
+        Self { resources }
+    }
+}
+
+/// The `ApiKeyResourceProvider` struct provides the following endpoints as REST API resources:
+///
+/// * `GET /api_keys` - List the node's API keys, without their secrets
+/// * `POST /api_keys` - Create an API key; the response contains the key's secret
+/// * `DELETE /api_keys/{api_key_id}` - Revoke an API key
+impl RestResourceProvider for ApiKeyResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
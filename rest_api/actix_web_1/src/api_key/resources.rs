@@ -0,0 +1,78 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use splinter::api_key::ApiKey;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApiKeyResponse {
+    pub id: String,
+    pub display_name: String,
+    /// Either `key` or `user`
+    pub identity_type: String,
+    /// The public key or user ID the API key authenticates as
+    pub identity: String,
+    /// The time the API key was created, in seconds since the Unix epoch
+    pub created_at: u64,
+}
+
+impl From<&ApiKey> for ApiKeyResponse {
+    fn from(api_key: &ApiKey) -> Self {
+        Self {
+            id: api_key.id().to_string(),
+            display_name: api_key.display_name().to_string(),
+            identity_type: api_key.identity().identity_type().to_string(),
+            identity: api_key.identity().value().to_string(),
+            created_at: to_secs(api_key.created_at()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListApiKeysResponse {
+    pub data: Vec<ApiKeyResponse>,
+}
+
+/// The body of a request to create an API key
+///
+/// An API key always authenticates as the client that created it. The identity may be given to
+/// state it explicitly, but a request for any other identity is refused.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewApiKey {
+    pub display_name: String,
+    /// Either `key` or `user`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_type: Option<String>,
+    /// The public key or user ID the API key authenticates as
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<String>,
+}
+
+/// The response to a request to create an API key
+///
+/// This is the only time the key's secret is returned; it cannot be retrieved later.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewApiKeyResponse {
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+    /// The secret to send in an `Authorization: ApiKey <key>` header
+    pub key: String,
+}
+
+fn to_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,209 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /audit/circuits/{circuit_id}/history` for listing the membership and service changes
+//!   of a circuit, oldest first, as JSON or as CSV
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::audit::store::AuditStore;
+use splinter::audit::AuditFilter;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{AuditEventResponse, CircuitHistoryQuery, ListAuditEventsResponse};
+#[cfg(feature = "authorization")]
+use super::AUDIT_READ_PERMISSION;
+
+const AUDIT_CIRCUIT_HISTORY_MIN: u32 = 2;
+
+const CSV_HEADER: &str = "id,timestamp,event_type,actor,circuit_id,detail";
+
+pub fn make_circuit_history_resource(store: Box<dyn AuditStore>) -> Resource {
+    let resource = Resource::build("/audit/circuits/{circuit_id}/history").add_request_guard(
+        ProtocolVersionRangeGuard::new(AUDIT_CIRCUIT_HISTORY_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, AUDIT_READ_PERMISSION, move |r, _| {
+            get_circuit_history(r, store.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| {
+            get_circuit_history(r, store.clone())
+        })
+    }
+}
+
+fn get_circuit_history(
+    req: HttpRequest,
+    store: Box<dyn AuditStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = match req.match_info().get("circuit_id") {
+        Some(circuit_id) => circuit_id.to_string(),
+        None => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Missing circuit_id"))
+                    .into_future(),
+            )
+        }
+    };
+
+    let query = match web::Query::<CircuitHistoryQuery>::from_query(req.query_string()) {
+        Ok(web::Query(query)) => query,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Invalid query: {}",
+                        err
+                    )))
+                    .into_future(),
+            )
+        }
+    };
+
+    let csv = match query.format.as_deref() {
+        None | Some("json") => false,
+        Some("csv") => true,
+        Some(format) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Invalid format \"{}\"; must be \"json\" or \"csv\"",
+                        format
+                    )))
+                    .into_future(),
+            )
+        }
+    };
+
+    let mut filter = AuditFilter::new().with_target(circuit_id.clone());
+    if let Some(since) = query.since {
+        filter = filter.with_since(UNIX_EPOCH + Duration::from_secs(since));
+    }
+    if let Some(until) = query.until {
+        filter = filter.with_until(UNIX_EPOCH + Duration::from_secs(until));
+    }
+
+    Box::new(
+        web::block(move || store.list_events(&filter)).then(move |res| {
+            Ok(match res {
+                Ok(entries) => {
+                    // The store lists the most recent events first
+                    let events = entries
+                        .iter()
+                        .rev()
+                        .map(AuditEventResponse::from)
+                        .collect::<Vec<_>>();
+                    if csv {
+                        HttpResponse::Ok()
+                            .content_type("text/csv")
+                            .header(
+                                "Content-Disposition",
+                                format!("attachment; filename=\"{}-history.csv\"", circuit_id),
+                            )
+                            .body(to_csv(events))
+                    } else {
+                        HttpResponse::Ok().json(ListAuditEventsResponse { data: events })
+                    }
+                }
+                Err(err) => {
+                    error!("Unable to list history of circuit {}: {}", circuit_id, err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+/// Writes the given events as CSV, with a header row.
+fn to_csv(events: Vec<AuditEventResponse>) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for event in events {
+        csv.push_str(
+            &[
+                event.id.to_string(),
+                event.timestamp.to_string(),
+                event.event_type,
+                event.actor,
+                event.target,
+                event.detail,
+            ]
+            .iter()
+            .map(|value| csv_escape(value))
+            .collect::<Vec<_>>()
+            .join(","),
+        );
+        csv.push('\n');
+    }
+    csv
+}
+
+/// Quotes a CSV value if it contains a separator, a quote or a line break.
+fn csv_escape(value: &str) -> String {
+    if value.contains(|c| c == ',' || c == '"' || c == '\n' || c == '\r') {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that events are written as CSV rows after the header, with values that contain
+    /// separators or quotes quoted.
+    #[test]
+    fn test_to_csv() {
+        let events = vec![
+            AuditEventResponse {
+                id: 1,
+                event_type: "proposal_submitted".to_string(),
+                actor: "02aa".to_string(),
+                target: "abcde-01234".to_string(),
+                detail: "members: alpha, beta".to_string(),
+                timestamp: 100,
+            },
+            AuditEventResponse {
+                id: 2,
+                event_type: "proposal_vote".to_string(),
+                actor: "02bb".to_string(),
+                target: "abcde-01234".to_string(),
+                detail: "said \"yes\"".to_string(),
+                timestamp: 200,
+            },
+        ];
+
+        assert_eq!(
+            to_csv(events),
+            "id,timestamp,event_type,actor,circuit_id,detail\n\
+             1,100,proposal_submitted,02aa,abcde-01234,\"members: alpha, beta\"\n\
+             2,200,proposal_vote,02bb,abcde-01234,\"said \"\"yes\"\"\"\n"
+        );
+    }
+}
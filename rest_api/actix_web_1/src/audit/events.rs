@@ -0,0 +1,295 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /audit/events` for listing the recorded audit events
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::audit::store::AuditStore;
+use splinter::audit::{AuditEventType, AuditFilter};
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{AuditEventResponse, AuditEventsQuery, ListAuditEventsResponse};
+#[cfg(feature = "authorization")]
+use super::AUDIT_READ_PERMISSION;
+
+const AUDIT_EVENTS_MIN: u32 = 2;
+
+pub fn make_audit_events_resource(store: Box<dyn AuditStore>) -> Resource {
+    let resource = Resource::build("/audit/events").add_request_guard(
+        ProtocolVersionRangeGuard::new(AUDIT_EVENTS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, AUDIT_READ_PERMISSION, move |r, _| {
+            list_audit_events(r, store.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| list_audit_events(r, store.clone()))
+    }
+}
+
+fn list_audit_events(
+    req: HttpRequest,
+    store: Box<dyn AuditStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let filter = match web::Query::<AuditEventsQuery>::from_query(req.query_string())
+        .map_err(|err| err.to_string())
+        .and_then(|web::Query(query)| to_filter(query))
+    {
+        Ok(filter) => filter,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&format!(
+                        "Invalid query: {}",
+                        err
+                    )))
+                    .into_future(),
+            )
+        }
+    };
+
+    Box::new(
+        web::block(move || store.list_events(&filter)).then(move |res| {
+            Ok(match res {
+                Ok(entries) => HttpResponse::Ok().json(ListAuditEventsResponse {
+                    data: entries.iter().map(AuditEventResponse::from).collect(),
+                }),
+                Err(err) => {
+                    error!("Unable to list audit events: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn to_filter(query: AuditEventsQuery) -> Result<AuditFilter, String> {
+    let mut filter = AuditFilter::new();
+    if let Some(event_type) = query.event_type {
+        filter = filter.with_event_type(
+            event_type
+                .parse::<AuditEventType>()
+                .map_err(|err| err.to_string())?,
+        );
+    }
+    if let Some(actor) = query.actor {
+        filter = filter.with_actor(actor);
+    }
+    if let Some(since) = query.since {
+        filter = filter.with_since(UNIX_EPOCH + Duration::from_secs(since));
+    }
+    if let Some(until) = query.until {
+        filter = filter.with_until(UNIX_EPOCH + Duration::from_secs(until));
+    }
+    if let Some(limit) = query.limit {
+        filter = filter.with_limit(limit);
+    }
+    Ok(filter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::audit::store::DieselAuditStore;
+    use splinter::audit::AuditEvent;
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a GET /audit/events request lists the recorded events most recent first, that
+    /// the event type, actor, time and limit query parameters filter the events, and that an
+    /// unknown event type is rejected.
+    fn test_list_audit_events() {
+        let store = create_store();
+        for (event_type, actor, target, secs) in &[
+            (
+                AuditEventType::ProposalSubmitted,
+                "02aa",
+                "abcde-01234",
+                100,
+            ),
+            (AuditEventType::ProposalVote, "02bb", "abcde-01234", 200),
+            (AuditEventType::RbacModified, "user:alice", "role:ops", 300),
+        ] {
+            store
+                .add_event(
+                    &AuditEvent::new(*event_type, actor.to_string(), target.to_string())
+                        .with_timestamp(UNIX_EPOCH + Duration::from_secs(*secs)),
+                )
+                .expect("Failed to add event");
+        }
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_audit_events_resource(store)]);
+
+        let list = |query: &str| {
+            let url = Url::parse(&format!("http://{}/audit/events?{}", bind_url, query))
+                .expect("Failed to parse URL");
+            Client::new()
+                .get(url)
+                .header("Authorization", "custom")
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .send()
+                .expect("Failed to perform request")
+        };
+        let events = |query: &str| {
+            let resp = list(query);
+            assert_eq!(resp.status(), StatusCode::OK);
+            resp.json::<ListAuditEventsResponse>()
+                .expect("Failed to deserialize body")
+                .data
+                .into_iter()
+                .map(|event| (event.event_type, event.timestamp))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            events(""),
+            vec![
+                ("rbac_modified".to_string(), 300),
+                ("proposal_vote".to_string(), 200),
+                ("proposal_submitted".to_string(), 100),
+            ]
+        );
+        assert_eq!(
+            events("event_type=proposal_vote"),
+            vec![("proposal_vote".to_string(), 200)]
+        );
+        assert_eq!(
+            events("actor=02aa"),
+            vec![("proposal_submitted".to_string(), 100)]
+        );
+        assert_eq!(
+            events("since=100&until=300"),
+            vec![
+                ("proposal_vote".to_string(), 200),
+                ("proposal_submitted".to_string(), 100),
+            ]
+        );
+        assert_eq!(events("limit=1"), vec![("rbac_modified".to_string(), 300)]);
+
+        assert_eq!(
+            list("event_type=circuit_created").status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store() -> Box<dyn AuditStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselAuditStore::new(pool))
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
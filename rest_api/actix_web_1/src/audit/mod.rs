@@ -0,0 +1,59 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoint for reading the node's audit log of administrative
+//! and authorization events.
+
+mod circuit_history;
+mod events;
+mod resources;
+
+use splinter::audit::store::AuditStore;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+pub use resources::{AuditEventResponse, ListAuditEventsResponse};
+
+#[cfg(feature = "authorization")]
+const AUDIT_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "audit.read",
+    permission_display_name: "Audit log read",
+    permission_description: "Allows the client to read the node's audit log",
+};
+
+pub struct AuditResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl AuditResourceProvider {
+    pub fn new(store: Box<dyn AuditStore>) -> Self {
+        let resources = vec![
+            events::make_audit_events_resource(store.clone()),
+            circuit_history::make_circuit_history_resource(store),
+        ];
+        Self { resources }
+    }
+}
+
+/// The `AuditResourceProvider` struct provides the following endpoints as REST API resources:
+///
+/// * `GET /audit/events` - List the recorded audit events, most recent first
+/// * `GET /audit/circuits/{circuit_id}/history` - List the membership and service changes of a
+///   circuit, oldest first, as JSON or CSV
+impl RestResourceProvider for AuditResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use splinter::audit::AuditEntry;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuditEventResponse {
+    pub id: i64,
+    pub event_type: String,
+    /// The identity responsible for the event, or the client address for failed authorizations
+    pub actor: String,
+    /// What the event applies to, such as a circuit ID or role ID
+    pub target: String,
+    pub detail: String,
+    /// The time the event occurred, in seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl From<&AuditEntry> for AuditEventResponse {
+    fn from(entry: &AuditEntry) -> Self {
+        let event = entry.event();
+        Self {
+            id: entry.id(),
+            event_type: event.event_type().to_string(),
+            actor: event.actor().to_string(),
+            target: event.target().to_string(),
+            detail: event.detail().to_string(),
+            timestamp: to_secs(event.timestamp()),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListAuditEventsResponse {
+    pub data: Vec<AuditEventResponse>,
+}
+
+/// The query parameters accepted when listing audit events
+#[derive(Debug, Default, Deserialize)]
+pub struct AuditEventsQuery {
+    pub event_type: Option<String>,
+    pub actor: Option<String>,
+    /// Only events at or after this time, in seconds since the Unix epoch
+    pub since: Option<u64>,
+    /// Only events before this time, in seconds since the Unix epoch
+    pub until: Option<u64>,
+    pub limit: Option<usize>,
+}
+
+/// The query parameters accepted when listing the history of a circuit
+#[derive(Debug, Default, Deserialize)]
+pub struct CircuitHistoryQuery {
+    /// The format of the response, "json" (the default) or "csv"
+    pub format: Option<String>,
+    /// Only events at or after this time, in seconds since the Unix epoch
+    pub since: Option<u64>,
+    /// Only events before this time, in seconds since the Unix epoch
+    pub until: Option<u64>,
+}
+
+fn to_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,57 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for reading the usage of each circuit, as recorded
+//! by this node.
+
+mod resources;
+mod usage_circuits;
+mod usage_circuits_circuit_id;
+
+use splinter::circuit::usage::CircuitUsageTracker;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(feature = "authorization")]
+const CIRCUIT_USAGE_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "circuit.usage.read",
+    permission_display_name: "Circuit usage read",
+    permission_description: "Allows the client to read the usage of each circuit",
+};
+
+pub struct CircuitUsageResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl CircuitUsageResourceProvider {
+    pub fn new(tracker: CircuitUsageTracker) -> Self {
+        let resources = vec![
+            usage_circuits::make_usage_circuits_resource(tracker.clone()),
+            usage_circuits_circuit_id::make_usage_circuits_circuit_id_resource(tracker),
+        ];
+        Self { resources }
+    }
+}
+
+/// The `CircuitUsageResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /usage/circuits` - List the usage of every circuit
+/// * `GET /usage/circuits/{circuit_id}` - Fetch the usage of a specific circuit
+impl RestResourceProvider for CircuitUsageResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
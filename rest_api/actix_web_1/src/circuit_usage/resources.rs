@@ -0,0 +1,55 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use splinter::circuit::usage::{CircuitUsage, CircuitUsageQuota};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CircuitUsageResponse {
+    pub circuit_id: String,
+    pub message_count: u64,
+    pub byte_count: u64,
+    pub quota_exceeded: bool,
+}
+
+impl CircuitUsageResponse {
+    pub fn new(usage: &CircuitUsage, quota_exceeded: bool) -> Self {
+        Self {
+            circuit_id: usage.circuit_id().to_string(),
+            message_count: usage.message_count(),
+            byte_count: usage.byte_count(),
+            quota_exceeded,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CircuitUsageQuotaResponse {
+    pub max_messages: Option<u64>,
+    pub max_bytes: Option<u64>,
+}
+
+impl From<&CircuitUsageQuota> for CircuitUsageQuotaResponse {
+    fn from(quota: &CircuitUsageQuota) -> Self {
+        Self {
+            max_messages: quota.max_messages(),
+            max_bytes: quota.max_bytes(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListCircuitUsageResponse {
+    pub data: Vec<CircuitUsageResponse>,
+    pub quota: CircuitUsageQuotaResponse,
+}
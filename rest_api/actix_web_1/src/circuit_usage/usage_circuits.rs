@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /usage/circuits` endpoint for listing the usage of every
+//! circuit.
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::circuit::usage::CircuitUsageTracker;
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{CircuitUsageQuotaResponse, CircuitUsageResponse, ListCircuitUsageResponse};
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_USAGE_READ_PERMISSION;
+
+const CIRCUIT_USAGE_LIST_MIN: u32 = 2;
+
+pub fn make_usage_circuits_resource(tracker: CircuitUsageTracker) -> Resource {
+    let resource = Resource::build("/usage/circuits").add_request_guard(
+        ProtocolVersionRangeGuard::new(CIRCUIT_USAGE_LIST_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_USAGE_READ_PERMISSION, move |_, _| {
+            list_usage(&tracker)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| list_usage(&tracker))
+    }
+}
+
+fn list_usage(
+    tracker: &CircuitUsageTracker,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let data = tracker
+        .list()
+        .iter()
+        .map(|usage| CircuitUsageResponse::new(usage, tracker.is_over_quota(usage.circuit_id())))
+        .collect();
+
+    Box::new(
+        HttpResponse::Ok()
+            .json(ListCircuitUsageResponse {
+                data,
+                quota: CircuitUsageQuotaResponse::from(tracker.quota()),
+            })
+            .into_future(),
+    )
+}
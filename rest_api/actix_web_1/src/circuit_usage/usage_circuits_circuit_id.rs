@@ -0,0 +1,207 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /usage/circuits/{circuit_id}` endpoint for fetching the usage of
+//! a specific circuit.
+
+use actix_web::{Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::circuit::usage::CircuitUsageTracker;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::CircuitUsageResponse;
+#[cfg(feature = "authorization")]
+use super::CIRCUIT_USAGE_READ_PERMISSION;
+
+const CIRCUIT_USAGE_FETCH_MIN: u32 = 2;
+
+pub fn make_usage_circuits_circuit_id_resource(tracker: CircuitUsageTracker) -> Resource {
+    let resource = Resource::build("/usage/circuits/{circuit_id}").add_request_guard(
+        ProtocolVersionRangeGuard::new(CIRCUIT_USAGE_FETCH_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, CIRCUIT_USAGE_READ_PERMISSION, move |r, _| {
+            fetch_usage(r, &tracker)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| fetch_usage(r, &tracker))
+    }
+}
+
+fn fetch_usage(
+    request: HttpRequest,
+    tracker: &CircuitUsageTracker,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = request.match_info().get("circuit_id").unwrap_or("");
+
+    let response = match tracker.get(circuit_id) {
+        Some(usage) => HttpResponse::Ok().json(CircuitUsageResponse::new(
+            &usage,
+            tracker.is_over_quota(circuit_id),
+        )),
+        None => HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+            "No usage has been recorded for circuit {}",
+            circuit_id
+        ))),
+    };
+
+    Box::new(response.into_future())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::circuit::usage::{CircuitUsage, CircuitUsageQuota};
+    use splinter::error::InternalError;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests a GET /usage/circuits/{circuit_id} request returns the usage recorded for the
+    /// circuit, and whether it has exceeded the quota.
+    fn test_fetch_usage_ok() {
+        let tracker = CircuitUsageTracker::new(CircuitUsageQuota::new().with_max_messages(1));
+        tracker.record_message("abcde-01234", 10);
+        tracker.record_message("abcde-01234", 20);
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_usage_circuits_circuit_id_resource(tracker)]);
+
+        let url = Url::parse(&format!("http://{}/usage/circuits/abcde-01234", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: CircuitUsageResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(
+            response,
+            CircuitUsageResponse::new(&CircuitUsage::new("abcde-01234", 2, 30), true)
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /usage/circuits/{circuit_id} request returns NotFound when no usage has been
+    /// recorded for the circuit.
+    fn test_fetch_usage_not_found() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_usage_circuits_circuit_id_resource(
+                CircuitUsageTracker::default(),
+            )]);
+
+        let url = Url::parse(&format!("http://{}/usage/circuits/abcde-01234", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
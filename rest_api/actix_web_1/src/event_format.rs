@@ -0,0 +1,33 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Negotiation of the format of the events sent by event streams.
+
+use splinter_rest_api_common::events::EventFormat;
+
+/// Negotiates the format of a stream's events from its request's query string, as described in
+/// [splinter_rest_api_common::events]. Without the `rest-api-event-envelopes` feature, events are
+/// always sent bare.
+pub(crate) fn negotiate_event_format(
+    #[allow(unused_variables)] query_string: &str,
+) -> Result<EventFormat, String> {
+    #[cfg(feature = "rest-api-event-envelopes")]
+    {
+        EventFormat::from_query(query_string)
+    }
+    #[cfg(not(feature = "rest-api-event-envelopes"))]
+    {
+        Ok(EventFormat::Bare)
+    }
+}
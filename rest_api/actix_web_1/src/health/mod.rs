@@ -0,0 +1,403 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /health/live` and `GET /health/ready` endpoints, which are used
+//! by orchestration systems such as Kubernetes to probe whether the node is alive and whether it
+//! is ready to serve requests.
+//!
+//! Liveness only requires the REST API to answer. Readiness runs every [`HealthCheck`] that was
+//! added to the provider, such as the database connection or the peer manager, and returns
+//! `503 Service Unavailable` with the result of each check if any of them fail. At most one run
+//! of each check is in progress at a time: requests made while a check is running wait for that
+//! run, so a hung check does not gain a thread for every probe.
+
+mod resources;
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use actix_web::{web, Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::rest_api::actix_web_1::{Method, Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::rest_api::ErrorResponse;
+
+use self::resources::{CheckResponse, HealthResponse};
+
+const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// A dependency of the node that must be healthy for the node to be ready.
+pub trait HealthCheck: Send + Sync {
+    /// Returns the name of the check, as reported by `GET /health/ready`
+    fn name(&self) -> &str;
+
+    /// Checks the dependency, returning a description of the problem if it is not healthy.
+    ///
+    /// This may block; a check that does not return within the provider's timeout fails.
+    fn check(&self) -> Result<(), String>;
+}
+
+pub struct HealthResourceProvider {
+    checks: Vec<SharedCheck>,
+    timeout: Duration,
+}
+
+impl HealthResourceProvider {
+    /// Creates a provider without checks, whose checks time out after 5 seconds.
+    pub fn new() -> Self {
+        Self {
+            checks: vec![],
+            timeout: DEFAULT_CHECK_TIMEOUT,
+        }
+    }
+
+    /// Adds a check that must pass for the node to be ready.
+    pub fn with_check(mut self, check: Box<dyn HealthCheck>) -> Self {
+        self.checks.push(SharedCheck::new(Arc::from(check)));
+        self
+    }
+
+    /// Sets how long each check may take before it fails.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl Default for HealthResourceProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The `HealthResourceProvider` struct provides the following endpoints as REST API resources:
+///
+/// * `GET /health/live` - Returns `200 OK` while the REST API is running
+/// * `GET /health/ready` - Runs the checks, returning `200 OK` if all of them pass and
+///   `503 Service Unavailable` otherwise
+///
+/// Neither endpoint requires authorization, so that they may be used as probes.
+impl RestResourceProvider for HealthResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        let live_handle = |_, _| get_live();
+        let checks = self.checks.clone();
+        let timeout = self.timeout;
+        let ready_handle = move |_, _| get_ready(checks.clone(), timeout);
+
+        #[cfg(feature = "authorization")]
+        {
+            vec![
+                Resource::build("/health/live").add_method(
+                    Method::Get,
+                    Permission::AllowUnauthenticated,
+                    live_handle,
+                ),
+                Resource::build("/health/ready").add_method(
+                    Method::Get,
+                    Permission::AllowUnauthenticated,
+                    ready_handle,
+                ),
+            ]
+        }
+        #[cfg(not(feature = "authorization"))]
+        {
+            vec![
+                Resource::build("/health/live").add_method(Method::Get, live_handle),
+                Resource::build("/health/ready").add_method(Method::Get, ready_handle),
+            ]
+        }
+    }
+}
+
+fn get_live() -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::Ok()
+            .json(HealthResponse::live())
+            .into_future(),
+    )
+}
+
+fn get_ready(
+    checks: Vec<SharedCheck>,
+    timeout: Duration,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        web::block(move || Ok::<_, ()>(run_checks(&checks, timeout))).then(
+            |result| -> Result<HttpResponse, Error> {
+                match result {
+                    Ok(checks) => {
+                        let response = HealthResponse::ready(checks);
+                        if response.is_pass() {
+                            Ok(HttpResponse::Ok().json(response))
+                        } else {
+                            Ok(HttpResponse::ServiceUnavailable().json(response))
+                        }
+                    }
+                    Err(err) => {
+                        error!("Unable to run health checks: {}", err);
+                        Ok(HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error()))
+                    }
+                }
+            },
+        ),
+    )
+}
+
+/// A check, and the state of its runs, shared by the requests that run it
+#[derive(Clone)]
+struct SharedCheck {
+    check: Arc<dyn HealthCheck>,
+    runs: Arc<(Mutex<CheckRuns>, Condvar)>,
+}
+
+#[derive(Default)]
+struct CheckRuns {
+    /// Whether a run of the check is in progress
+    running: bool,
+    /// The number of runs that have finished
+    finished: u64,
+    /// The result of the last run that finished
+    last: Option<CheckResponse>,
+}
+
+impl SharedCheck {
+    fn new(check: Arc<dyn HealthCheck>) -> Self {
+        Self {
+            check,
+            runs: Arc::new((Mutex::new(CheckRuns::default()), Condvar::new())),
+        }
+    }
+
+    /// Starts a run of the check, unless one is already in progress, returning the number of
+    /// finished runs once that run has finished.
+    fn start(&self) -> Result<u64, String> {
+        let (runs, _) = &*self.runs;
+        let mut state = runs
+            .lock()
+            .map_err(|_| "Health check lock was poisoned".to_string())?;
+        if state.running {
+            return Ok(state.finished + 1);
+        }
+
+        let check = self.check.clone();
+        let thread_runs = self.runs.clone();
+        thread::Builder::new()
+            .name(format!("HealthCheck-{}", self.check.name()))
+            .spawn(move || {
+                let start = Instant::now();
+                let result = check.check();
+                let response = CheckResponse::new(check.name(), result, start.elapsed());
+
+                let (runs, finished) = &*thread_runs;
+                if let Ok(mut state) = runs.lock() {
+                    state.running = false;
+                    state.finished += 1;
+                    state.last = Some(response);
+                    finished.notify_all();
+                }
+            })
+            .map_err(|err| format!("Unable to start check: {}", err))?;
+
+        state.running = true;
+        Ok(state.finished + 1)
+    }
+
+    /// Waits until the given number of runs have finished, returning the result of the last one,
+    /// or fails the check if the deadline passes first.
+    fn wait(&self, finished: u64, deadline: Instant, timeout: Duration) -> CheckResponse {
+        let (runs, condvar) = &*self.runs;
+        let mut state = match runs.lock() {
+            Ok(state) => state,
+            Err(_) => {
+                return self.fail(
+                    "Health check lock was poisoned".into(),
+                    Duration::from_secs(0),
+                )
+            }
+        };
+        while state.finished < finished {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining == Duration::from_secs(0) {
+                return self.fail(
+                    format!("Timed out after {}ms", timeout.as_millis()),
+                    timeout,
+                );
+            }
+            state = match condvar.wait_timeout(state, remaining) {
+                Ok((state, _)) => state,
+                Err(_) => {
+                    return self.fail(
+                        "Health check lock was poisoned".into(),
+                        Duration::from_secs(0),
+                    )
+                }
+            };
+        }
+
+        state
+            .last
+            .clone()
+            .unwrap_or_else(|| self.fail("Check has not finished".into(), Duration::from_secs(0)))
+    }
+
+    fn fail(&self, detail: String, duration: Duration) -> CheckResponse {
+        CheckResponse::new(self.check.name(), Err(detail), duration)
+    }
+}
+
+/// Runs the checks in parallel, failing those that do not return within the timeout. A check
+/// that is already running is not run again; its current run's result is used. The results are
+/// in the order of the checks.
+fn run_checks(checks: &[SharedCheck], timeout: Duration) -> Vec<CheckResponse> {
+    let started = checks.iter().map(|check| check.start()).collect::<Vec<_>>();
+
+    let deadline = Instant::now() + timeout;
+    started
+        .into_iter()
+        .zip(checks)
+        .map(|(started, check)| match started {
+            Ok(finished) => check.wait(finished, deadline, timeout),
+            Err(detail) => check.fail(detail, Duration::from_secs(0)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct TestCheck {
+        name: &'static str,
+        result: Result<(), String>,
+        delay: Duration,
+        runs: Arc<AtomicUsize>,
+    }
+
+    impl HealthCheck for TestCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn check(&self) -> Result<(), String> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(self.delay);
+            self.result.clone()
+        }
+    }
+
+    fn test_check(name: &'static str, result: Result<(), String>, delay: Duration) -> SharedCheck {
+        counted_check(name, result, delay, Arc::new(AtomicUsize::new(0)))
+    }
+
+    fn counted_check(
+        name: &'static str,
+        result: Result<(), String>,
+        delay: Duration,
+        runs: Arc<AtomicUsize>,
+    ) -> SharedCheck {
+        SharedCheck::new(Arc::new(TestCheck {
+            name,
+            result,
+            delay,
+            runs,
+        }))
+    }
+
+    /// Verify that the results are returned in the order of the checks, that a check that does
+    /// not return within the timeout fails, and that readiness requires every check to pass.
+    #[test]
+    fn test_run_checks() {
+        let checks = vec![
+            test_check("database", Ok(()), Duration::from_millis(0)),
+            test_check(
+                "peer_manager",
+                Err("Peer manager is not running".into()),
+                Duration::from_millis(0),
+            ),
+            test_check("orchestrator", Ok(()), Duration::from_secs(5)),
+        ];
+
+        let results = run_checks(&checks, Duration::from_millis(200));
+        assert_eq!(
+            results
+                .iter()
+                .map(|result| (result.name.as_str(), result.status))
+                .collect::<Vec<_>>(),
+            vec![
+                ("database", "pass"),
+                ("peer_manager", "fail"),
+                ("orchestrator", "fail"),
+            ]
+        );
+        assert_eq!(results[0].detail, None);
+        assert_eq!(
+            results[1].detail.as_deref(),
+            Some("Peer manager is not running")
+        );
+        assert_eq!(results[2].detail.as_deref(), Some("Timed out after 200ms"));
+        assert!(!HealthResponse::ready(results).is_pass());
+
+        let results = run_checks(
+            &[test_check("database", Ok(()), Duration::from_millis(0))],
+            Duration::from_millis(200),
+        );
+        assert!(HealthResponse::ready(results).is_pass());
+    }
+
+    /// Verify that a check that is still running is not run again:
+    ///
+    /// 1. Run a check that takes longer than the timeout, and verify that it times out
+    /// 2. Run the check again from two threads while the first run is in progress, and verify
+    ///    that both time out without starting another run
+    /// 3. Wait for the first run to finish, then run the check again and verify that it passes
+    ///    and that a second run was started
+    #[test]
+    fn test_run_checks_reuses_pending_check() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let checks = vec![counted_check(
+            "database",
+            Ok(()),
+            Duration::from_millis(500),
+            runs.clone(),
+        )];
+
+        // 1
+        let results = run_checks(&checks, Duration::from_millis(50));
+        assert_eq!(results[0].detail.as_deref(), Some("Timed out after 50ms"));
+
+        // 2
+        let requests = (0..2)
+            .map(|_| {
+                let checks = checks.clone();
+                thread::spawn(move || run_checks(&checks, Duration::from_millis(50)))
+            })
+            .collect::<Vec<_>>();
+        for request in requests {
+            let results = request.join().expect("Request thread panicked");
+            assert_eq!(results[0].status, "fail");
+        }
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+        // 3
+        thread::sleep(Duration::from_millis(600));
+        let results = run_checks(&checks, Duration::from_secs(2));
+        assert_eq!(results[0].status, "pass");
+        assert_eq!(runs.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,78 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+const PASS: &str = "pass";
+const FAIL: &str = "fail";
+
+/// The result of a single health check
+#[derive(Clone, Debug, Serialize)]
+pub struct CheckResponse {
+    pub name: String,
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl CheckResponse {
+    pub fn new(name: &str, result: Result<(), String>, duration: Duration) -> Self {
+        let (status, detail) = match result {
+            Ok(()) => (PASS, None),
+            Err(detail) => (FAIL, Some(detail)),
+        };
+        Self {
+            name: name.to_string(),
+            status,
+            detail,
+            duration_ms: duration.as_millis() as u64,
+        }
+    }
+
+    pub fn is_pass(&self) -> bool {
+        self.status == PASS
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<CheckResponse>,
+}
+
+impl HealthResponse {
+    /// The response of `GET /health/live`, which has no checks
+    pub fn live() -> Self {
+        Self {
+            status: PASS,
+            checks: vec![],
+        }
+    }
+
+    /// The response of `GET /health/ready`, which passes only if every check passed
+    pub fn ready(checks: Vec<CheckResponse>) -> Self {
+        let status = if checks.iter().all(CheckResponse::is_pass) {
+            PASS
+        } else {
+            FAIL
+        };
+        Self { status, checks }
+    }
+
+    pub fn is_pass(&self) -> bool {
+        self.status == PASS
+    }
+}
@@ -14,13 +14,21 @@
 
 use std::error::Error;
 use std::fmt;
-#[cfg(feature = "admin-service")]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "node-attestation",
+    feature = "scabbard-consensus-log"
+))]
 use std::fmt::Write;
 
 #[cfg(feature = "admin-service")]
 use serde::Serializer;
 
-#[cfg(feature = "admin-service")]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "node-attestation",
+    feature = "scabbard-consensus-log"
+))]
 pub fn to_hex(bytes: &[u8]) -> String {
     let mut buf = String::new();
     for b in bytes {
@@ -30,6 +38,28 @@ pub fn to_hex(bytes: &[u8]) -> String {
     buf
 }
 
+#[cfg(feature = "node-attestation")]
+pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
+    if hex.len() % 2 != 0 {
+        return Err(HexError {
+            context: format!("{} is not valid hex: odd number of digits", hex),
+            source: None,
+        });
+    }
+
+    let mut res = vec![];
+    for i in (0..hex.len()).step_by(2) {
+        res.push(
+            u8::from_str_radix(&hex[i..i + 2], 16).map_err(|err| HexError {
+                context: format!("{} contains invalid hex", hex),
+                source: Some(Box::new(err)),
+            })?,
+        );
+    }
+
+    Ok(res)
+}
+
 #[cfg(feature = "admin-service")]
 pub fn as_hex<S>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error>
 where
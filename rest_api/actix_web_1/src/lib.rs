@@ -13,26 +13,83 @@
 // limitations under the License.
 
 #[macro_use]
-#[cfg(any(feature = "admin-service", feature = "service"))]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "announcements",
+    feature = "api-keys",
+    feature = "audit",
+    feature = "health",
+    feature = "node-attestation",
+    feature = "peer-blocklist",
+    feature = "peer-metadata",
+    feature = "scabbard-consensus-log",
+    feature = "service",
+    feature = "service-restart-policy",
+    feature = "subsystem-control",
+    feature = "transfer-service"
+))]
 extern crate log;
 #[macro_use]
-#[cfg(feature = "admin-service")]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "announcements",
+    feature = "api-keys",
+    feature = "audit",
+    feature = "capabilities-report",
+    feature = "circuit-usage",
+    feature = "health",
+    feature = "node-attestation",
+    feature = "peer",
+    feature = "scabbard-consensus-log",
+    feature = "service-restart-policy",
+    feature = "startup-report",
+    feature = "subsystem-control"
+))]
 extern crate serde;
 #[macro_use]
-#[cfg(any(feature = "admin-service", feature = "service"))]
+#[cfg(any(
+    feature = "admin-service",
+    feature = "peer-blocklist",
+    feature = "peer-metadata",
+    feature = "peer-ref-counts",
+    feature = "service",
+    feature = "startup-report"
+))]
 extern crate serde_json;
 
 #[cfg(feature = "admin-service")]
 pub mod admin;
+#[cfg(feature = "announcements")]
+pub mod announcement;
+#[cfg(feature = "api-keys")]
+pub mod api_key;
+#[cfg(feature = "audit")]
+pub mod audit;
 #[cfg(feature = "biome")]
 pub mod biome;
+#[cfg(feature = "circuit-usage")]
+pub mod circuit_usage;
+#[cfg(any(feature = "admin-service", feature = "scabbard-service"))]
+mod event_format;
+#[cfg(feature = "health")]
+pub mod health;
 pub mod open_api;
+#[cfg(feature = "peer")]
+pub mod peer;
 #[cfg(feature = "registry")]
 pub mod registry;
 #[cfg(feature = "scabbard-service")]
 pub mod scabbard;
+#[cfg(feature = "scabbard-consensus-log")]
+pub mod scabbard_consensus;
 #[cfg(feature = "service")]
 pub mod service;
+#[cfg(feature = "service-restart-policy")]
+pub mod service_restart;
 pub mod status;
+#[cfg(feature = "subsystem-control")]
+pub mod subsystem;
+#[cfg(feature = "transfer-service")]
+pub mod transfer;
 
 mod hex;
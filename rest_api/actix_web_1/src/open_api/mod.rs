@@ -20,15 +20,13 @@ use futures::{Future, IntoFuture};
 
 pub use resource_provider::OpenApiResourceProvider;
 
+/// The hand-written OpenAPI document of splinterd's REST API
+pub const OPENAPI_BASE_DOCUMENT: &str =
+    include_str!("../../../../splinterd/api/static/openapi.yaml");
+
 pub fn get_openapi(
     _: HttpRequest,
     _: web::Payload,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
-    Box::new(
-        HttpResponse::Ok()
-            .body(include_str!(
-                "../../../../splinterd/api/static/openapi.yaml"
-            ))
-            .into_future(),
-    )
+    Box::new(HttpResponse::Ok().body(OPENAPI_BASE_DOCUMENT).into_future())
 }
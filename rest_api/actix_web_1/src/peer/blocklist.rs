@@ -0,0 +1,365 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /peers/blocklist` for listing the blocked public keys and IP addresses
+//! * `POST /peers/blocklist` for blocking a public key or IP address
+//!
+//! Expired blocks are removed from the store whenever a block is added, and are only listed if
+//! the `include_expired` query parameter is `true`.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::peer::blocklist::store::PeerBlocklistStore;
+use splinter::peer::blocklist::{BlockTarget, BlockedPeerBuilder};
+use splinter::rest_api::{
+    actix_web_1::{into_bytes, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{BlockedPeerResponse, ListBlockedPeersResponse, NewBlockedPeer};
+#[cfg(feature = "authorization")]
+use super::{PEER_READ_PERMISSION, PEER_WRITE_PERMISSION};
+
+const PEER_BLOCKLIST_MIN: u32 = 2;
+
+/// How long a block lasts if the request does not give a duration
+const DEFAULT_BLOCK_DURATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub fn make_blocklist_resource(store: Box<dyn PeerBlocklistStore>) -> Resource {
+    let post_store = store.clone();
+    let resource = Resource::build("/peers/blocklist").add_request_guard(
+        ProtocolVersionRangeGuard::new(PEER_BLOCKLIST_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, PEER_READ_PERMISSION, move |r, _| {
+                list_blocks(r, store.clone())
+            })
+            .add_method(Method::Post, PEER_WRITE_PERMISSION, move |_, payload| {
+                add_block(payload, post_store.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |r, _| list_blocks(r, store.clone()))
+            .add_method(Method::Post, move |_, payload| {
+                add_block(payload, post_store.clone())
+            })
+    }
+}
+
+fn list_blocks(
+    request: HttpRequest,
+    store: Box<dyn PeerBlocklistStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let query: web::Query<HashMap<String, String>> =
+        if let Ok(q) = web::Query::from_query(request.query_string()) {
+            q
+        } else {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            );
+        };
+
+    let include_expired = match query.get("include_expired") {
+        Some(value) => match value.parse::<bool>() {
+            Ok(include_expired) => include_expired,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Invalid include_expired value passed: {}. Error: {}",
+                            value, err
+                        )))
+                        .into_future(),
+                )
+            }
+        },
+        None => false,
+    };
+
+    Box::new(web::block(move || store.list_blocks()).then(move |res| {
+        Ok(match res {
+            Ok(blocks) => {
+                let now = SystemTime::now();
+                HttpResponse::Ok().json(ListBlockedPeersResponse {
+                    data: blocks
+                        .iter()
+                        .filter(|block| include_expired || !block.is_expired_at(now))
+                        .map(BlockedPeerResponse::from)
+                        .collect(),
+                })
+            }
+            Err(err) => {
+                error!("Unable to list peer blocklist: {}", err);
+                HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+            }
+        })
+    }))
+}
+
+fn add_block(
+    payload: web::Payload,
+    store: Box<dyn PeerBlocklistStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(into_bytes(payload).and_then(move |bytes| {
+        let new_block = match serde_json::from_slice::<NewBlockedPeer>(&bytes) {
+            Ok(new_block) => new_block,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future(),
+                ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+            }
+        };
+
+        let block = match BlockTarget::parse(&new_block.kind, &new_block.value)
+            .map_err(|err| err.to_string())
+            .and_then(|target| {
+                BlockedPeerBuilder::new()
+                    .with_target(target)
+                    .with_reason(new_block.reason)
+                    .with_duration(
+                        new_block
+                            .duration
+                            .map(Duration::from_secs)
+                            .unwrap_or(DEFAULT_BLOCK_DURATION),
+                    )
+                    .build()
+                    .map_err(|err| err.to_string())
+            }) {
+            Ok(block) => block,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&err))
+                        .into_future(),
+                )
+            }
+        };
+
+        Box::new(
+            web::block(move || {
+                store.remove_expired_blocks(SystemTime::now())?;
+                store.add_block(&block).map(|_| block)
+            })
+            .then(|res| {
+                Ok(match res {
+                    Ok(block) => HttpResponse::Ok().json(BlockedPeerResponse::from(&block)),
+                    Err(err) => {
+                        error!("Unable to add peer block: {}", err);
+                        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                    }
+                })
+            }),
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::peer::blocklist::store::DieselPeerBlocklistStore;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a POST /peers/blocklist request blocks an IP address for the given duration,
+    /// that a GET request lists it, that an unknown kind is rejected, and that expired blocks are
+    /// only listed when requested.
+    fn test_add_and_list_blocks() {
+        let store = create_store();
+        let expired = BlockedPeerBuilder::new()
+            .with_target(BlockTarget::parse("ip", "10.0.0.2").expect("Failed to parse target"))
+            .with_created_at(SystemTime::UNIX_EPOCH)
+            .with_duration(Duration::from_secs(60))
+            .build()
+            .expect("Failed to build block");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_blocklist_resource(store.clone())]);
+
+        let send = |method: reqwest::Method, query: &str, body: Option<serde_json::Value>| {
+            let url = Url::parse(&format!("http://{}/peers/blocklist{}", bind_url, query))
+                .expect("Failed to parse URL");
+            let mut request = Client::new()
+                .request(method, url)
+                .header("Authorization", "custom")
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+            if let Some(body) = body {
+                request = request.json(&body);
+            }
+            request.send().expect("Failed to perform request")
+        };
+
+        let resp = send(
+            reqwest::Method::POST,
+            "",
+            Some(json!({
+                "kind": "ip",
+                "value": "10.0.0.1",
+                "reason": "Flooding connections",
+                "duration": 3600,
+            })),
+        );
+        assert_eq!(resp.status(), StatusCode::OK);
+        let block = store
+            .get_block(&BlockTarget::parse("ip", "10.0.0.1").expect("Failed to parse target"))
+            .expect("Failed to get block")
+            .expect("Block was not added");
+        assert_eq!(block.reason(), "Flooding connections");
+        assert_eq!(
+            block.expires_at(),
+            block.created_at() + Duration::from_secs(3600)
+        );
+
+        assert_eq!(
+            send(
+                reqwest::Method::POST,
+                "",
+                Some(json!({ "kind": "node_id", "value": "node_a" })),
+            )
+            .status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        store.add_block(&expired).expect("Failed to add block");
+
+        let resp = send(reqwest::Method::GET, "", None);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: ListBlockedPeersResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(response.data, vec![BlockedPeerResponse::from(&block)]);
+
+        let resp = send(reqwest::Method::GET, "?include_expired=true", None);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: ListBlockedPeersResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(
+            response.data,
+            vec![
+                BlockedPeerResponse::from(&expired),
+                BlockedPeerResponse::from(&block)
+            ]
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store() -> Box<dyn PeerBlocklistStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselPeerBlocklistStore::new(pool))
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,272 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /peers/blocklist/{kind}/{value}` for fetching the block of a public key or IP address
+//! * `DELETE /peers/blocklist/{kind}/{value}` for removing the block of a public key or IP address
+//!
+//! The kind is `public_key` or `ip`.
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::peer::blocklist::store::PeerBlocklistStore;
+use splinter::peer::blocklist::BlockTarget;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::BlockedPeerResponse;
+#[cfg(feature = "authorization")]
+use super::{PEER_READ_PERMISSION, PEER_WRITE_PERMISSION};
+
+const PEER_BLOCKLIST_TARGET_MIN: u32 = 2;
+
+pub fn make_blocklist_kind_value_resource(store: Box<dyn PeerBlocklistStore>) -> Resource {
+    let delete_store = store.clone();
+    let resource = Resource::build("/peers/blocklist/{kind}/{value}").add_request_guard(
+        ProtocolVersionRangeGuard::new(PEER_BLOCKLIST_TARGET_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, PEER_READ_PERMISSION, move |r, _| {
+                fetch_block(r, store.clone())
+            })
+            .add_method(Method::Delete, PEER_WRITE_PERMISSION, move |r, _| {
+                remove_block(r, delete_store.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |r, _| fetch_block(r, store.clone()))
+            .add_method(Method::Delete, move |r, _| {
+                remove_block(r, delete_store.clone())
+            })
+    }
+}
+
+/// Parses the block target from the request's path
+fn parse_target(request: &HttpRequest) -> Result<BlockTarget, HttpResponse> {
+    let kind = request.match_info().get("kind").unwrap_or("");
+    let value = request.match_info().get("value").unwrap_or("");
+    BlockTarget::parse(kind, value).map_err(|err| {
+        HttpResponse::BadRequest().json(ErrorResponse::bad_request(&err.to_string()))
+    })
+}
+
+fn fetch_block(
+    request: HttpRequest,
+    store: Box<dyn PeerBlocklistStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let target = match parse_target(&request) {
+        Ok(target) => target,
+        Err(response) => return Box::new(response.into_future()),
+    };
+    Box::new(web::block(move || store.get_block(&target)).then(|res| {
+        Ok(match res {
+            Ok(Some(block)) => HttpResponse::Ok().json(BlockedPeerResponse::from(&block)),
+            Ok(None) => HttpResponse::NotFound().json(ErrorResponse::not_found("Block not found")),
+            Err(err) => {
+                error!("Unable to fetch peer block: {}", err);
+                HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+            }
+        })
+    }))
+}
+
+fn remove_block(
+    request: HttpRequest,
+    store: Box<dyn PeerBlocklistStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let target = match parse_target(&request) {
+        Ok(target) => target,
+        Err(response) => return Box::new(response.into_future()),
+    };
+    Box::new(web::block(move || store.remove_block(&target)).then(|res| {
+        Ok(match res {
+            Ok(Some(_)) => HttpResponse::Ok().finish(),
+            Ok(None) => HttpResponse::NotFound().json(ErrorResponse::not_found("Block not found")),
+            Err(err) => {
+                error!("Unable to remove peer block: {}", err);
+                HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+            }
+        })
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::peer::blocklist::store::DieselPeerBlocklistStore;
+    use splinter::peer::blocklist::BlockedPeerBuilder;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a GET /peers/blocklist/{kind}/{value} request returns a block, that an invalid
+    /// value is rejected, and that a DELETE request removes the block.
+    fn test_fetch_and_remove_block() {
+        let store = create_store();
+        let block = BlockedPeerBuilder::new()
+            .with_target(
+                BlockTarget::parse("public_key", "0102ab").expect("Failed to parse target"),
+            )
+            .with_reason("Sent invalid messages".to_string())
+            .with_duration(Duration::from_secs(3600))
+            .build()
+            .expect("Failed to build block");
+        store.add_block(&block).expect("Failed to add block");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_blocklist_kind_value_resource(store.clone())]);
+
+        let send = |method: reqwest::Method, path: &str| {
+            let url = Url::parse(&format!("http://{}/peers/blocklist/{}", bind_url, path))
+                .expect("Failed to parse URL");
+            Client::new()
+                .request(method, url)
+                .header("Authorization", "custom")
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .send()
+                .expect("Failed to perform request")
+        };
+
+        let resp = send(reqwest::Method::GET, "public_key/0102ab");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: BlockedPeerResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(response, BlockedPeerResponse::from(&block));
+
+        assert_eq!(
+            send(reqwest::Method::GET, "ip/not-an-address").status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        assert_eq!(
+            send(reqwest::Method::DELETE, "public_key/0102ab").status(),
+            StatusCode::OK
+        );
+        assert_eq!(
+            store
+                .get_block(block.target())
+                .expect("Failed to get block"),
+            None
+        );
+        assert_eq!(
+            send(reqwest::Method::DELETE, "public_key/0102ab").status(),
+            StatusCode::NOT_FOUND
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store() -> Box<dyn PeerBlocklistStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselPeerBlocklistStore::new(pool))
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /peers/capabilities` endpoint for listing the capabilities
+//! advertised by all authorized peers, along with any metadata operators have attached to them.
+
+#[cfg(feature = "peer-metadata")]
+use std::collections::HashMap;
+
+#[cfg(feature = "peer-metadata")]
+use actix_web::web;
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+#[cfg(feature = "peer-metadata")]
+use splinter::peer::metadata::store::PeerMetadataStore;
+use splinter::peer::PeerCapabilitiesRegistry;
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+#[cfg(feature = "peer-metadata")]
+use splinter::rest_api::ErrorResponse;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "peer-metadata")]
+use super::resources::PeerMetadataResponse;
+use super::resources::{ListPeerCapabilitiesResponse, PeerCapabilitiesResponse};
+#[cfg(feature = "authorization")]
+use super::PEER_READ_PERMISSION;
+
+const PEER_LIST_CAPABILITIES_MIN: u32 = 2;
+
+pub fn make_capabilities_resource(
+    registry: PeerCapabilitiesRegistry,
+    #[cfg(feature = "peer-metadata")] metadata_store: Option<Box<dyn PeerMetadataStore>>,
+) -> Resource {
+    let resource = Resource::build("/peers/capabilities").add_request_guard(
+        ProtocolVersionRangeGuard::new(PEER_LIST_CAPABILITIES_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, PEER_READ_PERMISSION, move |_, _| {
+            list_capabilities(
+                &registry,
+                #[cfg(feature = "peer-metadata")]
+                metadata_store.clone(),
+            )
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| {
+            list_capabilities(
+                &registry,
+                #[cfg(feature = "peer-metadata")]
+                metadata_store.clone(),
+            )
+        })
+    }
+}
+
+fn list_capabilities(
+    registry: &PeerCapabilitiesRegistry,
+    #[cfg(feature = "peer-metadata")] metadata_store: Option<Box<dyn PeerMetadataStore>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let data: Vec<PeerCapabilitiesResponse> = registry
+        .list()
+        .iter()
+        .map(|(peer_id, capabilities)| PeerCapabilitiesResponse::new(peer_id, capabilities))
+        .collect();
+
+    #[cfg(feature = "peer-metadata")]
+    {
+        if let Some(store) = metadata_store {
+            return Box::new(web::block(move || store.list_metadata()).then(move |res| {
+                Ok(match res {
+                    Ok(metadata) => {
+                        let mut metadata = metadata
+                            .iter()
+                            .map(|metadata| {
+                                (
+                                    metadata.peer_id().to_string(),
+                                    PeerMetadataResponse::from(metadata),
+                                )
+                            })
+                            .collect::<HashMap<_, _>>();
+                        let data = data
+                            .into_iter()
+                            .map(|peer| {
+                                let peer_metadata = metadata.remove(&peer.peer_id);
+                                peer.with_metadata(peer_metadata)
+                            })
+                            .collect();
+                        HttpResponse::Ok().json(ListPeerCapabilitiesResponse { data })
+                    }
+                    Err(err) => {
+                        error!("Unable to list peer metadata: {}", err);
+                        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                    }
+                })
+            }));
+        }
+    }
+
+    Box::new(
+        HttpResponse::Ok()
+            .json(ListPeerCapabilitiesResponse { data })
+            .into_future(),
+    )
+}
@@ -0,0 +1,215 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /peers/capabilities/{peer_id}` endpoint for fetching the
+//! capabilities advertised by a specific peer.
+
+use actix_web::{Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::peer::PeerCapabilitiesRegistry;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::PeerCapabilitiesResponse;
+#[cfg(feature = "authorization")]
+use super::PEER_READ_PERMISSION;
+
+const PEER_FETCH_CAPABILITIES_MIN: u32 = 2;
+
+pub fn make_capabilities_peer_id_resource(registry: PeerCapabilitiesRegistry) -> Resource {
+    let resource = Resource::build("/peers/capabilities/{peer_id}").add_request_guard(
+        ProtocolVersionRangeGuard::new(PEER_FETCH_CAPABILITIES_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, PEER_READ_PERMISSION, move |r, _| {
+            fetch_capabilities(r, &registry)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| fetch_capabilities(r, &registry))
+    }
+}
+
+fn fetch_capabilities(
+    request: HttpRequest,
+    registry: &PeerCapabilitiesRegistry,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = request.match_info().get("peer_id").unwrap_or("");
+
+    let response = match registry
+        .list()
+        .iter()
+        .find(|(token, _)| token.id_as_string() == peer_id)
+    {
+        Some((token, capabilities)) => {
+            HttpResponse::Ok().json(PeerCapabilitiesResponse::new(token, capabilities))
+        }
+        None => HttpResponse::NotFound().json(ErrorResponse::not_found(&format!(
+            "No capabilities have been advertised by peer {}",
+            peer_id
+        ))),
+    };
+
+    Box::new(response.into_future())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::error::InternalError;
+    use splinter::peer::{NodeCapabilitiesBuilder, PeerAuthorizationToken};
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests a GET /peers/capabilities/{peer_id} request returns the capabilities advertised by
+    /// the peer.
+    fn test_fetch_capabilities_ok() {
+        let registry = PeerCapabilitiesRegistry::new();
+        let capabilities = NodeCapabilitiesBuilder::new()
+            .with_service_type("scabbard")
+            .with_protocol("admin", 2)
+            .with_consensus_engine("2pc")
+            .build();
+        let peer_id = PeerAuthorizationToken::from_peer_id("node_a");
+        registry.insert(peer_id.clone(), "connection_id", capabilities.clone());
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_capabilities_peer_id_resource(registry)]);
+
+        let url = Url::parse(&format!("http://{}/peers/capabilities/node_a", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: PeerCapabilitiesResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(
+            response,
+            PeerCapabilitiesResponse::new(&peer_id, &capabilities)
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[test]
+    /// Tests a GET /peers/capabilities/{peer_id} request returns NotFound when the peer has not
+    /// advertised any capabilities.
+    fn test_fetch_capabilities_not_found() {
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_capabilities_peer_id_resource(
+                PeerCapabilitiesRegistry::new(),
+            )]);
+
+        let url = Url::parse(&format!("http://{}/peers/capabilities/node_a", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
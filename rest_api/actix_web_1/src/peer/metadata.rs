@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /peers/metadata` endpoint for listing the metadata operators
+//! have attached to peers.
+
+use actix_web::{web, Error, HttpResponse};
+use futures::Future;
+
+use splinter::peer::metadata::store::PeerMetadataStore;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{ListPeerMetadataResponse, PeerMetadataResponse};
+#[cfg(feature = "authorization")]
+use super::PEER_READ_PERMISSION;
+
+const PEER_LIST_METADATA_MIN: u32 = 2;
+
+pub fn make_metadata_resource(store: Box<dyn PeerMetadataStore>) -> Resource {
+    let resource = Resource::build("/peers/metadata").add_request_guard(
+        ProtocolVersionRangeGuard::new(PEER_LIST_METADATA_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, PEER_READ_PERMISSION, move |_, _| {
+            list_metadata(store.clone())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| list_metadata(store.clone()))
+    }
+}
+
+fn list_metadata(
+    store: Box<dyn PeerMetadataStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(web::block(move || store.list_metadata()).then(|res| {
+        Ok(match res {
+            Ok(metadata) => HttpResponse::Ok().json(ListPeerMetadataResponse {
+                data: metadata.iter().map(PeerMetadataResponse::from).collect(),
+            }),
+            Err(err) => {
+                error!("Unable to list peer metadata: {}", err);
+                HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+            }
+        })
+    }))
+}
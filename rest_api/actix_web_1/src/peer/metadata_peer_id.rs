@@ -0,0 +1,349 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /peers/metadata/{peer_id}` for fetching the metadata attached to a peer
+//! * `PUT /peers/metadata/{peer_id}` for setting the metadata attached to a peer
+//! * `DELETE /peers/metadata/{peer_id}` for removing the metadata attached to a peer
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::peer::metadata::store::PeerMetadataStore;
+use splinter::peer::metadata::PeerMetadataBuilder;
+use splinter::rest_api::{
+    actix_web_1::{into_bytes, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{PeerMetadataResponse, PeerMetadataUpdate};
+#[cfg(feature = "authorization")]
+use super::{PEER_READ_PERMISSION, PEER_WRITE_PERMISSION};
+
+const PEER_METADATA_MIN: u32 = 2;
+
+pub fn make_metadata_peer_id_resource(store: Box<dyn PeerMetadataStore>) -> Resource {
+    let put_store = store.clone();
+    let delete_store = store.clone();
+    let resource = Resource::build("/peers/metadata/{peer_id}").add_request_guard(
+        ProtocolVersionRangeGuard::new(PEER_METADATA_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, PEER_READ_PERMISSION, move |r, _| {
+                fetch_metadata(r, store.clone())
+            })
+            .add_method(Method::Put, PEER_WRITE_PERMISSION, move |r, payload| {
+                set_metadata(r, payload, put_store.clone())
+            })
+            .add_method(Method::Delete, PEER_WRITE_PERMISSION, move |r, _| {
+                remove_metadata(r, delete_store.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |r, _| fetch_metadata(r, store.clone()))
+            .add_method(Method::Put, move |r, payload| {
+                set_metadata(r, payload, put_store.clone())
+            })
+            .add_method(Method::Delete, move |r, _| {
+                remove_metadata(r, delete_store.clone())
+            })
+    }
+}
+
+fn fetch_metadata(
+    request: HttpRequest,
+    store: Box<dyn PeerMetadataStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = request
+        .match_info()
+        .get("peer_id")
+        .unwrap_or("")
+        .to_string();
+    Box::new(
+        web::block(move || store.get_metadata(&peer_id)).then(|res| {
+            Ok(match res {
+                Ok(Some(metadata)) => {
+                    HttpResponse::Ok().json(PeerMetadataResponse::from(&metadata))
+                }
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("Peer metadata not found")),
+                Err(err) => {
+                    error!("Unable to fetch peer metadata: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn set_metadata(
+    request: HttpRequest,
+    payload: web::Payload,
+    store: Box<dyn PeerMetadataStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = request
+        .match_info()
+        .get("peer_id")
+        .unwrap_or("")
+        .to_string();
+    Box::new(into_bytes(payload).and_then(move |bytes| {
+        let update = match serde_json::from_slice::<PeerMetadataUpdate>(&bytes) {
+            Ok(update) => update,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&format!(
+                            "Failed to parse payload: {}",
+                            err
+                        )))
+                        .into_future(),
+                ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+            }
+        };
+
+        let metadata = match PeerMetadataBuilder::new()
+            .with_peer_id(peer_id)
+            .with_notes(update.notes)
+            .with_labels(update.labels)
+            .build()
+        {
+            Ok(metadata) => metadata,
+            Err(err) => {
+                return Box::new(
+                    HttpResponse::BadRequest()
+                        .json(ErrorResponse::bad_request(&err.to_string()))
+                        .into_future(),
+                )
+            }
+        };
+
+        Box::new(
+            web::block(move || store.set_metadata(&metadata).map(|_| metadata)).then(|res| {
+                Ok(match res {
+                    Ok(metadata) => HttpResponse::Ok().json(PeerMetadataResponse::from(&metadata)),
+                    Err(err) => {
+                        error!("Unable to set peer metadata: {}", err);
+                        HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                    }
+                })
+            }),
+        )
+    }))
+}
+
+fn remove_metadata(
+    request: HttpRequest,
+    store: Box<dyn PeerMetadataStore>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let peer_id = request
+        .match_info()
+        .get("peer_id")
+        .unwrap_or("")
+        .to_string();
+    Box::new(
+        web::block(move || store.remove_metadata(&peer_id)).then(|res| {
+            Ok(match res {
+                Ok(Some(_)) => HttpResponse::Ok().finish(),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("Peer metadata not found")),
+                Err(err) => {
+                    error!("Unable to remove peer metadata: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::error::InternalError;
+    use splinter::migrations::run_sqlite_migrations;
+    use splinter::peer::metadata::store::DieselPeerMetadataStore;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a PUT /peers/metadata/{peer_id} request sets the peer's notes and labels, that
+    /// a GET request returns them, that a label with an empty key is rejected, and that a DELETE
+    /// request removes them.
+    fn test_set_fetch_and_remove_metadata() {
+        let store = create_store();
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_metadata_peer_id_resource(store.clone())]);
+
+        let url = Url::parse(&format!("http://{}/peers/metadata/node_a", bind_url))
+            .expect("Failed to parse URL");
+        let send = |method: reqwest::Method, body: Option<serde_json::Value>| {
+            let mut request = Client::new()
+                .request(method, url.clone())
+                .header("Authorization", "custom")
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION);
+            if let Some(body) = body {
+                request = request.json(&body);
+            }
+            request.send().expect("Failed to perform request")
+        };
+
+        assert_eq!(
+            send(reqwest::Method::GET, None).status(),
+            StatusCode::NOT_FOUND
+        );
+
+        let resp = send(
+            reqwest::Method::PUT,
+            Some(json!({
+                "notes": "Maintenance window on Sundays",
+                "labels": { "contact": "ops@example.com", "ticket": "OPS-123" },
+            })),
+        );
+        assert_eq!(resp.status(), StatusCode::OK);
+        let metadata = store
+            .get_metadata("node_a")
+            .expect("Failed to get metadata")
+            .expect("Metadata was not set");
+        assert_eq!(metadata.notes(), "Maintenance window on Sundays");
+        assert_eq!(
+            metadata.labels().get("ticket").map(String::as_str),
+            Some("OPS-123")
+        );
+
+        let resp = send(reqwest::Method::GET, None);
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: PeerMetadataResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(response, PeerMetadataResponse::from(&metadata));
+
+        assert_eq!(
+            send(
+                reqwest::Method::PUT,
+                Some(json!({ "labels": { "": "no key" } }))
+            )
+            .status(),
+            StatusCode::BAD_REQUEST
+        );
+
+        assert_eq!(send(reqwest::Method::DELETE, None).status(), StatusCode::OK);
+        assert_eq!(
+            store
+                .get_metadata("node_a")
+                .expect("Failed to get metadata"),
+            None
+        );
+        assert_eq!(
+            send(reqwest::Method::DELETE, None).status(),
+            StatusCode::NOT_FOUND
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store() -> Box<dyn PeerMetadataStore> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Box::new(DieselPeerMetadataStore::new(pool))
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,196 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for inspecting the capabilities advertised by peers,
+//! for managing the metadata operators attach to peers, and for blocking misbehaving peers.
+
+#[cfg(feature = "peer-blocklist")]
+mod blocklist;
+#[cfg(feature = "peer-blocklist")]
+mod blocklist_kind_value;
+mod capabilities;
+mod capabilities_peer_id;
+#[cfg(feature = "peer-metadata")]
+mod metadata;
+#[cfg(feature = "peer-metadata")]
+mod metadata_peer_id;
+#[cfg(feature = "peer-ref-counts")]
+mod ref_counts;
+mod resources;
+
+#[cfg(feature = "peer-blocklist")]
+use splinter::peer::blocklist::store::PeerBlocklistStore;
+#[cfg(feature = "peer-metadata")]
+use splinter::peer::metadata::store::PeerMetadataStore;
+use splinter::peer::PeerCapabilitiesRegistry;
+#[cfg(feature = "peer-ref-counts")]
+use splinter::peer::PeerRefCounts;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+#[cfg(feature = "authorization")]
+const PEER_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "peer.read",
+    permission_display_name: "Peer read",
+    permission_description: "Allows the client to read the capabilities advertised by peers",
+};
+
+#[cfg(feature = "peer-blocklist")]
+pub use resources::{BlockedPeerResponse, ListBlockedPeersResponse};
+#[cfg(feature = "peer-metadata")]
+pub use resources::{ListPeerMetadataResponse, PeerMetadataResponse};
+
+#[cfg(all(
+    feature = "authorization",
+    any(
+        feature = "peer-blocklist",
+        feature = "peer-metadata",
+        feature = "peer-ref-counts"
+    )
+))]
+const PEER_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "peer.write",
+    permission_display_name: "Peer write",
+    permission_description: "Allows the client to change how the node manages its peers",
+};
+
+pub struct PeerCapabilitiesResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl PeerCapabilitiesResourceProvider {
+    pub fn new(registry: PeerCapabilitiesRegistry) -> Self {
+        let resources = vec![
+            capabilities::make_capabilities_resource(
+                registry.clone(),
+                #[cfg(feature = "peer-metadata")]
+                None,
+            ),
+            capabilities_peer_id::make_capabilities_peer_id_resource(registry),
+        ];
+        Self { resources }
+    }
+
+    /// Creates a provider whose peer listing includes the metadata attached to each peer.
+    #[cfg(feature = "peer-metadata")]
+    pub fn new_with_metadata_store(
+        registry: PeerCapabilitiesRegistry,
+        metadata_store: Box<dyn PeerMetadataStore>,
+    ) -> Self {
+        let resources = vec![
+            capabilities::make_capabilities_resource(registry.clone(), Some(metadata_store)),
+            capabilities_peer_id::make_capabilities_peer_id_resource(registry),
+        ];
+        Self { resources }
+    }
+}
+
+/// The `PeerCapabilitiesResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /peers/capabilities` - List the capabilities advertised by all authorized peers, with
+///   the metadata attached to each peer if the provider was created with a metadata store
+/// * `GET /peers/capabilities/{peer_id}` - Fetch the capabilities advertised by a specific peer
+impl RestResourceProvider for PeerCapabilitiesResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+#[cfg(feature = "peer-ref-counts")]
+pub struct PeerRefCountsResourceProvider {
+    resources: Vec<Resource>,
+}
+
+#[cfg(feature = "peer-ref-counts")]
+impl PeerRefCountsResourceProvider {
+    pub fn new(ref_counts: PeerRefCounts) -> Self {
+        let resources = vec![ref_counts::make_ref_counts_resource(ref_counts)];
+        Self { resources }
+    }
+}
+
+/// The `PeerRefCountsResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /peers/ref-counts` - Fetch whether reference counting is strict, and the number of
+///   dangling references that have been removed
+/// * `PUT /peers/ref-counts` - Change whether reference counting is strict
+#[cfg(feature = "peer-ref-counts")]
+impl RestResourceProvider for PeerRefCountsResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+#[cfg(feature = "peer-metadata")]
+pub struct PeerMetadataResourceProvider {
+    resources: Vec<Resource>,
+}
+
+#[cfg(feature = "peer-metadata")]
+impl PeerMetadataResourceProvider {
+    pub fn new(store: Box<dyn PeerMetadataStore>) -> Self {
+        let resources = vec![
+            metadata::make_metadata_resource(store.clone()),
+            metadata_peer_id::make_metadata_peer_id_resource(store),
+        ];
+        Self { resources }
+    }
+}
+
+/// The `PeerMetadataResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /peers/metadata` - List the metadata attached to peers
+/// * `GET /peers/metadata/{peer_id}` - Fetch the metadata attached to a peer
+/// * `PUT /peers/metadata/{peer_id}` - Set the notes and labels attached to a peer
+/// * `DELETE /peers/metadata/{peer_id}` - Remove the metadata attached to a peer
+#[cfg(feature = "peer-metadata")]
+impl RestResourceProvider for PeerMetadataResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
+
+#[cfg(feature = "peer-blocklist")]
+pub struct PeerBlocklistResourceProvider {
+    resources: Vec<Resource>,
+}
+
+#[cfg(feature = "peer-blocklist")]
+impl PeerBlocklistResourceProvider {
+    pub fn new(store: Box<dyn PeerBlocklistStore>) -> Self {
+        let resources = vec![
+            blocklist::make_blocklist_resource(store.clone()),
+            blocklist_kind_value::make_blocklist_kind_value_resource(store),
+        ];
+        Self { resources }
+    }
+}
+
+/// The `PeerBlocklistResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /peers/blocklist` - List the blocked public keys and IP addresses
+/// * `POST /peers/blocklist` - Block a public key or IP address for a duration
+/// * `GET /peers/blocklist/{kind}/{value}` - Fetch the block of a public key or IP address
+/// * `DELETE /peers/blocklist/{kind}/{value}` - Remove the block of a public key or IP address
+#[cfg(feature = "peer-blocklist")]
+impl RestResourceProvider for PeerBlocklistResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
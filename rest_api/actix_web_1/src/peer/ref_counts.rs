@@ -0,0 +1,232 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /peers/ref-counts` and `PUT /peers/ref-counts` endpoints for
+//! inspecting and changing how the peer manager counts references to peers.
+
+use actix_web::{web, Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::peer::PeerRefCounts;
+use splinter::rest_api::{
+    actix_web_1::{into_bytes, Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+#[cfg(feature = "authorization")]
+use super::{PEER_READ_PERMISSION, PEER_WRITE_PERMISSION};
+
+const PEER_REF_COUNTS_MIN: u32 = 2;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PeerRefCountsResponse {
+    pub strict_ref_counts: bool,
+    pub dangling_ref_removals: u64,
+}
+
+impl From<&PeerRefCounts> for PeerRefCountsResponse {
+    fn from(ref_counts: &PeerRefCounts) -> Self {
+        Self {
+            strict_ref_counts: ref_counts.strict_ref_counts(),
+            dangling_ref_removals: ref_counts.dangling_ref_removals(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PeerRefCountsUpdate {
+    strict_ref_counts: bool,
+}
+
+pub fn make_ref_counts_resource(ref_counts: PeerRefCounts) -> Resource {
+    let put_ref_counts = ref_counts.clone();
+    let resource = Resource::build("/peers/ref-counts").add_request_guard(
+        ProtocolVersionRangeGuard::new(PEER_REF_COUNTS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource
+            .add_method(Method::Get, PEER_READ_PERMISSION, move |_, _| {
+                get_ref_counts(&ref_counts)
+            })
+            .add_method(Method::Put, PEER_WRITE_PERMISSION, move |_, payload| {
+                update_ref_counts(payload, put_ref_counts.clone())
+            })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource
+            .add_method(Method::Get, move |_, _| get_ref_counts(&ref_counts))
+            .add_method(Method::Put, move |_, payload| {
+                update_ref_counts(payload, put_ref_counts.clone())
+            })
+    }
+}
+
+fn get_ref_counts(
+    ref_counts: &PeerRefCounts,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(
+        HttpResponse::Ok()
+            .json(PeerRefCountsResponse::from(ref_counts))
+            .into_future(),
+    )
+}
+
+fn update_ref_counts(
+    payload: web::Payload,
+    ref_counts: PeerRefCounts,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(into_bytes(payload).and_then(move |bytes| {
+        match serde_json::from_slice::<PeerRefCountsUpdate>(&bytes) {
+            Ok(update) => {
+                ref_counts.set_strict_ref_counts(update.strict_ref_counts);
+                HttpResponse::Ok()
+                    .json(PeerRefCountsResponse::from(&ref_counts))
+                    .into_future()
+            }
+            Err(err) => HttpResponse::BadRequest()
+                .json(ErrorResponse::bad_request(&format!(
+                    "Failed to parse payload: {}",
+                    err
+                )))
+                .into_future(),
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use splinter::error::InternalError;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a PUT /peers/ref-counts request changes whether reference counting is strict,
+    /// and that a GET /peers/ref-counts request returns the updated setting.
+    fn test_update_ref_counts() {
+        let ref_counts = PeerRefCounts::new(true);
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_ref_counts_resource(ref_counts.clone())]);
+
+        let url = Url::parse(&format!("http://{}/peers/ref-counts", bind_url))
+            .expect("Failed to parse URL");
+        let resp = Client::new()
+            .put(url.clone())
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .json(&serde_json::json!({ "strict_ref_counts": false }))
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert!(!ref_counts.strict_ref_counts());
+
+        let resp = Client::new()
+            .get(url)
+            .header("Authorization", "custom")
+            .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+            .send()
+            .expect("Failed to perform request");
+
+        assert_eq!(resp.status(), StatusCode::OK);
+        let response: PeerRefCountsResponse = resp.json().expect("Failed to deserialize body");
+        assert_eq!(
+            response,
+            PeerRefCountsResponse {
+                strict_ref_counts: false,
+                dangling_ref_removals: 0,
+            }
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,150 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+#[cfg(any(feature = "peer-blocklist", feature = "peer-metadata"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "peer-blocklist")]
+use splinter::peer::blocklist::BlockedPeer;
+#[cfg(feature = "peer-metadata")]
+use splinter::peer::metadata::PeerMetadata;
+use splinter::peer::{NodeCapabilities, PeerAuthorizationToken};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PeerCapabilitiesResponse {
+    pub peer_id: String,
+    pub service_types: Vec<String>,
+    pub protocols: BTreeMap<String, u32>,
+    pub consensus_engines: Vec<String>,
+    /// The metadata operators have attached to the peer, if any
+    #[cfg(feature = "peer-metadata")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<PeerMetadataResponse>,
+}
+
+impl PeerCapabilitiesResponse {
+    pub fn new(peer_id: &PeerAuthorizationToken, capabilities: &NodeCapabilities) -> Self {
+        Self {
+            peer_id: peer_id.id_as_string(),
+            service_types: capabilities.service_types().to_vec(),
+            protocols: capabilities.protocols().clone(),
+            consensus_engines: capabilities.consensus_engines().to_vec(),
+            #[cfg(feature = "peer-metadata")]
+            metadata: None,
+        }
+    }
+
+    #[cfg(feature = "peer-metadata")]
+    pub fn with_metadata(mut self, metadata: Option<PeerMetadataResponse>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListPeerCapabilitiesResponse {
+    pub data: Vec<PeerCapabilitiesResponse>,
+}
+
+#[cfg(feature = "peer-metadata")]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PeerMetadataResponse {
+    pub peer_id: String,
+    pub notes: String,
+    pub labels: BTreeMap<String, String>,
+    /// The time the metadata was last changed, in seconds since the Unix epoch
+    pub updated_at: u64,
+}
+
+#[cfg(feature = "peer-metadata")]
+impl From<&PeerMetadata> for PeerMetadataResponse {
+    fn from(metadata: &PeerMetadata) -> Self {
+        Self {
+            peer_id: metadata.peer_id().to_string(),
+            notes: metadata.notes().to_string(),
+            labels: metadata.labels().clone(),
+            updated_at: to_seconds(metadata.updated_at()),
+        }
+    }
+}
+
+#[cfg(feature = "peer-metadata")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListPeerMetadataResponse {
+    pub data: Vec<PeerMetadataResponse>,
+}
+
+/// The body of a request to set a peer's metadata; any existing notes and labels are replaced
+#[cfg(feature = "peer-metadata")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PeerMetadataUpdate {
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+}
+
+#[cfg(feature = "peer-blocklist")]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlockedPeerResponse {
+    /// The kind of the blocked target, `public_key` or `ip`
+    pub kind: String,
+    pub value: String,
+    pub reason: String,
+    /// The time the block was added, in seconds since the Unix epoch
+    pub created_at: u64,
+    /// The time the block expires, in seconds since the Unix epoch
+    pub expires_at: u64,
+}
+
+#[cfg(feature = "peer-blocklist")]
+impl From<&BlockedPeer> for BlockedPeerResponse {
+    fn from(block: &BlockedPeer) -> Self {
+        Self {
+            kind: block.target().kind().to_string(),
+            value: block.target().value(),
+            reason: block.reason().to_string(),
+            created_at: to_seconds(block.created_at()),
+            expires_at: to_seconds(block.expires_at()),
+        }
+    }
+}
+
+#[cfg(feature = "peer-blocklist")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListBlockedPeersResponse {
+    pub data: Vec<BlockedPeerResponse>,
+}
+
+/// The body of a request to block a public key or IP address; an existing block of the same
+/// target is replaced
+#[cfg(feature = "peer-blocklist")]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct NewBlockedPeer {
+    pub kind: String,
+    pub value: String,
+    #[serde(default)]
+    pub reason: String,
+    /// How long the block lasts, in seconds; defaults to 24 hours
+    #[serde(default)]
+    pub duration: Option<u64>,
+}
+
+#[cfg(any(feature = "peer-blocklist", feature = "peer-metadata"))]
+fn to_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
@@ -19,11 +19,18 @@ mod nodes;
 mod nodes_identity;
 mod resources;
 
+use std::convert::TryFrom;
+#[cfg(feature = "registry-signed-entries")]
+use std::sync::Arc;
+
+#[cfg(feature = "registry-signed-entries")]
+use splinter::registry::NodeSignatureVerifier;
+use splinter::registry::{Node, RwRegistry};
 use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
 #[cfg(feature = "authorization")]
 use splinter::rest_api::auth::authorization::Permission;
 
-use splinter::registry::RwRegistry;
+use resources::nodes::NewNode;
 
 #[cfg(feature = "authorization")]
 const REGISTRY_READ_PERMISSION: Permission = Permission::Check {
@@ -44,14 +51,131 @@ pub struct RwRegistryRestResourceProvider {
 
 impl RwRegistryRestResourceProvider {
     pub fn new(registry: &dyn RwRegistry) -> Self {
+        Self::with_validator(registry, NodeValidator::default())
+    }
+
+    /// Creates a provider whose add and replace requests must be signed by one of the node's
+    /// keys. A request that replaces or deletes an existing node must be signed by one of the
+    /// existing node's keys, so that a node's keys can only be changed by the node's owner.
+    #[cfg(feature = "registry-signed-entries")]
+    pub fn with_signature_verifier(
+        registry: &dyn RwRegistry,
+        signature_verifier: NodeSignatureVerifier,
+    ) -> Self {
+        Self::with_validator(
+            registry,
+            NodeValidator {
+                signature_verifier: Some(Arc::new(signature_verifier)),
+            },
+        )
+    }
+
+    fn with_validator(registry: &dyn RwRegistry, validator: NodeValidator) -> Self {
         let resources = vec![
-            nodes_identity::make_nodes_identity_resource(registry.clone_box()),
-            nodes::make_nodes_resource(registry.clone_box()),
+            nodes_identity::make_nodes_identity_resource(registry.clone_box(), validator.clone()),
+            nodes::make_nodes_resource(registry.clone_box(), validator),
         ];
         Self { resources }
     }
 }
 
+/// Converts the nodes in add and replace requests, and checks delete requests, verifying their
+/// signatures if signed entries are required
+#[derive(Clone, Default)]
+struct NodeValidator {
+    #[cfg(feature = "registry-signed-entries")]
+    signature_verifier: Option<Arc<NodeSignatureVerifier>>,
+}
+
+impl NodeValidator {
+    /// Returns whether the existing node must be provided when a node is replaced
+    fn requires_existing_node(&self) -> bool {
+        #[cfg(feature = "registry-signed-entries")]
+        {
+            self.signature_verifier.is_some()
+        }
+        #[cfg(not(feature = "registry-signed-entries"))]
+        {
+            false
+        }
+    }
+
+    /// Converts a node from a request.
+    ///
+    /// # Arguments
+    ///
+    /// * `new_node` - The node from the request
+    /// * `existing_node` - The node being replaced, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing why the node is invalid.
+    #[cfg_attr(
+        not(feature = "registry-signed-entries"),
+        allow(unused_mut, unused_variables)
+    )]
+    fn to_node(&self, mut new_node: NewNode, existing_node: Option<&Node>) -> Result<Node, String> {
+        #[cfg(feature = "registry-signed-entries")]
+        let signature = new_node.signature.take();
+        let node = Node::try_from(new_node).map_err(|err| err.to_string())?;
+
+        #[cfg(feature = "registry-signed-entries")]
+        if let Some(signature_verifier) = &self.signature_verifier {
+            let signature =
+                signature.ok_or_else(|| format!("node {} must be signed", node.identity()))?;
+            signature_verifier
+                .verify(&node, &signature.public_key, &signature.signature)
+                .map_err(|err| err.to_string())?;
+            if let Some(existing_node) = existing_node {
+                if !existing_node.has_key(&signature.public_key) {
+                    return Err(format!(
+                        "node {} must be signed by one of its current keys",
+                        node.identity()
+                    ));
+                }
+            }
+        }
+
+        Ok(node)
+    }
+
+    /// Checks a request to delete a node.
+    ///
+    /// # Arguments
+    ///
+    /// * `node` - The node being deleted
+    /// * `public_key` - The public key that signed the node's deletion, if any
+    /// * `signature` - The request's signature of the node's deletion, if any
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing why the node may not be deleted.
+    #[cfg_attr(not(feature = "registry-signed-entries"), allow(unused_variables))]
+    fn check_deletion(
+        &self,
+        node: &Node,
+        public_key: Option<&str>,
+        signature: Option<&str>,
+    ) -> Result<(), String> {
+        #[cfg(feature = "registry-signed-entries")]
+        if let Some(signature_verifier) = &self.signature_verifier {
+            match (public_key, signature) {
+                (Some(public_key), Some(signature)) => signature_verifier
+                    .verify_deletion(node, public_key, signature)
+                    .map_err(|err| err.to_string())?,
+                _ => {
+                    return Err(format!(
+                        "deleting node {} must be signed by one of its keys",
+                        node.identity()
+                    ))
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
 /// The `RwRegistryRestResourceProvider` struct provides the following endpoints
 /// as REST API resources:
 ///
@@ -65,3 +189,87 @@ impl RestResourceProvider for RwRegistryRestResourceProvider {
         self.resources.clone()
     }
 }
+
+#[cfg(all(test, feature = "registry-signed-entries"))]
+mod tests {
+    use super::*;
+
+    use std::collections::HashMap;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+    use splinter::registry::node_signing_bytes;
+
+    use resources::nodes::NodeSignature;
+
+    /// Verify that, when signatures are required, unsigned nodes are rejected, signed nodes are
+    /// accepted, and a node that replaces an existing node must be signed by one of the existing
+    /// node's keys.
+    #[test]
+    fn test_node_validator_signatures() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = to_hex(
+            signer
+                .public_key()
+                .expect("Failed to get public key")
+                .as_slice(),
+        );
+
+        let validator = NodeValidator {
+            signature_verifier: Some(Arc::new(NodeSignatureVerifier::new(context.new_verifier()))),
+        };
+
+        assert!(validator
+            .to_node(new_node(&public_key, None), None)
+            .is_err());
+
+        let node = validator
+            .to_node(
+                new_node(&public_key, Some(sign(&*signer, &public_key))),
+                None,
+            )
+            .expect("Signed node was rejected");
+        assert_eq!(node.identity(), "Node-123");
+
+        let existing_node = Node::builder("Node-123")
+            .with_endpoint("12.0.0.123:8431")
+            .with_display_name("Bitwise IO - Node 1")
+            .with_key("0123")
+            .build()
+            .expect("Failed to build node");
+        assert!(validator
+            .to_node(
+                new_node(&public_key, Some(sign(&*signer, &public_key))),
+                Some(&existing_node),
+            )
+            .is_err());
+    }
+
+    fn new_node(public_key: &str, signature: Option<NodeSignature>) -> NewNode {
+        NewNode {
+            identity: "Node-123".into(),
+            endpoints: vec!["12.0.0.123:8431".into()],
+            display_name: "Bitwise IO - Node 1".into(),
+            keys: vec![public_key.into()],
+            metadata: HashMap::new(),
+            signature,
+        }
+    }
+
+    fn sign(signer: &dyn Signer, public_key: &str) -> NodeSignature {
+        let node = Node::try_from(new_node(public_key, None)).expect("Failed to build node");
+        NodeSignature {
+            public_key: public_key.into(),
+            signature: to_hex(
+                signer
+                    .sign(&node_signing_bytes(&node))
+                    .expect("Failed to sign node")
+                    .as_slice(),
+            ),
+        }
+    }
+
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
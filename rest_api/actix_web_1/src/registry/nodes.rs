@@ -18,13 +18,12 @@
 //! * `POST /registry/nodes` for adding a node to the registry
 
 use std::collections::HashMap;
-use std::convert::TryFrom;
 use std::fmt::Write as _;
 
 use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, stream::Stream, Future};
 use splinter::error::InvalidStateError;
-use splinter::registry::{MetadataPredicate, Node, RegistryReader, RegistryWriter, RwRegistry};
+use splinter::registry::{MetadataPredicate, RegistryReader, RegistryWriter, RwRegistry};
 use splinter::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
     paging::{PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET},
@@ -34,6 +33,7 @@ use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
 
 use super::error::RegistryRestApiError;
 use super::resources::nodes::{ListNodesResponse, NewNode, NodeResponse};
+use super::NodeValidator;
 #[cfg(feature = "authorization")]
 use super::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
 
@@ -41,7 +41,10 @@ const REGISTRY_LIST_NODES_MIN: u32 = 1;
 
 type Filter = HashMap<String, (String, String)>;
 
-pub fn make_nodes_resource(registry: Box<dyn RwRegistry>) -> Resource {
+pub(super) fn make_nodes_resource(
+    registry: Box<dyn RwRegistry>,
+    validator: NodeValidator,
+) -> Resource {
     let registry1 = registry.clone();
     let resource = Resource::build("/registry/nodes").add_request_guard(
         ProtocolVersionRangeGuard::new(REGISTRY_LIST_NODES_MIN, SPLINTER_PROTOCOL_VERSION),
@@ -53,7 +56,7 @@ pub fn make_nodes_resource(registry: Box<dyn RwRegistry>) -> Resource {
                 list_nodes(r, web::Data::new(registry.clone_box_as_reader()))
             })
             .add_method(Method::Post, REGISTRY_WRITE_PERMISSION, move |_, p| {
-                add_node(p, web::Data::new(registry1.clone()))
+                add_node(p, web::Data::new(registry1.clone()), validator.clone())
             })
     }
     #[cfg(not(feature = "authorization"))]
@@ -63,7 +66,7 @@ pub fn make_nodes_resource(registry: Box<dyn RwRegistry>) -> Resource {
                 list_nodes(r, web::Data::new(registry.clone_box_as_reader()))
             })
             .add_method(Method::Post, move |_, p| {
-                add_node(p, web::Data::new(registry1.clone()))
+                add_node(p, web::Data::new(registry1.clone()), validator.clone())
             })
     }
 }
@@ -238,6 +241,7 @@ fn to_predicates(filters: Option<Filter>) -> Result<Vec<MetadataPredicate>, Stri
 fn add_node(
     payload: web::Payload,
     registry: web::Data<Box<dyn RwRegistry>>,
+    validator: NodeValidator,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     Box::new(
         payload
@@ -247,10 +251,10 @@ fn add_node(
                 Ok::<_, Error>(body)
             })
             .into_future()
-            .and_then(move |body| match serde_json::from_slice::<NewNode>(&body) {
+            .and_then(move |body| match NewNode::from_json(&body) {
                 Ok(node) => Box::new(
                     web::block(move || {
-                        let new_node = Node::try_from(node).map_err(|err| {
+                        let new_node = validator.to_node(node, None).map_err(|err| {
                             RegistryRestApiError::InvalidStateError(
                                 InvalidStateError::with_message(format!(
                                     "Failed to add node, node is invalid: {}",
@@ -302,7 +306,7 @@ mod tests {
 
     use splinter::error::InternalError;
     use splinter::error::InvalidStateError;
-    use splinter::registry::{NodeIter, RegistryError};
+    use splinter::registry::{Node, NodeIter, RegistryError};
     use splinter::rest_api::actix_web_1::AuthConfig;
     use splinter::rest_api::auth::authorization::{
         AuthorizationHandler, AuthorizationHandlerResult,
@@ -317,9 +321,11 @@ mod tests {
     #[test]
     /// Tests a GET /registry/nodes request with no filters returns the expected nodes.
     fn test_list_nodes_ok() {
-        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
-            make_nodes_resource(Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()]))),
-        ]);
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_nodes_resource(
+                Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()])),
+                NodeValidator::default(),
+            )]);
 
         let url = Url::parse(&format!("http://{}/registry/nodes", bind_url))
             .expect("Failed to parse URL");
@@ -372,9 +378,11 @@ mod tests {
     #[test]
     /// Tests a GET /registry/nodes request with filters returns the expected node.
     fn test_list_nodes_with_filters_ok() {
-        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
-            make_nodes_resource(Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()]))),
-        ]);
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_nodes_resource(
+                Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()])),
+                NodeValidator::default(),
+            )]);
 
         let filter = percent_encode_filter_query("{\"company\":[\"=\",\"Bitwise IO\"]}");
         let url = Url::parse(&format!(
@@ -420,9 +428,11 @@ mod tests {
     #[test]
     /// Tests a GET /registry/nodes request with invalid filter returns BadRequest response.
     fn test_list_node_with_filters_bad_request() {
-        let (shutdown_handle, join_handle, bind_url) = run_rest_api_on_open_port(vec![
-            make_nodes_resource(Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()]))),
-        ]);
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_nodes_resource(
+                Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()])),
+                NodeValidator::default(),
+            )]);
 
         let filter = percent_encode_filter_query("{\"company\":[\"*\",\"Bitwise IO\"]}");
         let url = Url::parse(&format!(
@@ -449,7 +459,10 @@ mod tests {
     /// Test the POST /registry/nodes route for adding a node to the registry.
     fn test_add_node() {
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_nodes_resource(Box::new(MemRegistry::default()))]);
+            run_rest_api_on_open_port(vec![make_nodes_resource(
+                Box::new(MemRegistry::default()),
+                NodeValidator::default(),
+            )]);
 
         // Verify an invalid node gets a BAD_REQUEST response
         let url = Url::parse(&format!("http://{}/registry/nodes", bind_url))
@@ -562,6 +575,8 @@ mod tests {
             display_name: "Bitwise IO - Node 1".into(),
             keys: vec!["0123".into()],
             metadata,
+            #[cfg(feature = "registry-signed-entries")]
+            signature: None,
         }
     }
 
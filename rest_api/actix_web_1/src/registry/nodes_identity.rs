@@ -16,15 +16,17 @@
 //!
 //! * `GET /registry/nodes/{identity}` for fetching a node in the registry
 //! * `PUT /registry/nodes/{identity}` for replacing a node in the registry
-//! * `DELETE /registry/nodes/{identity}` for deleting a node from the registry
+//! * `DELETE /registry/nodes/{identity}` for deleting a node from the registry; when signed
+//!   entries are required, the `public_key` and `signature` query parameters must hold one of
+//!   the node's keys and its signature of the node's deletion
 
-use std::convert::TryFrom;
+use std::collections::HashMap;
 
 use actix_web::{error::BlockingError, web, Error, HttpRequest, HttpResponse};
 use futures::{future::IntoFuture, stream::Stream, Future};
 
 use splinter::error::InvalidStateError;
-use splinter::registry::{Node, RegistryReader, RegistryWriter, RwRegistry};
+use splinter::registry::{RegistryReader, RegistryWriter, RwRegistry};
 use splinter::rest_api::{
     actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
     ErrorResponse,
@@ -33,14 +35,19 @@ use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
 
 use super::error::RegistryRestApiError;
 use super::resources::nodes_identity::{NewNode, NodeResponse};
+use super::NodeValidator;
 #[cfg(feature = "authorization")]
 use super::{REGISTRY_READ_PERMISSION, REGISTRY_WRITE_PERMISSION};
 
 const REGISTRY_FETCH_NODE_MIN: u32 = 1;
 
-pub fn make_nodes_identity_resource(registry: Box<dyn RwRegistry>) -> Resource {
+pub(super) fn make_nodes_identity_resource(
+    registry: Box<dyn RwRegistry>,
+    validator: NodeValidator,
+) -> Resource {
     let registry1 = registry.clone();
     let registry2 = registry.clone();
+    let validator1 = validator.clone();
     let resource = Resource::build("/registry/nodes/{identity}").add_request_guard(
         ProtocolVersionRangeGuard::new(REGISTRY_FETCH_NODE_MIN, SPLINTER_PROTOCOL_VERSION),
     );
@@ -51,10 +58,10 @@ pub fn make_nodes_identity_resource(registry: Box<dyn RwRegistry>) -> Resource {
                 fetch_node(r, web::Data::new(registry.clone_box_as_reader()))
             })
             .add_method(Method::Put, REGISTRY_WRITE_PERMISSION, move |r, p| {
-                put_node(r, p, web::Data::new(registry1.clone_box_as_writer()))
+                put_node(r, p, web::Data::new(registry1.clone()), validator.clone())
             })
             .add_method(Method::Delete, REGISTRY_WRITE_PERMISSION, move |r, _| {
-                delete_node(r, web::Data::new(registry2.clone_box_as_writer()))
+                delete_node(r, web::Data::new(registry2.clone()), validator1.clone())
             })
     }
     #[cfg(not(feature = "authorization"))]
@@ -64,10 +71,10 @@ pub fn make_nodes_identity_resource(registry: Box<dyn RwRegistry>) -> Resource {
                 fetch_node(r, web::Data::new(registry.clone_box_as_reader()))
             })
             .add_method(Method::Put, move |r, p| {
-                put_node(r, p, web::Data::new(registry1.clone_box_as_writer()))
+                put_node(r, p, web::Data::new(registry1.clone()), validator.clone())
             })
             .add_method(Method::Delete, move |r, _| {
-                delete_node(r, web::Data::new(registry2.clone_box_as_writer()))
+                delete_node(r, web::Data::new(registry2.clone()), validator1.clone())
             })
     }
 }
@@ -105,7 +112,8 @@ fn fetch_node(
 fn put_node(
     request: HttpRequest,
     payload: web::Payload,
-    registry: web::Data<Box<dyn RegistryWriter>>,
+    registry: web::Data<Box<dyn RwRegistry>>,
+    validator: NodeValidator,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let path_identity = request
         .match_info()
@@ -120,48 +128,58 @@ fn put_node(
                 Ok::<_, Error>(body)
             })
             .into_future()
-            .and_then(move |body| match serde_json::from_slice::<NewNode>(&body) {
-                Ok(node) => Box::new(
-                    web::block(move || {
-                        let update_node = Node::try_from(node).map_err(|err| {
-                            RegistryRestApiError::InvalidStateError(
-                                InvalidStateError::with_message(format!(
-                                    "Failed to update node, node is invalid: {}",
-                                    err
-                                )),
-                            )
-                        })?;
-
-                        if update_node.identity() != path_identity {
-                            Err(RegistryRestApiError::InvalidStateError(
-                                InvalidStateError::with_message(format!(
-                                    "Node identity cannot be changed: {}",
-                                    update_node.identity()
-                                )),
-                            ))
-                        } else {
-                            registry
-                                .update_node(update_node)
-                                .map_err(RegistryRestApiError::from)
-                        }
-                    })
-                    .then(|res| {
-                        Ok(match res {
-                            Ok(_) => HttpResponse::Ok().finish(),
-                            Err(BlockingError::Error(RegistryRestApiError::InvalidStateError(
-                                err,
-                            ))) => HttpResponse::BadRequest().json(ErrorResponse::bad_request(
-                                &format!("Invalid node: {}", err),
-                            )),
-                            Err(err) => {
-                                error!("Unable to put node: {}", err);
-                                HttpResponse::InternalServerError()
-                                    .json(ErrorResponse::internal_error())
+            .and_then(move |body| match NewNode::from_json(&body) {
+                Ok(node) => {
+                    Box::new(
+                        web::block(move || {
+                            let existing_node = if validator.requires_existing_node() {
+                                registry
+                                    .get_node(&path_identity)
+                                    .map_err(RegistryRestApiError::from)?
+                            } else {
+                                None
+                            };
+                            let update_node = validator
+                                .to_node(node, existing_node.as_ref())
+                                .map_err(|err| {
+                                    RegistryRestApiError::InvalidStateError(
+                                        InvalidStateError::with_message(format!(
+                                            "Failed to update node, node is invalid: {}",
+                                            err
+                                        )),
+                                    )
+                                })?;
+
+                            if update_node.identity() != path_identity {
+                                Err(RegistryRestApiError::InvalidStateError(
+                                    InvalidStateError::with_message(format!(
+                                        "Node identity cannot be changed: {}",
+                                        update_node.identity()
+                                    )),
+                                ))
+                            } else {
+                                registry
+                                    .update_node(update_node)
+                                    .map_err(RegistryRestApiError::from)
                             }
                         })
-                    }),
-                )
-                    as Box<dyn Future<Item = HttpResponse, Error = Error>>,
+                        .then(|res| {
+                            Ok(match res {
+                                Ok(_) => HttpResponse::Ok().finish(),
+                                Err(BlockingError::Error(
+                                    RegistryRestApiError::InvalidStateError(err),
+                                )) => HttpResponse::BadRequest().json(ErrorResponse::bad_request(
+                                    &format!("Invalid node: {}", err),
+                                )),
+                                Err(err) => {
+                                    error!("Unable to put node: {}", err);
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                }
+                            })
+                        }),
+                    ) as Box<dyn Future<Item = HttpResponse, Error = Error>>
+                }
                 Err(err) => Box::new(
                     HttpResponse::BadRequest()
                         .json(ErrorResponse::bad_request(&format!(
@@ -176,15 +194,47 @@ fn put_node(
 
 fn delete_node(
     request: HttpRequest,
-    registry: web::Data<Box<dyn RegistryWriter>>,
+    registry: web::Data<Box<dyn RwRegistry>>,
+    validator: NodeValidator,
 ) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
     let identity = request
         .match_info()
         .get("identity")
         .unwrap_or("")
         .to_string();
+    let query = match web::Query::<HashMap<String, String>>::from_query(request.query_string()) {
+        Ok(query) => query.into_inner(),
+        Err(_) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request("Invalid query"))
+                    .into_future(),
+            )
+        }
+    };
     Box::new(
         web::block(move || {
+            if validator.requires_existing_node() {
+                let existing_node = match registry
+                    .get_node(&identity)
+                    .map_err(RegistryRestApiError::from)?
+                {
+                    Some(node) => node,
+                    None => return Ok(None),
+                };
+                validator
+                    .check_deletion(
+                        &existing_node,
+                        query.get("public_key").map(String::as_str),
+                        query.get("signature").map(String::as_str),
+                    )
+                    .map_err(|err| {
+                        RegistryRestApiError::InvalidStateError(InvalidStateError::with_message(
+                            format!("Failed to delete node: {}", err),
+                        ))
+                    })?;
+            }
+
             registry
                 .delete_node(&identity)
                 .map_err(RegistryRestApiError::from)
@@ -195,6 +245,9 @@ fn delete_node(
                 Ok(None) => {
                     HttpResponse::NotFound().json(ErrorResponse::not_found("Node not found"))
                 }
+                Err(BlockingError::Error(RegistryRestApiError::InvalidStateError(err))) => {
+                    HttpResponse::BadRequest().json(ErrorResponse::bad_request(&err.to_string()))
+                }
                 Err(err) => {
                     error!("Unable to delete node: {}", err);
                     HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
@@ -215,7 +268,7 @@ mod tests {
 
     use splinter::error::InternalError;
     use splinter::error::InvalidStateError;
-    use splinter::registry::{MetadataPredicate, NodeIter, RegistryError};
+    use splinter::registry::{MetadataPredicate, Node, NodeIter, RegistryError};
     use splinter::rest_api::actix_web_1::AuthConfig;
     use splinter::rest_api::actix_web_1::{RestApiBuilder, RestApiShutdownHandle};
     use splinter::rest_api::auth::authorization::{
@@ -228,9 +281,10 @@ mod tests {
     /// Tests a GET /registry/nodes/{identity} request returns the expected node.
     fn test_fetch_node_ok() {
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_nodes_identity_resource(Box::new(
-                MemRegistry::new(vec![get_node_1(), get_node_2()]),
-            ))]);
+            run_rest_api_on_open_port(vec![make_nodes_identity_resource(
+                Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()])),
+                NodeValidator::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/registry/nodes/{}",
@@ -263,9 +317,10 @@ mod tests {
     /// passed.
     fn test_fetch_node_not_found() {
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_nodes_identity_resource(Box::new(
-                MemRegistry::new(vec![get_node_1(), get_node_2()]),
-            ))]);
+            run_rest_api_on_open_port(vec![make_nodes_identity_resource(
+                Box::new(MemRegistry::new(vec![get_node_1(), get_node_2()])),
+                NodeValidator::default(),
+            )]);
 
         let url = Url::parse(&format!(
             "http://{}/registry/nodes/Node-not-valid",
@@ -291,9 +346,10 @@ mod tests {
     /// Test the PUT /registry/nodes/{identity} route for adding or updating a node in the registry.
     fn test_put_node() {
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_nodes_identity_resource(Box::new(
-                MemRegistry::new(vec![get_node_1()]),
-            ))]);
+            run_rest_api_on_open_port(vec![make_nodes_identity_resource(
+                Box::new(MemRegistry::new(vec![get_node_1()])),
+                NodeValidator::default(),
+            )]);
 
         // Verify no body (i.e. no updated Node) gets a BAD_REQUEST response
         let url = Url::parse(&format!(
@@ -373,9 +429,10 @@ mod tests {
     /// Test the DELETE /registry/nodes/{identity} route for deleting a node from the registry.
     fn test_delete_node() {
         let (shutdown_handle, join_handle, bind_url) =
-            run_rest_api_on_open_port(vec![make_nodes_identity_resource(Box::new(
-                MemRegistry::new(vec![get_node_1()]),
-            ))]);
+            run_rest_api_on_open_port(vec![make_nodes_identity_resource(
+                Box::new(MemRegistry::new(vec![get_node_1()])),
+                NodeValidator::default(),
+            )]);
 
         // Verify that an existing node gets an OK response
         let url = Url::parse(&format!(
@@ -409,6 +466,123 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    /// Test that, when signed entries are required, the DELETE /registry/nodes/{identity} route
+    /// only deletes a node if the deletion is signed by one of the node's keys.
+    ///
+    /// 1. Add a node with the key of a signer to the registry
+    /// 2. Verify that an unsigned deletion gets a BAD_REQUEST response
+    /// 3. Verify that a deletion signed by a key that is not one of the node's keys gets a
+    ///    BAD_REQUEST response
+    /// 4. Verify that a signature of the node itself, rather than its deletion, gets a
+    ///    BAD_REQUEST response
+    /// 5. Verify that a deletion signed by the node's key gets an OK response
+    #[cfg(feature = "registry-signed-entries")]
+    #[test]
+    fn test_delete_node_signed() {
+        use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+        use splinter::registry::NodeSignatureVerifier;
+        use splinter::registry::{node_deletion_signing_bytes, node_signing_bytes};
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let public_key = to_hex(
+            signer
+                .public_key()
+                .expect("Failed to get public key")
+                .as_slice(),
+        );
+        let other_signer = context.new_signer(context.new_random_private_key());
+        let other_public_key = to_hex(
+            other_signer
+                .public_key()
+                .expect("Failed to get public key")
+                .as_slice(),
+        );
+
+        // 1. Add a node with the key of a signer to the registry
+        let node = Node::builder("Node-123")
+            .with_endpoint("12.0.0.123:8431")
+            .with_display_name("Bitwise IO - Node 1")
+            .with_key(&public_key)
+            .build()
+            .expect("Failed to build node");
+        let sign = |signer: &dyn Signer, bytes: &[u8]| {
+            to_hex(signer.sign(bytes).expect("Failed to sign").as_slice())
+        };
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_nodes_identity_resource(
+                Box::new(MemRegistry::new(vec![node.clone()])),
+                NodeValidator {
+                    signature_verifier: Some(Arc::new(NodeSignatureVerifier::new(
+                        context.new_verifier(),
+                    ))),
+                },
+            )]);
+        let delete = |query: Option<(&str, String)>| {
+            let mut url = Url::parse(&format!(
+                "http://{}/registry/nodes/{}",
+                bind_url,
+                node.identity()
+            ))
+            .expect("Failed to parse URL");
+            if let Some((public_key, signature)) = query {
+                url.query_pairs_mut()
+                    .append_pair("public_key", public_key)
+                    .append_pair("signature", &signature);
+            }
+            Client::new()
+                .delete(url)
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .header("Authorization", "custom")
+                .send()
+                .expect("Failed to perform request")
+                .status()
+        };
+
+        // 2. Verify that an unsigned deletion gets a BAD_REQUEST response
+        assert_eq!(delete(None), StatusCode::BAD_REQUEST);
+
+        // 3. Verify that a deletion signed by a key that is not one of the node's keys gets a
+        //    BAD_REQUEST response
+        assert_eq!(
+            delete(Some((
+                &other_public_key,
+                sign(&*other_signer, &node_deletion_signing_bytes(&node)),
+            ))),
+            StatusCode::BAD_REQUEST
+        );
+
+        // 4. Verify that a signature of the node itself, rather than its deletion, gets a
+        //    BAD_REQUEST response
+        assert_eq!(
+            delete(Some((
+                &public_key,
+                sign(&*signer, &node_signing_bytes(&node))
+            ))),
+            StatusCode::BAD_REQUEST
+        );
+
+        // 5. Verify that a deletion signed by the node's key gets an OK response
+        assert_eq!(
+            delete(Some((
+                &public_key,
+                sign(&*signer, &node_deletion_signing_bytes(&node)),
+            ))),
+            StatusCode::OK
+        );
+
+        shutdown_handle
+            .shutdown()
+            .expect("Unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    #[cfg(feature = "registry-signed-entries")]
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
     fn run_rest_api_on_open_port(
         resources: Vec<Resource>,
     ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
@@ -460,6 +634,8 @@ mod tests {
             display_name: "Bitwise IO - Node 1".into(),
             keys: vec!["0123".into()],
             metadata,
+            #[cfg(feature = "registry-signed-entries")]
+            signature: None,
         }
     }
 
@@ -16,7 +16,11 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "rest-api-json-schema")]
+use serde_json::json;
 use splinter::registry::{InvalidNodeError, Node};
+#[cfg(feature = "rest-api-json-schema")]
+use splinter::rest_api::json_schema::JsonSchema;
 use splinter::rest_api::paging::Paging;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -61,6 +65,66 @@ pub struct NewNode {
     pub keys: Vec<String>,
     /// A map with node metadata.
     pub metadata: HashMap<String, String>,
+    /// The signature of the node entry, made with the private key of one of the node's keys.
+    #[cfg(feature = "registry-signed-entries")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<NodeSignature>,
+}
+
+/// The signature of a node entry; see [`splinter::registry::node_signing_bytes`] for the bytes
+/// that are signed.
+#[cfg(feature = "registry-signed-entries")]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeSignature {
+    /// The hex-encoded public key that signed the entry; it must be one of the node's keys.
+    pub public_key: String,
+    /// The hex-encoded signature
+    pub signature: String,
+}
+
+impl NewNode {
+    /// Deserializes a node from a JSON request body. When the `rest-api-json-schema` feature is
+    /// enabled, the body is first validated against the node schema so that every invalid field
+    /// is reported.
+    pub fn from_json(body: &[u8]) -> Result<Self, String> {
+        #[cfg(feature = "rest-api-json-schema")]
+        {
+            JsonSchema::new(new_node_schema())
+                .parse(body)
+                .map_err(|err| err.to_string())
+        }
+        #[cfg(not(feature = "rest-api-json-schema"))]
+        {
+            serde_json::from_slice(body).map_err(|err| err.to_string())
+        }
+    }
+}
+
+/// Returns the JSON schema of a [NewNode]
+#[cfg(feature = "rest-api-json-schema")]
+fn new_node_schema() -> serde_json::Value {
+    json!({
+        "type": "object",
+        "required": ["identity", "endpoints", "display_name", "keys", "metadata"],
+        "properties": {
+            "identity": { "type": "string", "minLength": 1 },
+            "endpoints": {
+                "type": "array",
+                "minItems": 1,
+                "items": { "type": "string", "minLength": 1 }
+            },
+            "display_name": { "type": "string", "minLength": 1 },
+            "keys": {
+                "type": "array",
+                "minItems": 1,
+                "items": { "type": "string", "minLength": 1 }
+            },
+            "metadata": {
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            }
+        }
+    })
 }
 
 impl TryFrom<NewNode> for Node {
@@ -13,10 +13,11 @@
 // limitations under the License.
 
 use std::collections::HashMap;
-use std::convert::TryFrom;
 
-use serde::{Deserialize, Serialize};
-use splinter::registry::{InvalidNodeError, Node};
+use serde::Serialize;
+use splinter::registry::Node;
+
+pub use super::nodes::NewNode;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct NodeResponse<'a> {
@@ -38,37 +39,3 @@ impl<'a> From<&'a Node> for NodeResponse<'a> {
         }
     }
 }
-
-/// Used to deserialize add and update requests
-#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
-pub struct NewNode {
-    /// The Splinter identity of the node; must be non-empty and unique in the registry.
-    pub identity: String,
-    /// The endpoints the node can be reached at; at least one endpoint must be provided, and each
-    /// endpoint must be non-empty and unique in the registry.
-    pub endpoints: Vec<String>,
-    /// A human-readable name for the node; must be non-empty.
-    pub display_name: String,
-    /// The list of public keys that are permitted to act on behalf of the node; at least one key
-    /// must be provided, and each key must be non-empty.
-    pub keys: Vec<String>,
-    /// A map with node metadata.
-    pub metadata: HashMap<String, String>,
-}
-
-impl TryFrom<NewNode> for Node {
-    type Error = InvalidNodeError;
-
-    fn try_from(node: NewNode) -> Result<Self, Self::Error> {
-        let mut builder = Node::builder(node.identity)
-            .with_endpoints(node.endpoints)
-            .with_display_name(node.display_name)
-            .with_keys(node.keys);
-
-        for (k, v) in node.metadata {
-            builder = builder.with_metadata(k, v);
-        }
-
-        builder.build()
-    }
-}
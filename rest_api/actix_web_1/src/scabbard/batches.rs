@@ -12,12 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "scabbard-batch-dependencies")]
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use transact::protocol::batch::BatchPair;
 use transact::protos::FromBytes;
 
-use actix_web::{web, Error as ActixError, HttpResponse};
+use actix_web::{error::BlockingError, web, Error as ActixError, HttpResponse};
 use futures::{stream::Stream, Future, IntoFuture};
 use splinter::{
     rest_api::{ErrorResponse, Method, ProtocolVersionRangeGuard},
@@ -25,7 +27,7 @@ use splinter::{
 };
 
 use scabbard::protocol;
-use scabbard::service::{Scabbard, SERVICE_TYPE};
+use scabbard::service::{Scabbard, ScabbardError, SERVICE_TYPE};
 use splinter_rest_api_common::scabbard::batches::BatchLinkResponse;
 #[cfg(feature = "authorization")]
 use splinter_rest_api_common::scabbard::SCABBARD_WRITE_PERMISSION;
@@ -35,7 +37,7 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
         service_type: SERVICE_TYPE.into(),
         route: "/batches".into(),
         method: Method::Post,
-        handler: Arc::new(move |_, payload, service| {
+        handler: Arc::new(move |_req, payload, service| {
             let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
                 Some(s) => s,
                 None => {
@@ -49,6 +51,24 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
             }
             .clone();
 
+            #[cfg(feature = "scabbard-batch-dependencies")]
+            let dependencies =
+                match web::Query::<HashMap<String, String>>::from_query(_req.query_string()) {
+                    Ok(query) => query.get("depends_on").map(|ids| {
+                        ids.split(',')
+                            .filter(|id| !id.is_empty())
+                            .map(String::from)
+                            .collect::<Vec<_>>()
+                    }),
+                    Err(_) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request("Invalid query"))
+                                .into_future(),
+                        )
+                    }
+                };
+
             Box::new(
                 payload
                     .from_err::<ActixError>()
@@ -57,15 +77,17 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
                         Ok::<_, ActixError>(body)
                     })
                     .into_future()
-                    .and_then(move |body| {
+                    .and_then(move |body| -> Box<dyn Future<Item = _, Error = _>> {
                         let batches: Vec<BatchPair> = match Vec::from_bytes(&body) {
                             Ok(b) => b,
                             Err(_) => {
-                                return HttpResponse::BadRequest()
-                                    .json(ErrorResponse::bad_request(
-                                        "Invalid body: not a valid list of batches",
-                                    ))
-                                    .into_future()
+                                return Box::new(
+                                    HttpResponse::BadRequest()
+                                        .json(ErrorResponse::bad_request(
+                                            "Invalid body: not a valid list of batches",
+                                        ))
+                                        .into_future(),
+                                )
                             }
                         };
 
@@ -73,30 +95,31 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
                             Ok(true) => (),
                             Ok(false) => {
                                 warn!("Rejecting submitted batch, too many pending batches");
-                                return HttpResponse::TooManyRequests().into_future();
+                                return Box::new(HttpResponse::TooManyRequests().into_future());
                             }
                             Err(err) => {
                                 error!("Failed to add batches: {}", err);
-                                return HttpResponse::InternalServerError()
-                                    .json(ErrorResponse::internal_error())
-                                    .into_future();
+                                return Box::new(
+                                    HttpResponse::InternalServerError()
+                                        .json(ErrorResponse::internal_error())
+                                        .into_future(),
+                                );
                             }
                         };
 
-                        match scabbard.add_batches(batches) {
-                            Ok(Some(link)) => HttpResponse::Accepted()
-                                .json(BatchLinkResponse::from(link.as_str()))
-                                .into_future(),
-                            Ok(None) => HttpResponse::BadRequest()
-                                .json(ErrorResponse::bad_request("No valid batches provided"))
-                                .into_future(),
-                            Err(err) => {
-                                error!("Failed to add batches: {}", err);
-                                HttpResponse::InternalServerError()
-                                    .json(ErrorResponse::internal_error())
-                                    .into_future()
+                        #[cfg(feature = "scabbard-batch-dependencies")]
+                        let add_batches = move || match dependencies {
+                            Some(dependencies) => {
+                                scabbard.add_batches_with_dependencies(batches, &dependencies)
                             }
-                        }
+                            None => scabbard.add_batches(batches),
+                        };
+                        #[cfg(not(feature = "scabbard-batch-dependencies"))]
+                        let add_batches = move || scabbard.add_batches(batches);
+
+                        // The batches may be checked against the service's batch policy, which
+                        // can wait on an HTTP request, so they are added on the blocking pool
+                        Box::new(web::block(add_batches).then(add_batches_response))
                     }),
             )
         }),
@@ -108,3 +131,39 @@ pub fn make_add_batches_to_queue_endpoint() -> ServiceEndpoint {
         permission: SCABBARD_WRITE_PERMISSION,
     }
 }
+
+fn add_batches_response(
+    result: Result<Option<String>, BlockingError<ScabbardError>>,
+) -> Result<HttpResponse, ActixError> {
+    match result {
+        Ok(Some(link)) => Ok(HttpResponse::Accepted().json(BatchLinkResponse::from(link.as_str()))),
+        Ok(None) => Ok(HttpResponse::BadRequest()
+            .json(ErrorResponse::bad_request("No valid batches provided"))),
+        #[cfg(feature = "scabbard-batch-dependencies")]
+        Err(BlockingError::Error(ScabbardError::InvalidDependency(msg))) => {
+            Ok(HttpResponse::BadRequest().json(ErrorResponse::bad_request(&msg)))
+        }
+        #[cfg(feature = "scabbard-batch-validation")]
+        Err(BlockingError::Error(ScabbardError::BatchRejected(msg))) => {
+            Ok(HttpResponse::Forbidden().json(ErrorResponse::forbidden(&msg)))
+        }
+        #[cfg(feature = "scabbard-quiet-hours")]
+        Err(BlockingError::Error(ScabbardError::QuietHours(remaining))) => {
+            Ok(HttpResponse::ServiceUnavailable()
+                .header("Retry-After", remaining.as_secs().to_string())
+                .json(ErrorResponse::service_unavailable(&format!(
+                    "Batches are not accepted during the circuit's quiet hours; retry in {} \
+                     seconds",
+                    remaining.as_secs()
+                ))))
+        }
+        Err(BlockingError::Error(err)) => {
+            error!("Failed to add batches: {}", err);
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+        Err(BlockingError::Canceled) => {
+            error!("Failed to add batches: blocking operation was canceled");
+            Ok(HttpResponse::InternalServerError().json(ErrorResponse::internal_error()))
+        }
+    }
+}
@@ -14,9 +14,18 @@
 
 pub mod batch_statuses;
 pub mod batches;
+#[cfg(feature = "scabbard-state-pruning")]
+pub mod prune;
+#[cfg(feature = "scabbard-receipt-queries")]
+pub mod receipts;
+#[cfg(feature = "scabbard-snapshot")]
+pub mod snapshot;
 pub mod state;
 pub mod state_address;
+pub mod state_proof;
 pub mod state_root;
+#[cfg(feature = "scabbard-batch-status-stream")]
+pub mod ws_batch_statuses;
 pub mod ws_subscribe;
 
 use splinter::service::rest_api::{ServiceEndpoint, ServiceEndpointProvider};
@@ -39,14 +48,24 @@ impl ScabbardServiceEndpointProvider {
 
 impl Default for ScabbardServiceEndpointProvider {
     fn default() -> Self {
-        let endpoints = vec![
+        #[allow(unused_mut)]
+        let mut endpoints = vec![
             batches::make_add_batches_to_queue_endpoint(),
             ws_subscribe::make_subscribe_endpoint(),
             batch_statuses::make_get_batch_status_endpoint(),
             state_address::make_get_state_at_address_endpoint(),
+            state_proof::make_get_state_proof_endpoint(),
             state::make_get_state_with_prefix_endpoint(),
             state_root::make_get_state_root_endpoint(),
         ];
+        #[cfg(feature = "scabbard-batch-status-stream")]
+        endpoints.push(ws_batch_statuses::make_batch_status_stream_endpoint());
+        #[cfg(feature = "scabbard-snapshot")]
+        endpoints.push(snapshot::make_create_snapshot_endpoint());
+        #[cfg(feature = "scabbard-state-pruning")]
+        endpoints.push(prune::make_prune_state_endpoint());
+        #[cfg(feature = "scabbard-receipt-queries")]
+        endpoints.push(receipts::make_list_receipts_endpoint());
         Self::new(endpoints)
     }
 }
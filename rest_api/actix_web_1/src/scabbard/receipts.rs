@@ -0,0 +1,259 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use actix_web::{web, HttpResponse};
+use futures::IntoFuture;
+use serde::Serialize;
+use splinter::{
+    rest_api::{percent_encode_filter_query, ErrorResponse, Method, ProtocolVersionRangeGuard},
+    service::rest_api::ServiceEndpoint,
+};
+use transact::protocol::receipt::{StateChange, TransactionResult};
+
+use scabbard::protocol;
+use scabbard::service::{QueriedReceipt, ReceiptQuery, Scabbard, ScabbardError, SERVICE_TYPE};
+use splinter_rest_api_common::paging::{Paging, PagingBuilder, DEFAULT_LIMIT, DEFAULT_OFFSET};
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+
+#[derive(Serialize)]
+struct ListReceiptsResponse {
+    data: Vec<ReceiptResponse>,
+    paging: Paging,
+}
+
+#[derive(Serialize)]
+struct ReceiptResponse {
+    transaction_id: String,
+    contract_name: Option<String>,
+    /// The commit time, in seconds since the Unix epoch
+    committed_at: Option<u64>,
+    state_changes: Vec<StateChangeResponse>,
+    events: Vec<EventResponse>,
+}
+
+#[derive(Serialize)]
+struct StateChangeResponse {
+    action: &'static str,
+    address: String,
+}
+
+#[derive(Serialize)]
+struct EventResponse {
+    event_type: String,
+    attributes: Vec<(String, String)>,
+}
+
+impl From<QueriedReceipt> for ReceiptResponse {
+    fn from(queried: QueriedReceipt) -> Self {
+        let contract_name = queried.contract_name().map(String::from);
+        let committed_at = queried.committed_at().and_then(|time| {
+            time.duration_since(SystemTime::UNIX_EPOCH)
+                .ok()
+                .map(|duration| duration.as_secs())
+        });
+        let receipt = queried.into_receipt();
+
+        let (state_changes, events) = match receipt.transaction_result {
+            TransactionResult::Valid {
+                state_changes,
+                events,
+                ..
+            } => (
+                state_changes
+                    .into_iter()
+                    .map(|change| match change {
+                        StateChange::Set { key, .. } => StateChangeResponse {
+                            action: "set",
+                            address: key,
+                        },
+                        StateChange::Delete { key } => StateChangeResponse {
+                            action: "delete",
+                            address: key,
+                        },
+                    })
+                    .collect(),
+                events
+                    .into_iter()
+                    .map(|event| EventResponse {
+                        event_type: event.event_type,
+                        attributes: event.attributes,
+                    })
+                    .collect(),
+            ),
+            TransactionResult::Invalid { .. } => (vec![], vec![]),
+        };
+
+        ReceiptResponse {
+            transaction_id: receipt.transaction_id,
+            contract_name,
+            committed_at,
+            state_changes,
+            events,
+        }
+    }
+}
+
+pub fn make_list_receipts_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/receipts".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let query: web::Query<HashMap<String, String>> =
+                if let Ok(q) = web::Query::from_query(request.query_string()) {
+                    q
+                } else {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("Invalid query"))
+                            .into_future(),
+                    );
+                };
+
+            let (receipt_query, link, offset, limit) =
+                match parse_query(&query, request.uri().path()) {
+                    Ok(parsed) => parsed,
+                    Err(msg) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request(&msg))
+                                .into_future(),
+                        )
+                    }
+                };
+
+            Box::new(match scabbard.query_receipts(&receipt_query) {
+                Ok(result) => {
+                    let paging = PagingBuilder::new(link, result.total() as usize)
+                        .with_limit(limit)
+                        .with_offset(offset)
+                        .build();
+                    HttpResponse::Ok()
+                        .json(ListReceiptsResponse {
+                            data: result
+                                .into_receipts()
+                                .into_iter()
+                                .map(ReceiptResponse::from)
+                                .collect(),
+                            paging,
+                        })
+                        .into_future()
+                }
+                Err(ScabbardError::InvalidReceiptQuery(msg)) => HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&msg))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to query receipts: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            splinter_rest_api_common::scabbard::SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_READ_PERMISSION,
+    }
+}
+
+/// Parses the receipt query, the link for the paging of its results with the filters it was
+/// given, and its offset and limit.
+fn parse_query(
+    query: &HashMap<String, String>,
+    path: &str,
+) -> Result<(ReceiptQuery, String, usize, usize), String> {
+    let mut receipt_query = ReceiptQuery::new();
+    let mut link = format!("{}?", path);
+
+    if let Some(after) = query.get("after") {
+        receipt_query = receipt_query.with_after(after.clone());
+        append_filter(&mut link, "after", after);
+    }
+    if let Some(until) = query.get("until") {
+        receipt_query = receipt_query.with_until(until.clone());
+        append_filter(&mut link, "until", until);
+    }
+    if let Some(contract) = query.get("contract") {
+        receipt_query = receipt_query.with_contract_name(contract.clone());
+        append_filter(&mut link, "contract", contract);
+    }
+    if let Some(value) = query.get("committed_from") {
+        receipt_query = receipt_query.with_committed_from(parse_time("committed_from", value)?);
+        append_filter(&mut link, "committed_from", value);
+    }
+    if let Some(value) = query.get("committed_before") {
+        receipt_query = receipt_query.with_committed_before(parse_time("committed_before", value)?);
+        append_filter(&mut link, "committed_before", value);
+    }
+
+    let offset = match query.get("offset") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|err| format!("Invalid offset value passed: {}. Error: {}", value, err))?,
+        None => DEFAULT_OFFSET,
+    };
+    let limit = match query.get("limit") {
+        Some(value) => value
+            .parse::<usize>()
+            .map_err(|err| format!("Invalid limit value passed: {}. Error: {}", value, err))?,
+        None => DEFAULT_LIMIT,
+    };
+
+    Ok((
+        receipt_query.with_offset(offset).with_limit(limit),
+        link,
+        offset,
+        limit,
+    ))
+}
+
+/// Parses a time given in seconds since the Unix epoch.
+fn parse_time(name: &str, value: &str) -> Result<SystemTime, String> {
+    value
+        .parse::<u64>()
+        .ok()
+        .and_then(|secs| SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            format!(
+                "Invalid {} value passed: {}. Expected seconds since the Unix epoch",
+                name, value
+            )
+        })
+}
+
+fn append_filter(link: &mut String, name: &str, value: &str) {
+    // Writing to a string cannot fail
+    let _ = write!(link, "{}={}&", name, percent_encode_filter_query(value));
+}
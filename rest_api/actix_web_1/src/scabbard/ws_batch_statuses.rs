@@ -0,0 +1,163 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use actix_web::{web, HttpResponse};
+use futures::IntoFuture;
+use serde::{Serialize, Serializer};
+use splinter::{
+    rest_api::{
+        new_websocket_event_sender, ErrorResponse, EventSender, Method, ProtocolVersionRangeGuard,
+        Request,
+    },
+    service::rest_api::ServiceEndpoint,
+};
+
+use scabbard::protocol;
+use scabbard::service::{
+    BatchInfo, BatchStatusSubscriber, Scabbard, StateSubscriberError, SERVICE_TYPE,
+};
+use splinter_rest_api_common::events::{
+    EventFormat, StreamEvent, SCABBARD_BATCH_STATUS_EVENT_TYPE, SCABBARD_BATCH_STATUS_EVENT_VERSION,
+};
+use splinter_rest_api_common::scabbard::batch_statuses::BatchInfoResponse;
+#[cfg(feature = "authorization")]
+use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
+
+use crate::event_format::negotiate_event_format;
+
+/// A status transition of a batch, sent in the same form as the entries of `/batch_statuses`
+#[derive(Debug)]
+struct BatchStatusEvent(BatchInfo);
+
+impl Serialize for BatchStatusEvent {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BatchInfoResponse::from(&self.0).serialize(serializer)
+    }
+}
+
+struct WsBatchStatusSubscriber {
+    sender: EventSender<StreamEvent<BatchStatusEvent>>,
+    format: EventFormat,
+}
+
+impl BatchStatusSubscriber for WsBatchStatusSubscriber {
+    fn handle_status(&self, info: BatchInfo) -> Result<(), StateSubscriberError> {
+        let event = self.format.format(
+            SCABBARD_BATCH_STATUS_EVENT_TYPE,
+            SCABBARD_BATCH_STATUS_EVENT_VERSION,
+            BatchStatusEvent(info),
+        );
+        self.sender.send(event).map_err(|_| {
+            debug!("Dropping batch status and unsubscribing due to websocket being closed");
+            StateSubscriberError::Unsubscribe
+        })
+    }
+}
+
+/// Streams the status transitions of the batches given by the `ids` query parameter over a
+/// websocket. The current status of each batch is sent first; the websocket is closed once every
+/// batch is committed or invalid.
+pub fn make_batch_status_stream_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/ws/batch_statuses".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, payload, service| {
+            let scabbard = match service.as_any().downcast_ref::<Scabbard>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to scabbard service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let query =
+                match web::Query::<HashMap<String, String>>::from_query(request.query_string()) {
+                    Ok(query) => query,
+                    Err(_) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request("Invalid query"))
+                                .into_future(),
+                        )
+                    }
+                };
+
+            let ids = match query.get("ids") {
+                Some(ids) if !ids.trim().is_empty() => ids
+                    .split(',')
+                    .filter(|id| !id.is_empty())
+                    .map(String::from)
+                    .collect::<HashSet<_>>(),
+                _ => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request("No batch IDs specified"))
+                            .into_future(),
+                    )
+                }
+            };
+
+            let format = match negotiate_event_format(request.query_string()) {
+                Ok(format) => format,
+                Err(msg) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&msg))
+                            .into_future(),
+                    )
+                }
+            };
+
+            let request = Request::from((request, payload));
+            match new_websocket_event_sender(request, Box::new(std::iter::empty())) {
+                Ok((sender, res)) => {
+                    if let Err(err) = scabbard.add_batch_status_subscriber(
+                        ids,
+                        Box::new(WsBatchStatusSubscriber { sender, format }),
+                    ) {
+                        error!("Unable to add batch status sender: {}", err);
+                        return Box::new(
+                            HttpResponse::InternalServerError()
+                                .json(ErrorResponse::internal_error())
+                                .into_future(),
+                        );
+                    }
+                    Box::new(res.into_future())
+                }
+                Err(err) => {
+                    error!("Failed to create websocket: {:?}", err);
+                    Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    )
+                }
+            }
+        }),
+        request_guards: vec![Arc::new(ProtocolVersionRangeGuard::new(
+            splinter_rest_api_common::scabbard::SCABBARD_BATCH_STATUS_STREAM_PROTOCOL_MIN,
+            protocol::SCABBARD_PROTOCOL_VERSION,
+        ))],
+        #[cfg(feature = "authorization")]
+        permission: SCABBARD_READ_PERMISSION,
+    }
+}
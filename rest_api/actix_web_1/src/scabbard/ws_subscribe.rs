@@ -29,15 +29,26 @@ use scabbard::protocol;
 use scabbard::service::{
     Scabbard, StateChangeEvent, StateSubscriber, StateSubscriberError, SERVICE_TYPE,
 };
+use splinter_rest_api_common::events::{
+    EventFormat, StreamEvent, SCABBARD_STATE_CHANGE_EVENT_TYPE, SCABBARD_STATE_CHANGE_EVENT_VERSION,
+};
 #[cfg(feature = "authorization")]
 use splinter_rest_api_common::scabbard::SCABBARD_READ_PERMISSION;
 
+use crate::event_format::negotiate_event_format;
+
 struct WsStateSubscriber {
-    sender: EventSender<StateChangeEvent>,
+    sender: EventSender<StreamEvent<StateChangeEvent>>,
+    format: EventFormat,
 }
 
 impl StateSubscriber for WsStateSubscriber {
     fn handle_event(&self, event: StateChangeEvent) -> Result<(), StateSubscriberError> {
+        let event = self.format.format(
+            SCABBARD_STATE_CHANGE_EVENT_TYPE,
+            SCABBARD_STATE_CHANGE_EVENT_VERSION,
+            event,
+        );
         self.sender.send(event).map_err(|_| {
             debug!(
                 "Dropping scabbard state change event and unsubscribing due to websocket being
@@ -78,6 +89,17 @@ pub fn make_subscribe_endpoint() -> ServiceEndpoint {
                     }
                 };
 
+            let format = match negotiate_event_format(request.query_string()) {
+                Ok(format) => format,
+                Err(msg) => {
+                    return Box::new(
+                        HttpResponse::BadRequest()
+                            .json(ErrorResponse::bad_request(&msg))
+                            .into_future(),
+                    )
+                }
+            };
+
             let last_seen_event_id = query.remove("last_seen_event");
 
             match last_seen_event_id {
@@ -106,11 +128,19 @@ pub fn make_subscribe_endpoint() -> ServiceEndpoint {
                 }
             };
 
+            let unseen_events = unseen_events.map(move |event| {
+                format.format(
+                    SCABBARD_STATE_CHANGE_EVENT_TYPE,
+                    SCABBARD_STATE_CHANGE_EVENT_VERSION,
+                    event,
+                )
+            });
+
             let request = Request::from((request, payload));
             match new_websocket_event_sender(request, Box::new(unseen_events)) {
                 Ok((sender, res)) => {
-                    if let Err(err) =
-                        scabbard.add_state_subscriber(Box::new(WsStateSubscriber { sender }))
+                    if let Err(err) = scabbard
+                        .add_state_subscriber(Box::new(WsStateSubscriber { sender, format }))
                     {
                         error!("Unable to add scabbard event sender: {}", err);
                         return Box::new(
@@ -0,0 +1,326 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the following endpoints:
+//!
+//! * `GET /scabbard/consensus/{circuit_id}/{service_id}/events` for listing the consensus events
+//!   of a scabbard service, including those that have already been executed
+
+use std::sync::Arc;
+
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use scabbard::store::PooledScabbardStoreFactory;
+use splinter::rest_api::{
+    actix_web_1::{Method, ProtocolVersionRangeGuard, Resource},
+    ErrorResponse,
+};
+use splinter::service::{CircuitId, FullyQualifiedServiceId, ServiceId};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{ConsensusEventResponse, ListConsensusEventsResponse};
+#[cfg(feature = "authorization")]
+use super::SCABBARD_CONSENSUS_READ_PERMISSION;
+
+const SCABBARD_CONSENSUS_EVENTS_MIN: u32 = 2;
+
+pub fn make_consensus_events_resource(
+    store_factory: Arc<dyn PooledScabbardStoreFactory>,
+) -> Resource {
+    let resource = Resource::build("/scabbard/consensus/{circuit_id}/{service_id}/events")
+        .add_request_guard(ProtocolVersionRangeGuard::new(
+            SCABBARD_CONSENSUS_EVENTS_MIN,
+            SPLINTER_PROTOCOL_VERSION,
+        ));
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Get,
+            SCABBARD_CONSENSUS_READ_PERMISSION,
+            move |r, _| list_consensus_events(r, store_factory.clone()),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |r, _| {
+            list_consensus_events(r, store_factory.clone())
+        })
+    }
+}
+
+fn list_consensus_events(
+    req: HttpRequest,
+    store_factory: Arc<dyn PooledScabbardStoreFactory>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let service_id = match parse_service_id(&req) {
+        Ok(service_id) => service_id,
+        Err(err) => {
+            return Box::new(
+                HttpResponse::BadRequest()
+                    .json(ErrorResponse::bad_request(&err))
+                    .into_future(),
+            )
+        }
+    };
+
+    Box::new(
+        web::block(move || {
+            let store = store_factory.new_store();
+            match store
+                .get_service(&service_id)
+                .map_err(|err| err.to_string())?
+            {
+                Some(_) => store
+                    .list_consensus_event_log(&service_id)
+                    .map(Some)
+                    .map_err(|err| err.to_string()),
+                None => Ok(None),
+            }
+        })
+        .then(move |res| {
+            Ok(match res {
+                Ok(Some(entries)) => HttpResponse::Ok().json(ListConsensusEventsResponse {
+                    data: entries.iter().map(ConsensusEventResponse::from).collect(),
+                }),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found("Scabbard service not found")),
+                Err(err) => {
+                    error!("Unable to list scabbard consensus events: {}", err);
+                    HttpResponse::InternalServerError().json(ErrorResponse::internal_error())
+                }
+            })
+        }),
+    )
+}
+
+fn parse_service_id(req: &HttpRequest) -> Result<FullyQualifiedServiceId, String> {
+    let circuit_id = req.match_info().get("circuit_id").unwrap_or("");
+    let service_id = req.match_info().get("service_id").unwrap_or("");
+
+    Ok(FullyQualifiedServiceId::new(
+        CircuitId::new(circuit_id).map_err(|err| format!("Invalid circuit ID: {}", err))?,
+        ServiceId::new(service_id).map_err(|err| format!("Invalid service ID: {}", err))?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use reqwest::{blocking::Client, StatusCode, Url};
+
+    use scabbard::migrations::run_sqlite_migrations;
+    use scabbard::store::{
+        ConsensusContext, ConsensusEvent, ConsensusType, ContextBuilder, Event, Message,
+        Participant, PooledSqliteScabbardStoreFactory, ScabbardServiceBuilder, ServiceStatus,
+        State,
+    };
+    use splinter::error::InternalError;
+    use splinter::rest_api::actix_web_1::{AuthConfig, RestApiBuilder, RestApiShutdownHandle};
+    use splinter::rest_api::auth::authorization::{
+        AuthorizationHandler, AuthorizationHandlerResult,
+    };
+    use splinter::rest_api::auth::identity::{Identity, IdentityProvider};
+    use splinter::rest_api::auth::AuthorizationHeader;
+
+    #[test]
+    /// Tests that a GET /scabbard/consensus/{circuit_id}/{service_id}/events request lists the
+    /// events of the service in position order, whether or not they have been executed, that an
+    /// unknown service is not found, and that an invalid circuit ID is rejected.
+    fn test_list_consensus_events() {
+        let store_factory = create_store_factory();
+        let store = store_factory.new_store();
+
+        let service_id = FullyQualifiedServiceId::new_from_string("abcde-01234::a000")
+            .expect("Failed to create service ID");
+        let peer_id = ServiceId::new("b000").expect("Failed to create service ID");
+
+        store
+            .add_service(
+                ScabbardServiceBuilder::default()
+                    .with_service_id(&service_id)
+                    .with_peers(&[peer_id.clone()])
+                    .with_consensus(&ConsensusType::TwoPC)
+                    .with_status(&ServiceStatus::Finalized)
+                    .build()
+                    .expect("Failed to build service"),
+            )
+            .expect("Failed to add service");
+        store
+            .add_consensus_context(
+                &service_id,
+                ConsensusContext::TwoPhaseCommit(
+                    ContextBuilder::default()
+                        .with_coordinator(service_id.service_id())
+                        .with_epoch(1)
+                        .with_participants(vec![Participant {
+                            process: peer_id.clone(),
+                            vote: None,
+                            decision_ack: false,
+                        }])
+                        .with_state(State::WaitingForStart)
+                        .with_this_process(service_id.service_id())
+                        .build()
+                        .expect("Failed to build context"),
+                ),
+            )
+            .expect("Failed to add context");
+
+        let start_id = store
+            .add_consensus_event(
+                &service_id,
+                ConsensusEvent::TwoPhaseCommit(Event::Start(vec![1, 2])),
+            )
+            .expect("Failed to add event");
+        store
+            .add_consensus_event(
+                &service_id,
+                ConsensusEvent::TwoPhaseCommit(Event::Deliver(
+                    peer_id,
+                    Message::VoteResponse(1, true),
+                )),
+            )
+            .expect("Failed to add event");
+        store
+            .update_consensus_event(&service_id, start_id, std::time::SystemTime::now(), 1)
+            .expect("Failed to update event");
+
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(vec![make_consensus_events_resource(store_factory)]);
+
+        let get = |path: &str| {
+            let url = Url::parse(&format!(
+                "http://{}/scabbard/consensus/{}/events",
+                bind_url, path
+            ))
+            .expect("Failed to parse URL");
+            Client::new()
+                .get(url)
+                .header("Authorization", "custom")
+                .header("SplinterProtocolVersion", SPLINTER_PROTOCOL_VERSION)
+                .send()
+                .expect("Failed to perform request")
+        };
+
+        let resp = get("abcde-01234/a000");
+        assert_eq!(resp.status(), StatusCode::OK);
+        let events = resp
+            .json::<ListConsensusEventsResponse>()
+            .expect("Failed to deserialize body")
+            .data;
+        assert_eq!(events.len(), 2);
+
+        assert_eq!(events[0].event_type, "start");
+        assert_eq!(events[0].value, Some("0102".to_string()));
+        assert!(events[0].executed_at.is_some());
+        assert_eq!(events[0].executed_epoch, Some(1));
+
+        assert_eq!(events[1].event_type, "deliver");
+        let deliver = events[1].deliver.as_ref().expect("Missing deliver message");
+        assert_eq!(deliver.from, "b000");
+        assert_eq!(deliver.message_type, "vote_response");
+        assert_eq!(deliver.vote, Some(true));
+        assert_eq!(events[1].executed_at, None);
+
+        assert_eq!(get("abcde-01234/c000").status(), StatusCode::NOT_FOUND);
+        assert_eq!(get("invalid/a000").status(), StatusCode::BAD_REQUEST);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
+    fn create_store_factory() -> Arc<dyn PooledScabbardStoreFactory> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        Arc::new(PooledSqliteScabbardStoreFactory::new(pool))
+    }
+
+    fn run_rest_api_on_open_port(
+        resources: Vec<Resource>,
+    ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
+        #[cfg(not(feature = "https-bind"))]
+        let bind = "127.0.0.1:0";
+        #[cfg(feature = "https-bind")]
+        let bind = splinter::rest_api::BindConfig::Http("127.0.0.1:0".into());
+
+        let identity_provider = MockIdentityProvider::default().clone_box();
+        let auth_config = AuthConfig::Custom {
+            resources: Vec::new(),
+            identity_provider,
+        };
+
+        let authorization_handlers = vec![MockAuthorizationHandler::default().clone_box()];
+
+        let result = RestApiBuilder::new()
+            .with_bind(bind)
+            .add_resources(resources.clone())
+            .push_auth_config(auth_config)
+            .with_authorization_handlers(authorization_handlers)
+            .build()
+            .expect("Failed to build REST API")
+            .run();
+        match result {
+            Ok((shutdown_handle, join_handle)) => {
+                let port = shutdown_handle.port_numbers()[0];
+                (shutdown_handle, join_handle, format!("127.0.0.1:{}", port))
+            }
+            Err(err) => panic!("Failed to run REST API: {}", err),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockIdentityProvider {}
+
+    impl IdentityProvider for MockIdentityProvider {
+        fn get_identity(
+            &self,
+            _authorization: &AuthorizationHeader,
+        ) -> Result<Option<Identity>, InternalError> {
+            Ok(Some(Identity::Custom("custom".to_string())))
+        }
+        fn clone_box(&self) -> Box<dyn IdentityProvider> {
+            Box::new(self.clone())
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockAuthorizationHandler {}
+
+    impl AuthorizationHandler for MockAuthorizationHandler {
+        fn has_permission(
+            &self,
+            _identity: &Identity,
+            _permission_id: &str,
+        ) -> Result<AuthorizationHandlerResult, InternalError> {
+            Ok(AuthorizationHandlerResult::Allow)
+        }
+        fn clone_box(&self) -> Box<dyn AuthorizationHandler> {
+            Box::new(self.clone())
+        }
+    }
+}
@@ -0,0 +1,59 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoint for inspecting the consensus events that scabbard v3
+//! services on this node have recorded, which is useful when debugging stuck commits.
+
+mod events;
+mod resources;
+
+use std::sync::Arc;
+
+use scabbard::store::PooledScabbardStoreFactory;
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+
+pub use resources::{
+    ConsensusEventResponse, DeliveredMessageResponse, ListConsensusEventsResponse,
+};
+
+#[cfg(feature = "authorization")]
+const SCABBARD_CONSENSUS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "scabbard.consensus.read",
+    permission_display_name: "Scabbard consensus read",
+    permission_description: "Allows the client to read the consensus events of scabbard services",
+};
+
+pub struct ScabbardConsensusResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl ScabbardConsensusResourceProvider {
+    pub fn new(store_factory: Arc<dyn PooledScabbardStoreFactory>) -> Self {
+        let resources = vec![events::make_consensus_events_resource(store_factory)];
+        Self { resources }
+    }
+}
+
+/// The `ScabbardConsensusResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /scabbard/consensus/{circuit_id}/{service_id}/events` - List every consensus event of
+///   a scabbard service in position order
+impl RestResourceProvider for ScabbardConsensusResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use scabbard::store::{ConsensusEvent, ConsensusEventLogEntry, Event, Message};
+
+use crate::hex::to_hex;
+
+/// A message delivered to the service by another process
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DeliveredMessageResponse {
+    /// The process that sent the message
+    pub from: String,
+    pub message_type: String,
+    pub epoch: u64,
+    /// The vote of a vote response
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vote: Option<bool>,
+    /// The hex-encoded value of a vote request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ConsensusEventResponse {
+    pub position: i32,
+    pub event_type: String,
+    /// The message of a deliver event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deliver: Option<DeliveredMessageResponse>,
+    /// The hex-encoded value of a start event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The vote of a vote event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vote: Option<bool>,
+    /// The time the event was recorded, in seconds since the Unix epoch
+    pub created_at: u64,
+    /// The time the event was executed, in seconds since the Unix epoch, or null if it is pending
+    pub executed_at: Option<u64>,
+    pub executed_epoch: Option<u64>,
+}
+
+impl From<&ConsensusEventLogEntry> for ConsensusEventResponse {
+    fn from(entry: &ConsensusEventLogEntry) -> Self {
+        let mut response = Self {
+            position: entry.position(),
+            event_type: String::new(),
+            deliver: None,
+            value: None,
+            vote: None,
+            created_at: to_secs(entry.created_at()),
+            executed_at: entry.executed_at().map(to_secs),
+            executed_epoch: entry.executed_epoch(),
+        };
+
+        let ConsensusEvent::TwoPhaseCommit(event) = entry.event();
+        match event {
+            Event::Alarm() => response.event_type = "alarm".into(),
+            Event::Deliver(from, message) => {
+                response.event_type = "deliver".into();
+                response.deliver = Some(DeliveredMessageResponse::new(from.as_str(), message));
+            }
+            Event::Start(value) => {
+                response.event_type = "start".into();
+                response.value = Some(to_hex(value));
+            }
+            Event::Vote(vote) => {
+                response.event_type = "vote".into();
+                response.vote = Some(*vote);
+            }
+        }
+
+        response
+    }
+}
+
+impl DeliveredMessageResponse {
+    fn new(from: &str, message: &Message) -> Self {
+        let (message_type, epoch, vote, value) = match message {
+            Message::VoteRequest(epoch, value) => {
+                ("vote_request", epoch, None, Some(to_hex(value)))
+            }
+            Message::VoteResponse(epoch, vote) => ("vote_response", epoch, Some(*vote), None),
+            Message::Commit(epoch) => ("commit", epoch, None, None),
+            Message::Abort(epoch) => ("abort", epoch, None, None),
+            Message::DecisionRequest(epoch) => ("decision_request", epoch, None, None),
+            Message::DecisionAck(epoch) => ("decision_ack", epoch, None, None),
+            Message::PreCommit(epoch) => ("pre_commit", epoch, None, None),
+            Message::PreCommitAck(epoch) => ("pre_commit_ack", epoch, None, None),
+        };
+
+        Self {
+            from: from.to_string(),
+            message_type: message_type.to_string(),
+            epoch: *epoch,
+            vote,
+            value,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListConsensusEventsResponse {
+    pub data: Vec<ConsensusEventResponse>,
+}
+
+fn to_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|since_epoch| since_epoch.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,52 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoint for reading whether the services run by this node's
+//! orchestrator are running, and how often they have crashed and been restarted.
+
+mod resources;
+mod services_restarts;
+
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::runtime::service::instance::ServiceRestartTracker;
+
+#[cfg(feature = "authorization")]
+const SERVICE_RESTARTS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "service.restarts.read",
+    permission_display_name: "Service restarts read",
+    permission_description: "Allows the client to read the restart history of services",
+};
+
+pub struct ServiceRestartResourceProvider {
+    resources: Vec<Resource>,
+}
+
+impl ServiceRestartResourceProvider {
+    pub fn new(tracker: ServiceRestartTracker) -> Self {
+        let resources = vec![services_restarts::make_services_restarts_resource(tracker)];
+        Self { resources }
+    }
+}
+
+/// The `ServiceRestartResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /services/restarts` - List the state and restart history of every service
+impl RestResourceProvider for ServiceRestartResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        self.resources.clone()
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::UNIX_EPOCH;
+
+use splinter::runtime::service::instance::{ServiceCrash, ServiceRestartHistory, ServiceRunState};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceRunStateResponse {
+    Running,
+    Restarting,
+    Failed,
+}
+
+impl From<ServiceRunState> for ServiceRunStateResponse {
+    fn from(state: ServiceRunState) -> Self {
+        match state {
+            ServiceRunState::Running => Self::Running,
+            ServiceRunState::Restarting => Self::Restarting,
+            ServiceRunState::Failed => Self::Failed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ServiceCrashResponse {
+    /// Seconds since the UNIX epoch
+    pub crashed_at: u64,
+    pub reason: String,
+}
+
+impl From<&ServiceCrash> for ServiceCrashResponse {
+    fn from(crash: &ServiceCrash) -> Self {
+        Self {
+            crashed_at: crash
+                .crashed_at()
+                .duration_since(UNIX_EPOCH)
+                .map(|since_epoch| since_epoch.as_secs())
+                .unwrap_or(0),
+            reason: crash.reason().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ServiceRestartResponse {
+    pub circuit: String,
+    pub service_id: String,
+    pub service_type: String,
+    pub state: ServiceRunStateResponse,
+    pub restarts: u32,
+    pub crashes: Vec<ServiceCrashResponse>,
+}
+
+impl From<&ServiceRestartHistory> for ServiceRestartResponse {
+    fn from(history: &ServiceRestartHistory) -> Self {
+        Self {
+            circuit: history.service().circuit.clone(),
+            service_id: history.service().service_id.clone(),
+            service_type: history.service().service_type.clone(),
+            state: history.state().into(),
+            restarts: history.restarts(),
+            crashes: history
+                .crashes()
+                .iter()
+                .map(ServiceCrashResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListServiceRestartsResponse {
+    pub data: Vec<ServiceRestartResponse>,
+}
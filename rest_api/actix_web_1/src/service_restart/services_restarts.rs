@@ -0,0 +1,70 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /services/restarts` endpoint for listing the state and restart
+//! history of every service run by the orchestrator.
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+use splinter::rest_api::ErrorResponse;
+use splinter::runtime::service::instance::ServiceRestartTracker;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{ListServiceRestartsResponse, ServiceRestartResponse};
+#[cfg(feature = "authorization")]
+use super::SERVICE_RESTARTS_READ_PERMISSION;
+
+const SERVICE_RESTARTS_LIST_MIN: u32 = 2;
+
+pub fn make_services_restarts_resource(tracker: ServiceRestartTracker) -> Resource {
+    let resource = Resource::build("/services/restarts").add_request_guard(
+        ProtocolVersionRangeGuard::new(SERVICE_RESTARTS_LIST_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(
+            Method::Get,
+            SERVICE_RESTARTS_READ_PERMISSION,
+            move |_, _| list_restarts(&tracker),
+        )
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| list_restarts(&tracker))
+    }
+}
+
+fn list_restarts(
+    tracker: &ServiceRestartTracker,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match tracker.list() {
+        Ok(histories) => Box::new(
+            HttpResponse::Ok()
+                .json(ListServiceRestartsResponse {
+                    data: histories.iter().map(ServiceRestartResponse::from).collect(),
+                })
+                .into_future(),
+        ),
+        Err(err) => {
+            error!("Unable to list service restarts: {}", err);
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            )
+        }
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /status/attestation` endpoint, which returns a document that
+//! attests to the node's identity, signed with the node's key.
+//!
+//! A partner that has received the node's public key out-of-band can verify the attestation with
+//! [`SignedNodeAttestation::verify`] before approving a circuit proposal that involves the node.
+//! The signature covers the UTF-8 bytes of the `attestation` string exactly as it is returned, so
+//! the document can be verified without serializing it again.
+
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use actix_web::{Error, HttpResponse};
+use cylinder::{PublicKey, Signature, Signer, Verifier};
+use futures::{future::IntoFuture, Future};
+use splinter::error::{InternalError, InvalidArgumentError};
+use splinter::rest_api::ErrorResponse;
+
+use crate::hex::{parse_hex, to_hex};
+
+/// The identity a node attests to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NodeAttestation {
+    node_id: String,
+    public_keys: Vec<String>,
+    endpoints: Vec<String>,
+    software_version: String,
+    timestamp: u64,
+}
+
+impl NodeAttestation {
+    pub fn node_id(&self) -> &str {
+        &self.node_id
+    }
+
+    /// Returns the hex-encoded public keys the node uses.
+    pub fn public_keys(&self) -> &[String] {
+        &self.public_keys
+    }
+
+    /// Returns the endpoints the node advertises to its peers.
+    pub fn endpoints(&self) -> &[String] {
+        &self.endpoints
+    }
+
+    pub fn software_version(&self) -> &str {
+        &self.software_version
+    }
+
+    /// Returns the time the attestation was made, in milliseconds since the UNIX epoch.
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+/// A node attestation, signed with one of the node's keys.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedNodeAttestation {
+    attestation: String,
+    public_key: String,
+    signature: String,
+}
+
+impl SignedNodeAttestation {
+    /// Returns the attestation, as the JSON document that was signed.
+    pub fn attestation(&self) -> &str {
+        &self.attestation
+    }
+
+    /// Returns the hex-encoded public key of the key that signed the attestation.
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    /// Returns the hex-encoded signature of the attestation.
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Verifies that the attestation was signed by the given key, and returns it.
+    ///
+    /// # Arguments
+    ///
+    /// * `verifier` - The verifier for the node's signing algorithm
+    /// * `public_key` - The hex-encoded public key the node is known to use
+    ///
+    /// # Errors
+    ///
+    /// Returns an [InvalidArgumentError] if the attestation was signed by another key, if the
+    /// signature does not match, or if the attestation does not list the key as one of the
+    /// node's keys.
+    pub fn verify(
+        &self,
+        verifier: &dyn Verifier,
+        public_key: &str,
+    ) -> Result<NodeAttestation, InvalidArgumentError> {
+        if self.public_key != public_key {
+            return Err(InvalidArgumentError::new(
+                "public_key",
+                "attestation was signed by another key",
+            ));
+        }
+
+        let key_bytes = parse_hex(public_key)
+            .map_err(|err| InvalidArgumentError::new("public_key", err.to_string()))?;
+        let signature_bytes = parse_hex(&self.signature)
+            .map_err(|err| InvalidArgumentError::new("signature", err.to_string()))?;
+
+        match verifier.verify(
+            self.attestation.as_bytes(),
+            &Signature::new(signature_bytes),
+            &PublicKey::new(key_bytes),
+        ) {
+            Ok(true) => (),
+            Ok(false) => {
+                return Err(InvalidArgumentError::new(
+                    "signature",
+                    "signature does not match the attestation",
+                ))
+            }
+            Err(err) => return Err(InvalidArgumentError::new("signature", err.to_string())),
+        }
+
+        let attestation: NodeAttestation = serde_json::from_str(&self.attestation)
+            .map_err(|err| InvalidArgumentError::new("attestation", err.to_string()))?;
+
+        if !attestation.public_keys.iter().any(|key| key == public_key) {
+            return Err(InvalidArgumentError::new(
+                "public_key",
+                format!("key is not one of node {}'s keys", attestation.node_id),
+            ));
+        }
+
+        Ok(attestation)
+    }
+}
+
+/// Makes signed attestations of the node's identity.
+///
+/// The attestation lists the public keys of all of the node's signers, and is signed by the
+/// first of them.
+#[derive(Clone)]
+pub struct NodeAttestor {
+    node_id: String,
+    endpoints: Vec<String>,
+    software_version: String,
+    signers: Arc<Mutex<Vec<Box<dyn Signer>>>>,
+}
+
+impl NodeAttestor {
+    /// Constructs a new `NodeAttestor`.
+    ///
+    /// # Arguments
+    ///
+    /// * `node_id` - The ID of the node
+    /// * `endpoints` - The endpoints the node advertises to its peers
+    /// * `software_version` - The version of the software the node runs
+    /// * `signers` - The node's signers
+    ///
+    /// # Errors
+    ///
+    /// Returns an [InvalidArgumentError] if no signers are given.
+    pub fn new(
+        node_id: &str,
+        endpoints: Vec<String>,
+        software_version: &str,
+        signers: Vec<Box<dyn Signer>>,
+    ) -> Result<Self, InvalidArgumentError> {
+        if signers.is_empty() {
+            return Err(InvalidArgumentError::new(
+                "signers",
+                "at least one signer is required",
+            ));
+        }
+
+        Ok(Self {
+            node_id: node_id.to_string(),
+            endpoints,
+            software_version: software_version.to_string(),
+            signers: Arc::new(Mutex::new(signers)),
+        })
+    }
+
+    /// Makes an attestation of the node's identity at the current time, and signs it.
+    pub fn attest(&self) -> Result<SignedNodeAttestation, InternalError> {
+        let signers = self.signers.lock().map_err(|_| {
+            InternalError::with_message("Node attestor's signer lock poisoned".into())
+        })?;
+
+        let public_keys = signers
+            .iter()
+            .map(|signer| {
+                signer
+                    .public_key()
+                    .map(|public_key| to_hex(public_key.as_slice()))
+                    .map_err(|err| InternalError::from_source(Box::new(err)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let attestation = serde_json::to_string(&NodeAttestation {
+            node_id: self.node_id.clone(),
+            public_keys: public_keys.clone(),
+            endpoints: self.endpoints.clone(),
+            software_version: self.software_version.clone(),
+            timestamp,
+        })
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let signature = signers[0]
+            .sign(attestation.as_bytes())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(SignedNodeAttestation {
+            attestation,
+            public_key: public_keys[0].clone(),
+            signature: to_hex(signature.as_slice()),
+        })
+    }
+}
+
+pub fn get_attestation(
+    attestor: &NodeAttestor,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match attestor.attest() {
+        Ok(signed_attestation) => {
+            Box::new(HttpResponse::Ok().json(signed_attestation).into_future())
+        }
+        Err(err) => {
+            error!("Unable to attest to the node's identity: {}", err);
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+
+    /// Verify that an attestation lists the keys of all of the node's signers, is signed by the
+    /// first of them, and is rejected if it is modified or checked against another key.
+    #[test]
+    fn test_attest_and_verify() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let other_signer = context.new_signer(context.new_random_private_key());
+        let public_key = to_hex(
+            signer
+                .public_key()
+                .expect("Failed to get public key")
+                .as_slice(),
+        );
+        let other_public_key = to_hex(
+            other_signer
+                .public_key()
+                .expect("Failed to get public key")
+                .as_slice(),
+        );
+
+        let attestor = NodeAttestor::new(
+            "node-1",
+            vec!["tcps://12.0.0.123:8044".to_string()],
+            "0.7.1",
+            vec![signer, other_signer],
+        )
+        .expect("Failed to create attestor");
+        let signed_attestation = attestor.attest().expect("Failed to attest");
+        assert_eq!(signed_attestation.public_key(), public_key);
+
+        let verifier = context.new_verifier();
+        let attestation = signed_attestation
+            .verify(&*verifier, &public_key)
+            .expect("Failed to verify attestation");
+        assert_eq!(attestation.node_id(), "node-1");
+        assert_eq!(
+            attestation.public_keys(),
+            &[public_key.clone(), other_public_key.clone()]
+        );
+        assert_eq!(attestation.endpoints(), &["tcps://12.0.0.123:8044"]);
+        assert_eq!(attestation.software_version(), "0.7.1");
+        assert!(attestation.timestamp() > 0);
+
+        assert!(signed_attestation
+            .verify(&*verifier, &other_public_key)
+            .is_err());
+
+        let mut modified_attestation = signed_attestation;
+        modified_attestation.attestation =
+            modified_attestation.attestation.replace("node-1", "node-2");
+        assert!(modified_attestation
+            .verify(&*verifier, &public_key)
+            .is_err());
+    }
+
+    /// Verify that an attestor requires a signer.
+    #[test]
+    fn test_attestor_requires_signer() {
+        assert!(NodeAttestor::new("node-1", vec![], "0.7.1", vec![]).is_err());
+    }
+}
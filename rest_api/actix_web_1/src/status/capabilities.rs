@@ -0,0 +1,151 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /capabilities` endpoint, which reports the optional features
+//! supported by the node, so that clients can adapt to the node instead of probing endpoints.
+
+use std::collections::BTreeMap;
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+/// The optional features supported by a node.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct Capabilities {
+    service_types: Vec<String>,
+    scabbard_versions: Vec<String>,
+    consensus_engines: Vec<String>,
+    protocols: BTreeMap<String, u32>,
+    auth_types: Vec<String>,
+    oauth_provider: Option<String>,
+    biome: Vec<String>,
+}
+
+impl Capabilities {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a service type the node is able to run.
+    pub fn with_service_type(mut self, service_type: &str) -> Self {
+        self.service_types.push(service_type.to_string());
+        self
+    }
+
+    /// Adds a version of scabbard the node is able to run, such as `v2`.
+    pub fn with_scabbard_version(mut self, version: &str) -> Self {
+        self.scabbard_versions.push(version.to_string());
+        self
+    }
+
+    /// Adds a consensus engine the node is able to run.
+    pub fn with_consensus_engine(mut self, consensus_engine: &str) -> Self {
+        self.consensus_engines.push(consensus_engine.to_string());
+        self
+    }
+
+    /// Adds a protocol, and the version of it, that the node supports.
+    pub fn with_protocol(mut self, protocol: &str, version: u32) -> Self {
+        self.protocols.insert(protocol.to_string(), version);
+        self
+    }
+
+    /// Adds a type of authentication the REST API accepts, such as `cylinder` or `biome`.
+    pub fn with_auth_type(mut self, auth_type: &str) -> Self {
+        self.auth_types.push(auth_type.to_string());
+        self
+    }
+
+    /// Sets the OAuth provider users may log in with.
+    pub fn with_oauth_provider(mut self, oauth_provider: &str) -> Self {
+        self.oauth_provider = Some(oauth_provider.to_string());
+        self
+    }
+
+    /// Adds a Biome component whose endpoints are available, such as `credentials`.
+    pub fn with_biome_component(mut self, component: &str) -> Self {
+        self.biome.push(component.to_string());
+        self
+    }
+
+    pub fn service_types(&self) -> &[String] {
+        &self.service_types
+    }
+
+    pub fn scabbard_versions(&self) -> &[String] {
+        &self.scabbard_versions
+    }
+
+    pub fn consensus_engines(&self) -> &[String] {
+        &self.consensus_engines
+    }
+
+    pub fn protocols(&self) -> &BTreeMap<String, u32> {
+        &self.protocols
+    }
+
+    pub fn auth_types(&self) -> &[String] {
+        &self.auth_types
+    }
+
+    /// Returns the OAuth provider, or `None` if OAuth is not configured.
+    pub fn oauth_provider(&self) -> Option<&str> {
+        self.oauth_provider.as_deref()
+    }
+
+    pub fn biome(&self) -> &[String] {
+        &self.biome
+    }
+}
+
+pub fn get_capabilities(
+    capabilities: &Capabilities,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    Box::new(HttpResponse::Ok().json(capabilities).into_future())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the capabilities serialize with every feature listed, and with a null OAuth
+    /// provider if OAuth is not configured.
+    #[test]
+    fn test_capabilities_serialization() {
+        let capabilities = Capabilities::new()
+            .with_service_type("scabbard")
+            .with_scabbard_version("v2")
+            .with_consensus_engine("2pc")
+            .with_protocol("admin", 2)
+            .with_auth_type("cylinder")
+            .with_auth_type("biome")
+            .with_biome_component("credentials");
+
+        assert_eq!(
+            serde_json::to_value(&capabilities).expect("Failed to serialize capabilities"),
+            serde_json::json!({
+                "service_types": ["scabbard"],
+                "scabbard_versions": ["v2"],
+                "consensus_engines": ["2pc"],
+                "protocols": {"admin": 2},
+                "auth_types": ["cylinder", "biome"],
+                "oauth_provider": null,
+                "biome": ["credentials"],
+            })
+        );
+
+        let capabilities = capabilities.with_oauth_provider("github");
+        assert_eq!(capabilities.oauth_provider(), Some("github"));
+    }
+}
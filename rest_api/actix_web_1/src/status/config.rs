@@ -0,0 +1,133 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /config` endpoint, which reports the configuration the node is
+//! running with, after the config file, environment, command line and defaults are merged, and
+//! where each value came from.
+
+use std::sync::{Arc, RwLock};
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::rest_api::ErrorResponse;
+
+/// A configuration value and its source.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ConfigValue {
+    name: String,
+    value: String,
+    source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+}
+
+impl ConfigValue {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the source of the value, such as `toml`, `cli`, `env` or `default`.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the file the value was read from, if its source is a file.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+}
+
+/// The configuration values of a node.
+///
+/// The values must already be redacted; the report returns them as they are given.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ConfigReport {
+    data: Vec<ConfigValue>,
+}
+
+impl ConfigReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a value, with its source and, if it was read from a file, the file.
+    pub fn with_value(mut self, name: &str, value: &str, source: &str, file: Option<&str>) -> Self {
+        self.data.push(ConfigValue {
+            name: name.to_string(),
+            value: value.to_string(),
+            source: source.to_string(),
+            file: file.map(String::from),
+        });
+        self
+    }
+
+    pub fn values(&self) -> &[ConfigValue] {
+        &self.data
+    }
+}
+
+pub fn get_config_report(
+    report: &Arc<RwLock<ConfigReport>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match report.read() {
+        Ok(report) => Box::new(HttpResponse::Ok().json(&*report).into_future()),
+        Err(_) => Box::new(
+            HttpResponse::InternalServerError()
+                .json(ErrorResponse::internal_error())
+                .into_future(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the report serializes each value in order, with the file only for values read
+    /// from a file.
+    #[test]
+    fn test_config_report_serialization() {
+        let report = ConfigReport::new()
+            .with_value(
+                "node_id",
+                "alpha",
+                "toml",
+                Some("/etc/splinter/splinterd.toml"),
+            )
+            .with_value("heartbeat", "30", "default", None);
+
+        assert_eq!(
+            serde_json::to_value(&report).expect("Failed to serialize report"),
+            serde_json::json!({
+                "data": [
+                    {
+                        "name": "node_id",
+                        "value": "alpha",
+                        "source": "toml",
+                        "file": "/etc/splinter/splinterd.toml",
+                    },
+                    {
+                        "name": "heartbeat",
+                        "value": "30",
+                        "source": "default",
+                    },
+                ],
+            })
+        );
+    }
+}
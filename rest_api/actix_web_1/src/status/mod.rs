@@ -13,7 +13,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "node-attestation")]
+mod attestation;
+#[cfg(feature = "capabilities-report")]
+mod capabilities;
+#[cfg(feature = "config-report")]
+mod config;
 mod resource_provider;
+#[cfg(feature = "startup-report")]
+mod startup;
 
 use actix_web::{Error, HttpResponse};
 use futures::{Future, IntoFuture};
@@ -21,7 +29,15 @@ use futures::{Future, IntoFuture};
 use splinter::rest_api::auth::authorization::Permission;
 use splinter_rest_api_common::status::Status;
 
+#[cfg(feature = "node-attestation")]
+pub use attestation::{NodeAttestation, NodeAttestor, SignedNodeAttestation};
+#[cfg(feature = "capabilities-report")]
+pub use capabilities::Capabilities;
+#[cfg(feature = "config-report")]
+pub use config::{ConfigReport, ConfigValue};
 pub use resource_provider::StatusResourceProvider;
+#[cfg(feature = "startup-report")]
+pub use startup::{StartupPhase, StartupReport};
 
 #[cfg(feature = "authorization")]
 pub const STATUS_READ_PERMISSION: Permission = Permission::Check {
@@ -30,6 +46,13 @@ pub const STATUS_READ_PERMISSION: Permission = Permission::Check {
     permission_description: "Allows the client to get node status info",
 };
 
+#[cfg(all(feature = "authorization", feature = "config-report"))]
+pub const CONFIG_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "config.read",
+    permission_display_name: "Config read",
+    permission_description: "Allows the client to view the node's configuration",
+};
+
 pub fn get_status(
     node_id: String,
     display_name: String,
@@ -13,9 +13,22 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(any(feature = "config-report", feature = "startup-report"))]
+use std::sync::{Arc, RwLock};
+
 use splinter::rest_api::{Resource, RestResourceProvider};
 
+#[cfg(feature = "node-attestation")]
+use super::attestation::{get_attestation, NodeAttestor};
+#[cfg(feature = "capabilities-report")]
+use super::capabilities::{get_capabilities, Capabilities};
+#[cfg(feature = "config-report")]
+use super::config::{get_config_report, ConfigReport};
 use super::get_status;
+#[cfg(feature = "startup-report")]
+use super::startup::{get_startup_report, StartupReport};
+#[cfg(all(feature = "authorization", feature = "config-report"))]
+use super::CONFIG_READ_PERMISSION;
 #[cfg(feature = "authorization")]
 use super::STATUS_READ_PERMISSION;
 
@@ -59,6 +72,78 @@ impl StatusResourceProvider {
             Self { resources }
         }
     }
+
+    /// Adds the `GET /status/startup` endpoint, which returns the timing breakdown of the node's
+    /// startup from the given report.
+    #[cfg(feature = "startup-report")]
+    pub fn with_startup_report(mut self, report: Arc<RwLock<StartupReport>>) -> Self {
+        let handle = move |_, _| get_startup_report(&report);
+        #[cfg(feature = "authorization")]
+        let startup_resource = Resource::build("/status/startup").add_method(
+            splinter::rest_api::Method::Get,
+            STATUS_READ_PERMISSION,
+            handle,
+        );
+        #[cfg(not(feature = "authorization"))]
+        let startup_resource =
+            Resource::build("/status/startup").add_method(splinter::rest_api::Method::Get, handle);
+        self.resources.push(startup_resource);
+        self
+    }
+
+    /// Adds the `GET /capabilities` endpoint, which returns the given optional features supported
+    /// by the node.
+    #[cfg(feature = "capabilities-report")]
+    pub fn with_capabilities(mut self, capabilities: Capabilities) -> Self {
+        let handle = move |_, _| get_capabilities(&capabilities);
+        #[cfg(feature = "authorization")]
+        let capabilities_resource = Resource::build("/capabilities").add_method(
+            splinter::rest_api::Method::Get,
+            STATUS_READ_PERMISSION,
+            handle,
+        );
+        #[cfg(not(feature = "authorization"))]
+        let capabilities_resource =
+            Resource::build("/capabilities").add_method(splinter::rest_api::Method::Get, handle);
+        self.resources.push(capabilities_resource);
+        self
+    }
+
+    /// Adds the `GET /config` endpoint, which returns the node's configuration from the given
+    /// report.
+    #[cfg(feature = "config-report")]
+    pub fn with_config_report(mut self, report: Arc<RwLock<ConfigReport>>) -> Self {
+        let handle = move |_, _| get_config_report(&report);
+        #[cfg(feature = "authorization")]
+        let config_resource = Resource::build("/config").add_method(
+            splinter::rest_api::Method::Get,
+            CONFIG_READ_PERMISSION,
+            handle,
+        );
+        #[cfg(not(feature = "authorization"))]
+        let config_resource =
+            Resource::build("/config").add_method(splinter::rest_api::Method::Get, handle);
+        self.resources.push(config_resource);
+        self
+    }
+
+    /// Adds the `GET /status/attestation` endpoint, which returns an attestation of the node's
+    /// identity signed by the given attestor.
+    #[cfg(feature = "node-attestation")]
+    pub fn with_attestation(mut self, attestor: NodeAttestor) -> Self {
+        let handle = move |_, _| get_attestation(&attestor);
+        #[cfg(feature = "authorization")]
+        let attestation_resource = Resource::build("/status/attestation").add_method(
+            splinter::rest_api::Method::Get,
+            STATUS_READ_PERMISSION,
+            handle,
+        );
+        #[cfg(not(feature = "authorization"))]
+        let attestation_resource = Resource::build("/status/attestation")
+            .add_method(splinter::rest_api::Method::Get, handle);
+        self.resources.push(attestation_resource);
+        self
+    }
 }
 
 impl RestResourceProvider for StatusResourceProvider {
@@ -0,0 +1,126 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /status/startup` endpoint, which reports how long each phase of
+//! the node's startup took.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use actix_web::{Error, HttpResponse};
+use futures::{future::IntoFuture, Future};
+use splinter::rest_api::ErrorResponse;
+
+/// The time taken by a single phase of the node's startup.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StartupPhase {
+    name: String,
+    duration_millis: u64,
+}
+
+impl StartupPhase {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn duration_millis(&self) -> u64 {
+        self.duration_millis
+    }
+}
+
+/// The timing breakdown of the node's startup.
+///
+/// Phases are added as they finish, so the report may be read while the node is still starting;
+/// the total is only set once startup is complete.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct StartupReport {
+    phases: Vec<StartupPhase>,
+    total_millis: Option<u64>,
+}
+
+impl StartupReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a phase that has finished, with the time it took.
+    pub fn add_phase(&mut self, name: &str, duration: Duration) {
+        self.phases.push(StartupPhase {
+            name: name.to_string(),
+            duration_millis: duration.as_millis() as u64,
+        });
+    }
+
+    /// Marks startup as complete, with the total time it took.
+    pub fn set_complete(&mut self, total: Duration) {
+        self.total_millis = Some(total.as_millis() as u64);
+    }
+
+    pub fn phases(&self) -> &[StartupPhase] {
+        &self.phases
+    }
+
+    /// Returns the total time startup took, or `None` if it has not completed.
+    pub fn total_millis(&self) -> Option<u64> {
+        self.total_millis
+    }
+}
+
+pub fn get_startup_report(
+    report: &Arc<RwLock<StartupReport>>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match report.read() {
+        Ok(report) => Box::new(HttpResponse::Ok().json(&*report).into_future()),
+        Err(_) => Box::new(
+            HttpResponse::InternalServerError()
+                .json(ErrorResponse::internal_error())
+                .into_future(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a startup report serializes its phases in the order they were added, and that
+    /// the total is only present once startup is complete.
+    #[test]
+    fn test_startup_report_serialization() {
+        let mut report = StartupReport::new();
+        report.add_phase("store_init", Duration::from_millis(1500));
+
+        assert_eq!(
+            serde_json::to_value(&report).expect("Failed to serialize report"),
+            serde_json::json!({
+                "phases": [{"name": "store_init", "duration_millis": 1500}],
+                "total_millis": null,
+            })
+        );
+
+        report.add_phase("rest_bind", Duration::from_micros(2500));
+        report.set_complete(Duration::from_secs(3));
+
+        assert_eq!(
+            serde_json::to_value(&report).expect("Failed to serialize report"),
+            serde_json::json!({
+                "phases": [
+                    {"name": "store_init", "duration_millis": 1500},
+                    {"name": "rest_bind", "duration_millis": 2},
+                ],
+                "total_millis": 3000,
+            })
+        );
+    }
+}
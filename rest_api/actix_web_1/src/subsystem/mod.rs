@@ -0,0 +1,91 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module defines the REST API endpoints for stopping and starting individual subsystems of
+//! a running node, such as the registry refresher, the metrics recorder or a single service, for
+//! maintenance.
+
+mod resources;
+mod services;
+mod subsystems;
+
+use splinter::rest_api::actix_web_1::{Resource, RestResourceProvider};
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::runtime::service::instance::ServiceSuspendHandle;
+use splinter::threading::subsystem::SubsystemManager;
+
+#[cfg(feature = "authorization")]
+const SUBSYSTEMS_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "subsystem.read",
+    permission_display_name: "Subsystem read",
+    permission_description: "Allows the client to read whether the node's subsystems are running",
+};
+
+#[cfg(feature = "authorization")]
+const SUBSYSTEMS_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "subsystem.write",
+    permission_display_name: "Subsystem write",
+    permission_description: "Allows the client to stop and start the node's subsystems",
+};
+
+const SUBSYSTEMS_MIN: u32 = 2;
+
+pub struct SubsystemResourceProvider {
+    manager: SubsystemManager,
+    suspend_handle: Option<ServiceSuspendHandle>,
+}
+
+impl SubsystemResourceProvider {
+    pub fn new(manager: SubsystemManager) -> Self {
+        Self {
+            manager,
+            suspend_handle: None,
+        }
+    }
+
+    /// Adds the endpoints that suspend and resume the services run by an orchestrator.
+    pub fn with_service_suspend_handle(mut self, suspend_handle: ServiceSuspendHandle) -> Self {
+        self.suspend_handle = Some(suspend_handle);
+        self
+    }
+}
+
+/// The `SubsystemResourceProvider` struct provides the following endpoints as REST API
+/// resources:
+///
+/// * `GET /subsystems` - List the subsystems, whether they are running, and the suspended
+///   services
+/// * `POST /subsystems/{name}/stop` - Stop a subsystem
+/// * `POST /subsystems/{name}/start` - Start a stopped subsystem
+/// * `POST /subsystems/services/{circuit_id}/{service_id}/stop` - Suspend a service
+/// * `POST /subsystems/services/{circuit_id}/{service_id}/start` - Resume a suspended service
+///
+/// The service endpoints are only provided if a suspend handle was added.
+impl RestResourceProvider for SubsystemResourceProvider {
+    fn resources(&self) -> Vec<Resource> {
+        let mut resources = vec![
+            subsystems::make_subsystems_resource(self.manager.clone(), self.suspend_handle.clone()),
+            subsystems::make_subsystem_stop_resource(self.manager.clone()),
+            subsystems::make_subsystem_start_resource(self.manager.clone()),
+        ];
+        if let Some(suspend_handle) = &self.suspend_handle {
+            resources.push(services::make_service_stop_resource(suspend_handle.clone()));
+            resources.push(services::make_service_start_resource(
+                suspend_handle.clone(),
+            ));
+        }
+        resources
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use splinter::runtime::service::instance::ServiceDefinition;
+use splinter::threading::subsystem::SubsystemStatus;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SubsystemResponse {
+    pub name: String,
+    pub running: bool,
+}
+
+impl From<&SubsystemStatus> for SubsystemResponse {
+    fn from(status: &SubsystemStatus) -> Self {
+        Self {
+            name: status.name().to_string(),
+            running: status.is_running(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SuspendedServiceResponse {
+    pub circuit: String,
+    pub service_id: String,
+    pub service_type: String,
+}
+
+impl From<&ServiceDefinition> for SuspendedServiceResponse {
+    fn from(service: &ServiceDefinition) -> Self {
+        Self {
+            circuit: service.circuit.clone(),
+            service_id: service.service_id.clone(),
+            service_type: service.service_type.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ListSubsystemsResponse {
+    pub data: Vec<SubsystemResponse>,
+    pub suspended_services: Vec<SuspendedServiceResponse>,
+}
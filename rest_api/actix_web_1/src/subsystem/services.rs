@@ -0,0 +1,122 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `POST /subsystems/services/{circuit_id}/{service_id}/stop` and
+//! `POST /subsystems/services/{circuit_id}/{service_id}/start` endpoints, which suspend and
+//! resume a single service run by the orchestrator.
+
+use actix_web::{Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::error::InternalError;
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+use splinter::rest_api::ErrorResponse;
+use splinter::runtime::service::instance::ServiceSuspendHandle;
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::SUBSYSTEMS_MIN;
+#[cfg(feature = "authorization")]
+use super::SUBSYSTEMS_WRITE_PERMISSION;
+
+pub fn make_service_stop_resource(suspend_handle: ServiceSuspendHandle) -> Resource {
+    let resource =
+        Resource::build("/subsystems/services/{circuit_id}/{service_id}/stop").add_request_guard(
+            ProtocolVersionRangeGuard::new(SUBSYSTEMS_MIN, SPLINTER_PROTOCOL_VERSION),
+        );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, SUBSYSTEMS_WRITE_PERMISSION, move |r, _| {
+            stop_service(r, &suspend_handle)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |r, _| stop_service(r, &suspend_handle))
+    }
+}
+
+pub fn make_service_start_resource(suspend_handle: ServiceSuspendHandle) -> Resource {
+    let resource =
+        Resource::build("/subsystems/services/{circuit_id}/{service_id}/start").add_request_guard(
+            ProtocolVersionRangeGuard::new(SUBSYSTEMS_MIN, SPLINTER_PROTOCOL_VERSION),
+        );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, SUBSYSTEMS_WRITE_PERMISSION, move |r, _| {
+            start_service(r, &suspend_handle)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |r, _| start_service(r, &suspend_handle))
+    }
+}
+
+fn stop_service(
+    request: HttpRequest,
+    suspend_handle: &ServiceSuspendHandle,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = request.match_info().get("circuit_id").unwrap_or("");
+    let service_id = request.match_info().get("service_id").unwrap_or("");
+    to_response(
+        circuit_id,
+        service_id,
+        "running",
+        suspend_handle.suspend_service(circuit_id, service_id),
+    )
+}
+
+fn start_service(
+    request: HttpRequest,
+    suspend_handle: &ServiceSuspendHandle,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let circuit_id = request.match_info().get("circuit_id").unwrap_or("");
+    let service_id = request.match_info().get("service_id").unwrap_or("");
+    to_response(
+        circuit_id,
+        service_id,
+        "suspended",
+        suspend_handle.resume_service(circuit_id, service_id),
+    )
+}
+
+fn to_response(
+    circuit_id: &str,
+    service_id: &str,
+    state: &str,
+    result: Result<bool, InternalError>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match result {
+        Ok(true) => Box::new(HttpResponse::Ok().finish().into_future()),
+        Ok(false) => Box::new(
+            HttpResponse::NotFound()
+                .json(ErrorResponse::not_found(&format!(
+                    "No {} service {}::{}",
+                    state, circuit_id, service_id
+                )))
+                .into_future(),
+        ),
+        Err(err) => {
+            error!(
+                "Unable to stop or start service {}::{}: {}",
+                circuit_id, service_id, err
+            );
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            )
+        }
+    }
+}
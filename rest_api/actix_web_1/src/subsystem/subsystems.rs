@@ -0,0 +1,165 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module provides the `GET /subsystems`, `POST /subsystems/{name}/stop` and
+//! `POST /subsystems/{name}/start` endpoints.
+
+use actix_web::{Error, HttpRequest, HttpResponse};
+use futures::{future::IntoFuture, Future};
+
+use splinter::rest_api::actix_web_1::{Method, ProtocolVersionRangeGuard, Resource};
+use splinter::rest_api::ErrorResponse;
+use splinter::runtime::service::instance::ServiceSuspendHandle;
+use splinter::threading::subsystem::{SubsystemError, SubsystemManager};
+use splinter_rest_api_common::SPLINTER_PROTOCOL_VERSION;
+
+use super::resources::{ListSubsystemsResponse, SubsystemResponse, SuspendedServiceResponse};
+use super::SUBSYSTEMS_MIN;
+#[cfg(feature = "authorization")]
+use super::{SUBSYSTEMS_READ_PERMISSION, SUBSYSTEMS_WRITE_PERMISSION};
+
+pub fn make_subsystems_resource(
+    manager: SubsystemManager,
+    suspend_handle: Option<ServiceSuspendHandle>,
+) -> Resource {
+    let resource = Resource::build("/subsystems").add_request_guard(
+        ProtocolVersionRangeGuard::new(SUBSYSTEMS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Get, SUBSYSTEMS_READ_PERMISSION, move |_, _| {
+            list_subsystems(&manager, suspend_handle.as_ref())
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Get, move |_, _| {
+            list_subsystems(&manager, suspend_handle.as_ref())
+        })
+    }
+}
+
+pub fn make_subsystem_stop_resource(manager: SubsystemManager) -> Resource {
+    let resource = Resource::build("/subsystems/{name}/stop").add_request_guard(
+        ProtocolVersionRangeGuard::new(SUBSYSTEMS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, SUBSYSTEMS_WRITE_PERMISSION, move |r, _| {
+            stop_subsystem(r, &manager)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |r, _| stop_subsystem(r, &manager))
+    }
+}
+
+pub fn make_subsystem_start_resource(manager: SubsystemManager) -> Resource {
+    let resource = Resource::build("/subsystems/{name}/start").add_request_guard(
+        ProtocolVersionRangeGuard::new(SUBSYSTEMS_MIN, SPLINTER_PROTOCOL_VERSION),
+    );
+    #[cfg(feature = "authorization")]
+    {
+        resource.add_method(Method::Post, SUBSYSTEMS_WRITE_PERMISSION, move |r, _| {
+            start_subsystem(r, &manager)
+        })
+    }
+    #[cfg(not(feature = "authorization"))]
+    {
+        resource.add_method(Method::Post, move |r, _| start_subsystem(r, &manager))
+    }
+}
+
+fn list_subsystems(
+    manager: &SubsystemManager,
+    suspend_handle: Option<&ServiceSuspendHandle>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let subsystems = match manager.list_subsystems() {
+        Ok(subsystems) => subsystems,
+        Err(err) => {
+            error!("Unable to list subsystems: {}", err);
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+    };
+    let suspended_services = match suspend_handle.map(|handle| handle.list_suspended_services()) {
+        Some(Ok(services)) => services,
+        Some(Err(err)) => {
+            error!("Unable to list suspended services: {}", err);
+            return Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            );
+        }
+        None => vec![],
+    };
+
+    Box::new(
+        HttpResponse::Ok()
+            .json(ListSubsystemsResponse {
+                data: subsystems.iter().map(SubsystemResponse::from).collect(),
+                suspended_services: suspended_services
+                    .iter()
+                    .map(SuspendedServiceResponse::from)
+                    .collect(),
+            })
+            .into_future(),
+    )
+}
+
+fn stop_subsystem(
+    request: HttpRequest,
+    manager: &SubsystemManager,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let name = request.match_info().get("name").unwrap_or("");
+    to_response(name, manager.stop_subsystem(name))
+}
+
+fn start_subsystem(
+    request: HttpRequest,
+    manager: &SubsystemManager,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    let name = request.match_info().get("name").unwrap_or("");
+    to_response(name, manager.start_subsystem(name))
+}
+
+fn to_response(
+    name: &str,
+    result: Result<(), SubsystemError>,
+) -> Box<dyn Future<Item = HttpResponse, Error = Error>> {
+    match result {
+        Ok(()) => Box::new(HttpResponse::Ok().finish().into_future()),
+        Err(SubsystemError::NotFound(_)) => Box::new(
+            HttpResponse::NotFound()
+                .json(ErrorResponse::not_found(&format!(
+                    "Subsystem not found: {}",
+                    name
+                )))
+                .into_future(),
+        ),
+        Err(SubsystemError::Internal(err)) => {
+            error!("Unable to stop or start subsystem {}: {}", name, err);
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(ErrorResponse::internal_error())
+                    .into_future(),
+            )
+        }
+    }
+}
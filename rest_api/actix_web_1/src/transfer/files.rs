@@ -0,0 +1,280 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::Read;
+use std::sync::Arc;
+
+use actix_web::{
+    error::{self, ErrorInternalServerError},
+    http::header,
+    web, Error as ActixError, HttpResponse,
+};
+use futures::{stream, stream::Stream, Future, IntoFuture};
+use splinter::{
+    rest_api::{ErrorResponse, Method},
+    service::{
+        rest_api::ServiceEndpoint,
+        transfer::{TransferService, SERVICE_TYPE},
+    },
+};
+
+#[cfg(feature = "authorization")]
+use super::{TRANSFER_READ_PERMISSION, TRANSFER_WRITE_PERMISSION};
+
+/// The size of the pieces that a file's content is streamed in
+const CONTENT_READ_SIZE: usize = 64 * 1024;
+
+/// `POST /send?name=<name>` stores the request body as a file and offers it to the service's
+/// peers, responding with the file's transfer record. A body larger than the service's maximum
+/// file size is rejected with `413 Payload Too Large`.
+pub fn make_send_file_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/send".into(),
+        method: Method::Post,
+        handler: Arc::new(move |request, payload, service| {
+            let transfer = match service.as_any().downcast_ref::<TransferService>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to transfer service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            }
+            .clone();
+            let max_file_size = transfer.max_file_size();
+
+            let content_length = request
+                .headers()
+                .get(header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok());
+            if matches!(content_length, Some(length) if length > max_file_size) {
+                return Box::new(payload_too_large(max_file_size).into_future());
+            }
+
+            let name =
+                match web::Query::<HashMap<String, String>>::from_query(request.query_string()) {
+                    Ok(query) => match query.get("name") {
+                        Some(name) if !name.is_empty() => name.clone(),
+                        _ => {
+                            return Box::new(
+                                HttpResponse::BadRequest()
+                                    .json(ErrorResponse::bad_request("No file name specified"))
+                                    .into_future(),
+                            )
+                        }
+                    },
+                    Err(_) => {
+                        return Box::new(
+                            HttpResponse::BadRequest()
+                                .json(ErrorResponse::bad_request("Invalid query"))
+                                .into_future(),
+                        )
+                    }
+                };
+
+            Box::new(
+                payload
+                    .from_err::<ActixError>()
+                    .fold(web::BytesMut::new(), move |mut body, chunk| {
+                        if (body.len() + chunk.len()) as u64 > max_file_size {
+                            return Err(error::InternalError::from_response(
+                                "payload is larger than the maximum file size",
+                                payload_too_large(max_file_size),
+                            )
+                            .into());
+                        }
+                        body.extend_from_slice(&chunk);
+                        Ok::<_, ActixError>(body)
+                    })
+                    .into_future()
+                    .and_then(move |body| match transfer.send_file(&name, &body[..]) {
+                        Ok(record) => HttpResponse::Accepted().json(record).into_future(),
+                        Err(err) => {
+                            error!("Failed to send file: {}", err);
+                            HttpResponse::InternalServerError()
+                                .json(ErrorResponse::internal_error())
+                                .into_future()
+                        }
+                    }),
+            )
+        }),
+        request_guards: vec![],
+        #[cfg(feature = "authorization")]
+        permission: TRANSFER_WRITE_PERMISSION,
+    }
+}
+
+fn payload_too_large(max_file_size: u64) -> HttpResponse {
+    HttpResponse::PayloadTooLarge().json(ErrorResponse::payload_too_large(&format!(
+        "File is larger than the maximum of {} bytes",
+        max_file_size
+    )))
+}
+
+/// `GET /files` lists the transfer records of the files sent and received by the service.
+pub fn make_list_files_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/files".into(),
+        method: Method::Get,
+        handler: Arc::new(move |_, _, service| {
+            let transfer = match service.as_any().downcast_ref::<TransferService>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to transfer service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            Box::new(match transfer.list_transfers() {
+                Ok(records) => HttpResponse::Ok().json(records).into_future(),
+                Err(err) => {
+                    error!("Failed to list files: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![],
+        #[cfg(feature = "authorization")]
+        permission: TRANSFER_READ_PERMISSION,
+    }
+}
+
+/// `GET /files/{file_id}` gets the transfer record of a file, which includes the progress of an
+/// incoming transfer.
+pub fn make_get_file_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/files/{file_id}".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let transfer = match service.as_any().downcast_ref::<TransferService>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to transfer service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let file_id = request.match_info().get("file_id").unwrap_or("");
+
+            Box::new(match transfer.get_transfer(file_id) {
+                Ok(Some(record)) => HttpResponse::Ok().json(record).into_future(),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found(&format!(
+                        "File not found: {}",
+                        file_id
+                    )))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to get file: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![],
+        #[cfg(feature = "authorization")]
+        permission: TRANSFER_READ_PERMISSION,
+    }
+}
+
+/// `GET /files/{file_id}/content` streams the content of a file that has been completely sent or
+/// received.
+pub fn make_get_file_content_endpoint() -> ServiceEndpoint {
+    ServiceEndpoint {
+        service_type: SERVICE_TYPE.into(),
+        route: "/files/{file_id}/content".into(),
+        method: Method::Get,
+        handler: Arc::new(move |request, _, service| {
+            let transfer = match service.as_any().downcast_ref::<TransferService>() {
+                Some(s) => s,
+                None => {
+                    error!("Failed to downcast to transfer service");
+                    return Box::new(
+                        HttpResponse::InternalServerError()
+                            .json(ErrorResponse::internal_error())
+                            .into_future(),
+                    );
+                }
+            };
+
+            let file_id = request.match_info().get("file_id").unwrap_or("");
+
+            Box::new(match transfer.open_file(file_id) {
+                Ok(Some(file)) => HttpResponse::Ok()
+                    .content_type("application/octet-stream")
+                    .streaming(stream::iter_result(FileContent { file }))
+                    .into_future(),
+                Ok(None) => HttpResponse::NotFound()
+                    .json(ErrorResponse::not_found(&format!(
+                        "File not available: {}",
+                        file_id
+                    )))
+                    .into_future(),
+                Err(err) => {
+                    error!("Failed to open file: {}", err);
+                    HttpResponse::InternalServerError()
+                        .json(ErrorResponse::internal_error())
+                        .into_future()
+                }
+            })
+        }),
+        request_guards: vec![],
+        #[cfg(feature = "authorization")]
+        permission: TRANSFER_READ_PERMISSION,
+    }
+}
+
+/// Reads a file's content in pieces for a streaming response.
+struct FileContent {
+    file: File,
+}
+
+impl Iterator for FileContent {
+    type Item = Result<web::Bytes, ActixError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = vec![0; CONTENT_READ_SIZE];
+        match self.file.read(&mut buf) {
+            Ok(0) => None,
+            Ok(read) => {
+                buf.truncate(read);
+                Some(Ok(web::Bytes::from(buf)))
+            }
+            Err(err) => {
+                error!("Failed to read file: {}", err);
+                Some(Err(ErrorInternalServerError("failed to read file")))
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! REST API endpoints of the transfer service, which transfers files between the members of a
+//! circuit.
+
+pub mod files;
+
+#[cfg(feature = "authorization")]
+use splinter::rest_api::auth::authorization::Permission;
+use splinter::service::rest_api::{ServiceEndpoint, ServiceEndpointProvider};
+
+#[cfg(feature = "authorization")]
+pub const TRANSFER_READ_PERMISSION: Permission = Permission::Check {
+    permission_id: "transfer.read",
+    permission_display_name: "Transfer read",
+    permission_description: "Allows the client to list and download transfer services' files",
+};
+#[cfg(feature = "authorization")]
+pub const TRANSFER_WRITE_PERMISSION: Permission = Permission::Check {
+    permission_id: "transfer.write",
+    permission_display_name: "Transfer write",
+    permission_description: "Allows the client to send files through transfer services",
+};
+
+pub struct TransferServiceEndpointProvider {
+    endpoints: Vec<ServiceEndpoint>,
+}
+
+impl ServiceEndpointProvider for TransferServiceEndpointProvider {
+    fn endpoints(&self) -> Vec<ServiceEndpoint> {
+        self.endpoints.clone()
+    }
+}
+
+impl Default for TransferServiceEndpointProvider {
+    fn default() -> Self {
+        Self {
+            endpoints: vec![
+                files::make_send_file_endpoint(),
+                files::make_list_files_endpoint(),
+                files::make_get_file_endpoint(),
+                files::make_get_file_content_endpoint(),
+            ],
+        }
+    }
+}
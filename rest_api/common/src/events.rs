@@ -0,0 +1,218 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Versioned envelopes for the events sent by event streams.
+//!
+//! By default, an event stream sends each event as its JSON form, which gains fields as the
+//! event does. A client that adds `envelope=1` to the stream's query string instead receives each
+//! event wrapped in an envelope, whose shape does not change:
+//!
+//! ```json
+//! {
+//!   "type": "scabbard_state_change",
+//!   "version": 1,
+//!   "timestamp": 1650000000000,
+//!   "payload": { ... }
+//! }
+//! ```
+//!
+//! `type` names the kind of event, and `version` the version of its `payload`; a client should
+//! ignore the events whose type or version it does not know. `timestamp` is the time the event
+//! was sent, in milliseconds since the UNIX epoch. The event types are:
+//!
+//! * `admin_service_event` - an admin service event; its version is the SplinterProtocolVersion
+//!   the stream was opened with
+//! * `scabbard_state_change` - a change to a scabbard service's state (version 1)
+//! * `scabbard_batch_status` - a status transition of a scabbard batch (version 1)
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+/// The query parameter that selects the format of a stream's events
+pub const EVENT_ENVELOPE_QUERY_PARAM: &str = "envelope";
+
+/// The only envelope format version
+pub const EVENT_ENVELOPE_VERSION: u32 = 1;
+
+/// The type of admin service events
+pub const ADMIN_SERVICE_EVENT_TYPE: &str = "admin_service_event";
+
+/// The type and payload version of scabbard state change events
+pub const SCABBARD_STATE_CHANGE_EVENT_TYPE: &str = "scabbard_state_change";
+pub const SCABBARD_STATE_CHANGE_EVENT_VERSION: u32 = 1;
+
+/// The type and payload version of scabbard batch status events
+pub const SCABBARD_BATCH_STATUS_EVENT_TYPE: &str = "scabbard_batch_status";
+pub const SCABBARD_BATCH_STATUS_EVENT_VERSION: u32 = 1;
+
+/// The format in which a stream sends its events.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventFormat {
+    /// Each event is sent as its JSON form
+    Bare,
+    /// Each event is sent in an [EventEnvelope]
+    Envelope,
+}
+
+impl EventFormat {
+    /// Negotiates the format from a request's query string: events are wrapped in envelopes if
+    /// the query string has `envelope=1`, and are sent bare if it has no `envelope` parameter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error message if the query string asks for an unsupported envelope version.
+    pub fn from_query(query_string: &str) -> Result<Self, String> {
+        let requested = query_string
+            .split('&')
+            .filter_map(|param| param.split_once('='))
+            .find(|(key, _)| *key == EVENT_ENVELOPE_QUERY_PARAM)
+            .map(|(_, value)| value);
+
+        match requested {
+            None => Ok(EventFormat::Bare),
+            Some(version) if version.parse::<u32>().ok() == Some(EVENT_ENVELOPE_VERSION) => {
+                Ok(EventFormat::Envelope)
+            }
+            Some(version) => Err(format!(
+                "Unsupported {} version: {}; the supported version is {}",
+                EVENT_ENVELOPE_QUERY_PARAM, version, EVENT_ENVELOPE_VERSION
+            )),
+        }
+    }
+
+    /// Puts an event into this format.
+    ///
+    /// # Arguments
+    ///
+    /// * `event_type` - The type of the event
+    /// * `version` - The version of the event's payload
+    /// * `payload` - The event
+    pub fn format<T: Serialize>(
+        self,
+        event_type: &str,
+        version: u32,
+        payload: T,
+    ) -> StreamEvent<T> {
+        match self {
+            EventFormat::Bare => StreamEvent::Bare(payload),
+            EventFormat::Envelope => {
+                StreamEvent::Envelope(EventEnvelope::new(event_type, version, payload))
+            }
+        }
+    }
+}
+
+/// An event, wrapped with its type, the version of its payload, and the time it was sent.
+#[derive(Clone, Debug, Serialize)]
+pub struct EventEnvelope<T: Serialize> {
+    #[serde(rename = "type")]
+    event_type: String,
+    version: u32,
+    timestamp: u64,
+    payload: T,
+}
+
+impl<T: Serialize> EventEnvelope<T> {
+    /// Wraps the given event, timestamped with the current time.
+    pub fn new(event_type: &str, version: u32, payload: T) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+
+        Self {
+            event_type: event_type.to_string(),
+            version,
+            timestamp,
+            payload,
+        }
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn payload(&self) -> &T {
+        &self.payload
+    }
+}
+
+/// An event as it is sent by a stream, in the format the client negotiated.
+#[derive(Clone, Debug, Serialize)]
+#[serde(untagged)]
+pub enum StreamEvent<T: Serialize> {
+    Bare(T),
+    Envelope(EventEnvelope<T>),
+}
+
+impl<T: Serialize> StreamEvent<T> {
+    /// Returns the event itself, without its envelope.
+    pub fn payload(&self) -> &T {
+        match self {
+            StreamEvent::Bare(payload) => payload,
+            StreamEvent::Envelope(envelope) => envelope.payload(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Serialize)]
+    struct TestEvent {
+        id: u32,
+    }
+
+    /// Verifies that the format is negotiated from the `envelope` query parameter.
+    #[test]
+    fn negotiate_format() {
+        assert_eq!(EventFormat::from_query(""), Ok(EventFormat::Bare));
+        assert_eq!(EventFormat::from_query("last=4"), Ok(EventFormat::Bare));
+        assert_eq!(
+            EventFormat::from_query("last=4&envelope=1"),
+            Ok(EventFormat::Envelope)
+        );
+        assert!(EventFormat::from_query("envelope=2").is_err());
+        assert!(EventFormat::from_query("envelope=true").is_err());
+    }
+
+    /// Verifies that a bare event is serialized as itself, and an enveloped event is serialized
+    /// with its type, version and timestamp.
+    #[test]
+    fn serialize_events() {
+        let bare = EventFormat::Bare.format("test_event", 3, TestEvent { id: 7 });
+        assert_eq!(
+            serde_json::to_value(&bare).expect("Failed to serialize"),
+            serde_json::json!({ "id": 7 })
+        );
+
+        let enveloped = EventFormat::Envelope.format("test_event", 3, TestEvent { id: 7 });
+        let value = serde_json::to_value(&enveloped).expect("Failed to serialize");
+        assert_eq!(value["type"], "test_event");
+        assert_eq!(value["version"], 3);
+        assert!(value["timestamp"].as_u64().expect("No timestamp") > 0);
+        assert_eq!(value["payload"], serde_json::json!({ "id": 7 }));
+        assert_eq!(enveloped.payload().id, 7);
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 pub mod error;
+pub mod events;
 pub mod paging;
 #[cfg(feature = "scabbard")]
 pub mod scabbard;
@@ -38,3 +38,8 @@ pub const SCABBARD_BATCH_STATUSES_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_GET_STATE_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_LIST_STATE_PROTOCOL_MIN: u32 = 1;
 pub const SCABBARD_STATE_ROOT_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_STATE_PROOF_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_SNAPSHOT_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_PRUNE_STATE_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_BATCH_STATUS_STREAM_PROTOCOL_MIN: u32 = 1;
+pub const SCABBARD_LIST_RECEIPTS_PROTOCOL_MIN: u32 = 1;
@@ -544,37 +544,96 @@ fn run() -> Result<(), CliError> {
                                 .default_value("300"),
                         ]),
                 ),
-        )
+        );
+
+    let state_subcommand = SubCommand::with_name("state")
+        .about("Get scabbard state information")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
         .subcommand(
-            SubCommand::with_name("state")
-                .about("Get scabbard state information")
-                .setting(AppSettings::SubcommandRequiredElseHelp)
-                .subcommand(
-                    SubCommand::with_name("root")
-                        .about("Get the current state root hash")
-                        .args(&[
-                            Arg::with_name("url")
-                                .help("URL to the scabbard REST API")
-                                .short("U")
-                                .long("url")
-                                .takes_value(true),
-                            Arg::with_name("service-id")
-                                .long_help(
-                                    "Fully-qualified service ID of the scabbard service (must be \
-                                     of the form 'circuit_id::service_id')",
-                                )
-                                .long("service-id")
-                                .takes_value(true)
-                                .required(true),
-                            Arg::with_name("key")
-                                .short("k")
-                                .long("key")
-                                .takes_value(true)
-                                .help("Name or path of private key"),
-                        ]),
-                ),
+            SubCommand::with_name("root")
+                .about("Get the current state root hash")
+                .args(&[
+                    Arg::with_name("url")
+                        .help("URL to the scabbard REST API")
+                        .short("U")
+                        .long("url")
+                        .takes_value(true),
+                    Arg::with_name("service-id")
+                        .long_help(
+                            "Fully-qualified service ID of the scabbard service (must be \
+                             of the form 'circuit_id::service_id')",
+                        )
+                        .long("service-id")
+                        .takes_value(true)
+                        .required(true),
+                    Arg::with_name("key")
+                        .short("k")
+                        .long("key")
+                        .takes_value(true)
+                        .help("Name or path of private key"),
+                ]),
         );
 
+    #[cfg(feature = "snapshot")]
+    let state_subcommand = state_subcommand.subcommand(
+        SubCommand::with_name("snapshot")
+            .about(
+                "Write a snapshot of the service's state and receipts to the node's snapshot \
+                 directory",
+            )
+            .args(&[
+                Arg::with_name("url")
+                    .help("URL to the scabbard REST API")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true),
+                Arg::with_name("service-id")
+                    .long_help(
+                        "Fully-qualified service ID of the scabbard service (must be of the form \
+                         'circuit_id::service_id')",
+                    )
+                    .long("service-id")
+                    .takes_value(true)
+                    .required(true),
+                Arg::with_name("key")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key"),
+            ]),
+    );
+
+    #[cfg(feature = "state-pruning")]
+    let state_subcommand = state_subcommand.subcommand(
+        SubCommand::with_name("prune")
+            .about(
+                "Prune the service's previous state roots that are beyond its retention policy, \
+                 and remove the state entries they no longer reference",
+            )
+            .args(&[
+                Arg::with_name("url")
+                    .help("URL to the scabbard REST API")
+                    .short("U")
+                    .long("url")
+                    .takes_value(true),
+                Arg::with_name("service-id")
+                    .long_help(
+                        "Fully-qualified service ID of the scabbard service (must be of the form \
+                         'circuit_id::service_id')",
+                    )
+                    .long("service-id")
+                    .takes_value(true)
+                    .required(true),
+                Arg::with_name("key")
+                    .short("k")
+                    .long("key")
+                    .takes_value(true)
+                    .help("Name or path of private key"),
+            ]),
+    );
+
+    app = app.subcommand(state_subcommand);
+
     let matches = app.get_matches();
 
     let log_level = match matches.occurrences_of("verbose") {
@@ -1177,6 +1236,69 @@ fn run() -> Result<(), CliError> {
 
                 Ok(())
             }
+            #[cfg(feature = "snapshot")]
+            ("snapshot", Some(matches)) => {
+                let url = matches
+                    .value_of("url")
+                    .map(ToOwned::to_owned)
+                    .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+                    .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+                let signer = load_signer(matches.value_of("key"))?;
+
+                let client = ReqwestScabbardClientBuilder::new()
+                    .with_url(&url)
+                    .with_auth(&create_cylinder_jwt_auth(signer)?)
+                    .build()?;
+
+                let full_service_id = matches
+                    .value_of("service-id")
+                    .ok_or_else(|| CliError::MissingArgument("service-id".into()))?;
+                let service_id = ServiceId::from_string(full_service_id)?;
+
+                let info = client.create_snapshot(&service_id)?;
+
+                println!(
+                    "Wrote snapshot {} at state root {} ({} entries, {} receipts)",
+                    info.file(),
+                    info.state_root(),
+                    info.entries(),
+                    info.receipts()
+                );
+
+                Ok(())
+            }
+            #[cfg(feature = "state-pruning")]
+            ("prune", Some(matches)) => {
+                let url = matches
+                    .value_of("url")
+                    .map(ToOwned::to_owned)
+                    .or_else(|| std::env::var(SPLINTER_REST_API_URL_ENV).ok())
+                    .unwrap_or_else(|| DEFAULT_SPLINTER_REST_API_URL.to_string());
+
+                let signer = load_signer(matches.value_of("key"))?;
+
+                let client = ReqwestScabbardClientBuilder::new()
+                    .with_url(&url)
+                    .with_auth(&create_cylinder_jwt_auth(signer)?)
+                    .build()?;
+
+                let full_service_id = matches
+                    .value_of("service-id")
+                    .ok_or_else(|| CliError::MissingArgument("service-id".into()))?;
+                let service_id = ServiceId::from_string(full_service_id)?;
+
+                let info = client.prune_state(&service_id)?;
+
+                println!(
+                    "Pruned {} previous state root(s), {} retained; current state root is {}",
+                    info.pruned_roots(),
+                    info.retained_roots(),
+                    info.state_root()
+                );
+
+                Ok(())
+            }
             _ => Err(CliError::InvalidSubcommand),
         },
         _ => Err(CliError::InvalidSubcommand),
@@ -28,6 +28,11 @@ pub use self::error::ScabbardClientError;
 pub use self::reqwest::ReqwestScabbardClient;
 #[cfg(feature = "reqwest")]
 pub use self::reqwest::ReqwestScabbardClientBuilder;
+#[cfg(feature = "snapshot")]
+pub use crate::snapshot::SnapshotInfo;
+pub use crate::state_proof::{InvalidStateProofError, StateProof, StateProofNode};
+#[cfg(feature = "state-pruning")]
+pub use crate::state_pruning::StatePruneInfo;
 
 /// A fully-qualified service ID (circuit and service ID)
 pub struct ServiceId {
@@ -146,6 +151,27 @@ pub trait ScabbardClient {
         address: &str,
     ) -> Result<Option<Vec<u8>>, ScabbardClientError>;
 
+    /// Get a Merkle proof of the value at the given `address` in state for the scabbard instance
+    /// with the given `service_id`. The proof is generated at the given `state_root`, or at the
+    /// current state root if none is given. Returns `None` if the state root is not known to the
+    /// scabbard instance.
+    ///
+    /// The returned proof should be checked with [`StateProof::verify`] against a state root
+    /// obtained from a trusted source.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The given address is not a valid hex address
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    fn get_state_proof(
+        &self,
+        service_id: &ServiceId,
+        address: &str,
+        state_root: Option<&str>,
+    ) -> Result<Option<StateProof>, ScabbardClientError>;
+
     /// Get all entries under the given address `prefix` in state for the scabbard instance with
     /// the given `service_id`.
     ///
@@ -170,6 +196,30 @@ pub trait ScabbardClient {
     /// * An internal error based on the underlying implementation
     fn get_current_state_root(&self, service_id: &ServiceId)
         -> Result<String, ScabbardClientError>;
+
+    /// Write a snapshot of the state and receipts of the scabbard instance with the given
+    /// `service_id` to its node's snapshot directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * Snapshots are not enabled on the node
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    #[cfg(feature = "snapshot")]
+    fn create_snapshot(&self, service_id: &ServiceId) -> Result<SnapshotInfo, ScabbardClientError>;
+
+    /// Prune the previous state roots of the scabbard instance with the given `service_id` that
+    /// are beyond its state retention policy, and remove the state entries that are no longer
+    /// referenced by any root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * An internal server error occurred in the scabbard service
+    /// * An internal error based on the underlying implementation
+    #[cfg(feature = "state-pruning")]
+    fn prune_state(&self, service_id: &ServiceId) -> Result<StatePruneInfo, ScabbardClientError>;
 }
 
 #[cfg(test)]
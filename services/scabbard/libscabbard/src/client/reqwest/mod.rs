@@ -30,7 +30,11 @@ use crate::protocol::SCABBARD_PROTOCOL_VERSION;
 
 use super::error::ScabbardClientError;
 use super::ScabbardClient;
-use super::{ServiceId, StateEntry};
+#[cfg(feature = "snapshot")]
+use super::SnapshotInfo;
+#[cfg(feature = "state-pruning")]
+use super::StatePruneInfo;
+use super::{ServiceId, StateEntry, StateProof};
 
 pub use builder::ReqwestScabbardClientBuilder;
 
@@ -146,6 +150,72 @@ impl ScabbardClient for ReqwestScabbardClient {
         }
     }
 
+    /// Get a Merkle proof of the value at the given `address` in state for the scabbard instance
+    /// with the given `service_id`, at the given `state_root` or at the current state root if none
+    /// is given. Returns `None` if the state root is not known to the scabbard instance.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error in any of the following cases:
+    /// * The client's URL was invalid
+    /// * The given address is not a valid hex address
+    /// * The REST API request failed
+    /// * An internal server error occurred in the scabbard service
+    fn get_state_proof(
+        &self,
+        service_id: &ServiceId,
+        address: &str,
+        state_root: Option<&str>,
+    ) -> Result<Option<StateProof>, ScabbardClientError> {
+        parse_hex(address)
+            .map_err(|err| ScabbardClientError::new_with_source("invalid address", err.into()))?;
+
+        let mut url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/state/{}/proof",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id(),
+            address
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+        if let Some(state_root) = state_root {
+            parse_hex(state_root).map_err(|err| {
+                ScabbardClientError::new_with_source("invalid state root", err.into())
+            })?;
+            url.set_query(Some(&format!("state_root={}", state_root)))
+        }
+
+        let response = Client::new()
+            .get(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            Ok(Some(response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize response body",
+                    err.into(),
+                )
+            })?))
+        } else if response.status().as_u16() == 404 {
+            Ok(None)
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to get state proof: {}: {}",
+                status, msg
+            )))
+        }
+    }
+
     /// Get all entries under the given address `prefix` in state for the scabbard instance with
     /// the given `service_id`.
     ///
@@ -253,6 +323,84 @@ impl ScabbardClient for ReqwestScabbardClient {
             )))
         }
     }
+
+    #[cfg(feature = "snapshot")]
+    fn create_snapshot(&self, service_id: &ServiceId) -> Result<SnapshotInfo, ScabbardClientError> {
+        let url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/snapshot",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id()
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+
+        let response = Client::new()
+            .post(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize response body",
+                    err.into(),
+                )
+            })
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to create snapshot: {}: {}",
+                status, msg
+            )))
+        }
+    }
+
+    #[cfg(feature = "state-pruning")]
+    fn prune_state(&self, service_id: &ServiceId) -> Result<StatePruneInfo, ScabbardClientError> {
+        let url = Url::parse(&format!(
+            "{}/scabbard/{}/{}/prune",
+            &self.url,
+            service_id.circuit(),
+            service_id.service_id()
+        ))
+        .map_err(|err| ScabbardClientError::new_with_source("invalid URL", err.into()))?;
+
+        let response = Client::new()
+            .post(url)
+            .header("SplinterProtocolVersion", SCABBARD_PROTOCOL_VERSION)
+            .header("Authorization", &self.auth)
+            .send()
+            .map_err(|err| ScabbardClientError::new_with_source("request failed", err.into()))?;
+
+        if response.status().is_success() {
+            response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize response body",
+                    err.into(),
+                )
+            })
+        } else {
+            let status = response.status();
+            let msg: ErrorResponse = response.json().map_err(|err| {
+                ScabbardClientError::new_with_source(
+                    "failed to deserialize error response body",
+                    err.into(),
+                )
+            })?;
+            Err(ScabbardClientError::new(&format!(
+                "failed to prune state: {}: {}",
+                status, msg
+            )))
+        }
+    }
 }
 
 /// Using the given `base_url` and `batch_link` to check batch statuses, `wait` the given duration
@@ -442,8 +590,8 @@ mod tests {
     };
 
     use actix_web::web;
-    use actix_web::HttpResponse;
-    use futures::future::IntoFuture;
+    use actix_web::{HttpRequest, HttpResponse};
+    use futures::future::{Future, IntoFuture};
     #[cfg(feature = "authorization")]
     use splinter::rest_api::auth::authorization::{
         AuthorizationHandler, AuthorizationHandlerResult, Permission,
@@ -459,17 +607,27 @@ mod tests {
             RestApiServerError, RestApiShutdownHandle,
         },
     };
+    use transact::{
+        database::{btree::BTreeDatabase, Database},
+        state::{
+            merkle::{kv, INDEXES},
+            StateChange, Write,
+        },
+    };
 
     const SCABBARD_ADD_BATCHES_PROTOCOL_MIN: u32 = 1;
     const SCABBARD_BATCH_STATUSES_PROTOCOL_MIN: u32 = 1;
     const SCABBARD_GET_STATE_PROTOCOL_MIN: u32 = 1;
     const SCABBARD_LIST_STATE_PROTOCOL_MIN: u32 = 1;
     const SCABBARD_STATE_ROOT_PROTOCOL_MIN: u32 = 1;
+    const SCABBARD_STATE_PROOF_PROTOCOL_MIN: u32 = 1;
 
     const MOCK_CIRCUIT_ID: &str = "01234-abcde";
     const MOCK_SERVICE_ID: &str = "ABCD";
     const MOCK_BATCH_ID: &str = "batch_id";
     const MOCK_STATE_ROOT_HASH: &str = "abcd";
+    const MOCK_PROOF_ADDRESS: &str =
+        "abcdef0000000000000000000000000000000000000000000000000000000000000001";
 
     const MOCK_AUTH: &str = "Bearer Cylinder:eyJhbGciOiJzZWNwMjU2azEiLCJ0eXAiOiJjeWxpbmRlcitqd3QifQ==.\
     eyJpc3MiOiIwMjA5MWEwNmNjNDZjNWUwZDg4ZTg5Mjg0OTM2ZWRiMTY4MDBiMDNiNTZhOGYxYjdlYzI5MmYyMzJiN2M4Mzg1YTIifQ==.\
@@ -691,6 +849,56 @@ mod tests {
         join_handle.join().expect("Unable to join rest api thread");
     }
 
+    /// Verify that the `ScabbardClient::get_state_proof` method works properly.
+    #[test]
+    fn get_state_proof() {
+        let mut resource_manager = ResourceManager::new();
+        let (shutdown_handle, join_handle, bind_url) =
+            run_rest_api_on_open_port(resource_manager.resources());
+
+        let client = ReqwestScabbardClientBuilder::new()
+            .with_url(&format!("http://{}", bind_url))
+            .with_auth(MOCK_AUTH)
+            .build()
+            .expect("unable to build client");
+        let service_id = ServiceId::new(MOCK_CIRCUIT_ID, MOCK_SERVICE_ID);
+        let expected_proof = mock_state_proof();
+
+        // Verify that a request at the current state root returns a proof that can be verified
+        let proof = client
+            .get_state_proof(&service_id, MOCK_PROOF_ADDRESS, None)
+            .expect("Failed to get state proof")
+            .expect("Proof should be returned");
+        assert_eq!(proof, expected_proof);
+        proof
+            .verify(expected_proof.state_root())
+            .expect("Proof should be valid");
+        assert_eq!(proof.value(), Some(&b"value"[..]));
+
+        // Verify that a request at an unknown state root returns `None`
+        let proof = client
+            .get_state_proof(&service_id, MOCK_PROOF_ADDRESS, Some("0123"))
+            .expect("Failed to get state proof at unknown root");
+        assert_eq!(proof, None);
+
+        // Verify that an invalid address results in an error being returned
+        assert!(client
+            .get_state_proof(&service_id, "not a valid address", None)
+            .is_err());
+
+        // Verify that an error response code results in an error being returned
+        resource_manager.internal_server_error(true);
+        assert!(client
+            .get_state_proof(&service_id, MOCK_PROOF_ADDRESS, None)
+            .is_err());
+        resource_manager.internal_server_error(false);
+
+        shutdown_handle
+            .shutdown()
+            .expect("unable to shutdown rest api");
+        join_handle.join().expect("Unable to join rest api thread");
+    }
+
     struct ResourceManager {
         resources: Vec<Resource>,
         internal_server_error: Arc<AtomicBool>,
@@ -944,6 +1152,37 @@ mod tests {
             }
             resources.push(state_address);
 
+            let internal_server_error_clone = internal_server_error.clone();
+            let mut state_proof =
+                Resource::build(&format!("{}/state/{{address}}/proof", scabbard_base))
+                    .add_request_guard(ProtocolVersionRangeGuard::new(
+                        SCABBARD_STATE_PROOF_PROTOCOL_MIN,
+                        SCABBARD_PROTOCOL_VERSION,
+                    ));
+            #[cfg(feature = "authorization")]
+            {
+                state_proof = state_proof.add_method(
+                    Method::Get,
+                    SCABBARD_READ_PERMISSION,
+                    move |request, _| {
+                        mock_state_proof_response(
+                            request,
+                            internal_server_error_clone.load(Ordering::SeqCst),
+                        )
+                    },
+                );
+            }
+            #[cfg(not(feature = "authorization"))]
+            {
+                state_proof = state_proof.add_method(Method::Get, move |request, _| {
+                    mock_state_proof_response(
+                        request,
+                        internal_server_error_clone.load(Ordering::SeqCst),
+                    )
+                });
+            }
+            resources.push(state_proof);
+
             let internal_server_error_clone = internal_server_error.clone();
             let mut state = Resource::build(&format!("{}/state", scabbard_base)).add_request_guard(
                 ProtocolVersionRangeGuard::new(
@@ -1091,6 +1330,60 @@ mod tests {
         }
     }
 
+    fn mock_state_proof() -> StateProof {
+        let db = BTreeDatabase::new(&INDEXES);
+        let initial_state_root = kv::MerkleRadixTree::new(db.clone_box(), None)
+            .expect("Failed to create tree")
+            .get_merkle_root();
+        let state_root = kv::MerkleState::new(db.clone_box())
+            .commit(
+                &initial_state_root,
+                &[StateChange::Set {
+                    key: MOCK_PROOF_ADDRESS.into(),
+                    value: b"value".to_vec(),
+                }],
+            )
+            .expect("Failed to commit entry");
+
+        StateProof::generate(
+            vec![(MOCK_PROOF_ADDRESS.to_string(), b"value".to_vec())],
+            &state_root,
+            MOCK_PROOF_ADDRESS,
+        )
+        .expect("Failed to generate proof")
+    }
+
+    fn mock_state_proof_response(
+        request: HttpRequest,
+        internal_server_error: bool,
+    ) -> Box<dyn Future<Item = HttpResponse, Error = actix_web::Error>> {
+        let proof = mock_state_proof();
+        let query: web::Query<HashMap<String, String>> =
+            web::Query::from_query(request.query_string()).expect("Failed to parse query");
+
+        if internal_server_error {
+            let response = ErrorResponse {
+                message: "Request failed".into(),
+            };
+            Box::new(
+                HttpResponse::InternalServerError()
+                    .json(response)
+                    .into_future(),
+            )
+        } else if query
+            .get("state_root")
+            .map(|state_root| state_root != proof.state_root())
+            .unwrap_or(false)
+        {
+            let response = ErrorResponse {
+                message: "State root not found".into(),
+            };
+            Box::new(HttpResponse::NotFound().json(response).into_future())
+        } else {
+            Box::new(HttpResponse::Ok().json(proof).into_future())
+        }
+    }
+
     fn run_rest_api_on_open_port(
         resources: Vec<Resource>,
     ) -> (RestApiShutdownHandle, std::thread::JoinHandle<()>, String) {
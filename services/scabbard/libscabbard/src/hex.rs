@@ -14,6 +14,7 @@
 
 use std::error::Error;
 use std::fmt;
+use std::fmt::Write;
 
 pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
     if hex.len() % 2 != 0 {
@@ -36,6 +37,15 @@ pub fn parse_hex(hex: &str) -> Result<Vec<u8>, HexError> {
     Ok(res)
 }
 
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut buf = String::new();
+    for b in bytes {
+        write!(&mut buf, "{:02x}", b).expect("Unable to write to string");
+    }
+
+    buf
+}
+
 #[derive(Debug)]
 pub struct HexError {
     context: String,
@@ -89,4 +99,15 @@ mod tests {
         let empty: Vec<u8> = Vec::with_capacity(0);
         assert_eq!(empty, parse_hex("").expect("unable to parse empty"));
     }
+
+    /// Test that to_hex produces lowercase hex that parse_hex round trips.
+    #[test]
+    fn test_to_hex() {
+        assert_eq!("000aff", to_hex(&[0u8, 10u8, 255u8]));
+        assert_eq!("", to_hex(&[]));
+        assert_eq!(
+            vec![1u8, 99u8],
+            parse_hex(&to_hex(&[1u8, 99u8])).expect("unable to parse round trip")
+        );
+    }
 }
@@ -45,4 +45,11 @@ pub mod protocol;
 pub mod protos;
 #[cfg(feature = "splinter-service")]
 pub mod service;
+#[cfg(feature = "snapshot")]
+pub mod snapshot;
+pub mod state_proof;
+#[cfg(feature = "state-pruning")]
+pub mod state_pruning;
 pub mod store;
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,192 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- MySQL cannot index TEXT columns without a prefix length, so the columns
+-- that are part of a key are VARCHAR.
+CREATE TABLE IF NOT EXISTS scabbard_service (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    consensus                 ENUM('2PC') NOT NULL,
+    status                    ENUM('PREPARED', 'FINALIZED', 'RETIRED') NOT NULL,
+    PRIMARY KEY (circuit_id, service_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS scabbard_peer (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    peer_service_id           VARCHAR(255) NOT NULL,
+    PRIMARY KEY (circuit_id, service_id, peer_service_id),
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS scabbard_v3_commit_history (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    epoch                     BIGINT NOT NULL,
+    value                     VARCHAR(255) NOT NULL,
+    decision                  ENUM('COMMIT', 'ABORT'),
+    PRIMARY KEY (circuit_id, service_id, epoch),
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS scabbard_alarm (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    alarm_type                ENUM('TWO_PHASE_COMMIT') NOT NULL,
+    alarm                     BIGINT NOT NULL,
+    PRIMARY KEY (circuit_id, service_id, alarm_type),
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_context (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    coordinator               TEXT NOT NULL,
+    epoch                     BIGINT NOT NULL,
+    last_commit_epoch         BIGINT,
+    state                     ENUM('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK') NOT NULL,
+    vote_timeout_start        BIGINT,
+    vote                      BOOLEAN,
+    decision_timeout_start    BIGINT,
+    ack_timeout_start         BIGINT,
+    PRIMARY KEY (circuit_id, service_id),
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE,
+    CHECK ( (vote_timeout_start IS NOT NULL) OR (state != 'VOTING') ),
+    CHECK ( (vote IS NOT NULL) OR (state != 'VOTED') ),
+    CHECK ( (decision_timeout_start IS NOT NULL) OR (state != 'VOTED') ),
+    CHECK ( (ack_timeout_start IS NOT NULL) OR (state != 'WAITING_FOR_DECISION_ACK') )
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_context_participant (
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    epoch                     BIGINT NOT NULL,
+    process                   VARCHAR(255) NOT NULL,
+    vote                      BOOLEAN,
+    decision_ack              BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (circuit_id, service_id, process),
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_event (
+    id                        BIGINT AUTO_INCREMENT PRIMARY KEY,
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    created_at                DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    executed_at               DATETIME(6),
+    executed_epoch            BIGINT,
+    position                  INTEGER NOT NULL DEFAULT 0,
+    event_type                ENUM('ALARM', 'DELIVER', 'START', 'VOTE') NOT NULL,
+    update_context_action_id  BIGINT,
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_deliver_event (
+    event_id                  BIGINT PRIMARY KEY,
+    epoch                     BIGINT NOT NULL,
+    receiver_service_id       TEXT NOT NULL,
+    message_type              ENUM('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK') NOT NULL,
+    vote_response             BOOLEAN,
+    vote_request              BLOB,
+    FOREIGN KEY (event_id) REFERENCES consensus_2pc_event(id) ON DELETE CASCADE,
+    CHECK ( (vote_response IS NOT NULL) OR (message_type != 'VOTE_RESPONSE') ),
+    CHECK ( (vote_request IS NOT NULL) OR (message_type != 'VOTE_REQUEST') )
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_start_event (
+    event_id                  BIGINT PRIMARY KEY,
+    value                     BLOB NOT NULL,
+    FOREIGN KEY (event_id) REFERENCES consensus_2pc_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_vote_event (
+    event_id                  BIGINT PRIMARY KEY,
+    vote                      BOOLEAN NOT NULL,
+    FOREIGN KEY (event_id) REFERENCES consensus_2pc_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_action (
+    id                        BIGINT AUTO_INCREMENT PRIMARY KEY,
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    created_at                DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    executed_at               DATETIME(6),
+    action_type               ENUM('UPDATE_CONTEXT', 'SEND_MESSAGE', 'NOTIFICATION') NOT NULL,
+    event_id                  BIGINT NOT NULL,
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE,
+    FOREIGN KEY (event_id) REFERENCES consensus_2pc_event(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_notification_action (
+    action_id                 BIGINT PRIMARY KEY,
+    notification_type         ENUM('REQUEST_FOR_START', 'COORDINATOR_REQUEST_FOR_VOTE', 'PARTICIPANT_REQUEST_FOR_VOTE', 'COMMIT', 'ABORT', 'MESSAGE_DROPPED') NOT NULL,
+    dropped_message           TEXT,
+    request_for_vote_value    BLOB,
+    FOREIGN KEY (action_id) REFERENCES consensus_2pc_action(id) ON DELETE CASCADE,
+    CHECK ( (dropped_message IS NOT NULL) OR (notification_type != 'MESSAGE_DROPPED') ),
+    CHECK ( (request_for_vote_value IS NOT NULL) OR (notification_type != 'PARTICIPANT_REQUEST_FOR_VOTE') )
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_send_message_action (
+    action_id                 BIGINT PRIMARY KEY,
+    epoch                     BIGINT NOT NULL,
+    receiver_service_id       TEXT NOT NULL,
+    message_type              ENUM('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK') NOT NULL,
+    vote_response             BOOLEAN,
+    vote_request              BLOB,
+    FOREIGN KEY (action_id) REFERENCES consensus_2pc_action(id) ON DELETE CASCADE,
+    CHECK ( (vote_response IS NOT NULL) OR (message_type != 'VOTE_RESPONSE') ),
+    CHECK ( (vote_request IS NOT NULL) OR (message_type != 'VOTE_REQUEST') )
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_update_context_action (
+    action_id                 BIGINT PRIMARY KEY,
+    coordinator               TEXT NOT NULL,
+    epoch                     BIGINT NOT NULL,
+    last_commit_epoch         BIGINT,
+    state                     ENUM('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK') NOT NULL,
+    vote_timeout_start        BIGINT,
+    vote                      BOOLEAN,
+    decision_timeout_start    BIGINT,
+    action_alarm              BIGINT,
+    ack_timeout_start         BIGINT,
+    FOREIGN KEY (action_id) REFERENCES consensus_2pc_action(id) ON DELETE CASCADE,
+    CHECK ( (vote_timeout_start IS NOT NULL) OR (state != 'VOTING') ),
+    CHECK ( (vote IS NOT NULL) OR (state != 'VOTED') ),
+    CHECK ( (decision_timeout_start IS NOT NULL) OR (state != 'VOTED') ),
+    CHECK ( (ack_timeout_start IS NOT NULL) OR (state != 'WAITING_FOR_DECISION_ACK') )
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS consensus_2pc_update_context_action_participant (
+    action_id                 BIGINT NOT NULL,
+    process                   VARCHAR(255) NOT NULL,
+    vote                      BOOLEAN,
+    decision_ack              BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (action_id, process),
+    FOREIGN KEY (action_id) REFERENCES consensus_2pc_update_context_action(action_id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
+
+CREATE TABLE IF NOT EXISTS supervisor_notification (
+    id                        BIGINT AUTO_INCREMENT PRIMARY KEY,
+    circuit_id                VARCHAR(255) NOT NULL,
+    service_id                VARCHAR(255) NOT NULL,
+    action_id                 BIGINT NOT NULL,
+    notification_type         ENUM('ABORT', 'COMMIT', 'REQUEST_FOR_START', 'COORDINATOR_REQUEST_FOR_VOTE', 'PARTICIPANT_REQUEST_FOR_VOTE') NOT NULL,
+    request_for_vote_value    BLOB,
+    created_at                DATETIME(6) NOT NULL DEFAULT CURRENT_TIMESTAMP(6),
+    executed_at               DATETIME(6),
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE,
+    FOREIGN KEY (action_id) REFERENCES consensus_2pc_action(id) ON DELETE CASCADE
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4;
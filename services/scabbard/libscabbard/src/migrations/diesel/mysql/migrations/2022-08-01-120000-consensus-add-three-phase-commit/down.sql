@@ -0,0 +1,44 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+DELETE FROM scabbard_service WHERE consensus = '3PC';
+
+DELETE FROM consensus_2pc_context
+    WHERE state IN ('WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED');
+DELETE FROM consensus_2pc_update_context_action
+    WHERE state IN ('WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED');
+DELETE FROM consensus_2pc_deliver_event
+    WHERE message_type IN ('PRE_COMMIT', 'PRE_COMMIT_ACK');
+DELETE FROM consensus_2pc_send_message_action
+    WHERE message_type IN ('PRE_COMMIT', 'PRE_COMMIT_ACK');
+
+ALTER TABLE consensus_2pc_send_message_action
+    MODIFY message_type ENUM('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK') NOT NULL;
+
+ALTER TABLE consensus_2pc_deliver_event
+    MODIFY message_type ENUM('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK') NOT NULL;
+
+ALTER TABLE consensus_2pc_update_context_action
+    DROP CHECK consensus_2pc_update_context_action_pre_committed_check,
+    DROP CHECK consensus_2pc_update_context_action_pre_commit_ack_check,
+    MODIFY state ENUM('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK') NOT NULL;
+
+ALTER TABLE consensus_2pc_context
+    DROP CHECK consensus_2pc_context_pre_committed_check,
+    DROP CHECK consensus_2pc_context_pre_commit_ack_check,
+    MODIFY state ENUM('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK') NOT NULL;
+
+ALTER TABLE scabbard_service
+    MODIFY consensus ENUM('2PC') NOT NULL;
@@ -0,0 +1,38 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+-- add the three phase commit consensus type, states and messages
+ALTER TABLE scabbard_service
+    MODIFY consensus ENUM('2PC', '3PC') NOT NULL;
+
+ALTER TABLE consensus_2pc_context
+    MODIFY state ENUM('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK', 'WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED') NOT NULL,
+    ADD CONSTRAINT consensus_2pc_context_pre_commit_ack_check
+        CHECK ( (ack_timeout_start IS NOT NULL) OR (state != 'WAITING_FOR_PRE_COMMIT_ACK') ),
+    ADD CONSTRAINT consensus_2pc_context_pre_committed_check
+        CHECK ( (decision_timeout_start IS NOT NULL) OR (state != 'PRE_COMMITTED') );
+
+ALTER TABLE consensus_2pc_update_context_action
+    MODIFY state ENUM('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK', 'WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED') NOT NULL,
+    ADD CONSTRAINT consensus_2pc_update_context_action_pre_commit_ack_check
+        CHECK ( (ack_timeout_start IS NOT NULL) OR (state != 'WAITING_FOR_PRE_COMMIT_ACK') ),
+    ADD CONSTRAINT consensus_2pc_update_context_action_pre_committed_check
+        CHECK ( (decision_timeout_start IS NOT NULL) OR (state != 'PRE_COMMITTED') );
+
+ALTER TABLE consensus_2pc_deliver_event
+    MODIFY message_type ENUM('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK', 'PRE_COMMIT', 'PRE_COMMIT_ACK') NOT NULL;
+
+ALTER TABLE consensus_2pc_send_message_action
+    MODIFY message_type ENUM('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK', 'PRE_COMMIT', 'PRE_COMMIT_ACK') NOT NULL;
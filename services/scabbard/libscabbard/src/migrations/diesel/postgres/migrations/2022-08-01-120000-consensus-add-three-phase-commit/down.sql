@@ -0,0 +1,33 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+ALTER TABLE consensus_2pc_context DROP CONSTRAINT pre_commit_ack_timeout_start_check;
+ALTER TABLE consensus_2pc_context DROP CONSTRAINT pre_committed_decision_timeout_start_check;
+
+ALTER TABLE consensus_2pc_update_context_action DROP CONSTRAINT pre_commit_ack_timeout_start_check;
+ALTER TABLE consensus_2pc_update_context_action DROP CONSTRAINT pre_committed_decision_timeout_start_check;
+
+DELETE FROM scabbard_service WHERE consensus = '3PC';
+
+DELETE FROM consensus_2pc_context
+    WHERE state IN ('WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED');
+DELETE FROM consensus_2pc_update_context_action
+    WHERE state IN ('WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED');
+DELETE FROM consensus_2pc_deliver_event
+    WHERE message_type IN ('PRE_COMMIT', 'PRE_COMMIT_ACK');
+DELETE FROM consensus_2pc_send_message_action
+    WHERE message_type IN ('PRE_COMMIT', 'PRE_COMMIT_ACK');
+
+-- values cannot be removed from an enum type, so the three phase commit values are left unused
@@ -0,0 +1,333 @@
+-- Copyright 2018-2022 Cargill Incorporated
+--
+-- Licensed under the Apache License, Version 2.0 (the "License");
+-- you may not use this file except in compliance with the License.
+-- You may obtain a copy of the License at
+--
+--     http://www.apache.org/licenses/LICENSE-2.0
+--
+-- Unless required by applicable law or agreed to in writing, software
+-- distributed under the License is distributed on an "AS IS" BASIS,
+-- WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+-- See the License for the specific language governing permissions and
+-- limitations under the License.
+-- -----------------------------------------------------------------------------
+
+PRAGMA foreign_keys=off;
+
+-- the views must be recreated, as they reference the tables being replaced
+DROP VIEW IF EXISTS consensus_2pc_actions_all;
+DROP VIEW IF EXISTS consensus_2pc_events_all;
+DROP VIEW IF EXISTS consensus_2pc_actions_and_events_all;
+DROP VIEW IF EXISTS consensus_2pc_events_and_contexts_all;
+
+-- add the three phase commit states and messages to the checks
+CREATE TABLE IF NOT EXISTS new_consensus_2pc_context (
+    circuit_id                TEXT NOT NULL,
+    service_id                TEXT NOT NULL,
+    coordinator               TEXT NOT NULL,
+    epoch                     BIGINT NOT NULL,
+    last_commit_epoch         BIGINT,
+    state                     TEXT NOT NULL
+    CHECK ( state IN ('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK', 'WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED') ),
+    vote_timeout_start        BIGINT
+    CHECK ( (vote_timeout_start IS NOT NULL) OR ( state != 'VOTING') ),
+    vote                      NUMERIC
+    CHECK ( (vote IS NOT NULL) OR ( state != 'VOTED') ),
+    decision_timeout_start    BIGINT
+    CHECK ( (decision_timeout_start IS NOT NULL) OR ( state NOT IN ('VOTED', 'PRE_COMMITTED')) ),
+    ack_timeout_start         BIGINT
+    CHECK ( (ack_timeout_start IS NOT NULL) OR ( state NOT IN ('WAITING_FOR_DECISION_ACK', 'WAITING_FOR_PRE_COMMIT_ACK')) ),
+    PRIMARY KEY (circuit_id, service_id),
+    FOREIGN KEY (circuit_id, service_id) REFERENCES scabbard_service(circuit_id, service_id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS new_consensus_2pc_deliver_event (
+    event_id                  INTEGER PRIMARY KEY,
+    epoch                     BIGINT NOT NULL,
+    receiver_service_id       TEXT NOT NULL,
+    message_type              TEXT NOT NULL
+    CHECK ( message_type IN ('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK', 'PRE_COMMIT', 'PRE_COMMIT_ACK') ),
+    vote_response             NUMERIC
+    CHECK ( (vote_response IS NOT NULL) OR (message_type != 'VOTE_RESPONSE') ),
+    vote_request              BINARY
+    CHECK ( (vote_request IS NOT NULL) OR (message_type != 'VOTE_REQUEST') ),
+    FOREIGN KEY (event_id) REFERENCES consensus_2pc_event(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS new_consensus_2pc_send_message_action (
+    action_id                 INTEGER PRIMARY KEY,
+    epoch                     BIGINT NOT NULL,
+    receiver_service_id       TEXT NOT NULL,
+    message_type              TEXT NOT NULL
+    CHECK ( message_type IN ('VOTE_RESPONSE', 'DECISION_REQUEST', 'VOTE_REQUEST', 'COMMIT', 'ABORT', 'DECISION_ACK', 'PRE_COMMIT', 'PRE_COMMIT_ACK') ),
+    vote_response             NUMERIC
+    CHECK ( (vote_response IS NOT NULL) OR (message_type != 'VOTE_RESPONSE') ),
+    vote_request              BINARY
+    CHECK ( (vote_request IS NOT NULL) OR (message_type != 'VOTE_REQUEST') ),
+    FOREIGN KEY (action_id) REFERENCES consensus_2pc_action(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS new_consensus_2pc_update_context_action (
+    action_id                 INTEGER PRIMARY KEY,
+    coordinator               TEXT NOT NULL,
+    epoch                     BIGINT NOT NULL,
+    last_commit_epoch         BIGINT,
+    state                     TEXT NOT NULL
+    CHECK ( state IN ('WAITING_FOR_START', 'VOTING', 'WAITING_FOR_VOTE', 'ABORT', 'COMMIT', 'WAITING_FOR_VOTE_REQUEST', 'VOTED', 'WAITING_FOR_DECISION_ACK', 'WAITING_FOR_PRE_COMMIT_ACK', 'PRE_COMMITTED') ),
+    vote_timeout_start        BIGINT
+    CHECK ( (vote_timeout_start IS NOT NULL) OR ( state != 'VOTING') ),
+    vote                      NUMERIC
+    CHECK ( (vote IS NOT NULL) OR ( state != 'VOTED') ),
+    decision_timeout_start    BIGINT
+    CHECK ( (decision_timeout_start IS NOT NULL) OR ( state NOT IN ('VOTED', 'PRE_COMMITTED')) ),
+    action_alarm  BIGINT,
+    ack_timeout_start         BIGINT
+    CHECK ( (ack_timeout_start IS NOT NULL) OR ( state NOT IN ('WAITING_FOR_DECISION_ACK', 'WAITING_FOR_PRE_COMMIT_ACK')) ),
+    FOREIGN KEY (action_id) REFERENCES consensus_2pc_action(id) ON DELETE CASCADE
+);
+
+INSERT INTO new_consensus_2pc_context
+    SELECT
+        circuit_id,
+        service_id,
+        coordinator,
+        epoch,
+        last_commit_epoch,
+        state,
+        vote_timeout_start,
+        vote,
+        decision_timeout_start,
+        ack_timeout_start
+    FROM consensus_2pc_context;
+
+INSERT INTO new_consensus_2pc_deliver_event
+    SELECT
+        event_id,
+        epoch,
+        receiver_service_id,
+        message_type,
+        vote_response,
+        vote_request
+    FROM consensus_2pc_deliver_event;
+
+INSERT INTO new_consensus_2pc_send_message_action
+    SELECT
+        action_id,
+        epoch,
+        receiver_service_id,
+        message_type,
+        vote_response,
+        vote_request
+    FROM consensus_2pc_send_message_action;
+
+INSERT INTO new_consensus_2pc_update_context_action
+    SELECT
+        action_id,
+        coordinator,
+        epoch,
+        last_commit_epoch,
+        state,
+        vote_timeout_start,
+        vote,
+        decision_timeout_start,
+        action_alarm,
+        ack_timeout_start
+    FROM consensus_2pc_update_context_action;
+
+DROP TABLE consensus_2pc_context;
+DROP TABLE consensus_2pc_deliver_event;
+DROP TABLE consensus_2pc_send_message_action;
+DROP TABLE consensus_2pc_update_context_action;
+
+ALTER TABLE new_consensus_2pc_context RENAME TO consensus_2pc_context;
+ALTER TABLE new_consensus_2pc_deliver_event RENAME TO consensus_2pc_deliver_event;
+ALTER TABLE new_consensus_2pc_send_message_action RENAME TO consensus_2pc_send_message_action;
+ALTER TABLE new_consensus_2pc_update_context_action RENAME TO consensus_2pc_update_context_action;
+
+-- ALL ACTIONS
+CREATE VIEW IF NOT EXISTS consensus_2pc_actions_all AS
+SELECT id,
+       event_id,
+       action_type,
+       circuit_id,
+       service_id,
+       consensus_2pc_notification_action.notification_type as n_notification_type,
+       consensus_2pc_notification_action.dropped_message as n_dropped_message,
+       consensus_2pc_notification_action.request_for_vote_value as n_request_for_vote_value,
+       consensus_2pc_send_message_action.epoch as s_epoch,
+       consensus_2pc_send_message_action.receiver_service_id as s_receiver_service_id,
+       consensus_2pc_send_message_action.message_type as s_message_type,
+       consensus_2pc_send_message_action.vote_response as s_vote_response,
+       consensus_2pc_send_message_action.vote_request as s_vote_request,
+       consensus_2pc_update_context_action.coordinator as uc_coordinator,
+       consensus_2pc_update_context_action.epoch as uc_epoch,
+       consensus_2pc_update_context_action.last_commit_epoch as uc_last_commit_epoch,
+       consensus_2pc_update_context_action.state as uc_state,
+       consensus_2pc_update_context_action.vote_timeout_start as uc_vote_timeout_start,
+       consensus_2pc_update_context_action.vote as uc_vote,
+       consensus_2pc_update_context_action.decision_timeout_start as uc_decision_timeout_start,
+       consensus_2pc_update_context_action.action_alarm as uc_action_alarm,
+       consensus_2pc_update_context_action.ack_timeout_start as uc_ack_timeout_start,
+       consensus_2pc_update_context_action_participant.process as ucp_process,
+       consensus_2pc_update_context_action_participant.vote as ucp_vote,
+       consensus_2pc_update_context_action_participant.decision_ack as ucp_decision_ack,
+       created_at,
+       executed_at
+FROM consensus_2pc_action
+LEFT JOIN consensus_2pc_notification_action ON consensus_2pc_action.id=consensus_2pc_notification_action.action_id
+LEFT JOIN consensus_2pc_send_message_action ON consensus_2pc_action.id=consensus_2pc_send_message_action.action_id
+LEFT JOIN consensus_2pc_update_context_action ON consensus_2pc_action.id=consensus_2pc_update_context_action.action_id
+LEFT JOIN consensus_2pc_update_context_action_participant ON consensus_2pc_action.id=consensus_2pc_update_context_action_participant.action_id
+ORDER BY id;
+
+-- ALL EVENTS
+CREATE VIEW IF NOT EXISTS consensus_2pc_events_all AS
+SELECT id,
+       circuit_id,
+       service_id,
+       event_type,
+       consensus_2pc_deliver_event.epoch as d_epoch,
+       consensus_2pc_deliver_event.receiver_service_id as d_receiver_service_id,
+       consensus_2pc_deliver_event.message_type as d_message_type,
+       consensus_2pc_deliver_event.vote_response as d_vote_response,
+       consensus_2pc_deliver_event.vote_request as d_vote_request,
+       consensus_2pc_start_event.value as s_value,
+       consensus_2pc_vote_event.vote as v_vote,
+       created_at,
+       executed_at,
+       executed_epoch
+FROM consensus_2pc_event
+LEFT JOIN consensus_2pc_deliver_event ON consensus_2pc_event.id=consensus_2pc_deliver_event.event_id
+LEFT JOIN consensus_2pc_start_event ON
+consensus_2pc_event.id=consensus_2pc_start_event.event_id
+LEFT JOIN consensus_2pc_vote_event ON
+consensus_2pc_event.id=consensus_2pc_vote_event.event_id
+ORDER BY id;
+
+-- ALL ACTIONS AND EVENTS
+CREATE VIEW IF NOT EXISTS consensus_2pc_actions_and_events_all AS
+SELECT id AS a_id,
+       event_id AS a_event_id,
+       action_type AS a_event_type,
+       consensus_2pc_notification_action.notification_type as n_notification_type,
+       consensus_2pc_notification_action.dropped_message as n_dropped_message,
+       consensus_2pc_notification_action.request_for_vote_value as n_request_for_vote_value,
+       consensus_2pc_send_message_action.epoch as s_epoch,
+       consensus_2pc_send_message_action.receiver_service_id as s_receiver_service_id,
+       consensus_2pc_send_message_action.message_type as s_message_type,
+       consensus_2pc_send_message_action.vote_response as s_vote_response,
+       consensus_2pc_send_message_action.vote_request as s_vote_request,
+       consensus_2pc_update_context_action.coordinator as uc_coordinator,
+       consensus_2pc_update_context_action.epoch as uc_epoch,
+       consensus_2pc_update_context_action.last_commit_epoch as uc_last_commit_epoch,
+       consensus_2pc_update_context_action.state as uc_state,
+       consensus_2pc_update_context_action.vote_timeout_start as uc_vote_timeout_start,
+       consensus_2pc_update_context_action.vote as uc_vote,
+       consensus_2pc_update_context_action.decision_timeout_start as uc_decision_timeout_start,
+       consensus_2pc_update_context_action.action_alarm as uc_action_alarm,
+       consensus_2pc_update_context_action.ack_timeout_start as uc_ack_timeout_start,
+       consensus_2pc_update_context_action_participant.process as ucp_process,
+       consensus_2pc_update_context_action_participant.vote as ucp_vote,
+       consensus_2pc_update_context_action_participant.decision_ack as ucp_decision_ack,
+       circuit_id,
+       service_id,
+       created_at,
+       executed_at,
+       '' as e_id,
+       '' as e_event_type,
+       '' as d_epoch,
+       '' as d_receiver_service_id,
+       '' as d_message_type,
+       '' as d_vote_response,
+       '' as d_vote_request,
+       '' as s_value,
+       '' as v_vote,
+       '' as e_executed_epoch
+FROM consensus_2pc_action
+LEFT JOIN consensus_2pc_notification_action ON consensus_2pc_action.id=consensus_2pc_notification_action.action_id
+LEFT JOIN consensus_2pc_send_message_action ON consensus_2pc_action.id=consensus_2pc_send_message_action.action_id
+LEFT JOIN consensus_2pc_update_context_action ON consensus_2pc_action.id=consensus_2pc_update_context_action.action_id
+LEFT JOIN consensus_2pc_update_context_action_participant ON consensus_2pc_action.id=consensus_2pc_update_context_action_participant.action_id
+UNION
+SELECT '' AS a_id,
+       '' AS a_event_id,
+       '' AS a_event_type,
+       '' as n_notification_type,
+       '' as n_dropped_message,
+       '' as n_request_for_vote_value,
+       '' as s_epoch,
+       '' as s_receiver_service_id,
+       '' as s_message_type,
+       '' as s_vote_response,
+       '' as s_vote_request,
+       '' as uc_coordinator,
+       ''  as uc_epoch,
+       '' as uc_last_commit_epoch,
+       '' as uc_state,
+       '' as uc_vote_timeout_start,
+       '' as uc_vote,
+       '' as uc_decision_timeout_start,
+       '' as uc_action_alarm,
+       '' as uc_ack_timeout_start,
+       '' as ucp_process,
+       '' as ucp_vote,
+       '' as ucp_decision_ack,
+       circuit_id,
+       service_id,
+       created_at,
+       executed_at,
+       id as e_id,
+       event_type as e_event_type,
+       consensus_2pc_deliver_event.epoch as d_epoch,
+       consensus_2pc_deliver_event.receiver_service_id as d_receiver_service_id,
+       consensus_2pc_deliver_event.message_type as d_message_type,
+       consensus_2pc_deliver_event.vote_response as d_vote_response,
+       consensus_2pc_deliver_event.vote_request as d_vote_request,
+       consensus_2pc_start_event.value as s_value,
+       consensus_2pc_vote_event.vote as v_vote,
+       executed_epoch as e_executed_epoch
+FROM consensus_2pc_event
+LEFT JOIN consensus_2pc_deliver_event ON consensus_2pc_event.id=consensus_2pc_deliver_event.event_id
+LEFT JOIN consensus_2pc_start_event ON
+consensus_2pc_event.id=consensus_2pc_start_event.event_id
+LEFT JOIN consensus_2pc_vote_event ON
+consensus_2pc_event.id=consensus_2pc_vote_event.event_id
+ORDER BY executed_at;
+
+CREATE VIEW IF NOT EXISTS consensus_2pc_events_and_contexts_all AS
+SELECT id AS e_id,
+       circuit_id,
+       service_id,
+       event_type,
+       consensus_2pc_deliver_event.epoch AS d_epoch,
+       consensus_2pc_deliver_event.receiver_service_id AS d_receiver_service_id,
+       consensus_2pc_deliver_event.message_type AS d_message_type,
+       consensus_2pc_deliver_event.vote_response AS d_vote_response,
+       consensus_2pc_deliver_event.vote_request AS d_vote_request,
+       consensus_2pc_start_event.value AS s_value,
+       consensus_2pc_vote_event.vote AS v_vote,
+       created_at AS e_created_at,
+       executed_at AS e_executed_at,
+       executed_epoch AS e_executed_epoch,
+       consensus_2pc_update_context_action.coordinator AS ctx_coordinator,
+       consensus_2pc_update_context_action.epoch AS ctx_epoch,
+       consensus_2pc_update_context_action.last_commit_epoch AS ctx_last_commit_epoch,
+       consensus_2pc_update_context_action.state AS ctx_state,
+       consensus_2pc_update_context_action.vote_timeout_start AS ctx_vote_timeout_start,
+       consensus_2pc_update_context_action.vote AS ctx_vote,
+       consensus_2pc_update_context_action.decision_timeout_start AS ctx_decision_timeout_start,
+       consensus_2pc_update_context_action.action_alarm AS ctx_action_alarm,
+       consensus_2pc_update_context_action.ack_timeout_start AS ctx_ack_timeout_start
+FROM consensus_2pc_event
+LEFT JOIN consensus_2pc_deliver_event
+    ON consensus_2pc_event.id=consensus_2pc_deliver_event.event_id
+LEFT JOIN consensus_2pc_start_event
+    ON consensus_2pc_event.id=consensus_2pc_start_event.event_id
+LEFT JOIN consensus_2pc_vote_event
+    ON consensus_2pc_event.id=consensus_2pc_vote_event.event_id
+LEFT JOIN consensus_2pc_update_context_action
+    ON consensus_2pc_event.update_context_action_id = consensus_2pc_update_context_action.action_id
+ORDER BY id;
+
+PRAGMA foreign_keys=on;
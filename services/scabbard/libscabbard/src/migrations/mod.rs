@@ -15,6 +15,8 @@
 #[cfg(feature = "diesel")]
 pub mod diesel;
 
+#[cfg(feature = "mysql")]
+pub use self::diesel::mysql::run_migrations as run_mysql_migrations;
 #[cfg(feature = "postgres")]
 pub use self::diesel::postgres::any_pending_migrations as any_pending_postgres_migrations;
 #[cfg(feature = "postgres")]
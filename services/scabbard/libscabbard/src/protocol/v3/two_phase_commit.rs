@@ -23,6 +23,8 @@ pub enum TwoPhaseCommitMessage {
     Abort(Abort),
     DecisionRequest(DecisionRequest),
     DecisionAck(DecisionAck),
+    PreCommit(PreCommit),
+    PreCommitAck(PreCommitAck),
 }
 
 impl TwoPhaseCommitMessage {
@@ -34,6 +36,8 @@ impl TwoPhaseCommitMessage {
             Self::Abort(Abort { epoch, .. }) => *epoch,
             Self::DecisionRequest(DecisionRequest { epoch, .. }) => *epoch,
             Self::DecisionAck(DecisionAck { epoch, .. }) => *epoch,
+            Self::PreCommit(PreCommit { epoch, .. }) => *epoch,
+            Self::PreCommitAck(PreCommitAck { epoch, .. }) => *epoch,
         }
     }
 }
@@ -70,6 +74,16 @@ pub struct DecisionAck {
     pub epoch: u64,
 }
 
+#[derive(Debug)]
+pub struct PreCommit {
+    pub epoch: u64,
+}
+
+#[derive(Debug)]
+pub struct PreCommitAck {
+    pub epoch: u64,
+}
+
 impl FromProto<scabbard_v3::VoteRequest> for VoteRequest {
     fn from_proto(source: scabbard_v3::VoteRequest) -> Result<Self, ProtoConversionError> {
         Ok(VoteRequest {
@@ -170,6 +184,38 @@ impl FromNative<DecisionAck> for scabbard_v3::DecisionAck {
     }
 }
 
+impl FromProto<scabbard_v3::PreCommit> for PreCommit {
+    fn from_proto(source: scabbard_v3::PreCommit) -> Result<Self, ProtoConversionError> {
+        Ok(PreCommit {
+            epoch: source.get_epoch(),
+        })
+    }
+}
+
+impl FromNative<PreCommit> for scabbard_v3::PreCommit {
+    fn from_native(source: PreCommit) -> Result<Self, ProtoConversionError> {
+        let mut proto_msg = scabbard_v3::PreCommit::new();
+        proto_msg.set_epoch(source.epoch);
+        Ok(proto_msg)
+    }
+}
+
+impl FromProto<scabbard_v3::PreCommitAck> for PreCommitAck {
+    fn from_proto(source: scabbard_v3::PreCommitAck) -> Result<Self, ProtoConversionError> {
+        Ok(PreCommitAck {
+            epoch: source.get_epoch(),
+        })
+    }
+}
+
+impl FromNative<PreCommitAck> for scabbard_v3::PreCommitAck {
+    fn from_native(source: PreCommitAck) -> Result<Self, ProtoConversionError> {
+        let mut proto_msg = scabbard_v3::PreCommitAck::new();
+        proto_msg.set_epoch(source.epoch);
+        Ok(proto_msg)
+    }
+}
+
 impl FromProto<scabbard_v3::TwoPhaseCommitMessage> for TwoPhaseCommitMessage {
     fn from_proto(
         mut source: scabbard_v3::TwoPhaseCommitMessage,
@@ -194,6 +240,12 @@ impl FromProto<scabbard_v3::TwoPhaseCommitMessage> for TwoPhaseCommitMessage {
             DECISION_ACK => Ok(TwoPhaseCommitMessage::DecisionAck(DecisionAck::from_proto(
                 source.take_decision_ack(),
             )?)),
+            PRE_COMMIT => Ok(TwoPhaseCommitMessage::PreCommit(PreCommit::from_proto(
+                source.take_pre_commit(),
+            )?)),
+            PRE_COMMIT_ACK => Ok(TwoPhaseCommitMessage::PreCommitAck(
+                PreCommitAck::from_proto(source.take_pre_commit_ack())?,
+            )),
             UNSET => Err(ProtoConversionError::InvalidTypeError(
                 "no message type was set".into(),
             )),
@@ -231,6 +283,14 @@ impl FromNative<TwoPhaseCommitMessage> for scabbard_v3::TwoPhaseCommitMessage {
                 proto_msg.set_message_type(DECISION_ACK);
                 proto_msg.set_decision_ack(scabbard_v3::DecisionAck::from_native(msg)?)
             }
+            TwoPhaseCommitMessage::PreCommit(msg) => {
+                proto_msg.set_message_type(PRE_COMMIT);
+                proto_msg.set_pre_commit(scabbard_v3::PreCommit::from_native(msg)?)
+            }
+            TwoPhaseCommitMessage::PreCommitAck(msg) => {
+                proto_msg.set_message_type(PRE_COMMIT_ACK);
+                proto_msg.set_pre_commit_ack(scabbard_v3::PreCommitAck::from_native(msg)?)
+            }
         }
 
         Ok(proto_msg)
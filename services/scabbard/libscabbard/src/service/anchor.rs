@@ -0,0 +1,281 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Anchoring: periodically publishing a scabbard service's state root to an external system.
+//!
+//! A published state root is a record, kept outside of the circuit, of the state the service
+//! had at a point in time. An auditor can later compare the anchored roots with the service's
+//! state to detect whether the history was rewritten after the fact.
+//!
+//! Anchors are published by an [`AnchorPublisher`]; [`HttpAnchorPublisher`] POSTs each anchor as
+//! JSON to a URL. Publishing is best-effort: a failed publish is logged and the state root is
+//! published again on the next interval.
+
+use std::collections::HashSet;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::blocking::Client;
+use serde::Serialize;
+use splinter::error::{InternalError, InvalidArgumentError};
+
+use super::state::ScabbardState;
+
+const DEFAULT_ANCHOR_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A scabbard service's state root at a point in time
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct StateRootAnchor {
+    circuit_id: String,
+    service_id: String,
+    state_root: String,
+    /// Seconds since the UNIX epoch
+    timestamp: u64,
+}
+
+impl StateRootAnchor {
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    pub fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    pub fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+/// Publishes state root anchors to an external system, such as an HTTP endpoint or another
+/// circuit
+pub trait AnchorPublisher: Send + Sync {
+    /// Publishes an anchor.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the anchor could not be published.
+    fn publish(&self, anchor: &StateRootAnchor) -> Result<(), InternalError>;
+}
+
+/// Publishes anchors by POSTing them as JSON to a URL.
+///
+/// The body of each request is an object with the `circuit_id`, `service_id`, `state_root` and
+/// `timestamp` of the anchor. Any response other than a success status is treated as a failure.
+pub struct HttpAnchorPublisher {
+    url: String,
+    client: Client,
+}
+
+impl HttpAnchorPublisher {
+    /// Creates a publisher for the given URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the URL does not use `http` or `https`, or if the
+    /// HTTP client could not be created.
+    pub fn new(url: &str) -> Result<Self, InvalidArgumentError> {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(InvalidArgumentError::new(
+                "url",
+                format!("anchor URL must use http or https: {}", url),
+            ));
+        }
+
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| InvalidArgumentError::new("url", err.to_string()))?;
+
+        Ok(Self {
+            url: url.to_string(),
+            client,
+        })
+    }
+}
+
+impl AnchorPublisher for HttpAnchorPublisher {
+    fn publish(&self, anchor: &StateRootAnchor) -> Result<(), InternalError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(anchor)
+            .send()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(InternalError::with_message(format!(
+                "anchor endpoint {} responded with {}",
+                self.url,
+                response.status()
+            )))
+        }
+    }
+}
+
+/// Which scabbard services are anchored, where their anchors are published, and how often
+#[derive(Clone)]
+pub struct AnchorConfig {
+    publisher: Arc<dyn AnchorPublisher>,
+    interval: Duration,
+    services: Option<HashSet<String>>,
+}
+
+impl AnchorConfig {
+    /// Creates a configuration that publishes the state root of every scabbard service once an
+    /// hour.
+    pub fn new(publisher: Arc<dyn AnchorPublisher>) -> Self {
+        Self {
+            publisher,
+            interval: DEFAULT_ANCHOR_INTERVAL,
+            services: None,
+        }
+    }
+
+    /// Sets how often the state roots are published.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Only anchors the given services, each formatted as `<circuit_id>::<service_id>`.
+    pub fn with_services(mut self, services: Vec<String>) -> Self {
+        self.services = Some(services.into_iter().collect());
+        self
+    }
+
+    /// Returns whether the given service is anchored
+    pub fn applies_to(&self, circuit_id: &str, service_id: &str) -> bool {
+        match &self.services {
+            Some(services) => services.contains(&format!("{}::{}", circuit_id, service_id)),
+            None => true,
+        }
+    }
+}
+
+/// A background thread that publishes a scabbard service's state root on an interval
+pub(crate) struct AnchorTask {
+    shutdown: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl AnchorTask {
+    /// Starts publishing the state root of the given service; the first anchor is published one
+    /// interval after the task is started.
+    pub fn start(
+        config: AnchorConfig,
+        circuit_id: String,
+        service_id: String,
+        state: Arc<Mutex<ScabbardState>>,
+    ) -> Result<Self, InternalError> {
+        let (shutdown, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name(format!("ScabbardAnchor-{}::{}", circuit_id, service_id))
+            .spawn(move || loop {
+                match receiver.recv_timeout(config.interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                let state_root = match state.lock() {
+                    Ok(state) => state.current_state_root().to_string(),
+                    Err(_) => {
+                        error!(
+                            "Unable to anchor scabbard service {}::{}: state lock poisoned",
+                            circuit_id, service_id
+                        );
+                        break;
+                    }
+                };
+                let anchor = StateRootAnchor {
+                    circuit_id: circuit_id.clone(),
+                    service_id: service_id.clone(),
+                    state_root,
+                    timestamp: SystemTime::now()
+                        .duration_since(UNIX_EPOCH)
+                        .map(|duration| duration.as_secs())
+                        .unwrap_or(0),
+                };
+
+                match config.publisher.publish(&anchor) {
+                    Ok(()) => debug!(
+                        "Anchored state root {} of scabbard service {}::{}",
+                        anchor.state_root, circuit_id, service_id
+                    ),
+                    Err(err) => warn!(
+                        "Unable to anchor state root {} of scabbard service {}::{}: {}",
+                        anchor.state_root, circuit_id, service_id, err
+                    ),
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            shutdown,
+            join_handle,
+        })
+    }
+
+    /// Stops publishing and waits for the thread to exit.
+    pub fn shutdown(self) -> Result<(), InternalError> {
+        // An error means the thread has already stopped
+        let _ = self.shutdown.send(());
+        self.join_handle
+            .join()
+            .map_err(|_| InternalError::with_message("anchor thread panicked".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that an anchor configuration without services applies to every service, and that
+    /// one with services only applies to those services.
+    #[test]
+    fn anchor_config_applies_to() {
+        struct NoopPublisher;
+
+        impl AnchorPublisher for NoopPublisher {
+            fn publish(&self, _anchor: &StateRootAnchor) -> Result<(), InternalError> {
+                Ok(())
+            }
+        }
+
+        let config = AnchorConfig::new(Arc::new(NoopPublisher));
+        assert!(config.applies_to("abcde-01234", "a000"));
+
+        let config = config.with_services(vec!["abcde-01234::a000".into()]);
+        assert!(config.applies_to("abcde-01234", "a000"));
+        assert!(!config.applies_to("abcde-01234", "b000"));
+        assert!(!config.applies_to("fghij-56789", "a000"));
+    }
+
+    /// Verify that only http and https anchor URLs are accepted.
+    #[test]
+    fn http_anchor_publisher_url() {
+        assert!(HttpAnchorPublisher::new("https://anchors.example.com/splinter").is_ok());
+        assert!(HttpAnchorPublisher::new("ftp://anchors.example.com").is_err());
+    }
+}
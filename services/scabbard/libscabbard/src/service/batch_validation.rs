@@ -0,0 +1,662 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batch validation: consulting an operator's policy before a submitted batch enters consensus.
+//!
+//! A [`BatchValidator`] is given a summary of each batch that is submitted to a scabbard service,
+//! and either accepts the batch or rejects it with a reason, which is returned to the submitter.
+//! This puts business-level controls, such as which signers may use which contracts, in front of
+//! the transaction handlers without changing them. Only the service that a batch is submitted to
+//! consults its validator; the other members of the circuit apply their own policies to the
+//! batches submitted to them.
+//!
+//! [`HttpBatchValidator`] POSTs the summary as JSON to a URL, and [`WasmBatchValidator`] runs a
+//! WebAssembly policy module. A validator that fails, rather than rejecting a batch, causes the
+//! submission to fail, so that batches are never let through unchecked.
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+use splinter::error::{InternalError, InvalidArgumentError};
+use transact::protocol::batch::BatchPair;
+use transact::protocol::transaction::TransactionHeader;
+use transact::protos::FromBytes;
+use wasm_instrument::gas_metering::{self, host_function, ConstantCostRules};
+use wasm_instrument::parity_wasm::{self, elements};
+use wasmi::{
+    Error as WasmiError, ExternVal, Externals, FuncInstance, FuncRef, HostError, ImportsBuilder,
+    MemoryRef, Module, ModuleImportResolver, ModuleInstance, ModuleRef, RuntimeArgs, RuntimeValue,
+    Signature, Trap, TrapKind,
+};
+
+use crate::hex::to_hex;
+use crate::service::ExecutionLimits;
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The default limit on the instructions a policy module may execute to validate a batch
+const DEFAULT_MAX_INSTRUCTIONS: u64 = 10_000_000;
+/// The default limit on the memory of a policy module, in bytes
+const DEFAULT_MAX_MEMORY: u64 = 16 * 1024 * 1024;
+/// The default limit on the stack height of a policy module
+const DEFAULT_MAX_STACK_HEIGHT: u32 = 64 * 1024;
+
+/// The function that a metered policy module calls to be charged for the instructions it is
+/// about to execute
+const GAS_MODULE: &str = "env";
+const GAS_FUNCTION: &str = "gas";
+const GAS_FUNCTION_INDEX: usize = 0;
+
+/// A summary of a submitted batch, as given to a [`BatchValidator`]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BatchValidationRequest {
+    circuit_id: String,
+    service_id: String,
+    batch_id: String,
+    signer_public_key: String,
+    transactions: Vec<TransactionSummary>,
+}
+
+impl BatchValidationRequest {
+    /// Summarizes the given batch, submitted to the given service.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the header of a transaction in the batch is invalid.
+    pub fn new(
+        circuit_id: &str,
+        service_id: &str,
+        batch: &BatchPair,
+    ) -> Result<Self, InternalError> {
+        let transactions = batch
+            .batch()
+            .transactions()
+            .iter()
+            .map(|txn| {
+                let header = TransactionHeader::from_bytes(txn.header())
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                Ok(TransactionSummary {
+                    transaction_id: txn.header_signature().to_string(),
+                    signer_public_key: to_hex(header.signer_public_key()),
+                    family_name: header.family_name().to_string(),
+                    family_version: header.family_version().to_string(),
+                    payload: to_hex(txn.payload()),
+                })
+            })
+            .collect::<Result<_, InternalError>>()?;
+
+        Ok(Self {
+            circuit_id: circuit_id.to_string(),
+            service_id: service_id.to_string(),
+            batch_id: batch.batch().header_signature().to_string(),
+            signer_public_key: to_hex(batch.header().signer_public_key()),
+            transactions,
+        })
+    }
+
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    pub fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    pub fn batch_id(&self) -> &str {
+        &self.batch_id
+    }
+
+    pub fn signer_public_key(&self) -> &str {
+        &self.signer_public_key
+    }
+
+    pub fn transactions(&self) -> &[TransactionSummary] {
+        &self.transactions
+    }
+}
+
+/// A summary of a transaction in a submitted batch; the public key and payload are hex-encoded.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TransactionSummary {
+    transaction_id: String,
+    signer_public_key: String,
+    family_name: String,
+    family_version: String,
+    payload: String,
+}
+
+impl TransactionSummary {
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    pub fn signer_public_key(&self) -> &str {
+        &self.signer_public_key
+    }
+
+    pub fn family_name(&self) -> &str {
+        &self.family_name
+    }
+
+    pub fn family_version(&self) -> &str {
+        &self.family_version
+    }
+
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+}
+
+/// Whether a [`BatchValidator`] lets a batch into consensus
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BatchVerdict {
+    Accept,
+    /// The batch is rejected for the given reason
+    Reject(String),
+}
+
+/// Applies a policy to the batches submitted to a scabbard service
+pub trait BatchValidator: Send + Sync {
+    /// Decides whether the summarized batch may enter consensus.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the policy could not be applied.
+    fn validate(&self, request: &BatchValidationRequest) -> Result<BatchVerdict, InternalError>;
+}
+
+/// Validates batches by POSTing their summaries as JSON to a URL.
+///
+/// A success status accepts the batch. A `400`, `403` or `422` status rejects it, with the
+/// `message` of a JSON body, or the text of the body, as the reason. Any other response is
+/// treated as a failure of the validator.
+pub struct HttpBatchValidator {
+    url: String,
+    client: Client,
+}
+
+impl HttpBatchValidator {
+    /// Creates a validator for the given URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the URL does not use `http` or `https`, or if the
+    /// HTTP client could not be created.
+    pub fn new(url: &str) -> Result<Self, InvalidArgumentError> {
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(InvalidArgumentError::new(
+                "url",
+                format!("validation URL must use http or https: {}", url),
+            ));
+        }
+
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|err| InvalidArgumentError::new("url", err.to_string()))?;
+
+        Ok(Self {
+            url: url.to_string(),
+            client,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct RejectionResponse {
+    message: String,
+}
+
+impl BatchValidator for HttpBatchValidator {
+    fn validate(&self, request: &BatchValidationRequest) -> Result<BatchVerdict, InternalError> {
+        let response = self
+            .client
+            .post(&self.url)
+            .json(request)
+            .send()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(BatchVerdict::Accept);
+        }
+
+        match status {
+            StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN | StatusCode::UNPROCESSABLE_ENTITY => {
+                let body = response
+                    .text()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                let reason = serde_json::from_str::<RejectionResponse>(&body)
+                    .map(|rejection| rejection.message)
+                    .unwrap_or(body);
+                Ok(BatchVerdict::Reject(reason))
+            }
+            _ => Err(InternalError::with_message(format!(
+                "validation endpoint {} responded with {}",
+                self.url, status
+            ))),
+        }
+    }
+}
+
+/// Validates batches with a WebAssembly policy module.
+///
+/// The module must export its `memory` and two functions:
+///
+/// * `alloc(len: i32) -> i32` returns the offset of `len` bytes of memory that the summary of a
+///   batch is written to, as JSON
+/// * `validate(offset: i32, len: i32) -> i32` is called with the location of the summary, and
+///   returns `0` to accept the batch, or the offset of the reason for rejecting it: a 4-byte
+///   little-endian length followed by that many bytes of UTF-8 text
+///
+/// The module may not import anything. A new instance of the module validates each batch, so no
+/// state is kept between batches.
+///
+/// The module is instrumented, as Sabre contracts are by [`ExecutionLimits`], to limit its memory
+/// and stack height, and to count the instructions it executes. A batch whose validation
+/// exceeds the instruction limit is rejected.
+pub struct WasmBatchValidator {
+    module: Module,
+    max_instructions: u64,
+}
+
+impl WasmBatchValidator {
+    /// Creates a validator that runs the given module within the default limits: 16 MiB of
+    /// memory, a stack height of 65536 and 10,000,000 instructions for each batch.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the module is invalid.
+    pub fn new(wasm: &[u8]) -> Result<Self, InvalidArgumentError> {
+        let limits = ExecutionLimits::new()
+            .with_max_memory(DEFAULT_MAX_MEMORY)
+            .with_max_stack_height(DEFAULT_MAX_STACK_HEIGHT);
+
+        Self::with_limits(wasm, &limits, DEFAULT_MAX_INSTRUCTIONS)
+    }
+
+    /// Creates a validator that runs the given module within the memory and stack limits of the
+    /// given execution limits, executing at most `max_instructions` instructions for each batch.
+    /// The time limit of the execution limits is not used, as the instruction limit bounds the
+    /// time the module runs for.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the module is invalid, or needs more memory than
+    /// the limit to start.
+    pub fn with_limits(
+        wasm: &[u8],
+        limits: &ExecutionLimits,
+        max_instructions: u64,
+    ) -> Result<Self, InvalidArgumentError> {
+        let module = parity_wasm::deserialize_buffer::<elements::Module>(wasm)
+            .map_err(|err| InvalidArgumentError::new("wasm", err.to_string()))?;
+        let module = limits
+            .limit_memory(module)
+            .map_err(|msg| InvalidArgumentError::new("wasm", msg))?;
+        // The instructions are metered before the stack limiter is injected, so that the
+        // limiter's own instructions are not charged
+        let module = gas_metering::inject(
+            module,
+            host_function::Injector::new(GAS_MODULE, GAS_FUNCTION),
+            &ConstantCostRules::default(),
+        )
+        .map_err(|_| {
+            InvalidArgumentError::new("wasm", "unable to meter the module's instructions".into())
+        })?;
+        let module = limits
+            .limit_stack_height(module)
+            .map_err(|msg| InvalidArgumentError::new("wasm", msg))?;
+        let wasm = parity_wasm::serialize(module)
+            .map_err(|err| InvalidArgumentError::new("wasm", err.to_string()))?;
+
+        let module = Module::from_buffer(&wasm)
+            .map_err(|err| InvalidArgumentError::new("wasm", err.to_string()))?;
+
+        Ok(Self {
+            module,
+            max_instructions,
+        })
+    }
+
+    /// Creates a validator that runs the module in the given file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the file cannot be read or the module is invalid.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, InvalidArgumentError> {
+        let path = path.as_ref();
+        let wasm = fs::read(path).map_err(|err| {
+            InvalidArgumentError::new("path", format!("{}: {}", path.display(), err))
+        })?;
+
+        Self::new(&wasm)
+    }
+
+    fn instantiate(&self) -> Result<(ModuleRef, MemoryRef), InternalError> {
+        let imports = ImportsBuilder::new().with_resolver(GAS_MODULE, &GasResolver);
+        let instance = ModuleInstance::new(&self.module, &imports)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .assert_no_start();
+
+        let memory = match instance.export_by_name("memory") {
+            Some(ExternVal::Memory(memory)) => memory,
+            _ => {
+                return Err(InternalError::with_message(
+                    "validation module does not export its memory".into(),
+                ))
+            }
+        };
+
+        Ok((instance, memory))
+    }
+
+    /// Runs the module to validate the summarized batch, charging the instructions it executes
+    /// to the given fuel.
+    fn run(
+        &self,
+        request: &BatchValidationRequest,
+        fuel: &mut Fuel,
+    ) -> Result<BatchVerdict, InternalError> {
+        let summary =
+            serde_json::to_vec(request).map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let len = i32::try_from(summary.len()).map_err(|_| {
+            InternalError::with_message("batch summary is too large to validate".into())
+        })?;
+
+        let (instance, memory) = self.instantiate()?;
+
+        let offset = invoke_i32(&instance, "alloc", &[RuntimeValue::I32(len)], fuel)?;
+        memory
+            .set(offset as u32, &summary)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let result = invoke_i32(
+            &instance,
+            "validate",
+            &[RuntimeValue::I32(offset), RuntimeValue::I32(len)],
+            fuel,
+        )?;
+        if result == 0 {
+            return Ok(BatchVerdict::Accept);
+        }
+
+        let reason_offset = result as u32;
+        let reason_len = memory
+            .get(reason_offset, 4)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        let reason_len =
+            u32::from_le_bytes([reason_len[0], reason_len[1], reason_len[2], reason_len[3]]);
+        let reason = memory
+            .get(reason_offset + 4, reason_len as usize)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(BatchVerdict::Reject(
+            String::from_utf8_lossy(&reason).into_owned(),
+        ))
+    }
+}
+
+impl BatchValidator for WasmBatchValidator {
+    fn validate(&self, request: &BatchValidationRequest) -> Result<BatchVerdict, InternalError> {
+        let mut fuel = Fuel {
+            remaining: self.max_instructions,
+            exhausted: false,
+        };
+
+        match self.run(request, &mut fuel) {
+            Err(_) if fuel.exhausted => Ok(BatchVerdict::Reject(format!(
+                "validation exceeded the policy's limit of {} instructions",
+                self.max_instructions
+            ))),
+            result => result,
+        }
+    }
+}
+
+/// Resolves the gas function imported by a metered policy module; nothing else may be imported.
+struct GasResolver;
+
+impl ModuleImportResolver for GasResolver {
+    fn resolve_func(&self, field_name: &str, signature: &Signature) -> Result<FuncRef, WasmiError> {
+        if field_name == GAS_FUNCTION
+            && signature.params().len() == 1
+            && signature.return_type().is_none()
+        {
+            Ok(FuncInstance::alloc_host(
+                signature.clone(),
+                GAS_FUNCTION_INDEX,
+            ))
+        } else {
+            Err(WasmiError::Instantiation(format!(
+                "validation module may not import {}.{}",
+                GAS_MODULE, field_name
+            )))
+        }
+    }
+}
+
+/// The instructions a policy module may still execute
+struct Fuel {
+    remaining: u64,
+    exhausted: bool,
+}
+
+impl Externals for Fuel {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        let cost = match (index, args.nth_value_checked(0)?) {
+            (GAS_FUNCTION_INDEX, RuntimeValue::I64(cost)) => cost as u64,
+            (GAS_FUNCTION_INDEX, RuntimeValue::I32(cost)) => u64::from(cost as u32),
+            _ => return Err(Trap::new(TrapKind::UnexpectedSignature)),
+        };
+
+        match self.remaining.checked_sub(cost) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                Ok(None)
+            }
+            None => {
+                self.exhausted = true;
+                Err(Trap::new(TrapKind::Host(Box::new(OutOfFuel))))
+            }
+        }
+    }
+}
+
+/// Stops a policy module that exceeds its instruction limit
+#[derive(Debug)]
+struct OutOfFuel;
+
+impl fmt::Display for OutOfFuel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("instruction limit exceeded")
+    }
+}
+
+impl HostError for OutOfFuel {}
+
+fn invoke_i32(
+    instance: &ModuleRef,
+    name: &str,
+    args: &[RuntimeValue],
+    fuel: &mut Fuel,
+) -> Result<i32, InternalError> {
+    match instance
+        .invoke_export(name, args, fuel)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+    {
+        Some(RuntimeValue::I32(value)) => Ok(value),
+        _ => Err(InternalError::with_message(format!(
+            "validation module's {} function did not return an i32",
+            name
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+    use transact::protocol::batch::BatchBuilder;
+    use transact::protocol::transaction::{HashMethod, TransactionBuilder};
+
+    /// A policy module that rejects every batch whose summary contains "forbidde", with the
+    /// reason "no", and accepts the rest. The summary is written at offset 1024 and the reason
+    /// is stored at offset 16.
+    const POLICY_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (data (i32.const 16) "\02\00\00\00no")
+          (func (export "alloc") (param i32) (result i32)
+            i32.const 1024)
+          (func $matches (param $at i32) (result i32)
+            (i32.and
+              (i32.eq (i32.load (local.get $at)) (i32.const 0x62726f66))
+              (i32.eq (i32.load (i32.add (local.get $at) (i32.const 4)))
+                      (i32.const 0x65646469))))
+          (func (export "validate") (param $offset i32) (param $len i32) (result i32)
+            (local $at i32)
+            (local.set $at (local.get $offset))
+            (block $done
+              (loop $next
+                (br_if $done
+                  (i32.gt_s (i32.add (local.get $at) (i32.const 8))
+                            (i32.add (local.get $offset) (local.get $len))))
+                (if (call $matches (local.get $at))
+                  (then (return (i32.const 16))))
+                (local.set $at (i32.add (local.get $at) (i32.const 1)))
+                (br $next)))
+            i32.const 0))
+    "#;
+
+    fn create_batch(signer: &dyn Signer, payload: &[u8]) -> BatchPair {
+        let txn = TransactionBuilder::new()
+            .with_family_name("test".into())
+            .with_family_version("1.0".into())
+            .with_inputs(vec![])
+            .with_outputs(vec![])
+            .with_payload_hash_method(HashMethod::Sha512)
+            .with_payload(payload.to_vec())
+            .build(signer)
+            .expect("Failed to build transaction");
+
+        BatchBuilder::new()
+            .with_transactions(vec![txn])
+            .build_pair(signer)
+            .expect("Failed to build batch")
+    }
+
+    /// Verify that a batch summary includes the batch and each of its transactions.
+    #[test]
+    fn batch_validation_request() {
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let batch = create_batch(&*signer, b"payload");
+
+        let request = BatchValidationRequest::new("abcde-01234", "a000", &batch)
+            .expect("Failed to summarize batch");
+
+        assert_eq!(request.circuit_id(), "abcde-01234");
+        assert_eq!(request.service_id(), "a000");
+        assert_eq!(request.batch_id(), batch.batch().header_signature());
+        assert_eq!(
+            request.signer_public_key(),
+            to_hex(batch.header().signer_public_key())
+        );
+        assert_eq!(request.transactions().len(), 1);
+        assert_eq!(request.transactions()[0].family_name(), "test");
+        assert_eq!(request.transactions()[0].family_version(), "1.0");
+        assert_eq!(request.transactions()[0].payload(), to_hex(b"payload"));
+    }
+
+    /// Verify that a WASM policy module accepts and rejects batches, with the module's reason.
+    #[test]
+    fn wasm_batch_validator() {
+        let wasm = wat::parse_str(POLICY_WAT).expect("Failed to compile policy");
+        let validator = WasmBatchValidator::new(&wasm).expect("Failed to load policy");
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+
+        let allowed =
+            BatchValidationRequest::new("abcde-01234", "a000", &create_batch(&*signer, b"allowed"))
+                .expect("Failed to summarize batch");
+        assert_eq!(
+            validator.validate(&allowed).expect("Failed to validate"),
+            BatchVerdict::Accept
+        );
+
+        // The payload is hex-encoded in the summary, so the module looks for it in the family
+        // name instead
+        let mut forbidden = allowed.clone();
+        forbidden.transactions[0].family_name = "forbidden".into();
+        assert_eq!(
+            validator.validate(&forbidden).expect("Failed to validate"),
+            BatchVerdict::Reject("no".into())
+        );
+    }
+
+    /// Verify that a batch is rejected if validating it exceeds the policy module's instruction
+    /// limit.
+    #[test]
+    fn wasm_batch_validator_instruction_limit() {
+        let wasm = wat::parse_str(
+            r#"
+            (module
+              (memory (export "memory") 1)
+              (func (export "alloc") (param i32) (result i32)
+                i32.const 1024)
+              (func (export "validate") (param i32) (param i32) (result i32)
+                (loop $forever
+                  (br $forever))
+                i32.const 0))
+            "#,
+        )
+        .expect("Failed to compile policy");
+        let validator = WasmBatchValidator::with_limits(&wasm, &ExecutionLimits::new(), 1000)
+            .expect("Failed to load policy");
+
+        let context = Secp256k1Context::new();
+        let signer = context.new_signer(context.new_random_private_key());
+        let request =
+            BatchValidationRequest::new("abcde-01234", "a000", &create_batch(&*signer, b"batch"))
+                .expect("Failed to summarize batch");
+
+        assert_eq!(
+            validator.validate(&request).expect("Failed to validate"),
+            BatchVerdict::Reject(
+                "validation exceeded the policy's limit of 1000 instructions".into()
+            )
+        );
+    }
+
+    /// Verify that invalid modules and URLs are rejected.
+    #[test]
+    fn invalid_validators() {
+        assert!(WasmBatchValidator::new(b"not wasm").is_err());
+        assert!(HttpBatchValidator::new("https://policy.example.com/batches").is_ok());
+        assert!(HttpBatchValidator::new("ftp://policy.example.com").is_err());
+    }
+}
@@ -35,6 +35,73 @@ use super::shared::ScabbardShared;
 use super::state::ScabbardState;
 use super::ScabbardVersion;
 
+/// Timing and retry settings for the two-phase commit consensus engine. Any setting that is not
+/// provided uses the engine's default.
+///
+/// These settings are only supported by scabbard version 2.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ConsensusSettings {
+    alarm_interval: Option<Duration>,
+    vote_timeout: Option<Duration>,
+    max_retries: Option<u32>,
+}
+
+impl ConsensusSettings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how often the engine checks its timers and polls for messages.
+    pub fn with_alarm_interval(mut self, alarm_interval: Duration) -> Self {
+        self.alarm_interval = Some(alarm_interval);
+        self
+    }
+
+    /// Sets how long a participant waits for the coordinator's result after voting before it
+    /// resends its vote.
+    pub fn with_vote_timeout(mut self, vote_timeout: Duration) -> Self {
+        self.vote_timeout = Some(vote_timeout);
+        self
+    }
+
+    /// Sets how many times the coordinator's verification request, or a participant's vote, is
+    /// resent after a timeout before giving up.
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = Some(max_retries);
+        self
+    }
+
+    pub fn alarm_interval(&self) -> Option<Duration> {
+        self.alarm_interval
+    }
+
+    pub fn vote_timeout(&self) -> Option<Duration> {
+        self.vote_timeout
+    }
+
+    pub fn max_retries(&self) -> Option<u32> {
+        self.max_retries
+    }
+
+    /// Returns true if none of the settings are provided.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn apply(&self, mut engine: TwoPhaseEngineV2) -> TwoPhaseEngineV2 {
+        if let Some(alarm_interval) = self.alarm_interval {
+            engine = engine.with_alarm_interval(alarm_interval);
+        }
+        if let Some(vote_timeout) = self.vote_timeout {
+            engine = engine.with_vote_timeout(vote_timeout);
+        }
+        if let Some(max_retries) = self.max_retries {
+            engine = engine.with_max_retries(max_retries);
+        }
+        engine
+    }
+}
+
 /// Component used by the service to manage and interact with consenus
 pub struct ScabbardConsensusManager {
     consensus_msg_tx: Sender<ConsensusMessage>,
@@ -52,6 +119,8 @@ impl ScabbardConsensusManager {
         state: Arc<Mutex<ScabbardState>>,
         // The coordinator timeout for the two-phase commit consensus engine
         coordinator_timeout: Duration,
+        // The remaining settings for the two-phase commit consensus engine; only used by version 2
+        consensus_settings: ConsensusSettings,
     ) -> Result<Self, ScabbardConsensusManagerError> {
         let peer_ids = shared
             .lock()
@@ -95,7 +164,8 @@ impl ScabbardConsensusManager {
                     }
                 }
                 ScabbardVersion::V2 => {
-                    let mut two_phase_engine = TwoPhaseEngineV2::new(coordinator_timeout);
+                    let mut two_phase_engine =
+                        consensus_settings.apply(TwoPhaseEngineV2::new(coordinator_timeout));
                     if let Err(err) = two_phase_engine.run(
                         consensus_msg_rx,
                         proposal_update_rx,
@@ -172,6 +242,23 @@ impl ScabbardProposalManager {
             state,
         }
     }
+
+    /// Asks the service's peers for the state it is missing, logging any error.
+    #[cfg(feature = "state-sync")]
+    fn request_state_sync(&self) {
+        let result = match (self.shared.lock(), self.state.lock()) {
+            (Ok(mut shared), Ok(state)) => {
+                super::state_sync::request_state_sync(&mut shared, &state)
+            }
+            _ => {
+                error!("Unable to request state sync: lock poisoned");
+                return;
+            }
+        };
+        if let Err(err) = result {
+            warn!("Unable to request state sync: {}", err);
+        }
+    }
 }
 
 impl ProposalManager for ScabbardProposalManager {
@@ -187,6 +274,18 @@ impl ProposalManager for ScabbardProposalManager {
             .lock()
             .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
 
+        // Queued batches are held until the quiet hours end
+        #[cfg(feature = "quiet-hours")]
+        if let Some(remaining) = shared.quiet_hours_remaining() {
+            trace!(
+                "Not proposing batches during quiet hours, which end in {} seconds",
+                remaining.as_secs()
+            );
+            self.proposal_update_sender
+                .send(ProposalUpdate::ProposalCreated(None))?;
+            return Ok(());
+        }
+
         if let Some(batch) = shared
             .pop_batch_from_queue()
             .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?
@@ -274,6 +373,10 @@ impl ProposalManager for ScabbardProposalManager {
         if hash.as_bytes() != proposal.summary {
             warn!("Hash mismatch: expected {} but was {}", id, hash);
 
+            // The proposal may build on batches that this replica has not committed
+            #[cfg(feature = "state-sync")]
+            self.request_state_sync();
+
             self.proposal_update_sender
                 .send(ProposalUpdate::ProposalInvalid(id.clone()))?;
         } else {
@@ -296,6 +399,9 @@ impl ProposalManager for ScabbardProposalManager {
             .lock()
             .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
 
+        #[cfg(feature = "batch-dependencies")]
+        let batch_id = proposal_batch_id(&shared, id);
+
         shared.remove_open_proposal(id);
 
         self.state
@@ -304,6 +410,13 @@ impl ProposalManager for ScabbardProposalManager {
             .commit()
             .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
 
+        #[cfg(feature = "batch-dependencies")]
+        if let Some(batch_id) = batch_id {
+            shared
+                .release_held_batches(&batch_id)
+                .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
+        }
+
         self.proposal_update_sender
             .send(ProposalUpdate::ProposalAccepted(id.clone()))?;
 
@@ -318,20 +431,50 @@ impl ProposalManager for ScabbardProposalManager {
             .lock()
             .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
 
+        #[cfg(feature = "batch-dependencies")]
+        let batch_id = proposal_batch_id(&shared, id);
+
         shared.remove_open_proposal(id);
 
-        self.state
+        let mut state = self
+            .state
             .lock()
-            .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?
+            .map_err(|_| ProposalManagerError::Internal(Box::new(ScabbardError::LockPoisoned)))?;
+
+        state
             .rollback()
             .map_err(|err| ProposalManagerError::Internal(Box::new(err)))?;
 
+        // The batches that depend on the rejected batch can never be committed
+        #[cfg(feature = "batch-dependencies")]
+        if let Some(batch_id) = batch_id {
+            for batch in shared.drop_held_batches(&batch_id) {
+                warn!(
+                    "Dropping batch {}; batch {} that it depends on was not committed",
+                    batch.batch().header_signature(),
+                    batch_id
+                );
+                state.batch_history().invalidate_batch(
+                    &batch,
+                    &format!("batch dependency {} was not committed", batch_id),
+                );
+            }
+        }
+
         info!("Rolled back proposal {}", id);
 
         Ok(())
     }
 }
 
+/// Returns the ID of the batch in the given open proposal, if the proposal is known.
+#[cfg(feature = "batch-dependencies")]
+fn proposal_batch_id(shared: &ScabbardShared, proposal_id: &ProposalId) -> Option<String> {
+    shared
+        .get_open_proposal(proposal_id)
+        .map(|(_, batch)| batch.batch().header_signature().to_string())
+}
+
 pub struct ScabbardConsensusNetworkSender {
     service_id: String,
     shared: Arc<Mutex<ScabbardShared>>,
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use std::error::Error;
+#[cfg(feature = "quiet-hours")]
+use std::time::Duration;
 
 use transact::database::error::DatabaseError;
 use transact::execution::adapter::ExecutionAdapterError;
@@ -24,26 +26,51 @@ use transact::state::merkle::StateDatabaseError;
 
 #[derive(Debug)]
 pub enum ScabbardError {
+    /// A submitted batch was rejected by the service's batch validator
+    #[cfg(feature = "batch-validation")]
+    BatchRejected(String),
     BatchVerificationFailed(Box<dyn Error + Send>),
     ConsensusFailed(ScabbardConsensusManagerError),
     InitializationFailed(Box<dyn Error + Send>),
     Internal(Box<dyn Error + Send>),
+    /// A submitted batch depends on a batch that is unknown or invalid
+    #[cfg(feature = "batch-dependencies")]
+    InvalidDependency(String),
+    /// A receipt query refers to an unknown receipt or uses filters that are unavailable
+    #[cfg(feature = "receipt-queries")]
+    InvalidReceiptQuery(String),
     LockPoisoned,
     MessageTypeUnset,
     NotConnected,
+    /// Snapshots are not configured for the service
+    #[cfg(feature = "snapshot")]
+    SnapshotsDisabled,
+    /// The service is in its quiet hours, which end after the given duration
+    #[cfg(feature = "quiet-hours")]
+    QuietHours(Duration),
     StateInteractionFailed(ScabbardStateError),
 }
 
 impl Error for ScabbardError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
+            #[cfg(feature = "batch-validation")]
+            ScabbardError::BatchRejected(_) => None,
             ScabbardError::BatchVerificationFailed(err) => Some(&**err),
             ScabbardError::ConsensusFailed(err) => Some(err),
             ScabbardError::InitializationFailed(err) => Some(&**err),
             ScabbardError::Internal(err) => Some(&**err),
+            #[cfg(feature = "batch-dependencies")]
+            ScabbardError::InvalidDependency(_) => None,
+            #[cfg(feature = "receipt-queries")]
+            ScabbardError::InvalidReceiptQuery(_) => None,
             ScabbardError::LockPoisoned => None,
             ScabbardError::MessageTypeUnset => None,
             ScabbardError::NotConnected => None,
+            #[cfg(feature = "snapshot")]
+            ScabbardError::SnapshotsDisabled => None,
+            #[cfg(feature = "quiet-hours")]
+            ScabbardError::QuietHours(_) => None,
             ScabbardError::StateInteractionFailed(err) => Some(err),
         }
     }
@@ -52,6 +79,8 @@ impl Error for ScabbardError {
 impl std::fmt::Display for ScabbardError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
+            #[cfg(feature = "batch-validation")]
+            ScabbardError::BatchRejected(msg) => write!(f, "{}", msg),
             ScabbardError::BatchVerificationFailed(err) => {
                 write!(f, "failed to verify batch: {}", err)
             }
@@ -62,11 +91,25 @@ impl std::fmt::Display for ScabbardError {
             ScabbardError::Internal(err) => {
                 write!(f, "internal error occurred: {}", err)
             }
+            #[cfg(feature = "batch-dependencies")]
+            ScabbardError::InvalidDependency(msg) => write!(f, "invalid batch dependency: {}", msg),
+            #[cfg(feature = "receipt-queries")]
+            ScabbardError::InvalidReceiptQuery(msg) => write!(f, "invalid receipt query: {}", msg),
             ScabbardError::LockPoisoned => write!(f, "internal lock poisoned"),
             ScabbardError::MessageTypeUnset => write!(f, "received message with unset type"),
             ScabbardError::NotConnected => {
                 write!(f, "attempted to send message, but service isn't connected")
             }
+            #[cfg(feature = "snapshot")]
+            ScabbardError::SnapshotsDisabled => {
+                write!(f, "snapshots are not enabled for this service")
+            }
+            #[cfg(feature = "quiet-hours")]
+            ScabbardError::QuietHours(remaining) => write!(
+                f,
+                "service is in its quiet hours for another {} seconds",
+                remaining.as_secs()
+            ),
             ScabbardError::StateInteractionFailed(err) => {
                 write!(f, "interaction with scabbard state failed: {}", err)
             }
@@ -0,0 +1,611 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Execution limits: bounding the resources a Sabre smart contract may use when it is executed.
+//!
+//! The memory and stack limits are enforced by instrumenting the contract's WebAssembly module
+//! each time it is loaded for execution; the contract stored in state is not changed. The
+//! module's memory is given a maximum size, so that growing it beyond the limit fails, and a
+//! stack height counter is injected, so that the contract traps when its nested calls exceed the
+//! limit. Both are deterministic: every service on the circuit aborts the same transactions.
+//!
+//! The time limit is enforced by executing the contract on its own thread while the executor
+//! waits for it, and aborting the transaction once the limit is reached. Unlike the other limits,
+//! it depends on the speed of each node, so services may disagree on the result of a batch that
+//! is close to the limit; such a batch is not committed. An aborted contract's thread cannot be
+//! interrupted: it stops at the contract's next state access, or when the contract returns.
+
+use std::cell::RefCell;
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha512};
+use transact::families::sabre::{admin::SettingsAdminPermission, handler::SabreTransactionHandler};
+use transact::handler::{ApplyError, ContextError, TransactionContext, TransactionHandler};
+use transact::protocol::sabre::payload::{Action, SabrePayload};
+use transact::protocol::sabre::state::{ContractBuilder, ContractList, ContractListBuilder};
+use transact::protocol::transaction::TransactionPair;
+use transact::protos::{FromBytes, IntoBytes};
+use wasm_instrument::parity_wasm::{
+    self,
+    elements::{MemoryType, Module},
+};
+
+use crate::hex::to_hex;
+
+/// The size of a WebAssembly memory page
+const WASM_PAGE_SIZE: u64 = 64 * 1024;
+/// The prefix of the addresses of Sabre contracts in state
+const CONTRACT_ADDRESS_PREFIX: &str = "00ec02";
+
+/// The limits on the resources a Sabre smart contract may use when it is executed
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionLimits {
+    max_memory: Option<u64>,
+    max_stack_height: Option<u32>,
+    max_execution_time: Option<Duration>,
+}
+
+impl ExecutionLimits {
+    /// Creates a set of limits that does not limit execution.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits the memory of a contract to the given number of bytes, rounded down to whole
+    /// WebAssembly pages of 64 KiB. Contracts that need more memory to start are not executed.
+    pub fn with_max_memory(mut self, max_memory: u64) -> Self {
+        self.max_memory = Some(max_memory);
+        self
+    }
+
+    /// Limits the stack height of a contract: each nested call adds the number of locals and
+    /// operand stack slots of the function called. This also bounds the depth of nested calls.
+    pub fn with_max_stack_height(mut self, max_stack_height: u32) -> Self {
+        self.max_stack_height = Some(max_stack_height);
+        self
+    }
+
+    /// Limits the time a contract may execute for.
+    pub fn with_max_execution_time(mut self, max_execution_time: Duration) -> Self {
+        self.max_execution_time = Some(max_execution_time);
+        self
+    }
+
+    pub fn max_memory(&self) -> Option<u64> {
+        self.max_memory
+    }
+
+    pub fn max_stack_height(&self) -> Option<u32> {
+        self.max_stack_height
+    }
+
+    pub fn max_execution_time(&self) -> Option<Duration> {
+        self.max_execution_time
+    }
+
+    /// Instruments the given WebAssembly module to enforce the memory and stack limits.
+    fn instrument(&self, wasm: &[u8]) -> Result<Vec<u8>, String> {
+        if self.max_memory.is_none() && self.max_stack_height.is_none() {
+            return Ok(wasm.to_vec());
+        }
+
+        let module = parity_wasm::deserialize_buffer::<Module>(wasm)
+            .map_err(|err| format!("contract is not a valid WebAssembly module: {}", err))?;
+        let module = self.limit_stack_height(self.limit_memory(module)?)?;
+
+        parity_wasm::serialize(module)
+            .map_err(|err| format!("unable to serialize the instrumented contract: {}", err))
+    }
+
+    /// Sets the memory limit as the maximum size of the module's memory.
+    pub(crate) fn limit_memory(&self, mut module: Module) -> Result<Module, String> {
+        if let Some(max_memory) = self.max_memory {
+            let max_pages = u32::try_from(max_memory / WASM_PAGE_SIZE).unwrap_or(u32::MAX);
+            if let Some(section) = module.memory_section_mut() {
+                for memory in section.entries_mut() {
+                    let limits = *memory.limits();
+                    if limits.initial() > max_pages {
+                        return Err(format!(
+                            "contract requires {} bytes of memory, exceeding the limit of {} bytes",
+                            u64::from(limits.initial()) * WASM_PAGE_SIZE,
+                            max_memory
+                        ));
+                    }
+                    let maximum = limits
+                        .maximum()
+                        .map_or(max_pages, |maximum| maximum.min(max_pages));
+                    *memory = MemoryType::new(limits.initial(), Some(maximum));
+                }
+            }
+        }
+
+        Ok(module)
+    }
+
+    /// Injects a stack height counter into the module, so that it traps when its nested calls
+    /// exceed the stack limit.
+    pub(crate) fn limit_stack_height(&self, module: Module) -> Result<Module, String> {
+        match self.max_stack_height {
+            Some(max_stack_height) => {
+                wasm_instrument::inject_stack_limiter(module, max_stack_height).map_err(|err| {
+                    format!("unable to limit the contract's stack height: {:?}", err)
+                })
+            }
+            None => Ok(module),
+        }
+    }
+
+    /// Describes the limits, for the receipts of the transactions that fail while they are in
+    /// effect.
+    fn describe(&self) -> String {
+        let mut limits = vec![];
+        if let Some(max_memory) = self.max_memory {
+            limits.push(format!("max memory {} bytes", max_memory));
+        }
+        if let Some(max_stack_height) = self.max_stack_height {
+            limits.push(format!("max stack height {}", max_stack_height));
+        }
+        if let Some(max_execution_time) = self.max_execution_time {
+            limits.push(format!(
+                "max execution time {} ms",
+                max_execution_time.as_millis()
+            ));
+        }
+        limits.join(", ")
+    }
+}
+
+/// A Sabre transaction handler that executes contracts within the given execution limits.
+///
+/// Transactions that do not execute a contract are handled by the Sabre handler as they are.
+pub(crate) struct LimitedSabreTransactionHandler {
+    handler: SabreTransactionHandler,
+    limits: ExecutionLimits,
+}
+
+impl LimitedSabreTransactionHandler {
+    pub fn new(limits: ExecutionLimits) -> Self {
+        Self {
+            handler: new_sabre_handler(),
+            limits,
+        }
+    }
+
+    /// Executes the contract on its own thread, handling its state accesses on this thread until
+    /// it finishes or the time limit is reached.
+    fn apply_with_time_limit(
+        &self,
+        transaction: &TransactionPair,
+        context: &LimitingContext,
+        time_limit: Duration,
+    ) -> Result<(), ApplyError> {
+        let (message_sender, message_receiver) = channel();
+        let (reply_sender, reply_receiver) = channel();
+
+        let transaction = transaction.clone();
+        thread::Builder::new()
+            .name("ScabbardContractExecution".into())
+            .spawn(move || {
+                let mut proxy = ProxyContext {
+                    sender: message_sender.clone(),
+                    replies: reply_receiver,
+                };
+                let result = new_sabre_handler().apply(&transaction, &mut proxy);
+                let _ = message_sender.send(ExecutionMessage::Finished(result));
+            })
+            .map_err(|err| {
+                ApplyError::InternalError(format!("Unable to start contract execution: {}", err))
+            })?;
+
+        let deadline = Instant::now() + time_limit;
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            match message_receiver.recv_timeout(remaining) {
+                Ok(ExecutionMessage::Request(request)) => {
+                    // If the reply cannot be sent, the execution thread has already stopped
+                    let _ = reply_sender.send(context.handle(request));
+                }
+                Ok(ExecutionMessage::Finished(result)) => return result,
+                Err(RecvTimeoutError::Timeout) => {
+                    return Err(ApplyError::InvalidTransaction(format!(
+                        "Contract execution exceeded the time limit of {} ms",
+                        time_limit.as_millis()
+                    )))
+                }
+                Err(RecvTimeoutError::Disconnected) => {
+                    return Err(ApplyError::InternalError(
+                        "Contract execution stopped without a result".into(),
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl TransactionHandler for LimitedSabreTransactionHandler {
+    fn family_name(&self) -> &str {
+        self.handler.family_name()
+    }
+
+    fn family_versions(&self) -> &[String] {
+        self.handler.family_versions()
+    }
+
+    fn apply(
+        &self,
+        transaction: &TransactionPair,
+        context: &mut dyn TransactionContext,
+    ) -> Result<(), ApplyError> {
+        let contract_address = match SabrePayload::from_bytes(transaction.transaction().payload())
+            .map(|payload| payload.action().clone())
+        {
+            Ok(Action::ExecuteContract(action)) => {
+                compute_contract_address(action.name(), action.version())
+            }
+            _ => return self.handler.apply(transaction, context),
+        };
+
+        let mut limiting_context = LimitingContext {
+            inner: &*context,
+            limits: &self.limits,
+            contract_address,
+            violation: RefCell::new(None),
+        };
+
+        let result = match self.limits.max_execution_time {
+            Some(time_limit) => {
+                self.apply_with_time_limit(transaction, &limiting_context, time_limit)
+            }
+            None => self.handler.apply(transaction, &mut limiting_context),
+        };
+
+        // A contract that traps or fails to allocate while limits are in effect may have
+        // exceeded them, so the limits are included in its receipt
+        result.map_err(|err| match (err, limiting_context.violation.into_inner()) {
+            (ApplyError::InvalidTransaction(_), Some(violation))
+            | (ApplyError::InternalError(_), Some(violation)) => ApplyError::InvalidTransaction(
+                format!("Contract exceeded its execution limits: {}", violation),
+            ),
+            (ApplyError::InvalidTransaction(msg), None) => ApplyError::InvalidTransaction(format!(
+                "{} (execution limits: {})",
+                msg,
+                self.limits.describe()
+            )),
+            (err, _) => err,
+        })
+    }
+}
+
+fn new_sabre_handler() -> SabreTransactionHandler {
+    SabreTransactionHandler::new(Box::new(SettingsAdminPermission))
+}
+
+/// Computes the state address of the given version of a Sabre contract.
+fn compute_contract_address(name: &str, version: &str) -> String {
+    let hash = Sha512::digest(format!("{},{}", name, version).as_bytes());
+    format!("{}{}", CONTRACT_ADDRESS_PREFIX, &to_hex(&hash)[..64])
+}
+
+/// A transaction context that instruments the executed contract when the Sabre handler loads it,
+/// and records why it could not be instrumented.
+struct LimitingContext<'a> {
+    inner: &'a dyn TransactionContext,
+    limits: &'a ExecutionLimits,
+    contract_address: String,
+    violation: RefCell<Option<String>>,
+}
+
+impl LimitingContext<'_> {
+    fn instrument_contracts(&self, value: &[u8]) -> Result<Vec<u8>, ContextError> {
+        let contracts = ContractList::from_bytes(value)
+            .map_err(|err| ContextError::SerializationError(Box::new(err)))?
+            .contracts()
+            .iter()
+            .map(|contract| {
+                let wasm = self.limits.instrument(contract.contract()).map_err(|msg| {
+                    self.violation.replace(Some(msg.clone()));
+                    ContextError::ResponseAttributeError(msg)
+                })?;
+                ContractBuilder::new()
+                    .with_name(contract.name().into())
+                    .with_version(contract.version().into())
+                    .with_inputs(contract.inputs().to_vec())
+                    .with_outputs(contract.outputs().to_vec())
+                    .with_creator(contract.creator().into())
+                    .with_contract(wasm)
+                    .build()
+                    .map_err(|err| ContextError::SerializationError(Box::new(err)))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        ContractListBuilder::new()
+            .with_contracts(contracts)
+            .build()
+            .map_err(|err| ContextError::SerializationError(Box::new(err)))?
+            .into_bytes()
+            .map_err(|err| ContextError::SerializationError(Box::new(err)))
+    }
+
+    /// Handles a state access of a contract executing on another thread.
+    fn handle(&self, request: ContextRequest) -> Result<ContextReply, ContextFailure> {
+        let reply = match request {
+            ContextRequest::GetStateEntries(addresses) => {
+                ContextReply::Entries(self.get_state_entries(&addresses)?)
+            }
+            ContextRequest::SetStateEntries(entries) => {
+                self.set_state_entries(entries)?;
+                ContextReply::Done
+            }
+            ContextRequest::DeleteStateEntries(addresses) => {
+                ContextReply::Deleted(self.delete_state_entries(&addresses)?)
+            }
+            ContextRequest::AddReceiptData(data) => {
+                self.add_receipt_data(data)?;
+                ContextReply::Done
+            }
+            ContextRequest::AddEvent(event_type, attributes, data) => {
+                self.add_event(event_type, attributes, data)?;
+                ContextReply::Done
+            }
+        };
+        Ok(reply)
+    }
+}
+
+impl TransactionContext for LimitingContext<'_> {
+    fn get_state_entries(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+        self.inner
+            .get_state_entries(addresses)?
+            .into_iter()
+            .map(|(address, value)| {
+                if address == self.contract_address {
+                    let value = self.instrument_contracts(&value)?;
+                    Ok((address, value))
+                } else {
+                    Ok((address, value))
+                }
+            })
+            .collect()
+    }
+
+    fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+        self.inner.set_state_entries(entries)
+    }
+
+    fn delete_state_entries(&self, addresses: &[String]) -> Result<Vec<String>, ContextError> {
+        self.inner.delete_state_entries(addresses)
+    }
+
+    fn add_receipt_data(&self, data: Vec<u8>) -> Result<(), ContextError> {
+        self.inner.add_receipt_data(data)
+    }
+
+    fn add_event(
+        &self,
+        event_type: String,
+        attributes: Vec<(String, String)>,
+        data: Vec<u8>,
+    ) -> Result<(), ContextError> {
+        self.inner.add_event(event_type, attributes, data)
+    }
+}
+
+/// The messages from the thread a contract executes on
+enum ExecutionMessage {
+    Request(ContextRequest),
+    Finished(Result<(), ApplyError>),
+}
+
+/// A state access of a contract executing on another thread
+enum ContextRequest {
+    GetStateEntries(Vec<String>),
+    SetStateEntries(Vec<(String, Vec<u8>)>),
+    DeleteStateEntries(Vec<String>),
+    AddReceiptData(Vec<u8>),
+    AddEvent(String, Vec<(String, String)>, Vec<u8>),
+}
+
+enum ContextReply {
+    Entries(Vec<(String, Vec<u8>)>),
+    Deleted(Vec<String>),
+    Done,
+}
+
+/// A `ContextError` that can be sent between threads
+struct ContextFailure {
+    authorization: bool,
+    message: String,
+}
+
+impl From<ContextError> for ContextFailure {
+    fn from(err: ContextError) -> Self {
+        match err {
+            ContextError::AuthorizationError(message) => Self {
+                authorization: true,
+                message,
+            },
+            err => Self {
+                authorization: false,
+                message: err.to_string(),
+            },
+        }
+    }
+}
+
+impl From<ContextFailure> for ContextError {
+    fn from(failure: ContextFailure) -> Self {
+        if failure.authorization {
+            ContextError::AuthorizationError(failure.message)
+        } else {
+            ContextError::ReceiveError(failure.message.into())
+        }
+    }
+}
+
+/// The transaction context of a contract executing on another thread, which sends each state
+/// access to the thread that owns the actual context.
+struct ProxyContext {
+    sender: Sender<ExecutionMessage>,
+    replies: Receiver<Result<ContextReply, ContextFailure>>,
+}
+
+impl ProxyContext {
+    fn request(&self, request: ContextRequest) -> Result<ContextReply, ContextError> {
+        self.sender
+            .send(ExecutionMessage::Request(request))
+            .map_err(|_| ContextError::SendError("contract execution was aborted".into()))?;
+        self.replies
+            .recv()
+            .map_err(|_| ContextError::ReceiveError("contract execution was aborted".into()))?
+            .map_err(ContextError::from)
+    }
+}
+
+impl TransactionContext for ProxyContext {
+    fn get_state_entries(
+        &self,
+        addresses: &[String],
+    ) -> Result<Vec<(String, Vec<u8>)>, ContextError> {
+        match self.request(ContextRequest::GetStateEntries(addresses.to_vec()))? {
+            ContextReply::Entries(entries) => Ok(entries),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn set_state_entries(&self, entries: Vec<(String, Vec<u8>)>) -> Result<(), ContextError> {
+        match self.request(ContextRequest::SetStateEntries(entries))? {
+            ContextReply::Done => Ok(()),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn delete_state_entries(&self, addresses: &[String]) -> Result<Vec<String>, ContextError> {
+        match self.request(ContextRequest::DeleteStateEntries(addresses.to_vec()))? {
+            ContextReply::Deleted(addresses) => Ok(addresses),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn add_receipt_data(&self, data: Vec<u8>) -> Result<(), ContextError> {
+        match self.request(ContextRequest::AddReceiptData(data))? {
+            ContextReply::Done => Ok(()),
+            _ => Err(unexpected_reply()),
+        }
+    }
+
+    fn add_event(
+        &self,
+        event_type: String,
+        attributes: Vec<(String, String)>,
+        data: Vec<u8>,
+    ) -> Result<(), ContextError> {
+        match self.request(ContextRequest::AddEvent(event_type, attributes, data))? {
+            ContextReply::Done => Ok(()),
+            _ => Err(unexpected_reply()),
+        }
+    }
+}
+
+fn unexpected_reply() -> ContextError {
+    ContextError::ReceiveError("unexpected reply to a contract's state access".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A module with a single memory of one initial page and no maximum, and a function that
+    /// calls itself:
+    ///
+    /// ```wat
+    /// (module
+    ///   (memory 1)
+    ///   (func $recurse (call $recurse)))
+    /// ```
+    const MODULE: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+        0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: () -> ()
+        0x03, 0x02, 0x01, 0x00, // function section
+        0x05, 0x03, 0x01, 0x00, 0x01, // memory section: initial 1, no maximum
+        0x0a, 0x06, 0x01, 0x04, 0x00, 0x10, 0x00, 0x0b, // code section: call 0
+    ];
+
+    fn memory_limits(wasm: &[u8]) -> (u32, Option<u32>) {
+        let module = parity_wasm::deserialize_buffer::<Module>(wasm).expect("invalid module");
+        let limits = *module.memory_section().expect("no memory").entries()[0].limits();
+        (limits.initial(), limits.maximum())
+    }
+
+    /// Verifies that modules are left as they are without memory or stack limits.
+    #[test]
+    fn instrument_without_limits() {
+        let limits = ExecutionLimits::new().with_max_execution_time(Duration::from_secs(1));
+
+        assert_eq!(limits.instrument(MODULE), Ok(MODULE.to_vec()));
+    }
+
+    /// Verifies that the memory limit is set as the maximum of the module's memory, rounded down
+    /// to whole pages, and that modules that need more memory to start are rejected.
+    #[test]
+    fn instrument_memory_limit() {
+        let limits = ExecutionLimits::new().with_max_memory(3 * WASM_PAGE_SIZE + 1);
+        let wasm = limits.instrument(MODULE).expect("failed to instrument");
+        assert_eq!(memory_limits(&wasm), (1, Some(3)));
+
+        let limits = ExecutionLimits::new().with_max_memory(WASM_PAGE_SIZE - 1);
+        assert!(limits.instrument(MODULE).is_err());
+    }
+
+    /// Verifies that a stack limiter is injected, which adds its stack height global.
+    #[test]
+    fn instrument_stack_limit() {
+        let limits = ExecutionLimits::new().with_max_stack_height(1024);
+        let wasm = limits.instrument(MODULE).expect("failed to instrument");
+
+        let module = parity_wasm::deserialize_buffer::<Module>(&wasm).expect("invalid module");
+        assert_eq!(
+            module
+                .global_section()
+                .map(|section| section.entries().len()),
+            Some(1)
+        );
+        assert_eq!(memory_limits(&wasm), (1, None));
+    }
+
+    /// Verifies that invalid modules are rejected.
+    #[test]
+    fn instrument_invalid_module() {
+        let limits = ExecutionLimits::new().with_max_memory(WASM_PAGE_SIZE);
+
+        assert!(limits.instrument(&[0x00, 0x61, 0x73]).is_err());
+    }
+
+    /// Verifies that contract addresses are computed as Sabre computes them.
+    #[test]
+    fn contract_address() {
+        let address = compute_contract_address("intkey_multiply", "1.0");
+
+        assert_eq!(address.len(), 70);
+        assert!(address.starts_with(CONTRACT_ADDRESS_PREFIX));
+        assert_eq!(
+            &address[6..],
+            &to_hex(&Sha512::digest(b"intkey_multiply,1.0"))[..64]
+        );
+    }
+}
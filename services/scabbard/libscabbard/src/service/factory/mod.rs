@@ -18,17 +18,17 @@ mod endpoint_provider;
 use std::collections::HashMap;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use std::collections::HashSet;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
 use std::convert::TryFrom;
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 use std::path::Path;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use std::sync::RwLock;
 use std::sync::{Arc, Mutex};
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
 use std::time::Duration;
 
 use cylinder::VerifierFactory;
+#[cfg(any(feature = "postgres", feature = "sqlite"))]
+use diesel::r2d2::Builder;
 #[cfg(feature = "diesel")]
 use diesel::r2d2::{ConnectionManager, Pool};
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
@@ -38,30 +38,58 @@ use sawtooth::receipt::store::ReceiptStore;
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 use splinter::error::InternalError;
 use splinter::error::{InvalidArgumentError, InvalidStateError};
+#[cfg(all(feature = "service-budgets", feature = "execution-limits"))]
+use splinter::runtime::service::instance::SERVICE_MEMORY_BUDGET_ARG;
+#[cfg(all(feature = "service-budgets", feature = "parallel-execution"))]
+use splinter::runtime::service::instance::SERVICE_THREAD_BUDGET_ARG;
 use splinter::service::instance::{
     FactoryCreateError, ServiceArgValidator, ServiceFactory, ServiceInstance,
 };
 use splinter::service::instance::{OrchestratableService, OrchestratableServiceFactory};
+#[cfg(feature = "connection-pool-settings")]
+use splinter::store::ConnectionPoolSettings;
+#[cfg(feature = "verification-pool")]
+use splinter::threading::verification::PooledVerifier;
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 use transact::database::Database;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use transact::state::merkle::sql;
 
 use crate::hex::parse_hex;
+#[cfg(feature = "anchoring")]
+use crate::service::AnchorConfig;
+#[cfg(feature = "batch-validation")]
+use crate::service::BatchValidator;
+#[cfg(feature = "execution-limits")]
+use crate::service::ExecutionLimits;
+#[cfg(feature = "quiet-hours")]
+use crate::service::QuietHours;
+#[cfg(feature = "receipt-pruning")]
+use crate::service::ReceiptRetention;
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 use crate::service::ScabbardStatePurgeHandler;
+#[cfg(feature = "snapshot")]
+use crate::service::SnapshotConfig;
+#[cfg(feature = "state-pruning")]
+use crate::service::StateRetention;
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use crate::service::{
     error::ScabbardError,
     state::merkle_state::{self, MerkleState, MerkleStateConfig},
-    Scabbard, ScabbardVersion, SERVICE_TYPE,
+    Scabbard, SERVICE_TYPE,
 };
+use crate::service::{ConsensusSettings, ScabbardVersion, DEFAULT_COORDINATOR_TIMEOUT};
 #[cfg(feature = "diesel")]
 use crate::store::diesel::DieselCommitHashStore;
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 use crate::store::transact::factory::{LmdbDatabaseFactory, LmdbDatabasePurgeHandle};
 #[cfg(any(feature = "postgres", feature = "sqlite"))]
 use crate::store::CommitHashStore;
+#[cfg(all(
+    feature = "receipt-queries",
+    any(feature = "postgres", feature = "sqlite")
+))]
+use crate::store::{DieselReceiptIndexStore, ReceiptIndexStore};
 
 #[cfg(all(feature = "lmdb", any(feature = "postgres", feature = "sqlite")))]
 const DEFAULT_LMDB_DIR: &str = "/var/lib/splinter";
@@ -161,6 +189,20 @@ pub struct ScabbardFactoryBuilder {
     storage_configuration: Option<ScabbardStorageConfiguration>,
     signature_verifier_factory: Option<Arc<Mutex<Box<dyn VerifierFactory>>>>,
     enable_state_autocleanup: Option<bool>,
+    #[cfg(feature = "anchoring")]
+    anchoring: Option<AnchorConfig>,
+    #[cfg(feature = "batch-validation")]
+    batch_validator: Option<Arc<dyn BatchValidator>>,
+    #[cfg(feature = "receipt-pruning")]
+    receipt_retention: Option<ReceiptRetention>,
+    #[cfg(feature = "snapshot")]
+    snapshots: Option<SnapshotConfig>,
+    #[cfg(feature = "state-pruning")]
+    state_retention: Option<StateRetention>,
+    #[cfg(feature = "connection-pool-settings")]
+    connection_pool_settings: Option<ConnectionPoolSettings>,
+    #[cfg(feature = "verification-pool")]
+    verification_pool: Option<PooledVerifier>,
 }
 
 impl ScabbardFactoryBuilder {
@@ -235,6 +277,66 @@ impl ScabbardFactoryBuilder {
         self
     }
 
+    /// Anchor the state roots of the services created by the resulting factory that are selected
+    /// by the given configuration.
+    #[cfg(feature = "anchoring")]
+    pub fn with_anchoring(mut self, anchoring: AnchorConfig) -> Self {
+        self.anchoring = Some(anchoring);
+        self
+    }
+
+    /// Check the batches submitted to the services created by the resulting factory with the
+    /// given validator before they are proposed.
+    #[cfg(feature = "batch-validation")]
+    pub fn with_batch_validator(mut self, batch_validator: Arc<dyn BatchValidator>) -> Self {
+        self.batch_validator = Some(batch_validator);
+        self
+    }
+
+    /// Prune the receipts of the services created by the resulting factory according to the
+    /// given policy.
+    #[cfg(feature = "receipt-pruning")]
+    pub fn with_receipt_retention(mut self, receipt_retention: ReceiptRetention) -> Self {
+        self.receipt_retention = Some(receipt_retention);
+        self
+    }
+
+    /// Write snapshots of the services created by the resulting factory as configured, and
+    /// restore those services from their snapshots when they start without any receipts.
+    #[cfg(feature = "snapshot")]
+    pub fn with_snapshots(mut self, snapshots: SnapshotConfig) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Keep the previous state roots of the services created by the resulting factory according
+    /// to the given policy, unless a service's arguments set its own limits.
+    #[cfg(feature = "state-pruning")]
+    pub fn with_state_retention(mut self, state_retention: StateRetention) -> Self {
+        self.state_retention = Some(state_retention);
+        self
+    }
+
+    /// Verify the signatures of the batches submitted to the services created by the resulting
+    /// factory on the given verification pool.
+    #[cfg(feature = "verification-pool")]
+    pub fn with_verification_pool(mut self, verification_pool: PooledVerifier) -> Self {
+        self.verification_pool = Some(verification_pool);
+        self
+    }
+
+    /// Sets the sizes and timeouts of the connection pools that the resulting factory creates
+    /// from a connection URI. A storage configuration with a connection pool keeps that pool's
+    /// settings.
+    #[cfg(feature = "connection-pool-settings")]
+    pub fn with_connection_pool_settings(
+        mut self,
+        connection_pool_settings: ConnectionPoolSettings,
+    ) -> Self {
+        self.connection_pool_settings = Some(connection_pool_settings);
+        self
+    }
+
     /// Set the signature verifier factory to be used by the resulting factory.  This is a required
     /// value, and omitting it will result in an [splinter::error::InvalidStateError] at build-time.
     pub fn with_signature_verifier_factory(
@@ -273,19 +375,32 @@ impl ScabbardFactoryBuilder {
                 .unwrap_or(DEFAULT_LMDB_DIR),
         );
 
+        #[cfg(feature = "connection-pool-settings")]
+        let connection_pool_settings = self.connection_pool_settings.unwrap_or_default();
+
         let store_factory_config = match storage_configuration {
             #[cfg(feature = "postgres")]
             ScabbardStorageConfiguration::ConnectionUri {
                 connection_uri: ConnectionUri::Postgres(url),
-            } => ScabbardFactoryStorageConfig::Postgres {
-                pool: get_postgres_pool(&*url)?,
-            },
+            } => {
+                let pool_builder = Pool::builder();
+                #[cfg(feature = "connection-pool-settings")]
+                let pool_builder = apply_pool_settings(&connection_pool_settings, pool_builder)?;
+                ScabbardFactoryStorageConfig::Postgres {
+                    pool: get_postgres_pool(&*url, pool_builder)?,
+                }
+            }
             #[cfg(feature = "sqlite")]
             ScabbardStorageConfiguration::ConnectionUri {
                 connection_uri: ConnectionUri::Sqlite(conn_str),
-            } => ScabbardFactoryStorageConfig::Sqlite {
-                pool: get_sqlite_pool(&*conn_str)?,
-            },
+            } => {
+                let pool_builder = Pool::builder();
+                #[cfg(feature = "connection-pool-settings")]
+                let pool_builder = apply_pool_settings(&connection_pool_settings, pool_builder)?;
+                ScabbardFactoryStorageConfig::Sqlite {
+                    pool: get_sqlite_pool(&*conn_str, pool_builder)?,
+                }
+            }
             #[cfg(feature = "postgres")]
             ScabbardStorageConfiguration::Postgres { pool } => {
                 ScabbardFactoryStorageConfig::Postgres { pool }
@@ -324,6 +439,18 @@ impl ScabbardFactoryBuilder {
             state_autocleanup_enabled,
             store_factory_config,
             signature_verifier_factory,
+            #[cfg(feature = "anchoring")]
+            anchoring: self.anchoring,
+            #[cfg(feature = "batch-validation")]
+            batch_validator: self.batch_validator,
+            #[cfg(feature = "receipt-pruning")]
+            receipt_retention: self.receipt_retention,
+            #[cfg(feature = "snapshot")]
+            snapshots: self.snapshots,
+            #[cfg(feature = "state-pruning")]
+            state_retention: self.state_retention,
+            #[cfg(feature = "verification-pool")]
+            verification_pool: self.verification_pool,
         })
     }
 
@@ -493,6 +620,30 @@ pub struct ScabbardFactory {
     signature_verifier_factory: Arc<Mutex<Box<dyn VerifierFactory>>>,
     #[cfg(any(feature = "postgres", feature = "sqlite"))]
     state_autocleanup_enabled: bool,
+    #[cfg(all(feature = "anchoring", any(feature = "postgres", feature = "sqlite")))]
+    anchoring: Option<AnchorConfig>,
+    #[cfg(all(
+        feature = "batch-validation",
+        any(feature = "postgres", feature = "sqlite")
+    ))]
+    batch_validator: Option<Arc<dyn BatchValidator>>,
+    #[cfg(all(
+        feature = "receipt-pruning",
+        any(feature = "postgres", feature = "sqlite")
+    ))]
+    receipt_retention: Option<ReceiptRetention>,
+    #[cfg(all(feature = "snapshot", any(feature = "postgres", feature = "sqlite")))]
+    snapshots: Option<SnapshotConfig>,
+    #[cfg(all(
+        feature = "state-pruning",
+        any(feature = "postgres", feature = "sqlite")
+    ))]
+    state_retention: Option<StateRetention>,
+    #[cfg(all(
+        feature = "verification-pool",
+        any(feature = "postgres", feature = "sqlite")
+    ))]
+    verification_pool: Option<PooledVerifier>,
 }
 
 pub struct ScabbardArgValidator;
@@ -547,6 +698,20 @@ impl ServiceArgValidator for ScabbardArgValidator {
             }
         }
 
+        parse_consensus_args(args)?;
+
+        #[cfg(feature = "execution-limits")]
+        parse_execution_limits_args(args)?;
+
+        #[cfg(feature = "parallel-execution")]
+        parse_execution_workers_arg(args)?;
+
+        #[cfg(feature = "quiet-hours")]
+        parse_quiet_hours_arg(args)?;
+
+        #[cfg(feature = "state-pruning")]
+        parse_state_retention_args(args, None)?;
+
         Ok(())
     }
 }
@@ -567,6 +732,15 @@ impl ServiceFactory for ScabbardFactory {
     ///   commit a proposal before the coordinator rejects it (if not provided, default is 30
     ///   seconds)
     /// - `version`: the protocol version for scabbard (possible values: "1", "2") (default: "1")
+    ///
+    /// The following optional entries tune the two-phase commit consensus engine and are only
+    /// supported by version "2":
+    /// - `alarm_interval`: how often (in milliseconds) the engine checks its timers; must be less
+    ///   than the coordinator timeout (default: 100 milliseconds)
+    /// - `vote_timeout`: the length of time (in milliseconds) that a participant waits for the
+    ///   result of a proposal after voting before it resends its vote (default: 30 seconds)
+    /// - `max_retries`: the number of times the coordinator's verification request, or a
+    ///   participant's vote, is resent after a timeout before giving up (default: 0)
     #[cfg(any(feature = "postgres", feature = "sqlite"))]
     fn create(
         &self,
@@ -653,18 +827,43 @@ impl ScabbardFactory {
             ))
         })?;
 
-        let coordinator_timeout = args
-            .get("coordinator_timeout")
-            .map(|timeout| match timeout.parse::<u64>() {
-                Ok(timeout) => Ok(Duration::from_millis(timeout)),
-                Err(err) => Err(FactoryCreateError::InvalidArguments(format!(
-                    "invalid coordinator_timeout: {}",
-                    err
-                ))),
-            })
-            .transpose()?;
-        let version = ScabbardVersion::try_from(args.get("version").map(String::as_str))
-            .map_err(FactoryCreateError::InvalidArguments)?;
+        let ConsensusArgs {
+            version,
+            coordinator_timeout,
+            consensus_settings,
+        } = parse_consensus_args(&args)
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        #[cfg(feature = "execution-limits")]
+        let execution_limits = parse_execution_limits_args(&args)
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        #[cfg(feature = "parallel-execution")]
+        let execution_workers = parse_execution_workers_arg(&args)
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        #[cfg(all(feature = "service-budgets", feature = "execution-limits"))]
+        let execution_limits = apply_memory_budget(execution_limits, &args)
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        #[cfg(all(feature = "service-budgets", feature = "parallel-execution"))]
+        let execution_workers = apply_thread_budget(execution_workers, &args)
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        #[cfg(feature = "quiet-hours")]
+        let quiet_hours = parse_quiet_hours_arg(&args)
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        #[cfg(feature = "state-pruning")]
+        let state_retention = parse_state_retention_args(&args, self.state_retention.as_ref())
+            .map_err(|err| FactoryCreateError::InvalidArguments(err.to_string()))?;
+
+        #[cfg(feature = "anchoring")]
+        let anchoring = self
+            .anchoring
+            .as_ref()
+            .filter(|anchoring| anchoring.applies_to(circuit_id, &service_id))
+            .cloned();
 
         #[cfg(feature = "lmdb")]
         let (merkle_state, state_purge): (_, Box<dyn ScabbardStatePurgeHandler>) =
@@ -747,7 +946,27 @@ impl ScabbardFactory {
             ),
         };
 
-        Scabbard::new(
+        #[cfg(feature = "receipt-queries")]
+        let receipt_index_store: Arc<dyn ReceiptIndexStore> = match &self.store_factory_config {
+            #[cfg(feature = "postgres")]
+            ScabbardFactoryStorageConfig::Postgres { pool } => Arc::new(
+                DieselReceiptIndexStore::new(pool.clone(), circuit_id, &service_id),
+            ),
+            #[cfg(feature = "sqlite")]
+            ScabbardFactoryStorageConfig::Sqlite { pool } => Arc::new(
+                DieselReceiptIndexStore::new(pool.clone(), circuit_id, &service_id),
+            ),
+            #[cfg(feature = "sqlite")]
+            ScabbardFactoryStorageConfig::SqliteExclusiveWrites { pool } => {
+                Arc::new(DieselReceiptIndexStore::new_with_write_exclusivity(
+                    pool.clone(),
+                    circuit_id,
+                    &service_id,
+                ))
+            }
+        };
+
+        let scabbard = Scabbard::new(
             service_id,
             circuit_id,
             version,
@@ -766,7 +985,67 @@ impl ScabbardFactory {
             admin_keys,
             coordinator_timeout,
         )
-        .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))
+        .map(|scabbard| scabbard.with_consensus_settings(consensus_settings))
+        .map_err(|err| FactoryCreateError::CreationFailed(Box::new(err)))?;
+
+        #[cfg(feature = "execution-limits")]
+        let scabbard = match execution_limits {
+            Some(execution_limits) => scabbard.with_execution_limits(execution_limits),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "parallel-execution")]
+        let scabbard = match execution_workers {
+            Some(execution_workers) => scabbard.with_execution_workers(execution_workers),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "quiet-hours")]
+        let scabbard = match quiet_hours {
+            Some(quiet_hours) => scabbard.with_quiet_hours(quiet_hours),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "anchoring")]
+        let scabbard = match anchoring {
+            Some(anchoring) => scabbard.with_anchoring(anchoring),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "batch-validation")]
+        let scabbard = match &self.batch_validator {
+            Some(batch_validator) => scabbard.with_batch_validator(batch_validator.clone()),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "receipt-pruning")]
+        let scabbard = match &self.receipt_retention {
+            Some(receipt_retention) => scabbard.with_receipt_retention(receipt_retention.clone()),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "receipt-queries")]
+        let scabbard = scabbard.with_receipt_index_store(receipt_index_store);
+
+        #[cfg(feature = "snapshot")]
+        let scabbard = match &self.snapshots {
+            Some(snapshots) => scabbard.with_snapshots(snapshots.clone()),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "state-pruning")]
+        let scabbard = match state_retention {
+            Some(state_retention) => scabbard.with_state_retention(state_retention),
+            None => scabbard,
+        };
+
+        #[cfg(feature = "verification-pool")]
+        let scabbard = match &self.verification_pool {
+            Some(verification_pool) => scabbard.with_verification_pool(verification_pool.clone()),
+            None => scabbard,
+        };
+
+        Ok(scabbard)
     }
 
     /// Check that the LMDB files doesn't exist for the given service.
@@ -880,12 +1159,294 @@ fn parse_list(values_list: &str) -> Result<Vec<String>, String> {
     }
 }
 
+/// The consensus-related service arguments of a scabbard service.
+struct ConsensusArgs {
+    version: ScabbardVersion,
+    coordinator_timeout: Option<Duration>,
+    consensus_settings: ConsensusSettings,
+}
+
+/// Parse and validate the consensus-related service arguments: `version`, `coordinator_timeout`
+/// and the two-phase commit tuning arguments, which are only supported by scabbard version 2.
+fn parse_consensus_args(
+    args: &HashMap<String, String>,
+) -> Result<ConsensusArgs, InvalidArgumentError> {
+    let version = ScabbardVersion::try_from(args.get("version").map(String::as_str))
+        .map_err(|msg| InvalidArgumentError::new("version", msg))?;
+
+    let coordinator_timeout = parse_millis_arg(args, "coordinator_timeout")?;
+    let alarm_interval = parse_millis_arg(args, "alarm_interval")?;
+    let vote_timeout = parse_millis_arg(args, "vote_timeout")?;
+    let max_retries = args
+        .get("max_retries")
+        .map(|retries| {
+            retries.parse::<u32>().map_err(|err| {
+                InvalidArgumentError::new("max_retries", format!("invalid value: {}", err))
+            })
+        })
+        .transpose()?;
+
+    let mut consensus_settings = ConsensusSettings::new();
+    if let Some(alarm_interval) = alarm_interval {
+        let effective_timeout =
+            coordinator_timeout.unwrap_or_else(|| Duration::from_secs(DEFAULT_COORDINATOR_TIMEOUT));
+        if alarm_interval >= effective_timeout {
+            return Err(InvalidArgumentError::new(
+                "alarm_interval",
+                "must be less than the coordinator timeout",
+            ));
+        }
+        consensus_settings = consensus_settings.with_alarm_interval(alarm_interval);
+    }
+    if let Some(vote_timeout) = vote_timeout {
+        consensus_settings = consensus_settings.with_vote_timeout(vote_timeout);
+    }
+    if let Some(max_retries) = max_retries {
+        consensus_settings = consensus_settings.with_max_retries(max_retries);
+    }
+
+    if version == ScabbardVersion::V1 && !consensus_settings.is_empty() {
+        return Err(InvalidArgumentError::new(
+            "version",
+            "alarm_interval, vote_timeout and max_retries are only supported by version 2",
+        ));
+    }
+
+    Ok(ConsensusArgs {
+        version,
+        coordinator_timeout,
+        consensus_settings,
+    })
+}
+
+/// Parse the optional `execution_max_memory`, `execution_max_stack_height` and
+/// `execution_max_time` service arguments, the limits on the memory in bytes, the stack height
+/// (which bounds the depth of nested calls) and the time in milliseconds of each execution of a
+/// Sabre contract. If none of them are set, execution is not limited.
+#[cfg(feature = "execution-limits")]
+fn parse_execution_limits_args(
+    args: &HashMap<String, String>,
+) -> Result<Option<ExecutionLimits>, InvalidArgumentError> {
+    let max_memory = args
+        .get("execution_max_memory")
+        .map(|bytes| match bytes.parse::<u64>() {
+            Ok(0) => Err(InvalidArgumentError::new(
+                "execution_max_memory",
+                "must be greater than 0",
+            )),
+            Ok(bytes) => Ok(bytes),
+            Err(err) => Err(InvalidArgumentError::new(
+                "execution_max_memory",
+                format!("invalid value: {}", err),
+            )),
+        })
+        .transpose()?;
+    let max_stack_height = args
+        .get("execution_max_stack_height")
+        .map(|height| match height.parse::<u32>() {
+            Ok(0) => Err(InvalidArgumentError::new(
+                "execution_max_stack_height",
+                "must be greater than 0",
+            )),
+            Ok(height) => Ok(height),
+            Err(err) => Err(InvalidArgumentError::new(
+                "execution_max_stack_height",
+                format!("invalid value: {}", err),
+            )),
+        })
+        .transpose()?;
+    let max_execution_time = parse_millis_arg(args, "execution_max_time")?;
+
+    if max_memory.is_none() && max_stack_height.is_none() && max_execution_time.is_none() {
+        return Ok(None);
+    }
+
+    let mut limits = ExecutionLimits::new();
+    if let Some(max_memory) = max_memory {
+        limits = limits.with_max_memory(max_memory);
+    }
+    if let Some(max_stack_height) = max_stack_height {
+        limits = limits.with_max_stack_height(max_stack_height);
+    }
+    if let Some(max_execution_time) = max_execution_time {
+        limits = limits.with_max_execution_time(max_execution_time);
+    }
+
+    Ok(Some(limits))
+}
+
+/// Parse the optional `execution_workers` service argument, the number of transactions the
+/// service may execute at the same time.
+#[cfg(feature = "parallel-execution")]
+fn parse_execution_workers_arg(
+    args: &HashMap<String, String>,
+) -> Result<Option<usize>, InvalidArgumentError> {
+    args.get("execution_workers")
+        .map(|workers| match workers.parse::<usize>() {
+            Ok(0) => Err(InvalidArgumentError::new(
+                "execution_workers",
+                "must be greater than 0",
+            )),
+            Ok(workers) => Ok(workers),
+            Err(err) => Err(InvalidArgumentError::new(
+                "execution_workers",
+                format!("invalid value: {}", err),
+            )),
+        })
+        .transpose()
+}
+
+/// Parse an optional budget argument, set by the service orchestrator, that is a positive number
+/// of threads or bytes of memory.
+#[cfg(all(
+    feature = "service-budgets",
+    any(feature = "execution-limits", feature = "parallel-execution")
+))]
+fn parse_budget_arg(
+    args: &HashMap<String, String>,
+    name: &str,
+) -> Result<Option<u64>, InvalidArgumentError> {
+    args.get(name)
+        .map(|budget| match budget.parse::<u64>() {
+            Ok(0) => Err(InvalidArgumentError::new(name, "must be greater than 0")),
+            Ok(budget) => Ok(budget),
+            Err(err) => Err(InvalidArgumentError::new(
+                name,
+                format!("invalid value: {}", err),
+            )),
+        })
+        .transpose()
+}
+
+/// Keeps the memory of the service's contracts within its memory budget, if the orchestrator set
+/// one: a contract may use no more memory than the budget, even if the `execution_max_memory`
+/// argument allows more.
+#[cfg(all(feature = "service-budgets", feature = "execution-limits"))]
+fn apply_memory_budget(
+    execution_limits: Option<ExecutionLimits>,
+    args: &HashMap<String, String>,
+) -> Result<Option<ExecutionLimits>, InvalidArgumentError> {
+    Ok(match parse_budget_arg(args, SERVICE_MEMORY_BUDGET_ARG)? {
+        Some(budget) => {
+            let limits = execution_limits.unwrap_or_default();
+            let max_memory = limits
+                .max_memory()
+                .map_or(budget, |max_memory| max_memory.min(budget));
+            Some(limits.with_max_memory(max_memory))
+        }
+        None => execution_limits,
+    })
+}
+
+/// Keeps the number of the service's execution workers within its thread budget, if the
+/// orchestrator set one.
+#[cfg(all(feature = "service-budgets", feature = "parallel-execution"))]
+fn apply_thread_budget(
+    execution_workers: Option<usize>,
+    args: &HashMap<String, String>,
+) -> Result<Option<usize>, InvalidArgumentError> {
+    Ok(match parse_budget_arg(args, SERVICE_THREAD_BUDGET_ARG)? {
+        Some(budget) => execution_workers.map(|workers| workers.min(budget as usize)),
+        None => execution_workers,
+    })
+}
+
+/// Parse the optional `quiet_hours` service argument, the schedule of windows during which the
+/// service's traffic is paused.
+#[cfg(feature = "quiet-hours")]
+fn parse_quiet_hours_arg(
+    args: &HashMap<String, String>,
+) -> Result<Option<QuietHours>, InvalidArgumentError> {
+    args.get("quiet_hours")
+        .map(|quiet_hours| {
+            quiet_hours
+                .parse::<QuietHours>()
+                .map_err(|msg| InvalidArgumentError::new("quiet_hours", msg))
+        })
+        .transpose()
+}
+
+/// Parse the optional `state_retention_roots` and `state_retention_days` service arguments, the
+/// number of previous state roots the service keeps and the number of days it keeps them for.
+/// The arguments override the corresponding limits of the given default policy; if neither the
+/// arguments nor a default are set, the service does not retain previous roots.
+#[cfg(feature = "state-pruning")]
+fn parse_state_retention_args(
+    args: &HashMap<String, String>,
+    default: Option<&StateRetention>,
+) -> Result<Option<StateRetention>, InvalidArgumentError> {
+    let max_roots = args
+        .get("state_retention_roots")
+        .map(|roots| {
+            roots.parse::<usize>().map_err(|err| {
+                InvalidArgumentError::new(
+                    "state_retention_roots",
+                    format!("invalid value: {}", err),
+                )
+            })
+        })
+        .transpose()?;
+    let max_days = args
+        .get("state_retention_days")
+        .map(|days| {
+            days.parse::<u64>().map_err(|err| {
+                InvalidArgumentError::new("state_retention_days", format!("invalid value: {}", err))
+            })
+        })
+        .transpose()?;
+
+    if max_roots.is_none() && max_days.is_none() {
+        return Ok(default.cloned());
+    }
+
+    let mut retention = default.cloned().unwrap_or_default();
+    if let Some(max_roots) = max_roots {
+        retention = retention.with_max_roots(max_roots);
+    }
+    if let Some(max_days) = max_days {
+        retention = retention.with_max_age(Duration::from_secs(max_days * 24 * 60 * 60));
+    }
+
+    Ok(Some(retention))
+}
+
+/// Parse an optional service argument that is a positive number of milliseconds.
+fn parse_millis_arg(
+    args: &HashMap<String, String>,
+    name: &str,
+) -> Result<Option<Duration>, InvalidArgumentError> {
+    args.get(name)
+        .map(|millis| match millis.parse::<u64>() {
+            Ok(0) => Err(InvalidArgumentError::new(name, "must be greater than 0")),
+            Ok(millis) => Ok(Duration::from_millis(millis)),
+            Err(err) => Err(InvalidArgumentError::new(
+                name,
+                format!("invalid value: {}", err),
+            )),
+        })
+        .transpose()
+}
+
+#[cfg(all(
+    feature = "connection-pool-settings",
+    any(feature = "postgres", feature = "sqlite")
+))]
+fn apply_pool_settings<M: diesel::r2d2::ManageConnection>(
+    settings: &ConnectionPoolSettings,
+    pool_builder: Builder<M>,
+) -> Result<Builder<M>, InvalidStateError> {
+    settings.apply(pool_builder).map_err(|err| {
+        InvalidStateError::with_message(format!("Invalid connection pool settings: {}", err))
+    })
+}
+
 #[cfg(feature = "postgres")]
 fn get_postgres_pool(
     url: &str,
+    pool_builder: Builder<ConnectionManager<diesel::pg::PgConnection>>,
 ) -> Result<Pool<ConnectionManager<diesel::pg::PgConnection>>, InvalidStateError> {
     let connection_manager = ConnectionManager::<diesel::pg::PgConnection>::new(url);
-    Pool::builder().build(connection_manager).map_err(|err| {
+    pool_builder.build(connection_manager).map_err(|err| {
         InvalidStateError::with_message(format!("Failed to build connection pool: {}", err))
     })
 }
@@ -893,6 +1454,7 @@ fn get_postgres_pool(
 #[cfg(feature = "sqlite")]
 fn get_sqlite_pool(
     conn_str: &str,
+    mut pool_builder: Builder<ConnectionManager<diesel::SqliteConnection>>,
 ) -> Result<Pool<ConnectionManager<diesel::SqliteConnection>>, InvalidStateError> {
     if (conn_str != ":memory:") && !Path::new(conn_str).exists() {
         return Err(InvalidStateError::with_message(format!(
@@ -901,12 +1463,11 @@ fn get_sqlite_pool(
         )));
     }
     let connection_manager = ConnectionManager::<diesel::sqlite::SqliteConnection>::new(conn_str);
-    let mut pool_builder = Pool::builder();
     // A new database is created for each connection to the in-memory SQLite
     // implementation; to ensure that the resulting stores will operate on the same
     // database, only one connection is allowed.
     if conn_str == ":memory:" {
-        pool_builder = pool_builder.max_size(1);
+        pool_builder = pool_builder.max_size(1).min_idle(None);
     }
     pool_builder.build(connection_manager).map_err(|err| {
         InvalidStateError::with_message(format!("Failed to build connection pool: {}", err))
@@ -1035,6 +1596,151 @@ mod tests {
         assert_eq!(scabbard.coordinator_timeout, Duration::from_millis(123));
     }
 
+    /// Verify that the two-phase commit tuning service arguments are properly set for a new
+    /// version 2 `Scabbard` instance.
+    #[test]
+    fn create_with_consensus_settings() {
+        let factory = get_factory();
+        let mut args = get_mock_args();
+        args.insert("version".into(), "2".into());
+        args.insert("alarm_interval".into(), "50".into());
+        args.insert("vote_timeout".into(), "2000".into());
+        args.insert("max_retries".into(), "3".into());
+
+        let service = factory
+            .create("".into(), "", "", args)
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+
+        assert_eq!(
+            scabbard.consensus_settings,
+            ConsensusSettings::new()
+                .with_alarm_interval(Duration::from_millis(50))
+                .with_vote_timeout(Duration::from_millis(2000))
+                .with_max_retries(3)
+        );
+    }
+
+    /// Verify that the execution limit service arguments are properly set for a new `Scabbard`
+    /// instance.
+    #[cfg(feature = "execution-limits")]
+    #[test]
+    fn create_with_execution_limits() {
+        let factory = get_factory();
+        let mut args = get_mock_args();
+        args.insert("execution_max_memory".into(), "16777216".into());
+        args.insert("execution_max_time".into(), "500".into());
+
+        let service = factory
+            .create("".into(), "", "", args)
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+
+        assert_eq!(
+            scabbard.execution_limits,
+            Some(
+                ExecutionLimits::new()
+                    .with_max_memory(16 * 1024 * 1024)
+                    .with_max_execution_time(Duration::from_millis(500))
+            )
+        );
+    }
+
+    /// Verify that the `quiet_hours` service argument is properly set for a new `Scabbard`
+    /// instance.
+    #[cfg(feature = "quiet-hours")]
+    #[test]
+    fn create_with_quiet_hours() {
+        let factory = get_factory();
+        let mut args = get_mock_args();
+        args.insert("quiet_hours".into(), "Mon-Fri 18:00-08:00".into());
+
+        let service = factory
+            .create("".into(), "", "", args)
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+
+        assert_eq!(
+            scabbard.quiet_hours,
+            Some(
+                "Mon-Fri 18:00-08:00"
+                    .parse::<QuietHours>()
+                    .expect("failed to parse quiet hours")
+            )
+        );
+    }
+
+    /// Verify that the `state_retention_roots` and `state_retention_days` service arguments
+    /// override the limits of the factory's default state retention policy.
+    #[cfg(feature = "state-pruning")]
+    #[test]
+    fn create_with_state_retention() {
+        let mut factory = get_factory();
+        factory.state_retention = Some(StateRetention::new().with_max_roots(10));
+
+        let service = factory
+            .create("".into(), "", "", get_mock_args())
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+        assert_eq!(
+            scabbard.state_retention,
+            Some(StateRetention::new().with_max_roots(10))
+        );
+
+        let mut args = get_mock_args();
+        args.insert("state_retention_days".into(), "7".into());
+        let service = factory
+            .create("".into(), "", "", args)
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+        assert_eq!(
+            scabbard.state_retention,
+            Some(
+                StateRetention::new()
+                    .with_max_roots(10)
+                    .with_max_age(Duration::from_secs(7 * 24 * 60 * 60))
+            )
+        );
+    }
+
+    /// Verify that only the services selected by the factory's anchoring configuration are
+    /// anchored.
+    #[cfg(feature = "anchoring")]
+    #[test]
+    fn create_with_anchoring() {
+        let mut factory = get_factory();
+        factory.anchoring = Some(
+            AnchorConfig::new(Arc::new(
+                crate::service::HttpAnchorPublisher::new("http://localhost:8080/anchors")
+                    .expect("failed to create publisher"),
+            ))
+            .with_services(vec!["abcde-01234::a000".into()]),
+        );
+
+        let create = |service_id: &str| {
+            factory
+                .create_scabbard(service_id.into(), "abcde-01234", get_mock_args())
+                .expect("failed to create service")
+        };
+        assert!(create("a000").anchoring.is_some());
+        assert!(create("b000").anchoring.is_none());
+    }
+
     /// Verify that `Scabbard` creation fails when the `peer_services` argument isn't specified.
     #[test]
     fn create_without_peer_services() {
@@ -1095,6 +1801,164 @@ mod tests {
         assert!(validator.validate(&args).is_err());
     }
 
+    /// Verify arg validation returns an error if a timeout is zero or not a number, or if the
+    /// alarm interval is not less than the coordinator timeout
+    #[test]
+    fn test_invalid_timeout_argument_validation() {
+        let validator = ScabbardArgValidator;
+
+        for (name, value) in &[
+            ("coordinator_timeout", "0"),
+            ("coordinator_timeout", "abc"),
+            ("vote_timeout", "-1"),
+            ("max_retries", "many"),
+        ] {
+            let mut args = get_mock_args();
+            args.insert("version".into(), "2".into());
+            args.insert(name.to_string(), value.to_string());
+            assert!(
+                validator.validate(&args).is_err(),
+                "{}={} was accepted",
+                name,
+                value
+            );
+        }
+
+        let mut args = get_mock_args();
+        args.insert("version".into(), "2".into());
+        args.insert("coordinator_timeout".into(), "100".into());
+        args.insert("alarm_interval".into(), "100".into());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("alarm_interval".into(), "10".into());
+        assert!(validator.validate(&args).is_ok());
+    }
+
+    /// Verify arg validation returns an error if the quiet hours schedule is invalid
+    #[cfg(feature = "quiet-hours")]
+    #[test]
+    fn test_quiet_hours_argument_validation() {
+        let validator = ScabbardArgValidator;
+
+        let mut args = get_mock_args();
+        args.insert(
+            "quiet_hours".into(),
+            "Mon-Fri 18:00-08:00; Sat,Sun 00:00-24:00".into(),
+        );
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("quiet_hours".into(), "after 6pm".into());
+        assert!(validator.validate(&args).is_err());
+    }
+
+    /// Verify arg validation returns an error if the execution limits are not positive numbers
+    #[cfg(feature = "execution-limits")]
+    #[test]
+    fn test_execution_limits_argument_validation() {
+        let validator = ScabbardArgValidator;
+
+        let mut args = get_mock_args();
+        args.insert("execution_max_memory".into(), "16777216".into());
+        args.insert("execution_max_stack_height".into(), "65536".into());
+        args.insert("execution_max_time".into(), "1000".into());
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("execution_max_stack_height".into(), "0".into());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("execution_max_stack_height".into(), "65536".into());
+        args.insert("execution_max_memory".into(), "16MB".into());
+        assert!(validator.validate(&args).is_err());
+    }
+
+    /// Verify that the `execution_workers` service argument is properly set for a new `Scabbard`
+    /// instance, and that arg validation returns an error if it is not a positive number
+    #[cfg(feature = "parallel-execution")]
+    #[test]
+    fn test_execution_workers_argument() {
+        let factory = get_factory();
+        let mut args = get_mock_args();
+        args.insert("execution_workers".into(), "4".into());
+        assert!(ScabbardArgValidator.validate(&args).is_ok());
+
+        let service = factory
+            .create("".into(), "", "", args.clone())
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+        assert_eq!(scabbard.execution_workers, 4);
+
+        args.insert("execution_workers".into(), "0".into());
+        assert!(ScabbardArgValidator.validate(&args).is_err());
+
+        args.insert("execution_workers".into(), "many".into());
+        assert!(ScabbardArgValidator.validate(&args).is_err());
+    }
+
+    /// Verify that the number of execution workers of a new `Scabbard` instance is kept within
+    /// the thread budget set by the orchestrator
+    #[cfg(all(feature = "service-budgets", feature = "parallel-execution"))]
+    #[test]
+    fn test_thread_budget_argument() {
+        let factory = get_factory();
+        let mut args = get_mock_args();
+        args.insert("execution_workers".into(), "4".into());
+        args.insert(SERVICE_THREAD_BUDGET_ARG.into(), "2".into());
+
+        let service = factory
+            .create("".into(), "", "", args.clone())
+            .expect("failed to create service");
+        let scabbard = (&*service)
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .expect("failed to downcast Service to Scabbard");
+        assert_eq!(scabbard.execution_workers, 2);
+
+        args.insert(SERVICE_THREAD_BUDGET_ARG.into(), "0".into());
+        assert!(factory.create("".into(), "", "", args).is_err());
+    }
+
+    /// Verify arg validation returns an error if the state retention limits are not numbers
+    #[cfg(feature = "state-pruning")]
+    #[test]
+    fn test_state_retention_argument_validation() {
+        let validator = ScabbardArgValidator;
+
+        let mut args = get_mock_args();
+        args.insert("state_retention_roots".into(), "100".into());
+        args.insert("state_retention_days".into(), "30".into());
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("state_retention_days".into(), "a month".into());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("state_retention_days".into(), "30".into());
+        args.insert("state_retention_roots".into(), "-1".into());
+        assert!(validator.validate(&args).is_err());
+    }
+
+    /// Verify arg validation returns an error if the two-phase commit tuning arguments are used
+    /// with version 1, or if the version is not supported
+    #[test]
+    fn test_version_argument_validation() {
+        let validator = ScabbardArgValidator;
+
+        let mut args = get_mock_args();
+        args.insert("max_retries".into(), "2".into());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("version".into(), "1".into());
+        assert!(validator.validate(&args).is_err());
+
+        args.insert("version".into(), "2".into());
+        assert!(validator.validate(&args).is_ok());
+
+        args.insert("version".into(), "3".into());
+        assert!(validator.validate(&args).is_err());
+    }
+
     fn get_factory() -> ScabbardFactory {
         let connection_manager = ConnectionManager::<diesel::SqliteConnection>::new(":memory:");
         let pool = Pool::builder()
@@ -1126,6 +1990,18 @@ mod tests {
             state_autocleanup_enabled: false,
             store_factory_config,
             signature_verifier_factory: Arc::new(Mutex::new(Box::new(Secp256k1Context::new()))),
+            #[cfg(feature = "anchoring")]
+            anchoring: None,
+            #[cfg(feature = "batch-validation")]
+            batch_validator: None,
+            #[cfg(feature = "receipt-pruning")]
+            receipt_retention: None,
+            #[cfg(feature = "snapshot")]
+            snapshots: None,
+            #[cfg(feature = "state-pruning")]
+            state_retention: None,
+            #[cfg(feature = "verification-pool")]
+            verification_pool: None,
         }
     }
 
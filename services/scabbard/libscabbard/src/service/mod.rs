@@ -16,11 +16,29 @@
 //! `transact` library for state. Scabbard uses two-phase consensus to reach agreement on
 //! transactions.
 
+#[cfg(feature = "anchoring")]
+mod anchor;
+#[cfg(feature = "batch-validation")]
+mod batch_validation;
 mod consensus;
 mod error;
+#[cfg(feature = "execution-limits")]
+mod execution_limits;
 pub(crate) mod factory;
+#[cfg(feature = "quiet-hours")]
+mod quiet_hours;
+#[cfg(feature = "receipt-pruning")]
+mod receipt_pruning;
+#[cfg(feature = "receipt-queries")]
+mod receipt_query;
 mod shared;
-mod state;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+pub(crate) mod state;
+#[cfg(feature = "state-pruning")]
+mod state_pruning;
+#[cfg(feature = "state-sync")]
+mod state_sync;
 #[cfg(feature = "scabbardv3")]
 pub mod v3;
 
@@ -28,12 +46,16 @@ use std::any::Any;
 use std::collections::{HashSet, VecDeque};
 use std::convert::TryFrom;
 use std::fmt::Write as _;
+#[cfg(feature = "receipt-queries")]
+use std::sync::PoisonError;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use cylinder::Verifier as SignatureVerifier;
 use protobuf::Message;
 use sawtooth::receipt::store::ReceiptStore;
+#[cfg(feature = "verification-pool")]
+use splinter::threading::verification::PooledVerifier;
 use splinter::{
     consensus::{Proposal, ProposalUpdate},
     service::instance::{
@@ -41,28 +63,64 @@ use splinter::{
         ServiceMessageContext, ServiceNetworkRegistry, ServiceStartError, ServiceStopError,
     },
 };
-use transact::{
-    protocol::batch::BatchPair,
-    protos::{FromBytes, IntoBytes},
-};
+use transact::{protocol::batch::BatchPair, protos::FromBytes};
 
+#[cfg(feature = "snapshot")]
+use crate::snapshot::SnapshotInfo;
+use crate::state_proof::StateProof;
+#[cfg(feature = "state-pruning")]
+use crate::state_pruning::StatePruneInfo;
 use crate::store::CommitHashStore;
+#[cfg(feature = "receipt-queries")]
+use crate::store::ReceiptIndexStore;
 
 use super::protos::scabbard::{ScabbardMessage, ScabbardMessage_Type};
-
+#[cfg(feature = "state-sync")]
+use super::protos::scabbard::{StateSyncRequest, StateSyncResponse};
+
+#[cfg(feature = "anchoring")]
+use anchor::AnchorTask;
+#[cfg(feature = "anchoring")]
+pub use anchor::{AnchorConfig, AnchorPublisher, HttpAnchorPublisher, StateRootAnchor};
+#[cfg(feature = "batch-validation")]
+pub use batch_validation::{
+    BatchValidationRequest, BatchValidator, BatchVerdict, HttpBatchValidator, TransactionSummary,
+    WasmBatchValidator,
+};
+pub use consensus::ConsensusSettings;
 use consensus::ScabbardConsensusManager;
-use error::ScabbardError;
+pub use error::ScabbardError;
 pub use error::StateSubscriberError;
+#[cfg(feature = "execution-limits")]
+pub use execution_limits::ExecutionLimits;
 pub use factory::ConnectionUri;
 pub use factory::ScabbardArgValidator;
 pub use factory::{ScabbardFactory, ScabbardFactoryBuilder, ScabbardStorageConfiguration};
+#[cfg(feature = "quiet-hours")]
+pub use quiet_hours::QuietHours;
+#[cfg(feature = "receipt-pruning")]
+use receipt_pruning::ReceiptPruningTask;
+#[cfg(feature = "receipt-pruning")]
+pub use receipt_pruning::{FileReceiptArchiver, ReceiptArchiver, ReceiptRetention};
+#[cfg(feature = "receipt-queries")]
+pub use receipt_query::{QueriedReceipt, ReceiptQuery, ReceiptQueryResult};
 use shared::ScabbardShared;
+#[cfg(feature = "snapshot")]
+pub use snapshot::SnapshotConfig;
+#[cfg(feature = "snapshot")]
+use snapshot::SnapshotTask;
 use state::merkle_state::MerkleState;
+#[cfg(feature = "batch-status-stream")]
+pub use state::BatchStatusSubscriber;
 use state::ScabbardState;
 pub use state::{
     BatchInfo, BatchInfoIter, BatchStatus, Events, InvalidTransaction, StateChange,
     StateChangeEvent, StateIter, StateSubscriber, ValidTransaction,
 };
+#[cfg(feature = "state-pruning")]
+use state_pruning::StatePruningTask;
+#[cfg(feature = "state-pruning")]
+pub use state_pruning::StateRetention;
 
 pub const SERVICE_TYPE: &str = "scabbard";
 
@@ -105,7 +163,55 @@ pub struct Scabbard {
     purge_handler: Arc<dyn ScabbardStatePurgeHandler>,
     /// The coordinator timeout for the two-phase commit consensus engine
     coordinator_timeout: Duration,
+    /// The other settings for the two-phase commit consensus engine
+    consensus_settings: ConsensusSettings,
     consensus: Arc<Mutex<Option<ScabbardConsensusManager>>>,
+    /// The windows during which submitted batches are rejected and queued batches are not
+    /// proposed
+    #[cfg(feature = "quiet-hours")]
+    quiet_hours: Option<QuietHours>,
+    /// The policy that submitted batches must satisfy to be proposed
+    #[cfg(feature = "batch-validation")]
+    batch_validator: Option<Arc<dyn BatchValidator>>,
+    /// Where and how often the service's state root is published, if it is anchored
+    #[cfg(feature = "anchoring")]
+    anchoring: Option<AnchorConfig>,
+    #[cfg(feature = "anchoring")]
+    anchor_task: Arc<Mutex<Option<AnchorTask>>>,
+    /// How many of the service's receipts are kept, if they are pruned
+    #[cfg(feature = "receipt-pruning")]
+    receipt_retention: Option<ReceiptRetention>,
+    #[cfg(any(feature = "receipt-pruning", feature = "receipt-queries"))]
+    receipt_store: Arc<dyn ReceiptStore>,
+    /// Records the contract and commit time of the service's transactions, if receipts can be
+    /// queried by them
+    #[cfg(feature = "receipt-queries")]
+    receipt_index_store: Option<Arc<dyn ReceiptIndexStore>>,
+    /// The ID of the newest receipt that has been pruned
+    #[cfg(feature = "receipt-pruning")]
+    receipt_high_water_mark: Arc<Mutex<Option<String>>>,
+    #[cfg(feature = "receipt-pruning")]
+    receipt_pruning_task: Arc<Mutex<Option<ReceiptPruningTask>>>,
+    /// Where the service's snapshots are written and restored from, if snapshots are enabled
+    #[cfg(feature = "snapshot")]
+    snapshots: Option<SnapshotConfig>,
+    #[cfg(feature = "snapshot")]
+    snapshot_task: Arc<Mutex<Option<SnapshotTask>>>,
+    /// How many previous state roots the service keeps, if they are not pruned as soon as they
+    /// are replaced
+    #[cfg(feature = "state-pruning")]
+    state_retention: Option<StateRetention>,
+    #[cfg(feature = "state-pruning")]
+    state_pruning_task: Arc<Mutex<Option<StatePruningTask>>>,
+    /// The limits on the resources of the Sabre contracts the service executes
+    #[cfg(feature = "execution-limits")]
+    execution_limits: Option<ExecutionLimits>,
+    /// The number of transactions the service may execute at the same time
+    #[cfg(feature = "parallel-execution")]
+    execution_workers: usize,
+    /// Verifies the signatures of batches in parallel, if set
+    #[cfg(feature = "verification-pool")]
+    verification_pool: Option<PooledVerifier>,
 }
 
 impl Scabbard {
@@ -145,7 +251,7 @@ impl Scabbard {
             merkle_state,
             state_autocleanup_enabled,
             commit_hash_store,
-            receipt_store,
+            receipt_store.clone(),
             #[cfg(feature = "metrics")]
             service_id.clone(),
             #[cfg(feature = "metrics")]
@@ -165,10 +271,322 @@ impl Scabbard {
             state: Arc::new(Mutex::new(state)),
             purge_handler: purge_handler.into(),
             coordinator_timeout,
+            consensus_settings: ConsensusSettings::default(),
             consensus: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "quiet-hours")]
+            quiet_hours: None,
+            #[cfg(feature = "batch-validation")]
+            batch_validator: None,
+            #[cfg(feature = "anchoring")]
+            anchoring: None,
+            #[cfg(feature = "anchoring")]
+            anchor_task: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "receipt-pruning")]
+            receipt_retention: None,
+            #[cfg(any(feature = "receipt-pruning", feature = "receipt-queries"))]
+            receipt_store,
+            #[cfg(feature = "receipt-queries")]
+            receipt_index_store: None,
+            #[cfg(feature = "receipt-pruning")]
+            receipt_high_water_mark: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "receipt-pruning")]
+            receipt_pruning_task: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "snapshot")]
+            snapshots: None,
+            #[cfg(feature = "snapshot")]
+            snapshot_task: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "state-pruning")]
+            state_retention: None,
+            #[cfg(feature = "state-pruning")]
+            state_pruning_task: Arc::new(Mutex::new(None)),
+            #[cfg(feature = "execution-limits")]
+            execution_limits: None,
+            #[cfg(feature = "parallel-execution")]
+            execution_workers: 1,
+            #[cfg(feature = "verification-pool")]
+            verification_pool: None,
         })
     }
 
+    /// Use the given settings for the two-phase commit consensus engine, in addition to the
+    /// coordinator timeout.
+    pub fn with_consensus_settings(mut self, consensus_settings: ConsensusSettings) -> Self {
+        self.consensus_settings = consensus_settings;
+        self
+    }
+
+    /// Pause the service's traffic during the given quiet hours: submitted batches are rejected
+    /// and batches that are already queued are not proposed until the quiet hours end.
+    #[cfg(feature = "quiet-hours")]
+    pub fn with_quiet_hours(mut self, quiet_hours: QuietHours) -> Self {
+        self.quiet_hours = Some(quiet_hours);
+        self
+    }
+
+    /// Check each submitted batch with the given validator, and reject the batches it does not
+    /// accept before they are proposed.
+    #[cfg(feature = "batch-validation")]
+    pub fn with_batch_validator(mut self, batch_validator: Arc<dyn BatchValidator>) -> Self {
+        self.batch_validator = Some(batch_validator);
+        self
+    }
+
+    /// Periodically publish the service's state root while it is running, as configured.
+    #[cfg(feature = "anchoring")]
+    pub fn with_anchoring(mut self, anchoring: AnchorConfig) -> Self {
+        self.anchoring = Some(anchoring);
+        self
+    }
+
+    /// Periodically prune the service's oldest receipts while it is running, as configured.
+    #[cfg(feature = "receipt-pruning")]
+    pub fn with_receipt_retention(mut self, receipt_retention: ReceiptRetention) -> Self {
+        self.receipt_retention = Some(receipt_retention);
+        self
+    }
+
+    /// Returns the ID of the newest receipt that has been pruned since the service was created;
+    /// events up to and including this receipt are no longer available.
+    #[cfg(feature = "receipt-pruning")]
+    pub fn receipt_high_water_mark(&self) -> Result<Option<String>, ScabbardError> {
+        Ok(self
+            .receipt_high_water_mark
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .clone())
+    }
+
+    /// Record the contract and commit time of each transaction the service commits in the given
+    /// store, so that its receipts can be queried by them.
+    #[cfg(feature = "receipt-queries")]
+    pub fn with_receipt_index_store(
+        mut self,
+        receipt_index_store: Arc<dyn ReceiptIndexStore>,
+    ) -> Self {
+        self.state
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .set_receipt_index_store(receipt_index_store.clone());
+        self.receipt_index_store = Some(receipt_index_store);
+        self
+    }
+
+    /// Returns a page of the service's transaction receipts that match the query, with the
+    /// total number that match.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScabbardError::InvalidReceiptQuery` if the query refers to a receipt that does
+    /// not exist, or filters by contract or commit time when the service has no receipt index.
+    #[cfg(feature = "receipt-queries")]
+    pub fn query_receipts(
+        &self,
+        query: &ReceiptQuery,
+    ) -> Result<ReceiptQueryResult, ScabbardError> {
+        receipt_query::query_receipts(
+            &*self.receipt_store,
+            self.receipt_index_store.as_deref(),
+            query,
+        )
+    }
+
+    /// Write snapshots of the service's state and receipts as configured, and restore the
+    /// service from its newest snapshot when it starts without any receipts.
+    #[cfg(feature = "snapshot")]
+    pub fn with_snapshots(mut self, snapshots: SnapshotConfig) -> Self {
+        self.snapshots = Some(snapshots);
+        self
+    }
+
+    /// Writes a snapshot of the service's current state and receipts to the configured
+    /// directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScabbardError::SnapshotsDisabled` if snapshots are not configured for the
+    /// service.
+    #[cfg(feature = "snapshot")]
+    pub fn create_snapshot(&self) -> Result<SnapshotInfo, ScabbardError> {
+        let snapshots = self
+            .snapshots
+            .as_ref()
+            .ok_or(ScabbardError::SnapshotsDisabled)?;
+        let state = self.state.lock().map_err(|_| ScabbardError::LockPoisoned)?;
+
+        snapshot::write_snapshot(snapshots, &self.circuit_id, &self.service_id, &state)
+            .map_err(|err| ScabbardError::Internal(Box::new(err)))
+    }
+
+    /// Keep the service's previous state roots as configured, pruning them periodically while the
+    /// service is running.
+    #[cfg(feature = "state-pruning")]
+    pub fn with_state_retention(mut self, state_retention: StateRetention) -> Self {
+        self.state_retention = Some(state_retention);
+        self
+    }
+
+    /// Execute Sabre contracts within the given limits; contracts that exceed them are aborted and
+    /// their transactions are invalid.
+    #[cfg(feature = "execution-limits")]
+    pub fn with_execution_limits(mut self, execution_limits: ExecutionLimits) -> Self {
+        self.execution_limits = Some(execution_limits);
+        self
+    }
+
+    /// Execute up to the given number of transactions at the same time, on as many worker
+    /// threads. Only transactions whose declared inputs and outputs do not conflict are executed
+    /// together, and their results are committed in the order of their batch, so the resulting
+    /// state is the same as with serial execution.
+    #[cfg(feature = "parallel-execution")]
+    pub fn with_execution_workers(mut self, execution_workers: usize) -> Self {
+        self.execution_workers = execution_workers.max(1);
+        self
+    }
+
+    /// Verify the signatures of batches and their transactions on the given verification pool,
+    /// in parallel, rather than one at a time with the service's own verifier.
+    #[cfg(feature = "verification-pool")]
+    pub fn with_verification_pool(mut self, verification_pool: PooledVerifier) -> Self {
+        self.verification_pool = Some(verification_pool);
+        self
+    }
+
+    /// Prunes the service's previous state roots that are beyond its state retention policy, and
+    /// removes the state entries that are no longer referenced by any root.
+    #[cfg(feature = "state-pruning")]
+    pub fn prune_state(&self) -> Result<StatePruneInfo, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .prune_state()?)
+    }
+
+    /// Responds to a peer's request to sync its state with the entries of this service's state
+    /// that differ.
+    #[cfg(feature = "state-sync")]
+    fn handle_state_sync_request(
+        &self,
+        request: &StateSyncRequest,
+        sender: &str,
+    ) -> Result<(), ServiceError> {
+        let shared = self
+            .shared
+            .lock()
+            .map_err(|_| ServiceError::PoisonedLock("shared lock poisoned".into()))?;
+        if !shared.peer_services().contains(sender) {
+            warn!(
+                "Ignoring state sync request from non-peer service {}",
+                sender
+            );
+            return Ok(());
+        }
+
+        let response = state_sync::create_sync_response(
+            request,
+            &*self
+                .state
+                .lock()
+                .map_err(|_| ServiceError::PoisonedLock("state lock poisoned".into()))?,
+        )
+        .map_err(|err| ServiceError::UnableToHandleMessage(Box::new(err)))?;
+
+        let mut msg = ScabbardMessage::new();
+        msg.set_message_type(ScabbardMessage_Type::STATE_SYNC_RESPONSE);
+        msg.set_state_sync_response(response);
+
+        shared
+            .network_sender()
+            .ok_or(ServiceError::NotStarted)?
+            .send(sender, &msg.write_to_bytes()?)
+            .map_err(|err| ServiceError::UnableToSendMessage(Box::new(err)))
+    }
+
+    /// Applies a peer's response to this service's request to sync its state.
+    #[cfg(feature = "state-sync")]
+    fn handle_state_sync_response(
+        &self,
+        response: &StateSyncResponse,
+        sender: &str,
+    ) -> Result<(), ServiceError> {
+        let mut shared = self
+            .shared
+            .lock()
+            .map_err(|_| ServiceError::PoisonedLock("shared lock poisoned".into()))?;
+        if !shared.peer_services().contains(sender) {
+            warn!(
+                "Ignoring state sync response from non-peer service {}",
+                sender
+            );
+            return Ok(());
+        }
+
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| ServiceError::PoisonedLock("state lock poisoned".into()))?;
+        match state_sync::apply_sync_response(
+            response,
+            sender,
+            shared.pending_state_sync_mut(),
+            &mut state,
+        ) {
+            Ok(true) => info!(
+                "Synced scabbard service {}::{} to state root {} agreed by its peers",
+                self.circuit_id,
+                self.service_id,
+                state.current_state_root(),
+            ),
+            Ok(false) => (),
+            Err(err) => warn!("Unable to sync state from {}: {}", sender, err),
+        }
+
+        Ok(())
+    }
+
+    /// Returns an error with the time until the quiet hours end if they are in effect.
+    #[cfg(feature = "quiet-hours")]
+    fn check_quiet_hours(&self) -> Result<(), ScabbardError> {
+        match self.quiet_hours.as_ref().and_then(QuietHours::remaining) {
+            Some(remaining) => Err(ScabbardError::QuietHours(remaining)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns an error with the validator's reason if it rejects any of the given batches.
+    #[cfg(feature = "batch-validation")]
+    fn check_batch_policy(&self, batches: &[BatchPair]) -> Result<(), ScabbardError> {
+        let validator = match &self.batch_validator {
+            Some(validator) => validator,
+            None => return Ok(()),
+        };
+
+        for batch in batches {
+            let request = BatchValidationRequest::new(&self.circuit_id, &self.service_id, batch)
+                .map_err(|err| ScabbardError::BatchVerificationFailed(Box::new(err)))?;
+            match validator
+                .validate(&request)
+                .map_err(|err| ScabbardError::Internal(Box::new(err)))?
+            {
+                BatchVerdict::Accept => (),
+                BatchVerdict::Reject(reason) => {
+                    debug!(
+                        "Batch {} rejected by validator: {}",
+                        request.batch_id(),
+                        reason
+                    );
+                    return Err(ScabbardError::BatchRejected(format!(
+                        "batch {} was rejected: {}",
+                        request.batch_id(),
+                        reason
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Fetch the value at the given `address` in the scabbard service's state. Returns `None` if
     /// the `address` is not set.
     pub fn get_state_at_address(&self, address: &str) -> Result<Option<Vec<u8>>, ScabbardError> {
@@ -190,6 +608,21 @@ impl Scabbard {
             .get_state_with_prefix(prefix)?)
     }
 
+    /// Generate a Merkle proof of the value at the given `address` in the scabbard service's
+    /// state, at the given `state_root` or at the current state root if none is given. Returns
+    /// `None` if the state root is unknown.
+    pub fn get_state_proof(
+        &self,
+        address: &str,
+        state_root: Option<&str>,
+    ) -> Result<Option<StateProof>, ScabbardError> {
+        Ok(self
+            .state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .get_state_proof(address, state_root)?)
+    }
+
     /// Get the current state root hash of the scabbard service's state.
     pub fn get_current_state_root(&self) -> Result<String, ScabbardError> {
         Ok(self
@@ -214,6 +647,11 @@ impl Scabbard {
     }
 
     pub fn add_batches(&self, batches: Vec<BatchPair>) -> Result<Option<String>, ScabbardError> {
+        #[cfg(feature = "quiet-hours")]
+        self.check_quiet_hours()?;
+        #[cfg(feature = "batch-validation")]
+        self.check_batch_policy(&batches)?;
+
         let mut shared = self
             .shared
             .lock()
@@ -235,31 +673,7 @@ impl Scabbard {
                 write!(link, "{},", batch.batch().header_signature())
                     .map_err(|e| ScabbardError::Internal(Box::new(e)))?;
 
-                match self.version {
-                    ScabbardVersion::V1 => shared.add_batch_to_queue(batch)?,
-                    ScabbardVersion::V2 => {
-                        if shared.is_coordinator() {
-                            shared.add_batch_to_queue(batch)?;
-                        } else {
-                            let batch_bytes = batch
-                                .into_bytes()
-                                .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
-
-                            let mut msg = ScabbardMessage::new();
-                            msg.set_message_type(ScabbardMessage_Type::NEW_BATCH);
-                            msg.set_new_batch(batch_bytes);
-                            let msg_bytes = msg
-                                .write_to_bytes()
-                                .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
-
-                            shared
-                                .network_sender()
-                                .ok_or(ScabbardError::NotConnected)?
-                                .send(shared.coordinator_service_id(), msg_bytes.as_slice())
-                                .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
-                        }
-                    }
-                }
+                shared.submit_batch(batch)?;
             }
 
             // Remove trailing comma
@@ -272,6 +686,92 @@ impl Scabbard {
         }
     }
 
+    /// Add batches that will not be proposed until all of the given, previously submitted,
+    /// batches have been committed.
+    ///
+    /// The batches are held by this service until their dependencies are committed, and are
+    /// marked invalid if a dependency is not committed. Batches that are submitted together are
+    /// proposed in the order given.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ScabbardError::InvalidDependency` if a dependency is unknown to this service or
+    /// is invalid.
+    #[cfg(feature = "batch-dependencies")]
+    pub fn add_batches_with_dependencies(
+        &self,
+        batches: Vec<BatchPair>,
+        dependencies: &[String],
+    ) -> Result<Option<String>, ScabbardError> {
+        #[cfg(feature = "quiet-hours")]
+        self.check_quiet_hours()?;
+        #[cfg(feature = "batch-validation")]
+        self.check_batch_policy(&batches)?;
+
+        let mut shared = self
+            .shared
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?;
+
+        if !shared.verify_batches(&batches)? {
+            return Ok(None);
+        }
+
+        let mut state = self.state.lock().map_err(|_| ScabbardError::LockPoisoned)?;
+
+        let mut uncommitted = HashSet::new();
+        for dependency in dependencies {
+            match state.batch_history().get_batch_status(dependency) {
+                BatchStatus::Committed(_) => (),
+                BatchStatus::Pending | BatchStatus::Valid(_) => {
+                    uncommitted.insert(dependency.to_string());
+                }
+                BatchStatus::Invalid(_) => {
+                    return Err(ScabbardError::InvalidDependency(format!(
+                        "batch {} is invalid",
+                        dependency
+                    )))
+                }
+                BatchStatus::Unknown => {
+                    return Err(ScabbardError::InvalidDependency(format!(
+                        "batch {} is unknown",
+                        dependency
+                    )))
+                }
+            }
+        }
+
+        let mut link = format!(
+            "/scabbard/{}/{}/batch_statuses?ids=",
+            self.circuit_id, self.service_id
+        );
+
+        for batch in batches {
+            state
+                .batch_history()
+                .add_batch(batch.batch().header_signature());
+
+            write!(link, "{},", batch.batch().header_signature())
+                .map_err(|e| ScabbardError::Internal(Box::new(e)))?;
+
+            if uncommitted.is_empty() {
+                shared.submit_batch(batch)?;
+            } else {
+                debug!(
+                    "Holding batch {} until its dependencies are committed",
+                    batch.batch().header_signature()
+                );
+                shared.hold_batch(batch, uncommitted.clone());
+            }
+        }
+
+        // Remove trailing comma
+        link.pop();
+
+        debug!("Batch Status Link Created: {}", link);
+        Ok(Some(link))
+    }
+
     /// Get the `BatchInfo` for each specified batch.
     ///
     /// # Arguments
@@ -291,6 +791,23 @@ impl Scabbard {
         Ok(state.batch_history().get_batch_info(ids, wait)?)
     }
 
+    /// Sends the current status of each of the given batches to the subscriber, followed by each
+    /// of their status transitions until they are committed or invalid.
+    #[cfg(feature = "batch-status-stream")]
+    pub fn add_batch_status_subscriber(
+        &self,
+        ids: HashSet<String>,
+        subscriber: Box<dyn BatchStatusSubscriber>,
+    ) -> Result<(), ScabbardError> {
+        self.state
+            .lock()
+            .map_err(|_| ScabbardError::LockPoisoned)?
+            .batch_history()
+            .add_status_subscriber(ids, subscriber);
+
+        Ok(())
+    }
+
     pub fn get_events_since(&self, event_id: Option<String>) -> Result<Events, ScabbardError> {
         Ok(self
             .state
@@ -339,6 +856,63 @@ impl ServiceInstance for Scabbard {
             .map_err(|_| ServiceStartError::PoisonedLock("shared lock poisoned".into()))?
             .set_network_sender(service_registry.connect(self.service_id())?);
 
+        // The proposal manager checks the quiet hours before proposing a queued batch
+        #[cfg(feature = "quiet-hours")]
+        self.shared
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("shared lock poisoned".into()))?
+            .set_quiet_hours(self.quiet_hours.clone());
+
+        #[cfg(feature = "verification-pool")]
+        self.shared
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("shared lock poisoned".into()))?
+            .set_verification_pool(self.verification_pool.clone());
+
+        #[cfg(feature = "snapshot")]
+        if let Some(snapshots) = &self.snapshots {
+            let restored = snapshot::restore_latest_snapshot(
+                snapshots,
+                &self.circuit_id,
+                &self.service_id,
+                &mut *self
+                    .state
+                    .lock()
+                    .map_err(|_| ServiceStartError::PoisonedLock("state lock poisoned".into()))?,
+            )
+            .map_err(|err| {
+                ServiceStartError::Internal(format!("Unable to restore snapshot: {}", err))
+            })?;
+            if let Some(info) = restored {
+                info!(
+                    "Restored scabbard service {}::{} to state root {} from {}",
+                    self.circuit_id,
+                    self.service_id,
+                    info.state_root(),
+                    info.file()
+                );
+            }
+        }
+
+        // Previous state roots are retained from the first batch committed after starting
+        #[cfg(feature = "state-pruning")]
+        self.state
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("state lock poisoned".into()))?
+            .set_state_retention(self.state_retention.clone());
+
+        #[cfg(feature = "execution-limits")]
+        self.state
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("state lock poisoned".into()))?
+            .set_execution_limits(self.execution_limits.clone());
+
+        #[cfg(feature = "parallel-execution")]
+        self.state
+            .lock()
+            .map_err(|_| ServiceStartError::PoisonedLock("state lock poisoned".into()))?
+            .set_execution_workers(self.execution_workers);
+
         self.state
             .lock()
             .map_err(|_| ServiceStartError::PoisonedLock("shared lock poisoned".into()))?
@@ -353,12 +927,104 @@ impl ServiceInstance for Scabbard {
                 self.shared.clone(),
                 self.state.clone(),
                 self.coordinator_timeout,
+                self.consensus_settings,
             )
             .map_err(|err| {
                 ServiceStartError::Internal(format!("Unable to start consensus: {}", err))
             })?,
         );
 
+        #[cfg(feature = "anchoring")]
+        if let Some(anchoring) = &self.anchoring {
+            let task = AnchorTask::start(
+                anchoring.clone(),
+                self.circuit_id.clone(),
+                self.service_id.clone(),
+                self.state.clone(),
+            )
+            .map_err(|err| {
+                ServiceStartError::Internal(format!("Unable to start anchoring: {}", err))
+            })?;
+            self.anchor_task
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("anchor lock poisoned".into()))?
+                .replace(task);
+        }
+
+        #[cfg(feature = "receipt-pruning")]
+        if let Some(receipt_retention) = &self.receipt_retention {
+            let task = ReceiptPruningTask::start(
+                receipt_retention.clone(),
+                self.circuit_id.clone(),
+                self.service_id.clone(),
+                self.receipt_store.clone(),
+                self.receipt_high_water_mark.clone(),
+            )
+            .map_err(|err| {
+                ServiceStartError::Internal(format!("Unable to start receipt pruning: {}", err))
+            })?;
+            self.receipt_pruning_task
+                .lock()
+                .map_err(|_| {
+                    ServiceStartError::PoisonedLock("receipt pruning lock poisoned".into())
+                })?
+                .replace(task);
+        }
+
+        #[cfg(feature = "snapshot")]
+        if let Some(snapshots) = &self.snapshots {
+            let task = SnapshotTask::start(
+                snapshots.clone(),
+                self.circuit_id.clone(),
+                self.service_id.clone(),
+                self.state.clone(),
+            )
+            .map_err(|err| {
+                ServiceStartError::Internal(format!("Unable to start snapshots: {}", err))
+            })?;
+            *self
+                .snapshot_task
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("snapshot lock poisoned".into()))? =
+                task;
+        }
+
+        #[cfg(feature = "state-pruning")]
+        if let Some(state_retention) = &self.state_retention {
+            let task = StatePruningTask::start(
+                state_retention,
+                self.circuit_id.clone(),
+                self.service_id.clone(),
+                self.state.clone(),
+            )
+            .map_err(|err| {
+                ServiceStartError::Internal(format!("Unable to start state pruning: {}", err))
+            })?;
+            self.state_pruning_task
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("state pruning lock poisoned".into()))?
+                .replace(task);
+        }
+
+        // A replica that fell behind while it was stopped catches up with its peers
+        #[cfg(feature = "state-sync")]
+        {
+            let mut shared = self
+                .shared
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("shared lock poisoned".into()))?;
+            let state = self
+                .state
+                .lock()
+                .map_err(|_| ServiceStartError::PoisonedLock("state lock poisoned".into()))?;
+            if let Err(err) = state_sync::request_state_sync(&mut shared, &state) {
+                warn!(
+                    "Unable to request state sync for scabbard service {}::{}: {}",
+                    self.circuit_id, self.service_id, err
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -368,6 +1034,50 @@ impl ServiceInstance for Scabbard {
     ) -> Result<(), ServiceStopError> {
         debug!("Stopping scabbard service with id {}", self.service_id);
 
+        #[cfg(feature = "anchoring")]
+        if let Some(task) = self
+            .anchor_task
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("anchor lock poisoned".into()))?
+            .take()
+        {
+            task.shutdown()
+                .map_err(|err| ServiceStopError::Internal(Box::new(err)))?;
+        }
+
+        #[cfg(feature = "receipt-pruning")]
+        if let Some(task) = self
+            .receipt_pruning_task
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("receipt pruning lock poisoned".into()))?
+            .take()
+        {
+            task.shutdown()
+                .map_err(|err| ServiceStopError::Internal(Box::new(err)))?;
+        }
+
+        #[cfg(feature = "snapshot")]
+        if let Some(task) = self
+            .snapshot_task
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("snapshot lock poisoned".into()))?
+            .take()
+        {
+            task.shutdown()
+                .map_err(|err| ServiceStopError::Internal(Box::new(err)))?;
+        }
+
+        #[cfg(feature = "state-pruning")]
+        if let Some(task) = self
+            .state_pruning_task
+            .lock()
+            .map_err(|_| ServiceStopError::PoisonedLock("state pruning lock poisoned".into()))?
+            .take()
+        {
+            task.shutdown()
+                .map_err(|err| ServiceStopError::Internal(Box::new(err)))?;
+        }
+
         // Shutdown consensus
         self.consensus
             .lock()
@@ -392,6 +1102,16 @@ impl ServiceInstance for Scabbard {
 
         state.stop_executor();
 
+        // The retained roots are only tracked in memory, so they are pruned before the service
+        // stops rather than left in its state
+        #[cfg(feature = "state-pruning")]
+        if let Err(err) = state.prune_retained_roots() {
+            warn!(
+                "Unable to prune retained state roots of scabbard service {}::{}: {}",
+                self.circuit_id, self.service_id, err
+            );
+        }
+
         service_registry.disconnect(self.service_id())?;
 
         Ok(())
@@ -411,13 +1131,23 @@ impl ServiceInstance for Scabbard {
     }
 
     fn purge(&mut self) -> Result<(), splinter::error::InternalError> {
+        // The receipts and commit hash are removed first, as the commit hash may be stored with
+        // the state that is removed by the purge handler
+        self.state
+            .lock()
+            .map_err(|_| {
+                splinter::error::InternalError::with_message("state lock poisoned".into())
+            })?
+            .purge_records()
+            .map_err(|err| splinter::error::InternalError::from_source(Box::new(err)))?;
+
         self.purge_handler.purge_state()
     }
 
     fn handle_message(
         &self,
         message_bytes: &[u8],
-        _message_context: &ServiceMessageContext,
+        message_context: &ServiceMessageContext,
     ) -> Result<(), ServiceError> {
         let message: ScabbardMessage = Message::parse_from_bytes(message_bytes)?;
 
@@ -515,6 +1245,25 @@ impl ServiceInstance for Scabbard {
                 }
                 Ok(())
             }
+            #[cfg(feature = "state-sync")]
+            ScabbardMessage_Type::STATE_SYNC_REQUEST => self.handle_state_sync_request(
+                message.get_state_sync_request(),
+                &message_context.sender,
+            ),
+            #[cfg(feature = "state-sync")]
+            ScabbardMessage_Type::STATE_SYNC_RESPONSE => self.handle_state_sync_response(
+                message.get_state_sync_response(),
+                &message_context.sender,
+            ),
+            #[cfg(not(feature = "state-sync"))]
+            ScabbardMessage_Type::STATE_SYNC_REQUEST
+            | ScabbardMessage_Type::STATE_SYNC_RESPONSE => {
+                warn!(
+                    "Ignoring state sync message from {}; state sync is not enabled",
+                    message_context.sender
+                );
+                Ok(())
+            }
             _ => Err(ServiceError::InvalidMessageFormat(Box::new(
                 ScabbardError::MessageTypeUnset,
             ))),
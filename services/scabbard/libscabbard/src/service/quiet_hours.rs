@@ -0,0 +1,285 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Quiet hours: recurring time windows during which a scabbard service pauses its traffic.
+//!
+//! A schedule is set with the `quiet_hours` service argument, which every scabbard service on
+//! the circuit should be given, so that all members agree on it when the circuit is proposed.
+//! While a window is open, the service rejects submitted batches and does not propose the batches
+//! it has queued; the queued batches are proposed once the window closes.
+//!
+//! A schedule is a `;`-separated list of windows, each formatted as `[<days> ]<start>-<end>`.
+//! Times are `HH:MM` in UTC, and a window whose end is not after its start continues into the
+//! next day. Days are a `,`-separated list of days (`Mon`, `Tue`, ...) or day ranges (`Mon-Fri`);
+//! a window without days applies every day. For example, `Mon-Fri 18:00-08:00; Sat,Sun
+//! 00:00-24:00` only allows traffic during business hours on weekdays.
+
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SECONDS_PER_MINUTE: u64 = 60;
+const SECONDS_PER_DAY: u64 = 24 * 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_WEEK: u64 = 7 * SECONDS_PER_DAY;
+/// The UNIX epoch was a Thursday; this is the number of seconds since the Monday before it.
+const EPOCH_WEEK_OFFSET: u64 = 3 * SECONDS_PER_DAY;
+
+const DAYS: [(&str, &str); 7] = [
+    ("mon", "monday"),
+    ("tue", "tuesday"),
+    ("wed", "wednesday"),
+    ("thu", "thursday"),
+    ("fri", "friday"),
+    ("sat", "saturday"),
+    ("sun", "sunday"),
+];
+
+/// A weekly schedule of windows during which a scabbard service's traffic is paused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuietHours {
+    /// The windows as `[start, end)` ranges of seconds since Monday 00:00 UTC; no range crosses
+    /// the end of the week.
+    ranges: Vec<(u64, u64)>,
+}
+
+impl QuietHours {
+    /// Returns how long the quiet hours will continue if the given time is within a window, or
+    /// `None` if traffic is allowed at that time. Adjoining and overlapping windows are treated as
+    /// a single window.
+    pub fn remaining_at(&self, time: SystemTime) -> Option<Duration> {
+        let since_epoch = time.duration_since(UNIX_EPOCH).ok()?.as_secs();
+        let mut now = (since_epoch + EPOCH_WEEK_OFFSET) % SECONDS_PER_WEEK;
+        let mut remaining = 0;
+
+        // Each iteration moves to the end of a window; a schedule that covers the whole week is
+        // quiet for at most a week at a time.
+        while remaining < SECONDS_PER_WEEK {
+            match self
+                .ranges
+                .iter()
+                .find(|(start, end)| *start <= now && now < *end)
+            {
+                Some((_, end)) => {
+                    remaining += end - now;
+                    now = end % SECONDS_PER_WEEK;
+                }
+                None => break,
+            }
+        }
+
+        if remaining == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(remaining.min(SECONDS_PER_WEEK)))
+        }
+    }
+
+    /// Returns how long the quiet hours will continue, or `None` if traffic is currently allowed.
+    pub fn remaining(&self) -> Option<Duration> {
+        self.remaining_at(SystemTime::now())
+    }
+}
+
+impl FromStr for QuietHours {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ranges = vec![];
+
+        for window in s.split(';').map(str::trim).filter(|w| !w.is_empty()) {
+            let (days, times) = match window.rsplit_once(char::is_whitespace) {
+                Some((days, times)) => (parse_days(days.trim())?, times),
+                None => ([true; 7], window),
+            };
+
+            let (start, end) = times
+                .split_once('-')
+                .ok_or_else(|| format!("window {:?} is not formatted as <start>-<end>", window))?;
+            let start = parse_time(start)?;
+            let end = parse_time(end)?;
+            if start == SECONDS_PER_DAY {
+                return Err(format!("window {:?} cannot start at 24:00", window));
+            }
+            if start == end {
+                return Err(format!("window {:?} is empty", window));
+            }
+            let duration = if end > start {
+                end - start
+            } else {
+                end + SECONDS_PER_DAY - start
+            };
+
+            for (day, _) in days.iter().enumerate().filter(|(_, enabled)| **enabled) {
+                let window_start = day as u64 * SECONDS_PER_DAY + start;
+                let window_end = window_start + duration;
+                if window_end > SECONDS_PER_WEEK {
+                    ranges.push((window_start, SECONDS_PER_WEEK));
+                    ranges.push((0, window_end - SECONDS_PER_WEEK));
+                } else {
+                    ranges.push((window_start, window_end));
+                }
+            }
+        }
+
+        if ranges.is_empty() {
+            return Err("must contain at least one window".into());
+        }
+
+        Ok(QuietHours { ranges })
+    }
+}
+
+/// Parses a `,`-separated list of days and day ranges into the enabled days, starting on Monday.
+fn parse_days(days: &str) -> Result<[bool; 7], String> {
+    let mut enabled = [false; 7];
+
+    for entry in days.split(',').map(str::trim) {
+        let (first, last) = match entry.split_once('-') {
+            Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+            None => {
+                let day = parse_day(entry)?;
+                (day, day)
+            }
+        };
+
+        // A range such as Fri-Mon wraps around the end of the week
+        let mut day = first;
+        loop {
+            enabled[day] = true;
+            if day == last {
+                break;
+            }
+            day = (day + 1) % 7;
+        }
+    }
+
+    Ok(enabled)
+}
+
+fn parse_day(day: &str) -> Result<usize, String> {
+    let lower = day.trim().to_lowercase();
+    DAYS.iter()
+        .position(|(short, long)| lower == *short || lower == *long)
+        .ok_or_else(|| format!("{:?} is not a day of the week", day))
+}
+
+/// Parses an `HH:MM` time into the number of seconds since midnight; `24:00` is allowed.
+fn parse_time(time: &str) -> Result<u64, String> {
+    let invalid = || format!("{:?} is not a time formatted as HH:MM", time);
+
+    let (hours, minutes) = time.trim().split_once(':').ok_or_else(invalid)?;
+    let hours = hours.parse::<u64>().map_err(|_| invalid())?;
+    let minutes = minutes.parse::<u64>().map_err(|_| invalid())?;
+    if minutes >= 60 || hours > 24 || (hours == 24 && minutes > 0) {
+        return Err(invalid());
+    }
+
+    Ok((hours * 60 + minutes) * SECONDS_PER_MINUTE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1970-01-05 00:00 UTC, a Monday
+    const MONDAY: u64 = 4 * SECONDS_PER_DAY;
+
+    fn at(day: u64, hours: u64, minutes: u64) -> SystemTime {
+        UNIX_EPOCH
+            + Duration::from_secs(MONDAY + day * SECONDS_PER_DAY + (hours * 60 + minutes) * 60)
+    }
+
+    /// Verify that valid schedules are parsed and invalid schedules are rejected.
+    #[test]
+    fn parse_quiet_hours() {
+        for valid in &[
+            "22:00-06:00",
+            "Mon-Fri 18:00-08:00; Sat,Sun 00:00-24:00",
+            "Friday-Monday 12:30-13:30;",
+            "sat 00:00-01:00",
+        ] {
+            assert!(
+                valid.parse::<QuietHours>().is_ok(),
+                "{} was rejected",
+                valid
+            );
+        }
+
+        for invalid in &[
+            "",
+            ";",
+            "22:00",
+            "10:00-10:00",
+            "24:00-01:00",
+            "25:00-06:00",
+            "22:60-06:00",
+            "Someday 22:00-06:00",
+            "Mon- 22:00-06:00",
+        ] {
+            assert!(
+                invalid.parse::<QuietHours>().is_err(),
+                "{} was accepted",
+                invalid
+            );
+        }
+    }
+
+    /// Verify that the remaining quiet time is reported within a window, including a window that
+    /// continues into the next day, and that no time is reported outside of a window.
+    #[test]
+    fn remaining_in_window() {
+        let quiet_hours = "Mon-Fri 18:00-08:00"
+            .parse::<QuietHours>()
+            .expect("Failed to parse quiet hours");
+
+        assert_eq!(quiet_hours.remaining_at(at(0, 12, 0)), None);
+        assert_eq!(quiet_hours.remaining_at(at(0, 8, 0)), None);
+        assert_eq!(
+            quiet_hours.remaining_at(at(0, 18, 0)),
+            Some(Duration::from_secs(14 * 60 * 60))
+        );
+        assert_eq!(
+            quiet_hours.remaining_at(at(1, 7, 30)),
+            Some(Duration::from_secs(30 * 60))
+        );
+        // Friday's window ends on Saturday morning; Sunday is not quiet
+        assert_eq!(
+            quiet_hours.remaining_at(at(5, 7, 0)),
+            Some(Duration::from_secs(60 * 60))
+        );
+        assert_eq!(quiet_hours.remaining_at(at(6, 7, 0)), None);
+        // Monday morning is not covered by the previous Sunday
+        assert_eq!(quiet_hours.remaining_at(at(7, 7, 0)), None);
+    }
+
+    /// Verify that adjoining windows, including across the end of the week, are treated as a
+    /// single window, and that a schedule covering the whole week terminates.
+    #[test]
+    fn remaining_across_windows() {
+        let quiet_hours = "Fri 18:00-24:00; Sat,Sun 00:00-24:00; Mon 00:00-08:00"
+            .parse::<QuietHours>()
+            .expect("Failed to parse quiet hours");
+        assert_eq!(
+            quiet_hours.remaining_at(at(4, 20, 0)),
+            Some(Duration::from_secs((4 + 48 + 8) * 60 * 60))
+        );
+
+        let always = "00:00-24:00"
+            .parse::<QuietHours>()
+            .expect("Failed to parse quiet hours");
+        assert_eq!(
+            always.remaining_at(at(2, 0, 0)),
+            Some(Duration::from_secs(SECONDS_PER_WEEK))
+        );
+    }
+}
@@ -0,0 +1,407 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Receipt pruning: bounding the number of transaction receipts a scabbard service keeps.
+//!
+//! A scabbard service stores a receipt for every transaction it commits, so a long-running
+//! service accumulates receipts in its database without bound. With a [`ReceiptRetention`]
+//! policy, the service periodically removes its oldest receipts beyond the number to retain.
+//!
+//! Before receipts are removed they are passed to the policy's [`ReceiptArchiver`], if it has
+//! one. [`FileReceiptArchiver`] appends them to a file; other destinations, such as object
+//! storage, are supported by implementing the trait. Receipts are only removed after they have
+//! been archived, so a failed archive leaves them in place until the next interval. An archiver
+//! may be given the same receipt more than once if removing it fails.
+//!
+//! The ID of the newest receipt that has been removed is the service's high-water mark: the
+//! events up to and including it are no longer available from the service.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use sawtooth::receipt::store::ReceiptStore;
+use serde::Serialize;
+use splinter::error::{InternalError, InvalidArgumentError};
+use transact::protocol::receipt::TransactionReceipt;
+use transact::protos::IntoBytes;
+
+use crate::hex::to_hex;
+
+const DEFAULT_PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+const PRUNE_BATCH_SIZE: u64 = 1000;
+
+/// Archives transaction receipts before they are removed from a scabbard service's receipt
+/// store
+pub trait ReceiptArchiver: Send + Sync {
+    /// Archives the given receipts of a service, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if the receipts could not be archived; they are not removed
+    /// from the service in that case.
+    fn archive(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        receipts: &[TransactionReceipt],
+    ) -> Result<(), InternalError>;
+}
+
+/// Archives receipts by appending them to a file per service in a directory.
+///
+/// The file of a service is named `<circuit_id>-<service_id>.receipts`. Each line of the file is
+/// a JSON object with the `id` of a receipt and the hex-encoded `receipt` itself.
+pub struct FileReceiptArchiver {
+    dir: PathBuf,
+}
+
+impl FileReceiptArchiver {
+    /// Creates an archiver that writes to the given directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the directory does not exist.
+    pub fn new(dir: &str) -> Result<Self, InvalidArgumentError> {
+        let dir = PathBuf::from(dir);
+        if !dir.is_dir() {
+            return Err(InvalidArgumentError::new(
+                "dir",
+                format!(
+                    "receipt archive directory does not exist: {}",
+                    dir.display()
+                ),
+            ));
+        }
+
+        Ok(Self { dir })
+    }
+}
+
+#[derive(Serialize)]
+struct ArchivedReceipt<'a> {
+    id: &'a str,
+    receipt: String,
+}
+
+impl ReceiptArchiver for FileReceiptArchiver {
+    fn archive(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        receipts: &[TransactionReceipt],
+    ) -> Result<(), InternalError> {
+        let path = self
+            .dir
+            .join(format!("{}-{}.receipts", circuit_id, service_id));
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let mut writer = BufWriter::new(file);
+        for receipt in receipts {
+            let line = serde_json::to_string(&ArchivedReceipt {
+                id: &receipt.transaction_id,
+                receipt: to_hex(
+                    &receipt
+                        .clone()
+                        .into_bytes()
+                        .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                ),
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            writeln!(writer, "{}", line)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        }
+
+        // The receipts are removed once this returns, so they must be on disk
+        writer
+            .into_inner()
+            .map_err(|err| InternalError::from_source(Box::new(err.into_error())))
+            .and_then(|file: File| {
+                file.sync_data()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))
+            })
+    }
+}
+
+/// How many receipts a scabbard service keeps, how often older ones are removed, and where they
+/// are archived
+#[derive(Clone)]
+pub struct ReceiptRetention {
+    retain: u64,
+    interval: Duration,
+    archiver: Option<Arc<dyn ReceiptArchiver>>,
+}
+
+impl ReceiptRetention {
+    /// Creates a policy that keeps the given number of the newest receipts, removing older ones
+    /// every ten minutes without archiving them.
+    pub fn new(retain: u64) -> Self {
+        Self {
+            retain,
+            interval: DEFAULT_PRUNE_INTERVAL,
+            archiver: None,
+        }
+    }
+
+    /// Sets how often receipts are pruned.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Archives receipts with the given archiver before they are removed.
+    pub fn with_archiver(mut self, archiver: Arc<dyn ReceiptArchiver>) -> Self {
+        self.archiver = Some(archiver);
+        self
+    }
+}
+
+/// A background thread that prunes a scabbard service's receipts on an interval
+pub(crate) struct ReceiptPruningTask {
+    shutdown: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl ReceiptPruningTask {
+    /// Starts pruning the receipts of the given service, recording the ID of the newest receipt
+    /// removed in `high_water_mark`; receipts are first pruned one interval after the task is
+    /// started.
+    pub fn start(
+        retention: ReceiptRetention,
+        circuit_id: String,
+        service_id: String,
+        receipt_store: Arc<dyn ReceiptStore>,
+        high_water_mark: Arc<Mutex<Option<String>>>,
+    ) -> Result<Self, InternalError> {
+        let (shutdown, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name(format!(
+                "ScabbardReceiptPruning-{}::{}",
+                circuit_id, service_id
+            ))
+            .spawn(move || loop {
+                match receiver.recv_timeout(retention.interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                match prune_receipts(&retention, &circuit_id, &service_id, &*receipt_store) {
+                    Ok(Some(id)) => {
+                        debug!(
+                            "Pruned receipts of scabbard service {}::{} up to {}",
+                            circuit_id, service_id, id
+                        );
+                        match high_water_mark.lock() {
+                            Ok(mut high_water_mark) => *high_water_mark = Some(id),
+                            Err(_) => {
+                                error!(
+                                    "Unable to prune receipts of scabbard service {}::{}: \
+                                     high-water mark lock poisoned",
+                                    circuit_id, service_id
+                                );
+                                break;
+                            }
+                        }
+                    }
+                    Ok(None) => (),
+                    Err(err) => warn!(
+                        "Unable to prune receipts of scabbard service {}::{}: {}",
+                        circuit_id, service_id, err
+                    ),
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            shutdown,
+            join_handle,
+        })
+    }
+
+    /// Stops pruning and waits for the thread to exit.
+    pub fn shutdown(self) -> Result<(), InternalError> {
+        // An error means the thread has already stopped
+        let _ = self.shutdown.send(());
+        self.join_handle
+            .join()
+            .map_err(|_| InternalError::with_message("receipt pruning thread panicked".into()))
+    }
+}
+
+/// Removes the oldest receipts beyond the number to retain, archiving each batch before it is
+/// removed. Returns the ID of the newest receipt removed, if any were.
+fn prune_receipts(
+    retention: &ReceiptRetention,
+    circuit_id: &str,
+    service_id: &str,
+    receipt_store: &dyn ReceiptStore,
+) -> Result<Option<String>, InternalError> {
+    let count = receipt_store
+        .count_txn_receipts()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    let mut excess = count.saturating_sub(retention.retain);
+    let mut high_water_mark = None;
+    while excess > 0 {
+        let batch = receipt_store
+            .list_receipts_since(None)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .take(excess.min(PRUNE_BATCH_SIZE) as usize)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        if batch.is_empty() {
+            break;
+        }
+
+        if let Some(archiver) = &retention.archiver {
+            archiver.archive(circuit_id, service_id, &batch)?;
+        }
+
+        for receipt in &batch {
+            receipt_store
+                .remove_txn_receipt_by_id(receipt.transaction_id.clone())
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            high_water_mark = Some(receipt.transaction_id.clone());
+        }
+
+        excess = excess.saturating_sub(batch.len() as u64);
+    }
+
+    Ok(high_water_mark)
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use sawtooth::migrations::run_sqlite_migrations;
+    use sawtooth::receipt::store::diesel::DieselReceiptStore;
+    use transact::protocol::receipt::TransactionResult;
+
+    struct CollectingArchiver(Mutex<Vec<String>>);
+
+    impl ReceiptArchiver for CollectingArchiver {
+        fn archive(
+            &self,
+            _circuit_id: &str,
+            _service_id: &str,
+            receipts: &[TransactionReceipt],
+        ) -> Result<(), InternalError> {
+            self.0.lock().expect("archiver lock poisoned").extend(
+                receipts
+                    .iter()
+                    .map(|receipt| receipt.transaction_id.clone()),
+            );
+            Ok(())
+        }
+    }
+
+    struct FailingArchiver;
+
+    impl ReceiptArchiver for FailingArchiver {
+        fn archive(
+            &self,
+            _circuit_id: &str,
+            _service_id: &str,
+            _receipts: &[TransactionReceipt],
+        ) -> Result<(), InternalError> {
+            Err(InternalError::with_message("archive unavailable".into()))
+        }
+    }
+
+    /// Verify that the oldest receipts beyond the number to retain are archived and removed, that
+    /// the newest removed receipt is returned as the high-water mark, and that nothing is removed
+    /// if archiving fails.
+    #[test]
+    fn prune_receipts_with_archiver() {
+        let receipt_store = create_receipt_store();
+        receipt_store
+            .add_txn_receipts(
+                ["ab", "cd", "ef", "01", "23"]
+                    .iter()
+                    .map(|id| mock_transaction_receipt(id))
+                    .collect(),
+            )
+            .expect("failed to add receipts to store");
+
+        let retention = ReceiptRetention::new(4).with_archiver(Arc::new(FailingArchiver));
+        assert!(prune_receipts(&retention, "abcde-01234", "a000", &receipt_store).is_err());
+        assert_eq!(
+            receipt_store.count_txn_receipts().expect("failed to count"),
+            5
+        );
+
+        let archiver = Arc::new(CollectingArchiver(Mutex::new(vec![])));
+        let retention = ReceiptRetention::new(2).with_archiver(archiver.clone());
+        let high_water_mark = prune_receipts(&retention, "abcde-01234", "a000", &receipt_store)
+            .expect("failed to prune receipts");
+
+        assert_eq!(high_water_mark, Some("ef".to_string()));
+        assert_eq!(
+            *archiver.0.lock().expect("archiver lock poisoned"),
+            vec!["ab".to_string(), "cd".to_string(), "ef".to_string()]
+        );
+        let remaining = receipt_store
+            .list_receipts_since(None)
+            .expect("failed to list receipts")
+            .map(|receipt| receipt.map(|receipt| receipt.transaction_id))
+            .collect::<Result<Vec<_>, _>>()
+            .expect("failed to list receipts");
+        assert_eq!(remaining, vec!["01".to_string(), "23".to_string()]);
+
+        // Nothing is left to prune
+        assert_eq!(
+            prune_receipts(&retention, "abcde-01234", "a000", &receipt_store)
+                .expect("failed to prune receipts"),
+            None
+        );
+    }
+
+    fn create_receipt_store() -> DieselReceiptStore<SqliteConnection> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        DieselReceiptStore::new(pool, Some("abcde-01234::a000".into()))
+    }
+
+    fn mock_transaction_receipt(id: &str) -> TransactionReceipt {
+        TransactionReceipt {
+            transaction_id: id.into(),
+            transaction_result: TransactionResult::Valid {
+                state_changes: vec![],
+                events: vec![],
+                data: vec![],
+            },
+        }
+    }
+}
@@ -0,0 +1,454 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Receipt queries: paging through a scabbard service's transaction receipts with filters.
+//!
+//! Receipts are returned in the order they were committed. A [`ReceiptQuery`] can restrict them
+//! to a range of transaction IDs, to the transactions that executed a contract, and to a window
+//! of commit times, and returns one page of the matching receipts with the total number that
+//! match.
+//!
+//! The receipt store does not record which contract a transaction executed or when it was
+//! committed, so the service records these in its [`ReceiptIndexStore`] as it commits. Receipts
+//! committed before the index was added have no contract or commit time, and never match a query
+//! that filters by either.
+
+use std::time::SystemTime;
+
+use sawtooth::receipt::store::ReceiptStore;
+use transact::protocol::receipt::TransactionReceipt;
+
+use crate::store::ReceiptIndexStore;
+
+use super::ScabbardError;
+
+/// The number of receipts whose index entries are looked up at a time
+const INDEX_LOOKUP_BATCH_SIZE: usize = 100;
+
+/// A query for a page of a scabbard service's transaction receipts
+#[derive(Clone, Debug, Default)]
+pub struct ReceiptQuery {
+    after: Option<String>,
+    until: Option<String>,
+    contract_name: Option<String>,
+    committed_from: Option<SystemTime>,
+    committed_before: Option<SystemTime>,
+    offset: usize,
+    limit: Option<usize>,
+}
+
+impl ReceiptQuery {
+    /// Creates a query for every receipt
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only return the receipts committed after the receipt with the given transaction ID.
+    pub fn with_after(mut self, transaction_id: String) -> Self {
+        self.after = Some(transaction_id);
+        self
+    }
+
+    /// Only return the receipts committed up to and including the receipt with the given
+    /// transaction ID.
+    pub fn with_until(mut self, transaction_id: String) -> Self {
+        self.until = Some(transaction_id);
+        self
+    }
+
+    /// Only return the receipts of transactions that executed the given contract. Transactions
+    /// that do not execute a Sabre contract are matched by their family name.
+    pub fn with_contract_name(mut self, contract_name: String) -> Self {
+        self.contract_name = Some(contract_name);
+        self
+    }
+
+    /// Only return the receipts committed at or after the given time.
+    pub fn with_committed_from(mut self, time: SystemTime) -> Self {
+        self.committed_from = Some(time);
+        self
+    }
+
+    /// Only return the receipts committed before the given time.
+    pub fn with_committed_before(mut self, time: SystemTime) -> Self {
+        self.committed_before = Some(time);
+        self
+    }
+
+    /// Skip the given number of matching receipts.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Return at most the given number of receipts; by default every matching receipt is
+    /// returned.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns whether the query filters receipts by their index entries.
+    fn uses_index(&self) -> bool {
+        self.contract_name.is_some()
+            || self.committed_from.is_some()
+            || self.committed_before.is_some()
+    }
+
+    /// Returns whether a receipt with the given contract and commit time matches the query's
+    /// filters.
+    fn matches(&self, contract_name: Option<&str>, committed_at: Option<SystemTime>) -> bool {
+        if let Some(expected) = &self.contract_name {
+            if contract_name != Some(expected.as_str()) {
+                return false;
+            }
+        }
+        if let Some(from) = self.committed_from {
+            if !committed_at.map(|time| time >= from).unwrap_or(false) {
+                return false;
+            }
+        }
+        if let Some(before) = self.committed_before {
+            if !committed_at.map(|time| time < before).unwrap_or(false) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// A transaction receipt with the details recorded in the service's receipt index
+#[derive(Clone, Debug)]
+pub struct QueriedReceipt {
+    receipt: TransactionReceipt,
+    contract_name: Option<String>,
+    committed_at: Option<SystemTime>,
+}
+
+impl QueriedReceipt {
+    /// Returns the transaction receipt
+    pub fn receipt(&self) -> &TransactionReceipt {
+        &self.receipt
+    }
+
+    /// Returns the contract the transaction executed, if it is known
+    pub fn contract_name(&self) -> Option<&str> {
+        self.contract_name.as_deref()
+    }
+
+    /// Returns the time the transaction was committed, if it is known
+    pub fn committed_at(&self) -> Option<SystemTime> {
+        self.committed_at
+    }
+
+    /// Returns the receipt, discarding the indexed details
+    pub fn into_receipt(self) -> TransactionReceipt {
+        self.receipt
+    }
+}
+
+/// A page of the receipts that match a [`ReceiptQuery`]
+#[derive(Clone, Debug)]
+pub struct ReceiptQueryResult {
+    receipts: Vec<QueriedReceipt>,
+    total: u64,
+}
+
+impl ReceiptQueryResult {
+    /// Returns the receipts in the page, in the order they were committed
+    pub fn receipts(&self) -> &[QueriedReceipt] {
+        &self.receipts
+    }
+
+    /// Returns the total number of receipts that match the query, ignoring its offset and limit
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Returns the receipts in the page
+    pub fn into_receipts(self) -> Vec<QueriedReceipt> {
+        self.receipts
+    }
+}
+
+/// Runs the query against the given stores.
+///
+/// # Errors
+///
+/// Returns `ScabbardError::InvalidReceiptQuery` if the query's `after` or `until` receipt does
+/// not exist, or if the query filters by contract or commit time and there is no index store.
+pub(super) fn query_receipts(
+    receipt_store: &dyn ReceiptStore,
+    index_store: Option<&dyn ReceiptIndexStore>,
+    query: &ReceiptQuery,
+) -> Result<ReceiptQueryResult, ScabbardError> {
+    for id in query.after.iter().chain(query.until.iter()) {
+        let receipt = receipt_store
+            .get_txn_receipt_by_id(id.clone())
+            .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
+        if receipt.is_none() {
+            return Err(ScabbardError::InvalidReceiptQuery(format!(
+                "no receipt exists for transaction {}",
+                id
+            )));
+        }
+    }
+
+    if query.uses_index() && index_store.is_none() {
+        return Err(ScabbardError::InvalidReceiptQuery(
+            "receipts cannot be filtered by contract or commit time without a receipt index".into(),
+        ));
+    }
+
+    let mut page = Page {
+        query,
+        index_store,
+        receipts: vec![],
+        total: 0,
+    };
+    let mut reached_until = query.until.is_none();
+    let mut batch = Vec::with_capacity(INDEX_LOOKUP_BATCH_SIZE);
+    for receipt in receipt_store
+        .list_receipts_since(query.after.clone())
+        .map_err(|err| ScabbardError::Internal(Box::new(err)))?
+    {
+        let receipt = receipt.map_err(|err| ScabbardError::Internal(Box::new(err)))?;
+        let is_until = query.until.as_ref() == Some(&receipt.transaction_id);
+        batch.push(receipt);
+        if batch.len() == INDEX_LOOKUP_BATCH_SIZE {
+            page.add_batch(std::mem::take(&mut batch))?;
+        }
+        if is_until {
+            reached_until = true;
+            break;
+        }
+    }
+    page.add_batch(batch)?;
+
+    // If the `until` receipt was not found after the `after` receipt, it precedes it and the
+    // range is empty.
+    if !reached_until {
+        return Ok(ReceiptQueryResult {
+            receipts: vec![],
+            total: 0,
+        });
+    }
+
+    Ok(ReceiptQueryResult {
+        receipts: page.receipts,
+        total: page.total,
+    })
+}
+
+/// The matching receipts that are in the query's page, and the count of all matching receipts
+struct Page<'a> {
+    query: &'a ReceiptQuery,
+    index_store: Option<&'a dyn ReceiptIndexStore>,
+    receipts: Vec<QueriedReceipt>,
+    total: u64,
+}
+
+impl<'a> Page<'a> {
+    fn add_batch(&mut self, batch: Vec<TransactionReceipt>) -> Result<(), ScabbardError> {
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut entries = match self.index_store {
+            Some(index_store) => index_store
+                .get_entries(
+                    &batch
+                        .iter()
+                        .map(|receipt| receipt.transaction_id.clone())
+                        .collect::<Vec<_>>(),
+                )
+                .map_err(|err| ScabbardError::Internal(Box::new(err)))?,
+            None => Default::default(),
+        };
+
+        for receipt in batch {
+            let (contract_name, committed_at) = match entries.remove(&receipt.transaction_id) {
+                Some(entry) => (
+                    entry.contract_name().map(String::from),
+                    Some(entry.committed_at()),
+                ),
+                None => (None, None),
+            };
+            if !self.query.matches(contract_name.as_deref(), committed_at) {
+                continue;
+            }
+
+            let in_page = self.total >= self.query.offset as u64
+                && self
+                    .query
+                    .limit
+                    .map(|limit| self.receipts.len() < limit)
+                    .unwrap_or(true);
+            if in_page {
+                self.receipts.push(QueriedReceipt {
+                    receipt,
+                    contract_name,
+                    committed_at,
+                });
+            }
+            self.total += 1;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use sawtooth::receipt::store::diesel::DieselReceiptStore;
+    use transact::protocol::receipt::TransactionResult;
+
+    use crate::migrations::run_sqlite_migrations;
+    use crate::store::{DieselReceiptIndexStore, ReceiptIndexEntry};
+
+    /// Verify that receipts are filtered by transaction ID range, contract and commit time, that
+    /// the total counts every match while only one page is returned, and that unknown IDs are
+    /// rejected.
+    #[test]
+    fn query_receipts_with_filters() {
+        let pool = create_connection_pool_and_migrate();
+        let receipt_store = DieselReceiptStore::new(pool.clone(), Some("abcde-01234::a000".into()));
+        let index_store = DieselReceiptIndexStore::new(pool, "abcde-01234", "a000");
+
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        receipt_store
+            .add_txn_receipts((0..10).map(mock_transaction_receipt).collect())
+            .expect("failed to add receipts");
+        index_store
+            .add_entries(
+                (0..10)
+                    .map(|i| {
+                        ReceiptIndexEntry::new(
+                            format!("txn{}", i),
+                            Some(if i % 2 == 0 { "even" } else { "odd" }.into()),
+                            start + Duration::from_secs(i),
+                        )
+                    })
+                    .collect(),
+            )
+            .expect("failed to add index entries");
+
+        let result = query_receipts(
+            &receipt_store,
+            Some(&index_store),
+            &ReceiptQuery::new().with_offset(2).with_limit(3),
+        )
+        .expect("failed to query all receipts");
+        assert_eq!(result.total(), 10);
+        assert_eq!(ids(&result), vec!["txn2", "txn3", "txn4"]);
+
+        let result = query_receipts(
+            &receipt_store,
+            Some(&index_store),
+            &ReceiptQuery::new()
+                .with_after("txn1".into())
+                .with_until("txn6".into())
+                .with_contract_name("even".into()),
+        )
+        .expect("failed to query by range and contract");
+        assert_eq!(result.total(), 3);
+        assert_eq!(ids(&result), vec!["txn2", "txn4", "txn6"]);
+        assert_eq!(result.receipts()[0].contract_name(), Some("even"));
+        assert_eq!(
+            result.receipts()[0].committed_at(),
+            Some(start + Duration::from_secs(2))
+        );
+
+        let result = query_receipts(
+            &receipt_store,
+            Some(&index_store),
+            &ReceiptQuery::new()
+                .with_committed_from(start + Duration::from_secs(7))
+                .with_committed_before(start + Duration::from_secs(9))
+                .with_limit(1),
+        )
+        .expect("failed to query by commit time");
+        assert_eq!(result.total(), 2);
+        assert_eq!(ids(&result), vec!["txn7"]);
+
+        // An `until` receipt that precedes the `after` receipt gives an empty range
+        let result = query_receipts(
+            &receipt_store,
+            Some(&index_store),
+            &ReceiptQuery::new()
+                .with_after("txn5".into())
+                .with_until("txn2".into()),
+        )
+        .expect("failed to query an empty range");
+        assert_eq!(result.total(), 0);
+        assert!(result.receipts().is_empty());
+
+        assert!(matches!(
+            query_receipts(
+                &receipt_store,
+                Some(&index_store),
+                &ReceiptQuery::new().with_after("unknown".into()),
+            ),
+            Err(ScabbardError::InvalidReceiptQuery(_))
+        ));
+        assert!(matches!(
+            query_receipts(
+                &receipt_store,
+                None,
+                &ReceiptQuery::new().with_contract_name("even".into()),
+            ),
+            Err(ScabbardError::InvalidReceiptQuery(_))
+        ));
+    }
+
+    fn ids(result: &ReceiptQueryResult) -> Vec<&str> {
+        result
+            .receipts()
+            .iter()
+            .map(|receipt| receipt.receipt().transaction_id.as_str())
+            .collect()
+    }
+
+    fn create_connection_pool_and_migrate() -> Pool<ConnectionManager<SqliteConnection>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        pool
+    }
+
+    fn mock_transaction_receipt(i: u64) -> TransactionReceipt {
+        TransactionReceipt {
+            transaction_id: format!("txn{}", i),
+            transaction_result: TransactionResult::Valid {
+                state_changes: vec![],
+                events: vec![],
+                data: vec![],
+            },
+        }
+    }
+}
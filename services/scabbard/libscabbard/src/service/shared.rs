@@ -13,14 +13,20 @@
 // limitations under the License.
 
 use std::collections::{HashMap, HashSet, VecDeque};
+#[cfg(feature = "quiet-hours")]
+use std::time::Duration;
 
+#[cfg(feature = "verification-pool")]
+use cylinder::VerificationError;
 use cylinder::{PublicKey, Signature, Verifier as SignatureVerifier};
-use openssl::hash::{hash, MessageDigest};
 use protobuf::Message;
+use sha2::{Digest, Sha512};
 use transact::protocol::batch::BatchPair;
 use transact::protocol::transaction::{HashMethod, TransactionHeader};
-use transact::protos::FromBytes;
+use transact::protos::{FromBytes, IntoBytes};
 
+#[cfg(feature = "verification-pool")]
+use splinter::threading::verification::{PooledVerifier, VerificationRequest};
 use splinter::{
     consensus::{PeerId, Proposal, ProposalId},
     service::instance::ServiceNetworkSender,
@@ -29,6 +35,10 @@ use splinter::{
 use crate::protos::scabbard::{ScabbardMessage, ScabbardMessage_Type};
 
 use super::error::ScabbardError;
+#[cfg(feature = "state-sync")]
+use super::state_sync::PendingStateSync;
+#[cfg(feature = "quiet-hours")]
+use super::QuietHours;
 use super::ScabbardVersion;
 
 const DEFAULT_PENDING_BATCH_LIMIT: usize = 30;
@@ -58,6 +68,20 @@ pub struct ScabbardShared {
     /// Whether scabbard is currently accepting new batches, a part of back pressure
     accepting_batches: bool,
     scabbard_version: ScabbardVersion,
+    /// Batches that have been submitted locally but depend on batches that have not been
+    /// committed yet, along with the IDs of those batches. A held batch is submitted once all of
+    /// its dependencies are committed.
+    #[cfg(feature = "batch-dependencies")]
+    held_batches: Vec<(BatchPair, HashSet<String>)>,
+    /// The windows during which queued batches are not proposed
+    #[cfg(feature = "quiet-hours")]
+    quiet_hours: Option<QuietHours>,
+    /// Verifies batch and transaction signatures in parallel, in place of `signature_verifier`
+    #[cfg(feature = "verification-pool")]
+    verification_pool: Option<PooledVerifier>,
+    /// This service's latest request to sync its state, until every peer has responded to it
+    #[cfg(feature = "state-sync")]
+    pending_state_sync: Option<PendingStateSync>,
 }
 
 impl ScabbardShared {
@@ -95,6 +119,14 @@ impl ScabbardShared {
             signature_verifier,
             accepting_batches: true,
             scabbard_version,
+            #[cfg(feature = "batch-dependencies")]
+            held_batches: Vec::new(),
+            #[cfg(feature = "quiet-hours")]
+            quiet_hours: None,
+            #[cfg(feature = "verification-pool")]
+            verification_pool: None,
+            #[cfg(feature = "state-sync")]
+            pending_state_sync: None,
         };
 
         // initialize pending_batches metric
@@ -122,6 +154,27 @@ impl ScabbardShared {
         self.accepting_batches
     }
 
+    #[cfg(feature = "quiet-hours")]
+    pub fn set_quiet_hours(&mut self, quiet_hours: Option<QuietHours>) {
+        self.quiet_hours = quiet_hours;
+    }
+
+    #[cfg(feature = "verification-pool")]
+    pub fn set_verification_pool(&mut self, verification_pool: Option<PooledVerifier>) {
+        self.verification_pool = verification_pool;
+    }
+
+    #[cfg(feature = "state-sync")]
+    pub fn pending_state_sync_mut(&mut self) -> &mut Option<PendingStateSync> {
+        &mut self.pending_state_sync
+    }
+
+    /// Returns how long the quiet hours will continue, or `None` if they are not in effect.
+    #[cfg(feature = "quiet-hours")]
+    pub fn quiet_hours_remaining(&self) -> Option<Duration> {
+        self.quiet_hours.as_ref().and_then(QuietHours::remaining)
+    }
+
     /// Updates pending batches metrics gauge
     ///
     /// # Arguments
@@ -205,6 +258,83 @@ impl ScabbardShared {
         Ok(batch)
     }
 
+    /// Submits a batch that was received locally. The coordinator (or any service, for scabbard
+    /// v1) adds the batch to its queue; other services send the batch to the coordinator.
+    pub fn submit_batch(&mut self, batch: BatchPair) -> Result<(), ScabbardError> {
+        if self.scabbard_version == ScabbardVersion::V1 || self.is_coordinator() {
+            return self.add_batch_to_queue(batch);
+        }
+
+        let batch_bytes = batch
+            .into_bytes()
+            .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
+
+        let mut msg = ScabbardMessage::new();
+        msg.set_message_type(ScabbardMessage_Type::NEW_BATCH);
+        msg.set_new_batch(batch_bytes);
+        let msg_bytes = msg
+            .write_to_bytes()
+            .map_err(|err| ScabbardError::Internal(Box::new(err)))?;
+
+        self.network_sender()
+            .ok_or(ScabbardError::NotConnected)?
+            .send(self.coordinator_service_id(), msg_bytes.as_slice())
+            .map_err(|err| ScabbardError::Internal(Box::new(err)))
+    }
+
+    /// Holds a locally submitted batch until all of the given batches have been committed.
+    #[cfg(feature = "batch-dependencies")]
+    pub fn hold_batch(&mut self, batch: BatchPair, dependencies: HashSet<String>) {
+        self.held_batches.push((batch, dependencies));
+    }
+
+    /// Records that a batch has been committed, submitting the held batches that no longer have
+    /// any uncommitted dependencies in the order they were held.
+    #[cfg(feature = "batch-dependencies")]
+    pub fn release_held_batches(&mut self, committed_batch_id: &str) -> Result<(), ScabbardError> {
+        let mut released = vec![];
+        for (batch, mut dependencies) in std::mem::take(&mut self.held_batches) {
+            dependencies.remove(committed_batch_id);
+            if dependencies.is_empty() {
+                released.push(batch);
+            } else {
+                self.held_batches.push((batch, dependencies));
+            }
+        }
+
+        for batch in released {
+            debug!(
+                "Submitting batch {}; its dependencies have been committed",
+                batch.batch().header_signature()
+            );
+            self.submit_batch(batch)?;
+        }
+
+        Ok(())
+    }
+
+    /// Removes and returns the held batches that depend, directly or through other held batches,
+    /// on a batch that will never be committed.
+    #[cfg(feature = "batch-dependencies")]
+    pub fn drop_held_batches(&mut self, failed_batch_id: &str) -> Vec<BatchPair> {
+        let mut failed_ids = vec![failed_batch_id.to_string()];
+        let mut dropped = vec![];
+
+        while let Some(failed_id) = failed_ids.pop() {
+            let (failed, held) = std::mem::take(&mut self.held_batches)
+                .into_iter()
+                .partition::<Vec<_>, _>(|(_, dependencies)| dependencies.contains(&failed_id));
+            self.held_batches = held;
+
+            for (batch, _) in failed {
+                failed_ids.push(batch.batch().header_signature().to_string());
+                dropped.push(batch);
+            }
+        }
+
+        dropped
+    }
+
     pub fn network_sender(&self) -> Option<&dyn ServiceNetworkSender> {
         self.network_sender.as_deref()
     }
@@ -235,6 +365,11 @@ impl ScabbardShared {
     }
 
     pub fn verify_batches(&self, batches: &[BatchPair]) -> Result<bool, ScabbardError> {
+        #[cfg(feature = "verification-pool")]
+        if let Some(verification_pool) = &self.verification_pool {
+            return verify_batches_in_pool(verification_pool, batches);
+        }
+
         for batch in batches {
             let batch_pub_key = batch.header().signer_public_key();
 
@@ -313,9 +448,7 @@ impl ScabbardShared {
 
                 if !match header.payload_hash_method() {
                     HashMethod::Sha512 => {
-                        let expected_hash = hash(MessageDigest::sha512(), txn.payload())
-                            .map_err(|err| ScabbardError::BatchVerificationFailed(Box::new(err)))?;
-                        header.payload_hash() == expected_hash.as_ref()
+                        header.payload_hash() == Sha512::digest(txn.payload()).as_slice()
                     }
                 } {
                     warn!(
@@ -332,6 +465,105 @@ impl ScabbardShared {
     }
 }
 
+/// Verifies the given batches as `ScabbardShared::verify_batches` does, but checks all of the
+/// batch and transaction signatures at once on the verification pool, after the other checks.
+#[cfg(feature = "verification-pool")]
+fn verify_batches_in_pool(
+    verification_pool: &PooledVerifier,
+    batches: &[BatchPair],
+) -> Result<bool, ScabbardError> {
+    let mut requests = vec![];
+    // The warning to log for each request if its signature is invalid
+    let mut failures = vec![];
+
+    for batch in batches {
+        let batch_pub_key = batch.header().signer_public_key();
+
+        requests.push(VerificationRequest::new(
+            batch.batch().header().to_vec(),
+            Signature::from_hex(batch.batch().header_signature())
+                .map_err(|err| ScabbardError::BatchVerificationFailed(Box::new(err)))?,
+            PublicKey::new(batch_pub_key.to_vec()),
+        ));
+        failures.push(format!(
+            "Batch failed signature verification: {}",
+            batch.batch().header_signature()
+        ));
+
+        if batch.header().transaction_ids().len() != batch.batch().transactions().len() {
+            warn!(
+                "Number of transactions in batch header does not match number of transactions
+                 in batch: {}",
+                batch.batch().header_signature(),
+            );
+            return Ok(false);
+        }
+
+        for (i, txn) in batch.batch().transactions().iter().enumerate() {
+            let header = TransactionHeader::from_bytes(txn.header())
+                .map_err(|err| ScabbardError::BatchVerificationFailed(Box::new(err)))?;
+
+            if txn.header_signature() != batch.header().transaction_ids()[i] {
+                warn!(
+                    "Transaction at index {} does not match corresponding transaction ID in
+                     batch header: {}",
+                    i,
+                    batch.batch().header_signature(),
+                );
+                return Ok(false);
+            }
+
+            if header.batcher_public_key() != batch_pub_key {
+                warn!(
+                    "Transaction batcher public key does not match batch signer public key -
+                     txn: {}, batch: {}",
+                    txn.header_signature(),
+                    batch.batch().header_signature(),
+                );
+                return Ok(false);
+            }
+
+            if !match header.payload_hash_method() {
+                HashMethod::Sha512 => {
+                    header.payload_hash() == Sha512::digest(txn.payload()).as_slice()
+                }
+            } {
+                warn!(
+                    "Transaction payload hash doesn't match payload - txn: {}, batch: {}",
+                    txn.header_signature(),
+                    batch.batch().header_signature()
+                );
+                return Ok(false);
+            }
+
+            requests.push(VerificationRequest::new(
+                txn.header().to_vec(),
+                Signature::from_hex(txn.header_signature())
+                    .map_err(|err| ScabbardError::BatchVerificationFailed(Box::new(err)))?,
+                PublicKey::new(header.signer_public_key().to_vec()),
+            ));
+            failures.push(format!(
+                "Transaction failed signature verification - txn: {}, batch: {}",
+                txn.header_signature(),
+                batch.batch().header_signature()
+            ));
+        }
+    }
+
+    let results = verification_pool.verify_all(requests).map_err(|err| {
+        ScabbardError::BatchVerificationFailed(Box::new(VerificationError::Internal(
+            err.to_string(),
+        )))
+    })?;
+    match results.iter().position(|valid| !valid) {
+        Some(index) => {
+            warn!("{}", failures[index]);
+            Ok(false)
+        }
+        None => Ok(true),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -381,6 +613,148 @@ mod tests {
         assert_eq!(non_coordinator_shared.coordinator_service_id(), "svc1");
     }
 
+    /// Verifies that held batches are submitted once all of their dependencies have been
+    /// committed, and that the batches which depend on a batch that will not be committed are
+    /// dropped.
+    ///
+    /// 1. Hold two batches, the first depending on batch "a" and the second on batches "a" and
+    ///    "b". Release "a" and verify that only the first batch is queued, then release "b" and
+    ///    verify that the second batch is queued.
+    /// 2. Hold a batch depending on batch "c" and another batch depending on the first. Drop the
+    ///    batches depending on "c" and verify that both batches are returned and are not queued
+    ///    when "c" is released.
+    #[cfg(feature = "batch-dependencies")]
+    #[test]
+    fn held_batches() {
+        let context = Secp256k1Context::new();
+
+        let mut shared = ScabbardShared::new(
+            VecDeque::new(),
+            None,
+            HashSet::new(),
+            "svc0".to_string(),
+            #[cfg(feature = "metrics")]
+            "vzrQS-rvwf4".to_string(),
+            context.new_verifier(),
+            ScabbardVersion::V1,
+        );
+
+        let batch_1 = make_batch(&context, "abcdef01");
+        let batch_2 = make_batch(&context, "abcdef02");
+
+        shared.hold_batch(batch_1.clone(), dependencies(&["a"]));
+        shared.hold_batch(batch_2.clone(), dependencies(&["a", "b"]));
+
+        shared
+            .release_held_batches("a")
+            .expect("Failed to release batches");
+        assert_eq!(queued_batch_ids(&mut shared), vec![batch_id(&batch_1)]);
+
+        shared
+            .release_held_batches("b")
+            .expect("Failed to release batches");
+        assert_eq!(queued_batch_ids(&mut shared), vec![batch_id(&batch_2)]);
+
+        let batch_3 = make_batch(&context, "abcdef03");
+        let batch_4 = make_batch(&context, "abcdef04");
+
+        shared.hold_batch(batch_3.clone(), dependencies(&["c"]));
+        shared.hold_batch(batch_4.clone(), dependencies(&[&batch_id(&batch_3)]));
+
+        let dropped = shared
+            .drop_held_batches("c")
+            .iter()
+            .map(batch_id)
+            .collect::<Vec<_>>();
+        assert_eq!(dropped, vec![batch_id(&batch_3), batch_id(&batch_4)]);
+
+        shared
+            .release_held_batches("c")
+            .expect("Failed to release batches");
+        assert!(queued_batch_ids(&mut shared).is_empty());
+    }
+
+    /// Verifies that batches are verified on the verification pool when one is set, and that
+    /// verification fails once the pool is shut down.
+    #[cfg(feature = "verification-pool")]
+    #[test]
+    fn verify_batches_with_pool() {
+        use splinter::threading::lifecycle::ShutdownHandle;
+        use splinter::threading::verification::VerificationPool;
+
+        let context = Secp256k1Context::new();
+        let mut pool =
+            VerificationPool::start(2, 100, &context).expect("Failed to start verification pool");
+
+        let mut shared = ScabbardShared::new(
+            VecDeque::new(),
+            None,
+            HashSet::new(),
+            "svc0".to_string(),
+            #[cfg(feature = "metrics")]
+            "vzrQS-rvwf4".to_string(),
+            context.new_verifier(),
+            ScabbardVersion::V2,
+        );
+        shared.set_verification_pool(Some(pool.verifier()));
+
+        let batches = vec![
+            make_batch(&context, "abcdef01"),
+            make_batch(&context, "abcdef02"),
+        ];
+        assert!(shared
+            .verify_batches(&batches)
+            .expect("Failed to verify batches"));
+
+        pool.signal_shutdown();
+        pool.wait_for_shutdown()
+            .expect("Failed to shut down verification pool");
+        assert!(shared.verify_batches(&batches).is_err());
+    }
+
+    #[cfg(any(feature = "batch-dependencies", feature = "verification-pool"))]
+    fn make_batch(context: &Secp256k1Context, address: &str) -> BatchPair {
+        use cylinder::Context;
+        use transact::{
+            families::command::CommandTransactionBuilder,
+            protocol::command::{BytesEntry, Command, SetState},
+        };
+
+        let signer = context.new_signer(context.new_random_private_key());
+        CommandTransactionBuilder::new()
+            .with_commands(vec![Command::SetState(SetState::new(vec![
+                BytesEntry::new(address.to_string(), b"value".to_vec()),
+            ]))])
+            .into_transaction_builder()
+            .expect("failed to convert to transaction builder")
+            .into_batch_builder(&*signer)
+            .expect("failed to build transaction")
+            .build_pair(&*signer)
+            .expect("Failed to build batch")
+    }
+
+    #[cfg(feature = "batch-dependencies")]
+    fn dependencies(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(ToString::to_string).collect()
+    }
+
+    #[cfg(feature = "batch-dependencies")]
+    fn batch_id(batch: &BatchPair) -> String {
+        batch.batch().header_signature().to_string()
+    }
+
+    #[cfg(feature = "batch-dependencies")]
+    fn queued_batch_ids(shared: &mut ScabbardShared) -> Vec<String> {
+        let mut ids = vec![];
+        while let Some(batch) = shared
+            .pop_batch_from_queue()
+            .expect("Failed to pop batch from queue")
+        {
+            ids.push(batch_id(&batch));
+        }
+        ids
+    }
+
     #[derive(Clone, Debug)]
     pub struct MockServiceNetworkSender;
 
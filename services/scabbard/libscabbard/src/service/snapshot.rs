@@ -0,0 +1,423 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Snapshots: writing a scabbard service's state and receipts to a file, and restoring them.
+//!
+//! A [`Snapshot`] of a service holds every entry of its state at its current state root, along
+//! with its transaction receipts, so that a node can be backed up or a replacement node seeded
+//! without replaying the service's history. Snapshots are written to the directory of a
+//! [`SnapshotConfig`] on demand, and on an interval if one is set. The service's state is locked
+//! while a snapshot is read, so no batch is committed partway through.
+//!
+//! A service that has no transaction receipts when it starts is restored from its newest
+//! snapshot in the directory, if there is one. To seed a new node, copy the snapshot files into
+//! its snapshot directory before the circuit's services are started on it.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use splinter::error::{InternalError, InvalidArgumentError};
+
+use crate::snapshot::{Snapshot, SnapshotInfo};
+
+use super::state::ScabbardState;
+
+const DEFAULT_RETAIN: usize = 3;
+const SNAPSHOT_EXTENSION: &str = "snapshot";
+
+/// Where a scabbard service's snapshots are written, how often, and how many are kept
+#[derive(Clone)]
+pub struct SnapshotConfig {
+    dir: PathBuf,
+    interval: Option<Duration>,
+    retain: usize,
+}
+
+impl SnapshotConfig {
+    /// Creates a configuration that writes snapshots to the given directory on demand only,
+    /// keeping the three newest snapshots of each service.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidArgumentError`] if the directory does not exist.
+    pub fn new(dir: &str) -> Result<Self, InvalidArgumentError> {
+        let dir = PathBuf::from(dir);
+        if !dir.is_dir() {
+            return Err(InvalidArgumentError::new(
+                "dir",
+                format!("snapshot directory does not exist: {}", dir.display()),
+            ));
+        }
+
+        Ok(Self {
+            dir,
+            interval: None,
+            retain: DEFAULT_RETAIN,
+        })
+    }
+
+    /// Also writes a snapshot of each service on the given interval.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Sets how many snapshots of each service are kept; older snapshots are removed when a new
+    /// one is written.
+    pub fn with_retain(mut self, retain: usize) -> Self {
+        self.retain = retain.max(1);
+        self
+    }
+
+    /// Returns the snapshot files of the given service in the directory, oldest first.
+    fn list_snapshots(&self, circuit_id: &str, service_id: &str) -> io::Result<Vec<PathBuf>> {
+        let prefix = format!("{}-{}-", circuit_id, service_id);
+
+        let mut snapshots = vec![];
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SNAPSHOT_EXTENSION) {
+                continue;
+            }
+            let created_at = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.strip_prefix(&prefix))
+                .and_then(|created_at| created_at.parse::<u64>().ok());
+            if let Some(created_at) = created_at {
+                snapshots.push((created_at, path));
+            }
+        }
+        snapshots.sort();
+
+        Ok(snapshots.into_iter().map(|(_, path)| path).collect())
+    }
+}
+
+/// Writes a snapshot of the given service to the configured directory, then removes its oldest
+/// snapshots beyond the number to retain.
+pub(crate) fn write_snapshot(
+    config: &SnapshotConfig,
+    circuit_id: &str,
+    service_id: &str,
+    state: &ScabbardState,
+) -> Result<SnapshotInfo, InternalError> {
+    let entries = state
+        .get_state_with_prefix(None)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    let receipts = state
+        .list_receipts()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    let created_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+
+    let snapshot = Snapshot::new(
+        circuit_id,
+        service_id,
+        state.current_state_root(),
+        created_at,
+        entries,
+        receipts,
+    )
+    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    let path = config.dir.join(format!(
+        "{}-{}-{}.{}",
+        circuit_id, service_id, created_at, SNAPSHOT_EXTENSION
+    ));
+    snapshot
+        .write(&path)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    let snapshots = config
+        .list_snapshots(circuit_id, service_id)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    for old_snapshot in snapshots
+        .iter()
+        .take(snapshots.len().saturating_sub(config.retain))
+    {
+        if let Err(err) = fs::remove_file(old_snapshot) {
+            warn!(
+                "Unable to remove old snapshot {}: {}",
+                old_snapshot.display(),
+                err
+            );
+        }
+    }
+
+    Ok(snapshot.info(&path))
+}
+
+/// Restores the given service from its newest snapshot in the configured directory, if it has
+/// no transaction receipts and there is a snapshot of it. Returns the restored snapshot, if any.
+pub(crate) fn restore_latest_snapshot(
+    config: &SnapshotConfig,
+    circuit_id: &str,
+    service_id: &str,
+    state: &mut ScabbardState,
+) -> Result<Option<SnapshotInfo>, InternalError> {
+    if state
+        .count_receipts()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        > 0
+    {
+        return Ok(None);
+    }
+
+    let path = match config
+        .list_snapshots(circuit_id, service_id)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        .pop()
+    {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    let snapshot =
+        Snapshot::read(&path).map_err(|err| InternalError::from_source(Box::new(err)))?;
+    if snapshot.circuit_id() != circuit_id || snapshot.service_id() != service_id {
+        return Err(InternalError::with_message(format!(
+            "snapshot {} is of service {}::{}",
+            path.display(),
+            snapshot.circuit_id(),
+            snapshot.service_id()
+        )));
+    }
+
+    state
+        .restore(
+            snapshot.state_root(),
+            snapshot
+                .state_entries()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            snapshot
+                .receipts()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?,
+        )
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    Ok(Some(snapshot.info(&path)))
+}
+
+/// A background thread that writes snapshots of a scabbard service on an interval
+pub(crate) struct SnapshotTask {
+    shutdown: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl SnapshotTask {
+    /// Starts writing snapshots of the given service, if the configuration has an interval; the
+    /// first snapshot is written one interval after the task is started.
+    pub fn start(
+        config: SnapshotConfig,
+        circuit_id: String,
+        service_id: String,
+        state: Arc<Mutex<ScabbardState>>,
+    ) -> Result<Option<Self>, InternalError> {
+        let interval = match config.interval {
+            Some(interval) => interval,
+            None => return Ok(None),
+        };
+
+        let (shutdown, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name(format!("ScabbardSnapshot-{}::{}", circuit_id, service_id))
+            .spawn(move || loop {
+                match receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                let result = match state.lock() {
+                    Ok(state) => write_snapshot(&config, &circuit_id, &service_id, &state),
+                    Err(_) => {
+                        error!(
+                            "Unable to snapshot scabbard service {}::{}: state lock poisoned",
+                            circuit_id, service_id
+                        );
+                        break;
+                    }
+                };
+
+                match result {
+                    Ok(info) => debug!(
+                        "Wrote snapshot of scabbard service {}::{} at state root {} to {}",
+                        circuit_id,
+                        service_id,
+                        info.state_root(),
+                        info.file()
+                    ),
+                    Err(err) => warn!(
+                        "Unable to snapshot scabbard service {}::{}: {}",
+                        circuit_id, service_id, err
+                    ),
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Some(Self {
+            shutdown,
+            join_handle,
+        }))
+    }
+
+    /// Stops writing snapshots and waits for the thread to exit.
+    pub fn shutdown(self) -> Result<(), InternalError> {
+        // An error means the thread has already stopped
+        let _ = self.shutdown.send(());
+        self.join_handle
+            .join()
+            .map_err(|_| InternalError::with_message("snapshot thread panicked".into()))
+    }
+}
+
+#[cfg(all(test, feature = "sqlite"))]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+    use sawtooth::migrations::run_sqlite_migrations;
+    use sawtooth::receipt::store::diesel::DieselReceiptStore;
+    use tempfile::TempDir;
+    use transact::{
+        database::{btree::BTreeDatabase, Database},
+        state::merkle::INDEXES,
+    };
+    use transact::{
+        families::command::CommandTransactionBuilder,
+        protocol::command::{BytesEntry, Command, SetState},
+    };
+
+    use cylinder::{secp256k1::Secp256k1Context, Context};
+
+    use crate::service::state::merkle_state::{MerkleState, MerkleStateConfig};
+    use crate::store::transact::{TransactCommitHashStore, CURRENT_STATE_ROOT_INDEX};
+
+    /// Verify that a snapshot restores a new service to the state root and receipts of the
+    /// service it was taken from, that only the newest snapshots are kept, and that a service
+    /// which already has receipts is not restored.
+    #[test]
+    fn snapshot_and_restore() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let config = SnapshotConfig::new(temp_dir.path().to_str().expect("Invalid path"))
+            .expect("Failed to create config")
+            .with_retain(1);
+
+        let mut source = create_state();
+        source.start_executor().expect("Failed to start executor");
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let batch = CommandTransactionBuilder::new()
+            .with_commands(vec![Command::SetState(SetState::new(vec![
+                BytesEntry::new("abcdef".into(), b"value1".to_vec()),
+                BytesEntry::new("012345".into(), b"value2".to_vec()),
+            ]))])
+            .into_transaction_builder()
+            .expect("Failed to convert to transaction builder")
+            .into_batch_builder(&*signer)
+            .expect("Failed to build transaction")
+            .build_pair(&*signer)
+            .expect("Failed to build batch");
+        source
+            .prepare_change(batch)
+            .expect("Failed to prepare change");
+        source.commit().expect("Failed to commit change");
+        source.stop_executor();
+
+        // Write an older snapshot, which is removed when the next one is written
+        let old_snapshot = temp_dir.path().join("abcde-01234-a000-0.snapshot");
+        fs::write(&old_snapshot, b"{}").expect("Failed to write old snapshot");
+
+        let info = write_snapshot(&config, "abcde-01234", "a000", &source)
+            .expect("Failed to write snapshot");
+        assert_eq!(info.state_root(), source.current_state_root());
+        assert_eq!(info.receipts(), 1);
+        assert!(!old_snapshot.exists());
+
+        let mut replacement = create_state();
+        assert_ne!(
+            replacement.current_state_root(),
+            source.current_state_root()
+        );
+        let restored = restore_latest_snapshot(&config, "abcde-01234", "a000", &mut replacement)
+            .expect("Failed to restore snapshot");
+        assert_eq!(restored, Some(info));
+        assert_eq!(
+            replacement.current_state_root(),
+            source.current_state_root()
+        );
+        assert_eq!(
+            replacement
+                .get_state_at_address("abcdef")
+                .expect("Failed to get state"),
+            Some(b"value1".to_vec())
+        );
+        assert_eq!(
+            replacement
+                .list_receipts()
+                .expect("Failed to list receipts"),
+            source.list_receipts().expect("Failed to list receipts")
+        );
+
+        // The replacement now has receipts, so it is not restored again
+        assert_eq!(
+            restore_latest_snapshot(&config, "abcde-01234", "a000", &mut replacement)
+                .expect("Failed to restore snapshot"),
+            None
+        );
+    }
+
+    fn create_state() -> ScabbardState {
+        let mut indexes = INDEXES.to_vec();
+        indexes.push(CURRENT_STATE_ROOT_INDEX);
+        let db = BTreeDatabase::new(&indexes);
+        let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+            .expect("Unable to create merkle state");
+        let commit_hash_store = TransactCommitHashStore::new(db);
+
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder()
+            .max_size(1)
+            .build(connection_manager)
+            .expect("Failed to build connection pool");
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))
+            .expect("Failed to run migrations");
+
+        ScabbardState::new(
+            merkle_state,
+            false,
+            Arc::new(commit_hash_store),
+            Arc::new(DieselReceiptStore::new(pool, None)),
+            #[cfg(feature = "metrics")]
+            "a000".to_string(),
+            #[cfg(feature = "metrics")]
+            "abcde-01234".to_string(),
+            vec![],
+        )
+        .expect("Failed to create state")
+    }
+}
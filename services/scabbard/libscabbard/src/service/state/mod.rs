@@ -13,6 +13,8 @@
 // limitations under the License.
 
 pub mod merkle_state;
+#[cfg(feature = "parallel-execution")]
+mod parallel_scheduler;
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::convert::TryFrom;
@@ -31,15 +33,28 @@ use serde::{
 };
 #[cfg(feature = "events")]
 use splinter::events::{ParseBytes, ParseError};
+#[cfg(feature = "otel-tracing")]
+use splinter::otel::Span;
 #[cfg(test)]
 use transact::families::command::CommandTransactionHandler;
+#[cfg(feature = "receipt-queries")]
+use transact::protocol::{
+    sabre::payload::{Action, SabrePayload},
+    transaction::TransactionHeader,
+};
+#[cfg(feature = "receipt-queries")]
+use transact::protos::FromBytes;
 use transact::{
     context::manager::sync::ContextManager,
-    execution::{adapter::static_adapter::StaticExecutionAdapter, executor::Executor},
+    execution::{
+        adapter::{static_adapter::StaticExecutionAdapter, ExecutionAdapter},
+        executor::Executor,
+    },
     families::sabre::{
         admin::SettingsAdminPermission, handler::SabreTransactionHandler,
         ADMINISTRATORS_SETTING_ADDRESS, ADMINISTRATORS_SETTING_KEY,
     },
+    handler::TransactionHandler,
     protocol::{
         batch::BatchPair,
         receipt::{TransactionReceipt, TransactionResult},
@@ -53,12 +68,28 @@ use transact::{
 
 use crate::protos::scabbard::{Setting, Setting_Entry};
 use crate::service::error::{ScabbardStateError, StateSubscriberError};
+#[cfg(feature = "execution-limits")]
+use crate::service::execution_limits::LimitedSabreTransactionHandler;
+#[cfg(feature = "execution-limits")]
+use crate::service::ExecutionLimits;
+#[cfg(feature = "state-pruning")]
+use crate::service::StateRetention;
+use crate::state_proof::StateProof;
+#[cfg(feature = "state-pruning")]
+use crate::state_pruning::StatePruneInfo;
 use crate::store::CommitHashStore;
+#[cfg(feature = "receipt-queries")]
+use crate::store::{ReceiptIndexEntry, ReceiptIndexStore};
+
+#[cfg(feature = "parallel-execution")]
+use parallel_scheduler::ParallelScheduler;
 
 const EXECUTION_TIMEOUT: u64 = 300; // five minutes
 const ITER_CACHE_SIZE: usize = 64;
 const COMPLETED_BATCH_INFO_ITER_RETRY: Duration = Duration::from_millis(100);
 const DEFAULT_BATCH_HISTORY_SIZE: usize = 100;
+#[cfg(feature = "receipt-queries")]
+const SABRE_FAMILY_NAME: &str = "sabre";
 
 /// Iterator over entries in a Scabbard service's state
 pub type StateIter = Box<dyn Iterator<Item = Result<(String, Vec<u8>), ScabbardStateError>>>;
@@ -72,12 +103,33 @@ pub struct ScabbardState {
     current_state_root: String,
     receipt_store: Arc<dyn ReceiptStore>,
     pending_changes: Option<(String, Vec<TransactionReceipt>)>,
+    /// Records the contract and commit time of each committed transaction, if receipts can be
+    /// queried by them
+    #[cfg(feature = "receipt-queries")]
+    receipt_index_store: Option<Arc<dyn ReceiptIndexStore>>,
+    /// The contract executed by each transaction of the pending changes
+    #[cfg(feature = "receipt-queries")]
+    pending_contract_names: Vec<(String, Option<String>)>,
     event_subscribers: Vec<Box<dyn StateSubscriber>>,
     #[cfg(feature = "metrics")]
     service_id: String,
     #[cfg(feature = "metrics")]
     circuit_id: String,
     batch_history: BatchHistory,
+    /// How many previous state roots are kept, if they are not pruned as soon as they are
+    /// replaced
+    #[cfg(feature = "state-pruning")]
+    state_retention: Option<StateRetention>,
+    /// The previous state roots that have not been pruned, with the times they were replaced,
+    /// oldest first
+    #[cfg(feature = "state-pruning")]
+    retained_roots: VecDeque<(String, SystemTime)>,
+    /// The limits on the resources of executed Sabre contracts, if they are limited
+    #[cfg(feature = "execution-limits")]
+    execution_limits: Option<ExecutionLimits>,
+    /// The number of transactions that may be executed at the same time
+    #[cfg(feature = "parallel-execution")]
+    execution_workers: usize,
 }
 
 impl ScabbardState {
@@ -148,26 +200,45 @@ impl ScabbardState {
             current_state_root,
             receipt_store,
             pending_changes: None,
+            #[cfg(feature = "receipt-queries")]
+            receipt_index_store: None,
+            #[cfg(feature = "receipt-queries")]
+            pending_contract_names: vec![],
             event_subscribers: vec![],
             #[cfg(feature = "metrics")]
             service_id,
             #[cfg(feature = "metrics")]
             circuit_id,
             batch_history: BatchHistory::new(),
+            #[cfg(feature = "state-pruning")]
+            state_retention: None,
+            #[cfg(feature = "state-pruning")]
+            retained_roots: VecDeque::new(),
+            #[cfg(feature = "execution-limits")]
+            execution_limits: None,
+            #[cfg(feature = "parallel-execution")]
+            execution_workers: 1,
         })
     }
 
     pub fn start_executor(&mut self) -> Result<(), ScabbardStateError> {
-        let mut executor = Executor::new(vec![Box::new(StaticExecutionAdapter::new_adapter(
-            vec![
-                Box::new(SabreTransactionHandler::new(Box::new(
-                    SettingsAdminPermission,
-                ))),
-                #[cfg(test)]
-                Box::new(CommandTransactionHandler::new()),
-            ],
-            self.context_manager.clone(),
-        )?)]);
+        // Each execution adapter executes one transaction at a time
+        #[cfg(feature = "parallel-execution")]
+        let workers = self.execution_workers;
+        #[cfg(not(feature = "parallel-execution"))]
+        let workers = 1;
+
+        let adapters = (0..workers)
+            .map(|_| {
+                StaticExecutionAdapter::new_adapter(
+                    self.transaction_handlers(),
+                    self.context_manager.clone(),
+                )
+                .map(|adapter| Box::new(adapter) as Box<dyn ExecutionAdapter>)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut executor = Executor::new(adapters);
         executor
             .start()
             .map_err(|err| ScabbardStateError(format!("failed to start executor: {}", err)))?;
@@ -177,6 +248,45 @@ impl ScabbardState {
         Ok(())
     }
 
+    /// Creates the transaction handlers of an execution adapter.
+    fn transaction_handlers(&self) -> Vec<Box<dyn TransactionHandler>> {
+        #[cfg(feature = "execution-limits")]
+        let sabre_handler: Box<dyn TransactionHandler> = match &self.execution_limits {
+            Some(limits) => Box::new(LimitedSabreTransactionHandler::new(limits.clone())),
+            None => Box::new(SabreTransactionHandler::new(Box::new(
+                SettingsAdminPermission,
+            ))),
+        };
+        #[cfg(not(feature = "execution-limits"))]
+        let sabre_handler: Box<dyn TransactionHandler> = Box::new(SabreTransactionHandler::new(
+            Box::new(SettingsAdminPermission),
+        ));
+
+        vec![
+            sabre_handler,
+            #[cfg(test)]
+            Box::new(CommandTransactionHandler::new()),
+        ]
+    }
+
+    /// Creates a scheduler for a batch that builds on the current state root. With more than one
+    /// execution worker, the transactions whose declared inputs and outputs do not conflict are
+    /// executed in parallel; their results are still applied in the batch's order.
+    fn new_scheduler(&self) -> Result<Box<dyn Scheduler>, ScabbardStateError> {
+        #[cfg(feature = "parallel-execution")]
+        if self.execution_workers > 1 {
+            return Ok(Box::new(ParallelScheduler::new(
+                Box::new(self.context_manager.clone()),
+                self.current_state_root.clone(),
+            )?));
+        }
+
+        Ok(Box::new(SerialScheduler::new(
+            Box::new(self.context_manager.clone()),
+            self.current_state_root.clone(),
+        )?))
+    }
+
     pub fn stop_executor(&mut self) {
         if let Some(executor) = self.executor.take() {
             executor.stop();
@@ -220,21 +330,51 @@ impl ScabbardState {
         ))
     }
 
+    /// Generate a Merkle proof of the value at the given `address`. The proof is generated at the
+    /// given `state_root`, or at the current state root if none is given. Returns `None` if the
+    /// state root is unknown, such as a root that has been pruned.
+    pub fn get_state_proof(
+        &self,
+        address: &str,
+        state_root: Option<&str>,
+    ) -> Result<Option<StateProof>, ScabbardStateError> {
+        let state_root = state_root.unwrap_or(&self.current_state_root);
+        let entries = match self.merkle_state.leaves(&state_root.to_string(), None) {
+            Ok(leaves) => leaves
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|err| ScabbardStateError(err.to_string()))?,
+            // The current state root may not be stored yet if nothing has been committed
+            Err(MerkleRadixLeafReadError::InvalidStateError(_))
+                if state_root == self.current_state_root =>
+            {
+                vec![]
+            }
+            Err(MerkleRadixLeafReadError::InvalidStateError(_)) => return Ok(None),
+            Err(err) => return Err(ScabbardStateError(err.to_string())),
+        };
+
+        StateProof::generate(entries, state_root, address)
+            .map(Some)
+            .map_err(|err| ScabbardStateError(err.to_string()))
+    }
+
     /// Get the current state root hash.
     pub fn current_state_root(&self) -> &str {
         &self.current_state_root
     }
 
     pub fn prepare_change(&mut self, batch: BatchPair) -> Result<String, ScabbardStateError> {
+        #[cfg(feature = "otel-tracing")]
+        let _span = splinter::otel::enter_span(
+            "scabbard execute batch",
+            &[("batch_id", batch.batch().header_signature())],
+        );
         let executor = self.executor.as_ref().ok_or_else(|| {
             ScabbardStateError("attempting to prepare a change on a stopped service".into())
         })?;
         // Setup the transact scheduler
         let (result_tx, result_rx) = std::sync::mpsc::channel();
-        let mut scheduler = SerialScheduler::new(
-            Box::new(self.context_manager.clone()),
-            self.current_state_root.clone(),
-        )?;
+        let mut scheduler = self.new_scheduler()?;
         scheduler.set_result_callback(Box::new(move |batch_result| {
             if result_tx.send(batch_result).is_err() {
                 error!("Unable to send batch result; receiver must have dropped");
@@ -288,10 +428,16 @@ impl ScabbardState {
             &receipts_into_transact_state_changes(&txn_receipts)?,
         )?;
         self.pending_changes = Some((signature.to_string(), txn_receipts));
+        #[cfg(feature = "receipt-queries")]
+        {
+            self.pending_contract_names = contract_names(&batch);
+        }
         Ok(state_root)
     }
 
     pub fn commit(&mut self) -> Result<(), ScabbardStateError> {
+        #[cfg(feature = "otel-tracing")]
+        let _span = splinter::otel::enter_span("scabbard commit", &[]);
         match self.pending_changes.take() {
             Some((signature, txn_receipts)) => {
                 let state_changes = receipts_into_transact_state_changes(&txn_receipts)?;
@@ -324,6 +470,9 @@ impl ScabbardState {
                         ))
                     })?;
 
+                #[cfg(feature = "receipt-queries")]
+                self.index_pending_receipts()?;
+
                 for event in events {
                     self.event_subscribers.retain(|subscriber| {
                         match subscriber.handle_event(event.clone()) {
@@ -344,23 +493,7 @@ impl ScabbardState {
                 );
 
                 if previous_state_root != self.current_state_root {
-                    self.merkle_state
-                        .prune(vec![previous_state_root.clone()])
-                        .map_err(|err| {
-                            ScabbardStateError(format!(
-                                "failed to prune previous state {}: {}",
-                                previous_state_root, err
-                            ))
-                        })?;
-
-                    if self.state_autocleanup_enabled {
-                        if let Err(err) = self.merkle_state.remove_pruned_entries() {
-                            error!(
-                                "failed to cleanup pruned state for root {}: {}",
-                                previous_state_root, err
-                            )
-                        }
-                    }
+                    self.release_state_root(previous_state_root)?;
                 }
 
                 Ok(())
@@ -369,7 +502,122 @@ impl ScabbardState {
         }
     }
 
+    /// Prunes a previous state root that has been replaced, or retains it if there is a state
+    /// retention policy.
+    fn release_state_root(&mut self, state_root: String) -> Result<(), ScabbardStateError> {
+        #[cfg(feature = "state-pruning")]
+        if self.state_retention.is_some() {
+            self.retained_roots.retain(|(root, _)| root != &state_root);
+            self.retained_roots
+                .push_back((state_root, SystemTime::now()));
+            return Ok(());
+        }
+
+        self.merkle_state
+            .prune(vec![state_root.clone()])
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to prune previous state {}: {}",
+                    state_root, err
+                ))
+            })?;
+
+        if self.state_autocleanup_enabled {
+            if let Err(err) = self.merkle_state.remove_pruned_entries() {
+                error!(
+                    "failed to cleanup pruned state for root {}: {}",
+                    state_root, err
+                )
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the policy for retaining previous state roots. If `None`, each previous root is
+    /// pruned as soon as a new root is committed.
+    #[cfg(feature = "state-pruning")]
+    pub fn set_state_retention(&mut self, state_retention: Option<StateRetention>) {
+        self.state_retention = state_retention;
+    }
+
+    /// Sets the limits on the resources of executed Sabre contracts. Takes effect when the
+    /// executor is started.
+    #[cfg(feature = "execution-limits")]
+    pub fn set_execution_limits(&mut self, execution_limits: Option<ExecutionLimits>) {
+        self.execution_limits = execution_limits;
+    }
+
+    /// Sets the number of transactions that may be executed at the same time. Takes effect when
+    /// the executor is started.
+    #[cfg(feature = "parallel-execution")]
+    pub fn set_execution_workers(&mut self, execution_workers: usize) {
+        self.execution_workers = execution_workers.max(1);
+    }
+
+    /// Prunes the previous state roots that are beyond the state retention policy, then removes
+    /// every state entry that is no longer referenced by a root. Without a policy, only the
+    /// entries of roots that have already been pruned are removed.
+    #[cfg(feature = "state-pruning")]
+    pub fn prune_state(&mut self) -> Result<StatePruneInfo, ScabbardStateError> {
+        let expired = match &self.state_retention {
+            Some(retention) => {
+                let replaced_at = self
+                    .retained_roots
+                    .iter()
+                    .map(|(_, replaced_at)| *replaced_at)
+                    .collect::<Vec<_>>();
+                retention.expired(&replaced_at, SystemTime::now())
+            }
+            None => self.retained_roots.len(),
+        };
+
+        self.prune_oldest_roots(expired)
+    }
+
+    /// Prunes every retained previous state root, then removes every state entry that is no
+    /// longer referenced by a root.
+    #[cfg(feature = "state-pruning")]
+    pub fn prune_retained_roots(&mut self) -> Result<StatePruneInfo, ScabbardStateError> {
+        self.prune_oldest_roots(self.retained_roots.len())
+    }
+
+    #[cfg(feature = "state-pruning")]
+    fn prune_oldest_roots(&mut self, count: usize) -> Result<StatePruneInfo, ScabbardStateError> {
+        // A root may have been committed again since it was retained, and must not be pruned
+        // while it is current
+        let roots = self
+            .retained_roots
+            .iter()
+            .take(count)
+            .map(|(root, _)| root)
+            .filter(|root| **root != self.current_state_root)
+            .cloned()
+            .collect::<Vec<_>>();
+        let pruned_roots = roots.len() as u64;
+
+        if !roots.is_empty() {
+            self.merkle_state
+                .prune(roots)
+                .map_err(|err| ScabbardStateError(format!("failed to prune state: {}", err)))?;
+        }
+        self.retained_roots.drain(..count);
+
+        self.merkle_state
+            .remove_pruned_entries()
+            .map_err(|err| ScabbardStateError(format!("failed to remove pruned state: {}", err)))?;
+
+        Ok(StatePruneInfo::new(
+            &self.current_state_root,
+            pruned_roots,
+            self.retained_roots.len() as u64,
+        ))
+    }
+
     pub fn rollback(&mut self) -> Result<(), ScabbardStateError> {
+        #[cfg(feature = "receipt-queries")]
+        self.pending_contract_names.clear();
+
         match self.pending_changes.take() {
             Some((_, txn_receipts)) => info!(
                 "discarded {} change(s)",
@@ -381,6 +629,40 @@ impl ScabbardState {
         Ok(())
     }
 
+    /// Records the contract and commit time of each transaction committed from now on in the
+    /// given store.
+    #[cfg(feature = "receipt-queries")]
+    pub fn set_receipt_index_store(&mut self, receipt_index_store: Arc<dyn ReceiptIndexStore>) {
+        self.receipt_index_store = Some(receipt_index_store);
+    }
+
+    /// Adds the index entries of the transactions that were just committed.
+    #[cfg(feature = "receipt-queries")]
+    fn index_pending_receipts(&mut self) -> Result<(), ScabbardStateError> {
+        let contract_names = std::mem::take(&mut self.pending_contract_names);
+        let receipt_index_store = match &self.receipt_index_store {
+            Some(receipt_index_store) => receipt_index_store,
+            None => return Ok(()),
+        };
+
+        let committed_at = SystemTime::now();
+        receipt_index_store
+            .add_entries(
+                contract_names
+                    .into_iter()
+                    .map(|(transaction_id, contract_name)| {
+                        ReceiptIndexEntry::new(transaction_id, contract_name, committed_at)
+                    })
+                    .collect(),
+            )
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to add transaction receipts to index: {}",
+                    err
+                ))
+            })
+    }
+
     pub fn batch_history(&mut self) -> &mut BatchHistory {
         &mut self.batch_history
     }
@@ -396,6 +678,267 @@ impl ScabbardState {
     pub fn clear_subscribers(&mut self) {
         self.event_subscribers.clear();
     }
+
+    /// Removes the transaction receipts and the current commit hash of this instance, which are
+    /// kept outside of the merkle state.
+    pub fn purge_records(&mut self) -> Result<(), ScabbardStateError> {
+        let receipt_ids = self
+            .receipt_store
+            .list_receipts_since(None)
+            .map_err(|err| ScabbardStateError(err.to_string()))?
+            .map(|receipt| receipt.map(|receipt| receipt.transaction_id))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ScabbardStateError(err.to_string()))?;
+
+        debug!("Removing {} transaction receipt(s)", receipt_ids.len());
+        for id in receipt_ids {
+            self.receipt_store
+                .remove_txn_receipt_by_id(id)
+                .map_err(|err| ScabbardStateError(err.to_string()))?;
+        }
+
+        #[cfg(feature = "receipt-queries")]
+        if let Some(receipt_index_store) = &self.receipt_index_store {
+            receipt_index_store
+                .remove_all_entries()
+                .map_err(|err| ScabbardStateError(err.to_string()))?;
+        }
+
+        self.commit_hash_store
+            .remove_current_commit_hash()
+            .map_err(|err| ScabbardStateError(err.to_string()))
+    }
+
+    /// Returns the number of transaction receipts of this instance.
+    #[cfg(feature = "snapshot")]
+    pub fn count_receipts(&self) -> Result<u64, ScabbardStateError> {
+        self.receipt_store
+            .count_txn_receipts()
+            .map_err(|err| ScabbardStateError(err.to_string()))
+    }
+
+    /// Returns the transaction receipts of this instance, oldest first.
+    #[cfg(feature = "snapshot")]
+    pub fn list_receipts(&self) -> Result<Vec<TransactionReceipt>, ScabbardStateError> {
+        self.receipt_store
+            .list_receipts_since(None)
+            .map_err(|err| ScabbardStateError(err.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ScabbardStateError(err.to_string()))
+    }
+
+    /// Replaces the state of this instance with the given entries and adds the given receipts,
+    /// as restored from a snapshot.
+    ///
+    /// Only an instance without transaction receipts may be restored, and the entries must
+    /// produce the given state root; otherwise the instance is left unchanged.
+    #[cfg(feature = "snapshot")]
+    pub fn restore(
+        &mut self,
+        state_root: &str,
+        entries: Vec<(String, Vec<u8>)>,
+        receipts: Vec<TransactionReceipt>,
+    ) -> Result<(), ScabbardStateError> {
+        let receipt_count = self.count_receipts()?;
+        if receipt_count > 0 {
+            return Err(ScabbardStateError(format!(
+                "unable to restore state: {} transaction receipt(s) already exist",
+                receipt_count
+            )));
+        }
+
+        let state_changes = entries
+            .into_iter()
+            .map(|(key, value)| TransactStateChange::Set { key, value })
+            .collect::<Vec<_>>();
+        let initial_state_root = self
+            .merkle_state
+            .get_initial_state_root()
+            .map_err(|err| ScabbardStateError(err.to_string()))?;
+        let restored_state_root = self
+            .merkle_state
+            .commit(&initial_state_root, &state_changes)?;
+
+        if restored_state_root != state_root {
+            if restored_state_root != self.current_state_root {
+                self.merkle_state
+                    .prune(vec![restored_state_root.clone()])
+                    .map_err(|err| ScabbardStateError(err.to_string()))?;
+            }
+            return Err(ScabbardStateError(format!(
+                "unable to restore state: entries produce state root {}, expected {}",
+                restored_state_root, state_root
+            )));
+        }
+
+        self.receipt_store
+            .add_txn_receipts(receipts)
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to add transaction receipts to store: {}",
+                    err
+                ))
+            })?;
+
+        let previous_state_root =
+            std::mem::replace(&mut self.current_state_root, restored_state_root);
+        self.write_current_state_root()?;
+
+        if previous_state_root != self.current_state_root {
+            self.merkle_state
+                .prune(vec![previous_state_root.clone()])
+                .map_err(|err| {
+                    ScabbardStateError(format!(
+                        "failed to prune previous state {}: {}",
+                        previous_state_root, err
+                    ))
+                })?;
+        }
+
+        info!(
+            "Restored {} state change(s) for state root {}",
+            state_changes.len(),
+            self.current_state_root
+        );
+
+        Ok(())
+    }
+
+    /// Returns whether a change has been prepared and is awaiting a commit or rollback.
+    #[cfg(feature = "state-sync")]
+    pub fn has_pending_changes(&self) -> bool {
+        self.pending_changes.is_some()
+    }
+
+    /// Returns the ID of the newest transaction receipt of this instance, if it has any.
+    #[cfg(feature = "state-sync")]
+    pub fn last_receipt_id(&self) -> Result<Option<String>, ScabbardStateError> {
+        self.receipt_store
+            .list_receipts_since(None)
+            .map_err(|err| ScabbardStateError(err.to_string()))?
+            .last()
+            .transpose()
+            .map(|receipt| receipt.map(|receipt| receipt.transaction_id))
+            .map_err(|err| ScabbardStateError(err.to_string()))
+    }
+
+    /// Returns the transaction receipts committed after the one with the given ID, oldest first.
+    #[cfg(feature = "state-sync")]
+    pub fn list_receipts_since(
+        &self,
+        id: Option<String>,
+    ) -> Result<Vec<TransactionReceipt>, ScabbardStateError> {
+        self.receipt_store
+            .list_receipts_since(id)
+            .map_err(|err| ScabbardStateError(err.to_string()))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ScabbardStateError(err.to_string()))
+    }
+
+    /// Applies the given changes to the current state and adds the given receipts, as synced
+    /// from a replica. Events are sent to the state subscribers for the added receipts.
+    ///
+    /// The changes must produce the given state root, and no change may be pending; otherwise
+    /// the instance is left unchanged.
+    #[cfg(feature = "state-sync")]
+    pub fn apply_synced_changes(
+        &mut self,
+        state_root: &str,
+        state_changes: Vec<TransactStateChange>,
+        receipts: Vec<TransactionReceipt>,
+    ) -> Result<(), ScabbardStateError> {
+        if self.pending_changes.is_some() {
+            return Err(ScabbardStateError(
+                "unable to apply synced state: a change is pending".into(),
+            ));
+        }
+
+        let synced_state_root = self
+            .merkle_state
+            .commit(&self.current_state_root, &state_changes)?;
+
+        if synced_state_root != state_root {
+            if synced_state_root != self.current_state_root {
+                self.merkle_state
+                    .prune(vec![synced_state_root.clone()])
+                    .map_err(|err| ScabbardStateError(err.to_string()))?;
+            }
+            return Err(ScabbardStateError(format!(
+                "unable to apply synced state: changes produce state root {}, expected {}",
+                synced_state_root, state_root
+            )));
+        }
+
+        let events = receipts
+            .iter()
+            .cloned()
+            .map(StateChangeEvent::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.receipt_store
+            .add_txn_receipts(receipts)
+            .map_err(|err| {
+                ScabbardStateError(format!(
+                    "failed to add transaction receipts to store: {}",
+                    err
+                ))
+            })?;
+
+        let previous_state_root =
+            std::mem::replace(&mut self.current_state_root, synced_state_root);
+        self.write_current_state_root()?;
+
+        info!(
+            "Synced {} state change(s) for state root {}",
+            state_changes.len(),
+            self.current_state_root
+        );
+
+        for event in events {
+            self.event_subscribers.retain(|subscriber| {
+                match subscriber.handle_event(event.clone()) {
+                    Ok(()) => true,
+                    Err(StateSubscriberError::Unsubscribe) => false,
+                    Err(err @ StateSubscriberError::UnableToHandleEvent(_)) => {
+                        error!("{}", err);
+                        true
+                    }
+                }
+            });
+        }
+
+        if previous_state_root != self.current_state_root {
+            self.release_state_root(previous_state_root)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the ID of each transaction in the batch with the contract it executes: the name of
+/// the Sabre contract for a Sabre contract execution, and otherwise the transaction's family.
+#[cfg(feature = "receipt-queries")]
+fn contract_names(batch: &BatchPair) -> Vec<(String, Option<String>)> {
+    batch
+        .batch()
+        .transactions()
+        .iter()
+        .map(|txn| {
+            let contract_name = TransactionHeader::from_bytes(txn.header())
+                .ok()
+                .map(|header| {
+                    if header.family_name() == SABRE_FAMILY_NAME {
+                        if let Ok(payload) = SabrePayload::from_bytes(txn.payload()) {
+                            if let Action::ExecuteContract(action) = payload.action() {
+                                return action.name().to_string();
+                            }
+                        }
+                    }
+                    header.family_name().to_string()
+                });
+            (txn.header_signature().to_string(), contract_name)
+        })
+        .collect()
 }
 
 fn receipts_into_transact_state_changes(
@@ -488,6 +1031,12 @@ pub trait StateSubscriber: Send {
     fn handle_event(&self, event: StateChangeEvent) -> Result<(), StateSubscriberError>;
 }
 
+/// Receives the status transitions of the batches it is subscribed to
+#[cfg(feature = "batch-status-stream")]
+pub trait BatchStatusSubscriber: Send {
+    fn handle_status(&self, info: BatchInfo) -> Result<(), StateSubscriberError>;
+}
+
 #[derive(PartialEq)]
 enum EventQuery {
     Fetch(Option<String>),
@@ -683,6 +1232,11 @@ pub struct BatchHistory {
     history: HashMap<String, BatchInfo>,
     limit: usize,
     batch_subscribers: Vec<(HashSet<String>, Sender<BatchInfo>)>,
+    #[cfg(feature = "batch-status-stream")]
+    status_subscribers: Vec<(HashSet<String>, Box<dyn BatchStatusSubscriber>)>,
+    /// The span of each batch that has been submitted but not yet committed or found invalid
+    #[cfg(feature = "otel-tracing")]
+    spans: HashMap<String, Span>,
 }
 
 impl BatchHistory {
@@ -691,12 +1245,18 @@ impl BatchHistory {
     }
 
     pub fn add_batch(&mut self, signature: &str) {
-        self.upsert_batch(signature.into(), BatchStatus::Pending);
+        self.update_batch_status(signature, BatchStatus::Pending);
     }
 
     fn update_batch_status(&mut self, signature: &str, status: BatchStatus) {
         let batch_info = self.upsert_batch(signature.into(), status);
 
+        #[cfg(feature = "otel-tracing")]
+        self.trace_batch_status(&batch_info);
+
+        #[cfg(feature = "batch-status-stream")]
+        self.send_batch_info_to_status_subscribers(&batch_info);
+
         match batch_info.status {
             BatchStatus::Invalid(_) | BatchStatus::Committed(_) => {
                 self.send_completed_batch_info_to_subscribers(batch_info)
@@ -705,6 +1265,37 @@ impl BatchHistory {
         }
     }
 
+    /// Returns the status of the batch, or `BatchStatus::Unknown` if it is not in the history.
+    #[cfg(feature = "batch-dependencies")]
+    pub fn get_batch_status(&self, signature: &str) -> BatchStatus {
+        self.history
+            .get(signature)
+            .map(|info| info.status.clone())
+            .unwrap_or(BatchStatus::Unknown)
+    }
+
+    /// Marks a batch as invalid without executing it; each of its transactions is reported as
+    /// invalid with the given error message.
+    #[cfg(feature = "batch-dependencies")]
+    pub fn invalidate_batch(&mut self, batch: &BatchPair, error_message: &str) {
+        let invalid = batch
+            .batch()
+            .transactions()
+            .iter()
+            .map(|txn| {
+                InvalidTransaction::new(
+                    txn.header_signature().to_string(),
+                    error_message.to_string(),
+                    vec![],
+                )
+            })
+            .collect();
+        self.update_batch_status(
+            batch.batch().header_signature(),
+            BatchStatus::Invalid(invalid),
+        );
+    }
+
     fn commit(&mut self, signature: &str) {
         match self.history.get_mut(signature) {
             Some(info) => match info.status.clone() {
@@ -727,6 +1318,49 @@ impl BatchHistory {
         }
     }
 
+    /// Starts a batch's span when it is submitted, and ends it once it is committed or found
+    /// invalid; a batch removed from the history without completing ends its span as well.
+    #[cfg(feature = "otel-tracing")]
+    fn trace_batch_status(&mut self, batch_info: &BatchInfo) {
+        match &batch_info.status {
+            BatchStatus::Pending => {
+                let span = Span::start("scabbard batch", &[("batch_id", batch_info.id.as_str())]);
+                if let Some(previous) = self.spans.insert(batch_info.id.clone(), span) {
+                    previous.end();
+                }
+            }
+            BatchStatus::Valid(_) => {
+                if let Some(span) = self.spans.get_mut(&batch_info.id) {
+                    span.add_event("valid");
+                }
+            }
+            BatchStatus::Invalid(_) => {
+                if let Some(mut span) = self.spans.remove(&batch_info.id) {
+                    span.set_error("invalid".into());
+                    span.end();
+                }
+            }
+            BatchStatus::Committed(_) => {
+                if let Some(span) = self.spans.remove(&batch_info.id) {
+                    span.end();
+                }
+            }
+            BatchStatus::Unknown => (),
+        }
+
+        if self.spans.len() > self.limit {
+            let history = &self.history;
+            let (kept, removed): (HashMap<_, _>, Vec<_>) = self
+                .spans
+                .drain()
+                .partition(|(id, _)| history.contains_key(id));
+            self.spans = kept;
+            for (_, span) in removed {
+                span.end();
+            }
+        }
+    }
+
     fn upsert_batch(&mut self, signature: String, status: BatchStatus) -> BatchInfo {
         match self.history.get_mut(&signature) {
             Some(info) => {
@@ -857,6 +1491,63 @@ impl BatchHistory {
             })
             .collect();
     }
+
+    /// Sends the current status of each of the given batches to the subscriber, followed by each
+    /// of their status transitions until they are committed or invalid.
+    ///
+    /// The subscriber is dropped once all of the batches are committed or invalid, or when it
+    /// returns `StateSubscriberError::Unsubscribe`.
+    #[cfg(feature = "batch-status-stream")]
+    pub fn add_status_subscriber(
+        &mut self,
+        mut ids: HashSet<String>,
+        subscriber: Box<dyn BatchStatusSubscriber>,
+    ) {
+        for info in self.no_wait_batch_info_iter(&ids).flatten() {
+            if let BatchStatus::Invalid(_) | BatchStatus::Committed(_) = info.status {
+                ids.remove(&info.id);
+            }
+
+            match subscriber.handle_status(info) {
+                Ok(()) => (),
+                Err(StateSubscriberError::Unsubscribe) => return,
+                Err(err @ StateSubscriberError::UnableToHandleEvent(_)) => error!("{}", err),
+            }
+        }
+
+        if !ids.is_empty() {
+            self.status_subscribers.push((ids, subscriber));
+        }
+    }
+
+    #[cfg(feature = "batch-status-stream")]
+    fn send_batch_info_to_status_subscribers(&mut self, info: &BatchInfo) {
+        self.status_subscribers = self
+            .status_subscribers
+            .drain(..)
+            .filter_map(|(mut pending_signatures, subscriber)| {
+                if !pending_signatures.contains(&info.id) {
+                    return Some((pending_signatures, subscriber));
+                }
+
+                if let BatchStatus::Invalid(_) | BatchStatus::Committed(_) = info.status {
+                    pending_signatures.remove(&info.id);
+                }
+
+                match subscriber.handle_status(info.clone()) {
+                    Ok(()) => (),
+                    Err(StateSubscriberError::Unsubscribe) => return None,
+                    Err(err @ StateSubscriberError::UnableToHandleEvent(_)) => error!("{}", err),
+                }
+
+                if pending_signatures.is_empty() {
+                    None
+                } else {
+                    Some((pending_signatures, subscriber))
+                }
+            })
+            .collect();
+    }
 }
 
 impl Default for BatchHistory {
@@ -865,6 +1556,10 @@ impl Default for BatchHistory {
             history: HashMap::new(),
             limit: DEFAULT_BATCH_HISTORY_SIZE,
             batch_subscribers: vec![],
+            #[cfg(feature = "batch-status-stream")]
+            status_subscribers: vec![],
+            #[cfg(feature = "otel-tracing")]
+            spans: HashMap::new(),
         }
     }
 }
@@ -950,6 +1645,8 @@ mod tests {
     };
     use sawtooth::migrations::run_sqlite_migrations;
     use sawtooth::receipt::store::diesel::DieselReceiptStore;
+    #[cfg(feature = "parallel-execution")]
+    use transact::protocol::batch::BatchBuilder;
     use transact::{
         database::{btree::BTreeDatabase, Database},
         families::command::CommandTransactionBuilder,
@@ -957,6 +1654,8 @@ mod tests {
         state::merkle::INDEXES,
     };
 
+    #[cfg(feature = "state-sync")]
+    use crate::service::state_sync;
     use crate::store::transact::{TransactCommitHashStore, CURRENT_STATE_ROOT_INDEX};
 
     use super::merkle_state::{MerkleState, MerkleStateConfig};
@@ -1054,6 +1753,59 @@ mod tests {
         Ok(())
     }
 
+    /// Verify that a batch status subscriber receives the current status of each of its batches,
+    /// then their transitions until every batch is committed or invalid, and is dropped after.
+    #[cfg(feature = "batch-status-stream")]
+    #[test]
+    fn batch_status_subscriber() {
+        struct ChannelSubscriber(Sender<BatchInfo>);
+
+        impl BatchStatusSubscriber for ChannelSubscriber {
+            fn handle_status(&self, info: BatchInfo) -> Result<(), StateSubscriberError> {
+                self.0
+                    .send(info)
+                    .map_err(|_| StateSubscriberError::Unsubscribe)
+            }
+        }
+
+        let mut history = BatchHistory::new();
+        history.add_batch("batch-id-1");
+        history.update_batch_status("batch-id-1", BatchStatus::Valid(vec![]));
+        history.commit("batch-id-1");
+
+        let (tx, rx) = channel();
+        history.add_status_subscriber(
+            vec!["batch-id-1", "batch-id-2"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            Box::new(ChannelSubscriber(tx)),
+        );
+
+        history.add_batch("batch-id-2");
+        history.add_batch("batch-id-3");
+        history.update_batch_status("batch-id-2", BatchStatus::Valid(vec![]));
+        history.commit("batch-id-2");
+
+        let mut statuses = rx
+            .try_iter()
+            .map(|info| (info.id, info.status))
+            .collect::<Vec<_>>();
+        // The order of the current statuses is not defined
+        statuses[..2].sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            statuses,
+            vec![
+                ("batch-id-1".to_string(), BatchStatus::Committed(vec![])),
+                ("batch-id-2".to_string(), BatchStatus::Unknown),
+                ("batch-id-2".to_string(), BatchStatus::Pending),
+                ("batch-id-2".to_string(), BatchStatus::Valid(vec![])),
+                ("batch-id-2".to_string(), BatchStatus::Committed(vec![])),
+            ]
+        );
+        assert!(history.status_subscribers.is_empty());
+    }
+
     /// Verify that the ChannelBatchInfoIter returns a value if it is sent after next is called.
     #[test]
     fn channel_batch_iter_batch_info_after_next() -> Result<(), Box<dyn std::error::Error>> {
@@ -1343,6 +2095,326 @@ mod tests {
         state.stop_executor();
     }
 
+    /// Verify that previous state roots are retained under a state retention policy until they
+    /// are pruned, and that a pruned root can no longer be read.
+    ///
+    /// 1. Initialize a new, empty `ScabbardState` that keeps one previous root.
+    /// 2. Commit three batches and verify that every previous root is retained.
+    /// 3. Prune the state and verify that only the newest previous root is kept, and that a state
+    ///    proof can still be generated at it but not at an older one.
+    /// 4. Prune every retained root and verify that none are left.
+    #[cfg(feature = "state-pruning")]
+    #[test]
+    fn prune_retained_roots() {
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            create_connection_pool_and_migrate(":memory:".to_string()),
+            None,
+        ));
+
+        let db = create_btree_db();
+        let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+            .expect("Unable to create merkle state");
+        let commit_hash_store = TransactCommitHashStore::new(db);
+
+        let mut state = ScabbardState::new(
+            merkle_state,
+            false,
+            Arc::new(commit_hash_store),
+            receipt_store,
+            #[cfg(feature = "metrics")]
+            "svc0".to_string(),
+            #[cfg(feature = "metrics")]
+            "vzrQS-rvwf4".to_string(),
+            vec![],
+        )
+        .expect("Failed to initialize state");
+        state.set_state_retention(Some(StateRetention::new().with_max_roots(1)));
+
+        state.start_executor().expect("Failed to start executor");
+
+        let address = "abcdef".to_string();
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let mut roots = vec![];
+        for value in &["value1", "value2", "value3"] {
+            let batch = CommandTransactionBuilder::new()
+                .with_commands(vec![Command::SetState(SetState::new(vec![
+                    BytesEntry::new(address.clone(), value.as_bytes().to_vec()),
+                ]))])
+                .into_transaction_builder()
+                .expect("failed to convert to transaction builder")
+                .into_batch_builder(&*signer)
+                .expect("failed to build transaction")
+                .build_pair(&*signer)
+                .expect("Failed to build batch");
+            state
+                .prepare_change(batch)
+                .expect("Failed to prepare change");
+            state.commit().expect("Failed to commit change");
+            roots.push(state.current_state_root().to_string());
+        }
+
+        let info = state.prune_state().expect("Failed to prune state");
+        assert_eq!(info.state_root(), roots[2]);
+        assert_eq!(info.pruned_roots(), 2);
+        assert_eq!(info.retained_roots(), 1);
+
+        assert!(state
+            .get_state_proof(&address, Some(&roots[1]))
+            .expect("Failed to get state proof at retained root")
+            .is_some());
+        assert!(state
+            .get_state_proof(&address, Some(&roots[0]))
+            .expect("Failed to get state proof at pruned root")
+            .is_none());
+
+        let info = state
+            .prune_retained_roots()
+            .expect("Failed to prune retained roots");
+        assert_eq!(info.pruned_roots(), 1);
+        assert_eq!(info.retained_roots(), 0);
+        assert_eq!(
+            state
+                .get_state_at_address(&address)
+                .expect("Failed to get state"),
+            Some(b"value3".to_vec())
+        );
+
+        state.stop_executor();
+    }
+
+    /// Verify that a replica that missed some batches is synced with its peers' state and
+    /// receipts only once all of its peers agree on the state root:
+    ///
+    /// 1. Commit a batch on the replica and both peers, two more on the first peer, and only one
+    ///    more on the second peer.
+    /// 2. Request a sync from the replica and respond from both peers; verify that the responses
+    ///    are not applied, as the peers disagree.
+    /// 3. Commit the last batch on the second peer, request a sync again, and verify that the
+    ///    first peer's response is not applied until the second peer has responded, and that a
+    ///    response from a service that was not asked is ignored.
+    /// 4. Verify that the replica has the peers' state root, entries, and newest receipt.
+    /// 5. Verify that the same response is not applied again.
+    #[cfg(feature = "state-sync")]
+    #[test]
+    fn sync_state_from_peer() {
+        let mut peer = create_state();
+        peer.start_executor().expect("Failed to start executor");
+        let mut other_peer = create_state();
+        other_peer
+            .start_executor()
+            .expect("Failed to start executor");
+        let mut replica = create_state();
+        replica.start_executor().expect("Failed to start executor");
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let batches = vec![
+            ("abcdef01", "value1"),
+            ("abcdef02", "value2"),
+            ("123456aa", "value3"),
+        ]
+        .into_iter()
+        .map(|(address, value)| {
+            CommandTransactionBuilder::new()
+                .with_commands(vec![Command::SetState(SetState::new(vec![
+                    BytesEntry::new(address.to_string(), value.as_bytes().to_vec()),
+                ]))])
+                .into_transaction_builder()
+                .expect("failed to convert to transaction builder")
+                .into_batch_builder(&*signer)
+                .expect("failed to build transaction")
+                .build_pair(&*signer)
+                .expect("Failed to build batch")
+        })
+        .collect::<Vec<_>>();
+
+        let last_batch = batches[2].clone();
+        for (i, batch) in batches.into_iter().enumerate() {
+            if i == 0 {
+                replica
+                    .prepare_change(batch.clone())
+                    .expect("Failed to prepare change");
+                replica.commit().expect("Failed to commit change");
+            }
+            if i < 2 {
+                other_peer
+                    .prepare_change(batch.clone())
+                    .expect("Failed to prepare change");
+                other_peer.commit().expect("Failed to commit change");
+            }
+            peer.prepare_change(batch)
+                .expect("Failed to prepare change");
+            peer.commit().expect("Failed to commit change");
+        }
+
+        let peers = vec!["peer".to_string(), "other-peer".to_string()];
+        let base_state_root = replica.current_state_root().to_string();
+
+        let request =
+            state_sync::create_sync_request(&replica).expect("Failed to create sync request");
+        let mut pending = Some(state_sync::PendingStateSync::new(
+            request.get_state_root().to_string(),
+            peers.clone(),
+        ));
+        let response = state_sync::create_sync_response(&request, &peer)
+            .expect("Failed to create sync response");
+        assert_eq!(response.get_receipts().len(), 2);
+        let other_response = state_sync::create_sync_response(&request, &other_peer)
+            .expect("Failed to create sync response");
+
+        assert!(
+            !state_sync::apply_sync_response(&response, "peer", &mut pending, &mut replica)
+                .expect("Failed to apply sync response")
+        );
+        assert!(!state_sync::apply_sync_response(
+            &other_response,
+            "other-peer",
+            &mut pending,
+            &mut replica
+        )
+        .expect("Failed to apply sync response"));
+        assert_eq!(replica.current_state_root(), base_state_root);
+        assert!(pending.is_none());
+
+        other_peer
+            .prepare_change(last_batch)
+            .expect("Failed to prepare change");
+        other_peer.commit().expect("Failed to commit change");
+
+        let request =
+            state_sync::create_sync_request(&replica).expect("Failed to create sync request");
+        let mut pending = Some(state_sync::PendingStateSync::new(
+            request.get_state_root().to_string(),
+            peers,
+        ));
+        let response = state_sync::create_sync_response(&request, &peer)
+            .expect("Failed to create sync response");
+        let other_response = state_sync::create_sync_response(&request, &other_peer)
+            .expect("Failed to create sync response");
+
+        assert!(
+            !state_sync::apply_sync_response(&response, "peer", &mut pending, &mut replica)
+                .expect("Failed to apply sync response")
+        );
+        assert!(!state_sync::apply_sync_response(
+            &response,
+            "intruder",
+            &mut pending,
+            &mut replica
+        )
+        .expect("Failed to apply sync response"));
+        assert_eq!(replica.current_state_root(), base_state_root);
+
+        assert!(state_sync::apply_sync_response(
+            &other_response,
+            "other-peer",
+            &mut pending,
+            &mut replica
+        )
+        .expect("Failed to apply sync response"));
+        assert_eq!(replica.current_state_root(), peer.current_state_root());
+        assert_eq!(
+            replica
+                .get_state_at_address("123456aa")
+                .expect("Failed to get state"),
+            Some(b"value3".to_vec())
+        );
+        assert_eq!(
+            replica.last_receipt_id().expect("Failed to get receipt"),
+            peer.last_receipt_id().expect("Failed to get receipt")
+        );
+
+        assert!(
+            !state_sync::apply_sync_response(&response, "peer", &mut pending, &mut replica)
+                .expect("Failed to apply sync response")
+        );
+
+        peer.stop_executor();
+        other_peer.stop_executor();
+        replica.stop_executor();
+    }
+
+    /// Verify that a batch executed by several workers produces the same state as when it is
+    /// executed serially, including the writes of conflicting transactions in the batch's order.
+    #[cfg(feature = "parallel-execution")]
+    #[test]
+    fn parallel_execution() {
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+
+        let mut entries = (0..8)
+            .map(|i| (format!("abcdef{:02}", i), format!("value{}", i)))
+            .collect::<Vec<_>>();
+        entries.push(("abcdef00".to_string(), "last".to_string()));
+        let transactions = entries
+            .into_iter()
+            .map(|(address, value)| {
+                CommandTransactionBuilder::new()
+                    .with_commands(vec![Command::SetState(SetState::new(vec![
+                        BytesEntry::new(address, value.into_bytes()),
+                    ]))])
+                    .into_transaction_builder()
+                    .expect("failed to convert to transaction builder")
+                    .build(&*signer)
+                    .expect("failed to build transaction")
+            })
+            .collect::<Vec<_>>();
+        let batch = BatchBuilder::new()
+            .with_transactions(transactions)
+            .build_pair(&*signer)
+            .expect("Failed to build batch");
+
+        let mut roots = vec![];
+        for workers in &[1, 4] {
+            let mut state = create_state();
+            state.set_execution_workers(*workers);
+            state.start_executor().expect("Failed to start executor");
+
+            state
+                .prepare_change(batch.clone())
+                .expect("Failed to prepare change");
+            state.commit().expect("Failed to commit change");
+            assert_eq!(
+                state
+                    .get_state_at_address("abcdef00")
+                    .expect("Failed to get state"),
+                Some(b"last".to_vec())
+            );
+            roots.push(state.current_state_root().to_string());
+
+            state.stop_executor();
+        }
+
+        assert_eq!(roots[0], roots[1]);
+    }
+
+    #[cfg(any(feature = "parallel-execution", feature = "state-sync"))]
+    fn create_state() -> ScabbardState {
+        let receipt_store = Arc::new(DieselReceiptStore::new(
+            create_connection_pool_and_migrate(":memory:".to_string()),
+            None,
+        ));
+
+        let db = create_btree_db();
+        let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))
+            .expect("Unable to create merkle state");
+        let commit_hash_store = TransactCommitHashStore::new(db);
+
+        ScabbardState::new(
+            merkle_state,
+            false,
+            Arc::new(commit_hash_store),
+            receipt_store,
+            #[cfg(feature = "metrics")]
+            "svc0".to_string(),
+            #[cfg(feature = "metrics")]
+            "vzrQS-rvwf4".to_string(),
+            vec![],
+        )
+        .expect("Failed to initialize state")
+    }
+
     fn mock_transaction_receipt(id: &str) -> TransactionReceipt {
         TransactionReceipt {
             transaction_id: id.into(),
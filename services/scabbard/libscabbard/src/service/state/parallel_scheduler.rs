@@ -0,0 +1,578 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A scheduler that executes the transactions of a batch in parallel, where their declared inputs
+//! and outputs allow it.
+//!
+//! A transaction depends on every earlier transaction that declares an output conflicting with
+//! one of its own inputs or outputs; two addresses conflict if either is a prefix of the other. A
+//! transaction is only executed once the transactions it depends on have been executed, in a
+//! context built on theirs, so that it reads their writes. Transactions that do not depend on
+//! each other are executed at the same time. The receipts of a batch are returned in the batch's
+//! order, so that their changes are applied in that order.
+//!
+//! Batches are executed one after the other, and the transactions of a batch also depend on the
+//! conflicting transactions of the valid batches executed before it.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+
+use transact::context::{ContextId, ContextLifecycle};
+use transact::protocol::{
+    batch::BatchPair,
+    receipt::{TransactionReceipt, TransactionResult},
+    transaction::TransactionPair,
+};
+use transact::scheduler::{
+    BatchExecutionResult, ExecutionTask, ExecutionTaskCompletionNotification,
+    ExecutionTaskCompletionNotifier, Scheduler, SchedulerError,
+};
+
+type ResultCallback = Box<dyn Fn(Option<BatchExecutionResult>) + Send>;
+type ErrorCallback = Box<dyn Fn(SchedulerError) + Send>;
+
+type Shared = Arc<(Mutex<SchedulerState>, Condvar)>;
+
+/// Schedules the transactions of batches for parallel execution, based on their declared inputs
+/// and outputs
+pub struct ParallelScheduler {
+    shared: Shared,
+    task_iterator_taken: bool,
+}
+
+impl ParallelScheduler {
+    /// Creates a scheduler whose transactions are executed on top of the given state.
+    ///
+    /// # Arguments
+    ///
+    /// * `context_lifecycle` - Creates the contexts that the transactions are executed in
+    /// * `state_id` - The state root that the first batch builds on
+    pub fn new(
+        context_lifecycle: Box<dyn ContextLifecycle>,
+        state_id: String,
+    ) -> Result<Self, SchedulerError> {
+        let state = SchedulerState {
+            context_lifecycle,
+            state_id,
+            result_callback: Box::new(|_| ()),
+            error_callback: Box::new(|_| ()),
+            pending_batches: VecDeque::new(),
+            current: None,
+            executed: vec![],
+            finalized: false,
+            cancelled: false,
+            done: false,
+        };
+
+        Ok(Self {
+            shared: Arc::new((Mutex::new(state), Condvar::new())),
+            task_iterator_taken: false,
+        })
+    }
+
+    /// Runs `f` on the scheduler's state, then wakes the task iterator, as `f` may have made a
+    /// transaction ready for execution.
+    fn update<F, T>(&self, f: F) -> Result<T, SchedulerError>
+    where
+        F: FnOnce(&mut SchedulerState) -> Result<T, SchedulerError>,
+    {
+        let (lock, condvar) = &*self.shared;
+        let mut state = lock_state(lock)?;
+        let result = f(&mut state);
+        condvar.notify_all();
+        result
+    }
+}
+
+impl Scheduler for ParallelScheduler {
+    fn set_result_callback(
+        &mut self,
+        callback: Box<dyn Fn(Option<BatchExecutionResult>) + Send>,
+    ) -> Result<(), SchedulerError> {
+        self.update(|state| {
+            state.result_callback = callback;
+            Ok(())
+        })
+    }
+
+    fn set_error_callback(
+        &mut self,
+        callback: Box<dyn Fn(SchedulerError) + Send>,
+    ) -> Result<(), SchedulerError> {
+        self.update(|state| {
+            state.error_callback = callback;
+            Ok(())
+        })
+    }
+
+    fn add_batch(&mut self, batch: BatchPair) -> Result<(), SchedulerError> {
+        self.update(|state| {
+            if state.finalized {
+                return Err(SchedulerError::SchedulerFinalized);
+            }
+
+            let batch_id = batch.batch().header_signature();
+            let duplicate = state
+                .pending_batches
+                .iter()
+                .chain(state.current.iter().map(|execution| &execution.batch))
+                .any(|pending| pending.batch().header_signature() == batch_id);
+            if duplicate {
+                return Err(SchedulerError::DuplicateBatch(batch_id.to_string()));
+            }
+
+            state.pending_batches.push_back(batch);
+            state.advance();
+            Ok(())
+        })
+    }
+
+    fn cancel(&mut self) -> Result<Vec<BatchPair>, SchedulerError> {
+        self.update(|state| {
+            state.cancelled = true;
+            let mut unexecuted = state
+                .current
+                .take()
+                .map(|execution| execution.batch)
+                .into_iter()
+                .collect::<Vec<_>>();
+            unexecuted.extend(state.pending_batches.drain(..));
+            state.finish();
+            Ok(unexecuted)
+        })
+    }
+
+    fn finalize(&mut self) -> Result<(), SchedulerError> {
+        self.update(|state| {
+            state.finalized = true;
+            state.advance();
+            Ok(())
+        })
+    }
+
+    fn take_task_iterator(
+        &mut self,
+    ) -> Result<Box<dyn Iterator<Item = ExecutionTask> + Send>, SchedulerError> {
+        if self.task_iterator_taken {
+            return Err(SchedulerError::NoTaskIterator);
+        }
+        self.task_iterator_taken = true;
+
+        Ok(Box::new(TaskIterator {
+            shared: self.shared.clone(),
+        }))
+    }
+
+    fn new_notifier(&mut self) -> Result<Box<dyn ExecutionTaskCompletionNotifier>, SchedulerError> {
+        Ok(Box::new(Notifier {
+            shared: self.shared.clone(),
+        }))
+    }
+}
+
+struct SchedulerState {
+    context_lifecycle: Box<dyn ContextLifecycle>,
+    state_id: String,
+    result_callback: ResultCallback,
+    error_callback: ErrorCallback,
+    pending_batches: VecDeque<BatchPair>,
+    /// The batch being executed
+    current: Option<BatchExecution>,
+    /// The transactions of the valid batches executed so far
+    executed: Vec<ExecutedTransaction>,
+    finalized: bool,
+    cancelled: bool,
+    /// Whether the end of the execution has been reported
+    done: bool,
+}
+
+impl SchedulerState {
+    /// Returns the next transaction whose dependencies have all been executed, creating the
+    /// context it is executed in.
+    fn next_task(&mut self) -> Option<ExecutionTask> {
+        let execution = self.current.as_mut()?;
+        if execution.invalid.is_some() {
+            return None;
+        }
+
+        let index = execution.transactions.iter().position(|transaction| {
+            matches!(transaction.status, Status::Pending)
+                && transaction
+                    .dependencies
+                    .iter()
+                    .all(|dependency| execution.transactions[*dependency].context_id().is_some())
+        })?;
+
+        let mut dependent_contexts = execution.transactions[index].earlier_contexts.clone();
+        dependent_contexts.extend(
+            execution.transactions[index]
+                .dependencies
+                .iter()
+                .filter_map(|dependency| execution.transactions[*dependency].context_id()),
+        );
+        let context_id = self
+            .context_lifecycle
+            .create_context(&dependent_contexts, &self.state_id);
+
+        let transaction = &mut execution.transactions[index];
+        transaction.status = Status::Running(context_id);
+        Some(ExecutionTask::new(transaction.pair.clone(), context_id))
+    }
+
+    /// Records the result of an executed transaction.
+    fn notify(&mut self, notification: ExecutionTaskCompletionNotification) {
+        let (context_id, receipt) = match notification {
+            ExecutionTaskCompletionNotification::Valid(context_id, pair) => {
+                match self
+                    .context_lifecycle
+                    .get_transaction_receipt(&context_id, pair.transaction().header_signature())
+                {
+                    Ok(receipt) => (context_id, receipt),
+                    Err(err) => {
+                        (self.error_callback)(SchedulerError::Internal(format!(
+                            "unable to get receipt of transaction {}: {}",
+                            pair.transaction().header_signature(),
+                            err
+                        )));
+                        return;
+                    }
+                }
+            }
+            ExecutionTaskCompletionNotification::Invalid(context_id, result) => (
+                context_id,
+                TransactionReceipt {
+                    state_changes: vec![],
+                    events: vec![],
+                    data: vec![],
+                    transaction_result: TransactionResult::Invalid {
+                        error_message: result.error_message,
+                        error_data: result.error_data,
+                    },
+                    transaction_id: result.transaction_id,
+                },
+            ),
+        };
+
+        let index = self.current.as_ref().and_then(|execution| {
+            execution.transactions.iter().position(|transaction| {
+                matches!(transaction.status, Status::Running(running) if running == context_id)
+            })
+        });
+        match (self.current.as_mut(), index) {
+            (Some(execution), Some(index)) => {
+                if let TransactionResult::Invalid { .. } = receipt.transaction_result {
+                    execution.invalid = Some(execution.invalid.map_or(index, |i| i.min(index)));
+                }
+                execution.transactions[index].status = Status::Executed(context_id, receipt);
+            }
+            _ => {
+                (self.error_callback)(SchedulerError::Internal(format!(
+                    "received a notification for unknown context {:?}",
+                    context_id
+                )));
+                return;
+            }
+        }
+
+        self.advance();
+    }
+
+    /// Reports the current batch once it has been executed, then starts the next batch. Once the
+    /// scheduler is finalized and every batch has been executed, the end of the execution is
+    /// reported.
+    fn advance(&mut self) {
+        loop {
+            match self.current.take() {
+                Some(execution) if !execution.is_complete() => {
+                    self.current = Some(execution);
+                    return;
+                }
+                Some(execution) => self.report(execution),
+                None => (),
+            }
+
+            if self.cancelled {
+                return;
+            }
+
+            match self.pending_batches.pop_front() {
+                Some(batch) => match BatchExecution::new(batch, &self.executed) {
+                    Ok(execution) => self.current = Some(execution),
+                    Err(err) => (self.error_callback)(err),
+                },
+                None => {
+                    if self.finalized {
+                        self.finish();
+                    }
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Reports the result of an executed batch. The contexts of a valid batch are kept, as later
+    /// batches may build on them.
+    fn report(&mut self, execution: BatchExecution) {
+        let invalid = execution.invalid;
+        let mut receipts = vec![];
+        let mut contexts = vec![];
+        for (index, transaction) in execution.transactions.into_iter().enumerate() {
+            let outputs = transaction.pair.header().outputs().to_vec();
+            match transaction.status {
+                Status::Executed(context_id, receipt) => {
+                    if invalid.map_or(true, |invalid| index <= invalid) {
+                        receipts.push(receipt);
+                    }
+                    contexts.push(ExecutedTransaction {
+                        outputs,
+                        context_id,
+                    });
+                }
+                Status::Running(context_id) => contexts.push(ExecutedTransaction {
+                    outputs,
+                    context_id,
+                }),
+                Status::Pending => (),
+            }
+        }
+
+        if invalid.is_some() {
+            for transaction in contexts {
+                self.context_lifecycle.drop_context(transaction.context_id);
+            }
+        } else {
+            self.executed.extend(contexts);
+        }
+
+        (self.result_callback)(Some(BatchExecutionResult {
+            batch: execution.batch,
+            receipts,
+        }));
+    }
+
+    /// Reports the end of the execution and drops the contexts that were kept for later batches.
+    fn finish(&mut self) {
+        if self.done {
+            return;
+        }
+        self.done = true;
+
+        for transaction in self.executed.drain(..) {
+            self.context_lifecycle.drop_context(transaction.context_id);
+        }
+        (self.result_callback)(None);
+    }
+}
+
+/// A batch whose transactions are being executed
+struct BatchExecution {
+    batch: BatchPair,
+    transactions: Vec<TransactionExecution>,
+    /// The index of the first transaction found to be invalid, if any; no further transactions of
+    /// the batch are executed once one is invalid
+    invalid: Option<usize>,
+}
+
+impl BatchExecution {
+    /// Determines the dependencies of the batch's transactions, on each other and on the
+    /// transactions of the batches executed before it.
+    fn new(batch: BatchPair, executed: &[ExecutedTransaction]) -> Result<Self, SchedulerError> {
+        let pairs = batch
+            .batch()
+            .transactions()
+            .iter()
+            .cloned()
+            .map(|transaction| transaction.into_pair())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| SchedulerError::Internal(err.to_string()))?;
+
+        let transactions = pairs
+            .iter()
+            .enumerate()
+            .map(|(index, pair)| {
+                let inputs = pair.header().inputs();
+                let outputs = pair.header().outputs();
+                TransactionExecution {
+                    pair: pair.clone(),
+                    dependencies: pairs[..index]
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, earlier)| {
+                            depends_on(earlier.header().outputs(), inputs, outputs)
+                        })
+                        .map(|(earlier_index, _)| earlier_index)
+                        .collect(),
+                    earlier_contexts: executed
+                        .iter()
+                        .filter(|earlier| depends_on(&earlier.outputs, inputs, outputs))
+                        .map(|earlier| earlier.context_id)
+                        .collect(),
+                    status: Status::Pending,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            batch,
+            transactions,
+            invalid: None,
+        })
+    }
+
+    /// Returns whether every transaction of the batch that will be executed has been executed.
+    fn is_complete(&self) -> bool {
+        match self.invalid {
+            Some(_) => !self
+                .transactions
+                .iter()
+                .any(|transaction| matches!(transaction.status, Status::Running(_))),
+            None => self
+                .transactions
+                .iter()
+                .all(|transaction| matches!(transaction.status, Status::Executed(..))),
+        }
+    }
+}
+
+struct TransactionExecution {
+    pair: TransactionPair,
+    /// The indices of the earlier transactions of the batch that this transaction depends on
+    dependencies: Vec<usize>,
+    /// The contexts of the transactions of earlier batches that this transaction depends on
+    earlier_contexts: Vec<ContextId>,
+    status: Status,
+}
+
+impl TransactionExecution {
+    /// Returns the context of the transaction, if it has been executed.
+    fn context_id(&self) -> Option<ContextId> {
+        match self.status {
+            Status::Executed(context_id, _) => Some(context_id),
+            _ => None,
+        }
+    }
+}
+
+enum Status {
+    Pending,
+    Running(ContextId),
+    Executed(ContextId, TransactionReceipt),
+}
+
+struct ExecutedTransaction {
+    outputs: Vec<Vec<u8>>,
+    context_id: ContextId,
+}
+
+/// Returns whether a transaction with the given inputs and outputs must be executed after an
+/// earlier transaction with the given outputs, so that it reads the earlier transaction's writes.
+fn depends_on(earlier_outputs: &[Vec<u8>], inputs: &[Vec<u8>], outputs: &[Vec<u8>]) -> bool {
+    earlier_outputs.iter().any(|written| {
+        inputs
+            .iter()
+            .chain(outputs.iter())
+            .any(|address| written.starts_with(address) || address.starts_with(written))
+    })
+}
+
+fn lock_state(lock: &Mutex<SchedulerState>) -> Result<MutexGuard<SchedulerState>, SchedulerError> {
+    lock.lock()
+        .map_err(|_| SchedulerError::Internal("scheduler state lock poisoned".into()))
+}
+
+/// Provides the transactions that are ready for execution, waiting while the transactions that
+/// are ready depend on transactions that are still being executed
+struct TaskIterator {
+    shared: Shared,
+}
+
+impl Iterator for TaskIterator {
+    type Item = ExecutionTask;
+
+    fn next(&mut self) -> Option<ExecutionTask> {
+        let (lock, condvar) = &*self.shared;
+        let mut state = match lock_state(lock) {
+            Ok(state) => state,
+            Err(err) => {
+                error!("Unable to get next execution task: {}", err);
+                return None;
+            }
+        };
+
+        loop {
+            if state.done || state.cancelled {
+                return None;
+            }
+            if let Some(task) = state.next_task() {
+                return Some(task);
+            }
+            state = match condvar.wait(state) {
+                Ok(state) => state,
+                Err(_) => {
+                    error!("Unable to get next execution task: scheduler state lock poisoned");
+                    return None;
+                }
+            };
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Notifier {
+    shared: Shared,
+}
+
+impl ExecutionTaskCompletionNotifier for Notifier {
+    fn notify(&self, notification: ExecutionTaskCompletionNotification) {
+        let (lock, condvar) = &*self.shared;
+        match lock_state(lock) {
+            Ok(mut state) => state.notify(notification),
+            Err(err) => error!("Unable to record execution result: {}", err),
+        }
+        condvar.notify_all();
+    }
+
+    fn clone_box(&self) -> Box<dyn ExecutionTaskCompletionNotifier> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a transaction depends on an earlier transaction only if the earlier
+    /// transaction writes to an address that the transaction reads or writes, where addresses
+    /// conflict if either is a prefix of the other.
+    #[test]
+    fn transaction_dependencies() {
+        let address = |bytes: &[u8]| bytes.to_vec();
+
+        // Reads a written address
+        assert!(depends_on(&[address(&[1, 2])], &[address(&[1, 2])], &[]));
+        // Writes a written address
+        assert!(depends_on(&[address(&[1, 2])], &[], &[address(&[1, 2])]));
+        // Reads a prefix of a written address, or an address under a written prefix
+        assert!(depends_on(&[address(&[1, 2, 3])], &[address(&[1, 2])], &[]));
+        assert!(depends_on(&[address(&[1])], &[address(&[1, 2])], &[]));
+        // Reads and writes other addresses
+        assert!(!depends_on(
+            &[address(&[1, 2])],
+            &[address(&[1, 3])],
+            &[address(&[2])]
+        ));
+        // The earlier transaction writes nothing
+        assert!(!depends_on(&[], &[address(&[1, 2])], &[address(&[1, 2])]));
+    }
+}
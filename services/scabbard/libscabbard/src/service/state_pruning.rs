@@ -0,0 +1,242 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! State pruning: bounding the number of previous state roots a scabbard service keeps.
+//!
+//! Every batch a scabbard service commits produces a new state root. By default, the previous
+//! root is pruned as soon as the new one is committed, and the state entries it no longer
+//! references are only removed from the database if state autocleanup is enabled. With a
+//! [`StateRetention`] policy, previous roots are kept until they are beyond the policy's limits,
+//! so that state proofs can still be generated at them. The service prunes them on an interval,
+//! and removes every state entry that is no longer referenced by a root, which keeps the size of
+//! its LMDB or SQL state bounded.
+//!
+//! The retained roots are tracked in memory. When the service is stopped, the roots it has
+//! retained are pruned, so that they are not left behind in its state once it is restarted.
+
+use std::sync::mpsc::{channel, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use splinter::error::InternalError;
+
+use super::state::ScabbardState;
+
+const DEFAULT_PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// How many previous state roots a scabbard service keeps, and how often older ones are pruned
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateRetention {
+    max_roots: Option<usize>,
+    max_age: Option<Duration>,
+    interval: Duration,
+}
+
+impl StateRetention {
+    /// Creates a policy without limits, which prunes every previous state root every ten
+    /// minutes. A root is kept while it is within every limit that is set.
+    pub fn new() -> Self {
+        Self {
+            max_roots: None,
+            max_age: None,
+            interval: DEFAULT_PRUNE_INTERVAL,
+        }
+    }
+
+    /// Keeps at most the given number of the newest previous state roots.
+    pub fn with_max_roots(mut self, max_roots: usize) -> Self {
+        self.max_roots = Some(max_roots);
+        self
+    }
+
+    /// Keeps previous state roots for at most the given time after they were replaced.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets how often previous state roots are pruned.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    pub fn max_roots(&self) -> Option<usize> {
+        self.max_roots
+    }
+
+    pub fn max_age(&self) -> Option<Duration> {
+        self.max_age
+    }
+
+    /// Returns how many of the given previous roots are beyond the policy's limits. The roots are
+    /// given by the times they were replaced, oldest first, so the expired roots are always the
+    /// first ones.
+    pub(crate) fn expired(&self, replaced_at: &[SystemTime], now: SystemTime) -> usize {
+        if self.max_roots.is_none() && self.max_age.is_none() {
+            return replaced_at.len();
+        }
+
+        let beyond_max_roots = self
+            .max_roots
+            .map(|max_roots| replaced_at.len().saturating_sub(max_roots))
+            .unwrap_or(0);
+        let beyond_max_age = self
+            .max_age
+            .map(|max_age| {
+                replaced_at
+                    .iter()
+                    .take_while(|replaced_at| {
+                        now.duration_since(**replaced_at)
+                            .map(|age| age > max_age)
+                            .unwrap_or(false)
+                    })
+                    .count()
+            })
+            .unwrap_or(0);
+
+        beyond_max_roots.max(beyond_max_age)
+    }
+}
+
+impl Default for StateRetention {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A background thread that prunes a scabbard service's previous state roots on an interval
+pub(crate) struct StatePruningTask {
+    shutdown: Sender<()>,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl StatePruningTask {
+    /// Starts pruning the state of the given service; state is first pruned one interval after
+    /// the task is started.
+    pub fn start(
+        retention: &StateRetention,
+        circuit_id: String,
+        service_id: String,
+        state: Arc<Mutex<ScabbardState>>,
+    ) -> Result<Self, InternalError> {
+        let interval = retention.interval;
+        let (shutdown, receiver) = channel();
+
+        let join_handle = thread::Builder::new()
+            .name(format!(
+                "ScabbardStatePruning-{}::{}",
+                circuit_id, service_id
+            ))
+            .spawn(move || loop {
+                match receiver.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => (),
+                    Ok(()) | Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                let result = match state.lock() {
+                    Ok(mut state) => state.prune_state(),
+                    Err(_) => {
+                        error!(
+                            "Unable to prune state of scabbard service {}::{}: state lock \
+                             poisoned",
+                            circuit_id, service_id
+                        );
+                        break;
+                    }
+                };
+
+                match result {
+                    Ok(info) if info.pruned_roots() > 0 => debug!(
+                        "Pruned {} previous state root(s) of scabbard service {}::{}, {} retained",
+                        info.pruned_roots(),
+                        circuit_id,
+                        service_id,
+                        info.retained_roots()
+                    ),
+                    Ok(_) => (),
+                    Err(err) => warn!(
+                        "Unable to prune state of scabbard service {}::{}: {}",
+                        circuit_id, service_id, err
+                    ),
+                }
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(Self {
+            shutdown,
+            join_handle,
+        })
+    }
+
+    /// Stops pruning and waits for the thread to exit.
+    pub fn shutdown(self) -> Result<(), InternalError> {
+        // An error means the thread has already stopped
+        let _ = self.shutdown.send(());
+        self.join_handle
+            .join()
+            .map_err(|_| InternalError::with_message("state pruning thread panicked".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the previous roots beyond the number to keep, or older than the age to keep
+    /// them for, are expired, and that every root is expired by a policy without limits.
+    #[test]
+    fn expired_roots() {
+        let now = SystemTime::now();
+        let replaced_at = [
+            now - Duration::from_secs(300),
+            now - Duration::from_secs(200),
+            now - Duration::from_secs(100),
+            now,
+        ];
+
+        assert_eq!(StateRetention::new().expired(&replaced_at, now), 4);
+        assert_eq!(StateRetention::new().expired(&[], now), 0);
+
+        assert_eq!(
+            StateRetention::new()
+                .with_max_roots(3)
+                .expired(&replaced_at, now),
+            1
+        );
+        assert_eq!(
+            StateRetention::new()
+                .with_max_roots(10)
+                .expired(&replaced_at, now),
+            0
+        );
+        assert_eq!(
+            StateRetention::new()
+                .with_max_age(Duration::from_secs(150))
+                .expired(&replaced_at, now),
+            2
+        );
+
+        // Both limits must be satisfied for a root to be kept
+        let retention = StateRetention::new()
+            .with_max_roots(3)
+            .with_max_age(Duration::from_secs(250));
+        assert_eq!(retention.expired(&replaced_at, now), 1);
+        let retention = StateRetention::new()
+            .with_max_roots(1)
+            .with_max_age(Duration::from_secs(250));
+        assert_eq!(retention.expired(&replaced_at, now), 3);
+    }
+}
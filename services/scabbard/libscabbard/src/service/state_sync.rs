@@ -0,0 +1,431 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! State sync: catching a lagging replica up with its peers by transferring only the state that
+//! differs.
+//!
+//! A scabbard service asks its peers to sync when it starts, such as after an outage, and when a
+//! proposal does not produce the state root it expects. The request holds a digest of the
+//! replica's entries under each address prefix of [`PREFIX_LEN`] characters: in effect, the
+//! hashes of the subtrees of its Merkle-radix tree at that depth. A peer compares them with the
+//! digests of its own current state, and responds with its entries under the prefixes that
+//! differ, along with the transaction receipts committed after the replica's newest receipt.
+//!
+//! Every commit must be agreed on by all of the circuit's members, so the replica only syncs to a
+//! state root that every peer it asked reports: it waits for all of their responses, ignores
+//! responses it did not request, and applies nothing if the peers disagree. A single member
+//! therefore cannot overwrite another replica's state with state the others have not committed.
+//! The replica replaces its entries under the differing prefixes with the peer's, and keeps the
+//! result only if it produces the agreed state root, so a response that is stale, truncated or
+//! altered is never applied. Both sides read their full state to compute the digests, but only
+//! the subtrees that changed are sent, which after a short outage is a small part of the state.
+
+use std::collections::{BTreeMap, HashSet};
+
+use protobuf::{Message, RepeatedField};
+use sha2::{Digest, Sha512};
+use splinter::error::InternalError;
+use transact::protocol::receipt::TransactionReceipt;
+use transact::protos::{FromBytes, IntoBytes};
+use transact::state::StateChange;
+
+use crate::protos::scabbard::{
+    ScabbardMessage, ScabbardMessage_Type, StateDigest, StateEntry, StateSyncRequest,
+    StateSyncResponse,
+};
+
+use super::shared::ScabbardShared;
+use super::state::ScabbardState;
+
+/// The length of the address prefixes that state digests are computed for: the six characters
+/// of a namespace, and one more byte
+const PREFIX_LEN: usize = 8;
+
+/// A replica's request to sync its state, and the responses its peers have sent to it so far
+pub(crate) struct PendingStateSync {
+    base_state_root: String,
+    /// The response of each peer the request was sent to, once it is received
+    responses: BTreeMap<String, Option<StateSyncResponse>>,
+}
+
+impl PendingStateSync {
+    pub(crate) fn new<I>(base_state_root: String, peers: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Self {
+            base_state_root,
+            responses: peers.into_iter().map(|peer| (peer, None)).collect(),
+        }
+    }
+}
+
+/// Sends a request to sync the given replica's state to each of its peers, replacing any request
+/// that is still pending.
+pub(crate) fn request_state_sync(
+    shared: &mut ScabbardShared,
+    state: &ScabbardState,
+) -> Result<(), InternalError> {
+    let request = create_sync_request(state)?;
+    *shared.pending_state_sync_mut() = Some(PendingStateSync::new(
+        request.get_state_root().to_string(),
+        shared.peer_services().iter().cloned(),
+    ));
+
+    let mut msg = ScabbardMessage::new();
+    msg.set_message_type(ScabbardMessage_Type::STATE_SYNC_REQUEST);
+    msg.set_state_sync_request(request);
+    let msg_bytes = msg
+        .write_to_bytes()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    let sender = shared
+        .network_sender()
+        .ok_or_else(|| InternalError::with_message("service is not started".into()))?;
+    for service in shared.peer_services() {
+        sender
+            .send(service, &msg_bytes)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    }
+
+    Ok(())
+}
+
+/// Creates a request to sync the given replica's state.
+pub(crate) fn create_sync_request(
+    state: &ScabbardState,
+) -> Result<StateSyncRequest, InternalError> {
+    let digests = compute_digests(read_entries(state, None)?)
+        .into_iter()
+        .map(|(prefix, digest)| {
+            let mut state_digest = StateDigest::new();
+            state_digest.set_prefix(prefix);
+            state_digest.set_digest(digest);
+            state_digest
+        })
+        .collect();
+
+    let mut request = StateSyncRequest::new();
+    request.set_state_root(state.current_state_root().into());
+    request.set_digests(RepeatedField::from_vec(digests));
+    if let Some(last_receipt_id) = state
+        .last_receipt_id()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+    {
+        request.set_last_receipt_id(last_receipt_id);
+    }
+
+    Ok(request)
+}
+
+/// Creates the response to a replica's request to sync its state with the given state. If the
+/// replica's state root is already the same, the response has no entries; it is still sent, as
+/// the replica waits for every peer's state root.
+pub(crate) fn create_sync_response(
+    request: &StateSyncRequest,
+    state: &ScabbardState,
+) -> Result<StateSyncResponse, InternalError> {
+    if request.get_state_root() == state.current_state_root() {
+        let mut response = StateSyncResponse::new();
+        response.set_base_state_root(request.get_state_root().into());
+        response.set_state_root(state.current_state_root().into());
+        return Ok(response);
+    }
+
+    let entries = read_entries(state, None)?;
+    let digests = compute_digests(entries.clone());
+    let requested_digests = request
+        .get_digests()
+        .iter()
+        .map(|digest| (digest.get_prefix(), digest.get_digest()))
+        .collect::<BTreeMap<_, _>>();
+
+    let prefixes = digests
+        .iter()
+        .filter(|(prefix, digest)| {
+            requested_digests.get(prefix.as_str()) != Some(&digest.as_slice())
+        })
+        .map(|(prefix, _)| prefix.clone())
+        .chain(
+            requested_digests
+                .keys()
+                .filter(|prefix| !digests.contains_key(**prefix))
+                .map(|prefix| prefix.to_string()),
+        )
+        .collect::<HashSet<_>>();
+
+    let entries = entries
+        .into_iter()
+        .filter(|(address, _)| prefixes.contains(prefix_of(address)))
+        .map(|(address, value)| {
+            let mut entry = StateEntry::new();
+            entry.set_address(address);
+            entry.set_value(value);
+            entry
+        })
+        .collect();
+
+    let last_receipt_id = match request.get_last_receipt_id() {
+        "" => None,
+        id => Some(id.to_string()),
+    };
+    let receipts = match state.list_receipts_since(last_receipt_id) {
+        Ok(receipts) => receipts
+            .into_iter()
+            .map(|receipt| receipt.into_bytes())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?,
+        Err(err) => {
+            warn!(
+                "Not syncing transaction receipts after {}: {}",
+                request.get_last_receipt_id(),
+                err
+            );
+            vec![]
+        }
+    };
+
+    let mut response = StateSyncResponse::new();
+    response.set_base_state_root(request.get_state_root().into());
+    response.set_state_root(state.current_state_root().into());
+    response.set_prefixes(RepeatedField::from_vec(prefixes.into_iter().collect()));
+    response.set_entries(RepeatedField::from_vec(entries));
+    response.set_receipts(RepeatedField::from_vec(receipts));
+
+    Ok(response)
+}
+
+/// Records a peer's response to the replica's pending request to sync its state and, once every
+/// peer has responded, applies the responses if they all report the same state root and
+/// receipts. Returns whether the state was changed.
+///
+/// A response to a request the replica did not send to the peer, or a request it has since
+/// replaced, is ignored, as is a response to a request made from a state root other than the
+/// current one.
+pub(crate) fn apply_sync_response(
+    response: &StateSyncResponse,
+    sender: &str,
+    pending: &mut Option<PendingStateSync>,
+    state: &mut ScabbardState,
+) -> Result<bool, InternalError> {
+    match pending.as_mut().and_then(|pending| {
+        if pending.base_state_root == response.get_base_state_root() {
+            pending.responses.get_mut(sender)
+        } else {
+            None
+        }
+    }) {
+        Some(peer_response) => *peer_response = Some(response.clone()),
+        None => {
+            debug!("Ignoring unrequested state sync response from {}", sender);
+            return Ok(false);
+        }
+    }
+
+    let responses = match pending.take() {
+        Some(PendingStateSync { responses, .. }) if responses.values().all(Option::is_some) => {
+            responses.into_iter().filter_map(|(_, response)| response)
+        }
+        not_complete => {
+            *pending = not_complete;
+            return Ok(false);
+        }
+    };
+
+    // The state root does not cover the receipts, so they must match as well
+    let mut response: Option<StateSyncResponse> = None;
+    for peer_response in responses {
+        match &response {
+            None => response = Some(peer_response),
+            Some(response)
+                if response.get_state_root() == peer_response.get_state_root()
+                    && response.get_receipts() == peer_response.get_receipts() => {}
+            Some(_) => {
+                warn!("Not syncing state: peers responded with different state or receipts");
+                return Ok(false);
+            }
+        }
+    }
+    let response = match response {
+        Some(response) => response,
+        None => return Ok(false),
+    };
+
+    if response.get_base_state_root() != state.current_state_root()
+        || response.get_state_root() == state.current_state_root()
+    {
+        return Ok(false);
+    }
+
+    let mut local_entries = BTreeMap::new();
+    for prefix in response.get_prefixes() {
+        local_entries.extend(read_entries(state, Some(prefix))?);
+    }
+
+    let synced_entries = response
+        .get_entries()
+        .iter()
+        .map(|entry| (entry.get_address().to_string(), entry.get_value().to_vec()))
+        .collect::<BTreeMap<_, _>>();
+
+    let state_changes = compute_changes(local_entries, synced_entries);
+
+    let receipts = response
+        .get_receipts()
+        .iter()
+        .map(|bytes| TransactionReceipt::from_bytes(bytes))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    state
+        .apply_synced_changes(response.get_state_root(), state_changes, receipts)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+    Ok(true)
+}
+
+/// Reads the entries of the current state, under the given address prefix if there is one.
+fn read_entries(
+    state: &ScabbardState,
+    prefix: Option<&str>,
+) -> Result<Vec<(String, Vec<u8>)>, InternalError> {
+    state
+        .get_state_with_prefix(prefix)
+        .map_err(|err| InternalError::from_source(Box::new(err)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+/// Computes a digest of the given entries under each address prefix that has any.
+fn compute_digests<I>(entries: I) -> BTreeMap<String, Vec<u8>>
+where
+    I: IntoIterator<Item = (String, Vec<u8>)>,
+{
+    let mut grouped = BTreeMap::<String, BTreeMap<String, Vec<u8>>>::new();
+    for (address, value) in entries {
+        grouped
+            .entry(prefix_of(&address).to_string())
+            .or_default()
+            .insert(address, value);
+    }
+
+    grouped
+        .into_iter()
+        .map(|(prefix, entries)| {
+            let mut hasher = Sha512::new();
+            for (address, value) in entries {
+                hasher.update(address.as_bytes());
+                hasher.update((value.len() as u64).to_be_bytes());
+                hasher.update(&value);
+            }
+            (prefix, hasher.finalize().to_vec())
+        })
+        .collect()
+}
+
+/// Computes the changes that replace the local entries with the synced entries.
+fn compute_changes(
+    local_entries: BTreeMap<String, Vec<u8>>,
+    synced_entries: BTreeMap<String, Vec<u8>>,
+) -> Vec<StateChange> {
+    let deletions = local_entries
+        .keys()
+        .filter(|address| !synced_entries.contains_key(*address))
+        .map(|address| StateChange::Delete {
+            key: address.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    synced_entries
+        .into_iter()
+        .filter(|(address, value)| local_entries.get(address) != Some(value))
+        .map(|(key, value)| StateChange::Set { key, value })
+        .chain(deletions)
+        .collect()
+}
+
+fn prefix_of(address: &str) -> &str {
+    address.get(..PREFIX_LEN).unwrap_or(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS_A1: &str = "abcdef01aa";
+    const ADDRESS_A2: &str = "abcdef01bb";
+    const ADDRESS_B: &str = "abcdef02aa";
+
+    /// Verifies that digests are computed for each address prefix, and only change with the
+    /// entries under their prefix.
+    #[test]
+    fn digests_by_prefix() {
+        let digests = compute_digests(vec![
+            (ADDRESS_A1.to_string(), b"1".to_vec()),
+            (ADDRESS_B.to_string(), b"2".to_vec()),
+            (ADDRESS_A2.to_string(), b"3".to_vec()),
+        ]);
+        assert_eq!(
+            digests.keys().collect::<Vec<_>>(),
+            vec!["abcdef01", "abcdef02"]
+        );
+
+        // The order of the entries does not matter
+        let reordered = compute_digests(vec![
+            (ADDRESS_A2.to_string(), b"3".to_vec()),
+            (ADDRESS_A1.to_string(), b"1".to_vec()),
+            (ADDRESS_B.to_string(), b"2".to_vec()),
+        ]);
+        assert_eq!(digests, reordered);
+
+        let changed = compute_digests(vec![
+            (ADDRESS_A1.to_string(), b"1".to_vec()),
+            (ADDRESS_B.to_string(), b"4".to_vec()),
+            (ADDRESS_A2.to_string(), b"3".to_vec()),
+        ]);
+        assert_eq!(digests["abcdef01"], changed["abcdef01"]);
+        assert_ne!(digests["abcdef02"], changed["abcdef02"]);
+    }
+
+    /// Verifies that only the entries that differ are set, and that local entries missing from
+    /// the synced entries are deleted.
+    #[test]
+    fn changes_from_synced_entries() {
+        let local = vec![
+            (ADDRESS_A1.to_string(), b"1".to_vec()),
+            (ADDRESS_A2.to_string(), b"2".to_vec()),
+        ]
+        .into_iter()
+        .collect();
+        let synced = vec![
+            (ADDRESS_A1.to_string(), b"1".to_vec()),
+            (ADDRESS_B.to_string(), b"3".to_vec()),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            compute_changes(local, synced),
+            vec![
+                StateChange::Set {
+                    key: ADDRESS_B.to_string(),
+                    value: b"3".to_vec(),
+                },
+                StateChange::Delete {
+                    key: ADDRESS_A2.to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::convert::TryFrom;
+use std::time::Duration;
 
 use splinter::error::InvalidArgumentError;
 use splinter::service::ServiceId;
@@ -22,6 +23,7 @@ use crate::store::ConsensusType;
 pub struct ScabbardArguments {
     peers: Vec<ServiceId>,
     consensus: ConsensusType,
+    coordinator_timeout: Option<Duration>,
 }
 
 impl ScabbardArguments {
@@ -29,7 +31,11 @@ impl ScabbardArguments {
         peers: Vec<ServiceId>,
         consensus: ConsensusType,
     ) -> Result<Self, InvalidArgumentError> {
-        Ok(Self { peers, consensus })
+        Ok(Self {
+            peers,
+            consensus,
+            coordinator_timeout: None,
+        })
     }
 
     pub fn peers(&self) -> &Vec<ServiceId> {
@@ -39,12 +45,17 @@ impl ScabbardArguments {
     pub fn consensus(&self) -> &ConsensusType {
         &self.consensus
     }
+
+    pub fn coordinator_timeout(&self) -> Option<Duration> {
+        self.coordinator_timeout
+    }
 }
 
 #[derive(Default)]
 pub struct ScabbardArgumentsBuilder {
     peers: Option<Vec<ServiceId>>,
     consensus: Option<ConsensusType>,
+    coordinator_timeout: Option<Duration>,
 }
 
 impl ScabbardArgumentsBuilder {
@@ -52,6 +63,7 @@ impl ScabbardArgumentsBuilder {
         Self {
             peers: None,
             consensus: None,
+            coordinator_timeout: None,
         }
     }
 
@@ -65,6 +77,11 @@ impl ScabbardArgumentsBuilder {
         self
     }
 
+    pub fn with_coordinator_timeout(mut self, coordinator_timeout: Duration) -> Self {
+        self.coordinator_timeout = Some(coordinator_timeout);
+        self
+    }
+
     pub fn build(self) -> Result<ScabbardArguments, InvalidArgumentError> {
         let peers = self
             .peers
@@ -73,7 +90,16 @@ impl ScabbardArgumentsBuilder {
         // currently defaults to TwoPC if none is provided
         let consensus = self.consensus.unwrap_or(ConsensusType::TwoPC);
 
-        ScabbardArguments::new(peers, consensus)
+        if self.coordinator_timeout == Some(Duration::from_millis(0)) {
+            return Err(InvalidArgumentError::new(
+                "coordinator_timeout",
+                "must be greater than 0",
+            ));
+        }
+
+        let mut arguments = ScabbardArguments::new(peers, consensus)?;
+        arguments.coordinator_timeout = self.coordinator_timeout;
+        Ok(arguments)
     }
 }
 
@@ -83,6 +109,7 @@ impl TryFrom<String> for ConsensusType {
     fn try_from(consensus: String) -> Result<Self, Self::Error> {
         match consensus.as_str() {
             "2PC" => Ok(ConsensusType::TwoPC),
+            "3PC" => Ok(ConsensusType::ThreePC),
             _ => Err(InvalidArgumentError::new(
                 "consensus",
                 "provided consensus type is not supported",
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::convert::TryFrom;
+use std::time::Duration;
 
 use splinter::{
     error::{InternalError, InvalidArgumentError},
@@ -29,14 +30,23 @@ impl ArgumentsConverter<ScabbardArguments, Vec<(String, String)>>
     for ScabbardArgumentsVecConverter
 {
     fn to_right(&self, left: ScabbardArguments) -> Result<Vec<(String, String)>, InternalError> {
-        Ok(vec![(
+        let mut arguments = vec![(
             "peer_services".to_string(),
             left.peers()
                 .iter()
                 .map(|service_id| service_id.to_string())
                 .collect::<Vec<String>>()
                 .join(","),
-        )])
+        )];
+
+        if let Some(coordinator_timeout) = left.coordinator_timeout() {
+            arguments.push((
+                "coordinator_timeout".to_string(),
+                coordinator_timeout.as_millis().to_string(),
+            ));
+        }
+
+        Ok(arguments)
     }
 
     fn to_left(&self, right: Vec<(String, String)>) -> Result<ScabbardArguments, InternalError> {
@@ -58,6 +68,16 @@ impl ArgumentsConverter<ScabbardArguments, Vec<(String, String)>>
                         .map_err(|err| InternalError::from_source(Box::new(err)))?;
                     arg_builder = arg_builder.with_consensus(consensus);
                 }
+                "coordinator_timeout" => {
+                    let millis = value.parse::<u64>().map_err(|err| {
+                        InternalError::from_source(Box::new(InvalidArgumentError::new(
+                            "coordinator_timeout",
+                            format!("invalid value: {}", err),
+                        )))
+                    })?;
+                    arg_builder =
+                        arg_builder.with_coordinator_timeout(Duration::from_millis(millis));
+                }
                 _ => {
                     return Err(InternalError::with_message(format!(
                         "Received unknown argument: {}",
@@ -26,9 +26,10 @@ use splinter::store::command::StoreCommandExecutor;
 use crate::store::ConsensusAction;
 use crate::store::ConsensusContext;
 use crate::store::ConsensusEvent;
+use crate::store::ConsensusType;
 use crate::store::PooledScabbardStoreFactory;
 
-use super::ConsensusActionRunner;
+use super::{ConsensusActionRunner, ThreePhaseCommitAlgorithm};
 
 pub use builder::ConsensusRunnerBuilder;
 use consensus_store_command_factory::ConsensusStoreCommandFactory;
@@ -103,12 +104,33 @@ where
 
         let epoch = context.epoch();
 
-        let algorithm = self.algorithms.get(event.algorithm_name()).ok_or_else(|| {
-            InternalError::with_message(format!("{} is not configured", event.algorithm_name()))
-        })?;
-        let actions = algorithm
-            .event(event, context)
-            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+        let service = store
+            .get_service(service_id)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .ok_or_else(|| {
+                InternalError::with_message(format!("No scabbard service {}", service_id))
+            })?;
+
+        let actions = match service.consensus() {
+            ConsensusType::TwoPC => {
+                let algorithm = self.algorithms.get(event.algorithm_name()).ok_or_else(|| {
+                    InternalError::with_message(format!(
+                        "{} is not configured",
+                        event.algorithm_name()
+                    ))
+                })?;
+                algorithm
+                    .event(event, context)
+                    .map_err(|e| InternalError::from_source(Box::new(e)))?
+            }
+            ConsensusType::ThreePC => {
+                let algorithm = match service.coordinator_timeout() {
+                    Some(timeout) => ThreePhaseCommitAlgorithm::new().with_timeout(timeout),
+                    None => ThreePhaseCommitAlgorithm::new(),
+                };
+                algorithm.event(event, context)?
+            }
+        };
 
         commands.push(
             self.consensus_store_command_factory
@@ -19,6 +19,8 @@ pub mod consensus_action_runner;
 #[cfg(feature = "scabbardv3-consensus-runner")]
 mod consensus_runner;
 mod process;
+#[cfg(feature = "scabbardv3-consensus-runner")]
+mod three_phase_commit;
 mod value;
 
 #[cfg(feature = "scabbardv3-consensus-action-runner")]
@@ -26,4 +28,6 @@ pub use consensus_action_runner::ConsensusActionRunner;
 #[cfg(feature = "scabbardv3-consensus-runner")]
 pub use consensus_runner::{ConsensusRunner, ConsensusRunnerBuilder};
 pub use process::ScabbardProcess;
+#[cfg(feature = "scabbardv3-consensus-runner")]
+pub use three_phase_commit::ThreePhaseCommitAlgorithm;
 pub use value::ScabbardValue;
@@ -0,0 +1,1021 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Three phase commit for scabbard services.
+//!
+//! Three phase commit extends two phase commit with a pre-commit round: once every process has
+//! voted to commit, the coordinator sends a `PreCommit` to the participants, and only commits
+//! once every participant has acknowledged it. An epoch is therefore only committed once every
+//! participant is pre-committed, which lets a participant that is not pre-committed abort on its
+//! own, so that no participant blocks until a crashed coordinator recovers:
+//!
+//! * A participant that has voted to commit but has not received a `PreCommit` aborts when its
+//!   decision timeout expires, and sends an `Abort` to every other process.
+//! * A pre-committed participant whose decision timeout expires runs a termination round: it
+//!   sends a `DecisionRequest` to every other process. A participant that has voted replies with
+//!   a `PreCommit`, pre-committing itself, or with its decision if it has made one. Once every
+//!   other participant has replied with a `PreCommit`, every participant is pre-committed and the
+//!   participant commits.
+//! * Any process that receives an `Abort` for its current epoch before committing aborts. The
+//!   coordinator keeps sending the `PreCommit` to the participants that have not acknowledged it,
+//!   and a participant that has since committed or aborted replies with its decision.
+//!
+//! The algorithm uses the same events, messages, contexts and actions as two phase commit, so
+//! that they are kept in the same tables of the scabbard store. The coordinator tracks the
+//! acknowledgements of the `PreCommit` with its participants' `decision_ack`.
+
+use std::time::{Duration, SystemTime};
+
+use splinter::error::InternalError;
+use splinter::service::ServiceId;
+
+use crate::store::{
+    Action, ConsensusAction, ConsensusContext, ConsensusEvent, Context, Event, Message,
+    Notification, Participant, State,
+};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The three phase commit consensus algorithm
+#[derive(Clone, Debug)]
+pub struct ThreePhaseCommitAlgorithm {
+    timeout: Duration,
+}
+
+impl ThreePhaseCommitAlgorithm {
+    /// Creates the algorithm with a timeout of 30 seconds for votes, acknowledgements and
+    /// decisions.
+    pub fn new() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets how long to wait for votes, acknowledgements and decisions.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Handles an event, returning the actions that must be run, in order.
+    pub fn event(
+        &self,
+        event: ConsensusEvent,
+        context: ConsensusContext,
+    ) -> Result<Vec<ConsensusAction>, InternalError> {
+        let ConsensusEvent::TwoPhaseCommit(event) = event;
+        let ConsensusContext::TwoPhaseCommit(context) = context;
+
+        Ok(self
+            .event_at(event, context, SystemTime::now())?
+            .into_iter()
+            .map(ConsensusAction::TwoPhaseCommit)
+            .collect())
+    }
+
+    fn event_at(
+        &self,
+        event: Event,
+        context: Context,
+        now: SystemTime,
+    ) -> Result<Vec<Action>, InternalError> {
+        if context.this_process() == context.coordinator() {
+            self.coordinator_event(event, context, now)
+        } else {
+            self.participant_event(event, context, now)
+        }
+    }
+
+    fn coordinator_event(
+        &self,
+        event: Event,
+        context: Context,
+        now: SystemTime,
+    ) -> Result<Vec<Action>, InternalError> {
+        let epoch = *context.epoch();
+
+        match (event, context.state().clone()) {
+            (Event::Alarm(), State::WaitingForStart) => Ok(vec![
+                update(context, None),
+                Action::Notify(Notification::RequestForStart()),
+            ]),
+            (Event::Start(value), State::WaitingForStart) => {
+                let participants = processes(&context);
+                let mut actions = vec![update_state(
+                    context,
+                    State::Voting {
+                        vote_timeout_start: now,
+                    },
+                    Some(now + self.timeout),
+                )?];
+                actions.extend(participants.into_iter().map(|process| {
+                    Action::SendMessage(process, Message::VoteRequest(epoch, value.clone()))
+                }));
+                Ok(actions)
+            }
+            (
+                Event::Deliver(from, Message::VoteResponse(vote_epoch, vote)),
+                State::Voting { vote_timeout_start },
+            ) if vote_epoch == epoch => {
+                if !vote {
+                    return self.abort(context, now);
+                }
+
+                let participants =
+                    with_participant(&context, &from, |participant| participant.vote = Some(true));
+                let alarm = Some(vote_timeout_start + self.timeout);
+
+                if participants.iter().all(|p| p.vote == Some(true)) {
+                    let context = context
+                        .into_builder()
+                        .with_participants(participants)
+                        .with_state(State::WaitingForVote)
+                        .build()
+                        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                    Ok(vec![
+                        update(context, alarm),
+                        Action::Notify(Notification::CoordinatorRequestForVote()),
+                    ])
+                } else {
+                    Ok(vec![update_participants(context, participants, alarm)?])
+                }
+            }
+            (Event::Alarm(), State::Voting { .. })
+            | (Event::Alarm(), State::WaitingForVote)
+            | (Event::Vote(false), State::WaitingForVote) => self.abort(context, now),
+            // A participant that has not pre-committed has aborted after its decision timeout
+            (Event::Deliver(_, Message::Abort(abort_epoch)), State::Voting { .. })
+            | (Event::Deliver(_, Message::Abort(abort_epoch)), State::WaitingForVote)
+            | (
+                Event::Deliver(_, Message::Abort(abort_epoch)),
+                State::WaitingForPreCommitAck { .. },
+            ) if abort_epoch == epoch => self.abort(context, now),
+            (Event::Vote(true), State::WaitingForVote) => {
+                let participants = processes(&context);
+                let participants_reset = reset_decision_acks(&context);
+                let context = context
+                    .into_builder()
+                    .with_participants(participants_reset)
+                    .with_state(State::WaitingForPreCommitAck {
+                        ack_timeout_start: now,
+                    })
+                    .build()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                let mut actions = vec![update(context, Some(now + self.timeout))];
+                actions.extend(
+                    participants
+                        .into_iter()
+                        .map(|process| Action::SendMessage(process, Message::PreCommit(epoch))),
+                );
+                Ok(actions)
+            }
+            (
+                Event::Deliver(from, Message::PreCommitAck(ack_epoch)),
+                State::WaitingForPreCommitAck { ack_timeout_start },
+            ) if ack_epoch == epoch => {
+                let participants = with_participant(&context, &from, |participant| {
+                    participant.decision_ack = true
+                });
+
+                if participants.iter().all(|p| p.decision_ack) {
+                    self.commit(context, now)
+                } else {
+                    Ok(vec![update_participants(
+                        context,
+                        participants,
+                        Some(ack_timeout_start + self.timeout),
+                    )?])
+                }
+            }
+            // A participant that has not acknowledged the pre-commit may have aborted, so the
+            // coordinator cannot commit without it; the pre-commit is sent again, and the
+            // participant replies with its decision if it has made one
+            (Event::Alarm(), State::WaitingForPreCommitAck { .. }) => {
+                let unacknowledged = context
+                    .participants()
+                    .iter()
+                    .filter(|participant| !participant.decision_ack)
+                    .map(|participant| participant.process.clone())
+                    .collect::<Vec<_>>();
+                let mut actions = vec![update_state(
+                    context,
+                    State::WaitingForPreCommitAck {
+                        ack_timeout_start: now,
+                    },
+                    Some(now + self.timeout),
+                )?];
+                actions.extend(
+                    unacknowledged
+                        .into_iter()
+                        .map(|process| Action::SendMessage(process, Message::PreCommit(epoch))),
+                );
+                Ok(actions)
+            }
+            (
+                Event::Deliver(from, Message::DecisionRequest(request_epoch)),
+                State::WaitingForPreCommitAck { .. },
+            ) if request_epoch == epoch => {
+                Ok(vec![Action::SendMessage(from, Message::PreCommit(epoch))])
+            }
+            (
+                Event::Deliver(from, Message::DecisionAck(ack_epoch)),
+                State::WaitingForDecisionAck { ack_timeout_start },
+            ) if ack_epoch == epoch => {
+                let participants = with_participant(&context, &from, |participant| {
+                    participant.decision_ack = true
+                });
+
+                if participants.iter().all(|p| p.decision_ack) {
+                    Ok(vec![next_epoch(context, Some(now))?])
+                } else {
+                    Ok(vec![update_participants(
+                        context,
+                        participants,
+                        Some(ack_timeout_start + self.timeout),
+                    )?])
+                }
+            }
+            // Participants that have not acknowledged the decision can request it again
+            (Event::Alarm(), State::WaitingForDecisionAck { .. }) => {
+                Ok(vec![next_epoch(context, Some(now))?])
+            }
+            (Event::Deliver(from, Message::DecisionRequest(request_epoch)), _) => {
+                match decision(&context, request_epoch) {
+                    Some(message) => Ok(vec![Action::SendMessage(from, message)]),
+                    None => Ok(vec![dropped(
+                        &from,
+                        &Message::DecisionRequest(request_epoch),
+                    )]),
+                }
+            }
+            (Event::Deliver(from, message), _) => Ok(vec![dropped(&from, &message)]),
+            // Alarms, starts and votes that are no longer relevant are ignored
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn participant_event(
+        &self,
+        event: Event,
+        context: Context,
+        now: SystemTime,
+    ) -> Result<Vec<Action>, InternalError> {
+        let epoch = *context.epoch();
+        let coordinator = context.coordinator().clone();
+
+        match (event, context.state().clone()) {
+            (
+                Event::Deliver(from, Message::VoteRequest(request_epoch, value)),
+                State::WaitingForVoteRequest,
+            ) if from == coordinator && request_epoch >= epoch => {
+                let context = context
+                    .into_builder()
+                    .with_epoch(request_epoch)
+                    .with_state(State::Voting {
+                        vote_timeout_start: now,
+                    })
+                    .build()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                Ok(vec![
+                    update(context, Some(now + self.timeout)),
+                    Action::Notify(Notification::ParticipantRequestForVote(value)),
+                ])
+            }
+            (Event::Vote(true), State::Voting { .. }) => Ok(vec![
+                update_state(
+                    context,
+                    State::Voted {
+                        vote: true,
+                        decision_timeout_start: now,
+                    },
+                    Some(now + self.timeout),
+                )?,
+                Action::SendMessage(coordinator, Message::VoteResponse(epoch, true)),
+            ]),
+            // A participant that does not vote in time votes to abort
+            (Event::Vote(false), State::Voting { .. }) | (Event::Alarm(), State::Voting { .. }) => {
+                Ok(vec![
+                    next_epoch(context, None)?,
+                    Action::SendMessage(coordinator, Message::VoteResponse(epoch, false)),
+                    Action::Notify(Notification::Abort()),
+                ])
+            }
+            (
+                Event::Deliver(from, Message::PreCommit(pre_commit_epoch)),
+                State::Voted { vote: true, .. },
+            )
+            | (
+                Event::Deliver(from, Message::PreCommit(pre_commit_epoch)),
+                State::PreCommitted { .. },
+            ) if from == coordinator && pre_commit_epoch == epoch => Ok(vec![
+                self.pre_commit(context, now)?,
+                Action::SendMessage(coordinator, Message::PreCommitAck(epoch)),
+            ]),
+            // Without a pre-commit, the epoch cannot have been committed, as that requires every
+            // participant to be pre-committed, so the participant aborts
+            (Event::Alarm(), State::Voted { vote: true, .. }) => {
+                let processes = processes(&context);
+                let mut actions = vec![next_epoch(context, None)?];
+                actions.extend(
+                    processes
+                        .into_iter()
+                        .map(|process| Action::SendMessage(process, Message::Abort(epoch))),
+                );
+                actions.push(Action::Notify(Notification::Abort()));
+                Ok(actions)
+            }
+            // The pre-committed participant asks the other processes for their state, and commits
+            // once every other participant has replied that it is pre-committed
+            (Event::Alarm(), State::PreCommitted { .. }) => {
+                if peers_pre_committed(&context, context.participants()) {
+                    return Ok(vec![
+                        next_epoch(commit_epoch(context)?, None)?,
+                        Action::Notify(Notification::Commit()),
+                    ]);
+                }
+
+                let processes = processes(&context);
+                let mut actions = vec![self.pre_commit(context, now)?];
+                actions.extend(
+                    processes.into_iter().map(|process| {
+                        Action::SendMessage(process, Message::DecisionRequest(epoch))
+                    }),
+                );
+                Ok(actions)
+            }
+            (
+                Event::Deliver(from, Message::DecisionRequest(request_epoch)),
+                State::Voted { vote: true, .. },
+            ) if from != coordinator && request_epoch == epoch => {
+                // The requesting participant is pre-committed, so every process voted to commit
+                Ok(vec![
+                    self.pre_commit(context, now)?,
+                    Action::SendMessage(coordinator, Message::PreCommitAck(epoch)),
+                    Action::SendMessage(from, Message::PreCommit(epoch)),
+                ])
+            }
+            (
+                Event::Deliver(from, Message::DecisionRequest(request_epoch)),
+                State::PreCommitted { .. },
+            ) if from != coordinator && request_epoch == epoch => {
+                Ok(vec![Action::SendMessage(from, Message::PreCommit(epoch))])
+            }
+            (
+                Event::Deliver(from, Message::PreCommit(pre_commit_epoch)),
+                State::PreCommitted {
+                    decision_timeout_start,
+                },
+            ) if pre_commit_epoch == epoch => {
+                let participants = with_participant(&context, &from, |participant| {
+                    participant.decision_ack = true
+                });
+
+                if peers_pre_committed(&context, &participants) {
+                    Ok(vec![
+                        next_epoch(commit_epoch(context)?, None)?,
+                        Action::Notify(Notification::Commit()),
+                    ])
+                } else {
+                    Ok(vec![update_participants(
+                        context,
+                        participants,
+                        Some(decision_timeout_start + self.timeout),
+                    )?])
+                }
+            }
+            (
+                Event::Deliver(from, Message::Commit(decision_epoch)),
+                State::Voted { vote: true, .. },
+            ) if from == coordinator && decision_epoch == epoch => Ok(vec![
+                next_epoch(commit_epoch(context)?, None)?,
+                Action::SendMessage(coordinator, Message::DecisionAck(epoch)),
+                Action::Notify(Notification::Commit()),
+            ]),
+            (Event::Deliver(_, Message::Commit(decision_epoch)), State::PreCommitted { .. })
+                if decision_epoch == epoch =>
+            {
+                Ok(vec![
+                    next_epoch(commit_epoch(context)?, None)?,
+                    Action::SendMessage(coordinator, Message::DecisionAck(epoch)),
+                    Action::Notify(Notification::Commit()),
+                ])
+            }
+            // An abort comes from the coordinator, or from a participant that had not
+            // pre-committed, so the epoch cannot have been committed
+            (Event::Deliver(_, Message::Abort(abort_epoch)), State::Voting { .. })
+            | (Event::Deliver(_, Message::Abort(abort_epoch)), State::Voted { .. })
+            | (Event::Deliver(_, Message::Abort(abort_epoch)), State::PreCommitted { .. })
+                if abort_epoch == epoch =>
+            {
+                Ok(vec![
+                    next_epoch(context, None)?,
+                    Action::Notify(Notification::Abort()),
+                ])
+            }
+            // The participant has already committed, possibly after its decision timeout, but the
+            // coordinator is still waiting for its acknowledgement
+            (Event::Deliver(from, Message::Commit(decision_epoch)), _)
+                if from == coordinator && decision_epoch < epoch =>
+            {
+                Ok(vec![Action::SendMessage(
+                    coordinator,
+                    Message::DecisionAck(decision_epoch),
+                )])
+            }
+            // The participant has already decided, but the coordinator is still waiting for its
+            // acknowledgement of the pre-commit, or another participant is still terminating
+            (Event::Deliver(from, Message::PreCommit(request_epoch)), _)
+            | (Event::Deliver(from, Message::DecisionRequest(request_epoch)), _)
+                if request_epoch < epoch =>
+            {
+                match decision(&context, request_epoch) {
+                    Some(Message::Commit(_)) if from == coordinator => {
+                        Ok(vec![Action::SendMessage(
+                            from,
+                            Message::PreCommitAck(request_epoch),
+                        )])
+                    }
+                    Some(message) => Ok(vec![Action::SendMessage(from, message)]),
+                    None => Ok(vec![]),
+                }
+            }
+            // The participant has already aborted
+            (Event::Deliver(_, Message::Abort(abort_epoch)), _) if abort_epoch < epoch => {
+                Ok(vec![])
+            }
+            (Event::Deliver(from, message), _) => Ok(vec![dropped(&from, &message)]),
+            // Alarms and votes that are no longer relevant are ignored
+            _ => Ok(vec![]),
+        }
+    }
+
+    /// Moves the participant to the pre-committed state, forgetting which other participants
+    /// have replied that they are pre-committed.
+    fn pre_commit(&self, context: Context, now: SystemTime) -> Result<Action, InternalError> {
+        let participants = reset_decision_acks(&context);
+        let context = context
+            .into_builder()
+            .with_participants(participants)
+            .with_state(State::PreCommitted {
+                decision_timeout_start: now,
+            })
+            .build()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        Ok(update(context, Some(now + self.timeout)))
+    }
+
+    fn abort(&self, context: Context, now: SystemTime) -> Result<Vec<Action>, InternalError> {
+        let epoch = *context.epoch();
+        let participants = processes(&context);
+
+        let mut actions = vec![next_epoch(context, Some(now))?];
+        actions.extend(
+            participants
+                .into_iter()
+                .map(|process| Action::SendMessage(process, Message::Abort(epoch))),
+        );
+        actions.push(Action::Notify(Notification::Abort()));
+        Ok(actions)
+    }
+
+    fn commit(&self, context: Context, now: SystemTime) -> Result<Vec<Action>, InternalError> {
+        let epoch = *context.epoch();
+        let participants = processes(&context);
+        let participants_reset = reset_decision_acks(&context);
+
+        let context = commit_epoch(context)?
+            .into_builder()
+            .with_participants(participants_reset)
+            .with_state(State::WaitingForDecisionAck {
+                ack_timeout_start: now,
+            })
+            .build()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let mut actions = vec![update(context, Some(now + self.timeout))];
+        actions.extend(
+            participants
+                .into_iter()
+                .map(|process| Action::SendMessage(process, Message::Commit(epoch))),
+        );
+        actions.push(Action::Notify(Notification::Commit()));
+        Ok(actions)
+    }
+}
+
+impl Default for ThreePhaseCommitAlgorithm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn update(context: Context, alarm: Option<SystemTime>) -> Action {
+    Action::Update(ConsensusContext::TwoPhaseCommit(context), alarm)
+}
+
+fn update_state(
+    context: Context,
+    state: State,
+    alarm: Option<SystemTime>,
+) -> Result<Action, InternalError> {
+    let context = context
+        .into_builder()
+        .with_state(state)
+        .build()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    Ok(update(context, alarm))
+}
+
+fn update_participants(
+    context: Context,
+    participants: Vec<Participant>,
+    alarm: Option<SystemTime>,
+) -> Result<Action, InternalError> {
+    let context = context
+        .into_builder()
+        .with_participants(participants)
+        .build()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    Ok(update(context, alarm))
+}
+
+/// Records the context's epoch as the last one that was committed.
+fn commit_epoch(context: Context) -> Result<Context, InternalError> {
+    let epoch = *context.epoch();
+    context
+        .into_builder()
+        .with_last_commit_epoch(epoch)
+        .build()
+        .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+/// Moves the context to the next epoch. The coordinator waits for the next value to agree on,
+/// which is requested when the given alarm goes off, and participants wait for its vote request.
+fn next_epoch(context: Context, alarm: Option<SystemTime>) -> Result<Action, InternalError> {
+    let state = if context.this_process() == context.coordinator() {
+        State::WaitingForStart
+    } else {
+        State::WaitingForVoteRequest
+    };
+    let epoch = *context.epoch() + 1;
+    let participants = context
+        .participants()
+        .iter()
+        .map(|participant| Participant {
+            process: participant.process.clone(),
+            vote: None,
+            decision_ack: false,
+        })
+        .collect();
+
+    let context = context
+        .into_builder()
+        .with_epoch(epoch)
+        .with_participants(participants)
+        .with_state(state)
+        .build()
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    Ok(update(context, alarm))
+}
+
+/// Returns the decision the coordinator made in the given epoch, if it is known.
+fn decision(context: &Context, epoch: u64) -> Option<Message> {
+    match context.last_commit_epoch() {
+        Some(last_commit_epoch) if last_commit_epoch == epoch => Some(Message::Commit(epoch)),
+        // Older decisions are not kept
+        Some(last_commit_epoch) if last_commit_epoch > epoch => None,
+        // Every epoch after the last commit and before the current one was aborted
+        _ if epoch < *context.epoch() => Some(Message::Abort(epoch)),
+        _ => None,
+    }
+}
+
+/// Returns whether every participant other than this one and the coordinator is marked as
+/// pre-committed.
+fn peers_pre_committed(context: &Context, participants: &[Participant]) -> bool {
+    participants
+        .iter()
+        .filter(|participant| &participant.process != context.coordinator())
+        .all(|participant| participant.decision_ack)
+}
+
+fn processes(context: &Context) -> Vec<ServiceId> {
+    context
+        .participants()
+        .iter()
+        .map(|participant| participant.process.clone())
+        .collect()
+}
+
+fn reset_decision_acks(context: &Context) -> Vec<Participant> {
+    context
+        .participants()
+        .iter()
+        .map(|participant| Participant {
+            decision_ack: false,
+            ..participant.clone()
+        })
+        .collect()
+}
+
+fn with_participant<F>(context: &Context, process: &ServiceId, f: F) -> Vec<Participant>
+where
+    F: Fn(&mut Participant),
+{
+    context
+        .participants()
+        .iter()
+        .cloned()
+        .map(|mut participant| {
+            if &participant.process == process {
+                f(&mut participant);
+            }
+            participant
+        })
+        .collect()
+}
+
+fn dropped(from: &ServiceId, message: &Message) -> Action {
+    Action::Notify(Notification::MessageDropped(format!(
+        "three phase commit message {:?} from {} was not expected",
+        message, from
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::store::ContextBuilder;
+
+    fn service_id(id: &str) -> ServiceId {
+        ServiceId::new(id).expect("Unable to create service id")
+    }
+
+    fn context(this_process: &str, epoch: u64, state: State) -> Context {
+        let coordinator = service_id("a000");
+        let participants = ["a000", "b000", "c000"]
+            .iter()
+            .filter(|id| **id != this_process)
+            .map(|id| Participant {
+                process: service_id(id),
+                vote: None,
+                decision_ack: false,
+            })
+            .collect();
+
+        ContextBuilder::new()
+            .with_coordinator(&coordinator)
+            .with_epoch(epoch)
+            .with_state(state)
+            .with_this_process(&service_id(this_process))
+            .with_participants(participants)
+            .build()
+            .expect("Unable to build context")
+    }
+
+    fn updated_context(actions: &[Action]) -> Context {
+        match actions.first() {
+            Some(Action::Update(ConsensusContext::TwoPhaseCommit(context), _)) => context.clone(),
+            action => panic!("Expected a context update, got {:?}", action),
+        }
+    }
+
+    /// Verify that the coordinator requests votes, pre-commits once every participant and the
+    /// coordinator have voted to commit, and commits once every participant has acknowledged the
+    /// pre-commit.
+    #[test]
+    fn coordinator_commit() {
+        let algorithm = ThreePhaseCommitAlgorithm::new();
+        let now = SystemTime::now();
+
+        let actions = algorithm
+            .event_at(
+                Event::Start(b"value".to_vec()),
+                context("a000", 1, State::WaitingForStart),
+                now,
+            )
+            .expect("Unable to handle start");
+        assert_eq!(actions.len(), 3);
+        assert_eq!(
+            actions[1],
+            Action::SendMessage(
+                service_id("b000"),
+                Message::VoteRequest(1, b"value".to_vec())
+            )
+        );
+        let context = updated_context(&actions);
+
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("b000"), Message::VoteResponse(1, true)),
+                context,
+                now,
+            )
+            .expect("Unable to handle vote response");
+        assert_eq!(actions.len(), 1);
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("c000"), Message::VoteResponse(1, true)),
+                updated_context(&actions),
+                now,
+            )
+            .expect("Unable to handle vote response");
+        assert_eq!(
+            actions[1],
+            Action::Notify(Notification::CoordinatorRequestForVote())
+        );
+        let context = updated_context(&actions);
+        assert_eq!(context.state(), &State::WaitingForVote);
+
+        let actions = algorithm
+            .event_at(Event::Vote(true), context, now)
+            .expect("Unable to handle vote");
+        assert_eq!(
+            &actions[1..],
+            &[
+                Action::SendMessage(service_id("b000"), Message::PreCommit(1)),
+                Action::SendMessage(service_id("c000"), Message::PreCommit(1)),
+            ]
+        );
+        let context = updated_context(&actions);
+
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("b000"), Message::PreCommitAck(1)),
+                context,
+                now,
+            )
+            .expect("Unable to handle pre-commit ack");
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("c000"), Message::PreCommitAck(1)),
+                updated_context(&actions),
+                now,
+            )
+            .expect("Unable to handle pre-commit ack");
+        assert_eq!(
+            &actions[1..],
+            &[
+                Action::SendMessage(service_id("b000"), Message::Commit(1)),
+                Action::SendMessage(service_id("c000"), Message::Commit(1)),
+                Action::Notify(Notification::Commit()),
+            ]
+        );
+        let context = updated_context(&actions);
+        assert_eq!(context.last_commit_epoch(), Some(1));
+        assert!(context.participants().iter().all(|p| !p.decision_ack));
+    }
+
+    /// Verify that the coordinator aborts when a participant votes to abort.
+    #[test]
+    fn coordinator_abort() {
+        let algorithm = ThreePhaseCommitAlgorithm::new();
+        let now = SystemTime::now();
+
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("b000"), Message::VoteResponse(1, false)),
+                context(
+                    "a000",
+                    1,
+                    State::Voting {
+                        vote_timeout_start: now,
+                    },
+                ),
+                now,
+            )
+            .expect("Unable to handle vote response");
+
+        let context = updated_context(&actions);
+        assert_eq!(context.epoch(), &2);
+        assert_eq!(context.state(), &State::WaitingForStart);
+        assert_eq!(actions.last(), Some(&Action::Notify(Notification::Abort())));
+    }
+
+    /// Verify that a participant that has voted, but not received a pre-commit, aborts when its
+    /// alarm goes off and tells the other processes, while a participant that has received a
+    /// pre-commit asks the other participants whether they are pre-committed, and commits once
+    /// they all are.
+    #[test]
+    fn participant_decision_timeout() {
+        let algorithm = ThreePhaseCommitAlgorithm::new();
+        let now = SystemTime::now();
+        let voted = State::Voted {
+            vote: true,
+            decision_timeout_start: now,
+        };
+
+        let actions = algorithm
+            .event_at(Event::Alarm(), context("b000", 1, voted.clone()), now)
+            .expect("Unable to handle alarm");
+        assert_eq!(
+            &actions[1..],
+            &[
+                Action::SendMessage(service_id("a000"), Message::Abort(1)),
+                Action::SendMessage(service_id("c000"), Message::Abort(1)),
+                Action::Notify(Notification::Abort()),
+            ]
+        );
+        let context_after_abort = updated_context(&actions);
+        assert_eq!(context_after_abort.epoch(), &2);
+        assert_eq!(context_after_abort.last_commit_epoch(), None);
+
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("a000"), Message::PreCommit(1)),
+                context("b000", 1, voted),
+                now,
+            )
+            .expect("Unable to handle pre-commit");
+        assert_eq!(
+            actions[1],
+            Action::SendMessage(service_id("a000"), Message::PreCommitAck(1))
+        );
+
+        let actions = algorithm
+            .event_at(Event::Alarm(), updated_context(&actions), now)
+            .expect("Unable to handle alarm");
+        assert_eq!(
+            &actions[1..],
+            &[
+                Action::SendMessage(service_id("a000"), Message::DecisionRequest(1)),
+                Action::SendMessage(service_id("c000"), Message::DecisionRequest(1)),
+            ]
+        );
+
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("c000"), Message::PreCommit(1)),
+                updated_context(&actions),
+                now,
+            )
+            .expect("Unable to handle pre-commit");
+        assert_eq!(actions[1], Action::Notify(Notification::Commit()));
+        let context = updated_context(&actions);
+        assert_eq!(context.last_commit_epoch(), Some(1));
+        assert_eq!(context.epoch(), &2);
+        assert_eq!(context.state(), &State::WaitingForVoteRequest);
+
+        // The coordinator's pre-commit is sent again after the participant has committed
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("a000"), Message::PreCommit(1)),
+                context.clone(),
+                now,
+            )
+            .expect("Unable to handle pre-commit");
+        assert_eq!(
+            actions,
+            vec![Action::SendMessage(
+                service_id("a000"),
+                Message::PreCommitAck(1)
+            )]
+        );
+
+        // The coordinator's commit arrives after the participant has committed
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("a000"), Message::Commit(1)),
+                context,
+                now,
+            )
+            .expect("Unable to handle commit");
+        assert_eq!(
+            actions,
+            vec![Action::SendMessage(
+                service_id("a000"),
+                Message::DecisionAck(1)
+            )]
+        );
+    }
+
+    /// Verify that a participant that has voted pre-commits when a pre-committed participant asks
+    /// for its state, as every process must have voted to commit.
+    #[test]
+    fn participant_termination_request() {
+        let algorithm = ThreePhaseCommitAlgorithm::new();
+        let now = SystemTime::now();
+
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("b000"), Message::DecisionRequest(1)),
+                context(
+                    "c000",
+                    1,
+                    State::Voted {
+                        vote: true,
+                        decision_timeout_start: now,
+                    },
+                ),
+                now,
+            )
+            .expect("Unable to handle decision request");
+        assert_eq!(
+            &actions[1..],
+            &[
+                Action::SendMessage(service_id("a000"), Message::PreCommitAck(1)),
+                Action::SendMessage(service_id("b000"), Message::PreCommit(1)),
+            ]
+        );
+        assert!(matches!(
+            updated_context(&actions).state(),
+            State::PreCommitted { .. }
+        ));
+    }
+
+    /// Verify that the processes abort consistently when the coordinator crashes after every
+    /// process has voted to commit, but before any participant has received its pre-commit.
+    ///
+    /// 1. The participants' alarms go off while they wait for the pre-commit, and they abort
+    /// 2. The coordinator recovers, having recorded that it sent the pre-commit, and sends it
+    ///    again when its alarm goes off instead of committing
+    /// 3. The participants reply that they have aborted, and the coordinator aborts
+    #[test]
+    fn coordinator_crash_before_pre_commit() {
+        let algorithm = ThreePhaseCommitAlgorithm::new();
+        let now = SystemTime::now();
+
+        // 1. The participants' alarms go off while they wait for the pre-commit, and they abort
+        let mut participant_contexts = vec![];
+        for participant in ["b000", "c000"].iter() {
+            let actions = algorithm
+                .event_at(
+                    Event::Alarm(),
+                    context(
+                        participant,
+                        1,
+                        State::Voted {
+                            vote: true,
+                            decision_timeout_start: now,
+                        },
+                    ),
+                    now,
+                )
+                .expect("Unable to handle alarm");
+            assert_eq!(actions.last(), Some(&Action::Notify(Notification::Abort())));
+            participant_contexts.push(updated_context(&actions));
+        }
+
+        // 2. The coordinator recovers, having recorded that it sent the pre-commit, and sends it
+        //    again when its alarm goes off instead of committing
+        let actions = algorithm
+            .event_at(
+                Event::Alarm(),
+                context(
+                    "a000",
+                    1,
+                    State::WaitingForPreCommitAck {
+                        ack_timeout_start: now,
+                    },
+                ),
+                now,
+            )
+            .expect("Unable to handle alarm");
+        assert_eq!(
+            &actions[1..],
+            &[
+                Action::SendMessage(service_id("b000"), Message::PreCommit(1)),
+                Action::SendMessage(service_id("c000"), Message::PreCommit(1)),
+            ]
+        );
+        let coordinator_context = updated_context(&actions);
+        assert_eq!(coordinator_context.last_commit_epoch(), None);
+
+        // 3. The participants reply that they have aborted, and the coordinator aborts
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("a000"), Message::PreCommit(1)),
+                participant_contexts[0].clone(),
+                now,
+            )
+            .expect("Unable to handle pre-commit");
+        assert_eq!(
+            actions,
+            vec![Action::SendMessage(service_id("a000"), Message::Abort(1))]
+        );
+
+        let actions = algorithm
+            .event_at(
+                Event::Deliver(service_id("b000"), Message::Abort(1)),
+                coordinator_context,
+                now,
+            )
+            .expect("Unable to handle abort");
+        let coordinator_context = updated_context(&actions);
+        assert_eq!(coordinator_context.epoch(), &2);
+        assert_eq!(coordinator_context.last_commit_epoch(), None);
+        assert_eq!(actions.last(), Some(&Action::Notify(Notification::Abort())));
+    }
+}
@@ -48,11 +48,17 @@ where
         service: FullyQualifiedServiceId,
         arguments: Self::Arguments,
     ) -> Result<Box<dyn StoreCommand<Context = K>>, InternalError> {
-        let built_service = ScabbardServiceBuilder::default()
+        let mut builder = ScabbardServiceBuilder::default()
             .with_service_id(&service)
             .with_peers(arguments.peers())
             .with_consensus(arguments.consensus())
-            .with_status(&ServiceStatus::Prepared)
+            .with_status(&ServiceStatus::Prepared);
+
+        if let Some(coordinator_timeout) = arguments.coordinator_timeout() {
+            builder = builder.with_coordinator_timeout(coordinator_timeout);
+        }
+
+        let built_service = builder
             .build()
             .map_err(|err| InternalError::from_source(Box::new(err)))?;
 
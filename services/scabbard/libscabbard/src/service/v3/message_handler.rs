@@ -21,8 +21,8 @@ use splinter::{
 use crate::protocol::v3::{
     message::ScabbardMessage,
     two_phase_commit::{
-        Abort, Commit, DecisionAck, DecisionRequest, TwoPhaseCommitMessage, VoteRequest,
-        VoteResponse,
+        Abort, Commit, DecisionAck, DecisionRequest, PreCommit, PreCommitAck,
+        TwoPhaseCommitMessage, VoteRequest, VoteResponse,
     },
 };
 use crate::protos::FromBytes as _;
@@ -69,7 +69,8 @@ impl MessageHandler for ScabbardMessageHandler {
 
         match message {
             ScabbardMessage::ConsensusMessage(msg_bytes) => match service.consensus() {
-                ConsensusType::TwoPC => {
+                // Three phase commit extends the two phase commit messages
+                ConsensusType::TwoPC | ConsensusType::ThreePC => {
                     let message = TwoPhaseCommitMessage::from_bytes(&msg_bytes)
                         .map_err(|e| InternalError::from_source(Box::new(e)))?;
                     let (_, from_service) = from_service.deconstruct();
@@ -108,5 +109,7 @@ fn into_store_msg(msg: TwoPhaseCommitMessage) -> Message {
             Message::DecisionRequest(epoch)
         }
         TwoPhaseCommitMessage::DecisionAck(DecisionAck { epoch }) => Message::DecisionAck(epoch),
+        TwoPhaseCommitMessage::PreCommit(PreCommit { epoch }) => Message::PreCommit(epoch),
+        TwoPhaseCommitMessage::PreCommitAck(PreCommitAck { epoch }) => Message::PreCommitAck(epoch),
     }
 }
@@ -0,0 +1,267 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The format of scabbard state snapshots.
+//!
+//! A [`Snapshot`] is a JSON file holding every entry of a scabbard service's state at a state
+//! root, along with the transaction receipts the service had committed up to that root. The
+//! entries can be checked against the state root by restoring them, so a snapshot that has been
+//! altered or truncated is not accepted.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use transact::protocol::receipt::TransactionReceipt;
+use transact::protos::{FromBytes, IntoBytes};
+
+use crate::hex::{parse_hex, to_hex};
+
+/// The version of the snapshot format written by this library
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// A scabbard service's state and transaction receipts at a state root
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Snapshot {
+    version: u32,
+    circuit_id: String,
+    service_id: String,
+    state_root: String,
+    /// Seconds since the UNIX epoch
+    created_at: u64,
+    /// The state entries, as pairs of an address and a hex-encoded value
+    state: Vec<(String, String)>,
+    /// The hex-encoded transaction receipts, oldest first
+    receipts: Vec<String>,
+}
+
+impl Snapshot {
+    /// Creates a snapshot of the given service's state entries and receipts at a state root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if a receipt could not be encoded.
+    pub fn new(
+        circuit_id: &str,
+        service_id: &str,
+        state_root: &str,
+        created_at: u64,
+        state: Vec<(String, Vec<u8>)>,
+        receipts: Vec<TransactionReceipt>,
+    ) -> io::Result<Self> {
+        Ok(Self {
+            version: SNAPSHOT_VERSION,
+            circuit_id: circuit_id.to_string(),
+            service_id: service_id.to_string(),
+            state_root: state_root.to_string(),
+            created_at,
+            state: state
+                .into_iter()
+                .map(|(address, value)| (address, to_hex(&value)))
+                .collect(),
+            receipts: receipts
+                .into_iter()
+                .map(|receipt| {
+                    receipt
+                        .into_bytes()
+                        .map(|bytes| to_hex(&bytes))
+                        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+                })
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Reads a snapshot from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file could not be read, is not a snapshot, or was written
+    /// in an unsupported version of the format.
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let snapshot: Self = serde_json::from_slice(&fs::read(path)?)?;
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "unsupported snapshot version {}, expected {}",
+                    snapshot.version, SNAPSHOT_VERSION
+                ),
+            ));
+        }
+
+        Ok(snapshot)
+    }
+
+    /// Writes the snapshot to a file. The snapshot is written to a temporary file that replaces
+    /// the given one once it is complete, so a partially written snapshot is never read.
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        fs::write(&tmp_path, serde_json::to_vec(self)?)?;
+        fs::File::open(&tmp_path)?.sync_all()?;
+        fs::rename(tmp_path, path)
+    }
+
+    pub fn circuit_id(&self) -> &str {
+        &self.circuit_id
+    }
+
+    pub fn service_id(&self) -> &str {
+        &self.service_id
+    }
+
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    /// Decodes the state entries of the snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if a value is not valid hex.
+    pub fn state_entries(&self) -> io::Result<Vec<(String, Vec<u8>)>> {
+        self.state
+            .iter()
+            .map(|(address, value)| {
+                parse_hex(value)
+                    .map(|value| (address.clone(), value))
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))
+            })
+            .collect()
+    }
+
+    /// Decodes the transaction receipts of the snapshot, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if a receipt is not valid hex or could not be parsed.
+    pub fn receipts(&self) -> io::Result<Vec<TransactionReceipt>> {
+        self.receipts
+            .iter()
+            .map(|receipt| {
+                let bytes = parse_hex(receipt)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+                TransactionReceipt::from_bytes(&bytes)
+                    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+            })
+            .collect()
+    }
+
+    /// Returns a summary of the snapshot, which was written to the given file.
+    pub fn info(&self, path: &Path) -> SnapshotInfo {
+        SnapshotInfo {
+            file: path.display().to_string(),
+            state_root: self.state_root.clone(),
+            created_at: self.created_at,
+            entries: self.state.len() as u64,
+            receipts: self.receipts.len() as u64,
+        }
+    }
+}
+
+/// A summary of a snapshot that was written or restored
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    file: String,
+    state_root: String,
+    /// Seconds since the UNIX epoch
+    created_at: u64,
+    entries: u64,
+    receipts: u64,
+}
+
+impl SnapshotInfo {
+    /// Returns the path of the snapshot file on the node that wrote it.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    pub fn created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    /// Returns the number of state entries in the snapshot.
+    pub fn entries(&self) -> u64 {
+        self.entries
+    }
+
+    /// Returns the number of transaction receipts in the snapshot.
+    pub fn receipts(&self) -> u64 {
+        self.receipts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tempfile::TempDir;
+    use transact::protocol::receipt::TransactionResult;
+
+    /// Verify that a snapshot written to a file is read back unchanged, with its entries and
+    /// receipts decoded, and that a snapshot of an unsupported version is rejected.
+    #[test]
+    fn snapshot_round_trip() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let path = temp_dir.path().join("abcde-01234-a000-1.snapshot");
+
+        let receipt = TransactionReceipt {
+            transaction_id: "ab".into(),
+            transaction_result: TransactionResult::Valid {
+                state_changes: vec![],
+                events: vec![],
+                data: vec![],
+            },
+        };
+        let snapshot = Snapshot::new(
+            "abcde-01234",
+            "a000",
+            "0123",
+            1,
+            vec![("abcdef".into(), b"value".to_vec())],
+            vec![receipt.clone()],
+        )
+        .expect("Failed to create snapshot");
+        snapshot.write(&path).expect("Failed to write snapshot");
+
+        let read = Snapshot::read(&path).expect("Failed to read snapshot");
+        assert_eq!(read, snapshot);
+        assert_eq!(
+            read.state_entries().expect("Failed to decode entries"),
+            vec![("abcdef".to_string(), b"value".to_vec())]
+        );
+        assert_eq!(
+            read.receipts().expect("Failed to decode receipts"),
+            vec![receipt]
+        );
+
+        let info = read.info(&path);
+        assert_eq!(info.state_root(), "0123");
+        assert_eq!(info.entries(), 1);
+        assert_eq!(info.receipts(), 1);
+
+        let mut unsupported = snapshot;
+        unsupported.version = SNAPSHOT_VERSION + 1;
+        unsupported.write(&path).expect("Failed to write snapshot");
+        assert!(Snapshot::read(&path).is_err());
+    }
+}
@@ -0,0 +1,440 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Merkle proofs of the values in a scabbard service's state.
+//!
+//! Scabbard state is a Merkle-Radix tree: each address is split into tokens of two hex
+//! characters, and each token selects a child of the node at the previous level. A node is hashed
+//! from its value and the hashes of its children, so the state root hash commits to every value
+//! in state.
+//!
+//! A [`StateProof`] contains the nodes on the path from the root to an address. Given a state root
+//! hash obtained from a trusted source, [`StateProof::verify`] checks that the value in the proof
+//! is the value at the address in that state, or that the address is not set, without trusting the
+//! node that produced the proof.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha512};
+use splinter::error::InternalError;
+
+use crate::hex::{parse_hex, to_hex};
+
+/// The number of hex characters in each token of an address.
+const TOKEN_SIZE: usize = 2;
+
+/// A proof of the value at an address in a scabbard service's state, at a given state root.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProof {
+    state_root: String,
+    address: String,
+    value: Option<Vec<u8>>,
+    nodes: Vec<StateProofNode>,
+}
+
+impl StateProof {
+    /// Generates the proof for an address from every entry in the state with the given root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InternalError`] if an entry's address is not made up of whole tokens, or if
+    /// the given entries do not produce the given state root.
+    pub fn generate<I>(entries: I, state_root: &str, address: &str) -> Result<Self, InternalError>
+    where
+        I: IntoIterator<Item = (String, Vec<u8>)>,
+    {
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut nodes = vec![];
+        build_node(&entries, 0, Some(address), &mut nodes)?;
+        nodes.reverse();
+
+        let value = if nodes.len() * TOKEN_SIZE == address.len() + TOKEN_SIZE {
+            nodes.last().and_then(|node| node.value.clone())
+        } else {
+            None
+        };
+
+        let proof = Self {
+            state_root: state_root.to_string(),
+            address: address.to_string(),
+            value,
+            nodes,
+        };
+
+        proof.verify(state_root).map_err(|err| {
+            InternalError::with_message(format!(
+                "Generated proof for {} does not match state root {}: {}",
+                address, state_root, err
+            ))
+        })?;
+
+        Ok(proof)
+    }
+
+    /// Returns the state root hash the proof was generated at
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    /// Returns the address the proof is for
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// Returns the value at the address, or `None` if the proof shows the address is not set
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_deref()
+    }
+
+    /// Returns the nodes on the path from the root towards the address
+    pub fn nodes(&self) -> &[StateProofNode] {
+        &self.nodes
+    }
+
+    /// Verifies the proof against a trusted state root hash.
+    ///
+    /// On success, the proof's value is the value at its address in the state with the given
+    /// root; if the value is `None`, the address is not set in that state.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`InvalidStateProofError`] if the proof is not for the given state root, or if
+    /// its nodes do not prove its value.
+    pub fn verify(&self, state_root: &str) -> Result<(), InvalidStateProofError> {
+        if self.state_root != state_root {
+            return Err(InvalidStateProofError::new(format!(
+                "proof is for state root {}, not {}",
+                self.state_root, state_root
+            )));
+        }
+
+        parse_hex(&self.address).map_err(|err| {
+            InvalidStateProofError::new(format!("invalid address {}: {}", self.address, err))
+        })?;
+
+        if self.nodes.is_empty() {
+            return Err(InvalidStateProofError::new("proof has no nodes".into()));
+        }
+
+        let mut expected_hash = self.state_root.clone();
+        for (depth, node) in self.nodes.iter().enumerate() {
+            let node_hash = node.hash().map_err(|err| {
+                InvalidStateProofError::new(format!("unable to hash node: {}", err))
+            })?;
+            if node_hash != expected_hash {
+                return Err(InvalidStateProofError::new(format!(
+                    "node at depth {} does not match hash {}",
+                    depth, expected_hash
+                )));
+            }
+
+            let is_last = depth + 1 == self.nodes.len();
+            let start = depth * TOKEN_SIZE;
+            match self.address.get(start..start + TOKEN_SIZE) {
+                // This is the node at the address itself
+                None => {
+                    if !is_last {
+                        return Err(InvalidStateProofError::new(
+                            "proof contains nodes below the address".into(),
+                        ));
+                    }
+                    if node.value != self.value {
+                        return Err(InvalidStateProofError::new(
+                            "value does not match the value at the address".into(),
+                        ));
+                    }
+                }
+                Some(token) => match node.children.get(token) {
+                    Some(child_hash) => {
+                        if is_last {
+                            return Err(InvalidStateProofError::new(format!(
+                                "proof is missing the node at depth {}",
+                                depth + 1
+                            )));
+                        }
+                        expected_hash = child_hash.clone();
+                    }
+                    // The path to the address ends here, so the address is not set
+                    None => {
+                        if !is_last {
+                            return Err(InvalidStateProofError::new(
+                                "proof contains nodes that are not on the path to the address"
+                                    .into(),
+                            ));
+                        }
+                        if self.value.is_some() {
+                            return Err(InvalidStateProofError::new(
+                                "value is given for an address that is not set".into(),
+                            ));
+                        }
+                    }
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A node of the Merkle-Radix tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateProofNode {
+    children: BTreeMap<String, String>,
+    value: Option<Vec<u8>>,
+}
+
+impl StateProofNode {
+    /// Returns the hashes of the node's children, keyed by address token
+    pub fn children(&self) -> &BTreeMap<String, String> {
+        &self.children
+    }
+
+    /// Returns the value stored at the node, if any
+    pub fn value(&self) -> Option<&[u8]> {
+        self.value.as_deref()
+    }
+
+    /// Computes the hash of the node: the first 32 bytes of the SHA-512 digest of its CBOR
+    /// encoding, as hex.
+    fn hash(&self) -> Result<String, InternalError> {
+        let digest = Sha512::digest(&self.to_cbor());
+        Ok(to_hex(&digest[..32]))
+    }
+
+    /// Encodes the node as the CBOR map `{"c": {token: child hash}, "v": value | null}`, with
+    /// keys in sorted order.
+    fn to_cbor(&self) -> Vec<u8> {
+        let mut buf = vec![];
+        write_cbor_header(&mut buf, CBOR_MAP, 2);
+
+        write_cbor_text(&mut buf, "c");
+        write_cbor_header(&mut buf, CBOR_MAP, self.children.len() as u64);
+        for (token, child_hash) in &self.children {
+            write_cbor_text(&mut buf, token);
+            write_cbor_text(&mut buf, child_hash);
+        }
+
+        write_cbor_text(&mut buf, "v");
+        match &self.value {
+            Some(value) => {
+                write_cbor_header(&mut buf, CBOR_BYTES, value.len() as u64);
+                buf.extend_from_slice(value);
+            }
+            None => buf.push(CBOR_NULL),
+        }
+
+        buf
+    }
+}
+
+const CBOR_BYTES: u8 = 2;
+const CBOR_TEXT: u8 = 3;
+const CBOR_MAP: u8 = 5;
+const CBOR_NULL: u8 = 0xf6;
+
+fn write_cbor_header(buf: &mut Vec<u8>, major_type: u8, len: u64) {
+    let major_type = major_type << 5;
+    if len < 24 {
+        buf.push(major_type | len as u8);
+    } else if len <= u64::from(u8::MAX) {
+        buf.push(major_type | 24);
+        buf.push(len as u8);
+    } else if len <= u64::from(u16::MAX) {
+        buf.push(major_type | 25);
+        buf.extend_from_slice(&(len as u16).to_be_bytes());
+    } else if len <= u64::from(u32::MAX) {
+        buf.push(major_type | 26);
+        buf.extend_from_slice(&(len as u32).to_be_bytes());
+    } else {
+        buf.push(major_type | 27);
+        buf.extend_from_slice(&len.to_be_bytes());
+    }
+}
+
+fn write_cbor_text(buf: &mut Vec<u8>, text: &str) {
+    write_cbor_header(buf, CBOR_TEXT, text.len() as u64);
+    buf.extend_from_slice(text.as_bytes());
+}
+
+/// Builds the node at the given depth from the sorted entries under it, returning its hash.
+///
+/// If the node is on the path to `address`, it is added to `path` after the nodes below it.
+fn build_node(
+    entries: &[(String, Vec<u8>)],
+    depth: usize,
+    address: Option<&str>,
+    path: &mut Vec<StateProofNode>,
+) -> Result<String, InternalError> {
+    let mut node = StateProofNode::default();
+
+    let mut i = 0;
+    while i < entries.len() {
+        let (entry_address, value) = &entries[i];
+        if entry_address.len() == depth {
+            node.value = Some(value.clone());
+            i += 1;
+            continue;
+        }
+
+        let token = entry_address
+            .get(depth..depth + TOKEN_SIZE)
+            .ok_or_else(|| {
+                InternalError::with_message(format!("Invalid address in state: {}", entry_address))
+            })?;
+        let end = i + entries[i..]
+            .iter()
+            .take_while(|(other, _)| other.get(depth..depth + TOKEN_SIZE) == Some(token))
+            .count();
+
+        let child_address =
+            address.filter(|address| address.get(depth..depth + TOKEN_SIZE) == Some(token));
+        let child_hash = build_node(&entries[i..end], depth + TOKEN_SIZE, child_address, path)?;
+        node.children.insert(token.to_string(), child_hash);
+
+        i = end;
+    }
+
+    let node_hash = node.hash()?;
+    if address.is_some() {
+        path.push(node);
+    }
+
+    Ok(node_hash)
+}
+
+/// Returned when a [`StateProof`] does not prove its value at the expected state root.
+#[derive(Debug)]
+pub struct InvalidStateProofError {
+    message: String,
+}
+
+impl InvalidStateProofError {
+    fn new(message: String) -> Self {
+        Self { message }
+    }
+}
+
+impl Error for InvalidStateProofError {}
+
+impl fmt::Display for InvalidStateProofError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid state proof: {}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use transact::{
+        database::{btree::BTreeDatabase, Database},
+        state::{
+            merkle::{kv, INDEXES},
+            StateChange, Write,
+        },
+    };
+
+    const ADDRESS_A: &str =
+        "abcdef0000000000000000000000000000000000000000000000000000000000000001";
+    const ADDRESS_B: &str =
+        "abcdef0000000000000000000000000000000000000000000000000000000000000002";
+    const ADDRESS_C: &str =
+        "012345000000000000000000000000000000000000000000000000000000000000000f";
+    const UNSET_ADDRESS: &str =
+        "abcd990000000000000000000000000000000000000000000000000000000000000001";
+
+    /// Commits the given entries to a new Merkle-Radix tree and returns the resulting state root.
+    fn commit_entries(entries: &[(&str, &[u8])]) -> String {
+        let db = BTreeDatabase::new(&INDEXES);
+        let initial_state_root = kv::MerkleRadixTree::new(db.clone_box(), None)
+            .expect("Failed to create tree")
+            .get_merkle_root();
+        let changes = entries
+            .iter()
+            .map(|(key, value)| StateChange::Set {
+                key: key.to_string(),
+                value: value.to_vec(),
+            })
+            .collect::<Vec<_>>();
+        kv::MerkleState::new(db.clone_box())
+            .commit(&initial_state_root, &changes)
+            .expect("Failed to commit entries")
+    }
+
+    fn entries() -> Vec<(String, Vec<u8>)> {
+        vec![
+            (ADDRESS_A.to_string(), b"a".to_vec()),
+            (ADDRESS_B.to_string(), b"b".to_vec()),
+            (ADDRESS_C.to_string(), b"c".to_vec()),
+        ]
+    }
+
+    /// Verify that proofs generated from the entries of a Merkle-Radix tree produce the tree's
+    /// state root, for both set and unset addresses.
+    #[test]
+    fn test_generate_and_verify() {
+        let state_root = commit_entries(&[(ADDRESS_A, b"a"), (ADDRESS_B, b"b"), (ADDRESS_C, b"c")]);
+
+        let proof = StateProof::generate(entries(), &state_root, ADDRESS_B)
+            .expect("Failed to generate proof");
+        assert_eq!(proof.value(), Some(&b"b"[..]));
+        assert_eq!(proof.nodes().len(), ADDRESS_B.len() / TOKEN_SIZE + 1);
+        proof.verify(&state_root).expect("Proof should be valid");
+
+        let proof = StateProof::generate(entries(), &state_root, UNSET_ADDRESS)
+            .expect("Failed to generate proof");
+        assert_eq!(proof.value(), None);
+        assert_eq!(proof.nodes().len(), 3);
+        proof.verify(&state_root).expect("Proof should be valid");
+
+        assert!(StateProof::generate(entries(), "0000", ADDRESS_A).is_err());
+    }
+
+    /// Verify that a proof is rejected if it is checked against a different state root or if its
+    /// value or nodes have been altered.
+    #[test]
+    fn test_verify_rejects_invalid_proofs() {
+        let state_root = commit_entries(&[(ADDRESS_A, b"a"), (ADDRESS_B, b"b"), (ADDRESS_C, b"c")]);
+        let proof = StateProof::generate(entries(), &state_root, ADDRESS_A)
+            .expect("Failed to generate proof");
+
+        let other_root = commit_entries(&[(ADDRESS_A, b"a")]);
+        assert!(proof.verify(&other_root).is_err());
+
+        let mut altered_value = proof.clone();
+        altered_value.value = Some(b"x".to_vec());
+        assert!(altered_value.verify(&state_root).is_err());
+
+        let mut altered_leaf = proof.clone();
+        if let Some(leaf) = altered_leaf.nodes.last_mut() {
+            leaf.value = Some(b"x".to_vec());
+        }
+        altered_leaf.value = Some(b"x".to_vec());
+        assert!(altered_leaf.verify(&state_root).is_err());
+
+        let mut missing_value = proof.clone();
+        missing_value.value = None;
+        assert!(missing_value.verify(&state_root).is_err());
+
+        let mut truncated = proof;
+        truncated.nodes.pop();
+        truncated.value = None;
+        assert!(truncated.verify(&state_root).is_err());
+    }
+}
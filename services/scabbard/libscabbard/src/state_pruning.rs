@@ -0,0 +1,50 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The result of pruning a scabbard service's state.
+
+use serde::{Deserialize, Serialize};
+
+/// A summary of a pruning of a scabbard service's state
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatePruneInfo {
+    state_root: String,
+    pruned_roots: u64,
+    retained_roots: u64,
+}
+
+impl StatePruneInfo {
+    pub fn new(state_root: &str, pruned_roots: u64, retained_roots: u64) -> Self {
+        Self {
+            state_root: state_root.to_string(),
+            pruned_roots,
+            retained_roots,
+        }
+    }
+
+    /// Returns the service's current state root, which is never pruned.
+    pub fn state_root(&self) -> &str {
+        &self.state_root
+    }
+
+    /// Returns the number of previous state roots that were pruned.
+    pub fn pruned_roots(&self) -> u64 {
+        self.pruned_roots
+    }
+
+    /// Returns the number of previous state roots that are still retained.
+    pub fn retained_roots(&self) -> u64 {
+        self.retained_roots
+    }
+}
@@ -25,6 +25,7 @@ use crate::store::pool::ConnectionPool;
 use super::{CommitHashStore, CommitHashStoreError};
 
 use operations::get_current_commit_hash::CommitHashStoreGetCurrentCommitHashOperation as _;
+use operations::remove_current_commit_hash::CommitHashStoreRemoveCurrentCommitHashOperation as _;
 use operations::set_current_commit_hash::CommitHashStoreSetCurrentCommitHashOperation as _;
 use operations::CommitHashStoreOperations;
 
@@ -93,6 +94,13 @@ impl CommitHashStore for DieselCommitHashStore<diesel::pg::PgConnection> {
             )
         })
     }
+
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        self.pool.execute_write(|conn| {
+            CommitHashStoreOperations::new(conn)
+                .remove_current_commit_hash(&*self.circuit_id, &*self.service_id)
+        })
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -113,6 +121,13 @@ impl CommitHashStore for DieselCommitHashStore<diesel::sqlite::SqliteConnection>
             )
         })
     }
+
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        self.pool.execute_write(|conn| {
+            CommitHashStoreOperations::new(conn)
+                .remove_current_commit_hash(&*self.circuit_id, &*self.service_id)
+        })
+    }
 }
 
 /// Database backed [CommitHashStore] implementation.
@@ -153,6 +168,11 @@ impl<'a> CommitHashStore for DieselInTransactionCommitHashStore<'a, diesel::pg::
             commit_hash,
         )
     }
+
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        CommitHashStoreOperations::new(self.conn)
+            .remove_current_commit_hash(&*self.circuit_id, &*self.service_id)
+    }
 }
 
 #[cfg(feature = "sqlite")]
@@ -171,6 +191,11 @@ impl<'a> CommitHashStore
             commit_hash,
         )
     }
+
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        CommitHashStoreOperations::new(self.conn)
+            .remove_current_commit_hash(&*self.circuit_id, &*self.service_id)
+    }
 }
 
 #[cfg(test)]
@@ -222,6 +247,14 @@ mod tests {
             commit_log_store_circuit_1.get_current_commit_hash()?
         );
 
+        // Remove the hash of the second circuit and verify that the original is unchanged.
+        commit_log_store_circuit_2.remove_current_commit_hash()?;
+        assert_eq!(None, commit_log_store_circuit_2.get_current_commit_hash()?);
+        assert_eq!(
+            Some("abcdef0123456789".to_string()),
+            commit_log_store_circuit_1.get_current_commit_hash()?
+        );
+
         Ok(())
     }
 
@@ -38,4 +38,9 @@ pub trait CommitHashStore {
     ///
     /// * `current_commit_hash` - the new "current" commit hash.
     fn set_current_commit_hash(&self, commit_hash: &str) -> Result<(), CommitHashStoreError>;
+
+    /// Removes the current commit hash value.
+    ///
+    /// This is used when the instance's state is purged.
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError>;
 }
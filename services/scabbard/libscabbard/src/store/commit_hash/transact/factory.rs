@@ -18,7 +18,7 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use openssl::hash::{hash, MessageDigest};
+use sha2::{Digest, Sha256};
 use splinter::error::InternalError;
 use transact::{
     database::lmdb::{LmdbContext, LmdbDatabase},
@@ -114,9 +114,7 @@ impl LmdbDatabaseFactory {
     }
 
     fn path_from_key(&self, key: &str) -> Result<PathBuf, InternalError> {
-        let hash = hash(MessageDigest::sha256(), key.as_bytes())
-            .map(|digest| to_hex(&*digest))
-            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+        let hash = to_hex(&Sha256::digest(key.as_bytes()));
         let db_path = Path::new(&*self.db_dir)
             .to_path_buf()
             .join(format!("{}-{}", hash, self.db_suffix));
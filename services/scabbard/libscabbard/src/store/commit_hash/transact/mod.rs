@@ -106,6 +106,35 @@ impl<D: Database + Clone> CommitHashStore for TransactCommitHashStore<D> {
 
         Ok(())
     }
+
+    fn remove_current_commit_hash(&self) -> Result<(), CommitHashStoreError> {
+        let mut writer = self
+            .db
+            .get_writer()
+            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+        match writer.index_delete(CURRENT_STATE_ROOT_INDEX, b"HEAD") {
+            Ok(()) => (),
+            Err(DatabaseError::WriterError(msg)) if msg.starts_with("Not an index") => {
+                return Err(CommitHashStoreError::InvalidState(
+                    InvalidStateError::with_message(
+                        "Missing current_state_root index in LMDB database".into(),
+                    ),
+                ))
+            }
+            Err(err) => {
+                return Err(CommitHashStoreError::Internal(InternalError::from_source(
+                    Box::new(err),
+                )))
+            }
+        }
+
+        writer
+            .commit()
+            .map_err(|e| InternalError::from_source(Box::new(e)))?;
+
+        Ok(())
+    }
 }
 
 fn to_hex(bytes: &[u8]) -> String {
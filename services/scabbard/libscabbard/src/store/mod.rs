@@ -17,10 +17,14 @@
 #[cfg(feature = "scabbardv3")]
 mod command;
 mod commit_hash;
+#[cfg(all(test, feature = "diesel-mysql-tests"))]
+pub(crate) mod diesel_mysql_test;
 #[cfg(all(test, feature = "diesel-postgres-tests"))]
 pub(crate) mod diesel_postgres_test;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub(crate) mod pool;
+#[cfg(feature = "receipt-queries")]
+mod receipt_index;
 #[cfg(feature = "scabbardv3-store")]
 mod scabbard_store;
 
@@ -35,6 +39,11 @@ pub use commit_hash::diesel;
 pub use commit_hash::transact;
 pub use commit_hash::{CommitHashStore, CommitHashStoreError};
 
+#[cfg(all(feature = "receipt-queries", feature = "diesel"))]
+pub use receipt_index::diesel::DieselReceiptIndexStore;
+#[cfg(feature = "receipt-queries")]
+pub use receipt_index::{ReceiptIndexEntry, ReceiptIndexStore, ReceiptIndexStoreError};
+
 #[cfg(all(feature = "scabbardv3-store", feature = "diesel"))]
 pub use scabbard_store::DieselScabbardStore;
 #[cfg(feature = "scabbardv3-store")]
@@ -42,11 +51,13 @@ pub use scabbard_store::PooledScabbardStoreFactory;
 #[cfg(feature = "scabbardv3-store")]
 pub use scabbard_store::{
     Action, AlarmType, CommitEntry, CommitEntryBuilder, ConsensusAction, ConsensusContext,
-    ConsensusDecision, ConsensusEvent, ConsensusType, Context, ContextBuilder, Event, Identified,
-    Message, Notification, Participant, ScabbardService, ScabbardServiceBuilder, ScabbardStore,
-    ScabbardStoreFactory, ServiceStatus, State, SupervisorNotification,
-    SupervisorNotificationBuilder, SupervisorNotificationType,
+    ConsensusDecision, ConsensusEvent, ConsensusEventLogEntry, ConsensusType, Context,
+    ContextBuilder, Event, Identified, Message, Notification, Participant, ScabbardService,
+    ScabbardServiceBuilder, ScabbardStore, ScabbardStoreFactory, ServiceStatus, State,
+    SupervisorNotification, SupervisorNotificationBuilder, SupervisorNotificationType,
 };
+#[cfg(all(feature = "scabbardv3-store", feature = "mysql"))]
+pub use scabbard_store::{MysqlScabbardStoreFactory, PooledMysqlScabbardStoreFactory};
 #[cfg(all(feature = "scabbardv3-store", feature = "postgres"))]
 pub use scabbard_store::{PgScabbardStoreFactory, PooledPgScabbardStoreFactory};
 #[cfg(all(feature = "scabbardv3-store", feature = "sqlite"))]
@@ -0,0 +1,317 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod models;
+mod operations;
+mod schema;
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+
+use diesel::r2d2::{ConnectionManager, Pool};
+use splinter::error::{InternalError, InvalidArgumentError};
+
+use crate::store::pool::ConnectionPool;
+
+use super::{ReceiptIndexEntry, ReceiptIndexStore, ReceiptIndexStoreError};
+
+use models::ReceiptIndexEntryModel;
+use operations::add_entries::ReceiptIndexStoreAddEntriesOperation as _;
+use operations::get_entries::ReceiptIndexStoreGetEntriesOperation as _;
+use operations::remove_all_entries::ReceiptIndexStoreRemoveAllEntriesOperation as _;
+use operations::remove_entries::ReceiptIndexStoreRemoveEntriesOperation as _;
+use operations::ReceiptIndexStoreOperations;
+
+/// Database backed [ReceiptIndexStore] implementation.
+#[derive(Clone)]
+pub struct DieselReceiptIndexStore<Conn: diesel::Connection + 'static> {
+    pool: ConnectionPool<Conn>,
+    circuit_id: Arc<str>,
+    service_id: Arc<str>,
+}
+
+impl<C: diesel::Connection> DieselReceiptIndexStore<C> {
+    /// Constructs new DieselReceiptIndexStore.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool` - Database connection pool
+    /// * `circuit_id` - The circuit associated with the store
+    /// * `service_id` - The service associated with the store
+    pub fn new(pool: Pool<ConnectionManager<C>>, circuit_id: &str, service_id: &str) -> Self {
+        Self {
+            pool: ConnectionPool::Normal(pool),
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+        }
+    }
+
+    /// Create a new `DieselReceiptIndexStore` with write exclusivity enabled.
+    ///
+    /// Write exclusivity is enforced by providing a connection pool that is wrapped in a
+    /// [`RwLock`]. This ensures that there may be only one writer, but many readers.
+    ///
+    /// # Arguments
+    ///
+    /// * `pool`: read-write lock-guarded connection pool for the database
+    /// * `circuit_id` - The circuit associated with the store
+    /// * `service_id` - The service associated with the store
+    pub fn new_with_write_exclusivity(
+        pool: Arc<RwLock<Pool<ConnectionManager<C>>>>,
+        circuit_id: &str,
+        service_id: &str,
+    ) -> Self {
+        Self {
+            pool: ConnectionPool::WriteExclusive(pool),
+            circuit_id: circuit_id.into(),
+            service_id: service_id.into(),
+        }
+    }
+
+    fn to_model(
+        &self,
+        entry: ReceiptIndexEntry,
+    ) -> Result<ReceiptIndexEntryModel, ReceiptIndexStoreError> {
+        let committed_at = entry
+            .committed_at
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .ok()
+            .and_then(|duration| i64::try_from(duration.as_secs()).ok())
+            .ok_or_else(|| {
+                InvalidArgumentError::new(
+                    "committed_at".to_string(),
+                    "commit time cannot be stored as seconds since the Unix epoch".to_string(),
+                )
+            })?;
+
+        Ok(ReceiptIndexEntryModel {
+            circuit_id: self.circuit_id.to_string(),
+            service_id: self.service_id.to_string(),
+            transaction_id: entry.transaction_id,
+            contract_name: entry.contract_name,
+            committed_at,
+        })
+    }
+}
+
+fn from_model(model: ReceiptIndexEntryModel) -> Result<ReceiptIndexEntry, ReceiptIndexStoreError> {
+    let committed_at = u64::try_from(model.committed_at)
+        .ok()
+        .and_then(|secs| SystemTime::UNIX_EPOCH.checked_add(Duration::from_secs(secs)))
+        .ok_or_else(|| {
+            InternalError::with_message(format!(
+                "commit time {} of transaction {} cannot be represented as a `SystemTime`",
+                model.committed_at, model.transaction_id
+            ))
+        })?;
+
+    Ok(ReceiptIndexEntry::new(
+        model.transaction_id,
+        model.contract_name,
+        committed_at,
+    ))
+}
+
+#[cfg(feature = "postgres")]
+impl ReceiptIndexStore for DieselReceiptIndexStore<diesel::pg::PgConnection> {
+    fn add_entries(&self, entries: Vec<ReceiptIndexEntry>) -> Result<(), ReceiptIndexStoreError> {
+        let models = entries
+            .into_iter()
+            .map(|entry| self.to_model(entry))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.pool
+            .execute_write(|conn| ReceiptIndexStoreOperations::new(conn).add_entries(models))
+    }
+
+    fn get_entries(
+        &self,
+        transaction_ids: &[String],
+    ) -> Result<HashMap<String, ReceiptIndexEntry>, ReceiptIndexStoreError> {
+        self.pool
+            .execute_read(|conn| {
+                ReceiptIndexStoreOperations::new(conn).get_entries(
+                    &*self.circuit_id,
+                    &*self.service_id,
+                    transaction_ids,
+                )
+            })?
+            .into_iter()
+            .map(|model| {
+                let entry = from_model(model)?;
+                Ok((entry.transaction_id().to_string(), entry))
+            })
+            .collect()
+    }
+
+    fn remove_entries(&self, transaction_ids: &[String]) -> Result<(), ReceiptIndexStoreError> {
+        self.pool.execute_write(|conn| {
+            ReceiptIndexStoreOperations::new(conn).remove_entries(
+                &*self.circuit_id,
+                &*self.service_id,
+                transaction_ids,
+            )
+        })
+    }
+
+    fn remove_all_entries(&self) -> Result<(), ReceiptIndexStoreError> {
+        self.pool.execute_write(|conn| {
+            ReceiptIndexStoreOperations::new(conn)
+                .remove_all_entries(&*self.circuit_id, &*self.service_id)
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl ReceiptIndexStore for DieselReceiptIndexStore<diesel::sqlite::SqliteConnection> {
+    fn add_entries(&self, entries: Vec<ReceiptIndexEntry>) -> Result<(), ReceiptIndexStoreError> {
+        let models = entries
+            .into_iter()
+            .map(|entry| self.to_model(entry))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.pool
+            .execute_write(|conn| ReceiptIndexStoreOperations::new(conn).add_entries(models))
+    }
+
+    fn get_entries(
+        &self,
+        transaction_ids: &[String],
+    ) -> Result<HashMap<String, ReceiptIndexEntry>, ReceiptIndexStoreError> {
+        self.pool
+            .execute_read(|conn| {
+                ReceiptIndexStoreOperations::new(conn).get_entries(
+                    &*self.circuit_id,
+                    &*self.service_id,
+                    transaction_ids,
+                )
+            })?
+            .into_iter()
+            .map(|model| {
+                let entry = from_model(model)?;
+                Ok((entry.transaction_id().to_string(), entry))
+            })
+            .collect()
+    }
+
+    fn remove_entries(&self, transaction_ids: &[String]) -> Result<(), ReceiptIndexStoreError> {
+        self.pool.execute_write(|conn| {
+            ReceiptIndexStoreOperations::new(conn).remove_entries(
+                &*self.circuit_id,
+                &*self.service_id,
+                transaction_ids,
+            )
+        })
+    }
+
+    fn remove_all_entries(&self) -> Result<(), ReceiptIndexStoreError> {
+        self.pool.execute_write(|conn| {
+            ReceiptIndexStoreOperations::new(conn)
+                .remove_all_entries(&*self.circuit_id, &*self.service_id)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use diesel::{
+        r2d2::{ConnectionManager, Pool},
+        sqlite::SqliteConnection,
+    };
+
+    use crate::migrations::run_sqlite_migrations;
+
+    /// Test that a DieselReceiptIndexStore using a SQLite connection pool can
+    /// 1. Add entries and get them by transaction ID, skipping unknown transactions
+    /// 2. Replace an existing entry
+    /// 3. Keep the entries of services on different circuits separate
+    /// 4. Remove some and then all of the entries of a service
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlite_receipt_index_store() -> Result<(), Box<dyn std::error::Error>> {
+        let pool = create_connection_pool_and_migrate()?;
+        let store_circuit_1 = DieselReceiptIndexStore::new(pool.clone(), "circuit_1", "service");
+        let store_circuit_2 = DieselReceiptIndexStore::new(pool, "circuit_2", "service");
+
+        let committed_at = SystemTime::UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let entry_1 = ReceiptIndexEntry::new("txn1".into(), Some("intkey".into()), committed_at);
+        let entry_2 = ReceiptIndexEntry::new("txn2".into(), None, committed_at);
+        store_circuit_1.add_entries(vec![entry_1.clone(), entry_2.clone()])?;
+
+        let entries = store_circuit_1.get_entries(&[
+            "txn1".to_string(),
+            "txn2".to_string(),
+            "txn3".to_string(),
+        ])?;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries.get("txn1"), Some(&entry_1));
+        assert_eq!(entries.get("txn2"), Some(&entry_2));
+
+        // Replace the second entry
+        let entry_2 = ReceiptIndexEntry::new(
+            "txn2".into(),
+            Some("xo".into()),
+            committed_at + Duration::from_secs(5),
+        );
+        store_circuit_1.add_entries(vec![entry_2.clone()])?;
+        assert_eq!(
+            store_circuit_1
+                .get_entries(&["txn2".to_string()])?
+                .get("txn2"),
+            Some(&entry_2)
+        );
+
+        // The service on a different circuit has no entries
+        assert!(store_circuit_2
+            .get_entries(&["txn1".to_string()])?
+            .is_empty());
+        store_circuit_2.add_entries(vec![entry_1.clone()])?;
+
+        store_circuit_1.remove_entries(&["txn1".to_string()])?;
+        let entries = store_circuit_1.get_entries(&["txn1".to_string(), "txn2".to_string()])?;
+        assert_eq!(entries.len(), 1);
+        assert!(entries.contains_key("txn2"));
+
+        store_circuit_1.remove_all_entries()?;
+        assert!(store_circuit_1
+            .get_entries(&["txn2".to_string()])?
+            .is_empty());
+
+        // Verify that the other service's entry is unchanged
+        assert_eq!(
+            store_circuit_2
+                .get_entries(&["txn1".to_string()])?
+                .get("txn1"),
+            Some(&entry_1)
+        );
+
+        Ok(())
+    }
+
+    /// Creates a connection pool for an in-memory SQLite database with only a single connection
+    /// available. Each connection is backed by a different in-memory SQLite database, so limiting
+    /// the pool to a single connection ensures that the same DB is used for all operations.
+    #[cfg(feature = "sqlite")]
+    fn create_connection_pool_and_migrate(
+    ) -> Result<Pool<ConnectionManager<SqliteConnection>>, Box<dyn std::error::Error>> {
+        let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
+        let pool = Pool::builder().max_size(1).build(connection_manager)?;
+
+        run_sqlite_migrations(&*pool.get().expect("Failed to get connection for migrations"))?;
+
+        Ok(pool)
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::schema::*;
+
+#[derive(Insertable, Queryable, Identifiable, AsChangeset)]
+#[cfg_attr(test, derive(Debug, PartialEq))]
+#[table_name = "scabbard_receipt_index"]
+#[primary_key(circuit_id, service_id, transaction_id)]
+pub struct ReceiptIndexEntryModel {
+    pub circuit_id: String,
+    pub service_id: String,
+    pub transaction_id: String,
+    pub contract_name: Option<String>,
+    /// The commit time, in seconds since the Unix epoch
+    pub committed_at: i64,
+}
@@ -0,0 +1,87 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "postgres")]
+use diesel::insert_into;
+use diesel::prelude::*;
+#[cfg(feature = "sqlite")]
+use diesel::replace_into;
+use splinter::error::InternalError;
+
+use crate::store::receipt_index::{
+    diesel::{models::ReceiptIndexEntryModel, schema::scabbard_receipt_index},
+    ReceiptIndexStoreError,
+};
+
+use super::ReceiptIndexStoreOperations;
+
+pub(in crate::store::receipt_index::diesel) trait ReceiptIndexStoreAddEntriesOperation {
+    fn add_entries(
+        &self,
+        entries: Vec<ReceiptIndexEntryModel>,
+    ) -> Result<(), ReceiptIndexStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ReceiptIndexStoreAddEntriesOperation
+    for ReceiptIndexStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn add_entries(
+        &self,
+        entries: Vec<ReceiptIndexEntryModel>,
+    ) -> Result<(), ReceiptIndexStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                for entry in &entries {
+                    replace_into(scabbard_receipt_index::table)
+                        .values(entry)
+                        .execute(self.conn)?;
+                }
+                Ok(())
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ReceiptIndexStoreAddEntriesOperation
+    for ReceiptIndexStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn add_entries(
+        &self,
+        entries: Vec<ReceiptIndexEntryModel>,
+    ) -> Result<(), ReceiptIndexStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                for entry in &entries {
+                    insert_into(scabbard_receipt_index::table)
+                        .values(entry)
+                        .on_conflict((
+                            scabbard_receipt_index::circuit_id,
+                            scabbard_receipt_index::service_id,
+                            scabbard_receipt_index::transaction_id,
+                        ))
+                        .do_update()
+                        .set(entry)
+                        .execute(self.conn)?;
+                }
+                Ok(())
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
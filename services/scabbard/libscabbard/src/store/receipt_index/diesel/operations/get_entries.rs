@@ -0,0 +1,64 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Nullable, Text};
+use splinter::error::InternalError;
+
+use crate::store::receipt_index::{
+    diesel::{models::ReceiptIndexEntryModel, schema::scabbard_receipt_index},
+    ReceiptIndexStoreError,
+};
+
+use super::{ReceiptIndexStoreOperations, MAX_IDS_PER_QUERY};
+
+pub(in crate::store::receipt_index::diesel) trait ReceiptIndexStoreGetEntriesOperation {
+    fn get_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        transaction_ids: &[String],
+    ) -> Result<Vec<ReceiptIndexEntryModel>, ReceiptIndexStoreError>;
+}
+
+impl<'a, C> ReceiptIndexStoreGetEntriesOperation for ReceiptIndexStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    ReceiptIndexEntryModel:
+        diesel::Queryable<(Text, Text, Text, Nullable<Text>, BigInt), C::Backend>,
+{
+    fn get_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        transaction_ids: &[String],
+    ) -> Result<Vec<ReceiptIndexEntryModel>, ReceiptIndexStoreError> {
+        let mut entries = Vec::with_capacity(transaction_ids.len());
+        for ids in transaction_ids.chunks(MAX_IDS_PER_QUERY) {
+            entries.extend(
+                scabbard_receipt_index::table
+                    .filter(
+                        scabbard_receipt_index::circuit_id
+                            .eq(circuit_id)
+                            .and(scabbard_receipt_index::service_id.eq(service_id))
+                            .and(scabbard_receipt_index::transaction_id.eq_any(ids)),
+                    )
+                    .load::<ReceiptIndexEntryModel>(self.conn)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?,
+            );
+        }
+
+        Ok(entries)
+    }
+}
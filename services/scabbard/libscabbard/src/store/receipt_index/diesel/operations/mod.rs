@@ -0,0 +1,32 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub(super) mod add_entries;
+pub(super) mod get_entries;
+pub(super) mod remove_all_entries;
+pub(super) mod remove_entries;
+
+/// The most transaction IDs that are bound in a single query, which keeps each query within
+/// SQLite's limit on the number of bound parameters.
+const MAX_IDS_PER_QUERY: usize = 500;
+
+pub struct ReceiptIndexStoreOperations<'a, C> {
+    conn: &'a C,
+}
+
+impl<'a, C: diesel::Connection> ReceiptIndexStoreOperations<'a, C> {
+    pub fn new(conn: &'a C) -> Self {
+        ReceiptIndexStoreOperations { conn }
+    }
+}
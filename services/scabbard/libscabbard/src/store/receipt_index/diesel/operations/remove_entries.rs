@@ -0,0 +1,91 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use diesel::{dsl::delete, prelude::*};
+use splinter::error::InternalError;
+
+use crate::store::receipt_index::{diesel::schema::scabbard_receipt_index, ReceiptIndexStoreError};
+
+use super::{ReceiptIndexStoreOperations, MAX_IDS_PER_QUERY};
+
+pub(in crate::store::receipt_index::diesel) trait ReceiptIndexStoreRemoveEntriesOperation {
+    fn remove_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        transaction_ids: &[String],
+    ) -> Result<(), ReceiptIndexStoreError>;
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> ReceiptIndexStoreRemoveEntriesOperation
+    for ReceiptIndexStoreOperations<'a, diesel::sqlite::SqliteConnection>
+{
+    fn remove_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        transaction_ids: &[String],
+    ) -> Result<(), ReceiptIndexStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                for ids in transaction_ids.chunks(MAX_IDS_PER_QUERY) {
+                    delete(
+                        scabbard_receipt_index::table.filter(
+                            scabbard_receipt_index::circuit_id
+                                .eq(circuit_id)
+                                .and(scabbard_receipt_index::service_id.eq(service_id))
+                                .and(scabbard_receipt_index::transaction_id.eq_any(ids)),
+                        ),
+                    )
+                    .execute(self.conn)?;
+                }
+                Ok(())
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<'a> ReceiptIndexStoreRemoveEntriesOperation
+    for ReceiptIndexStoreOperations<'a, diesel::pg::PgConnection>
+{
+    fn remove_entries(
+        &self,
+        circuit_id: &str,
+        service_id: &str,
+        transaction_ids: &[String],
+    ) -> Result<(), ReceiptIndexStoreError> {
+        self.conn
+            .transaction::<_, diesel::result::Error, _>(|| {
+                for ids in transaction_ids.chunks(MAX_IDS_PER_QUERY) {
+                    delete(
+                        scabbard_receipt_index::table.filter(
+                            scabbard_receipt_index::circuit_id
+                                .eq(circuit_id)
+                                .and(scabbard_receipt_index::service_id.eq(service_id))
+                                .and(scabbard_receipt_index::transaction_id.eq_any(ids)),
+                        ),
+                    )
+                    .execute(self.conn)?;
+                }
+                Ok(())
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(())
+    }
+}
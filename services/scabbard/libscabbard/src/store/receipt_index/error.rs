@@ -0,0 +1,76 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Error types and logic for ReceiptIndexStores.
+
+use std::error::Error;
+use std::fmt::Display;
+
+use splinter::error::{
+    InternalError, InvalidArgumentError, InvalidStateError, ResourceTemporarilyUnavailableError,
+};
+
+/// Error type for the [ReceiptIndexStore](super::ReceiptIndexStore) trait.
+///
+/// Any errors implementations of [ReceiptIndexStore](super::ReceiptIndexStore) can generate must be
+/// convertible to a ReceiptIndexStoreError enum member.
+
+#[derive(Debug)]
+/// Error states for fallible [ReceiptIndexStore](super::ReceiptIndexStore) operations.
+pub enum ReceiptIndexStoreError {
+    Internal(InternalError),
+    InvalidArgument(InvalidArgumentError),
+    InvalidState(InvalidStateError),
+    ResourceTemporarilyUnavailable(ResourceTemporarilyUnavailableError),
+}
+
+impl Display for ReceiptIndexStoreError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReceiptIndexStoreError::Internal(e) => e.fmt(f),
+            ReceiptIndexStoreError::InvalidArgument(e) => e.fmt(f),
+            ReceiptIndexStoreError::InvalidState(e) => e.fmt(f),
+            ReceiptIndexStoreError::ResourceTemporarilyUnavailable(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ReceiptIndexStoreError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReceiptIndexStoreError::Internal(e) => Some(e),
+            ReceiptIndexStoreError::InvalidArgument(e) => Some(e),
+            ReceiptIndexStoreError::InvalidState(e) => Some(e),
+            ReceiptIndexStoreError::ResourceTemporarilyUnavailable(e) => Some(e),
+        }
+    }
+}
+
+impl From<InternalError> for ReceiptIndexStoreError {
+    fn from(err: InternalError) -> Self {
+        ReceiptIndexStoreError::Internal(err)
+    }
+}
+
+impl From<InvalidArgumentError> for ReceiptIndexStoreError {
+    fn from(err: InvalidArgumentError) -> Self {
+        ReceiptIndexStoreError::InvalidArgument(err)
+    }
+}
+
+impl From<InvalidStateError> for ReceiptIndexStoreError {
+    fn from(err: InvalidStateError) -> Self {
+        ReceiptIndexStoreError::InvalidState(err)
+    }
+}
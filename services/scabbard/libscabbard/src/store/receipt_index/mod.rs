@@ -0,0 +1,103 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A store for the details of a scabbard service's transaction receipts that the receipt store
+//! does not keep.
+
+#[cfg(feature = "diesel")]
+pub mod diesel;
+mod error;
+
+use std::collections::HashMap;
+use std::time::SystemTime;
+
+pub use error::ReceiptIndexStoreError;
+
+/// The details of a committed transaction that receipts can be queried by
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceiptIndexEntry {
+    transaction_id: String,
+    contract_name: Option<String>,
+    committed_at: SystemTime,
+}
+
+impl ReceiptIndexEntry {
+    /// Creates a new entry
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_id` - The ID of the transaction, which is also the ID of its receipt
+    /// * `contract_name` - The Sabre contract the transaction executed, or the transaction's
+    ///   family if it is not a Sabre contract execution
+    /// * `committed_at` - The time the transaction was committed
+    pub fn new(
+        transaction_id: String,
+        contract_name: Option<String>,
+        committed_at: SystemTime,
+    ) -> Self {
+        Self {
+            transaction_id,
+            contract_name,
+            committed_at,
+        }
+    }
+
+    /// Returns the ID of the transaction
+    pub fn transaction_id(&self) -> &str {
+        &self.transaction_id
+    }
+
+    /// Returns the name of the contract the transaction executed, if it is known
+    pub fn contract_name(&self) -> Option<&str> {
+        self.contract_name.as_deref()
+    }
+
+    /// Returns the time the transaction was committed
+    pub fn committed_at(&self) -> SystemTime {
+        self.committed_at
+    }
+}
+
+/// A store for the [`ReceiptIndexEntry`]s of a single scabbard service.
+pub trait ReceiptIndexStore: Send + Sync {
+    /// Adds the given entries, replacing any existing entries of the same transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `entries` - The entries to add
+    fn add_entries(&self, entries: Vec<ReceiptIndexEntry>) -> Result<(), ReceiptIndexStoreError>;
+
+    /// Returns the entries of the given transactions, keyed by transaction ID. Transactions
+    /// without an entry are not included.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_ids` - The IDs of the transactions to look up
+    fn get_entries(
+        &self,
+        transaction_ids: &[String],
+    ) -> Result<HashMap<String, ReceiptIndexEntry>, ReceiptIndexStoreError>;
+
+    /// Removes the entries of the given transactions.
+    ///
+    /// # Arguments
+    ///
+    /// * `transaction_ids` - The IDs of the transactions whose entries are removed
+    fn remove_entries(&self, transaction_ids: &[String]) -> Result<(), ReceiptIndexStoreError>;
+
+    /// Removes every entry of the service.
+    ///
+    /// This is used when the instance's state is purged.
+    fn remove_all_entries(&self) -> Result<(), ReceiptIndexStoreError>;
+}
@@ -18,8 +18,8 @@ use splinter::service::FullyQualifiedServiceId;
 
 use crate::store::scabbard_store::ScabbardStoreError;
 use crate::store::scabbard_store::{
-    AlarmType, CommitEntry, ConsensusAction, ConsensusContext, ConsensusEvent, Identified,
-    ScabbardService, SupervisorNotification,
+    AlarmType, CommitEntry, ConsensusAction, ConsensusContext, ConsensusEvent,
+    ConsensusEventLogEntry, Identified, ScabbardService, SupervisorNotification,
 };
 
 use super::ScabbardStore;
@@ -214,6 +214,20 @@ impl ScabbardStore for Box<dyn ScabbardStore> {
         (&**self).list_consensus_events(service_id)
     }
 
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed, in position order
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The combined `CircuitId` and `ServiceId` of the service for which events
+    ///    should be listed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        (&**self).list_consensus_event_log(service_id)
+    }
+
     /// Get the current context for a given service
     ///
     /// # Arguments
@@ -19,6 +19,8 @@ mod schema;
 use std::sync::{Arc, RwLock};
 use std::time::SystemTime;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -32,8 +34,8 @@ use diesel::{
 use crate::store::pool::ConnectionPool;
 use crate::store::scabbard_store::ScabbardStoreError;
 use crate::store::scabbard_store::{
-    AlarmType, CommitEntry, ConsensusAction, ConsensusContext, ConsensusEvent, Identified,
-    ScabbardService, SupervisorNotification,
+    AlarmType, CommitEntry, ConsensusAction, ConsensusContext, ConsensusEvent,
+    ConsensusEventLogEntry, Identified, ScabbardService, SupervisorNotification,
 };
 
 use super::ScabbardStore;
@@ -49,6 +51,7 @@ use operations::get_current_consensus_context::GetCurrentContextAction as _;
 use operations::get_last_commit_entry::GetLastCommitEntryOperation as _;
 use operations::get_service::GetServiceOperation as _;
 use operations::list_consensus_actions::ListActionsOperation as _;
+use operations::list_consensus_event_log::ListEventLogOperation as _;
 use operations::list_consensus_events::ListEventsOperation as _;
 use operations::list_ready_services::ListReadyServicesOperation as _;
 use operations::list_supervisor_notifications::ListSupervisorNotificationOperation as _;
@@ -221,6 +224,252 @@ impl ScabbardStore for DieselScabbardStore<SqliteConnection> {
             ScabbardStoreOperations::new(conn).list_consensus_events(service_id)
         })
     }
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        self.pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).list_consensus_event_log(service_id)
+        })
+    }
+    /// Get the current context for a given service
+    fn get_current_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ConsensusContext>, ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).get_current_consensus_context(service_id)
+        })
+    }
+
+    /// Remove existing service
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).remove_service(service_id))
+    }
+
+    /// Set a scabbard alarm
+    fn set_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+        alarm: SystemTime,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).set_alarm(service_id, alarm_type, alarm)
+        })
+    }
+
+    /// Unset a scabbard alarm
+    fn unset_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).unset_alarm(service_id, alarm_type)
+        })
+    }
+
+    /// Get the scabbard alarm of a specified type for the given service
+    fn get_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+    ) -> Result<Option<SystemTime>, ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).get_alarm(service_id, alarm_type)
+        })
+    }
+
+    // add a new supervisor notification
+    fn add_supervisor_notification(
+        &self,
+        notification: SupervisorNotification,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).add_supervisor_notification(notification)
+        })
+    }
+
+    // get the next supervisor notification that needs to be handled
+    fn list_supervisor_notifications(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<Identified<SupervisorNotification>>, ScabbardStoreError> {
+        self.pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).list_supervisor_notifications(service_id)
+        })
+    }
+
+    // update an existing supervisor notification
+    fn update_supervisor_notification(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        notification_id: i64,
+        executed_at: SystemTime,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).update_supervisor_notification(
+                service_id,
+                notification_id,
+                executed_at,
+            )
+        })
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl ScabbardStore for DieselScabbardStore<MysqlConnection> {
+    /// Add a new context
+    fn add_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        context: ConsensusContext,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).add_consensus_context(service_id, context)
+        })
+    }
+    /// Update an existing context
+    fn update_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        context: ConsensusContext,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).update_consensus_context(service_id, context)
+        })
+    }
+    /// Add a 2 phase commit coordinator action
+    fn add_consensus_action(
+        &self,
+        action: ConsensusAction,
+        service_id: &FullyQualifiedServiceId,
+        event_id: i64,
+    ) -> Result<i64, ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).add_consensus_action(action, service_id, event_id)
+        })
+    }
+    /// Update an existing 2 phase commit action
+    fn update_consensus_action(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        action_id: i64,
+        executed_at: SystemTime,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).update_consensus_action(
+                service_id,
+                action_id,
+                executed_at,
+            )
+        })
+    }
+    /// List all coordinator actions for a given service_id
+    fn list_consensus_actions(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<Identified<ConsensusAction>>, ScabbardStoreError> {
+        self.pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).list_consensus_actions(service_id)
+        })
+    }
+    /// List ready services
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError> {
+        self.pool
+            .execute_read(|conn| ScabbardStoreOperations::new(conn).list_ready_services())
+    }
+    /// Add a new scabbard service
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).add_service(service))
+    }
+    /// Add a new commit entry
+    fn add_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
+        self.pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).add_commit_entry(commit_entry))
+    }
+    /// Get the commit entry for the specified service_id
+    fn get_last_commit_entry(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<CommitEntry>, ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).get_last_commit_entry(service_id)
+        })
+    }
+    /// Update an existing commit entry
+    fn update_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).update_commit_entry(commit_entry)
+        })
+    }
+    /// Update an existing scabbard service
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.pool
+            .execute_write(|conn| ScabbardStoreOperations::new(conn).update_service(service))
+    }
+    /// Get a service
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError> {
+        self.pool
+            .execute_read(|conn| ScabbardStoreOperations::new(conn).get_service(service_id))
+    }
+    /// Add a new consensus event
+    fn add_consensus_event(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        event: ConsensusEvent,
+    ) -> Result<i64, ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).add_consensus_event(service_id, event)
+        })
+    }
+    /// Update an existing consensus event
+    fn update_consensus_event(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        event_id: i64,
+        executed_at: SystemTime,
+        executed_epoch: u64,
+    ) -> Result<(), ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).update_consensus_event(
+                service_id,
+                event_id,
+                executed_at,
+                executed_epoch,
+            )
+        })
+    }
+    /// List all consensus events for a given service_id
+    fn list_consensus_events(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<Identified<ConsensusEvent>>, ScabbardStoreError> {
+        self.pool.execute_write(|conn| {
+            ScabbardStoreOperations::new(conn).list_consensus_events(service_id)
+        })
+    }
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        self.pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).list_consensus_event_log(service_id)
+        })
+    }
     /// Get the current context for a given service
     fn get_current_consensus_context(
         &self,
@@ -447,6 +696,16 @@ impl ScabbardStore for DieselScabbardStore<PgConnection> {
             ScabbardStoreOperations::new(conn).list_consensus_events(service_id)
         })
     }
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        self.pool.execute_read(|conn| {
+            ScabbardStoreOperations::new(conn).list_consensus_event_log(service_id)
+        })
+    }
     /// Get the current context for a given service
     fn get_current_consensus_context(
         &self,
@@ -667,6 +926,208 @@ impl<'a> ScabbardStore for DieselConnectionScabbardStore<'a, SqliteConnection> {
     ) -> Result<Vec<Identified<ConsensusEvent>>, ScabbardStoreError> {
         ScabbardStoreOperations::new(self.connection).list_consensus_events(service_id)
     }
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_consensus_event_log(service_id)
+    }
+    /// Get the current context for a given service
+    fn get_current_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ConsensusContext>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_current_consensus_context(service_id)
+    }
+    /// Remove existing service
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).remove_service(service_id)
+    }
+
+    /// Set a scabbard alarm
+    fn set_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+        alarm: SystemTime,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).set_alarm(service_id, alarm_type, alarm)
+    }
+
+    /// Unset a scabbard alarm
+    fn unset_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).unset_alarm(service_id, alarm_type)
+    }
+
+    /// Get the scabbard alarm of a specified type for the given service
+    fn get_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+    ) -> Result<Option<SystemTime>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_alarm(service_id, alarm_type)
+    }
+
+    // add a new supervisor notification
+    fn add_supervisor_notification(
+        &self,
+        notification: SupervisorNotification,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_supervisor_notification(notification)
+    }
+
+    // get the next supervisor notification that needs to be handled
+    fn list_supervisor_notifications(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<Identified<SupervisorNotification>>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_supervisor_notifications(service_id)
+    }
+
+    // update an existing supervisor notification
+    fn update_supervisor_notification(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        notification_id: i64,
+        executed_at: SystemTime,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_supervisor_notification(
+            service_id,
+            notification_id,
+            executed_at,
+        )
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<'a> ScabbardStore for DieselConnectionScabbardStore<'a, MysqlConnection> {
+    /// Add a new context
+    fn add_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        context: ConsensusContext,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_consensus_context(service_id, context)
+    }
+    /// Update an existing context
+    fn update_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        context: ConsensusContext,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_consensus_context(service_id, context)
+    }
+    /// Add a 2 phase commit coordinator action
+    fn add_consensus_action(
+        &self,
+        action: ConsensusAction,
+        service_id: &FullyQualifiedServiceId,
+        event_id: i64,
+    ) -> Result<i64, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection)
+            .add_consensus_action(action, service_id, event_id)
+    }
+    /// Update an existing 2 phase commit action
+    fn update_consensus_action(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        action_id: i64,
+        executed_at: SystemTime,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_consensus_action(
+            service_id,
+            action_id,
+            executed_at,
+        )
+    }
+    /// List all coordinator actions for a given service_id
+    fn list_consensus_actions(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<Identified<ConsensusAction>>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_consensus_actions(service_id)
+    }
+    /// List ready services
+    fn list_ready_services(&self) -> Result<Vec<FullyQualifiedServiceId>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_ready_services()
+    }
+    /// Add a new scabbard service
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_service(service)
+    }
+    /// Add a new commit entry
+    fn add_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_commit_entry(commit_entry)
+    }
+    /// Get the commit entry for the specified service_id
+    fn get_last_commit_entry(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<CommitEntry>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_last_commit_entry(service_id)
+    }
+    /// Update an existing commit entry
+    fn update_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_commit_entry(commit_entry)
+    }
+    /// Update an existing scabbard service
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_service(service)
+    }
+    /// Get service
+    fn get_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Option<ScabbardService>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).get_service(service_id)
+    }
+    /// Add a new consensus event
+    fn add_consensus_event(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        event: ConsensusEvent,
+    ) -> Result<i64, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).add_consensus_event(service_id, event)
+    }
+    /// Update an existing consensus event
+    fn update_consensus_event(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        event_id: i64,
+        executed_at: SystemTime,
+        executed_epoch: u64,
+    ) -> Result<(), ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).update_consensus_event(
+            service_id,
+            event_id,
+            executed_at,
+            executed_epoch,
+        )
+    }
+    /// List all consensus events for a given service_id
+    fn list_consensus_events(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<Identified<ConsensusEvent>>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_consensus_events(service_id)
+    }
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_consensus_event_log(service_id)
+    }
     /// Get the current context for a given service
     fn get_current_consensus_context(
         &self,
@@ -852,6 +1313,14 @@ impl<'a> ScabbardStore for DieselConnectionScabbardStore<'a, PgConnection> {
     ) -> Result<Vec<Identified<ConsensusEvent>>, ScabbardStoreError> {
         ScabbardStoreOperations::new(self.connection).list_consensus_events(service_id)
     }
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        ScabbardStoreOperations::new(self.connection).list_consensus_event_log(service_id)
+    }
     /// Get the current context for a given service
     fn get_current_consensus_context(
         &self,
@@ -935,6 +1404,8 @@ pub mod tests {
 
     #[cfg(feature = "sqlite")]
     use crate::migrations::run_sqlite_migrations;
+    #[cfg(feature = "diesel-mysql-tests")]
+    use crate::store::diesel_mysql_test::run_mysql_test;
     #[cfg(feature = "diesel-postgres-tests")]
     use crate::store::diesel_postgres_test::run_postgres_test;
 
@@ -947,6 +1418,8 @@ pub mod tests {
         SupervisorNotificationType,
     };
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    use diesel::mysql::MysqlConnection;
     #[cfg(feature = "diesel-postgres-tests")]
     use diesel::pg::PgConnection;
     use diesel::r2d2::{ConnectionManager, Pool};
@@ -1037,6 +1510,20 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_add_context() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+
+            let store = DieselScabbardStore::new(pool);
+
+            scabbard_store_add_context(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `add_consensus_action` operation is successful.
     ///
     /// 1. Add a valid context to the store
@@ -1115,6 +1602,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_add_actions() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_add_actions(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `list_consensus_actions` operation is successful.
     ///
     /// 1. Add a valid context to the store
@@ -1262,6 +1761,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_list_actions() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_list_actions(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `update_consensus_context` operation is successful.
     ///
     /// 1. Add a valid context to the store
@@ -1355,6 +1866,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_update_context() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_update_context(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `update_consensus_action` operation is successful.
     ///
     /// 1. Add a valid context to the store
@@ -1463,6 +1986,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_update_action() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_update_action(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `get_service` operation is successful.
     ///
     /// 1. Add a service in the finalized state to the database
@@ -1518,6 +2053,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_get_service() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_get_service(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `list_ready_services` operation is successful.
     ///
     /// 1. Add a service in the finalized state to the database
@@ -1673,6 +2220,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_list_ready_services() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_list_ready_services(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `add_commit_entry` operation is successful.
     ///
     /// 1. Add a valid service to the database
@@ -1756,9 +2315,21 @@ pub mod tests {
 
     #[cfg(feature = "diesel-postgres-tests")]
     #[test]
-    fn postgres_scabbard_add_commit_entry() -> Result<(), Box<dyn std::error::Error>> {
-        run_postgres_test(|url| {
-            let pool = create_postgres_pool(url)?;
+    fn postgres_scabbard_add_commit_entry() -> Result<(), Box<dyn std::error::Error>> {
+        run_postgres_test(|url| {
+            let pool = create_postgres_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_add_commit_entry(&store);
+
+            Ok(())
+        })
+    }
+
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_add_commit_entry() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
             let store = DieselScabbardStore::new(pool);
             scabbard_add_commit_entry(&store);
 
@@ -1855,6 +2426,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_get_last_commit_entry() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_get_last_commit_entry(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `update_commit_entry` operation is successful.
     ///
     /// 1. Add a valid service to the database
@@ -1951,6 +2534,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_update_commit_entry() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_update_commit_entry(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `update_service` operation is successful.
     ///
     /// 1. Add a service in the prepared state to the database
@@ -2042,6 +2637,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_update_service() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_update_service(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `add_consensus_event` operation is successful.
     ///
     /// 1. Add a valid participant context to the store
@@ -2128,6 +2735,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_add_event() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_add_event(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `update_consensus_event` operation is successful.
     ///
     /// 1. Add a valid participant context to the store
@@ -2246,6 +2865,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_update_event() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_update_event(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `list_consensus_events` operation is successful.
     ///
     /// 1. Add a valid participant context to the store
@@ -2425,6 +3056,126 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_list_events() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_list_events(&store);
+
+            Ok(())
+        })
+    }
+
+    /// Test that the scabbard store `list_consensus_event_log` operation is successful.
+    ///
+    /// 1. Add a valid participant context to the store
+    /// 2. Add a start event and a vote event to the store
+    /// 3. Update the `executed_at` time for the first event
+    /// 4. Call `list_consensus_event_log` and check that both events are returned in position
+    ///    order, with only the first marked as executed
+    fn scabbard_store_list_event_log(store: &dyn ScabbardStore) {
+        let coordinator_fqsi = FullyQualifiedServiceId::new_random();
+        let participant_fqsi = FullyQualifiedServiceId::new_random();
+
+        let service = ScabbardServiceBuilder::default()
+            .with_service_id(&coordinator_fqsi)
+            .with_peers(&[participant_fqsi.service_id().clone()])
+            .with_consensus(&ConsensusType::TwoPC)
+            .with_status(&ServiceStatus::Finalized)
+            .build()
+            .expect("failed to build service");
+
+        store.add_service(service).expect("failed to add service");
+
+        let context = ContextBuilder::default()
+            .with_coordinator(coordinator_fqsi.service_id())
+            .with_epoch(1)
+            .with_participants(vec![Participant {
+                process: participant_fqsi.service_id().clone(),
+                vote: None,
+                decision_ack: false,
+            }])
+            .with_state(State::WaitingForStart)
+            .with_this_process(coordinator_fqsi.service_id())
+            .build()
+            .expect("failed to build context");
+
+        store
+            .add_consensus_context(&coordinator_fqsi, ConsensusContext::TwoPhaseCommit(context))
+            .expect("failed to add context");
+
+        let start_id = store
+            .add_consensus_event(
+                &coordinator_fqsi,
+                ConsensusEvent::TwoPhaseCommit(Event::Start(b"value".to_vec())),
+            )
+            .expect("failed to add event");
+        store
+            .add_consensus_event(
+                &coordinator_fqsi,
+                ConsensusEvent::TwoPhaseCommit(Event::Vote(true)),
+            )
+            .expect("failed to add event");
+
+        store
+            .update_consensus_event(&coordinator_fqsi, start_id, SystemTime::now(), 1)
+            .expect("failed to update event");
+
+        let log = store
+            .list_consensus_event_log(&coordinator_fqsi)
+            .expect("failed to list event log");
+
+        assert_eq!(log.len(), 2);
+        assert!(log[0].position() < log[1].position());
+        assert_eq!(
+            log[0].event(),
+            &ConsensusEvent::TwoPhaseCommit(Event::Start(b"value".to_vec()))
+        );
+        assert!(log[0].executed_at().is_some());
+        assert_eq!(log[0].executed_epoch(), Some(1));
+        assert_eq!(
+            log[1].event(),
+            &ConsensusEvent::TwoPhaseCommit(Event::Vote(true))
+        );
+        assert!(log[1].executed_at().is_none());
+        assert_eq!(log[1].executed_epoch(), None);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_scabbard_store_list_event_log() {
+        let pool = create_sqlite_memory_pool();
+
+        let store = DieselScabbardStore::new(pool);
+        scabbard_store_list_event_log(&store);
+    }
+
+    #[cfg(feature = "diesel-postgres-tests")]
+    #[test]
+    fn postgres_scabbard_store_list_event_log() -> Result<(), Box<dyn std::error::Error>> {
+        run_postgres_test(|url| {
+            let pool = create_postgres_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_list_event_log(&store);
+
+            Ok(())
+        })
+    }
+
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_list_event_log() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_list_event_log(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `get_current_consensus_context` operation is successful.
     ///
     /// 1. Add two services to the database
@@ -2584,6 +3335,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_get_current_context() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_get_current_context(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `remove_service` operation is successful.
     ///
     /// 1. Add a services to the database
@@ -2683,6 +3446,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_remove_service() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_remove_service(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `set_alarm` operation is successful.
     ///
     /// 1. Add a service to the database
@@ -2768,6 +3543,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_set_alarm() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_set_alarm(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `unset_alarm` operation is successful.
     ///
     /// 1. Add a service to the database
@@ -2868,6 +3655,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_unset_alarm() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_unset_alarm(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `get_alarm` operation is successful.
     ///
     /// 1. Add a service to the database
@@ -2968,6 +3767,18 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_get_alarm() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_get_alarm(&store);
+
+            Ok(())
+        })
+    }
+
     /// Test that the scabbard store `supervisor_notification` operations are successful.
     ///
     /// 1. Add a service to the database
@@ -3119,6 +3930,166 @@ pub mod tests {
         })
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_get_supervisor_notification() -> Result<(), Box<dyn std::error::Error>>
+    {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_get_supervisor_notification(&store);
+
+            Ok(())
+        })
+    }
+
+    /// Test that the scabbard store can hold the state of a three phase commit service.
+    ///
+    /// 1. Add a three phase commit service with a coordinator timeout and check it is returned
+    ///    unchanged
+    /// 2. Add a context waiting for `PreCommit` acknowledgements, then update it to the
+    ///    pre-committed state, and check the current context is returned in that state
+    /// 3. Add a `PreCommitAck` deliver event and a `PreCommit` send message action, and check
+    ///    that both are listed
+    fn scabbard_store_three_phase_commit(store: &dyn ScabbardStore) {
+        let coordinator_fqsi = FullyQualifiedServiceId::new_random();
+        let participant_service_id = ServiceId::new_random();
+
+        let service = ScabbardServiceBuilder::default()
+            .with_service_id(&coordinator_fqsi)
+            .with_peers(&[participant_service_id.clone()])
+            .with_consensus(&ConsensusType::ThreePC)
+            .with_status(&ServiceStatus::Finalized)
+            .with_coordinator_timeout(Duration::from_millis(5000))
+            .build()
+            .expect("failed to build service");
+
+        store
+            .add_service(service.clone())
+            .expect("failed to add service");
+
+        assert_eq!(
+            store
+                .get_service(&coordinator_fqsi)
+                .expect("failed to get service")
+                .expect("service not found"),
+            service
+        );
+
+        let timeout_start = SystemTime::UNIX_EPOCH
+            .checked_add(Duration::from_secs(
+                SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .expect("failed to get duration since UNIX EPOCH")
+                    .as_secs(),
+            ))
+            .expect("failed to get timeout start");
+
+        let context_builder = || {
+            ContextBuilder::default()
+                .with_coordinator(coordinator_fqsi.service_id())
+                .with_epoch(1)
+                .with_participants(vec![Participant {
+                    process: participant_service_id.clone(),
+                    vote: Some(true),
+                    decision_ack: false,
+                }])
+                .with_this_process(coordinator_fqsi.service_id())
+        };
+
+        let context = context_builder()
+            .with_state(State::WaitingForPreCommitAck {
+                ack_timeout_start: timeout_start,
+            })
+            .build()
+            .expect("failed to build context");
+        store
+            .add_consensus_context(&coordinator_fqsi, ConsensusContext::TwoPhaseCommit(context))
+            .expect("failed to add context");
+
+        let context = context_builder()
+            .with_state(State::PreCommitted {
+                decision_timeout_start: timeout_start,
+            })
+            .build()
+            .expect("failed to build context");
+        store
+            .update_consensus_context(
+                &coordinator_fqsi,
+                ConsensusContext::TwoPhaseCommit(context.clone()),
+            )
+            .expect("failed to update context");
+
+        assert_eq!(
+            store
+                .get_current_consensus_context(&coordinator_fqsi)
+                .expect("failed to get context"),
+            Some(ConsensusContext::TwoPhaseCommit(context))
+        );
+
+        let event = ConsensusEvent::TwoPhaseCommit(Event::Deliver(
+            participant_service_id.clone(),
+            Message::PreCommitAck(1),
+        ));
+        let event_id = store
+            .add_consensus_event(&coordinator_fqsi, event.clone())
+            .expect("failed to add event");
+
+        let action = ConsensusAction::TwoPhaseCommit(Action::SendMessage(
+            participant_service_id,
+            Message::PreCommit(1),
+        ));
+        store
+            .add_consensus_action(action.clone(), &coordinator_fqsi, event_id)
+            .expect("failed to add action");
+
+        let events = store
+            .list_consensus_events(&coordinator_fqsi)
+            .expect("failed to list events");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].record, event);
+
+        let actions = store
+            .list_consensus_actions(&coordinator_fqsi)
+            .expect("failed to list actions");
+        assert_eq!(actions.len(), 1);
+        assert_eq!(actions[0].record, action);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_scabbard_store_three_phase_commit() {
+        let pool = create_sqlite_memory_pool();
+
+        let store = DieselScabbardStore::new(pool);
+
+        scabbard_store_three_phase_commit(&store);
+    }
+
+    #[cfg(feature = "diesel-postgres-tests")]
+    #[test]
+    fn postgres_scabbard_store_three_phase_commit() -> Result<(), Box<dyn std::error::Error>> {
+        run_postgres_test(|url| {
+            let pool = create_postgres_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_three_phase_commit(&store);
+
+            Ok(())
+        })
+    }
+
+    #[cfg(feature = "diesel-mysql-tests")]
+    #[test]
+    fn mysql_scabbard_store_three_phase_commit() -> Result<(), Box<dyn std::error::Error>> {
+        run_mysql_test(|url| {
+            let pool = create_mysql_pool(url)?;
+            let store = DieselScabbardStore::new(pool);
+            scabbard_store_three_phase_commit(&store);
+
+            Ok(())
+        })
+    }
+
     #[cfg(feature = "sqlite")]
     fn create_sqlite_memory_pool() -> Pool<ConnectionManager<SqliteConnection>> {
         let connection_manager = ConnectionManager::<SqliteConnection>::new(":memory:");
@@ -3153,6 +4124,16 @@ pub mod tests {
         }
     }
 
+    #[cfg(feature = "diesel-mysql-tests")]
+    fn create_mysql_pool(
+        url: &str,
+    ) -> Result<Pool<ConnectionManager<MysqlConnection>>, Box<dyn std::error::Error>> {
+        let connection_manager = ConnectionManager::<MysqlConnection>::new(url);
+        let pool = Pool::builder().build(connection_manager)?;
+
+        Ok(pool)
+    }
+
     #[cfg(feature = "diesel-postgres-tests")]
     fn create_postgres_pool(
         url: &str,
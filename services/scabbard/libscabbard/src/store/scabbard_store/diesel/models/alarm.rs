@@ -29,6 +29,8 @@ use diesel::{
     row::Row,
 };
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::Mysql;
 #[cfg(feature = "postgres")]
 use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
@@ -171,6 +173,21 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<AlarmTypeModelMapping, Mysql> for AlarmTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"TWO_PHASE_COMMIT") => Ok(AlarmTypeModel::TwoPhaseCommit),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<AlarmTypeModelMapping, Pg> for AlarmTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -186,6 +203,13 @@ impl FromSql<AlarmTypeModelMapping, Pg> for AlarmTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<AlarmTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<AlarmTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -30,6 +30,8 @@ use diesel::{
     row::Row,
 };
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::Mysql;
 #[cfg(feature = "postgres")]
 use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
@@ -164,6 +166,22 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<DecisionTypeModelMapping, Mysql> for DecisionTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"COMMIT") => Ok(DecisionTypeModel::Commit),
+            Some(b"ABORT") => Ok(DecisionTypeModel::Abort),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<DecisionTypeModelMapping, Pg> for DecisionTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -180,6 +198,13 @@ impl FromSql<DecisionTypeModelMapping, Pg> for DecisionTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<DecisionTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<DecisionTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
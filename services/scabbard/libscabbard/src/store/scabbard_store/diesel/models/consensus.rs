@@ -32,6 +32,8 @@ use diesel::{
     row::Row,
 };
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::Mysql;
 #[cfg(feature = "postgres")]
 use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
@@ -83,6 +85,8 @@ pub enum ContextStateModel {
     WaitingForVoteRequest,
     WaitingForVote,
     WaitingForDecisionAck,
+    WaitingForPreCommitAck,
+    PreCommitted,
 }
 
 pub struct ContextStateModelMapping;
@@ -159,6 +163,10 @@ impl<DB: Backend> ToSql<ContextStateModelMapping, DB> for ContextStateModel {
             ContextStateModel::WaitingForDecisionAck => {
                 out.write_all(b"WAITING_FOR_DECISION_ACK")?
             }
+            ContextStateModel::WaitingForPreCommitAck => {
+                out.write_all(b"WAITING_FOR_PRE_COMMIT_ACK")?
+            }
+            ContextStateModel::PreCommitted => out.write_all(b"PRE_COMMITTED")?,
         }
         Ok(IsNull::No)
     }
@@ -196,6 +204,30 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<ContextStateModelMapping, Mysql> for ContextStateModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"ABORT") => Ok(ContextStateModel::Abort),
+            Some(b"COMMIT") => Ok(ContextStateModel::Commit),
+            Some(b"VOTED") => Ok(ContextStateModel::Voted),
+            Some(b"VOTING") => Ok(ContextStateModel::Voting),
+            Some(b"WAITING_FOR_START") => Ok(ContextStateModel::WaitingForStart),
+            Some(b"WAITING_FOR_VOTE_REQUEST") => Ok(ContextStateModel::WaitingForVoteRequest),
+            Some(b"WAITING_FOR_VOTE") => Ok(ContextStateModel::WaitingForVote),
+            Some(b"WAITING_FOR_DECISION_ACK") => Ok(ContextStateModel::WaitingForDecisionAck),
+            Some(b"WAITING_FOR_PRE_COMMIT_ACK") => Ok(ContextStateModel::WaitingForPreCommitAck),
+            Some(b"PRE_COMMITTED") => Ok(ContextStateModel::PreCommitted),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<ContextStateModelMapping, Pg> for ContextStateModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -208,6 +240,8 @@ impl FromSql<ContextStateModelMapping, Pg> for ContextStateModel {
             Some(b"WAITING_FOR_VOTE_REQUEST") => Ok(ContextStateModel::WaitingForVoteRequest),
             Some(b"WAITING_FOR_VOTE") => Ok(ContextStateModel::WaitingForVote),
             Some(b"WAITING_FOR_DECISION_ACK") => Ok(ContextStateModel::WaitingForDecisionAck),
+            Some(b"WAITING_FOR_PRE_COMMIT_ACK") => Ok(ContextStateModel::WaitingForPreCommitAck),
+            Some(b"PRE_COMMITTED") => Ok(ContextStateModel::PreCommitted),
             Some(v) => Err(format!(
                 "Unrecognized enum variant: '{}'",
                 String::from_utf8_lossy(v)
@@ -218,6 +252,13 @@ impl FromSql<ContextStateModelMapping, Pg> for ContextStateModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<ContextStateModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<ContextStateModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -237,6 +278,8 @@ impl FromSql<ContextStateModelMapping, Sqlite> for ContextStateModel {
             Some(b"WAITING_FOR_VOTE_REQUEST") => Ok(ContextStateModel::WaitingForVoteRequest),
             Some(b"WAITING_FOR_VOTE") => Ok(ContextStateModel::WaitingForVote),
             Some(b"WAITING_FOR_DECISION_ACK") => Ok(ContextStateModel::WaitingForDecisionAck),
+            Some(b"WAITING_FOR_PRE_COMMIT_ACK") => Ok(ContextStateModel::WaitingForPreCommitAck),
+            Some(b"PRE_COMMITTED") => Ok(ContextStateModel::PreCommitted),
             Some(blob) => {
                 Err(format!("Unexpected variant: {}", String::from_utf8_lossy(blob)).into())
             }
@@ -350,6 +393,46 @@ impl
                 };
                 State::WaitingForDecisionAck { ack_timeout_start }
             }
+            ContextStateModel::WaitingForPreCommitAck => {
+                let ack_timeout_start = if let Some(t) = context.ack_timeout_start {
+                    SystemTime::UNIX_EPOCH
+                        .checked_add(Duration::from_secs(t as u64))
+                        .ok_or_else(|| {
+                            InternalError::with_message(
+                                "Failed to convert ack timeout start timestamp to SystemTime"
+                                    .to_string(),
+                            )
+                        })?
+                } else {
+                    return Err(InternalError::with_message(
+                        "Failed to convert to ConsensusContext, context has state \
+                        'WaitingForPreCommitAck' but 'ack_timeout_start' is unset"
+                            .to_string(),
+                    ));
+                };
+                State::WaitingForPreCommitAck { ack_timeout_start }
+            }
+            ContextStateModel::PreCommitted => {
+                let decision_timeout_start = if let Some(t) = context.decision_timeout_start {
+                    SystemTime::UNIX_EPOCH
+                        .checked_add(Duration::from_secs(t as u64))
+                        .ok_or_else(|| {
+                            InternalError::with_message(
+                                "Failed to convert decision timeout start timestamp to SystemTime"
+                                    .to_string(),
+                            )
+                        })?
+                } else {
+                    return Err(InternalError::with_message(
+                        "Failed to convert to ConsensusContext, context has state \
+                        'PreCommitted' but 'decision_timeout_start' is unset"
+                            .to_string(),
+                    ));
+                };
+                State::PreCommitted {
+                    decision_timeout_start,
+                }
+            }
         };
 
         let mut builder = ContextBuilder::default()
@@ -414,7 +497,20 @@ impl TryFrom<(&Context, &FullyQualifiedServiceId)> for Consensus2pcContextModel
                     .map_err(|err| InternalError::from_source(Box::new(err)))?;
                     (None, Some(vote), Some(time), None)
                 }
-                State::WaitingForDecisionAck { ack_timeout_start } => {
+                State::PreCommitted {
+                    decision_timeout_start,
+                } => {
+                    let time = i64::try_from(
+                        decision_timeout_start
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .map_err(|err| InternalError::from_source(Box::new(err)))?
+                            .as_secs(),
+                    )
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                    (None, None, Some(time), None)
+                }
+                State::WaitingForDecisionAck { ack_timeout_start }
+                | State::WaitingForPreCommitAck { ack_timeout_start } => {
                     let time = i64::try_from(
                         ack_timeout_start
                             .duration_since(SystemTime::UNIX_EPOCH)
@@ -569,7 +665,20 @@ impl TryFrom<(&Context, &i64, &Option<i64>)> for Consensus2pcUpdateContextAction
                     .map_err(|err| InternalError::from_source(Box::new(err)))?;
                     (None, Some(vote), Some(time), None)
                 }
-                State::WaitingForDecisionAck { ack_timeout_start } => {
+                State::PreCommitted {
+                    decision_timeout_start,
+                } => {
+                    let time = i64::try_from(
+                        decision_timeout_start
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .map_err(|err| InternalError::from_source(Box::new(err)))?
+                            .as_secs(),
+                    )
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                    (None, None, Some(time), None)
+                }
+                State::WaitingForDecisionAck { ack_timeout_start }
+                | State::WaitingForPreCommitAck { ack_timeout_start } => {
                     let time = i64::try_from(
                         ack_timeout_start
                             .duration_since(SystemTime::UNIX_EPOCH)
@@ -658,6 +767,8 @@ pub enum MessageTypeModel {
     Commit,
     Abort,
     DecisionAck,
+    PreCommit,
+    PreCommitAck,
 }
 
 pub struct MessageTypeModelMapping;
@@ -728,6 +839,8 @@ impl<DB: Backend> ToSql<MessageTypeModelMapping, DB> for MessageTypeModel {
             MessageTypeModel::Commit => out.write_all(b"COMMIT")?,
             MessageTypeModel::Abort => out.write_all(b"ABORT")?,
             MessageTypeModel::DecisionAck => out.write_all(b"DECISION_ACK")?,
+            MessageTypeModel::PreCommit => out.write_all(b"PRE_COMMIT")?,
+            MessageTypeModel::PreCommitAck => out.write_all(b"PRE_COMMIT_ACK")?,
         }
         Ok(IsNull::No)
     }
@@ -765,6 +878,28 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<MessageTypeModelMapping, Mysql> for MessageTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"VOTE_RESPONSE") => Ok(MessageTypeModel::VoteResponse),
+            Some(b"DECISION_REQUEST") => Ok(MessageTypeModel::DecisionRequest),
+            Some(b"VOTE_REQUEST") => Ok(MessageTypeModel::VoteRequest),
+            Some(b"COMMIT") => Ok(MessageTypeModel::Commit),
+            Some(b"ABORT") => Ok(MessageTypeModel::Abort),
+            Some(b"DECISION_ACK") => Ok(MessageTypeModel::DecisionAck),
+            Some(b"PRE_COMMIT") => Ok(MessageTypeModel::PreCommit),
+            Some(b"PRE_COMMIT_ACK") => Ok(MessageTypeModel::PreCommitAck),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<MessageTypeModelMapping, Pg> for MessageTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -775,6 +910,8 @@ impl FromSql<MessageTypeModelMapping, Pg> for MessageTypeModel {
             Some(b"COMMIT") => Ok(MessageTypeModel::Commit),
             Some(b"ABORT") => Ok(MessageTypeModel::Abort),
             Some(b"DECISION_ACK") => Ok(MessageTypeModel::DecisionAck),
+            Some(b"PRE_COMMIT") => Ok(MessageTypeModel::PreCommit),
+            Some(b"PRE_COMMIT_ACK") => Ok(MessageTypeModel::PreCommitAck),
             Some(v) => Err(format!(
                 "Unrecognized enum variant: '{}'",
                 String::from_utf8_lossy(v)
@@ -785,6 +922,13 @@ impl FromSql<MessageTypeModelMapping, Pg> for MessageTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<MessageTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<MessageTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -802,6 +946,8 @@ impl FromSql<MessageTypeModelMapping, Sqlite> for MessageTypeModel {
             Some(b"COMMIT") => Ok(MessageTypeModel::Commit),
             Some(b"ABORT") => Ok(MessageTypeModel::Abort),
             Some(b"DECISION_ACK") => Ok(MessageTypeModel::DecisionAck),
+            Some(b"PRE_COMMIT") => Ok(MessageTypeModel::PreCommit),
+            Some(b"PRE_COMMIT_ACK") => Ok(MessageTypeModel::PreCommitAck),
             Some(blob) => {
                 Err(format!("Unexpected variant: {}", String::from_utf8_lossy(blob)).into())
             }
@@ -951,6 +1097,30 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<NotificationTypeModelMapping, Mysql> for NotificationTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"REQUEST_FOR_START") => Ok(NotificationTypeModel::RequestForStart),
+            Some(b"COORDINATOR_REQUEST_FOR_VOTE") => {
+                Ok(NotificationTypeModel::CoordinatorRequestForVote)
+            }
+            Some(b"PARTICIPANT_REQUEST_FOR_VOTE") => {
+                Ok(NotificationTypeModel::ParticipantRequestForVote)
+            }
+            Some(b"COMMIT") => Ok(NotificationTypeModel::Commit),
+            Some(b"ABORT") => Ok(NotificationTypeModel::Abort),
+            Some(b"MESSAGE_DROPPED") => Ok(NotificationTypeModel::MessageDropped),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<NotificationTypeModelMapping, Pg> for NotificationTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -975,6 +1145,13 @@ impl FromSql<NotificationTypeModelMapping, Pg> for NotificationTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<NotificationTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<NotificationTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -1022,6 +1199,8 @@ impl From<&State> for String {
             State::WaitingForVoteRequest => String::from("WAITING_FOR_VOTE_REQUEST"),
             State::Voted { .. } => String::from("VOTED"),
             State::WaitingForDecisionAck { .. } => String::from("WAITING_FOR_DECISION_ACK"),
+            State::WaitingForPreCommitAck { .. } => String::from("WAITING_FOR_PRE_COMMIT_ACK"),
+            State::PreCommitted { .. } => String::from("PRE_COMMITTED"),
         }
     }
 }
@@ -1037,6 +1216,8 @@ impl From<&State> for ContextStateModel {
             State::WaitingForVoteRequest => ContextStateModel::WaitingForVoteRequest,
             State::Voted { .. } => ContextStateModel::Voted,
             State::WaitingForDecisionAck { .. } => ContextStateModel::WaitingForDecisionAck,
+            State::WaitingForPreCommitAck { .. } => ContextStateModel::WaitingForPreCommitAck,
+            State::PreCommitted { .. } => ContextStateModel::PreCommitted,
         }
     }
 }
@@ -1187,6 +1368,23 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<ActionTypeModelMapping, Mysql> for ActionTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"UPDATE_CONTEXT") => Ok(ActionTypeModel::Update),
+            Some(b"SEND_MESSAGE") => Ok(ActionTypeModel::SendMessage),
+            Some(b"NOTIFICATION") => Ok(ActionTypeModel::Notify),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<ActionTypeModelMapping, Pg> for ActionTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -1204,6 +1402,13 @@ impl FromSql<ActionTypeModelMapping, Pg> for ActionTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<ActionTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<ActionTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -1276,6 +1481,8 @@ impl From<&Message> for String {
             Message::Commit(_) => String::from("COMMIT"),
             Message::Abort(_) => String::from("ABORT"),
             Message::DecisionAck(_) => String::from("DECISION_ACK"),
+            Message::PreCommit(_) => String::from("PRE_COMMIT"),
+            Message::PreCommitAck(_) => String::from("PRE_COMMIT_ACK"),
         }
     }
 }
@@ -1289,6 +1496,8 @@ impl From<&Message> for MessageTypeModel {
             Message::Commit(_) => MessageTypeModel::Commit,
             Message::Abort(_) => MessageTypeModel::Abort,
             Message::DecisionAck(_) => MessageTypeModel::DecisionAck,
+            Message::PreCommit(_) => MessageTypeModel::PreCommit,
+            Message::PreCommitAck(_) => MessageTypeModel::PreCommitAck,
         }
     }
 }
@@ -1442,6 +1651,24 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<EventTypeModelMapping, Mysql> for EventTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"ALARM") => Ok(EventTypeModel::Alarm),
+            Some(b"DELIVER") => Ok(EventTypeModel::Deliver),
+            Some(b"START") => Ok(EventTypeModel::Start),
+            Some(b"VOTE") => Ok(EventTypeModel::Vote),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<EventTypeModelMapping, Pg> for EventTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -1460,6 +1687,13 @@ impl FromSql<EventTypeModelMapping, Pg> for EventTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<EventTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<EventTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -1513,6 +1747,8 @@ pub enum DeliverMessageTypeModel {
     Commit,
     Abort,
     DecisionAck,
+    PreCommit,
+    PreCommitAck,
 }
 
 pub struct DeliverMessageTypeModelMapping;
@@ -1585,6 +1821,8 @@ impl<DB: Backend> ToSql<DeliverMessageTypeModelMapping, DB> for DeliverMessageTy
             DeliverMessageTypeModel::Commit => out.write_all(b"COMMIT")?,
             DeliverMessageTypeModel::Abort => out.write_all(b"ABORT")?,
             DeliverMessageTypeModel::DecisionAck => out.write_all(b"DECISION_ACK")?,
+            DeliverMessageTypeModel::PreCommit => out.write_all(b"PRE_COMMIT")?,
+            DeliverMessageTypeModel::PreCommitAck => out.write_all(b"PRE_COMMIT_ACK")?,
         }
         Ok(IsNull::No)
     }
@@ -1622,6 +1860,28 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<DeliverMessageTypeModelMapping, Mysql> for DeliverMessageTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"VOTE_RESPONSE") => Ok(DeliverMessageTypeModel::VoteResponse),
+            Some(b"DECISION_REQUEST") => Ok(DeliverMessageTypeModel::DecisionRequest),
+            Some(b"VOTE_REQUEST") => Ok(DeliverMessageTypeModel::VoteRequest),
+            Some(b"COMMIT") => Ok(DeliverMessageTypeModel::Commit),
+            Some(b"ABORT") => Ok(DeliverMessageTypeModel::Abort),
+            Some(b"DECISION_ACK") => Ok(DeliverMessageTypeModel::DecisionAck),
+            Some(b"PRE_COMMIT") => Ok(DeliverMessageTypeModel::PreCommit),
+            Some(b"PRE_COMMIT_ACK") => Ok(DeliverMessageTypeModel::PreCommitAck),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<DeliverMessageTypeModelMapping, Pg> for DeliverMessageTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -1632,6 +1892,8 @@ impl FromSql<DeliverMessageTypeModelMapping, Pg> for DeliverMessageTypeModel {
             Some(b"COMMIT") => Ok(DeliverMessageTypeModel::Commit),
             Some(b"ABORT") => Ok(DeliverMessageTypeModel::Abort),
             Some(b"DECISION_ACK") => Ok(DeliverMessageTypeModel::DecisionAck),
+            Some(b"PRE_COMMIT") => Ok(DeliverMessageTypeModel::PreCommit),
+            Some(b"PRE_COMMIT_ACK") => Ok(DeliverMessageTypeModel::PreCommitAck),
             Some(v) => Err(format!(
                 "Unrecognized enum variant: '{}'",
                 String::from_utf8_lossy(v)
@@ -1642,6 +1904,13 @@ impl FromSql<DeliverMessageTypeModelMapping, Pg> for DeliverMessageTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<DeliverMessageTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<DeliverMessageTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -1659,6 +1928,8 @@ impl FromSql<DeliverMessageTypeModelMapping, Sqlite> for DeliverMessageTypeModel
             Some(b"COMMIT") => Ok(DeliverMessageTypeModel::Commit),
             Some(b"ABORT") => Ok(DeliverMessageTypeModel::Abort),
             Some(b"DECISION_ACK") => Ok(DeliverMessageTypeModel::DecisionAck),
+            Some(b"PRE_COMMIT") => Ok(DeliverMessageTypeModel::PreCommit),
+            Some(b"PRE_COMMIT_ACK") => Ok(DeliverMessageTypeModel::PreCommitAck),
             Some(blob) => {
                 Err(format!("Unexpected variant: {}", String::from_utf8_lossy(blob)).into())
             }
@@ -1683,6 +1954,8 @@ impl From<&Message> for DeliverMessageTypeModel {
             Message::Commit(_) => DeliverMessageTypeModel::Commit,
             Message::Abort(_) => DeliverMessageTypeModel::Abort,
             Message::DecisionAck(_) => DeliverMessageTypeModel::DecisionAck,
+            Message::PreCommit(_) => DeliverMessageTypeModel::PreCommit,
+            Message::PreCommitAck(_) => DeliverMessageTypeModel::PreCommitAck,
         }
     }
 }
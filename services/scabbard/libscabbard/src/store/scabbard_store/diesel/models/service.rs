@@ -15,6 +15,8 @@
 use std::convert::TryFrom;
 use std::io::Write;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::Mysql;
 #[cfg(feature = "postgres")]
 use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
@@ -49,11 +51,13 @@ pub struct ScabbardServiceModel {
     pub service_id: String,
     pub consensus: ConsensusTypeModel,
     pub status: ServiceStatusTypeModel,
+    pub coordinator_timeout: Option<i64>,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ConsensusTypeModel {
     Consensus2pc,
+    Consensus3pc,
 }
 
 // This has to be pub, due to its use in the table macro execution for IdentityModel
@@ -120,6 +124,7 @@ impl<DB: Backend> ToSql<ConsensusTypeModelMapping, DB> for ConsensusTypeModel {
     fn to_sql<W: Write>(&self, out: &mut Output<W, DB>) -> serialize::Result {
         match self {
             ConsensusTypeModel::Consensus2pc => out.write_all(b"2PC")?,
+            ConsensusTypeModel::Consensus3pc => out.write_all(b"3PC")?,
         }
         Ok(IsNull::No)
     }
@@ -157,11 +162,28 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<ConsensusTypeModelMapping, Mysql> for ConsensusTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"2PC") => Ok(ConsensusTypeModel::Consensus2pc),
+            Some(b"3PC") => Ok(ConsensusTypeModel::Consensus3pc),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<ConsensusTypeModelMapping, Pg> for ConsensusTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
         match bytes {
             Some(b"2PC") => Ok(ConsensusTypeModel::Consensus2pc),
+            Some(b"3PC") => Ok(ConsensusTypeModel::Consensus3pc),
             Some(v) => Err(format!(
                 "Unrecognized enum variant: '{}'",
                 String::from_utf8_lossy(v)
@@ -172,6 +194,13 @@ impl FromSql<ConsensusTypeModelMapping, Pg> for ConsensusTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<ConsensusTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<ConsensusTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -184,6 +213,7 @@ impl FromSql<ConsensusTypeModelMapping, Sqlite> for ConsensusTypeModel {
     fn from_sql(bytes: Option<&<Sqlite as Backend>::RawValue>) -> deserialize::Result<Self> {
         match bytes.map(|v| v.read_blob()) {
             Some(b"2PC") => Ok(ConsensusTypeModel::Consensus2pc),
+            Some(b"3PC") => Ok(ConsensusTypeModel::Consensus3pc),
             Some(blob) => {
                 Err(format!("Unexpected variant: {}", String::from_utf8_lossy(blob)).into())
             }
@@ -311,6 +341,23 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<ServiceStatusTypeModelMapping, Mysql> for ServiceStatusTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"PREPARED") => Ok(ServiceStatusTypeModel::Prepared),
+            Some(b"FINALIZED") => Ok(ServiceStatusTypeModel::Finalized),
+            Some(b"RETIRED") => Ok(ServiceStatusTypeModel::Retired),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<ServiceStatusTypeModelMapping, Pg> for ServiceStatusTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -328,6 +375,13 @@ impl FromSql<ServiceStatusTypeModelMapping, Pg> for ServiceStatusTypeModel {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<ServiceStatusTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<ServiceStatusTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -364,6 +418,9 @@ impl From<&ScabbardService> for ScabbardServiceModel {
             service_id: service.service_id().service_id().to_string(),
             consensus: service.consensus().into(),
             status: service.status().into(),
+            coordinator_timeout: service
+                .coordinator_timeout()
+                .map(|timeout| i64::try_from(timeout.as_millis()).unwrap_or(i64::MAX)),
         }
     }
 }
@@ -446,6 +503,7 @@ impl TryFrom<&str> for ConsensusType {
     fn try_from(consensus: &str) -> Result<Self, Self::Error> {
         match consensus {
             "2PC" => Ok(ConsensusType::TwoPC),
+            "3PC" => Ok(ConsensusType::ThreePC),
             _ => Err(InternalError::with_message(format!(
                 "Unknown consensus {}",
                 consensus
@@ -458,6 +516,7 @@ impl From<&ConsensusType> for String {
     fn from(consensus: &ConsensusType) -> Self {
         match *consensus {
             ConsensusType::TwoPC => "2PC".into(),
+            ConsensusType::ThreePC => "3PC".into(),
         }
     }
 }
@@ -466,6 +525,7 @@ impl From<&ConsensusTypeModel> for ConsensusType {
     fn from(consensus: &ConsensusTypeModel) -> Self {
         match consensus {
             ConsensusTypeModel::Consensus2pc => ConsensusType::TwoPC,
+            ConsensusTypeModel::Consensus3pc => ConsensusType::ThreePC,
         }
     }
 }
@@ -474,6 +534,7 @@ impl From<&ConsensusType> for ConsensusTypeModel {
     fn from(consensus: &ConsensusType) -> Self {
         match *consensus {
             ConsensusType::TwoPC => ConsensusTypeModel::Consensus2pc,
+            ConsensusType::ThreePC => ConsensusTypeModel::Consensus3pc,
         }
     }
 }
@@ -18,6 +18,8 @@ use std::time::SystemTime;
 use std::io::Write;
 
 use chrono::naive::NaiveDateTime;
+#[cfg(feature = "mysql")]
+use diesel::mysql::Mysql;
 #[cfg(feature = "postgres")]
 use diesel::pg::Pg;
 #[cfg(feature = "sqlite")]
@@ -270,6 +272,29 @@ where
     }
 }
 
+#[cfg(feature = "mysql")]
+impl FromSql<SupervisorNotificationTypeModelMapping, Mysql> for SupervisorNotificationTypeModel {
+    fn from_sql(bytes: Option<&<Mysql as Backend>::RawValue>) -> deserialize::Result<Self> {
+        match bytes {
+            Some(b"REQUEST_FOR_START") => Ok(SupervisorNotificationTypeModel::RequestForStart),
+            Some(b"COORDINATOR_REQUEST_FOR_VOTE") => {
+                Ok(SupervisorNotificationTypeModel::CoordinatorRequestForVote)
+            }
+            Some(b"PARTICIPANT_REQUEST_FOR_VOTE") => {
+                Ok(SupervisorNotificationTypeModel::ParticipantRequestForVote)
+            }
+            Some(b"COMMIT") => Ok(SupervisorNotificationTypeModel::Commit),
+            Some(b"ABORT") => Ok(SupervisorNotificationTypeModel::Abort),
+            Some(v) => Err(format!(
+                "Unrecognized enum variant: '{}'",
+                String::from_utf8_lossy(v)
+            )
+            .into()),
+            None => Err("Unexpected null for non-null column".into()),
+        }
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl FromSql<SupervisorNotificationTypeModelMapping, Pg> for SupervisorNotificationTypeModel {
     fn from_sql(bytes: Option<&<Pg as Backend>::RawValue>) -> deserialize::Result<Self> {
@@ -293,6 +318,13 @@ impl FromSql<SupervisorNotificationTypeModelMapping, Pg> for SupervisorNotificat
     }
 }
 
+#[cfg(feature = "mysql")]
+impl HasSqlType<SupervisorNotificationTypeModelMapping> for Mysql {
+    fn metadata(_lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
+        diesel::mysql::MysqlType::String
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl HasSqlType<SupervisorNotificationTypeModelMapping> for Pg {
     fn metadata(lookup: &Self::MetadataLookup) -> Self::TypeMetadata {
@@ -14,6 +14,8 @@
 
 use std::convert::TryFrom;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -104,6 +106,74 @@ impl<'a> AddCommitEntryOperation for ScabbardStoreOperations<'a, SqliteConnectio
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> AddCommitEntryOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn add_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(commit_entry.service_id().circuit_id().to_string())
+                        .and(
+                            scabbard_service::service_id
+                                .eq(commit_entry.service_id().service_id().to_string()),
+                        ),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist",
+                    )))
+                })?;
+
+            // check to see if a context with the given service_id exists
+            let context = consensus_2pc_context::table
+                .filter(
+                    consensus_2pc_context::circuit_id
+                        .eq(commit_entry.service_id().circuit_id().to_string())
+                        .and(
+                            consensus_2pc_context::service_id
+                                .eq(commit_entry.service_id().service_id().to_string()),
+                        ),
+                )
+                .first::<Consensus2pcContextModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(format!(
+                        "Cannot add commit entry, context with service ID {} does not exist",
+                        commit_entry.service_id()
+                    )))
+                })?;
+
+            let commit_entry = commit_entry
+                .into_builder()
+                .with_epoch(
+                    u64::try_from(context.epoch)
+                        .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                )
+                .build()
+                .map_err(ScabbardStoreError::InvalidState)?;
+
+            insert_into(scabbard_v3_commit_history::table)
+                .values(vec![CommitEntryModel::try_from(&commit_entry)?])
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> AddCommitEntryOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn add_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
@@ -15,11 +15,15 @@
 use std::convert::TryFrom;
 use std::time::SystemTime;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
 use diesel::sqlite::SqliteConnection;
 use diesel::{dsl::insert_into, prelude::*};
+#[cfg(feature = "mysql")]
+use diesel::{dsl::sql, sql_types::BigInt};
 use splinter::error::{InternalError, InvalidStateError};
 use splinter::service::FullyQualifiedServiceId;
 
@@ -160,7 +164,180 @@ impl<'a> AddActionOperation for ScabbardStoreOperations<'a, SqliteConnection> {
                             Some(value.clone()),
                             epoch,
                         ),
-                        Message::DecisionAck(epoch) => {
+                        Message::DecisionAck(epoch)
+                        | Message::PreCommit(epoch)
+                        | Message::PreCommitAck(epoch) => {
+                            (MessageTypeModel::from(&message), None, None, epoch)
+                        }
+                    };
+
+                    let send_message_action = Consensus2pcSendMessageActionModel {
+                        action_id,
+                        epoch: i64::try_from(epoch)
+                            .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                        receiver_service_id: format!("{}", receiving_process),
+                        message_type,
+                        vote_response,
+                        vote_request,
+                    };
+                    insert_into(consensus_2pc_send_message_action::table)
+                        .values(vec![send_message_action])
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+                    Ok(action_id)
+                }
+                Action::Notify(notification) => {
+                    let (notification_type, dropped_message, request_for_vote_value) =
+                        match &notification {
+                            Notification::MessageDropped(message) => (
+                                NotificationTypeModel::from(&notification),
+                                Some(message.clone()),
+                                None,
+                            ),
+                            Notification::ParticipantRequestForVote(value) => (
+                                NotificationTypeModel::from(&notification),
+                                None,
+                                Some(value.clone()),
+                            ),
+                            _ => (NotificationTypeModel::from(&notification), None, None),
+                        };
+
+                    let notification_action = Consensus2pcNotificationModel {
+                        action_id,
+                        notification_type,
+                        dropped_message,
+                        request_for_vote_value,
+                    };
+                    insert_into(consensus_2pc_notification_action::table)
+                        .values(vec![notification_action])
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+                    Ok(action_id)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<'a> AddActionOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn add_consensus_action(
+        &self,
+        action: ConsensusAction,
+        service_id: &FullyQualifiedServiceId,
+        event_id: i64,
+    ) -> Result<i64, ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            let ConsensusAction::TwoPhaseCommit(action) = action;
+
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_service::service_id.eq(service_id.service_id().to_string())),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist",
+                    )))
+                })?;
+
+            let insertable_action = InsertableConsensus2pcActionModel {
+                circuit_id: service_id.circuit_id().to_string(),
+                service_id: service_id.service_id().to_string(),
+                executed_at: None,
+                event_id,
+                action_type: ActionTypeModel::from(&action),
+            };
+
+            insert_into(consensus_2pc_action::table)
+                .values(vec![insertable_action])
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+            // MySQL does not support `RETURNING`, so the ID generated by this connection's
+            // insert is selected instead
+            let action_id = diesel::select(sql::<BigInt>("LAST_INSERT_ID()"))
+                .get_result::<i64>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            match action {
+                Action::Update(context, alarm) => match context {
+                    ConsensusContext::TwoPhaseCommit(context) => {
+                        let action_alarm = get_timestamp(alarm)?;
+
+                        let update_context_action = Consensus2pcUpdateContextActionModel::try_from(
+                            (&context, &action_id, &action_alarm),
+                        )?;
+
+                        insert_into(consensus_2pc_update_context_action::table)
+                            .values(vec![update_context_action])
+                            .execute(self.conn)
+                            .map_err(|err| {
+                                ScabbardStoreError::from_source_with_operation(
+                                    err,
+                                    OPERATION_NAME.to_string(),
+                                )
+                            })?;
+
+                        let participants =
+                            UpdateContextActionParticipantList::try_from((&context, &action_id))?
+                                .inner;
+                        insert_into(consensus_2pc_update_context_action_participant::table)
+                            .values(participants)
+                            .execute(self.conn)
+                            .map_err(|err| {
+                                ScabbardStoreError::from_source_with_operation(
+                                    err,
+                                    OPERATION_NAME.to_string(),
+                                )
+                            })?;
+
+                        Ok(action_id)
+                    }
+                },
+                Action::SendMessage(receiving_process, message) => {
+                    let (message_type, vote_response, vote_request, epoch) = match message {
+                        Message::DecisionRequest(epoch) => {
+                            (MessageTypeModel::from(&message), None, None, epoch)
+                        }
+                        Message::VoteResponse(epoch, vote) => {
+                            (MessageTypeModel::from(&message), Some(vote), None, epoch)
+                        }
+                        Message::Commit(epoch) => {
+                            (MessageTypeModel::from(&message), None, None, epoch)
+                        }
+                        Message::Abort(epoch) => {
+                            (MessageTypeModel::from(&message), None, None, epoch)
+                        }
+                        Message::VoteRequest(epoch, ref value) => (
+                            MessageTypeModel::from(&message),
+                            None,
+                            Some(value.clone()),
+                            epoch,
+                        ),
+                        Message::DecisionAck(epoch)
+                        | Message::PreCommit(epoch)
+                        | Message::PreCommitAck(epoch) => {
                             (MessageTypeModel::from(&message), None, None, epoch)
                         }
                     };
@@ -323,7 +500,9 @@ impl<'a> AddActionOperation for ScabbardStoreOperations<'a, PgConnection> {
                             Some(value.clone()),
                             epoch,
                         ),
-                        Message::DecisionAck(epoch) => {
+                        Message::DecisionAck(epoch)
+                        | Message::PreCommit(epoch)
+                        | Message::PreCommitAck(epoch) => {
                             (MessageTypeModel::from(&message), None, None, epoch)
                         }
                     };
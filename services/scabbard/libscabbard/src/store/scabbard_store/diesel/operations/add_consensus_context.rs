@@ -14,6 +14,8 @@
 
 use std::convert::TryFrom;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -105,6 +107,70 @@ impl<'a> AddContextOperation for ScabbardStoreOperations<'a, SqliteConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> AddContextOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn add_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        context: ConsensusContext,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            match context {
+                ConsensusContext::TwoPhaseCommit(context) => {
+                    // check to see if a service with the given service_id exists
+                    scabbard_service::table
+                        .filter(
+                            scabbard_service::circuit_id
+                                .eq(service_id.circuit_id().to_string())
+                                .and(
+                                    scabbard_service::service_id
+                                        .eq(service_id.service_id().to_string()),
+                                ),
+                        )
+                        .first::<ScabbardServiceModel>(self.conn)
+                        .optional()
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?
+                        .ok_or_else(|| {
+                            ScabbardStoreError::InvalidState(InvalidStateError::with_message(
+                                String::from("Service does not exist"),
+                            ))
+                        })?;
+
+                    let new_context = Consensus2pcContextModel::try_from((&context, service_id))?;
+                    let participants =
+                        ContextParticipantList::try_from((&context, service_id))?.inner;
+
+                    insert_into(consensus_2pc_context::table)
+                        .values(vec![new_context])
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+
+                    insert_into(consensus_2pc_context_participant::table)
+                        .values(participants)
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+                }
+            }
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> AddContextOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn add_consensus_context(
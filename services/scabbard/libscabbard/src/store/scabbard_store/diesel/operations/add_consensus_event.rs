@@ -14,11 +14,15 @@
 
 use std::convert::TryFrom;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
 use diesel::sqlite::SqliteConnection;
 use diesel::{dsl::insert_into, prelude::*};
+#[cfg(feature = "mysql")]
+use diesel::{dsl::sql, sql_types::BigInt};
 use splinter::error::{InternalError, InvalidStateError};
 use splinter::service::FullyQualifiedServiceId;
 
@@ -125,7 +129,141 @@ impl<'a> AddEventOperation for ScabbardStoreOperations<'a, SqliteConnection> {
                             Some(value.clone()),
                             epoch,
                         ),
-                        Message::DecisionAck(epoch) => {
+                        Message::DecisionAck(epoch)
+                        | Message::PreCommit(epoch)
+                        | Message::PreCommitAck(epoch) => {
+                            (DeliverMessageTypeModel::from(&message), None, None, epoch)
+                        }
+                    };
+
+                    let deliver_event = Consensus2pcDeliverEventModel {
+                        event_id,
+                        epoch: i64::try_from(epoch)
+                            .map_err(|err| InternalError::from_source(Box::new(err)))?,
+                        receiver_service_id: format!("{}", receiving_process),
+                        message_type,
+                        vote_response,
+                        vote_request,
+                    };
+                    insert_into(consensus_2pc_deliver_event::table)
+                        .values(vec![deliver_event])
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+                    Ok(event_id)
+                }
+                Event::Start(value) => {
+                    let start_event = Consensus2pcStartEventModel { event_id, value };
+                    insert_into(consensus_2pc_start_event::table)
+                        .values(vec![start_event])
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+                    Ok(event_id)
+                }
+                Event::Vote(vote) => {
+                    let vote_event = Consensus2pcVoteEventModel { event_id, vote };
+                    insert_into(consensus_2pc_vote_event::table)
+                        .values(vec![vote_event])
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+                    Ok(event_id)
+                }
+            }
+        })
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<'a> AddEventOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn add_consensus_event(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        event: ConsensusEvent,
+    ) -> Result<i64, ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            let ConsensusEvent::TwoPhaseCommit(event) = event;
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_service::service_id.eq(service_id.service_id().to_string())),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist",
+                    )))
+                })?;
+
+            let insertable_event = InsertableConsensus2pcEventModel {
+                circuit_id: service_id.circuit_id().to_string(),
+                service_id: service_id.service_id().to_string(),
+                executed_at: None,
+                event_type: EventTypeModel::from(&event),
+                update_context_action_id: None,
+            };
+
+            insert_into(consensus_2pc_event::table)
+                .values(vec![insertable_event])
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+            // MySQL does not support `RETURNING`, so the ID generated by this connection's
+            // insert is selected instead
+            let event_id = diesel::select(sql::<BigInt>("LAST_INSERT_ID()"))
+                .get_result::<i64>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            match event {
+                Event::Alarm() => Ok(event_id),
+                Event::Deliver(receiving_process, message) => {
+                    let (message_type, vote_response, vote_request, epoch) = match message {
+                        Message::DecisionRequest(epoch) => {
+                            (DeliverMessageTypeModel::from(&message), None, None, epoch)
+                        }
+                        Message::VoteResponse(epoch, vote) => (
+                            DeliverMessageTypeModel::from(&message),
+                            Some(vote),
+                            None,
+                            epoch,
+                        ),
+                        Message::Commit(epoch) => {
+                            (DeliverMessageTypeModel::from(&message), None, None, epoch)
+                        }
+                        Message::Abort(epoch) => {
+                            (DeliverMessageTypeModel::from(&message), None, None, epoch)
+                        }
+                        Message::VoteRequest(epoch, ref value) => (
+                            DeliverMessageTypeModel::from(&message),
+                            None,
+                            Some(value.clone()),
+                            epoch,
+                        ),
+                        Message::DecisionAck(epoch)
+                        | Message::PreCommit(epoch)
+                        | Message::PreCommitAck(epoch) => {
                             (DeliverMessageTypeModel::from(&message), None, None, epoch)
                         }
                     };
@@ -249,7 +387,9 @@ impl<'a> AddEventOperation for ScabbardStoreOperations<'a, PgConnection> {
                             Some(value.clone()),
                             epoch,
                         ),
-                        Message::DecisionAck(epoch) => {
+                        Message::DecisionAck(epoch)
+                        | Message::PreCommit(epoch)
+                        | Message::PreCommitAck(epoch) => {
                             (DeliverMessageTypeModel::from(&message), None, None, epoch)
                         }
                     };
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -83,6 +85,55 @@ impl<'a> AddServiceOperation for ScabbardStoreOperations<'a, SqliteConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> AddServiceOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // check to see if the service already exists
+            if scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service.service_id().circuit_id().to_string())
+                        .and(
+                            scabbard_service::service_id
+                                .eq(service.service_id().service_id().to_string()),
+                        ),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .is_some()
+            {
+                return Err(ScabbardStoreError::ConstraintViolation(
+                    ConstraintViolationError::with_violation_type(ConstraintViolationType::Unique),
+                ));
+            }
+
+            insert_into(scabbard_service::table)
+                .values(vec![ScabbardServiceModel::from(&service)])
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            if !service.peers().is_empty() {
+                insert_into(scabbard_peer::table)
+                    .values(Vec::<ScabbardPeerModel>::from(&service))
+                    .execute(self.conn)
+                    .map_err(|err| {
+                        ScabbardStoreError::from_source_with_operation(
+                            err,
+                            OPERATION_NAME.to_string(),
+                        )
+                    })?;
+            }
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> AddServiceOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn add_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
@@ -14,6 +14,8 @@
 
 use std::convert::TryFrom;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -58,6 +60,27 @@ impl<'a> AddSupervisorNotificationOperation for ScabbardStoreOperations<'a, Sqli
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> AddSupervisorNotificationOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn add_supervisor_notification(
+        &self,
+        notification: SupervisorNotification,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            let new_notification = InsertableSupervisorNotificationModel::try_from(&notification)?;
+
+            insert_into(supervisor_notification::table)
+                .values(vec![new_notification])
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> AddSupervisorNotificationOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn add_supervisor_notification(
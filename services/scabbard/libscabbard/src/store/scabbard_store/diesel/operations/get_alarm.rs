@@ -14,6 +14,8 @@
 
 use std::time::{Duration, SystemTime};
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
@@ -95,6 +97,60 @@ impl<'a> GetAlarmOperation for ScabbardStoreOperations<'a, SqliteConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> GetAlarmOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn get_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+    ) -> Result<Option<SystemTime>, ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_service::service_id.eq(service_id.service_id().to_string())),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Failed to get scabbard alarm, service does not exist",
+                    )))
+                })?;
+
+            scabbard_alarm::table
+                .filter(
+                    scabbard_alarm::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_alarm::service_id.eq(service_id.service_id().to_string()))
+                        .and(scabbard_alarm::alarm_type.eq(AlarmTypeModel::from(alarm_type))),
+                )
+                .select(scabbard_alarm::alarm)
+                .first::<i64>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .map(|t| {
+                    SystemTime::UNIX_EPOCH
+                        .checked_add(Duration::from_secs(t as u64))
+                        .ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "'alarm' timestamp could not be represented as a `SystemTime`"
+                                    .to_string(),
+                            ))
+                        })
+                })
+                .transpose()
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> GetAlarmOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn get_alarm(
@@ -12,6 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::convert::TryFrom;
+use std::time::Duration;
+
 use diesel::prelude::*;
 use splinter::error::{InternalError, InvalidArgumentError};
 use splinter::service::{FullyQualifiedServiceId, ServiceId};
@@ -43,6 +46,7 @@ pub(in crate::store::scabbard_store::diesel) trait GetServiceOperation {
 impl<'a, C> GetServiceOperation for ScabbardStoreOperations<'a, C>
 where
     C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<diesel::sql_types::BigInt, C::Backend>,
     String: diesel::deserialize::FromSql<diesel::sql_types::Text, C::Backend>,
     <C as diesel::Connection>::Backend: diesel::types::HasSqlType<ServiceStatusTypeModelMapping>,
     ServiceStatusTypeModel: diesel::deserialize::FromSql<ServiceStatusTypeModelMapping, C::Backend>,
@@ -88,11 +92,19 @@ where
                 .collect::<Result<Vec<_>, InvalidArgumentError>>()
                 .map_err(|err| InternalError::from_source(Box::new(err)))?;
 
-            let service = ScabbardServiceBuilder::default()
+            let mut builder = ScabbardServiceBuilder::default()
                 .with_service_id(service_id)
                 .with_consensus(&ConsensusType::from(&service_model.consensus))
                 .with_status(&ServiceStatus::from(&service_model.status))
-                .with_peers(service_peers.as_slice())
+                .with_peers(service_peers.as_slice());
+
+            if let Some(timeout) = service_model.coordinator_timeout {
+                let timeout = u64::try_from(timeout)
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                builder = builder.with_coordinator_timeout(Duration::from_millis(timeout));
+            }
+
+            let service = builder
                 .build()
                 .map_err(|err| InternalError::from_source(Box::new(err)))?;
 
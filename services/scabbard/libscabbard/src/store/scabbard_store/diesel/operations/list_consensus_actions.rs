@@ -194,6 +194,327 @@ impl<'a> ListActionsOperation for ScabbardStoreOperations<'a, SqliteConnection>
                             })?;
                         State::WaitingForDecisionAck { ack_timeout_start }
                     }
+                    ContextStateModel::WaitingForPreCommitAck => {
+                        let ack_timeout_start = get_system_time(update_context.ack_timeout_start)?
+                            .ok_or_else(|| {
+                                ScabbardStoreError::Internal(InternalError::with_message(
+                                    "Failed to list actions, context has state \
+                                    'WaitingForPreCommitAck' but no ack timeout start time set"
+                                        .to_string(),
+                                ))
+                            })?;
+                        State::WaitingForPreCommitAck { ack_timeout_start }
+                    }
+                    ContextStateModel::PreCommitted => {
+                        let decision_timeout_start = get_system_time(
+                            update_context.decision_timeout_start,
+                        )?
+                        .ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to list actions, context has state 'PreCommitted' but no \
+                                decision timeout start time set"
+                                    .to_string(),
+                            ))
+                        })?;
+                        State::PreCommitted {
+                            decision_timeout_start,
+                        }
+                    }
+                };
+
+                let mut context = ContextBuilder::default()
+                    .with_coordinator(&ServiceId::new(&update_context.coordinator).map_err(
+                        |err| {
+                            ScabbardStoreError::Internal(InternalError::from_source(Box::new(err)))
+                        },
+                    )?)
+                    .with_epoch(update_context.epoch as u64)
+                    .with_participants(final_participants)
+                    .with_state(state)
+                    .with_this_process(service_id.service_id());
+
+                if let Some(last_commit_epoch) = update_context.last_commit_epoch {
+                    context = context.with_last_commit_epoch(last_commit_epoch as u64)
+                };
+
+                let context = context.build().map_err(|err| {
+                    ScabbardStoreError::Internal(InternalError::from_source(Box::new(err)))
+                })?;
+
+                let action_alarm = get_system_time(update_context.action_alarm)?;
+                let action = Identified {
+                    id: update_context.action_id,
+                    record: ConsensusAction::TwoPhaseCommit(Action::Update(
+                        ConsensusContext::TwoPhaseCommit(context),
+                        action_alarm,
+                    )),
+                };
+                all_actions.push(action);
+            }
+            for send_message in send_message_actions {
+                let service_id =
+                    ServiceId::new(send_message.receiver_service_id).map_err(|err| {
+                        ScabbardStoreError::Internal(InternalError::from_source(Box::new(err)))
+                    })?;
+
+                let message = match send_message.message_type {
+                    MessageTypeModel::VoteResponse => Message::VoteResponse(
+                        send_message.epoch as u64,
+                        send_message.vote_response.ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to get 'vote response' send message action, no \
+                                associated vote found"
+                                    .to_string(),
+                            ))
+                        })?,
+                    ),
+                    MessageTypeModel::DecisionRequest => {
+                        Message::DecisionRequest(send_message.epoch as u64)
+                    }
+                    MessageTypeModel::VoteRequest => Message::VoteRequest(
+                        send_message.epoch as u64,
+                        send_message.vote_request.ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to get 'vote request' send message action, no \
+                                associated value found"
+                                    .to_string(),
+                            ))
+                        })?,
+                    ),
+                    MessageTypeModel::Commit => Message::Commit(send_message.epoch as u64),
+                    MessageTypeModel::Abort => Message::Abort(send_message.epoch as u64),
+                    MessageTypeModel::DecisionAck => {
+                        Message::DecisionAck(send_message.epoch as u64)
+                    }
+                    MessageTypeModel::PreCommit => Message::PreCommit(send_message.epoch as u64),
+                    MessageTypeModel::PreCommitAck => {
+                        Message::PreCommitAck(send_message.epoch as u64)
+                    }
+                };
+
+                let action = Identified {
+                    id: send_message.action_id,
+                    record: ConsensusAction::TwoPhaseCommit(Action::SendMessage(
+                        service_id, message,
+                    )),
+                };
+                all_actions.push(action);
+            }
+
+            for notification in notification_actions {
+                let notification_action = match notification.notification_type {
+                    NotificationTypeModel::RequestForStart => Notification::RequestForStart(),
+                    NotificationTypeModel::CoordinatorRequestForVote => {
+                        Notification::CoordinatorRequestForVote()
+                    }
+                    NotificationTypeModel::ParticipantRequestForVote => {
+                        Notification::ParticipantRequestForVote(
+                            notification.request_for_vote_value.ok_or_else(|| {
+                                ScabbardStoreError::Internal(InternalError::with_message(
+                                    "Failed to get 'request for vote' notification action, no \
+                                    associated value"
+                                        .to_string(),
+                                ))
+                            })?,
+                        )
+                    }
+                    NotificationTypeModel::Commit => Notification::Commit(),
+                    NotificationTypeModel::Abort => Notification::Abort(),
+                    NotificationTypeModel::MessageDropped => Notification::MessageDropped(
+                        notification.dropped_message.ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to get 'message dropped' notification action, no \
+                                associated dropped message found"
+                                    .to_string(),
+                            ))
+                        })?,
+                    ),
+                };
+                let action = Identified {
+                    id: notification.action_id,
+                    record: ConsensusAction::TwoPhaseCommit(Action::Notify(notification_action)),
+                };
+                all_actions.push(action);
+            }
+
+            all_actions.sort_by(|a, b| a.id.cmp(&b.id));
+
+            Ok(all_actions)
+        })
+    }
+}
+
+#[cfg(feature = "mysql")]
+impl<'a> ListActionsOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn list_consensus_actions(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<Identified<ConsensusAction>>, ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_service::service_id.eq(service_id.service_id().to_string())),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist",
+                    )))
+                })?;
+
+            let action_ids = consensus_2pc_action::table
+                .filter(
+                    consensus_2pc_action::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(
+                            consensus_2pc_action::service_id
+                                .eq(service_id.service_id().to_string())
+                                .and(consensus_2pc_action::executed_at.is_null()),
+                        ),
+                )
+                .order(consensus_2pc_action::id.desc())
+                .select(consensus_2pc_action::id)
+                .load::<i64>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            let mut all_actions = Vec::new();
+
+            let update_context_actions = consensus_2pc_update_context_action::table
+                .filter(consensus_2pc_update_context_action::action_id.eq_any(&action_ids))
+                .load::<Consensus2pcUpdateContextActionModel>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            let send_message_actions = consensus_2pc_send_message_action::table
+                .filter(consensus_2pc_send_message_action::action_id.eq_any(&action_ids))
+                .load::<Consensus2pcSendMessageActionModel>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            let notification_actions = consensus_2pc_notification_action::table
+                .filter(consensus_2pc_notification_action::action_id.eq_any(&action_ids))
+                .load::<Consensus2pcNotificationModel>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            for update_context in update_context_actions {
+                let participants = consensus_2pc_update_context_action_participant::table
+                    .filter(
+                        consensus_2pc_update_context_action_participant::action_id
+                            .eq(update_context.action_id),
+                    )
+                    .load::<Consensus2pcUpdateContextActionParticipantModel>(self.conn)
+                    .map_err(|err| {
+                        ScabbardStoreError::from_source_with_operation(
+                            err,
+                            OPERATION_NAME.to_string(),
+                        )
+                    })?;
+
+                let mut final_participants = Vec::new();
+
+                for participant in participants.into_iter() {
+                    let process = ServiceId::new(participant.process).map_err(|err| {
+                        ScabbardStoreError::Internal(InternalError::from_source(Box::new(err)))
+                    })?;
+                    final_participants.push(Participant {
+                        process,
+                        vote: participant.vote,
+                        decision_ack: participant.decision_ack,
+                    });
+                }
+
+                let state = match update_context.state {
+                    ContextStateModel::WaitingForStart => State::WaitingForStart,
+                    ContextStateModel::Voting => {
+                        let vote_timeout_start = get_system_time(
+                            update_context.vote_timeout_start,
+                        )?
+                        .ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "failed to get update context action with status 'voting', \
+                                    no vote timeout start time set"
+                                    .to_string(),
+                            ))
+                        })?;
+                        State::Voting { vote_timeout_start }
+                    }
+                    ContextStateModel::WaitingForVote => State::WaitingForVote,
+                    ContextStateModel::Abort => State::Abort,
+                    ContextStateModel::Commit => State::Commit,
+                    ContextStateModel::Voted => {
+                        let decision_timeout_start = get_system_time(
+                            update_context.decision_timeout_start,
+                        )?
+                        .ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to list actions, context has state 'Voted' but no decision \
+                                timeout start time set"
+                                .to_string(),
+                            ))
+                        })?;
+                        let vote = update_context.vote.ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to get update context action, no associated vote response \
+                                    found"
+                                    .to_string(),
+                            ))
+                        })?;
+                        State::Voted {
+                            vote,
+                            decision_timeout_start,
+                        }
+                    }
+                    ContextStateModel::WaitingForVoteRequest => State::WaitingForVoteRequest,
+                    ContextStateModel::WaitingForDecisionAck => {
+                        let ack_timeout_start = get_system_time(update_context.ack_timeout_start)?
+                            .ok_or_else(|| {
+                                ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to list actions, context has state 'WaitingForDecisionAck' \
+                                but no ack timeout start time set"
+                                .to_string(),
+                            ))
+                            })?;
+                        State::WaitingForDecisionAck { ack_timeout_start }
+                    }
+                    ContextStateModel::WaitingForPreCommitAck => {
+                        let ack_timeout_start = get_system_time(update_context.ack_timeout_start)?
+                            .ok_or_else(|| {
+                                ScabbardStoreError::Internal(InternalError::with_message(
+                                    "Failed to list actions, context has state \
+                                    'WaitingForPreCommitAck' but no ack timeout start time set"
+                                        .to_string(),
+                                ))
+                            })?;
+                        State::WaitingForPreCommitAck { ack_timeout_start }
+                    }
+                    ContextStateModel::PreCommitted => {
+                        let decision_timeout_start = get_system_time(
+                            update_context.decision_timeout_start,
+                        )?
+                        .ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to list actions, context has state 'PreCommitted' but no \
+                                decision timeout start time set"
+                                    .to_string(),
+                            ))
+                        })?;
+                        State::PreCommitted {
+                            decision_timeout_start,
+                        }
+                    }
                 };
 
                 let mut context = ContextBuilder::default()
@@ -260,6 +581,10 @@ impl<'a> ListActionsOperation for ScabbardStoreOperations<'a, SqliteConnection>
                     MessageTypeModel::DecisionAck => {
                         Message::DecisionAck(send_message.epoch as u64)
                     }
+                    MessageTypeModel::PreCommit => Message::PreCommit(send_message.epoch as u64),
+                    MessageTypeModel::PreCommitAck => {
+                        Message::PreCommitAck(send_message.epoch as u64)
+                    }
                 };
 
                 let action = Identified {
@@ -459,6 +784,32 @@ impl<'a> ListActionsOperation for ScabbardStoreOperations<'a, PgConnection> {
                             })?;
                         State::WaitingForDecisionAck { ack_timeout_start }
                     }
+                    ContextStateModel::WaitingForPreCommitAck => {
+                        let ack_timeout_start = get_system_time(update_context.ack_timeout_start)?
+                            .ok_or_else(|| {
+                                ScabbardStoreError::Internal(InternalError::with_message(
+                                    "Failed to list actions, context has state \
+                                    'WaitingForPreCommitAck' but no ack timeout start time set"
+                                        .to_string(),
+                                ))
+                            })?;
+                        State::WaitingForPreCommitAck { ack_timeout_start }
+                    }
+                    ContextStateModel::PreCommitted => {
+                        let decision_timeout_start = get_system_time(
+                            update_context.decision_timeout_start,
+                        )?
+                        .ok_or_else(|| {
+                            ScabbardStoreError::Internal(InternalError::with_message(
+                                "Failed to list actions, context has state 'PreCommitted' but no \
+                                decision timeout start time set"
+                                    .to_string(),
+                            ))
+                        })?;
+                        State::PreCommitted {
+                            decision_timeout_start,
+                        }
+                    }
                 };
 
                 let mut context = ContextBuilder::default()
@@ -525,6 +876,10 @@ impl<'a> ListActionsOperation for ScabbardStoreOperations<'a, PgConnection> {
                     MessageTypeModel::DecisionAck => {
                         Message::DecisionAck(send_message.epoch as u64)
                     }
+                    MessageTypeModel::PreCommit => Message::PreCommit(send_message.epoch as u64),
+                    MessageTypeModel::PreCommitAck => {
+                        Message::PreCommitAck(send_message.epoch as u64)
+                    }
                 };
 
                 let action = Identified {
@@ -0,0 +1,268 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use chrono::naive::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::sql_types::{BigInt, Binary, Bool, Integer, Nullable, Text, Timestamp};
+use splinter::error::{InternalError, InvalidStateError};
+use splinter::service::{FullyQualifiedServiceId, ServiceId};
+
+use crate::store::scabbard_store::diesel::{
+    models::{
+        Consensus2pcDeliverEventModel, Consensus2pcStartEventModel, Consensus2pcVoteEventModel,
+        ConsensusTypeModel, ConsensusTypeModelMapping, DeliverMessageTypeModel,
+        DeliverMessageTypeModelMapping, EventTypeModel, EventTypeModelMapping,
+        ScabbardServiceModel, ServiceStatusTypeModel, ServiceStatusTypeModelMapping,
+    },
+    schema::{
+        consensus_2pc_deliver_event, consensus_2pc_event, consensus_2pc_start_event,
+        consensus_2pc_vote_event, scabbard_service,
+    },
+};
+use crate::store::scabbard_store::ScabbardStoreError;
+use crate::store::scabbard_store::{
+    event::{ConsensusEvent, ConsensusEventLogEntry},
+    two_phase_commit::{Event, Message},
+};
+
+use super::ScabbardStoreOperations;
+
+const OPERATION_NAME: &str = "list_consensus_event_log";
+
+type EventRow = (
+    i64,
+    i32,
+    EventTypeModel,
+    NaiveDateTime,
+    Option<NaiveDateTime>,
+    Option<i64>,
+);
+
+pub(in crate::store::scabbard_store::diesel) trait ListEventLogOperation {
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError>;
+}
+
+impl<'a, C> ListEventLogOperation for ScabbardStoreOperations<'a, C>
+where
+    C: diesel::Connection,
+    i64: diesel::deserialize::FromSql<BigInt, C::Backend>,
+    String: diesel::deserialize::FromSql<Text, C::Backend>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<ServiceStatusTypeModelMapping>,
+    ServiceStatusTypeModel: diesel::deserialize::FromSql<ServiceStatusTypeModelMapping, C::Backend>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<ConsensusTypeModelMapping>,
+    ConsensusTypeModel: diesel::deserialize::FromSql<ConsensusTypeModelMapping, C::Backend>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<EventTypeModelMapping>,
+    <C as diesel::Connection>::Backend: diesel::types::HasSqlType<DeliverMessageTypeModelMapping>,
+    EventRow: diesel::Queryable<
+        (
+            BigInt,
+            Integer,
+            EventTypeModelMapping,
+            Timestamp,
+            Nullable<Timestamp>,
+            Nullable<BigInt>,
+        ),
+        C::Backend,
+    >,
+    Consensus2pcDeliverEventModel: diesel::Queryable<
+        (
+            BigInt,
+            BigInt,
+            Text,
+            DeliverMessageTypeModelMapping,
+            Nullable<Bool>,
+            Nullable<Binary>,
+        ),
+        C::Backend,
+    >,
+    Consensus2pcStartEventModel: diesel::Queryable<(BigInt, Binary), C::Backend>,
+    Consensus2pcVoteEventModel: diesel::Queryable<(BigInt, Bool), C::Backend>,
+{
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_service::service_id.eq(service_id.service_id().to_string())),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Service does not exist",
+                    )))
+                })?;
+
+            let event_rows = consensus_2pc_event::table
+                .filter(
+                    consensus_2pc_event::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(
+                            consensus_2pc_event::service_id.eq(service_id.service_id().to_string()),
+                        ),
+                )
+                .order((
+                    consensus_2pc_event::position.asc(),
+                    consensus_2pc_event::id.asc(),
+                ))
+                .select((
+                    consensus_2pc_event::id,
+                    consensus_2pc_event::position,
+                    consensus_2pc_event::event_type,
+                    consensus_2pc_event::created_at,
+                    consensus_2pc_event::executed_at,
+                    consensus_2pc_event::executed_epoch,
+                ))
+                .load::<EventRow>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            let event_ids = event_rows.iter().map(|row| row.0).collect::<Vec<_>>();
+
+            let mut deliver_events = consensus_2pc_deliver_event::table
+                .filter(consensus_2pc_deliver_event::event_id.eq_any(&event_ids))
+                .load::<Consensus2pcDeliverEventModel>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .into_iter()
+                .map(|deliver| (deliver.event_id, deliver))
+                .collect::<HashMap<_, _>>();
+
+            let mut start_events = consensus_2pc_start_event::table
+                .filter(consensus_2pc_start_event::event_id.eq_any(&event_ids))
+                .load::<Consensus2pcStartEventModel>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .into_iter()
+                .map(|start| (start.event_id, start.value))
+                .collect::<HashMap<_, _>>();
+
+            let vote_events = consensus_2pc_vote_event::table
+                .filter(consensus_2pc_vote_event::event_id.eq_any(&event_ids))
+                .load::<Consensus2pcVoteEventModel>(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .into_iter()
+                .map(|vote| (vote.event_id, vote.vote))
+                .collect::<HashMap<_, _>>();
+
+            event_rows
+                .into_iter()
+                .map(
+                    |(id, position, event_type, created_at, executed_at, executed_epoch)| {
+                        let event = match event_type {
+                            EventTypeModel::Alarm => Event::Alarm(),
+                            EventTypeModel::Deliver => {
+                                let deliver = deliver_events
+                                    .remove(&id)
+                                    .ok_or_else(|| missing_details(id, "deliver"))?;
+                                deliver_event(deliver)?
+                            }
+                            EventTypeModel::Start => Event::Start(
+                                start_events
+                                    .remove(&id)
+                                    .ok_or_else(|| missing_details(id, "start"))?,
+                            ),
+                            EventTypeModel::Vote => Event::Vote(
+                                *vote_events
+                                    .get(&id)
+                                    .ok_or_else(|| missing_details(id, "vote"))?,
+                            ),
+                        };
+
+                        Ok(ConsensusEventLogEntry::new(
+                            position,
+                            ConsensusEvent::TwoPhaseCommit(event),
+                            to_system_time(created_at)?,
+                            executed_at.map(to_system_time).transpose()?,
+                            executed_epoch.map(|epoch| epoch as u64),
+                        ))
+                    },
+                )
+                .collect()
+        })
+    }
+}
+
+fn deliver_event(deliver: Consensus2pcDeliverEventModel) -> Result<Event, ScabbardStoreError> {
+    let process = ServiceId::new(deliver.receiver_service_id)
+        .map_err(|err| ScabbardStoreError::Internal(InternalError::from_source(Box::new(err))))?;
+    let epoch = deliver.epoch as u64;
+
+    let message = match deliver.message_type {
+        DeliverMessageTypeModel::VoteResponse => Message::VoteResponse(
+            epoch,
+            deliver.vote_response.ok_or_else(|| {
+                ScabbardStoreError::Internal(InternalError::with_message(
+                    "Failed to list event log, deliver event has message type 'vote response' \
+                    but no associated vote"
+                        .to_string(),
+                ))
+            })?,
+        ),
+        DeliverMessageTypeModel::DecisionRequest => Message::DecisionRequest(epoch),
+        DeliverMessageTypeModel::VoteRequest => Message::VoteRequest(
+            epoch,
+            deliver.vote_request.ok_or_else(|| {
+                ScabbardStoreError::Internal(InternalError::with_message(
+                    "Failed to list event log, deliver event has message type 'vote request' \
+                    but no associated value"
+                        .to_string(),
+                ))
+            })?,
+        ),
+        DeliverMessageTypeModel::Commit => Message::Commit(epoch),
+        DeliverMessageTypeModel::Abort => Message::Abort(epoch),
+        DeliverMessageTypeModel::DecisionAck => Message::DecisionAck(epoch),
+        DeliverMessageTypeModel::PreCommit => Message::PreCommit(epoch),
+        DeliverMessageTypeModel::PreCommitAck => Message::PreCommitAck(epoch),
+    };
+
+    Ok(Event::Deliver(process, message))
+}
+
+fn missing_details(event_id: i64, event_type: &str) -> ScabbardStoreError {
+    ScabbardStoreError::Internal(InternalError::with_message(format!(
+        "Failed to list event log, {} event {} has no associated details",
+        event_type, event_id
+    )))
+}
+
+fn to_system_time(time: NaiveDateTime) -> Result<SystemTime, ScabbardStoreError> {
+    SystemTime::UNIX_EPOCH
+        .checked_add(Duration::from_secs(time.timestamp() as u64))
+        .ok_or_else(|| {
+            ScabbardStoreError::Internal(InternalError::with_message(
+                "timestamp could not be represented as a `SystemTime`".to_string(),
+            ))
+        })
+}
@@ -23,6 +23,7 @@ pub(super) mod get_current_consensus_context;
 pub(super) mod get_last_commit_entry;
 pub(super) mod get_service;
 pub(super) mod list_consensus_actions;
+pub(super) mod list_consensus_event_log;
 pub(super) mod list_consensus_events;
 pub(super) mod list_ready_services;
 pub(super) mod list_supervisor_notifications;
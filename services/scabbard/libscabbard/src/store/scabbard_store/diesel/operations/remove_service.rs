@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -61,6 +63,33 @@ impl<'a> RemoveServiceOperation for ScabbardStoreOperations<'a, SqliteConnection
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> RemoveServiceOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn remove_service(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // Verify the service attempting to be removed exists.
+            self.get_service(service_id).and_then(|_| {
+                let circuit_id = service_id.circuit_id().to_string();
+                let service_id = service_id.service_id().to_string();
+                // delete service and peers
+                delete(scabbard_service::table.find((&circuit_id, &service_id)))
+                    .execute(self.conn)
+                    .map_err(|err| {
+                        ScabbardStoreError::from_source_with_operation(
+                            err,
+                            OPERATION_NAME.to_string(),
+                        )
+                    })?;
+
+                Ok(())
+            })
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> RemoveServiceOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn remove_service(
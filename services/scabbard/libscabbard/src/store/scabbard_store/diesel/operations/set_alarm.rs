@@ -15,6 +15,8 @@
 use std::convert::TryFrom;
 use std::time::SystemTime;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -118,6 +120,81 @@ impl<'a> SetAlarmOperation for ScabbardStoreOperations<'a, SqliteConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> SetAlarmOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn set_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+        alarm: SystemTime,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_service::service_id.eq(service_id.service_id().to_string())),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Failed to set scabbard alarm, service does not exist",
+                    )))
+                })?;
+
+            let current_alarm = scabbard_alarm::table
+                .filter(
+                    scabbard_alarm::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_alarm::service_id.eq(service_id.service_id().to_string()))
+                        .and(scabbard_alarm::alarm_type.eq(AlarmTypeModel::from(alarm_type))),
+                )
+                .first::<ScabbardAlarmModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            let new_alarm = ScabbardAlarmModel {
+                circuit_id: service_id.circuit_id().to_string(),
+                service_id: service_id.service_id().to_string(),
+                alarm_type: AlarmTypeModel::from(alarm_type),
+                alarm: get_timestamp(alarm)?,
+            };
+
+            if current_alarm.is_some() {
+                // delete the current alarm
+                delete(
+                    scabbard_alarm::table.filter(
+                        scabbard_alarm::circuit_id
+                            .eq(service_id.circuit_id().to_string())
+                            .and(scabbard_alarm::service_id.eq(service_id.service_id().to_string()))
+                            .and(scabbard_alarm::alarm_type.eq(AlarmTypeModel::from(alarm_type))),
+                    ),
+                )
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+            }
+
+            insert_into(scabbard_alarm::table)
+                .values(vec![new_alarm])
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> SetAlarmOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn set_alarm(
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -99,6 +101,66 @@ impl<'a> UnsetAlarmOperation for ScabbardStoreOperations<'a, SqliteConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> UnsetAlarmOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn unset_alarm(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        alarm_type: &AlarmType,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            // check to see if a service with the given service_id exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_service::service_id.eq(service_id.service_id().to_string())),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Failed to unset scabbard alarm, service does not exist",
+                    )))
+                })?;
+
+            let current_alarm = scabbard_alarm::table
+                .filter(
+                    scabbard_alarm::circuit_id
+                        .eq(service_id.circuit_id().to_string())
+                        .and(scabbard_alarm::service_id.eq(service_id.service_id().to_string()))
+                        .and(scabbard_alarm::alarm_type.eq(AlarmTypeModel::from(alarm_type))),
+                )
+                .first::<ScabbardAlarmModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            if current_alarm.is_some() {
+                // delete the current alarm
+                delete(
+                    scabbard_alarm::table.filter(
+                        scabbard_alarm::circuit_id
+                            .eq(service_id.circuit_id().to_string())
+                            .and(scabbard_alarm::service_id.eq(service_id.service_id().to_string()))
+                            .and(scabbard_alarm::alarm_type.eq(AlarmTypeModel::from(alarm_type))),
+                    ),
+                )
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+            }
+
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> UnsetAlarmOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn unset_alarm(
@@ -84,6 +84,60 @@ impl<'a> UpdateCommitEntryOperation for ScabbardStoreOperations<'a, SqliteConnec
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> UpdateCommitEntryOperation for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn update_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            let epoch = i64::try_from(commit_entry.epoch().ok_or_else(|| {
+                ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                    "Commit entry does not have an epoch",
+                )))
+            })?)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            // check to see if a commit entry with the given service_id and epoch exists
+            scabbard_v3_commit_history::table
+                .filter(
+                    scabbard_v3_commit_history::circuit_id
+                        .eq(commit_entry.service_id().circuit_id().to_string())
+                        .and(
+                            scabbard_v3_commit_history::service_id
+                                .eq(commit_entry.service_id().service_id().to_string()),
+                        )
+                        .and(scabbard_v3_commit_history::epoch.eq(epoch)),
+                )
+                .first::<CommitEntryModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Commit entry does not exist",
+                    )))
+                })?;
+
+            delete(scabbard_v3_commit_history::table.find((
+                commit_entry.service_id().circuit_id().to_string(),
+                commit_entry.service_id().service_id().to_string(),
+                epoch,
+            )))
+            .execute(self.conn)
+            .map_err(|err| {
+                ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+            })?;
+
+            insert_into(scabbard_v3_commit_history::table)
+                .values(vec![CommitEntryModel::try_from(&commit_entry)?])
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> UpdateCommitEntryOperation for ScabbardStoreOperations<'a, PgConnection> {
     fn update_commit_entry(&self, commit_entry: CommitEntry) -> Result<(), ScabbardStoreError> {
@@ -14,6 +14,8 @@
 
 use std::convert::TryFrom;
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -147,6 +149,112 @@ impl<'a> UpdateContextAction for ScabbardStoreOperations<'a, SqliteConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> UpdateContextAction for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn update_consensus_context(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+        context: ConsensusContext,
+    ) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, _, _>(|| {
+            match context {
+                ConsensusContext::TwoPhaseCommit(context) => {
+                    // check to see if a service with the given service_id exists
+                    scabbard_service::table
+                        .filter(
+                            scabbard_service::circuit_id
+                                .eq(service_id.circuit_id().to_string())
+                                .and(
+                                    scabbard_service::service_id
+                                        .eq(service_id.service_id().to_string()),
+                                ),
+                        )
+                        .first::<ScabbardServiceModel>(self.conn)
+                        .optional()
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?
+                        .ok_or_else(|| {
+                            ScabbardStoreError::InvalidState(InvalidStateError::with_message(
+                                String::from("Service does not exist"),
+                            ))
+                        })?;
+
+                    let update_context =
+                        Consensus2pcContextModel::try_from((&context, service_id))?;
+
+                    update(consensus_2pc_context::table)
+                        .filter(
+                            consensus_2pc_context::circuit_id
+                                .eq(service_id.circuit_id().to_string())
+                                .and(
+                                    consensus_2pc_context::service_id
+                                        .eq(service_id.service_id().to_string()),
+                                ),
+                        )
+                        .set((
+                            consensus_2pc_context::coordinator.eq(update_context.coordinator),
+                            consensus_2pc_context::epoch.eq(update_context.epoch),
+                            consensus_2pc_context::last_commit_epoch
+                                .eq(update_context.last_commit_epoch),
+                            consensus_2pc_context::state.eq(update_context.state),
+                            consensus_2pc_context::vote_timeout_start
+                                .eq(update_context.vote_timeout_start),
+                            consensus_2pc_context::vote.eq(update_context.vote),
+                            consensus_2pc_context::decision_timeout_start
+                                .eq(update_context.decision_timeout_start),
+                            consensus_2pc_context::ack_timeout_start
+                                .eq(update_context.ack_timeout_start),
+                        ))
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+
+                    let updated_participants =
+                        ContextParticipantList::try_from((&context, service_id))?.inner;
+
+                    delete(
+                        consensus_2pc_context_participant::table.filter(
+                            consensus_2pc_context_participant::circuit_id
+                                .eq(service_id.circuit_id().to_string())
+                                .and(
+                                    consensus_2pc_context_participant::service_id
+                                        .eq(service_id.service_id().to_string()),
+                                ),
+                        ),
+                    )
+                    .execute(self.conn)
+                    .map_err(|err| {
+                        ScabbardStoreError::from_source_with_operation(
+                            err,
+                            OPERATION_NAME.to_string(),
+                        )
+                    })?;
+
+                    insert_into(consensus_2pc_context_participant::table)
+                        .values(updated_participants)
+                        .execute(self.conn)
+                        .map_err(|err| {
+                            ScabbardStoreError::from_source_with_operation(
+                                err,
+                                OPERATION_NAME.to_string(),
+                            )
+                        })?;
+
+                    Ok(())
+                }
+            }
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> UpdateContextAction for ScabbardStoreOperations<'a, PgConnection> {
     fn update_consensus_context(
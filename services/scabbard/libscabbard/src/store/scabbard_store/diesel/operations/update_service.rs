@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "mysql")]
+use diesel::mysql::MysqlConnection;
 #[cfg(feature = "postgres")]
 use diesel::pg::PgConnection;
 #[cfg(feature = "sqlite")]
@@ -105,6 +107,77 @@ impl<'a> UpdateServiceAction for ScabbardStoreOperations<'a, SqliteConnection> {
     }
 }
 
+#[cfg(feature = "mysql")]
+impl<'a> UpdateServiceAction for ScabbardStoreOperations<'a, MysqlConnection> {
+    fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
+        self.conn.transaction::<_, ScabbardStoreError, _>(|| {
+            // check to see if the service exists
+            scabbard_service::table
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service.service_id().circuit_id().to_string())
+                        .and(
+                            scabbard_service::service_id
+                                .eq(service.service_id().service_id().to_string()),
+                        ),
+                )
+                .first::<ScabbardServiceModel>(self.conn)
+                .optional()
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?
+                .ok_or_else(|| {
+                    ScabbardStoreError::InvalidState(InvalidStateError::with_message(String::from(
+                        "Failed to update service, service does not exist",
+                    )))
+                })?;
+
+            update(scabbard_service::table)
+                .filter(
+                    scabbard_service::circuit_id
+                        .eq(service.service_id().circuit_id().to_string())
+                        .and(
+                            scabbard_service::service_id
+                                .eq(service.service_id().service_id().to_string()),
+                        ),
+                )
+                .set(scabbard_service::status.eq(ServiceStatusTypeModel::from(service.status())))
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+            if !service.peers().is_empty() {
+                delete(
+                    scabbard_peer::table.filter(
+                        scabbard_peer::circuit_id
+                            .eq(service.service_id().circuit_id().to_string())
+                            .and(
+                                scabbard_peer::service_id
+                                    .eq(service.service_id().service_id().to_string()),
+                            ),
+                    ),
+                )
+                .execute(self.conn)
+                .map_err(|err| {
+                    ScabbardStoreError::from_source_with_operation(err, OPERATION_NAME.to_string())
+                })?;
+
+                insert_into(scabbard_peer::table)
+                    .values(Vec::<ScabbardPeerModel>::from(&service))
+                    .execute(self.conn)
+                    .map_err(|err| {
+                        ScabbardStoreError::from_source_with_operation(
+                            err,
+                            OPERATION_NAME.to_string(),
+                        )
+                    })?;
+            }
+            Ok(())
+        })
+    }
+}
+
 #[cfg(feature = "postgres")]
 impl<'a> UpdateServiceAction for ScabbardStoreOperations<'a, PgConnection> {
     fn update_service(&self, service: ScabbardService) -> Result<(), ScabbardStoreError> {
@@ -18,6 +18,7 @@ table! {
         service_id  -> Text,
         consensus -> crate::store::scabbard_store::diesel::models::ConsensusTypeModelMapping,
         status -> crate::store::scabbard_store::diesel::models::ServiceStatusTypeModelMapping,
+        coordinator_timeout -> Nullable<BigInt>,
     }
 }
 
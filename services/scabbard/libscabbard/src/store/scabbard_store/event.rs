@@ -14,6 +14,7 @@
 
 #[cfg(feature = "scabbardv3-consensus")]
 use std::convert::{TryFrom, TryInto as _};
+use std::time::SystemTime;
 
 #[cfg(feature = "scabbardv3-consensus")]
 use augrim::{error::InternalError, two_phase_commit::TwoPhaseCommitEvent};
@@ -35,6 +36,58 @@ impl ConsensusEvent {
     }
 }
 
+/// A consensus event of a service as it is recorded in the store, whether or not it has been
+/// executed
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ConsensusEventLogEntry {
+    position: i32,
+    event: ConsensusEvent,
+    created_at: SystemTime,
+    executed_at: Option<SystemTime>,
+    executed_epoch: Option<u64>,
+}
+
+impl ConsensusEventLogEntry {
+    pub(crate) fn new(
+        position: i32,
+        event: ConsensusEvent,
+        created_at: SystemTime,
+        executed_at: Option<SystemTime>,
+        executed_epoch: Option<u64>,
+    ) -> Self {
+        Self {
+            position,
+            event,
+            created_at,
+            executed_at,
+            executed_epoch,
+        }
+    }
+
+    /// Returns the position of the event in the service's event log
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    pub fn event(&self) -> &ConsensusEvent {
+        &self.event
+    }
+
+    pub fn created_at(&self) -> SystemTime {
+        self.created_at
+    }
+
+    /// Returns when the event was executed, or `None` if it is still pending
+    pub fn executed_at(&self) -> Option<SystemTime> {
+        self.executed_at
+    }
+
+    /// Returns the epoch of the context the event was executed in
+    pub fn executed_epoch(&self) -> Option<u64> {
+        self.executed_epoch
+    }
+}
+
 #[cfg(feature = "scabbardv3-consensus")]
 impl TryFrom<ConsensusEvent> for TwoPhaseCommitEvent<ScabbardProcess, ScabbardValue> {
     type Error = InternalError;
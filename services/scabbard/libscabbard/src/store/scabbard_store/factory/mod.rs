@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "mysql")]
+mod mysql;
 #[cfg(feature = "postgres")]
 mod postgres;
 #[cfg(feature = "sqlite")]
@@ -19,6 +21,8 @@ mod sqlite;
 
 use crate::store::scabbard_store::ScabbardStore;
 
+#[cfg(feature = "mysql")]
+pub use mysql::{MysqlScabbardStoreFactory, PooledMysqlScabbardStoreFactory};
 #[cfg(feature = "postgres")]
 pub use postgres::{PgScabbardStoreFactory, PooledPgScabbardStoreFactory};
 #[cfg(feature = "sqlite")]
@@ -17,7 +17,7 @@ mod alarm;
 mod boxed;
 mod commit;
 mod context;
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 mod diesel;
 mod error;
 mod event;
@@ -32,13 +32,13 @@ use std::time::SystemTime;
 
 pub(crate) use error::ScabbardStoreError;
 
-#[cfg(any(feature = "postgres", feature = "sqlite"))]
+#[cfg(any(feature = "mysql", feature = "postgres", feature = "sqlite"))]
 pub use self::diesel::DieselScabbardStore;
 pub use action::ConsensusAction;
 pub use alarm::AlarmType;
 pub use commit::{CommitEntry, CommitEntryBuilder, ConsensusDecision};
 pub use context::ConsensusContext;
-pub use event::ConsensusEvent;
+pub use event::{ConsensusEvent, ConsensusEventLogEntry};
 pub use identified::Identified;
 pub use service::{ConsensusType, ScabbardService, ScabbardServiceBuilder, ServiceStatus};
 pub use supervisor::{
@@ -48,6 +48,8 @@ pub use two_phase_commit::{
     Action, Context, ContextBuilder, Event, Message, Notification, Participant, State,
 };
 
+#[cfg(feature = "mysql")]
+pub use factory::{MysqlScabbardStoreFactory, PooledMysqlScabbardStoreFactory};
 #[cfg(feature = "postgres")]
 pub use factory::{PgScabbardStoreFactory, PooledPgScabbardStoreFactory};
 pub use factory::{PooledScabbardStoreFactory, ScabbardStoreFactory};
@@ -214,6 +216,18 @@ pub trait ScabbardStore {
         service_id: &FullyQualifiedServiceId,
     ) -> Result<Vec<Identified<ConsensusEvent>>, ScabbardStoreError>;
 
+    /// List every consensus event for a given service_id, including those that have been
+    /// executed, in position order
+    ///
+    /// # Arguments
+    ///
+    /// * `service_id` - The combined `CircuitId` and `ServiceId` of the service for which events
+    ///    should be listed
+    fn list_consensus_event_log(
+        &self,
+        service_id: &FullyQualifiedServiceId,
+    ) -> Result<Vec<ConsensusEventLogEntry>, ScabbardStoreError>;
+
     /// Get the current context for a given service
     ///
     /// # Arguments
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::fmt;
+use std::time::Duration;
 
 use splinter::error::InvalidStateError;
 use splinter::service::{FullyQualifiedServiceId, ServiceId};
@@ -23,6 +24,7 @@ pub struct ScabbardService {
     peers: Vec<ServiceId>,
     consensus: ConsensusType,
     status: ServiceStatus,
+    coordinator_timeout: Option<Duration>,
 }
 
 impl ScabbardService {
@@ -46,12 +48,18 @@ impl ScabbardService {
         &self.status
     }
 
+    /// Returns the coordinator timeout for the scabbard service, if one was configured
+    pub fn coordinator_timeout(&self) -> Option<Duration> {
+        self.coordinator_timeout
+    }
+
     pub fn into_builder(self) -> ScabbardServiceBuilder {
         ScabbardServiceBuilder {
             service_id: Some(self.service_id),
             peers: Some(self.peers),
             consensus: Some(self.consensus),
             status: Some(self.status),
+            coordinator_timeout: self.coordinator_timeout,
         }
     }
 }
@@ -62,6 +70,7 @@ pub struct ScabbardServiceBuilder {
     peers: Option<Vec<ServiceId>>,
     consensus: Option<ConsensusType>,
     status: Option<ServiceStatus>,
+    coordinator_timeout: Option<Duration>,
 }
 
 impl ScabbardServiceBuilder {
@@ -85,6 +94,11 @@ impl ScabbardServiceBuilder {
         self.status.clone()
     }
 
+    /// Returns the coordinator timeout for the service
+    pub fn coordinator_timeout(&self) -> Option<Duration> {
+        self.coordinator_timeout
+    }
+
     /// Sets the service ID
     ///
     /// # Arguments
@@ -128,6 +142,20 @@ impl ScabbardServiceBuilder {
         self
     }
 
+    /// Sets the coordinator timeout
+    ///
+    /// # Arguments
+    ///
+    ///  * `coordinator_timeout` - How long the consensus algorithm waits for votes,
+    ///    acknowledgements and decisions before giving up
+    pub fn with_coordinator_timeout(
+        mut self,
+        coordinator_timeout: Duration,
+    ) -> ScabbardServiceBuilder {
+        self.coordinator_timeout = Some(coordinator_timeout);
+        self
+    }
+
     /// Builds the `ScabbardService`
     ///
     /// Returns an error if the service ID, peers, or status is not set
@@ -157,6 +185,7 @@ impl ScabbardServiceBuilder {
             consensus,
             peers,
             status,
+            coordinator_timeout: self.coordinator_timeout,
         })
     }
 }
@@ -181,12 +210,14 @@ impl fmt::Display for ServiceStatus {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ConsensusType {
     TwoPC,
+    ThreePC,
 }
 
 impl fmt::Display for ConsensusType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConsensusType::TwoPC => write!(f, "Consensus: Two Phase Commit"),
+            ConsensusType::ThreePC => write!(f, "Consensus: Three Phase Commit"),
         }
     }
 }
@@ -171,6 +171,8 @@ impl ContextBuilder {
 pub struct Participant {
     pub process: ServiceId,
     pub vote: Option<bool>,
+    /// Whether the participant has acknowledged the coordinator's decision; under three phase
+    /// commit, this first tracks the acknowledgement of the `PreCommit`
     pub decision_ack: bool,
 }
 
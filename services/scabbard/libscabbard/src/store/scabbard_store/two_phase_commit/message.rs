@@ -22,8 +22,8 @@ use augrim::two_phase_commit::TwoPhaseCommitMessage as AugrimTwoPhaseCommitMessa
 use crate::protocol::v3::{
     message::ScabbardMessage,
     two_phase_commit::{
-        Abort, Commit, DecisionAck, DecisionRequest, TwoPhaseCommitMessage, VoteRequest,
-        VoteResponse,
+        Abort, Commit, DecisionAck, DecisionRequest, PreCommit, PreCommitAck,
+        TwoPhaseCommitMessage, VoteRequest, VoteResponse,
     },
 };
 use crate::protos::{scabbard_v3, IntoBytes, ProtoConversionError};
@@ -38,6 +38,11 @@ pub enum Message {
     DecisionRequest(u64),
     VoteResponse(u64, bool),
     DecisionAck(u64),
+    /// Sent by the coordinator of a three phase commit once every participant has voted to
+    /// commit
+    PreCommit(u64),
+    /// Sent by a participant of a three phase commit in response to a `PreCommit`
+    PreCommitAck(u64),
 }
 
 impl TryFrom<Message> for ScabbardMessage {
@@ -59,6 +64,10 @@ impl TryFrom<Message> for ScabbardMessage {
             Message::DecisionAck(epoch) => {
                 TwoPhaseCommitMessage::DecisionAck(DecisionAck { epoch })
             }
+            Message::PreCommit(epoch) => TwoPhaseCommitMessage::PreCommit(PreCommit { epoch }),
+            Message::PreCommitAck(epoch) => {
+                TwoPhaseCommitMessage::PreCommitAck(PreCommitAck { epoch })
+            }
         };
 
         Ok(ScabbardMessage::ConsensusMessage(IntoBytes::<
@@ -111,6 +120,11 @@ impl TryFrom<Message> for AugrimTwoPhaseCommitMessage<ScabbardValue> {
             Message::DecisionRequest(epoch) => Self::DecisionRequest(epoch),
             Message::VoteResponse(epoch, vote) => Self::VoteResponse(epoch, vote),
             Message::DecisionAck(epoch) => Self::DecisionAck(epoch),
+            Message::PreCommit(_) | Message::PreCommitAck(_) => {
+                return Err(InternalError::with_message(
+                    "three phase commit messages are not supported by two phase commit".into(),
+                ))
+            }
         })
     }
 }
@@ -36,6 +36,16 @@ pub enum State {
     WaitingForDecisionAck {
         ack_timeout_start: SystemTime,
     },
+    /// A three phase commit coordinator that has sent `PreCommit` to every participant and is
+    /// waiting for their acknowledgements
+    WaitingForPreCommitAck {
+        ack_timeout_start: SystemTime,
+    },
+    /// A three phase commit participant that has acknowledged a `PreCommit` and is waiting for
+    /// the coordinator's decision; if it times out, the participant commits
+    PreCommitted {
+        decision_timeout_start: SystemTime,
+    },
 }
 
 #[cfg(feature = "scabbardv3-consensus")]
@@ -60,6 +70,11 @@ impl TryFrom<State> for TwoPhaseCommitState<SystemTime> {
             State::WaitingForDecisionAck { ack_timeout_start } => {
                 Self::WaitingForDecisionAck { ack_timeout_start }
             }
+            State::WaitingForPreCommitAck { .. } | State::PreCommitted { .. } => {
+                return Err(InternalError::with_message(
+                    "three phase commit states are not supported by two phase commit".into(),
+                ))
+            }
         })
     }
 }
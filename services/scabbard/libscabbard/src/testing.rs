@@ -0,0 +1,734 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for integration-testing applications, such as Sabre smart contracts, against scabbard.
+//!
+//! A [`ScabbardTestCircuit`] runs scabbard services in the current process. The services are
+//! connected by an in-process circuit that delivers their messages on a background thread, and
+//! their state, commit hashes and receipts are kept in memory, so no splinter nodes, network or
+//! database are required.
+//!
+//! ```ignore
+//! let circuit = ScabbardTestCircuitBuilder::new()
+//!     .with_admin_keys(vec![admin_public_key])
+//!     .build()?;
+//!
+//! let infos = circuit.submit_batches_and_wait("a000", batches, Duration::from_secs(10))?;
+//! circuit.assert_state_at_address(&address, Some(b"value"));
+//!
+//! circuit.shutdown()?;
+//! ```
+
+use std::collections::{HashMap, HashSet};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use cylinder::{secp256k1::Secp256k1Context, VerifierFactory};
+use diesel::{
+    r2d2::{ConnectionManager, Pool},
+    sqlite::SqliteConnection,
+};
+use sawtooth::migrations::run_sqlite_migrations;
+use sawtooth::receipt::store::diesel::DieselReceiptStore;
+use splinter::error::{InternalError, InvalidStateError};
+use splinter::service::instance::{
+    ServiceConnectionError, ServiceDisconnectionError, ServiceInstance, ServiceMessageContext,
+    ServiceNetworkRegistry, ServiceNetworkSender, ServiceSendError,
+};
+use transact::{
+    database::{btree::BTreeDatabase, Database},
+    protocol::batch::BatchPair,
+    state::merkle::INDEXES,
+};
+
+use crate::service::state::merkle_state::{MerkleState, MerkleStateConfig};
+use crate::service::{
+    BatchInfo, BatchStatus, Scabbard, ScabbardStatePurgeHandler, ScabbardVersion,
+};
+use crate::store::transact::{TransactCommitHashStore, CURRENT_STATE_ROOT_INDEX};
+
+const DEFAULT_CIRCUIT_ID: &str = "ABCDE-01234";
+const DEFAULT_SERVICE_IDS: [&str; 2] = ["a000", "b000"];
+const STATE_ROOT_POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Builds a [`ScabbardTestCircuit`].
+///
+/// By default, the circuit has the ID `ABCDE-01234` and runs two scabbard v2 services, `a000` and
+/// `b000`, with no admin keys.
+pub struct ScabbardTestCircuitBuilder {
+    circuit_id: Option<String>,
+    service_ids: Option<Vec<String>>,
+    admin_keys: Vec<String>,
+    version: ScabbardVersion,
+    coordinator_timeout: Option<Duration>,
+}
+
+impl Default for ScabbardTestCircuitBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScabbardTestCircuitBuilder {
+    pub fn new() -> Self {
+        ScabbardTestCircuitBuilder {
+            circuit_id: None,
+            service_ids: None,
+            admin_keys: vec![],
+            version: ScabbardVersion::V2,
+            coordinator_timeout: None,
+        }
+    }
+
+    /// Sets the ID of the circuit the services run on.
+    pub fn with_circuit_id(mut self, circuit_id: &str) -> Self {
+        self.circuit_id = Some(circuit_id.to_string());
+        self
+    }
+
+    /// Sets the IDs of the services to run; a service is started for each ID.
+    pub fn with_service_ids(mut self, service_ids: Vec<String>) -> Self {
+        self.service_ids = Some(service_ids);
+        self
+    }
+
+    /// Sets the public keys that are authorized to create and manage Sabre contracts.
+    pub fn with_admin_keys(mut self, admin_keys: Vec<String>) -> Self {
+        self.admin_keys = admin_keys;
+        self
+    }
+
+    /// Sets the scabbard protocol version the services use.
+    pub fn with_version(mut self, version: ScabbardVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets the coordinator timeout for the services' two-phase commit consensus.
+    pub fn with_coordinator_timeout(mut self, coordinator_timeout: Duration) -> Self {
+        self.coordinator_timeout = Some(coordinator_timeout);
+        self
+    }
+
+    /// Creates the services and starts them on an in-process circuit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if no service IDs were given, if a service ID is repeated, or if
+    /// a service could not be created or started.
+    pub fn build(self) -> Result<ScabbardTestCircuit, InternalError> {
+        let circuit_id = self
+            .circuit_id
+            .unwrap_or_else(|| DEFAULT_CIRCUIT_ID.to_string());
+        let service_ids = self.service_ids.unwrap_or_else(|| {
+            DEFAULT_SERVICE_IDS
+                .iter()
+                .map(|service_id| service_id.to_string())
+                .collect()
+        });
+
+        if service_ids.is_empty() {
+            return Err(InternalError::with_message(
+                "At least one service ID is required".into(),
+            ));
+        }
+
+        let unique_ids = service_ids.iter().collect::<HashSet<_>>();
+        if unique_ids.len() != service_ids.len() {
+            return Err(InternalError::with_message(
+                "Service IDs must be unique".into(),
+            ));
+        }
+
+        let mut services = vec![];
+        for service_id in &service_ids {
+            let peer_services = service_ids
+                .iter()
+                .filter(|peer_id| *peer_id != service_id)
+                .cloned()
+                .collect();
+
+            services.push(create_service(
+                &circuit_id,
+                service_id,
+                self.version,
+                peer_services,
+                self.admin_keys.clone(),
+                self.coordinator_timeout,
+            )?);
+        }
+
+        let circuit = InprocCircuit::new(&circuit_id, &services)?;
+
+        for service in services.iter_mut() {
+            service.start(&circuit.registry).map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Unable to start service {}", service.service_id()),
+                )
+            })?;
+        }
+
+        Ok(ScabbardTestCircuit { services, circuit })
+    }
+}
+
+/// Scabbard services that run in the current process on an in-process circuit.
+///
+/// The services are stopped by [`ScabbardTestCircuit::shutdown`].
+pub struct ScabbardTestCircuit {
+    services: Vec<Scabbard>,
+    circuit: InprocCircuit,
+}
+
+impl ScabbardTestCircuit {
+    /// Returns the IDs of the services on the circuit.
+    pub fn service_ids(&self) -> Vec<&str> {
+        self.services
+            .iter()
+            .map(|service| service.service_id())
+            .collect()
+    }
+
+    /// Returns the service with the given ID, if it is on the circuit.
+    pub fn service(&self, service_id: &str) -> Option<&Scabbard> {
+        self.services
+            .iter()
+            .find(|service| service.service_id() == service_id)
+    }
+
+    /// Submits batches to the given service and returns their IDs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the service is not on the circuit, or if the service rejects
+    /// the batches, such as when a batch's signature is invalid.
+    pub fn submit_batches(
+        &self,
+        service_id: &str,
+        batches: Vec<BatchPair>,
+    ) -> Result<Vec<String>, InternalError> {
+        let service = self.get_service(service_id)?;
+
+        let batch_ids = batches
+            .iter()
+            .map(|batch| batch.batch().header_signature().to_string())
+            .collect();
+
+        match service
+            .add_batches(batches)
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+        {
+            Some(_) => Ok(batch_ids),
+            None => Err(InternalError::with_message(format!(
+                "Service {} rejected the batches",
+                service_id
+            ))),
+        }
+    }
+
+    /// Waits up to `timeout` for the given batches, which were submitted to the given service, to
+    /// be committed or found invalid, and returns their final `BatchInfo`s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the service is not on the circuit, or if the timeout expires
+    /// before every batch is committed or found invalid.
+    pub fn wait_for_batches(
+        &self,
+        service_id: &str,
+        batch_ids: &[String],
+        timeout: Duration,
+    ) -> Result<Vec<BatchInfo>, InternalError> {
+        self.get_service(service_id)?
+            .get_batch_info(batch_ids.iter().cloned().collect(), Some(timeout))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(InternalError::with_message)
+    }
+
+    /// Waits up to `timeout` for every service to reach the given state root.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the timeout expires before every service has the state
+    /// root.
+    pub fn wait_for_state_root(
+        &self,
+        state_root: &str,
+        timeout: Duration,
+    ) -> Result<(), InternalError> {
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let mut behind = vec![];
+            for service in &self.services {
+                let current_state_root = service
+                    .get_current_state_root()
+                    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+                if current_state_root != state_root {
+                    behind.push(service.service_id());
+                }
+            }
+
+            if behind.is_empty() {
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(InternalError::with_message(format!(
+                    "Timed out waiting for services {} to reach state root {}",
+                    behind.join(", "),
+                    state_root
+                )));
+            }
+
+            thread::sleep(STATE_ROOT_POLL_INTERVAL);
+        }
+    }
+
+    /// Submits batches to the given service, waits for them to be committed or found invalid,
+    /// and, if they were all committed, waits for every service to reach the submitting service's
+    /// state root. The batches' final `BatchInfo`s are returned.
+    ///
+    /// The timeout applies to each stage of waiting.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if the batches could not be submitted or if a timeout expires.
+    pub fn submit_batches_and_wait(
+        &self,
+        service_id: &str,
+        batches: Vec<BatchPair>,
+        timeout: Duration,
+    ) -> Result<Vec<BatchInfo>, InternalError> {
+        let batch_ids = self.submit_batches(service_id, batches)?;
+        let batch_infos = self.wait_for_batches(service_id, &batch_ids, timeout)?;
+
+        let all_committed = batch_infos
+            .iter()
+            .all(|info| matches!(info.status, BatchStatus::Committed(_)));
+        if all_committed {
+            let state_root = self
+                .get_service(service_id)?
+                .get_current_state_root()
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+            self.wait_for_state_root(&state_root, timeout)?;
+        }
+
+        Ok(batch_infos)
+    }
+
+    /// Asserts that the value at the given address is `expected` in every service's state; an
+    /// `expected` value of `None` asserts that the address is not set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any service has a different value, or if state could not be read.
+    pub fn assert_state_at_address(&self, address: &str, expected: Option<&[u8]>) {
+        for service in &self.services {
+            let value = service.get_state_at_address(address).unwrap_or_else(|err| {
+                panic!(
+                    "Unable to read state of service {}: {}",
+                    service.service_id(),
+                    err
+                )
+            });
+            assert_eq!(
+                value.as_deref(),
+                expected,
+                "unexpected value at address {} in the state of service {}",
+                address,
+                service.service_id()
+            );
+        }
+    }
+
+    /// Asserts that every service has the same state root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the state roots differ, or if a state root could not be read.
+    pub fn assert_state_roots_match(&self) {
+        let state_roots = self
+            .services
+            .iter()
+            .map(|service| {
+                let state_root = service.get_current_state_root().unwrap_or_else(|err| {
+                    panic!(
+                        "Unable to read state root of service {}: {}",
+                        service.service_id(),
+                        err
+                    )
+                });
+                (service.service_id(), state_root)
+            })
+            .collect::<Vec<_>>();
+
+        assert!(
+            state_roots.windows(2).all(|pair| pair[0].1 == pair[1].1),
+            "services have different state roots: {:?}",
+            state_roots
+        );
+    }
+
+    /// Stops the services and the in-process circuit.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InternalError` if a service could not be stopped.
+    pub fn shutdown(self) -> Result<(), InternalError> {
+        let ScabbardTestCircuit {
+            mut services,
+            circuit,
+        } = self;
+
+        for service in services.iter_mut() {
+            service.stop(&circuit.registry).map_err(|err| {
+                InternalError::from_source_with_message(
+                    Box::new(err),
+                    format!("Unable to stop service {}", service.service_id()),
+                )
+            })?;
+        }
+
+        circuit.shutdown()
+    }
+
+    fn get_service(&self, service_id: &str) -> Result<&Scabbard, InternalError> {
+        self.service(service_id).ok_or_else(|| {
+            InternalError::with_message(format!("Service {} is not on the circuit", service_id))
+        })
+    }
+}
+
+fn create_service(
+    circuit_id: &str,
+    service_id: &str,
+    version: ScabbardVersion,
+    peer_services: HashSet<String>,
+    admin_keys: Vec<String>,
+    coordinator_timeout: Option<Duration>,
+) -> Result<Scabbard, InternalError> {
+    let pool = Pool::builder()
+        .max_size(1)
+        .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+        .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    run_sqlite_migrations(
+        &*pool
+            .get()
+            .map_err(|err| InternalError::from_source(Box::new(err)))?,
+    )
+    .map_err(|err| InternalError::from_source(Box::new(err)))?;
+    let receipt_store =
+        DieselReceiptStore::new(pool, Some(format!("{}::{}", circuit_id, service_id)));
+
+    let mut indexes = INDEXES.to_vec();
+    indexes.push(CURRENT_STATE_ROOT_INDEX);
+    let db = BTreeDatabase::new(&indexes);
+    let merkle_state = MerkleState::new(MerkleStateConfig::key_value(db.clone_box()))?;
+    let commit_hash_store = TransactCommitHashStore::new(db);
+
+    Scabbard::new(
+        service_id.to_string(),
+        circuit_id,
+        version,
+        peer_services,
+        merkle_state,
+        false,
+        Arc::new(commit_hash_store),
+        Arc::new(receipt_store),
+        Box::new(InMemoryPurgeHandler),
+        Secp256k1Context::new().new_verifier(),
+        admin_keys,
+        coordinator_timeout,
+    )
+    .map_err(|err| InternalError::from_source(Box::new(err)))
+}
+
+/// The services' state is dropped with the services, so there is nothing to purge.
+struct InMemoryPurgeHandler;
+
+impl ScabbardStatePurgeHandler for InMemoryPurgeHandler {
+    fn purge_state(&self) -> Result<(), InternalError> {
+        Ok(())
+    }
+}
+
+enum InprocMessage {
+    Deliver {
+        sender: String,
+        recipient: String,
+        payload: Vec<u8>,
+    },
+    Shutdown,
+}
+
+/// Delivers messages between the services on a background thread, in the order they were sent.
+struct InprocCircuit {
+    registry: InprocServiceNetworkRegistry,
+    join_handle: thread::JoinHandle<()>,
+}
+
+impl InprocCircuit {
+    fn new(circuit_id: &str, services: &[Scabbard]) -> Result<Self, InternalError> {
+        let (sender, receiver) = channel();
+
+        let recipients = services
+            .iter()
+            .map(|service| (service.service_id().to_string(), service.clone()))
+            .collect();
+        let thread_circuit_id = circuit_id.to_string();
+        let join_handle = thread::Builder::new()
+            .name(format!("InprocCircuit-{}", circuit_id))
+            .spawn(move || deliver_messages(&thread_circuit_id, recipients, receiver))
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        Ok(InprocCircuit {
+            registry: InprocServiceNetworkRegistry {
+                sender,
+                connected_ids: Arc::new(Mutex::new(HashSet::new())),
+            },
+            join_handle,
+        })
+    }
+
+    fn shutdown(self) -> Result<(), InternalError> {
+        self.registry
+            .sender
+            .send(InprocMessage::Shutdown)
+            .map_err(|_| InternalError::with_message("In-process circuit thread exited".into()))?;
+        self.join_handle
+            .join()
+            .map_err(|_| InternalError::with_message("In-process circuit thread panicked".into()))
+    }
+}
+
+fn deliver_messages(
+    circuit_id: &str,
+    recipients: HashMap<String, Scabbard>,
+    receiver: Receiver<InprocMessage>,
+) {
+    while let Ok(message) = receiver.recv() {
+        let (sender, recipient_id, payload) = match message {
+            InprocMessage::Deliver {
+                sender,
+                recipient,
+                payload,
+            } => (sender, recipient, payload),
+            InprocMessage::Shutdown => break,
+        };
+
+        let recipient = match recipients.get(&recipient_id) {
+            Some(recipient) => recipient,
+            None => {
+                warn!(
+                    "Dropping message from {} to unknown service {}",
+                    sender, recipient_id
+                );
+                continue;
+            }
+        };
+
+        let context = ServiceMessageContext {
+            sender,
+            circuit: circuit_id.to_string(),
+            correlation_id: String::new(),
+        };
+
+        if let Err(err) = recipient.handle_message(&payload, &context) {
+            warn!(
+                "Service {} was unable to handle message from {}: {}",
+                recipient_id, context.sender, err
+            );
+        }
+    }
+}
+
+struct InprocServiceNetworkRegistry {
+    sender: Sender<InprocMessage>,
+    connected_ids: Arc<Mutex<HashSet<String>>>,
+}
+
+impl ServiceNetworkRegistry for InprocServiceNetworkRegistry {
+    fn connect(
+        &self,
+        service_id: &str,
+    ) -> Result<Box<dyn ServiceNetworkSender>, ServiceConnectionError> {
+        if self
+            .connected_ids
+            .lock()
+            .map_err(|_| ServiceConnectionError::RejectedError("lock poisoned".into()))?
+            .insert(service_id.into())
+        {
+            Ok(Box::new(InprocServiceNetworkSender {
+                service_id: service_id.to_string(),
+                sender: self.sender.clone(),
+            }))
+        } else {
+            Err(ServiceConnectionError::RejectedError(format!(
+                "service with id {} already connected",
+                service_id
+            )))
+        }
+    }
+
+    fn disconnect(&self, service_id: &str) -> Result<(), ServiceDisconnectionError> {
+        if self
+            .connected_ids
+            .lock()
+            .map_err(|_| ServiceDisconnectionError::RejectedError("lock poisoned".into()))?
+            .remove(service_id)
+        {
+            Ok(())
+        } else {
+            Err(ServiceDisconnectionError::RejectedError(format!(
+                "service with id {} not connected",
+                service_id
+            )))
+        }
+    }
+}
+
+#[derive(Clone)]
+struct InprocServiceNetworkSender {
+    service_id: String,
+    sender: Sender<InprocMessage>,
+}
+
+impl ServiceNetworkSender for InprocServiceNetworkSender {
+    fn send(&self, recipient: &str, message: &[u8]) -> Result<(), ServiceSendError> {
+        self.sender
+            .send(InprocMessage::Deliver {
+                sender: self.service_id.clone(),
+                recipient: recipient.to_string(),
+                payload: message.to_vec(),
+            })
+            .map_err(|_| {
+                ServiceSendError(Box::new(InvalidStateError::with_message(
+                    "in-process circuit has shut down".into(),
+                )))
+            })
+    }
+
+    fn send_and_await(
+        &self,
+        _recipient: &str,
+        _message: &[u8],
+    ) -> Result<Vec<u8>, ServiceSendError> {
+        Err(ServiceSendError(Box::new(InvalidStateError::with_message(
+            "in-process circuit does not support awaiting replies".into(),
+        ))))
+    }
+
+    fn reply(
+        &self,
+        message_origin: &ServiceMessageContext,
+        message: &[u8],
+    ) -> Result<(), ServiceSendError> {
+        self.send(&message_origin.sender, message)
+    }
+
+    fn clone_box(&self) -> Box<dyn ServiceNetworkSender> {
+        Box::new(self.clone())
+    }
+
+    fn send_with_sender(
+        &mut self,
+        recipient: &str,
+        message: &[u8],
+        sender: &str,
+    ) -> Result<(), ServiceSendError> {
+        self.sender
+            .send(InprocMessage::Deliver {
+                sender: sender.to_string(),
+                recipient: recipient.to_string(),
+                payload: message.to_vec(),
+            })
+            .map_err(|_| {
+                ServiceSendError(Box::new(InvalidStateError::with_message(
+                    "in-process circuit has shut down".into(),
+                )))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cylinder::Context;
+    use transact::{
+        families::command::CommandTransactionBuilder,
+        protocol::command::{BytesEntry, Command, SetState},
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    /// Verify that a batch submitted to one service of a test circuit is committed by both
+    /// services.
+    ///
+    /// 1. Build a test circuit with the default two services.
+    /// 2. Submit a batch that sets a value in state to the second service.
+    /// 3. Verify that the batch was committed and that both services have the value.
+    /// 4. Shut down the circuit.
+    #[test]
+    fn test_circuit_commits_batch() {
+        let circuit = ScabbardTestCircuitBuilder::new()
+            .build()
+            .expect("failed to build test circuit");
+        assert_eq!(circuit.service_ids(), vec!["a000", "b000"]);
+
+        let address = "abcdef".to_string();
+        let value = b"value".to_vec();
+
+        let signing_context = Secp256k1Context::new();
+        let signer = signing_context.new_signer(signing_context.new_random_private_key());
+        let batch = CommandTransactionBuilder::new()
+            .with_commands(vec![Command::SetState(SetState::new(vec![
+                BytesEntry::new(address.clone(), value.clone()),
+            ]))])
+            .into_transaction_builder()
+            .expect("failed to convert to transaction builder")
+            .into_batch_builder(&*signer)
+            .expect("failed to build transaction")
+            .build_pair(&*signer)
+            .expect("failed to build batch");
+
+        let batch_infos = circuit
+            .submit_batches_and_wait("b000", vec![batch], TIMEOUT)
+            .expect("failed to submit batch");
+        assert_eq!(batch_infos.len(), 1);
+        assert!(matches!(batch_infos[0].status, BatchStatus::Committed(_)));
+
+        circuit.assert_state_at_address(&address, Some(&value));
+        circuit.assert_state_at_address("0123456789", None);
+        circuit.assert_state_roots_match();
+
+        circuit
+            .shutdown()
+            .expect("failed to shut down test circuit");
+    }
+
+    /// Verify that a test circuit cannot be built with repeated service IDs.
+    #[test]
+    fn test_circuit_duplicate_service_ids() {
+        assert!(ScabbardTestCircuitBuilder::new()
+            .with_service_ids(vec!["a000".into(), "a000".into()])
+            .build()
+            .is_err());
+    }
+}
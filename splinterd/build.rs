@@ -26,7 +26,14 @@ const PATH: &str = "PATH";
 /// man pages stored in packaging/man. This build script will check if pandoc is installed locally
 /// and skip generating the manpages if it is not. If the build should fail if man pages cannot be
 /// generated set environment variable SPLINTER_FORCE_PANDOC=true
+///
+/// If the `grpc` feature is enabled, the gRPC services in the /protos directory are compiled
+/// first.
 fn main() -> Result<(), BuildError> {
+    #[cfg(feature = "grpc")]
+    tonic_build::compile_protos("protos/splinterd_grpc.proto")
+        .map_err(|err| BuildError(format!("Unable to compile gRPC protos: {}", err)))?;
+
     let paths = env::var(PATH)
         .map_err(|_| BuildError("Unable to read PATH environment variable".into()))?;
     let mut pandoc_exist = false;
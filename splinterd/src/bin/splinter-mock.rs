@@ -0,0 +1,112 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! `splinter-mock` serves a mock of the Splinter REST API with canned data, so that applications
+//! can be developed against the REST API without running a Splinter network.
+
+#[macro_use]
+extern crate log;
+
+use std::fs;
+use std::process;
+
+use clap::{clap_app, crate_version};
+use cylinder::{secp256k1::Secp256k1Context, Context};
+use log::LevelFilter;
+use log4rs::append::console::ConsoleAppender;
+use log4rs::config::{Appender, Config, Root};
+use splinter::rest_api::actix_web_1::{MockFixtures, MockResourceProvider};
+#[cfg(feature = "https-bind")]
+use splinter::rest_api::BindConfig;
+use splinter::rest_api::{AuthConfig, RestApiBuilder, RestResourceProvider};
+
+fn main() {
+    let matches = clap_app!(("splinter-mock") =>
+        (version: crate_version!())
+        (about: "Serves a mock of the Splinter REST API")
+        (@arg bind: -b --bind +takes_value
+            "The address the REST API listens on; default is 127.0.0.1:8080")
+        (@arg fixtures: -f --fixtures +takes_value
+            "A YAML or JSON file of the routes and event streams to serve; if not set, a sample \
+             two-node network is served")
+        (@arg verbose: -v --verbose +multiple
+          "Increase output verbosity"))
+    .get_matches();
+
+    let level = match matches.occurrences_of("verbose") {
+        0 => LevelFilter::Info,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    };
+    let log_config = Config::builder()
+        .appender(Appender::builder().build("stdout", Box::new(ConsoleAppender::builder().build())))
+        .build(Root::builder().appender("stdout").build(level));
+    match log_config {
+        Ok(log_config) => {
+            if let Err(err) = log4rs::init_config(log_config) {
+                eprintln!("Unable to start logger: {}", err);
+            }
+        }
+        Err(err) => eprintln!("Invalid log configuration: {}", err),
+    }
+
+    if let Err(err) = run(
+        matches.value_of("bind").unwrap_or("127.0.0.1:8080"),
+        matches.value_of("fixtures"),
+    ) {
+        error!("{}", err);
+        process::exit(1);
+    }
+}
+
+fn run(bind: &str, fixtures_path: Option<&str>) -> Result<(), String> {
+    let fixtures = match fixtures_path {
+        Some(path) => {
+            let fixtures = fs::read_to_string(path)
+                .map_err(|err| format!("Unable to read fixtures file {}: {}", path, err))?;
+            MockFixtures::from_yaml(&fixtures)
+                .map_err(|err| format!("Invalid fixtures file {}: {}", path, err))?
+        }
+        None => MockFixtures::sample(),
+    };
+
+    #[cfg(feature = "https-bind")]
+    let builder = RestApiBuilder::new().with_bind(BindConfig::Http(bind.into()));
+    #[cfg(not(feature = "https-bind"))]
+    let builder = RestApiBuilder::new().with_bind(bind);
+
+    // None of the mock resources require authorization, but the REST API requires an identity
+    // provider
+    let (shutdown_handle, join_handle) = builder
+        .add_resources(MockResourceProvider::new(fixtures).resources())
+        .with_auth_configs(vec![AuthConfig::Cylinder {
+            verifier: Secp256k1Context::new().new_verifier(),
+        }])
+        .build()
+        .and_then(|rest_api| rest_api.run())
+        .map_err(|err| format!("Unable to start mock REST API: {}", err))?;
+
+    info!("Serving mock REST API on http://{}", bind);
+
+    ctrlc::set_handler(move || {
+        if let Err(err) = shutdown_handle.shutdown() {
+            error!("Unable to shutdown mock REST API: {}", err);
+        }
+    })
+    .map_err(|err| format!("Unable to set Ctrl-C handler: {}", err))?;
+
+    join_handle
+        .join()
+        .map_err(|_| "Mock REST API thread panicked".to_string())
+}
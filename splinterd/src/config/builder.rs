@@ -176,6 +176,18 @@ impl ConfigBuilder {
                 None => None,
             })
             .ok_or_else(|| ConfigError::MissingValue("rest_api key".to_string()))?;
+        #[cfg(feature = "tls-peer-ca")]
+        let tls_peer_ca_files = self.partial_configs.iter().find_map(|p| {
+            p.tls_peer_ca_files().map(|peer_ca_files| {
+                (
+                    peer_ca_files
+                        .into_iter()
+                        .map(|(pattern, file)| (pattern, get_tls_file_path(&tls_cert_dir.0, &file)))
+                        .collect(),
+                    p.source(),
+                )
+            })
+        });
         let network_endpoints = self
             .partial_configs
             .iter()
@@ -211,6 +223,8 @@ impl ConfigBuilder {
             tls_rest_api_cert,
             #[cfg(feature = "https-bind")]
             tls_rest_api_key,
+            #[cfg(feature = "tls-peer-ca")]
+            tls_peer_ca_files,
             #[cfg(feature = "service-endpoint")]
             service_endpoint: self
                 .partial_configs
@@ -331,11 +345,301 @@ impl ConfigBuilder {
                 .partial_configs
                 .iter()
                 .find_map(|p| p.oauth_openid_scopes().map(|v| (v, p.source()))),
+            #[cfg(feature = "biome-notifications")]
+            notification_smtp_url: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.notification_smtp_url().map(|v| (v, p.source()))),
+            #[cfg(feature = "biome-notifications")]
+            notification_email_from: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.notification_email_from().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_url: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_url().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_bind_dn: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_bind_dn().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_bind_password: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_bind_password().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_user_search_base: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_user_search_base().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_user_search_filter: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_user_search_filter().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_group_search_base: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_group_search_base().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_group_search_filter: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_group_search_filter().map(|v| (v, p.source()))),
+            #[cfg(feature = "ldap")]
+            ldap_group_roles: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.ldap_group_roles().map(|v| (v, p.source()))),
+            #[cfg(feature = "rest-api-proxy-auth")]
+            rest_api_proxy_auth_header: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rest_api_proxy_auth_header().map(|v| (v, p.source()))),
+            #[cfg(feature = "rest-api-proxy-auth")]
+            rest_api_proxy_trusted_addresses: self.partial_configs.iter().find_map(|p| {
+                p.rest_api_proxy_trusted_addresses()
+                    .map(|v| (v, p.source()))
+            }),
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            rest_api_client_cert_identities: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rest_api_client_cert_identities().map(|v| (v, p.source()))),
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            rest_api_client_cert_roles: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rest_api_client_cert_roles().map(|v| (v, p.source()))),
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            tls_rest_api_client_ca: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.tls_rest_api_client_ca().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-usage")]
+            circuit_usage_message_quota: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_usage_message_quota().map(|v| (v, p.source()))),
+            #[cfg(feature = "circuit-usage")]
+            circuit_usage_byte_quota: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.circuit_usage_byte_quota().map(|v| (v, p.source()))),
+            #[cfg(feature = "connection-limits")]
+            max_inbound_connections: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.max_inbound_connections().map(|v| (v, p.source()))),
+            #[cfg(feature = "connection-limits")]
+            max_inbound_connections_per_ip: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.max_inbound_connections_per_ip().map(|v| (v, p.source()))),
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_size: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.database_pool_size().map(|v| (v, p.source()))),
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.database_pool_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_min_idle: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.database_pool_min_idle().map(|v| (v, p.source()))),
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_idle_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.database_pool_idle_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "clock-skew-detection")]
+            clock_skew_warn_threshold: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.clock_skew_warn_threshold().map(|v| (v, p.source()))),
+            #[cfg(feature = "clock-skew-detection")]
+            clock_skew_max: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.clock_skew_max().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-webhooks")]
+            admin_webhooks: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_webhooks().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-webhooks")]
+            admin_webhook_secret: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_webhook_secret().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-anchoring")]
+            scabbard_anchor_url: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_anchor_url().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-anchoring")]
+            scabbard_anchor_interval: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_anchor_interval().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-anchoring")]
+            scabbard_anchor_services: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_anchor_services().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-batch-validation")]
+            scabbard_batch_validator: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_batch_validator().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-receipt-pruning")]
+            scabbard_receipt_retention: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_receipt_retention().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-receipt-pruning")]
+            scabbard_receipt_prune_interval: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_receipt_prune_interval().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-receipt-pruning")]
+            scabbard_receipt_archive_dir: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_receipt_archive_dir().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-snapshot")]
+            scabbard_snapshot_dir: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_snapshot_dir().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-snapshot")]
+            scabbard_snapshot_interval: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_snapshot_interval().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_state_retention_roots: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_state_retention_roots().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_state_retention_days: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_state_retention_days().map(|v| (v, p.source()))),
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_state_prune_interval: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.scabbard_state_prune_interval().map(|v| (v, p.source()))),
+            #[cfg(feature = "service-restart-policy")]
+            service_max_restarts: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_max_restarts().map(|v| (v, p.source()))),
+            #[cfg(feature = "service-restart-policy")]
+            service_restart_max_backoff: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_restart_max_backoff().map(|v| (v, p.source()))),
+            #[cfg(feature = "service-transfer")]
+            transfer_max_file_size: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.transfer_max_file_size().map(|v| (v, p.source()))),
+            #[cfg(feature = "service-type-limits")]
+            service_max_instances: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_max_instances().map(|v| (v, p.source()))),
+            #[cfg(feature = "service-type-limits")]
+            service_thread_budget: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_thread_budget().map(|v| (v, p.source()))),
+            #[cfg(feature = "service-type-limits")]
+            service_memory_budget: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.service_memory_budget().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-deferred-proposals")]
+            admin_member_wait_timeout: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_member_wait_timeout().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_members: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_max_circuit_members().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_services: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_max_circuit_services().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_metadata_size: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_max_circuit_metadata_size().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_size: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_max_circuit_size().map(|v| (v, p.source()))),
+            #[cfg(feature = "admin-service-circuit-version")]
+            admin_circuit_version: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.admin_circuit_version().map(|v| (v, p.source()))),
             strict_ref_counts: self
                 .partial_configs
                 .iter()
                 .find_map(|p| p.strict_ref_counts().map(|v| (v, p.source())))
                 .ok_or_else(|| ConfigError::MissingValue("strict_ref_counts".to_string()))?,
+            #[cfg(feature = "peer-ref-counts")]
+            peer_retry_interval: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_retry_interval().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-ref-counts")]
+            peer_max_retry_frequency: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_max_retry_frequency().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_initial_delay: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_reconnect_initial_delay().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_max_delay: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_reconnect_max_delay().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_jitter: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_reconnect_jitter().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_max_attempts: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_reconnect_max_attempts().map(|v| (v, p.source()))),
+            #[cfg(feature = "peer-connect-priority")]
+            peer_connect_ramp_rate: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peer_connect_ramp_rate().map(|v| (v, p.source()))),
             #[cfg(feature = "tap")]
             influx_db: self
                 .partial_configs
@@ -450,6 +754,97 @@ impl ConfigBuilder {
                 .ok_or_else(|| {
                     ConfigError::MissingValue("lifecycle_executor_interval".to_string())
                 })?,
+            #[cfg(feature = "biome-credentials")]
+            biome_refresh_token_retention: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.biome_refresh_token_retention().map(|v| (v, p.source())))
+                .ok_or_else(|| {
+                    ConfigError::MissingValue("biome_refresh_token_retention".to_string())
+                })?,
+            #[cfg(feature = "oauth")]
+            oauth_session_retention: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.oauth_session_retention().map(|v| (v, p.source())))
+                .ok_or_else(|| ConfigError::MissingValue("oauth_session_retention".to_string()))?,
+            #[cfg(feature = "registry-auto-populate")]
+            registry_auto_populate: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.registry_auto_populate().map(|v| (v, p.source())))
+                .ok_or_else(|| ConfigError::MissingValue("registry_auto_populate".to_string()))?,
+            #[cfg(feature = "registry-signed-entries")]
+            registry_require_signatures: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.registry_require_signatures().map(|v| (v, p.source())))
+                .ok_or_else(|| {
+                    ConfigError::MissingValue("registry_require_signatures".to_string())
+                })?,
+            #[cfg(feature = "rest-api-degraded-mode")]
+            rest_api_degraded_mode: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rest_api_degraded_mode().map(|v| (v, p.source())))
+                .ok_or_else(|| ConfigError::MissingValue("rest_api_degraded_mode".to_string()))?,
+            #[cfg(feature = "rest-api-openapi")]
+            rest_api_swagger_ui: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.rest_api_swagger_ui().map(|v| (v, p.source())))
+                .ok_or_else(|| ConfigError::MissingValue("rest_api_swagger_ui".to_string()))?,
+            #[cfg(feature = "grpc")]
+            grpc_bind: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.grpc_bind().map(|v| (v, p.source()))),
+            #[cfg(feature = "signature-verification-pool")]
+            verification_threads: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.verification_threads().map(|v| (v, p.source()))),
+            #[cfg(feature = "pkcs11-signer")]
+            signing_key_uri: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.signing_key_uri().map(|v| (v, p.source()))),
+            #[cfg(feature = "vault-key-provider")]
+            vault_keys_url: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.vault_keys_url().map(|v| (v, p.source()))),
+            #[cfg(feature = "otel-tracing")]
+            otel_endpoint: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.otel_endpoint().map(|v| (v, p.source()))),
+            #[cfg(feature = "high-availability")]
+            high_availability: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.high_availability().map(|v| (v, p.source())))
+                .ok_or_else(|| ConfigError::MissingValue("high_availability".to_string()))?,
+            #[cfg(feature = "high-availability")]
+            high_availability_interval: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.high_availability_interval().map(|v| (v, p.source())))
+                .ok_or_else(|| {
+                    ConfigError::MissingValue("high_availability_interval".to_string())
+                })?,
+            #[cfg(feature = "peer-authorization-policy")]
+            peering_authorization: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.peering_authorization().map(|v| (v, p.source())))
+                .ok_or_else(|| ConfigError::MissingValue("peering_authorization".to_string()))?,
+            #[cfg(feature = "peer-authorization-policy")]
+            minimum_authorization: self
+                .partial_configs
+                .iter()
+                .find_map(|p| p.minimum_authorization().map(|v| (v, p.source())))
+                .ok_or_else(|| ConfigError::MissingValue("minimum_authorization".to_string()))?,
         })
     }
 }
@@ -623,4 +1018,76 @@ mod tests {
         // Compare the generated `PartialConfig` object against the expected values.
         assert_config_values(partial_config);
     }
+
+    /// Verify that the files of the `tls_peer_ca_files` values are resolved relative to the
+    /// certificate directory, unless they are absolute, and that the patterns are unchanged.
+    #[cfg(feature = "tls-peer-ca")]
+    #[test]
+    fn test_tls_peer_ca_files_paths() {
+        let partial_config =
+            PartialConfig::new(ConfigSource::CommandLine).with_tls_peer_ca_files(Some(vec![
+                ("*.acme.example.com:*".to_string(), "acme.pem".to_string()),
+                (
+                    "10.0.1.*:8044".to_string(),
+                    "/opt/certs/globex.pem".to_string(),
+                ),
+            ]));
+        let default = crate::config::DefaultPartialConfigBuilder::new()
+            .build()
+            .expect("Could not build default partial config");
+
+        let config = ConfigBuilder::new()
+            .with_partial_config(partial_config)
+            .with_partial_config(default)
+            .build()
+            .expect("Could not build config");
+
+        let cert_dir = Path::new(config.tls_cert_dir());
+        assert_eq!(
+            config.tls_peer_ca_files(),
+            Some(
+                &[
+                    (
+                        "*.acme.example.com:*".to_string(),
+                        cert_dir.join("acme.pem").to_str().unwrap().to_string(),
+                    ),
+                    (
+                        "10.0.1.*:8044".to_string(),
+                        "/opt/certs/globex.pem".to_string(),
+                    ),
+                ][..]
+            )
+        );
+    }
+
+    /// Verifies that a configuration built programmatically, as by an application embedding the
+    /// daemon, takes its values from the API source before the defaults.
+    #[test]
+    fn test_api_partial_config() {
+        let api_config = PartialConfig::new(ConfigSource::Api)
+            .with_node_id(Some(EXAMPLE_NODE_ID.to_string()))
+            .with_state_dir(Some("/var/lib/embedded".to_string()));
+        let default = crate::config::DefaultPartialConfigBuilder::new()
+            .build()
+            .expect("Could not build default partial config");
+
+        let config = ConfigBuilder::new()
+            .with_partial_config(api_config)
+            .with_partial_config(default)
+            .build()
+            .expect("Could not build config");
+
+        assert_eq!(
+            (config.node_id(), config.node_id_source()),
+            (Some(EXAMPLE_NODE_ID), Some(&ConfigSource::Api))
+        );
+        assert_eq!(
+            (config.state_dir(), config.state_dir_source()),
+            ("/var/lib/embedded", &ConfigSource::Api)
+        );
+        assert_eq!(
+            (config.heartbeat(), config.heartbeat_source()),
+            (30, &ConfigSource::Default)
+        );
+    }
 }
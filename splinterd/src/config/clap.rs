@@ -13,12 +13,14 @@
 // limitations under the License.
 
 //! `PartialConfig` builder using values from splinterd command line arguments.
-#[cfg(feature = "service2")]
+#[cfg(any(feature = "service2", feature = "biome-credentials", feature = "oauth"))]
 use std::time::Duration;
 
 use crate::config::{ConfigError, ConfigSource, PartialConfig, PartialConfigBuilder};
 use clap::{ArgMatches, ErrorKind};
 
+#[cfg(feature = "peer-authorization-policy")]
+use crate::config::PeerAuthorization;
 use crate::config::ScabbardState;
 
 /// `PartialConfig` builder which holds command line arguments, represented as clap `ArgMatches`.
@@ -37,6 +39,33 @@ fn parse_value(matches: &ArgMatches, arg: &str) -> Result<Option<u64>, ConfigErr
     }
 }
 
+/// Parses the values of a multiple-value argument formatted as `<key>=<value>` pairs. The pairs
+/// are split at the last `=`, so that the key may contain one.
+#[cfg(any(feature = "rest-api-client-cert-auth", feature = "service-type-limits"))]
+fn parse_pairs(
+    matches: &ArgMatches,
+    arg: &str,
+    format: &str,
+) -> Result<Option<Vec<(String, String)>>, ConfigError> {
+    matches
+        .values_of(arg)
+        .map(|values| {
+            values
+                .map(|value| {
+                    let mut parts = value.rsplitn(2, '=');
+                    match (parts.next(), parts.next()) {
+                        (Some(value), Some(key)) => Ok((key.to_owned(), value.to_owned())),
+                        _ => Err(ConfigError::InvalidArgument(format!(
+                            "{} must be in the format {}",
+                            arg, format
+                        ))),
+                    }
+                })
+                .collect::<Result<_, _>>()
+        })
+        .transpose()
+}
+
 impl<'a> ClapPartialConfigBuilder<'a> {
     pub fn new(matches: ArgMatches<'a>) -> Self {
         ClapPartialConfigBuilder { matches }
@@ -106,6 +135,43 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                 .with_tls_rest_api_key(self.matches.value_of("tls_rest_api_key").map(String::from));
         }
 
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            partial_config = partial_config.with_tls_rest_api_client_ca(
+                self.matches
+                    .value_of("tls_rest_api_client_ca")
+                    .map(String::from),
+            );
+        }
+
+        #[cfg(feature = "tls-peer-ca")]
+        {
+            partial_config = partial_config.with_tls_peer_ca_files(
+                self.matches
+                    .values_of("tls_peer_ca_files")
+                    .map(|values| {
+                        values
+                            .map(|value| {
+                                let mut parts = value.splitn(2, '=');
+                                match (parts.next(), parts.next()) {
+                                    (Some(pattern), Some(file)) => {
+                                        Ok((pattern.to_owned(), file.to_owned()))
+                                    }
+                                    (Some(_), None) => Err(ConfigError::InvalidArgument(
+                                        "TLS peer CA files must be in the format \
+                                         <pattern>=<file>"
+                                            .to_string(),
+                                    )),
+                                    // splitn always returns at least one item
+                                    _ => unreachable!(),
+                                }
+                            })
+                            .collect::<Result<_, _>>()
+                    })
+                    .transpose()?,
+            )
+        }
+
         #[cfg(feature = "service-endpoint")]
         {
             partial_config = partial_config
@@ -123,9 +189,14 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
 
         #[cfg(feature = "biome-credentials")]
         {
-            partial_config = partial_config.with_enable_biome_credentials(Some(
-                self.matches.is_present("enable_biome_credentials"),
-            ))
+            partial_config = partial_config
+                .with_enable_biome_credentials(Some(
+                    self.matches.is_present("enable_biome_credentials"),
+                ))
+                .with_biome_refresh_token_retention(
+                    parse_value(&self.matches, "biome_refresh_token_retention")?
+                        .map(Duration::from_secs),
+                )
         }
 
         #[cfg(feature = "oauth")]
@@ -173,6 +244,354 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
                         .values_of("oauth_openid_scopes")
                         .map(|values| values.map(String::from).collect()),
                 )
+                .with_oauth_session_retention(
+                    parse_value(&self.matches, "oauth_session_retention")?.map(Duration::from_secs),
+                )
+        }
+
+        #[cfg(feature = "biome-notifications")]
+        {
+            partial_config = partial_config
+                .with_notification_smtp_url(
+                    self.matches
+                        .value_of("notification_smtp_url")
+                        .map(String::from),
+                )
+                .with_notification_email_from(
+                    self.matches
+                        .value_of("notification_email_from")
+                        .map(String::from),
+                )
+        }
+
+        #[cfg(feature = "ldap")]
+        {
+            partial_config = partial_config
+                .with_ldap_url(self.matches.value_of("ldap_url").map(String::from))
+                .with_ldap_bind_dn(self.matches.value_of("ldap_bind_dn").map(String::from))
+                .with_ldap_bind_password(
+                    self.matches
+                        .value_of("ldap_bind_password")
+                        .map(String::from),
+                )
+                .with_ldap_user_search_base(
+                    self.matches
+                        .value_of("ldap_user_search_base")
+                        .map(String::from),
+                )
+                .with_ldap_user_search_filter(
+                    self.matches
+                        .value_of("ldap_user_search_filter")
+                        .map(String::from),
+                )
+                .with_ldap_group_search_base(
+                    self.matches
+                        .value_of("ldap_group_search_base")
+                        .map(String::from),
+                )
+                .with_ldap_group_search_filter(
+                    self.matches
+                        .value_of("ldap_group_search_filter")
+                        .map(String::from),
+                )
+                .with_ldap_group_roles(
+                    self.matches
+                        .values_of("ldap_group_roles")
+                        .map(|values| {
+                            values
+                                .map(|value| {
+                                    let mut parts = value.splitn(2, '=');
+                                    match (parts.next(), parts.next()) {
+                                        (Some(group), Some(role)) => {
+                                            Ok((group.to_owned(), role.to_owned()))
+                                        }
+                                        (Some(_), None) => Err(ConfigError::InvalidArgument(
+                                            "LDAP group roles must be in the format \
+                                             <group>=<role>"
+                                                .to_string(),
+                                        )),
+                                        // splitn always returns at least one item
+                                        _ => unreachable!(),
+                                    }
+                                })
+                                .collect::<Result<_, _>>()
+                        })
+                        .transpose()?,
+                )
+        }
+
+        #[cfg(feature = "rest-api-proxy-auth")]
+        {
+            partial_config = partial_config
+                .with_rest_api_proxy_auth_header(
+                    self.matches
+                        .value_of("rest_api_proxy_auth_header")
+                        .map(String::from),
+                )
+                .with_rest_api_proxy_trusted_addresses(
+                    self.matches
+                        .values_of("rest_api_proxy_trusted_addresses")
+                        .map(|values| values.map(String::from).collect()),
+                )
+        }
+
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            partial_config = partial_config
+                .with_rest_api_client_cert_identities(parse_pairs(
+                    &self.matches,
+                    "rest_api_client_cert_identities",
+                    "<certificate>=<user>",
+                )?)
+                .with_rest_api_client_cert_roles(parse_pairs(
+                    &self.matches,
+                    "rest_api_client_cert_roles",
+                    "<user>=<role>",
+                )?)
+        }
+
+        #[cfg(feature = "circuit-usage")]
+        {
+            partial_config = partial_config
+                .with_circuit_usage_message_quota(parse_value(
+                    &self.matches,
+                    "circuit_usage_message_quota",
+                )?)
+                .with_circuit_usage_byte_quota(parse_value(
+                    &self.matches,
+                    "circuit_usage_byte_quota",
+                )?)
+        }
+
+        #[cfg(feature = "connection-limits")]
+        {
+            partial_config = partial_config
+                .with_max_inbound_connections(parse_value(
+                    &self.matches,
+                    "max_inbound_connections",
+                )?)
+                .with_max_inbound_connections_per_ip(parse_value(
+                    &self.matches,
+                    "max_inbound_connections_per_ip",
+                )?)
+        }
+
+        #[cfg(feature = "database-pool-settings")]
+        {
+            partial_config = partial_config
+                .with_database_pool_size(parse_value(&self.matches, "database_pool_size")?)
+                .with_database_pool_timeout(parse_value(&self.matches, "database_pool_timeout")?)
+                .with_database_pool_min_idle(parse_value(&self.matches, "database_pool_min_idle")?)
+                .with_database_pool_idle_timeout(parse_value(
+                    &self.matches,
+                    "database_pool_idle_timeout",
+                )?)
+        }
+
+        #[cfg(feature = "clock-skew-detection")]
+        {
+            partial_config = partial_config
+                .with_clock_skew_warn_threshold(parse_value(
+                    &self.matches,
+                    "clock_skew_warn_threshold",
+                )?)
+                .with_clock_skew_max(parse_value(&self.matches, "clock_skew_max")?)
+        }
+
+        #[cfg(feature = "admin-webhooks")]
+        {
+            partial_config = partial_config
+                .with_admin_webhooks(
+                    self.matches
+                        .values_of("admin_webhooks")
+                        .map(|values| values.map(String::from).collect()),
+                )
+                .with_admin_webhook_secret(
+                    self.matches
+                        .value_of("admin_webhook_secret")
+                        .map(String::from),
+                )
+        }
+
+        #[cfg(feature = "scabbard-anchoring")]
+        {
+            partial_config = partial_config
+                .with_scabbard_anchor_url(
+                    self.matches
+                        .value_of("scabbard_anchor_url")
+                        .map(String::from),
+                )
+                .with_scabbard_anchor_interval(parse_value(
+                    &self.matches,
+                    "scabbard_anchor_interval",
+                )?)
+                .with_scabbard_anchor_services(
+                    self.matches
+                        .values_of("scabbard_anchor_services")
+                        .map(|values| values.map(String::from).collect()),
+                )
+        }
+
+        #[cfg(feature = "scabbard-batch-validation")]
+        {
+            partial_config = partial_config.with_scabbard_batch_validator(
+                self.matches
+                    .value_of("scabbard_batch_validator")
+                    .map(String::from),
+            );
+        }
+
+        #[cfg(feature = "scabbard-receipt-pruning")]
+        {
+            partial_config = partial_config
+                .with_scabbard_receipt_retention(parse_value(
+                    &self.matches,
+                    "scabbard_receipt_retention",
+                )?)
+                .with_scabbard_receipt_prune_interval(parse_value(
+                    &self.matches,
+                    "scabbard_receipt_prune_interval",
+                )?)
+                .with_scabbard_receipt_archive_dir(
+                    self.matches
+                        .value_of("scabbard_receipt_archive_dir")
+                        .map(String::from),
+                )
+        }
+
+        #[cfg(feature = "scabbard-snapshot")]
+        {
+            partial_config = partial_config
+                .with_scabbard_snapshot_dir(
+                    self.matches
+                        .value_of("scabbard_snapshot_dir")
+                        .map(String::from),
+                )
+                .with_scabbard_snapshot_interval(parse_value(
+                    &self.matches,
+                    "scabbard_snapshot_interval",
+                )?)
+        }
+
+        #[cfg(feature = "scabbard-state-pruning")]
+        {
+            partial_config = partial_config
+                .with_scabbard_state_retention_roots(parse_value(
+                    &self.matches,
+                    "scabbard_state_retention_roots",
+                )?)
+                .with_scabbard_state_retention_days(parse_value(
+                    &self.matches,
+                    "scabbard_state_retention_days",
+                )?)
+                .with_scabbard_state_prune_interval(parse_value(
+                    &self.matches,
+                    "scabbard_state_prune_interval",
+                )?)
+        }
+
+        #[cfg(feature = "service-restart-policy")]
+        {
+            partial_config = partial_config
+                .with_service_max_restarts(parse_value(&self.matches, "service_max_restarts")?)
+                .with_service_restart_max_backoff(parse_value(
+                    &self.matches,
+                    "service_restart_max_backoff",
+                )?)
+        }
+
+        #[cfg(feature = "service-transfer")]
+        {
+            partial_config = partial_config.with_transfer_max_file_size(parse_value(
+                &self.matches,
+                "transfer_max_file_size",
+            )?)
+        }
+
+        #[cfg(feature = "service-type-limits")]
+        {
+            partial_config = partial_config
+                .with_service_max_instances(parse_pairs(
+                    &self.matches,
+                    "service_max_instances",
+                    "<service type>=<count>",
+                )?)
+                .with_service_thread_budget(parse_pairs(
+                    &self.matches,
+                    "service_thread_budget",
+                    "<service type>=<threads>",
+                )?)
+                .with_service_memory_budget(parse_pairs(
+                    &self.matches,
+                    "service_memory_budget",
+                    "<service type>=<bytes>",
+                )?)
+        }
+
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        {
+            partial_config = partial_config.with_admin_member_wait_timeout(parse_value(
+                &self.matches,
+                "admin_member_wait_timeout",
+            )?)
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        {
+            partial_config = partial_config
+                .with_admin_max_circuit_members(parse_value(
+                    &self.matches,
+                    "admin_max_circuit_members",
+                )?)
+                .with_admin_max_circuit_services(parse_value(
+                    &self.matches,
+                    "admin_max_circuit_services",
+                )?)
+                .with_admin_max_circuit_metadata_size(parse_value(
+                    &self.matches,
+                    "admin_max_circuit_metadata_size",
+                )?)
+                .with_admin_max_circuit_size(parse_value(&self.matches, "admin_max_circuit_size")?);
+        }
+
+        #[cfg(feature = "admin-service-circuit-version")]
+        {
+            partial_config = partial_config
+                .with_admin_circuit_version(parse_value(&self.matches, "admin_circuit_version")?);
+        }
+
+        #[cfg(feature = "peer-ref-counts")]
+        {
+            partial_config = partial_config
+                .with_peer_retry_interval(parse_value(&self.matches, "peer_retry_interval")?)
+                .with_peer_max_retry_frequency(parse_value(
+                    &self.matches,
+                    "peer_max_retry_frequency",
+                )?)
+        }
+
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            partial_config = partial_config
+                .with_peer_reconnect_initial_delay(parse_value(
+                    &self.matches,
+                    "peer_reconnect_initial_delay",
+                )?)
+                .with_peer_reconnect_max_delay(parse_value(
+                    &self.matches,
+                    "peer_reconnect_max_delay",
+                )?)
+                .with_peer_reconnect_jitter(parse_value(&self.matches, "peer_reconnect_jitter")?)
+                .with_peer_reconnect_max_attempts(parse_value(
+                    &self.matches,
+                    "peer_reconnect_max_attempts",
+                )?)
+        }
+
+        #[cfg(feature = "peer-connect-priority")]
+        {
+            partial_config = partial_config
+                .with_peer_connect_ramp_rate(parse_value(&self.matches, "peer_connect_ramp_rate")?)
         }
 
         #[cfg(feature = "tap")]
@@ -221,6 +640,88 @@ impl PartialConfigBuilder for ClapPartialConfigBuilder<'_> {
             partial_config = partial_config.with_scabbard_autocleanup(Some(false));
         }
 
+        #[cfg(feature = "registry-auto-populate")]
+        if self.matches.is_present("registry_auto_populate") {
+            partial_config = partial_config.with_registry_auto_populate(Some(true));
+        }
+
+        #[cfg(feature = "registry-signed-entries")]
+        if self.matches.is_present("registry_require_signatures") {
+            partial_config = partial_config.with_registry_require_signatures(Some(true));
+        }
+
+        #[cfg(feature = "rest-api-degraded-mode")]
+        if self.matches.is_present("rest_api_degraded_mode") {
+            partial_config = partial_config.with_rest_api_degraded_mode(Some(true));
+        }
+
+        #[cfg(feature = "rest-api-openapi")]
+        if self.matches.is_present("rest_api_swagger_ui") {
+            partial_config = partial_config.with_rest_api_swagger_ui(Some(true));
+        }
+
+        #[cfg(feature = "grpc")]
+        {
+            partial_config =
+                partial_config.with_grpc_bind(self.matches.value_of("grpc_bind").map(String::from));
+        }
+
+        #[cfg(feature = "signature-verification-pool")]
+        {
+            partial_config = partial_config
+                .with_verification_threads(parse_value(&self.matches, "verification_threads")?);
+        }
+
+        #[cfg(feature = "pkcs11-signer")]
+        {
+            partial_config = partial_config
+                .with_signing_key_uri(self.matches.value_of("signing_key_uri").map(String::from));
+        }
+
+        #[cfg(feature = "vault-key-provider")]
+        {
+            partial_config = partial_config
+                .with_vault_keys_url(self.matches.value_of("vault_keys_url").map(String::from));
+        }
+
+        #[cfg(feature = "otel-tracing")]
+        {
+            partial_config = partial_config
+                .with_otel_endpoint(self.matches.value_of("otel_endpoint").map(String::from));
+        }
+
+        #[cfg(feature = "high-availability")]
+        {
+            if self.matches.is_present("high_availability") {
+                partial_config = partial_config.with_high_availability(Some(true));
+            }
+            partial_config = partial_config.with_high_availability_interval(parse_value(
+                &self.matches,
+                "high_availability_interval",
+            )?);
+        }
+
+        #[cfg(feature = "peer-authorization-policy")]
+        {
+            let parse_authorization = |value: &str| match value {
+                "trust" => PeerAuthorization::Trust,
+                "challenge" => PeerAuthorization::Challenge,
+                // Clap is configured to only accept these two values.
+                _ => unreachable!(),
+            };
+            partial_config = partial_config
+                .with_peering_authorization(
+                    self.matches
+                        .value_of("peering_authorization")
+                        .map(parse_authorization),
+                )
+                .with_minimum_authorization(
+                    self.matches
+                        .value_of("minimum_authorization")
+                        .map(parse_authorization),
+                );
+        }
+
         Ok(partial_config)
     }
 }
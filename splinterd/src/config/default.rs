@@ -19,6 +19,8 @@ use std::collections::HashMap;
 use crate::config::{ConfigError, ConfigSource, PartialConfig, PartialConfigBuilder};
 
 use super::logging::{LogEncoder, RootConfig, UnnamedAppenderConfig, UnnamedLoggerConfig};
+#[cfg(feature = "peer-authorization-policy")]
+use super::PeerAuthorization;
 use super::ScabbardState;
 
 const CONFIG_DIR: &str = "/etc/splinter";
@@ -48,12 +50,21 @@ const REGISTRY_AUTO_REFRESH: u64 = 600; // 600 seconds = 10 minutes
 const REGISTRY_FORCED_REFRESH: u64 = 10; // 10 seconds
 const HEARTBEAT: u64 = 30; // 30 seconds
 const ADMIN_TIMEOUT: u64 = 30; // 30 seconds
+#[cfg(feature = "high-availability")]
+const HIGH_AVAILABILITY_INTERVAL: u64 = 5; // 5 seconds
 
 const PEERING_KEY_NAME: &str = "splinterd";
 
 #[cfg(feature = "config-allow-keys")]
 const ALLOW_KEYS_FILE: &str = "allow_keys";
 
+#[cfg(feature = "biome-credentials")]
+const BIOME_REFRESH_TOKEN_RETENTION: std::time::Duration =
+    std::time::Duration::from_secs(5_184_000); // 60 days
+
+#[cfg(feature = "oauth")]
+const OAUTH_SESSION_RETENTION: std::time::Duration = std::time::Duration::from_secs(2_592_000); // 30 days
+
 #[cfg(feature = "service2")]
 const SERVICE_TIMER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
 
@@ -124,6 +135,10 @@ impl PartialConfigBuilder for DefaultPartialConfigBuilder {
             encoder: LogEncoder::default(),
             kind: super::logging::RawLogTarget::Stdout,
             size: None,
+            #[cfg(feature = "log-rotation")]
+            max_age: None,
+            #[cfg(feature = "log-rotation")]
+            max_files: None,
             filename: None,
             level: None,
         };
@@ -224,6 +239,52 @@ impl PartialConfigBuilder for DefaultPartialConfigBuilder {
                 partial_config.with_lifecycle_executor_interval(Some(LIFECYCLE_EXECUTOR_INTERVAL));
         }
 
+        #[cfg(feature = "biome-credentials")]
+        {
+            partial_config = partial_config
+                .with_biome_refresh_token_retention(Some(BIOME_REFRESH_TOKEN_RETENTION));
+        }
+
+        #[cfg(feature = "oauth")]
+        {
+            partial_config =
+                partial_config.with_oauth_session_retention(Some(OAUTH_SESSION_RETENTION));
+        }
+
+        #[cfg(feature = "registry-auto-populate")]
+        {
+            partial_config = partial_config.with_registry_auto_populate(Some(false));
+        }
+
+        #[cfg(feature = "registry-signed-entries")]
+        {
+            partial_config = partial_config.with_registry_require_signatures(Some(false));
+        }
+
+        #[cfg(feature = "rest-api-degraded-mode")]
+        {
+            partial_config = partial_config.with_rest_api_degraded_mode(Some(false));
+        }
+
+        #[cfg(feature = "rest-api-openapi")]
+        {
+            partial_config = partial_config.with_rest_api_swagger_ui(Some(false));
+        }
+
+        #[cfg(feature = "high-availability")]
+        {
+            partial_config = partial_config
+                .with_high_availability(Some(false))
+                .with_high_availability_interval(Some(HIGH_AVAILABILITY_INTERVAL));
+        }
+
+        #[cfg(feature = "peer-authorization-policy")]
+        {
+            partial_config = partial_config
+                .with_peering_authorization(Some(PeerAuthorization::Challenge))
+                .with_minimum_authorization(Some(PeerAuthorization::Trust));
+        }
+
         Ok(partial_config)
     }
 }
@@ -295,6 +356,41 @@ mod tests {
             config.service_timer_interval(),
             Some(SERVICE_TIMER_INTERVAL)
         );
+        #[cfg(feature = "biome-credentials")]
+        assert_eq!(
+            config.biome_refresh_token_retention(),
+            Some(BIOME_REFRESH_TOKEN_RETENTION)
+        );
+        #[cfg(feature = "oauth")]
+        assert_eq!(
+            config.oauth_session_retention(),
+            Some(OAUTH_SESSION_RETENTION)
+        );
+        #[cfg(feature = "registry-auto-populate")]
+        assert_eq!(config.registry_auto_populate(), Some(false));
+        #[cfg(feature = "registry-signed-entries")]
+        assert_eq!(config.registry_require_signatures(), Some(false));
+        #[cfg(feature = "rest-api-degraded-mode")]
+        assert_eq!(config.rest_api_degraded_mode(), Some(false));
+        #[cfg(feature = "rest-api-openapi")]
+        assert_eq!(config.rest_api_swagger_ui(), Some(false));
+        #[cfg(feature = "high-availability")]
+        assert_eq!(config.high_availability(), Some(false));
+        #[cfg(feature = "high-availability")]
+        assert_eq!(
+            config.high_availability_interval(),
+            Some(HIGH_AVAILABILITY_INTERVAL)
+        );
+        #[cfg(feature = "peer-authorization-policy")]
+        assert_eq!(
+            config.peering_authorization(),
+            Some(PeerAuthorization::Challenge)
+        );
+        #[cfg(feature = "peer-authorization-policy")]
+        assert_eq!(
+            config.minimum_authorization(),
+            Some(PeerAuthorization::Trust)
+        );
         // Assert the source is correctly identified for this `PartialConfig` object.
         assert_eq!(config.source(), ConfigSource::Default);
     }
@@ -0,0 +1,326 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Comparison of two `Config` objects, used to determine what changed when the configuration is
+//! reloaded.
+
+use super::Config;
+
+/// A configuration change that can be applied to a running daemon.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReloadableChange {
+    /// The logging configuration (root logger, appenders, loggers or verbosity) changed.
+    Logging,
+    /// The registry automatic refresh interval changed; the value is in seconds.
+    RegistryAutoRefresh(u64),
+    /// The registry forced refresh interval changed; the value is in seconds.
+    RegistryForcedRefresh(u64),
+    /// The heartbeat interval changed; the value is in seconds.
+    Heartbeat(u64),
+    /// The CORS allow list changed; `None` allows any domain.
+    #[cfg(feature = "rest-api-cors")]
+    AllowList(Option<Vec<String>>),
+}
+
+/// The differences between two `Config` objects.
+#[derive(Debug, Default)]
+pub struct ConfigDiff {
+    reloadable: Vec<ReloadableChange>,
+    requires_restart: Vec<&'static str>,
+}
+
+impl ConfigDiff {
+    /// Returns the changes that can be applied without restarting the daemon
+    pub fn reloadable(&self) -> &[ReloadableChange] {
+        &self.reloadable
+    }
+
+    /// Returns the names of the changed values that only take effect after a restart
+    pub fn requires_restart(&self) -> &[&'static str] {
+        &self.requires_restart
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reloadable.is_empty() && self.requires_restart.is_empty()
+    }
+
+    pub fn into_reloadable(self) -> Vec<ReloadableChange> {
+        self.reloadable
+    }
+
+    fn check_restart<T: PartialEq + ?Sized>(&mut self, name: &'static str, old: &T, new: &T) {
+        if old != new {
+            self.requires_restart.push(name);
+        }
+    }
+}
+
+impl Config {
+    /// Compares this configuration to a newly loaded one, returning the values that changed.
+    ///
+    /// Only the values that the daemon can apply while running are returned as reloadable
+    /// changes; the names of any other values that changed are returned so that the operator may
+    /// be warned that a restart is required.
+    pub fn diff(&self, new: &Config) -> ConfigDiff {
+        let mut diff = ConfigDiff::default();
+
+        if self.root_logger() != new.root_logger()
+            || self.appenders() != new.appenders()
+            || self.loggers() != new.loggers()
+            || self.verbosity() != new.verbosity()
+        {
+            diff.reloadable.push(ReloadableChange::Logging);
+        }
+        if self.registry_auto_refresh() != new.registry_auto_refresh() {
+            diff.reloadable.push(ReloadableChange::RegistryAutoRefresh(
+                new.registry_auto_refresh(),
+            ));
+        }
+        if self.registry_forced_refresh() != new.registry_forced_refresh() {
+            diff.reloadable
+                .push(ReloadableChange::RegistryForcedRefresh(
+                    new.registry_forced_refresh(),
+                ));
+        }
+        if self.heartbeat() != new.heartbeat() {
+            diff.reloadable
+                .push(ReloadableChange::Heartbeat(new.heartbeat()));
+        }
+        #[cfg(feature = "rest-api-cors")]
+        {
+            if self.allow_list() != new.allow_list() {
+                diff.reloadable.push(ReloadableChange::AllowList(
+                    new.allow_list().map(ToOwned::to_owned),
+                ));
+            }
+        }
+
+        diff.check_restart("config_dir", self.config_dir(), new.config_dir());
+        diff.check_restart("tls_cert_dir", self.tls_cert_dir(), new.tls_cert_dir());
+        diff.check_restart("tls_ca_file", self.tls_ca_file(), new.tls_ca_file());
+        diff.check_restart(
+            "tls_client_cert",
+            self.tls_client_cert(),
+            new.tls_client_cert(),
+        );
+        diff.check_restart(
+            "tls_client_key",
+            self.tls_client_key(),
+            new.tls_client_key(),
+        );
+        diff.check_restart(
+            "tls_server_cert",
+            self.tls_server_cert(),
+            new.tls_server_cert(),
+        );
+        diff.check_restart(
+            "tls_server_key",
+            self.tls_server_key(),
+            new.tls_server_key(),
+        );
+        diff.check_restart(
+            "network_endpoints",
+            self.network_endpoints(),
+            new.network_endpoints(),
+        );
+        diff.check_restart(
+            "advertised_endpoints",
+            self.advertised_endpoints(),
+            new.advertised_endpoints(),
+        );
+        diff.check_restart("peers", self.peers(), new.peers());
+        diff.check_restart("node_id", &self.node_id(), &new.node_id());
+        diff.check_restart("display_name", &self.display_name(), &new.display_name());
+        diff.check_restart(
+            "rest_api_endpoint",
+            self.rest_api_endpoint(),
+            new.rest_api_endpoint(),
+        );
+        diff.check_restart("database", self.database(), new.database());
+        #[cfg(feature = "database-pool-settings")]
+        {
+            diff.check_restart(
+                "database_pool_size",
+                &self.database_pool_size(),
+                &new.database_pool_size(),
+            );
+            diff.check_restart(
+                "database_pool_timeout",
+                &self.database_pool_timeout(),
+                &new.database_pool_timeout(),
+            );
+            diff.check_restart(
+                "database_pool_min_idle",
+                &self.database_pool_min_idle(),
+                &new.database_pool_min_idle(),
+            );
+            diff.check_restart(
+                "database_pool_idle_timeout",
+                &self.database_pool_idle_timeout(),
+                &new.database_pool_idle_timeout(),
+            );
+        }
+        diff.check_restart("registries", self.registries(), new.registries());
+        diff.check_restart("admin_timeout", &self.admin_timeout(), &new.admin_timeout());
+        diff.check_restart("state_dir", self.state_dir(), new.state_dir());
+        diff.check_restart("tls_insecure", &self.tls_insecure(), &new.tls_insecure());
+        diff.check_restart("no_tls", &self.no_tls(), &new.no_tls());
+        diff.check_restart(
+            "strict_ref_counts",
+            &self.strict_ref_counts(),
+            &new.strict_ref_counts(),
+        );
+        diff.check_restart("peering_key", self.peering_key(), new.peering_key());
+        #[cfg(feature = "pkcs11-signer")]
+        diff.check_restart(
+            "signing_key_uri",
+            &self.signing_key_uri(),
+            &new.signing_key_uri(),
+        );
+        #[cfg(feature = "vault-key-provider")]
+        diff.check_restart(
+            "vault_keys_url",
+            &self.vault_keys_url(),
+            &new.vault_keys_url(),
+        );
+        #[cfg(feature = "otel-tracing")]
+        diff.check_restart("otel_endpoint", &self.otel_endpoint(), &new.otel_endpoint());
+        #[cfg(feature = "peer-ref-counts")]
+        {
+            diff.check_restart(
+                "peer_retry_interval",
+                &self.peer_retry_interval(),
+                &new.peer_retry_interval(),
+            );
+            diff.check_restart(
+                "peer_max_retry_frequency",
+                &self.peer_max_retry_frequency(),
+                &new.peer_max_retry_frequency(),
+            );
+        }
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            diff.check_restart(
+                "peer_reconnect_initial_delay",
+                &self.peer_reconnect_initial_delay(),
+                &new.peer_reconnect_initial_delay(),
+            );
+            diff.check_restart(
+                "peer_reconnect_max_delay",
+                &self.peer_reconnect_max_delay(),
+                &new.peer_reconnect_max_delay(),
+            );
+            diff.check_restart(
+                "peer_reconnect_jitter",
+                &self.peer_reconnect_jitter(),
+                &new.peer_reconnect_jitter(),
+            );
+            diff.check_restart(
+                "peer_reconnect_max_attempts",
+                &self.peer_reconnect_max_attempts(),
+                &new.peer_reconnect_max_attempts(),
+            );
+        }
+        #[cfg(feature = "peer-connect-priority")]
+        {
+            diff.check_restart(
+                "peer_connect_ramp_rate",
+                &self.peer_connect_ramp_rate(),
+                &new.peer_connect_ramp_rate(),
+            );
+        }
+        #[cfg(feature = "high-availability")]
+        {
+            diff.check_restart(
+                "high_availability",
+                &self.high_availability(),
+                &new.high_availability(),
+            );
+            diff.check_restart(
+                "high_availability_interval",
+                &self.high_availability_interval(),
+                &new.high_availability_interval(),
+            );
+        }
+        #[cfg(feature = "tls-peer-ca")]
+        diff.check_restart(
+            "tls_peer_ca_files",
+            &self.tls_peer_ca_files(),
+            &new.tls_peer_ca_files(),
+        );
+
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::{
+        ConfigBuilder, DefaultPartialConfigBuilder, PartialConfigBuilder, TomlPartialConfigBuilder,
+    };
+
+    fn build_config(toml_string: &str) -> Config {
+        let toml = TomlPartialConfigBuilder::new(toml_string.to_string(), "test.toml".to_string())
+            .expect("Could not deserialize toml")
+            .build()
+            .expect("Could not build toml partial config");
+        let default = DefaultPartialConfigBuilder::new()
+            .build()
+            .expect("Could not build default partial config");
+        ConfigBuilder::new()
+            .with_partial_config(toml)
+            .with_partial_config(default)
+            .build()
+            .expect("Could not build final Config")
+    }
+
+    /// Verify that identical configurations have no differences, that reloadable values are
+    /// returned as reloadable changes with their new values, and that other values are reported
+    /// as requiring a restart.
+    #[test]
+    fn test_config_diff() {
+        let old = build_config(
+            r#"
+            version = "1"
+            heartbeat = 30
+            registry_auto_refresh = 600
+            "#,
+        );
+
+        assert!(old.diff(&old).is_empty());
+
+        let new = build_config(
+            r#"
+            version = "1"
+            heartbeat = 10
+            registry_auto_refresh = 600
+            registry_forced_refresh = 5
+            node_id = "other-node"
+            display_name = "Other Node"
+            "#,
+        );
+
+        let diff = old.diff(&new);
+        assert_eq!(
+            diff.reloadable(),
+            &[
+                ReloadableChange::RegistryForcedRefresh(5),
+                ReloadableChange::Heartbeat(10),
+            ]
+        );
+        assert_eq!(diff.requires_restart(), &["node_id", "display_name"]);
+    }
+}
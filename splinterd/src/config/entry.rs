@@ -0,0 +1,126 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A single configuration value, as logged at startup and reported by `GET /config`.
+
+#[cfg(feature = "config-report")]
+use splinter_rest_api_actix_web_1::status::ConfigReport;
+
+use super::ConfigSource;
+
+/// The text that replaces the value of a secret
+const REDACTED: &str = "<redacted>";
+
+/// A configuration value, formatted as text, and the source it was taken from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigEntry {
+    name: &'static str,
+    value: String,
+    source: ConfigSource,
+}
+
+impl ConfigEntry {
+    pub(super) fn new(name: &'static str, value: String, source: &ConfigSource) -> Self {
+        Self {
+            name,
+            value,
+            source: source.clone(),
+        }
+    }
+
+    /// Creates an entry for a secret, whose value is redacted.
+    pub(super) fn redacted(name: &'static str, source: &ConfigSource) -> Self {
+        Self::new(name, REDACTED.to_string(), source)
+    }
+
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn source(&self) -> &ConfigSource {
+        &self.source
+    }
+
+    /// Adds the entry to a configuration report, with its source as `toml`, `env`, `cli`,
+    /// `default` or `api`.
+    #[cfg(feature = "config-report")]
+    pub(super) fn add_to_report(&self, report: ConfigReport) -> ConfigReport {
+        let (source, file) = match &self.source {
+            ConfigSource::Toml { file } => ("toml", Some(file.as_str())),
+            ConfigSource::Environment => ("env", None),
+            ConfigSource::CommandLine => ("cli", None),
+            ConfigSource::Default => ("default", None),
+            ConfigSource::Api => ("api", None),
+        };
+        report.with_value(self.name, &self.value, source, file)
+    }
+}
+
+/// Replaces the password of a URL, such as a database connection URL, with `<redacted>`.
+pub(super) fn redact_url_password(url: &str) -> String {
+    let credentials_start = match url.find("://") {
+        Some(index) => index + 3,
+        None => return url.to_string(),
+    };
+    let authority_end = url[credentials_start..]
+        .find('/')
+        .map(|index| credentials_start + index)
+        .unwrap_or_else(|| url.len());
+
+    match url[credentials_start..authority_end].rfind('@') {
+        Some(at) => {
+            let credentials = &url[credentials_start..credentials_start + at];
+            match credentials.find(':') {
+                Some(colon) => format!(
+                    "{}{}{}",
+                    &url[..credentials_start + colon + 1],
+                    REDACTED,
+                    &url[credentials_start + at..]
+                ),
+                None => url.to_string(),
+            }
+        }
+        None => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that only the password of a URL is redacted.
+    #[test]
+    fn test_redact_url_password() {
+        assert_eq!(
+            redact_url_password("postgres://admin:hunter2@db:5432/splinter"),
+            "postgres://admin:<redacted>@db:5432/splinter"
+        );
+        assert_eq!(
+            redact_url_password("postgres://admin@db:5432/splinter"),
+            "postgres://admin@db:5432/splinter"
+        );
+        assert_eq!(
+            redact_url_password("sqlite:///var/lib/splinter/splinter_state.db"),
+            "sqlite:///var/lib/splinter/splinter_state.db"
+        );
+        assert_eq!(
+            redact_url_password("/var/lib/splinter/splinter_state.db"),
+            "/var/lib/splinter/splinter_state.db"
+        );
+    }
+}
@@ -43,6 +43,8 @@ const METRICS_URL_ENV: &str = "SPLINTER_INFLUX_URL";
 const METRICS_USERNAME_ENV: &str = "SPLINTER_INFLUX_USERNAME";
 #[cfg(feature = "tap")]
 const METRICS_PASSWORD_ENV: &str = "SPLINTER_INFLUX_PASSWORD";
+#[cfg(feature = "otel-tracing")]
+const OTEL_ENDPOINT_ENV: &str = "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT";
 
 /// Trait that outlines a basic read-only environment variable store
 pub trait EnvStore {
@@ -171,6 +173,11 @@ impl<K: EnvStore> PartialConfigBuilder for EnvPartialConfigBuilder<K> {
                 .with_influx_password(self.store.get(METRICS_PASSWORD_ENV))
         }
 
+        #[cfg(feature = "otel-tracing")]
+        {
+            config = config.with_otel_endpoint(self.store.get(OTEL_ENDPOINT_ENV));
+        }
+
         Ok(config)
     }
 }
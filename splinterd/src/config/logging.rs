@@ -16,6 +16,8 @@ use std::convert::From;
 use std::convert::TryFrom;
 use std::convert::TryInto;
 use std::ops::Deref;
+#[cfg(feature = "log-rotation")]
+use std::time::Duration;
 
 use log::Level;
 
@@ -32,7 +34,7 @@ pub struct LogConfig {
     pub loggers: Vec<LoggerConfig>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LoggerConfig {
     pub name: String,
     pub appenders: Option<Vec<String>>,
@@ -45,13 +47,13 @@ pub struct UnnamedLoggerConfig {
     pub level: Option<Level>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct RootConfig {
     pub appenders: Vec<String>,
     pub level: Level,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct AppenderConfig {
     pub name: String,
     pub encoder: LogEncoder,
@@ -65,15 +67,38 @@ pub struct UnnamedAppenderConfig {
     pub kind: RawLogTarget,
     pub filename: Option<String>,
     pub size: Option<u64>,
+    /// How long a rolling file is written to before it is rolled
+    #[cfg(feature = "log-rotation")]
+    pub max_age: Option<Duration>,
+    /// How many rolled files are kept
+    #[cfg(feature = "log-rotation")]
+    pub max_files: Option<u32>,
     pub level: Option<Level>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum LogTarget {
     Stdout,
     Stderr,
     File(String),
-    RollingFile { filename: String, size: u64 },
+    RollingFile {
+        filename: String,
+        size: u64,
+        /// Rolls the file once it has been written to for this long, in addition to when it
+        /// reaches its size
+        #[cfg(feature = "log-rotation")]
+        max_age: Option<Duration>,
+        /// Keeps this many rolled files, named `<filename>.1` (the newest) to
+        /// `<filename>.<max_files>`; rolled files are deleted if not set
+        #[cfg(feature = "log-rotation")]
+        max_files: Option<u32>,
+    },
+    /// The local syslog daemon, with the daemon facility
+    #[cfg(feature = "log-system-logger")]
+    Syslog,
+    /// The systemd journal
+    #[cfg(feature = "log-system-logger")]
+    Journald,
 }
 
 #[derive(Clone, Debug)]
@@ -82,9 +107,13 @@ pub enum RawLogTarget {
     Stderr,
     File,
     RollingFile,
+    #[cfg(feature = "log-system-logger")]
+    Syslog,
+    #[cfg(feature = "log-system-logger")]
+    Journald,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct LogEncoder {
     value: String,
 }
@@ -114,10 +143,7 @@ impl AppenderConfig {
     pub fn get_filename(&self) -> Option<&str> {
         match &self.kind {
             LogTarget::File(file) => Some(file),
-            LogTarget::RollingFile {
-                filename: file,
-                size: _,
-            } => Some(file),
+            LogTarget::RollingFile { filename: file, .. } => Some(file),
             _ => None,
         }
     }
@@ -130,6 +156,10 @@ impl From<TomlRawLogTarget> for RawLogTarget {
             TomlRawLogTarget::Stdout => RawLogTarget::Stdout,
             TomlRawLogTarget::Stderr => RawLogTarget::Stderr,
             TomlRawLogTarget::RollingFile => RawLogTarget::RollingFile,
+            #[cfg(feature = "log-system-logger")]
+            TomlRawLogTarget::Syslog => RawLogTarget::Syslog,
+            #[cfg(feature = "log-system-logger")]
+            TomlRawLogTarget::Journald => RawLogTarget::Journald,
         }
     }
 }
@@ -152,11 +182,19 @@ impl TryFrom<(String, UnnamedAppenderConfig)> for AppenderConfig {
                     Ok(LogTarget::RollingFile {
                         filename,
                         size: value.1.size.unwrap_or(DEFAULT_LOG_SIZE),
+                        #[cfg(feature = "log-rotation")]
+                        max_age: value.1.max_age,
+                        #[cfg(feature = "log-rotation")]
+                        max_files: value.1.max_files,
                     })
                 } else {
                     Err(ConfigError::MissingValue("filename".to_string()))
                 }
             }
+            #[cfg(feature = "log-system-logger")]
+            RawLogTarget::Syslog => Ok(LogTarget::Syslog),
+            #[cfg(feature = "log-system-logger")]
+            RawLogTarget::Journald => Ok(LogTarget::Journald),
         }?;
         Ok(AppenderConfig {
             name: value.0,
@@ -184,6 +222,10 @@ impl From<TomlUnnamedAppenderConfig> for UnnamedAppenderConfig {
             kind: unnamed.kind.into(),
             filename: unnamed.filename,
             size: unnamed.size.map(|s| s.into()),
+            #[cfg(feature = "log-rotation")]
+            max_age: unnamed.max_age.map(|a| a.into()),
+            #[cfg(feature = "log-rotation")]
+            max_files: unnamed.max_files,
             level: unnamed.level.map(|l| l.into()),
         }
     }
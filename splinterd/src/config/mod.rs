@@ -17,10 +17,35 @@
 //! These values may be sourced from a toml file, command line arguments, environment variables
 //! or pre-defined defaults. This module allows for configuration values from each of these
 //! sources to be combined into a final `Config` object.
+//!
+//! Applications that embed the daemon may also set values directly, with a `PartialConfig` whose
+//! source is `ConfigSource::Api`, and combine it with the defaults:
+//!
+//! ```no_run
+//! use splinterd::config::{
+//!     ConfigBuilder, ConfigSource, DefaultPartialConfigBuilder, PartialConfig,
+//!     PartialConfigBuilder,
+//! };
+//!
+//! # fn main() -> Result<(), splinterd::config::ConfigError> {
+//! let config = ConfigBuilder::new()
+//!     .with_partial_config(
+//!         PartialConfig::new(ConfigSource::Api)
+//!             .with_node_id(Some("embedded-node".into()))
+//!             .with_state_dir(Some("/var/lib/my-app/splinter".into())),
+//!     )
+//!     .with_partial_config(DefaultPartialConfigBuilder::new().build()?)
+//!     .build()?;
+//! # Ok(())
+//! # }
+//! ```
 
 mod builder;
 mod clap;
 mod default;
+#[cfg(feature = "config-reload")]
+mod diff;
+mod entry;
 mod env;
 mod error;
 mod logging;
@@ -29,8 +54,15 @@ mod toml;
 
 use std::time::Duration;
 
+#[cfg(feature = "config-report")]
+use splinter_rest_api_actix_web_1::status::ConfigReport;
+
 pub use crate::config::clap::ClapPartialConfigBuilder;
 pub use crate::config::default::DefaultPartialConfigBuilder;
+#[cfg(feature = "config-reload")]
+pub use crate::config::diff::{ConfigDiff, ReloadableChange};
+use crate::config::entry::redact_url_password;
+pub use crate::config::entry::ConfigEntry;
 pub use crate::config::env::EnvPartialConfigBuilder;
 pub use crate::config::toml::TomlPartialConfigBuilder;
 pub use builder::{ConfigBuilder, PartialConfigBuilder};
@@ -56,6 +88,8 @@ pub struct Config {
     tls_rest_api_cert: (String, ConfigSource),
     #[cfg(feature = "https-bind")]
     tls_rest_api_key: (String, ConfigSource),
+    #[cfg(feature = "tls-peer-ca")]
+    tls_peer_ca_files: Option<(Vec<(String, String)>, ConfigSource)>,
     #[cfg(feature = "service-endpoint")]
     service_endpoint: (String, ConfigSource),
     network_endpoints: (Vec<String>, ConfigSource),
@@ -91,7 +125,123 @@ pub struct Config {
     oauth_openid_auth_params: Option<(Vec<(String, String)>, ConfigSource)>,
     #[cfg(feature = "oauth")]
     oauth_openid_scopes: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "biome-notifications")]
+    notification_smtp_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "biome-notifications")]
+    notification_email_from: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_dn: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_password: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_base: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_filter: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_base: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_filter: Option<(String, ConfigSource)>,
+    #[cfg(feature = "ldap")]
+    ldap_group_roles: Option<(Vec<(String, String)>, ConfigSource)>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_auth_header: Option<(String, ConfigSource)>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_trusted_addresses: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_identities: Option<(Vec<(String, String)>, ConfigSource)>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_roles: Option<(Vec<(String, String)>, ConfigSource)>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    tls_rest_api_client_ca: Option<(String, ConfigSource)>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_message_quota: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_byte_quota: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections_per_ip: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_size: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_timeout: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_min_idle: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_idle_timeout: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_warn_threshold: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_max: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhooks: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhook_secret: Option<(String, ConfigSource)>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_interval: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_services: Option<(Vec<String>, ConfigSource)>,
+    #[cfg(feature = "scabbard-batch-validation")]
+    scabbard_batch_validator: Option<(String, ConfigSource)>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_retention: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_prune_interval: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_archive_dir: Option<(String, ConfigSource)>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_dir: Option<(String, ConfigSource)>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_interval: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_roots: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_days: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_prune_interval: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "service-restart-policy")]
+    service_max_restarts: Option<(u32, ConfigSource)>,
+    #[cfg(feature = "service-restart-policy")]
+    service_restart_max_backoff: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "service-transfer")]
+    transfer_max_file_size: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "service-type-limits")]
+    service_max_instances: Option<(Vec<(String, String)>, ConfigSource)>,
+    #[cfg(feature = "service-type-limits")]
+    service_thread_budget: Option<(Vec<(String, String)>, ConfigSource)>,
+    #[cfg(feature = "service-type-limits")]
+    service_memory_budget: Option<(Vec<(String, String)>, ConfigSource)>,
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    admin_member_wait_timeout: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_members: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_services: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_metadata_size: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_size: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "admin-service-circuit-version")]
+    admin_circuit_version: Option<(u64, ConfigSource)>,
     strict_ref_counts: (bool, ConfigSource),
+    #[cfg(feature = "peer-ref-counts")]
+    peer_retry_interval: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_max_retry_frequency: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_initial_delay: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_delay: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_jitter: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_attempts: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "peer-connect-priority")]
+    peer_connect_ramp_rate: Option<(u64, ConfigSource)>,
     #[cfg(feature = "tap")]
     influx_db: Option<(String, ConfigSource)>,
     #[cfg(feature = "tap")]
@@ -113,6 +263,36 @@ pub struct Config {
     service_timer_interval: (Duration, ConfigSource),
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: (Duration, ConfigSource),
+    #[cfg(feature = "biome-credentials")]
+    biome_refresh_token_retention: (Duration, ConfigSource),
+    #[cfg(feature = "oauth")]
+    oauth_session_retention: (Duration, ConfigSource),
+    #[cfg(feature = "registry-auto-populate")]
+    registry_auto_populate: (bool, ConfigSource),
+    #[cfg(feature = "registry-signed-entries")]
+    registry_require_signatures: (bool, ConfigSource),
+    #[cfg(feature = "rest-api-degraded-mode")]
+    rest_api_degraded_mode: (bool, ConfigSource),
+    #[cfg(feature = "rest-api-openapi")]
+    rest_api_swagger_ui: (bool, ConfigSource),
+    #[cfg(feature = "grpc")]
+    grpc_bind: Option<(String, ConfigSource)>,
+    #[cfg(feature = "signature-verification-pool")]
+    verification_threads: Option<(u64, ConfigSource)>,
+    #[cfg(feature = "pkcs11-signer")]
+    signing_key_uri: Option<(String, ConfigSource)>,
+    #[cfg(feature = "vault-key-provider")]
+    vault_keys_url: Option<(String, ConfigSource)>,
+    #[cfg(feature = "otel-tracing")]
+    otel_endpoint: Option<(String, ConfigSource)>,
+    #[cfg(feature = "high-availability")]
+    high_availability: (bool, ConfigSource),
+    #[cfg(feature = "high-availability")]
+    high_availability_interval: (u64, ConfigSource),
+    #[cfg(feature = "peer-authorization-policy")]
+    peering_authorization: (PeerAuthorization, ConfigSource),
+    #[cfg(feature = "peer-authorization-policy")]
+    minimum_authorization: (PeerAuthorization, ConfigSource),
 }
 
 impl Config {
@@ -154,6 +334,17 @@ impl Config {
         &self.tls_rest_api_key.0
     }
 
+    /// Returns the CA certificate files used to verify the peers whose endpoints match a pattern,
+    /// as `(pattern, file)` pairs
+    #[cfg(feature = "tls-peer-ca")]
+    pub fn tls_peer_ca_files(&self) -> Option<&[(String, String)]> {
+        if let Some((peer_ca_files, _)) = &self.tls_peer_ca_files {
+            Some(peer_ca_files)
+        } else {
+            None
+        }
+    }
+
     #[cfg(feature = "service-endpoint")]
     pub fn service_endpoint(&self) -> &str {
         &self.service_endpoint.0
@@ -304,10 +495,402 @@ impl Config {
         }
     }
 
+    #[cfg(feature = "biome-notifications")]
+    pub fn notification_smtp_url(&self) -> Option<&str> {
+        if let Some((smtp_url, _)) = &self.notification_smtp_url {
+            Some(smtp_url)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "biome-notifications")]
+    pub fn notification_email_from(&self) -> Option<&str> {
+        if let Some((email_from, _)) = &self.notification_email_from {
+            Some(email_from)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_url(&self) -> Option<&str> {
+        self.ldap_url.as_ref().map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_bind_dn(&self) -> Option<&str> {
+        self.ldap_bind_dn.as_ref().map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_bind_password(&self) -> Option<&str> {
+        self.ldap_bind_password
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_user_search_base(&self) -> Option<&str> {
+        self.ldap_user_search_base
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_user_search_filter(&self) -> Option<&str> {
+        self.ldap_user_search_filter
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_group_search_base(&self) -> Option<&str> {
+        self.ldap_group_search_base
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_group_search_filter(&self) -> Option<&str> {
+        self.ldap_group_search_filter
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_group_roles(&self) -> Option<&[(String, String)]> {
+        self.ldap_group_roles
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub fn rest_api_proxy_auth_header(&self) -> Option<&str> {
+        self.rest_api_proxy_auth_header
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub fn rest_api_proxy_trusted_addresses(&self) -> Option<&[String]> {
+        self.rest_api_proxy_trusted_addresses
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn rest_api_client_cert_identities(&self) -> Option<&[(String, String)]> {
+        self.rest_api_client_cert_identities
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn rest_api_client_cert_roles(&self) -> Option<&[(String, String)]> {
+        self.rest_api_client_cert_roles
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn tls_rest_api_client_ca(&self) -> Option<&str> {
+        self.tls_rest_api_client_ca
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    pub fn circuit_usage_message_quota(&self) -> Option<u64> {
+        self.circuit_usage_message_quota
+            .as_ref()
+            .map(|(quota, _)| *quota)
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    pub fn circuit_usage_byte_quota(&self) -> Option<u64> {
+        self.circuit_usage_byte_quota
+            .as_ref()
+            .map(|(quota, _)| *quota)
+    }
+
+    #[cfg(feature = "connection-limits")]
+    pub fn max_inbound_connections(&self) -> Option<u64> {
+        self.max_inbound_connections.as_ref().map(|(max, _)| *max)
+    }
+
+    #[cfg(feature = "connection-limits")]
+    pub fn max_inbound_connections_per_ip(&self) -> Option<u64> {
+        self.max_inbound_connections_per_ip
+            .as_ref()
+            .map(|(max, _)| *max)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_size(&self) -> Option<u64> {
+        self.database_pool_size.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_timeout(&self) -> Option<u64> {
+        self.database_pool_timeout.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_min_idle(&self) -> Option<u64> {
+        self.database_pool_min_idle
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_idle_timeout(&self) -> Option<u64> {
+        self.database_pool_idle_timeout
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn clock_skew_warn_threshold(&self) -> Option<u64> {
+        self.clock_skew_warn_threshold
+            .as_ref()
+            .map(|(threshold, _)| *threshold)
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn clock_skew_max(&self) -> Option<u64> {
+        self.clock_skew_max.as_ref().map(|(max, _)| *max)
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    pub fn admin_webhooks(&self) -> Option<&[String]> {
+        self.admin_webhooks
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    pub fn admin_webhook_secret(&self) -> Option<&str> {
+        self.admin_webhook_secret
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn scabbard_anchor_url(&self) -> Option<&str> {
+        self.scabbard_anchor_url
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn scabbard_anchor_interval(&self) -> Option<u64> {
+        self.scabbard_anchor_interval
+            .as_ref()
+            .map(|(interval, _)| *interval)
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn scabbard_anchor_services(&self) -> Option<&[String]> {
+        self.scabbard_anchor_services
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "scabbard-batch-validation")]
+    pub fn scabbard_batch_validator(&self) -> Option<&str> {
+        self.scabbard_batch_validator
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn scabbard_receipt_retention(&self) -> Option<u64> {
+        self.scabbard_receipt_retention
+            .as_ref()
+            .map(|(retention, _)| *retention)
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn scabbard_receipt_prune_interval(&self) -> Option<u64> {
+        self.scabbard_receipt_prune_interval
+            .as_ref()
+            .map(|(interval, _)| *interval)
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn scabbard_receipt_archive_dir(&self) -> Option<&str> {
+        self.scabbard_receipt_archive_dir
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    pub fn scabbard_snapshot_dir(&self) -> Option<&str> {
+        self.scabbard_snapshot_dir
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    pub fn scabbard_snapshot_interval(&self) -> Option<u64> {
+        self.scabbard_snapshot_interval
+            .as_ref()
+            .map(|(interval, _)| *interval)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_state_retention_roots(&self) -> Option<u64> {
+        self.scabbard_state_retention_roots
+            .as_ref()
+            .map(|(roots, _)| *roots)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_state_retention_days(&self) -> Option<u64> {
+        self.scabbard_state_retention_days
+            .as_ref()
+            .map(|(days, _)| *days)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_state_prune_interval(&self) -> Option<u64> {
+        self.scabbard_state_prune_interval
+            .as_ref()
+            .map(|(interval, _)| *interval)
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    pub fn service_max_restarts(&self) -> Option<u32> {
+        self.service_max_restarts
+            .as_ref()
+            .map(|(max_restarts, _)| *max_restarts)
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    pub fn service_restart_max_backoff(&self) -> Option<u64> {
+        self.service_restart_max_backoff
+            .as_ref()
+            .map(|(backoff, _)| *backoff)
+    }
+
+    #[cfg(feature = "service-transfer")]
+    pub fn transfer_max_file_size(&self) -> Option<u64> {
+        self.transfer_max_file_size.as_ref().map(|(size, _)| *size)
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn service_max_instances(&self) -> Option<&[(String, String)]> {
+        self.service_max_instances
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn service_thread_budget(&self) -> Option<&[(String, String)]> {
+        self.service_thread_budget
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn service_memory_budget(&self) -> Option<&[(String, String)]> {
+        self.service_memory_budget
+            .as_ref()
+            .map(|(value, _)| value.as_slice())
+    }
+
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    pub fn admin_member_wait_timeout(&self) -> Option<u64> {
+        self.admin_member_wait_timeout
+            .as_ref()
+            .map(|(timeout, _)| *timeout)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_members(&self) -> Option<u64> {
+        self.admin_max_circuit_members
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_services(&self) -> Option<u64> {
+        self.admin_max_circuit_services
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_metadata_size(&self) -> Option<u64> {
+        self.admin_max_circuit_metadata_size
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_size(&self) -> Option<u64> {
+        self.admin_max_circuit_size
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "admin-service-circuit-version")]
+    pub fn admin_circuit_version(&self) -> Option<u64> {
+        self.admin_circuit_version.as_ref().map(|(value, _)| *value)
+    }
+
     pub fn strict_ref_counts(&self) -> bool {
         self.strict_ref_counts.0
     }
 
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn peer_retry_interval(&self) -> Option<u64> {
+        self.peer_retry_interval
+            .as_ref()
+            .map(|(interval, _)| *interval)
+    }
+
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn peer_max_retry_frequency(&self) -> Option<u64> {
+        self.peer_max_retry_frequency
+            .as_ref()
+            .map(|(frequency, _)| *frequency)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_initial_delay(&self) -> Option<u64> {
+        self.peer_reconnect_initial_delay
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_max_delay(&self) -> Option<u64> {
+        self.peer_reconnect_max_delay
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_jitter(&self) -> Option<u64> {
+        self.peer_reconnect_jitter.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_max_attempts(&self) -> Option<u64> {
+        self.peer_reconnect_max_attempts
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "peer-connect-priority")]
+    pub fn peer_connect_ramp_rate(&self) -> Option<u64> {
+        self.peer_connect_ramp_rate
+            .as_ref()
+            .map(|(value, _)| *value)
+    }
+
     #[cfg(feature = "tap")]
     pub fn influx_db(&self) -> Option<&str> {
         if let Some((db, _)) = &self.influx_db {
@@ -358,6 +941,85 @@ impl Config {
         self.lifecycle_executor_interval.0
     }
 
+    #[cfg(feature = "biome-credentials")]
+    pub fn biome_refresh_token_retention(&self) -> Duration {
+        self.biome_refresh_token_retention.0
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_session_retention(&self) -> Duration {
+        self.oauth_session_retention.0
+    }
+
+    #[cfg(feature = "registry-auto-populate")]
+    pub fn registry_auto_populate(&self) -> bool {
+        self.registry_auto_populate.0
+    }
+
+    #[cfg(feature = "registry-signed-entries")]
+    pub fn registry_require_signatures(&self) -> bool {
+        self.registry_require_signatures.0
+    }
+
+    #[cfg(feature = "rest-api-degraded-mode")]
+    pub fn rest_api_degraded_mode(&self) -> bool {
+        self.rest_api_degraded_mode.0
+    }
+
+    #[cfg(feature = "rest-api-openapi")]
+    pub fn rest_api_swagger_ui(&self) -> bool {
+        self.rest_api_swagger_ui.0
+    }
+
+    #[cfg(feature = "grpc")]
+    pub fn grpc_bind(&self) -> Option<&str> {
+        self.grpc_bind.as_ref().map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "signature-verification-pool")]
+    pub fn verification_threads(&self) -> Option<u64> {
+        self.verification_threads.as_ref().map(|(value, _)| *value)
+    }
+
+    #[cfg(feature = "pkcs11-signer")]
+    pub fn signing_key_uri(&self) -> Option<&str> {
+        self.signing_key_uri
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "vault-key-provider")]
+    pub fn vault_keys_url(&self) -> Option<&str> {
+        self.vault_keys_url
+            .as_ref()
+            .map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "otel-tracing")]
+    pub fn otel_endpoint(&self) -> Option<&str> {
+        self.otel_endpoint.as_ref().map(|(value, _)| value.as_str())
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn high_availability(&self) -> bool {
+        self.high_availability.0
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn high_availability_interval(&self) -> u64 {
+        self.high_availability_interval.0
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn peering_authorization(&self) -> &PeerAuthorization {
+        &self.peering_authorization.0
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn minimum_authorization(&self) -> &PeerAuthorization {
+        &self.minimum_authorization.0
+    }
+
     pub fn config_dir_source(&self) -> &ConfigSource {
         &self.config_dir.1
     }
@@ -396,6 +1058,15 @@ impl Config {
         &self.tls_rest_api_key.1
     }
 
+    #[cfg(feature = "tls-peer-ca")]
+    fn tls_peer_ca_files_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.tls_peer_ca_files {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
     #[cfg(feature = "service-endpoint")]
     fn service_endpoint_source(&self) -> &ConfigSource {
         &self.service_endpoint.1
@@ -546,65 +1217,453 @@ impl Config {
         }
     }
 
-    fn strict_ref_counts_source(&self) -> &ConfigSource {
-        &self.strict_ref_counts.1
-    }
-
-    #[cfg(feature = "tap")]
-    pub fn influx_db_source(&self) -> Option<&ConfigSource> {
-        if let Some((_, source)) = &self.influx_db {
+    #[cfg(feature = "biome-notifications")]
+    fn notification_smtp_url_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.notification_smtp_url {
             Some(source)
         } else {
             None
         }
     }
 
-    #[cfg(feature = "tap")]
-    pub fn influx_url_source(&self) -> Option<&ConfigSource> {
-        if let Some((_, source)) = &self.influx_url {
+    #[cfg(feature = "biome-notifications")]
+    fn notification_email_from_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.notification_email_from {
             Some(source)
         } else {
             None
         }
     }
 
-    #[cfg(feature = "tap")]
-    pub fn influx_username_source(&self) -> Option<&ConfigSource> {
-        if let Some((_, source)) = &self.influx_username {
-            Some(source)
-        } else {
-            None
-        }
+    #[cfg(feature = "ldap")]
+    fn ldap_url_source(&self) -> Option<&ConfigSource> {
+        self.ldap_url.as_ref().map(|(_, source)| source)
     }
 
-    #[cfg(feature = "tap")]
-    pub fn influx_password_source(&self) -> Option<&ConfigSource> {
-        if let Some((_, source)) = &self.influx_password {
-            Some(source)
-        } else {
-            None
-        }
+    #[cfg(feature = "ldap")]
+    fn ldap_bind_dn_source(&self) -> Option<&ConfigSource> {
+        self.ldap_bind_dn.as_ref().map(|(_, source)| source)
     }
 
-    fn peering_key_source(&self) -> &ConfigSource {
-        &self.peering_key.1
+    #[cfg(feature = "ldap")]
+    fn ldap_bind_password_source(&self) -> Option<&ConfigSource> {
+        self.ldap_bind_password.as_ref().map(|(_, source)| source)
     }
 
-    pub fn root_logger(&self) -> &RootConfig {
-        &self.root_logger.0
+    #[cfg(feature = "ldap")]
+    fn ldap_user_search_base_source(&self) -> Option<&ConfigSource> {
+        self.ldap_user_search_base
+            .as_ref()
+            .map(|(_, source)| source)
     }
 
-    pub fn root_logger_source(&self) -> &ConfigSource {
-        &self.root_logger.1
+    #[cfg(feature = "ldap")]
+    fn ldap_user_search_filter_source(&self) -> Option<&ConfigSource> {
+        self.ldap_user_search_filter
+            .as_ref()
+            .map(|(_, source)| source)
     }
 
-    pub fn appenders(&self) -> Option<Vec<AppenderConfig>> {
-        self.appenders
+    #[cfg(feature = "ldap")]
+    fn ldap_group_search_base_source(&self) -> Option<&ConfigSource> {
+        self.ldap_group_search_base
             .as_ref()
-            .map(|configs| configs.iter().map(|c| c.0.clone()).collect())
+            .map(|(_, source)| source)
     }
 
-    pub fn loggers(&self) -> Option<Vec<LoggerConfig>> {
+    #[cfg(feature = "ldap")]
+    fn ldap_group_search_filter_source(&self) -> Option<&ConfigSource> {
+        self.ldap_group_search_filter
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "ldap")]
+    fn ldap_group_roles_source(&self) -> Option<&ConfigSource> {
+        self.ldap_group_roles.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    fn rest_api_proxy_auth_header_source(&self) -> Option<&ConfigSource> {
+        self.rest_api_proxy_auth_header
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    fn rest_api_proxy_trusted_addresses_source(&self) -> Option<&ConfigSource> {
+        self.rest_api_proxy_trusted_addresses
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    fn rest_api_client_cert_identities_source(&self) -> Option<&ConfigSource> {
+        self.rest_api_client_cert_identities
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    fn rest_api_client_cert_roles_source(&self) -> Option<&ConfigSource> {
+        self.rest_api_client_cert_roles
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    fn tls_rest_api_client_ca_source(&self) -> Option<&ConfigSource> {
+        self.tls_rest_api_client_ca
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    fn circuit_usage_message_quota_source(&self) -> Option<&ConfigSource> {
+        self.circuit_usage_message_quota
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    fn circuit_usage_byte_quota_source(&self) -> Option<&ConfigSource> {
+        self.circuit_usage_byte_quota
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "connection-limits")]
+    fn max_inbound_connections_source(&self) -> Option<&ConfigSource> {
+        self.max_inbound_connections
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "connection-limits")]
+    fn max_inbound_connections_per_ip_source(&self) -> Option<&ConfigSource> {
+        self.max_inbound_connections_per_ip
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    fn database_pool_size_source(&self) -> Option<&ConfigSource> {
+        self.database_pool_size.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    fn database_pool_timeout_source(&self) -> Option<&ConfigSource> {
+        self.database_pool_timeout
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    fn database_pool_min_idle_source(&self) -> Option<&ConfigSource> {
+        self.database_pool_min_idle
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    fn database_pool_idle_timeout_source(&self) -> Option<&ConfigSource> {
+        self.database_pool_idle_timeout
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    fn clock_skew_warn_threshold_source(&self) -> Option<&ConfigSource> {
+        self.clock_skew_warn_threshold
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    fn clock_skew_max_source(&self) -> Option<&ConfigSource> {
+        self.clock_skew_max.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    fn admin_webhooks_source(&self) -> Option<&ConfigSource> {
+        self.admin_webhooks.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    fn admin_webhook_secret_source(&self) -> Option<&ConfigSource> {
+        self.admin_webhook_secret.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    fn scabbard_anchor_url_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_anchor_url.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    fn scabbard_anchor_interval_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_anchor_interval
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    fn scabbard_anchor_services_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_anchor_services
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-batch-validation")]
+    fn scabbard_batch_validator_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_batch_validator
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    fn scabbard_receipt_retention_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_receipt_retention
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    fn scabbard_receipt_prune_interval_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_receipt_prune_interval
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    fn scabbard_receipt_archive_dir_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_receipt_archive_dir
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    fn scabbard_snapshot_dir_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_snapshot_dir
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    fn scabbard_snapshot_interval_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_snapshot_interval
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    fn scabbard_state_retention_roots_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_state_retention_roots
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    fn scabbard_state_retention_days_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_state_retention_days
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    fn scabbard_state_prune_interval_source(&self) -> Option<&ConfigSource> {
+        self.scabbard_state_prune_interval
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    fn service_max_restarts_source(&self) -> Option<&ConfigSource> {
+        self.service_max_restarts.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    fn service_restart_max_backoff_source(&self) -> Option<&ConfigSource> {
+        self.service_restart_max_backoff
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "service-transfer")]
+    fn transfer_max_file_size_source(&self) -> Option<&ConfigSource> {
+        self.transfer_max_file_size
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    fn service_max_instances_source(&self) -> Option<&ConfigSource> {
+        self.service_max_instances
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    fn service_thread_budget_source(&self) -> Option<&ConfigSource> {
+        self.service_thread_budget
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    fn service_memory_budget_source(&self) -> Option<&ConfigSource> {
+        self.service_memory_budget
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    fn admin_member_wait_timeout_source(&self) -> Option<&ConfigSource> {
+        self.admin_member_wait_timeout
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    fn admin_max_circuit_members_source(&self) -> Option<&ConfigSource> {
+        self.admin_max_circuit_members
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    fn admin_max_circuit_services_source(&self) -> Option<&ConfigSource> {
+        self.admin_max_circuit_services
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    fn admin_max_circuit_metadata_size_source(&self) -> Option<&ConfigSource> {
+        self.admin_max_circuit_metadata_size
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    fn admin_max_circuit_size_source(&self) -> Option<&ConfigSource> {
+        self.admin_max_circuit_size
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "admin-service-circuit-version")]
+    fn admin_circuit_version_source(&self) -> Option<&ConfigSource> {
+        self.admin_circuit_version
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    fn strict_ref_counts_source(&self) -> &ConfigSource {
+        &self.strict_ref_counts.1
+    }
+
+    #[cfg(feature = "peer-ref-counts")]
+    fn peer_retry_interval_source(&self) -> Option<&ConfigSource> {
+        self.peer_retry_interval.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-ref-counts")]
+    fn peer_max_retry_frequency_source(&self) -> Option<&ConfigSource> {
+        self.peer_max_retry_frequency
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    fn peer_reconnect_initial_delay_source(&self) -> Option<&ConfigSource> {
+        self.peer_reconnect_initial_delay
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    fn peer_reconnect_max_delay_source(&self) -> Option<&ConfigSource> {
+        self.peer_reconnect_max_delay
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    fn peer_reconnect_jitter_source(&self) -> Option<&ConfigSource> {
+        self.peer_reconnect_jitter
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    fn peer_reconnect_max_attempts_source(&self) -> Option<&ConfigSource> {
+        self.peer_reconnect_max_attempts
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "peer-connect-priority")]
+    fn peer_connect_ramp_rate_source(&self) -> Option<&ConfigSource> {
+        self.peer_connect_ramp_rate
+            .as_ref()
+            .map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "tap")]
+    pub fn influx_db_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.influx_db {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "tap")]
+    pub fn influx_url_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.influx_url {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "tap")]
+    pub fn influx_username_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.influx_username {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "tap")]
+    pub fn influx_password_source(&self) -> Option<&ConfigSource> {
+        if let Some((_, source)) = &self.influx_password {
+            Some(source)
+        } else {
+            None
+        }
+    }
+
+    fn peering_key_source(&self) -> &ConfigSource {
+        &self.peering_key.1
+    }
+
+    pub fn root_logger(&self) -> &RootConfig {
+        &self.root_logger.0
+    }
+
+    pub fn root_logger_source(&self) -> &ConfigSource {
+        &self.root_logger.1
+    }
+
+    pub fn appenders(&self) -> Option<Vec<AppenderConfig>> {
+        self.appenders
+            .as_ref()
+            .map(|configs| configs.iter().map(|c| c.0.clone()).collect())
+    }
+
+    pub fn loggers(&self) -> Option<Vec<LoggerConfig>> {
         self.loggers
             .as_ref()
             .map(|configs| configs.iter().map(|c| c.0.clone()).collect())
@@ -654,299 +1713,1131 @@ impl Config {
         &self.lifecycle_executor_interval.1
     }
 
+    #[cfg(feature = "biome-credentials")]
+    pub fn biome_refresh_token_retention_source(&self) -> &ConfigSource {
+        &self.biome_refresh_token_retention.1
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_session_retention_source(&self) -> &ConfigSource {
+        &self.oauth_session_retention.1
+    }
+
+    #[cfg(feature = "registry-auto-populate")]
+    pub fn registry_auto_populate_source(&self) -> &ConfigSource {
+        &self.registry_auto_populate.1
+    }
+
+    #[cfg(feature = "registry-signed-entries")]
+    pub fn registry_require_signatures_source(&self) -> &ConfigSource {
+        &self.registry_require_signatures.1
+    }
+
+    #[cfg(feature = "rest-api-degraded-mode")]
+    pub fn rest_api_degraded_mode_source(&self) -> &ConfigSource {
+        &self.rest_api_degraded_mode.1
+    }
+
+    #[cfg(feature = "rest-api-openapi")]
+    pub fn rest_api_swagger_ui_source(&self) -> &ConfigSource {
+        &self.rest_api_swagger_ui.1
+    }
+
+    #[cfg(feature = "grpc")]
+    fn grpc_bind_source(&self) -> Option<&ConfigSource> {
+        self.grpc_bind.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "signature-verification-pool")]
+    fn verification_threads_source(&self) -> Option<&ConfigSource> {
+        self.verification_threads.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "pkcs11-signer")]
+    fn signing_key_uri_source(&self) -> Option<&ConfigSource> {
+        self.signing_key_uri.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "vault-key-provider")]
+    fn vault_keys_url_source(&self) -> Option<&ConfigSource> {
+        self.vault_keys_url.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "otel-tracing")]
+    fn otel_endpoint_source(&self) -> Option<&ConfigSource> {
+        self.otel_endpoint.as_ref().map(|(_, source)| source)
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn high_availability_source(&self) -> &ConfigSource {
+        &self.high_availability.1
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn high_availability_interval_source(&self) -> &ConfigSource {
+        &self.high_availability_interval.1
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn peering_authorization_source(&self) -> &ConfigSource {
+        &self.peering_authorization.1
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn minimum_authorization_source(&self) -> &ConfigSource {
+        &self.minimum_authorization.1
+    }
+
     #[allow(clippy::cognitive_complexity)]
-    /// Displays the configuration value along with where the value was sourced from.
-    pub fn log_as_debug(&self) {
-        debug!(
-            "Config: config_dir: {} (source: {:?})",
-            self.config_dir(),
-            self.config_dir_source()
-        );
-        debug!(
-            "Config: tls_ca_file: {} (source: {:?})",
-            self.tls_ca_file(),
-            self.tls_ca_file_source()
-        );
-        debug!(
-            "Config: tls_cert_dir: {} (source: {:?})",
-            self.tls_cert_dir(),
-            self.tls_cert_dir_source()
-        );
-        debug!(
-            "Config: tls_client_cert: {} (source: {:?})",
-            self.tls_client_cert(),
-            self.tls_client_cert_source()
-        );
-        debug!(
-            "Config: tls_client_key: {} (source: {:?})",
-            self.tls_client_key(),
-            self.tls_client_key_source()
-        );
-        debug!(
-            "Config: tls_server_cert: {} (source: {:?})",
-            self.tls_server_cert(),
-            self.tls_server_cert_source()
-        );
-        debug!(
-            "Config: tls_server_key: {} (source: {:?})",
-            self.tls_server_key(),
-            self.tls_server_key_source()
-        );
+    /// Returns each configuration value, formatted as text, with its source. The values of
+    /// secrets, such as passwords, are redacted.
+    pub fn entries(&self) -> Vec<ConfigEntry> {
+        let mut entries = vec![
+            ConfigEntry::new(
+                "config_dir",
+                self.config_dir().to_string(),
+                self.config_dir_source(),
+            ),
+            ConfigEntry::new(
+                "tls_ca_file",
+                self.tls_ca_file().to_string(),
+                self.tls_ca_file_source(),
+            ),
+            ConfigEntry::new(
+                "tls_cert_dir",
+                self.tls_cert_dir().to_string(),
+                self.tls_cert_dir_source(),
+            ),
+            ConfigEntry::new(
+                "tls_client_cert",
+                self.tls_client_cert().to_string(),
+                self.tls_client_cert_source(),
+            ),
+            ConfigEntry::new(
+                "tls_client_key",
+                self.tls_client_key().to_string(),
+                self.tls_client_key_source(),
+            ),
+            ConfigEntry::new(
+                "tls_server_cert",
+                self.tls_server_cert().to_string(),
+                self.tls_server_cert_source(),
+            ),
+            ConfigEntry::new(
+                "tls_server_key",
+                self.tls_server_key().to_string(),
+                self.tls_server_key_source(),
+            ),
+        ];
         #[cfg(feature = "https-bind")]
         {
-            debug!(
-                "Config: tls_rest_api_cert: {} (source: {:?})",
-                self.tls_rest_api_cert(),
-                self.tls_rest_api_cert_source()
-            );
-            debug!(
-                "Config: tls_rest_api_key: {} (source: {:?})",
-                self.tls_rest_api_key(),
-                self.tls_rest_api_key_source()
-            );
+            entries.push(ConfigEntry::new(
+                "tls_rest_api_cert",
+                self.tls_rest_api_cert().to_string(),
+                self.tls_rest_api_cert_source(),
+            ));
+            entries.push(ConfigEntry::new(
+                "tls_rest_api_key",
+                self.tls_rest_api_key().to_string(),
+                self.tls_rest_api_key_source(),
+            ));
         }
-        #[cfg(feature = "service-endpoint")]
-        debug!(
-            "Config: service_endpoint: {} (source: {:?})",
-            self.service_endpoint(),
-            self.service_endpoint_source()
-        );
-        debug!(
-            "Config: network_endpoints: {:?} (source: {:?})",
-            self.network_endpoints(),
-            self.network_endpoints_source()
-        );
-        debug!(
-            "Config: advertised_endpoints: {:?} (source: {:?})",
-            self.advertised_endpoints(),
-            self.advertised_endpoints_source()
-        );
-        debug!(
-            "Config: peers: {:?} (source: {:?})",
-            self.peers(),
-            self.peers_source()
-        );
-        debug!(
-            "Config: peering_key: {:?} (source: {:?})",
-            self.peering_key(),
-            self.peering_key_source()
-        );
+        #[cfg(feature = "tls-peer-ca")]
+        {
+            if let (Some(peer_ca_files), Some(source)) =
+                (self.tls_peer_ca_files(), self.tls_peer_ca_files_source())
+            {
+                entries.push(ConfigEntry::new(
+                    "tls_peer_ca_files",
+                    format!("{:?}", peer_ca_files),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "service-endpoint")]
+        entries.push(ConfigEntry::new(
+            "service_endpoint",
+            self.service_endpoint().to_string(),
+            self.service_endpoint_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "network_endpoints",
+            format!("{:?}", self.network_endpoints()),
+            self.network_endpoints_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "advertised_endpoints",
+            format!("{:?}", self.advertised_endpoints()),
+            self.advertised_endpoints_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "peers",
+            format!("{:?}", self.peers()),
+            self.peers_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "peering_key",
+            self.peering_key().to_string(),
+            self.peering_key_source(),
+        ));
         if let (Some(id), Some(source)) = (self.node_id(), self.node_id_source()) {
-            debug!("Config: node_id: {} (source: {:?})", id, source,);
+            entries.push(ConfigEntry::new("node_id", id.to_string(), source));
         }
         if let (Some(name), Some(source)) = (self.display_name(), self.display_name_source()) {
-            debug!("Config: display_name: {} (source: {:?})", name, source,);
+            entries.push(ConfigEntry::new("display_name", name.to_string(), source));
         }
-        debug!(
-            "Config: rest_api_endpoint: {} (source: {:?})",
-            self.rest_api_endpoint(),
-            self.rest_api_endpoint_source()
-        );
-        debug!(
-            "Config: registries: {:?} (source: {:?})",
-            self.registries(),
-            self.registries_source()
-        );
-        debug!(
-            "Config: registry_auto_refresh: {} (source: {:?})",
-            self.registry_auto_refresh(),
-            self.registry_auto_refresh_source()
-        );
-        debug!(
-            "Config: registry_forced_refresh: {} (source: {:?})",
-            self.registry_forced_refresh(),
-            self.registry_forced_refresh_source()
-        );
-        debug!(
-            "Config: state_dir: {} (source: {:?})",
-            self.state_dir(),
-            self.state_dir_source()
-        );
-        debug!(
-            "Config: heartbeat: {} (source: {:?})",
-            self.heartbeat(),
-            self.heartbeat_source()
-        );
-        debug!(
-            "Config: admin_timeout: {:?} (source: {:?})",
-            self.admin_timeout(),
-            self.admin_timeout_source()
-        );
-        debug!(
-            "database: {} (source: {:?})",
-            self.database(),
+        entries.push(ConfigEntry::new(
+            "rest_api_endpoint",
+            self.rest_api_endpoint().to_string(),
+            self.rest_api_endpoint_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "registries",
+            format!("{:?}", self.registries()),
+            self.registries_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "registry_auto_refresh",
+            self.registry_auto_refresh().to_string(),
+            self.registry_auto_refresh_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "registry_forced_refresh",
+            self.registry_forced_refresh().to_string(),
+            self.registry_forced_refresh_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "state_dir",
+            self.state_dir().to_string(),
+            self.state_dir_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "heartbeat",
+            self.heartbeat().to_string(),
+            self.heartbeat_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "admin_timeout",
+            format!("{:?}", self.admin_timeout()),
+            self.admin_timeout_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "database",
+            redact_url_password(self.database()),
             self.database_source(),
-        );
-        debug!(
-            "Config: tls_insecure: {:?} (source: {:?})",
-            self.tls_insecure(),
-            self.tls_insecure_source()
-        );
-        debug!(
-            "Config: no_tls: {:?} (source: {:?})",
-            self.no_tls(),
-            self.no_tls_source()
-        );
+        ));
+        entries.push(ConfigEntry::new(
+            "tls_insecure",
+            format!("{:?}", self.tls_insecure()),
+            self.tls_insecure_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "no_tls",
+            format!("{:?}", self.no_tls()),
+            self.no_tls_source(),
+        ));
         #[cfg(feature = "rest-api-cors")]
-        self.log_allow_list();
+        if let (Some(list), Some(source)) = (self.allow_list(), self.allow_list_source()) {
+            entries.push(ConfigEntry::new(
+                "allow_list",
+                format!("{:?}", list),
+                source,
+            ));
+        }
         #[cfg(feature = "biome-credentials")]
-        debug!(
-            "Config: enable_biome_credentials: {:?} (source: {:?})",
-            self.enable_biome_credentials(),
-            self.enable_biome_credentials_source()
-        );
+        entries.push(ConfigEntry::new(
+            "enable_biome_credentials",
+            format!("{:?}", self.enable_biome_credentials()),
+            self.enable_biome_credentials_source(),
+        ));
         #[cfg(feature = "oauth")]
         {
             if let (Some(provider), Some(source)) =
                 (self.oauth_provider(), self.oauth_provider_source())
             {
-                debug!(
-                    "Config: oauth_provider: {} (source: {:?})",
-                    provider, source,
-                );
+                entries.push(ConfigEntry::new(
+                    "oauth_provider",
+                    provider.to_string(),
+                    source,
+                ));
             }
             if let (Some(client_id), Some(source)) =
                 (self.oauth_client_id(), self.oauth_client_id_source())
             {
-                debug!(
-                    "Config: oauth_client_id: {} (source: {:?})",
-                    client_id, source,
-                );
+                entries.push(ConfigEntry::new(
+                    "oauth_client_id",
+                    client_id.to_string(),
+                    source,
+                ));
             }
             if let (Some(_), Some(source)) = (
                 self.oauth_client_secret(),
                 self.oauth_client_secret_source(),
             ) {
-                debug!(
-                    "Config: oauth_client_secret: <HIDDEN> (source: {:?})",
-                    source,
-                );
+                entries.push(ConfigEntry::redacted("oauth_client_secret", source));
             }
             if let (Some(redirect_url), Some(source)) =
                 (self.oauth_redirect_url(), self.oauth_redirect_url_source())
             {
-                debug!(
-                    "Config: oauth_redirect_url: {} (source: {:?})",
-                    redirect_url, source,
-                );
+                entries.push(ConfigEntry::new(
+                    "oauth_redirect_url",
+                    redirect_url.to_string(),
+                    source,
+                ));
             }
             if let (Some(openid_url), Some(source)) =
                 (self.oauth_openid_url(), self.oauth_openid_url_source())
             {
-                debug!(
-                    "Config: oauth_openid_url: {} (source: {:?})",
-                    openid_url, source,
-                );
+                entries.push(ConfigEntry::new(
+                    "oauth_openid_url",
+                    openid_url.to_string(),
+                    source,
+                ));
             }
             if let (Some(auth_params), Some(source)) = (
                 self.oauth_openid_auth_params(),
                 self.oauth_openid_auth_params_source(),
             ) {
-                debug!(
-                    "Config: oauth_openid_auth_params: {:?} (source: {:?})",
-                    auth_params, source,
-                );
+                entries.push(ConfigEntry::new(
+                    "oauth_openid_auth_params",
+                    format!("{:?}", auth_params),
+                    source,
+                ));
             }
             if let (Some(scopes), Some(source)) = (
                 self.oauth_openid_scopes(),
                 self.oauth_openid_scopes_source(),
             ) {
-                debug!("Config: oauth_scopes: {:?} (source: {:?})", scopes, source,);
+                entries.push(ConfigEntry::new(
+                    "oauth_scopes",
+                    format!("{:?}", scopes),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "biome-notifications")]
+        {
+            // The SMTP URL may contain credentials, so only its source is logged
+            if let Some(source) = self.notification_smtp_url_source() {
+                entries.push(ConfigEntry::redacted("notification_smtp_url", source));
+            }
+            if let (Some(email_from), Some(source)) = (
+                self.notification_email_from(),
+                self.notification_email_from_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "notification_email_from",
+                    email_from.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "ldap")]
+        {
+            if let (Some(value), Some(source)) = (self.ldap_url(), self.ldap_url_source()) {
+                entries.push(ConfigEntry::new("ldap_url", value.to_string(), source));
+            }
+            if let (Some(value), Some(source)) = (self.ldap_bind_dn(), self.ldap_bind_dn_source()) {
+                entries.push(ConfigEntry::new("ldap_bind_dn", value.to_string(), source));
+            }
+            if let Some(source) = self.ldap_bind_password_source() {
+                entries.push(ConfigEntry::redacted("ldap_bind_password", source));
+            }
+            if let (Some(value), Some(source)) = (
+                self.ldap_user_search_base(),
+                self.ldap_user_search_base_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "ldap_user_search_base",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.ldap_user_search_filter(),
+                self.ldap_user_search_filter_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "ldap_user_search_filter",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.ldap_group_search_base(),
+                self.ldap_group_search_base_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "ldap_group_search_base",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.ldap_group_search_filter(),
+                self.ldap_group_search_filter_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "ldap_group_search_filter",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) =
+                (self.ldap_group_roles(), self.ldap_group_roles_source())
+            {
+                entries.push(ConfigEntry::new(
+                    "ldap_group_roles",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "rest-api-proxy-auth")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.rest_api_proxy_auth_header(),
+                self.rest_api_proxy_auth_header_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "rest_api_proxy_auth_header",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.rest_api_proxy_trusted_addresses(),
+                self.rest_api_proxy_trusted_addresses_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "rest_api_proxy_trusted_addresses",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.rest_api_client_cert_identities(),
+                self.rest_api_client_cert_identities_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "rest_api_client_cert_identities",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.rest_api_client_cert_roles(),
+                self.rest_api_client_cert_roles_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "rest_api_client_cert_roles",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.tls_rest_api_client_ca(),
+                self.tls_rest_api_client_ca_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "tls_rest_api_client_ca",
+                    value.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "circuit-usage")]
+        {
+            if let (Some(quota), Some(source)) = (
+                self.circuit_usage_message_quota(),
+                self.circuit_usage_message_quota_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "circuit_usage_message_quota",
+                    quota.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(quota), Some(source)) = (
+                self.circuit_usage_byte_quota(),
+                self.circuit_usage_byte_quota_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "circuit_usage_byte_quota",
+                    quota.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "connection-limits")]
+        {
+            if let (Some(max), Some(source)) = (
+                self.max_inbound_connections(),
+                self.max_inbound_connections_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "max_inbound_connections",
+                    max.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(max), Some(source)) = (
+                self.max_inbound_connections_per_ip(),
+                self.max_inbound_connections_per_ip_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "max_inbound_connections_per_ip",
+                    max.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "database-pool-settings")]
+        {
+            if let (Some(value), Some(source)) =
+                (self.database_pool_size(), self.database_pool_size_source())
+            {
+                entries.push(ConfigEntry::new(
+                    "database_pool_size",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.database_pool_timeout(),
+                self.database_pool_timeout_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "database_pool_timeout",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.database_pool_min_idle(),
+                self.database_pool_min_idle_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "database_pool_min_idle",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.database_pool_idle_timeout(),
+                self.database_pool_idle_timeout_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "database_pool_idle_timeout",
+                    value.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "clock-skew-detection")]
+        {
+            if let (Some(threshold), Some(source)) = (
+                self.clock_skew_warn_threshold(),
+                self.clock_skew_warn_threshold_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "clock_skew_warn_threshold",
+                    threshold.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(max), Some(source)) = (self.clock_skew_max(), self.clock_skew_max_source())
+            {
+                entries.push(ConfigEntry::new("clock_skew_max", max.to_string(), source));
+            }
+        }
+        #[cfg(feature = "admin-webhooks")]
+        {
+            if let (Some(value), Some(source)) =
+                (self.admin_webhooks(), self.admin_webhooks_source())
+            {
+                entries.push(ConfigEntry::new(
+                    "admin_webhooks",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+            // The secret itself is not logged
+            if let Some(source) = self.admin_webhook_secret_source() {
+                entries.push(ConfigEntry::redacted("admin_webhook_secret", source));
+            }
+        }
+        #[cfg(feature = "scabbard-anchoring")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.scabbard_anchor_url(),
+                self.scabbard_anchor_url_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_anchor_url",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(interval), Some(source)) = (
+                self.scabbard_anchor_interval(),
+                self.scabbard_anchor_interval_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_anchor_interval",
+                    interval.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.scabbard_anchor_services(),
+                self.scabbard_anchor_services_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_anchor_services",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "scabbard-batch-validation")]
+        if let (Some(value), Some(source)) = (
+            self.scabbard_batch_validator(),
+            self.scabbard_batch_validator_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "scabbard_batch_validator",
+                value.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "scabbard-receipt-pruning")]
+        {
+            if let (Some(retention), Some(source)) = (
+                self.scabbard_receipt_retention(),
+                self.scabbard_receipt_retention_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_receipt_retention",
+                    retention.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(interval), Some(source)) = (
+                self.scabbard_receipt_prune_interval(),
+                self.scabbard_receipt_prune_interval_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_receipt_prune_interval",
+                    interval.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.scabbard_receipt_archive_dir(),
+                self.scabbard_receipt_archive_dir_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_receipt_archive_dir",
+                    value.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "scabbard-snapshot")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.scabbard_snapshot_dir(),
+                self.scabbard_snapshot_dir_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_snapshot_dir",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(interval), Some(source)) = (
+                self.scabbard_snapshot_interval(),
+                self.scabbard_snapshot_interval_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_snapshot_interval",
+                    interval.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "scabbard-state-pruning")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.scabbard_state_retention_roots(),
+                self.scabbard_state_retention_roots_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_state_retention_roots",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.scabbard_state_retention_days(),
+                self.scabbard_state_retention_days_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_state_retention_days",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.scabbard_state_prune_interval(),
+                self.scabbard_state_prune_interval_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "scabbard_state_prune_interval",
+                    value.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "service-restart-policy")]
+        {
+            if let (Some(max_restarts), Some(source)) = (
+                self.service_max_restarts(),
+                self.service_max_restarts_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "service_max_restarts",
+                    max_restarts.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(backoff), Some(source)) = (
+                self.service_restart_max_backoff(),
+                self.service_restart_max_backoff_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "service_restart_max_backoff",
+                    backoff.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "service-transfer")]
+        {
+            if let (Some(size), Some(source)) = (
+                self.transfer_max_file_size(),
+                self.transfer_max_file_size_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "transfer_max_file_size",
+                    size.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "service-type-limits")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.service_max_instances(),
+                self.service_max_instances_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "service_max_instances",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.service_thread_budget(),
+                self.service_thread_budget_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "service_thread_budget",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.service_memory_budget(),
+                self.service_memory_budget_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "service_memory_budget",
+                    format!("{:?}", value),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        if let (Some(timeout), Some(source)) = (
+            self.admin_member_wait_timeout(),
+            self.admin_member_wait_timeout_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "admin_member_wait_timeout",
+                timeout.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let (Some(value), Some(source)) = (
+            self.admin_max_circuit_members(),
+            self.admin_max_circuit_members_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "admin_max_circuit_members",
+                value.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let (Some(value), Some(source)) = (
+            self.admin_max_circuit_services(),
+            self.admin_max_circuit_services_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "admin_max_circuit_services",
+                value.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let (Some(value), Some(source)) = (
+            self.admin_max_circuit_metadata_size(),
+            self.admin_max_circuit_metadata_size_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "admin_max_circuit_metadata_size",
+                value.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let (Some(value), Some(source)) = (
+            self.admin_max_circuit_size(),
+            self.admin_max_circuit_size_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "admin_max_circuit_size",
+                value.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "admin-service-circuit-version")]
+        if let (Some(value), Some(source)) = (
+            self.admin_circuit_version(),
+            self.admin_circuit_version_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "admin_circuit_version",
+                value.to_string(),
+                source,
+            ));
+        }
+        entries.push(ConfigEntry::new(
+            "strict_ref_counts",
+            format!("{:?}", self.strict_ref_counts()),
+            self.strict_ref_counts_source(),
+        ));
+        #[cfg(feature = "peer-ref-counts")]
+        {
+            if let (Some(interval), Some(source)) = (
+                self.peer_retry_interval(),
+                self.peer_retry_interval_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "peer_retry_interval",
+                    interval.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(frequency), Some(source)) = (
+                self.peer_max_retry_frequency(),
+                self.peer_max_retry_frequency_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "peer_max_retry_frequency",
+                    frequency.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.peer_reconnect_initial_delay(),
+                self.peer_reconnect_initial_delay_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "peer_reconnect_initial_delay",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.peer_reconnect_max_delay(),
+                self.peer_reconnect_max_delay_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "peer_reconnect_max_delay",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.peer_reconnect_jitter(),
+                self.peer_reconnect_jitter_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "peer_reconnect_jitter",
+                    value.to_string(),
+                    source,
+                ));
+            }
+            if let (Some(value), Some(source)) = (
+                self.peer_reconnect_max_attempts(),
+                self.peer_reconnect_max_attempts_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "peer_reconnect_max_attempts",
+                    value.to_string(),
+                    source,
+                ));
+            }
+        }
+        #[cfg(feature = "peer-connect-priority")]
+        {
+            if let (Some(value), Some(source)) = (
+                self.peer_connect_ramp_rate(),
+                self.peer_connect_ramp_rate_source(),
+            ) {
+                entries.push(ConfigEntry::new(
+                    "peer_connect_ramp_rate",
+                    value.to_string(),
+                    source,
+                ));
             }
         }
-        debug!(
-            "Config: strict_ref_counts: {:?} (source: {:?})",
-            self.strict_ref_counts(),
-            self.strict_ref_counts_source()
-        );
         #[cfg(feature = "tap")]
         {
             if let (Some(db), Some(source)) = (self.influx_db(), self.influx_db_source()) {
-                debug!("Config: influx_db: {:?} (source: {:?})", db, source,);
+                entries.push(ConfigEntry::new("influx_db", format!("{:?}", db), source));
             }
 
             if let (Some(url), Some(source)) = (self.influx_url(), self.influx_url_source()) {
-                debug!("Config: influx_url: {:?} (source: {:?})", url, source,);
+                entries.push(ConfigEntry::new("influx_url", format!("{:?}", url), source));
             }
 
             if let (Some(username), Some(source)) =
                 (self.influx_username(), self.influx_username_source())
             {
-                debug!(
-                    "Config: influx_username: {:?} (source: {:?})",
-                    username, source,
-                );
+                entries.push(ConfigEntry::new(
+                    "influx_username",
+                    format!("{:?}", username),
+                    source,
+                ));
             }
 
             if let (Some(_), Some(source)) = (self.influx_password(), self.influx_password_source())
             {
-                debug!("Config: influx_password: <HIDDEN> (source: {:?})", source,);
+                entries.push(ConfigEntry::redacted("influx_password", source));
             }
         }
         if let Some(loggers) = &self.loggers {
             for logger in loggers {
-                debug!("Config: logger: {:?} (source: {:?})", logger.0, logger.1);
+                entries.push(ConfigEntry::new(
+                    "logger",
+                    format!("{:?}", logger.0),
+                    &logger.1,
+                ));
             }
         }
         if let Some(appenders) = &self.appenders {
             for appender in appenders {
-                debug!(
-                    "Config: appender: {:?} (source: {:?})",
-                    appender.0, appender.1
-                );
+                entries.push(ConfigEntry::new(
+                    "appender",
+                    format!("{:?}", appender.0),
+                    &appender.1,
+                ));
             }
         }
-        debug!(
-            "Config: root_logger: {:?} (source: {:?})",
-            self.root_logger(),
-            self.root_logger_source()
-        );
-        debug!(
-            "Config: verbosity: {:?} (source: {:?})",
-            self.verbosity(),
-            self.verbosity_source()
-        );
+        entries.push(ConfigEntry::new(
+            "root_logger",
+            format!("{:?}", self.root_logger()),
+            self.root_logger_source(),
+        ));
+        entries.push(ConfigEntry::new(
+            "verbosity",
+            format!("{:?}", self.verbosity()),
+            self.verbosity_source(),
+        ));
         #[cfg(feature = "config-allow-keys")]
         {
-            debug!(
-                "Config: allow_keys_file: {:?} (source: {:?})",
-                self.allow_keys_file(),
-                self.allow_keys_file_source()
-            );
+            entries.push(ConfigEntry::new(
+                "allow_keys_file",
+                format!("{:?}", self.allow_keys_file()),
+                self.allow_keys_file_source(),
+            ));
         }
 
-        debug!(
-            "Config: scabbard_state: {:?}, (source: {:?})",
-            self.scabbard_state(),
-            self.scabbard_state_source()
-        );
+        entries.push(ConfigEntry::new(
+            "scabbard_state",
+            format!("{:?}", self.scabbard_state()),
+            self.scabbard_state_source(),
+        ));
 
-        debug!(
-            "Config: scabbard_autocleanup: {:?}, (source: {:?})",
-            self.scabbard_autocleanup(),
-            self.scabbard_autocleanup_source()
-        );
+        entries.push(ConfigEntry::new(
+            "scabbard_autocleanup",
+            format!("{:?}", self.scabbard_autocleanup()),
+            self.scabbard_autocleanup_source(),
+        ));
 
         #[cfg(feature = "service2")]
         {
-            debug!(
-                "Config: service_timer_interval: {:?}, (source: {:?})",
-                self.service_timer_interval(),
-                self.service_timer_interval_source()
-            );
+            entries.push(ConfigEntry::new(
+                "service_timer_interval",
+                format!("{:?}", self.service_timer_interval()),
+                self.service_timer_interval_source(),
+            ));
 
-            debug!(
-                "Config: lifecycle_executor_interval: {:?}, (source: {:?})",
-                self.lifecycle_executor_interval(),
-                self.lifecycle_executor_interval_source()
-            );
+            entries.push(ConfigEntry::new(
+                "lifecycle_executor_interval",
+                format!("{:?}", self.lifecycle_executor_interval()),
+                self.lifecycle_executor_interval_source(),
+            ));
+        }
+
+        #[cfg(feature = "biome-credentials")]
+        entries.push(ConfigEntry::new(
+            "biome_refresh_token_retention",
+            format!("{:?}", self.biome_refresh_token_retention()),
+            self.biome_refresh_token_retention_source(),
+        ));
+
+        #[cfg(feature = "oauth")]
+        entries.push(ConfigEntry::new(
+            "oauth_session_retention",
+            format!("{:?}", self.oauth_session_retention()),
+            self.oauth_session_retention_source(),
+        ));
+
+        #[cfg(feature = "registry-auto-populate")]
+        entries.push(ConfigEntry::new(
+            "registry_auto_populate",
+            format!("{:?}", self.registry_auto_populate()),
+            self.registry_auto_populate_source(),
+        ));
+        #[cfg(feature = "registry-signed-entries")]
+        entries.push(ConfigEntry::new(
+            "registry_require_signatures",
+            format!("{:?}", self.registry_require_signatures()),
+            self.registry_require_signatures_source(),
+        ));
+        #[cfg(feature = "rest-api-degraded-mode")]
+        entries.push(ConfigEntry::new(
+            "rest_api_degraded_mode",
+            format!("{:?}", self.rest_api_degraded_mode()),
+            self.rest_api_degraded_mode_source(),
+        ));
+        #[cfg(feature = "rest-api-openapi")]
+        entries.push(ConfigEntry::new(
+            "rest_api_swagger_ui",
+            format!("{:?}", self.rest_api_swagger_ui()),
+            self.rest_api_swagger_ui_source(),
+        ));
+        #[cfg(feature = "grpc")]
+        if let (Some(value), Some(source)) = (self.grpc_bind(), self.grpc_bind_source()) {
+            entries.push(ConfigEntry::new("grpc_bind", value.to_string(), source));
+        }
+        #[cfg(feature = "signature-verification-pool")]
+        if let (Some(value), Some(source)) = (
+            self.verification_threads(),
+            self.verification_threads_source(),
+        ) {
+            entries.push(ConfigEntry::new(
+                "verification_threads",
+                value.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "pkcs11-signer")]
+        if let (Some(value), Some(source)) = (self.signing_key_uri(), self.signing_key_uri_source())
+        {
+            // The query is omitted, since it may include the PIN
+            entries.push(ConfigEntry::new(
+                "signing_key_uri",
+                value.split('?').next().unwrap_or_default().to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "vault-key-provider")]
+        if let (Some(value), Some(source)) = (self.vault_keys_url(), self.vault_keys_url_source()) {
+            entries.push(ConfigEntry::new(
+                "vault_keys_url",
+                value.to_string(),
+                source,
+            ));
+        }
+        #[cfg(feature = "otel-tracing")]
+        if let (Some(value), Some(source)) = (self.otel_endpoint(), self.otel_endpoint_source()) {
+            entries.push(ConfigEntry::new(
+                "otel_endpoint",
+                redact_url_password(value),
+                source,
+            ));
         }
+        #[cfg(feature = "high-availability")]
+        entries.push(ConfigEntry::new(
+            "high_availability",
+            format!("{:?}", self.high_availability()),
+            self.high_availability_source(),
+        ));
+        #[cfg(feature = "high-availability")]
+        entries.push(ConfigEntry::new(
+            "high_availability_interval",
+            format!("{:?}", self.high_availability_interval()),
+            self.high_availability_interval_source(),
+        ));
+        #[cfg(feature = "peer-authorization-policy")]
+        entries.push(ConfigEntry::new(
+            "peering_authorization",
+            format!("{:?}", self.peering_authorization()),
+            self.peering_authorization_source(),
+        ));
+        #[cfg(feature = "peer-authorization-policy")]
+        entries.push(ConfigEntry::new(
+            "minimum_authorization",
+            format!("{:?}", self.minimum_authorization()),
+            self.minimum_authorization_source(),
+        ));
+        entries
+    }
+
+    /// Returns the configuration report served by `GET /config`.
+    #[cfg(feature = "config-report")]
+    pub fn report(&self) -> ConfigReport {
+        self.entries()
+            .iter()
+            .fold(ConfigReport::new(), |report, entry| {
+                entry.add_to_report(report)
+            })
     }
 
-    #[cfg(feature = "rest-api-cors")]
-    fn log_allow_list(&self) {
-        if let (Some(list), Some(source)) = (self.allow_list(), self.allow_list_source()) {
-            debug!("Config: allow_list: {:?} (source: {:?})", list, source,);
+    pub fn log_as_debug(&self) {
+        for entry in self.entries() {
+            debug!(
+                "Config: {}: {} (source: {:?})",
+                entry.name(),
+                entry.value(),
+                entry.source()
+            );
         }
     }
 }
@@ -957,6 +2848,14 @@ pub enum ScabbardState {
     Lmdb,
 }
 
+/// The authorization type used when connecting to a peer
+#[cfg(feature = "peer-authorization-policy")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PeerAuthorization {
+    Trust,
+    Challenge,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,6 +19,8 @@ use std::collections::HashMap;
 use std::time::Duration;
 
 use super::logging::{RootConfig, UnnamedAppenderConfig, UnnamedLoggerConfig};
+#[cfg(feature = "peer-authorization-policy")]
+use super::PeerAuthorization;
 use super::ScabbardState;
 
 /// `ConfigSource` displays the source of configuration values, used to identify which of the various
@@ -29,6 +31,7 @@ pub enum ConfigSource {
     Default,
     Environment,
     CommandLine,
+    Api,
 }
 
 /// `PartialConfig` is an intermediate representation of configuration values, used when combining
@@ -48,6 +51,8 @@ pub struct PartialConfig {
     tls_rest_api_cert: Option<String>,
     #[cfg(feature = "https-bind")]
     tls_rest_api_key: Option<String>,
+    #[cfg(feature = "tls-peer-ca")]
+    tls_peer_ca_files: Option<Vec<(String, String)>>,
     #[cfg(feature = "service-endpoint")]
     service_endpoint: Option<String>,
     network_endpoints: Option<Vec<String>>,
@@ -83,7 +88,123 @@ pub struct PartialConfig {
     oauth_openid_auth_params: Option<Vec<(String, String)>>,
     #[cfg(feature = "oauth")]
     oauth_openid_scopes: Option<Vec<String>>,
+    #[cfg(feature = "biome-notifications")]
+    notification_smtp_url: Option<String>,
+    #[cfg(feature = "biome-notifications")]
+    notification_email_from: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_url: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_dn: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_password: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_roles: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_auth_header: Option<String>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_trusted_addresses: Option<Vec<String>>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_identities: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_roles: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    tls_rest_api_client_ca: Option<String>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_message_quota: Option<u64>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_byte_quota: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections_per_ip: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_size: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_timeout: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_min_idle: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_idle_timeout: Option<u64>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_warn_threshold: Option<u64>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_max: Option<u64>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhooks: Option<Vec<String>>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhook_secret: Option<String>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_url: Option<String>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_interval: Option<u64>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_services: Option<Vec<String>>,
+    #[cfg(feature = "scabbard-batch-validation")]
+    scabbard_batch_validator: Option<String>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_retention: Option<u64>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_prune_interval: Option<u64>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_archive_dir: Option<String>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_dir: Option<String>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_interval: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_roots: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_days: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_prune_interval: Option<u64>,
+    #[cfg(feature = "service-restart-policy")]
+    service_max_restarts: Option<u32>,
+    #[cfg(feature = "service-restart-policy")]
+    service_restart_max_backoff: Option<u64>,
+    #[cfg(feature = "service-transfer")]
+    transfer_max_file_size: Option<u64>,
+    #[cfg(feature = "service-type-limits")]
+    service_max_instances: Option<Vec<(String, String)>>,
+    #[cfg(feature = "service-type-limits")]
+    service_thread_budget: Option<Vec<(String, String)>>,
+    #[cfg(feature = "service-type-limits")]
+    service_memory_budget: Option<Vec<(String, String)>>,
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    admin_member_wait_timeout: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_members: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_services: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_metadata_size: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_size: Option<u64>,
+    #[cfg(feature = "admin-service-circuit-version")]
+    admin_circuit_version: Option<u64>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_retry_interval: Option<u64>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_max_retry_frequency: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_initial_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_jitter: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_attempts: Option<u64>,
+    #[cfg(feature = "peer-connect-priority")]
+    peer_connect_ramp_rate: Option<u64>,
     #[cfg(feature = "tap")]
     influx_db: Option<String>,
     #[cfg(feature = "tap")]
@@ -105,6 +226,36 @@ pub struct PartialConfig {
     service_timer_interval: Option<Duration>,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Option<Duration>,
+    #[cfg(feature = "biome-credentials")]
+    biome_refresh_token_retention: Option<Duration>,
+    #[cfg(feature = "oauth")]
+    oauth_session_retention: Option<Duration>,
+    #[cfg(feature = "registry-auto-populate")]
+    registry_auto_populate: Option<bool>,
+    #[cfg(feature = "registry-signed-entries")]
+    registry_require_signatures: Option<bool>,
+    #[cfg(feature = "rest-api-degraded-mode")]
+    rest_api_degraded_mode: Option<bool>,
+    #[cfg(feature = "rest-api-openapi")]
+    rest_api_swagger_ui: Option<bool>,
+    #[cfg(feature = "grpc")]
+    grpc_bind: Option<String>,
+    #[cfg(feature = "signature-verification-pool")]
+    verification_threads: Option<u64>,
+    #[cfg(feature = "pkcs11-signer")]
+    signing_key_uri: Option<String>,
+    #[cfg(feature = "vault-key-provider")]
+    vault_keys_url: Option<String>,
+    #[cfg(feature = "otel-tracing")]
+    otel_endpoint: Option<String>,
+    #[cfg(feature = "high-availability")]
+    high_availability: Option<bool>,
+    #[cfg(feature = "high-availability")]
+    high_availability_interval: Option<u64>,
+    #[cfg(feature = "peer-authorization-policy")]
+    peering_authorization: Option<PeerAuthorization>,
+    #[cfg(feature = "peer-authorization-policy")]
+    minimum_authorization: Option<PeerAuthorization>,
 }
 
 impl PartialConfig {
@@ -123,6 +274,8 @@ impl PartialConfig {
             tls_rest_api_cert: None,
             #[cfg(feature = "https-bind")]
             tls_rest_api_key: None,
+            #[cfg(feature = "tls-peer-ca")]
+            tls_peer_ca_files: None,
             #[cfg(feature = "service-endpoint")]
             service_endpoint: None,
             network_endpoints: None,
@@ -158,7 +311,123 @@ impl PartialConfig {
             oauth_openid_auth_params: None,
             #[cfg(feature = "oauth")]
             oauth_openid_scopes: None,
+            #[cfg(feature = "biome-notifications")]
+            notification_smtp_url: None,
+            #[cfg(feature = "biome-notifications")]
+            notification_email_from: None,
+            #[cfg(feature = "ldap")]
+            ldap_url: None,
+            #[cfg(feature = "ldap")]
+            ldap_bind_dn: None,
+            #[cfg(feature = "ldap")]
+            ldap_bind_password: None,
+            #[cfg(feature = "ldap")]
+            ldap_user_search_base: None,
+            #[cfg(feature = "ldap")]
+            ldap_user_search_filter: None,
+            #[cfg(feature = "ldap")]
+            ldap_group_search_base: None,
+            #[cfg(feature = "ldap")]
+            ldap_group_search_filter: None,
+            #[cfg(feature = "ldap")]
+            ldap_group_roles: None,
+            #[cfg(feature = "rest-api-proxy-auth")]
+            rest_api_proxy_auth_header: None,
+            #[cfg(feature = "rest-api-proxy-auth")]
+            rest_api_proxy_trusted_addresses: None,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            rest_api_client_cert_identities: None,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            rest_api_client_cert_roles: None,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            tls_rest_api_client_ca: None,
+            #[cfg(feature = "circuit-usage")]
+            circuit_usage_message_quota: None,
+            #[cfg(feature = "circuit-usage")]
+            circuit_usage_byte_quota: None,
+            #[cfg(feature = "connection-limits")]
+            max_inbound_connections: None,
+            #[cfg(feature = "connection-limits")]
+            max_inbound_connections_per_ip: None,
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_size: None,
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_timeout: None,
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_min_idle: None,
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_idle_timeout: None,
+            #[cfg(feature = "clock-skew-detection")]
+            clock_skew_warn_threshold: None,
+            #[cfg(feature = "clock-skew-detection")]
+            clock_skew_max: None,
+            #[cfg(feature = "admin-webhooks")]
+            admin_webhooks: None,
+            #[cfg(feature = "admin-webhooks")]
+            admin_webhook_secret: None,
+            #[cfg(feature = "scabbard-anchoring")]
+            scabbard_anchor_url: None,
+            #[cfg(feature = "scabbard-anchoring")]
+            scabbard_anchor_interval: None,
+            #[cfg(feature = "scabbard-anchoring")]
+            scabbard_anchor_services: None,
+            #[cfg(feature = "scabbard-batch-validation")]
+            scabbard_batch_validator: None,
+            #[cfg(feature = "scabbard-receipt-pruning")]
+            scabbard_receipt_retention: None,
+            #[cfg(feature = "scabbard-receipt-pruning")]
+            scabbard_receipt_prune_interval: None,
+            #[cfg(feature = "scabbard-receipt-pruning")]
+            scabbard_receipt_archive_dir: None,
+            #[cfg(feature = "scabbard-snapshot")]
+            scabbard_snapshot_dir: None,
+            #[cfg(feature = "scabbard-snapshot")]
+            scabbard_snapshot_interval: None,
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_state_retention_roots: None,
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_state_retention_days: None,
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_state_prune_interval: None,
+            #[cfg(feature = "service-restart-policy")]
+            service_max_restarts: None,
+            #[cfg(feature = "service-restart-policy")]
+            service_restart_max_backoff: None,
+            #[cfg(feature = "service-transfer")]
+            transfer_max_file_size: None,
+            #[cfg(feature = "service-type-limits")]
+            service_max_instances: None,
+            #[cfg(feature = "service-type-limits")]
+            service_thread_budget: None,
+            #[cfg(feature = "service-type-limits")]
+            service_memory_budget: None,
+            #[cfg(feature = "admin-service-deferred-proposals")]
+            admin_member_wait_timeout: None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_members: None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_services: None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_metadata_size: None,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_max_circuit_size: None,
+            #[cfg(feature = "admin-service-circuit-version")]
+            admin_circuit_version: None,
             strict_ref_counts: None,
+            #[cfg(feature = "peer-ref-counts")]
+            peer_retry_interval: None,
+            #[cfg(feature = "peer-ref-counts")]
+            peer_max_retry_frequency: None,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_initial_delay: None,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_max_delay: None,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_jitter: None,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_max_attempts: None,
+            #[cfg(feature = "peer-connect-priority")]
+            peer_connect_ramp_rate: None,
             #[cfg(feature = "tap")]
             influx_db: None,
             #[cfg(feature = "tap")]
@@ -180,6 +449,36 @@ impl PartialConfig {
             service_timer_interval: None,
             #[cfg(feature = "service2")]
             lifecycle_executor_interval: None,
+            #[cfg(feature = "biome-credentials")]
+            biome_refresh_token_retention: None,
+            #[cfg(feature = "oauth")]
+            oauth_session_retention: None,
+            #[cfg(feature = "registry-auto-populate")]
+            registry_auto_populate: None,
+            #[cfg(feature = "registry-signed-entries")]
+            registry_require_signatures: None,
+            #[cfg(feature = "rest-api-degraded-mode")]
+            rest_api_degraded_mode: None,
+            #[cfg(feature = "rest-api-openapi")]
+            rest_api_swagger_ui: None,
+            #[cfg(feature = "grpc")]
+            grpc_bind: None,
+            #[cfg(feature = "signature-verification-pool")]
+            verification_threads: None,
+            #[cfg(feature = "pkcs11-signer")]
+            signing_key_uri: None,
+            #[cfg(feature = "vault-key-provider")]
+            vault_keys_url: None,
+            #[cfg(feature = "otel-tracing")]
+            otel_endpoint: None,
+            #[cfg(feature = "high-availability")]
+            high_availability: None,
+            #[cfg(feature = "high-availability")]
+            high_availability_interval: None,
+            #[cfg(feature = "peer-authorization-policy")]
+            peering_authorization: None,
+            #[cfg(feature = "peer-authorization-policy")]
+            minimum_authorization: None,
         }
     }
 
@@ -225,6 +524,11 @@ impl PartialConfig {
         self.tls_rest_api_key.clone()
     }
 
+    #[cfg(feature = "tls-peer-ca")]
+    pub fn tls_peer_ca_files(&self) -> Option<Vec<(String, String)>> {
+        self.tls_peer_ca_files.clone()
+    }
+
     #[cfg(feature = "service-endpoint")]
     pub fn service_endpoint(&self) -> Option<String> {
         self.service_endpoint.clone()
@@ -335,10 +639,300 @@ impl PartialConfig {
         self.oauth_openid_scopes.clone()
     }
 
+    #[cfg(feature = "biome-notifications")]
+    pub fn notification_smtp_url(&self) -> Option<String> {
+        self.notification_smtp_url.clone()
+    }
+
+    #[cfg(feature = "biome-notifications")]
+    pub fn notification_email_from(&self) -> Option<String> {
+        self.notification_email_from.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_url(&self) -> Option<String> {
+        self.ldap_url.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_bind_dn(&self) -> Option<String> {
+        self.ldap_bind_dn.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_bind_password(&self) -> Option<String> {
+        self.ldap_bind_password.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_user_search_base(&self) -> Option<String> {
+        self.ldap_user_search_base.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_user_search_filter(&self) -> Option<String> {
+        self.ldap_user_search_filter.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_group_search_base(&self) -> Option<String> {
+        self.ldap_group_search_base.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_group_search_filter(&self) -> Option<String> {
+        self.ldap_group_search_filter.clone()
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn ldap_group_roles(&self) -> Option<Vec<(String, String)>> {
+        self.ldap_group_roles.clone()
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub fn rest_api_proxy_auth_header(&self) -> Option<String> {
+        self.rest_api_proxy_auth_header.clone()
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub fn rest_api_proxy_trusted_addresses(&self) -> Option<Vec<String>> {
+        self.rest_api_proxy_trusted_addresses.clone()
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn rest_api_client_cert_identities(&self) -> Option<Vec<(String, String)>> {
+        self.rest_api_client_cert_identities.clone()
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn rest_api_client_cert_roles(&self) -> Option<Vec<(String, String)>> {
+        self.rest_api_client_cert_roles.clone()
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn tls_rest_api_client_ca(&self) -> Option<String> {
+        self.tls_rest_api_client_ca.clone()
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    pub fn circuit_usage_message_quota(&self) -> Option<u64> {
+        self.circuit_usage_message_quota
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    pub fn circuit_usage_byte_quota(&self) -> Option<u64> {
+        self.circuit_usage_byte_quota
+    }
+
+    #[cfg(feature = "connection-limits")]
+    pub fn max_inbound_connections(&self) -> Option<u64> {
+        self.max_inbound_connections
+    }
+
+    #[cfg(feature = "connection-limits")]
+    pub fn max_inbound_connections_per_ip(&self) -> Option<u64> {
+        self.max_inbound_connections_per_ip
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_size(&self) -> Option<u64> {
+        self.database_pool_size
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_timeout(&self) -> Option<u64> {
+        self.database_pool_timeout
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_min_idle(&self) -> Option<u64> {
+        self.database_pool_min_idle
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn database_pool_idle_timeout(&self) -> Option<u64> {
+        self.database_pool_idle_timeout
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn clock_skew_warn_threshold(&self) -> Option<u64> {
+        self.clock_skew_warn_threshold
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn clock_skew_max(&self) -> Option<u64> {
+        self.clock_skew_max
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    pub fn admin_webhooks(&self) -> Option<Vec<String>> {
+        self.admin_webhooks.clone()
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    pub fn admin_webhook_secret(&self) -> Option<String> {
+        self.admin_webhook_secret.clone()
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn scabbard_anchor_url(&self) -> Option<String> {
+        self.scabbard_anchor_url.clone()
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn scabbard_anchor_interval(&self) -> Option<u64> {
+        self.scabbard_anchor_interval
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn scabbard_anchor_services(&self) -> Option<Vec<String>> {
+        self.scabbard_anchor_services.clone()
+    }
+
+    #[cfg(feature = "scabbard-batch-validation")]
+    pub fn scabbard_batch_validator(&self) -> Option<String> {
+        self.scabbard_batch_validator.clone()
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn scabbard_receipt_retention(&self) -> Option<u64> {
+        self.scabbard_receipt_retention
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn scabbard_receipt_prune_interval(&self) -> Option<u64> {
+        self.scabbard_receipt_prune_interval
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn scabbard_receipt_archive_dir(&self) -> Option<String> {
+        self.scabbard_receipt_archive_dir.clone()
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    pub fn scabbard_snapshot_dir(&self) -> Option<String> {
+        self.scabbard_snapshot_dir.clone()
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    pub fn scabbard_snapshot_interval(&self) -> Option<u64> {
+        self.scabbard_snapshot_interval
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_state_retention_roots(&self) -> Option<u64> {
+        self.scabbard_state_retention_roots
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_state_retention_days(&self) -> Option<u64> {
+        self.scabbard_state_retention_days
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn scabbard_state_prune_interval(&self) -> Option<u64> {
+        self.scabbard_state_prune_interval
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    pub fn service_max_restarts(&self) -> Option<u32> {
+        self.service_max_restarts
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    pub fn service_restart_max_backoff(&self) -> Option<u64> {
+        self.service_restart_max_backoff
+    }
+
+    #[cfg(feature = "service-transfer")]
+    pub fn transfer_max_file_size(&self) -> Option<u64> {
+        self.transfer_max_file_size
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn service_max_instances(&self) -> Option<Vec<(String, String)>> {
+        self.service_max_instances.clone()
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn service_thread_budget(&self) -> Option<Vec<(String, String)>> {
+        self.service_thread_budget.clone()
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn service_memory_budget(&self) -> Option<Vec<(String, String)>> {
+        self.service_memory_budget.clone()
+    }
+
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    pub fn admin_member_wait_timeout(&self) -> Option<u64> {
+        self.admin_member_wait_timeout
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_members(&self) -> Option<u64> {
+        self.admin_max_circuit_members
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_services(&self) -> Option<u64> {
+        self.admin_max_circuit_services
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_metadata_size(&self) -> Option<u64> {
+        self.admin_max_circuit_metadata_size
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn admin_max_circuit_size(&self) -> Option<u64> {
+        self.admin_max_circuit_size
+    }
+
+    #[cfg(feature = "admin-service-circuit-version")]
+    pub fn admin_circuit_version(&self) -> Option<u64> {
+        self.admin_circuit_version
+    }
+
     pub fn strict_ref_counts(&self) -> Option<bool> {
         self.strict_ref_counts
     }
 
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn peer_retry_interval(&self) -> Option<u64> {
+        self.peer_retry_interval
+    }
+
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn peer_max_retry_frequency(&self) -> Option<u64> {
+        self.peer_max_retry_frequency
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_initial_delay(&self) -> Option<u64> {
+        self.peer_reconnect_initial_delay
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_max_delay(&self) -> Option<u64> {
+        self.peer_reconnect_max_delay
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_jitter(&self) -> Option<u64> {
+        self.peer_reconnect_jitter
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn peer_reconnect_max_attempts(&self) -> Option<u64> {
+        self.peer_reconnect_max_attempts
+    }
+
+    #[cfg(feature = "peer-connect-priority")]
+    pub fn peer_connect_ramp_rate(&self) -> Option<u64> {
+        self.peer_connect_ramp_rate
+    }
+
     #[cfg(feature = "tap")]
     pub fn influx_db(&self) -> Option<String> {
         self.influx_db.clone()
@@ -402,6 +996,81 @@ impl PartialConfig {
         self.lifecycle_executor_interval
     }
 
+    #[cfg(feature = "biome-credentials")]
+    pub fn biome_refresh_token_retention(&self) -> Option<Duration> {
+        self.biome_refresh_token_retention
+    }
+
+    #[cfg(feature = "oauth")]
+    pub fn oauth_session_retention(&self) -> Option<Duration> {
+        self.oauth_session_retention
+    }
+
+    #[cfg(feature = "registry-auto-populate")]
+    pub fn registry_auto_populate(&self) -> Option<bool> {
+        self.registry_auto_populate
+    }
+
+    #[cfg(feature = "registry-signed-entries")]
+    pub fn registry_require_signatures(&self) -> Option<bool> {
+        self.registry_require_signatures
+    }
+
+    #[cfg(feature = "rest-api-degraded-mode")]
+    pub fn rest_api_degraded_mode(&self) -> Option<bool> {
+        self.rest_api_degraded_mode
+    }
+
+    #[cfg(feature = "rest-api-openapi")]
+    pub fn rest_api_swagger_ui(&self) -> Option<bool> {
+        self.rest_api_swagger_ui
+    }
+
+    #[cfg(feature = "grpc")]
+    pub fn grpc_bind(&self) -> Option<String> {
+        self.grpc_bind.clone()
+    }
+
+    #[cfg(feature = "signature-verification-pool")]
+    pub fn verification_threads(&self) -> Option<u64> {
+        self.verification_threads
+    }
+
+    #[cfg(feature = "pkcs11-signer")]
+    pub fn signing_key_uri(&self) -> Option<String> {
+        self.signing_key_uri.clone()
+    }
+
+    #[cfg(feature = "vault-key-provider")]
+    pub fn vault_keys_url(&self) -> Option<String> {
+        self.vault_keys_url.clone()
+    }
+
+    #[cfg(feature = "otel-tracing")]
+    pub fn otel_endpoint(&self) -> Option<String> {
+        self.otel_endpoint.clone()
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn high_availability(&self) -> Option<bool> {
+        self.high_availability
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn high_availability_interval(&self) -> Option<u64> {
+        self.high_availability_interval
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn peering_authorization(&self) -> Option<PeerAuthorization> {
+        self.peering_authorization
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn minimum_authorization(&self) -> Option<PeerAuthorization> {
+        self.minimum_authorization
+    }
+
     /// Adds a `config_dir` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -506,6 +1175,23 @@ impl PartialConfig {
         self
     }
 
+    /// Adds a `tls_peer_ca_files` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_peer_ca_files` - List of `(pattern, file)` pairs; the peers whose endpoints match a
+    ///   pattern are verified using the certificate authority certificates in the file, instead
+    ///   of those in `tls_ca_file`.
+    ///
+    #[cfg(feature = "tls-peer-ca")]
+    pub fn with_tls_peer_ca_files(
+        mut self,
+        tls_peer_ca_files: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.tls_peer_ca_files = tls_peer_ca_files;
+        self
+    }
+
     /// Adds a `service_endpoint` value to the `PartialConfig` object.
     ///
     /// # Arguments
@@ -798,168 +1484,1194 @@ impl PartialConfig {
         self
     }
 
-    /// Adds a `strict_ref_counts` value to the `PartialConfig` object.
+    #[cfg(feature = "biome-notifications")]
+    /// Adds a `notification_smtp_url` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `strict_ref_counts` - Turns on panics if peer reference counting fails
+    /// * `notification_smtp_url` - URL of the SMTP server used to send email notifications
     ///
-    pub fn with_strict_ref_counts(mut self, strict_ref_counts: Option<bool>) -> Self {
-        self.strict_ref_counts = strict_ref_counts;
+    pub fn with_notification_smtp_url(mut self, notification_smtp_url: Option<String>) -> Self {
+        self.notification_smtp_url = notification_smtp_url;
         self
     }
 
-    #[cfg(feature = "tap")]
-    /// Adds an `influx_db` value to the `PartialConfig` object.
+    #[cfg(feature = "biome-notifications")]
+    /// Adds a `notification_email_from` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `influx_db` - Add the name of the InfluxDB database used for metrics
+    /// * `notification_email_from` - Address email notifications are sent from
     ///
-    pub fn with_influx_db(mut self, influx_db: Option<String>) -> Self {
-        self.influx_db = influx_db;
+    pub fn with_notification_email_from(mut self, notification_email_from: Option<String>) -> Self {
+        self.notification_email_from = notification_email_from;
         self
     }
 
-    #[cfg(feature = "tap")]
-    /// Adds an `influx_url` value to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_url` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `influx_url` - Add the URL of the InfluxDB database used for metrics
+    /// * `ldap_url` - URL of the LDAP server used to authenticate REST API users
     ///
-    pub fn with_influx_url(mut self, influx_url: Option<String>) -> Self {
-        self.influx_url = influx_url;
+    pub fn with_ldap_url(mut self, ldap_url: Option<String>) -> Self {
+        self.ldap_url = ldap_url;
         self
     }
 
-    #[cfg(feature = "tap")]
-    /// Adds an `influx_username` value to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_bind_dn` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `influx_username` - Add the username for authorization with the InfluxDB database used
-    ///    for metrics
+    /// * `ldap_bind_dn` - DN of the account used to search the LDAP directory
     ///
-    pub fn with_influx_username(mut self, influx_username: Option<String>) -> Self {
-        self.influx_username = influx_username;
+    pub fn with_ldap_bind_dn(mut self, ldap_bind_dn: Option<String>) -> Self {
+        self.ldap_bind_dn = ldap_bind_dn;
         self
     }
 
-    #[cfg(feature = "tap")]
-    /// Adds an `influx_password` value to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_bind_password` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `influx_password` - Add the password for authorization with the InfluxDB database used
-    ///    for metrics
+    /// * `ldap_bind_password` - Password of the account used to search the LDAP directory
     ///
-    pub fn with_influx_password(mut self, influx_password: Option<String>) -> Self {
-        self.influx_password = influx_password;
+    pub fn with_ldap_bind_password(mut self, ldap_bind_password: Option<String>) -> Self {
+        self.ldap_bind_password = ldap_bind_password;
         self
     }
 
-    /// Adds an `peering_key` value to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_user_search_base` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `peering_key` - Add the name of the key that should be used to peer with endpoints
-    ///    passed to the peer option when using challenge authorization
+    /// * `ldap_user_search_base` - Base DN under which LDAP users are searched for
     ///
-    pub fn with_peering_key(mut self, peering_key: Option<String>) -> Self {
-        self.peering_key = peering_key;
+    pub fn with_ldap_user_search_base(mut self, ldap_user_search_base: Option<String>) -> Self {
+        self.ldap_user_search_base = ldap_user_search_base;
         self
     }
 
-    /// Adds a `verbosity` value to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_user_search_filter` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    ///  * `level` - The log level the system will use for the root_logger logger
-    pub fn with_verbosity(mut self, level: Option<log::Level>) -> Self {
-        self.verbosity = level;
+    /// * `ldap_user_search_filter` - Filter used to find an LDAP user's entry
+    ///
+    pub fn with_ldap_user_search_filter(mut self, ldap_user_search_filter: Option<String>) -> Self {
+        self.ldap_user_search_filter = ldap_user_search_filter;
         self
     }
 
-    ///Adds a `root_logger` value to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_group_search_base` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `root_logger` - The root logger that handles default logs
-    pub fn with_root_logger(mut self, root_logger: Option<RootConfig>) -> Self {
-        self.root_logger = root_logger;
+    /// * `ldap_group_search_base` - Base DN under which LDAP groups are searched for
+    ///
+    pub fn with_ldap_group_search_base(mut self, ldap_group_search_base: Option<String>) -> Self {
+        self.ldap_group_search_base = ldap_group_search_base;
         self
     }
 
-    ///Adds `appenders` values to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_group_search_filter` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `appenders` - A set of appenders for the logging system to use
-    pub fn with_appenders(
+    /// * `ldap_group_search_filter` - Filter used to find an LDAP user's groups
+    ///
+    pub fn with_ldap_group_search_filter(
         mut self,
-        appenders: Option<HashMap<String, UnnamedAppenderConfig>>,
+        ldap_group_search_filter: Option<String>,
     ) -> Self {
-        self.appenders = appenders;
+        self.ldap_group_search_filter = ldap_group_search_filter;
         self
     }
 
-    ///Adds `loggers` values to the `PartialConfig` object.
+    #[cfg(feature = "ldap")]
+    /// Adds a `ldap_group_roles` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `loggers` - A set of loggers for the logging system to use
-    pub fn with_loggers(mut self, loggers: Option<HashMap<String, UnnamedLoggerConfig>>) -> Self {
-        self.loggers = loggers;
+    /// * `ldap_group_roles` - Pairs of LDAP groups and the RBAC roles granted to their members
+    ///
+    pub fn with_ldap_group_roles(
+        mut self,
+        ldap_group_roles: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.ldap_group_roles = ldap_group_roles;
         self
     }
 
-    #[cfg(feature = "config-allow-keys")]
-    /// Adds a `allow_keys_file` value to the  `PartialConfig` object.
+    #[cfg(feature = "rest-api-proxy-auth")]
+    /// Adds a `rest_api_proxy_auth_header` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `allow_keys_file` - List of public keys to allow
+    /// * `rest_api_proxy_auth_header` - The header in which an authenticating reverse proxy passes
+    ///   the authenticated user
     ///
-    pub fn with_allow_keys_file(mut self, allow_keys_file: Option<String>) -> Self {
-        self.allow_keys_file = allow_keys_file;
+    pub fn with_rest_api_proxy_auth_header(
+        mut self,
+        rest_api_proxy_auth_header: Option<String>,
+    ) -> Self {
+        self.rest_api_proxy_auth_header = rest_api_proxy_auth_header;
         self
     }
 
-    /// Adds a `scabbard_state` value to the  `PartialConfig` object.
+    #[cfg(feature = "rest-api-proxy-auth")]
+    /// Adds a `rest_api_proxy_trusted_addresses` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `scabbard_state` - Option of ScabbardState value for where to store state
+    /// * `rest_api_proxy_trusted_addresses` - The IP addresses of the reverse proxies whose
+    ///   identity header is trusted
     ///
-    pub fn with_scabbard_state(mut self, scabbard_state: Option<ScabbardState>) -> Self {
-        self.scabbard_state = scabbard_state;
+    pub fn with_rest_api_proxy_trusted_addresses(
+        mut self,
+        rest_api_proxy_trusted_addresses: Option<Vec<String>>,
+    ) -> Self {
+        self.rest_api_proxy_trusted_addresses = rest_api_proxy_trusted_addresses;
         self
     }
 
-    /// Adds a `scabbard_autocleanup` value to the  `PartialConfig` object.
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    /// Adds a `rest_api_client_cert_identities` value to the `PartialConfig` object.
     ///
     /// # Arguments
     ///
-    /// * `scabbard_autocleanup` - Option of bool value to enable autocleanup of pruned state.
+    /// * `rest_api_client_cert_identities` - Pairs of REST API client certificates, given as
+    ///   `sha256:<fingerprint>` or `san:<name>`, and the users they identify
     ///
-    pub fn with_scabbard_autocleanup(mut self, scabbard_autocleanup: Option<bool>) -> Self {
-        self.scabbard_autocleanup = scabbard_autocleanup;
+    pub fn with_rest_api_client_cert_identities(
+        mut self,
+        rest_api_client_cert_identities: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.rest_api_client_cert_identities = rest_api_client_cert_identities;
         self
     }
 
-    #[cfg(feature = "service2")]
-    pub fn with_service_timer_interval(mut self, service_timer_interval: Option<Duration>) -> Self {
-        self.service_timer_interval = service_timer_interval;
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    /// Adds a `rest_api_client_cert_roles` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rest_api_client_cert_roles` - Pairs of users identified by their client certificates and
+    ///   the RBAC roles granted to them
+    ///
+    pub fn with_rest_api_client_cert_roles(
+        mut self,
+        rest_api_client_cert_roles: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.rest_api_client_cert_roles = rest_api_client_cert_roles;
         self
     }
 
-    #[cfg(feature = "service2")]
-    pub fn with_lifecycle_executor_interval(
-        mut self,
-        lifecycle_executor_interval: Option<Duration>,
-    ) -> Self {
-        self.lifecycle_executor_interval = lifecycle_executor_interval;
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    /// Adds a `tls_rest_api_client_ca` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `tls_rest_api_client_ca` - The CA that REST API client certificates must be signed by
+    ///
+    pub fn with_tls_rest_api_client_ca(mut self, tls_rest_api_client_ca: Option<String>) -> Self {
+        self.tls_rest_api_client_ca = tls_rest_api_client_ca;
+        self
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    /// Adds a `circuit_usage_message_quota` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_usage_message_quota` - Number of messages a circuit may handle before it is
+    ///   reported as over quota
+    ///
+    pub fn with_circuit_usage_message_quota(
+        mut self,
+        circuit_usage_message_quota: Option<u64>,
+    ) -> Self {
+        self.circuit_usage_message_quota = circuit_usage_message_quota;
+        self
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    /// Adds a `circuit_usage_byte_quota` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `circuit_usage_byte_quota` - Number of payload bytes a circuit may handle before it is
+    ///   reported as over quota
+    ///
+    pub fn with_circuit_usage_byte_quota(mut self, circuit_usage_byte_quota: Option<u64>) -> Self {
+        self.circuit_usage_byte_quota = circuit_usage_byte_quota;
+        self
+    }
+
+    #[cfg(feature = "connection-limits")]
+    /// Adds a `max_inbound_connections` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_inbound_connections` - Number of concurrent inbound network connections the node
+    ///   accepts
+    ///
+    pub fn with_max_inbound_connections(mut self, max_inbound_connections: Option<u64>) -> Self {
+        self.max_inbound_connections = max_inbound_connections;
+        self
+    }
+
+    #[cfg(feature = "connection-limits")]
+    /// Adds a `max_inbound_connections_per_ip` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_inbound_connections_per_ip` - Number of concurrent inbound network connections the
+    ///   node accepts from a single IP address
+    ///
+    pub fn with_max_inbound_connections_per_ip(
+        mut self,
+        max_inbound_connections_per_ip: Option<u64>,
+    ) -> Self {
+        self.max_inbound_connections_per_ip = max_inbound_connections_per_ip;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    /// Adds a `database_pool_size` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_pool_size` - Maximum number of connections the database connection pool opens
+    ///
+    pub fn with_database_pool_size(mut self, database_pool_size: Option<u64>) -> Self {
+        self.database_pool_size = database_pool_size;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    /// Adds a `database_pool_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_pool_timeout` - Number of seconds to wait for a database connection from the
+    ///   pool
+    ///
+    pub fn with_database_pool_timeout(mut self, database_pool_timeout: Option<u64>) -> Self {
+        self.database_pool_timeout = database_pool_timeout;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    /// Adds a `database_pool_min_idle` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_pool_min_idle` - Number of idle connections the database connection pool
+    ///   keeps open
+    ///
+    pub fn with_database_pool_min_idle(mut self, database_pool_min_idle: Option<u64>) -> Self {
+        self.database_pool_min_idle = database_pool_min_idle;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    /// Adds a `database_pool_idle_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `database_pool_idle_timeout` - Number of seconds a database connection may be idle
+    ///   before it is closed
+    ///
+    pub fn with_database_pool_idle_timeout(
+        mut self,
+        database_pool_idle_timeout: Option<u64>,
+    ) -> Self {
+        self.database_pool_idle_timeout = database_pool_idle_timeout;
+        self
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    /// Adds a `clock_skew_warn_threshold` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock_skew_warn_threshold` - Number of seconds a peer's clock may differ from the local
+    ///   clock before a warning is logged
+    ///
+    pub fn with_clock_skew_warn_threshold(
+        mut self,
+        clock_skew_warn_threshold: Option<u64>,
+    ) -> Self {
+        self.clock_skew_warn_threshold = clock_skew_warn_threshold;
+        self
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    /// Adds a `clock_skew_max` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `clock_skew_max` - Number of seconds a connecting node's clock may differ from the local
+    ///   clock before the connection is refused
+    ///
+    pub fn with_clock_skew_max(mut self, clock_skew_max: Option<u64>) -> Self {
+        self.clock_skew_max = clock_skew_max;
+        self
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    /// Adds an `admin_webhooks` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_webhooks` - URLs that admin service events are posted to
+    ///
+    pub fn with_admin_webhooks(mut self, admin_webhooks: Option<Vec<String>>) -> Self {
+        self.admin_webhooks = admin_webhooks;
+        self
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    /// Adds an `admin_webhook_secret` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_webhook_secret` - Secret used to sign the payloads posted to the admin webhooks
+    ///
+    pub fn with_admin_webhook_secret(mut self, admin_webhook_secret: Option<String>) -> Self {
+        self.admin_webhook_secret = admin_webhook_secret;
+        self
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    /// Adds a `scabbard_anchor_url` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_anchor_url` - URL that scabbard state roots are posted to
+    ///
+    pub fn with_scabbard_anchor_url(mut self, scabbard_anchor_url: Option<String>) -> Self {
+        self.scabbard_anchor_url = scabbard_anchor_url;
+        self
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    /// Adds a `scabbard_anchor_interval` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_anchor_interval` - Number of seconds between publishing a scabbard service's
+    ///   state root
+    ///
+    pub fn with_scabbard_anchor_interval(mut self, scabbard_anchor_interval: Option<u64>) -> Self {
+        self.scabbard_anchor_interval = scabbard_anchor_interval;
+        self
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    /// Adds a `scabbard_anchor_services` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_anchor_services` - The scabbard services whose state roots are published,
+    ///   formatted as `<circuit_id>::<service_id>`
+    ///
+    pub fn with_scabbard_anchor_services(
+        mut self,
+        scabbard_anchor_services: Option<Vec<String>>,
+    ) -> Self {
+        self.scabbard_anchor_services = scabbard_anchor_services;
+        self
+    }
+
+    #[cfg(feature = "scabbard-batch-validation")]
+    /// Adds a `scabbard_batch_validator` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_batch_validator` - URL or WASM module path of the scabbard batch validator
+    ///
+    pub fn with_scabbard_batch_validator(
+        mut self,
+        scabbard_batch_validator: Option<String>,
+    ) -> Self {
+        self.scabbard_batch_validator = scabbard_batch_validator;
+        self
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    /// Adds a `scabbard_receipt_retention` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_receipt_retention` - Number of the newest transaction receipts each scabbard
+    ///   service keeps
+    ///
+    pub fn with_scabbard_receipt_retention(
+        mut self,
+        scabbard_receipt_retention: Option<u64>,
+    ) -> Self {
+        self.scabbard_receipt_retention = scabbard_receipt_retention;
+        self
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    /// Adds a `scabbard_receipt_prune_interval` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_receipt_prune_interval` - Number of seconds between pruning a scabbard
+    ///   service's receipts
+    ///
+    pub fn with_scabbard_receipt_prune_interval(
+        mut self,
+        scabbard_receipt_prune_interval: Option<u64>,
+    ) -> Self {
+        self.scabbard_receipt_prune_interval = scabbard_receipt_prune_interval;
+        self
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    /// Adds a `scabbard_receipt_archive_dir` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_receipt_archive_dir` - Directory that pruned receipts are archived to
+    ///
+    pub fn with_scabbard_receipt_archive_dir(
+        mut self,
+        scabbard_receipt_archive_dir: Option<String>,
+    ) -> Self {
+        self.scabbard_receipt_archive_dir = scabbard_receipt_archive_dir;
+        self
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    /// Adds a `scabbard_snapshot_dir` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_snapshot_dir` - Directory that scabbard state snapshots are written to and
+    ///   restored from
+    ///
+    pub fn with_scabbard_snapshot_dir(mut self, scabbard_snapshot_dir: Option<String>) -> Self {
+        self.scabbard_snapshot_dir = scabbard_snapshot_dir;
+        self
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    /// Adds a `scabbard_snapshot_interval` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_snapshot_interval` - Number of seconds between snapshots of a scabbard
+    ///   service's state
+    ///
+    pub fn with_scabbard_snapshot_interval(
+        mut self,
+        scabbard_snapshot_interval: Option<u64>,
+    ) -> Self {
+        self.scabbard_snapshot_interval = scabbard_snapshot_interval;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    /// Adds a `scabbard_state_retention_roots` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_state_retention_roots` - Number of previous state roots each scabbard service keeps
+    ///
+    pub fn with_scabbard_state_retention_roots(
+        mut self,
+        scabbard_state_retention_roots: Option<u64>,
+    ) -> Self {
+        self.scabbard_state_retention_roots = scabbard_state_retention_roots;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    /// Adds a `scabbard_state_retention_days` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_state_retention_days` - Number of days each scabbard service keeps its previous
+    ///   state roots
+    ///
+    pub fn with_scabbard_state_retention_days(
+        mut self,
+        scabbard_state_retention_days: Option<u64>,
+    ) -> Self {
+        self.scabbard_state_retention_days = scabbard_state_retention_days;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    /// Adds a `scabbard_state_prune_interval` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_state_prune_interval` - Number of seconds between pruning the previous state roots
+    ///   of a scabbard service
+    ///
+    pub fn with_scabbard_state_prune_interval(
+        mut self,
+        scabbard_state_prune_interval: Option<u64>,
+    ) -> Self {
+        self.scabbard_state_prune_interval = scabbard_state_prune_interval;
+        self
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    /// Adds a `service_max_restarts` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_max_restarts` - Number of times a crashed service is restarted before it is
+    ///   marked as failed
+    ///
+    pub fn with_service_max_restarts(mut self, service_max_restarts: Option<u32>) -> Self {
+        self.service_max_restarts = service_max_restarts;
+        self
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    /// Adds a `service_restart_max_backoff` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_restart_max_backoff` - Longest number of seconds to wait before restarting a
+    ///   crashed service
+    ///
+    pub fn with_service_restart_max_backoff(
+        mut self,
+        service_restart_max_backoff: Option<u64>,
+    ) -> Self {
+        self.service_restart_max_backoff = service_restart_max_backoff;
+        self
+    }
+
+    #[cfg(feature = "service-transfer")]
+    /// Adds a `transfer_max_file_size` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `transfer_max_file_size` - Largest number of bytes in a file sent or received by a
+    ///   transfer service
+    ///
+    pub fn with_transfer_max_file_size(mut self, transfer_max_file_size: Option<u64>) -> Self {
+        self.transfer_max_file_size = transfer_max_file_size;
+        self
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    /// Adds a `service_max_instances` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_max_instances` - List of `(service type, count)` pairs; the maximum number of
+    ///   services of each type that may run at the same time
+    ///
+    pub fn with_service_max_instances(
+        mut self,
+        service_max_instances: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.service_max_instances = service_max_instances;
+        self
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    /// Adds a `service_thread_budget` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_thread_budget` - List of `(service type, threads)` pairs; the number of threads
+    ///   each service of the type may use
+    ///
+    pub fn with_service_thread_budget(
+        mut self,
+        service_thread_budget: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.service_thread_budget = service_thread_budget;
+        self
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    /// Adds a `service_memory_budget` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `service_memory_budget` - List of `(service type, bytes)` pairs; the number of bytes of
+    ///   memory each service of the type may use
+    ///
+    pub fn with_service_memory_budget(
+        mut self,
+        service_memory_budget: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.service_memory_budget = service_memory_budget;
+        self
+    }
+
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    /// Adds a `admin_member_wait_timeout` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_member_wait_timeout` - Number of seconds a submitted circuit proposal waits for
+    ///   unreachable members to connect before it is dropped
+    ///
+    pub fn with_admin_member_wait_timeout(
+        mut self,
+        admin_member_wait_timeout: Option<u64>,
+    ) -> Self {
+        self.admin_member_wait_timeout = admin_member_wait_timeout;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    /// Adds a `admin_max_circuit_members` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_max_circuit_members` - Maximum number of members of a proposed circuit
+    ///
+    pub fn with_admin_max_circuit_members(
+        mut self,
+        admin_max_circuit_members: Option<u64>,
+    ) -> Self {
+        self.admin_max_circuit_members = admin_max_circuit_members;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    /// Adds a `admin_max_circuit_services` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_max_circuit_services` - Maximum number of services of a proposed circuit
+    ///
+    pub fn with_admin_max_circuit_services(
+        mut self,
+        admin_max_circuit_services: Option<u64>,
+    ) -> Self {
+        self.admin_max_circuit_services = admin_max_circuit_services;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    /// Adds a `admin_max_circuit_metadata_size` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_max_circuit_metadata_size` - Maximum size, in bytes, of a proposed circuit's application metadata
+    ///
+    pub fn with_admin_max_circuit_metadata_size(
+        mut self,
+        admin_max_circuit_metadata_size: Option<u64>,
+    ) -> Self {
+        self.admin_max_circuit_metadata_size = admin_max_circuit_metadata_size;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    /// Adds a `admin_max_circuit_size` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_max_circuit_size` - Maximum size, in bytes, of a proposed circuit, including its services' arguments
+    ///
+    pub fn with_admin_max_circuit_size(mut self, admin_max_circuit_size: Option<u64>) -> Self {
+        self.admin_max_circuit_size = admin_max_circuit_size;
+        self
+    }
+
+    #[cfg(feature = "admin-service-circuit-version")]
+    /// Adds a `admin_circuit_version` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `admin_circuit_version` - Newest circuit version the admin service proposes and accepts
+    ///
+    pub fn with_admin_circuit_version(mut self, admin_circuit_version: Option<u64>) -> Self {
+        self.admin_circuit_version = admin_circuit_version;
+        self
+    }
+
+    /// Adds a `strict_ref_counts` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `strict_ref_counts` - Turns on panics if peer reference counting fails
+    ///
+    pub fn with_strict_ref_counts(mut self, strict_ref_counts: Option<bool>) -> Self {
+        self.strict_ref_counts = strict_ref_counts;
+        self
+    }
+
+    #[cfg(feature = "peer-ref-counts")]
+    /// Adds a `peer_retry_interval` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_retry_interval` - How often, in seconds, the peer manager checks for pending peers
+    ///   to reconnect to
+    ///
+    pub fn with_peer_retry_interval(mut self, peer_retry_interval: Option<u64>) -> Self {
+        self.peer_retry_interval = peer_retry_interval;
+        self
+    }
+
+    #[cfg(feature = "peer-ref-counts")]
+    /// Adds a `peer_max_retry_frequency` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_max_retry_frequency` - The longest time, in seconds, the peer manager waits
+    ///   between attempts to reconnect to a pending peer
+    ///
+    pub fn with_peer_max_retry_frequency(mut self, peer_max_retry_frequency: Option<u64>) -> Self {
+        self.peer_max_retry_frequency = peer_max_retry_frequency;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    /// Adds a `peer_reconnect_initial_delay` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_reconnect_initial_delay` - The time, in seconds, the peer manager waits before
+    ///   first retrying a pending peer
+    ///
+    pub fn with_peer_reconnect_initial_delay(
+        mut self,
+        peer_reconnect_initial_delay: Option<u64>,
+    ) -> Self {
+        self.peer_reconnect_initial_delay = peer_reconnect_initial_delay;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    /// Adds a `peer_reconnect_max_delay` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_reconnect_max_delay` - The longest time, in seconds, to wait between attempts
+    ///   to reconnect to a peer
+    ///
+    pub fn with_peer_reconnect_max_delay(mut self, peer_reconnect_max_delay: Option<u64>) -> Self {
+        self.peer_reconnect_max_delay = peer_reconnect_max_delay;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    /// Adds a `peer_reconnect_jitter` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_reconnect_jitter` - The longest random time, in seconds, added to the wait
+    ///   before retrying a pending peer
+    ///
+    pub fn with_peer_reconnect_jitter(mut self, peer_reconnect_jitter: Option<u64>) -> Self {
+        self.peer_reconnect_jitter = peer_reconnect_jitter;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    /// Adds a `peer_reconnect_max_attempts` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_reconnect_max_attempts` - The number of times a pending peer is retried before
+    ///   the peer manager stops retrying it
+    ///
+    pub fn with_peer_reconnect_max_attempts(
+        mut self,
+        peer_reconnect_max_attempts: Option<u64>,
+    ) -> Self {
+        self.peer_reconnect_max_attempts = peer_reconnect_max_attempts;
+        self
+    }
+
+    #[cfg(feature = "peer-connect-priority")]
+    /// Adds a `peer_connect_ramp_rate` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peer_connect_ramp_rate` - The number of connections to idle peers requested per retry
+    ///   interval
+    ///
+    pub fn with_peer_connect_ramp_rate(mut self, peer_connect_ramp_rate: Option<u64>) -> Self {
+        self.peer_connect_ramp_rate = peer_connect_ramp_rate;
+        self
+    }
+
+    #[cfg(feature = "tap")]
+    /// Adds an `influx_db` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `influx_db` - Add the name of the InfluxDB database used for metrics
+    ///
+    pub fn with_influx_db(mut self, influx_db: Option<String>) -> Self {
+        self.influx_db = influx_db;
+        self
+    }
+
+    #[cfg(feature = "tap")]
+    /// Adds an `influx_url` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `influx_url` - Add the URL of the InfluxDB database used for metrics
+    ///
+    pub fn with_influx_url(mut self, influx_url: Option<String>) -> Self {
+        self.influx_url = influx_url;
+        self
+    }
+
+    #[cfg(feature = "tap")]
+    /// Adds an `influx_username` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `influx_username` - Add the username for authorization with the InfluxDB database used
+    ///    for metrics
+    ///
+    pub fn with_influx_username(mut self, influx_username: Option<String>) -> Self {
+        self.influx_username = influx_username;
+        self
+    }
+
+    #[cfg(feature = "tap")]
+    /// Adds an `influx_password` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `influx_password` - Add the password for authorization with the InfluxDB database used
+    ///    for metrics
+    ///
+    pub fn with_influx_password(mut self, influx_password: Option<String>) -> Self {
+        self.influx_password = influx_password;
+        self
+    }
+
+    /// Adds an `peering_key` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peering_key` - Add the name of the key that should be used to peer with endpoints
+    ///    passed to the peer option when using challenge authorization
+    ///
+    pub fn with_peering_key(mut self, peering_key: Option<String>) -> Self {
+        self.peering_key = peering_key;
+        self
+    }
+
+    /// Adds a `verbosity` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    ///  * `level` - The log level the system will use for the root_logger logger
+    pub fn with_verbosity(mut self, level: Option<log::Level>) -> Self {
+        self.verbosity = level;
+        self
+    }
+
+    ///Adds a `root_logger` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `root_logger` - The root logger that handles default logs
+    pub fn with_root_logger(mut self, root_logger: Option<RootConfig>) -> Self {
+        self.root_logger = root_logger;
+        self
+    }
+
+    ///Adds `appenders` values to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `appenders` - A set of appenders for the logging system to use
+    pub fn with_appenders(
+        mut self,
+        appenders: Option<HashMap<String, UnnamedAppenderConfig>>,
+    ) -> Self {
+        self.appenders = appenders;
+        self
+    }
+
+    ///Adds `loggers` values to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `loggers` - A set of loggers for the logging system to use
+    pub fn with_loggers(mut self, loggers: Option<HashMap<String, UnnamedLoggerConfig>>) -> Self {
+        self.loggers = loggers;
+        self
+    }
+
+    #[cfg(feature = "config-allow-keys")]
+    /// Adds a `allow_keys_file` value to the  `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `allow_keys_file` - List of public keys to allow
+    ///
+    pub fn with_allow_keys_file(mut self, allow_keys_file: Option<String>) -> Self {
+        self.allow_keys_file = allow_keys_file;
+        self
+    }
+
+    /// Adds a `scabbard_state` value to the  `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_state` - Option of ScabbardState value for where to store state
+    ///
+    pub fn with_scabbard_state(mut self, scabbard_state: Option<ScabbardState>) -> Self {
+        self.scabbard_state = scabbard_state;
+        self
+    }
+
+    /// Adds a `scabbard_autocleanup` value to the  `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `scabbard_autocleanup` - Option of bool value to enable autocleanup of pruned state.
+    ///
+    pub fn with_scabbard_autocleanup(mut self, scabbard_autocleanup: Option<bool>) -> Self {
+        self.scabbard_autocleanup = scabbard_autocleanup;
+        self
+    }
+
+    #[cfg(feature = "service2")]
+    pub fn with_service_timer_interval(mut self, service_timer_interval: Option<Duration>) -> Self {
+        self.service_timer_interval = service_timer_interval;
+        self
+    }
+
+    #[cfg(feature = "service2")]
+    pub fn with_lifecycle_executor_interval(
+        mut self,
+        lifecycle_executor_interval: Option<Duration>,
+    ) -> Self {
+        self.lifecycle_executor_interval = lifecycle_executor_interval;
+        self
+    }
+
+    /// Adds a `biome_refresh_token_retention` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `biome_refresh_token_retention` - How long Biome refresh tokens are kept after they were
+    ///   last issued.
+    ///
+    #[cfg(feature = "biome-credentials")]
+    pub fn with_biome_refresh_token_retention(
+        mut self,
+        biome_refresh_token_retention: Option<Duration>,
+    ) -> Self {
+        self.biome_refresh_token_retention = biome_refresh_token_retention;
+        self
+    }
+
+    /// Adds an `oauth_session_retention` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `oauth_session_retention` - How long OAuth sessions are kept after they were last
+    ///   authenticated.
+    ///
+    #[cfg(feature = "oauth")]
+    pub fn with_oauth_session_retention(
+        mut self,
+        oauth_session_retention: Option<Duration>,
+    ) -> Self {
+        self.oauth_session_retention = oauth_session_retention;
+        self
+    }
+
+    /// Adds a `registry_auto_populate` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_auto_populate` - Add the members of committed circuits to the local registry
+    ///
+    #[cfg(feature = "registry-auto-populate")]
+    pub fn with_registry_auto_populate(mut self, registry_auto_populate: Option<bool>) -> Self {
+        self.registry_auto_populate = registry_auto_populate;
+        self
+    }
+
+    /// Adds a `registry_require_signatures` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `registry_require_signatures` - Require the nodes written through the registry REST API
+    ///   to be signed by one of their keys
+    ///
+    #[cfg(feature = "registry-signed-entries")]
+    pub fn with_registry_require_signatures(
+        mut self,
+        registry_require_signatures: Option<bool>,
+    ) -> Self {
+        self.registry_require_signatures = registry_require_signatures;
+        self
+    }
+
+    /// Adds a `rest_api_degraded_mode` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rest_api_degraded_mode` - Serve cached read-only responses and reject writes while the
+    ///   database is unavailable
+    ///
+    #[cfg(feature = "rest-api-degraded-mode")]
+    pub fn with_rest_api_degraded_mode(mut self, rest_api_degraded_mode: Option<bool>) -> Self {
+        self.rest_api_degraded_mode = rest_api_degraded_mode;
+        self
+    }
+
+    /// Adds a `rest_api_swagger_ui` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `rest_api_swagger_ui` - Serve Swagger UI for the REST API's OpenAPI document
+    ///
+    #[cfg(feature = "rest-api-openapi")]
+    pub fn with_rest_api_swagger_ui(mut self, rest_api_swagger_ui: Option<bool>) -> Self {
+        self.rest_api_swagger_ui = rest_api_swagger_ui;
+        self
+    }
+
+    /// Adds a `grpc_bind` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `grpc_bind` - The address on which the gRPC server listens
+    ///
+    #[cfg(feature = "grpc")]
+    pub fn with_grpc_bind(mut self, grpc_bind: Option<String>) -> Self {
+        self.grpc_bind = grpc_bind;
+        self
+    }
+
+    /// Adds a `verification_threads` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `verification_threads` - The number of threads that verify batch and admin payload
+    ///   signatures
+    ///
+    #[cfg(feature = "signature-verification-pool")]
+    pub fn with_verification_threads(mut self, verification_threads: Option<u64>) -> Self {
+        self.verification_threads = verification_threads;
+        self
+    }
+
+    /// Adds a `signing_key_uri` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `signing_key_uri` - The PKCS#11 URI of the key to use for challenge authorization
+    ///
+    #[cfg(feature = "pkcs11-signer")]
+    pub fn with_signing_key_uri(mut self, signing_key_uri: Option<String>) -> Self {
+        self.signing_key_uri = signing_key_uri;
+        self
+    }
+
+    /// Adds a `vault_keys_url` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `vault_keys_url` - The URL of the Vault secret that holds the keys to use for challenge
+    ///   authorization
+    ///
+    #[cfg(feature = "vault-key-provider")]
+    pub fn with_vault_keys_url(mut self, vault_keys_url: Option<String>) -> Self {
+        self.vault_keys_url = vault_keys_url;
+        self
+    }
+
+    /// Adds an `otel_endpoint` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `otel_endpoint` - The URL of the OTLP/HTTP endpoint that traces are exported to
+    ///
+    #[cfg(feature = "otel-tracing")]
+    pub fn with_otel_endpoint(mut self, otel_endpoint: Option<String>) -> Self {
+        self.otel_endpoint = otel_endpoint;
+        self
+    }
+
+    /// Adds a `high_availability` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `high_availability` - Run as one daemon of an active-passive pair, waiting as a standby
+    ///   while the other daemon is active
+    ///
+    #[cfg(feature = "high-availability")]
+    pub fn with_high_availability(mut self, high_availability: Option<bool>) -> Self {
+        self.high_availability = high_availability;
+        self
+    }
+
+    /// Adds a `high_availability_interval` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `high_availability_interval` - How often, in seconds, the standby tries to take over and
+    ///   the active daemon checks that it is still active
+    ///
+    #[cfg(feature = "high-availability")]
+    pub fn with_high_availability_interval(
+        mut self,
+        high_availability_interval: Option<u64>,
+    ) -> Self {
+        self.high_availability_interval = high_availability_interval;
+        self
+    }
+
+    /// Adds a `peering_authorization` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `peering_authorization` - The authorization type used to connect to the peers that do
+    ///   not specify one
+    ///
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn with_peering_authorization(
+        mut self,
+        peering_authorization: Option<PeerAuthorization>,
+    ) -> Self {
+        self.peering_authorization = peering_authorization;
+        self
+    }
+
+    /// Adds a `minimum_authorization` value to the `PartialConfig` object.
+    ///
+    /// # Arguments
+    ///
+    /// * `minimum_authorization` - The weakest authorization type that circuits and peers of
+    ///   this node may use
+    ///
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn with_minimum_authorization(
+        mut self,
+        minimum_authorization: Option<PeerAuthorization>,
+    ) -> Self {
+        self.minimum_authorization = minimum_authorization;
         self
     }
 }
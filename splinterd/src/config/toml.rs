@@ -22,10 +22,17 @@ use serde::Deserialize as DeserializeTrait;
 use serde_derive::Deserialize;
 use std::collections::HashMap;
 use std::convert::TryInto;
-#[cfg(feature = "service2")]
+#[cfg(any(
+    feature = "service2",
+    feature = "biome-credentials",
+    feature = "log-rotation",
+    feature = "oauth"
+))]
 use std::time::Duration;
 
 use super::logging::{UnnamedAppenderConfig, UnnamedLoggerConfig};
+#[cfg(feature = "peer-authorization-policy")]
+use super::PeerAuthorization;
 use super::ScabbardState;
 
 /// `TOML_VERSION` represents the version of the toml config file.
@@ -42,6 +49,12 @@ pub enum TomlRawLogTarget {
     File,
     #[serde(alias = "rolling_file")]
     RollingFile,
+    #[cfg(feature = "log-system-logger")]
+    #[serde(alias = "syslog")]
+    Syslog,
+    #[cfg(feature = "log-system-logger")]
+    #[serde(alias = "journald")]
+    Journald,
 }
 
 #[derive(Deserialize, Clone, Debug)]
@@ -51,6 +64,10 @@ pub struct TomlUnnamedAppenderConfig {
     pub kind: TomlRawLogTarget,
     pub filename: Option<String>,
     pub size: Option<TomlLogFileSize>,
+    #[cfg(feature = "log-rotation")]
+    pub max_age: Option<TomlLogFileAge>,
+    #[cfg(feature = "log-rotation")]
+    pub max_files: Option<u32>,
     pub level: Option<TomlLogLevel>,
 }
 
@@ -148,6 +165,70 @@ impl<'de> Visitor<'de> for TomlLogFileSizeVisitor {
     }
 }
 
+/// How long a rolling log file is written to before it is rolled, such as "12h" or "7d".
+#[cfg(feature = "log-rotation")]
+#[derive(Clone, Debug)]
+pub struct TomlLogFileAge {
+    age: Duration,
+}
+
+#[cfg(feature = "log-rotation")]
+impl From<TomlLogFileAge> for Duration {
+    fn from(age: TomlLogFileAge) -> Self {
+        age.age
+    }
+}
+
+#[cfg(feature = "log-rotation")]
+impl<'de> DeserializeTrait<'de> for TomlLogFileAge {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_string(TomlLogFileAgeVisitor)
+    }
+}
+
+#[cfg(feature = "log-rotation")]
+struct TomlLogFileAgeVisitor;
+
+#[cfg(feature = "log-rotation")]
+impl<'de> Visitor<'de> for TomlLogFileAgeVisitor {
+    type Value = TomlLogFileAge;
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        self.visit_str(&v)
+    }
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        let split = v.find(|c: char| !c.is_ascii_digit()).unwrap_or(v.len());
+        let count: u64 = v[..split]
+            .parse()
+            .map_err(|e| E::custom(format!("age could not be parsed: {}", e)))?;
+        // Units can be s,m,h,d for seconds, minutes, hours and days.
+        let multiple = match &v[split..] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 60 * 60,
+            "d" => 24 * 60 * 60,
+            _ => return Err(E::custom("unit could not be parsed".to_string())),
+        };
+        if count == 0 {
+            return Err(E::custom("age must be greater than 0".to_string()));
+        }
+        Ok(TomlLogFileAge {
+            age: Duration::from_secs(count * multiple),
+        })
+    }
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(formatter, "<integer><s|m|h|d>")
+    }
+}
+
 /// `TomlConfig` object which holds values defined in a toml file. This struct must be
 /// treated as part of the external API of splinter because changes here
 /// will impact the valid format of the config file.
@@ -163,6 +244,10 @@ struct TomlConfig {
     tls_rest_api_cert: Option<String>,
     #[cfg(feature = "https-bind")]
     tls_rest_api_key: Option<String>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    tls_rest_api_client_ca: Option<String>,
+    #[cfg(feature = "tls-peer-ca")]
+    tls_peer_ca_files: Option<Vec<(String, String)>>,
     #[cfg(feature = "service-endpoint")]
     service_endpoint: Option<String>,
     network_endpoints: Option<Vec<String>>,
@@ -180,6 +265,8 @@ struct TomlConfig {
     version: Option<String>,
     #[cfg(feature = "rest-api-cors")]
     allow_list: Option<Vec<String>>,
+    #[cfg(feature = "biome-credentials")]
+    biome_refresh_token_retention: Option<u64>,
     #[cfg(feature = "oauth")]
     oauth_provider: Option<String>,
     #[cfg(feature = "oauth")]
@@ -194,6 +281,124 @@ struct TomlConfig {
     oauth_openid_auth_params: Option<Vec<(String, String)>>,
     #[cfg(feature = "oauth")]
     oauth_openid_scopes: Option<Vec<String>>,
+    #[cfg(feature = "oauth")]
+    oauth_session_retention: Option<u64>,
+    #[cfg(feature = "biome-notifications")]
+    notification_smtp_url: Option<String>,
+    #[cfg(feature = "biome-notifications")]
+    notification_email_from: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_url: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_dn: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_password: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_roles: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_auth_header: Option<String>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_trusted_addresses: Option<Vec<String>>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_identities: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_roles: Option<Vec<(String, String)>>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_message_quota: Option<u64>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_byte_quota: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections_per_ip: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_size: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_timeout: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_min_idle: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_idle_timeout: Option<u64>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_warn_threshold: Option<u64>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_max: Option<u64>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhooks: Option<Vec<String>>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhook_secret: Option<String>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_url: Option<String>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_interval: Option<u64>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_services: Option<Vec<String>>,
+    #[cfg(feature = "scabbard-batch-validation")]
+    scabbard_batch_validator: Option<String>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_retention: Option<u64>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_prune_interval: Option<u64>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_archive_dir: Option<String>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_dir: Option<String>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_interval: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_roots: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_days: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_prune_interval: Option<u64>,
+    #[cfg(feature = "service-restart-policy")]
+    service_max_restarts: Option<u32>,
+    #[cfg(feature = "service-restart-policy")]
+    service_restart_max_backoff: Option<u64>,
+    #[cfg(feature = "service-transfer")]
+    transfer_max_file_size: Option<u64>,
+    #[cfg(feature = "service-type-limits")]
+    service_max_instances: Option<Vec<(String, String)>>,
+    #[cfg(feature = "service-type-limits")]
+    service_thread_budget: Option<Vec<(String, String)>>,
+    #[cfg(feature = "service-type-limits")]
+    service_memory_budget: Option<Vec<(String, String)>>,
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    admin_member_wait_timeout: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_members: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_services: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_metadata_size: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_size: Option<u64>,
+    #[cfg(feature = "admin-service-circuit-version")]
+    admin_circuit_version: Option<u64>,
+    #[cfg(feature = "peer-ref-counts")]
+    strict_ref_counts: Option<bool>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_retry_interval: Option<u64>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_max_retry_frequency: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_initial_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_jitter: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_attempts: Option<u64>,
+    #[cfg(feature = "peer-connect-priority")]
+    peer_connect_ramp_rate: Option<u64>,
     #[cfg(feature = "tap")]
     influx_db: Option<String>,
     #[cfg(feature = "tap")]
@@ -214,6 +419,32 @@ struct TomlConfig {
     service_timer_interval: Option<u64>,
     #[cfg(feature = "lifecycle-executor-interval")]
     lifecycle_executor_interval: Option<u64>,
+    #[cfg(feature = "registry-auto-populate")]
+    registry_auto_populate: Option<bool>,
+    #[cfg(feature = "registry-signed-entries")]
+    registry_require_signatures: Option<bool>,
+    #[cfg(feature = "rest-api-degraded-mode")]
+    rest_api_degraded_mode: Option<bool>,
+    #[cfg(feature = "rest-api-openapi")]
+    rest_api_swagger_ui: Option<bool>,
+    #[cfg(feature = "grpc")]
+    grpc_bind: Option<String>,
+    #[cfg(feature = "signature-verification-pool")]
+    verification_threads: Option<u64>,
+    #[cfg(feature = "pkcs11-signer")]
+    signing_key_uri: Option<String>,
+    #[cfg(feature = "vault-key-provider")]
+    vault_keys_url: Option<String>,
+    #[cfg(feature = "otel-tracing")]
+    otel_endpoint: Option<String>,
+    #[cfg(feature = "high-availability")]
+    high_availability: Option<bool>,
+    #[cfg(feature = "high-availability")]
+    high_availability_interval: Option<u64>,
+    #[cfg(feature = "peer-authorization-policy")]
+    peering_authorization: Option<PeerAuthorizationToml>,
+    #[cfg(feature = "peer-authorization-policy")]
+    minimum_authorization: Option<PeerAuthorizationToml>,
 
     // Deprecated values
     cert_dir: Option<String>,
@@ -314,6 +545,18 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
                 .with_tls_rest_api_key(self.toml_config.tls_rest_api_key);
         }
 
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            partial_config =
+                partial_config.with_tls_rest_api_client_ca(self.toml_config.tls_rest_api_client_ca);
+        }
+
+        #[cfg(feature = "tls-peer-ca")]
+        {
+            partial_config =
+                partial_config.with_tls_peer_ca_files(self.toml_config.tls_peer_ca_files);
+        }
+
         #[cfg(feature = "service-endpoint")]
         {
             partial_config = partial_config.with_service_endpoint(self.toml_config.service_endpoint)
@@ -324,6 +567,15 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
             partial_config = partial_config.with_allow_list(self.toml_config.allow_list);
         }
 
+        #[cfg(feature = "biome-credentials")]
+        {
+            partial_config = partial_config.with_biome_refresh_token_retention(
+                self.toml_config
+                    .biome_refresh_token_retention
+                    .map(Duration::from_secs),
+            );
+        }
+
         #[cfg(feature = "oauth")]
         {
             partial_config = partial_config
@@ -333,7 +585,269 @@ impl PartialConfigBuilder for TomlPartialConfigBuilder {
                 .with_oauth_redirect_url(self.toml_config.oauth_redirect_url)
                 .with_oauth_openid_url(self.toml_config.oauth_openid_url)
                 .with_oauth_openid_auth_params(self.toml_config.oauth_openid_auth_params)
-                .with_oauth_openid_scopes(self.toml_config.oauth_openid_scopes);
+                .with_oauth_openid_scopes(self.toml_config.oauth_openid_scopes)
+                .with_oauth_session_retention(
+                    self.toml_config
+                        .oauth_session_retention
+                        .map(Duration::from_secs),
+                );
+        }
+
+        #[cfg(feature = "registry-auto-populate")]
+        {
+            partial_config =
+                partial_config.with_registry_auto_populate(self.toml_config.registry_auto_populate);
+        }
+
+        #[cfg(feature = "registry-signed-entries")]
+        {
+            partial_config = partial_config
+                .with_registry_require_signatures(self.toml_config.registry_require_signatures);
+        }
+
+        #[cfg(feature = "rest-api-degraded-mode")]
+        {
+            partial_config =
+                partial_config.with_rest_api_degraded_mode(self.toml_config.rest_api_degraded_mode);
+        }
+
+        #[cfg(feature = "rest-api-openapi")]
+        {
+            partial_config =
+                partial_config.with_rest_api_swagger_ui(self.toml_config.rest_api_swagger_ui);
+        }
+
+        #[cfg(feature = "grpc")]
+        {
+            partial_config = partial_config.with_grpc_bind(self.toml_config.grpc_bind);
+        }
+
+        #[cfg(feature = "signature-verification-pool")]
+        {
+            partial_config =
+                partial_config.with_verification_threads(self.toml_config.verification_threads);
+        }
+
+        #[cfg(feature = "pkcs11-signer")]
+        {
+            partial_config = partial_config.with_signing_key_uri(self.toml_config.signing_key_uri);
+        }
+
+        #[cfg(feature = "vault-key-provider")]
+        {
+            partial_config = partial_config.with_vault_keys_url(self.toml_config.vault_keys_url);
+        }
+
+        #[cfg(feature = "otel-tracing")]
+        {
+            partial_config = partial_config.with_otel_endpoint(self.toml_config.otel_endpoint);
+        }
+
+        #[cfg(feature = "high-availability")]
+        {
+            partial_config = partial_config
+                .with_high_availability(self.toml_config.high_availability)
+                .with_high_availability_interval(self.toml_config.high_availability_interval);
+        }
+
+        #[cfg(feature = "peer-authorization-policy")]
+        {
+            partial_config = partial_config
+                .with_peering_authorization(
+                    self.toml_config
+                        .peering_authorization
+                        .map(|inner| inner.into()),
+                )
+                .with_minimum_authorization(
+                    self.toml_config
+                        .minimum_authorization
+                        .map(|inner| inner.into()),
+                );
+        }
+
+        #[cfg(feature = "biome-notifications")]
+        {
+            partial_config = partial_config
+                .with_notification_smtp_url(self.toml_config.notification_smtp_url)
+                .with_notification_email_from(self.toml_config.notification_email_from);
+        }
+
+        #[cfg(feature = "ldap")]
+        {
+            partial_config = partial_config
+                .with_ldap_url(self.toml_config.ldap_url)
+                .with_ldap_bind_dn(self.toml_config.ldap_bind_dn)
+                .with_ldap_bind_password(self.toml_config.ldap_bind_password)
+                .with_ldap_user_search_base(self.toml_config.ldap_user_search_base)
+                .with_ldap_user_search_filter(self.toml_config.ldap_user_search_filter)
+                .with_ldap_group_search_base(self.toml_config.ldap_group_search_base)
+                .with_ldap_group_search_filter(self.toml_config.ldap_group_search_filter)
+                .with_ldap_group_roles(self.toml_config.ldap_group_roles);
+        }
+
+        #[cfg(feature = "rest-api-proxy-auth")]
+        {
+            partial_config = partial_config
+                .with_rest_api_proxy_auth_header(self.toml_config.rest_api_proxy_auth_header)
+                .with_rest_api_proxy_trusted_addresses(
+                    self.toml_config.rest_api_proxy_trusted_addresses,
+                );
+        }
+
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            partial_config = partial_config
+                .with_rest_api_client_cert_identities(
+                    self.toml_config.rest_api_client_cert_identities,
+                )
+                .with_rest_api_client_cert_roles(self.toml_config.rest_api_client_cert_roles);
+        }
+
+        #[cfg(feature = "circuit-usage")]
+        {
+            partial_config = partial_config
+                .with_circuit_usage_message_quota(self.toml_config.circuit_usage_message_quota)
+                .with_circuit_usage_byte_quota(self.toml_config.circuit_usage_byte_quota);
+        }
+
+        #[cfg(feature = "connection-limits")]
+        {
+            partial_config = partial_config
+                .with_max_inbound_connections(self.toml_config.max_inbound_connections)
+                .with_max_inbound_connections_per_ip(
+                    self.toml_config.max_inbound_connections_per_ip,
+                );
+        }
+
+        #[cfg(feature = "database-pool-settings")]
+        {
+            partial_config = partial_config
+                .with_database_pool_size(self.toml_config.database_pool_size)
+                .with_database_pool_timeout(self.toml_config.database_pool_timeout)
+                .with_database_pool_min_idle(self.toml_config.database_pool_min_idle)
+                .with_database_pool_idle_timeout(self.toml_config.database_pool_idle_timeout);
+        }
+
+        #[cfg(feature = "clock-skew-detection")]
+        {
+            partial_config = partial_config
+                .with_clock_skew_warn_threshold(self.toml_config.clock_skew_warn_threshold)
+                .with_clock_skew_max(self.toml_config.clock_skew_max);
+        }
+
+        #[cfg(feature = "admin-webhooks")]
+        {
+            partial_config = partial_config
+                .with_admin_webhooks(self.toml_config.admin_webhooks)
+                .with_admin_webhook_secret(self.toml_config.admin_webhook_secret);
+        }
+
+        #[cfg(feature = "scabbard-anchoring")]
+        {
+            partial_config = partial_config
+                .with_scabbard_anchor_url(self.toml_config.scabbard_anchor_url)
+                .with_scabbard_anchor_interval(self.toml_config.scabbard_anchor_interval)
+                .with_scabbard_anchor_services(self.toml_config.scabbard_anchor_services);
+        }
+
+        #[cfg(feature = "scabbard-batch-validation")]
+        {
+            partial_config = partial_config
+                .with_scabbard_batch_validator(self.toml_config.scabbard_batch_validator);
+        }
+
+        #[cfg(feature = "scabbard-receipt-pruning")]
+        {
+            partial_config = partial_config
+                .with_scabbard_receipt_retention(self.toml_config.scabbard_receipt_retention)
+                .with_scabbard_receipt_prune_interval(
+                    self.toml_config.scabbard_receipt_prune_interval,
+                )
+                .with_scabbard_receipt_archive_dir(self.toml_config.scabbard_receipt_archive_dir);
+        }
+
+        #[cfg(feature = "scabbard-snapshot")]
+        {
+            partial_config = partial_config
+                .with_scabbard_snapshot_dir(self.toml_config.scabbard_snapshot_dir)
+                .with_scabbard_snapshot_interval(self.toml_config.scabbard_snapshot_interval);
+        }
+
+        #[cfg(feature = "scabbard-state-pruning")]
+        {
+            partial_config = partial_config
+                .with_scabbard_state_retention_roots(
+                    self.toml_config.scabbard_state_retention_roots,
+                )
+                .with_scabbard_state_retention_days(self.toml_config.scabbard_state_retention_days)
+                .with_scabbard_state_prune_interval(self.toml_config.scabbard_state_prune_interval);
+        }
+
+        #[cfg(feature = "service-restart-policy")]
+        {
+            partial_config = partial_config
+                .with_service_max_restarts(self.toml_config.service_max_restarts)
+                .with_service_restart_max_backoff(self.toml_config.service_restart_max_backoff);
+        }
+
+        #[cfg(feature = "service-transfer")]
+        {
+            partial_config =
+                partial_config.with_transfer_max_file_size(self.toml_config.transfer_max_file_size);
+        }
+
+        #[cfg(feature = "service-type-limits")]
+        {
+            partial_config = partial_config
+                .with_service_max_instances(self.toml_config.service_max_instances)
+                .with_service_thread_budget(self.toml_config.service_thread_budget)
+                .with_service_memory_budget(self.toml_config.service_memory_budget);
+        }
+
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        {
+            partial_config = partial_config
+                .with_admin_member_wait_timeout(self.toml_config.admin_member_wait_timeout);
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        {
+            partial_config = partial_config
+                .with_admin_max_circuit_members(self.toml_config.admin_max_circuit_members)
+                .with_admin_max_circuit_services(self.toml_config.admin_max_circuit_services)
+                .with_admin_max_circuit_metadata_size(
+                    self.toml_config.admin_max_circuit_metadata_size,
+                )
+                .with_admin_max_circuit_size(self.toml_config.admin_max_circuit_size);
+        }
+
+        #[cfg(feature = "admin-service-circuit-version")]
+        {
+            partial_config =
+                partial_config.with_admin_circuit_version(self.toml_config.admin_circuit_version);
+        }
+
+        #[cfg(feature = "peer-ref-counts")]
+        {
+            partial_config = partial_config
+                .with_strict_ref_counts(self.toml_config.strict_ref_counts)
+                .with_peer_retry_interval(self.toml_config.peer_retry_interval)
+                .with_peer_max_retry_frequency(self.toml_config.peer_max_retry_frequency);
+        }
+
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            partial_config = partial_config
+                .with_peer_reconnect_initial_delay(self.toml_config.peer_reconnect_initial_delay)
+                .with_peer_reconnect_max_delay(self.toml_config.peer_reconnect_max_delay)
+                .with_peer_reconnect_jitter(self.toml_config.peer_reconnect_jitter)
+                .with_peer_reconnect_max_attempts(self.toml_config.peer_reconnect_max_attempts);
+        }
+
+        #[cfg(feature = "peer-connect-priority")]
+        {
+            partial_config =
+                partial_config.with_peer_connect_ramp_rate(self.toml_config.peer_connect_ramp_rate);
         }
 
         #[cfg(feature = "tap")]
@@ -459,6 +973,25 @@ impl From<ScabbardStateToml> for ScabbardState {
     }
 }
 
+#[cfg(feature = "peer-authorization-policy")]
+#[derive(Deserialize, Debug)]
+pub enum PeerAuthorizationToml {
+    #[serde(rename = "trust")]
+    Trust,
+    #[serde(rename = "challenge")]
+    Challenge,
+}
+
+#[cfg(feature = "peer-authorization-policy")]
+impl From<PeerAuthorizationToml> for PeerAuthorization {
+    fn from(other: PeerAuthorizationToml) -> Self {
+        match other {
+            PeerAuthorizationToml::Trust => PeerAuthorization::Trust,
+            PeerAuthorizationToml::Challenge => PeerAuthorization::Challenge,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::config::{LogEncoder, LoggerConfig};
@@ -799,6 +1332,8 @@ mod tests {
             kind = "rolling_file"
             filename = "/var/log/splinter/splinterd.log"
             size = "16.0M"
+            max_age = "7d"
+            max_files = 5
             [loggers.splinter]
             appenders = [ "stdout", "rolling_file"]
             level = "Warn"
@@ -903,6 +1438,14 @@ mod tests {
             "/var/log/splinter/splinterd.log"
         );
         assert_eq!(&*rolling_file.encoder, &*LogEncoder::default());
+        #[cfg(feature = "log-rotation")]
+        {
+            assert_eq!(
+                rolling_file.max_age,
+                Some(Duration::from_secs(7 * 24 * 60 * 60))
+            );
+            assert_eq!(rolling_file.max_files, Some(5));
+        }
 
         let loggers = toml.loggers();
         assert!(loggers.is_some());
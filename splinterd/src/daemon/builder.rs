@@ -12,13 +12,72 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "service-type-limits")]
+use std::collections::HashMap;
+#[cfg(feature = "database-pool-settings")]
+use std::convert::TryFrom;
+#[cfg(feature = "rest-api-proxy-auth")]
+use std::net::IpAddr;
+#[cfg(any(
+    feature = "config-report",
+    feature = "scabbard-anchoring",
+    feature = "scabbard-batch-validation",
+    feature = "scabbard-receipt-pruning"
+))]
+use std::sync::Arc;
+#[cfg(feature = "config-report")]
+use std::sync::RwLock;
 use std::time::Duration;
 
 use cylinder::Signer;
+#[cfg(feature = "scabbard-snapshot")]
+use scabbard::service::SnapshotConfig;
+#[cfg(feature = "scabbard-state-pruning")]
+use scabbard::service::StateRetention;
+#[cfg(feature = "scabbard-anchoring")]
+use scabbard::service::{AnchorConfig, HttpAnchorPublisher};
+#[cfg(feature = "scabbard-batch-validation")]
+use scabbard::service::{BatchValidator, HttpBatchValidator, WasmBatchValidator};
+#[cfg(feature = "scabbard-receipt-pruning")]
+use scabbard::service::{FileReceiptArchiver, ReceiptRetention};
+#[cfg(feature = "admin-service-proposal-limits")]
+use splinter::admin::service::ProposalLimits;
+#[cfg(feature = "peer-authorization-policy")]
+use splinter::admin::store::AuthorizationType;
+#[cfg(feature = "admin-webhooks")]
+use splinter::admin::webhook::Webhook;
+#[cfg(feature = "admin-service-circuit-version")]
+use splinter::admin::CIRCUIT_PROTOCOL_VERSION;
 use splinter::mesh::Mesh;
+#[cfg(feature = "clock-skew-detection")]
+use splinter::network::clock_skew::ClockSkewMonitor;
 use splinter::peer::PeerAuthorizationToken;
+#[cfg(feature = "rest-api-client-cert-auth")]
+use splinter::rest_api::auth::client_cert::CertificateMatcher;
+#[cfg(feature = "service-restart-policy")]
+use splinter::runtime::service::instance::RestartPolicy;
+#[cfg(feature = "service-type-limits")]
+use splinter::runtime::service::instance::ServiceTypeLimits;
+#[cfg(feature = "service-transfer")]
+use splinter::service::transfer;
+#[cfg(feature = "database-pool-settings")]
+use splinter::store::ConnectionPoolSettings;
+#[cfg(all(feature = "tap", feature = "subsystem-control"))]
+use splinter::tap::influx::InfluxRecorderSwitch;
+#[cfg(all(unix, feature = "unix-transport"))]
+use splinter::transport::socket::validate_unix_endpoint;
+#[cfg(feature = "config-report")]
+use splinter_rest_api_actix_web_1::status::ConfigReport;
 
+#[cfg(feature = "peer-authorization-policy")]
+use crate::config::PeerAuthorization;
+use crate::config::{Config, ScabbardState};
 use crate::daemon::error::CreateError;
+#[cfg(feature = "daemon-hooks")]
+use crate::daemon::hooks::DaemonHooks;
+#[cfg(feature = "config-reload")]
+use crate::daemon::reload::ConfigReloader;
+use crate::daemon::startup::StartupTimer;
 use crate::daemon::SplinterDaemon;
 
 #[derive(Default)]
@@ -38,16 +97,40 @@ pub struct SplinterDaemonBuilder {
     rest_api_server_cert: Option<String>,
     #[cfg(feature = "https-bind")]
     rest_api_server_key: Option<String>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_ca: Option<String>,
     db_url: Option<String>,
     registries: Vec<String>,
     registry_auto_refresh: Option<u64>,
     registry_forced_refresh: Option<u64>,
+    #[cfg(feature = "registry-auto-populate")]
+    registry_auto_populate: bool,
+    #[cfg(feature = "registry-signed-entries")]
+    registry_require_signatures: bool,
+    #[cfg(feature = "rest-api-degraded-mode")]
+    rest_api_degraded_mode: bool,
+    #[cfg(feature = "rest-api-openapi")]
+    rest_api_swagger_ui: bool,
+    #[cfg(feature = "grpc")]
+    grpc_bind: Option<String>,
+    #[cfg(feature = "signature-verification-pool")]
+    verification_threads: Option<usize>,
+    #[cfg(feature = "high-availability")]
+    high_availability: bool,
+    #[cfg(feature = "high-availability")]
+    high_availability_interval: Option<u64>,
+    #[cfg(feature = "peer-authorization-policy")]
+    peering_authorization: Option<AuthorizationType>,
+    #[cfg(feature = "peer-authorization-policy")]
+    minimum_authorization: Option<AuthorizationType>,
     heartbeat: Option<u64>,
     admin_timeout: Duration,
     #[cfg(feature = "rest-api-cors")]
     allow_list: Option<Vec<String>>,
     #[cfg(feature = "biome-credentials")]
     enable_biome_credentials: Option<bool>,
+    #[cfg(feature = "biome-credentials")]
+    biome_refresh_token_retention: Option<Duration>,
     #[cfg(feature = "oauth")]
     oauth_provider: Option<String>,
     #[cfg(feature = "oauth")]
@@ -62,7 +145,123 @@ pub struct SplinterDaemonBuilder {
     oauth_openid_auth_params: Option<Vec<(String, String)>>,
     #[cfg(feature = "oauth")]
     oauth_openid_scopes: Option<Vec<String>>,
+    #[cfg(feature = "oauth")]
+    oauth_session_retention: Option<Duration>,
+    #[cfg(feature = "biome-notifications")]
+    notification_smtp_url: Option<String>,
+    #[cfg(feature = "biome-notifications")]
+    notification_email_from: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_url: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_dn: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_password: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_roles: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_auth_header: Option<String>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_trusted_addresses: Option<Vec<String>>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_identities: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_roles: Option<Vec<(String, String)>>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_message_quota: Option<u64>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_byte_quota: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections_per_ip: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_size: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_timeout: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_min_idle: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_idle_timeout: Option<u64>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_warn_threshold: Option<u64>,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_max: Option<u64>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhooks: Option<Vec<String>>,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhook_secret: Option<String>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_url: Option<String>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_interval: Option<u64>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchor_services: Option<Vec<String>>,
+    #[cfg(feature = "scabbard-batch-validation")]
+    scabbard_batch_validator: Option<String>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_retention: Option<u64>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_prune_interval: Option<u64>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_archive_dir: Option<String>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_dir: Option<String>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshot_interval: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_roots: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention_days: Option<u64>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_prune_interval: Option<u64>,
+    #[cfg(feature = "service-restart-policy")]
+    service_max_restarts: Option<u32>,
+    #[cfg(feature = "service-restart-policy")]
+    service_restart_max_backoff: Option<u64>,
+    #[cfg(feature = "service-transfer")]
+    transfer_max_file_size: Option<u64>,
+    #[cfg(feature = "service-type-limits")]
+    service_max_instances: Option<Vec<(String, String)>>,
+    #[cfg(feature = "service-type-limits")]
+    service_thread_budget: Option<Vec<(String, String)>>,
+    #[cfg(feature = "service-type-limits")]
+    service_memory_budget: Option<Vec<(String, String)>>,
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    admin_member_wait_timeout: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_members: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_services: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_metadata_size: Option<u64>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_max_circuit_size: Option<u64>,
+    #[cfg(feature = "admin-service-circuit-version")]
+    admin_circuit_version: Option<u64>,
     strict_ref_counts: Option<bool>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_retry_interval: Option<u64>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_max_retry_frequency: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_initial_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_jitter: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_attempts: Option<u64>,
+    #[cfg(feature = "peer-connect-priority")]
+    peer_connect_ramp_rate: Option<u64>,
     signers: Option<Vec<Box<dyn Signer>>>,
     peering_token: Option<PeerAuthorizationToken>,
     enable_lmdb_state: bool,
@@ -71,6 +270,17 @@ pub struct SplinterDaemonBuilder {
     service_timer_interval: Option<Duration>,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Option<Duration>,
+    #[cfg(feature = "config-reload")]
+    config_reloader: Option<Box<dyn ConfigReloader>>,
+    #[cfg(feature = "daemon-hooks")]
+    hooks: Option<Box<dyn DaemonHooks>>,
+    #[cfg(feature = "daemon-hooks")]
+    disable_ctrlc_handler: bool,
+    startup_timer: Option<StartupTimer>,
+    #[cfg(feature = "config-report")]
+    config_report: Option<Arc<RwLock<ConfigReport>>>,
+    #[cfg(all(feature = "tap", feature = "subsystem-control"))]
+    metrics_switch: Option<InfluxRecorderSwitch>,
 }
 
 impl SplinterDaemonBuilder {
@@ -78,6 +288,329 @@ impl SplinterDaemonBuilder {
         Self::default()
     }
 
+    /// Creates a builder with the settings of the given configuration.
+    ///
+    /// The signers, the peering token and, if reloading is enabled, the configuration reloader
+    /// are not part of the configuration, and must still be provided before the daemon is built.
+    pub fn from_config(config: &Config) -> Self {
+        let mut daemon_builder = SplinterDaemonBuilder::new()
+            .with_state_dir(config.state_dir().to_string())
+            .with_network_endpoints(config.network_endpoints().to_vec())
+            .with_advertised_endpoints(config.advertised_endpoints().to_vec())
+            .with_initial_peers(config.peers().to_vec())
+            .with_node_id(config.node_id().map(String::from))
+            .with_display_name(config.display_name().map(String::from))
+            .with_rest_api_endpoint(config.rest_api_endpoint().to_string())
+            .with_db_url(config.database().to_string())
+            .with_registries(config.registries().to_vec())
+            .with_registry_auto_refresh(config.registry_auto_refresh())
+            .with_registry_forced_refresh(config.registry_forced_refresh())
+            .with_heartbeat(config.heartbeat())
+            .with_admin_timeout(config.admin_timeout())
+            .with_strict_ref_counts(config.strict_ref_counts());
+
+        #[cfg(feature = "peer-ref-counts")]
+        {
+            daemon_builder = daemon_builder
+                .with_peer_retry_interval(config.peer_retry_interval())
+                .with_peer_max_retry_frequency(config.peer_max_retry_frequency());
+        }
+
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            daemon_builder = daemon_builder
+                .with_peer_reconnect_initial_delay(config.peer_reconnect_initial_delay())
+                .with_peer_reconnect_max_delay(config.peer_reconnect_max_delay())
+                .with_peer_reconnect_jitter(config.peer_reconnect_jitter())
+                .with_peer_reconnect_max_attempts(config.peer_reconnect_max_attempts());
+        }
+
+        #[cfg(feature = "peer-connect-priority")]
+        {
+            daemon_builder =
+                daemon_builder.with_peer_connect_ramp_rate(config.peer_connect_ramp_rate());
+        }
+
+        #[cfg(feature = "authorization-handler-allow-keys")]
+        {
+            daemon_builder = daemon_builder.with_config_dir(config.config_dir().to_string());
+        }
+
+        #[cfg(feature = "https-bind")]
+        {
+            daemon_builder = daemon_builder
+                .with_rest_api_server_cert(config.tls_rest_api_cert().to_string())
+                .with_rest_api_server_key(config.tls_rest_api_key().to_string());
+        }
+
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            daemon_builder = daemon_builder
+                .with_rest_api_client_ca(config.tls_rest_api_client_ca().map(ToOwned::to_owned));
+        }
+
+        #[cfg(feature = "service-endpoint")]
+        {
+            daemon_builder =
+                daemon_builder.with_service_endpoint(String::from(config.service_endpoint()))
+        }
+        #[cfg(feature = "registry-auto-populate")]
+        {
+            daemon_builder =
+                daemon_builder.with_registry_auto_populate(config.registry_auto_populate());
+        }
+
+        #[cfg(feature = "registry-signed-entries")]
+        {
+            daemon_builder = daemon_builder
+                .with_registry_require_signatures(config.registry_require_signatures());
+        }
+
+        #[cfg(feature = "rest-api-degraded-mode")]
+        {
+            daemon_builder =
+                daemon_builder.with_rest_api_degraded_mode(config.rest_api_degraded_mode());
+        }
+
+        #[cfg(feature = "rest-api-openapi")]
+        {
+            daemon_builder = daemon_builder.with_rest_api_swagger_ui(config.rest_api_swagger_ui());
+        }
+
+        #[cfg(feature = "grpc")]
+        {
+            daemon_builder =
+                daemon_builder.with_grpc_bind(config.grpc_bind().map(ToOwned::to_owned));
+        }
+
+        #[cfg(feature = "signature-verification-pool")]
+        {
+            daemon_builder = daemon_builder
+                .with_verification_threads(config.verification_threads().map(|v| v as usize));
+        }
+
+        #[cfg(feature = "high-availability")]
+        {
+            daemon_builder = daemon_builder
+                .with_high_availability(config.high_availability())
+                .with_high_availability_interval(config.high_availability_interval());
+        }
+
+        #[cfg(feature = "peer-authorization-policy")]
+        {
+            let to_authorization_type = |authorization: &PeerAuthorization| match authorization {
+                PeerAuthorization::Trust => AuthorizationType::Trust,
+                PeerAuthorization::Challenge => AuthorizationType::Challenge,
+            };
+            daemon_builder = daemon_builder
+                .with_peering_authorization(to_authorization_type(config.peering_authorization()))
+                .with_minimum_authorization(to_authorization_type(config.minimum_authorization()));
+        }
+
+        #[cfg(feature = "rest-api-cors")]
+        {
+            daemon_builder =
+                daemon_builder.with_allow_list(config.allow_list().map(ToOwned::to_owned));
+        }
+
+        #[cfg(feature = "biome-credentials")]
+        {
+            daemon_builder = daemon_builder
+                .with_enable_biome_credentials(config.enable_biome_credentials())
+                .with_biome_refresh_token_retention(config.biome_refresh_token_retention());
+        }
+
+        #[cfg(feature = "oauth")]
+        {
+            daemon_builder = daemon_builder
+                .with_oauth_provider(config.oauth_provider().map(ToOwned::to_owned))
+                .with_oauth_client_id(config.oauth_client_id().map(ToOwned::to_owned))
+                .with_oauth_client_secret(config.oauth_client_secret().map(ToOwned::to_owned))
+                .with_oauth_redirect_url(config.oauth_redirect_url().map(ToOwned::to_owned))
+                .with_oauth_openid_url(config.oauth_openid_url().map(ToOwned::to_owned))
+                .with_oauth_openid_auth_params(
+                    config.oauth_openid_auth_params().map(ToOwned::to_owned),
+                )
+                .with_oauth_openid_scopes(config.oauth_openid_scopes().map(ToOwned::to_owned))
+                .with_oauth_session_retention(config.oauth_session_retention());
+        }
+
+        #[cfg(feature = "biome-notifications")]
+        {
+            daemon_builder = daemon_builder
+                .with_notification_smtp_url(config.notification_smtp_url().map(ToOwned::to_owned))
+                .with_notification_email_from(
+                    config.notification_email_from().map(ToOwned::to_owned),
+                );
+        }
+
+        #[cfg(feature = "ldap")]
+        {
+            daemon_builder = daemon_builder
+                .with_ldap_url(config.ldap_url().map(ToOwned::to_owned))
+                .with_ldap_bind_dn(config.ldap_bind_dn().map(ToOwned::to_owned))
+                .with_ldap_bind_password(config.ldap_bind_password().map(ToOwned::to_owned))
+                .with_ldap_user_search_base(config.ldap_user_search_base().map(ToOwned::to_owned))
+                .with_ldap_user_search_filter(
+                    config.ldap_user_search_filter().map(ToOwned::to_owned),
+                )
+                .with_ldap_group_search_base(config.ldap_group_search_base().map(ToOwned::to_owned))
+                .with_ldap_group_search_filter(
+                    config.ldap_group_search_filter().map(ToOwned::to_owned),
+                )
+                .with_ldap_group_roles(config.ldap_group_roles().map(ToOwned::to_owned));
+        }
+
+        #[cfg(feature = "rest-api-proxy-auth")]
+        {
+            daemon_builder = daemon_builder
+                .with_rest_api_proxy_auth_header(
+                    config.rest_api_proxy_auth_header().map(ToOwned::to_owned),
+                )
+                .with_rest_api_proxy_trusted_addresses(
+                    config
+                        .rest_api_proxy_trusted_addresses()
+                        .map(ToOwned::to_owned),
+                );
+        }
+
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        {
+            daemon_builder = daemon_builder
+                .with_rest_api_client_cert_identities(
+                    config
+                        .rest_api_client_cert_identities()
+                        .map(ToOwned::to_owned),
+                )
+                .with_rest_api_client_cert_roles(
+                    config.rest_api_client_cert_roles().map(ToOwned::to_owned),
+                );
+        }
+
+        #[cfg(feature = "circuit-usage")]
+        {
+            daemon_builder = daemon_builder
+                .with_circuit_usage_message_quota(config.circuit_usage_message_quota())
+                .with_circuit_usage_byte_quota(config.circuit_usage_byte_quota());
+        }
+        #[cfg(feature = "connection-limits")]
+        {
+            daemon_builder = daemon_builder
+                .with_max_inbound_connections(config.max_inbound_connections())
+                .with_max_inbound_connections_per_ip(config.max_inbound_connections_per_ip());
+        }
+        #[cfg(feature = "database-pool-settings")]
+        {
+            daemon_builder = daemon_builder
+                .with_database_pool_size(config.database_pool_size())
+                .with_database_pool_timeout(config.database_pool_timeout())
+                .with_database_pool_min_idle(config.database_pool_min_idle())
+                .with_database_pool_idle_timeout(config.database_pool_idle_timeout());
+        }
+        #[cfg(feature = "clock-skew-detection")]
+        {
+            daemon_builder = daemon_builder
+                .with_clock_skew_warn_threshold(config.clock_skew_warn_threshold())
+                .with_clock_skew_max(config.clock_skew_max());
+        }
+        #[cfg(feature = "admin-webhooks")]
+        {
+            daemon_builder = daemon_builder
+                .with_admin_webhooks(config.admin_webhooks().map(ToOwned::to_owned))
+                .with_admin_webhook_secret(config.admin_webhook_secret().map(ToOwned::to_owned));
+        }
+        #[cfg(feature = "scabbard-anchoring")]
+        {
+            daemon_builder = daemon_builder
+                .with_scabbard_anchor_url(config.scabbard_anchor_url().map(ToOwned::to_owned))
+                .with_scabbard_anchor_interval(config.scabbard_anchor_interval())
+                .with_scabbard_anchor_services(
+                    config.scabbard_anchor_services().map(ToOwned::to_owned),
+                );
+        }
+        #[cfg(feature = "scabbard-batch-validation")]
+        {
+            daemon_builder = daemon_builder.with_scabbard_batch_validator(
+                config.scabbard_batch_validator().map(ToOwned::to_owned),
+            );
+        }
+        #[cfg(feature = "scabbard-receipt-pruning")]
+        {
+            daemon_builder = daemon_builder
+                .with_scabbard_receipt_retention(config.scabbard_receipt_retention())
+                .with_scabbard_receipt_prune_interval(config.scabbard_receipt_prune_interval())
+                .with_scabbard_receipt_archive_dir(
+                    config.scabbard_receipt_archive_dir().map(ToOwned::to_owned),
+                );
+        }
+        #[cfg(feature = "scabbard-snapshot")]
+        {
+            daemon_builder = daemon_builder
+                .with_scabbard_snapshot_dir(config.scabbard_snapshot_dir().map(ToOwned::to_owned))
+                .with_scabbard_snapshot_interval(config.scabbard_snapshot_interval());
+        }
+        #[cfg(feature = "scabbard-state-pruning")]
+        {
+            daemon_builder = daemon_builder
+                .with_scabbard_state_retention_roots(config.scabbard_state_retention_roots())
+                .with_scabbard_state_retention_days(config.scabbard_state_retention_days())
+                .with_scabbard_state_prune_interval(config.scabbard_state_prune_interval());
+        }
+        #[cfg(feature = "service-restart-policy")]
+        {
+            daemon_builder = daemon_builder
+                .with_service_max_restarts(config.service_max_restarts())
+                .with_service_restart_max_backoff(config.service_restart_max_backoff());
+        }
+        #[cfg(feature = "service-transfer")]
+        {
+            daemon_builder =
+                daemon_builder.with_transfer_max_file_size(config.transfer_max_file_size());
+        }
+        #[cfg(feature = "service-type-limits")]
+        {
+            daemon_builder = daemon_builder
+                .with_service_max_instances(config.service_max_instances().map(ToOwned::to_owned))
+                .with_service_thread_budget(config.service_thread_budget().map(ToOwned::to_owned))
+                .with_service_memory_budget(config.service_memory_budget().map(ToOwned::to_owned));
+        }
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        {
+            daemon_builder =
+                daemon_builder.with_admin_member_wait_timeout(config.admin_member_wait_timeout());
+        }
+        #[cfg(feature = "admin-service-proposal-limits")]
+        {
+            daemon_builder = daemon_builder
+                .with_admin_max_circuit_members(config.admin_max_circuit_members())
+                .with_admin_max_circuit_services(config.admin_max_circuit_services())
+                .with_admin_max_circuit_metadata_size(config.admin_max_circuit_metadata_size())
+                .with_admin_max_circuit_size(config.admin_max_circuit_size());
+        }
+        #[cfg(feature = "admin-service-circuit-version")]
+        {
+            daemon_builder =
+                daemon_builder.with_admin_circuit_version(config.admin_circuit_version());
+        }
+        {
+            if config.scabbard_state() == &ScabbardState::Lmdb {
+                daemon_builder = daemon_builder.with_lmdb_state_enabled();
+            }
+            if config.scabbard_autocleanup() {
+                daemon_builder = daemon_builder.with_state_autocleanup_enabled();
+            }
+        }
+
+        #[cfg(feature = "service2")]
+        {
+            daemon_builder = daemon_builder
+                .with_service_timer_interval(config.service_timer_interval())
+                .with_lifecycle_executor_interval(config.lifecycle_executor_interval());
+        }
+
+        daemon_builder
+    }
+
     #[cfg(feature = "authorization-handler-allow-keys")]
     pub fn with_config_dir(mut self, value: String) -> Self {
         self.config_dir = Some(value);
@@ -137,6 +670,12 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn with_rest_api_client_ca(mut self, value: Option<String>) -> Self {
+        self.rest_api_client_ca = value;
+        self
+    }
+
     pub fn with_db_url(mut self, value: String) -> Self {
         self.db_url = Some(value);
         self
@@ -157,6 +696,66 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "registry-auto-populate")]
+    pub fn with_registry_auto_populate(mut self, value: bool) -> Self {
+        self.registry_auto_populate = value;
+        self
+    }
+
+    #[cfg(feature = "registry-signed-entries")]
+    pub fn with_registry_require_signatures(mut self, value: bool) -> Self {
+        self.registry_require_signatures = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-degraded-mode")]
+    pub fn with_rest_api_degraded_mode(mut self, value: bool) -> Self {
+        self.rest_api_degraded_mode = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-openapi")]
+    pub fn with_rest_api_swagger_ui(mut self, value: bool) -> Self {
+        self.rest_api_swagger_ui = value;
+        self
+    }
+
+    #[cfg(feature = "grpc")]
+    pub fn with_grpc_bind(mut self, value: Option<String>) -> Self {
+        self.grpc_bind = value;
+        self
+    }
+
+    #[cfg(feature = "signature-verification-pool")]
+    pub fn with_verification_threads(mut self, value: Option<usize>) -> Self {
+        self.verification_threads = value;
+        self
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn with_high_availability(mut self, value: bool) -> Self {
+        self.high_availability = value;
+        self
+    }
+
+    #[cfg(feature = "high-availability")]
+    pub fn with_high_availability_interval(mut self, value: u64) -> Self {
+        self.high_availability_interval = Some(value);
+        self
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn with_peering_authorization(mut self, value: AuthorizationType) -> Self {
+        self.peering_authorization = Some(value);
+        self
+    }
+
+    #[cfg(feature = "peer-authorization-policy")]
+    pub fn with_minimum_authorization(mut self, value: AuthorizationType) -> Self {
+        self.minimum_authorization = Some(value);
+        self
+    }
+
     pub fn with_heartbeat(mut self, value: u64) -> Self {
         self.heartbeat = Some(value);
         self
@@ -187,6 +786,12 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "biome-credentials")]
+    pub fn with_biome_refresh_token_retention(mut self, value: Duration) -> Self {
+        self.biome_refresh_token_retention = Some(value);
+        self
+    }
+
     #[cfg(feature = "oauth")]
     pub fn with_oauth_provider(mut self, value: Option<String>) -> Self {
         self.oauth_provider = value;
@@ -229,11 +834,362 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    #[cfg(feature = "oauth")]
+    pub fn with_oauth_session_retention(mut self, value: Duration) -> Self {
+        self.oauth_session_retention = Some(value);
+        self
+    }
+
+    #[cfg(feature = "biome-notifications")]
+    pub fn with_notification_smtp_url(mut self, value: Option<String>) -> Self {
+        self.notification_smtp_url = value;
+        self
+    }
+
+    #[cfg(feature = "biome-notifications")]
+    pub fn with_notification_email_from(mut self, value: Option<String>) -> Self {
+        self.notification_email_from = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_url(mut self, value: Option<String>) -> Self {
+        self.ldap_url = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_bind_dn(mut self, value: Option<String>) -> Self {
+        self.ldap_bind_dn = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_bind_password(mut self, value: Option<String>) -> Self {
+        self.ldap_bind_password = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_user_search_base(mut self, value: Option<String>) -> Self {
+        self.ldap_user_search_base = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_user_search_filter(mut self, value: Option<String>) -> Self {
+        self.ldap_user_search_filter = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_group_search_base(mut self, value: Option<String>) -> Self {
+        self.ldap_group_search_base = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_group_search_filter(mut self, value: Option<String>) -> Self {
+        self.ldap_group_search_filter = value;
+        self
+    }
+
+    #[cfg(feature = "ldap")]
+    pub fn with_ldap_group_roles(mut self, value: Option<Vec<(String, String)>>) -> Self {
+        self.ldap_group_roles = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub fn with_rest_api_proxy_auth_header(mut self, value: Option<String>) -> Self {
+        self.rest_api_proxy_auth_header = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-proxy-auth")]
+    pub fn with_rest_api_proxy_trusted_addresses(mut self, value: Option<Vec<String>>) -> Self {
+        self.rest_api_proxy_trusted_addresses = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn with_rest_api_client_cert_identities(
+        mut self,
+        value: Option<Vec<(String, String)>>,
+    ) -> Self {
+        self.rest_api_client_cert_identities = value;
+        self
+    }
+
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    pub fn with_rest_api_client_cert_roles(mut self, value: Option<Vec<(String, String)>>) -> Self {
+        self.rest_api_client_cert_roles = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    pub fn with_circuit_usage_message_quota(mut self, value: Option<u64>) -> Self {
+        self.circuit_usage_message_quota = value;
+        self
+    }
+
+    #[cfg(feature = "circuit-usage")]
+    pub fn with_circuit_usage_byte_quota(mut self, value: Option<u64>) -> Self {
+        self.circuit_usage_byte_quota = value;
+        self
+    }
+
+    #[cfg(feature = "connection-limits")]
+    pub fn with_max_inbound_connections(mut self, value: Option<u64>) -> Self {
+        self.max_inbound_connections = value;
+        self
+    }
+
+    #[cfg(feature = "connection-limits")]
+    pub fn with_max_inbound_connections_per_ip(mut self, value: Option<u64>) -> Self {
+        self.max_inbound_connections_per_ip = value;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn with_database_pool_size(mut self, value: Option<u64>) -> Self {
+        self.database_pool_size = value;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn with_database_pool_timeout(mut self, value: Option<u64>) -> Self {
+        self.database_pool_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn with_database_pool_min_idle(mut self, value: Option<u64>) -> Self {
+        self.database_pool_min_idle = value;
+        self
+    }
+
+    #[cfg(feature = "database-pool-settings")]
+    pub fn with_database_pool_idle_timeout(mut self, value: Option<u64>) -> Self {
+        self.database_pool_idle_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn with_clock_skew_warn_threshold(mut self, value: Option<u64>) -> Self {
+        self.clock_skew_warn_threshold = value;
+        self
+    }
+
+    #[cfg(feature = "clock-skew-detection")]
+    pub fn with_clock_skew_max(mut self, value: Option<u64>) -> Self {
+        self.clock_skew_max = value;
+        self
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    pub fn with_admin_webhooks(mut self, value: Option<Vec<String>>) -> Self {
+        self.admin_webhooks = value;
+        self
+    }
+
+    #[cfg(feature = "admin-webhooks")]
+    pub fn with_admin_webhook_secret(mut self, value: Option<String>) -> Self {
+        self.admin_webhook_secret = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn with_scabbard_anchor_url(mut self, value: Option<String>) -> Self {
+        self.scabbard_anchor_url = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn with_scabbard_anchor_interval(mut self, value: Option<u64>) -> Self {
+        self.scabbard_anchor_interval = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-anchoring")]
+    pub fn with_scabbard_anchor_services(mut self, value: Option<Vec<String>>) -> Self {
+        self.scabbard_anchor_services = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-batch-validation")]
+    pub fn with_scabbard_batch_validator(mut self, value: Option<String>) -> Self {
+        self.scabbard_batch_validator = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn with_scabbard_receipt_retention(mut self, value: Option<u64>) -> Self {
+        self.scabbard_receipt_retention = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn with_scabbard_receipt_prune_interval(mut self, value: Option<u64>) -> Self {
+        self.scabbard_receipt_prune_interval = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    pub fn with_scabbard_receipt_archive_dir(mut self, value: Option<String>) -> Self {
+        self.scabbard_receipt_archive_dir = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    pub fn with_scabbard_snapshot_dir(mut self, value: Option<String>) -> Self {
+        self.scabbard_snapshot_dir = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-snapshot")]
+    pub fn with_scabbard_snapshot_interval(mut self, value: Option<u64>) -> Self {
+        self.scabbard_snapshot_interval = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn with_scabbard_state_retention_roots(mut self, value: Option<u64>) -> Self {
+        self.scabbard_state_retention_roots = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn with_scabbard_state_retention_days(mut self, value: Option<u64>) -> Self {
+        self.scabbard_state_retention_days = value;
+        self
+    }
+
+    #[cfg(feature = "scabbard-state-pruning")]
+    pub fn with_scabbard_state_prune_interval(mut self, value: Option<u64>) -> Self {
+        self.scabbard_state_prune_interval = value;
+        self
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    pub fn with_service_max_restarts(mut self, value: Option<u32>) -> Self {
+        self.service_max_restarts = value;
+        self
+    }
+
+    #[cfg(feature = "service-restart-policy")]
+    pub fn with_service_restart_max_backoff(mut self, value: Option<u64>) -> Self {
+        self.service_restart_max_backoff = value;
+        self
+    }
+
+    #[cfg(feature = "service-transfer")]
+    pub fn with_transfer_max_file_size(mut self, value: Option<u64>) -> Self {
+        self.transfer_max_file_size = value;
+        self
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn with_service_max_instances(mut self, value: Option<Vec<(String, String)>>) -> Self {
+        self.service_max_instances = value;
+        self
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn with_service_thread_budget(mut self, value: Option<Vec<(String, String)>>) -> Self {
+        self.service_thread_budget = value;
+        self
+    }
+
+    #[cfg(feature = "service-type-limits")]
+    pub fn with_service_memory_budget(mut self, value: Option<Vec<(String, String)>>) -> Self {
+        self.service_memory_budget = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    pub fn with_admin_member_wait_timeout(mut self, value: Option<u64>) -> Self {
+        self.admin_member_wait_timeout = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn with_admin_max_circuit_members(mut self, value: Option<u64>) -> Self {
+        self.admin_max_circuit_members = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn with_admin_max_circuit_services(mut self, value: Option<u64>) -> Self {
+        self.admin_max_circuit_services = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn with_admin_max_circuit_metadata_size(mut self, value: Option<u64>) -> Self {
+        self.admin_max_circuit_metadata_size = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-proposal-limits")]
+    pub fn with_admin_max_circuit_size(mut self, value: Option<u64>) -> Self {
+        self.admin_max_circuit_size = value;
+        self
+    }
+
+    #[cfg(feature = "admin-service-circuit-version")]
+    pub fn with_admin_circuit_version(mut self, value: Option<u64>) -> Self {
+        self.admin_circuit_version = value;
+        self
+    }
+
     pub fn with_strict_ref_counts(mut self, strict_ref_counts: bool) -> Self {
         self.strict_ref_counts = Some(strict_ref_counts);
         self
     }
 
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn with_peer_retry_interval(mut self, value: Option<u64>) -> Self {
+        self.peer_retry_interval = value;
+        self
+    }
+
+    #[cfg(feature = "peer-ref-counts")]
+    pub fn with_peer_max_retry_frequency(mut self, value: Option<u64>) -> Self {
+        self.peer_max_retry_frequency = value;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn with_peer_reconnect_initial_delay(mut self, value: Option<u64>) -> Self {
+        self.peer_reconnect_initial_delay = value;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn with_peer_reconnect_max_delay(mut self, value: Option<u64>) -> Self {
+        self.peer_reconnect_max_delay = value;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn with_peer_reconnect_jitter(mut self, value: Option<u64>) -> Self {
+        self.peer_reconnect_jitter = value;
+        self
+    }
+
+    #[cfg(feature = "peer-reconnect-policy")]
+    pub fn with_peer_reconnect_max_attempts(mut self, value: Option<u64>) -> Self {
+        self.peer_reconnect_max_attempts = value;
+        self
+    }
+
+    #[cfg(feature = "peer-connect-priority")]
+    pub fn with_peer_connect_ramp_rate(mut self, value: Option<u64>) -> Self {
+        self.peer_connect_ramp_rate = value;
+        self
+    }
+
     pub fn with_signers(mut self, value: Vec<Box<dyn Signer>>) -> Self {
         self.signers = Some(value);
         self
@@ -269,11 +1225,70 @@ impl SplinterDaemonBuilder {
         self
     }
 
+    /// Reload the configuration with the given reloader when the daemon receives SIGHUP.
+    #[cfg(feature = "config-reload")]
+    pub fn with_config_reloader(mut self, config_reloader: Box<dyn ConfigReloader>) -> Self {
+        self.config_reloader = Some(config_reloader);
+        self
+    }
+
+    /// Call the given hooks as the daemon starts and stops.
+    #[cfg(feature = "daemon-hooks")]
+    pub fn with_hooks(mut self, hooks: Box<dyn DaemonHooks>) -> Self {
+        self.hooks = Some(hooks);
+        self
+    }
+
+    /// Do not stop the daemon on Ctrl-C, for applications that handle signals themselves. The
+    /// daemon is then stopped with the shutdown handle given to its hooks.
+    #[cfg(feature = "daemon-hooks")]
+    pub fn with_ctrlc_handler_disabled(mut self) -> Self {
+        self.disable_ctrlc_handler = true;
+        self
+    }
+
+    /// Continue timing startup with the given timer, so that the phases completed before the
+    /// daemon was built are included in the startup timing report.
+    pub fn with_startup_timer(mut self, startup_timer: StartupTimer) -> Self {
+        self.startup_timer = Some(startup_timer);
+        self
+    }
+
+    /// Serve the given configuration report at `GET /config`. The report is shared, so that it
+    /// can be updated when the configuration is reloaded.
+    #[cfg(feature = "config-report")]
+    pub fn with_config_report(mut self, config_report: Arc<RwLock<ConfigReport>>) -> Self {
+        self.config_report = Some(config_report);
+        self
+    }
+
+    /// Add the switch of the metrics recorder as the `metrics-recorder` subsystem, so that
+    /// recording can be stopped and started through the REST API.
+    #[cfg(all(feature = "tap", feature = "subsystem-control"))]
+    pub fn with_metrics_switch(mut self, metrics_switch: InfluxRecorderSwitch) -> Self {
+        self.metrics_switch = Some(metrics_switch);
+        self
+    }
+
     pub fn build(self) -> Result<SplinterDaemon, CreateError> {
         let heartbeat = self.heartbeat.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: heartbeat".to_string())
         })?;
 
+        // The interval is only required if the daemon is one of an active-passive pair
+        #[cfg(feature = "high-availability")]
+        let high_availability = if self.high_availability {
+            Some(Duration::from_secs(
+                self.high_availability_interval.ok_or_else(|| {
+                    CreateError::MissingRequiredField(
+                        "Missing field: high_availability_interval".to_string(),
+                    )
+                })?,
+            ))
+        } else {
+            None
+        };
+
         let mesh = Mesh::new(512, 128);
 
         #[cfg(feature = "authorization-handler-allow-keys")]
@@ -290,6 +1305,20 @@ impl SplinterDaemonBuilder {
             CreateError::MissingRequiredField("Missing field: service_endpoint".to_string())
         })?;
 
+        #[cfg(feature = "service-endpoint")]
+        if service_endpoint.starts_with("unix://") {
+            #[cfg(all(unix, feature = "unix-transport"))]
+            validate_unix_endpoint(&service_endpoint).map_err(|err| {
+                CreateError::InvalidArgument(format!("Invalid service endpoint: {}", err))
+            })?;
+            #[cfg(not(all(unix, feature = "unix-transport")))]
+            return Err(CreateError::InvalidArgument(
+                "Invalid service endpoint, 'unix://' requires splinterd to be built with the \
+                \"unix-transport\" feature"
+                    .to_string(),
+            ));
+        }
+
         let network_endpoints = self.network_endpoints.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: network_endpoints".to_string())
         })?;
@@ -340,6 +1369,383 @@ impl SplinterDaemonBuilder {
             (None, None) => None,
         };
 
+        #[cfg(feature = "rest-api-proxy-auth")]
+        let rest_api_proxy_trusted_addresses = self
+            .rest_api_proxy_trusted_addresses
+            .unwrap_or_default()
+            .iter()
+            .map(|address| {
+                address.parse::<IpAddr>().map_err(|_| {
+                    CreateError::InvalidArgument(format!(
+                        "Invalid REST API proxy trusted address: {}",
+                        address
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        #[cfg(feature = "rest-api-proxy-auth")]
+        if self.rest_api_proxy_auth_header.is_some() && rest_api_proxy_trusted_addresses.is_empty()
+        {
+            return Err(CreateError::MissingRequiredField(
+                "rest_api_proxy_trusted_addresses must be set when rest_api_proxy_auth_header is \
+                 set"
+                .into(),
+            ));
+        }
+
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        let rest_api_client_cert_identities = self
+            .rest_api_client_cert_identities
+            .unwrap_or_default()
+            .into_iter()
+            .map(|(certificate, user)| {
+                certificate
+                    .parse::<CertificateMatcher>()
+                    .map(|matcher| (matcher, user))
+                    .map_err(|err| {
+                        CreateError::InvalidArgument(format!(
+                            "Invalid REST API client certificate identity: {}",
+                            err
+                        ))
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        #[cfg(feature = "database-pool-settings")]
+        let database_pool_settings = {
+            let mut settings = ConnectionPoolSettings::new();
+            if let Some(size) = self.database_pool_size {
+                settings = settings.with_max_size(u32::try_from(size).map_err(|_| {
+                    CreateError::InvalidArgument(format!("Invalid database pool size: {}", size))
+                })?);
+            }
+            if let Some(timeout) = self.database_pool_timeout {
+                settings = settings.with_connection_timeout(Duration::from_secs(timeout));
+            }
+            if let Some(min_idle) = self.database_pool_min_idle {
+                settings = settings.with_min_idle(u32::try_from(min_idle).map_err(|_| {
+                    CreateError::InvalidArgument(format!(
+                        "Invalid database pool minimum idle connections: {}",
+                        min_idle
+                    ))
+                })?);
+            }
+            if let Some(timeout) = self.database_pool_idle_timeout {
+                settings = settings.with_idle_timeout(Duration::from_secs(timeout));
+            }
+            settings
+        };
+
+        #[cfg(feature = "admin-webhooks")]
+        let admin_webhooks = {
+            let secret = self.admin_webhook_secret;
+            self.admin_webhooks
+                .unwrap_or_default()
+                .iter()
+                .map(|url| {
+                    let webhook = Webhook::new(url).map_err(|err| {
+                        CreateError::InvalidArgument(format!("Invalid admin webhook: {}", err))
+                    })?;
+                    Ok(match &secret {
+                        Some(secret) => webhook.with_secret(secret.clone()),
+                        None => webhook,
+                    })
+                })
+                .collect::<Result<Vec<_>, CreateError>>()?
+        };
+
+        #[cfg(feature = "scabbard-anchoring")]
+        let scabbard_anchoring = match self.scabbard_anchor_url {
+            Some(url) => {
+                let publisher = HttpAnchorPublisher::new(&url).map_err(|err| {
+                    CreateError::InvalidArgument(format!("Invalid scabbard anchor URL: {}", err))
+                })?;
+                let mut anchoring = AnchorConfig::new(Arc::new(publisher));
+                if let Some(interval) = self.scabbard_anchor_interval {
+                    if interval == 0 {
+                        return Err(CreateError::InvalidArgument(
+                            "scabbard_anchor_interval must be greater than 0".into(),
+                        ));
+                    }
+                    anchoring = anchoring.with_interval(Duration::from_secs(interval));
+                }
+                if let Some(services) = self.scabbard_anchor_services {
+                    anchoring = anchoring.with_services(services);
+                }
+                Some(anchoring)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "scabbard-batch-validation")]
+        let scabbard_batch_validator = match self.scabbard_batch_validator {
+            Some(validator) => {
+                let validator: Arc<dyn BatchValidator> =
+                    if validator.starts_with("http://") || validator.starts_with("https://") {
+                        Arc::new(HttpBatchValidator::new(&validator).map_err(|err| {
+                            CreateError::InvalidArgument(format!(
+                                "Invalid scabbard batch validator URL: {}",
+                                err
+                            ))
+                        })?)
+                    } else {
+                        Arc::new(WasmBatchValidator::from_file(&validator).map_err(|err| {
+                            CreateError::InvalidArgument(format!(
+                                "Invalid scabbard batch validator module: {}",
+                                err
+                            ))
+                        })?)
+                    };
+                Some(validator)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "scabbard-receipt-pruning")]
+        let scabbard_receipt_retention = match self.scabbard_receipt_retention {
+            Some(retain) => {
+                let mut retention = ReceiptRetention::new(retain);
+                if let Some(interval) = self.scabbard_receipt_prune_interval {
+                    if interval == 0 {
+                        return Err(CreateError::InvalidArgument(
+                            "scabbard_receipt_prune_interval must be greater than 0".into(),
+                        ));
+                    }
+                    retention = retention.with_interval(Duration::from_secs(interval));
+                }
+                if let Some(dir) = self.scabbard_receipt_archive_dir {
+                    let archiver = FileReceiptArchiver::new(&dir).map_err(|err| {
+                        CreateError::InvalidArgument(format!(
+                            "Invalid scabbard receipt archive directory: {}",
+                            err
+                        ))
+                    })?;
+                    retention = retention.with_archiver(Arc::new(archiver));
+                }
+                Some(retention)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "scabbard-snapshot")]
+        let scabbard_snapshots = match self.scabbard_snapshot_dir {
+            Some(dir) => {
+                let mut snapshots = SnapshotConfig::new(&dir).map_err(|err| {
+                    CreateError::InvalidArgument(format!(
+                        "Invalid scabbard snapshot directory: {}",
+                        err
+                    ))
+                })?;
+                if let Some(interval) = self.scabbard_snapshot_interval {
+                    if interval == 0 {
+                        return Err(CreateError::InvalidArgument(
+                            "scabbard_snapshot_interval must be greater than 0".into(),
+                        ));
+                    }
+                    snapshots = snapshots.with_interval(Duration::from_secs(interval));
+                }
+                Some(snapshots)
+            }
+            None => None,
+        };
+
+        #[cfg(feature = "scabbard-state-pruning")]
+        let scabbard_state_retention = if self.scabbard_state_retention_roots.is_some()
+            || self.scabbard_state_retention_days.is_some()
+            || self.scabbard_state_prune_interval.is_some()
+        {
+            let mut retention = StateRetention::new();
+            if let Some(roots) = self.scabbard_state_retention_roots {
+                retention = retention.with_max_roots(roots as usize);
+            }
+            if let Some(days) = self.scabbard_state_retention_days {
+                retention = retention.with_max_age(Duration::from_secs(days * 24 * 60 * 60));
+            }
+            if let Some(interval) = self.scabbard_state_prune_interval {
+                if interval == 0 {
+                    return Err(CreateError::InvalidArgument(
+                        "scabbard_state_prune_interval must be greater than 0".into(),
+                    ));
+                }
+                retention = retention.with_interval(Duration::from_secs(interval));
+            }
+            Some(retention)
+        } else {
+            None
+        };
+
+        #[cfg(feature = "service-restart-policy")]
+        let service_restart_policy =
+            if self.service_max_restarts.is_some() || self.service_restart_max_backoff.is_some() {
+                let mut policy = RestartPolicy::new();
+                if let Some(max_restarts) = self.service_max_restarts {
+                    policy = policy.with_max_restarts(max_restarts);
+                }
+                if let Some(max_backoff) = self.service_restart_max_backoff {
+                    if max_backoff == 0 {
+                        return Err(CreateError::InvalidArgument(
+                            "service_restart_max_backoff must be greater than 0".into(),
+                        ));
+                    }
+                    policy = policy.with_max_backoff(Duration::from_secs(max_backoff));
+                }
+                Some(policy)
+            } else {
+                None
+            };
+
+        #[cfg(feature = "service-transfer")]
+        let transfer_max_file_size = match self.transfer_max_file_size {
+            Some(0) => {
+                return Err(CreateError::InvalidArgument(
+                    "transfer_max_file_size must be greater than 0".into(),
+                ))
+            }
+            Some(size) => size,
+            None => transfer::DEFAULT_MAX_FILE_SIZE,
+        };
+
+        #[cfg(feature = "service-type-limits")]
+        let service_type_limits = {
+            let mut service_type_limits = HashMap::<String, ServiceTypeLimits>::new();
+            for (service_type, max_instances) in
+                parse_service_type_values("service_max_instances", &self.service_max_instances)?
+            {
+                let limits = service_type_limits
+                    .remove(&service_type)
+                    .unwrap_or_default();
+                service_type_limits.insert(
+                    service_type,
+                    limits.with_max_instances(max_instances as usize),
+                );
+            }
+            for (service_type, thread_budget) in
+                parse_service_type_values("service_thread_budget", &self.service_thread_budget)?
+            {
+                let limits = service_type_limits
+                    .remove(&service_type)
+                    .unwrap_or_default();
+                service_type_limits.insert(
+                    service_type,
+                    limits.with_thread_budget(thread_budget as usize),
+                );
+            }
+            for (service_type, memory_budget) in
+                parse_service_type_values("service_memory_budget", &self.service_memory_budget)?
+            {
+                let limits = service_type_limits
+                    .remove(&service_type)
+                    .unwrap_or_default();
+                service_type_limits.insert(service_type, limits.with_memory_budget(memory_budget));
+            }
+            service_type_limits
+        };
+
+        #[cfg(feature = "clock-skew-detection")]
+        let clock_skew_monitor = {
+            let mut monitor = ClockSkewMonitor::new();
+            if let Some(warn_threshold) = self.clock_skew_warn_threshold {
+                monitor = monitor.with_warn_threshold(Duration::from_secs(warn_threshold));
+            }
+            if let Some(max) = self.clock_skew_max {
+                if max < monitor.warn_threshold().as_secs() {
+                    return Err(CreateError::InvalidArgument(
+                        "clock_skew_max must not be less than clock_skew_warn_threshold".into(),
+                    ));
+                }
+                monitor = monitor.with_max_skew(Duration::from_secs(max));
+            }
+            monitor
+        };
+
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        let admin_member_wait_timeout = match self.admin_member_wait_timeout {
+            Some(0) => {
+                return Err(CreateError::InvalidArgument(
+                    "admin_member_wait_timeout must be greater than 0".into(),
+                ))
+            }
+            timeout => timeout.map(Duration::from_secs),
+        };
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        let admin_proposal_limits = {
+            let mut limits = None;
+            match self.admin_max_circuit_members {
+                Some(0) => {
+                    return Err(CreateError::InvalidArgument(
+                        "admin_max_circuit_members must be greater than 0".into(),
+                    ))
+                }
+                Some(value) => {
+                    limits = Some(
+                        limits
+                            .unwrap_or_else(ProposalLimits::new)
+                            .with_max_members(value as usize),
+                    )
+                }
+                None => (),
+            }
+            match self.admin_max_circuit_services {
+                Some(0) => {
+                    return Err(CreateError::InvalidArgument(
+                        "admin_max_circuit_services must be greater than 0".into(),
+                    ))
+                }
+                Some(value) => {
+                    limits = Some(
+                        limits
+                            .unwrap_or_else(ProposalLimits::new)
+                            .with_max_services(value as usize),
+                    )
+                }
+                None => (),
+            }
+            match self.admin_max_circuit_metadata_size {
+                Some(0) => {
+                    return Err(CreateError::InvalidArgument(
+                        "admin_max_circuit_metadata_size must be greater than 0".into(),
+                    ))
+                }
+                Some(value) => {
+                    limits = Some(
+                        limits
+                            .unwrap_or_else(ProposalLimits::new)
+                            .with_max_metadata_size(value as usize),
+                    )
+                }
+                None => (),
+            }
+            match self.admin_max_circuit_size {
+                Some(0) => {
+                    return Err(CreateError::InvalidArgument(
+                        "admin_max_circuit_size must be greater than 0".into(),
+                    ))
+                }
+                Some(value) => {
+                    limits = Some(
+                        limits
+                            .unwrap_or_else(ProposalLimits::new)
+                            .with_max_circuit_size(value as usize),
+                    )
+                }
+                None => (),
+            }
+            limits
+        };
+
+        #[cfg(feature = "admin-service-circuit-version")]
+        let admin_circuit_version = match self.admin_circuit_version {
+            Some(version) if version == 0 || version > CIRCUIT_PROTOCOL_VERSION as u64 => {
+                return Err(CreateError::InvalidArgument(format!(
+                    "admin_circuit_version must be between 1 and {}",
+                    CIRCUIT_PROTOCOL_VERSION
+                )))
+            }
+            version => version.map(|version| version as i32),
+        };
+
         let db_url = self
             .db_url
             .ok_or_else(|| CreateError::MissingRequiredField("Missing field: db_url".to_string()))?
@@ -361,6 +1767,19 @@ impl SplinterDaemonBuilder {
             CreateError::MissingRequiredField("Missing field: enable_biome_credentials".to_string())
         })?;
 
+        #[cfg(feature = "biome-credentials")]
+        let biome_refresh_token_retention =
+            self.biome_refresh_token_retention.ok_or_else(|| {
+                CreateError::MissingRequiredField(
+                    "Missing field: biome_refresh_token_retention".to_string(),
+                )
+            })?;
+
+        #[cfg(feature = "oauth")]
+        let oauth_session_retention = self.oauth_session_retention.ok_or_else(|| {
+            CreateError::MissingRequiredField("Missing field: oauth_session_retention".to_string())
+        })?;
+
         let strict_ref_counts = self.strict_ref_counts.ok_or_else(|| {
             CreateError::MissingRequiredField("Missing field: strict_ref_counts".to_string())
         })?;
@@ -400,15 +1819,41 @@ impl SplinterDaemonBuilder {
             rest_api_endpoint,
             #[cfg(feature = "https-bind")]
             rest_api_ssl_settings,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            rest_api_client_ca: self.rest_api_client_ca,
             db_url,
             registries: self.registries,
             registry_auto_refresh,
             registry_forced_refresh,
+            #[cfg(feature = "registry-auto-populate")]
+            registry_auto_populate: self.registry_auto_populate,
+            #[cfg(feature = "registry-signed-entries")]
+            registry_require_signatures: self.registry_require_signatures,
+            #[cfg(feature = "rest-api-degraded-mode")]
+            rest_api_degraded_mode: self.rest_api_degraded_mode,
+            #[cfg(feature = "rest-api-openapi")]
+            rest_api_swagger_ui: self.rest_api_swagger_ui,
+            #[cfg(feature = "grpc")]
+            grpc_bind: self.grpc_bind,
+            #[cfg(feature = "signature-verification-pool")]
+            verification_threads: self.verification_threads,
+            #[cfg(feature = "high-availability")]
+            high_availability,
+            #[cfg(feature = "peer-authorization-policy")]
+            peering_authorization: self
+                .peering_authorization
+                .unwrap_or(AuthorizationType::Challenge),
+            #[cfg(feature = "peer-authorization-policy")]
+            minimum_authorization: self
+                .minimum_authorization
+                .unwrap_or(AuthorizationType::Trust),
             admin_timeout: self.admin_timeout,
             #[cfg(feature = "rest-api-cors")]
             allow_list: self.allow_list,
             #[cfg(feature = "biome-credentials")]
             enable_biome_credentials,
+            #[cfg(feature = "biome-credentials")]
+            biome_refresh_token_retention,
             #[cfg(feature = "oauth")]
             oauth_provider: self.oauth_provider,
             #[cfg(feature = "oauth")]
@@ -423,8 +1868,88 @@ impl SplinterDaemonBuilder {
             oauth_openid_auth_params: self.oauth_openid_auth_params,
             #[cfg(feature = "oauth")]
             oauth_openid_scopes: self.oauth_openid_scopes,
+            #[cfg(feature = "oauth")]
+            oauth_session_retention,
+            #[cfg(feature = "biome-notifications")]
+            notification_smtp_url: self.notification_smtp_url,
+            #[cfg(feature = "biome-notifications")]
+            notification_email_from: self.notification_email_from,
+            #[cfg(feature = "ldap")]
+            ldap_url: self.ldap_url,
+            #[cfg(feature = "ldap")]
+            ldap_bind_dn: self.ldap_bind_dn,
+            #[cfg(feature = "ldap")]
+            ldap_bind_password: self.ldap_bind_password,
+            #[cfg(feature = "ldap")]
+            ldap_user_search_base: self.ldap_user_search_base,
+            #[cfg(feature = "ldap")]
+            ldap_user_search_filter: self.ldap_user_search_filter,
+            #[cfg(feature = "ldap")]
+            ldap_group_search_base: self.ldap_group_search_base,
+            #[cfg(feature = "ldap")]
+            ldap_group_search_filter: self.ldap_group_search_filter,
+            #[cfg(feature = "ldap")]
+            ldap_group_roles: self.ldap_group_roles,
+            #[cfg(feature = "rest-api-proxy-auth")]
+            rest_api_proxy_auth_header: self.rest_api_proxy_auth_header,
+            #[cfg(feature = "rest-api-proxy-auth")]
+            rest_api_proxy_trusted_addresses,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            rest_api_client_cert_identities,
+            #[cfg(feature = "rest-api-client-cert-auth")]
+            rest_api_client_cert_roles: self.rest_api_client_cert_roles.unwrap_or_default(),
+            #[cfg(feature = "circuit-usage")]
+            circuit_usage_message_quota: self.circuit_usage_message_quota,
+            #[cfg(feature = "circuit-usage")]
+            circuit_usage_byte_quota: self.circuit_usage_byte_quota,
+            #[cfg(feature = "connection-limits")]
+            max_inbound_connections: self.max_inbound_connections,
+            #[cfg(feature = "connection-limits")]
+            max_inbound_connections_per_ip: self.max_inbound_connections_per_ip,
+            #[cfg(feature = "database-pool-settings")]
+            database_pool_settings,
+            #[cfg(feature = "clock-skew-detection")]
+            clock_skew_monitor,
+            #[cfg(feature = "admin-webhooks")]
+            admin_webhooks,
+            #[cfg(feature = "scabbard-anchoring")]
+            scabbard_anchoring,
+            #[cfg(feature = "scabbard-batch-validation")]
+            scabbard_batch_validator,
+            #[cfg(feature = "scabbard-receipt-pruning")]
+            scabbard_receipt_retention,
+            #[cfg(feature = "scabbard-snapshot")]
+            scabbard_snapshots,
+            #[cfg(feature = "scabbard-state-pruning")]
+            scabbard_state_retention,
+            #[cfg(feature = "service-restart-policy")]
+            service_restart_policy,
+            #[cfg(feature = "service-transfer")]
+            transfer_max_file_size,
+            #[cfg(feature = "service-type-limits")]
+            service_type_limits,
+            #[cfg(feature = "admin-service-deferred-proposals")]
+            admin_member_wait_timeout,
+            #[cfg(feature = "admin-service-proposal-limits")]
+            admin_proposal_limits,
+            #[cfg(feature = "admin-service-circuit-version")]
+            admin_circuit_version,
             heartbeat,
             strict_ref_counts,
+            #[cfg(feature = "peer-ref-counts")]
+            peer_retry_interval: self.peer_retry_interval,
+            #[cfg(feature = "peer-ref-counts")]
+            peer_max_retry_frequency: self.peer_max_retry_frequency,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_initial_delay: self.peer_reconnect_initial_delay,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_max_delay: self.peer_reconnect_max_delay,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_jitter: self.peer_reconnect_jitter,
+            #[cfg(feature = "peer-reconnect-policy")]
+            peer_reconnect_max_attempts: self.peer_reconnect_max_attempts,
+            #[cfg(feature = "peer-connect-priority")]
+            peer_connect_ramp_rate: self.peer_connect_ramp_rate,
             signers,
             peering_token,
             enable_lmdb_state: self.enable_lmdb_state,
@@ -433,6 +1958,37 @@ impl SplinterDaemonBuilder {
             service_timer_interval,
             #[cfg(feature = "service2")]
             lifecycle_executor_interval,
+            #[cfg(feature = "config-reload")]
+            config_reloader: self.config_reloader,
+            #[cfg(feature = "daemon-hooks")]
+            hooks: self.hooks,
+            #[cfg(feature = "daemon-hooks")]
+            disable_ctrlc_handler: self.disable_ctrlc_handler,
+            startup_timer: self.startup_timer.unwrap_or_default(),
+            #[cfg(feature = "config-report")]
+            config_report: self.config_report,
+            #[cfg(all(feature = "tap", feature = "subsystem-control"))]
+            metrics_switch: self.metrics_switch,
         })
     }
 }
+
+/// Parses the values of an option given as `(service type, number)` pairs, each of which must be
+/// a positive number.
+#[cfg(feature = "service-type-limits")]
+fn parse_service_type_values(
+    name: &str,
+    values: &Option<Vec<(String, String)>>,
+) -> Result<Vec<(String, u64)>, CreateError> {
+    values
+        .iter()
+        .flatten()
+        .map(|(service_type, value)| match value.parse::<u64>() {
+            Ok(number) if number > 0 => Ok((service_type.clone(), number)),
+            _ => Err(CreateError::InvalidArgument(format!(
+                "{} for {} must be a positive number: {}",
+                name, service_type, value
+            ))),
+        })
+        .collect()
+}
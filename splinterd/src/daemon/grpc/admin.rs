@@ -0,0 +1,319 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The gRPC `Admin` service, which submits circuit proposals and votes to the admin service and
+//! answers circuit and proposal queries from the admin service store.
+
+use protobuf::Message;
+use splinter::admin::service::{AdminCommands, AdminServiceError};
+use splinter::admin::store::{
+    AdminServiceStore, Circuit, CircuitPredicate, CircuitProposal, CircuitStatus, ProposalType,
+    Vote,
+};
+use splinter::protos::admin::CircuitManagementPayload;
+use splinter::service::instance::ServiceError;
+use tonic::{Request, Response, Status};
+
+use super::proto;
+use super::proto::admin_server::Admin;
+use super::{blocking, to_hex, GrpcAuthorizer, CIRCUIT_READ_PERMISSION, CIRCUIT_WRITE_PERMISSION};
+
+pub struct AdminGrpcService {
+    authorizer: GrpcAuthorizer,
+    admin_commands: Box<dyn AdminCommands>,
+    store: Box<dyn AdminServiceStore>,
+}
+
+impl AdminGrpcService {
+    pub fn new(
+        authorizer: GrpcAuthorizer,
+        admin_commands: Box<dyn AdminCommands>,
+        store: Box<dyn AdminServiceStore>,
+    ) -> Self {
+        Self {
+            authorizer,
+            admin_commands,
+            store,
+        }
+    }
+
+    /// Parses the payload and submits it to the admin service, checking that it is a vote or not
+    /// as expected.
+    async fn submit_payload(
+        &self,
+        request: Request<proto::SubmitPayloadRequest>,
+        vote: bool,
+    ) -> Result<Response<proto::SubmitPayloadResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), CIRCUIT_WRITE_PERMISSION)?;
+
+        let payload = CircuitManagementPayload::parse_from_bytes(
+            &request.into_inner().circuit_management_payload,
+        )
+        .map_err(|err| Status::invalid_argument(format!("Failed to parse payload: {}", err)))?;
+
+        if payload.has_circuit_vote_request() != vote {
+            return Err(Status::invalid_argument(if vote {
+                "Payload is not a vote; use SubmitProposal"
+            } else {
+                "Payload is a vote; use VoteOnProposal"
+            }));
+        }
+
+        let admin_commands = self.admin_commands.clone_boxed();
+        blocking(move || admin_commands.submit_circuit_change(payload))
+            .await?
+            .map_err(|err| match err {
+                AdminServiceError::ServiceError(ServiceError::UnableToHandleMessage(err)) => {
+                    debug!("{}", err);
+                    Status::invalid_argument(format!("Unable to handle message: {}", err))
+                }
+                AdminServiceError::ServiceError(ServiceError::InvalidMessageFormat(err)) => {
+                    Status::invalid_argument(format!("Failed to parse payload: {}", err))
+                }
+                err => {
+                    error!("{}", err);
+                    Status::internal("Unable to submit payload")
+                }
+            })?;
+
+        Ok(Response::new(proto::SubmitPayloadResponse {}))
+    }
+}
+
+#[tonic::async_trait]
+impl Admin for AdminGrpcService {
+    async fn submit_proposal(
+        &self,
+        request: Request<proto::SubmitPayloadRequest>,
+    ) -> Result<Response<proto::SubmitPayloadResponse>, Status> {
+        self.submit_payload(request, false).await
+    }
+
+    async fn vote_on_proposal(
+        &self,
+        request: Request<proto::SubmitPayloadRequest>,
+    ) -> Result<Response<proto::SubmitPayloadResponse>, Status> {
+        self.submit_payload(request, true).await
+    }
+
+    async fn list_circuits(
+        &self,
+        request: Request<proto::ListCircuitsRequest>,
+    ) -> Result<Response<proto::ListCircuitsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), CIRCUIT_READ_PERMISSION)?;
+        let request = request.into_inner();
+        let predicates = to_predicates(request.management_type, request.member);
+
+        let store = self.store.clone_boxed();
+        let circuits = blocking(move || {
+            store
+                .list_circuits(&predicates)
+                .map(|circuits| circuits.map(to_proto_circuit).collect::<Vec<_>>())
+        })
+        .await?
+        .map_err(|err| {
+            error!("Unable to list circuits: {}", err);
+            Status::internal("Unable to list circuits")
+        })?;
+
+        Ok(Response::new(proto::ListCircuitsResponse { circuits }))
+    }
+
+    async fn get_circuit(
+        &self,
+        request: Request<proto::GetCircuitRequest>,
+    ) -> Result<Response<proto::Circuit>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), CIRCUIT_READ_PERMISSION)?;
+        let circuit_id = request.into_inner().circuit_id;
+
+        let store = self.store.clone_boxed();
+        let id = circuit_id.clone();
+        match blocking(move || store.get_circuit(&id)).await? {
+            Ok(Some(circuit)) => Ok(Response::new(to_proto_circuit(circuit))),
+            Ok(None) => Err(Status::not_found(format!(
+                "Unable to find circuit: {}",
+                circuit_id
+            ))),
+            Err(err) => {
+                error!("Unable to get circuit {}: {}", circuit_id, err);
+                Err(Status::internal("Unable to get circuit"))
+            }
+        }
+    }
+
+    async fn list_proposals(
+        &self,
+        request: Request<proto::ListProposalsRequest>,
+    ) -> Result<Response<proto::ListProposalsResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), CIRCUIT_READ_PERMISSION)?;
+        let request = request.into_inner();
+        let predicates = to_predicates(request.management_type, request.member);
+
+        let store = self.store.clone_boxed();
+        let proposals = blocking(move || {
+            store
+                .list_proposals(&predicates)
+                .map(|proposals| proposals.map(to_proto_proposal).collect::<Vec<_>>())
+        })
+        .await?
+        .map_err(|err| {
+            error!("Unable to list proposals: {}", err);
+            Status::internal("Unable to list proposals")
+        })?;
+
+        Ok(Response::new(proto::ListProposalsResponse { proposals }))
+    }
+
+    async fn get_proposal(
+        &self,
+        request: Request<proto::GetProposalRequest>,
+    ) -> Result<Response<proto::Proposal>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), CIRCUIT_READ_PERMISSION)?;
+        let circuit_id = request.into_inner().circuit_id;
+
+        let store = self.store.clone_boxed();
+        let id = circuit_id.clone();
+        match blocking(move || store.get_proposal(&id)).await? {
+            Ok(Some(proposal)) => Ok(Response::new(to_proto_proposal(proposal))),
+            Ok(None) => Err(Status::not_found(format!(
+                "Unable to find proposal: {}",
+                circuit_id
+            ))),
+            Err(err) => {
+                error!("Unable to get proposal {}: {}", circuit_id, err);
+                Err(Status::internal("Unable to get proposal"))
+            }
+        }
+    }
+}
+
+/// Converts the optional filters of a list request, which are empty if unset, to predicates.
+fn to_predicates(management_type: String, member: String) -> Vec<CircuitPredicate> {
+    let mut predicates = vec![];
+    if !management_type.is_empty() {
+        predicates.push(CircuitPredicate::ManagementTypeEq(management_type));
+    }
+    if !member.is_empty() {
+        predicates.push(CircuitPredicate::MembersInclude(vec![member]));
+    }
+    predicates
+}
+
+fn to_proto_circuit(circuit: Circuit) -> proto::Circuit {
+    let status = match circuit.circuit_status() {
+        CircuitStatus::Active => proto::circuit::Status::Active,
+        CircuitStatus::Disbanded => proto::circuit::Status::Disbanded,
+        CircuitStatus::Abandoned => proto::circuit::Status::Abandoned,
+    };
+
+    proto::Circuit {
+        circuit_id: circuit.circuit_id().to_string(),
+        display_name: circuit.display_name().clone().unwrap_or_default(),
+        management_type: circuit.circuit_management_type().to_string(),
+        circuit_version: circuit.circuit_version(),
+        status: status as i32,
+        members: circuit
+            .members()
+            .iter()
+            .map(|node| proto::Member {
+                node_id: node.node_id().to_string(),
+                endpoints: node.endpoints().to_vec(),
+            })
+            .collect(),
+        services: circuit
+            .roster()
+            .iter()
+            .map(|service| proto::Service {
+                service_id: service.service_id().to_string(),
+                service_type: service.service_type().to_string(),
+                node_id: service.node_id().to_string(),
+            })
+            .collect(),
+    }
+}
+
+fn to_proto_proposal(proposal: CircuitProposal) -> proto::Proposal {
+    let proposal_type = match proposal.proposal_type() {
+        ProposalType::Create => proto::proposal::ProposalType::Create,
+        ProposalType::UpdateRoster => proto::proposal::ProposalType::UpdateRoster,
+        ProposalType::AddNode => proto::proposal::ProposalType::AddNode,
+        ProposalType::RemoveNode => proto::proposal::ProposalType::RemoveNode,
+        ProposalType::Disband => proto::proposal::ProposalType::Disband,
+    };
+    let circuit = proposal.circuit();
+
+    proto::Proposal {
+        proposal_type: proposal_type as i32,
+        circuit_id: proposal.circuit_id().to_string(),
+        circuit_hash: proposal.circuit_hash().to_string(),
+        requester: to_hex(proposal.requester().as_slice()),
+        requester_node_id: proposal.requester_node_id().to_string(),
+        display_name: circuit.display_name().clone().unwrap_or_default(),
+        management_type: circuit.circuit_management_type().to_string(),
+        comments: circuit.comments().clone().unwrap_or_default(),
+        members: circuit
+            .members()
+            .iter()
+            .map(|node| proto::Member {
+                node_id: node.node_id().to_string(),
+                endpoints: node.endpoints().to_vec(),
+            })
+            .collect(),
+        services: circuit
+            .roster()
+            .iter()
+            .map(|service| proto::Service {
+                service_id: service.service_id().to_string(),
+                service_type: service.service_type().to_string(),
+                node_id: service.node_id().to_string(),
+            })
+            .collect(),
+        votes: proposal
+            .votes()
+            .iter()
+            .map(|vote| proto::Vote {
+                public_key: to_hex(vote.public_key().as_slice()),
+                accept: matches!(vote.vote(), Vote::Accept),
+                voter_node_id: vote.voter_node_id().to_string(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that only the filters that are set become predicates.
+    #[test]
+    fn test_to_predicates() {
+        assert!(to_predicates(String::new(), String::new()).is_empty());
+
+        let predicates = to_predicates("gameroom".to_string(), "node-1".to_string());
+        assert_eq!(predicates.len(), 2);
+        assert!(matches!(
+            &predicates[0],
+            CircuitPredicate::ManagementTypeEq(management_type) if management_type == "gameroom"
+        ));
+        assert!(matches!(
+            &predicates[1],
+            CircuitPredicate::MembersInclude(members) if members == &["node-1".to_string()]
+        ));
+    }
+}
@@ -0,0 +1,224 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The gRPC interface for admin and scabbard operations.
+//!
+//! The interface exposes the circuit proposal, voting and circuit query operations of the admin
+//! REST API, and the batch submission and status operations of the scabbard REST API, as the
+//! `Admin` and `Scabbard` services defined in `protos/splinterd_grpc.proto`. It runs on its own
+//! thread, with its own Tokio runtime, so that it is independent of the REST API's runtime.
+//!
+//! Clients authenticate with the same Cylinder JWT as REST API clients, passed in the
+//! `authorization` metadata, and each call is authorized by the REST API's authorization handlers
+//! with the permission of the equivalent REST API endpoint.
+
+mod admin;
+mod scabbard;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use cylinder::Verifier;
+use splinter::admin::service::AdminCommands;
+use splinter::admin::store::AdminServiceStore;
+use splinter::error::InternalError;
+use splinter::rest_api::auth::authorization::{AuthorizationHandler, AuthorizationHandlerResult};
+use splinter::rest_api::auth::identity::{
+    cylinder::CylinderKeyIdentityProvider, Identity, IdentityProvider,
+};
+use splinter::rest_api::auth::AuthorizationHeader;
+use splinter::runtime::service::instance::{ManagedService, ServiceDefinition};
+use splinter::threading::lifecycle::ShutdownHandle;
+use tokio::sync::oneshot;
+use tonic::{metadata::MetadataMap, transport::Server, Status};
+
+use super::error::StartError;
+
+use self::admin::AdminGrpcService;
+use self::proto::admin_server::AdminServer;
+use self::proto::scabbard_server::ScabbardServer;
+use self::scabbard::ScabbardGrpcService;
+
+mod proto {
+    tonic::include_proto!("splinterd.grpc");
+}
+
+/// The permissions of the gRPC calls, which are those of the equivalent REST API endpoints
+const CIRCUIT_READ_PERMISSION: &str = "circuit.read";
+const CIRCUIT_WRITE_PERMISSION: &str = "circuit.write";
+const SCABBARD_READ_PERMISSION: &str = "scabbard.read";
+const SCABBARD_WRITE_PERMISSION: &str = "scabbard.write";
+
+/// Authenticates gRPC clients by their Cylinder JWTs, and checks their permissions.
+#[derive(Clone)]
+pub struct GrpcAuthorizer {
+    identity_provider: CylinderKeyIdentityProvider,
+    authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+}
+
+impl GrpcAuthorizer {
+    /// Creates a new authorizer.
+    ///
+    /// # Arguments
+    ///
+    /// * `verifier` - Verifies the signatures of clients' Cylinder JWTs
+    /// * `authorization_handlers` - The REST API's authorization handlers
+    pub fn new(
+        verifier: Box<dyn Verifier>,
+        authorization_handlers: Vec<Box<dyn AuthorizationHandler>>,
+    ) -> Self {
+        Self {
+            identity_provider: CylinderKeyIdentityProvider::new(Arc::new(Mutex::new(verifier))),
+            authorization_handlers,
+        }
+    }
+
+    /// Returns the identity of the client that sent the given metadata, if it has the permission.
+    /// As for the REST API, the first handler that allows or denies the permission decides it,
+    /// and the permission is denied if none do.
+    fn authorize(&self, metadata: &MetadataMap, permission_id: &str) -> Result<Identity, Status> {
+        let header = metadata
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<AuthorizationHeader>().ok())
+            .ok_or_else(|| Status::unauthenticated("Missing or invalid authorization"))?;
+
+        let identity = match self.identity_provider.get_identity(&header) {
+            Ok(Some(identity)) => identity,
+            Ok(None) => return Err(Status::unauthenticated("Invalid authorization")),
+            Err(err) => {
+                error!("Unable to get identity of gRPC client: {}", err);
+                return Err(Status::internal("Unable to authenticate client"));
+            }
+        };
+
+        for handler in &self.authorization_handlers {
+            match handler.has_permission(&identity, permission_id) {
+                Ok(AuthorizationHandlerResult::Allow) => return Ok(identity),
+                Ok(AuthorizationHandlerResult::Deny) => break,
+                Ok(AuthorizationHandlerResult::Continue) => (),
+                Err(err) => error!("{}", err),
+            }
+        }
+
+        Err(Status::permission_denied(format!(
+            "Client does not have permission {}",
+            permission_id
+        )))
+    }
+}
+
+/// Runs a blocking call, such as a store query, on the runtime's blocking thread pool.
+async fn blocking<F, T>(f: F) -> Result<T, Status>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(|err| {
+        error!("Blocking gRPC task failed: {}", err);
+        Status::internal("Internal error")
+    })
+}
+
+/// Encodes bytes, such as a public key, as a lowercase hex string.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Runs the gRPC server until it is shut down.
+pub struct GrpcServer {
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    join_handle: Option<thread::JoinHandle<()>>,
+}
+
+impl GrpcServer {
+    /// Starts the gRPC server on its own thread.
+    ///
+    /// # Arguments
+    ///
+    /// * `bind` - The address to listen on, such as `127.0.0.1:8085`
+    /// * `authorizer` - Authenticates and authorizes clients
+    /// * `admin_commands` - Handles submitted circuit proposals and votes
+    /// * `admin_store` - Answers circuit and proposal queries
+    /// * `services` - The services run by the orchestrator, which include the scabbard services
+    pub fn start(
+        bind: &str,
+        authorizer: GrpcAuthorizer,
+        admin_commands: Box<dyn AdminCommands>,
+        admin_store: Box<dyn AdminServiceStore>,
+        services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+    ) -> Result<Self, StartError> {
+        let addr = bind.parse::<SocketAddr>().map_err(|err| {
+            StartError::UserError(format!("Invalid gRPC bind address {}: {}", bind, err))
+        })?;
+
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .thread_name("gRPC Server")
+            .enable_all()
+            .build()
+            .map_err(|err| {
+                StartError::InternalError(format!("Unable to start gRPC runtime: {}", err))
+            })?;
+
+        let admin = AdminGrpcService::new(authorizer.clone(), admin_commands, admin_store);
+        let scabbard = ScabbardGrpcService::new(authorizer, services);
+
+        let (shutdown_tx, shutdown_rx) = oneshot::channel();
+        let join_handle = thread::Builder::new()
+            .name("gRPC Server".into())
+            .spawn(move || {
+                let result = runtime.block_on(
+                    Server::builder()
+                        .add_service(AdminServer::new(admin))
+                        .add_service(ScabbardServer::new(scabbard))
+                        .serve_with_shutdown(addr, async {
+                            let _ = shutdown_rx.await;
+                        }),
+                );
+                if let Err(err) = result {
+                    error!("gRPC server stopped with an error: {}", err);
+                }
+            })
+            .map_err(|err| {
+                StartError::InternalError(format!("Unable to start gRPC server thread: {}", err))
+            })?;
+
+        info!("gRPC server listening on {}", addr);
+
+        Ok(Self {
+            shutdown_tx: Some(shutdown_tx),
+            join_handle: Some(join_handle),
+        })
+    }
+}
+
+impl ShutdownHandle for GrpcServer {
+    fn signal_shutdown(&mut self) {
+        if let Some(shutdown_tx) = self.shutdown_tx.take() {
+            // The server has already stopped if the receiver was dropped
+            let _ = shutdown_tx.send(());
+        }
+    }
+
+    fn wait_for_shutdown(mut self) -> Result<(), InternalError> {
+        match self.join_handle.take() {
+            Some(join_handle) => join_handle
+                .join()
+                .map_err(|_| InternalError::with_message("gRPC server thread panicked".into())),
+            None => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The gRPC `Scabbard` service, which submits batches to the scabbard services run by the
+//! orchestrator and reports their status.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use scabbard::service::{BatchInfo, BatchStatus, Scabbard, SERVICE_TYPE};
+use splinter::runtime::service::instance::{ManagedService, ServiceDefinition};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::{Request, Response, Status};
+use transact::protocol::batch::BatchPair;
+use transact::protos::FromBytes;
+
+use super::proto;
+use super::proto::scabbard_server::Scabbard as ScabbardApi;
+use super::{blocking, GrpcAuthorizer, SCABBARD_READ_PERMISSION, SCABBARD_WRITE_PERMISSION};
+
+/// How often the status of watched batches is checked
+const WATCH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+pub struct ScabbardGrpcService {
+    authorizer: GrpcAuthorizer,
+    services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+}
+
+impl ScabbardGrpcService {
+    pub fn new(
+        authorizer: GrpcAuthorizer,
+        services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+    ) -> Self {
+        Self {
+            authorizer,
+            services,
+        }
+    }
+
+    /// Returns the scabbard service with the given circuit and service IDs.
+    fn get_scabbard(&self, circuit_id: &str, service_id: &str) -> Result<Scabbard, Status> {
+        let services = self.services.lock().map_err(|_| {
+            error!("Orchestrator's service lock poisoned");
+            Status::internal("Internal error")
+        })?;
+
+        let definition = ServiceDefinition {
+            circuit: circuit_id.to_string(),
+            service_id: service_id.to_string(),
+            service_type: SERVICE_TYPE.to_string(),
+        };
+        let managed_service = services.get(&definition).ok_or_else(|| {
+            Status::not_found(format!(
+                "Unable to find scabbard service {}::{}",
+                circuit_id, service_id
+            ))
+        })?;
+
+        managed_service
+            .service
+            .as_any()
+            .downcast_ref::<Scabbard>()
+            .cloned()
+            .ok_or_else(|| {
+                error!("Failed to downcast to scabbard service");
+                Status::internal("Internal error")
+            })
+    }
+}
+
+#[tonic::async_trait]
+impl ScabbardApi for ScabbardGrpcService {
+    type WatchBatchStatusesStream = ReceiverStream<Result<proto::BatchStatus, Status>>;
+
+    async fn submit_batches(
+        &self,
+        request: Request<proto::SubmitBatchesRequest>,
+    ) -> Result<Response<proto::SubmitBatchesResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), SCABBARD_WRITE_PERMISSION)?;
+        let request = request.into_inner();
+        let scabbard = self.get_scabbard(&request.circuit_id, &request.service_id)?;
+
+        let batches: Vec<BatchPair> = Vec::from_bytes(&request.batches)
+            .map_err(|_| Status::invalid_argument("Not a valid list of batches"))?;
+        let batch_ids = batches
+            .iter()
+            .map(|batch| batch.batch().header_signature().to_string())
+            .collect::<Vec<_>>();
+
+        blocking(move || {
+            match scabbard.accepting_batches() {
+                Ok(true) => (),
+                Ok(false) => {
+                    warn!("Rejecting submitted batch, too many pending batches");
+                    return Err(Status::resource_exhausted("Too many pending batches"));
+                }
+                Err(err) => {
+                    error!("Failed to add batches: {}", err);
+                    return Err(Status::internal("Unable to add batches"));
+                }
+            }
+
+            match scabbard.add_batches(batches) {
+                Ok(Some(_)) => Ok(()),
+                Ok(None) => Err(Status::invalid_argument("No valid batches provided")),
+                Err(err) => {
+                    error!("Failed to add batches: {}", err);
+                    Err(Status::internal("Unable to add batches"))
+                }
+            }
+        })
+        .await??;
+
+        Ok(Response::new(proto::SubmitBatchesResponse { batch_ids }))
+    }
+
+    async fn get_batch_statuses(
+        &self,
+        request: Request<proto::GetBatchStatusesRequest>,
+    ) -> Result<Response<proto::GetBatchStatusesResponse>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), SCABBARD_READ_PERMISSION)?;
+        let request = request.into_inner();
+        let scabbard = self.get_scabbard(&request.circuit_id, &request.service_id)?;
+
+        let ids = request.batch_ids.into_iter().collect::<HashSet<_>>();
+        let wait = match request.wait_secs {
+            0 => None,
+            secs => Some(Duration::from_secs(secs)),
+        };
+        let statuses = blocking(move || get_statuses(&scabbard, ids, wait)).await??;
+
+        Ok(Response::new(proto::GetBatchStatusesResponse {
+            statuses: statuses.into_iter().map(to_proto_status).collect(),
+        }))
+    }
+
+    async fn watch_batch_statuses(
+        &self,
+        request: Request<proto::WatchBatchStatusesRequest>,
+    ) -> Result<Response<Self::WatchBatchStatusesStream>, Status> {
+        self.authorizer
+            .authorize(request.metadata(), SCABBARD_READ_PERMISSION)?;
+        let request = request.into_inner();
+        let scabbard = self.get_scabbard(&request.circuit_id, &request.service_id)?;
+        let ids = request.batch_ids.into_iter().collect::<HashSet<_>>();
+
+        let (sender, receiver) = mpsc::channel(ids.len().max(1));
+        tokio::task::spawn_blocking(move || {
+            // The last status sent for each batch, so that only changes are sent
+            let mut sent: HashMap<String, BatchStatus> = HashMap::new();
+            loop {
+                let statuses = match get_statuses(&scabbard, ids.clone(), None) {
+                    Ok(statuses) => statuses,
+                    Err(status) => {
+                        let _ = sender.blocking_send(Err(status));
+                        return;
+                    }
+                };
+
+                let mut done = true;
+                for info in statuses {
+                    if !is_final(&info.status) {
+                        done = false;
+                    }
+                    if sent.get(&info.id) == Some(&info.status) {
+                        continue;
+                    }
+                    sent.insert(info.id.clone(), info.status.clone());
+                    if sender.blocking_send(Ok(to_proto_status(info))).is_err() {
+                        // The client has stopped watching
+                        return;
+                    }
+                }
+
+                if done {
+                    return;
+                }
+                std::thread::sleep(WATCH_POLL_INTERVAL);
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+/// Fetches the status of the given batches, waiting up to `wait` for them to complete if given.
+fn get_statuses(
+    scabbard: &Scabbard,
+    ids: HashSet<String>,
+    wait: Option<Duration>,
+) -> Result<Vec<BatchInfo>, Status> {
+    scabbard
+        .get_batch_info(ids, wait)
+        .map_err(|err| {
+            error!("Failed to get batch statuses: {}", err);
+            Status::internal("Unable to get batch statuses")
+        })?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| {
+            error!("Failed to get batch statuses: {}", err);
+            Status::internal("Unable to get batch statuses")
+        })
+}
+
+/// Returns whether a batch with the given status will not change status again.
+fn is_final(status: &BatchStatus) -> bool {
+    matches!(status, BatchStatus::Committed(_) | BatchStatus::Invalid(_))
+}
+
+fn to_proto_status(info: BatchInfo) -> proto::BatchStatus {
+    let (status, transactions) = match info.status {
+        BatchStatus::Unknown => (proto::batch_status::Status::Unknown, vec![]),
+        BatchStatus::Pending => (proto::batch_status::Status::Pending, vec![]),
+        BatchStatus::Invalid(txns) => (
+            proto::batch_status::Status::Invalid,
+            txns.into_iter()
+                .map(|txn| proto::TransactionStatus {
+                    transaction_id: txn.transaction_id,
+                    error_message: txn.error_message,
+                })
+                .collect(),
+        ),
+        BatchStatus::Valid(txns) => (
+            proto::batch_status::Status::Valid,
+            txns.into_iter()
+                .map(|txn| proto::TransactionStatus {
+                    transaction_id: txn.transaction_id,
+                    error_message: String::new(),
+                })
+                .collect(),
+        ),
+        BatchStatus::Committed(txns) => (
+            proto::batch_status::Status::Committed,
+            txns.into_iter()
+                .map(|txn| proto::TransactionStatus {
+                    transaction_id: txn.transaction_id,
+                    error_message: String::new(),
+                })
+                .collect(),
+        ),
+    };
+
+    proto::BatchStatus {
+        batch_id: info.id,
+        status: status as i32,
+        transactions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::SystemTime;
+
+    use scabbard::service::InvalidTransaction;
+
+    /// Verify that batch statuses are converted with their transactions, and that only committed
+    /// and invalid batches are final.
+    #[test]
+    fn test_to_proto_status() {
+        let info = BatchInfo {
+            id: "batch-1".to_string(),
+            status: BatchStatus::Invalid(vec![InvalidTransaction {
+                transaction_id: "txn-1".to_string(),
+                error_message: "bad payload".to_string(),
+                error_data: vec![],
+            }]),
+            timestamp: SystemTime::now(),
+        };
+        assert!(is_final(&info.status));
+
+        let status = to_proto_status(info);
+        assert_eq!(status.batch_id, "batch-1");
+        assert_eq!(status.status, proto::batch_status::Status::Invalid as i32);
+        assert_eq!(
+            status.transactions,
+            vec![proto::TransactionStatus {
+                transaction_id: "txn-1".to_string(),
+                error_message: "bad payload".to_string(),
+            }]
+        );
+
+        let pending = BatchInfo {
+            id: "batch-2".to_string(),
+            status: BatchStatus::Pending,
+            timestamp: SystemTime::now(),
+        };
+        assert!(!is_final(&pending.status));
+        assert_eq!(
+            to_proto_status(pending).status,
+            proto::batch_status::Status::Pending as i32
+        );
+    }
+}
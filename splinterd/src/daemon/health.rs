@@ -0,0 +1,193 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! The checks run by `GET /health/ready` to decide whether the node is ready.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use splinter::peer::PeerManagerConnector;
+use splinter::runtime::service::instance::{ManagedService, ServiceDefinition};
+#[cfg(feature = "service-restart-policy")]
+use splinter::runtime::service::instance::{ServiceRestartTracker, ServiceRunState};
+use splinter_rest_api_actix_web_1::health::HealthCheck;
+
+use super::store::ConnectionPool;
+
+/// Checks that a connection can be taken from the database connection pool.
+pub struct DatabaseCheck {
+    pool: ConnectionPool,
+}
+
+impl DatabaseCheck {
+    pub fn new(pool: ConnectionPool) -> Self {
+        Self { pool }
+    }
+}
+
+impl HealthCheck for DatabaseCheck {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        if self.pool.check_connection() {
+            Ok(())
+        } else {
+            Err("Unable to get a connection from the database connection pool".into())
+        }
+    }
+}
+
+/// Checks that the network listeners are still accepting connections.
+#[derive(Default)]
+pub struct ListenerCheck {
+    listeners: Vec<(String, Arc<AtomicBool>)>,
+}
+
+impl ListenerCheck {
+    /// Adds a bound listener. The returned guard must be held by the thread that accepts the
+    /// listener's connections; the listener is reported as stopped once the guard is dropped.
+    pub fn add_listener(&mut self, endpoint: &str) -> ListenerGuard {
+        let bound = Arc::new(AtomicBool::new(true));
+        self.listeners.push((endpoint.to_string(), bound.clone()));
+        ListenerGuard { bound }
+    }
+}
+
+impl HealthCheck for ListenerCheck {
+    fn name(&self) -> &str {
+        "network_listeners"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        let stopped = self
+            .listeners
+            .iter()
+            .filter(|(_, bound)| !bound.load(Ordering::SeqCst))
+            .map(|(endpoint, _)| endpoint.as_str())
+            .collect::<Vec<_>>();
+        if stopped.is_empty() {
+            Ok(())
+        } else {
+            Err(format!("Listeners stopped: {}", stopped.join(", ")))
+        }
+    }
+}
+
+/// Marks a network listener as stopped when it is dropped, including when its thread panics.
+pub struct ListenerGuard {
+    bound: Arc<AtomicBool>,
+}
+
+impl Drop for ListenerGuard {
+    fn drop(&mut self) {
+        self.bound.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Checks that the peer manager answers requests.
+pub struct PeerManagerCheck {
+    connector: PeerManagerConnector,
+}
+
+impl PeerManagerCheck {
+    pub fn new(connector: PeerManagerConnector) -> Self {
+        Self { connector }
+    }
+}
+
+impl HealthCheck for PeerManagerCheck {
+    fn name(&self) -> &str {
+        "peer_manager"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.connector
+            .list_peers()
+            .map(|_| ())
+            .map_err(|err| format!("Peer manager did not answer: {}", err))
+    }
+}
+
+/// Checks that the service orchestrator's services can be read and, if services are restarted
+/// when they crash, that none of them have failed.
+pub struct OrchestratorCheck {
+    services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+    #[cfg(feature = "service-restart-policy")]
+    restart_tracker: ServiceRestartTracker,
+}
+
+impl OrchestratorCheck {
+    pub fn new(
+        services: Arc<Mutex<HashMap<ServiceDefinition, ManagedService>>>,
+        #[cfg(feature = "service-restart-policy")] restart_tracker: ServiceRestartTracker,
+    ) -> Self {
+        Self {
+            services,
+            #[cfg(feature = "service-restart-policy")]
+            restart_tracker,
+        }
+    }
+}
+
+impl HealthCheck for OrchestratorCheck {
+    fn name(&self) -> &str {
+        "orchestrator"
+    }
+
+    fn check(&self) -> Result<(), String> {
+        self.services
+            .lock()
+            .map_err(|_| "The orchestrator's service lock was poisoned".to_string())?;
+
+        #[cfg(feature = "service-restart-policy")]
+        {
+            let failed = self
+                .restart_tracker
+                .list()
+                .map_err(|err| format!("Unable to list service states: {}", err))?
+                .into_iter()
+                .filter(|history| history.state() == ServiceRunState::Failed)
+                .map(|history| history.service().to_string())
+                .collect::<Vec<_>>();
+            if !failed.is_empty() {
+                return Err(format!("Services failed: {}", failed.join(", ")));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a listener is reported as stopped once its guard is dropped.
+    #[test]
+    fn test_listener_check() {
+        let mut check = ListenerCheck::default();
+        let _first = check.add_listener("tcps://0.0.0.0:8044");
+        let second = check.add_listener("tcp://0.0.0.0:8045");
+        assert!(check.check().is_ok());
+
+        drop(second);
+        assert_eq!(
+            check.check(),
+            Err("Listeners stopped: tcp://0.0.0.0:8045".to_string())
+        );
+    }
+}
@@ -0,0 +1,56 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hooks for applications that run the daemon from their own binary.
+
+use std::sync::mpsc::Sender;
+
+use super::DaemonSignal;
+
+/// Callbacks into an application that embeds the daemon, made as the daemon starts and stops.
+///
+/// Every callback is made on the thread that started the daemon, so a callback should return
+/// promptly.
+pub trait DaemonHooks: Send {
+    /// Called once the node, its transports and its REST API are running.
+    ///
+    /// # Arguments
+    ///
+    /// * `shutdown_handle` - Stops the daemon when signaled; the application may keep it
+    fn on_started(&mut self, _shutdown_handle: DaemonShutdownHandle) {}
+
+    /// Called when the daemon begins to shut down, before any of its components are stopped.
+    fn on_stopping(&mut self) {}
+
+    /// Called once every component of the daemon has stopped.
+    fn on_stopped(&mut self) {}
+}
+
+/// Signals a running daemon to shut down gracefully, as Ctrl-C does.
+#[derive(Clone)]
+pub struct DaemonShutdownHandle {
+    sender: Sender<DaemonSignal>,
+}
+
+impl DaemonShutdownHandle {
+    pub(super) fn new(sender: Sender<DaemonSignal>) -> Self {
+        Self { sender }
+    }
+
+    /// Signals the daemon to shut down. Signaling a daemon that is already shutting down has no
+    /// effect.
+    pub fn signal_shutdown(&self) {
+        let _ = self.sender.send(DaemonSignal::Shutdown);
+    }
+}
@@ -14,46 +14,100 @@
 
 pub mod builder;
 mod error;
+#[cfg(feature = "grpc")]
+mod grpc;
+#[cfg(feature = "health")]
+mod health;
+#[cfg(feature = "daemon-hooks")]
+pub mod hooks;
 #[cfg(feature = "service2")]
 mod lifecycle;
 mod registry;
+#[cfg(feature = "config-reload")]
+pub mod reload;
+#[cfg(feature = "high-availability")]
+mod standby;
+pub mod startup;
 mod store;
+#[cfg(feature = "systemd")]
+mod systemd;
 #[cfg(feature = "service2")]
 mod timer;
 
 use std::collections::HashMap;
 use std::fs;
+#[cfg(feature = "rest-api-proxy-auth")]
+use std::net::IpAddr;
 use std::path::Path;
 #[cfg(feature = "authorization-handler-allow-keys")]
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "config-reload")]
+use std::sync::mpsc::Sender;
+#[cfg(feature = "systemd")]
+use std::sync::mpsc::{RecvError, RecvTimeoutError};
+#[cfg(feature = "config-report")]
+use std::sync::RwLock;
 use std::sync::{mpsc::channel, Arc, Mutex};
 use std::thread;
 use std::time::Duration;
 
+#[cfg(feature = "signature-verification-pool")]
+use cylinder::Verifier;
 use cylinder::{secp256k1::Secp256k1Context, Signer, SigningError, VerifierFactory};
 #[cfg(feature = "scabbardv3")]
 use scabbard::service::v3::{ScabbardMessageByteConverter, ScabbardMessageHandlerFactory};
+#[cfg(feature = "scabbard-anchoring")]
+use scabbard::service::AnchorConfig;
+#[cfg(feature = "scabbard-batch-validation")]
+use scabbard::service::BatchValidator;
+#[cfg(feature = "scabbard-receipt-pruning")]
+use scabbard::service::ReceiptRetention;
 use scabbard::service::ScabbardArgValidator;
 use scabbard::service::ScabbardFactoryBuilder;
+#[cfg(feature = "scabbard-snapshot")]
+use scabbard::service::SnapshotConfig;
+#[cfg(feature = "scabbard-state-pruning")]
+use scabbard::service::StateRetention;
 #[cfg(feature = "service2")]
 use splinter::admin::lifecycle::sync::SyncLifecycleInterface;
 use splinter::admin::lifecycle::LifecycleDispatch;
+#[cfg(any(feature = "admin-webhooks", feature = "biome-notifications"))]
+use splinter::admin::service::AdminCommands;
+#[cfg(feature = "admin-service-proposal-limits")]
+use splinter::admin::service::ProposalLimits;
 use splinter::admin::service::{admin_service_id, AdminService, AdminServiceBuilder};
+#[cfg(feature = "peer-authorization-policy")]
+use splinter::admin::store::AuthorizationType;
+#[cfg(feature = "admin-webhooks")]
+use splinter::admin::webhook::{Webhook, WebhookDispatcher, WebhookDispatcherBuilder};
+use splinter::admin::CIRCUIT_PROTOCOL_VERSION;
 #[cfg(feature = "biome-credentials")]
 use splinter::biome::credentials::rest_api::BiomeCredentialsRestResourceProviderBuilder;
+#[cfg(feature = "biome-notifications")]
+use splinter::biome::notifications::{
+    rest_api::BiomeNotificationsRestResourceProvider,
+    sender::{NotificationSender, SlackWebhookSender, SmtpSender},
+    NotificationDispatcher,
+};
 #[cfg(feature = "biome-profile")]
 use splinter::biome::profile::rest_api::BiomeProfileRestResourceProvider;
+#[cfg(any(feature = "biome-credentials", feature = "oauth"))]
+use splinter::biome::retention::{BiomeRetentionPolicy, BiomeRetentionScheduler};
 use splinter::circuit::handlers::{
     AdminDirectMessageHandler, CircuitDirectMessageHandler, CircuitErrorHandler,
     CircuitMessageHandler, ServiceConnectRequestHandler, ServiceDisconnectRequestHandler,
 };
 use splinter::circuit::routing::{memory::RoutingTable, RoutingTableReader, RoutingTableWriter};
+#[cfg(feature = "circuit-usage")]
+use splinter::circuit::usage::{CircuitUsagePersister, CircuitUsageQuota, CircuitUsageTracker};
 #[cfg(feature = "service2")]
 use splinter::error::InternalError;
 use splinter::keys::insecure::AllowAllKeyPermissionManager;
 use splinter::mesh::Mesh;
 use splinter::network::auth::AuthorizationManager;
+#[cfg(feature = "clock-skew-detection")]
+use splinter::network::clock_skew::ClockSkewMonitor;
 use splinter::network::connection_manager::{
     authorizers::Authorizers, authorizers::InprocAuthorizer, ConnectionManager, Connector,
 };
@@ -61,20 +115,44 @@ use splinter::network::dispatch::{
     dispatch_channel, DispatchLoopBuilder, DispatchMessageSender, Dispatcher,
 };
 use splinter::network::handlers::{NetworkEchoHandler, NetworkHeartbeatHandler};
+#[cfg(feature = "peer-blocklist")]
+use splinter::peer::blocklist::PeerBlocklist;
 use splinter::peer::interconnect::NetworkMessageSender;
 use splinter::peer::interconnect::PeerInterconnectBuilder;
 use splinter::peer::PeerAuthorizationToken;
 use splinter::peer::PeerManager;
+use splinter::peer::{NodeCapabilities, NodeCapabilitiesBuilder};
+use splinter::protocol::PEER_AUTHORIZATION_PROTOCOL_VERSION;
 use splinter::protos::circuit::CircuitMessageType;
 use splinter::protos::network::NetworkMessageType;
 use splinter::public_key::PublicKey;
+#[cfg(feature = "registry-signed-entries")]
+use splinter::registry::NodeSignatureVerifier;
+#[cfg(feature = "registry-auto-populate")]
+use splinter::registry::RegistryAutoPopulator;
 use splinter::registry::{
-    LocalYamlRegistry, RegistryReader, RemoteYamlRegistry, RwRegistry, UnifiedRegistry,
+    LocalYamlRegistry, RegistryError, RegistryReader, RemoteYamlRegistry, RwRegistry,
+    UnifiedRegistry,
 };
+#[cfg(feature = "registry-mdns")]
+use splinter::registry::{MdnsAdvertisement, MdnsRegistry};
+#[cfg(feature = "rest-api-degraded-mode")]
+use splinter::rest_api::actix_web_1::DegradedMode;
+#[cfg(feature = "rest-api-openapi")]
+use splinter::rest_api::actix_web_1::OpenApiConfig;
 #[cfg(feature = "authorization-handler-allow-keys")]
 use splinter::rest_api::auth::authorization::allow_keys::AllowKeysAuthorizationHandler;
+#[cfg(feature = "authorization-bootstrap")]
+use splinter::rest_api::auth::authorization::bootstrap::BootstrapToken;
 #[cfg(feature = "authorization-handler-maintenance")]
 use splinter::rest_api::auth::authorization::maintenance::MaintenanceModeAuthorizationHandler;
+#[cfg(all(
+    feature = "authorization-handler-rbac",
+    feature = "rest-api-client-cert-auth"
+))]
+use splinter::rest_api::auth::authorization::rbac::ClientCertRoleAuthorizationHandler;
+#[cfg(all(feature = "authorization-handler-rbac", feature = "ldap"))]
+use splinter::rest_api::auth::authorization::rbac::LdapGroupAuthorizationHandler;
 #[cfg(feature = "authorization-handler-rbac")]
 use splinter::rest_api::auth::authorization::rbac::{
     rest_api::RoleBasedAuthorizationResourceProvider, RoleBasedAuthorizationHandler,
@@ -85,9 +163,21 @@ use splinter::rest_api::auth::authorization::rbac::{
     feature = "authorization-handler-allow-keys"
 ))]
 use splinter::rest_api::auth::authorization::AuthorizationHandler;
+#[cfg(feature = "rest-api-client-cert-auth")]
+use splinter::rest_api::auth::client_cert::CertificateMatcher;
+#[cfg(feature = "api-keys")]
+use splinter::rest_api::auth::identity::api_key::ApiKeyIdentityProvider;
+#[cfg(feature = "ldap")]
+use splinter::rest_api::auth::identity::ldap::LdapIdentityProviderBuilder;
+#[cfg(feature = "rest-api-cors")]
+use splinter::rest_api::cors::CorsAllowList;
 #[cfg(feature = "oauth")]
 use splinter::rest_api::OAuthConfig;
 use splinter::rest_api::{AuthConfig, RestApiBuilder, RestResourceProvider};
+#[cfg(feature = "service-restart-policy")]
+use splinter::runtime::service::instance::RestartPolicy;
+#[cfg(feature = "service-type-limits")]
+use splinter::runtime::service::instance::ServiceTypeLimits;
 use splinter::runtime::service::instance::{
     ServiceOrchestratorBuilder, ServiceProcessor, ServiceProcessorShutdownHandle,
 };
@@ -97,9 +187,21 @@ use splinter::runtime::service::{
     RoutingTableServiceTypeResolver, ServiceDispatcher,
 };
 use splinter::service::instance::ServiceArgValidator;
+#[cfg(feature = "service-transfer")]
+use splinter::service::transfer::{self, TransferArgValidator, TransferServiceFactory};
 #[cfg(any(feature = "scabbardv3", feature = "service-echo"))]
 use splinter::service::{MessageHandler, MessageHandlerFactory, ServiceType};
+#[cfg(feature = "database-pool-settings")]
+use splinter::store::ConnectionPoolSettings;
+#[cfg(all(feature = "tap", feature = "subsystem-control"))]
+use splinter::tap::influx::InfluxRecorderSwitch;
 use splinter::threading::lifecycle::ShutdownHandle;
+#[cfg(feature = "subsystem-control")]
+use splinter::threading::subsystem::SubsystemManager;
+#[cfg(feature = "signature-verification-pool")]
+use splinter::threading::verification::VerificationPool;
+#[cfg(feature = "connection-limits")]
+use splinter::transport::limit::{ConnectionLimiter, ConnectionLimits};
 use splinter::transport::{
     inproc::InprocTransport, multi::MultiTransport, AcceptError, Connection, Incoming, Listener,
     Transport,
@@ -107,18 +209,58 @@ use splinter::transport::{
 #[cfg(feature = "service-echo")]
 use splinter_echo::service::{EchoMessageByteConverter, EchoMessageHandlerFactory};
 use splinter_rest_api_actix_web_1::admin::{AdminServiceRestProvider, CircuitResourceProvider};
+#[cfg(feature = "announcements")]
+use splinter_rest_api_actix_web_1::announcement::AnnouncementResourceProvider;
+#[cfg(feature = "api-keys")]
+use splinter_rest_api_actix_web_1::api_key::ApiKeyResourceProvider;
+#[cfg(feature = "audit")]
+use splinter_rest_api_actix_web_1::audit::AuditResourceProvider;
 #[cfg(feature = "biome-key-management")]
 use splinter_rest_api_actix_web_1::biome::key_management::BiomeKeyManagementRestResourceProvider;
+#[cfg(feature = "circuit-usage")]
+use splinter_rest_api_actix_web_1::circuit_usage::CircuitUsageResourceProvider;
+#[cfg(feature = "health")]
+use splinter_rest_api_actix_web_1::health::HealthResourceProvider;
 use splinter_rest_api_actix_web_1::open_api;
+#[cfg(feature = "peer-blocklist")]
+use splinter_rest_api_actix_web_1::peer::PeerBlocklistResourceProvider;
+use splinter_rest_api_actix_web_1::peer::PeerCapabilitiesResourceProvider;
+#[cfg(feature = "peer-metadata")]
+use splinter_rest_api_actix_web_1::peer::PeerMetadataResourceProvider;
+#[cfg(feature = "peer-ref-counts")]
+use splinter_rest_api_actix_web_1::peer::PeerRefCountsResourceProvider;
 use splinter_rest_api_actix_web_1::registry::RwRegistryRestResourceProvider;
 use splinter_rest_api_actix_web_1::scabbard::ScabbardServiceEndpointProvider;
+#[cfg(feature = "scabbard-consensus-log")]
+use splinter_rest_api_actix_web_1::scabbard_consensus::ScabbardConsensusResourceProvider;
 use splinter_rest_api_actix_web_1::service::ServiceOrchestratorRestResourceProviderBuilder;
+#[cfg(feature = "service-restart-policy")]
+use splinter_rest_api_actix_web_1::service_restart::ServiceRestartResourceProvider;
 use splinter_rest_api_actix_web_1::status;
+#[cfg(feature = "subsystem-control")]
+use splinter_rest_api_actix_web_1::subsystem::SubsystemResourceProvider;
+#[cfg(feature = "service-transfer")]
+use splinter_rest_api_actix_web_1::transfer::TransferServiceEndpointProvider;
 
-use crate::node_id::get_node_id;
+use crate::node_id::{get_node_id, import_node_id_file};
 
 pub use error::{CreateError, StartError};
+#[cfg(feature = "grpc")]
+use grpc::{GrpcAuthorizer, GrpcServer};
+#[cfg(feature = "health")]
+use health::{DatabaseCheck, ListenerCheck, OrchestratorCheck, PeerManagerCheck};
+#[cfg(feature = "daemon-hooks")]
+use hooks::{DaemonHooks, DaemonShutdownHandle};
+#[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+use registry::RegistryRefreshHandle;
+#[cfg(feature = "subsystem-control")]
+use registry::RegistryRefresher;
 use registry::RegistryShutdownHandle;
+#[cfg(feature = "config-reload")]
+use reload::ConfigReloader;
+#[cfg(feature = "high-availability")]
+use standby::NodeLease;
+use startup::StartupTimer;
 pub use store::ConnectionUri;
 
 const ADMIN_SERVICE_PROCESSOR_INCOMING_CAPACITY: usize = 8;
@@ -126,10 +268,17 @@ const ADMIN_SERVICE_PROCESSOR_OUTGOING_CAPACITY: usize = 8;
 const ADMIN_SERVICE_PROCESSOR_CHANNEL_CAPACITY: usize = 8;
 #[cfg(feature = "service2")]
 const ADMIN_SERVICE_LIFECYCLE_TIMEOUT: u64 = 30;
+#[cfg(any(feature = "biome-credentials", feature = "oauth"))]
+const BIOME_RETENTION_INTERVAL: Duration = Duration::from_secs(3600); // 1 hour
+#[cfg(feature = "circuit-usage")]
+const CIRCUIT_USAGE_PERSIST_INTERVAL: Duration = Duration::from_secs(60);
 #[cfg(feature = "scabbardv3")]
 const SCABBARD_SERVICE_TYPE: ServiceType = ServiceType::new_static("scabbard:v3");
 #[cfg(feature = "service-echo")]
 const ECHO_SERVICE_TYPE: ServiceType = ServiceType::new_static("echo");
+/// The number of signature verification results the verification pool remembers
+#[cfg(feature = "signature-verification-pool")]
+const VERIFICATION_CACHE_SIZE: usize = 10_000;
 
 #[cfg(feature = "service2")]
 type BoxedByteMessageHandlerFactory =
@@ -150,15 +299,42 @@ pub struct SplinterDaemon {
     rest_api_endpoint: String,
     #[cfg(feature = "https-bind")]
     rest_api_ssl_settings: Option<(String, String)>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_ca: Option<String>,
     db_url: ConnectionUri,
     registries: Vec<String>,
     registry_auto_refresh: u64,
     registry_forced_refresh: u64,
+    #[cfg(feature = "registry-auto-populate")]
+    registry_auto_populate: bool,
+    #[cfg(feature = "registry-signed-entries")]
+    registry_require_signatures: bool,
+    #[cfg(feature = "rest-api-degraded-mode")]
+    rest_api_degraded_mode: bool,
+    #[cfg(feature = "rest-api-openapi")]
+    rest_api_swagger_ui: bool,
+    // The address of the gRPC server, if it is to be started
+    #[cfg(feature = "grpc")]
+    grpc_bind: Option<String>,
+    // The number of threads that verify signatures, if they are verified on a pool
+    #[cfg(feature = "signature-verification-pool")]
+    verification_threads: Option<usize>,
+    // How often the standby tries to take over, if the daemon is one of an active-passive pair
+    #[cfg(feature = "high-availability")]
+    high_availability: Option<Duration>,
+    // The authorization type used for the initial peers that do not specify one
+    #[cfg(feature = "peer-authorization-policy")]
+    peering_authorization: AuthorizationType,
+    // The weakest authorization type that circuits and initial peers may use
+    #[cfg(feature = "peer-authorization-policy")]
+    minimum_authorization: AuthorizationType,
     admin_timeout: Duration,
     #[cfg(feature = "rest-api-cors")]
     allow_list: Option<Vec<String>>,
     #[cfg(feature = "biome-credentials")]
     enable_biome_credentials: bool,
+    #[cfg(feature = "biome-credentials")]
+    biome_refresh_token_retention: Duration,
     #[cfg(feature = "oauth")]
     oauth_provider: Option<String>,
     #[cfg(feature = "oauth")]
@@ -173,8 +349,90 @@ pub struct SplinterDaemon {
     oauth_openid_auth_params: Option<Vec<(String, String)>>,
     #[cfg(feature = "oauth")]
     oauth_openid_scopes: Option<Vec<String>>,
+    #[cfg(feature = "oauth")]
+    oauth_session_retention: Duration,
+    #[cfg(feature = "biome-notifications")]
+    notification_smtp_url: Option<String>,
+    #[cfg(feature = "biome-notifications")]
+    notification_email_from: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_url: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_dn: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_bind_password: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_user_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_base: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_search_filter: Option<String>,
+    #[cfg(feature = "ldap")]
+    ldap_group_roles: Option<Vec<(String, String)>>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_auth_header: Option<String>,
+    #[cfg(feature = "rest-api-proxy-auth")]
+    rest_api_proxy_trusted_addresses: Vec<IpAddr>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_identities: Vec<(CertificateMatcher, String)>,
+    #[cfg(feature = "rest-api-client-cert-auth")]
+    rest_api_client_cert_roles: Vec<(String, String)>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_message_quota: Option<u64>,
+    #[cfg(feature = "circuit-usage")]
+    circuit_usage_byte_quota: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections: Option<u64>,
+    #[cfg(feature = "connection-limits")]
+    max_inbound_connections_per_ip: Option<u64>,
+    #[cfg(feature = "database-pool-settings")]
+    database_pool_settings: ConnectionPoolSettings,
+    #[cfg(feature = "clock-skew-detection")]
+    clock_skew_monitor: ClockSkewMonitor,
+    #[cfg(feature = "admin-webhooks")]
+    admin_webhooks: Vec<Webhook>,
+    #[cfg(feature = "scabbard-anchoring")]
+    scabbard_anchoring: Option<AnchorConfig>,
+    #[cfg(feature = "scabbard-batch-validation")]
+    scabbard_batch_validator: Option<Arc<dyn BatchValidator>>,
+    #[cfg(feature = "scabbard-receipt-pruning")]
+    scabbard_receipt_retention: Option<ReceiptRetention>,
+    #[cfg(feature = "scabbard-snapshot")]
+    scabbard_snapshots: Option<SnapshotConfig>,
+    #[cfg(feature = "scabbard-state-pruning")]
+    scabbard_state_retention: Option<StateRetention>,
+    #[cfg(feature = "service-restart-policy")]
+    service_restart_policy: Option<RestartPolicy>,
+    #[cfg(feature = "service-transfer")]
+    transfer_max_file_size: u64,
+    #[cfg(feature = "service-type-limits")]
+    service_type_limits: HashMap<String, ServiceTypeLimits>,
+    #[cfg(feature = "admin-service-deferred-proposals")]
+    admin_member_wait_timeout: Option<Duration>,
+    #[cfg(feature = "admin-service-proposal-limits")]
+    admin_proposal_limits: Option<ProposalLimits>,
+    // The newest circuit version the admin service proposes and accepts, if not the newest
+    // supported version
+    #[cfg(feature = "admin-service-circuit-version")]
+    admin_circuit_version: Option<i32>,
     heartbeat: u64,
     strict_ref_counts: bool,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_retry_interval: Option<u64>,
+    #[cfg(feature = "peer-ref-counts")]
+    peer_max_retry_frequency: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_initial_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_delay: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_jitter: Option<u64>,
+    #[cfg(feature = "peer-reconnect-policy")]
+    peer_reconnect_max_attempts: Option<u64>,
+    #[cfg(feature = "peer-connect-priority")]
+    peer_connect_ramp_rate: Option<u64>,
     signers: Vec<Box<dyn Signer>>,
     peering_token: PeerAuthorizationToken,
     #[cfg(feature = "config-allow-keys")]
@@ -185,6 +443,24 @@ pub struct SplinterDaemon {
     service_timer_interval: Duration,
     #[cfg(feature = "service2")]
     lifecycle_executor_interval: Duration,
+    #[cfg(feature = "config-reload")]
+    config_reloader: Option<Box<dyn ConfigReloader>>,
+    #[cfg(feature = "daemon-hooks")]
+    hooks: Option<Box<dyn DaemonHooks>>,
+    #[cfg(feature = "daemon-hooks")]
+    disable_ctrlc_handler: bool,
+    startup_timer: StartupTimer,
+    #[cfg(feature = "config-report")]
+    config_report: Option<Arc<RwLock<status::ConfigReport>>>,
+    #[cfg(all(feature = "tap", feature = "subsystem-control"))]
+    metrics_switch: Option<InfluxRecorderSwitch>,
+}
+
+/// The signals the daemon waits on once it has started.
+enum DaemonSignal {
+    Shutdown,
+    #[cfg(feature = "config-reload")]
+    Reload,
 }
 
 impl SplinterDaemon {
@@ -195,12 +471,37 @@ impl SplinterDaemon {
         let mut service_transport = InprocTransport::default();
         transport.add_transport(Box::new(service_transport.clone()));
 
-        let connection_pool = store::create_connection_pool(&self.db_url).map_err(|err| {
+        // Creating the connection pool also checks that the database has no pending migrations
+        self.startup_timer.start_phase();
+        #[cfg(not(feature = "database-pool-settings"))]
+        let connection_pool = store::create_connection_pool(&self.db_url);
+        #[cfg(feature = "database-pool-settings")]
+        let connection_pool =
+            store::create_connection_pool_with_settings(&self.db_url, &self.database_pool_settings);
+        let connection_pool = connection_pool.map_err(|err| {
             StartError::StorageError(format!("Failed to initialize connection pool: {}", err))
         })?;
-        let store_factory = store::create_store_factory(&connection_pool).map_err(|err| {
-            StartError::StorageError(format!("Failed to initialize store factory: {}", err))
-        })?;
+        let store_factory =
+            store::create_store_factory(&self.db_url, &connection_pool).map_err(|err| {
+                StartError::StorageError(format!("Failed to initialize store factory: {}", err))
+            })?;
+        #[cfg(feature = "rest-api-degraded-mode")]
+        let degraded_mode = if self.rest_api_degraded_mode {
+            Some(DegradedMode::new(Arc::new(connection_pool.clone())))
+        } else {
+            None
+        };
+        #[cfg(feature = "health")]
+        let health_provider = HealthResourceProvider::new()
+            .with_check(Box::new(DatabaseCheck::new(connection_pool.clone())));
+        self.startup_timer.finish_phase("store_init");
+
+        // A standby waits here, before binding any endpoints, until the active daemon stops
+        #[cfg(feature = "high-availability")]
+        let node_lease = match self.high_availability {
+            Some(interval) => Some(NodeLease::acquire(&self.db_url, interval)?),
+            None => None,
+        };
 
         let circuits_location = Path::new(&self.state_dir).join("circuits.yaml");
         let proposals_location = Path::new(&self.state_dir).join("circuit_proposals.yaml");
@@ -241,6 +542,7 @@ impl SplinterDaemon {
 
         // set up the listeners on the transport. This will set up listeners for different
         // transports based on the protocol prefix of the endpoint.
+        self.startup_timer.start_phase();
         let network_listeners = self
             .network_endpoints
             .iter()
@@ -249,6 +551,22 @@ impl SplinterDaemon {
             .map_err(|err| {
                 StartError::TransportError(format!("Cannot create listener for endpoint: {}", err))
             })?;
+        #[cfg(feature = "connection-limits")]
+        let network_listeners = {
+            let mut limits = ConnectionLimits::new();
+            if let Some(max_connections) = self.max_inbound_connections {
+                limits = limits.with_max_connections(max_connections as usize);
+            }
+            if let Some(max_connections_per_ip) = self.max_inbound_connections_per_ip {
+                limits = limits.with_max_connections_per_ip(max_connections_per_ip as usize);
+            }
+            // The limits are shared by all of the network listeners
+            let limiter = ConnectionLimiter::new(limits);
+            network_listeners
+                .into_iter()
+                .map(|listener| limiter.limit_listener(listener))
+                .collect::<Vec<_>>()
+        };
         debug!(
             "Listening for peer connections on {:?}",
             network_listeners
@@ -269,11 +587,35 @@ impl SplinterDaemon {
             transport.listen("inproc://admin-service")?,
             transport.listen("inproc://orchestator")?,
         ];
+        self.startup_timer.finish_phase("transport_bind");
 
         let secp256k1_context: Box<dyn VerifierFactory> = Box::new(Secp256k1Context::new());
+        #[cfg(feature = "signature-verification-pool")]
+        let verification_pool = match self.verification_threads {
+            Some(threads) => Some(
+                VerificationPool::start(threads, VERIFICATION_CACHE_SIZE, &*secp256k1_context)
+                    .map_err(|err| {
+                        StartError::InternalError(format!(
+                            "Unable to start signature verification pool: {}",
+                            err
+                        ))
+                    })?,
+            ),
+            None => None,
+        };
         let admin_service_verifier = secp256k1_context.new_verifier();
+        // Admin payloads are verified on the pool, so that a payload's signature is only checked
+        // once when it is both submitted to and received by this node's admin service
+        #[cfg(feature = "signature-verification-pool")]
+        let admin_service_verifier: Box<dyn Verifier> = match &verification_pool {
+            Some(verification_pool) => Box::new(verification_pool.verifier()),
+            None => admin_service_verifier,
+        };
         let auth_config_verifier = secp256k1_context.new_verifier();
+        #[cfg(feature = "grpc")]
+        let grpc_verifier = secp256k1_context.new_verifier();
         let signing_context = Arc::new(Mutex::new(secp256k1_context));
+        import_node_id_file(&self.state_dir, &*store_factory.get_node_id_store())?;
         let node_id: String = get_node_id(
             self.node_id.as_ref().map(|s| s.to_string()),
             store_factory.get_node_id_store(),
@@ -285,9 +627,19 @@ impl SplinterDaemon {
             self.signers.clone(),
             signing_context.clone(),
         )
-        .map_err(|err| {
+        .and_then(|manager| manager.with_local_capabilities(local_capabilities()));
+        #[cfg(feature = "clock-skew-detection")]
+        let authorization_manager = authorization_manager
+            .and_then(|manager| manager.with_clock_skew_monitor(self.clock_skew_monitor.clone()));
+        #[cfg(feature = "peer-blocklist")]
+        let authorization_manager = authorization_manager.and_then(|manager| {
+            manager
+                .with_peer_blocklist(PeerBlocklist::new(store_factory.get_peer_blocklist_store()))
+        });
+        let authorization_manager = authorization_manager.map_err(|err| {
             StartError::NetworkError(format!("Unable to create authorization manager: {}", err))
         })?;
+        let peer_capabilities_registry = authorization_manager.peer_capabilities_registry();
 
         let inproc_ids = vec![
             (
@@ -306,28 +658,76 @@ impl SplinterDaemon {
         authorizers.add_authorizer("inproc", inproc_authorizer);
         authorizers.add_authorizer("", authorization_manager.authorization_connector());
 
-        let mut connection_manager = ConnectionManager::builder()
+        // Allowing unused_mut because connection_manager_builder must be mutable if the
+        // `peer-reconnect-policy` feature is enabled
+        #[allow(unused_mut)]
+        let mut connection_manager_builder = ConnectionManager::builder()
             .with_authorizer(Box::new(authorizers))
             .with_matrix_life_cycle(self.mesh.get_life_cycle())
             .with_matrix_sender(self.mesh.get_sender())
             .with_transport(Box::new(transport))
-            .with_heartbeat_interval(self.heartbeat)
-            .start()
-            .map_err(|err| {
-                StartError::NetworkError(format!("Unable to start connection manager: {}", err))
-            })?;
+            .with_heartbeat_interval(self.heartbeat);
+        // The connection manager reconnects peers whose connections were dropped
+        #[cfg(feature = "peer-reconnect-policy")]
+        if let Some(max_delay) = self.peer_reconnect_max_delay {
+            connection_manager_builder =
+                connection_manager_builder.with_maximum_retry_frequency(max_delay);
+        }
+        let mut connection_manager = connection_manager_builder.start().map_err(|err| {
+            StartError::NetworkError(format!("Unable to start connection manager: {}", err))
+        })?;
         let connection_connector = connection_manager.connector();
 
-        let mut peer_manager = PeerManager::builder()
+        // Allowing unused_mut because peer_manager_builder must be mutable if the
+        // `peer-ref-counts` feature is enabled
+        #[allow(unused_mut)]
+        let mut peer_manager_builder = PeerManager::builder()
             .with_connector(connection_connector.clone())
             .with_identity(node_id.to_string())
             .with_strict_ref_counts(self.strict_ref_counts)
-            .start()
-            .map_err(|err| {
-                StartError::NetworkError(format!("Unable to start peer manager: {}", err))
-            })?;
+            .with_capabilities_registry(peer_capabilities_registry.clone());
+        #[cfg(feature = "peer-ref-counts")]
+        {
+            if let Some(retry_interval) = self.peer_retry_interval {
+                peer_manager_builder = peer_manager_builder.with_retry_interval(retry_interval);
+            }
+            if let Some(max_retry_frequency) = self.peer_max_retry_frequency {
+                peer_manager_builder =
+                    peer_manager_builder.with_max_retry_frequency(max_retry_frequency);
+            }
+        }
+        #[cfg(feature = "peer-reconnect-policy")]
+        {
+            if let Some(initial_delay) = self.peer_reconnect_initial_delay {
+                peer_manager_builder = peer_manager_builder.with_retry_frequency(initial_delay);
+            }
+            if let Some(max_delay) = self.peer_reconnect_max_delay {
+                peer_manager_builder = peer_manager_builder.with_max_retry_frequency(max_delay);
+            }
+            if let Some(jitter) = self.peer_reconnect_jitter {
+                peer_manager_builder = peer_manager_builder.with_retry_jitter(jitter);
+            }
+            if let Some(max_attempts) = self.peer_reconnect_max_attempts {
+                peer_manager_builder =
+                    peer_manager_builder.with_max_reconnect_attempts(max_attempts);
+            }
+        }
+        #[cfg(feature = "peer-connect-priority")]
+        {
+            if let Some(ramp_rate) = self.peer_connect_ramp_rate {
+                peer_manager_builder = peer_manager_builder.with_connect_ramp_rate(ramp_rate);
+            }
+        }
+        let mut peer_manager = peer_manager_builder.start().map_err(|err| {
+            StartError::NetworkError(format!("Unable to start peer manager: {}", err))
+        })?;
 
         let peer_connector = peer_manager.connector();
+        #[cfg(feature = "health")]
+        let health_provider =
+            health_provider.with_check(Box::new(PeerManagerCheck::new(peer_connector.clone())));
+        #[cfg(feature = "peer-ref-counts")]
+        let peer_ref_counts = peer_manager.ref_counts();
 
         // Listen for services
         Self::listen_for_services(
@@ -394,6 +794,9 @@ impl SplinterDaemon {
         #[cfg(feature = "scabbardv3")]
         let scabbard_store_factory = store::create_scabbard_store_factory(&connection_pool)?;
 
+        #[cfg(feature = "scabbard-consensus-log")]
+        let scabbard_consensus_store_factory = scabbard_store_factory.clone();
+
         #[cfg(feature = "service-echo")]
         let echo_store_factory = store::create_echo_store_factory(&connection_pool)?;
 
@@ -409,6 +812,38 @@ impl SplinterDaemon {
                 .into_boxed(),
         ];
 
+        #[cfg(feature = "circuit-usage")]
+        let circuit_usage_tracker = {
+            let mut quota = CircuitUsageQuota::new();
+            if let Some(max_messages) = self.circuit_usage_message_quota {
+                quota = quota.with_max_messages(max_messages);
+            }
+            if let Some(max_bytes) = self.circuit_usage_byte_quota {
+                quota = quota.with_max_bytes(max_bytes);
+            }
+
+            let tracker = CircuitUsageTracker::new(quota);
+            tracker.restore(
+                store_factory
+                    .get_circuit_usage_store()
+                    .list_usage()
+                    .map_err(|err| {
+                        StartError::StorageError(format!("Unable to load circuit usage: {}", err))
+                    })?,
+            );
+            tracker
+        };
+
+        #[cfg(feature = "circuit-usage")]
+        let mut circuit_usage_persister = CircuitUsagePersister::start(
+            circuit_usage_tracker.clone(),
+            store_factory.get_circuit_usage_store(),
+            CIRCUIT_USAGE_PERSIST_INTERVAL,
+        )
+        .map_err(|err| {
+            StartError::InternalError(format!("Unable to start circuit usage persister: {}", err))
+        })?;
+
         // Set up the Circuit dispatcher
         let circuit_dispatcher = set_up_circuit_dispatcher(
             network_sender.clone(),
@@ -430,6 +865,8 @@ impl SplinterDaemon {
             message_handlers,
             #[cfg(feature = "service2")]
             message_handler_task_pool.task_runner(),
+            #[cfg(feature = "circuit-usage")]
+            circuit_usage_tracker.clone(),
         );
         let mut circuit_dispatch_loop = DispatchLoopBuilder::new()
             .with_dispatcher(circuit_dispatcher)
@@ -442,12 +879,22 @@ impl SplinterDaemon {
 
         #[cfg(not(feature = "service2"))]
         // Set up the Network dispatcher
-        let network_dispatcher =
-            set_up_network_dispatcher(network_sender, &node_id, circuit_dispatch_sender);
+        let network_dispatcher = set_up_network_dispatcher(
+            network_sender,
+            &node_id,
+            circuit_dispatch_sender,
+            #[cfg(feature = "clock-skew-detection")]
+            self.clock_skew_monitor.clone(),
+        );
         #[cfg(feature = "service2")]
         // Set up the Network dispatcher
-        let network_dispatcher =
-            set_up_network_dispatcher(network_sender, &node_id, circuit_dispatch_sender);
+        let network_dispatcher = set_up_network_dispatcher(
+            network_sender,
+            &node_id,
+            circuit_dispatch_sender,
+            #[cfg(feature = "clock-skew-detection")]
+            self.clock_skew_monitor.clone(),
+        );
 
         let mut network_dispatch_loop = DispatchLoopBuilder::new()
             .with_dispatcher(network_dispatcher)
@@ -460,16 +907,23 @@ impl SplinterDaemon {
 
         // setup threads to listen on the network ports and add incoming connections to the network
         // these threads will just be dropped on shutdown
+        #[cfg(feature = "health")]
+        let mut listener_check = ListenerCheck::default();
         let _ = network_listeners
             .into_iter()
             .map(|mut network_listener| {
                 let connection_connector_clone = connection_connector.clone();
+                // the listener is reported as stopped by the readiness check once its thread exits
+                #[cfg(feature = "health")]
+                let listener_guard = listener_check.add_listener(&network_listener.endpoint());
                 thread::Builder::new()
                     .name(format!(
                         "NetworkIncomingListener-{}",
                         network_listener.endpoint()
                     ))
                     .spawn(move || {
+                        #[cfg(feature = "health")]
+                        let _listener_guard = listener_guard;
                         let endpoint = network_listener.endpoint();
                         for connection_result in network_listener.incoming() {
                             let connection = match connection_result {
@@ -504,16 +958,44 @@ impl SplinterDaemon {
                     err
                 ))
             })?;
+        #[cfg(feature = "health")]
+        let health_provider = health_provider.with_check(Box::new(listener_check));
 
         // hold on to peer refs for the peers provided to ensure the connections are kept around
         let mut peer_refs = vec![];
+        self.startup_timer.start_phase();
+        #[cfg(feature = "peer-authorization-policy")]
+        let peering_token = match self.peering_authorization {
+            AuthorizationType::Trust => PeerAuthorizationToken::from_peer_id(&node_id),
+            AuthorizationType::Challenge => self.peering_token.clone(),
+        };
+        #[cfg(not(feature = "peer-authorization-policy"))]
+        let peering_token = self.peering_token.clone();
         for endpoint in self.initial_peers.iter() {
-            let (endpoint, token) = parse_peer_endpoint(endpoint, &self.peering_token, &node_id);
+            let (endpoint, token) = parse_peer_endpoint(
+                endpoint,
+                &peering_token,
+                #[cfg(feature = "peer-authorization-policy")]
+                &self.peering_token,
+                &node_id,
+            );
+            #[cfg(feature = "peer-authorization-policy")]
+            if self.minimum_authorization == AuthorizationType::Challenge
+                && matches!(token, PeerAuthorizationToken::Trust { .. })
+            {
+                error!(
+                    "Not connecting to {}, trust authorization is not permitted by the minimum \
+                     authorization type",
+                    endpoint
+                );
+                continue;
+            }
             match peer_connector.add_unidentified_peer(endpoint, token) {
                 Ok(peer_ref) => peer_refs.push(peer_ref),
                 Err(err) => error!("Connect Error: {}", err),
             }
         }
+        self.startup_timer.finish_phase("peer_bootstrap");
 
         #[cfg(feature = "service2")]
         let mut executor = lifecycle::create_lifecycle_executor(
@@ -547,13 +1029,65 @@ impl SplinterDaemon {
             .with_lmdb_state_enabled(self.enable_lmdb_state)
             .with_state_autocleanup_enabled(self.enable_state_autocleanup);
 
+        #[cfg(feature = "scabbard-anchoring")]
+        if let Some(anchoring) = &self.scabbard_anchoring {
+            scabbard_factory_builder = scabbard_factory_builder.with_anchoring(anchoring.clone());
+        }
+
+        #[cfg(feature = "signature-verification-pool")]
+        if let Some(verification_pool) = &verification_pool {
+            scabbard_factory_builder =
+                scabbard_factory_builder.with_verification_pool(verification_pool.verifier());
+        }
+
+        #[cfg(feature = "scabbard-batch-validation")]
+        if let Some(validator) = &self.scabbard_batch_validator {
+            scabbard_factory_builder =
+                scabbard_factory_builder.with_batch_validator(Arc::clone(validator));
+        }
+
+        #[cfg(feature = "scabbard-receipt-pruning")]
+        if let Some(retention) = &self.scabbard_receipt_retention {
+            scabbard_factory_builder =
+                scabbard_factory_builder.with_receipt_retention(retention.clone());
+        }
+
+        #[cfg(feature = "scabbard-snapshot")]
+        if let Some(snapshots) = &self.scabbard_snapshots {
+            scabbard_factory_builder = scabbard_factory_builder.with_snapshots(snapshots.clone());
+        }
+
+        #[cfg(feature = "scabbard-state-pruning")]
+        if let Some(retention) = &self.scabbard_state_retention {
+            scabbard_factory_builder =
+                scabbard_factory_builder.with_state_retention(retention.clone());
+        }
+
         let scabbard_factory = scabbard_factory_builder
             .build()
             .map_err(|err| StartError::UserError(err.to_string()))?;
 
-        let mut orchestrator = ServiceOrchestratorBuilder::new()
+        let orchestrator_builder = ServiceOrchestratorBuilder::new()
             .with_connection(orchestrator_connection)
-            .with_service_factory(Box::new(scabbard_factory))
+            .with_service_factory(Box::new(scabbard_factory));
+        #[cfg(feature = "service-transfer")]
+        let orchestrator_builder = orchestrator_builder.with_service_factory(Box::new(
+            TransferServiceFactory::new(Path::new(&self.state_dir).join("transfer"))
+                .with_max_file_size(self.transfer_max_file_size),
+        ));
+        #[cfg(feature = "service-restart-policy")]
+        let orchestrator_builder = match &self.service_restart_policy {
+            Some(policy) => orchestrator_builder.with_restart_policy(policy.clone()),
+            None => orchestrator_builder,
+        };
+        #[cfg(feature = "service-type-limits")]
+        let orchestrator_builder = self.service_type_limits.iter().fold(
+            orchestrator_builder,
+            |orchestrator_builder, (service_type, limits)| {
+                orchestrator_builder.with_service_type_limits(service_type, limits.clone())
+            },
+        );
+        let mut orchestrator = orchestrator_builder
             .build()
             .map_err(|err| {
                 StartError::OrchestratorError(format!("failed to create new orchestrator: {}", err))
@@ -563,13 +1097,29 @@ impl SplinterDaemon {
                 StartError::OrchestratorError(format!("failed to start orchestrator: {}", err))
             })?;
 
-        let orchestrator_resources = ServiceOrchestratorRestResourceProviderBuilder::new()
+        let orchestrator_resources_builder = ServiceOrchestratorRestResourceProviderBuilder::new()
             .with_endpoint_factory(
                 scabbard::service::SERVICE_TYPE,
                 Box::new(ScabbardServiceEndpointProvider::default()),
-            )
+            );
+        #[cfg(feature = "service-transfer")]
+        let orchestrator_resources_builder = orchestrator_resources_builder.with_endpoint_factory(
+            transfer::SERVICE_TYPE,
+            Box::new(TransferServiceEndpointProvider::default()),
+        );
+        let orchestrator_resources = orchestrator_resources_builder
             .build(&orchestrator)
             .resources();
+        #[cfg(feature = "service-restart-policy")]
+        let service_restart_tracker = orchestrator.restart_tracker();
+        #[cfg(feature = "subsystem-control")]
+        let service_suspend_handle = orchestrator.suspend_handle();
+        #[cfg(feature = "health")]
+        let health_provider = health_provider.with_check(Box::new(OrchestratorCheck::new(
+            orchestrator.services(),
+            #[cfg(feature = "service-restart-policy")]
+            service_restart_tracker.clone(),
+        )));
         let mut orchestator_shutdown_handle =
             orchestrator.take_shutdown_handle().ok_or_else(|| {
                 StartError::OrchestratorError(
@@ -577,16 +1127,53 @@ impl SplinterDaemon {
                 )
             })?;
 
+        #[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+        let mut registry_refresh_handle = RegistryRefreshHandle::new();
         let (registry, mut registry_shutdown) = create_registry(
             &self.state_dir,
             &self.registries,
             self.registry_auto_refresh,
             self.registry_forced_refresh,
             &*store_factory,
+            #[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+            &mut registry_refresh_handle,
+            #[cfg(feature = "registry-mdns")]
+            registry::mdns_advertisement(
+                &node_id,
+                &self
+                    .display_name
+                    .clone()
+                    .unwrap_or_else(|| format!("Node: {}", node_id)),
+                &self.advertised_endpoints,
+                &self.signers,
+            ),
         );
 
+        // The subsystems that may be stopped and started again through the REST API
+        #[cfg(feature = "subsystem-control")]
+        let mut subsystem_manager = SubsystemManager::new();
+        #[cfg(feature = "subsystem-control")]
+        {
+            subsystem_manager.add_subsystem(
+                "registry-refresher",
+                Box::new(RegistryRefresher::new(
+                    registry_refresh_handle.clone(),
+                    self.registry_auto_refresh,
+                    self.registry_forced_refresh,
+                )),
+            )?;
+            #[cfg(feature = "tap")]
+            if let Some(metrics_switch) = self.metrics_switch.take() {
+                subsystem_manager.add_subsystem("metrics-recorder", Box::new(metrics_switch))?;
+            }
+        }
+
         let mut admin_service_builder = AdminServiceBuilder::new();
 
+        // The gRPC interface finds scabbard services among the orchestrator's services
+        #[cfg(feature = "grpc")]
+        let orchestrator_services = orchestrator.services();
+
         // allow unused mut, needs to be mutable if service2 is enabled
         #[allow(unused_mut)]
         let mut lifecycle_dispatches: Vec<Box<dyn LifecycleDispatch>> =
@@ -634,13 +1221,116 @@ impl SplinterDaemon {
 
         let mut validators: HashMap<String, Box<dyn ServiceArgValidator + Send>> = HashMap::new();
         validators.insert("scabbard".into(), Box::new(ScabbardArgValidator));
+        #[cfg(feature = "service-transfer")]
+        validators.insert(
+            transfer::SERVICE_TYPE.into(),
+            Box::new(TransferArgValidator),
+        );
 
         admin_service_builder = admin_service_builder.with_service_arg_validators(validators);
 
+        #[cfg(feature = "audit")]
+        {
+            admin_service_builder =
+                admin_service_builder.with_audit_store(store_factory.get_audit_store());
+        }
+
+        #[cfg(feature = "admin-service-deferred-proposals")]
+        if let Some(member_wait_timeout) = self.admin_member_wait_timeout {
+            admin_service_builder =
+                admin_service_builder.with_member_wait_timeout(member_wait_timeout);
+        }
+
+        #[cfg(feature = "admin-service-proposal-limits")]
+        if let Some(proposal_limits) = &self.admin_proposal_limits {
+            admin_service_builder =
+                admin_service_builder.with_proposal_limits(proposal_limits.clone());
+        }
+
+        #[cfg(feature = "admin-service-circuit-version")]
+        if let Some(circuit_version) = self.admin_circuit_version {
+            admin_service_builder = admin_service_builder.with_circuit_version(circuit_version);
+        }
+
+        #[cfg(feature = "peer-authorization-policy")]
+        {
+            admin_service_builder = admin_service_builder
+                .with_minimum_authorization_type(self.minimum_authorization.clone());
+        }
+
         let admin_service = admin_service_builder.build().map_err(|err| {
             StartError::AdminServiceError(format!("unable to create admin service: {}", err))
         })?;
 
+        #[cfg(feature = "biome-notifications")]
+        let mut notification_dispatcher = {
+            let mut senders: Vec<Box<dyn NotificationSender>> =
+                vec![Box::new(SlackWebhookSender::new())];
+            if let Some(smtp_url) = &self.notification_smtp_url {
+                let email_from = self.notification_email_from.as_deref().ok_or_else(|| {
+                    StartError::UserError(
+                        "--notification-email-from is required to send email notifications".into(),
+                    )
+                })?;
+                senders.push(Box::new(SmtpSender::new(smtp_url, email_from).map_err(
+                    |err| {
+                        StartError::UserError(format!(
+                            "invalid email notification configuration: {}",
+                            err
+                        ))
+                    },
+                )?));
+            }
+
+            let dispatcher = NotificationDispatcher::start(
+                &node_id,
+                store_factory.get_biome_notification_subscription_store(),
+                senders,
+            )
+            .map_err(|err| {
+                StartError::InternalError(format!(
+                    "Unable to start notification dispatcher: {}",
+                    err
+                ))
+            })?;
+            admin_service
+                .commands()
+                .add_event_subscriber("*", dispatcher.subscriber())
+                .map_err(|err| StartError::AdminServiceError(err.to_string()))?;
+            dispatcher
+        };
+
+        #[cfg(feature = "admin-webhooks")]
+        let webhook_dispatcher: Option<WebhookDispatcher> = if self.admin_webhooks.is_empty() {
+            None
+        } else {
+            let dispatcher = WebhookDispatcherBuilder::new()
+                .with_webhooks(self.admin_webhooks.clone())
+                .start(&node_id)
+                .map_err(|err| {
+                    StartError::InternalError(format!(
+                        "Unable to start webhook dispatcher: {}",
+                        err
+                    ))
+                })?;
+            admin_service
+                .commands()
+                .add_event_subscriber("*", dispatcher.subscriber())
+                .map_err(|err| StartError::AdminServiceError(err.to_string()))?;
+            Some(dispatcher)
+        };
+
+        #[cfg(feature = "registry-auto-populate")]
+        if self.registry_auto_populate {
+            admin_service
+                .commands()
+                .add_event_subscriber(
+                    "*",
+                    Box::new(RegistryAutoPopulator::new(&node_id, registry.clone_box())),
+                )
+                .map_err(|err| StartError::AdminServiceError(err.to_string()))?;
+        }
+
         let display_name: String = self
             .display_name
             .to_owned()
@@ -651,6 +1341,35 @@ impl SplinterDaemon {
         let network_endpoints = self.network_endpoints.clone();
         let advertised_endpoints = self.advertised_endpoints.clone();
 
+        #[cfg(feature = "node-attestation")]
+        let attestor = status::NodeAttestor::new(
+            &node_id,
+            advertised_endpoints.clone(),
+            env!("CARGO_PKG_VERSION"),
+            self.signers.clone(),
+        )
+        .map_err(|err| StartError::RestApiError(err.to_string()))?;
+
+        let status_provider = status::StatusResourceProvider::new(
+            node_id,
+            display_name,
+            #[cfg(feature = "service-endpoint")]
+            service_endpoint,
+            network_endpoints,
+            advertised_endpoints,
+        );
+        #[cfg(feature = "startup-report")]
+        let status_provider = status_provider.with_startup_report(self.startup_timer.report());
+        #[cfg(feature = "capabilities-report")]
+        let status_provider = status_provider.with_capabilities(self.capabilities());
+        #[cfg(feature = "node-attestation")]
+        let status_provider = status_provider.with_attestation(attestor);
+        #[cfg(feature = "config-report")]
+        let status_provider = match &self.config_report {
+            Some(config_report) => status_provider.with_config_report(config_report.clone()),
+            None => status_provider,
+        };
+
         let circuit_resource_provider =
             CircuitResourceProvider::new(store_factory.get_admin_service_store());
 
@@ -663,27 +1382,114 @@ impl SplinterDaemon {
         #[cfg(feature = "https-bind")]
         let bind = self.build_rest_api_bind()?;
 
+        #[cfg(not(feature = "peer-metadata"))]
+        let peer_capabilities_provider =
+            PeerCapabilitiesResourceProvider::new(peer_capabilities_registry);
+        // The peer listing includes the notes and labels operators have attached to each peer
+        #[cfg(feature = "peer-metadata")]
+        let peer_capabilities_provider = PeerCapabilitiesResourceProvider::new_with_metadata_store(
+            peer_capabilities_registry,
+            store_factory.get_peer_metadata_store(),
+        );
+
+        #[cfg(feature = "registry-signed-entries")]
+        let registry_resource_provider = if self.registry_require_signatures {
+            RwRegistryRestResourceProvider::with_signature_verifier(
+                &registry,
+                NodeSignatureVerifier::new(Secp256k1Context::new().new_verifier()),
+            )
+        } else {
+            RwRegistryRestResourceProvider::new(&registry)
+        };
+        #[cfg(not(feature = "registry-signed-entries"))]
+        let registry_resource_provider = RwRegistryRestResourceProvider::new(&registry);
+
+        let admin_resources = AdminServiceRestProvider::new(&admin_service).resources();
+        let registry_resources = registry_resource_provider.resources();
+        let circuit_resources = circuit_resource_provider.resources();
+        let status_resources = status_provider.resources();
+
+        // While the database is down, circuits, proposals, registry nodes and the node status
+        // are served from the responses cached by the degraded mode
+        #[cfg(feature = "rest-api-degraded-mode")]
+        let (admin_resources, registry_resources, circuit_resources, status_resources) =
+            match &degraded_mode {
+                Some(degraded_mode) => (
+                    degraded_mode.wrap_resources(admin_resources),
+                    degraded_mode.wrap_resources(registry_resources),
+                    degraded_mode.wrap_resources(circuit_resources),
+                    degraded_mode.wrap_resources(status_resources),
+                ),
+                None => (
+                    admin_resources,
+                    registry_resources,
+                    circuit_resources,
+                    status_resources,
+                ),
+            };
+
         // Allowing unused_mut because rest_api_builder must be mutable if feature biome is enabled
         #[allow(unused_mut)]
         let mut rest_api_builder = RestApiBuilder::new()
             .with_bind(bind)
-            .add_resources(AdminServiceRestProvider::new(&admin_service).resources())
-            .add_resources(RwRegistryRestResourceProvider::new(&registry).resources())
+            .add_resources(admin_resources)
+            .add_resources(registry_resources)
             .add_resources(orchestrator_resources)
-            .add_resources(circuit_resource_provider.resources())
-            .add_resources(
-                status::StatusResourceProvider::new(
-                    node_id,
-                    display_name,
-                    #[cfg(feature = "service-endpoint")]
-                    service_endpoint,
-                    network_endpoints,
-                    advertised_endpoints,
-                )
-                .resources(),
-            )
+            .add_resources(circuit_resources)
+            .add_resources(peer_capabilities_provider.resources())
+            .add_resources(status_resources)
             .add_resources(open_api::OpenApiResourceProvider::default().resources());
 
+        #[cfg(feature = "rest-api-openapi")]
+        {
+            rest_api_builder = rest_api_builder.with_openapi(
+                OpenApiConfig::new()
+                    .with_base_document(open_api::OPENAPI_BASE_DOCUMENT)
+                    .with_swagger_ui(self.rest_api_swagger_ui),
+            );
+        }
+
+        // The LDAP identity provider is built before the authorization handlers, because the
+        // groups of the users it authenticates are used to grant RBAC roles
+        #[cfg(feature = "ldap")]
+        let ldap_identity_provider = match &self.ldap_url {
+            Some(url) => {
+                let user_search_base = self.ldap_user_search_base.clone().ok_or_else(|| {
+                    StartError::RestApiError("missing LDAP user search base configuration".into())
+                })?;
+                let mut ldap_builder = LdapIdentityProviderBuilder::new()
+                    .with_url(url.clone())
+                    .with_user_search_base(user_search_base);
+                if let Some(bind_dn) = &self.ldap_bind_dn {
+                    ldap_builder = ldap_builder.with_bind_dn(bind_dn.clone());
+                }
+                if let Some(bind_password) = &self.ldap_bind_password {
+                    ldap_builder = ldap_builder.with_bind_password(bind_password.clone());
+                }
+                if let Some(user_search_filter) = &self.ldap_user_search_filter {
+                    ldap_builder = ldap_builder.with_user_search_filter(user_search_filter.clone());
+                }
+                if let Some(group_search_base) = &self.ldap_group_search_base {
+                    ldap_builder = ldap_builder.with_group_search_base(group_search_base.clone());
+                }
+                if let Some(group_search_filter) = &self.ldap_group_search_filter {
+                    ldap_builder =
+                        ldap_builder.with_group_search_filter(group_search_filter.clone());
+                }
+                Some(ldap_builder.build().map_err(|err| {
+                    StartError::RestApiError(format!(
+                        "Unable to build LDAP identity provider: {}",
+                        err
+                    ))
+                })?)
+            }
+            None => None,
+        };
+
+        // The gRPC interface checks permissions with the same handlers as the REST API
+        #[cfg(feature = "grpc")]
+        let grpc_authorization_handlers;
+
         #[cfg(feature = "authorization")]
         {
             // Allowing unused_mut because authorization_handlers must be mutable if
@@ -707,6 +1513,28 @@ impl SplinterDaemon {
             #[cfg(feature = "authorization-handler-rbac")]
             let rbac_store = store_factory.get_role_based_authorization_store();
 
+            // On first start, before any identity has been granted access, a one-time token is
+            // generated that can be exchanged for admin access
+            #[cfg(feature = "authorization-bootstrap")]
+            {
+                let bootstrap_token =
+                    BootstrapToken::new(Path::new(&self.state_dir).join("bootstrap_token"));
+                #[cfg(feature = "authorization-handler-allow-keys")]
+                let bootstrap_token = bootstrap_token.with_allow_keys_file(create_allow_keys_path(
+                    &self.config_dir,
+                    #[cfg(feature = "config-allow-keys")]
+                    &self.allow_keys_file,
+                    #[cfg(not(feature = "config-allow-keys"))]
+                    "allow_keys",
+                ));
+                #[cfg(feature = "authorization-handler-rbac")]
+                let bootstrap_token = bootstrap_token
+                    .with_rbac_store(store_factory.get_role_based_authorization_store());
+                if bootstrap_token.generate_if_unconfigured()? {
+                    rest_api_builder = rest_api_builder.add_resources(bootstrap_token.resources());
+                }
+            }
+
             #[cfg(feature = "authorization-handler-maintenance")]
             {
                 #[cfg(feature = "authorization-handler-rbac")]
@@ -723,23 +1551,51 @@ impl SplinterDaemon {
             {
                 authorization_handlers
                     .push(Box::new(RoleBasedAuthorizationHandler::new(rbac_store)));
-                rest_api_builder = rest_api_builder.add_resources(
-                    RoleBasedAuthorizationResourceProvider::new(
+                #[cfg(feature = "ldap")]
+                if let Some(ldap_identity_provider) = &ldap_identity_provider {
+                    authorization_handlers.push(Box::new(LdapGroupAuthorizationHandler::new(
+                        ldap_identity_provider.user_groups(),
+                        self.ldap_group_roles.clone().unwrap_or_default(),
                         store_factory.get_role_based_authorization_store(),
-                    )
-                    .resources(),
+                    )));
+                }
+                #[cfg(feature = "rest-api-client-cert-auth")]
+                if !self.rest_api_client_cert_roles.is_empty() {
+                    authorization_handlers.push(Box::new(ClientCertRoleAuthorizationHandler::new(
+                        self.rest_api_client_cert_roles.clone(),
+                        store_factory.get_role_based_authorization_store(),
+                    )));
+                }
+                let rbac_resource_provider = RoleBasedAuthorizationResourceProvider::new(
+                    store_factory.get_role_based_authorization_store(),
                 );
+                #[cfg(feature = "audit")]
+                let rbac_resource_provider =
+                    rbac_resource_provider.with_audit_store(store_factory.get_audit_store());
+                rest_api_builder =
+                    rest_api_builder.add_resources(rbac_resource_provider.resources());
+            }
+
+            #[cfg(feature = "grpc")]
+            {
+                grpc_authorization_handlers = authorization_handlers.clone();
             }
 
             rest_api_builder = rest_api_builder.with_authorization_handlers(authorization_handlers)
         }
 
+        // The allow list is shared with the REST API so that it may be changed on reload
         #[cfg(feature = "rest-api-cors")]
-        {
-            if let Some(list) = &self.allow_list {
+        let cors_allow_list = match &self.allow_list {
+            Some(list) => {
                 debug!("Allow listed domains added to CORS");
-                rest_api_builder = rest_api_builder.with_allow_list(list.to_vec());
+                CorsAllowList::new(list.to_vec())
             }
+            None => CorsAllowList::allow_any(),
+        };
+        #[cfg(feature = "rest-api-cors")]
+        {
+            rest_api_builder = rest_api_builder.with_cors_allow_list(cors_allow_list.clone());
         }
 
         #[allow(unused_mut)]
@@ -854,6 +1710,42 @@ impl SplinterDaemon {
             }
         }
 
+        // Add LDAP as an auth provider if an LDAP server is configured
+        #[cfg(feature = "ldap")]
+        if let Some(ldap_identity_provider) = ldap_identity_provider {
+            auth_configs.push(AuthConfig::Custom {
+                resources: vec![],
+                identity_provider: Box::new(ldap_identity_provider),
+            });
+        }
+
+        // Accept API keys issued through the /api_keys endpoints
+        #[cfg(feature = "api-keys")]
+        auth_configs.push(AuthConfig::Custom {
+            resources: vec![],
+            identity_provider: Box::new(ApiKeyIdentityProvider::new(
+                store_factory.get_api_key_store(),
+            )),
+        });
+
+        // Trust the identity header set by an authenticating reverse proxy
+        #[cfg(feature = "rest-api-proxy-auth")]
+        if let Some(header) = &self.rest_api_proxy_auth_header {
+            auth_configs.push(AuthConfig::Proxy {
+                header: header.clone(),
+                trusted_addresses: self.rest_api_proxy_trusted_addresses.clone(),
+            });
+        }
+
+        // Authenticate clients by their TLS client certificates; no other credentials are
+        // accepted
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        if !self.rest_api_client_cert_identities.is_empty() {
+            auth_configs.push(AuthConfig::ClientCertificate {
+                identities: self.rest_api_client_cert_identities.clone(),
+            });
+        }
+
         rest_api_builder = rest_api_builder.with_auth_configs(auth_configs);
 
         #[cfg(feature = "biome-key-management")]
@@ -876,23 +1768,237 @@ impl SplinterDaemon {
             );
         }
 
+        #[cfg(feature = "announcements")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                AnnouncementResourceProvider::new(store_factory.get_announcement_store())
+                    .resources(),
+            );
+        }
+
+        #[cfg(feature = "api-keys")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                ApiKeyResourceProvider::new(store_factory.get_api_key_store()).resources(),
+            );
+        }
+
+        #[cfg(feature = "audit")]
+        {
+            rest_api_builder = rest_api_builder
+                .with_audit_store(store_factory.get_audit_store())
+                .add_resources(
+                    AuditResourceProvider::new(store_factory.get_audit_store()).resources(),
+                );
+        }
+
+        #[cfg(feature = "peer-blocklist")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                PeerBlocklistResourceProvider::new(store_factory.get_peer_blocklist_store())
+                    .resources(),
+            );
+        }
+
+        #[cfg(feature = "peer-metadata")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                PeerMetadataResourceProvider::new(store_factory.get_peer_metadata_store())
+                    .resources(),
+            );
+        }
+
+        #[cfg(feature = "biome-notifications")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                BiomeNotificationsRestResourceProvider::new(Arc::new(
+                    store_factory.get_biome_notification_subscription_store(),
+                ))
+                .resources(),
+            );
+        }
+
+        #[cfg(feature = "circuit-usage")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                CircuitUsageResourceProvider::new(circuit_usage_tracker).resources(),
+            );
+        }
+
+        #[cfg(feature = "service-restart-policy")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                ServiceRestartResourceProvider::new(service_restart_tracker).resources(),
+            );
+        }
+
+        #[cfg(feature = "health")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(health_provider.resources());
+        }
+
+        #[cfg(feature = "subsystem-control")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                SubsystemResourceProvider::new(subsystem_manager.clone())
+                    .with_service_suspend_handle(service_suspend_handle)
+                    .resources(),
+            );
+        }
+
+        #[cfg(feature = "peer-ref-counts")]
+        {
+            rest_api_builder = rest_api_builder
+                .add_resources(PeerRefCountsResourceProvider::new(peer_ref_counts).resources());
+        }
+
+        #[cfg(feature = "scabbard-consensus-log")]
+        {
+            rest_api_builder = rest_api_builder.add_resources(
+                ScabbardConsensusResourceProvider::new(scabbard_consensus_store_factory)
+                    .resources(),
+            );
+        }
+
+        self.startup_timer.start_phase();
         let (rest_api_shutdown_handle, rest_api_join_handle) = rest_api_builder.build()?.run()?;
+        self.startup_timer.finish_phase("rest_bind");
+
+        #[cfg(feature = "grpc")]
+        let grpc_server = match &self.grpc_bind {
+            Some(bind) => Some(GrpcServer::start(
+                bind,
+                GrpcAuthorizer::new(grpc_verifier, grpc_authorization_handlers),
+                Box::new(admin_service.commands()),
+                store_factory.get_admin_service_store(),
+                orchestrator_services,
+            )?),
+            None => None,
+        };
+
+        #[cfg(any(feature = "biome-credentials", feature = "oauth"))]
+        let mut biome_retention_scheduler = {
+            #[allow(unused_mut)]
+            let mut policy = BiomeRetentionPolicy::new();
+
+            #[cfg(feature = "biome-credentials")]
+            {
+                policy = policy.with_refresh_token_retention(
+                    store_factory.get_biome_refresh_token_store(),
+                    self.biome_refresh_token_retention,
+                );
+            }
+
+            #[cfg(feature = "oauth")]
+            {
+                policy = policy.with_oauth_session_retention(
+                    store_factory.get_biome_oauth_user_session_store(),
+                    self.oauth_session_retention,
+                );
+            }
+
+            BiomeRetentionScheduler::start(policy, BIOME_RETENTION_INTERVAL).map_err(|err| {
+                StartError::InternalError(format!(
+                    "Unable to start Biome retention scheduler: {}",
+                    err
+                ))
+            })?
+        };
 
         let mut admin_shutdown_handle = Self::start_admin_service(admin_connection, admin_service)?;
 
+        self.startup_timer.finish();
+
         let (shutdown_tx, shutdown_rx) = channel();
-        ctrlc::set_handler(move || {
-            if shutdown_tx.send(()).is_err() {
-                // This was the second ctrl-c (as the receiver is dropped after the first one).
-                std::process::exit(0);
+        #[cfg(feature = "config-reload")]
+        let reload_signal_handle = start_reload_signal_thread(shutdown_tx.clone())?;
+        #[cfg(feature = "high-availability")]
+        let node_lease_monitor = match (&node_lease, self.high_availability) {
+            (Some(node_lease), Some(interval)) => {
+                let lease_lost_tx = shutdown_tx.clone();
+                Some(node_lease.monitor(interval, running.clone(), move || {
+                    let _ = lease_lost_tx.send(DaemonSignal::Shutdown);
+                })?)
             }
-        })
-        .expect("Error setting Ctrl-C handler");
+            _ => None,
+        };
+        #[cfg(feature = "daemon-hooks")]
+        let shutdown_handle = DaemonShutdownHandle::new(shutdown_tx.clone());
+        #[cfg(feature = "daemon-hooks")]
+        let handle_ctrlc = !self.disable_ctrlc_handler;
+        #[cfg(not(feature = "daemon-hooks"))]
+        let handle_ctrlc = true;
+        // Without the handler, the sender is kept until the daemon stops, and only the shutdown
+        // handle given to the hooks stops the daemon
+        if handle_ctrlc {
+            ctrlc::set_handler(move || {
+                if shutdown_tx.send(DaemonSignal::Shutdown).is_err() {
+                    // This was the second ctrl-c (as the receiver is dropped after the first one).
+                    std::process::exit(0);
+                }
+            })
+            .expect("Error setting Ctrl-C handler");
+        }
 
-        // recv that value, ignoring the result.
-        let _ = shutdown_rx.recv();
+        #[cfg(feature = "config-reload")]
+        let mut config_reloader = self.config_reloader.take();
+
+        // The node, its transports and the REST API are all running at this point
+        #[cfg(feature = "systemd")]
+        let watchdog_interval = systemd::watchdog_interval();
+        #[cfg(feature = "systemd")]
+        systemd::notify_ready();
+
+        #[cfg(feature = "daemon-hooks")]
+        let mut hooks = self.hooks.take();
+        #[cfg(feature = "daemon-hooks")]
+        if let Some(hooks) = hooks.as_mut() {
+            hooks.on_started(shutdown_handle);
+        }
+
+        // Wait for a shutdown signal, reloading the configuration whenever it is requested
+        loop {
+            #[cfg(feature = "systemd")]
+            let signal = match watchdog_interval {
+                Some(interval) => match shutdown_rx.recv_timeout(interval) {
+                    Err(RecvTimeoutError::Timeout) => {
+                        systemd::notify_watchdog();
+                        continue;
+                    }
+                    result => result.map_err(|_| RecvError),
+                },
+                None => shutdown_rx.recv(),
+            };
+            #[cfg(not(feature = "systemd"))]
+            let signal = shutdown_rx.recv();
+
+            match signal {
+                #[cfg(feature = "config-reload")]
+                Ok(DaemonSignal::Reload) => match config_reloader.as_mut() {
+                    Some(reloader) => reload::reload_config(
+                        &mut **reloader,
+                        &reload::ReloadTargets {
+                            connection_manager: &connection_manager,
+                            registry_refresh_handle: &registry_refresh_handle,
+                            #[cfg(feature = "rest-api-cors")]
+                            cors_allow_list: &cors_allow_list,
+                        },
+                    ),
+                    None => warn!("Received SIGHUP, but configuration reload is not configured"),
+                },
+                Ok(DaemonSignal::Shutdown) | Err(_) => break,
+            }
+        }
         drop(shutdown_rx);
+        #[cfg(feature = "config-reload")]
+        reload_signal_handle.close();
         info!("Initiating graceful shutdown (press Ctrl+C again to force)");
+        #[cfg(feature = "daemon-hooks")]
+        if let Some(hooks) = hooks.as_mut() {
+            hooks.on_stopping();
+        }
+        #[cfg(feature = "systemd")]
+        systemd::notify_stopping();
 
         running.store(false, Ordering::SeqCst);
 
@@ -910,6 +2016,55 @@ impl SplinterDaemon {
         if let Err(err) = rest_api_shutdown_handle.shutdown() {
             error!("Unable to cleanly shut down REST API server: {}", err);
         }
+
+        #[cfg(feature = "grpc")]
+        if let Some(mut grpc_server) = grpc_server {
+            grpc_server.signal_shutdown();
+            if let Err(err) = grpc_server.wait_for_shutdown() {
+                error!("Unable to cleanly shut down gRPC server: {}", err);
+            }
+        }
+
+        #[cfg(feature = "signature-verification-pool")]
+        if let Some(mut verification_pool) = verification_pool {
+            verification_pool.signal_shutdown();
+            if let Err(err) = verification_pool.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down signature verification pool: {}",
+                    err
+                );
+            }
+        }
+
+        #[cfg(feature = "biome-notifications")]
+        {
+            notification_dispatcher.signal_shutdown();
+            if let Err(err) = notification_dispatcher.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down notification dispatcher: {}",
+                    err
+                );
+            }
+        }
+
+        #[cfg(feature = "admin-webhooks")]
+        if let Some(mut webhook_dispatcher) = webhook_dispatcher {
+            webhook_dispatcher.signal_shutdown();
+            if let Err(err) = webhook_dispatcher.wait_for_shutdown() {
+                error!("Unable to cleanly shut down webhook dispatcher: {}", err);
+            }
+        }
+
+        #[cfg(any(feature = "biome-credentials", feature = "oauth"))]
+        {
+            biome_retention_scheduler.signal_shutdown();
+            if let Err(err) = biome_retention_scheduler.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down Biome retention scheduler: {}",
+                    err
+                );
+            }
+        }
         circuit_dispatch_loop.signal_shutdown();
         network_dispatch_loop.signal_shutdown();
 
@@ -917,10 +2072,30 @@ impl SplinterDaemon {
             error!("Unable to cleanly shut down circuit dispatch loop: {}", err);
         }
 
+        // Shut down after the circuit dispatch loop so that the final usage is persisted
+        #[cfg(feature = "circuit-usage")]
+        {
+            circuit_usage_persister.signal_shutdown();
+            if let Err(err) = circuit_usage_persister.wait_for_shutdown() {
+                error!(
+                    "Unable to cleanly shut down circuit usage persister: {}",
+                    err
+                );
+            }
+        }
+
         if let Err(err) = network_dispatch_loop.wait_for_shutdown() {
             error!("Unable to cleanly shut down network dispatch loop: {}", err);
         }
 
+        #[cfg(feature = "subsystem-control")]
+        {
+            subsystem_manager.signal_shutdown();
+            if let Err(err) = subsystem_manager.wait_for_shutdown() {
+                error!("Unable to cleanly shut down subsystems: {}", err);
+            }
+        }
+
         registry_shutdown.signal_shutdown();
         if let Err(err) = registry_shutdown.wait_for_shutdown() {
             error!("Unable to cleanly shut down network dispatch loop: {}", err);
@@ -979,6 +2154,22 @@ impl SplinterDaemon {
                 error!("Unable to cleanly shut down scabbard supervisor: {}", err);
             }
         }
+
+        // The lease is released last, so the standby only takes over once this daemon has stopped
+        #[cfg(feature = "high-availability")]
+        {
+            if let Some(node_lease_monitor) = node_lease_monitor {
+                if node_lease_monitor.join().is_err() {
+                    error!("Unable to cleanly shut down node lease monitor");
+                }
+            }
+            drop(node_lease);
+        }
+
+        #[cfg(feature = "daemon-hooks")]
+        if let Some(hooks) = hooks.as_mut() {
+            hooks.on_stopped();
+        }
         Ok(())
     }
 
@@ -1001,6 +2192,8 @@ impl SplinterDaemon {
                             .expect("There should be a value, due to the above or"),
                         cert_path: rest_api_server_cert.clone(),
                         key_path: rest_api_server_key.clone(),
+                        #[cfg(feature = "rest-api-client-cert-auth")]
+                        client_ca_path: self.rest_api_client_ca.clone(),
                     })
                 } else {
                     Err(StartError::RestApiError(
@@ -1013,6 +2206,68 @@ impl SplinterDaemon {
         }
     }
 
+    /// Returns the optional features of this node that are reported by `GET /capabilities`.
+    #[cfg(feature = "capabilities-report")]
+    fn capabilities(&self) -> status::Capabilities {
+        let local_capabilities = local_capabilities();
+
+        let mut capabilities = status::Capabilities::new()
+            .with_scabbard_version("v2")
+            .with_auth_type("cylinder");
+        #[cfg(feature = "scabbardv3")]
+        {
+            capabilities = capabilities.with_scabbard_version("v3");
+        }
+        for service_type in local_capabilities.service_types() {
+            capabilities = capabilities.with_service_type(service_type);
+        }
+        for consensus_engine in local_capabilities.consensus_engines() {
+            capabilities = capabilities.with_consensus_engine(consensus_engine);
+        }
+        for (protocol, version) in local_capabilities.protocols() {
+            capabilities = capabilities.with_protocol(protocol, *version);
+        }
+
+        #[cfg(feature = "biome-credentials")]
+        if self.enable_biome_credentials {
+            capabilities = capabilities
+                .with_auth_type("biome")
+                .with_biome_component("credentials");
+        }
+        #[cfg(feature = "biome-key-management")]
+        {
+            capabilities = capabilities.with_biome_component("key-management");
+        }
+        #[cfg(feature = "biome-profile")]
+        {
+            capabilities = capabilities.with_biome_component("profile");
+        }
+        #[cfg(feature = "oauth")]
+        if let Some(oauth_provider) = &self.oauth_provider {
+            capabilities = capabilities
+                .with_auth_type("oauth")
+                .with_oauth_provider(oauth_provider);
+        }
+        #[cfg(feature = "ldap")]
+        if self.ldap_url.is_some() {
+            capabilities = capabilities.with_auth_type("ldap");
+        }
+        #[cfg(feature = "api-keys")]
+        {
+            capabilities = capabilities.with_auth_type("api-key");
+        }
+        #[cfg(feature = "rest-api-proxy-auth")]
+        if self.rest_api_proxy_auth_header.is_some() {
+            capabilities = capabilities.with_auth_type("proxy");
+        }
+        #[cfg(feature = "rest-api-client-cert-auth")]
+        if !self.rest_api_client_cert_identities.is_empty() {
+            capabilities = capabilities.with_auth_type("client-certificate");
+        }
+
+        capabilities
+    }
+
     fn listen_for_services(
         connection_connector: Connector,
         internal_service_listeners: Vec<Box<dyn Listener>>,
@@ -1103,10 +2358,36 @@ impl SplinterDaemon {
     }
 }
 
+/// Returns the capabilities this node advertises to its peers.
+fn local_capabilities() -> NodeCapabilities {
+    #[allow(unused_mut)]
+    let mut builder = NodeCapabilitiesBuilder::new()
+        .with_service_type("scabbard")
+        .with_protocol("admin", CIRCUIT_PROTOCOL_VERSION as u32)
+        .with_protocol("peer-authorization", PEER_AUTHORIZATION_PROTOCOL_VERSION)
+        .with_consensus_engine("2pc");
+
+    #[cfg(feature = "scabbardv3")]
+    {
+        builder = builder.with_service_type(&SCABBARD_SERVICE_TYPE.to_string());
+    }
+    #[cfg(feature = "service-echo")]
+    {
+        builder = builder.with_service_type(&ECHO_SERVICE_TYPE.to_string());
+    }
+    #[cfg(feature = "service-transfer")]
+    {
+        builder = builder.with_service_type(transfer::SERVICE_TYPE);
+    }
+
+    builder.build()
+}
+
 fn set_up_network_dispatcher(
     network_sender: NetworkMessageSender,
     node_id: &str,
     circuit_sender: DispatchMessageSender<CircuitMessageType>,
+    #[cfg(feature = "clock-skew-detection")] clock_skew_monitor: ClockSkewMonitor,
 ) -> Dispatcher<NetworkMessageType> {
     let mut dispatcher = Dispatcher::<NetworkMessageType>::new(Box::new(network_sender));
 
@@ -1114,6 +2395,9 @@ fn set_up_network_dispatcher(
     dispatcher.set_handler(Box::new(network_echo_handler));
 
     let network_heartbeat_handler = NetworkHeartbeatHandler::new();
+    #[cfg(feature = "clock-skew-detection")]
+    let network_heartbeat_handler =
+        network_heartbeat_handler.with_clock_skew_monitor(clock_skew_monitor);
     // do not add auth guard
     dispatcher.set_handler(Box::new(network_heartbeat_handler));
 
@@ -1133,6 +2417,7 @@ fn set_up_circuit_dispatcher(
     #[cfg(feature = "service2")] message_handler_task_runner: impl MessageHandlerTaskRunner
         + Send
         + 'static,
+    #[cfg(feature = "circuit-usage")] circuit_usage_tracker: CircuitUsageTracker,
 ) -> Dispatcher<CircuitMessageType> {
     #[cfg(not(feature = "service2"))]
     let mut dispatcher = Dispatcher::<CircuitMessageType>::new(Box::new(network_sender));
@@ -1165,6 +2450,8 @@ fn set_up_circuit_dispatcher(
             Box::new(message_handler_task_runner),
         ),
     );
+    #[cfg(feature = "circuit-usage")]
+    let direct_message_handler = direct_message_handler.with_usage_tracker(circuit_usage_tracker);
     dispatcher.set_handler(Box::new(direct_message_handler));
 
     let circuit_error_handler =
@@ -1185,29 +2472,59 @@ fn create_registry(
     auto_refresh_interval: u64,
     forced_refresh_interval: u64,
     store_factory: &dyn splinter::store::StoreFactory,
+    #[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+    registry_refresh_handle: &mut RegistryRefreshHandle,
+    #[cfg(feature = "registry-mdns")] mut mdns_advertisement: Option<MdnsAdvertisement>,
 ) -> (Box<dyn RwRegistry>, RegistryShutdownHandle) {
     let mut registry_shutdown_handle = RegistryShutdownHandle::new();
 
     let local_registry = store_factory.get_registry_store();
 
-    let read_only_registries = registries
+    // Remote registries are fetched concurrently, each on its own thread, so that a slow or
+    // unreachable server only delays startup by its own fetch timeout; the registries are still
+    // added in the order they were configured
+    let pending_registries = registries
         .iter()
-        .filter_map(|registry| {
+        .map(|registry| {
             let (scheme, path) = parse_registry_arg(registry);
 
+            // The local node is advertised by the first mDNS registry
+            #[cfg(feature = "registry-mdns")]
+            {
+                if scheme == "mdns" {
+                    debug!("Attempting to add read-only registry of nodes discovered over mDNS");
+                    return match MdnsRegistry::new(mdns_advertisement.take()) {
+                        Ok(mut registry) => {
+                            if let Some(shutdown_handle) = registry.take_shutdown_handle() {
+                                registry_shutdown_handle.add_mdns_shutdown_handle(shutdown_handle)
+                            }
+                            PendingRegistry::Ready(Some(
+                                Box::new(registry) as Box<dyn RegistryReader>
+                            ))
+                        }
+                        Err(err) => {
+                            error!("Failed to add read-only MdnsRegistry: {}", err);
+                            PendingRegistry::Ready(None)
+                        }
+                    };
+                }
+            }
+
             if scheme == "file" {
                 debug!(
                     "Attempting to add local read-only registry from file: {}",
                     path
                 );
                 match LocalYamlRegistry::new(path) {
-                    Ok(registry) => Some(Box::new(registry) as Box<dyn RegistryReader>),
+                    Ok(registry) => {
+                        PendingRegistry::Ready(Some(Box::new(registry) as Box<dyn RegistryReader>))
+                    }
                     Err(err) => {
                         error!(
                             "Failed to add read-only LocalYamlRegistry '{}': {}",
                             path, err
                         );
-                        None
+                        PendingRegistry::Ready(None)
                     }
                 }
             } else if scheme == "http" || scheme == "https" {
@@ -1225,27 +2542,25 @@ fn create_registry(
                 } else {
                     None
                 };
-                match RemoteYamlRegistry::new(
-                    registry,
-                    state_dir,
-                    auto_refresh_interval,
-                    forced_refresh_interval,
-                ) {
-                    Ok(mut registry) => {
-                        // this should always return some
-                        if let Some(shutdown_handle) = registry.take_shutdown_handle() {
-                            registry_shutdown_handle
-                                .add_remote_yaml_shutdown_handle(shutdown_handle)
-                        }
-
-                        Some(Box::new(registry) as Box<dyn RegistryReader>)
-                    }
+                let url = registry.to_string();
+                let cache_dir = state_dir.to_string();
+                match thread::Builder::new()
+                    .name(format!("Remote Registry Initial Fetch: {}", registry))
+                    .spawn(move || {
+                        RemoteYamlRegistry::new(
+                            &url,
+                            &cache_dir,
+                            auto_refresh_interval,
+                            forced_refresh_interval,
+                        )
+                    }) {
+                    Ok(join_handle) => PendingRegistry::Remote(registry, join_handle),
                     Err(err) => {
                         error!(
                             "Failed to add read-only RemoteYamlRegistry '{}': {}",
                             registry, err
                         );
-                        None
+                        PendingRegistry::Ready(None)
                     }
                 }
             } else {
@@ -1253,9 +2568,43 @@ fn create_registry(
                     "Invalid registry URI scheme provided ({}): must be file, http, or https",
                     registry
                 );
-                None
+                PendingRegistry::Ready(None)
             }
         })
+        .collect::<Vec<_>>();
+
+    let read_only_registries = pending_registries
+        .into_iter()
+        .filter_map(|pending| match pending {
+            PendingRegistry::Ready(registry) => registry,
+            PendingRegistry::Remote(url, join_handle) => match join_handle.join() {
+                Ok(Ok(mut registry)) => {
+                    #[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+                    registry_refresh_handle
+                        .add_remote_yaml_refresh_handle(registry.refresh_handle());
+                    // this should always return some
+                    if let Some(shutdown_handle) = registry.take_shutdown_handle() {
+                        registry_shutdown_handle.add_remote_yaml_shutdown_handle(shutdown_handle)
+                    }
+
+                    Some(Box::new(registry) as Box<dyn RegistryReader>)
+                }
+                Ok(Err(err)) => {
+                    error!(
+                        "Failed to add read-only RemoteYamlRegistry '{}': {}",
+                        url, err
+                    );
+                    None
+                }
+                Err(_) => {
+                    error!(
+                        "Failed to add read-only RemoteYamlRegistry '{}': initial fetch panicked",
+                        url
+                    );
+                    None
+                }
+            },
+        })
         .collect();
 
     let unified_registry = Box::new(UnifiedRegistry::new(local_registry, read_only_registries));
@@ -1263,6 +2612,49 @@ fn create_registry(
     (unified_registry, registry_shutdown_handle)
 }
 
+/// A read-only registry that is being created by `create_registry`.
+enum PendingRegistry<'a> {
+    /// A registry that has been created, or `None` if it could not be
+    Ready(Option<Box<dyn RegistryReader>>),
+    /// A remote registry, identified by its URL, whose initial fetch is running on a thread
+    Remote(
+        &'a str,
+        thread::JoinHandle<Result<RemoteYamlRegistry, RegistryError>>,
+    ),
+}
+
+/// Starts a thread that sends a `DaemonSignal::Reload` each time the process receives SIGHUP.
+///
+/// The returned handle must be closed to stop the thread.
+#[cfg(feature = "config-reload")]
+fn start_reload_signal_thread(
+    sender: Sender<DaemonSignal>,
+) -> Result<signal_hook::iterator::Handle, StartError> {
+    let mut signals =
+        signal_hook::iterator::Signals::new(&[signal_hook::consts::SIGHUP]).map_err(|err| {
+            StartError::InternalError(format!("Unable to register SIGHUP handler: {}", err))
+        })?;
+    let handle = signals.handle();
+
+    thread::Builder::new()
+        .name("ConfigReloadSignal".into())
+        .spawn(move || {
+            for _ in signals.forever() {
+                if sender.send(DaemonSignal::Reload).is_err() {
+                    break;
+                }
+            }
+        })
+        .map_err(|err| {
+            StartError::InternalError(format!(
+                "Unable to start configuration reload signal thread: {}",
+                err
+            ))
+        })?;
+
+    Ok(handle)
+}
+
 // Parses a registry argument, returning the uri scheme (defaulting to file) and remaining uri data
 fn parse_registry_arg(registry: &str) -> (&str, &str) {
     let mut iter = registry.splitn(2, "://");
@@ -1317,6 +2709,43 @@ mod tests {
         );
     }
 
+    /// Verify that the +trust and +challenge suffixes override the default peering token, and
+    /// are removed from the endpoint.
+    #[cfg(feature = "peer-authorization-policy")]
+    #[test]
+    fn test_parse_peer_endpoint() {
+        let trust_token = PeerAuthorizationToken::from_peer_id("node-a");
+        let challenge_token = PeerAuthorizationToken::from_public_key(&[1, 2, 3]);
+
+        assert_eq!(
+            parse_peer_endpoint(
+                "tcps://node-b:8044",
+                &trust_token,
+                &challenge_token,
+                "node-a"
+            ),
+            ("tcps://node-b:8044".to_string(), trust_token.clone())
+        );
+        assert_eq!(
+            parse_peer_endpoint(
+                "tcps+challenge://node-b:8044",
+                &trust_token,
+                &challenge_token,
+                "node-a"
+            ),
+            ("tcps://node-b:8044".to_string(), challenge_token.clone())
+        );
+        assert_eq!(
+            parse_peer_endpoint(
+                "tcps+trust://node-b:8044",
+                &challenge_token,
+                &challenge_token,
+                "node-a"
+            ),
+            ("tcps://node-b:8044".to_string(), trust_token)
+        );
+    }
+
     #[cfg(feature = "authorization-handler-allow-keys")]
     #[test]
     fn test_create_allow_keys_path_absolute_path() {
@@ -1368,18 +2797,27 @@ fn create_allow_keys_path(config_path: &str, allow_keys_file: &str) -> PathBuf {
 /// Parse the peer endpoint that we want to connect to regardless of a circuit. The endpoint will
 /// either be in normal form impling it should use the configured peer authorization token for
 /// peering (usually challenge, unless no keys were provided) or includes +trust after the
-/// transport type which means a trust token should be used
+/// transport type which means a trust token should be used. With the `peer-authorization-policy`
+/// feature, +challenge after the transport type means the challenge token should be used.
 fn parse_peer_endpoint(
     endpoint: &str,
     peering_token: &PeerAuthorizationToken,
+    #[cfg(feature = "peer-authorization-policy")] challenge_token: &PeerAuthorizationToken,
     node_id: &str,
 ) -> (String, PeerAuthorizationToken) {
     // if endpoint is in the form tcp+trust://ipaddr:port Trust authorization must be used
     if endpoint.contains("+trust://") {
         // set endpoint to the form tcp://ipaddr:port, removing the +trust and return a trust token
         let endpoint = endpoint.replace("+trust://", "://");
-        (endpoint, PeerAuthorizationToken::from_peer_id(node_id))
-    } else {
-        (endpoint.to_string(), peering_token.clone())
+        return (endpoint, PeerAuthorizationToken::from_peer_id(node_id));
     }
+
+    // if endpoint is in the form tcp+challenge://ipaddr:port Challenge authorization must be used
+    #[cfg(feature = "peer-authorization-policy")]
+    if endpoint.contains("+challenge://") {
+        let endpoint = endpoint.replace("+challenge://", "://");
+        return (endpoint, challenge_token.clone());
+    }
+
+    (endpoint.to_string(), peering_token.clone())
 }
@@ -12,13 +12,28 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "registry-mdns")]
+use std::net::Ipv4Addr;
+#[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+use std::time::Duration;
+
+#[cfg(feature = "registry-mdns")]
+use cylinder::Signer;
 use splinter::error::InternalError;
 use splinter::registry::RemoteYamlShutdownHandle;
+#[cfg(feature = "registry-mdns")]
+use splinter::registry::{MdnsAdvertisement, MdnsRegistryShutdownHandle, Node};
+#[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+use splinter::registry::{RegistryError, RemoteYamlRefreshHandle};
 use splinter::threading::lifecycle::ShutdownHandle;
+#[cfg(feature = "subsystem-control")]
+use splinter::threading::subsystem::Subsystem;
 
 #[derive(Default)]
 pub struct RegistryShutdownHandle {
     remote_yaml_shutdown_handles: Vec<RemoteYamlShutdownHandle>,
+    #[cfg(feature = "registry-mdns")]
+    mdns_shutdown_handles: Vec<MdnsRegistryShutdownHandle>,
 }
 
 impl RegistryShutdownHandle {
@@ -29,6 +44,11 @@ impl RegistryShutdownHandle {
     pub fn add_remote_yaml_shutdown_handle(&mut self, handle: RemoteYamlShutdownHandle) {
         self.remote_yaml_shutdown_handles.push(handle);
     }
+
+    #[cfg(feature = "registry-mdns")]
+    pub fn add_mdns_shutdown_handle(&mut self, handle: MdnsRegistryShutdownHandle) {
+        self.mdns_shutdown_handles.push(handle);
+    }
 }
 
 impl ShutdownHandle for RegistryShutdownHandle {
@@ -36,6 +56,10 @@ impl ShutdownHandle for RegistryShutdownHandle {
         self.remote_yaml_shutdown_handles
             .iter_mut()
             .for_each(|handle| handle.signal_shutdown());
+        #[cfg(feature = "registry-mdns")]
+        self.mdns_shutdown_handles
+            .iter_mut()
+            .for_each(|handle| handle.signal_shutdown());
     }
 
     fn wait_for_shutdown(self) -> Result<(), InternalError> {
@@ -45,6 +69,12 @@ impl ShutdownHandle for RegistryShutdownHandle {
                 errors.push(err);
             }
         }
+        #[cfg(feature = "registry-mdns")]
+        for handle in self.mdns_shutdown_handles {
+            if let Err(err) = handle.wait_for_shutdown() {
+                errors.push(err);
+            }
+        }
 
         match errors.len() {
             0 => Ok(()),
@@ -60,3 +90,182 @@ impl ShutdownHandle for RegistryShutdownHandle {
         }
     }
 }
+
+/// Changes the refresh intervals of the daemon's remote registries.
+#[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+#[derive(Clone, Default)]
+pub struct RegistryRefreshHandle {
+    remote_yaml_refresh_handles: Vec<RemoteYamlRefreshHandle>,
+}
+
+#[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+impl RegistryRefreshHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_remote_yaml_refresh_handle(&mut self, handle: RemoteYamlRefreshHandle) {
+        self.remote_yaml_refresh_handles.push(handle);
+    }
+
+    /// Sets the automatic refresh interval, in seconds, of every remote registry; 0 disables
+    /// automatic refreshes.
+    pub fn set_auto_refresh_interval(&self, interval: u64) -> Result<(), RegistryError> {
+        let period = to_period(interval);
+        self.remote_yaml_refresh_handles
+            .iter()
+            .try_for_each(|handle| handle.set_automatic_refresh_period(period))
+    }
+
+    /// Sets the forced refresh interval, in seconds, of every remote registry; 0 disables forced
+    /// refreshes.
+    pub fn set_forced_refresh_interval(&self, interval: u64) -> Result<(), RegistryError> {
+        let period = to_period(interval);
+        self.remote_yaml_refresh_handles
+            .iter()
+            .try_for_each(|handle| handle.set_forced_refresh_period(period))
+    }
+}
+
+/// The `registry-refresher` subsystem: stopping it disables the automatic and forced refreshes of
+/// the daemon's remote registries, and starting it restores the intervals the daemon started with.
+#[cfg(feature = "subsystem-control")]
+pub struct RegistryRefresher {
+    refresh_handle: RegistryRefreshHandle,
+    auto_refresh: u64,
+    forced_refresh: u64,
+    running: bool,
+}
+
+#[cfg(feature = "subsystem-control")]
+impl RegistryRefresher {
+    pub fn new(
+        refresh_handle: RegistryRefreshHandle,
+        auto_refresh: u64,
+        forced_refresh: u64,
+    ) -> Self {
+        Self {
+            refresh_handle,
+            auto_refresh,
+            forced_refresh,
+            running: true,
+        }
+    }
+
+    fn set_intervals(&self, auto_refresh: u64, forced_refresh: u64) -> Result<(), InternalError> {
+        self.refresh_handle
+            .set_auto_refresh_interval(auto_refresh)
+            .and_then(|_| {
+                self.refresh_handle
+                    .set_forced_refresh_interval(forced_refresh)
+            })
+            .map_err(|err| InternalError::from_source(Box::new(err)))
+    }
+}
+
+#[cfg(feature = "subsystem-control")]
+impl Subsystem for RegistryRefresher {
+    fn is_running(&self) -> bool {
+        self.running
+    }
+
+    fn start(&mut self) -> Result<(), InternalError> {
+        if !self.running {
+            self.set_intervals(self.auto_refresh, self.forced_refresh)?;
+            self.running = true;
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) -> Result<(), InternalError> {
+        if self.running {
+            self.set_intervals(0, 0)?;
+            self.running = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "config-reload", feature = "subsystem-control"))]
+fn to_period(interval: u64) -> Option<Duration> {
+    if interval != 0 {
+        Some(Duration::from_secs(interval))
+    } else {
+        None
+    }
+}
+
+/// Builds the local node's mDNS advertisement from its first advertised endpoint whose host is an
+/// IPv4 address; returns `None` if there is no such endpoint or the node's entry is invalid.
+#[cfg(feature = "registry-mdns")]
+pub fn mdns_advertisement(
+    node_id: &str,
+    display_name: &str,
+    advertised_endpoints: &[String],
+    signers: &[Box<dyn Signer>],
+) -> Option<MdnsAdvertisement> {
+    let (address, port) = match advertised_endpoints
+        .iter()
+        .find_map(|endpoint| parse_ipv4_endpoint(endpoint))
+    {
+        Some(address_and_port) => address_and_port,
+        None => {
+            warn!(
+                "Not advertising node over mDNS, no advertised endpoint has an IPv4 address: {}",
+                advertised_endpoints.join(", ")
+            );
+            return None;
+        }
+    };
+
+    let keys = signers
+        .iter()
+        .filter_map(|signer| signer.public_key().ok())
+        .map(|public_key| public_key.as_hex())
+        .collect::<Vec<_>>();
+
+    match Node::builder(node_id)
+        .with_display_name(display_name)
+        .with_endpoints(advertised_endpoints.to_vec())
+        .with_keys(keys)
+        .build()
+    {
+        Ok(node) => Some(MdnsAdvertisement::new(node, address, port)),
+        Err(err) => {
+            warn!("Not advertising node over mDNS: {}", err);
+            None
+        }
+    }
+}
+
+/// Parses the IPv4 address and port of an endpoint such as `tcps://192.168.1.7:8044`.
+#[cfg(feature = "registry-mdns")]
+fn parse_ipv4_endpoint(endpoint: &str) -> Option<(Ipv4Addr, u16)> {
+    let address = endpoint
+        .split_once("://")
+        .map(|(_, address)| address)
+        .unwrap_or(endpoint);
+    let (host, port) = address.rsplit_once(':')?;
+    Some((host.parse().ok()?, port.parse().ok()?))
+}
+
+#[cfg(all(test, feature = "registry-mdns"))]
+mod tests {
+    use super::*;
+
+    /// Verify that only endpoints with an IPv4 address and a port are used for the mDNS
+    /// advertisement.
+    #[test]
+    fn test_parse_ipv4_endpoint() {
+        assert_eq!(
+            parse_ipv4_endpoint("tcps://192.168.1.7:8044"),
+            Some((Ipv4Addr::new(192, 168, 1, 7), 8044))
+        );
+        assert_eq!(
+            parse_ipv4_endpoint("10.0.0.1:8044"),
+            Some((Ipv4Addr::new(10, 0, 0, 1), 8044))
+        );
+        assert_eq!(parse_ipv4_endpoint("tcps://splinter-node-a:8044"), None);
+        assert_eq!(parse_ipv4_endpoint("tcps://192.168.1.7"), None);
+    }
+}
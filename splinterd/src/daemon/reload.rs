@@ -0,0 +1,95 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Live reload of the daemon's configuration.
+
+use splinter::error::InternalError;
+use splinter::network::connection_manager::ConnectionManager;
+#[cfg(feature = "rest-api-cors")]
+use splinter::rest_api::cors::CorsAllowList;
+
+use crate::config::ReloadableChange;
+
+use super::registry::RegistryRefreshHandle;
+
+/// Reloads the daemon's configuration from its sources.
+pub trait ConfigReloader: Send {
+    /// Reloads the configuration, applying any changes that are not applied by the daemon itself
+    /// (such as the logging configuration), and returns the changes the daemon must apply.
+    fn reload(&mut self) -> Result<Vec<ReloadableChange>, InternalError>;
+}
+
+/// The running components that reloaded configuration changes are applied to.
+pub struct ReloadTargets<'a> {
+    pub connection_manager: &'a ConnectionManager,
+    pub registry_refresh_handle: &'a RegistryRefreshHandle,
+    #[cfg(feature = "rest-api-cors")]
+    pub cors_allow_list: &'a CorsAllowList,
+}
+
+/// Reloads the configuration with the given reloader and applies the changes.
+///
+/// Errors are logged rather than returned, so that a bad configuration file does not stop the
+/// daemon; the previous values remain in effect.
+pub fn reload_config(reloader: &mut dyn ConfigReloader, targets: &ReloadTargets) {
+    info!("Reloading configuration");
+
+    let changes = match reloader.reload() {
+        Ok(changes) => changes,
+        Err(err) => {
+            error!("Unable to reload configuration: {}", err);
+            return;
+        }
+    };
+
+    if changes.is_empty() {
+        info!("No reloadable configuration values changed");
+    }
+
+    for change in changes {
+        match change {
+            // The reloader has already applied the logging configuration
+            ReloadableChange::Logging => info!("Logging configuration reloaded"),
+            ReloadableChange::RegistryAutoRefresh(interval) => {
+                info!("Setting registry auto refresh interval to {}s", interval);
+                if let Err(err) = targets
+                    .registry_refresh_handle
+                    .set_auto_refresh_interval(interval)
+                {
+                    error!("Unable to set registry auto refresh interval: {}", err);
+                }
+            }
+            ReloadableChange::RegistryForcedRefresh(interval) => {
+                info!("Setting registry forced refresh interval to {}s", interval);
+                if let Err(err) = targets
+                    .registry_refresh_handle
+                    .set_forced_refresh_interval(interval)
+                {
+                    error!("Unable to set registry forced refresh interval: {}", err);
+                }
+            }
+            ReloadableChange::Heartbeat(interval) => {
+                info!("Setting heartbeat interval to {}s", interval);
+                targets.connection_manager.set_heartbeat_interval(interval);
+            }
+            #[cfg(feature = "rest-api-cors")]
+            ReloadableChange::AllowList(allow_list) => {
+                info!("Setting CORS allow list to {:?}", allow_list);
+                targets
+                    .cors_allow_list
+                    .set_domains(allow_list.unwrap_or_else(|| vec!["*".into()]));
+            }
+        }
+    }
+}
@@ -0,0 +1,373 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Active-passive node pairs.
+//!
+//! Two daemons that share a node ID, keys and PostgreSQL database can run as an active-passive
+//! pair. Before binding its endpoints, a daemon takes the node's lease, a session-level
+//! PostgreSQL advisory lock. The second daemon waits as a standby until the lease is released,
+//! which happens when the active daemon stops or its database session ends; the standby then
+//! starts as usual, binding the network endpoints on its host and rejoining the circuits stored
+//! in the shared database.
+//!
+//! The active daemon checks its database session at a fixed interval. It fences itself, shutting
+//! down, as soon as a check fails or does not complete within [`FENCE_INTERVALS`] intervals of
+//! the last successful check. The lease's session is configured so that the database only ends
+//! it, releasing the lease to the standby, after [`SESSION_TIMEOUT_INTERVALS`] intervals without
+//! hearing from the active daemon; the active daemon has therefore stopped acting on the lease
+//! before the standby can take it. The advertised endpoints must follow the active daemon, for
+//! example through a floating IP address.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use diesel::{
+    connection::SimpleConnection,
+    pg::PgConnection,
+    sql_types::{BigInt, Bool},
+    Connection, RunQueryDsl,
+};
+
+use super::error::StartError;
+use super::store::ConnectionUri;
+
+/// The key of the advisory lock; a database holds the state of a single node, so the key is the
+/// same for every node
+const NODE_LEASE_KEY: i64 = 0x7370_6c69_6e74_6572;
+
+/// The number of intervals after the last successful check within which the next check must
+/// succeed, or the active daemon fences itself
+const FENCE_INTERVALS: u32 = 2;
+
+/// The number of intervals of silence after which the database ends the lease's session: one
+/// interval before the first keepalive probe, then one interval for each unanswered probe
+const SESSION_TIMEOUT_INTERVALS: u32 = 3;
+
+diesel::sql_function!(fn pg_try_advisory_lock(key: BigInt) -> Bool);
+
+/// The lease of the active daemon of a pair; the lease is released when it is dropped.
+pub struct NodeLease {
+    connection: Arc<Mutex<PgConnection>>,
+}
+
+impl NodeLease {
+    /// Takes the node's lease, waiting as a standby while another daemon holds it.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_url` - The node's database, which must be a PostgreSQL database
+    /// * `interval` - How often to try to take the lease while another daemon holds it
+    pub fn acquire(db_url: &ConnectionUri, interval: Duration) -> Result<Self, StartError> {
+        match db_url {
+            ConnectionUri::Postgres(url) => {
+                let connection = connect(url, interval)?;
+
+                let mut waiting = false;
+                loop {
+                    let acquired = diesel::select(pg_try_advisory_lock(NODE_LEASE_KEY))
+                        .get_result::<bool>(&connection)
+                        .map_err(|err| {
+                            StartError::StorageError(format!(
+                                "Unable to take the node lease: {}",
+                                err
+                            ))
+                        })?;
+                    if acquired {
+                        break;
+                    }
+                    if !waiting {
+                        info!("Another daemon is active for this node; waiting as standby");
+                        waiting = true;
+                    }
+                    thread::sleep(interval);
+                }
+
+                info!("Took the node lease; starting as the active daemon");
+                Ok(Self {
+                    connection: Arc::new(Mutex::new(connection)),
+                })
+            }
+            _ => Err(StartError::StorageError(format!(
+                "High availability requires a PostgreSQL database, not {}",
+                db_url
+            ))),
+        }
+    }
+
+    /// Starts a thread that checks the lease's database session at the given interval, and calls
+    /// `on_lost` if a check fails or does not succeed within [`FENCE_INTERVALS`] intervals of the
+    /// previous successful check. The thread stops once `running` is false or the lease is lost.
+    ///
+    /// The interval must be the one the lease was acquired with, as the lease's session timeout is
+    /// derived from it.
+    pub fn monitor<F>(
+        &self,
+        interval: Duration,
+        running: Arc<AtomicBool>,
+        on_lost: F,
+    ) -> Result<thread::JoinHandle<()>, StartError>
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let connection = self.connection.clone();
+
+        thread::Builder::new()
+            .name("NodeLeaseMonitor".into())
+            .spawn(move || {
+                let (check_tx, check_rx) = mpsc::channel();
+                let mut last_held = Instant::now();
+                while running.load(Ordering::SeqCst) {
+                    thread::sleep(interval);
+
+                    // The check runs on its own thread, so that a check stuck on an unresponsive
+                    // network cannot keep this daemon active past its deadline
+                    let connection = connection.clone();
+                    let check_tx = check_tx.clone();
+                    let check =
+                        thread::Builder::new()
+                            .name("NodeLeaseCheck".into())
+                            .spawn(move || {
+                                let _ = check_tx.send(is_session_open(&connection));
+                            });
+
+                    let deadline = last_held + interval * FENCE_INTERVALS;
+                    let held = check.is_ok()
+                        && check_rx
+                            .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                            .unwrap_or(false);
+
+                    if held {
+                        last_held = Instant::now();
+                    } else if running.load(Ordering::SeqCst) {
+                        error!(
+                            "Unable to confirm the node lease; shutting down, as the standby may \
+                             take over"
+                        );
+                        on_lost();
+                        break;
+                    }
+                }
+            })
+            .map_err(|err| {
+                StartError::InternalError(format!("Unable to start node lease monitor: {}", err))
+            })
+    }
+}
+
+/// Returns whether the lease's database session is still open.
+fn is_session_open(connection: &Mutex<PgConnection>) -> bool {
+    connection
+        .lock()
+        .map(|connection| diesel::sql_query("SELECT 1").execute(&*connection).is_ok())
+        .unwrap_or(false)
+}
+
+/// Connects to the node's database for the lease, bounding how long the lease's session may go
+/// without hearing from this daemon.
+///
+/// Statements time out after one interval. The client sends keepalives, and the server ends the
+/// session after [`SESSION_TIMEOUT_INTERVALS`] intervals of silence, which releases the lease.
+fn connect(url: &str, interval: Duration) -> Result<PgConnection, StartError> {
+    let secs = interval.as_secs().max(1);
+    let keepalives = format!(
+        "keepalives=1&keepalives_idle={secs}&keepalives_interval={secs}&keepalives_count={count}",
+        secs = secs,
+        count = SESSION_TIMEOUT_INTERVALS - 1,
+    );
+    let url = if url.contains('?') {
+        format!("{}&{}", url, keepalives)
+    } else {
+        format!("{}?{}", url, keepalives)
+    };
+
+    let connection = PgConnection::establish(&url).map_err(|err| {
+        StartError::StorageError(format!(
+            "Unable to connect to database for the node lease: {}",
+            err
+        ))
+    })?;
+
+    connection
+        .batch_execute(&format!(
+            "SET statement_timeout = {timeout}; \
+             SET tcp_keepalives_idle = {secs}; \
+             SET tcp_keepalives_interval = {secs}; \
+             SET tcp_keepalives_count = {count};",
+            timeout = secs * 1000,
+            secs = secs,
+            count = SESSION_TIMEOUT_INTERVALS - 1,
+        ))
+        .map_err(|err| {
+            StartError::StorageError(format!(
+                "Unable to configure the node lease's database session: {}",
+                err
+            ))
+        })?;
+
+    Ok(connection)
+}
+
+#[cfg(all(test, feature = "diesel-postgres-tests"))]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::panic;
+
+    use diesel::sql_types::{Integer, Text};
+
+    diesel::sql_function!(fn pg_backend_pid() -> Integer);
+    diesel::sql_function!(fn pg_terminate_backend(pid: Integer) -> Bool);
+    diesel::sql_function!(fn current_setting(name: Text) -> Text);
+
+    const INTERVAL: Duration = Duration::from_secs(1);
+
+    /// Verify that the lease is held by a single daemon, that its session is configured with a
+    /// statement timeout and keepalives, and that the standby takes over once the lease is
+    /// released.
+    ///
+    /// 1. Acquire the lease and verify its session settings
+    /// 2. Try to acquire the lease from a standby, and verify that it is still waiting after a
+    ///    few intervals
+    /// 3. Release the lease and verify that the standby takes it
+    #[test]
+    fn test_node_lease_standby() {
+        run_postgres_test("node_lease_standby", |db_url| {
+            let url = ConnectionUri::Postgres(db_url.into());
+
+            // 1. Acquire the lease and verify its session settings
+            let lease = NodeLease::acquire(&url, INTERVAL).expect("Failed to acquire lease");
+            {
+                let connection = lease
+                    .connection
+                    .lock()
+                    .expect("Lease connection lock poisoned");
+                let setting = |name: &str| {
+                    diesel::select(current_setting(name))
+                        .get_result::<String>(&*connection)
+                        .expect("Failed to read setting")
+                };
+                assert_eq!(setting("statement_timeout"), "1s");
+                assert_eq!(setting("tcp_keepalives_count"), "2");
+            }
+
+            // 2. Try to acquire the lease from a standby, and verify that it is still waiting after
+            //    a few intervals
+            let (acquired_tx, acquired_rx) = mpsc::channel();
+            let standby_url = ConnectionUri::Postgres(db_url.into());
+            let standby = thread::spawn(move || {
+                let lease = NodeLease::acquire(&standby_url, INTERVAL);
+                let _ = acquired_tx.send(());
+                lease
+            });
+            assert!(acquired_rx.recv_timeout(INTERVAL * 3).is_err());
+
+            // 3. Release the lease and verify that the standby takes it
+            drop(lease);
+            acquired_rx
+                .recv_timeout(INTERVAL * 3)
+                .expect("Standby did not take the lease");
+            standby
+                .join()
+                .expect("Standby thread panicked")
+                .expect("Standby failed to acquire lease");
+        });
+    }
+
+    /// Verify that the active daemon fences itself within the fence deadline once its session
+    /// ends.
+    ///
+    /// 1. Acquire the lease and start monitoring it
+    /// 2. Terminate the lease's session from another connection
+    /// 3. Verify that `on_lost` is called before the fence deadline
+    #[test]
+    fn test_node_lease_fences_when_session_ends() {
+        run_postgres_test("node_lease_fences", |db_url| {
+            let url = ConnectionUri::Postgres(db_url.into());
+
+            // 1. Acquire the lease and start monitoring it
+            let lease = NodeLease::acquire(&url, INTERVAL).expect("Failed to acquire lease");
+            let pid = {
+                let connection = lease
+                    .connection
+                    .lock()
+                    .expect("Lease connection lock poisoned");
+                diesel::select(pg_backend_pid())
+                    .get_result::<i32>(&*connection)
+                    .expect("Failed to get backend pid")
+            };
+
+            let running = Arc::new(AtomicBool::new(true));
+            let (lost_tx, lost_rx) = mpsc::channel();
+            let monitor = lease
+                .monitor(INTERVAL, running.clone(), move || {
+                    let _ = lost_tx.send(Instant::now());
+                })
+                .expect("Failed to start monitor");
+
+            // 2. Terminate the lease's session from another connection
+            let connection = PgConnection::establish(db_url).expect("Failed to connect");
+            let terminated_at = Instant::now();
+            assert!(diesel::select(pg_terminate_backend(pid))
+                .get_result::<bool>(&connection)
+                .expect("Failed to terminate lease session"));
+
+            // 3. Verify that `on_lost` is called before the fence deadline
+            let lost_at = lost_rx
+                .recv_timeout(INTERVAL * (FENCE_INTERVALS + 1))
+                .expect("Monitor did not fence the daemon");
+            assert!(lost_at.duration_since(terminated_at) <= INTERVAL * FENCE_INTERVALS);
+
+            running.store(false, Ordering::SeqCst);
+            monitor.join().expect("Monitor thread panicked");
+        });
+    }
+
+    /// Runs a test against its own database, which is created on the PostgreSQL server given by
+    /// `DIESEL_POSTGRES_TEST_URL` and dropped after the test, regardless of its result. If the
+    /// variable is not set, the test is skipped.
+    fn run_postgres_test<T>(db_name: &str, test: T)
+    where
+        T: FnOnce(&str) + panic::UnwindSafe,
+    {
+        let base_url = match env::var("DIESEL_POSTGRES_TEST_URL") {
+            Ok(url) => url,
+            Err(_) => {
+                println!(
+                    "Ignoring {}",
+                    thread::current().name().unwrap_or("<unknown test>")
+                );
+                return;
+            }
+        };
+
+        let connection = PgConnection::establish(&base_url).expect("Failed to connect");
+        connection
+            .batch_execute(&format!("CREATE DATABASE {};", db_name))
+            .expect("Failed to create test database");
+
+        let url = format!("{}/{}", base_url, db_name);
+        let result = panic::catch_unwind(move || test(&url));
+
+        connection
+            .batch_execute(&format!("DROP DATABASE {};", db_name))
+            .expect("Failed to drop test database");
+
+        if let Err(err) = result {
+            panic::resume_unwind(err);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Timing of the phases of the daemon's startup.
+
+#[cfg(feature = "startup-report")]
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "startup-report")]
+use splinter_rest_api_actix_web_1::status::StartupReport;
+
+/// Records how long each phase of the daemon's startup takes.
+///
+/// Phases are timed from the end of the previous phase (or from when the timer was created, for
+/// the first phase) unless `start_phase` is called, so that work between phases that should not be
+/// attributed to either can be excluded. Time not attributed to a phase is reported as "other".
+pub struct StartupTimer {
+    started_at: Instant,
+    phase_started_at: Instant,
+    phases: Vec<(&'static str, Duration)>,
+    #[cfg(feature = "startup-report")]
+    report: Arc<RwLock<StartupReport>>,
+}
+
+impl StartupTimer {
+    pub fn new() -> Self {
+        let now = Instant::now();
+        Self {
+            started_at: now,
+            phase_started_at: now,
+            phases: vec![],
+            #[cfg(feature = "startup-report")]
+            report: Arc::new(RwLock::new(StartupReport::new())),
+        }
+    }
+
+    /// Marks the start of the next phase.
+    pub fn start_phase(&mut self) {
+        self.phase_started_at = Instant::now();
+    }
+
+    /// Records the time since the start of the current phase as the duration of the named phase,
+    /// and starts the next phase.
+    pub fn finish_phase(&mut self, name: &'static str) {
+        let now = Instant::now();
+        let duration = now.duration_since(self.phase_started_at);
+        self.phase_started_at = now;
+
+        debug!("Startup phase {} took {}ms", name, duration.as_millis());
+
+        #[cfg(feature = "startup-report")]
+        match self.report.write() {
+            Ok(mut report) => report.add_phase(name, duration),
+            Err(_) => warn!(
+                "Unable to add phase {} to startup report: lock poisoned",
+                name
+            ),
+        }
+
+        self.phases.push((name, duration));
+    }
+
+    /// Returns the report that the phases are added to as they finish.
+    #[cfg(feature = "startup-report")]
+    pub fn report(&self) -> Arc<RwLock<StartupReport>> {
+        self.report.clone()
+    }
+
+    /// Logs the total startup time and the time taken by each phase.
+    pub fn finish(&mut self) {
+        let total = self.started_at.elapsed();
+        let attributed = self
+            .phases
+            .iter()
+            .fold(Duration::from_secs(0), |sum, (_, duration)| sum + *duration);
+
+        let mut breakdown = self
+            .phases
+            .iter()
+            .map(|(name, duration)| format!("{}: {}ms", name, duration.as_millis()))
+            .collect::<Vec<_>>();
+        breakdown.push(format!(
+            "other: {}ms",
+            total
+                .checked_sub(attributed)
+                .unwrap_or_default()
+                .as_millis()
+        ));
+
+        info!(
+            "Startup completed in {}ms ({})",
+            total.as_millis(),
+            breakdown.join(", ")
+        );
+
+        #[cfg(feature = "startup-report")]
+        match self.report.write() {
+            Ok(mut report) => report.set_complete(total),
+            Err(_) => warn!("Unable to complete startup report: lock poisoned"),
+        }
+    }
+}
+
+impl Default for StartupTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that each phase is timed from the end of the previous phase, unless a new phase is
+    /// explicitly started.
+    #[test]
+    fn test_startup_timer_phases() {
+        let mut timer = StartupTimer::new();
+        std::thread::sleep(Duration::from_millis(20));
+        timer.finish_phase("first");
+
+        std::thread::sleep(Duration::from_millis(20));
+        timer.start_phase();
+        timer.finish_phase("second");
+
+        timer.finish();
+
+        assert_eq!(timer.phases.len(), 2);
+        assert_eq!(timer.phases[0].0, "first");
+        assert!(timer.phases[0].1 >= Duration::from_millis(20));
+        assert_eq!(timer.phases[1].0, "second");
+        assert!(timer.phases[1].1 < Duration::from_millis(20));
+    }
+}
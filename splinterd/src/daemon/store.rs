@@ -16,13 +16,21 @@
 use std::sync::Arc;
 #[cfg(feature = "database-sqlite")]
 use std::sync::RwLock;
+#[cfg(any(feature = "health", feature = "rest-api-degraded-mode"))]
+use std::time::Duration;
 
 #[cfg(feature = "diesel")]
 use diesel::r2d2::{ConnectionManager, Pool};
+#[cfg(feature = "rest-api-degraded-mode")]
+use splinter::rest_api::actix_web_1::DatabaseHealthCheck;
+#[cfg(feature = "database-sqlite")]
+use splinter::store::memory::MemoryStoreFactory;
 #[cfg(feature = "database-postgres")]
 use splinter::store::postgres;
 #[cfg(feature = "database-sqlite")]
 use splinter::store::sqlite;
+#[cfg(feature = "database-pool-settings")]
+use splinter::store::ConnectionPoolSettings;
 use splinter::{
     error::{InternalError, InvalidArgumentError},
     store::StoreFactory,
@@ -44,6 +52,10 @@ use splinter_echo::store::PooledPgEchoStoreFactory;
 #[cfg(all(feature = "service-echo", feature = "database-sqlite"))]
 use splinter_echo::store::PooledSqliteEchoStoreFactory;
 
+#[cfg(any(feature = "health", feature = "rest-api-degraded-mode"))]
+const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Clone)]
 pub enum ConnectionPool {
     #[cfg(feature = "database-postgres")]
     Postgres {
@@ -86,13 +98,78 @@ pub fn create_connection_pool(
     }
 }
 
+/// Creates a connection pool with the given sizes and timeouts. An in-memory database is always
+/// limited to a single connection.
+#[cfg(feature = "database-pool-settings")]
+pub fn create_connection_pool_with_settings(
+    connection_uri: &ConnectionUri,
+    settings: &ConnectionPoolSettings,
+) -> Result<ConnectionPool, InternalError> {
+    match connection_uri {
+        #[cfg(feature = "database-postgres")]
+        ConnectionUri::Postgres(url) => {
+            let pool = postgres::create_postgres_connection_pool_with_settings(url, settings)?;
+            Ok(ConnectionPool::Postgres { pool })
+        }
+        #[cfg(feature = "database-sqlite")]
+        ConnectionUri::Sqlite(conn_str) => {
+            let pool = sqlite::create_sqlite_connection_pool_with_settings(conn_str, settings)?;
+            Ok(ConnectionPool::Sqlite {
+                pool: Arc::new(RwLock::new(pool)),
+            })
+        }
+        #[cfg(feature = "database-sqlite")]
+        ConnectionUri::Memory => {
+            let pool = sqlite::create_sqlite_connection_pool_with_settings(":memory:", settings)?;
+            Ok(ConnectionPool::Sqlite {
+                pool: Arc::new(RwLock::new(pool)),
+            })
+        }
+        #[cfg(not(feature = "database-sqlite"))]
+        ConnectionUri::Memory => Err(InternalError::with_message(
+            "Unsupported connection pool type: memory".into(),
+        )),
+    }
+}
+
+#[cfg(any(feature = "health", feature = "rest-api-degraded-mode"))]
+impl ConnectionPool {
+    /// Returns `true` if a connection can be taken from the pool; connections are tested when
+    /// they are taken from the pool.
+    pub fn check_connection(&self) -> bool {
+        match self {
+            #[cfg(feature = "database-postgres")]
+            ConnectionPool::Postgres { pool } => pool.get_timeout(HEALTH_CHECK_TIMEOUT).is_ok(),
+            #[cfg(feature = "database-sqlite")]
+            ConnectionPool::Sqlite { pool } => pool
+                .read()
+                .map(|pool| pool.get_timeout(HEALTH_CHECK_TIMEOUT).is_ok())
+                .unwrap_or(false),
+            #[cfg(not(any(feature = "database-postgres", feature = "database-sqlite")))]
+            ConnectionPool::Unsupported => false,
+        }
+    }
+}
+
+#[cfg(feature = "rest-api-degraded-mode")]
+impl DatabaseHealthCheck for ConnectionPool {
+    fn is_available(&self) -> bool {
+        self.check_connection()
+    }
+}
+
 /// Creates a `StoreFactory` backed by the given connection
 ///
+/// For a `memory` connection, the registry, node ID and Biome stores are kept in memory, and the
+/// remaining stores share the connection pool's in-memory database with the service stores.
+///
 /// # Arguments
 ///
 /// * `connection_uri` - The identifier of the storage connection that will be used by all stores
 ///   created by the resulting factory
+/// * `connection_pool` - The connection pool created for the `connection_uri`
 pub fn create_store_factory(
+    #[allow(unused_variables)] connection_uri: &ConnectionUri,
     connection_pool: &ConnectionPool,
 ) -> Result<Box<dyn StoreFactory>, InternalError> {
     match connection_pool {
@@ -101,6 +178,10 @@ pub fn create_store_factory(
             Ok(Box::new(postgres::PgStoreFactory::new(pool.clone())))
         }
         #[cfg(feature = "database-sqlite")]
+        ConnectionPool::Sqlite { pool } if matches!(connection_uri, ConnectionUri::Memory) => Ok(
+            Box::new(MemoryStoreFactory::new_with_write_exclusivity(pool.clone())),
+        ),
+        #[cfg(feature = "database-sqlite")]
         ConnectionPool::Sqlite { pool } => Ok(Box::new(
             sqlite::SqliteStoreFactory::new_with_write_exclusivity(pool.clone()),
         )),
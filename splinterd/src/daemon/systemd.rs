@@ -0,0 +1,54 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Notifications sent to systemd about the state of the daemon.
+//!
+//! When splinterd is not run by systemd (`NOTIFY_SOCKET` is not set), these are no-ops.
+
+use std::time::Duration;
+
+use sd_notify::NotifyState;
+
+/// Tells systemd that the daemon has finished starting up.
+pub fn notify_ready() {
+    notify(&[NotifyState::Ready], "ready");
+}
+
+/// Tells systemd that the daemon is shutting down.
+pub fn notify_stopping() {
+    notify(&[NotifyState::Stopping], "stopping");
+}
+
+/// Tells systemd that the daemon is still running, resetting the watchdog timer.
+pub fn notify_watchdog() {
+    notify(&[NotifyState::Watchdog], "watchdog");
+}
+
+/// Returns how often the watchdog must be notified, or `None` if the watchdog is not enabled.
+///
+/// This is half of the `WatchdogSec` configured for the service, as recommended by systemd.
+pub fn watchdog_interval() -> Option<Duration> {
+    let mut usec = 0;
+    if sd_notify::watchdog_enabled(false, &mut usec) {
+        Some(Duration::from_micros(usec / 2))
+    } else {
+        None
+    }
+}
+
+fn notify(state: &[NotifyState], name: &str) {
+    if let Err(err) = sd_notify::notify(false, state) {
+        warn!("Unable to send {} notification to systemd: {}", name, err);
+    }
+}
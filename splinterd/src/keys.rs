@@ -0,0 +1,141 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Loading of the daemon's signing keys.
+
+use std::path::Path;
+
+use cylinder::{secp256k1::Secp256k1Context, Context, Signer};
+use splinter::error::InternalError;
+use splinter::peer::PeerAuthorizationToken;
+#[cfg(feature = "pkcs11-signer")]
+use splinter::signing::pkcs11::{Pkcs11Signer, Pkcs11Uri};
+#[cfg(feature = "vault-key-provider")]
+use splinter::signing::vault::VaultKeyProvider;
+use splinter::signing::{DirectoryKeyProvider, KeyProvider};
+
+use crate::config::Config;
+use crate::error::UserError;
+
+/// The signers of the daemon and the token that identifies it to its peers
+pub type ChallengeAuthorizationArgs = (Vec<Box<dyn Signer>>, PeerAuthorizationToken);
+
+/// Returns the provider of the daemon's keys: the Vault secret identified by `vault_keys_url`,
+/// if it is set, or the `keys` directory of its configuration directory.
+pub fn key_provider(config: &Config) -> Result<Box<dyn KeyProvider>, UserError> {
+    #[cfg(feature = "vault-key-provider")]
+    {
+        if let Some(vault_keys_url) = config.vault_keys_url() {
+            return VaultKeyProvider::from_env(vault_keys_url)
+                .map(|provider| Box::new(provider) as Box<dyn KeyProvider>)
+                .map_err(UserError::InternalError);
+        }
+    }
+
+    Ok(Box::new(DirectoryKeyProvider::new(
+        Path::new(config.config_dir()).join("keys"),
+    )))
+}
+
+/// Loads the daemon's signing keys from the given key provider.
+///
+/// The key named by `peering_key` is the first of the returned signers, and identifies the node
+/// to its peers. If no key has that name, the only key is used instead.
+pub fn load_signer_keys(
+    key_provider: &dyn KeyProvider,
+    peering_key: &str,
+) -> Result<ChallengeAuthorizationArgs, UserError> {
+    let keys = key_provider.keys().map_err(UserError::InternalError)?;
+
+    let mut peer_token = None;
+    let mut signing_keys = vec![];
+    let mut last_known_key = String::default();
+    for (name, private_key) in keys {
+        let signing_key = Secp256k1Context::new().new_signer(private_key);
+
+        if name == peering_key {
+            peer_token = Some(PeerAuthorizationToken::from_public_key(
+                signing_key
+                    .public_key()
+                    .map_err(|err| {
+                        UserError::InternalError(InternalError::from_source(Box::new(err)))
+                    })?
+                    .as_slice(),
+            ));
+
+            // put configured peering signing key in the front of the Vec
+            signing_keys.insert(0, signing_key);
+        } else {
+            signing_keys.push(signing_key);
+        }
+        last_known_key = name;
+    }
+
+    let token = if signing_keys.is_empty() {
+        return Err(UserError::InternalError(InternalError::with_message(
+            "Must have a signing key for challenge authorization, run the \
+            `splinter keygen --system` command to generate a key for the daemon"
+                .to_string(),
+        )));
+    } else if let Some(token) = peer_token {
+        token
+    } else if signing_keys.len() == 1 {
+        let signing_key = &signing_keys[0];
+        warn!(
+            "Peering key name provided was not found, defaulting to the only key \
+                provided: {}",
+            last_known_key
+        );
+        PeerAuthorizationToken::from_public_key(
+            signing_key
+                .public_key()
+                .map_err(|err| UserError::InternalError(InternalError::from_source(Box::new(err))))?
+                .as_slice(),
+        )
+    } else {
+        return Err(UserError::InternalError(InternalError::with_message(
+            format!(
+                "Unable to decide which key to use for required authorization for \
+            provided peers. Peering key {} was not found and there are more then one \
+            configured signing key",
+                peering_key,
+            ),
+        )));
+    };
+
+    Ok((signing_keys, token))
+}
+
+/// Loads the daemon's signing key from the PKCS#11 token identified by `signing_key_uri`.
+///
+/// The key is the daemon's only signer, and identifies the node to its peers; the keys in the
+/// configuration directory are not loaded.
+#[cfg(feature = "pkcs11-signer")]
+pub fn load_pkcs11_signer_key(
+    signing_key_uri: &str,
+) -> Result<ChallengeAuthorizationArgs, UserError> {
+    let uri = signing_key_uri
+        .parse::<Pkcs11Uri>()
+        .map_err(|err| UserError::InvalidArgument(format!("Invalid signing key URI: {}", err)))?;
+    let signer = Pkcs11Signer::new(&uri).map_err(UserError::InternalError)?;
+
+    let token = PeerAuthorizationToken::from_public_key(
+        signer
+            .public_key()
+            .map_err(|err| UserError::InternalError(InternalError::from_source(Box::new(err))))?
+            .as_slice(),
+    );
+
+    Ok((vec![Box::new(signer)], token))
+}
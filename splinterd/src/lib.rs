@@ -11,8 +11,26 @@
 // WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
 // See the License for the specific language governing permissions and
 // limitations under the License.
-#[cfg(feature = "node")]
+
+//! The Splinter daemon as a library.
+//!
+//! Applications that embed a Splinter node build a [`Config`](config::Config), either from the
+//! same sources as `splinterd` or programmatically from a [`PartialConfig`](config::PartialConfig)
+//! with the [`ConfigSource::Api`](config::ConfigSource::Api) source, and run the daemon built by
+//! [`SplinterDaemonBuilder::from_config`](daemon::builder::SplinterDaemonBuilder::from_config)
+//! from their own binary.
+
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate clap;
+
+pub mod config;
+pub mod daemon;
+pub mod error;
+pub mod keys;
+pub mod logging;
 #[cfg(feature = "node")]
 pub mod node;
+pub mod node_id;
+pub mod transport;
@@ -12,19 +12,30 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "log-system-logger")]
+mod journald;
+#[cfg(feature = "log-rotation")]
+mod rotation;
+#[cfg(feature = "log-system-logger")]
+mod syslog;
+
 use std::convert::{From, Into, TryInto};
 use std::fs::OpenOptions;
+#[cfg(feature = "log-rotation")]
+use std::io;
 use std::path::Path;
 
+#[cfg(feature = "log-rotation")]
+use log4rs::append::rolling_file::policy::compound::roll::{fixed_window::FixedWindowRoller, Roll};
+#[cfg(not(feature = "log-rotation"))]
+use log4rs::append::rolling_file::policy::compound::trigger::size::SizeTrigger;
 use log4rs::{
     append::{
         console::{ConsoleAppender, Target},
         file::FileAppender,
         rolling_file::{
             policy::{
-                compound::{
-                    roll::delete::DeleteRoller, trigger::size::SizeTrigger, CompoundPolicy,
-                },
+                compound::{roll::delete::DeleteRoller, CompoundPolicy},
                 Policy,
             },
             RollingFileAppender,
@@ -44,6 +55,13 @@ use crate::config::{
 };
 use crate::error::UserError;
 
+#[cfg(feature = "log-system-logger")]
+use journald::JournaldAppender;
+#[cfg(feature = "log-rotation")]
+use rotation::RotationTrigger;
+#[cfg(feature = "log-system-logger")]
+use syslog::SyslogAppender;
+
 impl TryInto<Appender> for AppenderConfig {
     type Error = std::io::Error;
     fn try_into(self) -> Result<Appender, Self::Error> {
@@ -64,6 +82,32 @@ impl TryInto<Appender> for AppenderConfig {
             LogTarget::File(path) => {
                 Box::new(FileAppender::builder().encoder(encoder).build(path)?)
             }
+            #[cfg(feature = "log-rotation")]
+            LogTarget::RollingFile {
+                filename,
+                size,
+                max_age,
+                max_files,
+            } => {
+                let trigger = Box::new(RotationTrigger::new(Path::new(filename), *size, *max_age));
+                let roll: Box<dyn Roll> = match max_files {
+                    Some(count) if *count > 0 => Box::new(
+                        FixedWindowRoller::builder()
+                            .base(1)
+                            .build(&format!("{}.{{}}", filename), *count)
+                            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?,
+                    ),
+                    _ => Box::new(DeleteRoller::new()),
+                };
+                let policy: Box<dyn Policy> = Box::new(CompoundPolicy::new(trigger, roll));
+
+                Box::new(
+                    RollingFileAppender::builder()
+                        .encoder(encoder)
+                        .build(filename, policy)?,
+                )
+            }
+            #[cfg(not(feature = "log-rotation"))]
             LogTarget::RollingFile { filename, size } => {
                 let trigger = Box::new(SizeTrigger::new(*size));
                 let roll = Box::new(DeleteRoller::new());
@@ -75,6 +119,10 @@ impl TryInto<Appender> for AppenderConfig {
                         .build(filename, policy)?,
                 )
             }
+            #[cfg(feature = "log-system-logger")]
+            LogTarget::Syslog => Box::new(SyslogAppender::new(encoder)?),
+            #[cfg(feature = "log-system-logger")]
+            LogTarget::Journald => Box::new(JournaldAppender::new(encoder)?),
         };
         let mut builder = Appender::builder();
         if let Some(level) = self.level {
@@ -0,0 +1,113 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An appender that sends log messages to the systemd journal, using its native protocol.
+
+use std::io;
+use std::os::unix::net::UnixDatagram;
+
+use log::Record;
+use log4rs::{
+    append::Append,
+    encode::{writer::simple::SimpleWriter, Encode},
+};
+
+use super::syslog::{severity, IDENTIFIER};
+
+/// The socket journald listens on for messages in its native protocol
+const JOURNALD_SOCKET: &str = "/run/systemd/journal/socket";
+
+/// Sends each message, formatted by the appender's encoder, to journald with the syslog priority
+/// of the message's level, and the module, file and line it was logged from.
+#[derive(Debug)]
+pub struct JournaldAppender {
+    socket: UnixDatagram,
+    encoder: Box<dyn Encode>,
+}
+
+impl JournaldAppender {
+    /// Connects to journald's socket.
+    pub fn new(encoder: Box<dyn Encode>) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(JOURNALD_SOCKET)?;
+        Ok(Self { socket, encoder })
+    }
+}
+
+impl Append for JournaldAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        let mut writer = SimpleWriter(vec![]);
+        self.encoder.encode(&mut writer, record)?;
+        let mut message = writer.0;
+        while message.last() == Some(&b'\n') {
+            message.pop();
+        }
+
+        let mut entry = vec![];
+        add_field(
+            &mut entry,
+            "PRIORITY",
+            severity(record.level()).to_string().as_bytes(),
+        );
+        add_field(&mut entry, "SYSLOG_IDENTIFIER", IDENTIFIER.as_bytes());
+        add_field(&mut entry, "MESSAGE", &message);
+        if let Some(module) = record.module_path() {
+            add_field(&mut entry, "CODE_MODULE", module.as_bytes());
+        }
+        if let Some(file) = record.file() {
+            add_field(&mut entry, "CODE_FILE", file.as_bytes());
+        }
+        if let Some(line) = record.line() {
+            add_field(&mut entry, "CODE_LINE", line.to_string().as_bytes());
+        }
+
+        self.socket.send(&entry)?;
+        Ok(())
+    }
+
+    fn flush(&self) {}
+}
+
+/// Adds a field to a journal entry. A value that contains a newline is written as its length, as
+/// a little-endian 64-bit integer, followed by the value; any other value follows an `=`.
+fn add_field(entry: &mut Vec<u8>, name: &str, value: &[u8]) {
+    entry.extend_from_slice(name.as_bytes());
+    if value.contains(&b'\n') {
+        entry.push(b'\n');
+        entry.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        entry.push(b'=');
+    }
+    entry.extend_from_slice(value);
+    entry.push(b'\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that single-line values are written after an `=`, and multi-line values after their
+    /// length.
+    #[test]
+    fn test_add_field() {
+        let mut entry = vec![];
+        add_field(&mut entry, "PRIORITY", b"3");
+        add_field(&mut entry, "MESSAGE", b"first\nsecond");
+
+        let mut expected = b"PRIORITY=3\nMESSAGE\n".to_vec();
+        expected.extend_from_slice(&12u64.to_le_bytes());
+        expected.extend_from_slice(b"first\nsecond\n");
+        assert_eq!(entry, expected);
+    }
+}
@@ -0,0 +1,116 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A trigger that rolls a log file once it reaches its size or has been written to for too long.
+
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+
+use log4rs::append::rolling_file::{policy::compound::trigger::Trigger, LogFile};
+
+/// Rolls a log file once it is larger than its size, or once it has been written to for longer
+/// than its maximum age, if it has one.
+#[derive(Debug)]
+pub struct RotationTrigger {
+    size: u64,
+    max_age: Option<Duration>,
+    started: Mutex<SystemTime>,
+}
+
+impl RotationTrigger {
+    /// Creates a trigger for the log file at the given path.
+    ///
+    /// A file that already exists is aged from when it was created, or, if the file system does
+    /// not record when files are created, from now.
+    pub fn new(path: &Path, size: u64, max_age: Option<Duration>) -> Self {
+        let started = fs::metadata(path)
+            .and_then(|metadata| metadata.created())
+            .unwrap_or_else(|_| SystemTime::now());
+        Self {
+            size,
+            max_age,
+            started: Mutex::new(started),
+        }
+    }
+
+    /// Returns true if a file of the given length should be rolled at the given time; if so, the
+    /// age of the next file starts at that time.
+    fn should_roll(&self, len: u64, now: SystemTime) -> anyhow::Result<bool> {
+        let mut started = self
+            .started
+            .lock()
+            .map_err(|_| anyhow::anyhow!("log rotation lock was poisoned"))?;
+
+        let expired = match self.max_age {
+            // An empty file is not rolled, so that an idle log does not leave empty files behind
+            Some(max_age) if len > 0 => now
+                .duration_since(*started)
+                .map(|age| age >= max_age)
+                .unwrap_or(false),
+            _ => false,
+        };
+
+        if len > self.size || expired {
+            *started = now;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+}
+
+impl Trigger for RotationTrigger {
+    fn trigger(&self, file: &LogFile) -> anyhow::Result<bool> {
+        self.should_roll(file.len_estimate(), SystemTime::now())
+    }
+
+    fn is_pre_process(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that a file is rolled once it is larger than its size, or once it has been written
+    /// to for longer than its maximum age, and that the age starts again when it is rolled.
+    #[test]
+    fn test_should_roll() {
+        let dir = tempfile::tempdir().expect("Failed to create temp dir");
+        let trigger = RotationTrigger::new(
+            &dir.path().join("splinterd.log"),
+            1_000,
+            Some(Duration::from_secs(60)),
+        );
+        let started = *trigger.started.lock().expect("Lock poisoned");
+
+        assert!(!trigger.should_roll(500, started).expect("Failed to check"));
+        assert!(trigger
+            .should_roll(1_001, started)
+            .expect("Failed to check"));
+
+        let later = started + Duration::from_secs(60);
+        assert!(!trigger.should_roll(0, later).expect("Failed to check"));
+        assert!(trigger.should_roll(500, later).expect("Failed to check"));
+        assert!(!trigger
+            .should_roll(500, later + Duration::from_secs(59))
+            .expect("Failed to check"));
+        assert!(trigger
+            .should_roll(500, later + Duration::from_secs(60))
+            .expect("Failed to check"));
+    }
+}
@@ -0,0 +1,103 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An appender that sends log messages to the local syslog daemon.
+
+use std::io;
+use std::os::unix::net::UnixDatagram;
+use std::process;
+
+use log::{Level, Record};
+use log4rs::{
+    append::Append,
+    encode::{writer::simple::SimpleWriter, Encode},
+};
+
+/// The socket the syslog daemon listens on
+const SYSLOG_SOCKET: &str = "/dev/log";
+
+/// The syslog facility for system daemons
+const LOG_DAEMON: u8 = 3;
+
+/// The name messages are logged with
+pub const IDENTIFIER: &str = "splinterd";
+
+/// Returns the syslog severity of a log level; trace messages are logged as debug messages.
+pub fn severity(level: Level) -> u8 {
+    match level {
+        Level::Error => 3,
+        Level::Warn => 4,
+        Level::Info => 6,
+        Level::Debug | Level::Trace => 7,
+    }
+}
+
+/// Sends each message, formatted by the appender's encoder, to the local syslog daemon with the
+/// daemon facility and the severity of the message's level.
+#[derive(Debug)]
+pub struct SyslogAppender {
+    socket: UnixDatagram,
+    encoder: Box<dyn Encode>,
+}
+
+impl SyslogAppender {
+    /// Connects to the syslog daemon's socket.
+    pub fn new(encoder: Box<dyn Encode>) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect(SYSLOG_SOCKET)?;
+        Ok(Self { socket, encoder })
+    }
+}
+
+impl Append for SyslogAppender {
+    fn append(&self, record: &Record) -> anyhow::Result<()> {
+        let mut writer = SimpleWriter(format_header(record.level(), process::id()).into_bytes());
+        self.encoder.encode(&mut writer, record)?;
+        let mut message = writer.0;
+        // The daemon adds its own line ending
+        while message.last() == Some(&b'\n') {
+            message.pop();
+        }
+
+        self.socket.send(&message)?;
+        Ok(())
+    }
+
+    fn flush(&self) {}
+}
+
+/// Returns the header of a message in the local syslog format, `<PRI>splinterd[PID]: `.
+fn format_header(level: Level, pid: u32) -> String {
+    format!(
+        "<{}>{}[{}]: ",
+        LOG_DAEMON * 8 + severity(level),
+        IDENTIFIER,
+        pid
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Verify that the priority in the header combines the daemon facility with the level's
+    /// severity.
+    #[test]
+    fn test_format_header() {
+        assert_eq!(format_header(Level::Error, 42), "<27>splinterd[42]: ");
+        assert_eq!(format_header(Level::Warn, 42), "<28>splinterd[42]: ");
+        assert_eq!(format_header(Level::Info, 42), "<30>splinterd[42]: ");
+        assert_eq!(format_header(Level::Trace, 42), "<31>splinterd[42]: ");
+    }
+}
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::fs;
+use std::path::Path;
+
 use rand::Rng;
 use splinter::error::InternalError;
-use splinter::node_id::store::NodeIdStore;
+use splinter::node_id::store::{file::FileNodeIdStore, NodeIdStore};
 
 use crate::error::UserError;
 
@@ -25,6 +28,80 @@ pub fn get_node_id(
     get_from_store(passed_in_node_id, node_id_store)
 }
 
+/// Imports the node ID from the soft-deprecated `node_id` file in the state directory, if the file
+/// exists.
+///
+/// The value is saved to the node ID store, unless the store already has a node ID, and the file is
+/// then archived as `node_id.old` so that the import is not attempted again. The import fails if
+/// the store already has a different node ID, in which case the file is left in place.
+pub fn import_node_id_file(
+    state_dir: &str,
+    node_id_store: &dyn NodeIdStore,
+) -> Result<(), UserError> {
+    let node_id_path = Path::new(state_dir).join("node_id");
+    if !node_id_path.exists() {
+        return Ok(());
+    }
+    let archive_path = Path::new(state_dir).join("node_id.old");
+
+    let file_node_id = FileNodeIdStore::new(node_id_path.clone())
+        .get_node_id()
+        .map_err(|err| UserError::from(InternalError::from_source(Box::new(err))))?;
+    let db_node_id = node_id_store
+        .get_node_id()
+        .map_err(|err| UserError::from(InternalError::from_source(Box::new(err))))?;
+
+    match (file_node_id, db_node_id) {
+        (Some(file_node_id), None) => {
+            node_id_store
+                .set_node_id(file_node_id.clone())
+                .map_err(|err| UserError::from(InternalError::from_source(Box::new(err))))?;
+            info!(
+                "Imported node_id {} from {} to database",
+                file_node_id,
+                node_id_path.display()
+            );
+        }
+        (Some(file_node_id), Some(db_node_id)) if file_node_id == db_node_id => {
+            info!(
+                "node_id {} from {} has already been imported to database",
+                file_node_id,
+                node_id_path.display()
+            );
+        }
+        (Some(file_node_id), Some(db_node_id)) => {
+            return Err(UserError::InvalidArgument(format!(
+                "node_id from {} {} does not match node_id from database {}",
+                node_id_path.display(),
+                file_node_id,
+                db_node_id
+            )));
+        }
+        (None, _) => warn!(
+            "Skipping node_id import: {} is empty",
+            node_id_path.display()
+        ),
+    }
+
+    fs::rename(&node_id_path, &archive_path).map_err(|err| {
+        UserError::io_err_with_source(
+            &format!(
+                "Unable to rename {} to {}",
+                node_id_path.display(),
+                archive_path.display()
+            ),
+            Box::new(err),
+        )
+    })?;
+    info!(
+        "Renamed {} to {}",
+        node_id_path.display(),
+        archive_path.display()
+    );
+
+    Ok(())
+}
+
 fn get_random_node_id() -> String {
     format!("n{}", rand::thread_rng().gen::<u16>())
 }
@@ -63,3 +140,86 @@ fn get_from_store(
         (Err(err), _) => Err(UserError::from(InternalError::from_source(Box::new(err)))),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::cell::RefCell;
+
+    use splinter::node_id::store::error::NodeIdStoreError;
+    use tempfile::Builder;
+
+    struct MockNodeIdStore {
+        value: RefCell<Option<String>>,
+    }
+
+    impl MockNodeIdStore {
+        fn new(value: Option<&str>) -> Self {
+            Self {
+                value: RefCell::new(value.map(String::from)),
+            }
+        }
+    }
+
+    impl NodeIdStore for MockNodeIdStore {
+        fn get_node_id(&self) -> Result<Option<String>, NodeIdStoreError> {
+            Ok(self.value.borrow().clone())
+        }
+
+        fn set_node_id(&self, node_id: String) -> Result<(), NodeIdStoreError> {
+            self.value.replace(Some(node_id));
+            Ok(())
+        }
+    }
+
+    /// Verify that the node_id file is imported into an empty store and archived.
+    #[test]
+    fn test_import_node_id_file() {
+        let temp_dir = Builder::new()
+            .prefix("test_import_node_id_file")
+            .tempdir()
+            .expect("Unable to create temp dir");
+        let state_dir = temp_dir.path().to_str().expect("Invalid temp dir path");
+        fs::write(temp_dir.path().join("node_id"), "file_node\n")
+            .expect("Unable to write node_id file");
+
+        let store = MockNodeIdStore::new(None);
+        import_node_id_file(state_dir, &store).expect("Unable to import node_id file");
+
+        assert_eq!(store.value.borrow().as_deref(), Some("file_node"));
+        assert!(!temp_dir.path().join("node_id").exists());
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("node_id.old"))
+                .expect("Unable to read archived node_id file"),
+            "file_node\n"
+        );
+
+        // A second start has nothing left to import
+        import_node_id_file(state_dir, &store).expect("Unable to import node_id file");
+    }
+
+    /// Verify that a node_id file matching the stored node ID is archived, and that one which
+    /// conflicts with it is left in place and results in an error.
+    #[test]
+    fn test_import_node_id_file_already_set() {
+        let temp_dir = Builder::new()
+            .prefix("test_import_node_id_file_already_set")
+            .tempdir()
+            .expect("Unable to create temp dir");
+        let state_dir = temp_dir.path().to_str().expect("Invalid temp dir path");
+
+        fs::write(temp_dir.path().join("node_id"), "db_node").expect("Unable to write file");
+        let store = MockNodeIdStore::new(Some("db_node"));
+        import_node_id_file(state_dir, &store).expect("Unable to import node_id file");
+        assert!(!temp_dir.path().join("node_id").exists());
+
+        fs::write(temp_dir.path().join("node_id"), "file_node").expect("Unable to write file");
+        assert!(matches!(
+            import_node_id_file(state_dir, &store),
+            Err(UserError::InvalidArgument(_))
+        ));
+        assert!(temp_dir.path().join("node_id").exists());
+        assert_eq!(store.value.borrow().as_deref(), Some("db_node"));
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright 2018-2022 Cargill Incorporated
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Reloads the splinterd configuration from the same sources it was loaded from at startup.
+
+use std::path::Path;
+#[cfg(feature = "config-report")]
+use std::sync::{Arc, RwLock};
+
+use clap::ArgMatches;
+use log4rs::Handle;
+use splinter::error::InternalError;
+#[cfg(feature = "config-report")]
+use splinter_rest_api_actix_web_1::status::ConfigReport;
+use splinterd::config::{Config, ReloadableChange};
+use splinterd::daemon::reload::ConfigReloader;
+use splinterd::logging::configure_logging;
+
+use crate::create_config;
+
+/// Rebuilds the configuration from the config file, the command line arguments, the environment
+/// and the defaults, and applies any logging changes itself.
+pub struct ConfigFileReloader {
+    config_file: String,
+    matches: ArgMatches<'static>,
+    log_handle: Handle,
+    config: Config,
+    #[cfg(feature = "config-report")]
+    config_report: Option<Arc<RwLock<ConfigReport>>>,
+}
+
+impl ConfigFileReloader {
+    /// Creates a reloader for the given config file, where `config` is the configuration
+    /// currently in effect.
+    pub fn new(
+        config_file: String,
+        matches: ArgMatches<'static>,
+        log_handle: Handle,
+        config: Config,
+    ) -> Self {
+        Self {
+            config_file,
+            matches,
+            log_handle,
+            config,
+            #[cfg(feature = "config-report")]
+            config_report: None,
+        }
+    }
+
+    /// Updates the given configuration report after each reload. Values that require a restart
+    /// to change keep the value in effect.
+    #[cfg(feature = "config-report")]
+    pub fn with_config_report(mut self, config_report: Arc<RwLock<ConfigReport>>) -> Self {
+        self.config_report = Some(config_report);
+        self
+    }
+}
+
+impl ConfigReloader for ConfigFileReloader {
+    fn reload(&mut self) -> Result<Vec<ReloadableChange>, InternalError> {
+        let config_file_path = if Path::new(&self.config_file).is_file() {
+            Some(&*self.config_file)
+        } else {
+            None
+        };
+
+        let config = create_config(config_file_path, self.matches.clone())
+            .map_err(|err| InternalError::from_source(Box::new(err)))?;
+
+        let diff = self.config.diff(&config);
+        for name in diff.requires_restart() {
+            warn!(
+                "Configuration value {} changed; restart splinterd for the change to take effect",
+                name
+            );
+        }
+
+        if diff.reloadable().contains(&ReloadableChange::Logging) {
+            configure_logging(&config, &self.log_handle)
+                .map_err(|err| InternalError::from_source(Box::new(err)))?;
+        }
+
+        config.log_as_debug();
+        #[cfg(feature = "config-report")]
+        {
+            if let Some(config_report) = &self.config_report {
+                let mut config_report = config_report.write().map_err(|_| {
+                    InternalError::with_message("Config report lock poisoned".into())
+                })?;
+                *config_report =
+                    merge_report(&config_report, &config.report(), diff.requires_restart());
+            }
+        }
+        self.config = config;
+
+        Ok(diff.into_reloadable())
+    }
+}
+
+/// Returns the new report, except that the values named in `requires_restart` are taken from
+/// the current report, or omitted if it does not have them.
+#[cfg(feature = "config-report")]
+fn merge_report(
+    current: &ConfigReport,
+    new: &ConfigReport,
+    requires_restart: &[&str],
+) -> ConfigReport {
+    let mut report = ConfigReport::new();
+    for value in new.values() {
+        let value = if requires_restart.contains(&value.name()) {
+            match current
+                .values()
+                .iter()
+                .find(|old| old.name() == value.name())
+            {
+                Some(old) => old,
+                None => continue,
+            }
+        } else {
+            value
+        };
+        report = report.with_value(value.name(), value.value(), value.source(), value.file());
+    }
+    report
+}
@@ -16,8 +16,17 @@ use std::fs;
 use std::path::Path;
 
 use splinter::transport::multi::MultiTransport;
+#[cfg(all(feature = "tls-rustls", not(feature = "tls-openssl")))]
+use splinter::transport::socket::RustlsTransport;
 use splinter::transport::socket::TcpTransport;
+#[cfg(not(any(feature = "tls-openssl", feature = "tls-rustls")))]
+use splinter::transport::socket::TlsInitError;
+#[cfg(feature = "tls-openssl")]
 use splinter::transport::socket::TlsTransport;
+#[cfg(all(unix, feature = "unix-transport"))]
+use splinter::transport::socket::UnixTransport;
+#[cfg(feature = "tls-peer-ca")]
+use splinter::transport::tls::PeerTrustAnchor;
 use splinter::transport::tls::{TlsConfig, TlsConfigBuilder};
 #[cfg(feature = "ws-transport")]
 use splinter::transport::ws::WsTransport;
@@ -35,6 +44,10 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
         Box::new(TcpTransport::default()),
     ];
 
+    // add unix domain socket transport, used by services on the same host
+    #[cfg(all(unix, feature = "unix-transport"))]
+    transports.push(Box::new(UnixTransport::default()));
+
     // add web socket transport
 
     // add tls transport
@@ -43,6 +56,14 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
         validate_tls_config(&tls_config)?;
         print_tls_config(&tls_config)?;
 
+        #[cfg(not(any(feature = "tls-openssl", feature = "tls-rustls")))]
+        return Err(GetTransportError::TlsTransport(
+            TlsInitError::ProtocolError(
+                "splinterd was built without a TLS implementation; use --no-tls".into(),
+            ),
+        ));
+
+        #[cfg(all(feature = "tls-openssl", not(feature = "tls-peer-ca")))]
         transports.push(Box::new(TlsTransport::new(
             tls_config.ca_certs_file().to_owned(),
             tls_config.client_private_key_file().to_string(),
@@ -50,6 +71,24 @@ pub fn build_transport(config: &Config) -> Result<MultiTransport, GetTransportEr
             tls_config.server_private_key_file().to_string(),
             tls_config.server_cert_file().to_string(),
         )?));
+        #[cfg(feature = "tls-peer-ca")]
+        transports.push(Box::new(TlsTransport::new_with_peer_trust_anchors(
+            tls_config.ca_certs_file().to_owned(),
+            tls_config.client_private_key_file().to_string(),
+            tls_config.client_cert_file().to_string(),
+            tls_config.server_private_key_file().to_string(),
+            tls_config.server_cert_file().to_string(),
+            tls_config.peer_trust_anchors().to_vec(),
+        )?));
+        // Only used when OpenSSL is not available, such as when cross-compiling
+        #[cfg(all(feature = "tls-rustls", not(feature = "tls-openssl")))]
+        transports.push(Box::new(RustlsTransport::new(
+            tls_config.ca_certs_file().to_owned(),
+            tls_config.client_private_key_file().to_string(),
+            tls_config.client_cert_file().to_string(),
+            tls_config.server_private_key_file().to_string(),
+            tls_config.server_cert_file().to_string(),
+        )?));
 
         #[cfg(feature = "ws-transport")]
         transports.push(Box::new(WsTransport::new(Some(&tls_config)).map_err(
@@ -76,6 +115,14 @@ fn build_tls_config(config: &Config) -> Result<TlsConfig, GetTransportError> {
         builder = builder.with_ca_certs_file(config.tls_ca_file().to_string());
     }
 
+    #[cfg(feature = "tls-peer-ca")]
+    for (pattern, file) in config.tls_peer_ca_files().unwrap_or(&[]) {
+        let peer_trust_anchor = PeerTrustAnchor::new(pattern, file.to_string()).map_err(|e| {
+            GetTransportError::Cert(format!("Invalid TLS peer CA file pattern: {}", e))
+        })?;
+        builder = builder.with_peer_trust_anchor(peer_trust_anchor);
+    }
+
     builder
         .build()
         .map_err(|e| GetTransportError::Cert(format!("TLS config error: {}", e)))
@@ -123,6 +170,17 @@ fn validate_tls_config(tls_config: &TlsConfig) -> Result<(), GetTransportError>
         }
     }
 
+    #[cfg(feature = "tls-peer-ca")]
+    for peer_trust_anchor in tls_config.peer_trust_anchors() {
+        if !Path::new(peer_trust_anchor.ca_certs_file()).is_file() {
+            return Err(GetTransportError::Cert(format!(
+                "Must provide a valid file containing ca certs for peers matching {}: {}",
+                peer_trust_anchor.endpoint_pattern(),
+                peer_trust_anchor.ca_certs_file()
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -146,6 +204,14 @@ fn print_tls_config(tls_config: &TlsConfig) -> Result<(), GetTransportError> {
     if let Some(ca_path) = tls_config.ca_certs_file() {
         debug!("Using ca certs file: {:?}", ca_path);
     }
+    #[cfg(feature = "tls-peer-ca")]
+    for peer_trust_anchor in tls_config.peer_trust_anchors() {
+        debug!(
+            "Using ca certs file for peers matching {}: {:?}",
+            peer_trust_anchor.endpoint_pattern(),
+            fs::canonicalize(peer_trust_anchor.ca_certs_file())?
+        );
+    }
 
     Ok(())
 }